@@ -0,0 +1,4 @@
+module bench
+
+// slices.Sort and slices.Clone need Go 1.21 or newer
+go 1.21
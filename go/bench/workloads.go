@@ -0,0 +1,189 @@
+// Benchmark workloads, Go version.
+// Demonstrates the same workloads as rust/bench_harness/workloads/rust_workloads.rs
+//
+// The harness in rust/bench_harness runs this program as
+//     workloads <workload> <size> <warmup> <runs>
+// and reads back one "checksum <value>" line followed by one
+// "run <nanoseconds>" line per measured run. The checksums must match the
+// Rust and Java versions.
+//
+// Timing happens in here, around the work only, so process startup
+// doesn't count.
+
+package main
+
+import (
+    "bytes"
+    "encoding/json"
+    "fmt"
+    "io"
+    "net"
+    "net/http"
+    "os"
+    "slices"
+    "strconv"
+    "strings"
+    "time"
+)
+
+// lcg is the shared pseudo-random generator: a 64-bit LCG, identical in
+// all three languages. Go's uint64 arithmetic wraps on overflow.
+type lcg struct{ state uint64 }
+
+func newLcg() *lcg { return &lcg{state: 42} }
+
+func (g *lcg) next() uint64 {
+    g.state = g.state*6364136223846793005 + 1442695040888963407
+    return g.state >> 33
+}
+
+// A workload does the work once and returns a checksum of the result.
+type workload func() uint64
+
+// ===== fib: recursive Fibonacci, pure function calls =====
+func fib(n uint64) uint64 {
+    if n < 2 {
+        return n
+    }
+    return fib(n-1) + fib(n-2)
+}
+
+// ===== sort: sorting random integers =====
+func sortWorkload(size int) workload {
+    g := newLcg()
+    input := make([]uint64, size)
+    for i := range input {
+        input[i] = g.next()
+    }
+    return func() uint64 {
+        values := slices.Clone(input)
+        slices.Sort(values)
+        return values[0] + values[size/2] + values[size-1]
+    }
+}
+
+// ===== json: parsing a document into generic values =====
+func jsonWorkload(size int) workload {
+    g := newLcg()
+    var text strings.Builder
+    text.WriteString("[")
+    for i := 0; i < size; i++ {
+        score := g.next() % 1000
+        tagCount := g.next() % 4
+        tags := make([]string, 0, tagCount)
+        for k := uint64(0); k < tagCount; k++ {
+            tags = append(tags, fmt.Sprintf("\"t%d\"", k))
+        }
+        if i > 0 {
+            text.WriteString(",")
+        }
+        fmt.Fprintf(&text, "{\"id\":%d,\"name\":\"item-%d\",\"score\":%d,\"tags\":[%s],\"active\":%t}",
+            i, i, score, strings.Join(tags, ","), i%2 == 0)
+    }
+    text.WriteString("]")
+    document := []byte(text.String())
+
+    return func() uint64 {
+        // Decoding into `any` gives generic values, like serde_json::Value
+        var records []any
+        if err := json.Unmarshal(document, &records); err != nil {
+            panic(err)
+        }
+        var checksum uint64
+        for _, item := range records {
+            record := item.(map[string]any)
+            checksum += uint64(record["id"].(float64))
+            checksum += uint64(record["score"].(float64))
+            checksum += uint64(len(record["tags"].([]any)))
+            if record["active"].(bool) {
+                checksum++
+            }
+        }
+        return checksum
+    }
+}
+
+// ===== http: request/response round trips over localhost =====
+func httpWorkload(requests int) workload {
+    listener, err := net.Listen("tcp", "127.0.0.1:0")
+    if err != nil {
+        panic(err)
+    }
+    mux := http.NewServeMux()
+    mux.HandleFunc("/echo", func(w http.ResponseWriter, r *http.Request) {
+        body, _ := io.ReadAll(r.Body)
+        w.Header().Set("Content-Type", "text/plain")
+        w.Write(body)
+    })
+    go http.Serve(listener, mux)
+
+    client := &http.Client{}
+    url := "http://" + listener.Addr().String() + "/echo"
+    return func() uint64 {
+        var checksum uint64
+        for i := 0; i < requests; i++ {
+            response, err := client.Post(url, "text/plain", bytes.NewBufferString("ping-"+strconv.Itoa(i)))
+            if err != nil {
+                panic(err)
+            }
+            body, _ := io.ReadAll(response.Body)
+            // Reading to the end and closing lets the connection be reused
+            response.Body.Close()
+            checksum += uint64(len(body))
+        }
+        return checksum
+    }
+}
+
+func main() {
+    if len(os.Args) != 5 {
+        fmt.Fprintln(os.Stderr, "usage: workloads <fib|sort|json|http> <size> <warmup> <runs>")
+        os.Exit(1)
+    }
+    size, err1 := strconv.Atoi(os.Args[2])
+    warmup, err2 := strconv.Atoi(os.Args[3])
+    runs, err3 := strconv.Atoi(os.Args[4])
+    if err1 != nil || err2 != nil || err3 != nil {
+        fmt.Fprintln(os.Stderr, "size, warmup and runs must be numbers")
+        os.Exit(1)
+    }
+
+    var work workload
+    switch os.Args[1] {
+    case "fib":
+        work = func() uint64 { return fib(uint64(size)) }
+    case "sort":
+        work = sortWorkload(size)
+    case "json":
+        work = jsonWorkload(size)
+    case "http":
+        work = httpWorkload(size)
+    default:
+        fmt.Fprintf(os.Stderr, "unknown workload `%s`\n", os.Args[1])
+        os.Exit(1)
+    }
+
+    var checksum uint64
+    for i := 0; i < warmup; i++ {
+        checksum = work()
+    }
+    times := make([]int64, runs)
+    for i := range times {
+        start := time.Now()
+        checksum = work()
+        times[i] = time.Since(start).Nanoseconds()
+    }
+    fmt.Printf("checksum %d\n", checksum)
+    for _, nanos := range times {
+        fmt.Printf("run %d\n", nanos)
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Each workload is prepared once, then run repeatedly as a closure
+ * - time.Now() includes a monotonic reading, so time.Since is safe to use
+ * - Closing response bodies lets net/http reuse the connection
+ *
+ * To run: go run . fib 30 3 10
+ */
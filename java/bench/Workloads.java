@@ -0,0 +1,252 @@
+// Benchmark workloads, Java version.
+// Demonstrates the same workloads as rust/bench_harness/workloads/rust_workloads.rs
+//
+// The harness in rust/bench_harness runs this program as
+//     java Workloads <workload> <size> <warmup> <runs>
+// and reads back one "checksum <value>" line followed by one
+// "run <nanoseconds>" line per measured run. The checksums must match the
+// Rust and Go versions.
+//
+// Timing happens in here, around the work only, so JVM startup doesn't
+// count, and the warmup runs give the JIT compiler time to kick in.
+
+import com.sun.net.httpserver.HttpServer;
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.OutputStream;
+import java.net.InetSocketAddress;
+import java.net.URI;
+import java.net.http.HttpClient;
+import java.net.http.HttpRequest;
+import java.net.http.HttpResponse;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.concurrent.Executors;
+
+public class Workloads {
+    // The shared pseudo-random generator: a 64-bit LCG, identical in all
+    // three languages. Java's long arithmetic wraps just like Rust's
+    // wrapping_mul, and >>> is the unsigned shift.
+    static final class Lcg {
+        private long state = 42;
+
+        long next() {
+            state = state * 6364136223846793005L + 1442695040888963407L;
+            return state >>> 33;
+        }
+    }
+
+    interface Workload {
+        long run() throws Exception;
+    }
+
+    // ===== fib: recursive Fibonacci, pure function calls =====
+    static long fib(long n) {
+        return n < 2 ? n : fib(n - 1) + fib(n - 2);
+    }
+
+    // ===== sort: sorting random integers =====
+    static Workload sort(int size) {
+        Lcg lcg = new Lcg();
+        long[] input = new long[size];
+        for (int i = 0; i < size; i++) {
+            input[i] = lcg.next();
+        }
+        return () -> {
+            long[] values = input.clone();
+            Arrays.sort(values);
+            return values[0] + values[size / 2] + values[size - 1];
+        };
+    }
+
+    // ===== json: parsing a document into generic values =====
+    // The JDK has no JSON parser, so JsonParser below is a small one, just
+    // big enough for this document (objects, arrays, strings without
+    // escapes, integers, booleans).
+    static Workload json(int size) {
+        Lcg lcg = new Lcg();
+        StringBuilder text = new StringBuilder("[");
+        for (int i = 0; i < size; i++) {
+            long score = lcg.next() % 1000;
+            long tagCount = lcg.next() % 4;
+            List<String> tags = new ArrayList<>();
+            for (int k = 0; k < tagCount; k++) {
+                tags.add("\"t" + k + "\"");
+            }
+            if (i > 0) {
+                text.append(',');
+            }
+            text.append("{\"id\":").append(i)
+                .append(",\"name\":\"item-").append(i)
+                .append("\",\"score\":").append(score)
+                .append(",\"tags\":[").append(String.join(",", tags))
+                .append("],\"active\":").append(i % 2 == 0)
+                .append('}');
+        }
+        text.append(']');
+        String document = text.toString();
+
+        return () -> {
+            long checksum = 0;
+            for (Object item : (List<?>) new JsonParser(document).parse()) {
+                Map<?, ?> record = (Map<?, ?>) item;
+                checksum += (Long) record.get("id");
+                checksum += (Long) record.get("score");
+                checksum += ((List<?>) record.get("tags")).size();
+                checksum += (Boolean) record.get("active") ? 1 : 0;
+            }
+            return checksum;
+        };
+    }
+
+    static final class JsonParser {
+        private final String text;
+        private int pos = 0;
+
+        JsonParser(String text) {
+            this.text = text;
+        }
+
+        Object parse() {
+            char c = text.charAt(pos);
+            if (c == '{') {
+                Map<String, Object> object = new HashMap<>();
+                pos++;
+                while (text.charAt(pos) != '}') {
+                    String key = (String) parse();
+                    pos++; // ':'
+                    object.put(key, parse());
+                    if (text.charAt(pos) == ',') {
+                        pos++;
+                    }
+                }
+                pos++;
+                return object;
+            } else if (c == '[') {
+                List<Object> array = new ArrayList<>();
+                pos++;
+                while (text.charAt(pos) != ']') {
+                    array.add(parse());
+                    if (text.charAt(pos) == ',') {
+                        pos++;
+                    }
+                }
+                pos++;
+                return array;
+            } else if (c == '"') {
+                int end = text.indexOf('"', pos + 1);
+                String value = text.substring(pos + 1, end);
+                pos = end + 1;
+                return value;
+            } else if (text.startsWith("true", pos)) {
+                pos += 4;
+                return Boolean.TRUE;
+            } else if (text.startsWith("false", pos)) {
+                pos += 5;
+                return Boolean.FALSE;
+            } else {
+                int start = pos;
+                while (pos < text.length() && (Character.isDigit(text.charAt(pos)) || text.charAt(pos) == '-')) {
+                    pos++;
+                }
+                return Long.parseLong(text.substring(start, pos));
+            }
+        }
+    }
+
+    // ===== http: request/response round trips over localhost =====
+    static HttpServer server;
+
+    static Workload http(int requests) throws IOException {
+        // Without this the built-in server waits for more data before
+        // sending small responses (Nagle's algorithm), adding ~40ms each
+        System.setProperty("sun.net.httpserver.nodelay", "true");
+        server = HttpServer.create(new InetSocketAddress("127.0.0.1", 0), 0);
+        server.createContext("/echo", exchange -> {
+            byte[] body;
+            try (InputStream in = exchange.getRequestBody()) {
+                body = in.readAllBytes();
+            }
+            exchange.getResponseHeaders().set("Content-Type", "text/plain");
+            exchange.sendResponseHeaders(200, body.length);
+            try (OutputStream out = exchange.getResponseBody()) {
+                out.write(body);
+            }
+        });
+        server.setExecutor(Executors.newCachedThreadPool(runnable -> {
+            Thread thread = new Thread(runnable);
+            thread.setDaemon(true);
+            return thread;
+        }));
+        server.start();
+
+        HttpClient client = HttpClient.newBuilder().version(HttpClient.Version.HTTP_1_1).build();
+        URI uri = URI.create("http://127.0.0.1:" + server.getAddress().getPort() + "/echo");
+        return () -> {
+            long checksum = 0;
+            for (int i = 0; i < requests; i++) {
+                HttpRequest request = HttpRequest.newBuilder(uri)
+                    .POST(HttpRequest.BodyPublishers.ofString("ping-" + i))
+                    .build();
+                HttpResponse<byte[]> response = client.send(request, HttpResponse.BodyHandlers.ofByteArray());
+                checksum += response.body().length;
+            }
+            return checksum;
+        };
+    }
+
+    public static void main(String[] args) throws Exception {
+        if (args.length != 4) {
+            System.err.println("usage: java Workloads <fib|sort|json|http> <size> <warmup> <runs>");
+            System.exit(1);
+        }
+        int size = Integer.parseInt(args[1]);
+        int warmup = Integer.parseInt(args[2]);
+        int runs = Integer.parseInt(args[3]);
+
+        Workload workload;
+        switch (args[0]) {
+            case "fib" -> workload = () -> fib(size);
+            case "sort" -> workload = sort(size);
+            case "json" -> workload = json(size);
+            case "http" -> workload = http(size);
+            default -> {
+                System.err.println("unknown workload `" + args[0] + "`");
+                System.exit(1);
+                return;
+            }
+        }
+
+        long checksum = 0;
+        for (int i = 0; i < warmup; i++) {
+            checksum = workload.run();
+        }
+        long[] times = new long[runs];
+        for (int i = 0; i < runs; i++) {
+            long start = System.nanoTime();
+            checksum = workload.run();
+            times[i] = System.nanoTime() - start;
+        }
+        System.out.println("checksum " + checksum);
+        for (long nanos : times) {
+            System.out.println("run " + nanos);
+        }
+        // The HTTP server's own thread would keep the JVM running
+        if (server != null) {
+            server.stop(0);
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Each workload is prepared once, then run repeatedly
+ * - Warmup runs let the JIT compile the hot code before timing starts
+ * - System.nanoTime() is the monotonic clock for measuring durations
+ *
+ * To compile: javac -d /tmp/bench Workloads.java
+ * To run: java -cp /tmp/bench Workloads fib 30 3 10
+ */
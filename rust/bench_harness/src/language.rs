@@ -0,0 +1,142 @@
+//! Building each language's workload program.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Language {
+    Rust,
+    Go,
+    Java,
+}
+
+/// A built workload program: run `command` with `args`, then the workload
+/// arguments.
+#[derive(Debug)]
+pub struct Program {
+    pub command: PathBuf,
+    pub args: Vec<OsString>,
+}
+
+/// The result of trying to build a language's program.
+#[derive(Debug)]
+pub enum Prepared {
+    Ready(Program),
+    /// The toolchain isn't installed; the reason is shown in the report.
+    Skipped(String),
+}
+
+impl Language {
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::Rust => "Rust",
+            Language::Go => "Go",
+            Language::Java => "Java",
+        }
+    }
+
+    /// Builds the workload program under `build_dir`. A missing toolchain
+    /// means [`Prepared::Skipped`]; a program that fails to build is an error.
+    pub fn prepare(self, root: &Path, build_dir: &Path) -> Result<Prepared> {
+        match self {
+            Language::Rust => prepare_rust(root).map(Prepared::Ready),
+            Language::Go => {
+                if !is_installed("go", "version") {
+                    return Ok(Prepared::Skipped("`go` not found".to_string()));
+                }
+                let output = build_dir.join("go_workloads");
+                run_build(
+                    Command::new("go")
+                        .args(["build", "-o"])
+                        .arg(&output)
+                        .arg(".")
+                        .current_dir(root.join("go").join("bench")),
+                    "go build",
+                )?;
+                Ok(Prepared::Ready(Program {
+                    command: output,
+                    args: Vec::new(),
+                }))
+            }
+            Language::Java => {
+                if !is_installed("javac", "-version") || !is_installed("java", "-version") {
+                    return Ok(Prepared::Skipped("`javac`/`java` not found".to_string()));
+                }
+                let classes = build_dir.join("java");
+                run_build(
+                    Command::new("javac")
+                        .arg("-d")
+                        .arg(&classes)
+                        .arg(root.join("java").join("bench").join("Workloads.java")),
+                    "javac",
+                )?;
+                Ok(Prepared::Ready(Program {
+                    command: PathBuf::from("java"),
+                    args: vec!["-cp".into(), classes.into(), "Workloads".into()],
+                }))
+            }
+        }
+    }
+}
+
+/// The subset of cargo's JSON messages we care about.
+#[derive(Deserialize)]
+struct CargoMessage {
+    executable: Option<PathBuf>,
+}
+
+/// Builds `rust_workloads` in release mode, asking cargo where it put it.
+fn prepare_rust(root: &Path) -> Result<Program> {
+    let output = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .args([
+            "build",
+            "--release",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+            "--bin",
+            "rust_workloads",
+            "--manifest-path",
+        ])
+        .arg(root.join("rust").join("bench_harness").join("Cargo.toml"))
+        .stderr(Stdio::inherit())
+        .output()
+        .context("cannot run cargo")?;
+    if !output.status.success() {
+        bail!("cargo build failed for the Rust workloads");
+    }
+    let executable = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .find_map(|message| message.executable);
+    match executable {
+        Some(command) => Ok(Program {
+            command,
+            args: Vec::new(),
+        }),
+        None => bail!("cargo did not report where rust_workloads was built"),
+    }
+}
+
+fn is_installed(tool: &str, version_flag: &str) -> bool {
+    Command::new(tool)
+        .arg(version_flag)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn run_build(command: &mut Command, what: &str) -> Result<()> {
+    let status = command
+        .status()
+        .with_context(|| format!("cannot run {what}"))?;
+    if !status.success() {
+        bail!("{what} failed");
+    }
+    Ok(())
+}
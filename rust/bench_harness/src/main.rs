@@ -0,0 +1,142 @@
+//! `bench_harness` times the same workloads written in Rust, Go, and Java,
+//! and prints a comparison table.
+//!
+//! ```text
+//! cargo run --release -p bench-harness                 # everything, as Markdown
+//! cargo run --release -p bench-harness -- --quick      # small sizes, a quick look
+//! cargo run --release -p bench-harness -- --format csv --workload fib --workload sort
+//! ```
+//!
+//! Each language's implementation is a standalone program
+//! (`rust/bench_harness/workloads/`, `go/bench/`, `java/bench/`) that does
+//! its own warmup and timing and reports back over stdout; see
+//! [`measure`] for the protocol. A language whose toolchain isn't
+//! installed is skipped rather than failing the whole run.
+
+mod language;
+mod measure;
+mod report;
+mod workload;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+
+use language::{Language, Prepared};
+use report::{Format, Row, Status};
+use workload::Workload;
+
+#[derive(Parser)]
+#[command(
+    name = "bench_harness",
+    version,
+    about = "Compare the same workloads in Rust, Go, and Java"
+)]
+struct Cli {
+    /// Path to the top of this repository (the directory holding rust/, go/, java/).
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Workloads to run (repeat the flag for several). Default: all of them.
+    #[arg(long = "workload", value_enum)]
+    workloads: Vec<Workload>,
+
+    /// Languages to run (repeat the flag for several). Default: all of them.
+    #[arg(long = "language", value_enum)]
+    languages: Vec<Language>,
+
+    /// Untimed runs before measuring, so caches and JITs warm up.
+    #[arg(long, default_value_t = 3)]
+    warmup: usize,
+
+    /// Timed runs per workload and language.
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+
+    /// Use small inputs: a fast sanity check rather than a benchmark.
+    #[arg(long)]
+    quick: bool,
+
+    #[arg(long, value_enum, default_value_t = Format::Markdown)]
+    format: Format,
+}
+
+fn main() -> ExitCode {
+    match try_main() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns `Ok(false)` when a workload failed or the languages disagreed.
+fn try_main() -> Result<bool> {
+    let cli = Cli::parse();
+    anyhow::ensure!(cli.runs > 0, "--runs must be at least 1");
+    let root = cli
+        .root
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../.."));
+    let build_dir = root.join("rust").join("target").join("bench_harness");
+    let workloads = or_all(cli.workloads, Workload::value_variants());
+    let languages = or_all(cli.languages, Language::value_variants());
+
+    let mut programs = Vec::new();
+    for language in languages {
+        eprintln!("🔧 preparing {}", language.label());
+        programs.push((language, language.prepare(&root, &build_dir)?));
+    }
+
+    let mut rows = Vec::new();
+    for workload in &workloads {
+        let size = workload.size(cli.quick);
+        let mut expected: Option<(Language, u64)> = None;
+        for (language, prepared) in &programs {
+            let status = match prepared {
+                Prepared::Skipped(reason) => Status::Skipped(reason.clone()),
+                Prepared::Ready(program) => {
+                    eprintln!("⏱  {} {}", language.label(), workload.label(size));
+                    match program.measure(*workload, size, cli.warmup, cli.runs) {
+                        Err(err) => Status::Failed(format!("{err:#}")),
+                        Ok(measurement) => match expected {
+                            Some((first, checksum)) if checksum != measurement.checksum => {
+                                Status::Failed(format!(
+                                    "checksum {} differs from {}'s {checksum}",
+                                    measurement.checksum,
+                                    first.label()
+                                ))
+                            }
+                            _ => {
+                                expected.get_or_insert((*language, measurement.checksum));
+                                Status::Measured(measurement.stats())
+                            }
+                        },
+                    }
+                }
+            };
+            rows.push(Row {
+                workload: workload.label(size),
+                language: language.label(),
+                status,
+            });
+        }
+    }
+
+    let passed = rows
+        .iter()
+        .all(|row| !matches!(row.status, Status::Failed(_)));
+    print!("{}", report::render(&rows, cli.format));
+    Ok(passed)
+}
+
+fn or_all<T: Copy>(chosen: Vec<T>, all: &[T]) -> Vec<T> {
+    if chosen.is_empty() {
+        all.to_vec()
+    } else {
+        chosen
+    }
+}
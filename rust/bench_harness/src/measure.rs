@@ -0,0 +1,163 @@
+//! Running a workload program and summarizing its timings.
+//!
+//! A workload program is invoked as `<program> <workload> <size> <warmup>
+//! <runs>`. It prepares its input, runs the workload `warmup` times
+//! untimed and `runs` times timed, then prints:
+//!
+//! ```text
+//! checksum 832040
+//! run 4120334
+//! run 4098112
+//! ...
+//! ```
+//!
+//! with one `run` line (in nanoseconds) per timed run. The checksum proves
+//! every language did the same work.
+
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::language::Program;
+use crate::workload::Workload;
+
+/// What one program reported for one workload.
+#[derive(Debug, PartialEq)]
+pub struct Measurement {
+    pub checksum: u64,
+    pub times: Vec<Duration>,
+}
+
+/// Summary statistics over the timed runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Program {
+    pub fn measure(
+        &self,
+        workload: Workload,
+        size: usize,
+        warmup: usize,
+        runs: usize,
+    ) -> Result<Measurement> {
+        let output = Command::new(&self.command)
+            .args(&self.args)
+            .arg(workload.name())
+            .args([size.to_string(), warmup.to_string(), runs.to_string()])
+            .output()
+            .with_context(|| format!("cannot run {}", self.command.display()))?;
+        if !output.status.success() {
+            bail!(
+                "exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        let measurement = parse_output(&String::from_utf8_lossy(&output.stdout))?;
+        if measurement.times.len() != runs {
+            bail!(
+                "reported {} runs instead of {runs}",
+                measurement.times.len()
+            );
+        }
+        Ok(measurement)
+    }
+}
+
+/// Parses the `checksum`/`run` lines a workload program prints.
+pub fn parse_output(stdout: &str) -> Result<Measurement> {
+    let mut checksum = None;
+    let mut times = Vec::new();
+    for line in stdout.lines() {
+        match line.split_once(' ') {
+            Some(("checksum", value)) => {
+                checksum = Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("bad line `{line}`"))?,
+                );
+            }
+            Some(("run", nanos)) => {
+                let nanos: u64 = nanos
+                    .parse()
+                    .with_context(|| format!("bad line `{line}`"))?;
+                times.push(Duration::from_nanos(nanos));
+            }
+            _ => bail!("unexpected output line `{line}`"),
+        }
+    }
+    match checksum {
+        Some(checksum) => Ok(Measurement { checksum, times }),
+        None => bail!("no checksum line in the output"),
+    }
+}
+
+impl Measurement {
+    /// Summarizes the timings. There is always at least one run.
+    pub fn stats(&self) -> Stats {
+        let mut sorted = self.times.clone();
+        sorted.sort();
+        let runs = sorted.len();
+        let median = if runs % 2 == 1 {
+            sorted[runs / 2]
+        } else {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        };
+        let nanos: Vec<f64> = sorted.iter().map(|time| time.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+        Stats {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_protocol() {
+        let measurement = parse_output("checksum 832040\nrun 1500\nrun 2500\n").unwrap();
+        assert_eq!(
+            measurement,
+            Measurement {
+                checksum: 832040,
+                times: vec![Duration::from_nanos(1500), Duration::from_nanos(2500)],
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(parse_output("run 10\n").is_err(), "missing checksum");
+        assert!(parse_output("checksum 1\nrun fast\n").is_err());
+        assert!(parse_output("checksum 1\nhello\n").is_err());
+    }
+
+    #[test]
+    fn summarizes_runs() {
+        let measurement = Measurement {
+            checksum: 0,
+            times: [40, 10, 30, 20].map(Duration::from_nanos).to_vec(),
+        };
+        let stats = measurement.stats();
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(25));
+        assert_eq!(stats.mean, Duration::from_nanos(25));
+        // sqrt(((15^2 + 5^2) * 2) / 4) = sqrt(125)
+        assert_eq!(stats.std_dev, Duration::from_nanos(11));
+    }
+}
@@ -0,0 +1,186 @@
+//! Rendering the results as a Markdown table or CSV.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::measure::Stats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Markdown,
+    Csv,
+}
+
+/// One workload in one language.
+#[derive(Debug)]
+pub struct Row {
+    pub workload: String,
+    pub language: &'static str,
+    pub status: Status,
+}
+
+#[derive(Debug)]
+pub enum Status {
+    Measured(Stats),
+    Skipped(String),
+    Failed(String),
+}
+
+pub fn render(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Markdown => markdown(rows),
+        Format::Csv => csv(rows),
+    }
+}
+
+/// The fastest median among the rows for `workload`.
+fn fastest(rows: &[Row], workload: &str) -> Option<Duration> {
+    rows.iter()
+        .filter(|row| row.workload == workload)
+        .filter_map(|row| match &row.status {
+            Status::Measured(stats) => Some(stats.median),
+            _ => None,
+        })
+        .min()
+}
+
+/// How many times slower than the fastest language, e.g. `2.50`.
+fn relative(median: Duration, fastest: Option<Duration>) -> f64 {
+    match fastest {
+        Some(fastest) if !fastest.is_zero() => median.as_secs_f64() / fastest.as_secs_f64(),
+        _ => 1.0,
+    }
+}
+
+fn markdown(rows: &[Row]) -> String {
+    let mut out = String::from(
+        "| Workload | Language | Median | Min | Mean | Std dev | vs fastest |\n\
+         |----------|----------|-------:|----:|-----:|--------:|-----------:|\n",
+    );
+    for row in rows {
+        let cells = match &row.status {
+            Status::Measured(stats) => format!(
+                "{} | {} | {} | {} | {:.2}×",
+                human(stats.median),
+                human(stats.min),
+                human(stats.mean),
+                human(stats.std_dev),
+                relative(stats.median, fastest(rows, &row.workload))
+            ),
+            Status::Skipped(reason) => format!("skipped: {reason} | | | |"),
+            Status::Failed(reason) => format!("❌ {} | | | |", reason.replace('|', "\\|")),
+        };
+        writeln!(out, "| {} | {} | {cells} |", row.workload, row.language).unwrap();
+    }
+    out
+}
+
+fn csv(rows: &[Row]) -> String {
+    let mut out = String::from(
+        "workload,language,status,runs,median_ns,min_ns,mean_ns,std_dev_ns,relative\n",
+    );
+    for row in rows {
+        let cells = match &row.status {
+            Status::Measured(stats) => format!(
+                "ok,{},{},{},{},{},{:.3}",
+                stats.runs,
+                stats.median.as_nanos(),
+                stats.min.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.std_dev.as_nanos(),
+                relative(stats.median, fastest(rows, &row.workload))
+            ),
+            Status::Skipped(reason) => format!("skipped: {},,,,,,", quote_free(reason)),
+            Status::Failed(reason) => format!("failed: {},,,,,,", quote_free(reason)),
+        };
+        writeln!(out, "{},{},{cells}", row.workload, row.language).unwrap();
+    }
+    out
+}
+
+/// Keeps free-text reasons from breaking the CSV columns.
+fn quote_free(text: &str) -> String {
+    text.replace([',', '\n'], " ")
+}
+
+/// A duration with a unit that keeps it readable, e.g. `4.12 ms`.
+fn human(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos >= 1e9 {
+        format!("{:.2} s", nanos / 1e9)
+    } else if nanos >= 1e6 {
+        format!("{:.2} ms", nanos / 1e6)
+    } else if nanos >= 1e3 {
+        format!("{:.2} µs", nanos / 1e3)
+    } else {
+        format!("{nanos} ns")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(median_ms: u64) -> Stats {
+        let median = Duration::from_millis(median_ms);
+        Stats {
+            runs: 5,
+            min: median,
+            median,
+            mean: median,
+            std_dev: Duration::ZERO,
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                workload: "fib (32)".to_string(),
+                language: "Rust",
+                status: Status::Measured(stats(4)),
+            },
+            Row {
+                workload: "fib (32)".to_string(),
+                language: "Go",
+                status: Status::Skipped("`go` not found".to_string()),
+            },
+            Row {
+                workload: "fib (32)".to_string(),
+                language: "Java",
+                status: Status::Measured(stats(10)),
+            },
+        ]
+    }
+
+    #[test]
+    fn markdown_table() {
+        assert_eq!(
+            render(&rows(), Format::Markdown),
+            "| Workload | Language | Median | Min | Mean | Std dev | vs fastest |\n\
+             |----------|----------|-------:|----:|-----:|--------:|-----------:|\n\
+             | fib (32) | Rust | 4.00 ms | 4.00 ms | 4.00 ms | 0 ns | 1.00× |\n\
+             | fib (32) | Go | skipped: `go` not found | | | | |\n\
+             | fib (32) | Java | 10.00 ms | 10.00 ms | 10.00 ms | 0 ns | 2.50× |\n"
+        );
+    }
+
+    #[test]
+    fn csv_table() {
+        assert_eq!(
+            render(&rows(), Format::Csv),
+            "workload,language,status,runs,median_ns,min_ns,mean_ns,std_dev_ns,relative\n\
+             fib (32),Rust,ok,5,4000000,4000000,4000000,0,1.000\n\
+             fib (32),Go,skipped: `go` not found,,,,,,\n\
+             fib (32),Java,ok,5,10000000,10000000,10000000,0,2.500\n"
+        );
+    }
+
+    #[test]
+    fn human_units() {
+        assert_eq!(human(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(human(Duration::from_micros(1500)), "1.50 ms");
+        assert_eq!(human(Duration::from_secs(2)), "2.00 s");
+    }
+}
@@ -0,0 +1,48 @@
+//! The workloads every language implements.
+
+use clap::ValueEnum;
+
+/// One benchmark. The name is what the workload programs take as their
+/// first argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Workload {
+    /// Naive recursive Fibonacci: function calls and integer arithmetic.
+    Fib,
+    /// Sorting pseudo-random integers.
+    Sort,
+    /// Parsing a JSON array of records into generic values.
+    Json,
+    /// HTTP POST round trips to an echo server on localhost.
+    Http,
+}
+
+impl Workload {
+    pub fn name(self) -> &'static str {
+        match self {
+            Workload::Fib => "fib",
+            Workload::Sort => "sort",
+            Workload::Json => "json",
+            Workload::Http => "http",
+        }
+    }
+
+    /// The input size: `n` for fib, elements for sort, records for json,
+    /// requests for http.
+    pub fn size(self, quick: bool) -> usize {
+        match (self, quick) {
+            (Workload::Fib, false) => 32,
+            (Workload::Fib, true) => 20,
+            (Workload::Sort, false) => 1_000_000,
+            (Workload::Sort, true) => 10_000,
+            (Workload::Json, false) => 50_000,
+            (Workload::Json, true) => 1_000,
+            (Workload::Http, false) => 2_000,
+            (Workload::Http, true) => 50,
+        }
+    }
+
+    /// How the workload appears in the report, e.g. `sort (1000000)`.
+    pub fn label(self, size: usize) -> String {
+        format!("{} ({size})", self.name())
+    }
+}
@@ -0,0 +1,260 @@
+// Benchmark workloads, Rust version.
+//
+// The harness (../src/main.rs) runs this program as
+//     rust_workloads <workload> <size> <warmup> <runs>
+// and reads back one `checksum <value>` line followed by one
+// `run <nanoseconds>` line per measured run. go/bench/workloads.go and
+// java/bench/Workloads.java implement the same workloads and protocol,
+// and must print the same checksums.
+//
+// Timing happens in here, around the work only, so process startup (and
+// for Java, the JIT warming up) doesn't count.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Instant;
+
+/// The shared pseudo-random generator: a 64-bit LCG, identical in all
+/// three languages so they work on the same data.
+struct Lcg(u64);
+
+impl Lcg {
+    fn new() -> Self {
+        Lcg(42)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+}
+
+/// One workload, prepared for repeated runs.
+trait Workload {
+    /// Does the work once, returning a checksum of the result.
+    fn run(&mut self) -> u64;
+}
+
+// ===== fib: recursive Fibonacci, pure function calls =====
+
+struct Fib(u64);
+
+fn fib(n: u64) -> u64 {
+    if n < 2 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+impl Workload for Fib {
+    fn run(&mut self) -> u64 {
+        // black_box stops the compiler computing the answer ahead of time
+        fib(std::hint::black_box(self.0))
+    }
+}
+
+// ===== sort: sorting random integers =====
+
+struct Sort {
+    input: Vec<u64>,
+    scratch: Vec<u64>,
+}
+
+impl Sort {
+    fn new(size: usize) -> Self {
+        let mut lcg = Lcg::new();
+        let input = (0..size).map(|_| lcg.next()).collect();
+        Sort {
+            input,
+            scratch: Vec::new(),
+        }
+    }
+}
+
+impl Workload for Sort {
+    fn run(&mut self) -> u64 {
+        self.scratch.clone_from(&self.input);
+        self.scratch.sort_unstable();
+        let n = self.scratch.len();
+        self.scratch[0] + self.scratch[n / 2] + self.scratch[n - 1]
+    }
+}
+
+// ===== json: parsing a document into generic values =====
+
+struct Json(String);
+
+impl Json {
+    fn new(size: usize) -> Self {
+        let mut lcg = Lcg::new();
+        let mut text = String::from("[");
+        for i in 0..size {
+            let score = lcg.next() % 1000;
+            let tags: Vec<String> = (0..lcg.next() % 4).map(|k| format!("\"t{k}\"")).collect();
+            if i > 0 {
+                text.push(',');
+            }
+            text.push_str(&format!(
+                "{{\"id\":{i},\"name\":\"item-{i}\",\"score\":{score},\"tags\":[{}],\"active\":{}}}",
+                tags.join(","),
+                i % 2 == 0
+            ));
+        }
+        text.push(']');
+        Json(text)
+    }
+}
+
+impl Workload for Json {
+    fn run(&mut self) -> u64 {
+        let value: serde_json::Value = serde_json::from_str(&self.0).expect("valid JSON");
+        let mut checksum = 0;
+        for record in value.as_array().expect("an array") {
+            checksum += record["id"].as_u64().unwrap();
+            checksum += record["score"].as_u64().unwrap();
+            checksum += record["tags"].as_array().unwrap().len() as u64;
+            checksum += record["active"].as_bool().unwrap() as u64;
+        }
+        checksum
+    }
+}
+
+// ===== http: request/response round trips over localhost =====
+
+/// A minimal HTTP/1.1 echo server and client on std alone: the server
+/// answers every POST with the request body. One keep-alive connection.
+struct Http {
+    requests: usize,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Http {
+    fn new(requests: usize) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                thread::spawn(move || serve(stream));
+            }
+        });
+        let writer = TcpStream::connect(address).expect("connect");
+        writer.set_nodelay(true).unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        Http {
+            requests,
+            reader,
+            writer,
+        }
+    }
+}
+
+/// Reads the headers of one message, returning its Content-Length, or
+/// `None` when the connection closed.
+fn read_head(reader: &mut BufReader<TcpStream>) -> Option<usize> {
+    let mut length = 0;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            return Some(length);
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok()?;
+            }
+        }
+    }
+}
+
+fn serve(stream: TcpStream) {
+    stream.set_nodelay(true).unwrap();
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+    while let Some(length) = read_head(&mut reader) {
+        let mut body = vec![0; length];
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {length}\r\n\r\n"
+        )
+        .into_bytes();
+        response.extend_from_slice(&body);
+        if writer.write_all(&response).is_err() {
+            return;
+        }
+    }
+}
+
+impl Workload for Http {
+    fn run(&mut self) -> u64 {
+        let mut checksum = 0;
+        for i in 0..self.requests {
+            let body = format!("ping-{i}");
+            let request = format!(
+                "POST /echo HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            self.writer.write_all(request.as_bytes()).expect("send");
+            let length = read_head(&mut self.reader).expect("response");
+            let mut echoed = vec![0; length];
+            self.reader.read_exact(&mut echoed).expect("body");
+            checksum += echoed.len() as u64;
+        }
+        checksum
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [name, size, warmup, runs] = args.as_slice() else {
+        eprintln!("usage: rust_workloads <fib|sort|json|http> <size> <warmup> <runs>");
+        return ExitCode::FAILURE;
+    };
+    let (Ok(size), Ok(warmup), Ok(runs)) = (
+        size.parse::<usize>(),
+        warmup.parse::<usize>(),
+        runs.parse::<usize>(),
+    ) else {
+        eprintln!("size, warmup and runs must be numbers");
+        return ExitCode::FAILURE;
+    };
+
+    let mut workload: Box<dyn Workload> = match name.as_str() {
+        "fib" => Box::new(Fib(size as u64)),
+        "sort" => Box::new(Sort::new(size)),
+        "json" => Box::new(Json::new(size)),
+        "http" => Box::new(Http::new(size)),
+        _ => {
+            eprintln!("unknown workload `{name}`");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut checksum = 0;
+    for _ in 0..warmup {
+        checksum = workload.run();
+    }
+    let mut times = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        checksum = workload.run();
+        times.push(start.elapsed().as_nanos());
+    }
+    println!("checksum {checksum}");
+    for nanos in times {
+        println!("run {nanos}");
+    }
+    ExitCode::SUCCESS
+}
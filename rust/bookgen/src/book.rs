@@ -0,0 +1,231 @@
+//! Laying out the course as an mdBook.
+//!
+//! ```text
+//! book/
+//! ├── book.toml
+//! └── src/
+//!     ├── SUMMARY.md             <- the table of contents
+//!     ├── README.md              <- the introduction
+//!     └── lifetimes/
+//!         ├── README.md          <- the chapter's overview
+//!         └── 01_elision.md      <- one page per example
+//! ```
+//!
+//! Each example group is a chapter, and the top-level examples make up the
+//! first one. Chapters come in the order the course reaches them, and the
+//! pages in a chapter in course order.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use tutor::course::{Course, Example};
+use tutor::page::{fence, Page};
+
+/// The directory holding the top-level examples' pages.
+const BASICS: &str = "basics";
+
+const BOOK_TOML: &str = r#"# Generated by `cargo run -p bookgen`; edits will be overwritten.
+[book]
+title = "Learn Rust by Example"
+language = "en"
+src = "src"
+
+[rust]
+edition = "2021"
+
+[output.html]
+default-theme = "light"
+"#;
+
+struct Chapter<'a> {
+    dir: &'a str,
+    examples: Vec<&'a Example>,
+}
+
+impl Chapter<'_> {
+    fn title(&self) -> String {
+        if self.dir == BASICS {
+            return "Getting Started".to_string();
+        }
+        self.dir
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_uppercase().chain(chars).collect()
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Writes the book for `course` into `out`, replacing any book generated
+/// there before. Returns how many pages were written.
+pub fn write(course: &Course, out: &Path) -> Result<usize> {
+    let src = out.join("src");
+    if src.join("SUMMARY.md").is_file() {
+        fs::remove_dir_all(&src).with_context(|| format!("cannot clear {}", src.display()))?;
+    }
+    fs::create_dir_all(&src).with_context(|| format!("cannot create {}", src.display()))?;
+    write_file(&out.join("book.toml"), BOOK_TOML)?;
+
+    let chapters = chapters(course);
+    let mut summary = String::from("# Summary\n\n[Introduction](README.md)\n\n");
+    for chapter in &chapters {
+        fs::create_dir_all(src.join(chapter.dir))?;
+        writeln!(
+            summary,
+            "- [{}]({}/README.md)",
+            chapter.title(),
+            chapter.dir
+        )?;
+        let mut overview = format!("# {}\n\n", chapter.title());
+        for example in &chapter.examples {
+            let title = title(course, example);
+            let file = format!("{}.md", stem(example));
+            writeln!(summary, "  - [{title}]({}/{file})", chapter.dir)?;
+            writeln!(overview, "- [{title}]({file})")?;
+            write_file(
+                &src.join(chapter.dir).join(&file),
+                &render(course, example)?,
+            )?;
+        }
+        write_file(&src.join(chapter.dir).join("README.md"), &overview)?;
+    }
+    write_file(&src.join("SUMMARY.md"), &summary)?;
+    write_file(&src.join("README.md"), &introduction(course))?;
+    Ok(course.examples.len())
+}
+
+/// Groups the examples into chapters, keeping the course order.
+fn chapters(course: &Course) -> Vec<Chapter<'_>> {
+    let mut chapters: Vec<Chapter> = Vec::new();
+    for example in &course.examples {
+        let dir = example.group.as_deref().unwrap_or(BASICS);
+        match chapters.iter_mut().find(|chapter| chapter.dir == dir) {
+            Some(chapter) => chapter.examples.push(example),
+            None => chapters.push(Chapter {
+                dir,
+                examples: vec![example],
+            }),
+        }
+    }
+    chapters
+}
+
+/// One example's page.
+fn render(course: &Course, example: &Example) -> Result<String> {
+    let source = fs::read_to_string(&example.path)
+        .with_context(|| format!("cannot read {}", example.path.display()))?;
+    let page = Page::parse(&source);
+    let mut markdown = format!("# {}\n\n", title(course, example));
+
+    if let Some(lesson) = course.lessons.get(&example.name) {
+        let mut about = format!("*{}", lesson.difficulty);
+        if !lesson.tags.is_empty() {
+            write!(about, " · {}", lesson.tags.join(", "))?;
+        }
+        about.push('*');
+        let links: Vec<String> = lesson
+            .requires
+            .iter()
+            .filter_map(|id| course.find(id).map(|index| &course.examples[index]))
+            .map(|required| {
+                let title = title(course, required);
+                format!("[{title}]({})", link(example, required))
+            })
+            .collect();
+        if !links.is_empty() {
+            write!(about, " — builds on {}", links.join(", "))?;
+        }
+        markdown.push_str(&about);
+        markdown.push_str("\n\n");
+    }
+    if !page.intro.is_empty() {
+        markdown.push_str(&page.intro);
+        markdown.push('\n');
+    }
+
+    // Single-file examples run on the playground as they are. Examples in a
+    // cargo package use its other files or crates, so they are only shown.
+    let info = if example.manifest.is_some() {
+        "rust,ignore"
+    } else {
+        "rust,editable"
+    };
+    let fence = fence(&page.code);
+    writeln!(markdown, "{fence}{info}\n{}\n{fence}\n", page.code)?;
+    writeln!(
+        markdown,
+        "The source is `rust/{}`. Run it locally with `cargo run -p tutor -- run {}`.\n",
+        relative_path(course, example),
+        example.name
+    )?;
+
+    if let Some(expected) = &page.expected {
+        writeln!(
+            markdown,
+            "### Output\n\n```text\n{}\n```\n",
+            expected.trim_end()
+        )?;
+    }
+    if !page.notes.is_empty() {
+        markdown.push_str(&page.notes);
+    }
+    Ok(markdown)
+}
+
+fn introduction(course: &Course) -> String {
+    format!(
+        "# Learn Rust by Example\n\n\
+         This book is generated from the {} example programs in `rust/examples/`, \
+         in the order the `tutor` teaches them: every lesson comes after the ones \
+         it builds on. Each page shows one program, what it prints, and its key \
+         concepts.\n\n\
+         The examples are ordinary Rust programs, so you can also run them \
+         yourself. From the `rust/` directory:\n\n\
+         ```bash\ncargo run -p tutor -- run 01_hello_world\n```\n",
+        course.examples.len()
+    )
+}
+
+/// The lesson title from `lessons.toml`, or the example's name, escaped
+/// so that `Vec<T>` isn't taken for an HTML tag.
+fn title(course: &Course, example: &Example) -> String {
+    let title = course
+        .lessons
+        .get(&example.name)
+        .map_or(example.name.as_str(), |lesson| lesson.title.as_str());
+    title.replace('<', "\\<").replace('>', "\\>")
+}
+
+fn stem(example: &Example) -> &str {
+    example.name.rsplit('/').next().unwrap_or(&example.name)
+}
+
+/// A link from `from`'s page to `to`'s.
+fn link(from: &Example, to: &Example) -> String {
+    let dir = |example: &Example| example.group.clone().unwrap_or_else(|| BASICS.to_string());
+    if dir(from) == dir(to) {
+        format!("{}.md", stem(to))
+    } else {
+        format!("../{}/{}.md", dir(to), stem(to))
+    }
+}
+
+/// The example's path inside `rust/`, e.g. `examples/02_variables.rs`.
+fn relative_path(course: &Course, example: &Example) -> String {
+    example
+        .path
+        .strip_prefix(&course.root)
+        .unwrap_or(&example.path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("cannot write {}", path.display()))
+}
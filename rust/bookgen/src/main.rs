@@ -0,0 +1,59 @@
+//! `bookgen` turns the examples into an [mdBook](https://rust-lang.github.io/mdBook/),
+//! so the same sources are both runnable programs and a browsable book.
+//!
+//! ```text
+//! cargo run -p bookgen                 # writes rust/book/
+//! mdbook serve book                    # then read it at http://localhost:3000
+//! ```
+
+mod book;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::Result;
+use clap::Parser;
+use tutor::course::Course;
+
+#[derive(Parser)]
+#[command(
+    name = "bookgen",
+    version,
+    about = "Generate an mdBook of the course from the example sources"
+)]
+struct Cli {
+    /// Path to the `rust/` directory of this repository.
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Where to write the book (default: `book/` in the root).
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    match try_main() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_main() -> Result<()> {
+    let cli = Cli::parse();
+    let root = cli
+        .root
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."));
+    let course = Course::discover(&root)?;
+    let out = cli.out.unwrap_or_else(|| course.root.join("book"));
+
+    let pages = book::write(&course, &out)?;
+    println!("📖 Wrote {pages} pages to {}", out.display());
+    println!(
+        "   Build it with `mdbook build {0}` or read it with `mdbook serve {0}`.",
+        out.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,316 @@
+//! Compiling an example, and compiling its broken snippets.
+//!
+//! Plain examples are single files for `rustc`. An example in a cargo
+//! package is first built with cargo, which also builds its dependencies;
+//! its snippets are then compiled with `rustc` directly against those
+//! dependencies, so the package itself is never modified.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::discover::{stem, Example};
+use crate::snippet::{Expected, Snippet};
+
+/// How to compile more code the way an example was compiled.
+#[derive(Debug, Default)]
+pub struct Build {
+    edition: String,
+    /// `--extern` crates: the package's library and its direct dependencies.
+    externs: Vec<(String, PathBuf)>,
+    /// Where the rest of the dependency graph was built.
+    search_dirs: Vec<PathBuf>,
+}
+
+/// One error reported by `rustc`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Diagnostic {
+    pub message: String,
+    pub code: Option<DiagnosticCode>,
+    pub level: String,
+    pub rendered: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiagnosticCode {
+    pub code: String,
+}
+
+/// Compiles `example` as the tutor would, with artifacts under `work_dir`.
+/// The inner error holds the compiler's diagnostics.
+pub fn compile(example: &Example, work_dir: &Path) -> Result<Result<Build, String>> {
+    fs::create_dir_all(work_dir)
+        .with_context(|| format!("cannot create {}", work_dir.display()))?;
+    match &example.manifest {
+        Some(manifest) => cargo_build(manifest, &stem(&example.path)),
+        None => {
+            let build = Build {
+                edition: "2021".to_string(),
+                ..Build::default()
+            };
+            let output = work_dir.join(format!(
+                "{}{}",
+                stem(&example.path),
+                std::env::consts::EXE_SUFFIX
+            ));
+            let errors = rustc(&build, &example.path, &["-o".as_ref(), output.as_os_str()])?;
+            if errors.is_empty() {
+                Ok(Ok(build))
+            } else {
+                Ok(Err(render(&errors)))
+            }
+        }
+    }
+}
+
+/// Compiles `example` with `snippet` uncommented and returns the errors.
+pub fn check_snippet(
+    example: &Example,
+    build: &Build,
+    snippet: &Snippet,
+    work_dir: &Path,
+) -> Result<Vec<Diagnostic>> {
+    let source = fs::read_to_string(&example.path)
+        .with_context(|| format!("cannot read {}", example.path.display()))?;
+    let dir = work_dir.join(format!("line{}", snippet.line));
+    fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    // Keep the file name: the crate name rustc picks depends on it.
+    let path = dir.join(
+        example
+            .path
+            .file_name()
+            .context("example has no file name")?,
+    );
+    fs::write(&path, snippet.uncomment(&source))
+        .with_context(|| format!("cannot write {}", path.display()))?;
+    // Type checking is enough to see the errors, and skips linking.
+    rustc(
+        build,
+        &path,
+        &[
+            "--emit=metadata".as_ref(),
+            "--out-dir".as_ref(),
+            dir.as_os_str(),
+        ],
+    )
+}
+
+impl Expected {
+    /// Whether `errors` contain the documented error.
+    pub fn is_met_by(&self, errors: &[Diagnostic]) -> bool {
+        match self {
+            Expected::Code(code) => errors
+                .iter()
+                .any(|error| error.code.as_ref().is_some_and(|c| &c.code == code)),
+            Expected::Message(message) => errors
+                .iter()
+                .any(|error| error.message.contains(message.as_str())),
+            Expected::AnyError => !errors.is_empty(),
+        }
+    }
+}
+
+/// The error codes (or messages, for errors without one) in `errors`.
+pub fn summarize(errors: &[Diagnostic]) -> String {
+    if errors.is_empty() {
+        return "no errors".to_string();
+    }
+    let mut seen: Vec<String> = Vec::new();
+    for error in errors {
+        let label = match &error.code {
+            Some(code) => code.code.clone(),
+            None => format!("`{}`", error.message),
+        };
+        if !seen.contains(&label) {
+            seen.push(label);
+        }
+    }
+    seen.join(", ")
+}
+
+/// Runs `rustc` on `source` and returns the errors it reported.
+fn rustc(build: &Build, source: &Path, extra_args: &[&std::ffi::OsStr]) -> Result<Vec<Diagnostic>> {
+    let mut command = Command::new("rustc");
+    command
+        .arg(format!("--edition={}", build.edition))
+        .arg("--error-format=json")
+        .arg("--json=diagnostic-rendered-ansi")
+        .args(extra_args);
+    for dir in &build.search_dirs {
+        command
+            .arg("-L")
+            .arg(format!("dependency={}", dir.display()));
+    }
+    for (name, path) in &build.externs {
+        command
+            .arg("--extern")
+            .arg(format!("{name}={}", path.display()));
+    }
+    let output = command
+        .arg(source)
+        .output()
+        .context("failed to launch rustc; is Rust installed and on your PATH?")?;
+    Ok(String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Diagnostic>(line).ok())
+        .filter(|diagnostic| {
+            diagnostic.level == "error" && !diagnostic.message.starts_with("aborting due to")
+        })
+        .collect())
+}
+
+fn render(errors: &[Diagnostic]) -> String {
+    errors
+        .iter()
+        .map(|error| {
+            error
+                .rendered
+                .clone()
+                .unwrap_or_else(|| error.message.clone())
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    manifest_path: PathBuf,
+    edition: String,
+    dependencies: Vec<Dependency>,
+    targets: Vec<Target>,
+}
+
+#[derive(Deserialize)]
+struct Dependency {
+    name: String,
+    rename: Option<String>,
+    /// `None` for normal dependencies; `dev` and `build` ones don't count.
+    kind: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    name: String,
+    kind: Vec<String>,
+}
+
+/// A line of `cargo build --message-format=json` output.
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    target: Option<Target>,
+    #[serde(default)]
+    filenames: Vec<PathBuf>,
+}
+
+/// Builds the `bin` of the package at `manifest` and works out how to
+/// compile more code against the same dependencies.
+fn cargo_build(manifest: &Path, bin: &str) -> Result<Result<Build, String>> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(&cargo)
+        .args(["build", "--quiet", "--color=always"])
+        .arg("--message-format=json-render-diagnostics")
+        .arg("--manifest-path")
+        .arg(manifest)
+        .args(["--bin", bin])
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+    if !output.status.success() {
+        return Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()));
+    }
+    let artifacts: Vec<CargoMessage> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .filter(|message| message.reason == "compiler-artifact")
+        .collect();
+
+    let metadata = Command::new(&cargo)
+        .args([
+            "metadata",
+            "--no-deps",
+            "--format-version=1",
+            "--manifest-path",
+        ])
+        .arg(manifest)
+        .output()
+        .context("failed to run cargo metadata")?;
+    if !metadata.status.success() {
+        bail!("cargo metadata failed for {}", manifest.display());
+    }
+    let metadata: Metadata =
+        serde_json::from_slice(&metadata.stdout).context("cannot parse cargo metadata")?;
+    let manifest = fs::canonicalize(manifest)?;
+    let package = metadata
+        .packages
+        .into_iter()
+        .find(|package| fs::canonicalize(&package.manifest_path).is_ok_and(|path| path == manifest))
+        .with_context(|| format!("cargo metadata doesn't list {}", manifest.display()))?;
+
+    // The crates the bin can name: its own package's library, and the
+    // normal dependencies under their (possibly renamed) names.
+    let mut wanted: Vec<(String, String)> = package
+        .targets
+        .iter()
+        .filter(|target| {
+            target
+                .kind
+                .iter()
+                .any(|kind| kind == "lib" || kind == "rlib")
+        })
+        .map(|target| (crate_name(&target.name), crate_name(&target.name)))
+        .collect();
+    wanted.extend(
+        package
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.kind.is_none())
+            .map(|dependency| {
+                let extern_name = dependency.rename.as_deref().unwrap_or(&dependency.name);
+                (crate_name(extern_name), crate_name(&dependency.name))
+            }),
+    );
+
+    let mut build = Build {
+        edition: package.edition,
+        ..Build::default()
+    };
+    for artifact in &artifacts {
+        let Some(target) = &artifact.target else {
+            continue;
+        };
+        let is_library = target
+            .kind
+            .iter()
+            .any(|kind| matches!(kind.as_str(), "lib" | "rlib" | "proc-macro"));
+        let file = artifact
+            .filenames
+            .iter()
+            .find(|file| file.extension().is_none_or(|ext| ext != "rmeta"));
+        if let (true, Some(file)) = (is_library, file) {
+            if let Some(dir) = file.parent() {
+                if !build.search_dirs.iter().any(|known| known == dir) {
+                    build.search_dirs.push(dir.to_path_buf());
+                }
+            }
+            for (extern_name, _) in wanted
+                .iter()
+                .filter(|(_, name)| *name == crate_name(&target.name))
+            {
+                build.externs.push((extern_name.clone(), file.clone()));
+            }
+        }
+    }
+    Ok(Ok(build))
+}
+
+/// Crate names use underscores where package names may use dashes.
+fn crate_name(name: &str) -> String {
+    name.replace('-', "_")
+}
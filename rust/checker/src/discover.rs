@@ -0,0 +1,91 @@
+//! Finding the examples, using the same rules as the tutor.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// One example file.
+#[derive(Debug, Clone)]
+pub struct Example {
+    /// `02_variables`, or `lifetimes/01_elision` for grouped examples.
+    pub name: String,
+    pub path: PathBuf,
+    /// The group's `Cargo.toml` when the group is a cargo package, in which
+    /// case the example is the `[[bin]]` named after its file.
+    pub manifest: Option<PathBuf>,
+}
+
+/// Every `.rs` file in `<root>/examples` and its group directories, in the
+/// order the tutor would list them. A package's `build.rs` is skipped.
+pub fn discover(root: &Path) -> Result<Vec<Example>> {
+    let dir = root.join("examples");
+    let mut examples = Vec::new();
+    let mut groups = Vec::new();
+    for path in sorted_entries(&dir)? {
+        if path.is_dir() {
+            groups.push(path);
+        } else if is_rust_file(&path) {
+            examples.push(Example {
+                name: stem(&path),
+                path,
+                manifest: None,
+            });
+        }
+    }
+    for group in groups {
+        let manifest = package_manifest(&group)?;
+        for path in sorted_entries(&group)? {
+            let is_build_script = manifest.is_some() && path.ends_with("build.rs");
+            if is_rust_file(&path) && !is_build_script {
+                examples.push(Example {
+                    name: format!("{}/{}", stem(&group), stem(&path)),
+                    path,
+                    manifest: manifest.clone(),
+                });
+            }
+        }
+    }
+    Ok(examples)
+}
+
+fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| format!("cannot read {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// The group's `Cargo.toml`, unless it is marked `[package.metadata.tutor]
+/// standalone = true`: then its files are compiled with rustc, one at a
+/// time, as if the manifest weren't there.
+fn package_manifest(dir: &Path) -> Result<Option<PathBuf>> {
+    let path = dir.join("Cargo.toml");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let manifest: toml::Table =
+        toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+    let standalone = manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("tutor"))
+        .and_then(|tutor| tutor.get("standalone"))
+        .and_then(|standalone| standalone.as_bool())
+        .unwrap_or(false);
+    Ok((!standalone).then_some(path))
+}
+
+fn is_rust_file(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == "rs")
+}
+
+pub(crate) fn stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
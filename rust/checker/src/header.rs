@@ -0,0 +1,291 @@
+//! The metadata at the top of every example.
+//!
+//! ```text
+//! // Example 2: Variables and Mutability          <- title line
+//! // Demonstrates immutable and mutable variables
+//! //
+//! // Concepts: let, mut, shadowing, constants     <- fields
+//! // Difficulty: beginner
+//! // Minutes: 10
+//! // Related: go/examples/02_variables.go, java/examples/Variables.java
+//! ```
+//!
+//! The title line comes first: a heading, the example's number, and its
+//! title. The fields may come anywhere in the comment that opens the file,
+//! each at most once. All but `Related` are required; `Related` lists the
+//! same lesson in the other languages, as paths from the repository root.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The names of the fields, in the order they are written.
+pub const FIELDS: [&str; 4] = ["Concepts", "Difficulty", "Minutes", "Related"];
+
+/// Anything longer should be split into two examples.
+const MAX_MINUTES: u32 = 120;
+
+/// An example's metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    /// `Variables and Mutability`, without the heading and number.
+    pub title: String,
+    pub concepts: Vec<String>,
+    pub difficulty: Difficulty,
+    /// About how long the example takes to work through.
+    pub minutes: u32,
+    /// Paths from the repository root, e.g. `go/examples/02_variables.go`.
+    pub related: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl Difficulty {
+    pub fn parse(text: &str) -> Option<Difficulty> {
+        match text {
+            "beginner" => Some(Difficulty::Beginner),
+            "intermediate" => Some(Difficulty::Intermediate),
+            "advanced" => Some(Difficulty::Advanced),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Advanced => "advanced",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Whether `line` is one of the fields, e.g. `// Minutes: 10`.
+pub fn is_field(line: &str) -> bool {
+    field(line).is_some()
+}
+
+/// `("Minutes", "10")` for `// Minutes: 10`.
+fn field(line: &str) -> Option<(&'static str, &str)> {
+    let (name, value) = line.trim_end().strip_prefix("// ")?.split_once(':')?;
+    let name = FIELDS.into_iter().find(|field| *field == name)?;
+    Some((name, value.trim()))
+}
+
+/// Reads the header at the top of `source`, or says everything that is
+/// wrong with it.
+pub fn parse_header(source: &str) -> Result<Header, Vec<String>> {
+    let lines: Vec<&str> = source
+        .lines()
+        .take_while(|line| line.starts_with("//"))
+        .collect();
+    let mut problems = Vec::new();
+
+    let title = match lines.first().and_then(|line| title(line)) {
+        Some(title) => title.to_string(),
+        None => {
+            problems.push(
+                "the first line should be `// <Heading> <N>: <Title>`, e.g. \
+                 `// Lifetimes 1: Lifetime Elision`"
+                    .to_string(),
+            );
+            String::new()
+        }
+    };
+
+    let mut values: [Option<&str>; FIELDS.len()] = [None; FIELDS.len()];
+    for (name, value) in lines.iter().filter_map(|line| field(line)) {
+        let slot = FIELDS.iter().position(|field| *field == name).unwrap();
+        if values[slot].is_some() {
+            problems.push(format!("`// {name}:` is given more than once"));
+        } else {
+            values[slot] = Some(value);
+        }
+    }
+    let [concepts, difficulty, minutes, related] = values;
+    let mut required = |name: &str, value: Option<&str>| match value {
+        Some("") => {
+            problems.push(format!("`// {name}:` is empty"));
+            None
+        }
+        Some(value) => Some(value.to_string()),
+        None => {
+            problems.push(format!("`// {name}:` is missing"));
+            None
+        }
+    };
+    let concepts = required("Concepts", concepts);
+    let difficulty = required("Difficulty", difficulty);
+    let minutes = required("Minutes", minutes);
+
+    let concepts = concepts.map(|value| list(&value)).unwrap_or_default();
+    if concepts.iter().any(String::is_empty) {
+        problems.push("`// Concepts:` has an empty entry".to_string());
+    }
+    let difficulty = difficulty.and_then(|value| {
+        let parsed = Difficulty::parse(&value);
+        if parsed.is_none() {
+            problems.push(format!(
+                "`// Difficulty: {value}` should be beginner, intermediate, or advanced"
+            ));
+        }
+        parsed
+    });
+    let minutes = minutes.and_then(|value| match value.parse() {
+        Ok(minutes @ 1..=MAX_MINUTES) => Some(minutes),
+        _ => {
+            problems.push(format!(
+                "`// Minutes: {value}` should be a whole number from 1 to {MAX_MINUTES}"
+            ));
+            None
+        }
+    });
+    if related == Some("") {
+        problems.push("`// Related:` is empty; leave it out instead".to_string());
+    }
+    let related: Vec<PathBuf> = related
+        .filter(|value| !value.is_empty())
+        .map(list)
+        .unwrap_or_default()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    for path in &related {
+        if !path
+            .extension()
+            .is_some_and(|extension| extension == "go" || extension == "java")
+        {
+            problems.push(format!(
+                "`// Related:` lists {}, which isn't a Go or Java file",
+                path.display()
+            ));
+        }
+    }
+
+    match (difficulty, minutes) {
+        (Some(difficulty), Some(minutes)) if problems.is_empty() => Ok(Header {
+            title,
+            concepts,
+            difficulty,
+            minutes,
+            related,
+        }),
+        _ => Err(problems),
+    }
+}
+
+/// [`parse_header`], and also checks that the related files exist in
+/// `repo`, the top of the repository.
+pub fn check_header(source: &str, repo: &Path) -> Result<Header, Vec<String>> {
+    let header = parse_header(source)?;
+    let missing: Vec<String> = header
+        .related
+        .iter()
+        .filter(|path| !repo.join(path).is_file())
+        .map(|path| {
+            format!(
+                "`// Related:` lists {}, which doesn't exist",
+                path.display()
+            )
+        })
+        .collect();
+    if missing.is_empty() {
+        Ok(header)
+    } else {
+        Err(missing)
+    }
+}
+
+/// `Variables and Mutability` in `// Example 2: Variables and Mutability`.
+fn title(line: &str) -> Option<&str> {
+    let (heading, title) = line.strip_prefix("// ")?.split_once(": ")?;
+    let (words, number) = heading.rsplit_once(' ')?;
+    let numbered = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+    let title = title.trim();
+    (numbered && !words.trim().is_empty() && !title.is_empty()).then_some(title)
+}
+
+/// `a, b, c` as `["a", "b", "c"]`.
+fn list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_every_field() {
+        let source = "\
+// Example 2: Variables and Mutability
+// Demonstrates immutable and mutable variables
+//
+// Concepts: let, mut, shadowing
+// Difficulty: beginner
+// Minutes: 10
+// Related: go/examples/02_variables.go, java/examples/Variables.java
+
+fn main() {}
+";
+        assert_eq!(
+            parse_header(source),
+            Ok(Header {
+                title: "Variables and Mutability".to_string(),
+                concepts: vec!["let".into(), "mut".into(), "shadowing".into()],
+                difficulty: Difficulty::Beginner,
+                minutes: 10,
+                related: vec![
+                    "go/examples/02_variables.go".into(),
+                    "java/examples/Variables.java".into(),
+                ],
+            })
+        );
+        assert!(is_field("// Related: go/examples/02_variables.go"));
+        assert!(!is_field("// Demonstrates immutable and mutable variables"));
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let source = "\
+// Variables and Mutability
+// Difficulty: expert
+// Minutes: ten
+// Minutes: 10
+// Concepts: let, , mut
+
+// Related: fields after the header don't count
+fn main() {}
+";
+        assert_eq!(
+            parse_header(source).unwrap_err(),
+            [
+                "the first line should be `// <Heading> <N>: <Title>`, e.g. \
+                 `// Lifetimes 1: Lifetime Elision`",
+                "`// Minutes:` is given more than once",
+                "`// Concepts:` has an empty entry",
+                "`// Difficulty: expert` should be beginner, intermediate, or advanced",
+                "`// Minutes: ten` should be a whole number from 1 to 120",
+            ]
+        );
+        assert_eq!(
+            parse_header("// Example 1: Hello\nfn main() {}\n").unwrap_err(),
+            [
+                "`// Concepts:` is missing",
+                "`// Difficulty:` is missing",
+                "`// Minutes:` is missing",
+            ]
+        );
+    }
+}
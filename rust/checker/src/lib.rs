@@ -0,0 +1,29 @@
+//! Keeps the examples honest as Rust evolves.
+//!
+//! Every example must compile, and every snippet of deliberately broken
+//! code in its comments must still fail the way the comment says:
+//!
+//! ```text
+//! // This would cause an error:
+//! // x = 6;
+//! // error[E0384]: cannot assign twice to immutable variable `x`
+//! ```
+//!
+//! For each such snippet the checker writes a copy of the example with
+//! those lines uncommented and asks `rustc` for its errors. The snippet
+//! passes if one of them has the documented code (`E0384`), or, for errors
+//! without a code (`// error: no rules expected ...`), the documented
+//! message.
+//!
+//! Every example must also start with a header of metadata (its concepts,
+//! difficulty, and so on) that tools can read; see [`parse_header`].
+
+mod compile;
+mod discover;
+mod header;
+mod snippet;
+
+pub use compile::{check_snippet, compile, summarize, Build, Diagnostic};
+pub use discover::{discover, Example};
+pub use header::{check_header, is_field, parse_header, Difficulty, Header, FIELDS};
+pub use snippet::{find_snippets, Expected, Snippet};
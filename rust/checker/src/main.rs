@@ -0,0 +1,118 @@
+//! `checker` compiles every example, then checks that each
+//! `// This would cause an error:` snippet in them still fails with the
+//! error its comment documents.
+//!
+//! ```text
+//! cargo run -p checker                     # every example
+//! cargo run -p checker -- 02_variables     # only the examples named (or starting with) this
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::Result;
+use clap::Parser;
+
+use checker::{check_snippet, compile, discover, find_snippets};
+
+#[derive(Parser)]
+#[command(
+    name = "checker",
+    version,
+    about = "Check that the examples compile and their broken snippets still fail"
+)]
+struct Cli {
+    /// Path to the `rust/` directory of this repository.
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Only check examples whose name starts with one of these.
+    examples: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    match try_main() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns `Ok(false)` when an example or snippet doesn't behave as documented.
+fn try_main() -> Result<bool> {
+    let cli = Cli::parse();
+    let root = cli
+        .root
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."));
+    let work_dir = root.join("target").join("checker");
+
+    let mut examples = discover(&root)?;
+    if !cli.examples.is_empty() {
+        examples.retain(|example| {
+            cli.examples
+                .iter()
+                .any(|prefix| example.name.starts_with(prefix.as_str()))
+        });
+    }
+
+    let (mut snippets_checked, mut problems) = (0, Vec::new());
+    for example in &examples {
+        let dir = work_dir.join(&example.name);
+        let build = match compile(example, &dir)? {
+            Ok(build) => build,
+            Err(diagnostics) => {
+                println!("❌ {} does not compile:\n{diagnostics}", example.name);
+                problems.push(example.name.clone());
+                continue;
+            }
+        };
+        let source = std::fs::read_to_string(&example.path)?;
+        let snippets = find_snippets(&source);
+        let mut wrong = Vec::new();
+        for snippet in &snippets {
+            let errors = check_snippet(example, &build, snippet, &dir)?;
+            snippets_checked += 1;
+            if !snippet.expected.is_met_by(&errors) {
+                wrong.push(format!(
+                    "line {}: expected {}, got {}",
+                    snippet.line,
+                    snippet.expected,
+                    checker::summarize(&errors)
+                ));
+            }
+        }
+        if wrong.is_empty() {
+            match snippets.len() {
+                0 => println!("✅ {}", example.name),
+                1 => println!("✅ {} (1 broken snippet fails as documented)", example.name),
+                n => println!(
+                    "✅ {} ({n} broken snippets fail as documented)",
+                    example.name
+                ),
+            }
+        } else {
+            println!("❌ {}", example.name);
+            for line in &wrong {
+                println!("     {line}");
+            }
+            problems.push(example.name.clone());
+        }
+    }
+
+    println!(
+        "\nChecked {} examples and {snippets_checked} broken snippets.",
+        examples.len()
+    );
+    if problems.is_empty() {
+        return Ok(true);
+    }
+    println!(
+        "{} examples need attention: {}",
+        problems.len(),
+        problems.join(", ")
+    );
+    Ok(false)
+}
@@ -0,0 +1,153 @@
+//! Finding the commented-out broken code in an example.
+
+use std::fmt;
+
+/// The line that introduces a snippet.
+const MARKER: &str = "// This would cause an error:";
+
+/// A commented-out snippet that is supposed not to compile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    /// The 1-based line of the `// This would cause an error:` comment.
+    pub line: usize,
+    /// The 0-based indexes of the commented-out code lines.
+    pub code: Vec<usize>,
+    pub expected: Expected,
+}
+
+/// What the comment after the snippet says the compiler will report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    /// `// error[E0382]: ...`
+    Code(String),
+    /// `// error: no rules expected ...`, for errors that have no code.
+    Message(String),
+    /// The comment doesn't name the error; any error will do.
+    AnyError,
+}
+
+/// Finds every snippet in `source`.
+///
+/// A snippet is the run of `//` lines after the marker. It ends at the
+/// `// error...` line documenting what goes wrong, or, if there isn't one,
+/// at the first line that isn't a comment.
+pub fn find_snippets(source: &str) -> Vec<Snippet> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut snippets = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim() != MARKER {
+            continue;
+        }
+        let mut code = Vec::new();
+        let mut expected = Expected::AnyError;
+        for (offset, line) in lines[index + 1..].iter().enumerate() {
+            let Some(comment) = line.trim().strip_prefix("//") else {
+                break;
+            };
+            let comment = comment.trim();
+            if let Some(rest) = comment.strip_prefix("error[") {
+                if let Some((error_code, _)) = rest.split_once(']') {
+                    expected = Expected::Code(error_code.to_string());
+                }
+                break;
+            }
+            if let Some(message) = comment.strip_prefix("error:") {
+                expected = Expected::Message(message.trim().to_string());
+                break;
+            }
+            code.push(index + 1 + offset);
+        }
+        if !code.is_empty() {
+            snippets.push(Snippet {
+                line: index + 1,
+                code,
+                expected,
+            });
+        }
+    }
+    snippets
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Code(code) => f.write_str(code),
+            Expected::Message(message) => write!(f, "`{message}`"),
+            Expected::AnyError => f.write_str("an error"),
+        }
+    }
+}
+
+impl Snippet {
+    /// `source` with this snippet's lines uncommented.
+    pub fn uncomment(&self, source: &str) -> String {
+        let mut out = String::with_capacity(source.len());
+        for (index, line) in source.lines().enumerate() {
+            if self.code.contains(&index) {
+                let start = line.find("//").unwrap_or(0);
+                let rest = &line[start + 2..];
+                out.push_str(&line[..start]);
+                out.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+            } else {
+                out.push_str(line);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+fn main() {
+    let x = 5;
+    // This would cause an error:
+    // x = 6;
+    // error[E0384]: cannot assign twice to immutable variable `x`
+
+    // This would cause an error:
+    // let v = my_vec3![1, 2];
+    // error: unexpected end of macro invocation
+    println!(\"{x}\");
+}
+
+// This would cause an error:
+// struct Cat;
+// impl Speak for Cat {}
+";
+
+    #[test]
+    fn finds_every_snippet() {
+        assert_eq!(
+            find_snippets(SOURCE),
+            vec![
+                Snippet {
+                    line: 3,
+                    code: vec![3],
+                    expected: Expected::Code("E0384".to_string()),
+                },
+                Snippet {
+                    line: 7,
+                    code: vec![7],
+                    expected: Expected::Message("unexpected end of macro invocation".to_string()),
+                },
+                Snippet {
+                    line: 13,
+                    code: vec![13, 14],
+                    expected: Expected::AnyError,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn uncomments_only_the_snippet() {
+        let snippet = &find_snippets(SOURCE)[2];
+        let fixed = snippet.uncomment(SOURCE);
+        assert!(fixed.contains("\nstruct Cat;\nimpl Speak for Cat {}\n"));
+        assert!(fixed.contains("    // x = 6;\n"));
+    }
+}
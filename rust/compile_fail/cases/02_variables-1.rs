@@ -0,0 +1,80 @@
+// Example 2: Variables and Mutability
+// Demonstrates immutable and mutable variables
+//
+// Concepts: let, mut, shadowing, constants
+// Difficulty: beginner
+// Minutes: 5
+// Related: go/examples/02_variables.go, java/examples/Variables.java
+
+fn main() {
+    // Immutable variable (default in Rust)
+    let x = 5;
+    println!("The value of x is: {}", x);
+    
+    // This would cause an error:
+    x = 6;  // Cannot assign twice to immutable variable
+    // error[E0384]: cannot assign twice to immutable variable `x`
+    
+    // Mutable variable (explicitly marked with 'mut')
+    let mut y = 10;
+    println!("The value of y is: {}", y);
+    
+    y = 15;  // This works because y is mutable
+    println!("The new value of y is: {}", y);
+    
+    // Shadowing - creating a new variable with the same name
+    let z = 5;
+    let z = z + 1;  // New variable, shadows the first
+    let z = z * 2;  // Another new variable
+    println!("The value of z is: {}", z);  // Prints 12
+    
+    // Shadowing allows changing types
+    let spaces = "   ";  // String
+    let spaces = spaces.len();  // Number
+    println!("Number of spaces: {}", spaces);
+}
+
+/*
+ * Key Concepts:
+ * - Variables are immutable by default (Rust's safety feature)
+ * - Use 'mut' to make a variable mutable
+ * - Shadowing creates new variables with the same name
+ * - Shadowing allows type changes, mutation doesn't
+ */
+
+//# `let x = 5;`
+//# `let` binds the value 5 to the name `x`. Rust infers the type (`i32`)
+//# from the value. Without `mut`, the binding is immutable: `x` will be 5
+//# for as long as it exists.
+//#
+//# `// x = 6;`
+//# Uncommenting this line is the classic first Rust error, E0384. The
+//# compiler refuses to change an immutable variable, so "who changed x?"
+//# is never a question you have to debug.
+//#
+//# `let mut y = 10;`
+//# `mut` opts in to change. Reading the declaration tells you right away
+//# that `y` can be reassigned later on.
+//#
+//# `y = 15;`
+//# Assignment without `let` changes the existing variable. It must keep
+//# the same type: `y = "fifteen";` would not compile.
+//#
+//# `let z = z + 1;`
+//# Shadowing: this `let` makes a brand new variable, also called `z`,
+//# from the old one. The old `z` is never modified; it is just hidden.
+//#
+//# `let spaces = spaces.len();`
+//# Because shadowing creates a new variable, the new `spaces` can have a
+//# different type (a number instead of a `&str`). With `mut` and
+//# assignment that would be a type error.
+
+// EXPECTED:
+// The value of x is: 5
+// The value of y is: 10
+// The new value of y is: 15
+// The value of z is: 12
+// Number of spaces: 3
+
+// Generated by `cargo xtask compile-fail` from examples/02_variables.rs, with the
+// snippet at line 14 uncommented.
@@ -0,0 +1,113 @@
+// Example 3: Ownership and Moves
+// Demonstrates how each value has exactly one owner, and what "moving" means
+//
+// Concepts: ownership, moves, scope, String
+// Difficulty: beginner
+// Minutes: 5
+
+fn main() {
+    // Simple values like integers are copied, not moved
+    let a = 5;
+    let b = a; // b gets its own copy of 5
+    println!("a = {}, b = {}", a, b); // Both still usable
+
+    // A String owns data on the heap, so assigning it MOVES ownership
+    let s1 = String::from("hello");
+    let s2 = s1; // s1's String now belongs to s2
+    println!("s2 = {}", s2);
+
+    // This would cause an error:
+    // println!("s1 = {}", s1);
+    // error[E0382]: borrow of moved value: `s1`
+    //   - "value moved here" points at `let s2 = s1;`
+    //   - "value borrowed here after move" points at the println!
+    // After the move, s1 is no longer valid. Only one owner at a time!
+
+    // Passing a value to a function also moves it
+    let name = String::from("Ferris");
+    take_ownership(name); // name is moved into the function
+    // This would cause an error:
+    // println!("{}", name);
+    // error[E0382]: borrow of moved value: `name`
+    // The function now owns the String, and drops it when it returns.
+
+    // Functions can give ownership back by returning a value
+    let greeting = give_ownership();
+    println!("Got back: {}", greeting);
+
+    // Ownership can go in and come back out again
+    let word = String::from("round");
+    let word = take_and_give_back(word); // Shadowing: a new `word` owns the String
+    println!("Took a trip and came back: {}", word);
+
+    // Scope decides WHEN a value is cleaned up
+    {
+        let temporary = String::from("I live in this block");
+        println!("{}", temporary);
+    } // temporary goes out of scope here - its memory is freed automatically
+    // This would cause an error:
+    println!("{}", temporary);
+    // error[E0425]: cannot find value `temporary` in this scope
+}
+
+fn take_ownership(text: String) {
+    println!("I own \"{}\" now", text);
+} // text goes out of scope and the String is dropped here
+
+fn give_ownership() -> String {
+    String::from("a brand new String") // Ownership moves out to the caller
+}
+
+fn take_and_give_back(text: String) -> String {
+    text // Returning moves ownership back
+}
+
+/*
+ * Key Concepts:
+ * - Every value has exactly ONE owner
+ * - When the owner goes out of scope, the value is dropped (memory freed)
+ * - Assigning a String or passing it to a function MOVES it
+ * - After a move, the old variable can't be used (error E0382)
+ * - Simple values like integers are copied instead of moved
+ */
+
+//# `let b = a;`
+//# Integers are `Copy`: assigning one copies its bits, so `a` and `b` are
+//# two independent values and both stay usable.
+//#
+//# `let s1 = String::from("hello");`
+//# A `String` keeps its text on the heap. `s1` is its owner, and when the
+//# owner goes away the heap memory is freed.
+//#
+//# `let s2 = s1;`
+//# This doesn't copy the text; it moves ownership to `s2`. If both
+//# variables owned the String, it would be freed twice, so Rust makes
+//# `s1` unusable from here on (error E0382 if you try).
+//#
+//# `take_ownership(name);`
+//# Passing a value to a function moves it too, exactly like assignment.
+//# The function's parameter `text` becomes the owner.
+//#
+//# `let greeting = give_ownership();`
+//# Returning a value moves it out of the function to the caller, so
+//# `greeting` now owns the String created inside `give_ownership`.
+//#
+//# `let word = take_and_give_back(word);`
+//# Handing a value to a function and getting it back works, but it's
+//# clumsy. The next lessons show the usual alternative: borrowing.
+//#
+//# `} // temporary goes out of scope here`
+//# At the closing brace `temporary` goes out of scope and Rust drops it,
+//# freeing its memory. There is no garbage collector and no `free` call:
+//# the end of the scope is the end of the value.
+
+// EXPECTED:
+// a = 5, b = 5
+// s2 = hello
+// I own "Ferris" now
+// Got back: a brand new String
+// Took a trip and came back: round
+// I live in this block
+
+// Generated by `cargo xtask compile-fail` from examples/03_ownership.rs, with the
+// snippet at line 48 uncommented.
@@ -0,0 +1,82 @@
+// Example 4: Clone and Copy
+// Demonstrates how to duplicate values instead of moving them
+//
+// Concepts: Clone, Copy, deep copies
+// Difficulty: beginner
+// Minutes: 5
+
+fn main() {
+    // .clone() makes a deep copy: a second String with its own heap data
+    let original = String::from("hello");
+    let copy = original.clone();
+    println!("original = {}, copy = {}", original, copy); // Both are valid
+
+    // The two Strings are completely independent
+    let mut edited = original.clone();
+    edited.push_str(", world");
+    println!("original = {}, edited = {}", original, edited);
+
+    // Types that implement Copy are duplicated automatically on assignment
+    let x: i32 = 42;
+    let y = x; // Copied - no .clone() needed
+    println!("x = {}, y = {}", x, y);
+
+    let point = (3, 4); // Tuples of Copy types are Copy too
+    let other_point = point;
+    println!("point = {:?}, other_point = {:?}", point, other_point);
+
+    // A Vec is NOT Copy, because it owns heap memory
+    let numbers = vec![1, 2, 3];
+    let moved_numbers = numbers;
+    // This would cause an error:
+    println!("{:?}", numbers);
+    // error[E0382]: borrow of moved value: `numbers`
+    // help: consider cloning the value if the performance cost is acceptable
+
+    let cloned_numbers = moved_numbers.clone();
+    println!("moved = {:?}, cloned = {:?}", moved_numbers, cloned_numbers);
+
+    // Your own types can opt in to Clone and Copy with derive
+    let a = Meters(10);
+    let b = a; // Copy, because Meters derives Copy
+    println!("a = {:?}, b = {:?}, total = {}", a, b, a.0 + b.0);
+
+    let label = Label { text: String::from("fragile") };
+    let label_copy = label.clone(); // Clone, but NOT Copy (it holds a String)
+    println!("label = {}, label_copy = {}", label.text, label_copy.text);
+}
+
+// Copy requires Clone; both are cheap for a single integer
+#[derive(Debug, Clone, Copy)]
+struct Meters(u32);
+
+// This would cause an error:
+// #[derive(Debug, Clone, Copy)]
+// struct Label { text: String }
+// error[E0204]: the trait `Copy` cannot be implemented for this type
+// A String can't be copied bit-for-bit, so neither can a struct holding one.
+#[derive(Debug, Clone)]
+struct Label {
+    text: String,
+}
+
+/*
+ * Key Concepts:
+ * - .clone() makes an explicit, independent deep copy
+ * - Copy types (integers, floats, bool, char, tuples of Copy types)
+ *   are duplicated implicitly instead of moved
+ * - Anything that owns heap memory (String, Vec) is not Copy
+ * - Cloning costs time and memory, so Rust makes you ask for it
+ */
+
+// EXPECTED:
+// original = hello, copy = hello
+// original = hello, edited = hello, world
+// x = 42, y = 42
+// point = (3, 4), other_point = (3, 4)
+// moved = [1, 2, 3], cloned = [1, 2, 3]
+// a = Meters(10), b = Meters(10), total = 20
+// label = fragile, label_copy = fragile
+
+// Generated by `cargo xtask compile-fail` from examples/04_cloning.rs, with the
+// snippet at line 31 uncommented.
@@ -0,0 +1,91 @@
+// Example 5: References, Borrowing, and Slices
+// Demonstrates using a value without taking ownership of it
+//
+// Concepts: references, borrowing, &T
+// Difficulty: beginner
+// Minutes: 5
+
+fn main() {
+    // &s creates a reference: it lets you look at s without owning it
+    let s = String::from("hello world");
+    let length = calculate_length(&s); // Borrow s
+    println!("'{}' has {} bytes", s, length); // s is still ours!
+
+    // You can have as many shared (read-only) borrows as you like
+    let r1 = &s;
+    let r2 = &s;
+    println!("r1 = {}, r2 = {}", r1, r2);
+
+    // Shared references are read-only
+    // This would cause an error:
+    // r1.push_str("!");
+    // error[E0596]: cannot borrow `*r1` as mutable, as it is behind a `&` reference
+
+    // A slice is a reference to PART of a collection
+    let hello = &s[0..5]; // bytes 0 up to (not including) 5
+    let world = &s[6..]; // from byte 6 to the end
+    println!("slices: '{}' and '{}'", hello, world);
+
+    // Functions that take &str work with whole Strings AND slices
+    println!("first word of s: {}", first_word(&s));
+    println!("first word of a literal: {}", first_word("borrow checker"));
+
+    // Array slices work the same way
+    let numbers = [10, 20, 30, 40, 50];
+    let middle = &numbers[1..4];
+    println!("middle = {:?}, sum = {}", middle, sum(middle));
+
+    // A slice keeps its owner borrowed for as long as the slice is used
+    let mut sentence = String::from("slices borrow");
+    let word = first_word(&sentence);
+    // This would cause an error:
+    sentence.clear();
+    // error[E0502]: cannot borrow `sentence` as mutable because it is also borrowed as immutable
+    // Clearing the String would leave `word` pointing at nothing.
+    println!("first word: {}", word);
+    sentence.clear(); // Fine here: `word` is never used again
+    println!("after clear: '{}'", sentence);
+}
+
+// &str would accept more than &String does; first_word below takes one
+#[allow(clippy::ptr_arg)]
+fn calculate_length(s: &String) -> usize {
+    s.len()
+} // s goes out of scope, but it doesn't own the String, so nothing is dropped
+
+fn first_word(s: &str) -> &str {
+    match s.find(' ') {
+        Some(index) => &s[..index],
+        None => s,
+    }
+}
+
+fn sum(values: &[i32]) -> i32 {
+    let mut total = 0;
+    for value in values {
+        total += value;
+    }
+    total
+}
+
+/*
+ * Key Concepts:
+ * - &value borrows without taking ownership; the owner keeps the value
+ * - Many shared (&) references can exist at the same time
+ * - Shared references can't be used to modify the value (E0596)
+ * - Slices (&str, &[T]) borrow a range of a String or array
+ * - While a borrow is in use, the owner can't be mutated (E0502)
+ */
+
+// EXPECTED:
+// 'hello world' has 11 bytes
+// r1 = hello world, r2 = hello world
+// slices: 'hello' and 'world'
+// first word of s: hello
+// first word of a literal: borrow
+// middle = [20, 30, 40], sum = 90
+// first word: slices
+// after clear: ''
+
+// Generated by `cargo xtask compile-fail` from examples/05_borrowing.rs, with the
+// snippet at line 41 uncommented.
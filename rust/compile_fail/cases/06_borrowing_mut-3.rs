@@ -0,0 +1,86 @@
+// Example 6: Mutable References
+// Demonstrates changing a value you don't own, and Rust's borrowing rules
+//
+// Concepts: &mut T, borrowing rules, aliasing
+// Difficulty: beginner
+// Minutes: 5
+
+fn main() {
+    // &mut lets a function change a value it doesn't own
+    let mut greeting = String::from("hello");
+    add_world(&mut greeting);
+    println!("greeting = {}", greeting);
+
+    // The value itself must be declared mut to be borrowed mutably
+    let fixed = String::from("can't touch this");
+    // This would cause an error:
+    // add_world(&mut fixed);
+    // error[E0596]: cannot borrow `fixed` as mutable, as it is not declared as mutable
+    println!("fixed = {}", fixed);
+
+    // Rule 1: only ONE mutable borrow at a time
+    let mut count = 0;
+    let first = &mut count;
+    // This would cause an error:
+    // let second = &mut count;
+    // error[E0499]: cannot borrow `count` as mutable more than once at a time
+    // (only if `first` is used after this line)
+    *first += 1; // * follows the reference to the value it points at
+    println!("count = {}", count);
+
+    // Rule 2: no mutable borrow while shared borrows are in use
+    let mut scores = vec![10, 20, 30];
+    let top = &scores[0];
+    // This would cause an error:
+    scores.push(40);
+    // error[E0502]: cannot borrow `scores` as mutable because it is also borrowed as immutable
+    // push might move the Vec's data elsewhere, leaving `top` dangling!
+    println!("top score = {}", top);
+    scores.push(40); // Fine: `top` is no longer used (its borrow has ended)
+    println!("scores = {:?}", scores);
+
+    // Borrows end after their last use, so these are fine one after another
+    let mut text = String::from("step");
+    let r1 = &text;
+    println!("shared: {}", r1);
+    let r2 = &mut text; // OK: r1 is done
+    r2.push_str(" two");
+    println!("mutable: {}", r2);
+
+    // Mutable slices let you change part of a collection in place
+    let mut numbers = [1, 2, 3, 4, 5];
+    double_all(&mut numbers[2..]);
+    println!("numbers = {:?}", numbers);
+}
+
+fn add_world(text: &mut String) {
+    text.push_str(", world");
+}
+
+fn double_all(values: &mut [i32]) {
+    for value in values.iter_mut() {
+        *value *= 2;
+    }
+}
+
+/*
+ * Key Concepts:
+ * - &mut T is an exclusive borrow that allows changes
+ * - You can have EITHER one &mut OR any number of & - never both at once
+ * - The borrowed variable itself must be declared `mut`
+ * - A borrow lasts until its last use, not until the end of the block
+ * - These rules prevent data races and dangling references at compile time
+ */
+
+// EXPECTED:
+// greeting = hello, world
+// fixed = can't touch this
+// count = 1
+// top score = 10
+// scores = [10, 20, 30, 40]
+// shared: step
+// mutable: step two
+// numbers = [1, 2, 6, 8, 10]
+
+// Generated by `cargo xtask compile-fail` from examples/06_borrowing_mut.rs, with the
+// snippet at line 34 uncommented.
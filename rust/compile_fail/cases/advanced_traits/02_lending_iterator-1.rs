@@ -0,0 +1,251 @@
+// Advanced Traits 2: Generic Associated Types
+// Demonstrates associated types with their own parameters, with a lending iterator that Iterator can't express
+//
+// Concepts: generic associated types (GATs), lending iterators, where Self: 'a, type families
+// Difficulty: advanced
+// Minutes: 20
+//
+// An Iterator's items can't borrow from the iterator. Look at the
+// signature: `fn next(&mut self) -> Option<Self::Item>`. The `&mut self`
+// borrow ends when next() returns, and `Item` is one type for every call,
+// so it can't mention that borrow. That's fine when the items live
+// somewhere else, like a Vec that `iter()` borrows, but it rules out:
+// - overlapping mutable windows into a slice: std has `windows` for
+//   shared ones, and no `windows_mut`, because two windows from that
+//   iterator would alias if both were kept
+// - reading lines into one String that is reused, rather than a new
+//   String per line
+//
+// A generic associated type fixes the signature. `type Item<'a>` is a
+// different type for each lifetime, so next() can return a `Self::Item`
+// tied to the `&'a mut self` it was called with:
+//
+//     fn next(&mut self) -> Option<Self::Item<'_>>;
+//
+// The catch is what "lending" means: an item has to be dropped before
+// next() can be called again, so there's no collect(), and no `for`
+// loop, which wants an Iterator. That's why std's Iterator doesn't work
+// this way, and why these are separate traits.
+//
+// GATs can take type parameters too: the last section uses one to let
+// a struct pick between Rc and Arc.
+
+use std::io::{self, BufRead};
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+
+// ===== A lending iterator =====
+
+trait LendingIterator {
+    /// The item, borrowed from the iterator for `'a`. `Self: 'a` says
+    /// that the iterator outlives the borrow, which any borrow of it must.
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+/// Every `size`-long window of a slice, in turn, mutably.
+struct WindowsMut<'s, T> {
+    slice: &'s mut [T],
+    start: usize,
+    size: usize,
+}
+
+fn windows_mut<T>(slice: &mut [T], size: usize) -> WindowsMut<'_, T> {
+    assert!(size > 0, "a window holds at least one value");
+    WindowsMut {
+        slice,
+        start: 0,
+        size,
+    }
+}
+
+impl<'s, T> LendingIterator for WindowsMut<'s, T> {
+    type Item<'a>
+        = &'a mut [T]
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<&mut [T]> {
+        let window = self.slice.get_mut(self.start..self.start + self.size)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+/// The lines of a reader, each read into the same String, so that no
+/// line after the longest one allocates.
+struct ReusedLines<R> {
+    reader: R,
+    line: String,
+}
+
+impl<R: BufRead> ReusedLines<R> {
+    fn new(reader: R) -> Self {
+        ReusedLines {
+            reader,
+            line: String::new(),
+        }
+    }
+}
+
+impl<R: BufRead> LendingIterator for ReusedLines<R> {
+    type Item<'a>
+        = io::Result<&'a str>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<io::Result<&str>> {
+        self.line.clear();
+        match self.reader.read_line(&mut self.line) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(self.line.trim_end_matches('\n'))),
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+// ===== A type family =====
+
+/// Picks a kind of shared pointer, for any T: the GAT takes a type
+/// rather than a lifetime.
+trait PointerFamily {
+    type Pointer<T>: Deref<Target = T> + Clone;
+
+    fn new<T>(value: T) -> Self::Pointer<T>;
+}
+
+struct RcFamily;
+struct ArcFamily;
+
+impl PointerFamily for RcFamily {
+    type Pointer<T> = Rc<T>;
+
+    fn new<T>(value: T) -> Rc<T> {
+        Rc::new(value)
+    }
+}
+
+impl PointerFamily for ArcFamily {
+    type Pointer<T> = Arc<T>;
+
+    fn new<T>(value: T) -> Arc<T> {
+        Arc::new(value)
+    }
+}
+
+/// Tags shared by many documents. Single-threaded code picks Rc, and
+/// pays no atomic counting; code that shares across threads picks Arc.
+/// Without a GAT, the struct would need a parameter per pointer type it
+/// holds: `Pointer<String>` and `Pointer<Vec<u32>>` here.
+struct TagSet<P: PointerFamily> {
+    names: Vec<P::Pointer<String>>,
+    ids: P::Pointer<Vec<u32>>,
+}
+
+impl<P: PointerFamily> TagSet<P> {
+    fn new(names: &[&str]) -> Self {
+        TagSet {
+            names: names.iter().map(|name| P::new(name.to_string())).collect(),
+            ids: P::new((1..=names.len() as u32).collect()),
+        }
+    }
+
+    /// A cheap copy: every pointer is cloned, none of what they point to
+    fn share(&self) -> Self {
+        TagSet {
+            names: self.names.clone(),
+            ids: P::Pointer::clone(&self.ids),
+        }
+    }
+
+    fn describe(&self) -> String {
+        let names: Vec<&str> = self.names.iter().map(|name| name.as_str()).collect();
+        format!("{names:?} with ids {:?}", *self.ids)
+    }
+}
+
+fn main() {
+    println!("-- windows_mut --");
+    // A running smoothing filter: each window pulls its last value
+    // towards the one before, in place
+    let mut samples = [10, 0, 20, 0, 30];
+    let mut windows = windows_mut(&mut samples, 2);
+    while let Some(window) = windows.next() {
+        window[1] = (window[0] + window[1]) / 2;
+    }
+    println!("smoothed: {samples:?}");
+
+    let mut letters = ['a', 'b', 'c', 'd'];
+    let mut windows = windows_mut(&mut letters, 3);
+    while let Some(window) = windows.next() {
+        window.reverse();
+        println!("reversed a window: {}", window.iter().collect::<String>());
+    }
+    println!("letters: {letters:?}");
+
+    println!("-- reused lines --");
+    let input = "first\nsecond line\nthird\n";
+    let mut lines = ReusedLines::new(input.as_bytes());
+    let mut longest = String::new();
+    while let Some(line) = lines.next() {
+        let line = line.expect("reading from a byte slice can't fail");
+        if line.len() > longest.len() {
+            // The line is gone on the next call; keep a copy of it
+            longest = line.to_string();
+        }
+        println!("{line:?}");
+    }
+    println!("longest: {longest:?}");
+
+    println!("-- pointer families --");
+    let local: TagSet<RcFamily> = TagSet::new(&["rust", "go", "java"]);
+    let copy = local.share();
+    println!(
+        "Rc:  {}, shared by {}",
+        copy.describe(),
+        Rc::strong_count(&local.ids)
+    );
+    let threaded: TagSet<ArcFamily> = TagSet::new(&["async"]);
+    let handle = {
+        let copy = threaded.share();
+        std::thread::spawn(move || copy.describe())
+    };
+    println!("Arc: {}, from another thread", handle.join().unwrap());
+
+    // This would cause an error:
+    let mut windows = windows_mut(&mut samples, 2);
+    let first = windows.next().unwrap();
+    let second = windows.next().unwrap();
+    first[1] = second[0];
+    // error[E0499]: cannot borrow `windows` as mutable more than once at a time
+}
+
+/*
+ * Key Concepts:
+ * - Iterator::Item is one type for every call, so items can't borrow the iterator
+ * - type Item<'a> where Self: 'a lets each next() return a borrow of self
+ * - An item must be dropped before the next call: no collect(), no for loop
+ * - GATs can take types too: type Pointer<T> picks Rc or Arc for any T
+ */
+
+// EXPECTED:
+// -- windows_mut --
+// smoothed: [10, 5, 12, 6, 18]
+// reversed a window: cba
+// reversed a window: dab
+// letters: ['c', 'd', 'a', 'b']
+// -- reused lines --
+// "first"
+// "second line"
+// "third"
+// longest: "second line"
+// -- pointer families --
+// Rc:  ["rust", "go", "java"] with ids [1, 2, 3], shared by 2
+// Arc: ["async"] with ids [1], from another thread
+
+// Generated by `cargo xtask compile-fail` from examples/advanced_traits/02_lending_iterator.rs, with the
+// snippet at line 219 uncommented.
@@ -0,0 +1,231 @@
+// Advanced Traits 3: Supertraits
+// Demonstrates traits that require other traits, and what that does and doesn't give you
+//
+// Concepts: supertraits, default methods over supertraits, trait upcasting, bounds that imply other bounds
+// Difficulty: advanced
+// Minutes: 15
+//
+// `trait Check: Describe` says that every type which implements Check
+// must implement Describe as well. Two things follow:
+// - inside Check, its default methods can call Describe's methods on
+//   `self`, because any Self that gets here has them
+// - anywhere code has a `T: Check`, or a `dyn Check`, it can call
+//   Describe's methods too, without asking for them separately
+//
+// It looks like inheritance, and it isn't. Check gets no fields and no
+// code from Describe, and an `impl Check` can't override describe(): the
+// two impls are written separately, and a type that forgets Describe
+// doesn't compile. Java's `interface Check extends Describe` is the
+// closest thing; Go gets the same effect by embedding one interface in
+// another.
+//
+// Since Rust 1.86 a `&dyn Check` also converts to a `&dyn Describe`,
+// which is called trait upcasting.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+
+// ===== A chain of supertraits =====
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Ok,
+    Failing,
+}
+
+/// A health check. Every check can describe itself.
+trait Check: Describe {
+    fn run(&self) -> Status;
+
+    /// A default method that uses the supertrait
+    fn report(&self) -> String {
+        format!("{}: {:?}", self.describe(), self.run())
+    }
+}
+
+/// A check that is worth trying more than once before giving up.
+trait Retry: Check {
+    fn attempts(&self) -> u32;
+
+    /// Both supertraits' methods are here: run() from Check, describe()
+    /// from Describe, which Check requires
+    fn run_with_retries(&self) -> (Status, u32) {
+        for attempt in 1..=self.attempts() {
+            if self.run() == Status::Ok {
+                return (Status::Ok, attempt);
+            }
+            println!("  {} failed attempt {attempt}", self.describe());
+        }
+        (Status::Failing, self.attempts())
+    }
+}
+
+struct DiskSpace {
+    mount: &'static str,
+    free_percent: u32,
+}
+
+impl Describe for DiskSpace {
+    fn describe(&self) -> String {
+        format!("disk {}", self.mount)
+    }
+}
+
+impl Check for DiskSpace {
+    fn run(&self) -> Status {
+        if self.free_percent >= 10 {
+            Status::Ok
+        } else {
+            Status::Failing
+        }
+    }
+}
+
+/// A service that answers after a few tries, as one that is starting up
+/// would.
+struct Http {
+    url: &'static str,
+    fails_before_ok: Cell<u32>,
+}
+
+impl Describe for Http {
+    fn describe(&self) -> String {
+        format!("GET {}", self.url)
+    }
+}
+
+impl Check for Http {
+    fn run(&self) -> Status {
+        match self.fails_before_ok.get() {
+            0 => Status::Ok,
+            left => {
+                self.fails_before_ok.set(left - 1);
+                Status::Failing
+            }
+        }
+    }
+}
+
+impl Retry for Http {
+    fn attempts(&self) -> u32 {
+        3
+    }
+}
+
+/// Takes anything that can describe itself, checks included.
+fn log(item: &dyn Describe) {
+    println!("log: {}", item.describe());
+}
+
+// ===== Supertraits as a bundle of bounds =====
+
+/// What a key in a tally must do. Any type that has all four traits can
+/// be a Key, so a generic function needs one bound instead of four.
+trait Key: Ord + Clone + fmt::Display {}
+
+impl Key for &str {}
+impl Key for u16 {}
+
+fn tally<K: Key>(keys: &[K]) -> BTreeMap<K, usize> {
+    let mut counts = BTreeMap::new();
+    for key in keys {
+        *counts.entry(key.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Uses Display, which Key brings along.
+fn show<K: Key>(counts: &BTreeMap<K, usize>) -> String {
+    let parts: Vec<String> = counts.iter().map(|(key, n)| format!("{key}={n}")).collect();
+    parts.join(" ")
+}
+
+fn main() {
+    println!("-- checks --");
+    let checks: Vec<Box<dyn Check>> = vec![
+        Box::new(DiskSpace {
+            mount: "/",
+            free_percent: 42,
+        }),
+        Box::new(DiskSpace {
+            mount: "/var",
+            free_percent: 3,
+        }),
+        Box::new(Http {
+            url: "/health",
+            fails_before_ok: Cell::new(1),
+        }),
+    ];
+    for check in &checks {
+        // report() is Check's; it calls describe() through the supertrait
+        println!("{}", check.report());
+    }
+    // Upcasting: a &dyn Check is a &dyn Describe too
+    log(checks[0].as_ref());
+
+    println!("-- retries --");
+    let starting = Http {
+        url: "/ready",
+        fails_before_ok: Cell::new(2),
+    };
+    let (status, attempts) = starting.run_with_retries();
+    println!(
+        "{}: {status:?} after {attempts} attempts",
+        starting.describe()
+    );
+    let down = Http {
+        url: "/down",
+        fails_before_ok: Cell::new(u32::MAX),
+    };
+    println!("{:?}", down.run_with_retries());
+
+    println!("-- bundled bounds --");
+    let languages = ["rust", "go", "rust", "java", "rust", "go"];
+    println!("{}", show(&tally(&languages)));
+    let ports: [u16; 4] = [443, 80, 443, 8080];
+    println!("{}", show(&tally(&ports)));
+
+    // This would cause an error:
+    struct Ping;
+    impl Check for Ping {
+        fn run(&self) -> Status {
+            Status::Ok
+        }
+    }
+    // error[E0277]: the trait bound `Ping: Describe` is not satisfied
+}
+
+/*
+ * Key Concepts:
+ * - trait Check: Describe means every Check must also implement Describe
+ * - Default methods and generic code can use the supertrait's methods
+ * - It is not inheritance: no fields, no overriding, two separate impls
+ * - &dyn Check upcasts to &dyn Describe (Rust 1.86 and later)
+ * - A trait with supertraits and no methods bundles bounds under one name
+ */
+
+// EXPECTED:
+// -- checks --
+// disk /: Ok
+// disk /var: Failing
+// GET /health: Failing
+// log: disk /
+// -- retries --
+//   GET /ready failed attempt 1
+//   GET /ready failed attempt 2
+// GET /ready: Ok after 3 attempts
+//   GET /down failed attempt 1
+//   GET /down failed attempt 2
+//   GET /down failed attempt 3
+// (Failing, 3)
+// -- bundled bounds --
+// go=2 java=1 rust=3
+// 80=1 443=2 8080=1
+
+// Generated by `cargo xtask compile-fail` from examples/advanced_traits/03_supertraits.rs, with the
+// snippet at line 193 uncommented.
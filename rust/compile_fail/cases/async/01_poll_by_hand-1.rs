@@ -0,0 +1,126 @@
+// Async 1: Polling a Future by Hand
+// Demonstrates what the Future trait is and what `.await` asks of it
+//
+// Concepts: Future, poll, Poll, Context, .await
+// Difficulty: advanced
+// Minutes: 10
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+// A future is a value that can be asked "are you done yet?" via poll().
+// It answers Poll::Ready(value) or Poll::Pending ("ask me again later").
+//
+// This one needs to be polled a few times before it finishes.
+struct Countdown {
+    remaining: u32,
+}
+
+impl Future for Countdown {
+    type Output = &'static str;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.remaining == 0 {
+            return Poll::Ready("liftoff!");
+        }
+        println!("  poll: {} to go, returning Pending", self.remaining);
+        self.remaining -= 1;
+        // A future that returns Pending MUST arrange for the waker to be
+        // called when it can make progress; otherwise nobody polls it again.
+        // We can always make progress, so we wake ourselves immediately.
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+// A Waker is how a future says "poll me again". This one just counts.
+struct CountingWaker {
+    wakes: std::sync::atomic::AtomicU32,
+}
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.wakes.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+// An `async fn` is compiled into a state machine that implements Future.
+// Every `.await` is a point where it may return Pending.
+async fn launch() -> String {
+    let message = Countdown { remaining: 2 }.await;
+    format!("the rocket says {}", message)
+}
+
+fn main() {
+    let waker_state = Arc::new(CountingWaker {
+        wakes: std::sync::atomic::AtomicU32::new(0),
+    });
+    let waker = Waker::from(Arc::clone(&waker_state));
+    let mut cx = Context::from_waker(&waker);
+
+    // Futures do NOTHING until polled. Creating one runs no code at all.
+    let mut countdown = Countdown { remaining: 3 };
+    println!("Polling a Countdown by hand:");
+    loop {
+        // poll() takes Pin<&mut Self>: the future promises not to move in
+        // memory once polling starts. Pin::new works for types that don't
+        // care about moving (they are `Unpin`), like Countdown.
+        match Pin::new(&mut countdown).poll(&mut cx) {
+            Poll::Ready(value) => {
+                println!("  poll: Ready({:?})", value);
+                break;
+            }
+            Poll::Pending => continue,
+        }
+    }
+
+    // async fn futures hold references into themselves across .await, so
+    // they are NOT Unpin. Box::pin puts them somewhere they can't move.
+    println!("Polling an async fn by hand:");
+    let mut future = Box::pin(launch());
+    let result = loop {
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            break value;
+        }
+    };
+    println!("  {}", result);
+    // This would cause an error:
+    let mut future = launch();
+    let _ = Pin::new(&mut future).poll(&mut cx);
+    // error[E0277]: `{async fn body of launch()}` cannot be unpinned
+
+    println!(
+        "The futures asked to be woken {} times.",
+        waker_state.wakes.load(std::sync::atomic::Ordering::SeqCst)
+    );
+}
+
+/*
+ * Key Concepts:
+ * - A Future is polled; it returns Poll::Ready(value) or Poll::Pending
+ * - Futures are lazy: nothing happens until something polls them
+ * - Returning Pending means promising to call the Waker later
+ * - async fn compiles to a state machine implementing Future
+ * - Pin guarantees a future won't move while it is being polled
+ */
+
+// EXPECTED:
+// Polling a Countdown by hand:
+//   poll: 3 to go, returning Pending
+//   poll: 2 to go, returning Pending
+//   poll: 1 to go, returning Pending
+//   poll: Ready("liftoff!")
+// Polling an async fn by hand:
+//   poll: 2 to go, returning Pending
+//   poll: 1 to go, returning Pending
+//   the rocket says liftoff!
+// The futures asked to be woken 5 times.
+
+// Generated by `cargo xtask compile-fail` from examples/async/01_poll_by_hand.rs, with the
+// snippet at line 93 uncommented.
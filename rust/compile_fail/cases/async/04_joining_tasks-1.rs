@@ -0,0 +1,99 @@
+// Async 4: Spawning and Joining Tasks
+// Demonstrates tokio::spawn, JoinHandle, JoinSet, and spawn_blocking
+//
+// Concepts: tokio::spawn, JoinHandle, JoinSet, spawn_blocking
+// Difficulty: advanced
+// Minutes: 10
+
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinSet;
+use tokio::time;
+
+async fn download(id: u64) -> usize {
+    time::sleep(Duration::from_millis(50 * id)).await;
+    (id as usize) * 1024
+}
+
+#[tokio::main]
+async fn main() {
+    let start = Instant::now();
+
+    // tokio::spawn is the async version of thread::spawn: it starts a task
+    // that runs in the background, even if we never await the handle.
+    // Tasks are far cheaper than threads; spawning thousands is normal.
+    let handles: Vec<_> = (1..=4).map(|id| tokio::spawn(download(id))).collect();
+
+    let mut total = 0;
+    for handle in handles {
+        // Awaiting a JoinHandle gives Result<T, JoinError>; Err means the
+        // task panicked or was cancelled
+        total += handle.await.unwrap();
+    }
+    println!(
+        "[{:>4} ms] downloaded {} bytes from 4 tasks",
+        start.elapsed().as_millis(),
+        total
+    );
+
+    // Spawned tasks must own their data, just like threads
+    let name = String::from("report.txt");
+    let handle = tokio::spawn(async move { format!("saved {}", name) });
+    println!("{}", handle.await.unwrap());
+    // This would cause an error:
+    let name = String::from("report.txt");
+    tokio::spawn(async { println!("{}", name) });
+    // error[E0373]: async block may outlive the current function, but it borrows `name`
+
+    // JoinSet collects results in the order tasks FINISH
+    let start = Instant::now();
+    let mut set = JoinSet::new();
+    for id in [3, 1, 2] {
+        set.spawn(async move {
+            download(id).await;
+            id
+        });
+    }
+    while let Some(result) = set.join_next().await {
+        println!(
+            "[{:>4} ms] task {} finished",
+            start.elapsed().as_millis(),
+            result.unwrap()
+        );
+    }
+
+    // CPU-heavy or blocking work belongs on spawn_blocking, which runs it
+    // on a separate thread pool so async tasks keep running meanwhile
+    let start = Instant::now();
+    let heavy = tokio::task::spawn_blocking(|| (1..=5_000_000u64).sum::<u64>());
+    let light = tokio::spawn(async {
+        time::sleep(Duration::from_millis(10)).await;
+        "light task still ran"
+    });
+    println!("{}", light.await.unwrap());
+    println!(
+        "[{:>4} ms] blocking sum = {}",
+        start.elapsed().as_millis(),
+        heavy.await.unwrap()
+    );
+
+    // A task can be cancelled with abort()
+    let forever = tokio::spawn(async { time::sleep(Duration::from_secs(3600)).await });
+    forever.abort();
+    println!(
+        "aborted task is_cancelled = {}",
+        forever.await.unwrap_err().is_cancelled()
+    );
+}
+
+/*
+ * Key Concepts:
+ * - tokio::spawn runs a future as an independent background task
+ * - Awaiting a JoinHandle returns the task's result
+ * - JoinSet yields results as tasks complete
+ * - spawn_blocking keeps slow synchronous work off the async threads
+ * - abort() cancels a task at its next .await
+ */
+
+// Generated by `cargo xtask compile-fail` from examples/async/04_joining_tasks.rs, with the
+// snippet at line 43 uncommented.
@@ -0,0 +1,173 @@
+// Bits and Bytes 1: Integer Overflow
+// Demonstrates what happens when arithmetic doesn't fit, and how to say what you want instead
+//
+// Concepts: overflow checks in debug and release, checked_*, wrapping_*, saturating_*, overflowing_*, Wrapping<T>, as casts
+// Difficulty: intermediate
+// Minutes: 15
+//
+// A u8 holds 0 to 255, so what is `255_u8 + 1`? Rust's answer depends on
+// how the program was built:
+// - in a debug build (`cargo build`, `cargo run`, or plain `rustc`),
+//   overflow checks are on, and the addition panics with "attempt to add
+//   with overflow"
+// - in a release build (`cargo build --release`, or `rustc -O`) they are
+//   off, and the result wraps around to 0, the way the hardware does it
+//
+// Either way it's a bug, never undefined behavior as in C. The checks
+// are off in release only because they cost time; a project that wants
+// them there anyway sets `overflow-checks = true` under
+// `[profile.release]` in Cargo.toml.
+//
+// When overflow is something your code expects, don't rely on either
+// build's behavior: pick one with a method, and it's the same in both.
+// - checked_add returns None when the result doesn't fit
+// - wrapping_add wraps around, on purpose
+// - saturating_add stops at the largest (or smallest) value
+// - overflowing_add returns the wrapped result and whether it wrapped
+// There's one of each for sub, mul, div, neg, abs, shl, and so on.
+//
+// Go and Java always wrap, silently, in every build: Java's
+// `Integer.MAX_VALUE + 1` is `Integer.MIN_VALUE`. Java has the checked
+// kind as `Math.addExact`, which throws.
+
+use std::hint::black_box;
+use std::num::Wrapping;
+use std::panic;
+
+// ===== Debug and release =====
+
+/// Adds one, in whatever way this build does it. black_box hides the
+/// value from the compiler, which would otherwise see the overflow coming
+/// and refuse to compile `u8::MAX + 1`.
+fn add_one(value: u8) -> u8 {
+    black_box(value) + 1
+}
+
+// ===== Picking a behavior =====
+
+/// The size in bytes of a width x height image at 4 bytes a pixel, or
+/// None if it overflows. The numbers come from a file, so they can be
+/// anything, and a wrapped size would allocate a buffer that is too small.
+fn image_size(width: u32, height: u32) -> Option<u32> {
+    width.checked_mul(height)?.checked_mul(4)
+}
+
+/// A volume control: turning it past either end leaves it at the end.
+fn adjust_volume(volume: u8, change: i8) -> u8 {
+    volume.saturating_add_signed(change)
+}
+
+/// The 32-bit FNV-1a hash. Hashes multiply and are meant to wrap, so
+/// Wrapping<u32> makes every operator on it wrap, in every build, without
+/// a wrapping_mul at each step.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    let mut hash = Wrapping(0x811c_9dc5_u32);
+    for &byte in bytes {
+        hash ^= u32::from(byte);
+        hash *= 0x0100_0193;
+    }
+    hash.0
+}
+
+fn main() {
+    println!("-- debug and release --");
+    // A panic prints its message to stderr; keep this demo's output tidy
+    panic::set_hook(Box::new(|_| {}));
+    match panic::catch_unwind(|| add_one(255)) {
+        Ok(sum) => println!("255 + 1 = {sum}: overflow checks are off (a release build)"),
+        Err(_) => println!("255 + 1 panicked: overflow checks are on (a debug build)"),
+    }
+    let _ = panic::take_hook();
+
+    println!("-- picking a behavior --");
+    let level: u8 = 250;
+    println!("checked:     {:?}", level.checked_add(10));
+    println!("wrapping:    {}", level.wrapping_add(10));
+    println!("saturating:  {}", level.saturating_add(10));
+    println!("overflowing: {:?}", level.overflowing_add(10));
+    println!(
+        "3 - 5 as u32: {:?}, or {}",
+        3_u32.checked_sub(5),
+        3_u32.saturating_sub(5)
+    );
+    // The smallest i8 has no positive counterpart: -(-128) is 128, which
+    // doesn't fit
+    println!(
+        "abs(-128_i8): {:?}, or {}",
+        i8::MIN.checked_abs(),
+        i8::MIN.wrapping_abs()
+    );
+    // Dividing by zero, and MIN / -1, panic in release too
+    println!("i32::MIN / -1: {:?}", i32::MIN.checked_div(-1));
+    println!("7 / 0: {:?}", 7_i32.checked_div(0));
+
+    println!("-- in practice --");
+    println!("1920x1080 image: {:?} bytes", image_size(1920, 1080));
+    println!("70000x70000 image: {:?} bytes", image_size(70_000, 70_000));
+    println!(
+        "volume 250 + 10 = {}, 5 - 10 = {}",
+        adjust_volume(250, 10),
+        adjust_volume(5, -10)
+    );
+    println!("fnv1a(\"hello\") = {:#010x}", fnv1a(b"hello"));
+    println!("fnv1a(\"\") = {:#010x}", fnv1a(b""));
+
+    println!("-- as casts --");
+    // `as` never fails. Between integers it keeps the low bits, which
+    // also reinterprets the sign; from floats it rounds toward zero and
+    // saturates, with NaN as 0
+    println!("300 as u8 = {}", 300_i32 as u8);
+    println!("-1 as u32 = {}", -1_i32 as u32);
+    println!("200 as i8 = {}", 200_u8 as i8);
+    println!(
+        "3.99 as u8 = {}, -5.0 as u8 = {}, 1e10 as i32 = {}",
+        3.99_f64 as u8, -5.0_f64 as u8, 1e10_f64 as i32
+    );
+    println!("NaN as i32 = {}", black_box(f64::NAN) as i32);
+    // TryFrom is the conversion that says when a value doesn't fit
+    println!("u8::try_from(300): {:?}", u8::try_from(300_i32));
+    println!("u8::try_from(200): {:?}", u8::try_from(200_i32));
+
+    // This would cause an error:
+    const LIMIT: u8 = 200 + 100;
+    // error[E0080]: attempt to compute `200_u8 + 100_u8`, which would overflow
+}
+
+/*
+ * Key Concepts:
+ * - Overflow panics in debug builds and wraps in release; it's a bug in both
+ * - checked_*, wrapping_*, saturating_*, and overflowing_* behave the same in every build
+ * - Wrapping<T> makes every operator wrap, for hashes and checksums
+ * - Division by zero and MIN / -1 panic in release too
+ * - as truncates integers and saturates floats; TryFrom reports values that don't fit
+ */
+
+// EXPECTED:
+// -- debug and release --
+// 255 + 1 panicked: overflow checks are on (a debug build)
+// -- picking a behavior --
+// checked:     None
+// wrapping:    4
+// saturating:  255
+// overflowing: (4, true)
+// 3 - 5 as u32: None, or 0
+// abs(-128_i8): None, or -128
+// i32::MIN / -1: None
+// 7 / 0: None
+// -- in practice --
+// 1920x1080 image: Some(8294400) bytes
+// 70000x70000 image: None bytes
+// volume 250 + 10 = 255, 5 - 10 = 0
+// fnv1a("hello") = 0x4f9f2cab
+// fnv1a("") = 0x811c9dc5
+// -- as casts --
+// 300 as u8 = 44
+// -1 as u32 = 4294967295
+// 200 as i8 = -56
+// 3.99 as u8 = 3, -5.0 as u8 = 0, 1e10 as i32 = 2147483647
+// NaN as i32 = 0
+// u8::try_from(300): Err(TryFromIntError(()))
+// u8::try_from(200): Ok(200)
+
+// Generated by `cargo xtask compile-fail` from examples/bits/01_overflow.rs, with the
+// snippet at line 131 uncommented.
@@ -0,0 +1,183 @@
+// Bits and Bytes 3: Byte Order
+// Demonstrates turning integers into bytes and back, and why the order of the bytes has to be agreed on
+//
+// Concepts: big-endian and little-endian, to_be_bytes/from_le_bytes, native endianness, swap_bytes, reading integers out of a byte slice
+// Difficulty: intermediate
+// Minutes: 15
+//
+// 0x12345678 is four bytes, but which one comes first? There are two
+// answers in common use:
+// - big-endian puts the most significant byte first: 12 34 56 78, the
+//   way the number is written. Network protocols use it, which is why
+//   it's also called network byte order.
+// - little-endian puts the least significant byte first: 78 56 34 12.
+//   x86 and ARM CPUs keep integers in memory this way, and so do many
+//   file formats, like ZIP, WAV, and BMP.
+//
+// Inside one program it doesn't matter. It matters the moment bytes
+// leave: a file or a packet is just bytes, so the format has to say
+// which order its integers are in, and both sides have to use it.
+//
+// Every integer type has the conversions built in:
+// - to_be_bytes / to_le_bytes turn a u32 into a [u8; 4] in that order
+// - from_be_bytes / from_le_bytes go back, from exactly 4 bytes
+// - to_ne_bytes / from_ne_bytes use the CPU's own ("native") order,
+//   which is only right for bytes that never leave the machine
+// Go has the same in encoding/binary (binary.BigEndian.Uint32); Java's
+// ByteBuffer is big-endian unless told otherwise.
+
+use std::fmt::Write;
+
+/// The bytes as hex, the way a hex dump shows them.
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (i, byte) in bytes.iter().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        write!(out, "{byte:02x}").unwrap();
+    }
+    out
+}
+
+// ===== Reading integers out of a buffer =====
+
+/// A u32 from the 4 bytes at `offset`, big-endian, or None if the buffer
+/// is too short. `try_into` turns the 4-byte slice into the [u8; 4] that
+/// from_be_bytes wants; it fails only if the slice isn't 4 long, which
+/// get() has ruled out.
+fn read_u32_be(buffer: &[u8], offset: usize) -> Option<u32> {
+    let bytes = buffer.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+/// The same with `first_chunk`, which does the length check and the
+/// conversion to an array in one step.
+fn read_u16_le(buffer: &[u8], offset: usize) -> Option<u16> {
+    let bytes = buffer.get(offset..)?.first_chunk::<2>()?;
+    Some(u16::from_le_bytes(*bytes))
+}
+
+// ===== Writing a record =====
+
+/// A sensor reading as 8 bytes: a u16 sensor id, an i16 temperature in
+/// tenths of a degree, and a u32 timestamp, all big-endian.
+#[derive(Debug, PartialEq)]
+struct Reading {
+    sensor: u16,
+    tenths_celsius: i16,
+    timestamp: u32,
+}
+
+impl Reading {
+    fn to_bytes(&self) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        bytes[0..2].copy_from_slice(&self.sensor.to_be_bytes());
+        bytes[2..4].copy_from_slice(&self.tenths_celsius.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; 8]) -> Reading {
+        Reading {
+            sensor: u16::from_be_bytes([bytes[0], bytes[1]]),
+            tenths_celsius: i16::from_be_bytes([bytes[2], bytes[3]]),
+            timestamp: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+        }
+    }
+}
+
+fn main() {
+    println!("-- one number, two orders --");
+    let number: u32 = 0x1234_5678;
+    println!("big-endian:    {}", hex(&number.to_be_bytes()));
+    println!("little-endian: {}", hex(&number.to_le_bytes()));
+    let native = if cfg!(target_endian = "little") {
+        "little"
+    } else {
+        "big"
+    };
+    println!("this CPU is {native}-endian, so to_ne_bytes matches the {native}-endian line");
+
+    println!("-- reading the wrong way --");
+    let bytes = [0x00, 0x00, 0x01, 0x00];
+    println!(
+        "{} as big-endian:    {}",
+        hex(&bytes),
+        u32::from_be_bytes(bytes)
+    );
+    println!(
+        "{} as little-endian: {}",
+        hex(&bytes),
+        u32::from_le_bytes(bytes)
+    );
+    // swap_bytes reverses the order, which turns one reading into the other
+    println!("256 with its bytes swapped: {}", 256_u32.swap_bytes());
+    // Signed integers are stored in two's complement: -2_i16 is 0xfffe
+    println!(
+        "-2_i16: be {}, le {}",
+        hex(&(-2_i16).to_be_bytes()),
+        hex(&(-2_i16).to_le_bytes())
+    );
+    println!("1.5_f32: be {}", hex(&1.5_f32.to_be_bytes()));
+
+    println!("-- reading from a buffer --");
+    let buffer = [0xca, 0xfe, 0xba, 0xbe, 0x34, 0x12];
+    println!("buffer: {}", hex(&buffer));
+    println!("u32 at 0 (be): {:x?}", read_u32_be(&buffer, 0));
+    println!("u32 at 4 (be): {:?}", read_u32_be(&buffer, 4));
+    println!("u16 at 4 (le): {:x?}", read_u16_le(&buffer, 4));
+    println!("u16 at 5 (le): {:?}", read_u16_le(&buffer, 5));
+    println!("u16 at 9 (le): {:?}", read_u16_le(&buffer, 9));
+
+    println!("-- a record --");
+    let reading = Reading {
+        sensor: 7,
+        tenths_celsius: -35,
+        timestamp: 1_700_000_000,
+    };
+    let encoded = reading.to_bytes();
+    println!("{reading:?}");
+    println!("encoded: {}", hex(&encoded));
+    let decoded = Reading::from_bytes(&encoded);
+    println!("decoded the same: {}", decoded == reading);
+
+    // This would cause an error:
+    let value = u32::from_be_bytes(&buffer[0..4]);
+    // error[E0308]: mismatched types
+}
+
+/*
+ * Key Concepts:
+ * - Big-endian puts the most significant byte first; little-endian the least
+ * - Networks use big-endian; x86, ARM, and many file formats little-endian
+ * - to_be_bytes/from_be_bytes and the le versions convert in a chosen order
+ * - Native order (to_ne_bytes) is only safe for bytes that stay on one machine
+ * - from_*_bytes takes an array: get a slice, then try_into or first_chunk
+ */
+
+// EXPECTED:
+// -- one number, two orders --
+// big-endian:    12 34 56 78
+// little-endian: 78 56 34 12
+// this CPU is little-endian, so to_ne_bytes matches the little-endian line
+// -- reading the wrong way --
+// 00 00 01 00 as big-endian:    256
+// 00 00 01 00 as little-endian: 65536
+// 256 with its bytes swapped: 65536
+// -2_i16: be ff fe, le fe ff
+// 1.5_f32: be 3f c0 00 00
+// -- reading from a buffer --
+// buffer: ca fe ba be 34 12
+// u32 at 0 (be): Some(cafebabe)
+// u32 at 4 (be): None
+// u16 at 4 (le): Some(1234)
+// u16 at 5 (le): None
+// u16 at 9 (le): None
+// -- a record --
+// Reading { sensor: 7, tenths_celsius: -35, timestamp: 1700000000 }
+// encoded: 00 07 ff dd 65 53 f1 00
+// decoded the same: true
+
+// Generated by `cargo xtask compile-fail` from examples/bits/03_byte_order.rs, with the
+// snippet at line 145 uncommented.
@@ -0,0 +1,75 @@
+// Closures 1: Closure Basics
+// Demonstrates closure syntax, type inference, and capturing the environment
+//
+// Concepts: closure syntax, type inference, captures
+// Difficulty: intermediate
+// Minutes: 5
+
+fn apply_twice(f: impl Fn(i32) -> i32, value: i32) -> i32 {
+    f(f(value))
+}
+
+fn main() {
+    // A closure is an anonymous function you can store in a variable.
+    // Parameters go between pipes; the body follows.
+    let add_one = |x: i32| -> i32 { x + 1 }; // fully annotated
+    let add_two = |x| x + 2; // types inferred, braces optional
+    println!("add_one(5) = {}, add_two(5) = {}", add_one(5), add_two(5));
+
+    // Unlike a `fn`, a closure can use variables from where it was made
+    let bonus = 10;
+    let add_bonus = |x| x + bonus; // captures `bonus` by reference
+    println!("add_bonus(5) = {}", add_bonus(5));
+    // This would cause an error:
+    // fn add_bonus_fn(x: i32) -> i32 { x + bonus }
+    // error[E0434]: can't capture dynamic environment in a fn item
+
+    // Closures are passed to functions like any other value
+    println!("apply_twice(add_bonus, 1) = {}", apply_twice(add_bonus, 1));
+    println!("apply_twice(|x| x * 3, 1) = {}", apply_twice(|x| x * 3, 1));
+
+    // An inferred closure gets ONE concrete type on first use
+    let identity = |x| x;
+    let n = identity(5);
+    // This would cause an error:
+    let s = identity(String::from("five"));
+    // error[E0308]: mismatched types
+    println!("identity(5) = {}", n);
+
+    // Closures shine with iterator adapters and sort keys
+    let mut words = vec!["banana", "kiwi", "apple", "cherry"];
+    words.sort_by_key(|word| word.len());
+    println!("by length: {:?}", words);
+    let min_len = 5;
+    let long: Vec<_> = words.iter().filter(|word| word.len() >= min_len).collect();
+    println!("at least {} letters: {:?}", min_len, long);
+
+    // Plain functions work wherever a closure is expected
+    fn shout(word: &&str) -> String {
+        word.to_uppercase()
+    }
+    let shouted: Vec<String> = words.iter().map(shout).collect();
+    println!("{:?}", shouted);
+}
+
+/*
+ * Key Concepts:
+ * - Closures: |params| body, usually with inferred types
+ * - Closures capture variables from their surrounding scope
+ * - Each closure has its own unique, unnameable type
+ * - Accept closures with `impl Fn(...)` or a generic parameter
+ * - Named functions can be passed where closures are expected
+ */
+
+// EXPECTED:
+// add_one(5) = 6, add_two(5) = 7
+// add_bonus(5) = 15
+// apply_twice(add_bonus, 1) = 21
+// apply_twice(|x| x * 3, 1) = 9
+// identity(5) = 5
+// by length: ["kiwi", "apple", "banana", "cherry"]
+// at least 5 letters: ["apple", "banana", "cherry"]
+// ["KIWI", "APPLE", "BANANA", "CHERRY"]
+
+// Generated by `cargo xtask compile-fail` from examples/closures/01_closure_basics.rs, with the
+// snippet at line 34 uncommented.
@@ -0,0 +1,101 @@
+// Closures 2: Fn, FnMut, and FnOnce
+// Demonstrates the three closure traits and what each lets a closure do
+//
+// Concepts: Fn, FnMut, FnOnce
+// Difficulty: intermediate
+// Minutes: 10
+
+// Fn: can be called many times and only READS what it captured
+fn call_three_times(f: impl Fn() -> String) {
+    for _ in 0..3 {
+        println!("  {}", f());
+    }
+}
+
+// FnMut: can be called many times and may CHANGE what it captured.
+// The caller needs `mut` because calling it mutates its state.
+fn call_n_times(mut f: impl FnMut(), n: usize) {
+    for _ in 0..n {
+        f();
+    }
+}
+
+// FnOnce: can be called only once, because it may MOVE its captures out
+fn call_once(f: impl FnOnce() -> Vec<String>) -> Vec<String> {
+    f()
+}
+
+fn main() {
+    // Reads `greeting` -> implements Fn
+    let greeting = String::from("hello");
+    println!("Fn:");
+    call_three_times(|| format!("{} world", greeting));
+    println!("greeting is still usable: {}", greeting);
+
+    // Mutates `count` -> implements FnMut, but not Fn
+    let mut count = 0;
+    println!("FnMut:");
+    call_n_times(|| count += 1, 4);
+    println!("  count = {}", count);
+    // This would cause an error:
+    // call_three_times(|| { count += 1; count.to_string() });
+    // error[E0594]: cannot assign to `count`, as it is a captured variable in a `Fn` closure
+
+    // Moves `names` out of itself -> implements only FnOnce
+    let names = vec![String::from("Ann"), String::from("Bo")];
+    let give_away = || names;
+    println!("FnOnce: {:?}", call_once(give_away));
+    // This would cause an error:
+    let names = vec![String::from("Ann")];
+    let give_away = || names;
+    give_away();
+    give_away();
+    // error[E0382]: use of moved value: `give_away`
+
+    // The traits form a hierarchy: every Fn is also FnMut and FnOnce, and
+    // every FnMut is also FnOnce. So a function asking for FnOnce accepts
+    // the widest range of closures, and one asking for Fn the narrowest.
+    let tag = String::from("reused");
+    let reader = || vec![tag.clone()];
+    println!(
+        "Fn passed where FnOnce is expected: {:?}",
+        call_once(reader)
+    );
+    println!("and called again: {:?}", reader());
+
+    // Standard library examples:
+    //   Option::map         takes FnOnce (called at most once)
+    //   Iterator::for_each  takes FnMut  (called once per item)
+    //   thread::spawn       takes FnOnce (runs once, on another thread)
+    let mut total = 0;
+    (1..=5).for_each(|n| total += n);
+    println!("sum via for_each: {}", total);
+    let length = Some(String::from("five")).map(|s| s.len());
+    println!("Option::map: {:?}", length);
+}
+
+/*
+ * Key Concepts:
+ * - Fn: reads captures, callable many times
+ * - FnMut: mutates captures, callable many times (needs `mut`)
+ * - FnOnce: may consume captures, callable once
+ * - The compiler picks the traits from what the body does
+ * - Ask for the most general trait your function can work with
+ */
+
+// EXPECTED:
+// Fn:
+//   hello world
+//   hello world
+//   hello world
+// greeting is still usable: hello
+// FnMut:
+//   count = 4
+// FnOnce: ["Ann", "Bo"]
+// Fn passed where FnOnce is expected: ["reused"]
+// and called again: ["reused"]
+// sum via for_each: 15
+// Option::map: Some(4)
+
+// Generated by `cargo xtask compile-fail` from examples/closures/02_fn_traits.rs, with the
+// snippet at line 48 uncommented.
@@ -0,0 +1,92 @@
+// Closures 4: Returning Closures
+// Demonstrates impl Fn return types, Box<dyn Fn>, and composing functions
+//
+// Concepts: impl Fn, Box<dyn Fn>, function composition
+// Difficulty: intermediate
+// Minutes: 5
+
+// `impl Fn(i32) -> i32` means "some closure type; the caller doesn't need
+// to know which". Each closure has its own hidden type, so this is how
+// you name it in a signature.
+fn multiplier(factor: i32) -> impl Fn(i32) -> i32 {
+    move |x| x * factor
+}
+
+// Returning one of SEVERAL different closures needs a single type for
+// all of them: a boxed trait object.
+fn operation(name: &str) -> Box<dyn Fn(i32, i32) -> i32> {
+    match name {
+        "add" => Box::new(|a, b| a + b),
+        "max" => Box::new(|a, b| a.max(b)),
+        _ => Box::new(|_, _| 0),
+    }
+}
+// This would cause an error:
+fn pick(up: bool, n: i32) -> impl Fn(i32) -> i32 { if up { move |x| x + n } else { move |x| x - n } }
+// error[E0308]: `if` and `else` have incompatible types
+
+// Closures that build closures: compose(f, g) runs f, then g
+fn compose<A, B, C>(f: impl Fn(A) -> B, g: impl Fn(B) -> C) -> impl Fn(A) -> C {
+    move |x| g(f(x))
+}
+
+// Returning FnMut lets the closure keep private state between calls
+fn id_generator(prefix: &str) -> impl FnMut() -> String {
+    let prefix = prefix.to_string();
+    let mut next = 0;
+    move || {
+        next += 1;
+        format!("{}-{}", prefix, next)
+    }
+}
+
+fn main() {
+    let double = multiplier(2);
+    let triple = multiplier(3);
+    println!("double(7) = {}, triple(7) = {}", double(7), triple(7));
+
+    for name in ["add", "max", "unknown"] {
+        let op = operation(name);
+        println!("{}(4, 9) = {}", name, op(4, 9));
+    }
+
+    let parse_then_double = compose(|s: &str| s.len() as i32, multiplier(2));
+    println!(
+        "length of \"hello\" doubled = {}",
+        parse_then_double("hello")
+    );
+
+    let mut next_id = id_generator("order");
+    let ids: Vec<String> = (0..3).map(|_| next_id()).collect();
+    println!("ids: {:?}", ids);
+
+    // A list of closures with different behaviour, all the same type
+    let pipeline: Vec<Box<dyn Fn(i32) -> i32>> = vec![
+        Box::new(multiplier(10)),
+        Box::new(|x| x + 1),
+        Box::new(|x| x * x),
+    ];
+    let result = pipeline.iter().fold(2, |value, step| step(value));
+    println!("2 -> *10 -> +1 -> squared = {}", result);
+}
+
+/*
+ * Key Concepts:
+ * - `impl Fn(...)` returns one specific (hidden) closure type
+ * - Box<dyn Fn(...)> returns any of several closure types
+ * - Returned closures almost always need `move`
+ * - Closures can build and combine other closures
+ * - Returning FnMut gives a closure private, persistent state
+ */
+
+// EXPECTED:
+// double(7) = 14, triple(7) = 21
+// add(4, 9) = 13
+// max(4, 9) = 9
+// unknown(4, 9) = 0
+// length of "hello" doubled = 10
+// ids: ["order-1", "order-2", "order-3"]
+// 2 -> *10 -> +1 -> squared = 441
+
+// Generated by `cargo xtask compile-fail` from examples/closures/04_returning_closures.rs, with the
+// snippet at line 24 uncommented.
@@ -0,0 +1,83 @@
+// Collections 2: HashMap<K, V> - Looking Things Up by Key
+// Demonstrates inserting, reading, updating, and counting with HashMap
+//
+// Concepts: HashMap, entry API, counting
+// Difficulty: beginner
+// Minutes: 5
+
+use std::collections::HashMap;
+
+fn main() {
+    // Inserting key -> value pairs
+    let mut scores: HashMap<String, u32> = HashMap::new();
+    scores.insert(String::from("Blue"), 10);
+    scores.insert(String::from("Yellow"), 50);
+
+    // Reading returns an Option, because the key may be absent
+    match scores.get("Blue") {
+        Some(score) => println!("Blue has {}", score),
+        None => println!("Blue hasn't played"),
+    }
+    println!("Red: {:?}", scores.get("Red"));
+
+    // Inserting an existing key overwrites the old value
+    scores.insert(String::from("Blue"), 25);
+    println!("Blue after overwrite: {}", scores["Blue"]);
+
+    // entry().or_insert() only inserts if the key is missing
+    scores.entry(String::from("Yellow")).or_insert(0);
+    scores.entry(String::from("Red")).or_insert(0);
+
+    // HashMap iteration order is NOT defined - sort for stable output
+    let mut teams: Vec<_> = scores.iter().collect();
+    teams.sort();
+    println!("teams: {:?}", teams);
+
+    // The classic word counter: update a value based on the old one
+    let text = "the quick brown fox jumps over the lazy dog the end";
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in text.split_whitespace() {
+        let count = counts.entry(word).or_insert(0);
+        *count += 1; // or_insert returns &mut V
+    }
+    println!("'the' appears {} times", counts["the"]);
+
+    // Removing
+    let removed = scores.remove("Red");
+    println!("removed Red: {:?}, {} teams left", removed, scores.len());
+
+    // Ownership: inserting a String moves it into the map
+    let key = String::from("Green");
+    scores.insert(key, 5);
+    // This would cause an error:
+    println!("{}", key);
+    // error[E0382]: borrow of moved value: `key`
+
+    // Building a map from an iterator of pairs
+    let lengths: HashMap<&str, usize> = ["apple", "kiwi"]
+        .iter()
+        .map(|fruit| (*fruit, fruit.len()))
+        .collect();
+    println!("kiwi has {} letters", lengths["kiwi"]);
+}
+
+/*
+ * Key Concepts:
+ * - HashMap<K, V> maps keys to values with fast average lookup
+ * - get() returns Option<&V>; map[key] panics if the key is missing
+ * - entry(k).or_insert(v) is the idiom for "insert or update"
+ * - Iteration order is arbitrary - sort if you need a stable order
+ * - Owned keys/values (String) are moved into the map
+ */
+
+// EXPECTED:
+// Blue has 10
+// Red: None
+// Blue after overwrite: 25
+// teams: [("Blue", 25), ("Red", 0), ("Yellow", 50)]
+// 'the' appears 3 times
+// removed Red: Some(0), 2 teams left
+// kiwi has 4 letters
+
+// Generated by `cargo xtask compile-fail` from examples/collections/02_hashmap.rs, with the
+// snippet at line 52 uncommented.
@@ -0,0 +1,95 @@
+// Collections 5: Iterator Pipelines
+// Demonstrates map, filter, fold, collect, and friends
+//
+// Concepts: map, filter, fold, collect, iterator adapters
+// Difficulty: intermediate
+// Minutes: 10
+
+fn main() {
+    let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+    // An iterator produces items one at a time. Adapters like map and
+    // filter build a pipeline; NOTHING happens until it is consumed.
+    let lazy = numbers.iter().map(|n| n * 2); // No work done yet
+    let doubled: Vec<i32> = lazy.collect(); // collect() runs the pipeline
+    println!("doubled: {:?}", doubled);
+
+    // filter keeps items that match a condition
+    let evens: Vec<&i32> = numbers.iter().filter(|n| *n % 2 == 0).collect();
+    println!("evens: {:?}", evens);
+
+    // Chain adapters together: squares of odd numbers, summed
+    let total: i32 = numbers.iter().filter(|n| *n % 2 == 1).map(|n| n * n).sum();
+    println!("sum of odd squares: {}", total);
+
+    // The same thing as a loop, for comparison
+    let mut total_loop = 0;
+    for n in &numbers {
+        if n % 2 == 1 {
+            total_loop += n * n;
+        }
+    }
+    println!("same with a loop:   {}", total_loop);
+
+    // fold carries an accumulator through every item
+    // (this one is .product(), which is how clippy would have you write it)
+    #[allow(clippy::unnecessary_fold)]
+    let product = numbers.iter().take(5).fold(1, |acc, n| acc * n);
+    println!("1 * 2 * 3 * 4 * 5 = {}", product);
+
+    // enumerate adds an index; zip pairs two iterators up
+    let names = ["ana", "ben", "cy"];
+    let ages = [31, 25, 40];
+    for (i, (name, age)) in names.iter().zip(ages.iter()).enumerate() {
+        println!("{}: {} is {}", i, name, age);
+    }
+
+    // Searching: find, any, all, position
+    println!("first > 7: {:?}", numbers.iter().find(|n| **n > 7));
+    println!("any negative? {}", numbers.iter().any(|n| *n < 0));
+    println!("all positive? {}", numbers.iter().all(|n| *n > 0));
+    println!("position of 4: {:?}", numbers.iter().position(|n| *n == 4));
+
+    // collect can build many collection types, even a String
+    let shout: String = "hello".chars().map(|c| c.to_ascii_uppercase()).collect();
+    println!("{}", shout);
+
+    // iter() borrows, into_iter() takes ownership, iter_mut() changes
+    let mut words = vec![String::from("a"), String::from("b")];
+    for word in words.iter_mut() {
+        word.push('!');
+    }
+    let joined: Vec<String> = words.into_iter().map(|w| w.repeat(2)).collect();
+    println!("{:?}", joined);
+    // This would cause an error:
+    println!("{:?}", words);
+    // error[E0382]: borrow of moved value: `words` (into_iter consumed it)
+}
+
+/*
+ * Key Concepts:
+ * - Iterators are lazy: adapters describe work, consumers perform it
+ * - Adapters: map, filter, take, skip, enumerate, zip, chain, ...
+ * - Consumers: collect, sum, fold, count, find, any, all, ...
+ * - Pipelines often replace loops with clearer, equally fast code
+ * - iter() = &T, iter_mut() = &mut T, into_iter() = T (consumes)
+ */
+
+// EXPECTED:
+// doubled: [2, 4, 6, 8, 10, 12, 14, 16, 18, 20]
+// evens: [2, 4, 6, 8, 10]
+// sum of odd squares: 165
+// same with a loop:   165
+// 1 * 2 * 3 * 4 * 5 = 120
+// 0: ana is 31
+// 1: ben is 25
+// 2: cy is 40
+// first > 7: Some(8)
+// any negative? false
+// all positive? true
+// position of 4: Some(3)
+// HELLO
+// ["a!a!", "b!b!"]
+
+// Generated by `cargo xtask compile-fail` from examples/collections/05_iterators.rs, with the
+// snippet at line 64 uncommented.
@@ -0,0 +1,80 @@
+// Concurrency 1: Spawning and Joining Threads
+// Demonstrates thread::spawn, JoinHandle::join, and move closures
+//
+// Concepts: thread::spawn, JoinHandle::join, move closures
+// Difficulty: intermediate
+// Minutes: 5
+
+use std::thread;
+use std::time::Duration;
+
+use learn_rust_core::{timed, Timer};
+
+// Pretend to do some slow work, like waiting on a disk or network.
+fn slow_task(id: u32) -> u32 {
+    thread::sleep(Duration::from_millis(200));
+    id * 10
+}
+
+fn main() {
+    // One after another: four tasks take four times as long
+    let sequential: Vec<u32> = timed("sequential", || (1..=4).map(slow_task).collect());
+    println!("sequential: {:?}", sequential);
+
+    // thread::spawn runs a closure on a new OS thread and returns a
+    // JoinHandle. The threads run at the same time, so the total is
+    // roughly the time of ONE task, not four.
+    let parallel: Vec<u32> = {
+        // Prints how long it ran for when the block ends and it's dropped
+        let _timer = Timer::start("parallel");
+        let handles: Vec<thread::JoinHandle<u32>> = (1..=4)
+            .map(|id| thread::spawn(move || slow_task(id)))
+            .collect();
+
+        // join() waits for the thread to finish and hands back its result
+        // (wrapped in a Result, because the thread might have panicked).
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    };
+    println!("parallel:   {:?}", parallel);
+
+    // A spawned thread may outlive the function that created it, so its
+    // closure must OWN everything it uses. `move` transfers ownership.
+    let name = String::from("worker");
+    let handle = thread::spawn(move || format!("hello from {}", name));
+    println!("{}", handle.join().unwrap());
+    // This would cause an error:
+    let name = String::from("worker");
+    let handle = thread::spawn(|| println!("{}", name));
+    // error[E0373]: closure may outlive the current function, but it borrows `name`
+
+    // Scoped threads are guaranteed to finish before the scope ends, so
+    // they CAN borrow local data without `move`.
+    let numbers = [1, 2, 3, 4, 5, 6, 7, 8];
+    let (left, right) = numbers.split_at(numbers.len() / 2);
+    let total = thread::scope(|scope| {
+        let a = scope.spawn(|| left.iter().sum::<i32>());
+        let b = scope.spawn(|| right.iter().sum::<i32>());
+        a.join().unwrap() + b.join().unwrap()
+    });
+    println!("sum computed by two scoped threads: {}", total);
+
+    // A panic in a thread doesn't crash the program; join() reports it.
+    // (The panic message itself still shows up on stderr.)
+    let result = thread::spawn(|| panic!("something went wrong in the thread")).join();
+    println!("panicked thread joined with is_err() = {}", result.is_err());
+}
+
+/*
+ * Key Concepts:
+ * - thread::spawn starts a new thread and returns a JoinHandle
+ * - join() blocks until the thread ends and returns its result
+ * - Threads run concurrently: total time ≈ the slowest thread
+ * - `move` closures give a thread ownership of the data it needs
+ * - thread::scope lets threads borrow local data safely
+ */
+
+// Generated by `cargo xtask compile-fail` from examples/concurrency/01_spawn_join.rs, with the
+// snippet at line 48 uncommented.
@@ -0,0 +1,126 @@
+// Enums 1: Enums and Exhaustive match
+// Demonstrates enum variants with data and why match must cover every case
+//
+// Concepts: enums, variants with data, match, exhaustiveness
+// Difficulty: beginner
+// Minutes: 10
+
+// An enum is a type that is exactly ONE of several variants.
+// Each variant can carry its own kind of data.
+#[derive(Debug)]
+enum Shape {
+    Circle { radius: f64 }, // named fields, like a struct
+    Rectangle(f64, f64),    // unnamed fields, like a tuple
+    Point,                  // no data at all
+}
+
+fn area(shape: &Shape) -> f64 {
+    // match compares a value against patterns, top to bottom, and runs the
+    // first arm that fits. Every variant MUST be handled.
+    match shape {
+        Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+        Shape::Rectangle(width, height) => width * height,
+        Shape::Point => 0.0,
+    }
+}
+// This would cause an error:
+fn name(shape: &Shape) -> &str { match shape { Shape::Circle { .. } => "circle", Shape::Point => "point" } }
+// error[E0004]: non-exhaustive patterns: `&Shape::Rectangle(_, _)` not covered
+
+#[derive(Debug, Clone, Copy)]
+enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+#[allow(clippy::match_like_matches_macro)] // matches!(day, ...) is the short form
+fn is_weekend(day: Weekday) -> bool {
+    // `|` matches several patterns in one arm, and `_` matches anything.
+    // A wildcard satisfies exhaustiveness, but it also hides new variants
+    // from you later, so prefer listing variants when there are few.
+    match day {
+        Weekday::Saturday | Weekday::Sunday => true,
+        _ => false,
+    }
+}
+
+fn main() {
+    let shapes = [
+        Shape::Circle { radius: 1.0 },
+        Shape::Rectangle(3.0, 4.0),
+        Shape::Point,
+    ];
+    for shape in &shapes {
+        println!("{:?} has area {:.2}", shape, area(shape));
+    }
+
+    // match is an expression: every arm produces a value of the same type
+    let day = Weekday::Saturday;
+    let plan = match day {
+        Weekday::Friday => "almost there",
+        Weekday::Saturday | Weekday::Sunday => "rest",
+        _ => "work",
+    };
+    println!("{:?}: {} (weekend: {})", day, plan, is_weekend(day));
+    for day in [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Sunday,
+    ] {
+        println!("{:?} is a weekend? {}", day, is_weekend(day));
+    }
+
+    // Option is just an enum from the standard library:
+    //     enum Option<T> { Some(T), None }
+    // so matching on it must handle None too. That's how Rust avoids null.
+    let maybe: Option<u32> = "42".parse().ok();
+    match maybe {
+        Some(n) => println!("parsed {}", n),
+        None => println!("not a number"),
+    }
+
+    // Matching on integers: ranges and a catch-all are required, since
+    // the compiler won't assume you've covered every u8 otherwise
+    let score: u8 = 87;
+    let grade = match score {
+        90..=100 => 'A',
+        80..=89 => 'B',
+        70..=79 => 'C',
+        _ => 'F',
+    };
+    println!("score {} -> grade {}", score, grade);
+}
+
+/*
+ * Key Concepts:
+ * - Enum variants can hold no data, tuple-like data, or named fields
+ * - match must be exhaustive: every possible value needs an arm
+ * - `|` combines patterns; `_` matches anything
+ * - match is an expression, so it returns a value
+ * - Option<T> is an ordinary enum, which is why None must be handled
+ */
+
+// EXPECTED:
+// Circle { radius: 1.0 } has area 3.14
+// Rectangle(3.0, 4.0) has area 12.00
+// Point has area 0.00
+// Saturday: rest (weekend: true)
+// Monday is a weekend? false
+// Tuesday is a weekend? false
+// Wednesday is a weekend? false
+// Thursday is a weekend? false
+// Friday is a weekend? false
+// Sunday is a weekend? true
+// parsed 42
+// score 87 -> grade B
+
+// Generated by `cargo xtask compile-fail` from examples/enums/01_match_exhaustiveness.rs, with the
+// snippet at line 26 uncommented.
@@ -0,0 +1,88 @@
+// Error Handling 3: The ? Operator
+// Demonstrates passing errors up to the caller without boilerplate
+//
+// Concepts: ? operator, error propagation
+// Difficulty: intermediate
+// Minutes: 5
+
+use std::num::ParseIntError;
+
+fn main() {
+    println!("sum of \"1 2 3\" = {:?}", sum_numbers("1 2 3"));
+    println!("sum of \"1 x 3\" = {:?}", sum_numbers("1 x 3"));
+
+    println!("verbose version agrees: {:?}", sum_numbers_verbose("4 5"));
+
+    println!("first char doubled: {:?}", first_char_upper("rust"));
+    println!("first char of empty: {:?}", first_char_upper(""));
+
+    // main can return Result too! See `run` below.
+    if let Err(error) = run() {
+        println!("run() failed: {}", error);
+    }
+}
+
+// `?` means: "if this is Err, return it from the function right now;
+// otherwise give me the Ok value"
+fn sum_numbers(text: &str) -> Result<i32, ParseIntError> {
+    let mut total = 0;
+    for word in text.split_whitespace() {
+        total += word.parse::<i32>()?;
+    }
+    Ok(total)
+}
+
+// Exactly what ? does for us, written out by hand
+#[allow(clippy::question_mark)]
+fn sum_numbers_verbose(text: &str) -> Result<i32, ParseIntError> {
+    let mut total = 0;
+    for word in text.split_whitespace() {
+        let number = match word.parse::<i32>() {
+            Ok(number) => number,
+            Err(error) => return Err(error),
+        };
+        total += number;
+    }
+    Ok(total)
+}
+
+// ? works on Option too, in functions that return Option
+fn first_char_upper(text: &str) -> Option<char> {
+    let first = text.chars().next()?; // Return None if the text is empty
+    first.to_uppercase().next()
+}
+
+// A function can chain several fallible steps with ?
+fn run() -> Result<(), ParseIntError> {
+    let width: u32 = "12".parse()?;
+    let height: u32 = "seven".parse()?; // Fails here, so the rest never runs
+    println!("area = {}", width * height);
+    Ok(())
+}
+
+// This would cause an error:
+fn no_result() -> i32 {
+    let n: i32 = "5".parse()?;
+    n
+}
+// error[E0277]: the `?` operator can only be used in a function that returns
+//               `Result` or `Option`
+
+/*
+ * Key Concepts:
+ * - expr? unwraps Ok/Some, or returns the Err/None immediately
+ * - It replaces a match-and-return with a single character
+ * - It only works inside functions that return Result or Option (E0277)
+ * - main can return Result<(), E> to use ? at the top level
+ */
+
+// EXPECTED:
+// sum of "1 2 3" = Ok(6)
+// sum of "1 x 3" = Err(ParseIntError { kind: InvalidDigit })
+// verbose version agrees: Ok(9)
+// first char doubled: Some('R')
+// first char of empty: None
+// run() failed: invalid digit found in string
+
+// Generated by `cargo xtask compile-fail` from examples/error_handling/03_question_mark.rs, with the
+// snippet at line 63 uncommented.
@@ -0,0 +1,80 @@
+// FFI 1: Calling C from Rust
+// Demonstrates extern "C" blocks, linking, and wrapping C in safe functions
+//
+// Concepts: extern "C", linking, safe wrappers
+// Difficulty: advanced
+// Minutes: 5
+//
+// FFI (Foreign Function Interface) lets Rust call code written in other
+// languages, almost always through the C calling convention. The C code
+// for this chapter is in c/mathlib.c; build.rs compiles it before these
+// examples are built.
+
+use std::os::raw::c_int;
+
+// An `extern "C"` block DECLARES functions that exist somewhere else. The
+// signatures must match the C header (c/mathlib.h) exactly: the compiler
+// can't see the C code, so it just takes our word for it.
+extern "C" {
+    fn ml_add(a: i32, b: i32) -> i32;
+    fn ml_average(values: *const f64, len: usize) -> f64;
+    // Functions from the C standard library work the same way
+    fn abs(value: c_int) -> c_int;
+}
+
+/// A safe wrapper: takes a slice, so the pointer and length always match.
+fn average(values: &[f64]) -> f64 {
+    // SAFETY: `values.as_ptr()` points at `values.len()` initialized f64s,
+    // which is exactly what ml_average reads, and it doesn't keep the
+    // pointer after returning.
+    unsafe { ml_average(values.as_ptr(), values.len()) }
+}
+
+fn main() {
+    // Every foreign call is unsafe: Rust can't check C's side of the deal
+    // SAFETY: ml_add takes two ints by value and has no other requirements
+    let sum = unsafe { ml_add(2, 40) };
+    println!("ml_add(2, 40) = {}", sum);
+
+    // This would cause an error:
+    let sum = ml_add(2, 40);
+    // error[E0133]: call to unsafe function `ml_add` is unsafe and requires unsafe function or block
+
+    // SAFETY: abs() is defined for every int except INT_MIN
+    println!("abs(-7) = {}", unsafe { abs(-7) });
+
+    // With the wrapper, callers never write `unsafe`
+    println!(
+        "average of [1, 2, 3, 4] = {}",
+        average(&[1.0, 2.0, 3.0, 4.0])
+    );
+    println!("average of [] = {}", average(&[]));
+
+    // Type sizes: i32 is always C's int32_t, but C's `int` and `long`
+    // differ between platforms, so use std::os::raw (or core::ffi) types
+    // like c_int and c_long when a header uses them.
+    println!(
+        "c_int is {} bytes, c_long is {} bytes here",
+        size_of::<c_int>(),
+        size_of::<std::os::raw::c_long>()
+    );
+}
+
+/*
+ * Key Concepts:
+ * - `extern "C" { ... }` declares functions implemented in C
+ * - The declarations must match the C header; nothing checks them
+ * - Calling a foreign function is always `unsafe`
+ * - build.rs compiles and links the C code (here with the `cc` crate)
+ * - Wrap foreign calls in safe functions with Rust-friendly types
+ */
+
+// EXPECTED:
+// ml_add(2, 40) = 42
+// abs(-7) = 7
+// average of [1, 2, 3, 4] = 2.5
+// average of [] = 0
+// c_int is 4 bytes, c_long is 8 bytes here
+
+// Generated by `cargo xtask compile-fail` from examples/ffi/01_calling_c.rs, with the
+// snippet at line 39 uncommented.
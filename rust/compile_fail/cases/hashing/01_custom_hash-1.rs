@@ -0,0 +1,163 @@
+// Hashing 1: Hash and Eq for Your Own Keys
+// Demonstrates deriving and hand-writing Hash and Eq for a key type, and the rule that keeps the two in agreement
+//
+// Concepts: Hash, Eq, PartialEq, Hasher, derive, the Hash/Eq contract, HashMap keys
+// Difficulty: intermediate
+// Minutes: 15
+//
+// A HashMap finds a key in two steps. It hashes the key, a function that
+// turns it into a u64, and uses the number to pick a slot in its table
+// to look in. Then it compares the key with `==` against what it finds
+// there, since different keys can hash to the same number. So a key type
+// needs both `Hash` and `Eq`, and the two must agree:
+//
+//     if a == b, then hash(a) == hash(b)
+//
+// (The other way round needn't hold: unequal keys may share a hash. That
+// is a collision, and the third example is about what they cost.)
+//
+// `#[derive(PartialEq, Eq, Hash)]` keeps the rule without thinking about
+// it, because both look at every field the same way. That is the right
+// choice for most keys, like `Cell` below.
+//
+// Writing them by hand is for keys whose equality is looser than their
+// fields. A `Username` here is equal to another that differs only in
+// case, so its `Hash` must ignore case too: it feeds the hasher each
+// byte lowercased. `CarelessUsername` compares the same way but derives
+// `Hash`, which hashes the string as written. "Ferris" and "FERRIS" are
+// equal, hash differently, and so land in different slots: the map
+// can't find one by the other, and happily holds both. Nothing checks
+// the rule for you, not the compiler and not the map; breaking it just
+// makes lookups quietly wrong. Clippy does catch the most common way to
+// break it, a derived Hash next to a hand-written PartialEq, and this
+// file has to switch that lint off to compile under `-D warnings`.
+//
+// Floats are the usual surprise. `f64` is only PartialEq, not Eq, since
+// NaN != NaN, and it has no Hash either, so it can't be a key at all.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, DefaultHasher, Hash, Hasher};
+
+/// A square on a grid. Derived: both traits look at `row` and `col`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Cell {
+    row: i32,
+    col: i32,
+}
+
+/// A username, compared without regard to case.
+#[derive(Debug, Clone)]
+struct Username(String);
+
+impl PartialEq for Username {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for Username {}
+
+impl Hash for Username {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash exactly what eq looks at: the bytes, lowercased
+        for byte in self.0.bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        // Ends the string, the way str's own Hash does, so that a tuple
+        // of ("ab", "c") and one of ("a", "bc") feed the hasher different bytes
+        state.write_u8(0xff);
+    }
+}
+
+/// Compared like a Username, but with `Hash` derived, which hashes the
+/// string as written.
+#[allow(clippy::derived_hash_with_manual_eq)] // clippy spots this very mistake
+#[derive(Debug, Clone, Hash)]
+struct CarelessUsername(String);
+
+impl PartialEq for CarelessUsername {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for CarelessUsername {}
+
+/// The hash a map would compute for `value`.
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A HashMap whose hashes are the same in every run. A plain HashMap
+/// picks random keys for its hasher, which the third example explains;
+/// this one doesn't, so this program prints the same thing every time.
+type RepeatableMap<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+
+fn main() {
+    println!("-- derived --");
+    let mut board: HashMap<Cell, &str> = HashMap::new();
+    board.insert(Cell { row: 0, col: 0 }, "start");
+    board.insert(Cell { row: 2, col: 3 }, "treasure");
+    // A Cell made just now finds the one inserted earlier: equal fields,
+    // equal hash
+    let looking_at = Cell { row: 2, col: 3 };
+    println!("{looking_at:?} holds {:?}", board.get(&looking_at));
+
+    println!("\n-- by hand --");
+    let mut posts: HashMap<Username, u32> = HashMap::new();
+    posts.insert(Username("Ferris".to_string()), 3);
+    *posts.entry(Username("FERRIS".to_string())).or_insert(0) += 1;
+    let ferris = Username("ferris".to_string());
+    println!("posts by {:?}: {:?}", ferris.0, posts.get(&ferris));
+    println!("entries: {}", posts.len());
+    println!(
+        "same hash for Ferris and FERRIS: {}",
+        hash_of(&Username("Ferris".to_string())) == hash_of(&Username("FERRIS".to_string()))
+    );
+
+    println!("\n-- when Hash and Eq disagree --");
+    let upper = CarelessUsername("FERRIS".to_string());
+    let title = CarelessUsername("Ferris".to_string());
+    println!("equal: {}", upper == title);
+    println!("same hash: {}", hash_of(&upper) == hash_of(&title));
+    let mut careless: RepeatableMap<CarelessUsername, u32> = RepeatableMap::default();
+    careless.insert(title, 3);
+    println!("posts by FERRIS: {:?}", careless.get(&upper));
+    careless.insert(upper, 1);
+    println!("entries for one user: {}", careless.len());
+
+    // This would cause an error:
+    let mut prices: HashMap<f64, &str> = HashMap::new();
+    prices.insert(0.1, "cheap");
+    // error[E0599]: the method `insert` exists for struct `HashMap<f64, &str>`, but its trait bounds were not satisfied
+}
+
+/*
+ * Key Concepts:
+ * - A HashMap hashes a key to find where to look, then compares with ==
+ * - Keys need Hash and Eq, and equal keys must have equal hashes
+ * - #[derive(PartialEq, Eq, Hash)] keeps the rule automatically
+ * - A hand-written Hash must ignore whatever a hand-written Eq ignores
+ * - Breaking the rule compiles fine; lookups just quietly fail
+ * - f64 can't be a key: it has no Eq (NaN != NaN) and no Hash
+ */
+
+// EXPECTED:
+// -- derived --
+// Cell { row: 2, col: 3 } holds Some("treasure")
+//
+// -- by hand --
+// posts by "ferris": Some(4)
+// entries: 1
+// same hash for Ferris and FERRIS: true
+//
+// -- when Hash and Eq disagree --
+// equal: true
+// same hash: false
+// posts by FERRIS: None
+// entries for one user: 2
+
+// Generated by `cargo xtask compile-fail` from examples/hashing/01_custom_hash.rs, with the
+// snippet at line 131 uncommented.
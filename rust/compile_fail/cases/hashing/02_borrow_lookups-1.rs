@@ -0,0 +1,161 @@
+// Hashing 2: Looking Up by a Borrowed Key
+// Demonstrates how a HashMap with String keys can be searched with a &str, through the Borrow trait, and how to give your own key type the same
+//
+// Concepts: Borrow, HashMap::get's signature, ?Sized, lookups that don't allocate, the Borrow contract
+// Difficulty: intermediate
+// Minutes: 15
+//
+// A `HashMap<String, u32>` owns its keys, yet `map.get("Blue")` works
+// with a plain `&str`; nobody has to build a String to ask. That is
+// because `get` doesn't take a `&K`:
+//
+//     pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+//     where
+//         K: Borrow<Q>,
+//         Q: Hash + Eq,
+//
+// It takes a reference to anything the keys can be borrowed as. String
+// implements `Borrow<str>`, so Q can be str (which is why `?Sized`: str
+// has no size of its own). The map hashes the `&str` it is given, finds
+// the slot, and compares each key there with `key.borrow() == "Blue"`.
+//
+// That only works if the borrowed form hashes and compares exactly like
+// the owned one, which is what the Borrow trait promises, and why
+// String and str agree byte for byte. Your own key type can make the
+// same promise. `Isbn` below wraps a String and derives Hash and Eq,
+// which hash and compare that String, so `impl Borrow<str> for Isbn` is
+// honest, and the catalog can be searched with a `&str`. `Barcode` has no
+// such impl, so every lookup has to build a whole Barcode first, String
+// allocation and all.
+//
+// A Borrow that isn't honest is a bug in waiting. `Username` from the
+// first example, equal to another whatever the case, can't promise that
+// its borrowed str compares the same way: "ferris" == "Ferris" is false
+// for str. Give it `Borrow<str>` anyway and lookups by `&str` fail even
+// for the exact spelling that was inserted, as the last section shows.
+//
+// (One gap: a map keyed by `(String, u32)` can't be searched with a
+// `(&str, u32)`, because a tuple of them isn't something the key borrows
+// as. Such maps either build the key, or split into a map of maps.)
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, DefaultHasher, Hash, Hasher};
+
+/// A book number. Hash and Eq are the String's, so it can lend out a
+/// `&str` that hashes and compares the same.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Isbn(String);
+
+impl Borrow<str> for Isbn {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A product code, without a Borrow impl.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Barcode(String);
+
+/// A username compared without regard to case, as in the first example,
+/// with a Borrow impl it can't honor.
+#[derive(Debug, Clone)]
+struct Username(String);
+
+impl PartialEq for Username {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for Username {}
+
+impl Hash for Username {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.0.bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl Borrow<str> for Username {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A HashMap whose hashes are the same in every run, as in the first
+/// example, so that the broken lookups fail the same way every time.
+type RepeatableMap<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+
+fn main() {
+    println!("-- String keys, &str lookups --");
+    let mut scores: HashMap<String, u32> = HashMap::new();
+    scores.insert("Blue".to_string(), 10);
+    let team = String::from("Blue");
+    println!("by &str:    {:?}", scores.get("Blue"));
+    println!("by &String: {:?}", scores.get(&team));
+    println!("by a slice of a longer string: {:?}", scores.get(&"Blue team"[..4]));
+
+    println!("\n-- a key type that lends out a &str --");
+    let mut catalog: HashMap<Isbn, &str> = HashMap::new();
+    catalog.insert(Isbn("978-1718503106".to_string()), "The Rust Programming Language");
+    catalog.insert(Isbn("978-1492052593".to_string()), "Programming Rust");
+    // Straight from the text of a request, say, without building an Isbn
+    let wanted = "978-1718503106";
+    println!("{wanted}: {:?}", catalog.get(wanted));
+    println!("is 978-0000000000 there? {}", catalog.contains_key("978-0000000000"));
+
+    println!("\n-- a key type that doesn't --");
+    let mut stock: HashMap<Barcode, u32> = HashMap::new();
+    stock.insert(Barcode("0042".to_string()), 7);
+    // Every lookup allocates a String to build the key it asks with
+    println!("0042: {:?}", stock.get(&Barcode("0042".to_string())));
+
+    println!("\n-- a Borrow that breaks the promise --");
+    let mut posts: RepeatableMap<Username, u32> = RepeatableMap::default();
+    posts.insert(Username("Ferris".to_string()), 3);
+    println!("by Username(\"FERRIS\"): {:?}", posts.get(&Username("FERRIS".to_string())));
+    // The str "Ferris" hashes its bytes as written; the key was hashed
+    // lowercased, so the map looks in the wrong slot
+    println!("by \"Ferris\": {:?}", posts.get("Ferris"));
+    // Right slot this time, but then "Ferris" == "ferris" is false for str
+    println!("by \"ferris\": {:?}", posts.get("ferris"));
+
+    // A Barcode borrows only as itself, so get wants a &Barcode
+    // This would cause an error:
+    println!("{:?}", stock.get("0042"));
+    // error[E0308]: mismatched types
+}
+
+/*
+ * Key Concepts:
+ * - HashMap::get takes &Q for any Q the keys implement Borrow<Q> for
+ * - String: Borrow<str> is why a String-keyed map can be searched with a &str
+ * - Borrow promises that the borrowed form hashes and compares like the owned one
+ * - A newtype over String can implement Borrow<str> when its Hash and Eq are the String's
+ * - Without Borrow, every lookup has to build (and allocate) a whole key
+ * - A dishonest Borrow makes lookups fail, even for the exact key inserted
+ */
+
+// EXPECTED:
+// -- String keys, &str lookups --
+// by &str:    Some(10)
+// by &String: Some(10)
+// by a slice of a longer string: Some(10)
+//
+// -- a key type that lends out a &str --
+// 978-1718503106: Some("The Rust Programming Language")
+// is 978-0000000000 there? false
+//
+// -- a key type that doesn't --
+// 0042: Some(7)
+//
+// -- a Borrow that breaks the promise --
+// by Username("FERRIS"): Some(3)
+// by "Ferris": None
+// by "ferris": None
+
+// Generated by `cargo xtask compile-fail` from examples/hashing/02_borrow_lookups.rs, with the
+// snippet at line 127 uncommented.
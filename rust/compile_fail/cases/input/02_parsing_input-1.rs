@@ -0,0 +1,113 @@
+// Input 2: Parsing What the User Typed
+// Demonstrates str::parse, the errors it returns, and handling bad lines without crashing
+//
+// Concepts: parse, FromStr, turbofish, ParseIntError, IntErrorKind, stderr
+// Difficulty: beginner
+// Minutes: 10
+//
+// Everything read from stdin is text. To get a number out of it, call
+// parse(), which works for every type that implements FromStr: integers,
+// floats, bool, char, IpAddr, and your own types. Rust has to know which
+// type you want, from an annotation (`let n: i32 = s.parse()?`) or a
+// turbofish (`s.parse::<i32>()`).
+//
+// parse() returns a Result, because users type all sorts of things. This
+// example first parses some awkward strings to show what goes wrong, then
+// adds up the numbers you give it, one per line, and reports each line
+// that isn't one instead of stopping at the first:
+//
+//     printf '1\n2\nthree\n\n40\n' | cargo run -p input-examples --bin 02_parsing_input
+
+use std::io::{self, BufRead};
+use std::num::{IntErrorKind, ParseIntError};
+
+use learn_rust_core::section;
+
+/// What went wrong, in words. kind() tells the errors apart, so a program
+/// can say more than "invalid digit found in string".
+fn describe(error: &ParseIntError) -> &'static str {
+    match error.kind() {
+        IntErrorKind::Empty => "there is nothing to parse",
+        IntErrorKind::InvalidDigit => "it is not a whole number",
+        IntErrorKind::PosOverflow => "it is too big for an i32",
+        IntErrorKind::NegOverflow => "it is too small for an i32",
+        // The enum is #[non_exhaustive]: new kinds may be added later
+        _ => "it is not an i32",
+    }
+}
+
+fn main() -> io::Result<()> {
+    section("what parse() makes of it");
+    // The "42\n" is what read_line hands you: parse() doesn't skip
+    // whitespace, so trim the line first
+    for text in ["42", "-7", "42\n", "", "4.5", "forty-two", "3000000000"] {
+        match text.parse::<i32>() {
+            Ok(number) => println!("{text:?} -> {number}"),
+            Err(error) => println!("{text:?} -> error: {}", describe(&error)),
+        }
+    }
+
+    section("other types");
+    let ratio: f64 = "4.5".parse().unwrap_or(0.0);
+    let verbose = "true".parse::<bool>();
+    // Every parse error can say what went wrong with to_string()
+    let letter = "xy".parse::<char>().map_err(|error| error.to_string());
+    println!("{ratio}, {verbose:?}, {letter:?}");
+
+    // This would cause an error:
+    let number: i32 = "42".parse();
+    // error[E0308]: mismatched types (expected `i32`, found `Result<_, _>`)
+
+    section("adding up stdin");
+    let mut total: i64 = 0;
+    let mut numbers = 0;
+    for (index, line) in io::stdin().lock().lines().enumerate() {
+        let line = line?;
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        match text.parse::<i32>() {
+            Ok(number) => {
+                total += i64::from(number);
+                numbers += 1;
+            }
+            // Problems go to stderr, so that `> total.txt` keeps only the
+            // answer, and the user still sees them
+            Err(error) => eprintln!(
+                "line {}: skipping {text:?}: {}",
+                index + 1,
+                describe(&error)
+            ),
+        }
+    }
+    println!("{numbers} number(s), total {total}");
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - s.parse::<T>() turns text into any T that implements FromStr
+ * - The target type comes from an annotation or a turbofish
+ * - parse() returns a Result; match on it instead of unwrap() on user input
+ * - Trim what read_line gives you: "42\n" is not a number
+ * - ParseIntError::kind() says why: empty, invalid digit, or overflow
+ * - Report a bad line on stderr and carry on, rather than giving up on all of them
+ */
+
+// EXPECTED:
+// -- what parse() makes of it --
+// "42" -> 42
+// "-7" -> -7
+// "42\n" -> error: it is not a whole number
+// "" -> error: there is nothing to parse
+// "4.5" -> error: it is not a whole number
+// "forty-two" -> error: it is not a whole number
+// "3000000000" -> error: it is too big for an i32
+// -- other types --
+// 4.5, Ok(true), Err("too many characters in string")
+// -- adding up stdin --
+// 0 number(s), total 0
+
+// Generated by `cargo xtask compile-fail` from examples/input/02_parsing_input.rs, with the
+// snippet at line 57 uncommented.
@@ -0,0 +1,87 @@
+// Macros 1: Your First macro_rules!
+// Demonstrates defining a declarative macro, matching rules, and expansion
+//
+// Concepts: macro_rules!, rules, expansion
+// Difficulty: intermediate
+// Minutes: 5
+
+// A macro_rules! macro is a list of rules: `(pattern) => { expansion }`.
+// The macro is matched against the TOKENS it is called with, before type
+// checking, and replaced by the expansion of the first rule that fits.
+macro_rules! hello {
+    // No arguments
+    () => {
+        println!("Hello from a macro!")
+    };
+    // One expression, bound to the metavariable $name
+    ($name:expr) => {
+        println!("Hello, {}!", $name)
+    };
+}
+
+// The simplest possible my_vec!: exactly three elements. (03_repetition.rs
+// makes it take any number.)
+macro_rules! my_vec3 {
+    ($a:expr, $b:expr, $c:expr) => {{
+        // Double braces: the outer pair belongs to macro_rules!, the inner
+        // pair is a block expression in the generated code, so the macro
+        // can contain statements and still produce a value.
+        let mut v = Vec::new();
+        v.push($a);
+        v.push($b);
+        v.push($c);
+        v
+    }};
+}
+
+// Why a macro instead of a function? Macros can do things functions
+// can't, like taking a variable number of arguments (println!) or using
+// the expression's source text (stringify!).
+macro_rules! show {
+    ($e:expr) => {
+        println!("{} = {:?}", stringify!($e), $e)
+    };
+}
+
+fn main() {
+    hello!();
+    hello!("Ferris");
+    hello!(String::from("macro") + "s"); // any expression works
+
+    let v = my_vec3![1, 2, 3]; // (), [] and {} all work for calling a macro
+    println!("{:?}", v);
+    // This would cause an error:
+    let v = my_vec3![1, 2];
+    // error: unexpected end of macro invocation
+
+    show!(2 + 3 * 4);
+    show!(v.len());
+    show!("abc".to_uppercase());
+
+    // The expansion is checked AFTER it's generated, so type errors point
+    // at the code the macro produced
+    // This would cause an error:
+    // let v: Vec<i32> = my_vec3![1, "two", 3];
+    // error[E0308]: mismatched types
+}
+
+/*
+ * Key Concepts:
+ * - macro_rules! defines rules: (pattern) => { expansion }
+ * - $name:expr captures an expression from the call site
+ * - The first matching rule wins; no match is a compile error
+ * - {{ ... }} lets a macro expand to a block that produces a value
+ * - stringify!($e) turns tokens into a string literal
+ */
+
+// EXPECTED:
+// Hello from a macro!
+// Hello, Ferris!
+// Hello, macros!
+// [1, 2, 3]
+// 2 + 3 * 4 = 14
+// v.len() = 3
+// "abc".to_uppercase() = "ABC"
+
+// Generated by `cargo xtask compile-fail` from examples/macros/01_first_macro.rs, with the
+// snippet at line 53 uncommented.
@@ -0,0 +1,108 @@
+// Macros 4: Recursive Macros
+// Demonstrates macros that call themselves, and token-tree munching
+//
+// Concepts: recursive macros, token-tree munching
+// Difficulty: advanced
+// Minutes: 10
+
+// max! of any number of values: peel off one value per step
+macro_rules! max {
+    ($x:expr) => { $x };
+    ($x:expr, $($rest:expr),+) => {{
+        let a = $x;
+        let b = max!($($rest),+);
+        if a > b { a } else { b }
+    }};
+}
+
+// A "tt muncher" processes its input a few tokens at a time, carrying the
+// result so far in brackets. This one turns `1 plus 2 times 3` into a
+// left-to-right calculation.
+macro_rules! calc {
+    // Done: only the accumulator is left
+    (@acc [$acc:expr]) => { $acc };
+    (@acc [$acc:expr] plus $n:literal $($rest:tt)*) => {
+        calc!(@acc [$acc + $n] $($rest)*)
+    };
+    (@acc [$acc:expr] times $n:literal $($rest:tt)*) => {
+        calc!(@acc [($acc) * $n] $($rest)*)
+    };
+    (@acc [$acc:expr] minus $n:literal $($rest:tt)*) => {
+        calc!(@acc [$acc - $n] $($rest)*)
+    };
+    // Entry point: the first number starts the accumulator.
+    // `@acc` is just a marker token that keeps internal rules apart from
+    // what users are meant to type.
+    ($n:literal $($rest:tt)*) => {
+        calc!(@acc [$n] $($rest)*)
+    };
+}
+
+// Recursion can also generate items: one enum with a name() per variant
+macro_rules! named_enum {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        #[derive(Debug, Clone, Copy)]
+        enum $name {
+            $($variant),*
+        }
+
+        impl $name {
+            const ALL: &'static [$name] = &[$($name::$variant),*];
+
+            fn name(&self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant)),*
+                }
+            }
+        }
+    };
+}
+
+named_enum!(Planet {
+    Mercury,
+    Venus,
+    Earth,
+    Mars,
+});
+
+fn main() {
+    println!("max!(3) = {}", max!(3));
+    println!("max!(3, 9, 4, 1) = {}", max!(3, 9, 4, 1));
+    println!("max!(\"pear\", \"apple\") = {}", max!("pear", "apple"));
+
+    println!("1 plus 2 times 3 = {}", calc!(1 plus 2 times 3));
+    println!("10 minus 4 plus 1 = {}", calc!(10 minus 4 plus 1));
+
+    for planet in Planet::ALL {
+        println!("{:?} is called {:?}", planet, planet.name());
+    }
+
+    // Recursion has a limit (128 levels by default), so a macro that never
+    // reaches its base case fails to compile instead of running forever.
+    // This would cause an error:
+    macro_rules! forever { () => { forever!() }; } forever!();
+    // error: recursion limit reached while expanding `forever!`
+}
+
+/*
+ * Key Concepts:
+ * - A macro may call itself, consuming part of its input each time
+ * - The base case rule must come first (or be distinct) to stop recursion
+ * - tt munchers walk token streams, carrying state in brackets
+ * - Internal rules are often marked with a token like @acc
+ * - Macros can generate whole items: enums, impls, functions
+ */
+
+// EXPECTED:
+// max!(3) = 3
+// max!(3, 9, 4, 1) = 9
+// max!("pear", "apple") = pear
+// 1 plus 2 times 3 = 9
+// 10 minus 4 plus 1 = 7
+// Mercury is called "Mercury"
+// Venus is called "Venus"
+// Earth is called "Earth"
+// Mars is called "Mars"
+
+// Generated by `cargo xtask compile-fail` from examples/macros/04_recursive_macros.rs, with the
+// snippet at line 82 uncommented.
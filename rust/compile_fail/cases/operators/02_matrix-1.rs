@@ -0,0 +1,355 @@
+// Operators 2: A Matrix with Index, *, and for Loops
+// Demonstrates indexing with a tuple, operators on references, Display with precision, FromStr, and IntoIterator
+//
+// Concepts: Index, IndexMut, operators on &T, IntoIterator for &T, Formatter precision, FromStr errors
+// Difficulty: intermediate
+// Minutes: 15
+//
+// `m[(row, col)]` is `*Index::index(&m, (row, col))`: the index can be any
+// type, and a tuple reads well for two dimensions. IndexMut adds
+// `m[(row, col)] = x`. Both return references into the matrix, and
+// panic for an index that is out of bounds, the way a slice does.
+//
+// A matrix isn't Copy, so `a * b` with `Mul for Matrix` would move both
+// of them into the multiplication, and neither could be used again. The
+// usual answer is to implement the operator for references, `&a * &b`,
+// and, if it's convenient, for values too, forwarding to the reference
+// impl. std does it for its numbers, which is why `&1 + &2` compiles, and
+// crates like num-bigint do it for the types that aren't Copy.
+//
+// `for row in &m` calls `IntoIterator::into_iter(&m)`. Implementing
+// IntoIterator for `&Matrix` is what makes that loop work, the same way
+// `for x in &vec` works, and there is no need to write an iterator type:
+// the rows are chunks of the data, and `slice::Chunks` already is one.
+//
+// In Java this would be `a.times(b)` and `m.get(row, col)`, in Go
+// `a.Mul(b)` and `m.At(row, col)`, which is how gonum spells it.
+
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Matrix {
+    rows: usize,
+    cols: usize,
+    /// Row after row, `cols` numbers each.
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    fn zeros(rows: usize, cols: usize) -> Matrix {
+        Matrix {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    fn identity(size: usize) -> Matrix {
+        let mut matrix = Matrix::zeros(size, size);
+        for i in 0..size {
+            matrix[(i, i)] = 1.0;
+        }
+        matrix
+    }
+
+    fn transpose(&self) -> Matrix {
+        let mut result = Matrix::zeros(self.cols, self.rows);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                result[(col, row)] = self[(row, col)];
+            }
+        }
+        result
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        assert!(
+            row < self.rows && col < self.cols,
+            "index ({row}, {col}) out of bounds for a {}x{} matrix",
+            self.rows,
+            self.cols
+        );
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        assert!(
+            row < self.rows && col < self.cols,
+            "index ({row}, {col}) out of bounds for a {}x{} matrix",
+            self.rows,
+            self.cols
+        );
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+impl Add for &Matrix {
+    type Output = Matrix;
+
+    fn add(self, other: &Matrix) -> Matrix {
+        assert_eq!(
+            (self.rows, self.cols),
+            (other.rows, other.cols),
+            "adding matrices of different sizes"
+        );
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(a, b)| a + b)
+            .collect();
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data,
+        }
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, other: &Matrix) -> Matrix {
+        assert_eq!(
+            self.cols, other.rows,
+            "multiplying a {}x{} matrix by a {}x{} one",
+            self.rows, self.cols, other.rows, other.cols
+        );
+        let mut result = Matrix::zeros(self.rows, other.cols);
+        for row in 0..self.rows {
+            for col in 0..other.cols {
+                result[(row, col)] = (0..self.cols)
+                    .map(|k| self[(row, k)] * other[(k, col)])
+                    .sum();
+            }
+        }
+        result
+    }
+}
+
+/// For `a * b` on values, which moves both: see the broken code in main.
+impl Mul for Matrix {
+    type Output = Matrix;
+
+    fn mul(self, other: Matrix) -> Matrix {
+        &self * &other
+    }
+}
+
+/// Scaling, `&m * 2.0`.
+impl Mul<f64> for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, factor: f64) -> Matrix {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|x| x * factor).collect(),
+        }
+    }
+}
+
+/// `for row in &matrix` yields each row as a slice.
+impl<'a> IntoIterator for &'a Matrix {
+    type Item = &'a [f64];
+    type IntoIter = std::slice::Chunks<'a, f64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // chunks(0) panics, and a matrix with no columns has no rows to give
+        self.data.chunks(self.cols.max(1))
+    }
+}
+
+/// One row per line, in columns as wide as the widest number. The
+/// precision defaults to 1 decimal, and `{:.3}` asks for 3.
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(1);
+        let cells: Vec<String> = self
+            .data
+            .iter()
+            .map(|x| format!("{x:.precision$}"))
+            .collect();
+        let width = cells.iter().map(String::len).max().unwrap_or(0);
+        for (i, row) in cells.chunks(self.cols.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let row: Vec<String> = row.iter().map(|cell| format!("{cell:>width$}")).collect();
+            write!(f, "[{}]", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum ParseMatrixError {
+    Empty,
+    BadNumber {
+        row: usize,
+        col: usize,
+        text: String,
+    },
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseMatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMatrixError::Empty => write!(f, "a matrix needs at least one number"),
+            ParseMatrixError::BadNumber { row, col, text } => {
+                write!(f, "row {row}, column {col}: {text:?} is not a number")
+            }
+            ParseMatrixError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => {
+                write!(f, "row {row}: expected {expected} numbers, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMatrixError {}
+
+/// Rows separated by `;`, numbers by whitespace: "1 2; 3 4".
+impl FromStr for Matrix {
+    type Err = ParseMatrixError;
+
+    fn from_str(text: &str) -> Result<Matrix, ParseMatrixError> {
+        let mut data = Vec::new();
+        let mut cols = 0;
+        let lines: Vec<&str> = text.split(';').collect();
+        for (i, line) in lines.iter().enumerate() {
+            let mut found = 0;
+            for (j, word) in line.split_whitespace().enumerate() {
+                let number = word.parse().map_err(|_| ParseMatrixError::BadNumber {
+                    row: i + 1,
+                    col: j + 1,
+                    text: word.to_string(),
+                })?;
+                data.push(number);
+                found += 1;
+            }
+            if i == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(ParseMatrixError::RaggedRow {
+                    row: i + 1,
+                    expected: cols,
+                    found,
+                });
+            }
+        }
+        if data.is_empty() {
+            return Err(ParseMatrixError::Empty);
+        }
+        Ok(Matrix {
+            rows: lines.len(),
+            cols,
+            data,
+        })
+    }
+}
+
+fn main() {
+    println!("-- indexing --");
+    let mut m: Matrix = "1 2 3; 4 5 6".parse().unwrap();
+    println!("{}x{}, m[(1, 2)] = {}", m.rows, m.cols, m[(1, 2)]);
+    m[(0, 0)] = 10.0;
+    println!("{m}");
+    // m[(2, 0)] would panic: "index (2, 0) out of bounds for a 2x3 matrix"
+
+    println!("-- operators --");
+    let a: Matrix = "1 2; 3 4".parse().unwrap();
+    let b: Matrix = "0 1; 1 0".parse().unwrap();
+    println!("a + b =\n{}", &a + &b);
+    println!("a * b =\n{}", &a * &b);
+    println!("a * I == a: {}", &a * &Matrix::identity(2) == a);
+    println!("a * 0.5 =\n{:.2}", &a * 0.5);
+    let tall = m.transpose();
+    println!("m * mT =\n{}", &m * &tall);
+    // Values work too, with the value impl; the matrices are moved into it
+    let product = a.clone() * b.clone();
+    println!("by value: {:?}", product.data);
+
+    println!("-- iterating --");
+    for row in &m {
+        println!("{row:?}, sum {}", row.iter().sum::<f64>());
+    }
+    let biggest = (&m).into_iter().flatten().fold(f64::MIN, |a, &b| a.max(b));
+    println!("biggest: {biggest}");
+
+    println!("-- parsing --");
+    for text in ["1 2; 3 4", "1.5 -2e3", "1 2; 3", "1 x; 3 4", "  "] {
+        match text.parse::<Matrix>() {
+            Ok(matrix) => println!(
+                "{text:?} -> {}x{} {:?}",
+                matrix.rows, matrix.cols, matrix.data
+            ),
+            Err(error) => println!("{text:?} -> error: {error}"),
+        }
+    }
+
+    // This would cause an error:
+    let c = a * b;
+    println!("{c} came from {a}");
+    // error[E0382]: borrow of moved value: `a`
+}
+
+/*
+ * Key Concepts:
+ * - Index<Idx> lets m[idx] take any index type; IndexMut adds assignment
+ * - Index returns a reference, and panics when out of bounds, like slices do
+ * - Implement operators on &T for non-Copy types, so a * b doesn't move them
+ * - IntoIterator for &T makes `for x in &t` work; reuse std's iterators
+ * - Formatter::precision() lets Display honour {:.3}
+ * - FromStr errors can say exactly where the input went wrong
+ */
+
+// EXPECTED:
+// -- indexing --
+// 2x3, m[(1, 2)] = 6
+// [10.0  2.0  3.0]
+// [ 4.0  5.0  6.0]
+// -- operators --
+// a + b =
+// [1.0 3.0]
+// [4.0 4.0]
+// a * b =
+// [2.0 1.0]
+// [4.0 3.0]
+// a * I == a: true
+// a * 0.5 =
+// [0.50 1.00]
+// [1.50 2.00]
+// m * mT =
+// [113.0  68.0]
+// [ 68.0  77.0]
+// by value: [2.0, 1.0, 4.0, 3.0]
+// -- iterating --
+// [10.0, 2.0, 3.0], sum 15
+// [4.0, 5.0, 6.0], sum 15
+// biggest: 10
+// -- parsing --
+// "1 2; 3 4" -> 2x2 [1.0, 2.0, 3.0, 4.0]
+// "1.5 -2e3" -> 1x2 [1.5, -2000.0]
+// "1 2; 3" -> error: row 2: expected 2 numbers, found 1
+// "1 x; 3 4" -> error: row 1, column 2: "x" is not a number
+// "  " -> error: a matrix needs at least one number
+
+// Generated by `cargo xtask compile-fail` from examples/operators/02_matrix.rs, with the
+// snippet at line 307 uncommented.
@@ -0,0 +1,151 @@
+// Process 2: Streaming Output, Line by Line
+// Demonstrates spawn(), piped stdout and stdin, reading lines as they arrive, and kill()
+//
+// Concepts: spawn, Child, Stdio::piped, BufReader, try_wait, wait, kill, deadlocks
+// Difficulty: intermediate
+// Minutes: 15
+//
+// output() hands over everything at the end. For a program that runs for
+// a while (a build, a download, a server), you want each line as soon as
+// it is printed. spawn() starts the program and returns a Child at once;
+// with stdout set to Stdio::piped(), child.stdout is the reading end of a
+// pipe, and a BufReader gives you its lines as the child writes them.
+//
+// The same works the other way: with Stdio::piped() for stdin, whatever
+// we write to child.stdin, the child reads. Closing it (by dropping it) is
+// how the child learns that the input has ended.
+//
+// A spawned child must be waited for with wait(): that collects its exit
+// status, and until then it lingers in the process table. And a child that
+// never ends can be ended with kill().
+
+use std::env;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+/// What this program does when another copy of it starts it as a child.
+fn child(role: &str) -> io::Result<()> {
+    match role {
+        // A slow program: a line, then a pause, three times
+        "ticks" => {
+            for tick in 1..=3 {
+                println!("tick {tick}");
+                thread::sleep(Duration::from_millis(200));
+            }
+        }
+        // A program that never stops by itself
+        "forever" => {
+            for tick in 1.. {
+                println!("tick {tick}");
+                thread::sleep(Duration::from_millis(20));
+            }
+        }
+        // Reads lines until its input ends, and shouts them back
+        "shout" => {
+            for line in io::stdin().lock().lines() {
+                println!("{}!", line?.to_uppercase());
+            }
+        }
+        _ => unreachable!("no child role {role}"),
+    }
+    Ok(())
+}
+
+fn this_program(role: &str) -> io::Result<Command> {
+    let mut command = Command::new(env::current_exe()?);
+    command.args(["child", role]);
+    Ok(command)
+}
+
+fn main() -> io::Result<()> {
+    if let (Some("child"), Some(role)) = (env::args().nth(1).as_deref(), env::args().nth(2)) {
+        return child(&role);
+    }
+
+    println!("Reading lines as they arrive:");
+    let mut child = this_program("ticks")?.stdout(Stdio::piped()).spawn()?;
+    // take() moves the pipe out of the Child, so that it can be read while
+    // `child` is still used to wait
+    let stdout = child.stdout.take().expect("stdout is piped");
+
+    // This would cause an error:
+    let reader = BufReader::new(child.stdout);
+    // error[E0277]: the trait bound `Option<ChildStdout>: std::io::Read` is not satisfied
+    // child.stdout is only Some when stdout was piped, hence the take()
+
+    for line in BufReader::new(stdout).lines() {
+        let line = line?;
+        // try_wait() asks without blocking: None means it's still running
+        let running = child.try_wait()?.is_none();
+        println!("  got {line:?}, child still running: {running}");
+    }
+    // The lines end when the child closes its stdout, usually by exiting
+    let status = child.wait()?;
+    println!("  finished: {}", status.success());
+
+    println!("\nWriting to a child's stdin:");
+    let mut child = this_program("shout")?
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    {
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        writeln!(stdin, "hello")?;
+        writeln!(stdin, "is anybody there")?;
+        // stdin is dropped here, which closes the pipe: the child sees the
+        // end of its input and can finish
+    }
+    // Write everything, then read. A child that writes a lot before it has
+    // read all its input would fill its stdout pipe and wait for us to
+    // read, while we wait for it to read: a deadlock. For big inputs,
+    // write from another thread while this one reads.
+    let output = child.wait_with_output()?;
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    println!("\nStopping a child that never ends:");
+    let mut child = this_program("forever")?.stdout(Stdio::piped()).spawn()?;
+    let mut lines = BufReader::new(child.stdout.take().expect("stdout is piped")).lines();
+    for line in lines.by_ref().take(3) {
+        println!("  got {:?}", line?);
+    }
+    // Our end of the pipe stays open until it's killed: if we closed it
+    // first, the child's next println! would fail, and it would panic
+    child.kill()?;
+    // Still wait(), to collect the status of the child we killed
+    let status = child.wait()?;
+    drop(lines);
+    println!("  killed it; success: {}", status.success());
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - spawn() starts a program and returns a Child without waiting for it
+ * - Stdio::piped() connects its stdin or stdout to us; take() them out of the Child
+ * - BufReader::new(stdout).lines() yields each line as soon as it is written
+ * - Dropping child.stdin closes it, which is the child's end of input
+ * - Always wait() for a spawned child, even after kill()
+ * - Two full pipes waiting on each other deadlock; write and read from separate threads
+ */
+
+// EXPECTED:
+// Reading lines as they arrive:
+//   got "tick 1", child still running: true
+//   got "tick 2", child still running: true
+//   got "tick 3", child still running: true
+//   finished: true
+//
+// Writing to a child's stdin:
+// HELLO!
+// IS ANYBODY THERE!
+//
+// Stopping a child that never ends:
+//   got "tick 1"
+//   got "tick 2"
+//   got "tick 3"
+//   killed it; success: false
+
+// Generated by `cargo xtask compile-fail` from examples/process/02_streaming_output.rs, with the
+// snippet at line 73 uncommented.
@@ -0,0 +1,98 @@
+// Smart Pointers 2: Rc<T>
+// Demonstrates shared ownership with reference counting
+//
+// Concepts: Rc, reference counting, shared ownership
+// Difficulty: intermediate
+// Minutes: 10
+
+use std::rc::Rc;
+
+#[derive(Debug)]
+struct Config {
+    name: String,
+}
+
+// Two services that both need to keep the same config alive
+struct Service {
+    label: &'static str,
+    config: Rc<Config>,
+}
+
+fn main() {
+    // Normally a value has exactly one owner. Rc ("reference counted")
+    // allows several: the value is freed when the LAST owner is dropped.
+    let config = Rc::new(Config {
+        name: String::from("production"),
+    });
+    println!("owners after creating: {}", Rc::strong_count(&config));
+
+    // Rc::clone doesn't copy the Config; it bumps the count
+    let web = Service {
+        label: "web",
+        config: Rc::clone(&config),
+    };
+    let worker = Service {
+        label: "worker",
+        config: Rc::clone(&config),
+    };
+    println!("owners with two services: {}", Rc::strong_count(&config));
+
+    for service in [&web, &worker] {
+        println!("{} uses {}", service.label, service.config.name);
+    }
+    // Both point at the very same allocation
+    println!("same Config? {}", Rc::ptr_eq(&web.config, &worker.config));
+
+    drop(web);
+    println!("owners after dropping web: {}", Rc::strong_count(&config));
+
+    // Rc only gives shared (read-only) access
+    // This would cause an error:
+    config.name.push_str("-2");
+    // error[E0596]: cannot borrow data in an `Rc` as mutable
+
+    // Rc is single-threaded: its count isn't atomic, so it can't be sent
+    // to another thread. Use Arc for that (see the concurrency chapter).
+    drop(worker);
+    println!("owners at the end: {}", Rc::strong_count(&config));
+    println!("config: {:?}", config);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_one_allocation_and_count_owners() {
+        let config = Rc::new(Config {
+            name: String::from("test"),
+        });
+        let copies: Vec<Rc<Config>> = (0..3).map(|_| Rc::clone(&config)).collect();
+        assert_eq!(Rc::strong_count(&config), 4);
+        assert!(copies.iter().all(|copy| Rc::ptr_eq(copy, &config)));
+
+        drop(copies);
+        assert_eq!(Rc::strong_count(&config), 1);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Rc<T> lets several owners share one value
+ * - Rc::clone increments a count; dropping an Rc decrements it
+ * - The value is freed when the count reaches zero
+ * - Rc gives shared access only, and only within one thread
+ */
+
+// EXPECTED:
+// owners after creating: 1
+// owners with two services: 3
+// web uses production
+// worker uses production
+// same Config? true
+// owners after dropping web: 2
+// owners at the end: 1
+// config: Config { name: "production" }
+
+// Generated by `cargo xtask compile-fail` from examples/smart_pointers/02_rc.rs, with the
+// snippet at line 50 uncommented.
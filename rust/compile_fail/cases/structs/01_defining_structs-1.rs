@@ -0,0 +1,107 @@
+// Structs 1: Defining and Creating Structs
+// Demonstrates named-field, tuple, and unit structs, and update syntax
+//
+// Concepts: named-field structs, tuple structs, unit structs, update syntax
+// Difficulty: beginner
+// Minutes: 10
+
+// A struct groups related values under one name. Each field has a type.
+#[derive(Debug, Clone)]
+struct User {
+    username: String,
+    email: String,
+    active: bool,
+    login_count: u64,
+}
+
+// Tuple structs: fields have no names, only positions. Useful for giving
+// a plain value its own type, so a Meters can't be mixed up with Seconds.
+#[derive(Debug, Clone, Copy)]
+struct Meters(f64);
+#[derive(Debug, Clone, Copy)]
+struct Seconds(f64);
+
+// Unit structs have no fields at all. They are useful as markers, or as
+// something to implement a trait on.
+#[derive(Debug)]
+struct AlwaysEqual;
+
+fn speed(distance: Meters, time: Seconds) -> f64 {
+    distance.0 / time.0 // tuple struct fields are .0, .1, ...
+}
+
+fn new_user(username: String, email: String) -> User {
+    // Field init shorthand: `username` means `username: username`
+    User {
+        username,
+        email,
+        active: true,
+        login_count: 0,
+    }
+}
+
+fn main() {
+    let mut user = new_user(String::from("ferris"), String::from("ferris@example.com"));
+    // Dot notation reads and (for `mut` bindings) writes fields.
+    // Mutability applies to the WHOLE struct, never to single fields.
+    user.login_count += 1;
+    println!(
+        "{} <{}> logged in {} time(s)",
+        user.username, user.email, user.login_count
+    );
+
+    // Struct update syntax: new values for some fields, the rest copied
+    // (or moved!) from another instance with `..`
+    let admin = User {
+        username: String::from("admin"),
+        email: String::from("admin@example.com"),
+        ..user.clone()
+    };
+    println!("{:?}", admin);
+
+    // {:#?} pretty-prints a Debug struct over several lines
+    println!("{:#?}", user);
+
+    let distance = Meters(100.0);
+    let time = Seconds(9.58);
+    println!("{:.2} m/s", speed(distance, time));
+    // This would cause an error:
+    println!("{:.2} m/s", speed(time, distance));
+    // error[E0308]: arguments to this function are incorrect
+
+    // Destructuring a tuple struct
+    let Meters(raw) = distance;
+    println!("raw value: {}", raw);
+    println!(
+        "unit struct: {:?} ({} bytes)",
+        AlwaysEqual,
+        std::mem::size_of::<AlwaysEqual>()
+    );
+    println!("{} is active: {}", admin.username, admin.active);
+}
+
+/*
+ * Key Concepts:
+ * - Structs with named fields group related data
+ * - Field init shorthand and `..other` update syntax cut repetition
+ * - Tuple structs (`struct Meters(f64)`) give plain values distinct types
+ * - Unit structs have no data
+ * - #[derive(Debug)] lets you print a struct with {:?} or {:#?}
+ */
+
+// EXPECTED:
+// ferris <ferris@example.com> logged in 1 time(s)
+// User { username: "admin", email: "admin@example.com", active: true, login_count: 1 }
+// User {
+//     username: "ferris",
+//     email: "ferris@example.com",
+//     active: true,
+//     login_count: 1,
+// }
+// 10.44 m/s
+// raw value: 100
+// unit struct: AlwaysEqual (0 bytes)
+// admin is active: true
+
+// Generated by `cargo xtask compile-fail` from examples/structs/01_defining_structs.rs, with the
+// snippet at line 68 uncommented.
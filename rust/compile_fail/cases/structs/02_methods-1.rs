@@ -0,0 +1,109 @@
+// Structs 2: Methods and Associated Functions
+// Demonstrates impl blocks, &self / &mut self / self, and constructors
+//
+// Concepts: impl blocks, &self, &mut self, self, constructors
+// Difficulty: beginner
+// Minutes: 10
+
+#[derive(Debug)]
+struct Counter {
+    name: String,
+    count: u32,
+    step: u32,
+}
+
+// Everything in an `impl` block belongs to the type
+impl Counter {
+    // An associated function has no `self`: call it as Counter::new(...).
+    // `new` is the conventional name for a constructor.
+    fn new(name: &str) -> Counter {
+        Counter::with_step(name, 1)
+    }
+
+    fn with_step(name: &str, step: u32) -> Self {
+        // Inside an impl, `Self` is another name for the type
+        Self {
+            name: name.to_string(),
+            count: 0,
+            step,
+        }
+    }
+
+    // &self: borrows the counter to READ it
+    fn value(&self) -> u32 {
+        self.count
+    }
+
+    // &mut self: borrows the counter to CHANGE it
+    fn tick(&mut self) {
+        self.count += self.step;
+    }
+
+    // Returning &mut Self lets calls be chained
+    fn tick_by(&mut self, times: u32) -> &mut Self {
+        for _ in 0..times {
+            self.tick();
+        }
+        self
+    }
+
+    // self: takes ownership; the counter can't be used afterwards
+    fn finish(self) -> String {
+        format!("{} finished at {}", self.name, self.count)
+    }
+}
+
+// A type can have several impl blocks; this is handy for grouping
+impl Counter {
+    fn is_past(&self, other: &Counter) -> bool {
+        self.count > other.count
+    }
+}
+
+fn main() {
+    let mut clicks = Counter::new("clicks");
+    clicks.tick();
+    clicks.tick();
+    // Method calls borrow automatically: clicks.value() is really
+    // Counter::value(&clicks)
+    println!("{} = {}", clicks.name, clicks.value());
+    println!("same call, spelled out: {}", Counter::value(&clicks));
+
+    let mut laps = Counter::with_step("laps", 10);
+    laps.tick_by(3).tick_by(1);
+    println!("{:?}", laps);
+    println!("laps past clicks? {}", laps.is_past(&clicks));
+
+    let summary = clicks.finish();
+    println!("{}", summary);
+    // This would cause an error:
+    clicks.tick();
+    // error[E0382]: borrow of moved value: `clicks`
+
+    // Calling a &mut self method needs a mutable binding
+    let frozen = Counter::new("frozen");
+    // This would cause an error:
+    // frozen.tick();
+    // error[E0596]: cannot borrow `frozen` as mutable, as it is not declared as mutable
+    println!("{}", frozen.finish());
+}
+
+/*
+ * Key Concepts:
+ * - impl blocks attach functions and methods to a type
+ * - Associated functions (no self) are called with Type::name()
+ * - &self reads, &mut self modifies, self consumes
+ * - Self is an alias for the type inside its impl
+ * - Returning &mut Self enables method chaining
+ */
+
+// EXPECTED:
+// clicks = 2
+// same call, spelled out: 2
+// Counter { name: "laps", count: 40, step: 10 }
+// laps past clicks? true
+// clicks finished at 2
+// frozen finished at 0
+
+// Generated by `cargo xtask compile-fail` from examples/structs/02_methods.rs, with the
+// snippet at line 79 uncommented.
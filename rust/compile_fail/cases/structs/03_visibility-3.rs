@@ -0,0 +1,108 @@
+// Structs 3: Modules and Visibility
+// Demonstrates mod, pub, private fields, pub(crate), and use paths
+//
+// Concepts: mod, pub, private fields, pub(crate), use paths
+// Difficulty: beginner
+// Minutes: 10
+
+// A module is a named namespace for items. Everything inside is PRIVATE
+// to the module unless marked `pub`.
+mod bank {
+    // The struct is public, but its fields are not: code outside this
+    // module can't touch `balance` directly, so it can't go negative.
+    #[derive(Debug)]
+    pub struct Account {
+        pub owner: String, // anyone can read and write this
+        balance: u64,      // only code in `bank` can see this
+    }
+
+    impl Account {
+        // With private fields, outside code can't write `Account { .. }`,
+        // so a public constructor is the only way to make one
+        pub fn open(owner: &str) -> Account {
+            Account {
+                owner: owner.to_string(),
+                balance: 0,
+            }
+        }
+
+        pub fn balance(&self) -> u64 {
+            self.balance
+        }
+
+        pub fn deposit(&mut self, amount: u64) {
+            self.balance += amount;
+            audit::log(&self.owner, "deposit", amount);
+        }
+
+        pub fn withdraw(&mut self, amount: u64) -> Result<(), String> {
+            if amount > self.balance {
+                return Err(format!("insufficient funds: {} < {}", self.balance, amount));
+            }
+            self.balance -= amount;
+            audit::log(&self.owner, "withdraw", amount);
+            Ok(())
+        }
+    }
+
+    // Modules nest. `audit` is private to `bank`...
+    mod audit {
+        // ...and pub(super) makes `log` visible to the parent module only
+        pub(super) fn log(owner: &str, action: &str, amount: u64) {
+            println!("  [audit] {} {} {}", owner, action, amount);
+        }
+    }
+
+    // pub(crate): usable anywhere in this crate, but not by other crates
+    pub(crate) fn bank_name() -> &'static str {
+        "Ferris Savings"
+    }
+}
+
+// `use` brings a path into scope so you don't repeat it
+use bank::Account;
+
+fn main() {
+    println!("Welcome to {}", bank::bank_name());
+
+    let mut account = Account::open("ferris");
+    account.deposit(100);
+    match account.withdraw(250) {
+        Ok(()) => println!("withdrew 250"),
+        Err(message) => println!("error: {}", message),
+    }
+    account.withdraw(30).unwrap();
+    account.owner.push_str(" the crab"); // public field: fine
+    println!("{} has {}", account.owner, account.balance());
+
+    // This would cause an error:
+    // account.balance = 1_000_000;
+    // error[E0616]: field `balance` of struct `Account` is private
+
+    // This would cause an error:
+    // let forged = Account { owner: String::from("mallory"), balance: 5 };
+    // error[E0451]: field `balance` of struct `Account` is private
+
+    // This would cause an error:
+    bank::audit::log("mallory", "deposit", 5);
+    // error[E0603]: module `audit` is private
+}
+
+/*
+ * Key Concepts:
+ * - `mod` creates a namespace; items are private by default
+ * - `pub` exposes an item; struct fields need their own `pub`
+ * - Private fields + public methods protect invariants
+ * - pub(crate) and pub(super) give in-between visibility
+ * - `use` shortens paths; `::` separates path segments
+ */
+
+// EXPECTED:
+// Welcome to Ferris Savings
+//   [audit] ferris deposit 100
+// error: insufficient funds: 100 < 250
+//   [audit] ferris withdraw 30
+// ferris the crab has 70
+
+// Generated by `cargo xtask compile-fail` from examples/structs/03_visibility.rs, with the
+// snippet at line 86 uncommented.
@@ -0,0 +1,117 @@
+// Unsafe 3: transmute and Its Pitfalls
+// Demonstrates reinterpreting bits, and the safe functions to use instead
+//
+// Concepts: transmute, bit reinterpretation, safe alternatives
+// Difficulty: advanced
+// Minutes: 10
+//
+// `std::mem::transmute::<A, B>(a)` takes the bits of `a` and pretends they
+// are a `B`. The only thing the compiler checks is that A and B are the
+// same size. Everything else (is every bit pattern a valid B? do the
+// lifetimes still hold?) is on you, which makes transmute the most
+// dangerous function in the standard library.
+//
+// Check this file for undefined behavior with:
+//   cargo +nightly miri run -p unsafe-examples --bin 03_transmute_pitfalls
+
+use std::mem;
+
+fn main() {
+    // ===== A legitimate use: looking at the bits of a float =====
+    let x = 1.5f32;
+    // SAFETY: f32 and u32 are both 4 bytes, and every bit pattern is a
+    // valid u32.
+    // (The compiler warns that this transmute is unnecessary, and it is
+    // right; we silence the warning only to show the transmute itself.)
+    #[allow(unnecessary_transmutes)]
+    let bits: u32 = unsafe { mem::transmute(x) };
+    println!("1.5f32 as bits: {:#034b}", bits);
+    // ...but there is a safe method that does exactly this:
+    assert_eq!(bits, x.to_bits());
+    println!("to_bits() gives the same: {}", bits == x.to_bits());
+
+    // ===== Bytes to integers: endianness =====
+    let bytes = [0x01, 0x00, 0x00, 0x00];
+    // SAFETY: [u8; 4] and u32 are both 4 bytes, and any bytes make a u32
+    #[allow(unnecessary_transmutes)]
+    let number: u32 = unsafe { mem::transmute(bytes) };
+    // The answer depends on the machine: 1 on little-endian (x86, ARM),
+    // 16777216 on big-endian. The safe functions make you say which:
+    println!("transmuted: {}", number);
+    println!("from_le_bytes: {}", u32::from_le_bytes(bytes));
+    println!("from_be_bytes: {}", u32::from_be_bytes(bytes));
+
+    // ===== Pitfall 1: sizes must match =====
+    // This would cause an error:
+    let wide: u64 = unsafe { mem::transmute(7u32) };
+    // error[E0512]: cannot transmute between types of different sizes, or dependently-sized types
+
+    // ===== Pitfall 2: invalid values =====
+    // A bool must be exactly 0 or 1. Transmuting the byte 2 into a bool
+    // compiles, and is undefined behavior the moment it happens:
+    //   let b: bool = unsafe { mem::transmute(2u8) };
+    //   Miri: Undefined Behavior: constructing invalid value of type bool:
+    //   encountered 0x02, but expected a boolean
+    // The same goes for enums (an out-of-range discriminant), char (a
+    // surrogate or a value past 0x10FFFF), and references (null). Safe
+    // conversions check instead:
+    let byte = 2u8;
+    let as_bool = match byte {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    };
+    println!("{} as a bool: {:?}", byte, as_bool);
+    println!("0xD800 as a char: {:?}", char::from_u32(0xD800));
+
+    // ===== Pitfall 3: inventing lifetimes =====
+    // transmute can turn &'a T into &'static T. The types line up, so it
+    // compiles, but the reference still dies with its value:
+    //   fn forever(s: &str) -> &'static str { unsafe { mem::transmute(s) } }
+    //   let dangling = forever(&String::from("gone"));
+    //   println!("{}", dangling); // Miri: ... a dangling reference (use-after-free)
+    // Things that really do live forever, like string literals, already
+    // have 'static references, no transmute needed. (So does memory you
+    // leak on purpose with String::leak, though Miri will report the leak.)
+    let forever: &'static str = "string literals live forever";
+    println!("{}", forever);
+
+    // ===== Pitfall 4: & to &mut =====
+    // Transmuting &T into &mut T is ALWAYS undefined behavior, even if you
+    // never write through it. Shared data that needs mutation belongs in a
+    // Cell or RefCell (see the smart_pointers chapter).
+
+    // Before reaching for transmute, look for: to_bits/from_bits,
+    // to_le_bytes/from_le_bytes, `as` casts, char::from_u32, TryFrom,
+    // and pointer casts (`ptr as *const U`).
+    println!("\nsize_of::<f32>() = {}", mem::size_of::<f32>());
+    println!(
+        "size_of::<Option<&u8>>() = {}",
+        mem::size_of::<Option<&u8>>()
+    );
+}
+
+/*
+ * Key Concepts:
+ * - transmute reinterprets bits; the compiler only checks the sizes
+ * - Every bit pattern must be a valid value of the target type
+ * - transmute can forge lifetimes and &mut, both of which are UB traps
+ * - Safe alternatives exist for almost every real use
+ * - Miri reports invalid values the moment they are created
+ */
+
+// EXPECTED:
+// 1.5f32 as bits: 0b00111111110000000000000000000000
+// to_bits() gives the same: true
+// transmuted: 1
+// from_le_bytes: 1
+// from_be_bytes: 16777216
+// 2 as a bool: None
+// 0xD800 as a char: None
+// string literals live forever
+//
+// size_of::<f32>() = 4
+// size_of::<Option<&u8>>() = 8
+
+// Generated by `cargo xtask compile-fail` from examples/unsafe/03_transmute_pitfalls.rs, with the
+// snippet at line 45 uncommented.
@@ -0,0 +1,2 @@
+//! Nothing to see here: this package is its tests. `tests/compile_fail.rs`
+//! runs the cases in `cases/` with trybuild; see `Cargo.toml`.
@@ -0,0 +1,59 @@
+// Every program in cases/ is an example with one of its broken snippets
+// uncommented. The first test passes only if each one fails to compile
+// with the errors in its .stderr file; the second, only if those errors
+// include the one the example's comment documents.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Manifest {
+    case: Vec<Case>,
+}
+
+#[derive(Deserialize)]
+struct Case {
+    name: String,
+    example: String,
+    line: usize,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+#[test]
+fn broken_snippets_do_not_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("cases/**/*.rs");
+}
+
+#[test]
+fn every_case_fails_with_its_documented_error() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let manifest: Manifest =
+        toml::from_str(&fs::read_to_string(dir.join("cases.toml")).unwrap()).unwrap();
+    assert!(!manifest.case.is_empty());
+    for case in manifest.case {
+        let stderr = dir.join("cases").join(format!("{}.stderr", case.name));
+        let Ok(stderr) = fs::read_to_string(&stderr) else {
+            panic!(
+                "{} (from {} line {}) has no .stderr; record it with \
+                 `TRYBUILD=overwrite cargo test -p compile-fail`",
+                case.name, case.example, case.line
+            );
+        };
+        let expected = match (&case.code, &case.message) {
+            (Some(code), _) => format!("error[{code}]"),
+            (None, Some(message)) => message.clone(),
+            (None, None) => "error".to_string(),
+        };
+        assert!(
+            stderr.contains(&expected),
+            "{} (from {} line {}) should fail with {expected}",
+            case.name,
+            case.example,
+            case.line
+        );
+    }
+}
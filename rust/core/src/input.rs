@@ -0,0 +1,75 @@
+//! Reading what the user types.
+
+use std::io::{self, BufRead, Write};
+
+/// Prints `question`, waits for a line from stdin, and returns it without
+/// the line ending.
+///
+/// # Errors
+///
+/// Fails with [`io::ErrorKind::UnexpectedEof`] if stdin ends first, as it
+/// does when nobody is typing (in the tutor, or with `< /dev/null`), and
+/// with any error from reading stdin or writing stdout.
+pub fn prompt(question: &str) -> io::Result<String> {
+    prompt_from(&mut io::stdin().lock(), &mut io::stdout(), question)
+}
+
+/// [`prompt`], reading from `input` and writing to `output`, so that it can
+/// be tested, or read from a file.
+pub fn prompt_from(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+) -> io::Result<String> {
+    write!(output, "{question} ")?;
+    // stdout is line buffered, and the question has no newline yet
+    output.flush()?;
+    match read_line_from(input)? {
+        Some(line) => Ok(line),
+        None => Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "input ended before an answer",
+        )),
+    }
+}
+
+/// The next line of stdin without its line ending, or `None` at the end.
+pub fn read_line() -> io::Result<Option<String>> {
+    read_line_from(&mut io::stdin().lock())
+}
+
+fn read_line_from(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let len = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(len);
+    Ok(Some(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_answer_comes_back_without_its_line_ending() {
+        let mut output = Vec::new();
+        let answer = prompt_from(&mut "Ferris\r\nignored\n".as_bytes(), &mut output, "Name?");
+        assert_eq!(answer.unwrap(), "Ferris");
+        assert_eq!(output, b"Name? ");
+    }
+
+    #[test]
+    fn running_out_of_input_is_an_error() {
+        let error = prompt_from(&mut "".as_bytes(), &mut Vec::new(), "Name?").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn read_line_reads_until_the_end() {
+        let mut input = "one\n\nthree".as_bytes();
+        let lines: Vec<_> = std::iter::from_fn(|| read_line_from(&mut input).unwrap()).collect();
+        assert_eq!(lines, ["one", "", "three"]);
+    }
+}
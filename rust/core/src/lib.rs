@@ -0,0 +1,65 @@
+//! Helpers the chapter packages share, so that each example can get on
+//! with its topic instead of repeating the same scaffolding:
+//!
+//! - [`section`] prints a header between the parts of an example.
+//! - [`demo!`] prints an expression's source next to its value.
+//! - [`Timer`] and [`timed`] say how long a piece of code took.
+//! - [`prompt`] and [`read_line`] read what the user types.
+//!
+//! Only the packages can use it: the standalone chapters are single files
+//! that compile with nothing but rustc, so they keep to std.
+//!
+//! ```
+//! use learn_rust_core::{demo, section};
+//!
+//! section("numbers");
+//! let doubled = demo!(21 * 2);
+//! assert_eq!(doubled, 42);
+//! ```
+//!
+//! prints
+//!
+//! ```text
+//! -- numbers --
+//! 21 * 2 = 42
+//! ```
+//!
+//! It is also the course's example of a documented library: every public
+//! item has docs, `#![deny(missing_docs)]` keeps it that way, and the
+//! examples in them run as tests. `cargo run -p tutor -- docs` builds and
+//! opens them; the `docs` chapter explains how they're written.
+
+#![deny(missing_docs)]
+
+mod input;
+mod output;
+mod timing;
+
+pub use input::{prompt, prompt_from, read_line};
+pub use output::{section, section_header};
+pub use timing::{timed, Timer};
+
+/// Prints the source of each expression and its value (its `Debug` form)
+/// to stdout, then hands the value back, like `dbg!` does on stderr:
+///
+/// ```
+/// # use learn_rust_core::demo;
+/// let words = demo!("a b c".split(' ').count()); // "a b c".split(' ').count() = 3
+/// let (a, b) = demo!(u8::MAX, i8::MIN); // u8::MAX = 255, then i8::MIN = -128
+/// # assert_eq!((words, a, b), (3, 255, -128));
+/// ```
+#[macro_export]
+macro_rules! demo {
+    ($expr:expr $(,)?) => {
+        // A match keeps any temporaries in $expr alive while it's printed
+        match $expr {
+            value => {
+                ::std::println!("{} = {:?}", ::std::stringify!($expr), &value);
+                value
+            }
+        }
+    };
+    ($($expr:expr),+ $(,)?) => {
+        ($($crate::demo!($expr)),+)
+    };
+}
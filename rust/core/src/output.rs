@@ -0,0 +1,44 @@
+//! Section headers.
+
+use std::env;
+use std::io::{self, IsTerminal};
+
+/// Bold cyan, then back to normal.
+const COLOR: (&str, &str) = ("\x1b[1;36m", "\x1b[0m");
+
+/// Prints `-- title --`, in color when stdout is a terminal.
+///
+/// Piped into a file or another program, and so when the tutor checks an
+/// example's `// EXPECTED:` block, the header is plain text. Setting
+/// `NO_COLOR` (see <https://no-color.org>) turns the color off everywhere.
+pub fn section(title: &str) {
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    println!("{}", section_header(title, color));
+}
+
+/// The line [`section`] prints.
+pub fn section_header(title: &str, color: bool) -> String {
+    let header = format!("-- {title} --");
+    if color {
+        format!("{}{header}{}", COLOR.0, COLOR.1)
+    } else {
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_headers_are_dashed() {
+        assert_eq!(section_header("numbers", false), "-- numbers --");
+    }
+
+    #[test]
+    fn colored_headers_reset_the_color_after_the_title() {
+        let header = section_header("numbers", true);
+        assert!(header.starts_with("\x1b[1;36m-- numbers --"));
+        assert!(header.ends_with("\x1b[0m"));
+    }
+}
@@ -0,0 +1,75 @@
+//! Timing a piece of code.
+
+use std::time::{Duration, Instant};
+
+/// Measures from when it's created until it's dropped, then prints how
+/// long that was:
+///
+/// ```
+/// # use learn_rust_core::Timer;
+/// {
+///     let _timer = Timer::start("sorting");
+///     let mut numbers: Vec<u32> = (0..1000).rev().collect();
+///     numbers.sort();
+/// } // prints "sorting took 0 ms"
+/// ```
+///
+/// Bind it to a name like `_timer`: plain `_` would drop it, and stop it,
+/// on the spot.
+#[derive(Debug)]
+pub struct Timer {
+    label: String,
+    start: Instant,
+}
+
+impl Timer {
+    /// Starts timing now. `label` names what is being timed in the line
+    /// printed when the timer is dropped.
+    pub fn start(label: &str) -> Self {
+        Timer {
+            label: label.to_string(),
+            start: Instant::now(),
+        }
+    }
+
+    /// How long it has been running so far.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        println!("{} took {} ms", self.label, self.elapsed().as_millis());
+    }
+}
+
+/// Runs `work`, prints how long it took, and returns its result.
+///
+/// ```
+/// # use learn_rust_core::timed;
+/// let total: u64 = timed("summing", || (1..=100).sum()); // summing took 0 ms
+/// assert_eq!(total, 5050);
+/// ```
+pub fn timed<T>(label: &str, work: impl FnOnce() -> T) -> T {
+    let _timer = Timer::start(label);
+    work()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn a_timer_counts_from_its_start() {
+        let timer = Timer::start("sleeping");
+        thread::sleep(Duration::from_millis(20));
+        assert!(timer.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn timed_returns_what_the_work_returns() {
+        assert_eq!(timed("work", || "done"), "done");
+    }
+}
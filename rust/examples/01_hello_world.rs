@@ -1,5 +1,10 @@
 // Example 1: Basic Hello World
 // This is the simplest Rust program possible
+//
+// Concepts: fn main, println!, macros
+// Difficulty: beginner
+// Minutes: 5
+// Related: go/examples/01_hello_world.go, java/examples/HelloWorld.java
 
 fn main() {
     println!("Hello, World!");
@@ -11,3 +16,16 @@ fn main() {
  * 2. println! - Macro that prints to console
  * 3. The ! indicates it's a macro, not a function
  */
+
+//# `fn main() {`
+//# `fn` declares a function. `main` is special: it is where every Rust
+//# program starts. It takes no arguments here and returns nothing.
+//#
+//# `println!("Hello, World!");`
+//# `println!` prints a line of text. The `!` means it's a macro, not a
+//# function: macros are expanded into more code at compile time, which is
+//# how `println!` can check its format string before the program runs.
+//# The line ends with `;`, like most statements in Rust.
+
+// EXPECTED:
+// Hello, World!
@@ -0,0 +1,182 @@
+// Advanced Traits 1: Associated Types
+// Demonstrates types and constants that each implementation of a trait chooses, and when a generic parameter fits better
+//
+// Concepts: associated types, associated consts, generic trait parameters, default type parameters, fully qualified syntax
+// Difficulty: advanced
+// Minutes: 15
+//
+// Iterator has a type in it that each implementor picks: `type Item`.
+// A Vec<String>'s iterator hands out Strings, a Chars hands out chars,
+// and code that takes any iterator writes `I::Item` for whatever it is.
+// That is an associated type. A trait can have associated consts the
+// same way.
+//
+// The other way to put a type in a trait is a generic parameter, like
+// From<T>. The difference is how many implementations a type can have:
+// - with an associated type, one: Countdown is a Source of u32 and
+//   nothing else, so the compiler always knows what `pull()` returns
+// - with a parameter, one per type argument: a Distance can be
+//   `Measure<Meters>` and `Measure<Feet>` at once, and the caller has to
+//   say which one it means
+// Pick the associated type when the implementor decides ("a Countdown
+// produces u32s"), and the parameter when the caller does ("convert this
+// to feet").
+//
+// A parameter can have a default: std's `trait Add<Rhs = Self>` is why
+// `impl Add for Money` means Add<Money>. Java and Go only have the
+// parameter kind, in `Iterator<T>` and `Source[T any]`; and because Java
+// erases type arguments, a class can implement Iterator<T> for one T only,
+// which makes it work like an associated type after all.
+
+use std::collections::VecDeque;
+
+// ===== Associated types and consts =====
+
+/// Hands out values one at a time, a little like Iterator.
+trait Source {
+    /// What it hands out; each implementation picks one.
+    type Item;
+    /// How many values `pull_batch` takes at a time.
+    const BATCH: usize;
+
+    fn pull(&mut self) -> Option<Self::Item>;
+
+    fn pull_batch(&mut self) -> Vec<Self::Item> {
+        std::iter::from_fn(|| self.pull())
+            .take(Self::BATCH)
+            .collect()
+    }
+}
+
+/// Counts down to 1.
+struct Countdown(u32);
+
+impl Source for Countdown {
+    type Item = u32;
+    const BATCH: usize = 3;
+
+    fn pull(&mut self) -> Option<u32> {
+        let next = self.0;
+        self.0 = next.checked_sub(1)?;
+        Some(next)
+    }
+}
+
+/// The words of a line of text, front to back.
+struct Tokens(VecDeque<String>);
+
+impl Tokens {
+    fn new(line: &str) -> Self {
+        Tokens(line.split_whitespace().map(String::from).collect())
+    }
+}
+
+impl Source for Tokens {
+    type Item = String;
+    const BATCH: usize = 2;
+
+    fn pull(&mut self) -> Option<String> {
+        self.0.pop_front()
+    }
+}
+
+/// Works with any Source, and returns whatever its Item is.
+fn drain<S: Source>(source: &mut S) -> Vec<S::Item> {
+    std::iter::from_fn(|| source.pull()).collect()
+}
+
+/// Only for Sources of u32, said with `Source<Item = u32>`.
+fn total<S: Source<Item = u32>>(mut source: S) -> u32 {
+    std::iter::from_fn(|| source.pull()).sum()
+}
+
+// ===== A generic parameter instead =====
+
+struct Meters;
+struct Feet;
+
+/// How long something is in `Unit`; a type can be measured in several.
+/// Without a unit, `Measure` means `Measure<Meters>`.
+trait Measure<Unit = Meters> {
+    fn measure(&self) -> f64;
+}
+
+struct Distance {
+    meters: f64,
+}
+
+impl Measure for Distance {
+    fn measure(&self) -> f64 {
+        self.meters
+    }
+}
+
+impl Measure<Feet> for Distance {
+    fn measure(&self) -> f64 {
+        self.meters / 0.3048
+    }
+}
+
+/// The caller picks the unit, and with it the impl.
+fn describe<U>(what: &str, length: &impl Measure<U>, unit: &str) -> String {
+    format!("{what}: {:.1} {unit}", length.measure())
+}
+
+fn main() {
+    println!("-- associated types --");
+    let mut countdown = Countdown(5);
+    println!("batch: {:?}", countdown.pull_batch());
+    println!("batch: {:?}", countdown.pull_batch());
+    println!("batch: {:?}", countdown.pull_batch());
+
+    let mut tokens = Tokens::new("let answer = 42 ;");
+    println!("batch: {:?}", tokens.pull_batch());
+    println!("rest: {:?}", drain(&mut tokens));
+    println!("total of Countdown(4): {}", total(Countdown(4)));
+    // total(Tokens::new("1 2")) doesn't compile: its Item is String
+
+    // An associated const belongs to the impl, so name the type
+    println!(
+        "batch sizes: Countdown {}, Tokens {}",
+        Countdown::BATCH,
+        <Tokens as Source>::BATCH
+    );
+
+    println!("-- generic parameters --");
+    let run = Distance { meters: 1609.344 };
+    // The fully qualified form names the type, the trait, and its
+    // argument. Written as a type, `Measure` alone is Measure<Meters>
+    println!("{:.1} m", <Distance as Measure>::measure(&run));
+    println!("{:.1} ft", <Distance as Measure<Feet>>::measure(&run));
+    println!("{}", describe::<Feet>("a mile", &run, "ft"));
+    println!("{}", describe::<Meters>("a mile", &run, "m"));
+
+    // A method call doesn't use the default: both impls have a measure()
+    // This would cause an error:
+    // let length = run.measure();
+    // error[E0283]: type annotations needed
+}
+
+/*
+ * Key Concepts:
+ * - An associated type is chosen once per impl; code names it as S::Item
+ * - Source<Item = u32> constrains an associated type in a bound
+ * - Associated consts let each impl supply a value, read as Type::CONST
+ * - A generic parameter allows one impl per argument, so callers must pick
+ * - Trait<Rhs = Self> gives a parameter a default; <T as Trait<U>>::f picks an impl
+ */
+
+// EXPECTED:
+// -- associated types --
+// batch: [5, 4, 3]
+// batch: [2, 1]
+// batch: []
+// batch: ["let", "answer"]
+// rest: ["=", "42", ";"]
+// total of Countdown(4): 10
+// batch sizes: Countdown 3, Tokens 2
+// -- generic parameters --
+// 1609.3 m
+// 5280.0 ft
+// a mile: 5280.0 ft
+// a mile: 1609.3 m
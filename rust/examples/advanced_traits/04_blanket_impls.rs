@@ -0,0 +1,252 @@
+// Advanced Traits 4: Blanket Impls
+// Demonstrates implementing a trait for every type that meets a bound, and the rules that keep impls from clashing
+//
+// Concepts: blanket impls, extension traits, forwarding impls for &T and Box<T>, coherence, the orphan rule
+// Difficulty: advanced
+// Minutes: 20
+//
+// `impl<T: fmt::Display> Summary for T` implements Summary for every type
+// that is Display, now and later: i32, String, and a struct someone
+// writes next year. That's a blanket impl, and std leans on them:
+// - `impl<T: Display> ToString for T` is why anything you can print has
+//   .to_string()
+// - `impl<T, U: From<T>> Into<U> for T` is why implementing From gives
+//   you Into for free
+// - `impl<I: Iterator> IntoIterator for I` is why a `for` loop takes an
+//   iterator as well as a collection
+//
+// Three patterns come up again and again:
+// - extension traits add methods to types you don't own, like str, or to
+//   every Iterator, the way the itertools crate does
+// - forwarding impls make `&T` and `Box<T>` implement a trait whenever T
+//   does, so generic code takes shapes, references to shapes, and boxed
+//   trait objects alike
+// - a blanket impl over a bound, like Summary above
+//
+// The price is coherence: Rust insists that there is never more than one
+// impl of a trait for a type, anywhere in the program. So a blanket impl
+// rules out writing a specific one for any type it already covers, and
+// the orphan rule says a crate may only implement a trait for a type if
+// it owns one of the two, so that two crates can't clash either.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+// ===== A blanket impl over a bound =====
+
+trait Summary {
+    /// At most `width` characters, ending in "..." if anything was cut.
+    fn summary(&self, width: usize) -> String;
+}
+
+/// Every Display type gets a summary
+impl<T: fmt::Display + ?Sized> Summary for T {
+    fn summary(&self, width: usize) -> String {
+        let text = self.to_string();
+        if text.chars().count() <= width {
+            return text;
+        }
+        let kept: String = text.chars().take(width.saturating_sub(3)).collect();
+        format!("{kept}...")
+    }
+}
+
+struct Commit {
+    hash: &'static str,
+    message: &'static str,
+}
+
+impl fmt::Display for Commit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.hash, self.message)
+    }
+}
+
+// ===== Extension traits =====
+
+/// Methods for str that std doesn't have.
+trait StrExt {
+    fn word_count(&self) -> usize;
+    fn initials(&self) -> String;
+}
+
+impl StrExt for str {
+    fn word_count(&self) -> usize {
+        self.split_whitespace().count()
+    }
+
+    fn initials(&self) -> String {
+        self.split_whitespace()
+            .filter_map(|word| word.chars().next())
+            .flat_map(char::to_uppercase)
+            .collect()
+    }
+}
+
+/// Methods for every iterator. Iterator is a supertrait, so the default
+/// methods can use next() and the rest, and the blanket impl below is
+/// empty: there's nothing left for an implementor to write.
+trait IteratorExt: Iterator {
+    /// How many times each item appears.
+    fn counts(self) -> HashMap<Self::Item, usize>
+    where
+        Self: Sized,
+        Self::Item: Hash + Eq,
+    {
+        let mut counts = HashMap::new();
+        for item in self {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// The items joined by `separator`, as strings.
+    fn join_with(self, separator: &str) -> String
+    where
+        Self: Sized,
+        Self::Item: fmt::Display,
+    {
+        let parts: Vec<String> = self.map(|item| item.to_string()).collect();
+        parts.join(separator)
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+// ===== Forwarding impls =====
+
+trait Shape {
+    fn area(&self) -> f64;
+    fn name(&self) -> String;
+}
+
+struct Circle {
+    radius: f64,
+}
+
+struct Square {
+    side: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    fn name(&self) -> String {
+        format!("circle r={}", self.radius)
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+
+    fn name(&self) -> String {
+        format!("square {}x{}", self.side, self.side)
+    }
+}
+
+/// A reference to a shape is a shape. `?Sized` lets S be `dyn Shape`.
+impl<S: Shape + ?Sized> Shape for &S {
+    fn area(&self) -> f64 {
+        (**self).area()
+    }
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+}
+
+/// And so is a box of one, boxed trait objects included.
+impl<S: Shape + ?Sized> Shape for Box<S> {
+    fn area(&self) -> f64 {
+        (**self).area()
+    }
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+}
+
+/// One generic function for all of them.
+fn largest<S: Shape>(shapes: &[S]) -> Option<String> {
+    let mut best: Option<&S> = None;
+    for shape in shapes {
+        if best.is_none_or(|best| shape.area() > best.area()) {
+            best = Some(shape);
+        }
+    }
+    best.map(|shape| format!("{} ({:.2})", shape.name(), shape.area()))
+}
+
+fn main() {
+    println!("-- blanket impl --");
+    println!("{}", 1234.5678_f64.summary(6));
+    println!("{}", "a short note".summary(20));
+    let commit = Commit {
+        hash: "9f2c1e4",
+        message: "Fix the off-by-one in the window iterator",
+    };
+    println!("{}", commit.summary(30));
+
+    println!("-- extension traits --");
+    let title = "the rust programming language";
+    println!(
+        "{:?}: {} words, initials {}",
+        title,
+        title.word_count(),
+        title.initials()
+    );
+    let counts = "mississippi".chars().counts();
+    let mut letters: Vec<_> = counts.into_iter().collect();
+    letters.sort();
+    println!("letters in mississippi: {letters:?}");
+    println!("{}", (1..=5).map(|n| n * n).join_with(", "));
+
+    println!("-- forwarding impls --");
+    let circles = [Circle { radius: 1.0 }, Circle { radius: 2.5 }];
+    println!("owned:     {:?}", largest(&circles));
+    let references: Vec<&Circle> = circles.iter().collect();
+    println!("borrowed:  {:?}", largest(&references));
+    let mixed: Vec<Box<dyn Shape>> = vec![
+        Box::new(Square { side: 5.0 }),
+        Box::new(Circle { radius: 2.0 }),
+    ];
+    println!("boxed dyn: {:?}", largest(&mixed));
+    println!("none:      {:?}", largest::<Square>(&[]));
+
+    // This would cause an error:
+    // impl Summary for String {
+    //     fn summary(&self, _width: usize) -> String {
+    //         self.clone()
+    //     }
+    // }
+    // error[E0119]: conflicting implementations of trait `Summary` for type `String`
+}
+
+/*
+ * Key Concepts:
+ * - impl<T: Bound> Trait for T implements a trait for every type with the bound
+ * - Extension traits add methods to str, Iterator, or any type you don't own
+ * - Forwarding impls for &T and Box<T> let generic code take all three forms
+ * - Coherence: one impl per trait and type, so a blanket impl excludes specific ones
+ * - The orphan rule: implement a trait only if you own the trait or the type
+ */
+
+// EXPECTED:
+// -- blanket impl --
+// 123...
+// a short note
+// 9f2c1e4 Fix the off-by-one ...
+// -- extension traits --
+// "the rust programming language": 4 words, initials TRPL
+// letters in mississippi: [('i', 4), ('m', 1), ('p', 2), ('s', 4)]
+// 1, 4, 9, 16, 25
+// -- forwarding impls --
+// owned:     Some("circle r=2.5 (19.63)")
+// borrowed:  Some("circle r=2.5 (19.63)")
+// boxed dyn: Some("square 5x5 (25.00)")
+// none:      None
@@ -0,0 +1,177 @@
+// Allocator 1: Counting Every Allocation
+// Demonstrates GlobalAlloc, #[global_allocator], wrapping the system allocator, and which everyday operations allocate
+//
+// Concepts: GlobalAlloc, #[global_allocator], System allocator, allocation counting
+// Difficulty: advanced
+// Minutes: 15
+//
+// Box, Vec, String, Rc, and every collection get their memory from the
+// global allocator: a single value implementing std::alloc::GlobalAlloc,
+// whose four methods are asked for a block of a given size and alignment
+// (a Layout), to zero one, to resize one, and to free one. Unless a
+// program says otherwise, that is std::alloc::System, the platform's
+// malloc and free.
+//
+// A binary can pick its own with #[global_allocator] on a static. People
+// do this to use a faster allocator (jemalloc, mimalloc), to add limits or
+// tracking, or, as here, to count. `counting/lib.rs` wraps System: each
+// method bumps a counter, then passes the call on. measure(|| ...) reports
+// what a closure asked for.
+//
+// The trait is `unsafe impl`: the compiler can't check that alloc returns
+// memory that is really free and big enough, and all of Rust's safe
+// collections trust that it does. Forwarding to System keeps the promise.
+//
+// The counts below show some things you can take for granted: creating an
+// empty Vec or String allocates nothing, a Box of a zero-sized type
+// allocates nothing, and a clone of a Vec<String> allocates once for the
+// Vec and once for each String.
+
+use std::rc::Rc;
+
+use counting::{measure, Counting, Stats};
+
+// The whole program's allocations, including println!'s, now go through this
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn show(what: &str, stats: Stats) {
+    println!(
+        "{what:<34} {:>6} {:>8} {:>6} {:>6}",
+        stats.allocations, stats.reallocations, stats.deallocations, stats.bytes
+    );
+}
+
+fn main() {
+    println!(
+        "{:<34} {:>6} {:>8} {:>6} {:>6}",
+        "", "allocs", "reallocs", "frees", "bytes"
+    );
+
+    // Dropped at the end of each closure, so the frees are counted too
+    show("Vec::<u64>::new()", measure(|| drop(Vec::<u64>::new())).1);
+    show("String::new()", measure(|| drop(String::new())).1);
+    show(
+        "\"literal\" (a &'static str)",
+        measure(|| "literal".len()).1,
+    );
+    show("Box::new(7_u64)", measure(|| drop(Box::new(7_u64))).1);
+    show(
+        "Box::new(()) (zero-sized)",
+        measure(|| drop(Box::new(()))).1,
+    );
+    show("vec![1_u64, 2, 3]", measure(|| drop(vec![1_u64, 2, 3])).1);
+    show(
+        "\"hello\".to_string()",
+        measure(|| drop("hello".to_string())).1,
+    );
+    show(
+        "Rc::new(7_u64) (plus two counts)",
+        measure(|| drop(Rc::new(7_u64))).1,
+    );
+
+    let names: Vec<String> = ["ada", "grace", "linus"].map(String::from).to_vec();
+    show(
+        "names.clone() (3 Strings)",
+        measure(|| drop(names.clone())).1,
+    );
+    show(
+        "names.iter().map(String::len)",
+        measure(|| names.iter().map(String::len).sum::<usize>()).1,
+    );
+
+    // Kept, not dropped: one allocation, no free yet
+    let (kept, stats) = measure(|| Box::new([0_u8; 100]));
+    show("Box::new([0_u8; 100]), kept", stats);
+    let ((), stats) = measure(|| drop(kept));
+    show("...dropped later", stats);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        struct Leaky;
+
+        impl std::alloc::GlobalAlloc for Leaky {
+            unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+                std::alloc::System.alloc(layout)
+            }
+            unsafe fn dealloc(&self, _ptr: *mut u8, _layout: std::alloc::Layout) {}
+        }
+    }
+    // error[E0200]: the trait `GlobalAlloc` requires an `unsafe impl` declaration
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_collections_do_not_allocate() {
+        let ((), stats) = measure(|| {
+            drop(Vec::<u64>::new());
+            drop(String::new());
+            drop(std::collections::HashMap::<u8, u8>::new());
+        });
+        assert_eq!(stats, Stats::default());
+    }
+
+    #[test]
+    fn zero_sized_boxes_do_not_allocate() {
+        let ((), stats) = measure(|| drop(Box::new(())));
+        assert_eq!(stats.allocations, 0);
+    }
+
+    #[test]
+    fn every_allocation_is_freed_once() {
+        let ((), stats) = measure(|| {
+            let names: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+            drop(names.clone());
+        });
+        assert_eq!(stats.allocations, stats.deallocations);
+    }
+
+    #[test]
+    fn cloning_a_vec_of_strings_allocates_for_each_string() {
+        let names: Vec<String> = ["ada", "grace", "linus"].map(String::from).to_vec();
+        let (copy, stats) = measure(|| names.clone());
+        assert_eq!(stats.allocations, 1 + names.len());
+        assert_eq!(copy, names);
+    }
+
+    #[test]
+    fn an_rc_allocates_its_counts_with_its_value() {
+        let (rc, stats) = measure(|| Rc::new(7_u64));
+        assert_eq!(stats.allocations, 1);
+        assert_eq!(
+            stats.bytes,
+            2 * std::mem::size_of::<usize>() + std::mem::size_of::<u64>()
+        );
+        // Cloning an Rc only bumps a count
+        let (_second, stats) = measure(|| Rc::clone(&rc));
+        assert_eq!(stats, Stats::default());
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Every heap allocation goes through one GlobalAlloc, System unless you choose
+ * - #[global_allocator] on a static installs another, once per binary
+ * - GlobalAlloc is an unsafe trait: safe code trusts the memory it hands out
+ * - A wrapper can count or limit allocations and forward the real work to System
+ * - Empty Vecs and Strings, literals, and zero-sized Boxes allocate nothing
+ */
+
+// EXPECTED:
+//                                    allocs reallocs  frees  bytes
+// Vec::<u64>::new()                       0        0      0      0
+// String::new()                           0        0      0      0
+// "literal" (a &'static str)              0        0      0      0
+// Box::new(7_u64)                         1        0      1      8
+// Box::new(()) (zero-sized)               0        0      0      0
+// vec![1_u64, 2, 3]                       1        0      1     24
+// "hello".to_string()                     1        0      1      5
+// Rc::new(7_u64) (plus two counts)        1        0      1     24
+// names.clone() (3 Strings)               4        0      4     85
+// names.iter().map(String::len)           0        0      0      0
+// Box::new([0_u8; 100]), kept             1        0      0    100
+// ...dropped later                        0        0      1      0
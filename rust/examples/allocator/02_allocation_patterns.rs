@@ -0,0 +1,258 @@
+// Allocator 2: Where the Allocations Come From
+// Demonstrates counting the allocations of common patterns: string concatenation in a loop, Vec growth, with_capacity, and collect
+//
+// Concepts: string concatenation, Vec growth, with_capacity, collect
+// Difficulty: advanced
+// Minutes: 25
+//
+// Each call to the allocator costs time: finding a free block, maybe
+// asking the OS for more memory, and later freeing it. One allocation is
+// nothing to worry about. A thousand where one would do, in a loop that
+// runs all day, is a cost worth seeing, and the counting allocator from
+// 01_counting_allocator shows it.
+//
+// Two sources account for most of them:
+//
+// - Temporaries. `s = s + &n.to_string()` creates a String for every
+//   number, only to copy it into `s` and free it; format! does the same.
+//   write! formats straight into the String that's being built.
+// - Growth. A Vec or String that runs out of room reallocates: it asks
+//   for a bigger block (about double) and moves everything over. Pushing
+//   1000 values into Vec::new() does that eight or so times. with_capacity
+//   asks for the room once, up front, and collect() does so for you when
+//   the iterator knows its length.
+//
+// The bars show allocator requests (allocations plus reallocations) for
+// building the same thing each way. The exact counts depend on std's
+// growth strategy, which it doesn't promise; the tests check the
+// differences that hold whatever the strategy.
+
+use std::fmt::Write;
+
+use counting::{measure, Counting, Stats};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const COUNT: u64 = 1000;
+
+/// "0,1,2,...,999," by adding each piece to the String in turn.
+fn concat_with_plus() -> String {
+    let mut s = String::new();
+    for n in 0..COUNT {
+        s = s + &n.to_string() + ",";
+    }
+    s
+}
+
+fn concat_with_format() -> String {
+    let mut s = String::new();
+    for n in 0..COUNT {
+        s += &format!("{n},");
+    }
+    s
+}
+
+fn concat_with_write() -> String {
+    let mut s = String::new();
+    for n in 0..COUNT {
+        write!(s, "{n},").unwrap();
+    }
+    s
+}
+
+fn concat_with_capacity() -> String {
+    // 1000 numbers of at most 3 digits, and a comma each
+    let mut s = String::with_capacity(4 * COUNT as usize);
+    for n in 0..COUNT {
+        write!(s, "{n},").unwrap();
+    }
+    s
+}
+
+fn concat_with_join() -> String {
+    let pieces: Vec<String> = (0..COUNT).map(|n| n.to_string()).collect();
+    pieces.join(",") + ","
+}
+
+fn push_without_capacity() -> Vec<u64> {
+    let mut v = Vec::new();
+    for n in 0..COUNT {
+        v.push(n * n);
+    }
+    v
+}
+
+fn push_with_capacity() -> Vec<u64> {
+    let mut v = Vec::with_capacity(COUNT as usize);
+    for n in 0..COUNT {
+        v.push(n * n);
+    }
+    v
+}
+
+fn collect_exact() -> Vec<u64> {
+    (0..COUNT).map(|n| n * n).collect()
+}
+
+/// filter() can't say how many items it will keep, so collect() grows.
+fn collect_filtered() -> Vec<u64> {
+    (0..COUNT * 2)
+        .map(|n| n * n)
+        .filter(|n| n % 2 == 0)
+        .collect()
+}
+
+/// A pattern's name, and a function that builds the result that way.
+type Pattern<T> = (&'static str, fn() -> T);
+
+/// Prints each measurement with a bar scaled to the largest of them.
+fn chart<T: PartialEq + std::fmt::Debug>(title: &str, rows: &[Pattern<T>]) {
+    println!("{title}");
+    let results: Vec<(&str, T, Stats)> = rows
+        .iter()
+        .map(|&(name, build)| {
+            let (value, stats) = measure(build);
+            (name, value, stats)
+        })
+        .collect();
+    let widest = results
+        .iter()
+        .map(|(_, _, stats)| stats.requests())
+        .max()
+        .unwrap_or(1);
+    for (name, value, stats) in &results {
+        let bar = "#".repeat((stats.requests() * 40).div_ceil(widest));
+        println!("  {name:<28} {:>5}  {bar}", stats.requests());
+        assert_eq!(value, &results[0].1, "{name} built something else");
+    }
+}
+
+fn main() {
+    chart(
+        "Building \"0,1,2,...,999,\" (allocator requests)",
+        &[
+            ("s = s + &n.to_string()", concat_with_plus),
+            ("s += &format!(..)", concat_with_format),
+            ("pieces, then join", concat_with_join),
+            ("write!(s, ..)", concat_with_write),
+            ("with_capacity, then write!", concat_with_capacity),
+        ],
+    );
+    println!();
+    chart(
+        "A Vec of 1000 squares (allocator requests)",
+        &[
+            ("push onto Vec::new()", push_without_capacity),
+            ("push onto with_capacity", push_with_capacity),
+            ("collect() from a range", collect_exact),
+        ],
+    );
+
+    let (v, stats) = measure(push_without_capacity);
+    println!(
+        "\npush onto Vec::new(): 1 allocation, {} reallocations to reach capacity {}",
+        stats.reallocations,
+        v.capacity()
+    );
+    let (v, stats) = measure(collect_filtered);
+    println!(
+        "collect() after filter(): {} requests for {} items",
+        stats.requests(),
+        v.len()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let first = String::from("0,");
+        let second = String::from("1,");
+        let line = first + second;
+    }
+    // error[E0308]: mismatched types
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_way_builds_the_same_string() {
+        let expected = concat_with_plus();
+        assert!(expected.starts_with("0,1,2,") && expected.ends_with("999,"));
+        for build in [
+            concat_with_format,
+            concat_with_write,
+            concat_with_capacity,
+            concat_with_join,
+        ] {
+            assert_eq!(build(), expected);
+        }
+    }
+
+    #[test]
+    fn temporaries_cost_an_allocation_per_iteration() {
+        for build in [concat_with_plus, concat_with_format, concat_with_join] {
+            let (_, stats) = measure(build);
+            assert!(stats.allocations >= COUNT as usize, "{stats:?}");
+        }
+    }
+
+    #[test]
+    fn write_has_no_temporaries_only_growth() {
+        let (_, stats) = measure(concat_with_write);
+        assert_eq!(stats.allocations, 1);
+        assert!(stats.reallocations > 1);
+        assert!(stats.requests() < 20, "{stats:?}");
+    }
+
+    #[test]
+    fn enough_capacity_means_a_single_allocation() {
+        let (s, stats) = measure(concat_with_capacity);
+        assert_eq!((stats.allocations, stats.reallocations), (1, 0));
+        assert!(s.len() <= s.capacity());
+
+        let (_, stats) = measure(push_with_capacity);
+        assert_eq!((stats.allocations, stats.reallocations), (1, 0));
+    }
+
+    #[test]
+    fn pushing_without_capacity_reallocates_as_it_grows() {
+        let (v, stats) = measure(push_without_capacity);
+        assert_eq!(v.len(), COUNT as usize);
+        assert_eq!(stats.allocations, 1);
+        assert!(stats.reallocations > 1);
+    }
+
+    #[test]
+    fn collect_from_a_range_allocates_once() {
+        let (v, stats) = measure(collect_exact);
+        assert_eq!(v, push_with_capacity());
+        assert_eq!(stats.requests(), 1);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Temporaries (to_string, format!) allocate on every pass through a loop
+ * - write! formats into an existing String, with no temporary
+ * - A growing Vec or String reallocates about log2(n) times; with_capacity once
+ * - collect() reserves up front when the iterator knows its length
+ * - A counting allocator turns "this allocates a lot" into a number a test can check
+ */
+
+// EXPECTED:
+// Building "0,1,2,...,999," (allocator requests)
+//   s = s + &n.to_string()        1010  ########################################
+//   s += &format!(..)             1010  ########################################
+//   pieces, then join             1003  ########################################
+//   write!(s, ..)                   10  #
+//   with_capacity, then write!       1  #
+//
+// A Vec of 1000 squares (allocator requests)
+//   push onto Vec::new()             9  ########################################
+//   push onto with_capacity          1  #####
+//   collect() from a range           1  #####
+//
+// push onto Vec::new(): 1 allocation, 8 reallocations to reach capacity 1024
+// collect() after filter(): 9 requests for 1000 items
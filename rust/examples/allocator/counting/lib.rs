@@ -0,0 +1,175 @@
+//! A global allocator that counts what it does, then lets the system
+//! allocator do it.
+//!
+//! Every `Box`, `Vec`, and `String` in a program gets its memory from one
+//! global allocator, which is `std::alloc::System` (malloc and free) unless
+//! the program picks another:
+//!
+//! ```
+//! #[global_allocator]
+//! static ALLOCATOR: counting::Counting = counting::Counting;
+//!
+//! let (_greeting, stats) = counting::measure(|| "hi".to_string());
+//! assert_eq!(stats.allocations, 1);
+//! ```
+//!
+//! Only a binary (or a test) can do that, once; a library only provides
+//! the type. With [`Counting`] installed, [`measure`] reports how many
+//! allocations some code made.
+//!
+//! The counts are kept per thread, so code measured on one thread isn't
+//! blamed for another thread's allocations, such as the other tests that
+//! `cargo test` runs in parallel.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// The allocator: [`System`], with every call counted.
+pub struct Counting;
+
+/// What the allocator was asked to do, on one thread.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Fresh blocks: `alloc` and `alloc_zeroed`.
+    pub allocations: usize,
+    /// Blocks grown or shrunk, which may move them.
+    pub reallocations: usize,
+    pub deallocations: usize,
+    /// Bytes asked for by allocations and reallocations (a reallocation
+    /// counts its new size).
+    pub bytes: usize,
+}
+
+impl Stats {
+    const ZERO: Stats = Stats {
+        allocations: 0,
+        reallocations: 0,
+        deallocations: 0,
+        bytes: 0,
+    };
+
+    /// Allocations plus reallocations: each is a trip to the allocator
+    /// that may have to find memory.
+    pub fn requests(&self) -> usize {
+        self.allocations + self.reallocations
+    }
+
+    fn since(self, earlier: Stats) -> Stats {
+        Stats {
+            allocations: self.allocations - earlier.allocations,
+            reallocations: self.reallocations - earlier.reallocations,
+            deallocations: self.deallocations - earlier.deallocations,
+            bytes: self.bytes - earlier.bytes,
+        }
+    }
+}
+
+thread_local! {
+    // A const initializer and a Copy type: no lazy setup and no destructor,
+    // so reading it can never itself allocate, which inside an allocator
+    // would recurse forever
+    static COUNTS: Cell<Stats> = const { Cell::new(Stats::ZERO) };
+}
+
+fn record(update: impl FnOnce(&mut Stats)) {
+    // try_with: during thread teardown the counter may already be gone
+    let _ = COUNTS.try_with(|counts| {
+        let mut stats = counts.get();
+        update(&mut stats);
+        counts.set(stats);
+    });
+}
+
+// SAFETY: every method passes its arguments straight to System, which
+// upholds GlobalAlloc's contract; the counting on the side doesn't touch
+// the memory.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(|stats| {
+            stats.allocations += 1;
+            stats.bytes += layout.size();
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(|stats| {
+            stats.allocations += 1;
+            stats.bytes += layout.size();
+        });
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(|stats| {
+            stats.reallocations += 1;
+            stats.bytes += new_size;
+        });
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(|stats| stats.deallocations += 1);
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Everything this thread has asked the allocator for so far.
+pub fn current() -> Stats {
+    COUNTS.with(Cell::get)
+}
+
+/// Runs `f` and returns its result with what it asked the allocator for.
+/// All zeros unless [`Counting`] is the global allocator.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Stats) {
+    let before = current();
+    let result = f();
+    (result, current().since(before))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn a_box_is_one_allocation_and_one_deallocation() {
+        let ((), stats) = measure(|| drop(Box::new(42_u64)));
+        assert_eq!(stats.allocations, 1);
+        assert_eq!(stats.deallocations, 1);
+        assert_eq!(stats.bytes, 8);
+    }
+
+    #[test]
+    fn nothing_allocated_counts_nothing() {
+        let (sum, stats) = measure(|| (1..=100).sum::<u32>());
+        assert_eq!(sum, 5050);
+        assert_eq!(stats, Stats::default());
+    }
+
+    #[test]
+    fn growing_a_vec_reallocates() {
+        let (v, stats) = measure(|| {
+            let mut v = vec![0_u8; 16];
+            v.extend_from_slice(&[1; 1000]);
+            v
+        });
+        // vec! with a length uses alloc_zeroed, extending past it grows it
+        assert_eq!(stats.allocations, 1);
+        assert!(stats.reallocations >= 1);
+        assert_eq!(v.len(), 1016);
+    }
+
+    #[test]
+    fn each_thread_counts_its_own() {
+        let inner = std::thread::spawn(|| measure(|| drop(vec![1_u8; 100])).1)
+            .join()
+            .unwrap();
+        // Not the allocations spawning the thread made here, nor any other
+        // test's
+        assert_eq!(inner.allocations, 1);
+        assert_eq!(inner.bytes, 100);
+    }
+}
@@ -0,0 +1,151 @@
+// Async 2: A Minimal Executor
+// Demonstrates how an executor schedules tasks and how wakers drive it
+//
+// Concepts: executors, tasks, Waker, scheduling
+// Difficulty: advanced
+// Minutes: 15
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// ===== A timer future =====
+// Instead of busy-waiting, the timer hands its waker to a helper thread
+// that calls wake() when the time is up. Real runtimes use one shared
+// timer thread (or the OS) instead of one thread per timer.
+struct Sleep {
+    deadline: Instant,
+    started: bool,
+}
+
+fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        deadline: Instant::now() + duration,
+        started: false,
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        if !self.started {
+            self.started = true;
+            let waker = cx.waker().clone();
+            let deadline = self.deadline;
+            thread::spawn(move || {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                waker.wake();
+            });
+        }
+        Poll::Pending
+    }
+}
+
+// ===== The executor =====
+type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+// Waking a task just puts its id back on the run queue
+struct TaskWaker {
+    id: usize,
+    queue: Mutex<Sender<usize>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        // The executor may already have exited; nothing to do then
+        let _ = self.queue.lock().unwrap().send(self.id);
+    }
+}
+
+struct Executor {
+    tasks: HashMap<usize, Task>,
+}
+
+impl Executor {
+    fn new() -> Executor {
+        Executor {
+            tasks: HashMap::new(),
+        }
+    }
+
+    fn spawn(&mut self, future: impl Future<Output = ()> + Send + 'static) {
+        let id = self.tasks.len();
+        self.tasks.insert(id, Box::pin(future));
+    }
+
+    // Poll every task once, then only poll the ones that have been woken.
+    // When all tasks are done, we are done.
+    fn run(mut self) {
+        let (queue, ready) = mpsc::channel();
+        for &id in self.tasks.keys() {
+            queue.send(id).unwrap();
+        }
+
+        let mut polls = 0;
+        while !self.tasks.is_empty() {
+            // Blocks while every task is waiting: the executor sleeps
+            // instead of spinning, which is what makes async efficient.
+            let id = ready.recv().unwrap();
+            let Some(task) = self.tasks.get_mut(&id) else {
+                continue; // woken after it already finished
+            };
+            let waker = Waker::from(Arc::new(TaskWaker {
+                id,
+                queue: Mutex::new(queue.clone()),
+            }));
+            polls += 1;
+            if task
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                self.tasks.remove(&id);
+            }
+        }
+        println!("executor finished after {} polls", polls);
+    }
+}
+
+async fn brew(name: &'static str, steps: u32, start: Instant) {
+    for step in 1..=steps {
+        // Each .await hands control back to the executor, which runs
+        // other tasks while this one waits.
+        sleep(Duration::from_millis(100)).await;
+        println!(
+            "[{:>4} ms] {} finished step {}",
+            start.elapsed().as_millis(),
+            name,
+            step
+        );
+    }
+}
+
+fn main() {
+    let start = Instant::now();
+    let mut executor = Executor::new();
+    executor.spawn(brew("tea", 2, start));
+    executor.spawn(brew("coffee", 3, start));
+    executor.spawn(brew("soup", 1, start));
+    // Three tasks, six 100 ms sleeps, ONE executor thread: because tasks
+    // wait concurrently, it all takes about 300 ms, not 600 ms.
+    executor.run();
+    println!("total: {} ms", start.elapsed().as_millis());
+}
+
+/*
+ * Key Concepts:
+ * - An executor owns tasks and polls them until they are Ready
+ * - Wakers put a task back on the executor's run queue
+ * - Pending futures cost nothing while they wait
+ * - Many tasks can share one thread because .await yields control
+ * - tokio and other runtimes are industrial-strength versions of this
+ */
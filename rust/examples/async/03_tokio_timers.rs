@@ -0,0 +1,75 @@
+// Async 3: tokio Basics and Timers
+// Demonstrates #[tokio::main], tokio::time::sleep, timeout, and interval
+//
+// Concepts: #[tokio::main], tokio::time::sleep, timeout, interval
+// Difficulty: advanced
+// Minutes: 5
+
+use std::time::{Duration, Instant};
+
+use tokio::time;
+
+async fn fetch(name: &str, millis: u64) -> String {
+    // tokio's sleep: like our hand-made Sleep, but backed by a real timer
+    time::sleep(Duration::from_millis(millis)).await;
+    format!("{} ({} ms)", name, millis)
+}
+
+// #[tokio::main] builds a runtime (the executor) and runs main on it
+#[tokio::main]
+async fn main() {
+    let start = Instant::now();
+
+    // Awaiting one after another: the waits add up
+    let a = fetch("first", 100).await;
+    let b = fetch("second", 100).await;
+    println!(
+        "[{:>4} ms] sequential: {}, {}",
+        start.elapsed().as_millis(),
+        a,
+        b
+    );
+
+    // tokio::join! polls both futures concurrently: the waits overlap
+    let start = Instant::now();
+    let (a, b) = tokio::join!(fetch("first", 100), fetch("second", 100));
+    println!(
+        "[{:>4} ms] joined:     {}, {}",
+        start.elapsed().as_millis(),
+        a,
+        b
+    );
+
+    // timeout gives up on a future that takes too long
+    match time::timeout(Duration::from_millis(50), fetch("slow", 500)).await {
+        Ok(result) => println!("got {}", result),
+        Err(_) => println!("slow request timed out after 50 ms"),
+    }
+
+    // select! races futures and keeps whichever finishes first; the
+    // loser is dropped (cancelled) at its next .await
+    tokio::select! {
+        result = fetch("tortoise", 150) => println!("winner: {}", result),
+        result = fetch("hare", 60) => println!("winner: {}", result),
+    }
+
+    // interval ticks at a steady rate, accounting for time spent working
+    let start = Instant::now();
+    let mut ticker = time::interval(Duration::from_millis(50));
+    for n in 1..=3 {
+        ticker.tick().await; // the first tick completes immediately
+        println!("[{:>4} ms] tick {}", start.elapsed().as_millis(), n);
+    }
+
+    // Never block inside async code: std::thread::sleep would freeze the
+    // whole worker thread, and every task scheduled on it would stall.
+}
+
+/*
+ * Key Concepts:
+ * - #[tokio::main] starts a runtime and blocks on the async main
+ * - .await in sequence = one after another; join! = concurrently
+ * - timeout and select! build deadlines and races from futures
+ * - Dropping a future cancels it
+ * - Use tokio::time::sleep, never thread::sleep, in async code
+ */
@@ -0,0 +1,87 @@
+// Async 5: An Async TCP Echo Server
+// Demonstrates TcpListener, one task per connection, and AsyncRead/AsyncWrite
+//
+// Concepts: TcpListener, task per connection, AsyncRead, AsyncWrite
+// Difficulty: advanced
+// Minutes: 5
+
+use std::time::Instant;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+// Echo every line back to the client until it disconnects
+async fn handle(stream: TcpStream, id: usize) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        writer
+            .write_all(format!("echo[{}]: {}\n", id, line).as_bytes())
+            .await?;
+    }
+    Ok(())
+}
+
+async fn client(address: std::net::SocketAddr, name: &str) -> std::io::Result<Vec<String>> {
+    let stream = TcpStream::connect(address).await?;
+    let (reader, mut writer) = stream.into_split();
+    for word in ["hello", "from", name] {
+        writer.write_all(format!("{}\n", word).as_bytes()).await?;
+    }
+    // Closing our half tells the server we're done sending
+    writer.shutdown().await?;
+
+    let mut replies = Vec::new();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        replies.push(line);
+    }
+    Ok(replies)
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let start = Instant::now();
+
+    // Port 0 asks the OS for any free port so the example never collides
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    println!("echo server listening on {}", address);
+
+    // The server: accept connections forever, one task each. A slow client
+    // only blocks its own task, never the accept loop or other clients.
+    tokio::spawn(async move {
+        let mut next_id = 0;
+        loop {
+            let Ok((stream, peer)) = listener.accept().await else {
+                break;
+            };
+            next_id += 1;
+            println!("  accepted connection {} from {}", next_id, peer);
+            tokio::spawn(handle(stream, next_id));
+        }
+    });
+
+    // Three clients talk to the server at the same time
+    let (a, b, c) = tokio::join!(
+        client(address, "alice"),
+        client(address, "bob"),
+        client(address, "carol")
+    );
+    for replies in [a?, b?, c?] {
+        println!("{:?}", replies);
+    }
+    println!("done in {} ms", start.elapsed().as_millis());
+
+    // Returning from main shuts the runtime down, stopping the server task
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - tokio::net provides async versions of TcpListener and TcpStream
+ * - Spawn a task per connection; tasks are cheap
+ * - AsyncBufReadExt::lines and AsyncWriteExt::write_all do async I/O
+ * - into_split lets reading and writing happen independently
+ * - Binding to port 0 picks a free port automatically
+ */
@@ -0,0 +1,310 @@
+// Atomics 1: Memory Orderings
+// Demonstrates what Relaxed, Acquire/Release, and SeqCst promise, and what they don't
+//
+// Concepts: Ordering::Relaxed, Acquire and Release, SeqCst, happens-before, loom
+// Difficulty: advanced
+// Minutes: 25
+//
+// Every atomic operation is indivisible, whatever its Ordering. The
+// ordering is about everything else: the other memory a thread reads and
+// writes around it. Compilers and CPUs reorder memory accesses that don't
+// depend on each other, and one core's writes can reach another core late,
+// or in a different order. An Ordering says which of those reorderings
+// other threads may notice:
+// - Relaxed: only the atomic itself is kept consistent. Enough for a
+//   counter nothing else depends on: every fetch_add is counted.
+// - Release, on a store, and Acquire, on a load that reads what it
+//   stored: everything the storing thread wrote before the store, the
+//   loading thread sees after the load. This is how one thread hands data
+//   to another, and how a lock hands what it protects to its next owner.
+// - SeqCst: on top of that, all SeqCst operations fall into one order
+//   that every thread agrees on. It is only needed when a thread decides
+//   something from a store it did *not* see, as in the store-buffering
+//   test below.
+//
+// The bugs a weaker ordering lets in are rare, and rarer still on x86,
+// whose CPUs reorder less than ARM's, so ordinary tests can't be trusted
+// to find them. The loom tests at the bottom explore every outcome the
+// memory model allows instead (see examples/concurrency/07_loom.rs):
+//
+//     cargo run -p tutor -- verify atomics/01_orderings
+
+/// std's threads and atomics normally; loom's when loom is checking us.
+mod sync {
+    #[cfg(loom)]
+    pub use loom::cell::UnsafeCell;
+    #[cfg(loom)]
+    pub use loom::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    #[cfg(loom)]
+    pub use loom::sync::Arc;
+    #[cfg(loom)]
+    pub use loom::thread;
+
+    #[cfg(not(loom))]
+    pub use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    #[cfg(not(loom))]
+    pub use std::sync::Arc;
+    #[cfg(not(loom))]
+    pub use std::thread;
+
+    /// std's UnsafeCell behind loom's API, where every access goes through
+    /// a closure so that loom can check when it happens.
+    #[cfg(not(loom))]
+    pub struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+    #[cfg(not(loom))]
+    impl<T> UnsafeCell<T> {
+        pub fn new(value: T) -> Self {
+            UnsafeCell(std::cell::UnsafeCell::new(value))
+        }
+
+        pub fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+            f(self.0.get())
+        }
+
+        pub fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+            f(self.0.get())
+        }
+    }
+}
+
+use sync::{thread, Arc, AtomicBool, AtomicUsize, Ordering, UnsafeCell};
+
+// ===== Relaxed: a counter =====
+
+/// Counts to `threads * per_thread` on `threads` threads at once.
+fn count_in_parallel(threads: usize, per_thread: usize) -> usize {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let hits = Arc::clone(&hits);
+            thread::spawn(move || {
+                for _ in 0..per_thread {
+                    hits.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // join() is what makes the threads' updates visible here; the load
+    // itself doesn't need to order anything
+    hits.load(Ordering::Relaxed)
+}
+
+// ===== Release and Acquire: handing data over =====
+
+/// A value one thread sends, once, and others read when it's there: a
+/// bare-bones OnceLock. The value is plain memory; the `ready` flag is the
+/// only atomic, and its orderings are what make it safe to read.
+struct Mailbox<T> {
+    ready: AtomicBool,
+    value: UnsafeCell<Option<T>>,
+    /// Release and Acquire normally, Relaxed in the broken version.
+    store: Ordering,
+    load: Ordering,
+}
+
+// SAFETY: `value` is written once, before `ready` is set, and only read
+// after `ready` is seen set; Release and Acquire order the two
+unsafe impl<T: Send + Sync> Sync for Mailbox<T> {}
+
+impl<T: Clone> Mailbox<T> {
+    fn new() -> Self {
+        Mailbox {
+            ready: AtomicBool::new(false),
+            value: UnsafeCell::new(None),
+            store: Ordering::Release,
+            load: Ordering::Acquire,
+        }
+    }
+
+    /// The same with Relaxed orderings, which is a bug: a reader can see
+    /// `ready` set and still read the value from before it was written.
+    fn relaxed() -> Self {
+        Mailbox {
+            store: Ordering::Relaxed,
+            load: Ordering::Relaxed,
+            ..Mailbox::new()
+        }
+    }
+
+    /// Puts `value` in the mailbox.
+    ///
+    /// # Safety
+    ///
+    /// Only one thread may call `send`, and only once.
+    unsafe fn send(&self, value: T) {
+        // SAFETY: nobody reads before `ready` is set, and the caller
+        // promises nobody else writes
+        self.value.with_mut(|slot| unsafe { *slot = Some(value) });
+        self.ready.store(true, self.store);
+    }
+
+    fn receive(&self) -> Option<T> {
+        if !self.ready.load(self.load) {
+            return None;
+        }
+        // SAFETY: `ready` is set, so the write is finished, and it was the
+        // only one
+        self.value.with(|slot| unsafe { (*slot).clone() })
+    }
+}
+
+// ===== SeqCst: store buffering =====
+
+/// The store-buffering test, `rounds` times over: each of two threads
+/// sets its own flag, then reads the other's. Returns how many rounds
+/// both read false, so that each missed a store the other had already
+/// made. Acquire and Release allow it, because nothing orders a store
+/// before a later load of a different atomic; SeqCst forbids it.
+///
+/// This one uses std directly: it is a demonstration to run, not a test
+/// for loom to check.
+fn store_buffering(rounds: usize, store: Ordering, load: Ordering) -> usize {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Barrier;
+
+    let (x, y) = (AtomicBool::new(false), AtomicBool::new(false));
+    // Starts each round on both threads at once, and waits for its end
+    let barrier = Barrier::new(3);
+    let play = |mine: &AtomicBool, theirs: &AtomicBool| -> Vec<bool> {
+        (0..rounds)
+            .map(|_| {
+                barrier.wait();
+                mine.store(true, store);
+                let seen = theirs.load(load);
+                barrier.wait();
+                seen
+            })
+            .collect()
+    };
+    std::thread::scope(|s| {
+        let a = s.spawn(|| play(&x, &y));
+        let b = s.spawn(|| play(&y, &x));
+        for _ in 0..rounds {
+            barrier.wait();
+            barrier.wait();
+            x.store(false, Ordering::Relaxed);
+            y.store(false, Ordering::Relaxed);
+        }
+        let (a, b) = (a.join().unwrap(), b.join().unwrap());
+        a.iter().zip(&b).filter(|&(a, b)| !a && !b).count()
+    })
+}
+
+fn main() {
+    println!(
+        "Relaxed counter, 4 threads x 100000: {}",
+        count_in_parallel(4, 100_000)
+    );
+
+    let mailbox = Arc::new(Mailbox::new());
+    let sender = {
+        let mailbox = Arc::clone(&mailbox);
+        // SAFETY: this is the only thread that sends
+        thread::spawn(move || unsafe { mailbox.send("port = 8080".to_string()) })
+    };
+    let mut polls = 0;
+    let message = loop {
+        polls += 1;
+        if let Some(message) = mailbox.receive() {
+            break message;
+        }
+        std::hint::spin_loop();
+    };
+    sender.join().unwrap();
+    println!("mailbox: {:?}, after {} polls", message, polls);
+
+    // On one thread the relaxed mailbox behaves too; only loom shows its bug
+    let relaxed = Mailbox::relaxed();
+    // SAFETY: the only send
+    unsafe { relaxed.send(3) };
+    println!("relaxed mailbox, one thread: {:?}", relaxed.receive());
+
+    // How often Acquire/Release lets both threads miss depends on the CPU:
+    // often on most machines, but it may be never on one with a single core
+    let rounds = 20_000;
+    println!("store buffering, {} rounds; both stores missed:", rounds);
+    let weak = store_buffering(rounds, Ordering::Release, Ordering::Acquire);
+    println!("  with Release/Acquire: {} times", weak);
+    let strong = store_buffering(rounds, Ordering::SeqCst, Ordering::SeqCst);
+    println!("  with SeqCst:          {} times", strong);
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_relaxed_counter_counts_everything() {
+        assert_eq!(count_in_parallel(8, 10_000), 80_000);
+    }
+
+    #[test]
+    fn the_mailbox_is_empty_until_something_is_sent() {
+        let mailbox = Mailbox::new();
+        assert_eq!(mailbox.receive(), None);
+        // SAFETY: the only send
+        unsafe { mailbox.send(7) };
+        assert_eq!(mailbox.receive(), Some(7));
+        assert_eq!(mailbox.receive(), Some(7));
+    }
+
+    #[test]
+    fn seq_cst_never_lets_both_threads_miss() {
+        assert_eq!(
+            store_buffering(2_000, Ordering::SeqCst, Ordering::SeqCst),
+            0
+        );
+    }
+}
+
+// Model checks: loom runs each body once for every outcome that the
+// memory model allows, and fails on the first that breaks something.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+
+    /// One thread sends while this one tries to receive.
+    fn send_while_receiving(mailbox: Mailbox<String>) {
+        let mailbox = Arc::new(mailbox);
+        let sender = {
+            let mailbox = Arc::clone(&mailbox);
+            // SAFETY: the only thread that sends
+            thread::spawn(move || unsafe { mailbox.send("hello".to_string()) })
+        };
+        if let Some(message) = mailbox.receive() {
+            assert_eq!(message, "hello");
+        }
+        sender.join().unwrap();
+    }
+
+    // The reader can see `ready` and still read `value` while the write to
+    // it isn't visible yet: loom reports the two accesses as a data race
+    #[test]
+    #[should_panic(expected = "Causality violation")]
+    fn loom_catches_the_relaxed_mailbox() {
+        loom::model(|| send_while_receiving(Mailbox::relaxed()));
+    }
+
+    #[test]
+    fn release_and_acquire_make_the_mailbox_safe() {
+        loom::model(|| send_while_receiving(Mailbox::new()));
+    }
+
+    #[test]
+    fn relaxed_is_enough_for_a_counter() {
+        loom::model(|| assert_eq!(count_in_parallel(2, 2), 4));
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Every atomic operation is indivisible; the Ordering is about other memory
+ * - Relaxed for counters and flags that nothing else depends on
+ * - A Release store and an Acquire load of it hand over everything written before
+ * - SeqCst adds one global order; needed when acting on a store you didn't see
+ * - Ordering bugs are rare on real hardware; loom explores every allowed outcome
+ */
@@ -0,0 +1,292 @@
+// Atomics 2: A Spinlock
+// Demonstrates building a lock from one AtomicBool, and why it needs Acquire and Release
+//
+// Concepts: compare_exchange_weak, Acquire and Release, UnsafeCell, unsafe impl Sync, spin_loop
+// Difficulty: advanced
+// Minutes: 25
+//
+// A Mutex is, underneath, a flag that says whether someone holds the lock,
+// plus memory that only the holder may touch. A spinlock is the simplest
+// version: a thread that finds the flag set keeps trying ("spins") until
+// it can set it itself, rather than asking the operating system to put it
+// to sleep. That is only a good idea when the lock is held for a few
+// instructions; std's Mutex spins briefly and then sleeps, so outside an
+// exercise or a kernel, use that.
+//
+// Taking the lock is a compare_exchange from false to true, and giving it
+// back is a store of false. Which Orderings they use is the whole point:
+// - taking it with Acquire means this thread sees every write that the
+//   previous holder made to the value
+// - giving it back with Release means the next holder sees ours
+// With Relaxed on both, the flag itself still works, so no two threads
+// ever hold the lock at once, yet one can read the value before the
+// previous holder's write to it arrives. `SpinLock::relaxed` has that
+// bug; the loom tests at the bottom catch it, and pass the real one:
+//
+//     cargo run -p tutor -- verify atomics/02_spinlock
+
+/// std's threads and atomics normally; loom's when loom is checking us.
+mod sync {
+    #[cfg(loom)]
+    pub use loom::cell::UnsafeCell;
+    #[cfg(loom)]
+    pub use loom::hint;
+    #[cfg(loom)]
+    pub use loom::sync::atomic::{AtomicBool, Ordering};
+    #[cfg(loom)]
+    pub use loom::sync::Arc;
+    #[cfg(loom)]
+    pub use loom::thread;
+
+    #[cfg(not(loom))]
+    pub use std::hint;
+    #[cfg(not(loom))]
+    pub use std::sync::atomic::{AtomicBool, Ordering};
+    #[cfg(not(loom))]
+    pub use std::sync::Arc;
+    #[cfg(not(loom))]
+    pub use std::thread;
+
+    /// std's UnsafeCell behind loom's API, where every access goes through
+    /// a closure so that loom can check when it happens.
+    #[cfg(not(loom))]
+    pub struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+    #[cfg(not(loom))]
+    impl<T> UnsafeCell<T> {
+        pub fn new(value: T) -> Self {
+            UnsafeCell(std::cell::UnsafeCell::new(value))
+        }
+
+        pub fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+            f(self.0.get())
+        }
+    }
+}
+
+use std::panic::{self, AssertUnwindSafe};
+
+use sync::{hint, thread, Arc, AtomicBool, Ordering, UnsafeCell};
+
+/// A value that one thread at a time may use.
+///
+/// std's Mutex returns a guard that derefs to the value. This one takes a
+/// closure instead, so that every use of the value stays inside the
+/// lock() call and can't outlive it, which also lets loom see each access.
+struct SpinLock<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+    /// Acquire and Release normally, Relaxed in the broken version.
+    acquire: Ordering,
+    release: Ordering,
+}
+
+// SAFETY: only the thread holding the lock touches `value`, and the
+// Acquire/Release pair hands it from one holder to the next, so sharing a
+// SpinLock between threads is like sending the T between them
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+impl<T> SpinLock<T> {
+    fn new(value: T) -> Self {
+        SpinLock {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+            acquire: Ordering::Acquire,
+            release: Ordering::Release,
+        }
+    }
+
+    /// The same lock with Relaxed orderings, which is a bug: it still
+    /// keeps threads out of each other's way, but doesn't make one
+    /// holder's writes visible to the next.
+    fn relaxed(value: T) -> Self {
+        SpinLock {
+            acquire: Ordering::Relaxed,
+            release: Ordering::Relaxed,
+            ..SpinLock::new(value)
+        }
+    }
+
+    /// Waits until the lock is free, then runs `f` on the value while
+    /// holding it.
+    fn lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, self.acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            // Wait for the holder with plain loads, which don't fight over
+            // the cache line the way repeated compare_exchanges would
+            while self.locked.load(Ordering::Relaxed) {
+                hint::spin_loop();
+            }
+        }
+
+        /// Gives the lock back when dropped, even if `f` panics.
+        struct Unlock<'a>(&'a AtomicBool, Ordering);
+
+        impl Drop for Unlock<'_> {
+            fn drop(&mut self) {
+                self.0.store(false, self.1);
+            }
+        }
+
+        let _unlock = Unlock(&self.locked, self.release);
+        // SAFETY: we hold the lock, so no other thread is using the value
+        self.value.with_mut(|value| f(unsafe { &mut *value }))
+    }
+
+    /// Whether some thread holds the lock right now; out of date as soon
+    /// as it returns, so only good for reporting.
+    fn is_locked(&self) -> bool {
+        self.locked.load(Ordering::Relaxed)
+    }
+}
+
+fn main() {
+    let log = Arc::new(SpinLock::new(Vec::new()));
+    let handles: Vec<_> = (0..8)
+        .map(|id| {
+            let log = Arc::clone(&log);
+            thread::spawn(move || {
+                for n in 0..1000 {
+                    log.lock(|entries| entries.push((id, n)));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let (total, in_order) = log.lock(|entries| {
+        // Each thread's own entries are in the order it pushed them
+        let in_order = (0..8).all(|id| {
+            let mine: Vec<_> = entries.iter().filter(|e| e.0 == id).map(|e| e.1).collect();
+            mine.windows(2).all(|pair| pair[0] < pair[1])
+        });
+        (entries.len(), in_order)
+    });
+    println!("8 threads x 1000 pushes: {} entries", total);
+    println!("each thread's entries in order: {}", in_order);
+    println!("locked afterwards: {}", log.is_locked());
+
+    // The lock is released even when the closure panics
+    panic::set_hook(Box::new(|_| {}));
+    let lock = SpinLock::new(0);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        lock.lock(|_| panic!("inside the lock"))
+    }));
+    println!(
+        "after a panic inside: panicked {}, locked {}",
+        result.is_err(),
+        lock.is_locked()
+    );
+    println!("and it can be taken again: {}", lock.lock(|n| *n + 1));
+
+    // On one thread the relaxed lock behaves too; only loom shows its bug
+    let relaxed = SpinLock::relaxed(41);
+    println!("relaxed, one thread: {}", relaxed.lock(|n| *n + 1));
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        struct Unguarded(std::cell::UnsafeCell<i32>);
+        let shared = std::sync::Arc::new(Unguarded(std::cell::UnsafeCell::new(0)));
+        std::thread::spawn(move || unsafe { *shared.0.get() += 1 });
+    }
+    // error[E0277]: `UnsafeCell<i32>` cannot be shared between threads safely
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_increment_is_lost() {
+        let counter = Arc::new(SpinLock::new(0u64));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for _ in 0..10_000 {
+                        counter.lock(|n| *n += 1);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.lock(|n| *n), 40_000);
+    }
+
+    #[test]
+    fn the_lock_is_held_only_inside_the_closure() {
+        let lock = SpinLock::new(String::new());
+        assert!(!lock.is_locked());
+        lock.lock(|s| {
+            s.push_str("held");
+        });
+        assert!(!lock.is_locked());
+        assert!(lock.lock(|s| s.len() == 4));
+    }
+
+    #[test]
+    fn a_panic_inside_releases_the_lock() {
+        let lock = SpinLock::new(1);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| lock.lock(|_| panic!("boom"))));
+        assert!(result.is_err());
+        assert!(!lock.is_locked());
+        assert_eq!(lock.lock(|n| *n), 1);
+    }
+}
+
+// Model checks: loom runs each body once for every interleaving, and every
+// outcome the memory model allows, and fails on the first that breaks.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+
+    /// Two threads each add one under the lock.
+    fn increment_twice(lock: SpinLock<usize>) {
+        let lock = Arc::new(lock);
+        let other = {
+            let lock = Arc::clone(&lock);
+            thread::spawn(move || lock.lock(|n| *n += 1))
+        };
+        lock.lock(|n| *n += 1);
+        other.join().unwrap();
+        assert_eq!(lock.lock(|n| *n), 2);
+    }
+
+    // The second holder can read the value before the first holder's write
+    // is visible to it: loom reports the two accesses as a data race
+    #[test]
+    #[should_panic(expected = "Causality violation")]
+    fn loom_catches_the_relaxed_spinlock() {
+        loom::model(|| increment_twice(SpinLock::relaxed(0)));
+    }
+
+    #[test]
+    fn acquire_and_release_make_the_spinlock_correct() {
+        loom::model(|| increment_twice(SpinLock::new(0)));
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A lock is a flag plus memory that only the flag's holder touches
+ * - Take the lock with an Acquire compare_exchange, give it back with a Release store
+ * - Relaxed keeps holders apart but doesn't hand their writes on
+ * - unsafe impl Sync states the promise the compiler can't check
+ * - Spin only for very short critical sections; std's Mutex sleeps
+ */
+
+// EXPECTED:
+// 8 threads x 1000 pushes: 8000 entries
+// each thread's entries in order: true
+// locked afterwards: false
+// after a panic inside: panicked true, locked false
+// and it can be taken again: 1
+// relaxed, one thread: 42
@@ -0,0 +1,379 @@
+// Atomics 3: A Lock-Free Stats Counter
+// Demonstrates counters that many threads update without a lock, and what their readers can see
+//
+// Concepts: AtomicUsize, fetch_add, fetch_max and fetch_min, torn snapshots, packing into one atomic
+// Difficulty: advanced
+// Minutes: 20
+//
+// A server that records how long each request took wants the count, the
+// total, and the slowest and fastest, updated from every worker thread
+// without them queueing for a Mutex. Each of those is one atomic, and
+// fetch_add, fetch_max, and fetch_min update it in a single step, so no
+// update is ever lost. Nothing else depends on them, so Relaxed is enough
+// for the updates.
+//
+// Reading them back is where it gets subtle. Each field is right on its
+// own, but a reader that loads `count` and then `total` can land between
+// a worker's two fetch_adds and see a count that includes a request the
+// total doesn't: the snapshot is torn. Two ways out:
+// - put the fields that must agree in one atomic, so a single fetch_add
+//   updates both, and a single load reads both (PackedStats below)
+// - only read once the writers have finished, and make sure their
+//   writes are visible: each worker says so with a Release fetch_add
+//   when it is done, and the reader waits for all of them with Acquire
+// The loom tests at the bottom find the torn snapshot, and the stale
+// totals a Relaxed "done" counter lets a reader see:
+//
+//     cargo run -p tutor -- verify atomics/03_stats_counter
+
+/// std's threads and atomics normally; loom's when loom is checking us.
+mod sync {
+    #[cfg(loom)]
+    pub use loom::hint;
+    #[cfg(loom)]
+    pub use loom::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    #[cfg(loom)]
+    pub use loom::sync::Arc;
+    #[cfg(loom)]
+    pub use loom::thread;
+
+    #[cfg(not(loom))]
+    pub use std::hint;
+    #[cfg(not(loom))]
+    pub use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    #[cfg(not(loom))]
+    pub use std::sync::Arc;
+    #[cfg(not(loom))]
+    pub use std::thread;
+}
+
+use sync::{hint, thread, Arc, AtomicU64, AtomicUsize, Ordering};
+
+/// What a reader gets back from the counters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Summary {
+    count: usize,
+    total: usize,
+    /// `None` until something is recorded.
+    min_max: Option<(usize, usize)>,
+}
+
+impl Summary {
+    fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.total as f64 / self.count as f64)
+    }
+}
+
+// ===== One atomic per field =====
+
+/// Request timings, in microseconds, that any thread can record.
+struct Stats {
+    count: AtomicUsize,
+    total: AtomicUsize,
+    min: AtomicUsize,
+    max: AtomicUsize,
+    /// How many workers have finished recording.
+    finished: AtomicUsize,
+    /// Release and Acquire normally, Relaxed in the broken version.
+    finish: Ordering,
+    check: Ordering,
+}
+
+impl Stats {
+    fn new() -> Self {
+        Stats {
+            count: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+            min: AtomicUsize::new(usize::MAX),
+            max: AtomicUsize::new(0),
+            finished: AtomicUsize::new(0),
+            finish: Ordering::Release,
+            check: Ordering::Acquire,
+        }
+    }
+
+    /// The same with a Relaxed `finished` counter, which is a bug: a
+    /// reader can see every worker finished and still miss their records.
+    fn with_relaxed_finish() -> Self {
+        Stats {
+            finish: Ordering::Relaxed,
+            check: Ordering::Relaxed,
+            ..Stats::new()
+        }
+    }
+
+    fn record(&self, micros: usize) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total.fetch_add(micros, Ordering::Relaxed);
+        self.min.fetch_min(micros, Ordering::Relaxed);
+        self.max.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    /// Reads each field; while other threads record, the fields can
+    /// disagree with each other.
+    fn snapshot(&self) -> Summary {
+        let count = self.count.load(Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+        let min = self.min.load(Ordering::Relaxed);
+        let max = self.max.load(Ordering::Relaxed);
+        Summary {
+            count,
+            total,
+            min_max: (count > 0).then_some((min, max)),
+        }
+    }
+
+    /// Called by each worker once it has recorded everything.
+    fn finish(&self) {
+        self.finished.fetch_add(1, self.finish);
+    }
+
+    /// The summary, once `workers` workers have called finish, without
+    /// waiting for their threads to end.
+    fn wait_for(&self, workers: usize) -> Summary {
+        while self.finished.load(self.check) < workers {
+            hint::spin_loop();
+        }
+        self.snapshot()
+    }
+}
+
+// ===== Fields that must agree, in one atomic =====
+
+/// Count and total packed into one AtomicU64: the count in the high 32
+/// bits, the total in the low 32. One fetch_add updates both, so a reader
+/// never sees one without the other. The price is range: the total has to
+/// stay below 2^32 microseconds, a little over an hour.
+struct PackedStats {
+    packed: AtomicU64,
+}
+
+impl PackedStats {
+    const TOTAL_BITS: u32 = 32;
+
+    fn new() -> Self {
+        PackedStats {
+            packed: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, micros: u32) {
+        let add = (1 << Self::TOTAL_BITS) | u64::from(micros);
+        let before = self.packed.fetch_add(add, Ordering::Relaxed);
+        debug_assert!(
+            (before & u64::from(u32::MAX)) + u64::from(micros) <= u64::from(u32::MAX),
+            "the total overflowed into the count"
+        );
+    }
+
+    fn snapshot(&self) -> Summary {
+        let packed = self.packed.load(Ordering::Relaxed);
+        Summary {
+            count: (packed >> Self::TOTAL_BITS) as usize,
+            total: (packed & u64::from(u32::MAX)) as usize,
+            min_max: None,
+        }
+    }
+}
+
+/// Runs `workers` threads that each record 1 to `per_worker`
+/// microseconds, and reads the summary while they may still be running.
+fn serve(workers: usize, per_worker: usize) -> Summary {
+    let stats = Arc::new(Stats::new());
+    for _ in 0..workers {
+        let stats = Arc::clone(&stats);
+        thread::spawn(move || {
+            for micros in 1..=per_worker {
+                stats.record(micros);
+            }
+            stats.finish();
+        });
+    }
+    // The threads are never joined: the finish count is what says their
+    // records are complete, and Acquire is what makes them visible here
+    stats.wait_for(workers)
+}
+
+fn main() {
+    let summary = serve(4, 1000);
+    println!("4 workers x 1000 requests:");
+    println!("  count {}, total {} us", summary.count, summary.total);
+    println!("  min and max: {:?}", summary.min_max);
+    println!("  mean: {:.1} us", summary.mean().unwrap());
+
+    println!(
+        "empty: {:?}, mean {:?}",
+        Stats::new().snapshot(),
+        Stats::new().snapshot().mean()
+    );
+
+    // On one thread the relaxed finish behaves too; only loom shows its bug
+    let relaxed = Stats::with_relaxed_finish();
+    relaxed.record(12);
+    relaxed.finish();
+    println!("relaxed finish, one thread: {:?}", relaxed.wait_for(1));
+
+    // Every request takes 5us, so a snapshot agrees with itself exactly
+    // when total == 5 * count
+    let packed = Arc::new(PackedStats::new());
+    let writer = {
+        let packed = Arc::clone(&packed);
+        thread::spawn(move || {
+            for _ in 0..100_000 {
+                packed.record(5);
+            }
+        })
+    };
+    let mut torn = 0;
+    for _ in 0..10_000 {
+        let summary = packed.snapshot();
+        if summary.total != 5 * summary.count {
+            torn += 1;
+        }
+    }
+    writer.join().unwrap();
+    println!(
+        "packed: {:?}, torn snapshots seen: {}",
+        packed.snapshot(),
+        torn
+    );
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_record_is_counted() {
+        let summary = serve(8, 500);
+        assert_eq!(summary.count, 4000);
+        assert_eq!(summary.total, 8 * (500 * 501 / 2));
+        assert_eq!(summary.min_max, Some((1, 500)));
+        assert_eq!(summary.mean(), Some(250.5));
+    }
+
+    #[test]
+    fn an_empty_summary_has_no_min_max_or_mean() {
+        let summary = Stats::new().snapshot();
+        assert_eq!(summary.count, 0);
+        assert_eq!(summary.min_max, None);
+        assert_eq!(summary.mean(), None);
+    }
+
+    #[test]
+    fn packed_stats_unpack_what_was_recorded() {
+        let stats = PackedStats::new();
+        for micros in [10, 20, 30] {
+            stats.record(micros);
+        }
+        let summary = stats.snapshot();
+        assert_eq!((summary.count, summary.total), (3, 60));
+    }
+
+    #[test]
+    fn a_packed_snapshot_is_never_torn() {
+        let stats = Arc::new(PackedStats::new());
+        let writers: Vec<_> = (0..2)
+            .map(|_| {
+                let stats = Arc::clone(&stats);
+                thread::spawn(move || (0..20_000).for_each(|_| stats.record(3)))
+            })
+            .collect();
+        for _ in 0..5_000 {
+            let summary = stats.snapshot();
+            assert_eq!(summary.total, 3 * summary.count);
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        assert_eq!(stats.snapshot().count, 40_000);
+    }
+}
+
+// Model checks: loom runs each body once for every interleaving, and every
+// outcome the memory model allows, and fails on the first that breaks.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "torn snapshot")]
+    fn loom_finds_a_torn_snapshot() {
+        loom::model(|| {
+            let stats = Arc::new(Stats::new());
+            let writer = {
+                let stats = Arc::clone(&stats);
+                thread::spawn(move || stats.record(5))
+            };
+            let summary = stats.snapshot();
+            assert_eq!(
+                summary.total,
+                5 * summary.count,
+                "torn snapshot: {:?}",
+                summary
+            );
+            writer.join().unwrap();
+        });
+    }
+
+    #[test]
+    fn a_packed_snapshot_is_never_torn() {
+        loom::model(|| {
+            let stats = Arc::new(PackedStats::new());
+            let writer = {
+                let stats = Arc::clone(&stats);
+                thread::spawn(move || stats.record(5))
+            };
+            let summary = stats.snapshot();
+            assert_eq!(summary.total, 5 * summary.count);
+            writer.join().unwrap();
+        });
+    }
+
+    /// One worker records a request and finishes; this thread waits for it
+    /// and expects to see the record.
+    fn wait_for_one_worker(stats: Stats) {
+        let stats = Arc::new(stats);
+        let worker = {
+            let stats = Arc::clone(&stats);
+            thread::spawn(move || {
+                stats.record(7);
+                stats.finish();
+            })
+        };
+        let summary = stats.wait_for(1);
+        assert_eq!(summary.count, 1, "stale count after finish: {:?}", summary);
+        worker.join().unwrap();
+    }
+
+    // With Relaxed, seeing `finished` go up says nothing about the other
+    // fields: a load of `count` may still return the old 0
+    #[test]
+    #[should_panic(expected = "stale count")]
+    fn loom_catches_a_relaxed_finish() {
+        loom::model(|| wait_for_one_worker(Stats::with_relaxed_finish()));
+    }
+
+    #[test]
+    fn release_and_acquire_make_the_records_visible() {
+        loom::model(|| wait_for_one_worker(Stats::new()));
+    }
+}
+
+/*
+ * Key Concepts:
+ * - fetch_add, fetch_max and fetch_min update a counter in one step, with no lock
+ * - Relaxed is enough for counters that nothing else depends on
+ * - Reading several atomics one by one can give a torn snapshot
+ * - Pack values that must agree into one atomic, or read after a Release/Acquire handoff
+ * - A Release "done" counter lets a reader trust the data without joining threads
+ */
+
+// EXPECTED:
+// 4 workers x 1000 requests:
+//   count 4000, total 2002000 us
+//   min and max: Some((1, 1000))
+//   mean: 500.5 us
+// empty: Summary { count: 0, total: 0, min_max: None }, mean None
+// relaxed finish, one thread: Summary { count: 1, total: 12, min_max: Some((12, 12)) }
+// packed: Summary { count: 100000, total: 500000, min_max: None }, torn snapshots seen: 0
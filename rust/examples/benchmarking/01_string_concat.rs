@@ -0,0 +1,111 @@
+// Benchmarking 1: Building Strings
+// Demonstrates why one way of joining strings is quadratic, and how to measure it
+//
+// Concepts: criterion, quadratic time, string joining
+// Difficulty: intermediate
+// Minutes: 10
+//
+// `workloads/lib.rs` joins words with spaces four ways. They all give the
+// same string; they differ in how much copying it takes. format!() builds
+// a brand new string on every step, copying everything joined so far.
+// push_str() appends in place, and copies only when the string runs out of
+// room and doubles its capacity. with_capacity() and join() size the
+// string once, up front, and never copy at all.
+//
+// This program counts that work, which is the same on every machine. How
+// much time it costs is a question for a benchmark: `cargo bench -p
+// benchmarking-examples --bench strings` runs `benches/strings.rs`, where
+// criterion times each version at 10, 100, and 1000 words. Counting tells
+// us the shape (format!() copies 100 times more for 10 times the words);
+// only timing tells us whether it matters at the sizes we have.
+
+use workloads::strings;
+
+/// How many bytes format!() copies to join `words`: on every step, all of
+/// the string so far, plus the new word.
+fn bytes_copied_by_format(words: &[String]) -> usize {
+    let mut length = 0;
+    let mut copied = 0;
+    for (i, word) in words.iter().enumerate() {
+        length += word.len() + usize::from(i > 0);
+        copied += length;
+    }
+    copied
+}
+
+/// How many times push_str() had to grow the string, and the bytes it
+/// copied doing so.
+fn growth_of_push_str(words: &[String]) -> (usize, usize) {
+    let mut joined = String::new();
+    let (mut grew, mut copied) = (0, 0);
+    for (i, word) in words.iter().enumerate() {
+        let before = (joined.capacity(), joined.len());
+        if i > 0 {
+            joined.push(' ');
+        }
+        joined.push_str(word);
+        if joined.capacity() != before.0 {
+            grew += 1;
+            copied += before.1;
+        }
+    }
+    (grew, copied)
+}
+
+fn main() {
+    let words = workloads::words(1000);
+    let joined = strings::with_join(&words);
+    println!("1000 words, {} bytes joined", joined.len());
+    let versions = [
+        ("format", strings::with_format(&words)),
+        ("push_str", strings::with_push_str(&words)),
+        ("with_capacity", strings::with_capacity(&words)),
+    ];
+    for (name, result) in &versions {
+        println!("  {:<14} same result: {}", name, *result == joined);
+    }
+    println!();
+
+    println!(
+        "{:>6}  {:>16}  {:>22}",
+        "words", "format! copies", "push_str grows/copies"
+    );
+    for count in [10, 100, 1000] {
+        let words = workloads::words(count);
+        let (grew, copied) = growth_of_push_str(&words);
+        println!(
+            "{:>6}  {:>10} bytes  {:>2} times, {:>6} bytes",
+            count,
+            bytes_copied_by_format(&words),
+            grew,
+            copied
+        );
+    }
+    println!("with_capacity and join copy nothing: they allocate once");
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let joined = words[0] + " " + &words[1];
+    // error[E0507]: cannot move out of index of `Vec<String>`
+}
+
+/*
+ * Key Concepts:
+ * - Rebuilding a string on every step (s = format!("{} {}", s, w)) is quadratic
+ * - push_str grows by doubling, so appending is amortized O(1)
+ * - String::with_capacity and join allocate once, when the size is known
+ * - Counting work shows how code scales; a benchmark shows what it costs
+ * - All versions must give the same result before timing them means anything
+ */
+
+// EXPECTED:
+// 1000 words, 4889 bytes joined
+//   format         same result: true
+//   push_str       same result: true
+//   with_capacity  same result: true
+//
+//  words    format! copies   push_str grows/copies
+//     10         155 bytes   3 times,     22 bytes
+//    100       19145 bytes   7 times,    490 bytes
+//   1000     2396495 bytes  11 times,   8161 bytes
+// with_capacity and join copy nothing: they allocate once
@@ -0,0 +1,131 @@
+// Benchmarking 2: HashMap vs BTreeMap
+// Demonstrates what each map does per lookup, and why the benchmark needs both sizes
+//
+// Concepts: HashMap, BTreeMap, lookup cost, benchmark sizes
+// Difficulty: intermediate
+// Minutes: 10
+//
+// A HashMap lookup hashes the key once, jumps to a slot, and compares
+// the key with whatever is there: about one comparison, whatever the
+// size. A BTreeMap lookup walks down a tree of sorted nodes, comparing as
+// it goes, so the number of comparisons grows with the logarithm of the
+// size (more than log2(n), because it reads each node's keys in a line,
+// and a node holds up to eleven). That sounds like HashMap always wins, but hashing costs more than
+// comparing two integers, and a BTreeMap's nodes sit next to each other in
+// memory. `benches/maps.rs` times both at 100 and 10,000 keys to find out.
+//
+// To see the work itself, this program wraps the keys in a type that
+// counts how often it is hashed and compared. What it can't show is time:
+// that depends on the hash function, the cache, and the machine, which is
+// exactly why we benchmark instead of guessing.
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+
+use workloads::maps;
+
+thread_local! {
+    static HASHES: Cell<usize> = const { Cell::new(0) };
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A u64 that counts every hash and every ordering comparison.
+#[derive(PartialEq, Eq)]
+struct Counted(u64);
+
+impl Hash for Counted {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        HASHES.set(HASHES.get() + 1);
+        self.0.hash(state);
+    }
+}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Counted {
+    fn cmp(&self, other: &Self) -> Ordering {
+        COMPARISONS.set(COMPARISONS.get() + 1);
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Work per lookup, averaged over looking up every key once.
+fn per_lookup(
+    counter: &'static std::thread::LocalKey<Cell<usize>>,
+    lookup: impl Fn(&Counted) -> bool,
+    keys: &[u64],
+) -> f64 {
+    counter.set(0);
+    let found = keys.iter().filter(|&&key| lookup(&Counted(key))).count();
+    assert_eq!(found, keys.len());
+    counter.get() as f64 / keys.len() as f64
+}
+
+fn main() {
+    // The benchmark's own functions agree on what they find
+    let keys = workloads::numbers(10_000);
+    let queries: Vec<u64> = keys.iter().flat_map(|&key| [key, key ^ 1]).collect();
+    let hash_hits = maps::hits_hash(&maps::hash_map(&keys), &queries);
+    let btree_hits = maps::hits_btree(&maps::btree_map(&keys), &queries);
+    println!(
+        "{} queries: HashMap found {}, BTreeMap found {}",
+        queries.len(),
+        hash_hits,
+        btree_hits
+    );
+    println!();
+
+    for size in [100, 10_000] {
+        let keys = workloads::numbers(size);
+        let hash: HashMap<Counted, ()> = keys.iter().map(|&key| (Counted(key), ())).collect();
+        let btree: BTreeMap<Counted, ()> = keys.iter().map(|&key| (Counted(key), ())).collect();
+        println!("{} keys", size);
+        println!(
+            "  HashMap:  {:.2} hashes per lookup",
+            per_lookup(&HASHES, |key| hash.contains_key(key), &keys)
+        );
+        println!(
+            "  BTreeMap: {:.2} comparisons per lookup",
+            per_lookup(&COMPARISONS, |key| btree.contains_key(key), &keys)
+        );
+    }
+
+    // A BTreeMap keeps its keys sorted, which a HashMap can't offer at any speed
+    let btree = maps::btree_map(&[30, 10, 20]);
+    println!(
+        "\nBTreeMap keys in order: {:?}",
+        btree.keys().collect::<Vec<_>>()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let map: HashMap<f64, &str> = HashMap::from([(0.5, "half")]);
+    // error[E0277]: the trait bound `{float}: Eq` is not satisfied (f64 is neither Eq nor Hash)
+}
+
+/*
+ * Key Concepts:
+ * - HashMap: one hash and about one comparison per lookup, at any size
+ * - BTreeMap: comparisons per lookup grow like log(n), but keys stay sorted
+ * - Operation counts show how code scales; only a benchmark shows the time
+ * - Benchmark at more than one size: the winner can change as data grows
+ * - BTreeMap needs Ord keys, HashMap needs Hash + Eq (so no f64 keys)
+ */
+
+// EXPECTED:
+// 20000 queries: HashMap found 10000, BTreeMap found 10000
+//
+// 100 keys
+//   HashMap:  1.00 hashes per lookup
+//   BTreeMap: 9.96 comparisons per lookup
+// 10000 keys
+//   HashMap:  1.00 hashes per lookup
+//   BTreeMap: 21.33 comparisons per lookup
+//
+// BTreeMap keys in order: [10, 20, 30]
@@ -0,0 +1,80 @@
+// Benchmarking 3: Iterators vs Loops, and Reading Criterion
+// Demonstrates zero-cost iterators, black_box, criterion's report, and baselines
+//
+// Concepts: zero-cost iterators, black_box, criterion reports, baselines
+// Difficulty: intermediate
+// Minutes: 5
+//
+// `workloads/lib.rs` sums the squares of the even numbers three ways: an
+// iterator chain, a `for` loop, and a `while` loop that indexes. People
+// often expect the chain to be slower, because it is made of closures and
+// adapter structs, and the closures and structs do all disappear when
+// it's optimized. That doesn't make the three the same machine code,
+// though: the optimizer sees each one a little differently, and with
+// random numbers, where half the values are even, how it compiles the
+// even test can matter. `cargo bench --bench loops` shows whether it
+// does, on your machine, and `baselines.json` what it showed on ours.
+// Neither "iterators are free" nor "loops are faster" beats measuring.
+//
+// A benchmark measures what the optimizer left, though, so criterion's
+// closures pass their inputs through std::hint::black_box(). Without it,
+// the compiler may notice that the input never changes, and compute the
+// sum once; the benchmark would then time nothing at all.
+//
+// Criterion prints a line like this for every benchmark:
+//
+//     loops/iterator   time:   [4.9512 µs 4.9720 µs 4.9951 µs]
+//                      change: [-1.2034% -0.4001% +0.3121%] (p = 0.34 > 0.05)
+//                      No change in performance detected.
+//
+// The three times are a confidence interval for the mean: the middle one
+// is the estimate. The change line compares it with the last run, and
+// only calls it a change when the difference is unlikely to be noise.
+// Those results are kept under `target/criterion/`, which isn't
+// committed, so `baselines.json` in this chapter keeps a copy of one
+// machine's: `tutor bench` runs every benchmark and shows a table of the
+// new times next to those, and `tutor bench --save-baseline` replaces them.
+
+use workloads::loops;
+
+fn main() {
+    let values = workloads::numbers(10_000);
+    let sums = [
+        ("iterator", loops::with_iterator(&values)),
+        ("for_loop", loops::with_for_loop(&values)),
+        ("index_loop", loops::with_index_loop(&values)),
+    ];
+    for (name, sum) in sums {
+        println!("{:<10} {}", name, sum);
+    }
+    println!(
+        "all equal: {}",
+        sums.iter().all(|&(_, sum)| sum == sums[0].1)
+    );
+
+    // The chain is lazy: nothing runs until count() asks for values
+    let evens = values.iter().filter(|&&value| value.is_multiple_of(2));
+    println!("even numbers: {} of {}", evens.count(), values.len());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let squares = values.iter().map(|value| value * value).sum();
+    // error[E0283]: type annotations needed
+}
+
+/*
+ * Key Concepts:
+ * - Iterator chains lose their closures and adapters when optimized
+ * - Equivalent code can still compile differently; only measuring tells
+ * - Indexing is bounds-checked unless the optimizer proves it in range
+ * - black_box() stops the optimizer from deleting the work being timed
+ * - Criterion reports a confidence interval and whether a change is significant
+ * - Benchmark in release mode (cargo bench does) and compare against a baseline
+ */
+
+// EXPECTED:
+// iterator   15990331590217642000
+// for_loop   15990331590217642000
+// index_loop 15990331590217642000
+// all equal: true
+// even numbers: 5019 of 10000
@@ -0,0 +1,30 @@
+//! The same sum as an iterator chain, a `for` loop, and an indexed loop.
+//! The closures and adapters optimize away, but the three needn't tie.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+
+use workloads::loops;
+
+fn sums(c: &mut Criterion) {
+    let values = workloads::numbers(10_000);
+    let mut group = c.benchmark_group("loops");
+    group.bench_function("iterator", |b| {
+        b.iter(|| loops::with_iterator(black_box(&values)))
+    });
+    group.bench_function("for_loop", |b| {
+        b.iter(|| loops::with_for_loop(black_box(&values)))
+    });
+    group.bench_function("index_loop", |b| {
+        b.iter(|| loops::with_index_loop(black_box(&values)))
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20).warm_up_time(Duration::from_millis(300)).measurement_time(Duration::from_secs(1));
+    targets = sums
+}
+criterion_main!(benches);
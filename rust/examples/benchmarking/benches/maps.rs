@@ -0,0 +1,37 @@
+//! Looking up keys that are there and keys that aren't, in a HashMap and a
+//! BTreeMap of the same size.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+
+use workloads::maps;
+
+fn lookups(c: &mut Criterion) {
+    let mut group = c.benchmark_group("maps");
+    for size in [100, 10_000] {
+        let keys = workloads::numbers(size);
+        // Half hits, half misses, in a different order than inserted
+        let queries: Vec<u64> = keys.iter().rev().flat_map(|&key| [key, key ^ 1]).collect();
+        let hash = maps::hash_map(&keys);
+        let btree = maps::btree_map(&keys);
+        group.bench_with_input(
+            BenchmarkId::new("hash_map", size),
+            &queries,
+            |b, queries| b.iter(|| maps::hits_hash(&hash, black_box(queries))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("btree_map", size),
+            &queries,
+            |b, queries| b.iter(|| maps::hits_btree(&btree, black_box(queries))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20).warm_up_time(Duration::from_millis(300)).measurement_time(Duration::from_secs(1));
+    targets = lookups
+}
+criterion_main!(benches);
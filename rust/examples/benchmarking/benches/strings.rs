@@ -0,0 +1,41 @@
+//! Four ways of joining words, at three sizes. The quadratic one falls
+//! further behind each time the input grows tenfold.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+
+use workloads::strings;
+
+fn join(c: &mut Criterion) {
+    let mut group = c.benchmark_group("strings");
+    for count in [10, 100, 1000] {
+        let words = workloads::words(count);
+        // black_box keeps the optimizer from computing the result once,
+        // outside the loop, or throwing it away unused
+        group.bench_with_input(BenchmarkId::new("format", count), &words, |b, words| {
+            b.iter(|| strings::with_format(black_box(words)))
+        });
+        group.bench_with_input(BenchmarkId::new("push_str", count), &words, |b, words| {
+            b.iter(|| strings::with_push_str(black_box(words)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("with_capacity", count),
+            &words,
+            |b, words| b.iter(|| strings::with_capacity(black_box(words))),
+        );
+        group.bench_with_input(BenchmarkId::new("join", count), &words, |b, words| {
+            b.iter(|| strings::with_join(black_box(words)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // Fewer, shorter samples than the defaults, so the chapter runs in
+    // seconds; drop this line for more precise numbers
+    config = Criterion::default().sample_size(20).warm_up_time(Duration::from_millis(300)).measurement_time(Duration::from_secs(1));
+    targets = join
+}
+criterion_main!(benches);
@@ -0,0 +1,187 @@
+//! The code the benchmarking chapter measures: several ways of doing the
+//! same three jobs. The numbered examples show that each way gives the
+//! same answer, and `benches/` times them with criterion.
+//!
+//! ```
+//! let words = workloads::words(3);
+//! assert_eq!(workloads::strings::with_join(&words), "w0 w1 w2");
+//! ```
+
+/// `count` short words, `w0` to `w<count - 1>`.
+pub fn words(count: usize) -> Vec<String> {
+    (0..count).map(|i| format!("w{}", i)).collect()
+}
+
+/// `count` pseudo-random numbers, the same ones on every run, so benchmark
+/// runs can be compared.
+pub fn numbers(count: usize) -> Vec<u64> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..count)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+        .collect()
+}
+
+/// Joining words with spaces, four ways.
+pub mod strings {
+    /// Builds a new string on every step, copying everything so far:
+    /// quadratic in the total length.
+    pub fn with_format(words: &[String]) -> String {
+        let mut joined = String::new();
+        for (i, word) in words.iter().enumerate() {
+            joined = if i == 0 {
+                word.clone()
+            } else {
+                format!("{} {}", joined, word)
+            };
+        }
+        joined
+    }
+
+    /// Appends in place; the string doubles its capacity when it's full.
+    pub fn with_push_str(words: &[String]) -> String {
+        let mut joined = String::new();
+        for (i, word) in words.iter().enumerate() {
+            if i > 0 {
+                joined.push(' ');
+            }
+            joined.push_str(word);
+        }
+        joined
+    }
+
+    /// Appends in place, into a string that is big enough from the start.
+    pub fn with_capacity(words: &[String]) -> String {
+        let length = words.iter().map(String::len).sum::<usize>() + words.len().saturating_sub(1);
+        let mut joined = String::with_capacity(length);
+        for (i, word) in words.iter().enumerate() {
+            if i > 0 {
+                joined.push(' ');
+            }
+            joined.push_str(word);
+        }
+        joined
+    }
+
+    /// Lets the standard library do it (it sizes the string first, too).
+    pub fn with_join(words: &[String]) -> String {
+        words.join(" ")
+    }
+}
+
+/// Looking keys up in a HashMap and in a BTreeMap.
+pub mod maps {
+    use std::collections::{BTreeMap, HashMap};
+
+    pub fn hash_map(keys: &[u64]) -> HashMap<u64, usize> {
+        keys.iter().enumerate().map(|(i, &key)| (key, i)).collect()
+    }
+
+    pub fn btree_map(keys: &[u64]) -> BTreeMap<u64, usize> {
+        keys.iter().enumerate().map(|(i, &key)| (key, i)).collect()
+    }
+
+    /// How many of `queries` are keys of `map`.
+    pub fn hits_hash(map: &HashMap<u64, usize>, queries: &[u64]) -> usize {
+        queries
+            .iter()
+            .filter(|query| map.contains_key(query))
+            .count()
+    }
+
+    /// How many of `queries` are keys of `map`.
+    pub fn hits_btree(map: &BTreeMap<u64, usize>, queries: &[u64]) -> usize {
+        queries
+            .iter()
+            .filter(|query| map.contains_key(query))
+            .count()
+    }
+}
+
+/// Summing the squares of the even numbers, with an iterator and with loops.
+pub mod loops {
+    pub fn with_iterator(values: &[u64]) -> u64 {
+        values
+            .iter()
+            .filter(|&&value| value.is_multiple_of(2))
+            .map(|&value| value.wrapping_mul(value))
+            .fold(0, u64::wrapping_add)
+    }
+
+    pub fn with_for_loop(values: &[u64]) -> u64 {
+        let mut sum: u64 = 0;
+        for &value in values {
+            if value.is_multiple_of(2) {
+                sum = sum.wrapping_add(value.wrapping_mul(value));
+            }
+        }
+        sum
+    }
+
+    /// Indexes instead of iterating, so every `values[i]` is bounds-checked,
+    /// unless the optimizer can prove `i` is in range (here it can).
+    pub fn with_index_loop(values: &[u64]) -> u64 {
+        let mut sum: u64 = 0;
+        let mut i = 0;
+        while i < values.len() {
+            if values[i].is_multiple_of(2) {
+                sum = sum.wrapping_add(values[i].wrapping_mul(values[i]));
+            }
+            i += 1;
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A benchmark of wrong code measures nothing useful, so every version
+    // must agree before any of them is timed.
+
+    #[test]
+    fn every_join_agrees() {
+        for count in [0, 1, 2, 100] {
+            let words = words(count);
+            let expected = strings::with_join(&words);
+            assert_eq!(strings::with_format(&words), expected);
+            assert_eq!(strings::with_push_str(&words), expected);
+            assert_eq!(strings::with_capacity(&words), expected);
+        }
+    }
+
+    #[test]
+    fn with_capacity_never_grows() {
+        let words = words(100);
+        let joined = strings::with_capacity(&words);
+        assert_eq!(joined.capacity(), joined.len());
+    }
+
+    #[test]
+    fn both_maps_find_the_same_keys() {
+        let keys = numbers(1000);
+        let queries: Vec<u64> = keys
+            .iter()
+            .step_by(2)
+            .map(|key| key + 1)
+            .chain(keys.iter().copied())
+            .collect();
+        let hits = maps::hits_hash(&maps::hash_map(&keys), &queries);
+        assert_eq!(maps::hits_btree(&maps::btree_map(&keys), &queries), hits);
+        assert!(hits >= keys.len());
+    }
+
+    #[test]
+    fn every_loop_agrees() {
+        let values = numbers(1000);
+        let expected = loops::with_iterator(&values);
+        assert_eq!(loops::with_for_loop(&values), expected);
+        assert_eq!(loops::with_index_loop(&values), expected);
+    }
+}
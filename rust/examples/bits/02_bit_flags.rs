@@ -0,0 +1,299 @@
+// Bits and Bytes 2: Bit Flags
+// Demonstrates packing yes/no options into the bits of one integer, with a flags type written by hand
+//
+// Concepts: bitwise operators, masks and shifts, a newtype over u8, BitOr/BitAnd/Not, count_ones, bit fields
+// Difficulty: intermediate
+// Minutes: 20
+//
+// File permissions, keyboard modifiers, and protocol options are sets of
+// yes/no switches, and the cheap way to store one is a bit each. Unix
+// gives execute bit 0, write bit 1, and read bit 2, so 0b110 is read and
+// write. An integer is then a whole set, and the bitwise operators are
+// the set operations:
+// - `a | b` is the union: every flag in either
+// - `a & b` is the intersection: the flags in both
+// - `a & !b` removes b's flags from a, and `a ^ b` toggles them
+// - `a & b == b` asks whether a has all of b's flags
+//
+// A bare u8 would do, but then any number is a valid set of flags, and
+// any u8 can be passed where permissions were meant. So the flags get
+// their own type, a newtype over u8 with a const per flag and the
+// operators implemented on it. That's what the bitflags crate generates;
+// here it's written out.
+//
+// The same masks and shifts pull a number out of some of an integer's
+// bits, which is how hardware registers and packed formats store small
+// fields side by side.
+
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not, Sub};
+
+// ===== A flags type =====
+
+/// A set of file permissions, one bit each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+struct Permissions(u8);
+
+impl Permissions {
+    const READ: Permissions = Permissions(1 << 2);
+    const WRITE: Permissions = Permissions(1 << 1);
+    const EXECUTE: Permissions = Permissions(1 << 0);
+
+    /// Every flag there is, and the names Display uses for them.
+    const ALL: [(Permissions, char); 3] = [
+        (Permissions::READ, 'r'),
+        (Permissions::WRITE, 'w'),
+        (Permissions::EXECUTE, 'x'),
+    ];
+
+    const fn empty() -> Self {
+        Permissions(0)
+    }
+
+    const fn all() -> Self {
+        Permissions(Self::READ.0 | Self::WRITE.0 | Self::EXECUTE.0)
+    }
+
+    /// The set for these bits, or None if some of them aren't flags. A
+    /// plain `Permissions(bits)` would let bit 7 in, and Display would
+    /// quietly ignore it.
+    fn from_bits(bits: u8) -> Option<Self> {
+        (bits & !Self::all().0 == 0).then_some(Permissions(bits))
+    }
+
+    fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Whether every flag in `other` is set.
+    fn contains(self, other: Permissions) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn insert(&mut self, other: Permissions) {
+        self.0 |= other.0;
+    }
+
+    fn remove(&mut self, other: Permissions) {
+        self.0 &= !other.0;
+    }
+
+    fn toggle(&mut self, other: Permissions) {
+        self.0 ^= other.0;
+    }
+
+    fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The flags that are set, one at a time.
+    fn iter(self) -> impl Iterator<Item = Permissions> {
+        Self::ALL
+            .into_iter()
+            .map(|(flag, _)| flag)
+            .filter(move |&flag| self.contains(flag))
+    }
+}
+
+impl BitOr for Permissions {
+    type Output = Permissions;
+
+    fn bitor(self, other: Permissions) -> Permissions {
+        Permissions(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Permissions {
+    fn bitor_assign(&mut self, other: Permissions) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for Permissions {
+    type Output = Permissions;
+
+    fn bitand(self, other: Permissions) -> Permissions {
+        Permissions(self.0 & other.0)
+    }
+}
+
+/// The complement, within the flags that exist: `!READ` is write and
+/// execute, not a u8 with seven bits set.
+impl Not for Permissions {
+    type Output = Permissions;
+
+    fn not(self) -> Permissions {
+        Permissions(!self.0 & Self::all().0)
+    }
+}
+
+/// `a - b` is a without b's flags, as for sets.
+impl Sub for Permissions {
+    type Output = Permissions;
+
+    fn sub(self, other: Permissions) -> Permissions {
+        Permissions(self.0 & !other.0)
+    }
+}
+
+/// "rw-", the way `ls -l` shows it.
+impl fmt::Display for Permissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (flag, name) in Self::ALL {
+            let shown = if self.contains(flag) { name } else { '-' };
+            write!(f, "{shown}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Unix mode bits: three sets of permissions, three bits each, for the
+/// owner, the group, and everyone else. 0o754 is rwxr-xr--.
+fn mode_string(mode: u16) -> String {
+    [6, 3, 0]
+        .iter()
+        .map(|shift| {
+            let bits = ((mode >> shift) & 0b111) as u8;
+            Permissions::from_bits(bits)
+                .expect("three bits are always flags")
+                .to_string()
+        })
+        .collect()
+}
+
+// ===== Bit fields =====
+
+/// A color packed into 16 bits as RGB565: five bits of red, six of green,
+/// five of blue, from the top bit down.
+fn pack_rgb565(red: u8, green: u8, blue: u8) -> u16 {
+    // Keep each channel's top bits, then slide it into its field
+    let r = u16::from(red >> 3);
+    let g = u16::from(green >> 2);
+    let b = u16::from(blue >> 3);
+    (r << 11) | (g << 5) | b
+}
+
+fn unpack_rgb565(packed: u16) -> (u8, u8, u8) {
+    // Shift the field down to bit 0, then mask off everything above it
+    let r = (packed >> 11) & 0b1_1111;
+    let g = (packed >> 5) & 0b11_1111;
+    let b = packed & 0b1_1111;
+    // Scale back up to 0..=255
+    ((r << 3) as u8, (g << 2) as u8, (b << 3) as u8)
+}
+
+fn main() {
+    println!("-- flags --");
+    let mut perms = Permissions::READ | Permissions::WRITE;
+    println!("{perms} = {:#05b}, {} flags", perms.bits(), perms.len());
+    println!("can write: {}", perms.contains(Permissions::WRITE));
+    println!(
+        "can read and execute: {}",
+        perms.contains(Permissions::READ | Permissions::EXECUTE)
+    );
+    perms.insert(Permissions::EXECUTE);
+    println!("after insert(EXECUTE): {perms}");
+    perms.remove(Permissions::WRITE);
+    println!("after remove(WRITE): {perms}");
+    perms.toggle(Permissions::READ | Permissions::WRITE);
+    println!("after toggle(READ | WRITE): {perms}");
+    let mut built = Permissions::empty();
+    built |= Permissions::READ;
+    println!(
+        "empty | READ: {built}, empty is empty: {}",
+        Permissions::empty().is_empty()
+    );
+    println!(
+        "!READ = {}, all - WRITE = {}",
+        !Permissions::READ,
+        Permissions::all() - Permissions::WRITE
+    );
+    println!(
+        "common to rw- and -wx: {}",
+        Permissions(0b110) & Permissions(0b011)
+    );
+    let names: Vec<String> = Permissions::all()
+        .iter()
+        .map(|flag| format!("{:03b}", flag.bits()))
+        .collect();
+    println!("one at a time: {}", names.join(" "));
+    println!("from_bits(0b101): {:?}", Permissions::from_bits(0b101));
+    println!(
+        "from_bits(0b1000_0001): {:?}",
+        Permissions::from_bits(0b1000_0001)
+    );
+    println!("mode 0o754: {}", mode_string(0o754));
+    println!("mode 0o640: {}", mode_string(0o640));
+
+    println!("-- counting bits --");
+    let value: u32 = 0b1011_0000;
+    println!(
+        "{value:#b}: {} ones, {} trailing zeros, {} leading zeros",
+        value.count_ones(),
+        value.trailing_zeros(),
+        value.leading_zeros()
+    );
+    // The lowest set bit; a power of two has exactly one
+    println!("lowest set bit: {:#b}", value & value.wrapping_neg());
+    println!(
+        "64 is a power of two: {}, 96: {}",
+        64_u32.is_power_of_two(),
+        96_u32.is_power_of_two()
+    );
+    println!("next power of two after 96: {}", 96_u32.next_power_of_two());
+    println!(
+        "0x12345678 rotated left 8: {:#x}",
+        0x1234_5678_u32.rotate_left(8)
+    );
+
+    println!("-- bit fields --");
+    let orange = pack_rgb565(255, 165, 0);
+    println!("orange (255, 165, 0) as RGB565: {orange:#06x} = {orange:016b}");
+    println!(
+        "and back: {:?}, the low bits are lost",
+        unpack_rgb565(orange)
+    );
+
+    // This would cause an error:
+    // let perms = Permissions::READ | 0b100;
+    // error[E0308]: mismatched types
+}
+
+/*
+ * Key Concepts:
+ * - One bit per flag: | is union, & intersection, & ! removal, ^ toggle
+ * - A newtype over the integer keeps flags apart from numbers and other flags
+ * - from_bits rejects bits that aren't flags; Not stays within the known flags
+ * - count_ones, trailing_zeros, and friends answer questions about bits directly
+ * - Bit fields: shift the field down, then mask it; mask then shift to pack
+ */
+
+// EXPECTED:
+// -- flags --
+// rw- = 0b110, 2 flags
+// can write: true
+// can read and execute: false
+// after insert(EXECUTE): rwx
+// after remove(WRITE): r-x
+// after toggle(READ | WRITE): -wx
+// empty | READ: r--, empty is empty: true
+// !READ = -wx, all - WRITE = r-x
+// common to rw- and -wx: -w-
+// one at a time: 100 010 001
+// from_bits(0b101): Some(Permissions(5))
+// from_bits(0b1000_0001): None
+// mode 0o754: rwxr-xr--
+// mode 0o640: rw-r-----
+// -- counting bits --
+// 0b10110000: 3 ones, 4 trailing zeros, 24 leading zeros
+// lowest set bit: 0b10000
+// 64 is a power of two: true, 96: false
+// next power of two after 96: 128
+// 0x12345678 rotated left 8: 0x34567812
+// -- bit fields --
+// orange (255, 165, 0) as RGB565: 0xfd20 = 1111110100100000
+// and back: (248, 164, 0), the low bits are lost
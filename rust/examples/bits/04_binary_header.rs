@@ -0,0 +1,369 @@
+// Bits and Bytes 4: Parsing a Binary Header
+// Demonstrates reading a real file format's header field by field, and refusing bytes that don't fit it
+//
+// Concepts: binary formats, a cursor over a byte slice, const generic arrays, magic numbers, error types for bad input, writing the bytes back
+// Difficulty: intermediate
+// Minutes: 20
+//
+// A WAV file starts with a 44-byte header that says how to play the
+// samples after it. Every field sits at a fixed offset, and every
+// integer is little-endian:
+//
+//     offset  size  field
+//          0     4  "RIFF"
+//          4     4  u32: file size - 8
+//          8     4  "WAVE"
+//         12     4  "fmt "
+//         16     4  u32: 16, the size of the fmt fields that follow
+//         20     2  u16: 1 for plain PCM samples
+//         22     2  u16: channels
+//         24     4  u32: samples per second
+//         28     4  u32: bytes per second
+//         32     2  u16: bytes per sample frame, all channels
+//         34     2  u16: bits per sample
+//         36     4  "data"
+//         40     4  u32: how many bytes of samples follow
+//
+// Reading it is a cursor moving through the bytes: take 4, check they
+// are "RIFF", take 4 more as a u32, and so on. Every take can run out of
+// bytes, since a file can be cut short, and every field can hold
+// nonsense, since a file can be anything. So each step returns a Result,
+// and `?` stops at the first problem with an error that says where.
+//
+// The fixed-size tags at the start, like "RIFF" and "WAVE", are magic
+// numbers: they reject a file that isn't a WAV at all before anything
+// else is read. Real WAV files may have other chunks before "data"; this
+// reader handles the simple layout most programs write.
+
+use std::error::Error;
+use std::fmt;
+
+// ===== The header =====
+
+#[derive(Debug, Clone, PartialEq)]
+struct WavHeader {
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+    data_len: u32,
+}
+
+impl WavHeader {
+    const LEN: usize = 44;
+
+    fn block_align(&self) -> u16 {
+        self.channels * (self.bits_per_sample / 8)
+    }
+
+    fn byte_rate(&self) -> u32 {
+        self.sample_rate * u32::from(self.block_align())
+    }
+
+    fn duration_ms(&self) -> u64 {
+        u64::from(self.data_len) * 1000 / u64::from(self.byte_rate())
+    }
+
+    /// The 44 bytes, in the order the table above gives them.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::LEN);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + self.data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16_u32.to_le_bytes());
+        bytes.extend_from_slice(&1_u16.to_le_bytes());
+        bytes.extend_from_slice(&self.channels.to_le_bytes());
+        bytes.extend_from_slice(&self.sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&self.byte_rate().to_le_bytes());
+        bytes.extend_from_slice(&self.block_align().to_le_bytes());
+        bytes.extend_from_slice(&self.bits_per_sample.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&self.data_len.to_le_bytes());
+        bytes
+    }
+}
+
+// ===== What can go wrong =====
+
+#[derive(Debug, PartialEq)]
+enum HeaderError {
+    /// The bytes ran out at `offset`, wanting `needed` more.
+    TooShort { offset: usize, needed: usize },
+    /// A tag wasn't what the format has there.
+    BadTag {
+        offset: usize,
+        expected: [u8; 4],
+        found: [u8; 4],
+    },
+    /// A field holds a value this reader doesn't handle.
+    Unsupported { field: &'static str, value: u32 },
+    /// Two fields disagree.
+    Inconsistent {
+        field: &'static str,
+        expected: u32,
+        found: u32,
+    },
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HeaderError::TooShort { offset, needed } => {
+                write!(
+                    f,
+                    "too short: needed {needed} more bytes at offset {offset}"
+                )
+            }
+            HeaderError::BadTag {
+                offset,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {:?} at offset {offset}, found {:?}",
+                String::from_utf8_lossy(expected),
+                String::from_utf8_lossy(found)
+            ),
+            HeaderError::Unsupported { field, value } => write!(f, "unsupported {field}: {value}"),
+            HeaderError::Inconsistent {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{field} should be {expected}, but the header says {found}"
+            ),
+        }
+    }
+}
+
+impl Error for HeaderError {}
+
+// ===== Reading =====
+
+/// A cursor over a byte slice: each read takes the next bytes and moves
+/// past them.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, offset: 0 }
+    }
+
+    /// The next N bytes, as an array. N comes from the caller's type:
+    /// `let tag: [u8; 4] = reader.take()?` takes four.
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], HeaderError> {
+        let rest = &self.bytes[self.offset..];
+        let chunk = rest
+            .first_chunk::<N>()
+            .ok_or_else(|| HeaderError::TooShort {
+                offset: self.offset,
+                needed: N - rest.len(),
+            })?;
+        self.offset += N;
+        Ok(*chunk)
+    }
+
+    fn u16_le(&mut self) -> Result<u16, HeaderError> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn u32_le(&mut self) -> Result<u32, HeaderError> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    /// Takes four bytes, which must be `expected`.
+    fn tag(&mut self, expected: &[u8; 4]) -> Result<(), HeaderError> {
+        let offset = self.offset;
+        let found: [u8; 4] = self.take()?;
+        if &found != expected {
+            return Err(HeaderError::BadTag {
+                offset,
+                expected: *expected,
+                found,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn parse(bytes: &[u8]) -> Result<WavHeader, HeaderError> {
+    let mut reader = Reader::new(bytes);
+    reader.tag(b"RIFF")?;
+    let riff_len = reader.u32_le()?;
+    reader.tag(b"WAVE")?;
+    reader.tag(b"fmt ")?;
+    let fmt_len = reader.u32_le()?;
+    if fmt_len != 16 {
+        return Err(HeaderError::Unsupported {
+            field: "fmt chunk size",
+            value: fmt_len,
+        });
+    }
+    let format = reader.u16_le()?;
+    if format != 1 {
+        return Err(HeaderError::Unsupported {
+            field: "sample format",
+            value: format.into(),
+        });
+    }
+    let channels = reader.u16_le()?;
+    let sample_rate = reader.u32_le()?;
+    let byte_rate = reader.u32_le()?;
+    let block_align = reader.u16_le()?;
+    let bits_per_sample = reader.u16_le()?;
+    reader.tag(b"data")?;
+    let data_len = reader.u32_le()?;
+
+    if channels == 0 {
+        return Err(HeaderError::Unsupported {
+            field: "channel count",
+            value: 0,
+        });
+    }
+    if !matches!(bits_per_sample, 8 | 16 | 24 | 32) {
+        return Err(HeaderError::Unsupported {
+            field: "bits per sample",
+            value: bits_per_sample.into(),
+        });
+    }
+    // The derived fields must agree with the ones they're derived from.
+    // The header's numbers can be anything, so the products can overflow,
+    // and checked_mul says so instead of panicking or wrapping
+    let expected_align =
+        channels
+            .checked_mul(bits_per_sample / 8)
+            .ok_or(HeaderError::Unsupported {
+                field: "channel count",
+                value: channels.into(),
+            })?;
+    if block_align != expected_align {
+        return Err(HeaderError::Inconsistent {
+            field: "block align",
+            expected: expected_align.into(),
+            found: block_align.into(),
+        });
+    }
+    let expected_rate = match sample_rate.checked_mul(u32::from(block_align)) {
+        Some(rate) if rate > 0 => rate,
+        _ => {
+            return Err(HeaderError::Unsupported {
+                field: "sample rate",
+                value: sample_rate,
+            })
+        }
+    };
+    if byte_rate != expected_rate {
+        return Err(HeaderError::Inconsistent {
+            field: "byte rate",
+            expected: expected_rate,
+            found: byte_rate,
+        });
+    }
+    if riff_len != data_len.wrapping_add(36) {
+        return Err(HeaderError::Inconsistent {
+            field: "RIFF size",
+            expected: data_len.wrapping_add(36),
+            found: riff_len,
+        });
+    }
+    Ok(WavHeader {
+        channels,
+        sample_rate,
+        bits_per_sample,
+        data_len,
+    })
+}
+
+/// Sixteen bytes a line, with the offset in front, as `xxd` shows them.
+fn hex_dump(bytes: &[u8]) {
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|byte| format!("{byte:02x}")).collect();
+        let text: String = chunk
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii_graphic() {
+                    byte as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        println!("{:08x}: {:<47}  {text}", line * 16, hex.join(" "));
+    }
+}
+
+fn main() {
+    println!("-- writing a header --");
+    // One second of CD audio: 44100 samples a second, 16 bits, stereo
+    let cd = WavHeader {
+        channels: 2,
+        sample_rate: 44_100,
+        bits_per_sample: 16,
+        data_len: 176_400,
+    };
+    let bytes = cd.to_bytes();
+    hex_dump(&bytes);
+
+    println!("-- reading it back --");
+    let parsed = parse(&bytes).expect("our own header parses");
+    println!("{parsed:?}");
+    println!(
+        "{} ms, round trip is the same: {}",
+        parsed.duration_ms(),
+        parsed == cd
+    );
+
+    println!("-- bad input --");
+    let mut cases: Vec<(&str, Vec<u8>)> =
+        vec![("cut short", bytes[..30].to_vec()), ("empty", Vec::new())];
+    let mut big_endian = bytes.clone();
+    big_endian[3] = b'X'; // "RIFX" is the big-endian variant of the format
+    cases.push(("RIFX", big_endian));
+    let mut float = bytes.clone();
+    float[20..22].copy_from_slice(&3_u16.to_le_bytes()); // 3 is IEEE float samples
+    cases.push(("float samples", float));
+    let mut wrong_rate = bytes.clone();
+    wrong_rate[28..32].copy_from_slice(&88_200_u32.to_le_bytes());
+    cases.push(("wrong byte rate", wrong_rate));
+    let mut huge = bytes.clone();
+    huge[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+    cases.push(("huge sample rate", huge));
+    for (name, case) in &cases {
+        match parse(case) {
+            Ok(header) => println!("{name}: parsed {header:?}"),
+            Err(error) => println!("{name}: {error}"),
+        }
+    }
+
+    // This would cause an error:
+    // let magic: [u8; 4] = bytes[0..4];
+    // error[E0308]: mismatched types
+}
+
+/*
+ * Key Concepts:
+ * - A binary format fixes every field's offset, size, and byte order
+ * - A cursor that takes N bytes at a time turns the table into code
+ * - Every read can run out of bytes, so each returns a Result
+ * - Magic numbers reject the wrong kind of file before anything else
+ * - Check fields against each other, with checked arithmetic: the input can be anything
+ */
+
+// EXPECTED:
+// -- writing a header --
+// 00000000: 52 49 46 46 34 b1 02 00 57 41 56 45 66 6d 74 20  RIFF4...WAVEfmt.
+// 00000010: 10 00 00 00 01 00 02 00 44 ac 00 00 10 b1 02 00  ........D.......
+// 00000020: 04 00 10 00 64 61 74 61 10 b1 02 00              ....data....
+// -- reading it back --
+// WavHeader { channels: 2, sample_rate: 44100, bits_per_sample: 16, data_len: 176400 }
+// 1000 ms, round trip is the same: true
+// -- bad input --
+// cut short: too short: needed 2 more bytes at offset 28
+// empty: too short: needed 4 more bytes at offset 0
+// RIFX: expected "RIFF" at offset 0, found "RIFX"
+// float samples: unsupported sample format: 3
+// wrong byte rate: byte rate should be 176400, but the header says 88200
+// huge sample rate: unsupported sample rate: 4294967295
@@ -0,0 +1,95 @@
+// Build scripts 1: Generating a Lookup Table
+// Demonstrates build.rs, writing Rust source into OUT_DIR, include!, rerun-if-changed, and a perfect hash found at build time
+//
+// Concepts: build.rs, OUT_DIR, include!, rerun-if-changed, perfect hashing
+// Difficulty: advanced
+// Minutes: 10
+//
+// A package with a build.rs is built in two steps. First cargo compiles
+// build.rs and runs it, on the machine doing the build; then it compiles
+// the package itself. Whatever the script can compute, the package gets
+// for free at run time, and what it prints on stdout are instructions for
+// cargo, one per line:
+//
+//     cargo:rerun-if-changed=data/colors.txt   run me again if this changes
+//     cargo:rustc-env=NAME=value                set NAME for env!()
+//     cargo:warning=...                         show this to whoever builds
+//
+// This package's build.rs reads data/colors.txt, a list of CSS color
+// names, and writes a Rust file with a table of them into OUT_DIR, a
+// directory cargo gives each package for generated files (somewhere under
+// target/, never in the source tree). colors/lib.rs pastes it in:
+//
+//     include!(concat!(env!("OUT_DIR"), "/colors.rs"));
+//
+// The table is a *perfect hash*: the script tries seeds for the hash
+// function until every color lands in a slot of its own, so a lookup is
+// one hash and one comparison, with no collisions to walk through. The
+// search takes a moment, and happens once, at build time. Add a color to
+// data/colors.txt, run this again, and the script reruns and finds a new
+// seed; change nothing, and it doesn't run at all.
+
+use colors::{lookup, seed, slots, NAMES};
+
+/// The generated file, as text, to show what build.rs wrote.
+const GENERATED: &str = include_str!(concat!(env!("OUT_DIR"), "/colors.rs"));
+
+fn main() {
+    for name in ["rebeccapurple", "Tomato", "TEAL", "grey", "blurple"] {
+        match lookup(name) {
+            Some(rgb) => println!("{name:>13} = {rgb}"),
+            None => println!("{name:>13}: not a color in the table"),
+        }
+    }
+
+    println!(
+        "\n{} colors in {} slots; seed {} puts each in its own",
+        NAMES.len(),
+        slots(),
+        seed()
+    );
+
+    println!("\nThe start of OUT_DIR/colors.rs:");
+    for line in GENERATED.lines().take(8) {
+        if line.is_empty() {
+            println!();
+        } else {
+            println!("    {line}");
+        }
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let generated_at = env!("COLORS_GENERATED_AT");
+    }
+    // error: environment variable `COLORS_GENERATED_AT` not defined at compile time
+}
+
+/*
+ * Key Concepts:
+ * - build.rs runs before the package is compiled, on the machine doing the build
+ * - Generated code goes in OUT_DIR and comes back in with include!(concat!(env!("OUT_DIR"), ...))
+ * - cargo:rerun-if-changed limits reruns to the files the script actually reads
+ * - Work done at build time (like finding a perfect hash) costs nothing at run time
+ * - A panic in build.rs fails the build, which suits bad input data
+ */
+
+// EXPECTED:
+// rebeccapurple = #663399
+//        Tomato = #ff6347
+//          TEAL = #008080
+//          grey: not a color in the table
+//       blurple: not a color in the table
+//
+// 36 colors in 128 slots; seed 362 puts each in its own
+//
+// The start of OUT_DIR/colors.rs:
+//     // Generated by build.rs from data/colors.txt. Do not edit.
+//
+//     /// The seed that gives every color its own slot.
+//     const SEED: u64 = 362;
+//
+//     static TABLE: [Option<(&str, Rgb)>; 128] = [
+//         Some(("cyan", Rgb(0, 255, 255))),
+//         Some(("chocolate", Rgb(210, 105, 30))),
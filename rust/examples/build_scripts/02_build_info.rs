@@ -0,0 +1,90 @@
+// Build scripts 2: Build-Time Environment Variables
+// Demonstrates env! and option_env!, the variables cargo sets, and embedding the git commit with cargo:rustc-env
+//
+// Concepts: env!, option_env!, cargo environment variables, cargo:rustc-env
+// Difficulty: intermediate
+// Minutes: 10
+//
+// env!("NAME") reads an environment variable while the program is being
+// compiled and bakes its value into the binary as a &'static str; it is a
+// compile error if the variable isn't set. option_env! gives an
+// Option<&'static str> instead. Neither looks at the environment the
+// program later runs in: that is std::env::var, at run time.
+//
+// Cargo sets a few for every crate it compiles, from Cargo.toml:
+// CARGO_PKG_NAME, CARGO_PKG_VERSION, CARGO_MANIFEST_DIR, ... Build scripts
+// get more, describing the build rather than the package: TARGET (the
+// platform being compiled for), HOST, PROFILE, OUT_DIR, and a CARGO_CFG_*
+// for each cfg of the target. A build script can pass any of them on, or
+// anything else it works out, with `cargo:rustc-env=NAME=value`.
+//
+// This package's build.rs passes on TARGET and PROFILE, and runs
+// `git rev-parse` to embed the commit being built: the version line a
+// bug report should include. If git isn't installed, or the code isn't in
+// a checkout, it sets nothing, so the program uses option_env! and copes.
+// build.rs also asks to rerun when HEAD moves; otherwise cargo would keep
+// the commit of the first build forever.
+//
+// What this prints depends on your machine and on the commit you're at,
+// so it has no EXPECTED block.
+
+/// The version line, as `--version` might print it.
+fn version() -> String {
+    let commit = option_env!("GIT_COMMIT").unwrap_or("unknown commit");
+    format!(
+        "{} {} ({commit}, {} build for {})",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        env!("BUILD_PROFILE"),
+        env!("BUILD_TARGET")
+    )
+}
+
+fn main() {
+    println!("{}", version());
+
+    // Baked in at compile time: where the package was when it was built
+    println!("\nbuilt from {}", env!("CARGO_MANIFEST_DIR"));
+
+    // Read at run time, from whatever environment the program runs in
+    match std::env::var("BUILD_TARGET") {
+        Ok(value) => println!("BUILD_TARGET is also set now: {value}"),
+        Err(_) => println!("BUILD_TARGET is not set now: env! read it at compile time"),
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        const COMMIT: &str = option_env!("GIT_COMMIT");
+    }
+    // error[E0308]: mismatched types
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_version_line_names_the_package_and_target() {
+        let line = version();
+        assert!(line.starts_with("build-scripts-examples 0.1.0 ("));
+        assert!(line.contains(env!("BUILD_TARGET")));
+    }
+
+    #[test]
+    fn a_commit_hash_is_twelve_hex_digits() {
+        if let Some(commit) = option_env!("GIT_COMMIT") {
+            assert_eq!(commit.len(), 12);
+            assert!(commit.chars().all(|c| c.is_ascii_hexdigit()));
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - env! bakes a compile-time environment variable into the binary; option_env! allows it to be missing
+ * - std::env::var reads the environment at run time, which is a different thing
+ * - Cargo sets CARGO_PKG_* for every crate, and TARGET, PROFILE, OUT_DIR for build scripts
+ * - cargo:rustc-env=NAME=value passes a value from build.rs to env!
+ * - Embedding the git commit needs rerun-if-changed on HEAD to stay current
+ */
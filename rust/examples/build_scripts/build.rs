@@ -0,0 +1,160 @@
+// Build script: cargo compiles and runs this BEFORE building the package,
+// and reads what it prints.
+//
+// It does two jobs:
+//
+// 1. Turns data/colors.txt into Rust source: a lookup table with one slot
+//    per color, found by trying seeds for the hash in colors/hash.rs until
+//    no two colors land in the same slot (a "perfect hash"). The file goes
+//    in OUT_DIR, cargo's scratch directory for this package, and
+//    colors/lib.rs pulls it in with include!.
+// 2. Sets environment variables for the compiler with
+//    `cargo:rustc-env=NAME=value`, which the code reads with env!: the git
+//    commit being built, and cargo's own TARGET and PROFILE, which only
+//    build scripts are given.
+//
+// A panic here fails the build with the message, the right response to a
+// data file with a typo in it.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+include!("colors/hash.rs");
+
+/// How many seeds to try before giving up on a perfect hash.
+const MAX_SEEDS: u64 = 1_000_000;
+
+fn main() {
+    generate_color_table();
+    embed_build_info();
+
+    // Without any rerun-if-changed line, cargo reruns the script when any
+    // file in the package changes; with them, only when these do
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=data/colors.txt");
+    println!("cargo:rerun-if-changed=colors/hash.rs");
+}
+
+fn generate_color_table() {
+    let text = fs::read_to_string("data/colors.txt").expect("cannot read data/colors.txt");
+    let mut colors = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parsed = line
+            .split_once(char::is_whitespace)
+            .and_then(|(name, hex)| Some((name, parse_hex(hex.trim())?)));
+        match parsed {
+            Some((name, rgb)) => colors.push((name.to_ascii_lowercase(), rgb)),
+            None => panic!(
+                "data/colors.txt:{}: expected `name #rrggbb`, found `{line}`",
+                number + 1
+            ),
+        }
+    }
+
+    // Twice as many slots as colors makes a seed without collisions easy
+    // to find; a power of two turns the modulo into a mask
+    let slots = (colors.len() * 2).next_power_of_two();
+    let (seed, table) = (0..MAX_SEEDS)
+        .find_map(|seed| place(&colors, slots, seed).map(|table| (seed, table)))
+        .unwrap_or_else(|| panic!("no perfect hash for {} colors", colors.len()));
+
+    let mut source = String::new();
+    writeln!(
+        source,
+        "// Generated by build.rs from data/colors.txt. Do not edit."
+    )
+    .unwrap();
+    writeln!(source).unwrap();
+    writeln!(source, "/// The seed that gives every color its own slot.").unwrap();
+    writeln!(source, "const SEED: u64 = {seed};").unwrap();
+    writeln!(source).unwrap();
+    writeln!(source, "static TABLE: [Option<(&str, Rgb)>; {slots}] = [").unwrap();
+    for slot in &table {
+        match slot {
+            Some(index) => {
+                let (name, [r, g, b]) = &colors[*index];
+                writeln!(source, "    Some(({name:?}, Rgb({r}, {g}, {b}))),").unwrap();
+            }
+            None => writeln!(source, "    None,").unwrap(),
+        }
+    }
+    writeln!(source, "];").unwrap();
+    writeln!(source).unwrap();
+    writeln!(source, "/// Every color, in the order of data/colors.txt.").unwrap();
+    writeln!(source, "pub const NAMES: [&str; {}] = [", colors.len()).unwrap();
+    for (name, _) in &colors {
+        writeln!(source, "    {name:?},").unwrap();
+    }
+    writeln!(source, "];").unwrap();
+
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR for build scripts");
+    fs::write(Path::new(&out_dir).join("colors.rs"), source).expect("cannot write colors.rs");
+}
+
+fn parse_hex(hex: &str) -> Option<[u8; 3]> {
+    let digits = hex.strip_prefix('#')?;
+    if digits.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// The index of the color in each slot, if `seed` puts no two colors in
+/// the same one.
+fn place(colors: &[(String, [u8; 3])], slots: usize, seed: u64) -> Option<Vec<Option<usize>>> {
+    let mut table = vec![None; slots];
+    for (index, (name, _)) in colors.iter().enumerate() {
+        let slot = &mut table[(hash(name, seed) % slots as u64) as usize];
+        if slot.is_some() {
+            return None;
+        }
+        *slot = Some(index);
+    }
+    Some(table)
+}
+
+fn embed_build_info() {
+    // Set by cargo for every build script: the target being compiled for
+    // (not necessarily this machine), and "debug" or "release"
+    let target = env::var("TARGET").unwrap();
+    let profile = env::var("PROFILE").unwrap();
+    println!("cargo:rustc-env=BUILD_TARGET={target}");
+    println!("cargo:rustc-env=BUILD_PROFILE={profile}");
+
+    // Git may not be installed, and the code may not be in a checkout (a
+    // downloaded .zip); the build shouldn't fail for either
+    let Some(commit) = git(&["rev-parse", "--short=12", "HEAD"]) else {
+        return;
+    };
+    println!("cargo:rustc-env=GIT_COMMIT={commit}");
+    // Rerun when a commit is made or another branch is checked out: HEAD
+    // changes on a checkout, the branch's ref file on a commit
+    if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+        let head = Path::new(&git_dir).join("HEAD");
+        println!("cargo:rerun-if-changed={}", head.display());
+        if let Some(branch) = git(&["symbolic-ref", "HEAD"]) {
+            let branch_ref = Path::new(&git_dir).join(branch);
+            // A missing file would make cargo rerun the script every time
+            if branch_ref.is_file() {
+                println!("cargo:rerun-if-changed={}", branch_ref.display());
+            }
+        }
+    }
+}
+
+/// What `git args...` prints, trimmed, if it runs and succeeds.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
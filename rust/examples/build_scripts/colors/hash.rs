@@ -0,0 +1,18 @@
+// The hash behind the generated table. Both build.rs, which searches for
+// a seed that gives every color its own slot, and the library, which
+// looks colors up with that seed, `include!` this file, so the two can't
+// disagree about where a color lives.
+
+/// FNV-1a over the ASCII-lowercased bytes of `key`, started from `seed`,
+/// then mixed so that every bit of the result depends on every bit of
+/// the input: the table only uses the lowest few.
+fn hash(key: &str, seed: u64) -> u64 {
+    let mut h = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for byte in key.bytes() {
+        h ^= u64::from(byte.to_ascii_lowercase());
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^ (h >> 33)
+}
@@ -0,0 +1,88 @@
+//! CSS color names, looked up in a table that `build.rs` generated from
+//! `data/colors.txt`.
+//!
+//! The generated file lives in cargo's `OUT_DIR`, not in the repository,
+//! and `include!` pastes it in here as if it had been written by hand.
+//! `env!("OUT_DIR")` is filled in at compile time, because cargo sets it
+//! for the compiler whenever a package has a build script.
+//!
+//! ```
+//! assert_eq!(colors::lookup("RebeccaPurple").unwrap().to_string(), "#663399");
+//! assert_eq!(colors::lookup("not a color"), None);
+//! ```
+
+use std::fmt;
+
+include!("hash.rs");
+
+/// A color's red, green, and blue channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// SEED, TABLE, and NAMES
+include!(concat!(env!("OUT_DIR"), "/colors.rs"));
+
+/// The color named `name`, ignoring ASCII case. One hash and one string
+/// comparison, however many colors there are: the generated table has no
+/// collisions to resolve.
+pub fn lookup(name: &str) -> Option<Rgb> {
+    let slot = (hash(name, SEED) % TABLE.len() as u64) as usize;
+    match TABLE[slot] {
+        Some((key, rgb)) if key.eq_ignore_ascii_case(name) => Some(rgb),
+        _ => None,
+    }
+}
+
+/// How many slots the table has, for the curious: more than there are
+/// colors, so that a seed without collisions exists.
+pub fn slots() -> usize {
+    TABLE.len()
+}
+
+/// The seed build.rs found.
+pub fn seed() -> u64 {
+    SEED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_color_in_the_data_file_is_found() {
+        for name in NAMES {
+            assert!(lookup(name).is_some(), "{name} is missing");
+        }
+    }
+
+    #[test]
+    fn every_color_has_a_slot_of_its_own() {
+        let used = TABLE.iter().filter(|slot| slot.is_some()).count();
+        assert_eq!(used, NAMES.len());
+    }
+
+    #[test]
+    fn lookups_ignore_case() {
+        assert_eq!(lookup("Red"), Some(Rgb(255, 0, 0)));
+        assert_eq!(lookup("TEAL"), lookup("teal"));
+    }
+
+    #[test]
+    fn unknown_names_are_not_found() {
+        for name in ["", "reddish", "grey", "#ff0000"] {
+            assert_eq!(lookup(name), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn colors_print_as_hex() {
+        assert_eq!(Rgb(255, 165, 0).to_string(), "#ffa500");
+        assert_eq!(lookup("navy").unwrap().to_string(), "#000080");
+    }
+}
@@ -0,0 +1,130 @@
+// CLI 1: Parsing std::env::args by Hand
+// Demonstrates env::args, a small hand-written parser, and exit codes
+//
+// Concepts: env::args, argument parsing, exit codes
+// Difficulty: beginner
+// Minutes: 10
+//
+// A program's arguments arrive as a list of strings: the program's own
+// name first, then whatever followed it on the command line. Turning them
+// into settings is our job. Doing it once by hand shows what a library
+// like clap takes off our hands: flags in any order, values after options,
+// `--` to end the options, and good errors when something doesn't fit.
+
+use std::env;
+use std::process::ExitCode;
+
+/// What `greet [--shout] [--times N] [--] NAME` asked for.
+#[derive(Debug, PartialEq)]
+struct Config {
+    name: String,
+    shout: bool,
+    times: u32,
+}
+
+/// Parses the arguments after the program name.
+fn parse(args: impl IntoIterator<Item = String>) -> Result<Config, String> {
+    let mut args = args.into_iter();
+    let mut name = None;
+    let mut shout = false;
+    let mut times = 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--shout" | "-s" => shout = true,
+            // An option with a value takes the next argument too
+            "--times" | "-n" => {
+                let value = args.next().ok_or("--times needs a number")?;
+                times = value
+                    .parse()
+                    .map_err(|_| format!("--times: {:?} isn't a number", value))?;
+            }
+            // Everything after `--` is a value, even if it starts with '-'
+            "--" => {
+                name = args.next();
+                break;
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option {}", flag)),
+            _ if name.is_some() => return Err(format!("unexpected argument {:?}", arg)),
+            _ => name = Some(arg),
+        }
+    }
+    let name = name.ok_or("missing NAME")?;
+    Ok(Config { name, shout, times })
+}
+
+fn greet(config: &Config) -> String {
+    let greeting = format!("hello, {}!", config.name);
+    let greeting = if config.shout {
+        greeting.to_uppercase()
+    } else {
+        greeting
+    };
+    vec![greeting; config.times as usize].join(" ")
+}
+
+fn main() -> ExitCode {
+    // Try some command lines, as if typed after `greet`
+    let examples: [&[&str]; 7] = [
+        &["Ana"],
+        &["--shout", "Ana"],
+        &["Ana", "-n", "2"],
+        &["--", "--weird-name"],
+        &["--times", "two", "Ana"],
+        &["--loud", "Ana"],
+        &[],
+    ];
+    for example in examples {
+        let args = example.iter().map(|arg| arg.to_string());
+        match parse(args) {
+            Ok(config) => println!("greet {:?} -> {}", example, greet(&config)),
+            Err(error) => println!("greet {:?} -> error: {}", example, error),
+        }
+    }
+
+    // The real arguments. args() panics if one isn't valid Unicode;
+    // args_os() gives OsStrings for programs that must accept any path.
+    let mut args = env::args();
+    let program = args.next().unwrap_or_default();
+    let rest: Vec<String> = args.collect();
+    println!("this program got {} arguments", rest.len());
+    if rest.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let first: String = env::args().nth(1)?;
+    // error[E0277]: the `?` operator can only be used in a function that returns `Result` or `Option`
+
+    // Errors go to stderr and end in a non-zero exit code, so scripts can
+    // tell that something went wrong. 2 traditionally means "bad usage".
+    match parse(rest) {
+        Ok(config) => {
+            println!("{}", greet(&config));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}: {}", program, error);
+            eprintln!("usage: {} [--shout] [--times N] [--] NAME", program);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - env::args() yields the program name, then each argument, as Strings
+ * - A parser walks the list: flags, options that take the next value, `--`
+ * - Every mistake needs a helpful message, which is most of the code
+ * - Errors go to stderr, and ExitCode::from(2) signals bad usage
+ */
+
+// EXPECTED:
+// greet ["Ana"] -> hello, Ana!
+// greet ["--shout", "Ana"] -> HELLO, ANA!
+// greet ["Ana", "-n", "2"] -> hello, Ana! hello, Ana!
+// greet ["--", "--weird-name"] -> hello, --weird-name!
+// greet ["--times", "two", "Ana"] -> error: --times: "two" isn't a number
+// greet ["--loud", "Ana"] -> error: unknown option --loud
+// greet [] -> error: missing NAME
+// this program got 0 arguments
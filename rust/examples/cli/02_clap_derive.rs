@@ -0,0 +1,133 @@
+// CLI 2: clap's Derive API
+// Demonstrates #[derive(Parser)], flags, options, defaults, and validation
+//
+// Concepts: #[derive(Parser)], flags, options, defaults, validation
+// Difficulty: intermediate
+// Minutes: 10
+//
+// With clap we describe the arguments as a struct and derive a parser for
+// it. Field types say what each argument is: a bool is a flag, an Option
+// is an optional option, a Vec takes many values. Doc comments become the
+// --help text. clap then handles everything 01_env_args did by hand, plus
+// help, version, suggestions for typos, and errors in a standard format.
+
+use std::path::PathBuf;
+
+use clap::{ArgAction, Parser, ValueEnum};
+
+/// Resize images, as a demonstration of clap's derive API.
+#[derive(Debug, Parser)]
+#[command(name = "resize", version, about)]
+struct Cli {
+    /// The images to resize
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Width in pixels (16 to 8192)
+    #[arg(short, long, default_value_t = 800, value_parser = clap::value_parser!(u32).range(16..=8192))]
+    width: u32,
+
+    /// Output format; by default each file keeps its own
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// JPEG quality, as a percentage
+    #[arg(long, default_value_t = 85, value_parser = parse_percent)]
+    quality: u8,
+
+    /// Where to write the results
+    #[arg(short, long, env = "RESIZE_OUT", default_value = "out")]
+    out_dir: PathBuf,
+
+    /// Print more; repeat for even more (-vv)
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Show what would happen without writing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+/// A custom validator: any function from &str to Result works.
+fn parse_percent(text: &str) -> Result<u8, String> {
+    let value: u8 = text
+        .trim_end_matches('%')
+        .parse()
+        .map_err(|_| format!("{:?} isn't a percentage", text))?;
+    if value > 100 {
+        return Err(format!("{}% is more than 100%", value));
+    }
+    Ok(value)
+}
+
+/// Parses `line` as if it were typed after the program name.
+fn try_line(line: &str) {
+    let args = std::iter::once("resize").chain(line.split_whitespace());
+    match Cli::try_parse_from(args) {
+        Ok(cli) => println!("resize {}\n  -> {:?}", line, cli),
+        // kind() says what went wrong; the first line of the rendered
+        // error is what clap would print after "error: "
+        Err(error) => {
+            let message = error.to_string();
+            let first_line = message.lines().next().unwrap_or_default();
+            println!("resize {}\n  -> {:?}: {}", line, error.kind(), first_line);
+        }
+    }
+}
+
+fn main() {
+    // try_parse_from parses any list of arguments and returns the error
+    // instead of printing it and exiting, which is what tests want too
+    try_line("cat.png");
+    try_line("-w 64 --format webp -vv --dry-run a.jpg b.jpg");
+    try_line("--quality 90% cat.png");
+    try_line("cat.png -f gif");
+    try_line("cat.png --width 4");
+    try_line("cat.png --quality 120");
+    try_line("cat.png --widht 100");
+    try_line("-w 100");
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let cli = Cli::parse_from(["resize", "cat.png"]).width.unwrap_or(800);
+    // error[E0599]: no method named `unwrap_or` found for type `u32` in the current scope
+
+    // Cli::parse() is the usual call in a real program: it reads
+    // env::args, and on an error (or --help) prints and exits by itself
+    println!("with real arguments, run with `-- --help` to see the generated help");
+}
+
+/*
+ * Key Concepts:
+ * - #[derive(Parser)] on a struct; the field types decide argument kinds
+ * - #[arg(short, long)] for -w/--width; default_value_t for defaults
+ * - value_parser validates: ranges, ValueEnum choices, or your own fn
+ * - env = "VAR" reads a fallback from the environment
+ * - Cli::parse() for programs, Cli::try_parse_from(...) for tests
+ */
+
+// EXPECTED:
+// resize cat.png
+//   -> Cli { files: ["cat.png"], width: 800, format: None, quality: 85, out_dir: "out", verbose: 0, dry_run: false }
+// resize -w 64 --format webp -vv --dry-run a.jpg b.jpg
+//   -> Cli { files: ["a.jpg", "b.jpg"], width: 64, format: Some(Webp), quality: 85, out_dir: "out", verbose: 2, dry_run: true }
+// resize --quality 90% cat.png
+//   -> Cli { files: ["cat.png"], width: 800, format: None, quality: 90, out_dir: "out", verbose: 0, dry_run: false }
+// resize cat.png -f gif
+//   -> InvalidValue: error: invalid value 'gif' for '--format <FORMAT>'
+// resize cat.png --width 4
+//   -> ValueValidation: error: invalid value '4' for '--width <WIDTH>': 4 is not in 16..=8192
+// resize cat.png --quality 120
+//   -> ValueValidation: error: invalid value '120' for '--quality <QUALITY>': 120% is more than 100%
+// resize cat.png --widht 100
+//   -> UnknownArgument: error: unexpected argument '--widht' found
+// resize -w 100
+//   -> MissingRequiredArgument: error: the following required arguments were not provided:
+// with real arguments, run with `-- --help` to see the generated help
@@ -0,0 +1,129 @@
+// CLI 3: Subcommands
+// Demonstrates #[derive(Subcommand)], global flags, and argument groups
+//
+// Concepts: #[derive(Subcommand)], global flags, argument groups
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Tools like git and cargo do several jobs, each with its own arguments:
+// `git commit -m ...`, `git log --oneline`. In clap, the jobs are the
+// variants of an enum that derives Subcommand. Each variant holds that
+// subcommand's arguments, and a `match` on the enum runs it. Arguments
+// marked `global` work before or after the subcommand's name.
+
+use clap::{Args, Parser, Subcommand};
+
+/// A to-do list on the command line.
+#[derive(Debug, Parser)]
+#[command(name = "todo")]
+struct Cli {
+    /// Use this list instead of the default one
+    #[arg(long, global = true, default_value = "todo.txt")]
+    list: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Add a task
+    Add {
+        /// What needs doing; several words are joined with spaces
+        #[arg(required = true, num_args = 1..)]
+        words: Vec<String>,
+        /// 1 is most urgent
+        #[arg(short, long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..=5))]
+        priority: u8,
+    },
+    /// Show tasks
+    #[command(alias = "ls")]
+    List(ListArgs),
+    /// Mark tasks as done
+    Done {
+        /// The numbers `list` shows
+        #[arg(required = true)]
+        ids: Vec<u32>,
+    },
+}
+
+/// A subcommand's arguments can also be their own struct.
+#[derive(Debug, Args)]
+struct ListArgs {
+    /// Include finished tasks
+    #[arg(short, long)]
+    all: bool,
+    /// Only tasks at this priority or more urgent
+    #[arg(long, conflicts_with = "all")]
+    urgent: Option<u8>,
+}
+
+/// What running `cli` would do.
+fn run(cli: Cli) -> String {
+    match cli.command {
+        Command::Add { words, priority } => {
+            format!("add {:?} (p{}) to {}", words.join(" "), priority, cli.list)
+        }
+        Command::List(ListArgs { all, urgent }) => match (all, urgent) {
+            (true, _) => format!("list everything in {}", cli.list),
+            (false, Some(level)) => {
+                format!("list open tasks at p{} or above in {}", level, cli.list)
+            }
+            (false, None) => format!("list open tasks in {}", cli.list),
+        },
+        Command::Done { ids } => format!("mark {:?} done in {}", ids, cli.list),
+    }
+}
+
+fn try_line(line: &str) {
+    let args = std::iter::once("todo").chain(line.split_whitespace());
+    match Cli::try_parse_from(args) {
+        Ok(cli) => println!("todo {:<34} -> {}", line, run(cli)),
+        Err(error) => {
+            let message = error.to_string();
+            let first_line = message.lines().next().unwrap_or_default();
+            println!("todo {:<34} -> {:?}: {}", line, error.kind(), first_line);
+        }
+    }
+}
+
+fn main() {
+    try_line("add buy milk");
+    try_line("add -p 1 fix the roof");
+    try_line("ls");
+    try_line("--list work.txt list --urgent 2");
+    try_line("done 3 5 --list work.txt");
+    try_line("list --all --urgent 1");
+    try_line("done three");
+    try_line("remove 3");
+    try_line("");
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    match Cli::parse().command {
+        Command::Add { words, .. } => println!("{:?}", words),
+        Command::Done { ids } => println!("{:?}", ids),
+    }
+    // error[E0004]: non-exhaustive patterns: `Command::List(_)` not covered
+}
+
+/*
+ * Key Concepts:
+ * - An enum deriving Subcommand, with #[command(subcommand)] on the field
+ * - Each variant carries its own arguments, inline or as an Args struct
+ * - global = true lets an option go before or after the subcommand
+ * - alias, num_args, conflicts_with tune how arguments are accepted
+ * - match on the enum to run the chosen subcommand; the compiler checks
+ *   that every one is handled
+ */
+
+// EXPECTED:
+// todo add buy milk                       -> add "buy milk" (p3) to todo.txt
+// todo add -p 1 fix the roof              -> add "fix the roof" (p1) to todo.txt
+// todo ls                                 -> list open tasks in todo.txt
+// todo --list work.txt list --urgent 2    -> list open tasks at p2 or above in work.txt
+// todo done 3 5 --list work.txt           -> mark [3, 5] done in work.txt
+// todo list --all --urgent 1              -> ArgumentConflict: error: the argument '--all' cannot be used with '--urgent <URGENT>'
+// todo done three                         -> ValueValidation: error: invalid value 'three' for '<IDS>...': invalid digit found in string
+// todo remove 3                           -> InvalidSubcommand: error: unrecognized subcommand 'remove'
+// todo                                    -> DisplayHelpOnMissingArgumentOrSubcommand: A to-do list on the command line
@@ -0,0 +1,114 @@
+// CLI 4: Shell Completions
+// Demonstrates clap::CommandFactory and clap_complete::generate
+//
+// Concepts: clap::CommandFactory, clap_complete, shell completions
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Because clap knows every subcommand, flag, and value a program accepts,
+// it can write the script that makes Tab complete them in bash, zsh, fish,
+// PowerShell, or elvish. Programs usually offer this as a subcommand
+// (`mytool completions zsh > ~/.zfunc/_mytool`), or generate the scripts
+// at build time so an installer can put them into place.
+
+use std::io;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::{generate, Shell};
+
+/// A note-taking tool that can write its own completion scripts.
+#[derive(Debug, Parser)]
+#[command(name = "notes")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Write a new note
+    New {
+        /// Tags to file the note under
+        #[arg(short, long)]
+        tag: Vec<String>,
+    },
+    /// Search the notes
+    Search {
+        query: String,
+        /// Only notes with this tag
+        #[arg(short, long)]
+        tag: Option<String>,
+    },
+    /// Print a completion script for your shell
+    Completions {
+        /// bash, zsh, fish, powershell, or elvish
+        shell: Shell,
+    },
+}
+
+/// The completion script for `shell`.
+fn script(shell: Shell) -> String {
+    // CommandFactory::command() builds the clap::Command that the derive
+    // describes, which is what the generators walk
+    let mut command = Cli::command();
+    let mut out = Vec::new();
+    generate(shell, &mut command, "notes", &mut out);
+    String::from_utf8(out).expect("completion scripts are UTF-8")
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Some(Command::Completions { shell }) = cli.command {
+        // The real thing: `cargo run --bin 04_shell_completions -- completions bash`
+        let mut command = Cli::command();
+        generate(shell, &mut command, "notes", &mut io::stdout());
+        return;
+    }
+
+    // Without arguments, look at what the generators produce
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+        let script = script(shell);
+        let knows = ["new", "search", "tag", "completions"]
+            .iter()
+            .all(|word| script.contains(word));
+        println!(
+            "{:<5} script knows every subcommand and flag: {}",
+            shell, knows
+        );
+    }
+
+    // fish scripts are one `complete` line per argument, easy to read
+    println!("some of the fish script:");
+    for line in script(Shell::Fish)
+        .lines()
+        .filter(|line| line.contains("__fish_notes_using_subcommand search"))
+    {
+        println!("  {}", line);
+    }
+
+    // Shell implements ValueEnum, so it parses like any other argument
+    let error = Cli::try_parse_from(["notes", "completions", "cmd.exe"]).unwrap_err();
+    println!("completions cmd.exe -> {:?}", error.kind());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    generate(Shell::Bash, Cli::command(), "notes", &mut io::stdout());
+    // error[E0308]: mismatched types (generate needs `&mut Command`)
+}
+
+/*
+ * Key Concepts:
+ * - CommandFactory::command() gives the clap::Command behind a derive
+ * - clap_complete::generate writes a completion script for one shell
+ * - Expose it as a `completions <SHELL>` subcommand, or run it at build time
+ * - Shell is a ValueEnum, so clap validates the shell's name for us
+ */
+
+// EXPECTED:
+// bash  script knows every subcommand and flag: true
+// zsh   script knows every subcommand and flag: true
+// fish  script knows every subcommand and flag: true
+// some of the fish script:
+//   complete -c notes -n "__fish_notes_using_subcommand search" -s t -l tag -d 'Only notes with this tag' -r
+//   complete -c notes -n "__fish_notes_using_subcommand search" -s h -l help -d 'Print help'
+// completions cmd.exe -> InvalidValue
@@ -0,0 +1,60 @@
+// Clippy 1: Needless Clones
+// Demonstrates code that compiles and works, but copies data it doesn't need to
+//
+// Concepts: clippy, needless clones, borrowing instead of copying
+// Difficulty: intermediate
+// Minutes: 5
+//
+// Every example in this chapter is deliberately written the long way.
+// Run `cargo run -p tutor -- lint clippy/01_needless_clones` to see what
+// clippy thinks of it, then fix each warning until the lesson is clean.
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn shout(text: &str) -> String {
+    text.to_uppercase()
+}
+
+fn total_length(words: &[String]) -> usize {
+    words.iter().map(|word| word.len()).sum()
+}
+
+fn main() {
+    // Point is Copy, so .clone() does nothing that plain assignment wouldn't
+    let origin = Point { x: 0, y: 0 };
+    let moved = origin.clone();
+    println!("origin ({}, {}), copy ({}, {})", origin.x, origin.y, moved.x, moved.y);
+
+    // A String is cloned just to be borrowed as &str
+    let greeting = String::from("hello");
+    println!("{}", shout(&greeting.clone()));
+
+    // A Vec is cloned to be passed by reference, then the original is
+    // never used again
+    let words = vec![String::from("borrow"), String::from("checker")];
+    let copy = words.clone();
+    println!("total length: {}", total_length(&copy));
+
+    // .to_string() on something that is already a String
+    let name = String::from("Ferris");
+    let label = name.to_string();
+    println!("{} says hi", label);
+}
+
+/*
+ * Key Concepts:
+ * - Copy types (integers, small structs of them) never need .clone()
+ * - Clone only when you truly need a second, independent value
+ * - &value borrows for free; &value.clone() copies and then borrows
+ * - Clippy's lints point out clones a reviewer would question
+ */
+
+// EXPECTED:
+// origin (0, 0), copy (0, 0)
+// HELLO
+// total length: 13
+// Ferris says hi
@@ -0,0 +1,59 @@
+// Clippy 2: Manual Index Loops
+// Demonstrates C-style loops over indices where iterators say it better
+//
+// Concepts: clippy, index loops, iterators
+// Difficulty: intermediate
+// Minutes: 5
+//
+// Run `cargo run -p tutor -- lint clippy/02_index_loops` and rewrite each
+// loop the way clippy suggests.
+
+fn main() {
+    let temperatures = [18.5, 21.0, 19.5, 23.0];
+
+    // Indexing with a counter: every temperatures[i] is bounds-checked, and
+    // an off-by-one in the range would panic
+    let mut sum = 0.0;
+    for i in 0..temperatures.len() {
+        sum += temperatures[i];
+    }
+    println!("average: {:.2}", sum / temperatures.len() as f64);
+
+    // Keeping a separate counter next to the loop variable
+    let days = ["Mon", "Tue", "Wed", "Thu"];
+    let mut day_number = 0;
+    for day in days.iter() {
+        println!("day {}: {}", day_number, day);
+        day_number += 1;
+    }
+
+    // Copying element by element into another array
+    let mut backup = [0.0; 4];
+    for i in 0..temperatures.len() {
+        backup[i] = temperatures[i];
+    }
+    println!("backup: {:?}", backup);
+
+    // Checking for emptiness by length
+    let warnings: Vec<&str> = Vec::new();
+    if warnings.len() == 0 {
+        println!("no warnings");
+    }
+}
+
+/*
+ * Key Concepts:
+ * - for x in &items (or .iter()) visits every element, no indices needed
+ * - .enumerate() gives you the index when you do need it
+ * - copy_from_slice and clone_from_slice replace element-by-element loops
+ * - .is_empty() says what you mean better than .len() == 0
+ */
+
+// EXPECTED:
+// average: 20.50
+// day 0: Mon
+// day 1: Tue
+// day 2: Wed
+// day 3: Thu
+// backup: [18.5, 21.0, 19.5, 23.0]
+// no warnings
@@ -0,0 +1,55 @@
+// Clippy 3: unwrap() Everywhere
+// Demonstrates code that works today and panics on the first unexpected input
+//
+// Concepts: clippy, unwrap, expect, error handling
+// Difficulty: intermediate
+// Minutes: 5
+//
+// This lesson also turns on clippy's `unwrap_used` lint, which is off by
+// default: `unwrap()` is fine in tests and quick experiments, but in real
+// code every unwrap is a crash waiting for bad input.
+// Run `cargo run -p tutor -- lint clippy/03_unwrap_abuse` to start.
+
+use std::collections::HashMap;
+
+fn parse_age(text: &str) -> Option<u32> {
+    // Would panic on "abc"
+    Some(text.trim().parse::<u32>().unwrap())
+}
+
+fn main() {
+    let inputs = ["42", " 7 "];
+    for input in inputs {
+        println!("age: {}", parse_age(input).unwrap());
+    }
+
+    let mut stock = HashMap::new();
+    stock.insert("apples", 3);
+
+    // Checking first and then unwrapping anyway
+    let pears = stock.get("pears");
+    if pears.is_some() {
+        println!("pears: {}", pears.unwrap());
+    } else {
+        println!("no pears");
+    }
+
+    // Would panic if "apples" were ever missing
+    let apples = stock.get("apples").unwrap();
+    println!("apples: {}", apples);
+}
+
+/*
+ * Key Concepts:
+ * - unwrap() turns a missing value or an error into a panic
+ * - ? passes the problem on to the caller instead
+ * - if let / match handle both cases where you are
+ * - unwrap_or and unwrap_or_default supply a fallback value
+ * - Checking is_some() and then unwrapping is a match written twice
+ */
+
+// EXPECTED:
+// age: 42
+// age: 7
+// no pears
+// apples: 3
@@ -0,0 +1,79 @@
+// Closures 3: move Closures
+// Demonstrates forcing a closure to take ownership of its captures
+//
+// Concepts: move closures, ownership of captures
+// Difficulty: intermediate
+// Minutes: 5
+
+use std::thread;
+
+fn make_greeter(name: String) -> impl Fn() -> String {
+    // Without `move`, the closure would borrow `name`, which is dropped
+    // when this function returns. `move` makes the closure own it.
+    move || format!("Hello, {}!", name)
+}
+
+fn main() {
+    // By default a closure borrows as little as it can
+    let data = vec![1, 2, 3];
+    let borrows = || println!("borrowing {:?}", data);
+    borrows();
+    println!("data is still ours: {:?}", data);
+
+    // `move` transfers ownership into the closure, even when only reading
+    let moves = move || println!("owning {:?}", data);
+    moves();
+    // This would cause an error:
+    // println!("{:?}", data);
+    // error[E0382]: borrow of moved value: `data`
+
+    // Copy types are copied in, so the original stays usable
+    let limit = 3;
+    let under_limit = move |n: i32| n < limit;
+    println!(
+        "2 < {}? {}, limit still usable: {}",
+        limit,
+        under_limit(2),
+        limit
+    );
+
+    // The most common reason to need `move`: closures that outlive the
+    // current scope, like returned closures and threads
+    let greeter = make_greeter(String::from("Ferris"));
+    println!("{}", greeter());
+
+    let message = String::from("from another thread");
+    let handle = thread::spawn(move || format!("got the message {:?}", message));
+    println!("{}", handle.join().unwrap());
+
+    // To move a COPY and keep the original, clone before the closure
+    let config = String::from("debug=true");
+    let config_for_worker = config.clone();
+    let worker = move || config_for_worker.len();
+    println!("worker saw {} bytes; we still have {:?}", worker(), config);
+
+    // move decides HOW things are captured; the body decides which Fn
+    // trait is implemented. This move closure only reads, so it is Fn.
+    let counter_name = String::from("clicks");
+    let describe = move || format!("counter {}", counter_name);
+    println!("{} / {}", describe(), describe());
+}
+
+/*
+ * Key Concepts:
+ * - Closures borrow captures by default, as little as possible
+ * - `move` makes the closure own everything it captures
+ * - Returned closures and thread closures usually need `move`
+ * - Clone first if you still need the original afterwards
+ * - move affects capturing, not which Fn trait the closure implements
+ */
+
+// EXPECTED:
+// borrowing [1, 2, 3]
+// data is still ours: [1, 2, 3]
+// owning [1, 2, 3]
+// 2 < 3? true, limit still usable: 3
+// Hello, Ferris!
+// got the message "from another thread"
+// worker saw 10 bytes; we still have "debug=true"
+// counter clicks / counter clicks
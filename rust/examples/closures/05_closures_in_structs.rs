@@ -0,0 +1,140 @@
+// Closures 5: Storing Closures in Structs
+// Demonstrates generic closure fields, boxed closure fields, and callbacks
+//
+// Concepts: generic closure fields, boxed closures, callbacks
+// Difficulty: intermediate
+// Minutes: 10
+
+// Generic field: the struct is specialised for one closure type. Fast
+// (calls can be inlined), but every Validator<F> with a different F is a
+// different type.
+struct Validator<F>
+where
+    F: Fn(&str) -> bool,
+{
+    name: &'static str,
+    check: F,
+}
+
+impl<F> Validator<F>
+where
+    F: Fn(&str) -> bool,
+{
+    fn validate(&self, input: &str) -> Result<(), String> {
+        // Calling a closure stored in a field needs parentheses around the
+        // field access, otherwise Rust looks for a METHOD named `check`
+        if (self.check)(input) {
+            Ok(())
+        } else {
+            Err(format!("{:?} failed {}", input, self.name))
+        }
+    }
+}
+
+// Boxed field: any closure fits, so different buttons can share a type
+// and live in the same Vec.
+struct Button {
+    label: String,
+    on_click: Box<dyn FnMut() -> String>,
+}
+
+impl Button {
+    fn new(label: &str, on_click: impl FnMut() -> String + 'static) -> Button {
+        Button {
+            label: label.to_string(),
+            on_click: Box::new(on_click),
+        }
+    }
+
+    fn click(&mut self) -> String {
+        (self.on_click)()
+    }
+}
+
+// A subscriber gets each event and may answer it
+type Callback = Box<dyn Fn(&str) -> Option<String>>;
+
+// An event bus: subscribers register callbacks that run on publish
+#[derive(Default)]
+struct EventBus {
+    subscribers: Vec<Callback>,
+}
+
+impl EventBus {
+    fn subscribe(&mut self, callback: impl Fn(&str) -> Option<String> + 'static) {
+        self.subscribers.push(Box::new(callback));
+    }
+
+    fn publish(&self, event: &str) -> Vec<String> {
+        self.subscribers
+            .iter()
+            .filter_map(|callback| callback(event))
+            .collect()
+    }
+}
+
+fn main() {
+    let not_empty = Validator {
+        name: "not_empty",
+        check: |s: &str| !s.is_empty(),
+    };
+    let max_len = 8;
+    let short = Validator {
+        name: "short",
+        check: move |s: &str| s.len() <= max_len,
+    };
+    for input in ["ferris", "", "a very long name"] {
+        println!(
+            "{:?}: {:?} {:?}",
+            input,
+            not_empty.validate(input),
+            short.validate(input)
+        );
+    }
+
+    let mut clicks = 0;
+    let mut buttons = vec![
+        Button::new("count", move || {
+            clicks += 1;
+            format!("clicked {} time(s)", clicks)
+        }),
+        Button::new("hello", || String::from("hello!")),
+    ];
+    for _ in 0..2 {
+        for button in &mut buttons {
+            let output = button.click();
+            println!("[{}] {}", button.label, output);
+        }
+    }
+
+    let mut bus = EventBus::default();
+    bus.subscribe(|event| Some(format!("logger saw {}", event)));
+    bus.subscribe(|event| {
+        event
+            .starts_with("error")
+            .then(|| format!("pager woke up for {}", event))
+    });
+    for event in ["user_login", "error_disk_full"] {
+        println!("{} -> {:?}", event, bus.publish(event));
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A generic field (F: Fn...) stores one specific closure type
+ * - Box<dyn Fn...> fields store any closure, at the cost of a box
+ * - Call a closure field with (self.field)(args)
+ * - Stored closures usually need 'static (own their data via move)
+ * - Callbacks and event handlers are closures in structs
+ */
+
+// EXPECTED:
+// "ferris": Ok(()) Ok(())
+// "": Err("\"\" failed not_empty") Ok(())
+// "a very long name": Ok(()) Err("\"a very long name\" failed short")
+// [count] clicked 1 time(s)
+// [hello] hello!
+// [count] clicked 2 time(s)
+// [hello] hello!
+// user_login -> ["logger saw user_login"]
+// error_disk_full -> ["logger saw error_disk_full", "pager woke up for error_disk_full"]
@@ -0,0 +1,83 @@
+// Collections 1: Vec<T> - A Growable List
+// Demonstrates creating, reading, updating, and iterating over vectors
+//
+// Concepts: Vec, indexing, get, push, iteration
+// Difficulty: beginner
+// Minutes: 5
+
+fn main() {
+    // Creating vectors
+    let empty: Vec<i32> = Vec::new(); // Type annotation needed: no values yet
+    let mut numbers = vec![1, 2, 3]; // vec! macro infers Vec<i32>
+    println!("empty = {:?}, numbers = {:?}", empty, numbers);
+
+    // Adding and removing at the end
+    numbers.push(4);
+    numbers.push(5);
+    let last = numbers.pop(); // Option: the Vec might be empty
+    println!("after push/pop: {:?}, popped {:?}", numbers, last);
+
+    // Reading: indexing panics if out of range, get returns Option
+    let third = numbers[2];
+    let tenth = numbers.get(9);
+    println!("third = {}, tenth = {:?}", third, tenth);
+    // This would crash at runtime:
+    // let crash = numbers[99];
+    // thread 'main' panicked: index out of bounds: the len is 4 but the index is 99
+
+    // Iterating
+    for n in &numbers {
+        print!("{} ", n); // Shared borrow: read only
+    }
+    println!();
+    for n in &mut numbers {
+        *n *= 10; // Mutable borrow: change in place
+    }
+    println!("multiplied: {:?}", numbers);
+
+    // Useful methods
+    numbers.insert(0, 5); // Insert at an index (shifts the rest)
+    numbers.retain(|n| *n != 20); // Keep only the items that match
+    println!("insert + retain: {:?}", numbers);
+    println!("len = {}, contains 30? {}", numbers.len(), numbers.contains(&30));
+
+    let mut words = vec!["pear", "apple", "fig"];
+    words.sort();
+    println!("sorted: {:?}", words);
+    words.sort_by_key(|word| word.len());
+    println!("sorted by length: {:?}", words);
+
+    // Capacity: how much room is reserved before the Vec must grow
+    let mut reserved = Vec::with_capacity(10);
+    reserved.push('x');
+    println!("len = {}, capacity >= 10? {}", reserved.len(), reserved.capacity() >= 10);
+
+    // A reference into a Vec blocks changes while it is in use
+    let first = &numbers[0];
+    // This would cause an error:
+    // numbers.push(60);
+    // error[E0502]: cannot borrow `numbers` as mutable because it is also borrowed as immutable
+    println!("first = {}", first);
+}
+
+/*
+ * Key Concepts:
+ * - Vec<T> stores values of one type contiguously on the heap
+ * - push/pop work at the end; insert/remove shift elements
+ * - v[i] panics when out of range; v.get(i) returns an Option
+ * - Iterate with &v (read) or &mut v (modify)
+ * - Borrowing rules (E0502) protect references from reallocation
+ */
+
+// EXPECTED:
+// empty = [], numbers = [1, 2, 3]
+// after push/pop: [1, 2, 3, 4], popped Some(5)
+// third = 3, tenth = None
+// 1 2 3 4
+// multiplied: [10, 20, 30, 40]
+// insert + retain: [5, 10, 30, 40]
+// len = 4, contains 30? true
+// sorted: ["apple", "fig", "pear"]
+// sorted by length: ["fig", "pear", "apple"]
+// len = 1, capacity >= 10? true
+// first = 5
@@ -0,0 +1,64 @@
+// Collections 3: HashSet<T> - Unique Values
+// Demonstrates membership tests, de-duplication, and set operations
+//
+// Concepts: HashSet, membership, de-duplication, set operations
+// Difficulty: beginner
+// Minutes: 5
+
+use std::collections::HashSet;
+
+fn main() {
+    // insert returns false if the value was already present
+    let mut visited = HashSet::new();
+    println!("insert 'home': {}", visited.insert("home"));
+    println!("insert 'about': {}", visited.insert("about"));
+    println!("insert 'home' again: {}", visited.insert("home"));
+    println!("visited {} unique pages", visited.len());
+
+    // Fast membership checks
+    println!("visited 'contact'? {}", visited.contains("contact"));
+
+    // De-duplicate a list by collecting into a set
+    let tags = vec!["rust", "go", "rust", "java", "go"];
+    let unique: HashSet<&str> = tags.into_iter().collect();
+    println!("{} unique tags", unique.len());
+
+    // Set operations. Results are sorted for readable output.
+    let rustaceans: HashSet<&str> = ["ana", "ben", "cy"].into_iter().collect();
+    let gophers: HashSet<&str> = ["ben", "cy", "dee"].into_iter().collect();
+
+    println!("both:        {:?}", sorted(rustaceans.intersection(&gophers)));
+    println!("either:      {:?}", sorted(rustaceans.union(&gophers)));
+    println!("rust only:   {:?}", sorted(rustaceans.difference(&gophers)));
+    println!("not shared:  {:?}", sorted(rustaceans.symmetric_difference(&gophers)));
+    println!("disjoint?    {}", rustaceans.is_disjoint(&gophers));
+}
+
+// Sets have no order, so sort a copy before printing
+fn sorted<'a>(items: impl Iterator<Item = &'a &'a str>) -> Vec<&'a str> {
+    let mut items: Vec<&str> = items.copied().collect();
+    items.sort();
+    items
+}
+
+/*
+ * Key Concepts:
+ * - HashSet<T> stores each value at most once
+ * - insert() tells you whether the value was new
+ * - contains() is fast no matter how large the set is
+ * - intersection, union, difference answer "who is in which group?"
+ * - Like HashMap, iteration order is arbitrary
+ */
+
+// EXPECTED:
+// insert 'home': true
+// insert 'about': true
+// insert 'home' again: false
+// visited 2 unique pages
+// visited 'contact'? false
+// 3 unique tags
+// both:        ["ben", "cy"]
+// either:      ["ana", "ben", "cy", "dee"]
+// rust only:   ["ana"]
+// not shared:  ["ana", "dee"]
+// disjoint?    false
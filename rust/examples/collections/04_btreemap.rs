@@ -0,0 +1,65 @@
+// Collections 4: BTreeMap<K, V> - A Sorted Map
+// Demonstrates ordered iteration and range queries
+//
+// Concepts: BTreeMap, ordered iteration, range queries
+// Difficulty: beginner
+// Minutes: 5
+
+use std::collections::BTreeMap;
+
+fn main() {
+    // Same API as HashMap, but keys are kept in sorted order
+    let mut population = BTreeMap::new();
+    population.insert("Tokyo", 37_400_000);
+    population.insert("Delhi", 31_000_000);
+    population.insert("Cairo", 21_300_000);
+    population.insert("Lagos", 15_400_000);
+
+    // Iteration is always in key order - no sorting needed
+    for (city, people) in &population {
+        println!("{:<6} {:>11}", city, people);
+    }
+
+    // First and last entries are cheap to find
+    println!("first: {:?}", population.first_key_value());
+    println!("last:  {:?}", population.last_key_value());
+
+    // Range queries: every key between two bounds
+    let mut by_year = BTreeMap::new();
+    by_year.insert(1995, "Java");
+    by_year.insert(2009, "Go");
+    by_year.insert(2015, "Rust 1.0");
+    by_year.insert(1972, "C");
+    for (year, language) in by_year.range(1990..2010) {
+        println!("{} released in {}", language, year);
+    }
+
+    // Perfect for "grouped and sorted" output, like a word index
+    let text = "banana apple cherry avocado blueberry";
+    let mut index: BTreeMap<char, Vec<&str>> = BTreeMap::new();
+    for word in text.split_whitespace() {
+        let letter = word.chars().next().unwrap_or('?');
+        index.entry(letter).or_default().push(word);
+    }
+    println!("index: {:?}", index);
+}
+
+/*
+ * Key Concepts:
+ * - BTreeMap keeps keys sorted; HashMap does not
+ * - Choose BTreeMap for ordered output or range queries
+ * - Choose HashMap when you only need fast lookups
+ * - The APIs (insert, get, entry) are nearly identical
+ * - BTreeSet is the sorted counterpart of HashSet
+ */
+
+// EXPECTED:
+// Cairo     21300000
+// Delhi     31000000
+// Lagos     15400000
+// Tokyo     37400000
+// first: Some(("Cairo", 21300000))
+// last:  Some(("Tokyo", 37400000))
+// Java released in 1995
+// Go released in 2009
+// index: {'a': ["apple", "avocado"], 'b': ["banana", "blueberry"], 'c': ["cherry"]}
@@ -0,0 +1,82 @@
+// Concurrency 2: Message Passing with Channels
+// Demonstrates mpsc::channel, multiple producers, and iterating a receiver
+//
+// Concepts: mpsc::channel, multiple producers, receiving in a loop
+// Difficulty: intermediate
+// Minutes: 5
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let start = Instant::now();
+
+    // A channel has two halves: a Sender (tx) and a Receiver (rx).
+    // mpsc = "multiple producer, single consumer": many senders, one receiver.
+    let (tx, rx) = mpsc::channel();
+
+    // Give each producer its own clone of the sender
+    for id in 1..=3 {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for step in 1..=3 {
+                thread::sleep(Duration::from_millis(50 * id));
+                // send() MOVES the value into the channel
+                tx.send(format!("producer {} step {}", id, step)).unwrap();
+            }
+        });
+    }
+    // Drop the original sender. The receiver's loop ends once EVERY sender
+    // is gone; forgetting this line makes the loop below wait forever.
+    drop(tx);
+
+    // Iterating the receiver blocks until a message arrives, and stops
+    // when all senders have been dropped.
+    for message in rx {
+        println!("[{:>4} ms] {}", start.elapsed().as_millis(), message);
+    }
+    // The producers slept 50/100/150 ms per step in parallel, so everything
+    // arrives in about 450 ms instead of the 900 ms a single thread needs.
+    println!(
+        "all producers done after {} ms",
+        start.elapsed().as_millis()
+    );
+
+    // Once sent, a value belongs to the receiving side
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let report = vec![1, 2, 3];
+        tx.send(report).unwrap();
+        // This would cause an error:
+        // println!("{:?}", report);
+        // error[E0382]: borrow of moved value: `report`
+    });
+    println!("received {:?}", rx.recv().unwrap());
+    handle.join().unwrap();
+
+    // sync_channel has a fixed capacity: send() blocks when it is full,
+    // which keeps a fast producer from racing ahead of a slow consumer.
+    let (tx, rx) = mpsc::sync_channel(1);
+    let start = Instant::now();
+    let producer = thread::spawn(move || {
+        for n in 1..=3 {
+            tx.send(n).unwrap();
+            println!("[{:>4} ms] sent {}", start.elapsed().as_millis(), n);
+        }
+    });
+    for n in rx {
+        thread::sleep(Duration::from_millis(100)); // slow consumer
+        println!("[{:>4} ms] handled {}", start.elapsed().as_millis(), n);
+    }
+    producer.join().unwrap();
+}
+
+/*
+ * Key Concepts:
+ * - Channels move data between threads instead of sharing it
+ * - Clone the Sender for each producer; there is one Receiver
+ * - The receiver loop ends when every Sender has been dropped
+ * - send() transfers ownership of the value
+ * - sync_channel(n) is bounded and applies back-pressure
+ */
@@ -0,0 +1,79 @@
+// Concurrency 3: Shared State with Arc<Mutex<T>>
+// Demonstrates Arc for shared ownership and Mutex for safe mutation
+//
+// Concepts: Arc, Mutex, shared state
+// Difficulty: intermediate
+// Minutes: 5
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use learn_rust_core::Timer;
+
+fn main() {
+    // Mutex<T> guards its data: lock() returns a guard that gives access,
+    // and the lock is released automatically when the guard is dropped.
+    let counter = Mutex::new(0);
+    {
+        let mut guard = counter.lock().unwrap();
+        *guard += 1;
+    } // guard dropped here -> unlocked
+    println!("counter = {}", counter.lock().unwrap());
+
+    // To share ONE mutex between threads, each thread needs an owner.
+    // Rc isn't thread-safe; Arc ("atomically reference counted") is.
+    let timer = Timer::start("8 threads x 10,000 increments");
+    let counter = Arc::new(Mutex::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..8 {
+        let counter = Arc::clone(&counter); // cheap: bumps a count
+        handles.push(thread::spawn(move || {
+            for _ in 0..10_000 {
+                *counter.lock().unwrap() += 1;
+            }
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    drop(timer);
+    println!("counter = {}", counter.lock().unwrap());
+    // This would cause an error:
+    // let counter = std::rc::Rc::new(Mutex::new(0));
+    // thread::spawn(move || *counter.lock().unwrap() += 1);
+    // error[E0277]: `Rc<Mutex<i32>>` cannot be sent between threads safely
+
+    // Holding the lock for a long time makes other threads wait. Do the
+    // slow work first, then lock only to publish the result.
+    let timer = Timer::start("4 threads, locking only to publish");
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let handles: Vec<_> = (1..=4u64)
+        .map(|id| {
+            let results = Arc::clone(&results);
+            thread::spawn(move || {
+                let value: u64 = (0..2_000_000u64).map(|n| n % (id + 1)).sum(); // no lock held
+                results.lock().unwrap().push((id, value)); // lock only here
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    drop(timer);
+    let mut results = results.lock().unwrap().clone();
+    results.sort();
+    println!("results {:?}", results);
+
+    // How many threads can truly run at the same time depends on your CPU
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    println!("available parallelism on this machine: {}", cores);
+}
+
+/*
+ * Key Concepts:
+ * - Mutex<T> allows one thread at a time to access T
+ * - The lock is released when the guard goes out of scope
+ * - Arc<T> shares ownership across threads (Rc can't)
+ * - Arc<Mutex<T>> is the standard pattern for shared mutable state
+ * - Keep critical sections short to avoid contention
+ */
@@ -0,0 +1,112 @@
+// Concurrency 4: A Worker Pool
+// Demonstrates a fixed set of threads pulling jobs from a shared queue
+//
+// Concepts: thread pools, job queues, Arc<Mutex<Receiver>>
+// Difficulty: advanced
+// Minutes: 10
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// A job is any closure that can be sent to another thread and run once
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+struct ThreadPool {
+    workers: Vec<thread::JoinHandle<()>>,
+    sender: Option<mpsc::Sender<Job>>,
+}
+
+impl ThreadPool {
+    fn new(size: usize) -> ThreadPool {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        // There is only one Receiver, so the workers share it behind a Mutex.
+        // Whichever idle worker grabs the lock first takes the next job.
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..size)
+            .map(|id| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    // The guard is a temporary, so the lock is released
+                    // before the job runs and other workers can pick up work.
+                    let message = receiver.lock().unwrap().recv();
+                    match message {
+                        Ok(job) => {
+                            println!("  worker {} picked up a job", id);
+                            job();
+                        }
+                        Err(_) => break, // channel closed: time to stop
+                    }
+                })
+            })
+            .collect();
+
+        ThreadPool {
+            workers,
+            sender: Some(sender),
+        }
+    }
+
+    fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if let Some(sender) = &self.sender {
+            sender.send(Box::new(job)).unwrap();
+        }
+    }
+}
+
+// Dropping the pool closes the channel and waits for every worker
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            worker.join().unwrap();
+        }
+    }
+}
+
+fn run_jobs(threads: usize, jobs: u64) -> u128 {
+    let start = Instant::now();
+    let (done_tx, done_rx) = mpsc::channel();
+    {
+        let pool = ThreadPool::new(threads);
+        for job in 1..=jobs {
+            let done_tx = done_tx.clone();
+            pool.execute(move || {
+                thread::sleep(Duration::from_millis(100)); // simulated work
+                done_tx.send(job * job).unwrap();
+            });
+        }
+    } // pool dropped here: waits for all jobs
+    drop(done_tx);
+    let total: u64 = done_rx.iter().sum();
+    let elapsed = start.elapsed().as_millis();
+    println!(
+        "{} jobs on {} thread(s): sum = {}, {} ms",
+        jobs, threads, total, elapsed
+    );
+    elapsed
+}
+
+fn main() {
+    // Each job takes 100 ms. With one worker they queue up one by one; with
+    // four workers, four run at the same time.
+    println!("One worker:");
+    let slow = run_jobs(1, 8);
+    println!("Four workers:");
+    let fast = run_jobs(4, 8);
+    println!("speed-up: {:.1}x", slow as f64 / fast.max(1) as f64);
+}
+
+/*
+ * Key Concepts:
+ * - A pool reuses a fixed number of threads instead of one per task
+ * - Jobs are boxed closures: Box<dyn FnOnce() + Send + 'static>
+ * - Workers share one Receiver through Arc<Mutex<...>>
+ * - Closing the channel tells the workers to shut down
+ * - Drop lets the pool clean up after itself automatically
+ */
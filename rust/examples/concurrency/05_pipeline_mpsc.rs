@@ -0,0 +1,265 @@
+// Concurrency 5: A Pipeline with std::sync::mpsc
+// Demonstrates a producer, a pool of workers, and an aggregator joined by channels, with graceful shutdown
+//
+// Concepts: pipelines, worker pools, channels, graceful shutdown
+// Difficulty: advanced
+// Minutes: 25
+//
+// Bigger concurrent programs are often built as a pipeline: one thread
+// produces work, several workers do it, and one thread collects the
+// results. Each stage only talks to the next through a channel, so no
+// stage shares data with another, and each can be tested on its own.
+//
+//     producer --jobs--> worker 0 --+
+//                   \--> worker 1 --+--results--> aggregator
+//                    \-> worker 2 --+
+//
+// Shutting down is where pipelines go wrong. Here it travels down the
+// pipe: when the producer runs out of input, or is asked to stop, it
+// drops its Sender. Each worker's recv() then fails once the jobs already
+// queued are done, so the worker returns and drops its results Sender;
+// when the last one has, the aggregator's loop ends. Nothing is cut off
+// halfway, so every job that was sent is finished, exactly once.
+//
+// "Asked to stop" is an AtomicBool here, which the aggregator sets once it
+// has enough results, the way a Ctrl-C handler would. std's Receiver
+// can't be cloned, so the workers share it behind a Mutex, as in
+// 04_worker_pool; 06_pipeline_select does without both.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// One unit of work: a number whose digits are to be added up.
+#[derive(Debug)]
+struct Job {
+    id: u64,
+    value: u64,
+}
+
+/// A worker's answer, with the job it answers and who did it.
+#[derive(Debug)]
+struct Done {
+    id: u64,
+    worker: usize,
+    digit_sum: u64,
+}
+
+/// What the aggregator has once the pipeline has drained.
+#[derive(Debug)]
+struct Summary {
+    /// How many jobs the producer sent before it stopped.
+    produced: u64,
+    /// Every result, in the order they arrived.
+    results: Vec<Done>,
+}
+
+impl Summary {
+    /// Whether each job that was sent came back exactly once: none lost,
+    /// none twice.
+    fn exactly_once(&self) -> bool {
+        let mut ids: Vec<u64> = self.results.iter().map(|done| done.id).collect();
+        ids.sort_unstable();
+        ids.into_iter().eq(0..self.produced)
+    }
+
+    fn total(&self) -> u64 {
+        self.results.iter().map(|done| done.digit_sum).sum()
+    }
+
+    /// How many results each of `workers` workers sent.
+    fn per_worker(&self, workers: usize) -> Vec<usize> {
+        let mut counts = vec![0; workers];
+        for done in &self.results {
+            counts[done.worker] += 1;
+        }
+        counts
+    }
+}
+
+fn digit_sum(mut n: u64) -> u64 {
+    let mut sum = 0;
+    while n > 0 {
+        sum += n % 10;
+        n /= 10;
+    }
+    sum
+}
+
+/// Runs `inputs` through `workers` worker threads. With `stop_after`, the
+/// aggregator asks the producer to stop once that many results are in;
+/// whatever was sent by then is still finished.
+fn run_pipeline(inputs: Vec<u64>, workers: usize, stop_after: Option<usize>) -> Summary {
+    // Bounded, so the producer can't run far ahead of the workers
+    let (job_tx, job_rx) = mpsc::sync_channel::<Job>(4);
+    let (done_tx, done_rx) = mpsc::channel::<Done>();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let producer = {
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut produced = 0;
+            for value in inputs {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                job_tx
+                    .send(Job {
+                        id: produced,
+                        value,
+                    })
+                    .unwrap();
+                produced += 1;
+            }
+            // job_tx is dropped here, which is what tells the workers to finish
+            produced
+        })
+    };
+
+    let job_rx = Arc::new(Mutex::new(job_rx));
+    let handles: Vec<_> = (0..workers)
+        .map(|worker| {
+            let job_rx = Arc::clone(&job_rx);
+            let done_tx = done_tx.clone();
+            thread::spawn(move || loop {
+                // The lock is held only while waiting, not while working
+                let job = job_rx.lock().unwrap().recv();
+                let Ok(job) = job else {
+                    break; // the producer is gone and the queue is empty
+                };
+                let digit_sum = digit_sum(job.value);
+                done_tx
+                    .send(Done {
+                        id: job.id,
+                        worker,
+                        digit_sum,
+                    })
+                    .unwrap();
+            })
+        })
+        .collect();
+    // Only the workers' clones may keep the results channel open, or the
+    // loop below would never end
+    drop(done_tx);
+
+    // The aggregator is this thread
+    let mut results = Vec::new();
+    for done in done_rx {
+        results.push(done);
+        if Some(results.len()) == stop_after {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+    let produced = producer.join().unwrap();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    Summary { produced, results }
+}
+
+fn main() {
+    let inputs: Vec<u64> = (1..=1000).map(|n| n * 7919).collect();
+    let expected: u64 = inputs.iter().map(|&n| digit_sum(n)).sum();
+
+    let summary = run_pipeline(inputs.clone(), 4, None);
+    println!("1000 jobs, 4 workers");
+    println!(
+        "produced {}, processed {}, each exactly once: {}",
+        summary.produced,
+        summary.results.len(),
+        summary.exactly_once()
+    );
+    println!("total {}, on one thread {expected}", summary.total());
+    // How the jobs were split between the workers changes from run to run
+    let per_worker = summary.per_worker(4);
+    println!(
+        "the workers' shares add up to {}",
+        per_worker.iter().sum::<usize>()
+    );
+
+    // Stop early: the producer stops sending, the rest drains
+    let many: Vec<u64> = (1..=1_000_000).collect();
+    let summary = run_pipeline(many, 4, Some(100));
+    println!("\nasked to stop after 100 of 1,000,000 results");
+    println!("stopped early: {}", summary.produced < 1_000_000);
+    println!(
+        "everything sent was processed, exactly once: {}",
+        summary.exactly_once()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let (_tx, rx) = mpsc::channel::<Job>();
+        let second = rx.clone();
+    }
+    // error[E0599]: no method named `clone` found for struct `std::sync::mpsc::Receiver<T>` in the current scope
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_job_is_processed_exactly_once() {
+        let summary = run_pipeline((0..5000).collect(), 4, None);
+        assert_eq!(summary.produced, 5000);
+        assert_eq!(summary.results.len(), 5000);
+        assert!(summary.exactly_once());
+    }
+
+    #[test]
+    fn any_number_of_workers_gets_the_same_answer() {
+        let inputs: Vec<u64> = (0..2000).map(|n| n * 31).collect();
+        let expected: u64 = inputs.iter().map(|&n| digit_sum(n)).sum();
+        for workers in [1, 2, 8] {
+            let summary = run_pipeline(inputs.clone(), workers, None);
+            assert!(summary.exactly_once());
+            assert_eq!(summary.total(), expected);
+            assert_eq!(summary.per_worker(workers).iter().sum::<usize>(), 2000);
+        }
+    }
+
+    #[test]
+    fn stopping_early_loses_nothing_that_was_sent() {
+        for _ in 0..20 {
+            let summary = run_pipeline((0..100_000).collect(), 3, Some(10));
+            assert!(summary.produced >= 10);
+            assert_eq!(summary.results.len() as u64, summary.produced);
+            assert!(summary.exactly_once());
+        }
+    }
+
+    #[test]
+    fn no_input_shuts_down_at_once() {
+        let summary = run_pipeline(Vec::new(), 4, None);
+        assert_eq!(summary.produced, 0);
+        assert!(summary.results.is_empty());
+    }
+
+    #[test]
+    fn digit_sums() {
+        assert_eq!(digit_sum(0), 0);
+        assert_eq!(digit_sum(7919), 26);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A pipeline: producer -> workers -> aggregator, each stage joined by a channel
+ * - A bounded sync_channel keeps the producer from racing ahead
+ * - Shutdown flows downstream: dropping a Sender ends the next stage's loop
+ * - Drop the original Sender after cloning it, or the receiving loop never ends
+ * - Finishing what was sent, then stopping, means every job is done exactly once
+ */
+
+// EXPECTED:
+// 1000 jobs, 4 workers
+// produced 1000, processed 1000, each exactly once: true
+// total 30410, on one thread 30410
+// the workers' shares add up to 1000
+//
+// asked to stop after 100 of 1,000,000 results
+// stopped early: true
+// everything sent was processed, exactly once: true
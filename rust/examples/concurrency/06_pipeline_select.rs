@@ -0,0 +1,281 @@
+// Concurrency 6: The Same Pipeline with crossbeam-channel and select!
+// Demonstrates cloneable receivers, select! over several channels, a disconnect as a shutdown broadcast, and timeouts
+//
+// Concepts: crossbeam-channel, select!, cloneable receivers, timeouts
+// Difficulty: advanced
+// Minutes: 25
+//
+// crossbeam-channel is the channel crate most Rust code reaches for when
+// std's isn't enough (std's mpsc is in fact built on its design). Three
+// of its features reshape the pipeline from 05_pipeline_mpsc:
+//
+// - Its channels are multi-producer *and* multi-consumer. A Receiver can
+//   be cloned, so each worker gets its own, with no Arc<Mutex<...>>.
+// - select! waits on several channel operations at once and runs the
+//   first one that is ready. The producer waits either to send its next
+//   job or to hear that it should stop, so a stop request reaches it
+//   even while it is blocked on a full queue.
+// - A channel nobody ever sends on is a broadcast: dropping its Sender
+//   disconnects it, and every receiver, however many, sees that at once.
+//   That replaces the AtomicBool, and it can't be missed.
+//
+// select! can also give up after a while: the aggregator treats a long
+// silence from the workers as a bug and says so, instead of hanging.
+
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::{bounded, select, unbounded};
+
+/// How long the aggregator waits for a result before it gives up.
+const STALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+struct Job {
+    id: u64,
+    value: u64,
+}
+
+#[derive(Debug)]
+struct Done {
+    id: u64,
+    worker: usize,
+    digit_sum: u64,
+}
+
+#[derive(Debug)]
+struct Summary {
+    produced: u64,
+    results: Vec<Done>,
+}
+
+impl Summary {
+    /// Whether each job that was sent came back exactly once.
+    fn exactly_once(&self) -> bool {
+        let mut ids: Vec<u64> = self.results.iter().map(|done| done.id).collect();
+        ids.sort_unstable();
+        ids.into_iter().eq(0..self.produced)
+    }
+
+    fn total(&self) -> u64 {
+        self.results.iter().map(|done| done.digit_sum).sum()
+    }
+
+    fn per_worker(&self, workers: usize) -> Vec<usize> {
+        let mut counts = vec![0; workers];
+        for done in &self.results {
+            counts[done.worker] += 1;
+        }
+        counts
+    }
+}
+
+fn digit_sum(mut n: u64) -> u64 {
+    let mut sum = 0;
+    while n > 0 {
+        sum += n % 10;
+        n /= 10;
+    }
+    sum
+}
+
+fn run_pipeline(inputs: Vec<u64>, workers: usize, stop_after: Option<usize>) -> Summary {
+    let (job_tx, job_rx) = bounded::<Job>(4);
+    let (done_tx, done_rx) = unbounded::<Done>();
+    // Never sent on: dropping stop_tx is the signal
+    let (stop_tx, stop_rx) = bounded::<()>(0);
+
+    let producer = thread::spawn(move || {
+        let mut produced = 0;
+        for value in inputs {
+            let job = Job {
+                id: produced,
+                value,
+            };
+            select! {
+                send(job_tx, job) -> sent => sent.expect("every worker has hung up"),
+                recv(stop_rx) -> _ => break,
+            }
+            produced += 1;
+        }
+        produced
+    });
+
+    // Every worker has its own Receiver; each job still goes to only one
+    let handles: Vec<_> = (0..workers)
+        .map(|worker| {
+            let job_rx = job_rx.clone();
+            let done_tx = done_tx.clone();
+            thread::spawn(move || {
+                // Ends when the producer is gone and the queue is empty
+                for job in job_rx {
+                    let digit_sum = digit_sum(job.value);
+                    done_tx
+                        .send(Done {
+                            id: job.id,
+                            worker,
+                            digit_sum,
+                        })
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(job_rx);
+    drop(done_tx);
+
+    let mut stop_tx = Some(stop_tx);
+    let mut results = Vec::new();
+    loop {
+        select! {
+            recv(done_rx) -> done => match done {
+                Ok(done) => {
+                    results.push(done);
+                    if Some(results.len()) == stop_after {
+                        drop(stop_tx.take());
+                    }
+                }
+                Err(_) => break, // every worker has finished
+            },
+            default(STALL_TIMEOUT) => panic!("no result for {STALL_TIMEOUT:?}: is a worker stuck?"),
+        }
+    }
+    let produced = producer.join().unwrap();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    Summary { produced, results }
+}
+
+fn main() {
+    let inputs: Vec<u64> = (1..=1000).map(|n| n * 7919).collect();
+    let expected: u64 = inputs.iter().map(|&n| digit_sum(n)).sum();
+
+    let summary = run_pipeline(inputs, 4, None);
+    println!("1000 jobs, 4 workers, each with its own Receiver");
+    println!(
+        "produced {}, processed {}, each exactly once: {}",
+        summary.produced,
+        summary.results.len(),
+        summary.exactly_once()
+    );
+    println!("total {}, on one thread {expected}", summary.total());
+    println!(
+        "the workers' shares add up to {}",
+        summary.per_worker(4).iter().sum::<usize>()
+    );
+
+    let summary = run_pipeline((1..=1_000_000).collect(), 4, Some(100));
+    println!("\nasked to stop after 100 of 1,000,000 results");
+    println!("stopped early: {}", summary.produced < 1_000_000);
+    println!(
+        "everything sent was processed, exactly once: {}",
+        summary.exactly_once()
+    );
+
+    // A disconnected channel wakes every receiver: the broadcast on its own
+    let (stop_tx, stop_rx) = bounded::<()>(0);
+    let listeners: Vec<_> = (0..3)
+        .map(|_| {
+            let stop_rx = stop_rx.clone();
+            thread::spawn(move || stop_rx.recv().is_err())
+        })
+        .collect();
+    drop(stop_tx);
+    let woken = listeners
+        .into_iter()
+        .map(|listener| listener.join().unwrap())
+        .filter(|&disconnected| disconnected)
+        .count();
+    println!("\ndropping one Sender woke {woken} listeners");
+
+    // select! with a timeout, on a channel that stays quiet
+    let (_quiet_tx, quiet_rx) = unbounded::<u64>();
+    select! {
+        recv(quiet_rx) -> message => println!("got {message:?}"),
+        default(Duration::from_millis(50)) => println!("nothing within 50 ms"),
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let (_tx, rx) = unbounded::<Job>();
+        for _ in 0..2 {
+            thread::spawn(move || for _job in rx {});
+        }
+    }
+    // error[E0382]: use of moved value: `rx`
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_job_is_processed_exactly_once() {
+        let summary = run_pipeline((0..5000).collect(), 4, None);
+        assert_eq!(summary.produced, 5000);
+        assert_eq!(summary.results.len(), 5000);
+        assert!(summary.exactly_once());
+    }
+
+    #[test]
+    fn any_number_of_workers_gets_the_same_answer() {
+        let inputs: Vec<u64> = (0..2000).map(|n| n * 31).collect();
+        let expected: u64 = inputs.iter().map(|&n| digit_sum(n)).sum();
+        for workers in [1, 2, 8] {
+            let summary = run_pipeline(inputs.clone(), workers, None);
+            assert!(summary.exactly_once());
+            assert_eq!(summary.total(), expected);
+            assert_eq!(summary.per_worker(workers).iter().sum::<usize>(), 2000);
+        }
+    }
+
+    #[test]
+    fn stopping_early_loses_nothing_that_was_sent() {
+        for _ in 0..20 {
+            let summary = run_pipeline((0..100_000).collect(), 3, Some(10));
+            assert!(summary.produced >= 10);
+            assert_eq!(summary.results.len() as u64, summary.produced);
+            assert!(summary.exactly_once());
+        }
+    }
+
+    #[test]
+    fn a_stop_reaches_a_producer_blocked_on_a_full_queue() {
+        // One worker and a queue of four: the producer spends most of its
+        // time blocked in select!, and must still hear the stop
+        let summary = run_pipeline((0..1_000_000).collect(), 1, Some(1));
+        assert!(summary.produced < 1_000_000);
+        assert!(summary.exactly_once());
+    }
+
+    #[test]
+    fn no_input_shuts_down_at_once() {
+        let summary = run_pipeline(Vec::new(), 4, None);
+        assert_eq!(summary.produced, 0);
+        assert!(summary.results.is_empty());
+    }
+}
+
+/*
+ * Key Concepts:
+ * - crossbeam-channel Receivers are Clone: many consumers, no Mutex
+ * - select! waits on several sends and receives and runs the first ready one
+ * - Dropping the only Sender of an unused channel broadcasts "stop" to every receiver
+ * - select! with default(timeout) turns a hang into an error you can see
+ * - The pipeline's shape and its exactly-once guarantee are the same as with std
+ */
+
+// EXPECTED:
+// 1000 jobs, 4 workers, each with its own Receiver
+// produced 1000, processed 1000, each exactly once: true
+// total 30410, on one thread 30410
+// the workers' shares add up to 1000
+//
+// asked to stop after 100 of 1,000,000 results
+// stopped early: true
+// everything sent was processed, exactly once: true
+//
+// dropping one Sender woke 3 listeners
+// nothing within 50 ms
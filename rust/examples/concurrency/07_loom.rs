@@ -0,0 +1,261 @@
+// Concurrency 7: Finding a Race with loom
+// Demonstrates a lock-free counter with a check-then-act race, a loom model that finds it, and the compare_exchange fix
+//
+// Concepts: atomics, data races, loom, compare_exchange
+// Difficulty: advanced
+// Minutes: 25
+//
+// A TicketCounter hands out numbered tickets, at most `limit` of them,
+// to any number of threads, without a lock. The first version below
+// looks right: every operation on the counter is atomic, and a test that
+// runs eight threads against it passes, nearly every time. But checking
+// "is there room?" and then taking a ticket are two operations, and
+// another thread can take the last ticket in between:
+//
+//     thread A: load -> 1 of 2 issued, room left
+//     thread B: load -> 1 of 2 issued, room left
+//     thread A: fetch_add -> ticket 1
+//     thread B: fetch_add -> ticket 2, one more than the limit allows
+//
+// A bug that needs two threads to interleave just so is what ordinary
+// tests miss. loom (https://docs.rs/loom) doesn't wait for luck: inside
+// loom::model, its own versions of threads and atomics run the test body
+// once for every interleaving that can make a difference, and fail on the
+// first one that breaks an assertion. The loom tests here only exist when
+// compiled with `--cfg loom`, which `tutor verify concurrency/loom` does:
+//
+//     RUSTFLAGS="--cfg loom" cargo test --release -p concurrency-examples --bin 07_loom
+//
+// The fix makes the check and the take one atomic step: compare_exchange
+// only bumps the count if it is still the value we checked, and if
+// another thread got there first, we look again.
+
+/// std's threads and atomics normally; loom's when loom is checking us.
+mod sync {
+    #[cfg(loom)]
+    pub use loom::sync::atomic::{AtomicUsize, Ordering};
+    #[cfg(loom)]
+    pub use loom::sync::Arc;
+    #[cfg(loom)]
+    pub use loom::thread;
+
+    #[cfg(not(loom))]
+    pub use std::sync::atomic::{AtomicUsize, Ordering};
+    #[cfg(not(loom))]
+    pub use std::sync::Arc;
+    #[cfg(not(loom))]
+    pub use std::thread;
+}
+
+use sync::{thread, Arc, AtomicUsize, Ordering};
+
+/// Something that hands out tickets 0, 1, 2, ... up to a limit.
+trait Tickets: Send + Sync + 'static {
+    fn with_limit(limit: usize) -> Self
+    where
+        Self: Sized;
+    /// The next ticket, or `None` once `limit` have been handed out.
+    fn take(&self) -> Option<usize>;
+    fn issued(&self) -> usize;
+}
+
+/// Racy: between the load and the fetch_add, another thread can take the
+/// last ticket.
+struct RacyCounter {
+    issued: AtomicUsize,
+    limit: usize,
+}
+
+impl Tickets for RacyCounter {
+    fn with_limit(limit: usize) -> Self {
+        RacyCounter {
+            issued: AtomicUsize::new(0),
+            limit,
+        }
+    }
+
+    fn take(&self) -> Option<usize> {
+        if self.issued.load(Ordering::SeqCst) >= self.limit {
+            return None;
+        }
+        Some(self.issued.fetch_add(1, Ordering::SeqCst))
+    }
+
+    fn issued(&self) -> usize {
+        self.issued.load(Ordering::SeqCst)
+    }
+}
+
+/// Correct: checks and takes in a single compare_exchange, and retries if
+/// the count changed in between.
+struct TicketCounter {
+    issued: AtomicUsize,
+    limit: usize,
+}
+
+impl Tickets for TicketCounter {
+    fn with_limit(limit: usize) -> Self {
+        TicketCounter {
+            issued: AtomicUsize::new(0),
+            limit,
+        }
+    }
+
+    fn take(&self) -> Option<usize> {
+        let mut current = self.issued.load(Ordering::SeqCst);
+        loop {
+            if current >= self.limit {
+                return None;
+            }
+            match self.issued.compare_exchange(
+                current,
+                current + 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(ticket) => return Some(ticket),
+                // Someone else took a ticket first; `actual` is the new count
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    fn issued(&self) -> usize {
+        self.issued.load(Ordering::SeqCst)
+    }
+}
+
+/// Runs `threads` threads that each take tickets until there are none
+/// left, and returns every ticket handed out.
+fn hand_out<T: Tickets>(counter: Arc<T>, threads: usize) -> Vec<usize> {
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                let mut mine = Vec::new();
+                while let Some(ticket) = counter.take() {
+                    mine.push(ticket);
+                }
+                mine
+            })
+        })
+        .collect();
+    let mut tickets: Vec<usize> = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    tickets.sort_unstable();
+    tickets
+}
+
+fn main() {
+    // The racy interleaving, replayed by hand on one thread
+    let racy = RacyCounter::with_limit(2);
+    racy.take();
+    let a_sees_room = racy.issued.load(Ordering::SeqCst) < racy.limit;
+    let b_sees_room = racy.issued.load(Ordering::SeqCst) < racy.limit;
+    println!("limit 2, 1 issued; A sees room: {a_sees_room}, B sees room: {b_sees_room}");
+    let a = racy.issued.fetch_add(1, Ordering::SeqCst);
+    let b = racy.issued.fetch_add(1, Ordering::SeqCst);
+    println!(
+        "A takes ticket {a}, B takes ticket {b}: {} issued",
+        racy.issued()
+    );
+
+    // On one thread, both versions behave
+    let racy = RacyCounter::with_limit(3);
+    let fixed = TicketCounter::with_limit(3);
+    let racy_tickets: Vec<_> = std::iter::from_fn(|| racy.take()).collect();
+    let fixed_tickets: Vec<_> = std::iter::from_fn(|| fixed.take()).collect();
+    println!("\none thread, limit 3: racy {racy_tickets:?}, fixed {fixed_tickets:?}");
+
+    // The fixed counter on real threads
+    let counter = Arc::new(TicketCounter::with_limit(1000));
+    let tickets = hand_out(Arc::clone(&counter), 8);
+    let unique = tickets.windows(2).all(|pair| pair[0] < pair[1]);
+    println!(
+        "8 threads, limit 1000: {} tickets, all different: {unique}, issued {}",
+        tickets.len(),
+        counter.issued()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let counter = std::rc::Rc::new(TicketCounter::with_limit(1));
+        thread::spawn(move || counter.take());
+    }
+    // error[E0277]: `Rc<TicketCounter>` cannot be sent between threads safely
+}
+
+// Ordinary tests, with std's threads. Run against the racy counter, the
+// first one would pass too, almost every time: that is the problem.
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_fixed_counter_never_goes_over_its_limit() {
+        for _ in 0..20 {
+            let counter = Arc::new(TicketCounter::with_limit(500));
+            let tickets = hand_out(Arc::clone(&counter), 8);
+            assert_eq!(tickets, (0..500).collect::<Vec<_>>());
+            assert_eq!(counter.issued(), 500);
+        }
+    }
+
+    #[test]
+    fn both_counters_stop_at_the_limit_on_one_thread() {
+        let racy = RacyCounter::with_limit(2);
+        let fixed = TicketCounter::with_limit(2);
+        for counter in [&racy as &dyn Tickets, &fixed] {
+            assert_eq!(counter.take(), Some(0));
+            assert_eq!(counter.take(), Some(1));
+            assert_eq!(counter.take(), None);
+            assert_eq!(counter.issued(), 2);
+        }
+    }
+}
+
+// Model checks: loom runs each body under every interleaving that matters.
+// Two threads race for the only ticket; at most one may get it.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+
+    fn race_for_the_last_ticket<T: Tickets>() {
+        let counter = Arc::new(T::with_limit(1));
+        let tickets = hand_out(Arc::clone(&counter), 2);
+        assert!(
+            tickets.len() <= 1,
+            "handed out more tickets than the limit: {tickets:?}"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "handed out more tickets than the limit")]
+    fn loom_finds_the_race_in_the_racy_counter() {
+        loom::model(race_for_the_last_ticket::<RacyCounter>);
+    }
+
+    #[test]
+    fn loom_finds_no_race_in_the_fixed_counter() {
+        loom::model(race_for_the_last_ticket::<TicketCounter>);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Atomic operations one at a time don't make a sequence of them atomic
+ * - Check-then-act (load, then fetch_add) races; compare_exchange makes it one step
+ * - Ordinary threaded tests pass by luck; the bad interleaving is rare
+ * - loom::model runs a test under every interleaving that matters, deterministically
+ * - A `sync` module switches between std and loom types with #[cfg(loom)]
+ */
+
+// EXPECTED:
+// limit 2, 1 issued; A sees room: true, B sees room: true
+// A takes ticket 1, B takes ticket 2: 3 issued
+//
+// one thread, limit 3: racy [0, 1, 2], fixed [0, 1, 2]
+// 8 threads, limit 1000: 1000 tickets, all different: true, issued 1000
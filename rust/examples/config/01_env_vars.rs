@@ -0,0 +1,107 @@
+// Config 1: Environment Variables and .env Files
+// Demonstrates std::env::var, its errors, defaults, prefixes, and loading a .env file with dotenvy
+//
+// Concepts: env::var, VarError, var_os, env::vars, prefixes, .env files, dotenvy
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Environment variables are settings a program gets from whoever starts
+// it: the shell, a service manager, a container, a CI job. They are the
+// usual way to configure a program without changing its files (the
+// twelve-factor app makes it a rule), and the usual place for secrets
+// such as database passwords.
+//
+// std::env::var(name) reads one. It returns a Result, because the
+// variable may not be set, or (rarely) may not be valid UTF-8; var_os()
+// returns the raw OsString instead. Every value is text, so a number has
+// to be parsed, and a missing variable usually means "use the default".
+//
+// A `.env` file keeps development settings next to the code: NAME=value
+// lines that a library such as dotenvy reads as if they were set in the
+// environment. Try it with variables of your own:
+//
+//     MYAPP_PORT=9000 MYAPP_WORKERS=lots cargo run -p config-examples --bin 01_env_vars
+
+use std::env::{self, VarError};
+use std::path::Path;
+
+use learn_rust_core::section;
+
+/// The port from MYAPP_PORT, or 8080 if it isn't set. A value that is set
+/// but wrong is an error, not the default: silently ignoring a typo is
+/// worse than refusing to start.
+fn port() -> Result<u16, String> {
+    match env::var("MYAPP_PORT") {
+        Ok(text) => text
+            .parse()
+            .map_err(|error| format!("MYAPP_PORT={text}: {error}")),
+        Err(VarError::NotPresent) => Ok(8080),
+        Err(VarError::NotUnicode(raw)) => Err(format!("MYAPP_PORT is not UTF-8: {raw:?}")),
+    }
+}
+
+fn main() {
+    section("one variable");
+    // Set in every shell, so this one is (almost) always there
+    println!("PATH is set: {}", env::var_os("PATH").is_some());
+    match env::var("MYAPP_GREETING") {
+        Ok(greeting) => println!("MYAPP_GREETING = {greeting}"),
+        Err(error) => println!("MYAPP_GREETING: {error}"),
+    }
+    // The shortest way to a default, for a value that needs no parsing
+    let host = env::var("MYAPP_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    println!("host: {host}");
+    match port() {
+        Ok(port) => println!("port: {port}"),
+        Err(problem) => println!("error: {problem}"),
+    }
+
+    section("all of ours");
+    // The whole environment is env::vars(); a prefix picks out ours. The
+    // tutor runs this with none set, so you'll see them only if you set
+    // some yourself.
+    let mut ours: Vec<(String, String)> = env::vars()
+        .filter(|(name, _)| name.starts_with("MYAPP_"))
+        .collect();
+    ours.sort();
+    println!("{} MYAPP_ variable(s) {ours:?}", ours.len());
+
+    section("a .env file");
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("example.env");
+    // from_path_iter() only reads the file. dotenvy::dotenv() would also
+    // set each variable in our own environment, unless it is set already,
+    // so a real environment variable beats the file.
+    match dotenvy::from_path_iter(&path) {
+        Ok(lines) => {
+            for line in lines {
+                match line {
+                    Ok((name, value)) => println!("{name} = {value}"),
+                    Err(error) => println!("error: {error}"),
+                }
+            }
+        }
+        Err(error) => println!("cannot read {}: {error}", path.display()),
+    }
+}
+
+/*
+ * Key Concepts:
+ * - env::var(name) returns Result<String, VarError>: NotPresent or NotUnicode
+ * - var_os() gives the raw OsString, and is the one to use for paths
+ * - Every value is text: parse it, and treat a bad value as an error, not a default
+ * - Give your program's variables a prefix, and find them with env::vars()
+ * - A .env file holds development settings; dotenvy reads it into the environment
+ * - Real environment variables should beat the .env file, and dotenv() does that
+ */
+
+// EXPECTED:
+// -- one variable --
+// PATH is set: true
+// MYAPP_GREETING: environment variable not found
+// host: 127.0.0.1
+// port: 8080
+// -- all of ours --
+// 0 MYAPP_ variable(s) []
+// -- a .env file --
+// MYAPP_PORT = 3001
+// MYAPP_DATABASE_URL = sqlite://dev.db
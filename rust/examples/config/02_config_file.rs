@@ -0,0 +1,93 @@
+// Config 2: Reading a Config File
+// Demonstrates a TOML config file read into a struct of Options, with its errors
+//
+// Concepts: TOML, serde, Option fields, deny_unknown_fields, missing files, error messages
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Settings that rarely change, or that there are too many of for
+// environment variables, go in a file. TOML is the usual choice in Rust
+// (Cargo.toml is one): `key = value` lines, with strings in quotes,
+// numbers and booleans as they are, and [sections] for tables.
+//
+// serde reads it straight into a struct. The struct this chapter uses is
+// settings::Layer, in which every field is an Option: the file only has
+// to mention what it changes, and everything else keeps its default.
+// #[serde(deny_unknown_fields)] makes a key that isn't a field an error,
+// so `prot = 80` is caught instead of quietly doing nothing.
+//
+// A missing file is usually fine (there is nothing to override), but a
+// file that is there and wrong is not: the program should stop and say
+// where the mistake is.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use learn_rust_core::section;
+use settings::{Layer, Settings, Source};
+
+fn main() {
+    section("app.toml");
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("app.toml");
+    let layer = match fs::read_to_string(&path) {
+        Ok(text) => match Layer::from_toml(&text) {
+            Ok(layer) => layer,
+            Err(error) => {
+                eprintln!("error in {}: {error}", path.display());
+                return;
+            }
+        },
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Layer::default(),
+        Err(error) => {
+            eprintln!("cannot read {}: {error}", path.display());
+            return;
+        }
+    };
+    // What the file says, and nothing else: workers isn't in it
+    println!("{layer:?}");
+    let mut settings = Settings::default();
+    settings.apply(Source::File, &layer);
+    for (name, value, source) in settings.table() {
+        println!("{name:<14}{value:<12}({source})");
+    }
+
+    section("mistakes");
+    // toml's errors say where, and what it expected
+    let mistakes = [
+        "prot = 3000",
+        "port = \"3000\"",
+        "port = 99999",
+        "log_level = \"loud\"",
+        "workers = -1",
+    ];
+    for text in mistakes {
+        let error = Layer::from_toml(text).unwrap_err();
+        // The first line is the message; the others point at the spot
+        println!("{text:<22}-> {}", error.message());
+    }
+}
+
+/*
+ * Key Concepts:
+ * - serde reads a TOML file into a struct with toml::from_str
+ * - Option fields make every key optional, for a file that only overrides
+ * - deny_unknown_fields turns a misspelled key into an error
+ * - The wrong type, an out-of-range number, or an unknown enum value is an error too
+ * - A missing config file means "no overrides"; an invalid one means stop
+ */
+
+// EXPECTED:
+// -- app.toml --
+// Layer { host: Some("0.0.0.0"), port: Some(3000), workers: None, log_level: Some(Warn), database_url: None }
+// host          0.0.0.0     (config file)
+// port          3000        (config file)
+// workers       4           (default)
+// log_level     warn        (config file)
+// database_url  (none)      (default)
+// -- mistakes --
+// prot = 3000           -> unknown field `prot`, expected one of `host`, `port`, `workers`, `log_level`, `database_url`
+// port = "3000"         -> invalid type: string "3000", expected u16
+// port = 99999          -> invalid value: integer `99999`, expected u16
+// log_level = "loud"    -> unknown variant `loud`, expected one of `error`, `warn`, `info`, `debug`
+// workers = -1          -> invalid value: integer `-1`, expected usize
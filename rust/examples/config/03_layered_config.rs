@@ -0,0 +1,137 @@
+// Config 3: Layered Configuration
+// Demonstrates defaults, a file, a .env file, the environment, and flags merged into one Config
+//
+// Concepts: precedence, layers, Option merging, clap flatten, try_parse_from, provenance
+// Difficulty: intermediate
+// Minutes: 20
+//
+// A real program reads its settings from several places, and the rule for
+// which one wins is always the same shape: the more specific, the more
+// recent, the closer to this one run, the stronger.
+//
+//     defaults  <  config file  <  .env file  <  environment  <  flags
+//
+// The defaults are in the code. The config file is shared by everyone who
+// runs the program here. A .env file is one developer's machine. An
+// environment variable is this shell, or this container. A flag is this
+// one command.
+//
+// Each source except the defaults is a settings::Layer, where every field
+// is an Option, and Settings::apply() copies only the fields a layer has.
+// So a later layer wins exactly where it says something, and nowhere
+// else. Settings also remembers where each value came from: when a
+// program does something surprising, "port 3001 (.env file)" answers the
+// first question.
+//
+// The flags are the same Layer, flattened into the clap parser, so a
+// setting has one name everywhere: port in the file, MYAPP_PORT in the
+// environment, --port on the command line. Try:
+//
+//     cargo run -p config-examples --bin 03_layered_config -- --workers 8
+//     MYAPP_HOST=localhost cargo run -p config-examples --bin 03_layered_config
+//     cargo run -p config-examples --bin 03_layered_config -- --config missing.toml
+
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use learn_rust_core::section;
+use settings::{Layer, Settings, Sources};
+
+/// Prints the settings, and where each one came from.
+#[derive(Debug, Parser)]
+struct Cli {
+    /// The TOML file to read
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/app.toml"))]
+    config: PathBuf,
+    /// The .env file to read
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/example.env"))]
+    env_file: PathBuf,
+    #[command(flatten)]
+    settings: Layer,
+}
+
+impl Cli {
+    fn sources(self, vars: Vec<(String, String)>) -> Sources {
+        Sources {
+            file: Some(self.config),
+            dotenv: Some(self.env_file),
+            vars,
+            flags: self.settings,
+        }
+    }
+}
+
+fn print(settings: &Settings) {
+    for (name, value, source) in settings.table() {
+        println!("{name:<14}{value:<18}({source})");
+    }
+}
+
+fn main() -> ExitCode {
+    section("this run");
+    // Whatever you ran it with; the tutor runs it with no flags and no
+    // MYAPP_ variables, so only the two files change anything
+    let cli = Cli::parse();
+    match settings::load(&cli.sources(env::vars().collect())) {
+        Ok(settings) => print(&settings),
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    section("every layer");
+    // The same, with an environment and a command line made up here, so
+    // that each layer wins somewhere:
+    //   host      file      0.0.0.0
+    //   database  .env      sqlite://dev.db
+    //   workers   env       MYAPP_WORKERS=2, the .env file doesn't say
+    //   log_level env       MYAPP_LOG_LEVEL beats the file's warn
+    //   port      flag      --port beats the file's 3000 and .env's 3001
+    let vars = [("MYAPP_WORKERS", "2"), ("MYAPP_LOG_LEVEL", "debug")];
+    let vars = vars.map(|(name, value)| (name.to_string(), value.to_string()));
+    let cli = Cli::try_parse_from(["03_layered_config", "--port", "9000"]).unwrap();
+    print(&settings::load(&cli.sources(vars.to_vec())).unwrap());
+
+    section("mistakes");
+    // A mistake in any layer is an error that names it, never a default
+    let vars = vec![("MYAPP_WORKERS".to_string(), "many".to_string())];
+    let cli = Cli::try_parse_from(["03_layered_config"]).unwrap();
+    println!("{}", settings::load(&cli.sources(vars)).unwrap_err());
+    let vars = vec![("MYAPP_PROT".to_string(), "80".to_string())];
+    let cli = Cli::try_parse_from(["03_layered_config"]).unwrap();
+    println!("{}", settings::load(&cli.sources(vars)).unwrap_err());
+    // clap checks the flags before there is anything to load
+    let error = Cli::try_parse_from(["03_layered_config", "--port", "http"]).unwrap_err();
+    println!("{}", error.to_string().lines().next().unwrap());
+    ExitCode::SUCCESS
+}
+
+/*
+ * Key Concepts:
+ * - Later, more specific sources override earlier ones: defaults < file < .env < env < flags
+ * - A layer is the config with every field optional; merging copies only what it has
+ * - Remember where each value came from, and show it when asked
+ * - One struct for the file and the flags (serde + clap) keeps the names the same
+ * - A missing file is an empty layer; a wrong value anywhere is an error naming it
+ */
+
+// EXPECTED:
+// -- this run --
+// host          0.0.0.0           (config file)
+// port          3001              (.env file)
+// workers       4                 (default)
+// log_level     warn              (config file)
+// database_url  sqlite://dev.db   (.env file)
+// -- every layer --
+// host          0.0.0.0           (config file)
+// port          9000              (command line)
+// workers       2                 (environment)
+// log_level     debug             (environment)
+// database_url  sqlite://dev.db   (.env file)
+// -- mistakes --
+// MYAPP_WORKERS=many: invalid digit found in string
+// MYAPP_PROT is not a setting
+// error: invalid value 'http' for '--port <PORT>': invalid digit found in string
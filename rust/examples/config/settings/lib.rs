@@ -0,0 +1,406 @@
+//! A typed [`Config`] assembled from layers, each overriding the ones
+//! before it:
+//!
+//! 1. the defaults, in [`Config::default`]
+//! 2. a TOML file, `app.toml`
+//! 3. a `.env` file, read as more environment variables
+//! 4. the real environment variables, `MYAPP_PORT` and so on
+//! 5. command-line flags, `--port` and so on
+//!
+//! Every layer except the first is a [`Layer`], the same fields with each
+//! one optional, because a layer only sets what it mentions. [`Settings`]
+//! starts from the defaults and [applies](Settings::apply) the layers in
+//! order, remembering which [`Source`] each value came from, and [`load`]
+//! does all of that in the order above.
+//!
+//! ```
+//! use settings::{Layer, Settings, Source};
+//!
+//! let mut settings = Settings::default();
+//! settings.apply(Source::File, &Layer::from_toml("port = 8000\nworkers = 2").unwrap());
+//! settings.apply(Source::Env, &Layer::from_vars([("MYAPP_PORT", "9000")]).unwrap());
+//! assert_eq!(settings.config.port, 9000);
+//! assert_eq!(settings.config.workers, 2);
+//! assert_eq!(settings.origin("port"), Source::Env);
+//! ```
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+/// What every environment variable for this program starts with, so that
+/// it leaves everybody else's alone.
+pub const ENV_PREFIX: &str = "MYAPP_";
+
+/// The finished configuration: every field has a value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// The address to listen on.
+    pub host: String,
+    pub port: u16,
+    /// How many requests to handle at once.
+    pub workers: usize,
+    pub log_level: LogLevel,
+    /// Where the database is; none means keep everything in memory.
+    pub database_url: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            workers: 4,
+            log_level: LogLevel::Info,
+            database_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    /// What environment variables and flags go through; the file goes
+    /// through serde, with the same names.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_lowercase().as_str() {
+            "error" => Ok(LogLevel::Error),
+            "warn" => Ok(LogLevel::Warn),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err(format!("{text:?} is not error, warn, info, or debug")),
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Some of a [`Config`]: what one source says, and nothing about the
+/// rest. The same struct reads the TOML file with serde and the flags
+/// with clap, so the two can't disagree about the names.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, clap::Args)]
+#[serde(deny_unknown_fields)]
+pub struct Layer {
+    /// The address to listen on
+    #[arg(long)]
+    pub host: Option<String>,
+    /// The port to listen on
+    #[arg(long)]
+    pub port: Option<u16>,
+    /// How many requests to handle at once
+    #[arg(long)]
+    pub workers: Option<usize>,
+    /// error, warn, info, or debug
+    #[arg(long)]
+    pub log_level: Option<LogLevel>,
+    /// Where the database is
+    #[arg(long)]
+    pub database_url: Option<String>,
+}
+
+impl Layer {
+    /// The layer a TOML file describes. A key that isn't a field is an
+    /// error, not ignored, so that a typo doesn't go unnoticed.
+    pub fn from_toml(text: &str) -> Result<Layer, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// The layer in environment variables: `MYAPP_PORT=9000` sets `port`.
+    /// Variables without the prefix are someone else's and are skipped;
+    /// one with the prefix that isn't a field is an error.
+    pub fn from_vars<K, V>(vars: impl IntoIterator<Item = (K, V)>) -> Result<Layer, ConfigError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut layer = Layer::default();
+        for (name, value) in vars {
+            let (name, value) = (name.as_ref(), value.as_ref());
+            let Some(field) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            match field.to_lowercase().as_str() {
+                "host" => layer.host = Some(value.to_string()),
+                "port" => layer.port = Some(parse_var(name, value)?),
+                "workers" => layer.workers = Some(parse_var(name, value)?),
+                "log_level" => layer.log_level = Some(parse_var(name, value)?),
+                "database_url" => layer.database_url = Some(value.to_string()),
+                _ => return Err(ConfigError::UnknownVar(name.to_string())),
+            }
+        }
+        Ok(layer)
+    }
+}
+
+fn parse_var<T: FromStr>(name: &str, value: &str) -> Result<T, ConfigError>
+where
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|error: T::Err| ConfigError::Var {
+        name: name.to_string(),
+        value: value.to_string(),
+        problem: error.to_string(),
+    })
+}
+
+/// Where a value came from, from the weakest source to the strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    Default,
+    File,
+    DotEnv,
+    Env,
+    Flag,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Source::Default => "default",
+            Source::File => "config file",
+            Source::DotEnv => ".env file",
+            Source::Env => "environment",
+            Source::Flag => "command line",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A [`Config`], and where each of its values came from.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Settings {
+    pub config: Config,
+    origins: BTreeMap<&'static str, Source>,
+}
+
+impl Settings {
+    /// Overrides every value `layer` has, as coming from `source`.
+    pub fn apply(&mut self, source: Source, layer: &Layer) {
+        macro_rules! set {
+            ($field:ident) => {
+                if let Some(value) = &layer.$field {
+                    self.config.$field = value.clone();
+                    self.origins.insert(stringify!($field), source);
+                }
+            };
+        }
+        set!(host);
+        set!(port);
+        set!(workers);
+        set!(log_level);
+        // The only optional field, so a layer that has it sets it to Some
+        if let Some(url) = &layer.database_url {
+            self.config.database_url = Some(url.clone());
+            self.origins.insert("database_url", source);
+        }
+    }
+
+    /// Where the field called `field` got its value.
+    pub fn origin(&self, field: &str) -> Source {
+        self.origins.get(field).copied().unwrap_or(Source::Default)
+    }
+
+    /// The fields as (name, value, source), in the order they are declared.
+    pub fn table(&self) -> Vec<(&'static str, String, Source)> {
+        let config = &self.config;
+        let database = config.database_url.as_deref().unwrap_or("(none)");
+        [
+            ("host", config.host.clone()),
+            ("port", config.port.to_string()),
+            ("workers", config.workers.to_string()),
+            ("log_level", config.log_level.to_string()),
+            ("database_url", database.to_string()),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name, value, self.origin(name)))
+        .collect()
+    }
+}
+
+/// Everything [`load`] reads, besides the defaults.
+#[derive(Debug, Default)]
+pub struct Sources {
+    /// The TOML file, if there is one.
+    pub file: Option<PathBuf>,
+    /// The `.env` file, if there is one.
+    pub dotenv: Option<PathBuf>,
+    /// The environment, usually `std::env::vars()`.
+    pub vars: Vec<(String, String)>,
+    pub flags: Layer,
+}
+
+/// The defaults, overridden by the file, then the `.env` file, then the
+/// environment, then the flags. A file that doesn't exist is an empty
+/// layer; a file that exists but can't be read or parsed is an error.
+pub fn load(sources: &Sources) -> Result<Settings, ConfigError> {
+    let mut settings = Settings::default();
+    if let Some(path) = &sources.file {
+        if let Some(text) = read_if_exists(path)? {
+            let layer = Layer::from_toml(&text).map_err(|error| ConfigError::File {
+                path: path.clone(),
+                error,
+            })?;
+            settings.apply(Source::File, &layer);
+        }
+    }
+    if let Some(path) = &sources.dotenv {
+        if let Some(vars) = read_dotenv(path)? {
+            settings.apply(Source::DotEnv, &Layer::from_vars(vars)?);
+        }
+    }
+    let vars = sources.vars.iter().map(|(name, value)| (name, value));
+    settings.apply(Source::Env, &Layer::from_vars(vars)?);
+    settings.apply(Source::Flag, &sources.flags);
+    Ok(settings)
+}
+
+fn read_if_exists(path: &Path) -> Result<Option<String>, ConfigError> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(ConfigError::Read {
+            path: path.to_path_buf(),
+            error,
+        }),
+    }
+}
+
+/// The variables in a `.env` file, without setting them: dotenvy's
+/// `dotenv()` would copy them into our own environment instead.
+fn read_dotenv(path: &Path) -> Result<Option<Vec<(String, String)>>, ConfigError> {
+    let error = |error| ConfigError::DotEnv {
+        path: path.to_path_buf(),
+        error,
+    };
+    let lines = match dotenvy::from_path_iter(path) {
+        Ok(lines) => lines,
+        Err(dotenv) if dotenv.not_found() => return Ok(None),
+        Err(dotenv) => return Err(error(dotenv)),
+    };
+    lines.collect::<Result<_, _>>().map(Some).map_err(error)
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read {
+        path: PathBuf,
+        error: io::Error,
+    },
+    File {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    DotEnv {
+        path: PathBuf,
+        error: dotenvy::Error,
+    },
+    Var {
+        name: String,
+        value: String,
+        problem: String,
+    },
+    UnknownVar(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Read { path, error } => {
+                write!(f, "cannot read {}: {error}", path.display())
+            }
+            ConfigError::File { path, error } => write!(f, "in {}: {error}", path.display()),
+            ConfigError::DotEnv { path, error } => write!(f, "in {}: {error}", path.display()),
+            ConfigError::Var {
+                name,
+                value,
+                problem,
+            } => write!(f, "{name}={value}: {problem}"),
+            ConfigError::UnknownVar(name) => write!(f, "{name} is not a setting"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variables_are_matched_by_prefix_and_parsed() {
+        let layer = Layer::from_vars([
+            ("HOME", "/home/ferris"),
+            ("MYAPP_WORKERS", "16"),
+            ("MYAPP_LOG_LEVEL", "DEBUG"),
+        ])
+        .unwrap();
+        assert_eq!(layer.workers, Some(16));
+        assert_eq!(layer.log_level, Some(LogLevel::Debug));
+        assert_eq!(layer.host, None);
+
+        let error = Layer::from_vars([("MYAPP_PORT", "http")]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "MYAPP_PORT=http: invalid digit found in string"
+        );
+        let error = Layer::from_vars([("MYAPP_PROT", "80")]).unwrap_err();
+        assert_eq!(error.to_string(), "MYAPP_PROT is not a setting");
+    }
+
+    #[test]
+    fn a_later_layer_wins_only_where_it_says_something() {
+        let mut settings = Settings::default();
+        let file = Layer::from_toml("host = \"0.0.0.0\"\nport = 80").unwrap();
+        settings.apply(Source::File, &file);
+        let flags = Layer {
+            port: Some(8000),
+            database_url: Some("sqlite://app.db".to_string()),
+            ..Layer::default()
+        };
+        settings.apply(Source::Flag, &flags);
+
+        assert_eq!(settings.config.host, "0.0.0.0");
+        assert_eq!(settings.config.port, 8000);
+        assert_eq!(settings.config.workers, 4);
+        assert_eq!(
+            settings.config.database_url.as_deref(),
+            Some("sqlite://app.db")
+        );
+        let origins: Vec<Source> = settings.table().iter().map(|row| row.2).collect();
+        assert_eq!(
+            origins,
+            [
+                Source::File,
+                Source::Flag,
+                Source::Default,
+                Source::Default,
+                Source::Flag
+            ]
+        );
+    }
+}
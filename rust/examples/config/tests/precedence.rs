@@ -0,0 +1,137 @@
+// Checks the order the layers override each other in, one layer at a
+// time: each test writes the files it needs into a temporary directory,
+// makes up the environment and the flags, and loads. None of them reads
+// the real environment, so they pass whatever MYAPP_ variables are set.
+
+use std::fs;
+
+use settings::{load, Layer, LogLevel, Source, Sources};
+use tempfile::TempDir;
+
+struct Fixture {
+    dir: TempDir,
+    sources: Sources,
+}
+
+impl Fixture {
+    /// No files, no variables, no flags, just where the files would be.
+    fn new() -> Fixture {
+        let dir = tempfile::tempdir().unwrap();
+        let sources = Sources {
+            file: Some(dir.path().join("app.toml")),
+            dotenv: Some(dir.path().join(".env")),
+            ..Sources::default()
+        };
+        Fixture { dir, sources }
+    }
+
+    fn file(self, text: &str) -> Fixture {
+        fs::write(self.dir.path().join("app.toml"), text).unwrap();
+        self
+    }
+
+    fn dotenv(self, text: &str) -> Fixture {
+        fs::write(self.dir.path().join(".env"), text).unwrap();
+        self
+    }
+
+    fn var(mut self, name: &str, value: &str) -> Fixture {
+        self.sources
+            .vars
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn flags(mut self, flags: Layer) -> Fixture {
+        self.sources.flags = flags;
+        self
+    }
+}
+
+#[test]
+fn without_any_source_everything_is_a_default() {
+    let settings = load(&Fixture::new().sources).unwrap();
+    assert_eq!(settings.config, Default::default());
+    assert!(settings.table().iter().all(|row| row.2 == Source::Default));
+}
+
+#[test]
+fn the_file_overrides_the_defaults_it_mentions() {
+    let fixture = Fixture::new().file("port = 3000\nlog_level = \"warn\"\n");
+    let settings = load(&fixture.sources).unwrap();
+    assert_eq!(settings.config.port, 3000);
+    assert_eq!(settings.config.log_level, LogLevel::Warn);
+    assert_eq!(settings.origin("port"), Source::File);
+    assert_eq!(settings.config.workers, 4);
+    assert_eq!(settings.origin("workers"), Source::Default);
+}
+
+#[test]
+fn the_dotenv_file_overrides_the_config_file() {
+    let fixture = Fixture::new()
+        .file("port = 3000\nhost = \"0.0.0.0\"\n")
+        .dotenv("# for my machine\nMYAPP_PORT=3001\n");
+    let settings = load(&fixture.sources).unwrap();
+    assert_eq!(settings.config.port, 3001);
+    assert_eq!(settings.origin("port"), Source::DotEnv);
+    assert_eq!(settings.config.host, "0.0.0.0");
+    assert_eq!(settings.origin("host"), Source::File);
+}
+
+#[test]
+fn the_environment_overrides_the_dotenv_file() {
+    let fixture = Fixture::new()
+        .dotenv("MYAPP_PORT=3001\nMYAPP_WORKERS=2\n")
+        .var("MYAPP_PORT", "4000")
+        .var("HOME", "/home/ferris");
+    let settings = load(&fixture.sources).unwrap();
+    assert_eq!(settings.config.port, 4000);
+    assert_eq!(settings.origin("port"), Source::Env);
+    assert_eq!(settings.config.workers, 2);
+    assert_eq!(settings.origin("workers"), Source::DotEnv);
+}
+
+#[test]
+fn flags_override_every_other_layer() {
+    let fixture = Fixture::new()
+        .file("port = 3000\nworkers = 8\n")
+        .dotenv("MYAPP_PORT=3001\n")
+        .var("MYAPP_PORT", "4000")
+        .flags(Layer {
+            port: Some(9000),
+            ..Layer::default()
+        });
+    let settings = load(&fixture.sources).unwrap();
+    assert_eq!(settings.config.port, 9000);
+    assert_eq!(settings.origin("port"), Source::Flag);
+    assert_eq!(settings.config.workers, 8);
+    assert_eq!(settings.origin("workers"), Source::File);
+}
+
+#[test]
+fn a_missing_file_is_skipped_but_a_broken_one_is_an_error() {
+    let fixture = Fixture::new().dotenv("MYAPP_HOST=localhost\n");
+    assert_eq!(load(&fixture.sources).unwrap().config.host, "localhost");
+
+    let fixture = Fixture::new().file("prot = 80\n");
+    let error = load(&fixture.sources).unwrap_err().to_string();
+    assert!(error.contains("app.toml"), "{error}");
+    assert!(error.contains("unknown field `prot`"), "{error}");
+}
+
+#[test]
+fn a_bad_variable_is_an_error_naming_it() {
+    let fixture = Fixture::new().var("MYAPP_LOG_LEVEL", "loud");
+    let error = load(&fixture.sources).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "MYAPP_LOG_LEVEL=loud: \"loud\" is not error, warn, info, or debug"
+    );
+
+    // In the .env file too, even when the environment would override it
+    let fixture = Fixture::new()
+        .dotenv("MYAPP_PROT=80\n")
+        .var("MYAPP_PORT", "80");
+    let error = load(&fixture.sources).unwrap_err();
+    assert_eq!(error.to_string(), "MYAPP_PROT is not a setting");
+}
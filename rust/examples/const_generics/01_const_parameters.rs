@@ -0,0 +1,124 @@
+// Const Generics 1: Arrays and Const Parameters
+// Demonstrates [T; N], functions and structs generic over a length N
+//
+// Concepts: const generics, [T; N], generic lengths
+// Difficulty: intermediate
+// Minutes: 10
+//
+// An array's length is part of its type: [u8; 4] and [u8; 16] are as
+// different as u8 and u16. A const generic parameter, written
+// `const N: usize`, lets one function or struct work for every length
+// while the compiler still knows the exact length of each use. That means
+// no heap allocation, no length checks at runtime, and mistakes in sizes
+// caught before the program runs.
+
+use std::array;
+
+/// Works for an array of any length; N is inferred from the argument.
+fn sum<const N: usize>(values: [i32; N]) -> i32 {
+    values.iter().sum()
+}
+
+/// Returns an array whose length the caller picks.
+fn squares<const N: usize>() -> [u64; N] {
+    array::from_fn(|i| (i * i) as u64)
+}
+
+/// Remembers the last N values it was given, without ever allocating.
+struct Recent<const N: usize> {
+    values: [f64; N],
+    next: usize,
+    filled: usize,
+}
+
+impl<const N: usize> Recent<N> {
+    fn new() -> Self {
+        Recent {
+            values: [0.0; N],
+            next: 0,
+            filled: 0,
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        self.values[self.next] = value;
+        self.next = (self.next + 1) % N;
+        self.filled = (self.filled + 1).min(N);
+    }
+
+    fn average(&self) -> Option<f64> {
+        if self.filled == 0 {
+            return None;
+        }
+        Some(self.values[..self.filled].iter().sum::<f64>() / self.filled as f64)
+    }
+
+    /// N is a value we can use, here and in any other method
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+fn main() {
+    // One function, three array types
+    println!("sum of 2: {}", sum([1, 2]));
+    println!("sum of 5: {}", sum([1, 2, 3, 4, 5]));
+    println!("sum of 0: {}", sum([]));
+
+    // When N can't be inferred, we name it
+    let small = squares::<4>();
+    let bigger: [u64; 6] = squares();
+    println!("squares: {:?} and {:?}", small, bigger);
+
+    let mut recent: Recent<3> = Recent::new();
+    println!("average of nothing: {:?}", recent.average());
+    for reading in [10.0, 20.0, 30.0, 40.0] {
+        recent.push(reading);
+    }
+    println!(
+        "last {} readings average {:?}",
+        recent.capacity(),
+        recent.average()
+    );
+
+    // The length lives in the type, so a Recent<3> costs exactly what its
+    // fields cost, with nothing stored on the heap
+    println!(
+        "Recent<3> is {} bytes, Recent<100> is {} bytes",
+        std::mem::size_of::<Recent<3>>(),
+        std::mem::size_of::<Recent<100>>()
+    );
+
+    // Slices forget the length; try_into() gets an array back, checking
+    // the length at runtime because a slice's length isn't known earlier
+    let bytes: &[u8] = &[0xde, 0xad, 0xbe, 0xef, 0x01];
+    let header: Result<[u8; 4], _> = bytes[..4].try_into();
+    let wrong: Result<[u8; 4], _> = bytes.try_into();
+    println!("header: {:x?}, whole slice: {}", header, wrong.is_ok());
+    println!("as u32: {:#x}", u32::from_be_bytes(header.unwrap()));
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let pair: [i32; 2] = squares::<3>().map(|x| x as i32);
+    // error[E0308]: mismatched types (expected an array with a size of 2, found one with a size of 3)
+}
+
+/*
+ * Key Concepts:
+ * - An array's length is part of its type: [T; N]
+ * - `const N: usize` makes functions and types generic over a length
+ * - N is inferred from arguments, or given with ::<N> or a type annotation
+ * - N can be used as a value inside the generic code
+ * - Slices have a runtime length; try_into() converts back to an array
+ */
+
+// EXPECTED:
+// sum of 2: 3
+// sum of 5: 15
+// sum of 0: 0
+// squares: [0, 1, 4, 9] and [0, 1, 4, 9, 16, 25]
+// average of nothing: None
+// last 3 readings average Some(30.0)
+// Recent<3> is 40 bytes, Recent<100> is 816 bytes
+// header: Ok([de, ad, be, ef]), whole slice: false
+// as u32: 0xdeadbeef
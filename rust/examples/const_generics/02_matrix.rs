@@ -0,0 +1,151 @@
+// Const Generics 2: A Matrix With Checked Dimensions
+// Demonstrates Matrix<R, C>, where the sizes make bad arithmetic a compile error
+//
+// Concepts: const generics, compile-time dimensions, operator traits
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Multiplying an R×C matrix by a C×K one gives an R×K matrix; any other
+// combination is meaningless. A matrix type that stores its sizes in
+// fields has to check that rule at runtime and decide what to do when it
+// fails. With the sizes as const parameters, the rule becomes the type of
+// the multiplication: `Matrix<R, C> * Matrix<C, K> = Matrix<R, K>`, and a
+// mismatched product simply doesn't compile.
+
+use std::fmt;
+use std::ops::{Add, Mul};
+
+/// R rows and C columns of numbers, stored inline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Matrix<const R: usize, const C: usize> {
+    cells: [[i64; C]; R],
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    fn new(cells: [[i64; C]; R]) -> Self {
+        Matrix { cells }
+    }
+
+    fn zero() -> Self {
+        Matrix { cells: [[0; C]; R] }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        (R, C)
+    }
+
+    fn scale(mut self, factor: i64) -> Self {
+        for row in &mut self.cells {
+            for cell in row {
+                *cell *= factor;
+            }
+        }
+        self
+    }
+}
+
+/// Only matrices of the same size can be added
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Matrix<R, C>;
+
+    fn add(mut self, other: Matrix<R, C>) -> Matrix<R, C> {
+        for (row, other_row) in self.cells.iter_mut().zip(other.cells) {
+            for (cell, other_cell) in row.iter_mut().zip(other_row) {
+                *cell += other_cell;
+            }
+        }
+        self
+    }
+}
+
+/// The inner sizes must agree: both are the same parameter C
+impl<const R: usize, const C: usize, const K: usize> Mul<Matrix<C, K>> for Matrix<R, C> {
+    type Output = Matrix<R, K>;
+
+    fn mul(self, other: Matrix<C, K>) -> Matrix<R, K> {
+        let mut product = Matrix::<R, K>::zero();
+        for r in 0..R {
+            for k in 0..K {
+                product.cells[r][k] = (0..C).map(|c| self.cells[r][c] * other.cells[c][k]).sum();
+            }
+        }
+        product
+    }
+}
+
+impl<const R: usize, const C: usize> fmt::Display for Matrix<R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.cells {
+            let cells: Vec<String> = row.iter().map(|cell| format!("{:>3}", cell)).collect();
+            writeln!(f, "  [{} ]", cells.join(""))?;
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+    let b = Matrix::new([[1, 0], [0, 1], [2, 2]]);
+    println!("a is {:?}:\n{}", a.size(), a);
+    println!("b is {:?}:\n{}", b.size(), b);
+
+    // 2×3 times 3×2 is 2×2, and the compiler works that out for us
+    let ab = a * b;
+    println!("a * b is {:?}:\n{}", ab.size(), ab);
+
+    // 3×2 times 2×3 is 3×3: the order of a product matters
+    let ba = b * a;
+    println!("b * a is {:?}:\n{}", ba.size(), ba);
+
+    // A column vector is just a matrix with one column
+    let v = Matrix::new([[1], [1], [1]]);
+    println!("row sums of a:\n{}", a * v);
+
+    println!("a + a == a scaled by 2: {}", a + a == a.scale(2));
+
+    // No sizes are stored: the type holds them, the value holds only cells
+    println!(
+        "Matrix<2, 3> is {} bytes",
+        std::mem::size_of::<Matrix<2, 3>>()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let wrong = a * a;
+    // error[E0308]: mismatched types (expected `Matrix<3, _>`, found `Matrix<2, 3>`)
+}
+
+/*
+ * Key Concepts:
+ * - Several const parameters describe a shape: Matrix<R, C>
+ * - Reusing a parameter (C in Matrix<R, C> * Matrix<C, K>) makes sizes agree
+ * - Mismatched sizes are type errors, found before the program runs
+ * - Nested arrays [[T; C]; R] store everything inline, with no heap
+ * - Operator traits (Add, Mul) can be implemented for const generic types
+ */
+
+// EXPECTED:
+// a is (2, 3):
+//   [  1  2  3 ]
+//   [  4  5  6 ]
+//
+// b is (3, 2):
+//   [  1  0 ]
+//   [  0  1 ]
+//   [  2  2 ]
+//
+// a * b is (2, 2):
+//   [  7  8 ]
+//   [ 16 17 ]
+//
+// b * a is (3, 3):
+//   [  1  2  3 ]
+//   [  4  5  6 ]
+//   [ 10 14 18 ]
+//
+// row sums of a:
+//   [  6 ]
+//   [ 15 ]
+//
+// a + a == a scaled by 2: true
+// Matrix<2, 3> is 48 bytes
@@ -0,0 +1,110 @@
+// Const Generics 3: Const Expressions, Defaults, and Compile-Time Checks
+// Demonstrates const fn arguments, default const parameters, and const assertions
+//
+// Concepts: const fn, default const parameters, const assertions
+// Difficulty: advanced
+// Minutes: 10
+//
+// A const argument can be any expression the compiler can evaluate: a
+// literal, a `const` item, or a call to a `const fn`, written in braces.
+// Const parameters may have defaults, like type parameters. And since the
+// value is known while compiling, a rule such as "N must not be zero" can
+// be checked then, too, so a bad size never makes it into a binary. The
+// one thing stable Rust doesn't allow yet is arithmetic on a *generic*
+// parameter in a type, like [u8; N * 2].
+
+/// Sizes computed at compile time
+const fn kib(n: usize) -> usize {
+    n * 1024
+}
+
+const KEY_LEN: usize = 32;
+type Key = [u8; KEY_LEN];
+
+/// A fixed-size packet; without a size, it is 8 bytes.
+struct Packet<const N: usize = 8> {
+    payload: [u8; N],
+}
+
+impl<const N: usize> Packet<N> {
+    fn filled(byte: u8) -> Self {
+        Packet { payload: [byte; N] }
+    }
+
+    fn len(&self) -> usize {
+        self.payload.len()
+    }
+}
+
+/// A ring buffer: its index arithmetic divides by N, so N = 0 is ruled out.
+struct Ring<const N: usize> {
+    slots: [Option<char>; N],
+    next: usize,
+}
+
+impl<const N: usize> Ring<N> {
+    fn new() -> Self {
+        // Evaluated once per N while compiling, not when new() runs
+        const { assert!(N > 0, "a ring needs room for at least one value") };
+        Ring {
+            slots: [None; N],
+            next: 0,
+        }
+    }
+
+    fn push(&mut self, value: char) {
+        self.slots[self.next % N] = Some(value);
+        self.next += 1;
+    }
+
+    fn contents(&self) -> String {
+        self.slots.iter().flatten().collect()
+    }
+}
+
+/// [u8; N * 2] is not allowed as a generic return type yet; an array of
+/// two arrays holds the same bytes.
+fn doubled<const N: usize>(bytes: [u8; N]) -> [[u8; N]; 2] {
+    [bytes, bytes]
+}
+
+fn main() {
+    let key: Key = [7; KEY_LEN];
+    println!("key is {} bytes", key.len());
+
+    let page = Packet::<{ kib(4) }>::filled(0);
+    println!("a page packet holds {} bytes", page.len());
+
+    // The default applies when the type is written without arguments
+    let small: Packet = Packet::filled(1);
+    println!("a default packet holds {} bytes", small.len());
+
+    let mut ring = Ring::<3>::new();
+    for letter in "abcde".chars() {
+        ring.push(letter);
+    }
+    println!("ring of 3 after a..e: {}", ring.contents());
+
+    let twice = doubled([1, 2, 3]);
+    println!("doubled: {:?}", twice.as_flattened());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let empty = Ring::<0>::new();
+    // error[E0080]: evaluation panicked: a ring needs room for at least one value
+}
+
+/*
+ * Key Concepts:
+ * - Const arguments can be literals, const items, or {const fn calls}
+ * - Const parameters can have defaults: struct Packet<const N: usize = 8>
+ * - const { assert!(...) } rejects bad sizes at compile time
+ * - Arithmetic on a generic N in a type ([T; N * 2]) isn't stable yet
+ */
+
+// EXPECTED:
+// key is 32 bytes
+// a page packet holds 4096 bytes
+// a default packet holds 8 bytes
+// ring of 3 after a..e: dec
+// doubled: [1, 2, 3, 1, 2, 3]
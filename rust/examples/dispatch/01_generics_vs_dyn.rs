@@ -0,0 +1,130 @@
+// Dispatch 1: Generics vs dyn Trait
+// Demonstrates the same function written with a generic bound and with trait objects
+//
+// Concepts: static dispatch, dynamic dispatch, monomorphization, vtables, dyn compatibility
+// Difficulty: advanced
+// Minutes: 15
+//
+// There are two ways to write a function that works with any Shape.
+// `fn total_area<S: Shape>(shapes: &[S])` is generic: the compiler
+// writes a separate copy of it for every type it is called with, a
+// total_area for Circle and another for Square, each calling that type's
+// area() directly. That is monomorphization, and it is called static
+// dispatch because which area() runs is settled at compile time.
+//
+// `fn total_area_dyn(shapes: &[Box<dyn Shape>])` takes trait objects.
+// There is one copy of it, and it doesn't know what it is adding up: each
+// Box<dyn Shape> is a pointer to the shape and a pointer to its type's
+// vtable, a table of its methods, and every area() call is looked up
+// there while the program runs. That is dynamic dispatch.
+//
+// Both give the same answer; `shapes/lib.rs` in this chapter has both,
+// and this example runs them side by side. What they cost is different,
+// and the next two examples measure it: the generic version is usually
+// faster, because the call can be inlined, and the dyn version is usually
+// smaller, because there is only one of it. And only trait objects can
+// keep circles and squares in one Vec.
+
+use std::any::type_name;
+use std::mem::size_of;
+
+#[cfg(feature = "broken")]
+use shapes::Circle;
+use shapes::{Shape, Square};
+
+use learn_rust_core::section;
+
+/// Generic, like `shapes::total_area`: one copy per `S`, and each copy
+/// knows which `S` it is.
+fn describe<S: Shape>(shapes: &[S]) -> String {
+    format!(
+        "the copy for {}, given {} shapes",
+        type_name::<S>(),
+        shapes.len()
+    )
+}
+
+/// How many machine words a `T` takes.
+fn words<T>() -> usize {
+    size_of::<T>() / size_of::<usize>()
+}
+
+fn main() {
+    section("the same answer");
+    let squares = shapes::squares(10);
+    let boxed = shapes::boxed(&squares);
+    let borrowed = shapes::borrowed(&squares);
+    println!("{:<16}{}", "generic", shapes::total_area(&squares));
+    println!("{:<16}{}", "Box<dyn Shape>", shapes::total_area_dyn(&boxed));
+    println!(
+        "{:<16}{}",
+        "&dyn Shape",
+        shapes::total_area_dyn_ref(&borrowed)
+    );
+
+    section("one copy per type");
+    // Each call below runs a different function, written by the compiler
+    println!("{}", describe(&squares));
+    println!("{}", describe(&[shapes::Circle(1.0), shapes::Circle(2.0)]));
+    let triangle = shapes::Triangle {
+        base: 2.0,
+        height: 3.0,
+    };
+    println!("{}", describe(&[triangle; 3]));
+
+    section("fat pointers");
+    println!("{:<16}{} word", "&Square", words::<&Square>());
+    println!(
+        "{:<16}{} words: the shape, and its vtable",
+        "&dyn Shape",
+        words::<&dyn Shape>()
+    );
+    println!(
+        "{:<16}{} words",
+        "Box<dyn Shape>",
+        words::<Box<dyn Shape>>()
+    );
+
+    section("different types together");
+    let mixed = shapes::mixed(6);
+    let names: Vec<&str> = mixed.iter().map(|shape| shape.name()).collect();
+    println!("{}", names.join(", "));
+    println!("total area: {:.2}", shapes::total_area_dyn(&mixed));
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let total = shapes::total_area(&[Circle(1.0), Square(1.0)]);
+    // error[E0308]: mismatched types (expected `Circle`, found `Square`)
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let copies: Vec<Box<dyn Clone>> = Vec::new();
+    // error[E0038]: the trait `Clone` is not dyn compatible
+}
+
+/*
+ * Key Concepts:
+ * - A generic function is compiled once per type it is used with (monomorphization)
+ * - Static dispatch: the call is known at compile time, so it can be inlined
+ * - A trait object is a fat pointer: the data, and a vtable of its type's methods
+ * - Dynamic dispatch: one copy of the code, and a lookup through the vtable per call
+ * - Only trait objects (or an enum) can hold different types in one collection
+ * - A trait with generic methods, or methods returning Self, can't be a dyn Trait
+ */
+
+// EXPECTED:
+// -- the same answer --
+// generic         385
+// Box<dyn Shape>  385
+// &dyn Shape      385
+// -- one copy per type --
+// the copy for shapes::Square, given 10 shapes
+// the copy for shapes::Circle, given 2 shapes
+// the copy for shapes::Triangle, given 3 shapes
+// -- fat pointers --
+// &Square         1 word
+// &dyn Shape      2 words: the shape, and its vtable
+// Box<dyn Shape>  2 words
+// -- different types together --
+// circle, square, triangle, circle, square, triangle
+// total area: 91.41
@@ -0,0 +1,107 @@
+// Dispatch 2: What Dynamic Dispatch Costs at Run Time
+// Demonstrates benchmarking generics, dyn Trait, and an enum doing the same work
+//
+// Concepts: inlining, vtable calls, enum dispatch, criterion, benchmark groups
+// Difficulty: advanced
+// Minutes: 10
+//
+// "Generics are faster than dyn" is advice everyone has heard; this
+// chapter's benchmark puts a number on it. `benches/dispatch.rs` sums the
+// areas of 100 and then 10,000 squares three ways: with the generic
+// total_area::<Square>, with &dyn Shape references to the same squares,
+// and with a Vec<Box<dyn Shape>>. Then it sums 10,000 shapes of three
+// kinds, as boxed trait objects and as an enum. Run it with
+//
+//     cargo bench -p dispatch-examples --bench dispatch
+//     cargo run -p tutor -- bench total_area
+//
+// On the machine that measured `baselines.json`, it came out like this:
+//
+//     total_area/generic/100        22.7 ns
+//     total_area/dyn_ref/100       390.3 ns
+//     total_area/dyn_box/100       389.5 ns
+//     total_area/generic/10000      4.3 µs
+//     total_area/dyn_ref/10000     45.6 µs
+//     total_area/dyn_box/10000     46.0 µs
+//     mixed/dyn_box                45.5 µs
+//     mixed/enum                    4.5 µs
+//
+// Ten to seventeen times slower, for the same arithmetic. The vtable
+// lookup itself is cheap. What costs is what it prevents: in the generic
+// copy, area() is inlined into the loop, which becomes a few instructions
+// per square that the optimizer can unroll and schedule as it likes.
+// Through a vtable, every square is a real function call, with its
+// arguments and return, and nothing the optimizer can see through.
+//
+// The boxes barely matter here, because boxes allocated one after another
+// sit next to each other in memory; in a long-running program, where
+// they end up scattered, following each pointer can cost more than the
+// call. And the enum is as fast as the generic version: a match on a
+// closed set of types is inlined like any other code, so when you know
+// every type up front, an enum beats a trait object.
+//
+// Ten times a few nanoseconds is still only nanoseconds. It matters in a
+// hot loop over millions of items; it doesn't for a handful of plugins
+// called once per request, where dyn's flexibility, and its smaller code
+// (see the next example), can be worth much more.
+
+use learn_rust_core::section;
+
+const COUNT: usize = 10_000;
+
+fn main() {
+    section("one type: 10000 squares");
+    let squares = shapes::squares(COUNT);
+    println!("{:<8}{}", "generic", shapes::total_area(&squares));
+    println!(
+        "{:<8}{}",
+        "dyn_ref",
+        shapes::total_area_dyn_ref(&shapes::borrowed(&squares))
+    );
+    println!(
+        "{:<8}{}",
+        "dyn_box",
+        shapes::total_area_dyn(&shapes::boxed(&squares))
+    );
+
+    section("three types: 10000 shapes");
+    let boxed = shapes::mixed(COUNT);
+    let enums = shapes::mixed_enum(COUNT);
+    println!("{:<8}{:.2}", "dyn_box", shapes::total_area_dyn(&boxed));
+    // The generic function again, with S = AnyShape: static dispatch
+    // into the enum, and a match inside it
+    println!("{:<8}{:.2}", "enum", shapes::total_area(&enums));
+
+    section("what is in a Vec");
+    println!(
+        "{:<8}{} bytes per shape, in the Vec",
+        "enum",
+        std::mem::size_of::<shapes::AnyShape>()
+    );
+    println!(
+        "{:<8}{} bytes per shape in the Vec, plus the box",
+        "dyn_box",
+        std::mem::size_of::<Box<dyn shapes::Shape>>()
+    );
+}
+
+/*
+ * Key Concepts:
+ * - The cost of dyn is mostly the inlining it prevents, not the vtable lookup
+ * - Generic and enum dispatch let the optimizer see, and rearrange, the called code
+ * - Boxed trait objects add a pointer to follow, and an allocation, per item
+ * - An enum is the fastest way to mix a closed set of types
+ * - Measure in the loop that matters; elsewhere the difference is noise
+ */
+
+// EXPECTED:
+// -- one type: 10000 squares --
+// generic 385000
+// dyn_ref 385000
+// dyn_box 385000
+// -- three types: 10000 shapes --
+// dyn_box 549920.39
+// enum    549920.39
+// -- what is in a Vec --
+// enum    24 bytes per shape, in the Vec
+// dyn_box 16 bytes per shape in the Vec, plus the box
@@ -0,0 +1,196 @@
+// Dispatch 3: What Monomorphization Costs in Code Size
+// Demonstrates measuring the machine code generics and dyn Trait compile to
+//
+// Concepts: monomorphization, code bloat, object files, LLVM IR, rustc --emit
+// Difficulty: advanced
+// Minutes: 15
+//
+// The price of a generic function is paid in code: every type it is used
+// with gets its own copy, optimized for that type. A few copies are
+// nothing, but a big generic function used with many types, in a big
+// program, adds up, both in the size of the binary and in how long it
+// takes to compile. A dyn version is compiled once however many types
+// use it; each type adds only a small vtable.
+//
+// This example is a small harness that measures it. It writes two
+// versions of the same program, one with `fn report<S: Shape>(&[S])` and
+// one with `fn report(&[&dyn Shape])`, each using 1, 4, and then 16
+// shape types, and compiles each with rustc, optimized, the way
+// `cargo build --release` would:
+//
+//     rustc --crate-type=lib --emit=obj,llvm-ir -C opt-level=3 generic_16.rs
+//
+// It then counts the copies of report() in the LLVM IR (the code rustc
+// hands to the optimizer's back end, one `define` per function) and
+// measures the object file, the machine code before it is linked with
+// the standard library. A real binary is much bigger, mostly std, but
+// the difference between the two versions is the same.
+//
+// The sizes depend on the compiler version and the target, so what you
+// see won't match these numbers exactly; the shape of the table will:
+//
+//     types  copies (generic, dyn)  object size (generic, dyn)
+//         1          1        1         15.4 KB    16.1 KB
+//         4          4        1         42.6 KB    19.4 KB
+//        16         16        1        152.4 KB    33.2 KB
+//
+// With one type the two are about the same. After that every generic
+// copy costs as much again, and not only for report() itself: each copy
+// sorts with its own closure, so each brings its own copy of the sort
+// too. Each dyn type adds its area() and a vtable. report() is marked
+// #[inline(never)] so that its copies stay separate functions we can
+// count; without it the optimizer might inline them into their callers,
+// which is more code again, not less.
+//
+// When the copies do add up, the usual fix keeps the generic signature
+// for its convenience and moves the work into a non-generic function:
+// `fn report<S: Shape>(shapes: &[S])` collects the areas and calls
+// `fn report_areas(areas: Vec<f64>)`, and only the small wrapper is
+// copied. std does the same in places, like fs::read::<P: AsRef<Path>>.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+/// How many shape types each pair of programs uses.
+const TYPE_COUNTS: [usize; 3] = [1, 4, 16];
+
+#[derive(Clone, Copy)]
+enum Dispatch {
+    Generic,
+    Dyn,
+}
+
+/// What one compiled program came to.
+struct Measurement {
+    /// `define`s of report() in the LLVM IR.
+    copies: usize,
+    /// Bytes in the object file.
+    size: u64,
+}
+
+/// A library with `types` shape types and a `report` function written
+/// the `dispatch` way, called once with shapes of each type.
+fn program(types: usize, dispatch: Dispatch) -> String {
+    let mut source = String::from("pub trait Shape {\n    fn area(&self) -> f64;\n}\n\n");
+    for i in 0..types {
+        let _ = writeln!(
+            source,
+            "pub struct Shape{i}(pub f64);\n\
+             impl Shape for Shape{i} {{\n    \
+                 fn area(&self) -> f64 {{ self.0 * {}.0 }}\n\
+             }}\n",
+            i + 1
+        );
+    }
+    let signature = match dispatch {
+        Dispatch::Generic => "pub fn report<S: Shape>(shapes: &[S]) -> String",
+        Dispatch::Dyn => "pub fn report(shapes: &[&dyn Shape]) -> String",
+    };
+    let _ = writeln!(
+        source,
+        "#[inline(never)]\n\
+         {signature} {{\n    \
+             let mut areas: Vec<f64> = shapes.iter().map(|shape| shape.area()).collect();\n    \
+             areas.sort_by(|a, b| a.total_cmp(b));\n    \
+             let total: f64 = areas.iter().sum();\n    \
+             let median = areas.get(areas.len() / 2).copied().unwrap_or(0.0);\n    \
+             format!(\"{{}} shapes, total {{total:.2}}, median {{median:.2}}\", areas.len())\n\
+         }}\n"
+    );
+    source.push_str("pub fn run() -> Vec<String> {\n    vec![\n");
+    for i in 0..types {
+        let call = match dispatch {
+            Dispatch::Generic => format!("report(&[Shape{i}(1.0), Shape{i}(2.0)])"),
+            Dispatch::Dyn => format!("report(&[&Shape{i}(1.0), &Shape{i}(2.0)])"),
+        };
+        let _ = writeln!(source, "        {call},");
+    }
+    source.push_str("    ]\n}\n");
+    source
+}
+
+/// Compiles `source` in `dir` and measures what came out.
+fn measure(dir: &Path, name: &str, source: &str) -> Result<Measurement, String> {
+    let path = dir.join(format!("{name}.rs"));
+    fs::write(&path, source)
+        .map_err(|error| format!("cannot write {}: {error}", path.display()))?;
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc)
+        .args([
+            "--edition",
+            "2021",
+            "--crate-type=lib",
+            "--emit=obj,llvm-ir",
+        ])
+        .args(["-C", "opt-level=3", "-C", "codegen-units=1"])
+        .arg("--out-dir")
+        .arg(dir)
+        .arg(&path)
+        .output()
+        .map_err(|error| format!("cannot run rustc: {error}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+
+    let ir = fs::read_to_string(dir.join(format!("{name}.ll")))
+        .map_err(|error| format!("rustc wrote no LLVM IR: {error}"))?;
+    let copies = ir
+        .lines()
+        .filter(|line| line.starts_with("define") && line.contains("report"))
+        .count();
+    let size = fs::metadata(dir.join(format!("{name}.o")))
+        .map_err(|error| format!("rustc wrote no object file: {error}"))?
+        .len();
+    Ok(Measurement { copies, size })
+}
+
+fn kilobytes(bytes: u64) -> String {
+    format!("{:.1} KB", bytes as f64 / 1024.0)
+}
+
+fn run(dir: &Path) -> Result<(), String> {
+    println!("types  copies (generic, dyn)  object size (generic, dyn)");
+    for types in TYPE_COUNTS {
+        let generic = measure(
+            dir,
+            &format!("generic_{types}"),
+            &program(types, Dispatch::Generic),
+        )?;
+        let dynamic = measure(dir, &format!("dyn_{types}"), &program(types, Dispatch::Dyn))?;
+        println!(
+            "{types:>5}  {:>9} {:>8}  {:>14} {:>10}",
+            generic.copies,
+            dynamic.copies,
+            kilobytes(generic.size),
+            kilobytes(dynamic.size)
+        );
+    }
+    Ok(())
+}
+
+fn main() {
+    let dir: PathBuf = env::temp_dir().join(format!("dispatch_sizes_{}", process::id()));
+    if let Err(error) = fs::create_dir_all(&dir) {
+        eprintln!("cannot create {}: {error}", dir.display());
+        process::exit(1);
+    }
+    let result = run(&dir);
+    let _ = fs::remove_dir_all(&dir);
+    if let Err(error) = result {
+        eprintln!("{error}");
+        process::exit(1);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Every type a generic function is used with gets its own optimized copy
+ * - More copies mean a bigger binary and a longer compile (code bloat)
+ * - A dyn Trait function is compiled once; each type adds only a vtable
+ * - rustc --emit=llvm-ir and --emit=obj show what the compiler produced
+ * - Default to generics; reach for dyn when the copies, or the types, multiply
+ * - A common trick: a small generic wrapper around a non-generic inner function
+ */
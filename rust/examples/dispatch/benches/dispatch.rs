@@ -0,0 +1,45 @@
+//! Summing areas with static and with dynamic dispatch. The generic copy
+//! for `Square` has `area()` inlined into its loop; the `dyn` versions
+//! make a call through the vtable for every shape, and with boxes they
+//! also follow a pointer to each shape on the heap.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+
+fn one_type(c: &mut Criterion) {
+    let mut group = c.benchmark_group("total_area");
+    for size in [100, 10_000] {
+        let squares = shapes::squares(size);
+        let borrowed = shapes::borrowed(&squares);
+        let boxed = shapes::boxed(&squares);
+        group.bench_with_input(BenchmarkId::new("generic", size), &squares, |b, squares| {
+            b.iter(|| shapes::total_area(black_box(squares)))
+        });
+        group.bench_with_input(BenchmarkId::new("dyn_ref", size), &borrowed, |b, shapes| {
+            b.iter(|| shapes::total_area_dyn_ref(black_box(shapes)))
+        });
+        group.bench_with_input(BenchmarkId::new("dyn_box", size), &boxed, |b, shapes| {
+            b.iter(|| shapes::total_area_dyn(black_box(shapes)))
+        });
+    }
+    group.finish();
+}
+
+fn mixed_types(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mixed");
+    let boxed = shapes::mixed(10_000);
+    let enums = shapes::mixed_enum(10_000);
+    group.bench_function("dyn_box", |b| {
+        b.iter(|| shapes::total_area_dyn(black_box(&boxed)))
+    });
+    group.bench_function("enum", |b| b.iter(|| shapes::total_area(black_box(&enums))));
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20).warm_up_time(Duration::from_millis(300)).measurement_time(Duration::from_secs(1));
+    targets = one_type, mixed_types
+}
+criterion_main!(benches);
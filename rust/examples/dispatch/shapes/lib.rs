@@ -0,0 +1,180 @@
+//! The code the dispatch chapter measures: three shapes, and one function
+//! written two ways. [`total_area`] is generic, so it is compiled again
+//! for every shape type it is called with; [`total_area_dyn`] takes trait
+//! objects, so there is one copy of it and every `area()` call goes
+//! through a vtable. [`AnyShape`], an enum, is a third way to mix shapes.
+//! The numbered examples show that every way gives the same answer, and
+//! `benches/` times them with criterion.
+//!
+//! ```
+//! use shapes::Square;
+//!
+//! let squares = [Square(2.0), Square(3.0)];
+//! assert_eq!(shapes::total_area(&squares), 13.0);
+//! assert_eq!(shapes::total_area_dyn(&shapes::boxed(&squares)), 13.0);
+//! ```
+
+use std::f64::consts::PI;
+
+pub trait Shape {
+    fn name(&self) -> &'static str;
+    fn area(&self) -> f64;
+}
+
+/// A circle with this radius.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle(pub f64);
+
+/// A square with sides this long.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Square(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub base: f64,
+    pub height: f64,
+}
+
+impl Shape for Circle {
+    fn name(&self) -> &'static str {
+        "circle"
+    }
+    fn area(&self) -> f64 {
+        PI * self.0 * self.0
+    }
+}
+
+impl Shape for Square {
+    fn name(&self) -> &'static str {
+        "square"
+    }
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+impl Shape for Triangle {
+    fn name(&self) -> &'static str {
+        "triangle"
+    }
+    fn area(&self) -> f64 {
+        self.base * self.height / 2.0
+    }
+}
+
+/// The other way to hold different shapes together: a closed set, known
+/// when the code is written. A `match` picks the method, instead of a
+/// vtable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnyShape {
+    Circle(Circle),
+    Square(Square),
+    Triangle(Triangle),
+}
+
+impl Shape for AnyShape {
+    fn name(&self) -> &'static str {
+        match self {
+            AnyShape::Circle(circle) => circle.name(),
+            AnyShape::Square(square) => square.name(),
+            AnyShape::Triangle(triangle) => triangle.name(),
+        }
+    }
+    fn area(&self) -> f64 {
+        match self {
+            AnyShape::Circle(circle) => circle.area(),
+            AnyShape::Square(square) => square.area(),
+            AnyShape::Triangle(triangle) => triangle.area(),
+        }
+    }
+}
+
+/// Static dispatch: each copy knows its `S`, so it calls `S::area`
+/// directly and the optimizer can inline it into the loop.
+pub fn total_area<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(Shape::area).sum()
+}
+
+/// Dynamic dispatch: one copy for every shape, which looks up `area` in
+/// each shape's vtable and calls it there. Each shape is a separate heap
+/// allocation, too.
+pub fn total_area_dyn(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+/// Dynamic dispatch without the boxes: the shapes stay where they are, so
+/// only the vtable call is left.
+pub fn total_area_dyn_ref(shapes: &[&dyn Shape]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+/// `count` squares with sides from 1 to 10, the same ones on every run.
+pub fn squares(count: usize) -> Vec<Square> {
+    (0..count).map(|i| Square((i % 10 + 1) as f64)).collect()
+}
+
+/// A copy of each shape in its own box, as trait objects.
+pub fn boxed<S: Shape + Clone + 'static>(shapes: &[S]) -> Vec<Box<dyn Shape>> {
+    shapes
+        .iter()
+        .map(|shape| Box::new(shape.clone()) as Box<dyn Shape>)
+        .collect()
+}
+
+/// A trait object borrowing each shape.
+pub fn borrowed<S: Shape>(shapes: &[S]) -> Vec<&dyn Shape> {
+    shapes.iter().map(|shape| shape as &dyn Shape).collect()
+}
+
+/// `count` shapes of all three kinds in turn, as trait objects: a `Vec`
+/// of one type can't hold them.
+pub fn mixed(count: usize) -> Vec<Box<dyn Shape>> {
+    mixed_enum(count)
+        .into_iter()
+        .map(|shape| match shape {
+            AnyShape::Circle(circle) => Box::new(circle) as Box<dyn Shape>,
+            AnyShape::Square(square) => Box::new(square),
+            AnyShape::Triangle(triangle) => Box::new(triangle),
+        })
+        .collect()
+}
+
+/// The same shapes as [`mixed`], as an enum.
+pub fn mixed_enum(count: usize) -> Vec<AnyShape> {
+    (0..count)
+        .map(|i| {
+            let size = (i % 10 + 1) as f64;
+            match i % 3 {
+                0 => AnyShape::Circle(Circle(size)),
+                1 => AnyShape::Square(Square(size)),
+                _ => AnyShape::Triangle(Triangle {
+                    base: size,
+                    height: 2.0,
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_version_gives_the_same_total() {
+        let squares = squares(1_000);
+        let total = total_area(&squares);
+        assert_eq!(total, 38_500.0);
+        assert_eq!(total_area_dyn(&boxed(&squares)), total);
+        assert_eq!(total_area_dyn_ref(&borrowed(&squares)), total);
+    }
+
+    #[test]
+    fn mixed_shapes_take_turns() {
+        let names: Vec<&str> = mixed(4).iter().map(|shape| shape.name()).collect();
+        assert_eq!(names, ["circle", "square", "triangle", "circle"]);
+        let expected = PI + 4.0 + 3.0 + PI * 16.0;
+        assert!((total_area_dyn(&mixed(4)) - expected).abs() < 1e-9);
+        assert_eq!(total_area(&mixed_enum(4)), total_area_dyn(&mixed(4)));
+    }
+}
@@ -0,0 +1,146 @@
+// Docs 1: Doc Comments
+// Demonstrates writing documentation with `///` and `//!`, and that a doc comment is an attribute
+//
+// Concepts: doc comments, outer and inner docs, #[doc] attributes, Markdown in docs, # Examples and # Errors sections
+// Difficulty: beginner
+// Minutes: 15
+//
+// Rust has two kinds of comment. `//` is for whoever reads the code, and
+// the compiler throws it away. `///` is documentation, for whoever uses
+// the code, and the compiler keeps it: `/// Adds one.` above a function
+// is another way to write the attribute `#[doc = " Adds one."]`, which
+// rustdoc reads to build the function's page. The first section below
+// shows this by handing a documented function to a macro that only
+// accepts `#[doc]` attributes, and gets the comments back as strings.
+//
+// `///` documents the item after it. `//!` documents the item it is
+// inside, which in practice means the module or crate whose file it
+// starts: it becomes an inner attribute, `#![doc = "..."]`. The shared
+// library's src/lib.rs opens with `//!` lines describing the crate, and
+// each of its functions and types has `///` lines above it.
+//
+// The text is Markdown. The first paragraph is the summary, the one
+// sentence shown in lists of items, so it should stand alone. Headings
+// mark the sections readers look for: `# Examples`, `# Errors` for what
+// a `Result` can fail with, `# Panics`, and `# Safety` for an unsafe
+// function's contract. The last section reads the shared library's doc
+// comments out of its source, the way rustdoc finds them, and lists
+// which sections each has.
+//
+//     cargo run -p tutor -- docs
+//
+// builds the shared library's docs and opens them in a browser, so you
+// can compare each comment here with the page it turns into.
+
+use doc_reader::{doc_comments, headings};
+use learn_rust_core::section;
+
+/// Defines the function it is given and hands back its docs, one string
+/// per line: the function's doc comment arrives as `#[doc = "..."]`
+/// attributes.
+macro_rules! documented {
+    ($(#[doc = $doc:literal])+ fn $($function:tt)+) => {{
+        #[allow(dead_code)]
+        fn $($function)+
+        [$($doc),+]
+    }};
+}
+
+/// The shared library's source, by file.
+const CORE: [(&str, &str); 4] = [
+    ("lib.rs", include_str!("../../core/src/lib.rs")),
+    ("input.rs", include_str!("../../core/src/input.rs")),
+    ("output.rs", include_str!("../../core/src/output.rs")),
+    ("timing.rs", include_str!("../../core/src/timing.rs")),
+];
+
+fn main() {
+    section("a doc comment is an attribute");
+    let docs = documented! {
+        /// Adds one, wrapping around at `u8::MAX`.
+        ///
+        /// The summary is the first paragraph.
+        fn inc(x: u8) -> u8 {
+            x.wrapping_add(1)
+        }
+    };
+    for doc in docs {
+        println!("#[doc = {doc:?}]");
+    }
+
+    section("the shared library's doc comments");
+    for (file, source) in CORE {
+        for comment in doc_comments(source) {
+            let documents = if comment.inner {
+                "the module".to_string()
+            } else {
+                comment.item.trim_end_matches(" {").to_string()
+            };
+            let summary = comment.text.split("\n\n").next().unwrap_or_default();
+            let sections = headings(&comment.text);
+            println!(
+                "{file}:{} {} {documents}",
+                comment.line,
+                if comment.inner { "//!" } else { "///" }
+            );
+            println!("    {}", summary.replace('\n', " "));
+            if !sections.is_empty() {
+                println!("    sections: {}", sections.join(", "));
+            }
+        }
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let docs = documented! {
+        fn undocumented() {}
+    };
+    // error: no rules expected keyword `fn`
+}
+
+/*
+ * Key Concepts:
+ * - `//` comments are for the code's readers; `///` and `//!` are docs for its users
+ * - `///` documents the next item, `//!` the module or crate it is inside
+ * - A doc comment is sugar for a #[doc = "..."] attribute, which is what rustdoc reads
+ * - Docs are Markdown: a one-paragraph summary first, then sections
+ *   like # Examples, # Errors, # Panics, and # Safety
+ */
+
+// EXPECTED:
+// -- a doc comment is an attribute --
+// #[doc = " Adds one, wrapping around at `u8::MAX`."]
+// #[doc = ""]
+// #[doc = " The summary is the first paragraph."]
+// -- the shared library's doc comments --
+// lib.rs:1 //! the module
+//     Helpers the chapter packages share, so that each example can get on with its topic instead of repeating the same scaffolding:
+// lib.rs:42 /// macro_rules! demo
+//     Prints the source of each expression and its value (its `Debug` form) to stdout, then hands the value back, like `dbg!` does on stderr:
+// input.rs:1 //! the module
+//     Reading what the user types.
+// input.rs:5 /// pub fn prompt(question: &str) -> io::Result<String>
+//     Prints `question`, waits for a line from stdin, and returns it without the line ending.
+//     sections: Errors
+// input.rs:17 /// pub fn prompt_from(
+//     [`prompt`], reading from `input` and writing to `output`, so that it can be tested, or read from a file.
+// input.rs:36 /// pub fn read_line() -> io::Result<Option<String>>
+//     The next line of stdin without its line ending, or `None` at the end.
+// output.rs:1 //! the module
+//     Section headers.
+// output.rs:6 /// const COLOR: (&str, &str) = ("\x1b[1;36m", "\x1b[0m");
+//     Bold cyan, then back to normal.
+// output.rs:9 /// pub fn section(title: &str)
+//     Prints `-- title --`, in color when stdout is a terminal.
+// output.rs:19 /// pub fn section_header(title: &str, color: bool) -> String
+//     The line [`section`] prints.
+// timing.rs:1 //! the module
+//     Timing a piece of code.
+// timing.rs:5 /// pub struct Timer
+//     Measures from when it's created until it's dropped, then prints how long that was:
+// timing.rs:26 /// pub fn start(label: &str) -> Self
+//     Starts timing now. `label` names what is being timed in the line printed when the timer is dropped.
+// timing.rs:35 /// pub fn elapsed(&self) -> Duration
+//     How long it has been running so far.
+// timing.rs:47 /// pub fn timed<T>(label: &str, work: impl FnOnce() -> T) -> T
+//     Runs `work`, prints how long it took, and returns its result.
@@ -0,0 +1,146 @@
+// Docs 2: Intra-Doc Links
+// Demonstrates linking from one item's docs to another by its Rust path, and what rustdoc does with a link that leads nowhere
+//
+// Concepts: intra-doc links, link resolution, disambiguators, rustdoc::broken_intra_doc_links, rustdoc lints
+// Difficulty: beginner
+// Minutes: 15
+//
+// Docs link to other items all the time: "returns a [`Timer`]", "like
+// [`prompt`], but...". Markdown links need a URL, and the URL of an
+// item's page is rustdoc's business, so rustdoc lets you write the item's
+// path instead:
+//
+//     /// Returns a [`Timer`] that has already started.
+//     /// See [`Timer::elapsed`], [`demo!`], and [its docs](Timer::start).
+//
+// The square brackets make a link; the backticks inside only make it
+// look like code. Rustdoc looks the path up the way the compiler would
+// at the item being documented: whatever is in scope there, the crate's
+// own items and anything the file `use`s, is a valid target. The shared
+// library's input.rs links to [`io::ErrorKind::UnexpectedEof`], which
+// works because that file has `use std::io`.
+//
+// When a name could mean two things, say the function `section` and a
+// module also called `section`, a disambiguator picks one: `fn@section`,
+// `mod@section`, `struct@Timer`, `macro@demo`. A trailing `()` also
+// means a function, and a trailing `!` a macro.
+//
+// A link whose path resolves to nothing is a warning, from the rustdoc
+// lint `rustdoc::broken_intra_doc_links`:
+//
+//     warning: unresolved link to `Stopwatch`
+//
+// It is only a warning, and links go stale when items are renamed, so
+// `tutor docs` builds with warnings as errors, and `cargo xtask verify`
+// runs it. This example looks every link in the shared library up the
+// way rustdoc does, then does the same for a draft doc comment with a
+// stale link in it.
+
+use std::collections::BTreeSet;
+
+use doc_reader::{doc_comments, links, public_items};
+use learn_rust_core::section;
+
+/// The shared library's source, by file.
+const CORE: [(&str, &str); 4] = [
+    ("lib.rs", include_str!("../../core/src/lib.rs")),
+    ("input.rs", include_str!("../../core/src/input.rs")),
+    ("output.rs", include_str!("../../core/src/output.rs")),
+    ("timing.rs", include_str!("../../core/src/timing.rs")),
+];
+
+/// A doc comment for a new function in the shared library, written
+/// before `Stopwatch` was renamed `Timer`.
+const DRAFT: &str = "\
+/// Like [`timed`], but keeps a [`Stopwatch`] running between calls to
+/// it: see [`Timer::elapsed()`], and [the header it prints](fn@section),
+/// and the [Rust book](https://doc.rust-lang.org/book/).
+pub fn lap() {}
+";
+
+/// Where rustdoc would find `target`, linked from docs in `source`:
+/// among the crate's own items in `names`, or through one of the file's
+/// `use` declarations. `None` if it finds nothing.
+fn resolve(target: &str, source: &str, names: &BTreeSet<String>) -> Option<String> {
+    let (said, path) = match target.split_once('@') {
+        Some((kind, path)) => (format!(", marked {kind}@ so only a {kind} will do"), path),
+        None if target.ends_with("()") => {
+            (", marked () so only a function will do".to_string(), target)
+        }
+        None => (String::new(), target),
+    };
+    let path = path.trim_end_matches("()");
+    let first = path.split("::").next().unwrap_or(path);
+    if names.contains(first) {
+        return Some(format!("this crate's `{first}`{said}"));
+    }
+    source
+        .lines()
+        .find(|line| {
+            line.starts_with("use ")
+                && line
+                    .split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .any(|word| word == first)
+        })
+        .map(|line| format!("through `{}`{said}", line.trim_end_matches(';')))
+}
+
+fn main() {
+    // The names a link can start with: everything the crate makes public
+    let mut names = BTreeSet::new();
+    for (_, source) in CORE {
+        for (item, _) in public_items(source) {
+            let (_, name) = item.split_once(' ').unwrap_or_default();
+            names.insert(name.to_string());
+        }
+    }
+
+    section("the shared library's links");
+    for (file, source) in CORE {
+        for comment in doc_comments(source) {
+            for target in links(&comment.text) {
+                match resolve(&target, source, &names) {
+                    Some(found) => println!("{file}:{} [`{target}`] -> {found}", comment.line),
+                    None => println!("{file}:{} [`{target}`] -> nothing", comment.line),
+                }
+            }
+        }
+    }
+
+    section("a draft with a stale link");
+    let draft = &doc_comments(DRAFT)[0];
+    for target in links(&draft.text) {
+        match resolve(&target, include_str!("../../core/src/lib.rs"), &names) {
+            Some(found) => println!("[`{target}`] -> {found}"),
+            None => println!("warning: unresolved link to `{target}`"),
+        }
+    }
+    println!("(the book's URL is an ordinary Markdown link: rustdoc leaves it alone)");
+}
+
+/*
+ * Key Concepts:
+ * - [`Name`] in docs links to the item, found by path the way the compiler would find it
+ * - Anything in scope where the docs are can be linked, including what the file `use`s
+ * - Disambiguators like fn@, struct@, and mod@ pick between items with the same name
+ * - rustdoc::broken_intra_doc_links warns about links that lead nowhere;
+ *   build docs with warnings as errors to keep links from going stale
+ */
+
+// EXPECTED:
+// -- the shared library's links --
+// lib.rs:1 [`section`] -> this crate's `section`
+// lib.rs:1 [`demo!`] -> this crate's `demo!`
+// lib.rs:1 [`Timer`] -> this crate's `Timer`
+// lib.rs:1 [`timed`] -> this crate's `timed`
+// lib.rs:1 [`prompt`] -> this crate's `prompt`
+// lib.rs:1 [`read_line`] -> this crate's `read_line`
+// input.rs:5 [`io::ErrorKind::UnexpectedEof`] -> through `use std::io::{self, BufRead, Write}`
+// input.rs:17 [`prompt`] -> this crate's `prompt`
+// output.rs:19 [`section`] -> this crate's `section`
+// -- a draft with a stale link --
+// [`timed`] -> this crate's `timed`
+// warning: unresolved link to `Stopwatch`
+// [`Timer::elapsed()`] -> this crate's `Timer`, marked () so only a function will do
+// [`fn@section`] -> this crate's `section`, marked fn@ so only a fn will do
+// (the book's URL is an ordinary Markdown link: rustdoc leaves it alone)
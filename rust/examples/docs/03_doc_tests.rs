@@ -0,0 +1,114 @@
+// Docs 3: Doc Tests
+// Demonstrates examples in docs that run as tests, hidden setup lines, and the attributes that say how to test a block
+//
+// Concepts: doc tests, cargo test --doc, hidden lines, no_run, should_panic, compile_fail, ignore, text blocks
+// Difficulty: intermediate
+// Minutes: 20
+//
+// An example in the docs is the first thing most readers copy, so it had
+// better work. Rustdoc makes sure it does: every code block in a doc
+// comment is Rust unless it says otherwise, and
+//
+//     cargo test --doc -p learn-rust-core
+//
+// compiles and runs each one as a little program of its own, one test
+// per block. A block that panics, an `assert_eq!` that fails, fails its
+// test. `cargo test` runs the doc tests along with the rest.
+//
+// A doc test is outside the crate, like any user of it, so it must `use`
+// what it calls. That line is noise on the page, so a line that starts
+// with `# ` is compiled but not shown. The examples on the shared
+// library's items hide their `use` lines, and the asserts that check
+// what their comments say. Rustdoc also wraps the block in
+// `fn main() { ... }` unless it writes its own main: the last section
+// prints the program a test really compiles.
+//
+// The words after the opening fence say how to treat the block:
+//
+//     ```text          not Rust at all: shown, never compiled
+//     ```no_run        compiled but not run (it deletes files, say)
+//     ```should_panic  run, and passes only if it panics
+//     ```compile_fail  passes only if it fails to compile
+//     ```ignore        neither compiled nor run; best avoided
+//
+// The shared library uses `text` to show what an example prints, which
+// rustdoc can't check. This example finds every block in the shared
+// library's docs and says what `cargo test --doc` does with it.
+
+use doc_reader::{code_blocks, doc_comments};
+use learn_rust_core::section;
+
+/// The shared library's source, by file.
+const CORE: [(&str, &str); 4] = [
+    ("lib.rs", include_str!("../../core/src/lib.rs")),
+    ("input.rs", include_str!("../../core/src/input.rs")),
+    ("output.rs", include_str!("../../core/src/output.rs")),
+    ("timing.rs", include_str!("../../core/src/timing.rs")),
+];
+
+fn main() {
+    section("the code blocks in the shared library's docs");
+    let mut tests = 0;
+    let mut demo = None;
+    for (file, source) in CORE {
+        for comment in doc_comments(source) {
+            for block in code_blocks(&comment.text) {
+                let fence = match block.attributes.join(",") {
+                    attributes if attributes.is_empty() => "```".to_string(),
+                    attributes => format!("```{attributes}"),
+                };
+                let hidden = block.lines.len() - block.shown().len();
+                let verdict = if block.is_tested() {
+                    tests += 1;
+                    format!("a doc test, {hidden} of {} lines hidden", block.lines.len())
+                } else {
+                    "shown, not tested".to_string()
+                };
+                let location = format!("{file}:{}", comment.line);
+                println!("{location:<12} {fence:<8} {verdict}");
+                if comment.item.contains("macro_rules! demo") {
+                    demo = Some(block);
+                }
+            }
+        }
+    }
+    println!("{tests} doc tests, as `cargo test --doc -p learn-rust-core` counts them");
+
+    let Some(demo) = demo else {
+        return;
+    };
+    section("demo!'s example, as the page shows it");
+    for line in demo.shown() {
+        println!("{line}");
+    }
+    section("and as the test compiles it");
+    print!("{}", demo.test_program());
+}
+
+/*
+ * Key Concepts:
+ * - Code blocks in docs are Rust by default, and cargo test --doc runs each one
+ * - A doc test is a separate program using the crate from outside: it needs its `use`s
+ * - Lines starting with `# ` are compiled but hidden from the page
+ * - text, no_run, should_panic, compile_fail, and ignore say how a block is tested
+ * - Examples that are tested stay correct as the code changes
+ */
+
+// EXPECTED:
+// -- the code blocks in the shared library's docs --
+// lib.rs:1     ```      a doc test, 0 of 5 lines hidden
+// lib.rs:1     ```text  shown, not tested
+// lib.rs:42    ```      a doc test, 2 of 4 lines hidden
+// timing.rs:5  ```      a doc test, 1 of 6 lines hidden
+// timing.rs:47 ```      a doc test, 1 of 3 lines hidden
+// 4 doc tests, as `cargo test --doc -p learn-rust-core` counts them
+// -- demo!'s example, as the page shows it --
+// let words = demo!("a b c".split(' ').count()); // "a b c".split(' ').count() = 3
+// let (a, b) = demo!(u8::MAX, i8::MIN); // u8::MAX = 255, then i8::MIN = -128
+// -- and as the test compiles it --
+// fn main() {
+//     use learn_rust_core::demo;
+//     let words = demo!("a b c".split(' ').count()); // "a b c".split(' ').count() = 3
+//     let (a, b) = demo!(u8::MAX, i8::MIN); // u8::MAX = 255, then i8::MIN = -128
+//     assert_eq!((words, a, b), (3, 255, -128));
+// }
@@ -0,0 +1,132 @@
+// Docs 4: Missing Docs
+// Demonstrates making the compiler insist that every public item is documented with #![deny(missing_docs)]
+//
+// Concepts: missing_docs, lint levels, crate-level docs, public API, #[allow] on one item
+// Difficulty: beginner
+// Minutes: 10
+//
+// Documentation decays the way tests do: a new function goes in without
+// any, and nobody notices. The `missing_docs` lint notices. It is off by
+// default; switched on, it reports every public item without docs: the
+// crate itself, modules, functions, types, their public fields and
+// variants, and exported macros. Private items are the crate's own
+// business and need none.
+//
+//     #![deny(missing_docs)]
+//
+// at the top of lib.rs makes a missing doc a compile error, and the
+// shared library does just that. `#![warn(missing_docs)]` only warns,
+// which suits a crate catching up. One item can opt out with
+// `#[allow(missing_docs)]` above it, which at least says so on purpose.
+//
+// This file denies it too, which is why it opens with `//!` docs for the
+// crate and why its `shapes` module and everything public in it has docs.
+// The program lists the public items in the shared library and in this
+// file, and whether each has a doc comment, which is roughly the check
+// the lint makes. The undocumented function at the bottom only compiles
+// with the `broken` feature, and then doesn't. The listing counts it
+// anyway: reading the source, it can't tell what a `cfg` switches off.
+//
+//     cargo run -p tutor -- run docs/04_missing_docs --broken
+
+//! The docs chapter's last example: a binary crate that documents all of
+//! its public items, because the lint below says it must.
+
+#![deny(missing_docs)]
+
+use doc_reader::public_items;
+use learn_rust_core::section;
+
+/// The shared library's source, by file.
+const CORE: [(&str, &str); 4] = [
+    ("lib.rs", include_str!("../../core/src/lib.rs")),
+    ("input.rs", include_str!("../../core/src/input.rs")),
+    ("output.rs", include_str!("../../core/src/output.rs")),
+    ("timing.rs", include_str!("../../core/src/timing.rs")),
+];
+
+/// Shapes with an area, documented down to their fields.
+pub mod shapes {
+    /// A circle, centered anywhere.
+    pub struct Circle {
+        /// The distance from the center to the edge.
+        pub radius: f64,
+    }
+
+    impl Circle {
+        /// The area inside the circle.
+        pub fn area(&self) -> f64 {
+            std::f64::consts::PI * self.radius * self.radius
+        }
+    }
+
+    // Private, so the lint leaves it alone
+    fn _unit() -> Circle {
+        Circle { radius: 1.0 }
+    }
+}
+
+fn main() {
+    let files = CORE
+        .into_iter()
+        .map(|(file, source)| (format!("core/src/{file}"), source))
+        .chain([(
+            "04_missing_docs.rs".to_string(),
+            include_str!("04_missing_docs.rs"),
+        )]);
+    for (file, source) in files {
+        section(&file);
+        let items = public_items(source);
+        for (item, documented) in &items {
+            let status = if *documented { "documented" } else { "MISSING" };
+            println!("{item:<24} {status}");
+        }
+        let documented = items.iter().filter(|(_, documented)| *documented).count();
+        println!("{documented} of {} documented", items.len());
+    }
+
+    let circle = shapes::Circle { radius: 2.0 };
+    println!("\na circle of radius 2 has area {:.2}", circle.area());
+}
+
+// This would cause an error:
+#[cfg(feature = "broken")]
+pub fn undocumented() {}
+// error: missing documentation for a function
+
+/*
+ * Key Concepts:
+ * - #![deny(missing_docs)] makes every undocumented public item a compile error
+ * - It covers the crate, modules, functions, types, public fields, variants, and exported macros
+ * - Private items need no docs; #[allow(missing_docs)] excuses one item on purpose
+ * - #![warn(missing_docs)] is the gentler start for a crate catching up
+ */
+
+// EXPECTED:
+// -- core/src/lib.rs --
+// macro demo!              documented
+// 1 of 1 documented
+// -- core/src/input.rs --
+// fn prompt                documented
+// fn prompt_from           documented
+// fn read_line             documented
+// 3 of 3 documented
+// -- core/src/output.rs --
+// fn section               documented
+// fn section_header        documented
+// 2 of 2 documented
+// -- core/src/timing.rs --
+// struct Timer             documented
+// fn start                 documented
+// fn elapsed               documented
+// fn timed                 documented
+// 4 of 4 documented
+// -- 04_missing_docs.rs --
+// mod shapes               documented
+// struct Circle            documented
+// field radius             documented
+// fn area                  documented
+// fn undocumented          MISSING
+// 4 of 5 documented
+//
+// a circle of radius 2 has area 12.57
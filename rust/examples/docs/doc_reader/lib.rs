@@ -0,0 +1,454 @@
+//! Reading the documentation out of Rust source, the way rustdoc starts:
+//! find the doc comments, then the links and code blocks in their
+//! Markdown. The docs chapter runs it over the source of
+//! `learn_rust_core`, the library the chapter packages share.
+//!
+//! A doc comment is an attribute. The compiler turns `/// Adds one.` into
+//! `#[doc = " Adds one."]` on the item after it, and `//! ...` into
+//! `#![doc = "..."]` on the module it's in, and rustdoc reads the
+//! attributes. This crate reads the comments instead, which comes to the
+//! same thing for source that writes its docs as comments:
+//!
+//! ```
+//! let source = "/// Adds one, like [`i32::checked_add`] without the check.\n\
+//!               pub fn inc(x: i32) -> i32 { x + 1 }\n";
+//! let docs = doc_reader::doc_comments(source);
+//! assert_eq!(docs[0].item, "pub fn inc(x: i32) -> i32 { x + 1 }");
+//! assert_eq!(doc_reader::links(&docs[0].text), ["i32::checked_add"]);
+//! ```
+//!
+//! It is documented the way the chapter teaches, `#![deny(missing_docs)]`
+//! and all: `cargo run -p tutor -- docs docs-examples` shows the result.
+
+#![deny(missing_docs)]
+
+/// One doc comment: a run of `///` lines, or a run of `//!` lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocComment {
+    /// The line it starts on, counting from 1.
+    pub line: usize,
+    /// Whether it is `//!`, which documents the module it is in, rather
+    /// than `///`, which documents the item after it.
+    pub inner: bool,
+    /// The first line of the item it documents, after any attributes, or
+    /// nothing for an inner comment.
+    pub item: String,
+    /// The Markdown, with the `///` (and the space after it) taken off
+    /// each line.
+    pub text: String,
+}
+
+/// Every doc comment in `source`, in order.
+///
+/// `////` is not a doc comment: four slashes or more make an ordinary
+/// comment again.
+pub fn doc_comments(source: &str) -> Vec<DocComment> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut comments: Vec<DocComment> = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let Some((inner, _)) = doc_line(lines[index]) else {
+            index += 1;
+            continue;
+        };
+        let start = index;
+        let mut text = Vec::new();
+        while let Some((same, line)) = lines.get(index).and_then(|line| doc_line(line)) {
+            if same != inner {
+                break;
+            }
+            text.push(line);
+            index += 1;
+        }
+        let item = if inner {
+            String::new()
+        } else {
+            lines[index..]
+                .iter()
+                .map(|line| line.trim())
+                .find(|line| !line.starts_with("#[") && !line.is_empty())
+                .unwrap_or_default()
+                .to_string()
+        };
+        comments.push(DocComment {
+            line: start + 1,
+            inner,
+            item,
+            text: text.join("\n"),
+        });
+    }
+    comments
+}
+
+/// Whether `line` is a doc comment line (and if so, whether an inner
+/// one), and its text.
+fn doc_line(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    let (inner, rest) = if let Some(rest) = trimmed.strip_prefix("//!") {
+        (true, rest)
+    } else {
+        let rest = trimmed.strip_prefix("///")?;
+        if rest.starts_with('/') {
+            return None;
+        }
+        (false, rest)
+    };
+    Some((inner, rest.strip_prefix(' ').unwrap_or(rest)))
+}
+
+/// The headings in `markdown`, like `Examples` and `Errors`, in order.
+/// A `#` line in a code block is code, not a heading.
+///
+/// ```
+/// let markdown = "Reads a line.\n\n# Errors\n\n```\n# let x = 1;\n```";
+/// assert_eq!(doc_reader::headings(markdown), ["Errors"]);
+/// ```
+pub fn headings(markdown: &str) -> Vec<&str> {
+    let mut headings = Vec::new();
+    let mut in_block = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+        } else if !in_block {
+            if let Some(heading) = line.trim_start_matches('#').strip_prefix(' ') {
+                if line.starts_with('#') {
+                    headings.push(heading);
+                }
+            }
+        }
+    }
+    headings
+}
+
+/// The intra-doc links in `markdown`, in order: what ``[`Timer`]`` and
+/// `[the timer](Timer)` link to, when that is a path to an item rather
+/// than a URL.
+///
+/// Brackets in code are code, so code blocks and `` `inline code` `` are
+/// skipped, and so is text in brackets that couldn't be a path, like
+/// `[see below]`.
+///
+/// ```
+/// let links = doc_reader::links("Starts a [`Timer`]; see [its docs](Timer::start).");
+/// assert_eq!(links, ["Timer", "Timer::start"]);
+/// assert!(doc_reader::links("`&[u8]` and [a list](https://example.com)").is_empty());
+/// ```
+pub fn links(markdown: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut in_block = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+            continue;
+        }
+        if !in_block {
+            links_in_line(line, &mut links);
+        }
+    }
+    links
+}
+
+fn links_in_line(line: &str, links: &mut Vec<String>) {
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '`' => {
+                // Skip to the end of the code span
+                i += 1;
+                while i < chars.len() && chars[i] != '`' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            '[' => {
+                let Some(close) = (i + 1..chars.len()).find(|&j| chars[j] == ']') else {
+                    return;
+                };
+                let label: String = chars[i + 1..close].iter().collect();
+                let after = chars.get(close + 1);
+                let target = if after == Some(&'(') {
+                    let end = (close + 2..chars.len())
+                        .find(|&j| chars[j] == ')')
+                        .unwrap_or(chars.len());
+                    let target: String = chars[close + 2..end].iter().collect();
+                    i = end;
+                    target
+                } else {
+                    i = close;
+                    if after == Some(&':') || after == Some(&'[') {
+                        String::new()
+                    } else {
+                        label
+                    }
+                };
+                let target = target.trim_matches('`');
+                if is_path(target) {
+                    links.push(target.to_string());
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Whether `target` could name an item: `Timer`, `io::Error`, `demo!`,
+/// `fn@section`, `Timer::start()`.
+fn is_path(target: &str) -> bool {
+    !target.is_empty()
+        && !target.contains("://")
+        && target
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && target
+            .chars()
+            .all(|c| c.is_alphanumeric() || "_:!@()".contains(c))
+}
+
+/// A fenced code block in doc Markdown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeBlock {
+    /// The words after the opening fence: `text`, `no_run`,
+    /// `should_panic`, and so on. Rustdoc reads "```" alone as
+    /// "```rust".
+    pub attributes: Vec<String>,
+    /// The lines between the fences.
+    pub lines: Vec<String>,
+}
+
+/// The attributes that leave a block Rust code, and so a doc test.
+const RUST_ATTRIBUTES: &[&str] = &[
+    "rust",
+    "ignore",
+    "no_run",
+    "should_panic",
+    "compile_fail",
+    "edition2015",
+    "edition2018",
+    "edition2021",
+    "edition2024",
+];
+
+impl CodeBlock {
+    /// Whether rustdoc treats the block as Rust: it has no attributes, or
+    /// only ones like `no_run` that say how to test it.
+    pub fn is_rust(&self) -> bool {
+        self.attributes
+            .iter()
+            .all(|attribute| RUST_ATTRIBUTES.contains(&attribute.as_str()))
+    }
+
+    /// Whether `cargo test --doc` compiles the block: it is Rust, and not
+    /// marked `ignore`.
+    pub fn is_tested(&self) -> bool {
+        self.is_rust()
+            && !self
+                .attributes
+                .iter()
+                .any(|attribute| attribute == "ignore")
+    }
+
+    /// The lines the rendered docs show: in Rust, those not hidden by
+    /// starting with `# `.
+    pub fn shown(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter(|line| !self.is_rust() || hidden(line).is_none())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The program a doc test compiles: every line, hidden or not, with
+    /// its `# ` taken off, inside a `fn main` unless the block writes its
+    /// own.
+    ///
+    /// ```
+    /// let block = &doc_reader::code_blocks("```\n# let x = 1;\nassert_eq!(x, 1);\n```")[0];
+    /// assert_eq!(block.shown(), ["assert_eq!(x, 1);"]);
+    /// assert_eq!(
+    ///     block.test_program(),
+    ///     "fn main() {\n    let x = 1;\n    assert_eq!(x, 1);\n}\n"
+    /// );
+    /// ```
+    pub fn test_program(&self) -> String {
+        let lines: Vec<&str> = self
+            .lines
+            .iter()
+            .map(|line| hidden(line).unwrap_or(line))
+            .collect();
+        if lines
+            .iter()
+            .any(|line| line.trim_start().starts_with("fn main"))
+        {
+            return lines.iter().map(|line| format!("{line}\n")).collect();
+        }
+        let mut program = String::from("fn main() {\n");
+        for line in lines {
+            if line.is_empty() {
+                program.push('\n');
+            } else {
+                program.push_str(&format!("    {line}\n"));
+            }
+        }
+        program.push_str("}\n");
+        program
+    }
+}
+
+/// The code of a line hidden from the rendered docs with `# `, or `None`
+/// if it is shown.
+fn hidden(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if trimmed == "#" {
+        return Some("");
+    }
+    trimmed.strip_prefix("# ")
+}
+
+/// The fenced code blocks in `markdown`, in order.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<CodeBlock> = None;
+    for line in markdown.lines() {
+        let Some(info) = line.trim_start().strip_prefix("```") else {
+            if let Some(block) = &mut open {
+                block.lines.push(line.to_string());
+            }
+            continue;
+        };
+        match open.take() {
+            Some(block) => blocks.push(block),
+            None => {
+                open = Some(CodeBlock {
+                    attributes: info
+                        .split([',', ' '])
+                        .filter(|word| !word.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                    lines: Vec::new(),
+                });
+            }
+        }
+    }
+    blocks
+}
+
+/// The public items declared in `source`, like `fn prompt` or
+/// `struct Timer`, and whether each has a doc comment: what
+/// `#![deny(missing_docs)]` checks, give or take. (The lint also knows
+/// which items can be reached from outside the crate, which takes a
+/// compiler to work out.)
+///
+/// ```
+/// let items = doc_reader::public_items("/// Documented.\npub fn one() {}\npub struct Two;\n");
+/// assert_eq!(items, [("fn one".to_string(), true), ("struct Two".to_string(), false)]);
+/// ```
+pub fn public_items(source: &str) -> Vec<(String, bool)> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut items = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        let name = if let Some(rest) = trimmed.strip_prefix("pub ") {
+            declared(rest)
+        } else if trimmed.starts_with("macro_rules!")
+            && index > 0
+            && lines[..index]
+                .iter()
+                .rev()
+                .take_while(|line| line.trim().starts_with("#["))
+                .any(|line| line.contains("macro_export"))
+        {
+            let name = trimmed["macro_rules!".len()..].trim_start();
+            Some(format!("macro {}!", identifier(name)))
+        } else {
+            None
+        };
+        let Some(name) = name else {
+            continue;
+        };
+        let documented = lines[..index]
+            .iter()
+            .rev()
+            .map(|line| line.trim())
+            .find(|line| !line.starts_with("#["))
+            .is_some_and(|line| doc_line(line).is_some_and(|(inner, _)| !inner));
+        items.push((name, documented));
+    }
+    items
+}
+
+/// `fn prompt` for `fn prompt(...)`, `field label` for `label: String,`,
+/// or `None` for `pub use` and anything else that needs no docs.
+fn declared(rest: &str) -> Option<String> {
+    const KINDS: &[&str] = &[
+        "fn", "struct", "enum", "trait", "mod", "const", "static", "type", "union",
+    ];
+    let (word, after) = rest.split_once(' ').unwrap_or((rest, ""));
+    if KINDS.contains(&word) {
+        return Some(format!("{word} {}", identifier(after)));
+    }
+    let (field, _) = rest.split_once(':')?;
+    (!field.is_empty() && field.chars().all(|c| c.is_alphanumeric() || c == '_'))
+        .then(|| format!("field {field}"))
+}
+
+fn identifier(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doc_comments_run_until_another_kind_of_line() {
+        let source = "//! The module.\n//! Second line.\n\n/// One.\n#[derive(Debug)]\npub struct One;\n//// Not docs.\nfn two() {}\n";
+        let comments = doc_comments(source);
+        assert_eq!(comments.len(), 2);
+        assert!(comments[0].inner);
+        assert_eq!(comments[0].text, "The module.\nSecond line.");
+        assert_eq!(comments[1].line, 4);
+        assert_eq!(comments[1].item, "pub struct One;");
+    }
+
+    #[test]
+    fn links_skip_code_and_urls() {
+        let markdown = "Use [`demo!`] or [`fn@section`], with `[not, this]`.\n\
+                        ```\nlet v = [a, b];\n```\n\
+                        Read <https://example.com> and [`io::ErrorKind::UnexpectedEof`].";
+        assert_eq!(
+            links(markdown),
+            ["demo!", "fn@section", "io::ErrorKind::UnexpectedEof"]
+        );
+    }
+
+    #[test]
+    fn code_blocks_keep_their_attributes() {
+        let blocks = code_blocks("```text\n-- numbers --\n```\n\n```no_run\n# use std::fs;\nfs::remove_file(\"x\")?;\n```");
+        assert_eq!(blocks.len(), 2);
+        assert!(!blocks[0].is_rust());
+        assert_eq!(blocks[0].shown(), ["-- numbers --"]);
+        assert_eq!(blocks[1].attributes, ["no_run"]);
+        assert!(blocks[1].is_tested());
+        assert_eq!(blocks[1].shown().len(), 1);
+    }
+
+    #[test]
+    fn fields_and_exported_macros_are_public_items() {
+        let source = "pub struct Point {\n    /// Across.\n    pub x: i32,\n    pub y: i32,\n}\npub use std::fmt;\n#[macro_export]\nmacro_rules! point {\n";
+        assert_eq!(
+            public_items(source),
+            [
+                ("struct Point".to_string(), false),
+                ("field x".to_string(), true),
+                ("field y".to_string(), false),
+                ("macro point!".to_string(), false),
+            ]
+        );
+    }
+}
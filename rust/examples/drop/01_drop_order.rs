@@ -0,0 +1,171 @@
+// Drop 1: The Drop Trait and Drop Order
+// Demonstrates impl Drop, and the order in which Rust drops values
+//
+// Concepts: Drop, drop order, scopes
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Every value is dropped exactly once, when its owner goes away, and a
+// type that implements Drop gets to run code at that moment. The order is
+// fixed by the language, not by the optimizer: locals go in reverse order
+// of declaration, fields and elements go front to back, and temporaries
+// go at the end of their statement. Knowing the rules matters as soon as
+// one value has to outlive another, like a lock and the data it guards.
+
+use std::cell::RefCell;
+
+thread_local! {
+    // Records who was dropped, so the tests can check the order
+    static DROPPED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn take_dropped() -> Vec<String> {
+    DROPPED.with(|dropped| dropped.take())
+}
+
+/// Says when it is dropped.
+struct Noisy(String);
+
+impl Noisy {
+    fn new(name: &str) -> Noisy {
+        Noisy(name.to_string())
+    }
+}
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        println!("  drop {}", self.0);
+        DROPPED.with(|dropped| dropped.borrow_mut().push(self.0.clone()));
+    }
+}
+
+/// Fields are dropped after the struct's own drop, in declaration order.
+struct Pair {
+    first: Noisy,
+    second: Noisy,
+}
+
+fn locals() {
+    let _a = Noisy::new("a");
+    let _b = Noisy::new("b");
+    let _c = Noisy::new("c");
+    println!("  end of scope");
+}
+
+fn consume(value: Noisy) {
+    println!("  consume got {}", value.0);
+}
+
+fn main() {
+    println!("locals, in reverse order:");
+    locals();
+
+    println!("fields and elements, front to back:");
+    let pair = Pair {
+        first: Noisy::new("pair.first"),
+        second: Noisy::new("pair.second"),
+    };
+    println!("  pair holds {} and {}", pair.first.0, pair.second.0);
+    drop(pair);
+    let list = vec![Noisy::new("list[0]"), Noisy::new("list[1]")];
+    drop(list);
+
+    println!("a moved value is dropped by its new owner:");
+    let moved = Noisy::new("moved");
+    consume(moved);
+    println!("  back in main");
+
+    println!("`_` drops at once, `_name` keeps the value:");
+    let _ = Noisy::new("underscore");
+    let _kept = Noisy::new("kept");
+    println!("  after both lets");
+
+    println!("a temporary lives until the end of its statement:");
+    let length = Noisy::new("temporary").0.len();
+    println!("  length was {}", length);
+
+    println!("reassignment drops the old value:");
+    let mut slot = Noisy::new("old");
+    println!("  slot holds {}", slot.0);
+    slot = Noisy::new("new");
+    println!("  slot holds {}", slot.0);
+
+    println!("dropped so far: {}", take_dropped().len());
+    println!("end of main");
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    slot.drop();
+    // error[E0040]: explicit use of destructor method
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locals_are_dropped_in_reverse_order() {
+        take_dropped();
+        locals();
+        assert_eq!(take_dropped(), ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn fields_and_elements_are_dropped_front_to_back() {
+        take_dropped();
+        drop(Pair {
+            first: Noisy::new("first"),
+            second: Noisy::new("second"),
+        });
+        drop(vec![Noisy::new("0"), Noisy::new("1"), Noisy::new("2")]);
+        assert_eq!(take_dropped(), ["first", "second", "0", "1", "2"]);
+    }
+
+    #[test]
+    fn underscore_drops_immediately() {
+        take_dropped();
+        let _ = Noisy::new("now");
+        let _later = Noisy::new("later");
+        assert_eq!(take_dropped(), ["now"]);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Drop::drop runs once, when the owner of a value goes away
+ * - Locals drop in reverse declaration order; fields and elements in order
+ * - Temporaries drop at the end of the statement; `let _ =` drops at once
+ * - Moving a value moves the responsibility of dropping it
+ * - drop() can't be called as a method; std::mem::drop does it instead
+ */
+
+// EXPECTED:
+// locals, in reverse order:
+//   end of scope
+//   drop c
+//   drop b
+//   drop a
+// fields and elements, front to back:
+//   pair holds pair.first and pair.second
+//   drop pair.first
+//   drop pair.second
+//   drop list[0]
+//   drop list[1]
+// a moved value is dropped by its new owner:
+//   consume got moved
+//   drop moved
+//   back in main
+// `_` drops at once, `_name` keeps the value:
+//   drop underscore
+//   after both lets
+// a temporary lives until the end of its statement:
+//   drop temporary
+//   length was 9
+// reassignment drops the old value:
+//   slot holds old
+//   drop old
+//   slot holds new
+// dropped so far: 11
+// end of main
+//   drop new
+//   drop kept
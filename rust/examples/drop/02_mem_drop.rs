@@ -0,0 +1,152 @@
+// Drop 2: Dropping Early With std::mem::drop
+// Demonstrates drop(), Option::take, and std::mem::forget
+//
+// Concepts: drop(), Option::take, mem::forget
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Waiting for the end of the scope is usually right, but some values hold
+// on to something others are waiting for: a lock, a borrow, a buffer that
+// hasn't been written out yet. std::mem::drop ends their life on the spot.
+// There is no magic in it; it takes its argument by value and does
+// nothing, so the value is dropped as the function returns.
+
+use std::cell::{Cell, RefCell};
+use std::io::{BufWriter, Write};
+use std::sync::Mutex;
+
+thread_local! {
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn drops() -> usize {
+    DROPS.with(Cell::get)
+}
+
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+/// The whole of std::mem::drop, under another name.
+fn discard<T>(_value: T) {}
+
+/// A connection pool slot: taking the connection out of the Option drops
+/// it now, while the struct that held it lives on.
+struct Slot {
+    connection: Option<Counted>,
+}
+
+fn main() {
+    // A MutexGuard unlocks when dropped. Locking twice on one thread
+    // without dropping the first guard would wait forever.
+    let queue = Mutex::new(vec![1, 2, 3]);
+    let guard = queue.lock().unwrap();
+    let first = guard[0];
+    drop(guard);
+    queue.lock().unwrap().push(first * 10);
+    println!("queue: {:?}", queue.lock().unwrap());
+
+    // The same goes for a RefCell borrow, checked at runtime
+    let names = RefCell::new(vec!["ana"]);
+    let reader = names.borrow();
+    println!("first name: {}", reader[0]);
+    drop(reader);
+    names.borrow_mut().push("ben");
+    println!("names: {:?}", names.borrow());
+
+    // BufWriter collects writes and passes them on in one go, at the
+    // latest when it is dropped
+    let mut sink = Vec::new();
+    let mut writer = BufWriter::new(&mut sink);
+    writer.write_all(b"hello, sink").unwrap();
+    println!("written before drop: {} bytes", writer.get_ref().len());
+    drop(writer);
+    println!("written after drop: {} bytes", sink.len());
+
+    // discard() behaves exactly like drop()
+    let before = drops();
+    discard(Counted);
+    drop(Counted);
+    println!("dropped by discard and drop: {}", drops() - before);
+
+    // Dropping a field: take() leaves None behind
+    let mut slot = Slot {
+        connection: Some(Counted),
+    };
+    let before = drops();
+    slot.connection.take();
+    println!(
+        "connection dropped: {}, slot empty: {}",
+        drops() - before,
+        slot.connection.is_none()
+    );
+
+    // forget() gives up ownership without running the destructor. It's
+    // safe, because leaking is safe, but the cleanup never happens.
+    let before = drops();
+    std::mem::forget(Counted);
+    println!("dropped by forget: {}", drops() - before);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let guard = queue.lock().unwrap();
+        drop(guard);
+        println!("{}", guard[0]);
+    }
+    // error[E0382]: borrow of moved value: `guard`
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropping_a_guard_unlocks_the_mutex() {
+        let value = Mutex::new(1);
+        let guard = value.lock().unwrap();
+        assert!(value.try_lock().is_err());
+        drop(guard);
+        assert!(value.try_lock().is_ok());
+    }
+
+    #[test]
+    fn dropping_a_buf_writer_flushes_it() {
+        let mut sink = Vec::new();
+        let mut writer = BufWriter::new(&mut sink);
+        writer.write_all(b"data").unwrap();
+        drop(writer);
+        assert_eq!(sink, b"data");
+    }
+
+    #[test]
+    fn discard_drops_and_forget_does_not() {
+        let before = drops();
+        discard(Counted);
+        std::mem::forget(Counted);
+        assert_eq!(drops() - before, 1);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - drop(value) moves the value into a function that does nothing, ending its life
+ * - Drop guards early to release locks and borrows others are waiting for
+ * - Option::take() drops what a field holds without dropping the struct
+ * - mem::forget skips the destructor; leaks are safe but lose the cleanup
+ * - A dropped value is moved, so the compiler rejects any later use
+ */
+
+// EXPECTED:
+// queue: [1, 2, 3, 10]
+// first name: ana
+// names: ["ana", "ben"]
+// written before drop: 0 bytes
+// written after drop: 11 bytes
+// dropped by discard and drop: 2
+// connection dropped: 1, slot empty: true
+// dropped by forget: 0
@@ -0,0 +1,203 @@
+// Drop 3: Guards and RAII
+// Demonstrates guard types whose Drop undoes work unless it is committed
+//
+// Concepts: guard types, Drop, commit or roll back
+// Difficulty: intermediate
+// Minutes: 15
+//
+// RAII ("resource acquisition is initialization") ties a cleanup to a
+// value: creating the value takes the resource, dropping it gives it back.
+// Because Rust drops values on every way out of a scope, the cleanup runs
+// after a return, after `?`, and while a panic unwinds the stack, with no
+// `finally` block to forget. MutexGuard and File work like this; writing
+// our own guard takes nothing more than a Drop impl.
+
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+
+/// Adds entries to a ledger, and takes them all out again when dropped,
+/// unless `commit` was called first.
+struct Transaction<'a> {
+    ledger: &'a mut Vec<String>,
+    start: usize,
+    committed: bool,
+}
+
+impl<'a> Transaction<'a> {
+    fn begin(ledger: &'a mut Vec<String>) -> Transaction<'a> {
+        let start = ledger.len();
+        Transaction {
+            ledger,
+            start,
+            committed: false,
+        }
+    }
+
+    fn push(&mut self, entry: &str) {
+        self.ledger.push(entry.to_string());
+    }
+
+    /// Consumes the guard, so nothing can be added after committing.
+    fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            println!("  rolling back to {} entries", self.start);
+            self.ledger.truncate(self.start);
+        }
+    }
+}
+
+/// Adds all entries or none. Empty entries are an error, and entries
+/// longer than ten bytes are a bug that panics.
+fn add_all(ledger: &mut Vec<String>, entries: &[&str]) -> Result<(), String> {
+    let mut tx = Transaction::begin(ledger);
+    for entry in entries {
+        if entry.is_empty() {
+            return Err("empty entry".to_string());
+        }
+        assert!(entry.len() <= 10, "entry too long: {}", entry);
+        tx.push(entry);
+    }
+    tx.commit();
+    Ok(())
+}
+
+/// Runs a closure when dropped: a general-purpose scope guard.
+struct Defer<F: FnMut()>(F);
+
+impl<F: FnMut()> Drop for Defer<F> {
+    fn drop(&mut self) {
+        (self.0)();
+    }
+}
+
+/// Counts itself as active while `work` runs, however `work` ends.
+fn tracked(active: &Cell<u32>, work: impl FnOnce()) {
+    active.set(active.get() + 1);
+    let _done = Defer(|| active.set(active.get() - 1));
+    work();
+}
+
+/// Runs `f`, printing the panic message if it panics.
+fn attempt(f: impl FnOnce()) {
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(f)) {
+        let message = payload
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| payload.downcast_ref::<&str>().copied())
+            .unwrap_or("?");
+        println!("panicked: {}", message);
+    }
+}
+
+fn main() {
+    // Keep the default panic messages out of the output
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut ledger = Vec::new();
+    add_all(&mut ledger, &["rent", "food"]).unwrap();
+    println!("after commit: {:?}", ledger);
+
+    // An early return drops the guard before it was committed
+    let result = add_all(&mut ledger, &["books", ""]);
+    println!("{:?}, ledger: {:?}", result, ledger);
+
+    // So does a panic, as it unwinds through add_all
+    attempt(|| {
+        add_all(&mut ledger, &["fuel", "bus", "electricity"]).unwrap();
+    });
+    println!("after a panic: {:?}", ledger);
+
+    let active = Cell::new(0);
+    tracked(&active, || {
+        println!("active while working: {}", active.get())
+    });
+    attempt(|| tracked(&active, || panic!("worker failed")));
+    println!("active afterwards: {}", active.get());
+
+    // MutexGuard unlocks during unwinding too, and marks the mutex as
+    // poisoned so the next user knows the data may be half-updated
+    let balance = Mutex::new(100);
+    attempt(|| {
+        let mut guard = balance.lock().unwrap();
+        *guard -= 30;
+        panic!("crashed mid-update");
+    });
+    println!("mutex poisoned: {}", balance.is_poisoned());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let mut tx = Transaction::begin(&mut ledger);
+        ledger.push("sneaky".to_string());
+        tx.push("late");
+    }
+    // error[E0499]: cannot borrow `ledger` as mutable more than once at a time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_committed_transaction_keeps_its_entries() {
+        let mut ledger = vec!["old".to_string()];
+        add_all(&mut ledger, &["a", "b"]).unwrap();
+        assert_eq!(ledger, ["old", "a", "b"]);
+    }
+
+    #[test]
+    fn an_error_rolls_the_transaction_back() {
+        let mut ledger = vec!["old".to_string()];
+        assert!(add_all(&mut ledger, &["a", "", "b"]).is_err());
+        assert_eq!(ledger, ["old"]);
+    }
+
+    #[test]
+    fn a_panic_rolls_the_transaction_back() {
+        let mut ledger = vec!["old".to_string()];
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            add_all(&mut ledger, &["a", "much too long"])
+        }));
+        assert!(result.is_err());
+        assert_eq!(ledger, ["old"]);
+    }
+
+    #[test]
+    fn defer_runs_when_the_work_panics() {
+        let active = Cell::new(0);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            tracked(&active, || panic!("boom"));
+        }));
+        assert!(result.is_err());
+        assert_eq!(active.get(), 0);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A guard acquires in its constructor and releases in Drop
+ * - Drop runs on every exit: falling off the end, return, ?, and unwinding
+ * - A `committed` flag (set by a consuming method) turns off the cleanup
+ * - A Defer guard holding a closure is the general "run this on exit"
+ * - With panic = "abort" nothing unwinds, so no destructors run
+ */
+
+// EXPECTED:
+// after commit: ["rent", "food"]
+//   rolling back to 2 entries
+// Err("empty entry"), ledger: ["rent", "food"]
+//   rolling back to 2 entries
+// panicked: entry too long: electricity
+// after a panic: ["rent", "food"]
+// active while working: 1
+// panicked: worker failed
+// active afterwards: 0
+// panicked: crashed mid-update
+// mutex poisoned: true
@@ -0,0 +1,188 @@
+// Drop 4: A Temporary File That Cleans Up After Itself
+// Demonstrates a TempFile type that deletes its file in Drop
+//
+// Concepts: Drop, temporary files, cleanup
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Files outlive the program that made them, so a temporary file needs
+// someone to delete it: after success, after an error, and after a panic.
+// That is exactly what Drop is for. The type below owns a path, creates
+// the file in the system's temp directory, and removes it when dropped.
+// Drop can't return an error, so a failed delete is quietly ignored: a
+// leftover file in the temp directory is better than a crash.
+
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Makes every name unique within this process; the process id makes it
+/// unique between processes.
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// A file in the temp directory that is deleted when this value is dropped.
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn new(prefix: &str) -> io::Result<TempFile> {
+        let name = format!(
+            "{}-{}-{}.tmp",
+            prefix,
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(name);
+        // create_new fails rather than clobbering a file that's already there
+        fs::File::create_new(&path)?;
+        Ok(TempFile { path })
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gives up on deleting the file and returns where it is. A type with
+    /// Drop can't be taken apart, so we take the path and leave an empty one.
+    fn keep(mut self) -> PathBuf {
+        std::mem::take(&mut self.path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.path.as_os_str().is_empty() {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Writes a report through a temporary file and reads it back. If
+/// `fail` is set it panics halfway, after the file was created.
+fn render(lines: &[&str], fail: bool) -> io::Result<(String, PathBuf)> {
+    let scratch = TempFile::new("report")?;
+    fs::write(scratch.path(), lines.join("\n"))?;
+    if fail {
+        panic!("renderer crashed");
+    }
+    let text = fs::read_to_string(scratch.path())?;
+    Ok((text, scratch.path().to_path_buf()))
+}
+
+fn main() -> io::Result<()> {
+    panic::set_hook(Box::new(|_| {}));
+
+    let file = TempFile::new("demo")?;
+    fs::write(file.path(), "scratch data")?;
+    println!("contents: {:?}", fs::read_to_string(file.path())?);
+    let path = file.path().to_path_buf();
+    println!("exists while in use: {}", path.exists());
+    drop(file);
+    println!("exists after drop: {}", path.exists());
+
+    // The file is gone by the time render() returns
+    let (text, used) = render(&["total: 3", "errors: 0"], false)?;
+    println!("report: {:?}", text);
+    println!("report file left behind: {}", used.exists());
+
+    // ...and when it panics, because unwinding drops `scratch` too
+    let before = leftover("report");
+    let outcome = panic::catch_unwind(|| render(&["total: 3"], true));
+    println!("render panicked: {}", outcome.is_err());
+    println!(
+        "files left after the panic: {}",
+        leftover("report") - before
+    );
+
+    // keep() turns the cleanup off
+    let kept = TempFile::new("kept")?.keep();
+    println!("kept file exists: {}", kept.exists());
+    fs::remove_file(&kept)?;
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let TempFile { path } = TempFile::new("moved")?;
+        println!("{}", path.display());
+    }
+    // error[E0509]: cannot move out of type `TempFile`, which implements the `Drop` trait
+
+    Ok(())
+}
+
+/// Counts this process's files in the temp directory whose name starts
+/// with `prefix`.
+fn leftover(prefix: &str) -> usize {
+    let start = format!("{}-{}-", prefix, std::process::id());
+    fs::read_dir(std::env::temp_dir())
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with(&start))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn the_file_is_removed_when_dropped() {
+        let file = TempFile::new("test-drop").unwrap();
+        let path = file.path().to_path_buf();
+        assert!(path.exists());
+        drop(file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn the_file_is_removed_when_the_code_using_it_panics() {
+        let mut seen = None;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let file = TempFile::new("test-panic").unwrap();
+            seen = Some(file.path().to_path_buf());
+            panic!("boom");
+        }));
+        assert!(result.is_err());
+        assert!(!seen.unwrap().exists());
+    }
+
+    #[test]
+    fn every_temp_file_gets_its_own_path() {
+        let a = TempFile::new("test-unique").unwrap();
+        let b = TempFile::new("test-unique").unwrap();
+        assert_ne!(a.path(), b.path());
+    }
+
+    #[test]
+    fn keep_leaves_the_file_in_place() {
+        let path = TempFile::new("test-keep").unwrap().keep();
+        assert!(path.exists());
+        fs::remove_file(path).unwrap();
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Tie an outside resource (a file, a socket, a lock) to a value's lifetime
+ * - Drop runs on success, on errors, and while unwinding from a panic
+ * - Drop can't return errors; ignore or log them, never panic there
+ * - A type with Drop can't be destructured; mem::take moves a field out
+ * - A "keep" or "persist" method opts out of the cleanup
+ */
+
+// EXPECTED:
+// contents: "scratch data"
+// exists while in use: true
+// exists after drop: false
+// report: "total: 3\nerrors: 0"
+// report file left behind: false
+// render panicked: true
+// files left after the panic: 0
+// kept file exists: true
@@ -0,0 +1,104 @@
+// Enums 2: if let, let else, and while let
+// Demonstrates concise matching when only one pattern matters
+//
+// Concepts: if let, while let, let else
+// Difficulty: beginner
+// Minutes: 10
+
+#[derive(Debug)]
+enum Message {
+    Text(String),
+    Ping,
+    Quit,
+}
+
+fn parse_port(input: &str) -> Result<u16, String> {
+    // let-else: bind on a match, or leave the function (the else block
+    // must diverge: return, break, continue, or panic)
+    let Ok(port) = input.trim().parse::<u16>() else {
+        return Err(format!("{:?} is not a port number", input));
+    };
+    Ok(port)
+}
+
+fn main() {
+    let messages = vec![
+        Message::Text(String::from("hello")),
+        Message::Ping,
+        Message::Text(String::from("bye")),
+        Message::Quit,
+    ];
+
+    // When you care about ONE variant, a full match is noisy:
+    for message in &messages {
+        #[allow(clippy::single_match)] // clippy agrees
+        match message {
+            Message::Text(text) => println!("match:  text {:?}", text),
+            _ => {}
+        }
+    }
+    // `if let` says the same thing directly. It is NOT exhaustive:
+    // anything that doesn't fit the pattern is simply skipped (or goes to
+    // the else branch).
+    for message in &messages {
+        if let Message::Text(text) = message {
+            println!("if let: text {:?}", text);
+        } else {
+            println!("if let: something else ({:?})", message);
+        }
+    }
+
+    // matches! turns a pattern into a bool
+    let pings = messages
+        .iter()
+        .filter(|m| matches!(m, Message::Ping))
+        .count();
+    println!("{} ping(s)", pings);
+
+    println!("{:?}", parse_port("8080"));
+    println!("{:?}", parse_port("eighty"));
+
+    // `while let` loops for as long as the pattern keeps matching.
+    // Vec::pop returns Some(item) until the vector is empty, then None.
+    let mut stack = vec![1, 2, 3];
+    while let Some(top) = stack.pop() {
+        println!("popped {}", top);
+    }
+
+    // Handy with iterators you advance by hand
+    let mut words = "process messages until quit".split_whitespace();
+    #[allow(clippy::while_let_on_iterator)] // a for loop would do here, too
+    while let Some(word) = words.next() {
+        if word == "until" {
+            break;
+        }
+        println!("word: {}", word);
+    }
+    println!("left over: {:?}", words.collect::<Vec<_>>());
+}
+
+/*
+ * Key Concepts:
+ * - if let handles one pattern and ignores (or else-branches) the rest
+ * - let ... else binds or exits early, keeping the happy path unindented
+ * - while let repeats until the pattern stops matching
+ * - matches!(value, pattern) gives a bool
+ * - Use match when every case matters, if let when only one does
+ */
+
+// EXPECTED:
+// match:  text "hello"
+// match:  text "bye"
+// if let: text "hello"
+// if let: something else (Ping)
+// if let: text "bye"
+// if let: something else (Quit)
+// 1 ping(s)
+// Ok(8080)
+// Err("\"eighty\" is not a port number")
+// popped 3
+// popped 2
+// popped 1
+// word: process
+// word: messages
+// left over: ["quit"]
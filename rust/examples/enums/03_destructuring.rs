@@ -0,0 +1,122 @@
+// Enums 3: Destructuring
+// Demonstrates taking structs, tuples, enums, and slices apart with patterns
+//
+// Concepts: patterns, destructuring, structs, tuples, slices
+// Difficulty: beginner
+// Minutes: 10
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[allow(dead_code)]
+enum Color {
+    Rgb(u8, u8, u8),
+    Hsv { hue: u16, saturation: u8, value: u8 },
+}
+
+struct Pixel {
+    position: Point,
+    color: Color,
+}
+
+fn describe(pixel: &Pixel) -> String {
+    // Patterns nest: take the Pixel apart, then its Point and its Color
+    match pixel {
+        Pixel {
+            position: Point { x: 0, y: 0 },
+            ..
+        } => String::from("the origin pixel"),
+        Pixel {
+            position: Point { x, y },
+            color: Color::Rgb(r, g, b),
+        } => format!("({}, {}) is rgb({}, {}, {})", x, y, r, g, b),
+        Pixel {
+            position: Point { x, y },
+            color: Color::Hsv { hue, .. }, // `..` skips the remaining fields
+        } => format!("({}, {}) has hue {}", x, y, hue),
+    }
+}
+
+fn main() {
+    // Tuples: let itself takes a pattern
+    let (name, age) = ("Ferris", 8);
+    println!("{} is {}", name, age);
+
+    // Structs: field names pick the parts; `x: left` renames
+    let point = Point { x: 3, y: -2 };
+    let Point { x: left, y } = point;
+    println!("x = {}, y = {}", left, y);
+
+    // Function parameters are patterns too
+    let distance = |&(a, b): &(i32, i32)| a.abs() + b.abs();
+    println!("manhattan distance of (3, -4): {}", distance(&(3, -4)));
+
+    let pixels = [
+        Pixel {
+            position: Point { x: 0, y: 0 },
+            color: Color::Rgb(0, 0, 0),
+        },
+        Pixel {
+            position: Point { x: 4, y: 2 },
+            color: Color::Rgb(255, 128, 0),
+        },
+        Pixel {
+            position: Point { x: 1, y: 9 },
+            color: Color::Hsv {
+                hue: 200,
+                saturation: 50,
+                value: 80,
+            },
+        },
+    ];
+    for pixel in &pixels {
+        println!("{}", describe(pixel));
+    }
+
+    // Slices: match on length and pick out elements
+    for list in [&[][..], &[7], &[1, 2], &[1, 2, 3, 4]] {
+        let summary = match list {
+            [] => String::from("empty"),
+            [only] => format!("just {}", only),
+            [first, second] => format!("a pair: {} and {}", first, second),
+            [first, .., last] => format!("{} items from {} to {}", list.len(), first, last),
+        };
+        println!("{:?}: {}", list, summary);
+    }
+
+    // Ignoring parts: `_` for one value, `..` for "the rest"
+    let (first, _, third, ..) = (1, 2, 3, 4, 5);
+    println!("first = {}, third = {}", first, third);
+
+    // Destructuring with references: `&` in a pattern "un-references"
+    let pairs = vec![(1, 'a'), (2, 'b')];
+    for &(number, letter) in &pairs {
+        println!("{} -> {}", number, letter);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Patterns mirror the shape of the value they take apart
+ * - let, function parameters, and match arms all accept patterns
+ * - Patterns nest: structs inside enums inside structs
+ * - `..` ignores remaining fields or elements; `_` ignores one value
+ * - Slice patterns match on length: [], [x], [first, .., last]
+ */
+
+// EXPECTED:
+// Ferris is 8
+// x = 3, y = -2
+// manhattan distance of (3, -4): 7
+// the origin pixel
+// (4, 2) is rgb(255, 128, 0)
+// (1, 9) has hue 200
+// []: empty
+// [7]: just 7
+// [1, 2]: a pair: 1 and 2
+// [1, 2, 3, 4]: 4 items from 1 to 4
+// first = 1, third = 3
+// 1 -> a
+// 2 -> b
@@ -0,0 +1,97 @@
+// Enums 4: Match Guards and @ Bindings
+// Demonstrates extra conditions on arms and naming a value while testing it
+//
+// Concepts: match guards, @ bindings
+// Difficulty: beginner
+// Minutes: 10
+
+#[derive(Debug)]
+enum Reading {
+    Temperature(f64),
+    Humidity(u8),
+    Error { code: u16 },
+}
+
+fn classify(reading: &Reading) -> String {
+    match reading {
+        // A guard (`if ...`) adds a condition the pattern alone can't express
+        Reading::Temperature(t) if *t < 0.0 => format!("freezing: {:.1}°C", t),
+        Reading::Temperature(t) if *t > 35.0 => format!("heatwave: {:.1}°C", t),
+        Reading::Temperature(t) => format!("mild: {:.1}°C", t),
+
+        // `name @ pattern` tests against a range AND keeps the value
+        Reading::Humidity(h @ 0..=30) => format!("dry air ({}%)", h),
+        Reading::Humidity(h @ 31..=60) => format!("comfortable ({}%)", h),
+        Reading::Humidity(h) => format!("humid ({}%)", h),
+
+        // @ works on any sub-pattern, here combined with `|`
+        Reading::Error {
+            code: code @ (404 | 410),
+        } => format!("sensor missing (code {})", code),
+        Reading::Error { code } => format!("sensor error {}", code),
+    }
+}
+
+fn main() {
+    let readings = [
+        Reading::Temperature(-3.5),
+        Reading::Temperature(21.0),
+        Reading::Temperature(38.2),
+        Reading::Humidity(25),
+        Reading::Humidity(45),
+        Reading::Humidity(80),
+        Reading::Error { code: 404 },
+        Reading::Error { code: 500 },
+    ];
+    for reading in &readings {
+        println!("{:<25} => {}", format!("{:?}", reading), classify(reading));
+    }
+
+    // Guards can use variables from outside the pattern
+    let limit = 10;
+    for n in [3, 10, 42] {
+        let verdict = match n {
+            x if x < limit => "under the limit",
+            x if x == limit => "exactly the limit",
+            _ => "over the limit",
+        };
+        println!("{} is {}", n, verdict);
+    }
+
+    // Guards don't count toward exhaustiveness: the compiler can't prove
+    // `x if x >= 0` and `x if x < 0` cover everything, so a catch-all arm
+    // is still needed.
+    let number = -7;
+    let sign = match number {
+        x if x > 0 => "positive",
+        x if x < 0 => "negative",
+        _ => "zero",
+    };
+    println!("{} is {}", number, sign);
+    // This would cause an error:
+    // let sign = match number { x if x >= 0 => "non-negative", x if x < 0 => "negative" };
+    // error[E0004]: non-exhaustive patterns: `i32::MIN..=i32::MAX` not covered
+}
+
+/*
+ * Key Concepts:
+ * - A match guard (`pattern if condition`) adds a runtime check
+ * - Guards can refer to variables outside the match
+ * - The compiler ignores guards when checking exhaustiveness
+ * - `name @ pattern` binds the value that matched a sub-pattern
+ * - Ranges (`0..=30`) and alternatives (`404 | 410`) combine with @
+ */
+
+// EXPECTED:
+// Temperature(-3.5)         => freezing: -3.5°C
+// Temperature(21.0)         => mild: 21.0°C
+// Temperature(38.2)         => heatwave: 38.2°C
+// Humidity(25)              => dry air (25%)
+// Humidity(45)              => comfortable (45%)
+// Humidity(80)              => humid (80%)
+// Error { code: 404 }       => sensor missing (code 404)
+// Error { code: 500 }       => sensor error 500
+// 3 is under the limit
+// 10 is exactly the limit
+// 42 is over the limit
+// -7 is negative
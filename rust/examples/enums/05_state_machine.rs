@@ -0,0 +1,131 @@
+// Enums 5: A State Machine
+// Demonstrates modelling states and transitions with enums and match
+//
+// Concepts: state machines, enums, transitions
+// Difficulty: intermediate
+// Minutes: 10
+
+use std::fmt;
+
+// Each state carries only the data that makes sense in that state: there
+// is no "tracking number" field that is empty until the order ships.
+#[derive(Debug, Clone, PartialEq)]
+enum Order {
+    Cart { items: Vec<String> },
+    Paid { items: Vec<String>, amount: u32 },
+    Shipped { tracking: String },
+    Delivered,
+    Cancelled { reason: String },
+}
+
+#[derive(Debug)]
+enum Event {
+    AddItem(String),
+    Pay(u32),
+    Ship(String),
+    Deliver,
+    Cancel(String),
+}
+
+impl Order {
+    // Taking `self` by value means the old state is consumed: you can't
+    // accidentally keep using an order that has moved on.
+    fn handle(self, event: Event) -> Result<Order, String> {
+        // Matching on (state, event) pairs lists every legal transition
+        // in one place. Anything not listed is rejected by the last arm.
+        match (self, event) {
+            (Order::Cart { mut items }, Event::AddItem(item)) => {
+                items.push(item);
+                Ok(Order::Cart { items })
+            }
+            (Order::Cart { items }, Event::Pay(amount)) if !items.is_empty() => {
+                Ok(Order::Paid { items, amount })
+            }
+            (Order::Paid { .. }, Event::Ship(tracking)) => Ok(Order::Shipped { tracking }),
+            (Order::Shipped { .. }, Event::Deliver) => Ok(Order::Delivered),
+            (Order::Cart { .. } | Order::Paid { .. }, Event::Cancel(reason)) => {
+                Ok(Order::Cancelled { reason })
+            }
+            (state, event) => Err(format!("cannot {:?} when the order is {}", event, state)),
+        }
+    }
+}
+
+impl fmt::Display for Order {
+    // No wildcard here on purpose: add a new state and this match stops
+    // compiling until you decide how to display it.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Order::Cart { items } => write!(f, "a cart with {} item(s)", items.len()),
+            Order::Paid { amount, .. } => write!(f, "paid (${})", amount),
+            Order::Shipped { tracking } => write!(f, "shipped ({})", tracking),
+            Order::Delivered => write!(f, "delivered"),
+            Order::Cancelled { reason } => write!(f, "cancelled: {}", reason),
+        }
+    }
+}
+
+fn run(events: Vec<Event>) {
+    let mut order = Order::Cart { items: Vec::new() };
+    println!("start: {}", order);
+    for event in events {
+        let description = format!("{:?}", event);
+        // handle consumes the order, so keep a copy to report errors
+        match order.clone().handle(event) {
+            Ok(next) => {
+                println!("  {:<26} -> {}", description, next);
+                order = next;
+            }
+            Err(message) => println!("  {:<26} !! {}", description, message),
+        }
+    }
+    println!("end: {}\n", order);
+}
+
+fn main() {
+    // The happy path
+    run(vec![
+        Event::AddItem(String::from("book")),
+        Event::AddItem(String::from("pen")),
+        Event::Pay(25),
+        Event::Ship(String::from("TRACK-123")),
+        Event::Deliver,
+    ]);
+
+    // Illegal transitions are caught and the state stays the same
+    run(vec![
+        Event::Pay(10), // can't pay for an empty cart
+        Event::AddItem(String::from("mug")),
+        Event::Deliver, // can't deliver before shipping
+        Event::Pay(8),
+        Event::Cancel(String::from("changed my mind")),
+        Event::Ship(String::from("TRACK-456")), // cancelled orders don't ship
+    ]);
+}
+
+/*
+ * Key Concepts:
+ * - Enum variants model states; each holds only its own data
+ * - Matching on (state, event) tuples makes transitions explicit
+ * - Guards express conditions like "cart must not be empty"
+ * - A final catch-all arm rejects every illegal transition
+ * - Exhaustive matches (no `_`) make adding a state a guided change
+ */
+
+// EXPECTED:
+// start: a cart with 0 item(s)
+//   AddItem("book")            -> a cart with 1 item(s)
+//   AddItem("pen")             -> a cart with 2 item(s)
+//   Pay(25)                    -> paid ($25)
+//   Ship("TRACK-123")          -> shipped (TRACK-123)
+//   Deliver                    -> delivered
+// end: delivered
+//
+// start: a cart with 0 item(s)
+//   Pay(10)                    !! cannot Pay(10) when the order is a cart with 0 item(s)
+//   AddItem("mug")             -> a cart with 1 item(s)
+//   Deliver                    !! cannot Deliver when the order is a cart with 1 item(s)
+//   Pay(8)                     -> paid ($8)
+//   Cancel("changed my mind")  -> cancelled: changed my mind
+//   Ship("TRACK-456")          !! cannot Ship("TRACK-456") when the order is cancelled: changed my mind
+// end: cancelled: changed my mind
@@ -0,0 +1,93 @@
+// Error Design 1: A Hand-Rolled Error Enum
+// Demonstrates the baseline: an enum with Display, Error::source, and matching
+//
+// Concepts: error enums, Display, Error::source
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Open `config_loader/hand_rolled.rs` first. Its ConfigError is the error
+// type from error_handling/06_config_parser.rs, grown a little: one
+// variant per way loading can fail, a Display impl with the message for
+// each, and a source() impl that hands out the lower-level error inside.
+// It is about forty lines of boilerplate, and in return callers get a
+// type they can match on, which is what this example does.
+
+use std::error::Error;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use config_loader::hand_rolled::{load, ConfigError};
+use config_loader::{chain, in_sample_dir, Config, CASES};
+
+/// A caller that recovers from some errors: it can, because it knows
+/// exactly which variants exist.
+fn load_or_default(path: &Path) -> Result<Config, ConfigError> {
+    match load(path) {
+        Err(ConfigError::Io { source, .. }) if source.kind() == ErrorKind::NotFound => Ok(Config {
+            name: "default".to_string(),
+            port: 8080,
+            workers: 1,
+        }),
+        other => other,
+    }
+}
+
+fn main() {
+    in_sample_dir(|| {
+        for name in CASES {
+            match load(Path::new(name)) {
+                Ok(config) => println!("{}: {:?}", name, config),
+                Err(error) => {
+                    // Each error in the chain, top to bottom
+                    let messages = chain(&error);
+                    println!("{}: {}", name, messages[0]);
+                    for cause in &messages[1..] {
+                        println!("    caused by: {}", cause);
+                    }
+                }
+            }
+        }
+
+        let fallback = load_or_default(Path::new("missing.conf")).unwrap();
+        println!("missing.conf with a fallback: {}", fallback.name);
+
+        // The variant's fields are there to inspect, typed
+        if let Err(ConfigError::Syntax { line, .. }) = load(Path::new("syntax.conf")) {
+            println!("the editor should jump to line {}", line);
+        }
+
+        // And the error is still a std::error::Error, like any other
+        let error: Box<dyn Error> = load(Path::new("no_name.conf")).unwrap_err().into();
+        println!("as Box<dyn Error>: {}", error);
+    });
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    match load(Path::new("good.conf")) {
+        Ok(_) => {}
+        Err(ConfigError::Io { .. } | ConfigError::Syntax { .. }) => {}
+    }
+    // error[E0004]: non-exhaustive patterns: `Err(ConfigError::BadNumber { .. })` and `Err(ConfigError::MissingKey(_))` not covered
+}
+
+/*
+ * Key Concepts:
+ * - One variant per failure, each carrying the data a caller might need
+ * - Display gives the message; source() links to the underlying error
+ * - Callers can match on variants to recover from specific failures
+ * - The cost is boilerplate: Display, Error, and map_err/From by hand
+ */
+
+// EXPECTED:
+// good.conf: Config { name: "demo server", port: 8080, workers: 4 }
+// missing.conf: cannot read missing.conf
+//     caused by: No such file or directory (os error 2)
+// syntax.conf: line 2: expected `key = value`, found "port 8080"
+// bad_port.conf: `port` must be a number
+//     caused by: invalid digit found in string
+// huge_port.conf: `port` must be a number
+//     caused by: number too large to fit in target type
+// no_name.conf: required key `name` is missing
+// missing.conf with a fallback: default
+// the editor should jump to line 2
+// as Box<dyn Error>: required key `name` is missing
@@ -0,0 +1,91 @@
+// Error Design 3: anyhow and Context
+// Demonstrates anyhow::Result, .context(), bail!, error reports, and downcasting
+//
+// Concepts: anyhow::Result, context, bail!, downcasting
+// Difficulty: intermediate
+// Minutes: 10
+//
+// `config_loader/with_anyhow.rs` has no error type at all. Every function
+// returns anyhow::Result<T>, `?` turns any error into an anyhow::Error,
+// and .context("...") wraps it in one more line of explanation: the
+// chain now says which file was being read *and* what went wrong in it.
+// The price is that callers can no longer match on what failed. They get
+// one opaque type, and can only ask "is there an io::Error in here?" by
+// downcasting. That suits applications, where errors are mostly reported
+// rather than handled.
+
+use std::io;
+use std::path::Path;
+
+use config_loader::in_sample_dir;
+use config_loader::with_anyhow::load;
+
+fn main() {
+    in_sample_dir(|| {
+        let error = load(Path::new("bad_port.conf")).unwrap_err();
+
+        // Display shows only the outermost context...
+        println!("{{}}:   {}", error);
+        // ...the alternate form shows the whole chain on one line...
+        println!("{{:#}}: {:#}", error);
+        // ...and Debug prints a report, which is what main's Err shows
+        // (with a backtrace at the end if RUST_BACKTRACE=1 is set)
+        println!("{{:?}}:\n{:?}", error);
+        println!();
+
+        // The chain is there to walk, as with any error
+        for (depth, cause) in error.chain().enumerate() {
+            println!("{}: {}", depth, cause);
+        }
+        println!("root cause: {}", error.root_cause());
+        println!();
+
+        // A caller that wants to treat one failure specially must downcast
+        for name in ["missing.conf", "syntax.conf", "good.conf"] {
+            match load(Path::new(name)) {
+                Ok(config) => println!("{}: loaded {:?}", name, config.name),
+                Err(error) => match error.downcast_ref::<io::Error>() {
+                    Some(io_error) => println!("{}: I/O error, kind {:?}", name, io_error.kind()),
+                    None => println!("{}: {:#}", name, error),
+                },
+            }
+        }
+    });
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    if let Err(config_loader::hand_rolled::ConfigError::MissingKey(key)) =
+        load(Path::new("no_name.conf"))
+    {
+        println!("missing {}", key);
+    }
+    // error[E0308]: mismatched types (expected `Error`, found `ConfigError`)
+}
+
+/*
+ * Key Concepts:
+ * - anyhow::Result<T> accepts any error through `?`, with no enum to write
+ * - .context() / .with_context() add a line to the chain at each layer
+ * - bail!("...") returns an ad-hoc error; anyhow!("...") makes one
+ * - {:#} prints the chain on one line; {:?} prints a "Caused by:" report
+ * - Callers can't match, only downcast: good for applications, poor for libraries
+ */
+
+// EXPECTED:
+// {}:   invalid config in bad_port.conf
+// {:#}: invalid config in bad_port.conf: `port` must be a number: invalid digit found in string
+// {:?}:
+// invalid config in bad_port.conf
+//
+// Caused by:
+//     0: `port` must be a number
+//     1: invalid digit found in string
+//
+// 0: invalid config in bad_port.conf
+// 1: `port` must be a number
+// 2: invalid digit found in string
+// root cause: invalid digit found in string
+//
+// missing.conf: I/O error, kind NotFound
+// syntax.conf: invalid config in syntax.conf: line 2: expected `key = value`, found "port 8080"
+// good.conf: loaded "demo server"
@@ -0,0 +1,133 @@
+// Error Design 4: Comparing the Three Versions
+// Demonstrates the error chains side by side, and what each design costs
+//
+// Concepts: error chains, library vs application errors
+// Difficulty: intermediate
+// Minutes: 10
+//
+// This is the harness for the chapter: it loads every sample file with all
+// three versions of the loader and prints what a user would see. The
+// hand-rolled and thiserror chains are identical, as they should be. The
+// anyhow chain has one more line on top, the file name, because context
+// is cheap to add; the enums could carry it too, with one more variant or
+// field. Below the chains are the differences a caller feels.
+//
+// The rule of thumb: a library returns an enum (hand-rolled or derived
+// with thiserror), because its callers need to decide what to do about
+// each failure. An application's main() mostly reports errors, so it can
+// use anyhow and spend the effort on good context instead.
+
+use std::mem::size_of;
+use std::path::Path;
+
+use config_loader::{
+    chain, hand_rolled, in_sample_dir, with_anyhow, with_thiserror, Config, CASES,
+};
+
+fn main() {
+    in_sample_dir(|| {
+        for name in CASES {
+            let path = Path::new(name);
+            println!("{}", name);
+            let versions = [
+                (
+                    "hand-rolled",
+                    hand_rolled::load(path).map_err(|e| chain(&e)),
+                ),
+                (
+                    "thiserror",
+                    with_thiserror::load(path).map_err(|e| chain(&e)),
+                ),
+                (
+                    "anyhow",
+                    with_anyhow::load(path)
+                        .map_err(|e| e.chain().map(|cause| cause.to_string()).collect()),
+                ),
+            ];
+            for (version, result) in versions {
+                match result {
+                    Ok(config) => println!("  {:<12} ok, port {}", version, config.port),
+                    Err(messages) => println!("  {:<12} {}", version, messages.join(" -> ")),
+                }
+            }
+        }
+        println!();
+
+        // Can a caller tell a bad port from a missing name?
+        let matchable = matches!(
+            with_thiserror::load(Path::new("no_name.conf")),
+            Err(with_thiserror::ConfigError::MissingKey("name"))
+        );
+        println!("enum: match on MissingKey(\"name\"): {}", matchable);
+        let error = with_anyhow::load(Path::new("no_name.conf")).unwrap_err();
+        println!(
+            "anyhow: only the message says so: {}",
+            error.root_cause().to_string().contains("`name`")
+        );
+    });
+
+    // What every Result pays for its error type, on a 64-bit machine: a
+    // Config is 32 bytes, and anyhow's Result is no bigger
+    println!(
+        "size of Result<Config, hand_rolled::ConfigError>: {} bytes",
+        size_of::<Result<Config, hand_rolled::ConfigError>>()
+    );
+    println!("size of Config: {} bytes", size_of::<Config>());
+    println!(
+        "size of anyhow::Result<Config>: {} bytes",
+        size_of::<anyhow::Result<Config>>()
+    );
+    println!(
+        "size of anyhow::Error: {} bytes",
+        size_of::<anyhow::Error>()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let error: with_thiserror::ConfigError = with_anyhow::load(Path::new("no_name.conf"))
+        .unwrap_err()
+        .into();
+    // error[E0277]: the trait bound `ConfigError: From<anyhow::Error>` is not satisfied
+}
+
+/*
+ * Key Concepts:
+ * - Hand-rolled and thiserror enums behave identically; thiserror is less code
+ * - anyhow makes context easy, so its chains often say more
+ * - Enums can be matched; anyhow errors can only be downcast or printed
+ * - anyhow::Error is one pointer wide, which keeps Result<T> small
+ * - Libraries: an enum (usually thiserror). Applications: anyhow
+ */
+
+// EXPECTED:
+// good.conf
+//   hand-rolled  ok, port 8080
+//   thiserror    ok, port 8080
+//   anyhow       ok, port 8080
+// missing.conf
+//   hand-rolled  cannot read missing.conf -> No such file or directory (os error 2)
+//   thiserror    cannot read missing.conf -> No such file or directory (os error 2)
+//   anyhow       cannot read missing.conf -> No such file or directory (os error 2)
+// syntax.conf
+//   hand-rolled  line 2: expected `key = value`, found "port 8080"
+//   thiserror    line 2: expected `key = value`, found "port 8080"
+//   anyhow       invalid config in syntax.conf -> line 2: expected `key = value`, found "port 8080"
+// bad_port.conf
+//   hand-rolled  `port` must be a number -> invalid digit found in string
+//   thiserror    `port` must be a number -> invalid digit found in string
+//   anyhow       invalid config in bad_port.conf -> `port` must be a number -> invalid digit found in string
+// huge_port.conf
+//   hand-rolled  `port` must be a number -> number too large to fit in target type
+//   thiserror    `port` must be a number -> number too large to fit in target type
+//   anyhow       invalid config in huge_port.conf -> `port` must be a number -> number too large to fit in target type
+// no_name.conf
+//   hand-rolled  required key `name` is missing
+//   thiserror    required key `name` is missing
+//   anyhow       invalid config in no_name.conf -> required key `name` is missing
+//
+// enum: match on MissingKey("name"): true
+// anyhow: only the message says so: true
+// size of Result<Config, hand_rolled::ConfigError>: 40 bytes
+// size of Config: 32 bytes
+// size of anyhow::Result<Config>: 32 bytes
+// size of anyhow::Error: 8 bytes
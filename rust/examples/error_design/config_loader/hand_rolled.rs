@@ -0,0 +1,88 @@
+//! Version 1: everything written out by hand.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
+
+use crate::{split_line, Config};
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Syntax {
+        line: usize,
+        text: String,
+    },
+    BadNumber {
+        key: &'static str,
+        source: ParseIntError,
+    },
+    MissingKey(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, .. } => write!(f, "cannot read {}", path.display()),
+            ConfigError::Syntax { line, text } => {
+                write!(f, "line {}: expected `key = value`, found {:?}", line, text)
+            }
+            ConfigError::BadNumber { key, .. } => write!(f, "`{}` must be a number", key),
+            ConfigError::MissingKey(key) => write!(f, "required key `{}` is missing", key),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            ConfigError::BadNumber { source, .. } => Some(source),
+            ConfigError::Syntax { .. } | ConfigError::MissingKey(_) => None,
+        }
+    }
+}
+
+pub fn load(path: &Path) -> Result<Config, ConfigError> {
+    let text = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse(&text)
+}
+
+pub fn parse(text: &str) -> Result<Config, ConfigError> {
+    let (mut name, mut port, mut workers) = (None, None, None);
+    for (index, line) in text.lines().enumerate() {
+        let pair = split_line(line).map_err(|()| ConfigError::Syntax {
+            line: index + 1,
+            text: line.trim().to_string(),
+        })?;
+        match pair {
+            Some(("name", value)) => name = Some(value.to_string()),
+            Some(("port", value)) => port = Some(number("port", value)?),
+            Some(("workers", value)) => workers = Some(number("workers", value)?),
+            _ => {}
+        }
+    }
+    Ok(Config {
+        name: name.ok_or(ConfigError::MissingKey("name"))?,
+        port: port.ok_or(ConfigError::MissingKey("port"))?,
+        workers: workers.unwrap_or(1),
+    })
+}
+
+fn number<T>(key: &'static str, value: &str) -> Result<T, ConfigError>
+where
+    T: std::str::FromStr<Err = ParseIntError>,
+{
+    value
+        .parse()
+        .map_err(|source| ConfigError::BadNumber { key, source })
+}
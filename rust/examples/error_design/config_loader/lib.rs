@@ -0,0 +1,100 @@
+//! One small config loader, written three times, for the examples in this
+//! chapter.
+//!
+//! It reads `key = value` lines, like `error_handling/06_config_parser.rs`:
+//!
+//! ```text
+//! name = demo server
+//! port = 8080
+//! workers = 4
+//! ```
+//!
+//! Each module has the same `load` function and differs only in how it
+//! reports failures:
+//!
+//! ```text
+//! config_loader/
+//! ├── lib.rs             <- Config, shared by all three
+//! ├── hand_rolled.rs     <- an error enum with Display, Error, and From by hand
+//! ├── with_thiserror.rs  <- the same enum, derived with thiserror
+//! └── with_anyhow.rs     <- no error type at all: anyhow::Error plus context
+//! ```
+
+pub mod hand_rolled;
+pub mod with_anyhow;
+pub mod with_thiserror;
+
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub name: String,
+    pub port: u16,
+    pub workers: u32,
+}
+
+/// Splits a non-blank, non-comment line into its key and value.
+/// Returns Ok(None) for lines to skip and Err(()) for malformed ones, so
+/// each module can turn that into its own kind of error.
+fn split_line(line: &str) -> Result<Option<(&str, &str)>, ()> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let (key, value) = line.split_once('=').ok_or(())?;
+    Ok(Some((key.trim(), value.trim())))
+}
+
+/// The error's own message, followed by each `source()` in turn: the
+/// "error chain" that every version of `load` produces.
+pub fn chain(error: &(dyn Error + 'static)) -> Vec<String> {
+    let mut messages = vec![error.to_string()];
+    let mut current = error.source();
+    while let Some(cause) = current {
+        messages.push(cause.to_string());
+        current = cause.source();
+    }
+    messages
+}
+
+/// The config files the examples load, by name. `missing.conf` is listed
+/// in [`CASES`] but never written, to get an I/O error.
+const SAMPLES: [(&str, &str); 5] = [
+    (
+        "good.conf",
+        "name = demo server\nport = 8080\nworkers = 4\n",
+    ),
+    ("syntax.conf", "name = demo server\nport 8080\n"),
+    ("bad_port.conf", "name = demo server\nport = eighty\n"),
+    ("huge_port.conf", "name = demo server\nport = 70000\n"),
+    ("no_name.conf", "# the name is missing\nport = 8080\n"),
+];
+
+/// Every file the examples try to load, good and bad.
+pub const CASES: [&str; 6] = [
+    "good.conf",
+    "missing.conf",
+    "syntax.conf",
+    "bad_port.conf",
+    "huge_port.conf",
+    "no_name.conf",
+];
+
+/// Writes the sample files to a fresh temporary directory and runs `f`
+/// with that directory as the current one, so the examples can load
+/// `bad_port.conf` by name and print the same paths on every machine.
+pub fn in_sample_dir<T>(f: impl FnOnce() -> T) -> T {
+    let dir: PathBuf = std::env::temp_dir().join(format!("error-design-{}", std::process::id()));
+    fs::create_dir_all(&dir).expect("can create the sample directory");
+    for (name, contents) in SAMPLES {
+        fs::write(dir.join(name), contents).expect("can write a sample file");
+    }
+    let previous = std::env::current_dir().expect("has a current directory");
+    std::env::set_current_dir(&dir).expect("can enter the sample directory");
+    let result = f();
+    std::env::set_current_dir(previous).expect("can go back");
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
@@ -0,0 +1,43 @@
+//! Version 3: no error type. Every failure is an `anyhow::Error`, and each
+//! layer adds a line of context as the error passes through.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::{split_line, Config};
+
+pub fn load(path: &Path) -> Result<Config> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    parse(&text).with_context(|| format!("invalid config in {}", path.display()))
+}
+
+pub fn parse(text: &str) -> Result<Config> {
+    let (mut name, mut port, mut workers) = (None, None, None);
+    for (index, line) in text.lines().enumerate() {
+        let Ok(pair) = split_line(line) else {
+            bail!(
+                "line {}: expected `key = value`, found {:?}",
+                index + 1,
+                line.trim()
+            );
+        };
+        match pair {
+            Some(("name", value)) => name = Some(value.to_string()),
+            Some(("port", value)) => {
+                port = Some(value.parse().context("`port` must be a number")?);
+            }
+            Some(("workers", value)) => {
+                workers = Some(value.parse().context("`workers` must be a number")?);
+            }
+            _ => {}
+        }
+    }
+    Ok(Config {
+        name: name.ok_or_else(|| anyhow!("required key `name` is missing"))?,
+        port: port.context("required key `port` is missing")?,
+        workers: workers.unwrap_or(1),
+    })
+}
@@ -0,0 +1,69 @@
+//! Version 2: the same error enum, with thiserror writing the Display,
+//! Error, and From impls that version 1 spells out.
+
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::{split_line, Config};
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    // A field named `source` is the error's source() automatically
+    #[error("cannot read {}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("line {line}: expected `key = value`, found {text:?}")]
+    Syntax { line: usize, text: String },
+
+    #[error("`{key}` must be a number")]
+    BadNumber {
+        key: &'static str,
+        #[source]
+        cause: ParseIntError,
+    },
+
+    #[error("required key `{0}` is missing")]
+    MissingKey(&'static str),
+}
+
+pub fn load(path: &Path) -> Result<Config, ConfigError> {
+    let text = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse(&text)
+}
+
+pub fn parse(text: &str) -> Result<Config, ConfigError> {
+    let (mut name, mut port, mut workers) = (None, None, None);
+    for (index, line) in text.lines().enumerate() {
+        let pair = split_line(line).map_err(|()| ConfigError::Syntax {
+            line: index + 1,
+            text: line.trim().to_string(),
+        })?;
+        match pair {
+            Some(("name", value)) => name = Some(value.to_string()),
+            Some(("port", value)) => port = Some(number("port", value)?),
+            Some(("workers", value)) => workers = Some(number("workers", value)?),
+            _ => {}
+        }
+    }
+    Ok(Config {
+        name: name.ok_or(ConfigError::MissingKey("name"))?,
+        port: port.ok_or(ConfigError::MissingKey("port"))?,
+        workers: workers.unwrap_or(1),
+    })
+}
+
+fn number<T>(key: &'static str, value: &str) -> Result<T, ConfigError>
+where
+    T: std::str::FromStr<Err = ParseIntError>,
+{
+    value
+        .parse()
+        .map_err(|cause| ConfigError::BadNumber { key, cause })
+}
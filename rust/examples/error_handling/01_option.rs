@@ -0,0 +1,83 @@
+// Error Handling 1: Option - A Value That Might Be Missing
+// Demonstrates Option<T> and its most useful combinators
+//
+// Concepts: Option, Some, None, combinators
+// Difficulty: beginner
+// Minutes: 5
+//
+// Rust has no null. A value that might not exist is an Option:
+//   Some(value) - it's there
+//   None        - it isn't
+
+fn main() {
+    let names = vec!["Ada", "Grace", "Linus"];
+
+    // Many standard methods return Option instead of crashing
+    let first = names.first(); // Option<&&str>
+    let tenth = names.get(10); // None - there is no tenth name
+    println!("first = {:?}, tenth = {:?}", first, tenth);
+
+    // match forces you to handle both cases
+    match find_index(&names, "Grace") {
+        Some(index) => println!("Grace is at index {}", index),
+        None => println!("Grace isn't here"),
+    }
+
+    // if let is shorter when you only care about Some
+    if let Some(index) = find_index(&names, "Linus") {
+        println!("Linus is at index {}", index);
+    }
+
+    // Combinators transform an Option without writing a match
+    let length = names.get(1).map(|name| name.len()); // Some(5)
+    let missing = names.get(9).map(|name| name.len()); // None
+    println!("map: {:?} and {:?}", length, missing);
+
+    // unwrap_or supplies a fallback value
+    println!("unwrap_or: {}", names.get(9).unwrap_or(&"nobody"));
+
+    // and_then chains steps that can each fail
+    let initial = names.first().and_then(|name| name.chars().next());
+    println!("and_then: {:?}", initial);
+
+    // filter keeps the value only if a condition holds
+    let long_name = names.first().filter(|name| name.len() > 3);
+    println!("filter: {:?}", long_name);
+
+    // ok_or turns an Option into a Result with an error message
+    let result: Result<&&str, &str> = names.get(7).ok_or("index out of range");
+    println!("ok_or: {:?}", result);
+
+    // unwrap() panics on None - only use it when None is truly impossible
+    // This would crash at runtime:
+    // let crash = names.get(99).unwrap();
+    // thread 'main' panicked: called `Option::unwrap()` on a `None` value
+}
+
+fn find_index(names: &[&str], wanted: &str) -> Option<usize> {
+    for (index, name) in names.iter().enumerate() {
+        if *name == wanted {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/*
+ * Key Concepts:
+ * - Option<T> is either Some(T) or None - Rust's replacement for null
+ * - The compiler makes you handle None before using the value
+ * - map, and_then, filter, unwrap_or transform Options concisely
+ * - ok_or converts "missing" into an error (a Result)
+ * - unwrap() panics on None; prefer handling it
+ */
+
+// EXPECTED:
+// first = Some("Ada"), tenth = None
+// Grace is at index 1
+// Linus is at index 2
+// map: Some(5) and None
+// unwrap_or: nobody
+// and_then: Some('A')
+// filter: None
+// ok_or: Err("index out of range")
@@ -0,0 +1,91 @@
+// Error Handling 2: Result - An Operation That Might Fail
+// Demonstrates Result<T, E> and its combinators
+//
+// Concepts: Result, Ok, Err, combinators
+// Difficulty: beginner
+// Minutes: 5
+//
+// Result carries either the success value or an explanation of the failure:
+//   Ok(value)  - it worked
+//   Err(error) - it didn't, and here's why
+
+use std::num::ParseIntError;
+
+fn main() {
+    // str::parse returns a Result because the text might not be a number
+    let good: Result<i32, ParseIntError> = "42".parse();
+    let bad: Result<i32, ParseIntError> = "forty-two".parse();
+    println!("good = {:?}", good);
+    println!("bad  = {:?}", bad);
+
+    // match handles both outcomes explicitly
+    match "17".parse::<i32>() {
+        Ok(number) => println!("parsed {}", number),
+        Err(error) => println!("could not parse: {}", error),
+    }
+
+    // map transforms the success value, leaving errors alone
+    let doubled = "21".parse::<i32>().map(|n| n * 2);
+    println!("map: {:?}", doubled);
+
+    // map_err transforms the error, leaving successes alone
+    let friendly = "x".parse::<i32>().map_err(|e| format!("bad input ({})", e));
+    println!("map_err: {:?}", friendly);
+
+    // and_then chains operations that can each fail
+    #[allow(clippy::bind_instead_of_map)] // map would do, since this step can't fail
+    let checked = "250".parse::<i32>().and_then(|n| Ok(n.clamp(0, 100)));
+    println!("and_then: {:?}", checked);
+
+    // unwrap_or_else computes a fallback from the error
+    #[allow(clippy::unnecessary_lazy_evaluations)] // this one ignores the error
+    let value = "oops".parse::<i32>().unwrap_or_else(|_| 0);
+    println!("unwrap_or_else: {}", value);
+
+    // Our own functions can return Result too
+    for input in [10, 0, -3] {
+        match safe_divide(100, input) {
+            Ok(answer) => println!("100 / {} = {}", input, answer),
+            Err(message) => println!("100 / {} failed: {}", input, message),
+        }
+    }
+
+    // .ok() throws the error away and gives an Option
+    println!("ok(): {:?}", safe_divide(1, 0).ok());
+
+    // expect() panics with YOUR message on Err
+    // This would crash at runtime:
+    // let n: i32 = "abc".parse().expect("config value must be a number");
+    // thread 'main' panicked: config value must be a number: ParseIntError { kind: InvalidDigit }
+}
+
+fn safe_divide(a: i32, b: i32) -> Result<i32, String> {
+    if b == 0 {
+        Err(String::from("division by zero"))
+    } else if b < 0 {
+        Err(format!("negative divisor {}", b))
+    } else {
+        Ok(a / b)
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Result<T, E> is Ok(T) or Err(E): failure is part of the type
+ * - You can't accidentally ignore an error - you must unpack the Result
+ * - map / map_err / and_then / unwrap_or_else work like Option's versions
+ * - expect("why") documents why a panic should never happen
+ */
+
+// EXPECTED:
+// good = Ok(42)
+// bad  = Err(ParseIntError { kind: InvalidDigit })
+// parsed 17
+// map: Ok(42)
+// map_err: Err("bad input (invalid digit found in string)")
+// and_then: Ok(100)
+// unwrap_or_else: 0
+// 100 / 10 = 10
+// 100 / 0 failed: division by zero
+// 100 / -3 failed: negative divisor -3
+// ok(): None
@@ -0,0 +1,91 @@
+// Error Handling 4: Converting Errors with From
+// Demonstrates how ? turns one error type into another automatically
+//
+// Concepts: From, ? conversions, error types
+// Difficulty: intermediate
+// Minutes: 5
+
+use std::fmt;
+use std::num::ParseIntError;
+
+// Our function can fail in two different ways...
+#[derive(Debug)]
+enum AgeError {
+    NotANumber(ParseIntError),
+    TooOld(u32),
+}
+
+impl fmt::Display for AgeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AgeError::NotANumber(error) => write!(f, "not a number: {}", error),
+            AgeError::TooOld(age) => write!(f, "{} is not a realistic age", age),
+        }
+    }
+}
+
+// ...so we teach Rust how to turn a ParseIntError into an AgeError.
+// With this impl in place, `?` does the conversion for us.
+impl From<ParseIntError> for AgeError {
+    fn from(error: ParseIntError) -> Self {
+        AgeError::NotANumber(error)
+    }
+}
+
+fn parse_age(text: &str) -> Result<u32, AgeError> {
+    let age: u32 = text.trim().parse()?; // ParseIntError -> AgeError via From
+    if age > 150 {
+        return Err(AgeError::TooOld(age));
+    }
+    Ok(age)
+}
+
+// Without From we'd have to convert by hand at every call site:
+fn parse_age_by_hand(text: &str) -> Result<u32, AgeError> {
+    let age: u32 = text.trim().parse().map_err(AgeError::NotANumber)?;
+    Ok(age)
+}
+
+// From isn't just for errors: it's Rust's general conversion trait
+struct Celsius(f64);
+
+impl From<Celsius> for f64 {
+    fn from(c: Celsius) -> f64 {
+        c.0 * 9.0 / 5.0 + 32.0
+    }
+}
+
+fn main() {
+    for input in ["42", " 7 ", "abc", "200"] {
+        match parse_age(input) {
+            Ok(age) => println!("{:>6?} -> age {}", input, age),
+            Err(error) => println!("{:>6?} -> error: {}", input, error),
+        }
+    }
+    println!("by hand: {:?}", parse_age_by_hand("30").ok());
+
+    // Implementing From also gives you .into() for free
+    let fahrenheit: f64 = Celsius(100.0).into();
+    println!("100°C = {}°F", fahrenheit);
+
+    // Box<dyn Error> accepts ANY error type through From
+    let boxed: Result<u32, Box<dyn std::error::Error>> = "x".parse::<u32>().map_err(Into::into);
+    println!("boxed: {}", boxed.unwrap_err());
+}
+
+/*
+ * Key Concepts:
+ * - `?` calls From::from on the error before returning it
+ * - impl From<TheirError> for MyError lets ? convert automatically
+ * - map_err is the manual alternative for one-off conversions
+ * - Implementing From<A> for B also gives you a.into()
+ */
+
+// EXPECTED:
+// "42" -> age 42
+// " 7 " -> age 7
+// "abc" -> error: not a number: invalid digit found in string
+// "200" -> error: 200 is not a realistic age
+// by hand: Some(30)
+// 100°C = 212°F
+// boxed: invalid digit found in string
@@ -0,0 +1,118 @@
+// Error Handling 5: A Hand-Rolled Error Type
+// Demonstrates an error enum that implements std::error::Error
+//
+// Concepts: custom errors, std::error::Error, Display
+// Difficulty: intermediate
+// Minutes: 10
+
+use std::error::Error;
+use std::fmt;
+use std::num::ParseFloatError;
+
+// One enum describes every way our "bank" can fail
+#[derive(Debug)]
+enum BankError {
+    InsufficientFunds { needed: f64, available: f64 },
+    AccountNotFound(String),
+    InvalidAmount(ParseFloatError),
+}
+
+// Display: the human-readable message
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BankError::InsufficientFunds { needed, available } => write!(
+                f,
+                "insufficient funds: needed ${:.2}, have ${:.2}",
+                needed, available
+            ),
+            BankError::AccountNotFound(name) => write!(f, "no account named '{}'", name),
+            BankError::InvalidAmount(_) => write!(f, "amount is not a valid number"),
+        }
+    }
+}
+
+// Error: marks the type as an error, and optionally exposes the
+// lower-level error that caused it through source()
+impl Error for BankError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BankError::InvalidAmount(cause) => Some(cause),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseFloatError> for BankError {
+    fn from(error: ParseFloatError) -> Self {
+        BankError::InvalidAmount(error)
+    }
+}
+
+struct Account {
+    owner: String,
+    balance: f64,
+}
+
+fn withdraw(accounts: &mut [Account], owner: &str, amount: &str) -> Result<f64, BankError> {
+    let amount: f64 = amount.parse()?;
+    let account = accounts
+        .iter_mut()
+        .find(|account| account.owner == owner)
+        .ok_or_else(|| BankError::AccountNotFound(owner.to_string()))?;
+
+    if account.balance < amount {
+        return Err(BankError::InsufficientFunds {
+            needed: amount,
+            available: account.balance,
+        });
+    }
+    account.balance -= amount;
+    Ok(account.balance)
+}
+
+// Walks the chain of source() errors, like many CLI tools do
+fn report(error: &dyn Error) {
+    println!("error: {}", error);
+    let mut cause = error.source();
+    while let Some(inner) = cause {
+        println!("  caused by: {}", inner);
+        cause = inner.source();
+    }
+}
+
+fn main() {
+    let mut accounts = vec![
+        Account { owner: String::from("alice"), balance: 100.0 },
+        Account { owner: String::from("bob"), balance: 20.0 },
+    ];
+
+    let attempts = [("alice", "30"), ("bob", "50"), ("carol", "5"), ("alice", "ten")];
+    for (owner, amount) in attempts {
+        match withdraw(&mut accounts, owner, amount) {
+            Ok(left) => println!("{} withdrew {}; ${:.2} left", owner, amount, left),
+            Err(error) => report(&error),
+        }
+    }
+
+    // Because BankError implements Error, it fits in a Box<dyn Error>
+    let boxed: Box<dyn Error> = Box::new(BankError::AccountNotFound(String::from("dave")));
+    println!("boxed: {}", boxed);
+}
+
+/*
+ * Key Concepts:
+ * - An error enum lists every failure mode; match can handle each one
+ * - Display gives the message; Debug is derived for developers
+ * - impl Error (with optional source()) makes it a "real" error that
+ *   interoperates with Box<dyn Error> and error-reporting code
+ * - From impls let ? convert lower-level errors into your enum
+ */
+
+// EXPECTED:
+// alice withdrew 30; $70.00 left
+// error: insufficient funds: needed $50.00, have $20.00
+// error: no account named 'carol'
+// error: amount is not a valid number
+//   caused by: invalid float literal
+// boxed: no account named 'dave'
@@ -0,0 +1,182 @@
+// Error Handling 6: Putting It Together - A Config File Parser
+// Demonstrates errors propagating end to end, from file I/O to main
+//
+// Concepts: error propagation, file I/O, parsing, main returning Result
+// Difficulty: intermediate
+// Minutes: 15
+//
+// The program reads `key = value` lines, e.g.
+//     name = demo server
+//     port = 8080
+//     workers = 4
+// and turns them into a typed Config. Every failure - a missing file, a
+// line without '=', a port that isn't a number, a required key that is
+// absent - travels up to main as a ConfigError with context attached.
+//
+// Run it with a path to parse your own file:
+//   ./06_config_parser my_config.txt
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+use std::path::Path;
+
+#[derive(Debug)]
+struct Config {
+    name: String,
+    port: u16,
+    workers: u32,
+}
+
+impl Config {
+    fn summary(&self) -> String {
+        format!(
+            "'{}' on port {} with {} workers",
+            self.name, self.port, self.workers
+        )
+    }
+}
+
+#[derive(Debug)]
+enum ConfigError {
+    Io(io::Error),
+    Syntax { line: usize, text: String },
+    BadNumber { key: String, source: ParseIntError },
+    MissingKey(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(_) => write!(f, "could not read the config file"),
+            ConfigError::Syntax { line, text } => {
+                write!(f, "line {}: expected `key = value`, found {:?}", line, text)
+            }
+            ConfigError::BadNumber { key, .. } => write!(f, "`{}` must be a number", key),
+            ConfigError::MissingKey(key) => write!(f, "required key `{}` is missing", key),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io(error) => Some(error),
+            ConfigError::BadNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        ConfigError::Io(error)
+    }
+}
+
+// Layer 1: file -> text (io::Error becomes ConfigError via ?)
+fn load_config(path: &Path) -> Result<Config, ConfigError> {
+    let text = fs::read_to_string(path)?;
+    parse_config(&text)
+}
+
+// Layer 2: text -> key/value pairs -> Config
+fn parse_config(text: &str) -> Result<Config, ConfigError> {
+    let pairs = parse_pairs(text)?;
+    Ok(Config {
+        name: required(&pairs, "name")?.to_string(),
+        port: number(&pairs, "port")?,
+        workers: number(&pairs, "workers")?,
+    })
+}
+
+fn parse_pairs(text: &str) -> Result<HashMap<&str, &str>, ConfigError> {
+    let mut pairs = HashMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue; // Skip blank lines and comments
+        }
+        let (key, value) = line.split_once('=').ok_or_else(|| ConfigError::Syntax {
+            line: index + 1,
+            text: line.to_string(),
+        })?;
+        pairs.insert(key.trim(), value.trim());
+    }
+    Ok(pairs)
+}
+
+// Layer 3: individual values
+fn required<'a>(pairs: &HashMap<&str, &'a str>, key: &'static str) -> Result<&'a str, ConfigError> {
+    pairs.get(key).copied().ok_or(ConfigError::MissingKey(key))
+}
+
+fn number<T>(pairs: &HashMap<&str, &str>, key: &'static str) -> Result<T, ConfigError>
+where
+    T: std::str::FromStr<Err = ParseIntError>,
+{
+    required(pairs, key)?
+        .parse()
+        .map_err(|source| ConfigError::BadNumber {
+            key: key.to_string(),
+            source,
+        })
+}
+
+// Prints an error and every underlying cause
+fn report(error: &dyn Error) {
+    println!("  error: {}", error);
+    let mut cause = error.source();
+    while let Some(inner) = cause {
+        println!("    caused by: {}", inner);
+        cause = inner.source();
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // With a path argument, parse that file and let ? report failures
+    if let Some(path) = std::env::args().nth(1) {
+        let config = load_config(Path::new(&path))?;
+        println!("{}", config.summary());
+        return Ok(());
+    }
+
+    // Otherwise, demonstrate each failure with sample files
+    let dir = std::env::temp_dir().join("learn_rust_config_parser");
+    fs::create_dir_all(&dir)?;
+    let samples = [
+        ("good.conf", "# demo\nname = demo server\nport = 8080\nworkers = 4\n"),
+        ("syntax.conf", "name = demo\nport 8080\n"),
+        ("number.conf", "name = demo\nport = eighty\nworkers = 4\n"),
+        ("missing.conf", "name = demo\nport = 8080\n"),
+        ("too_big.conf", "name = demo\nport = 99999\nworkers = 1\n"),
+    ];
+    for (file, contents) in samples {
+        fs::write(dir.join(file), contents)?;
+    }
+
+    let mut files: Vec<&str> = samples.iter().map(|(file, _)| *file).collect();
+    files.push("does_not_exist.conf");
+    for file in files {
+        println!("{}:", file);
+        match load_config(&dir.join(file)) {
+            Ok(config) => println!("  ok: {}", config.summary()),
+            Err(error) => report(&error),
+        }
+    }
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - Each layer returns Result and uses ? to pass failures upward
+ * - From<io::Error> lets file errors flow into ConfigError automatically
+ * - map_err / ok_or_else attach context (which key, which line)
+ * - source() preserves the original low-level error for reporting
+ * - main returning Result<(), Box<dyn Error>> ends the chain cleanly
+ */
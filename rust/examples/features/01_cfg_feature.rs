@@ -0,0 +1,106 @@
+// Features 1: Optional Code with cfg(feature)
+// Demonstrates [features] in Cargo.toml, default features, #[cfg(feature = ...)] on items and blocks, and cfg! in expressions
+//
+// Concepts: cargo features, default features, #[cfg(feature)], cfg!
+// Difficulty: intermediate
+// Minutes: 10
+//
+// A feature is a name in the [features] table of Cargo.toml that a build
+// can switch on. Turning one on does two things: it can switch on other
+// features and optional dependencies (`json = ["dep:serde", ...]`), and it
+// passes `--cfg feature="json"` to the compiler, so the code can ask:
+//
+//     #[cfg(feature = "json")]       keep this item (or block) only if on
+//     #[cfg(not(feature = "json"))]  ... only if off
+//     cfg!(feature = "json")         true or false, as an expression
+//
+// The first two remove the code before it's type checked: a module, a
+// function, or a derive that needs serde can only be written that way,
+// since serde isn't there to check it against when the feature is off.
+// cfg! doesn't remove anything; both branches of the `if` must compile
+// either way, and the optimizer drops the dead one.
+//
+// The `default` feature lists what a build gets without asking. Here it
+// is ["json"], so `cargo run --bin 01_cfg_feature` prints the report as
+// JSON too, and `--no-default-features` leaves that out. Features only
+// ever add code; nothing can ask for a feature to be off, which matters
+// in 02_optional_dependencies.
+
+use report::count_words;
+
+const TEXTS: [(&str, &str); 2] = [
+    ("a", "The rain in Spain stays mainly in the plain."),
+    ("b", "In Hartford, Hereford, and Hampshire..."),
+];
+
+fn main() {
+    println!("built with features: {:?}", report::enabled_features());
+
+    let report = count_words("rain", &TEXTS);
+    print!("\n{report}");
+
+    // The json module only exists with the feature, so the code that uses
+    // it has to go too, not just be skipped
+    #[cfg(feature = "json")]
+    {
+        let text = report::json::to_string(&report);
+        println!("\nas JSON ({} bytes), up to the first row:", text.len());
+        for line in text.lines().take(7) {
+            println!("  {line}");
+        }
+        assert_eq!(report::json::from_str(&text).unwrap(), report);
+    }
+    #[cfg(not(feature = "json"))]
+    println!("\nbuilt without `json`: cargo run -p features-examples --bin 01_cfg_feature");
+
+    if cfg!(feature = "async") {
+        println!("\n`async` is on: 02_optional_dependencies counts on tokio tasks");
+    } else {
+        println!("\n`async` is off; --features async turns it on");
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        if cfg!(feature = "async") {
+            let report = report::gather::count_words("rain", &TEXTS);
+        }
+    }
+    // error[E0433]: cannot find `gather` in `report`
+}
+
+/*
+ * Key Concepts:
+ * - A feature is a named switch in [features]; `default` lists the ones a build gets unasked
+ * - Features can turn on optional dependencies (`dep:serde`) and other features
+ * - #[cfg(feature = "x")] removes items and blocks before they're type checked
+ * - cfg!(feature = "x") is a bool: both branches must still compile
+ * - --features adds, --no-default-features removes the defaults; features never subtract
+ */
+
+// EXPECTED:
+// built with features: ["json"]
+//
+// rain (14 words)
+//   in         3
+//   the        2
+//   and        1
+//   hampshire  1
+//   hartford   1
+//   hereford   1
+//   mainly     1
+//   plain      1
+//   rain       1
+//   spain      1
+//   stays      1
+//
+// as JSON (623 bytes), up to the first row:
+//   {
+//     "title": "rain",
+//     "rows": [
+//       {
+//         "word": "in",
+//         "count": 3
+//       },
+//
+// `async` is off; --features async turns it on
@@ -0,0 +1,94 @@
+// Features 2: Optional Dependencies and Feature Unification
+// Demonstrates optional dependencies, cfg-gated main functions, and why features must be additive across a build
+//
+// Concepts: optional dependencies, feature unification, additive features
+// Difficulty: advanced
+// Minutes: 10
+//
+// `tokio = { version = "1", optional = true }` makes tokio a dependency
+// only when something turns it on, here the `async` feature with
+// `async = ["dep:tokio"]`. A build without it doesn't download or compile
+// tokio at all, which is why libraries put their heavier parts behind
+// features: most users never pay for them.
+//
+// Without `async` this file has no tokio to call, so it has two mains,
+// one for each case. Try both:
+//
+//     cargo run -p features-examples --bin 02_optional_dependencies
+//     cargo run -p features-examples --bin 02_optional_dependencies --features async
+//
+// Feature unification. Cargo compiles each crate once per build, with
+// every feature that anything in the build asked for. If one package
+// depends on tokio with ["macros"] and another with ["net"], both get
+// tokio with ["macros", "net"]. That has two consequences:
+//
+// - Features must be *additive*. Turning one on may add code, never change
+//   or remove it, because some other crate in the build can always turn
+//   it on behind your back. A `no-json` feature that removed serde would
+//   break whoever needed it.
+// - A package can seem to work only because of someone else's features.
+//   In this workspace, examples/async asks tokio for "net" and "time";
+//   `cargo build --workspace` compiles tokio once with those on, so code
+//   here calling tokio::time::sleep would build, though this Cargo.toml
+//   asks for neither, then fail when built on its own.
+//
+// `tutor verify features --all-features-matrix` builds this package on its
+// own with every combination of its features, and shows what each one
+// resolves to: --features async also gets `default`, so json, and
+// --no-default-features gets nothing. `cargo tree -e features -i tokio`
+// shows who turned on which feature of a dependency.
+
+use report::count_words;
+
+const TEXTS: [(&str, &str); 3] = [
+    ("a", "one fish two fish"),
+    ("b", "red fish blue fish"),
+    ("c", "one two red blue"),
+];
+
+#[cfg(feature = "async")]
+#[tokio::main]
+async fn main() {
+    let sequential = count_words("fish", &TEXTS);
+    let gathered = report::gather::count_words("fish", &TEXTS).await;
+    print!("{gathered}");
+    println!("\ncounted on {} tokio tasks", TEXTS.len());
+    assert_eq!(gathered, sequential);
+    show_broken();
+}
+
+#[cfg(not(feature = "async"))]
+fn main() {
+    print!("{}", count_words("fish", &TEXTS));
+    println!("\ncounted in a loop: --features async counts on tokio tasks");
+    show_broken();
+}
+
+fn show_broken() {
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        // tokio is only a dependency when `async` turns it on
+        let runtime = tokio::runtime::Runtime::new();
+    }
+    // error[E0433]: cannot find module or crate `tokio` in this scope
+}
+
+/*
+ * Key Concepts:
+ * - `optional = true`, plus a feature that lists "dep:name", makes a dependency opt-in
+ * - #[cfg] can choose between whole functions, even two mains
+ * - Cargo builds each crate once per build, with the union of every feature requested
+ * - So features must be additive: one may add code, never remove or change it
+ * - Build a package alone, with each feature combination, to catch features it forgot to ask for
+ */
+
+// EXPECTED:
+// fish (12 words)
+//   fish       4
+//   blue       2
+//   one        2
+//   red        2
+//   two        2
+//
+// counted in a loop: --features async counts on tokio tasks
@@ -0,0 +1,45 @@
+//! Counting on tokio tasks. Only compiled with the `async` feature, which
+//! also turns on the optional tokio dependency.
+
+use std::collections::BTreeMap;
+
+use tokio::task::JoinSet;
+
+use crate::{add_words, Report};
+
+/// Counts the words of every text on a task of its own, then merges the
+/// counts. The report is the same as [`count_words`](crate::count_words)
+/// gives; only the work is spread out.
+pub async fn count_words(title: &str, texts: &[(&str, &str)]) -> Report {
+    let mut tasks = JoinSet::new();
+    for (_, text) in texts {
+        let text = text.to_string();
+        tasks.spawn(async move {
+            let mut counts = BTreeMap::new();
+            add_words(&mut counts, &text);
+            counts
+        });
+    }
+
+    let mut total = BTreeMap::new();
+    while let Some(counts) = tasks.join_next().await {
+        for (word, count) in counts.expect("counting words doesn't panic") {
+            *total.entry(word).or_insert(0) += count;
+        }
+    }
+    Report::new(title, total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tasks_count_the_same_as_a_loop() {
+        let texts = [("a", "red green red"), ("b", "green blue"), ("c", "red")];
+        assert_eq!(
+            count_words("t", &texts).await,
+            crate::count_words("t", &texts)
+        );
+    }
+}
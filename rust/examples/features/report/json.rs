@@ -0,0 +1,33 @@
+//! Reports as JSON. Only compiled with the `json` feature, which also
+//! turns on the optional serde and serde_json dependencies.
+
+use crate::Report;
+
+/// `report` as pretty-printed JSON.
+pub fn to_string(report: &Report) -> String {
+    // Serializing plain strings and numbers can't fail
+    serde_json::to_string_pretty(report).expect("a report is always valid JSON")
+}
+
+/// Reads a report back from JSON.
+pub fn from_str(text: &str) -> serde_json::Result<Report> {
+    serde_json::from_str(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_round_trip_through_json() {
+        let report = crate::count_words("t", &[("a", "one two two")]);
+        let text = to_string(&report);
+        assert!(text.contains("\"word\": \"two\""));
+        assert_eq!(from_str(&text).unwrap(), report);
+    }
+
+    #[test]
+    fn a_report_without_rows_is_an_error() {
+        assert!(from_str(r#"{"title": "t"}"#).is_err());
+    }
+}
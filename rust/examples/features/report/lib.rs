@@ -0,0 +1,142 @@
+//! Word counts for a few texts, with the parts that need other crates
+//! behind cargo features.
+//!
+//! Everything at the top level compiles with no features at all. The
+//! modules only exist when their feature is on:
+//!
+//! | feature | module   | pulls in          |
+//! |---------|----------|-------------------|
+//! | `json`  | `json`   | serde, serde_json |
+//! | `async` | `gather` | tokio             |
+//!
+//! `json` is a default feature, so `--no-default-features` builds the
+//! library with neither.
+//!
+//! ```
+//! let report = report::count_words("fruit", &[("a", "apple apple pear")]);
+//! assert_eq!(report.total(), 3);
+//! assert_eq!(report.rows[0].word, "apple");
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "async")]
+pub mod gather;
+#[cfg(feature = "json")]
+pub mod json;
+
+/// How often one word appears.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+pub struct Row {
+    pub word: String,
+    pub count: u32,
+}
+
+/// Word counts, most frequent first (ties in alphabetical order).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    pub title: String,
+    pub rows: Vec<Row>,
+}
+
+impl Report {
+    /// A report of `counts`, sorted.
+    pub fn new(title: &str, counts: BTreeMap<String, u32>) -> Self {
+        let mut rows: Vec<Row> = counts
+            .into_iter()
+            .map(|(word, count)| Row { word, count })
+            .collect();
+        // The map iterates alphabetically, and the sort is stable
+        rows.sort_by_key(|row| Reverse(row.count));
+        Report {
+            title: title.to_string(),
+            rows,
+        }
+    }
+
+    /// How many words were counted.
+    pub fn total(&self) -> u32 {
+        self.rows.iter().map(|row| row.count).sum()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({} words)", self.title, self.total())?;
+        for row in &self.rows {
+            writeln!(f, "  {:<10} {}", row.word, row.count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Adds the words of `text`, lowercased and without punctuation, to `counts`.
+pub fn add_words(counts: &mut BTreeMap<String, u32>, text: &str) {
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+    }
+}
+
+/// Counts the words of every text, one after another. (`gather`, with
+/// the `async` feature, does the same on a task per text.)
+pub fn count_words(title: &str, texts: &[(&str, &str)]) -> Report {
+    let mut counts = BTreeMap::new();
+    for (_, text) in texts {
+        add_words(&mut counts, text);
+    }
+    Report::new(title, counts)
+}
+
+/// The features this copy of the library was compiled with.
+pub fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    // cfg! is true or false at compile time; both branches still compile
+    if cfg!(feature = "json") {
+        features.push("json");
+    }
+    if cfg!(feature = "async") {
+        features.push("async");
+    }
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_are_counted_without_case_or_punctuation() {
+        let report = count_words("t", &[("a", "The cat, the hat."), ("b", "THE END")]);
+        assert_eq!(report.total(), 6);
+        assert_eq!(
+            report.rows[0],
+            Row {
+                word: "the".to_string(),
+                count: 3
+            }
+        );
+    }
+
+    #[test]
+    fn ties_are_alphabetical() {
+        let report = count_words("t", &[("a", "pear apple fig apple pear")]);
+        let words: Vec<&str> = report.rows.iter().map(|row| row.word.as_str()).collect();
+        assert_eq!(words, ["apple", "pear", "fig"]);
+    }
+
+    #[test]
+    fn enabled_features_match_the_cfg() {
+        assert_eq!(enabled_features().contains(&"json"), cfg!(feature = "json"));
+        assert_eq!(
+            enabled_features().contains(&"async"),
+            cfg!(feature = "async")
+        );
+    }
+}
@@ -0,0 +1,105 @@
+// FFI 2: Sharing Structs with #[repr(C)]
+// Demonstrates struct layout, passing structs by value and by pointer
+//
+// Concepts: #[repr(C)], struct layout, passing by pointer
+// Difficulty: advanced
+// Minutes: 10
+//
+// Rust is free to reorder a struct's fields and choose its padding.
+// C is not: fields are laid out in order. To share a struct with C,
+// tell Rust to use C's rules with #[repr(C)].
+
+// Matches `typedef struct { double x; double y; } ml_point;` in mathlib.h
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+extern "C" {
+    // By value: C gets its own copy
+    fn ml_distance(a: Point, b: Point) -> f64;
+    // By pointer: C modifies our struct in place
+    fn ml_scale(point: *mut Point, factor: f64);
+}
+
+// Without #[repr(C)], using the struct in an extern block gets a warning,
+// because the layout C expects is not guaranteed:
+#[cfg(feature = "broken")]
+mod broken {
+    #[deny(improper_ctypes)]
+    extern "C" {
+        fn ml_distance(a: RustPoint, b: RustPoint) -> f64;
+    }
+
+    pub struct RustPoint {
+        pub x: f64,
+        pub y: f64,
+    }
+}
+// error: `extern` block uses type `RustPoint`, which is not FFI-safe
+
+// Layout matters even more with mixed field sizes
+#[repr(C)]
+struct CLayout {
+    small: u8,
+    big: u64,
+    tiny: u8,
+}
+
+#[allow(dead_code)]
+struct RustLayout {
+    small: u8,
+    big: u64,
+    tiny: u8,
+}
+
+fn main() {
+    let origin = Point { x: 0.0, y: 0.0 };
+    let mut corner = Point { x: 3.0, y: 4.0 };
+
+    // SAFETY: Point is #[repr(C)] and matches ml_point field for field
+    let distance = unsafe { ml_distance(origin, corner) };
+    println!("distance from {:?} to {:?} = {}", origin, corner, distance);
+
+    // `&mut corner` turns into a *mut Point automatically
+    // SAFETY: the pointer is valid and unaliased for the call, and C
+    // doesn't keep it afterwards
+    unsafe { ml_scale(&mut corner, 2.0) };
+    println!("after ml_scale(2.0): {:?}", corner);
+
+    // C puts fields in order and pads each to its alignment:
+    //   small (1) + padding (7) + big (8) + tiny (1) + padding (7) = 24
+    // Rust reorders to big, small, tiny: 8 + 1 + 1 + padding (6) = 16
+    println!("size of #[repr(C)] struct: {} bytes", size_of::<CLayout>());
+    println!(
+        "size of the default-layout struct: {} bytes",
+        size_of::<RustLayout>()
+    );
+    let c_layout = CLayout {
+        small: 1,
+        big: 2,
+        tiny: 3,
+    };
+    println!(
+        "fields: {} {} {}",
+        c_layout.small, c_layout.big, c_layout.tiny
+    );
+}
+
+/*
+ * Key Concepts:
+ * - #[repr(C)] lays a struct out exactly like C would
+ * - Without it, Rust may reorder fields and the layout is unspecified
+ * - Structs can cross by value (copied) or by pointer (shared)
+ * - &mut T coerces to *mut T when calling a foreign function
+ * - The improper_ctypes lint warns about types C can't understand
+ */
+
+// EXPECTED:
+// distance from Point { x: 0.0, y: 0.0 } to Point { x: 3.0, y: 4.0 } = 5
+// after ml_scale(2.0): Point { x: 6.0, y: 8.0 }
+// size of #[repr(C)] struct: 24 bytes
+// size of the default-layout struct: 16 bytes
+// fields: 1 2 3
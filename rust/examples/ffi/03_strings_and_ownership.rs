@@ -0,0 +1,112 @@
+// FFI 3: Strings and Ownership Across the Boundary
+// Demonstrates CString, CStr, and freeing memory on the side that allocated it
+//
+// Concepts: CString, CStr, ownership across FFI
+// Difficulty: advanced
+// Minutes: 10
+//
+// Rust strings are UTF-8 bytes plus a length. C strings are bytes ending
+// in a 0 byte, with no length. CString (owned) and CStr (borrowed) are the
+// C-shaped versions of String and &str.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+
+extern "C" {
+    fn ml_count_vowels(text: *const c_char) -> usize;
+    fn ml_shout(text: *const c_char) -> *mut c_char;
+    fn ml_free_string(text: *mut c_char);
+}
+
+/// Borrows a Rust string for the duration of one C call.
+fn count_vowels(text: &str) -> usize {
+    // CString::new appends the 0 byte, and fails if `text` contains one
+    // already (C would think the string ended there)
+    let c_text = CString::new(text).expect("text contains a NUL byte");
+    // SAFETY: `c_text` is a valid NUL-terminated string that outlives the
+    // call, and ml_count_vowels only reads it
+    unsafe { ml_count_vowels(c_text.as_ptr()) }
+}
+
+/// A string allocated by C. Dropping it hands the memory back to C.
+///
+/// C's malloc and Rust's allocator are different allocators, so memory
+/// must be freed by whichever side allocated it. Rust must never call
+/// `CString::from_raw` (which frees with Rust's allocator) on this.
+struct CText(NonNull<c_char>);
+
+impl CText {
+    fn as_c_str(&self) -> &CStr {
+        // SAFETY: ml_shout returns a NUL-terminated string, which stays
+        // valid until we free it in Drop
+        unsafe { CStr::from_ptr(self.0.as_ptr()) }
+    }
+}
+
+impl Drop for CText {
+    fn drop(&mut self) {
+        // SAFETY: the pointer came from ml_shout and is freed exactly once
+        unsafe { ml_free_string(self.0.as_ptr()) }
+    }
+}
+
+fn shout(text: &str) -> CText {
+    let c_text = CString::new(text).expect("text contains a NUL byte");
+    // SAFETY: valid NUL-terminated input; the result is ours to free
+    let loud = unsafe { ml_shout(c_text.as_ptr()) };
+    CText(NonNull::new(loud).expect("ml_shout ran out of memory"))
+}
+
+fn main() {
+    // ===== Rust lends a string to C =====
+    println!(
+        "vowels in \"foreign function\": {}",
+        count_vowels("foreign function")
+    );
+
+    // A 0 byte in the middle can't be represented in a C string
+    println!("CString::new(\"a\\0b\") = {:?}", CString::new("a\0b"));
+
+    // ===== C gives a string to Rust =====
+    let loud = shout("hello from rust");
+    // CStr -> &str checks the bytes are UTF-8 (C makes no such promise)
+    println!("ml_shout returned: {:?}", loud.as_c_str().to_str());
+    // to_string_lossy() replaces invalid UTF-8 instead of failing
+    println!("as a String: {}", loud.as_c_str().to_string_lossy());
+    drop(loud); // ml_free_string runs here
+
+    // ===== The classic dangling pointer =====
+    // `CString::new(..).unwrap()` is a temporary, dropped at the end of the
+    // statement, so the pointer dangles before C ever sees it:
+    //   let ptr = CString::new("oops").unwrap().as_ptr();
+    //   unsafe { ml_count_vowels(ptr) }  // reads freed memory
+    // warning: this creates a dangling pointer because temporary `CString` is
+    //          dropped at end of statement
+    // Keep the CString in a variable for as long as C uses the pointer.
+
+    // ===== Rust gives a string to C to keep =====
+    // into_raw() hands ownership to C. It must come back through
+    // CString::from_raw so RUST frees it: the mirror image of CText.
+    let kept = CString::new("owned by C for a while").unwrap().into_raw();
+    // ...C would store and use `kept` here...
+    // SAFETY: `kept` came from CString::into_raw and is reclaimed once
+    let back = unsafe { CString::from_raw(kept) };
+    println!("got it back: {:?}", back);
+}
+
+/*
+ * Key Concepts:
+ * - CString/CStr are the NUL-terminated versions of String/&str
+ * - CString::new fails if the text contains a 0 byte
+ * - Memory is freed by the side that allocated it
+ * - Wrap foreign-owned pointers in a type whose Drop frees them
+ * - into_raw/from_raw move ownership of a Rust string across and back
+ */
+
+// EXPECTED:
+// vowels in "foreign function": 6
+// CString::new("a\0b") = Err(NulError(1, [97, 0, 98]))
+// ml_shout returned: Ok("HELLO FROM RUST!")
+// as a String: HELLO FROM RUST!
+// got it back: "owned by C for a while"
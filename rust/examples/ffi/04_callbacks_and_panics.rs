@@ -0,0 +1,133 @@
+// FFI 4: Callbacks and Panic Safety
+// Demonstrates passing Rust functions and closures to C, and containing panics
+//
+// Concepts: extern "C" fn callbacks, catch_unwind, panics across FFI
+// Difficulty: advanced
+// Minutes: 10
+//
+// C can call back into Rust through a function pointer, as long as the
+// function uses the C calling convention: `extern "C" fn`.
+
+use std::any::Any;
+use std::os::raw::c_void;
+use std::panic::{self, AssertUnwindSafe};
+
+type Visitor = extern "C" fn(value: i32, user_data: *mut c_void);
+
+extern "C" {
+    fn ml_for_each(values: *const i32, len: usize, callback: Visitor, user_data: *mut c_void);
+}
+
+// ===== A plain callback =====
+// C passes `user_data` back to us untouched; here it points at a total
+extern "C" fn add_to_total(value: i32, user_data: *mut c_void) {
+    // SAFETY: main() passes a pointer to a live i64 as user_data
+    let total = unsafe { &mut *user_data.cast::<i64>() };
+    *total += value as i64;
+}
+
+// ===== Closures through a trampoline =====
+// C only understands function pointers, not closures. A generic
+// "trampoline" function casts user_data back to the closure and calls it.
+
+/// What the trampoline needs: the closure, plus a slot for a panic.
+struct CallbackState<F> {
+    callback: F,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+extern "C" fn trampoline<F: FnMut(i32)>(value: i32, user_data: *mut c_void) {
+    // SAFETY: for_each() passes a pointer to a live CallbackState<F>
+    let state = unsafe { &mut *user_data.cast::<CallbackState<F>>() };
+    if state.panic.is_some() {
+        return; // an earlier call panicked: skip the rest
+    }
+    // A panic must NOT unwind out of an `extern "C" fn` into C: C has no
+    // idea how to clean up its stack frames. Rust aborts the whole process
+    // if that happens. So catch the panic here and re-raise it later, on
+    // the Rust side of the boundary.
+    let callback = &mut state.callback;
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| callback(value))) {
+        state.panic = Some(payload);
+    }
+}
+
+/// Calls `callback` on every value, via C. Safe to use with any closure,
+/// including one that panics.
+fn for_each<F: FnMut(i32)>(values: &[i32], callback: F) {
+    let mut state = CallbackState {
+        callback,
+        panic: None,
+    };
+    // SAFETY: the values pointer/length come from a slice; `state` outlives
+    // the call, and trampoline::<F> interprets user_data as exactly this type
+    unsafe {
+        ml_for_each(
+            values.as_ptr(),
+            values.len(),
+            trampoline::<F>,
+            (&raw mut state).cast(),
+        );
+    }
+    // Back in Rust: it's safe to continue the panic now
+    if let Some(payload) = state.panic {
+        panic::resume_unwind(payload);
+    }
+}
+
+fn main() {
+    let values = [1, 2, 3, 4];
+
+    let mut total: i64 = 0;
+    // SAFETY: add_to_total expects user_data to point at an i64, and it does
+    unsafe {
+        ml_for_each(
+            values.as_ptr(),
+            values.len(),
+            add_to_total,
+            (&raw mut total).cast(),
+        );
+    }
+    println!("total via a plain callback: {}", total);
+
+    // Closures, including ones that capture, work through for_each
+    let mut seen = Vec::new();
+    for_each(&values, |value| seen.push(value * 10));
+    println!("collected by a closure: {:?}", seen);
+
+    // A panicking callback comes back out as an ordinary Rust panic.
+    // (The hook just hides the default panic message for the demo.)
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(|| {
+        for_each(&[5, -1, 7], |value| {
+            if value < 0 {
+                panic!("negative value {}", value);
+            }
+        })
+    });
+    let _ = panic::take_hook();
+    let message = result
+        .unwrap_err()
+        .downcast::<String>()
+        .map(|message| *message)
+        .unwrap_or_default();
+    println!("the panic crossed back safely: {:?}", message);
+
+    // Without the catch_unwind in the trampoline, the same panic would
+    // reach C's stack frames, and Rust would abort instead:
+    //   panic in a function that cannot unwind
+}
+
+/*
+ * Key Concepts:
+ * - Callbacks for C must be `extern "C" fn`
+ * - A void* user_data parameter carries Rust state through C
+ * - A generic trampoline lets C call Rust closures
+ * - Panics must never unwind into C; catch them at the boundary
+ * - resume_unwind re-raises a caught panic on the Rust side
+ */
+
+// EXPECTED:
+// total via a plain callback: 10
+// collected by a closure: [10, 20, 30, 40]
+// the panic crossed back safely: "negative value -1"
@@ -0,0 +1,22 @@
+// Build script: cargo compiles and runs this BEFORE building the examples.
+//
+// It compiles c/mathlib.c into libmathlib.a in cargo's output directory
+// and prints the `cargo:rustc-link-lib=static=mathlib` line that tells
+// rustc to link it, so the `extern "C"` blocks in the examples resolve.
+
+fn main() {
+    cc::Build::new()
+        .file("c/mathlib.c")
+        .warnings(true)
+        .compile("mathlib");
+
+    // The C library uses sqrt() from libm, which isn't linked by default
+    // on Unix. Build scripts run on the HOST, so ask cargo about the TARGET.
+    if std::env::var("CARGO_CFG_TARGET_FAMILY").as_deref() == Ok("unix") {
+        println!("cargo:rustc-link-lib=m");
+    }
+
+    // Only rebuild the C code when it changes
+    println!("cargo:rerun-if-changed=c/mathlib.c");
+    println!("cargo:rerun-if-changed=c/mathlib.h");
+}
@@ -0,0 +1,64 @@
+#include "mathlib.h"
+
+#include <ctype.h>
+#include <math.h>
+#include <stdlib.h>
+#include <string.h>
+
+int32_t ml_add(int32_t a, int32_t b) {
+    return a + b;
+}
+
+double ml_average(const double *values, size_t len) {
+    if (len == 0) {
+        return 0.0;
+    }
+    double total = 0.0;
+    for (size_t i = 0; i < len; i++) {
+        total += values[i];
+    }
+    return total / (double)len;
+}
+
+double ml_distance(ml_point a, ml_point b) {
+    return sqrt((a.x - b.x) * (a.x - b.x) + (a.y - b.y) * (a.y - b.y));
+}
+
+void ml_scale(ml_point *point, double factor) {
+    point->x *= factor;
+    point->y *= factor;
+}
+
+size_t ml_count_vowels(const char *text) {
+    size_t count = 0;
+    for (; *text != '\0'; text++) {
+        if (strchr("aeiouAEIOU", *text) != NULL) {
+            count++;
+        }
+    }
+    return count;
+}
+
+char *ml_shout(const char *text) {
+    size_t len = strlen(text);
+    char *loud = malloc(len + 2);
+    if (loud == NULL) {
+        return NULL;
+    }
+    for (size_t i = 0; i < len; i++) {
+        loud[i] = (char)toupper((unsigned char)text[i]);
+    }
+    loud[len] = '!';
+    loud[len + 1] = '\0';
+    return loud;
+}
+
+void ml_free_string(char *text) {
+    free(text);
+}
+
+void ml_for_each(const int32_t *values, size_t len, ml_visitor callback, void *user_data) {
+    for (size_t i = 0; i < len; i++) {
+        callback(values[i], user_data);
+    }
+}
@@ -0,0 +1,32 @@
+/* A small C library for the FFI chapter. build.rs compiles it with the
+ * `cc` crate and links it into every example in this folder. */
+#ifndef MATHLIB_H
+#define MATHLIB_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+/* Plain numbers */
+int32_t ml_add(int32_t a, int32_t b);
+double ml_average(const double *values, size_t len);
+
+/* A struct passed by value and by pointer. Rust mirrors it with #[repr(C)]. */
+typedef struct {
+    double x;
+    double y;
+} ml_point;
+
+double ml_distance(ml_point a, ml_point b);
+void ml_scale(ml_point *point, double factor);
+
+/* Strings. ml_count_vowels only borrows its argument. ml_shout returns a
+ * NEW string that the caller owns and must give back with ml_free_string. */
+size_t ml_count_vowels(const char *text);
+char *ml_shout(const char *text);
+void ml_free_string(char *text);
+
+/* Callbacks: calls `callback(values[i], user_data)` for every element. */
+typedef void (*ml_visitor)(int32_t value, void *user_data);
+void ml_for_each(const int32_t *values, size_t len, ml_visitor callback, void *user_data);
+
+#endif
@@ -0,0 +1,38 @@
+/* A C program that calls into the Rust library in ../src/lib.rs. */
+#include <stdio.h>
+
+#include "rust_from_c.h"
+
+int main(void) {
+    int32_t values[] = {4, -2, 9, 1};
+    size_t len = sizeof values / sizeof values[0];
+
+    printf("sum: %lld\n", (long long)rfc_sum(values, len));
+
+    RfcStats stats = rfc_stats(values, len);
+    printf("stats: count=%zu min=%d max=%d\n", stats.count, stats.min, stats.max);
+
+    /* Rust allocates the counter; C only holds the pointer */
+    RfcCounter *counter = rfc_counter_new();
+    for (size_t i = 0; i < len; i++) {
+        rfc_counter_add(counter, values[i]);
+    }
+    printf("counter: total=%lld after %u additions\n",
+           (long long)rfc_counter_total(counter), rfc_counter_additions(counter));
+    /* ...and Rust frees it */
+    rfc_counter_free(counter);
+
+    char *greeting = rfc_greet("C");
+    printf("greeting: %s\n", greeting);
+    rfc_string_free(greeting);
+
+    int32_t quotient = 0;
+    int32_t status = rfc_divide(10, 3, &quotient);
+    printf("10 / 3: status=%d quotient=%d\n", status, quotient);
+    /* Rust panics on division by zero; the library turns that into a code
+     * instead of crashing this program */
+    status = rfc_divide(10, 0, &quotient);
+    printf("10 / 0: status=%d (RFC_PANIC is %d)\n", status, RFC_PANIC);
+
+    return 0;
+}
@@ -0,0 +1,41 @@
+/* C declarations for the functions in ../src/lib.rs. Written by hand to
+ * match the Rust signatures exactly; tools like cbindgen can generate this
+ * file instead. */
+#ifndef RUST_FROM_C_H
+#define RUST_FROM_C_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define RFC_OK 0
+#define RFC_NULL_POINTER (-1)
+#define RFC_PANIC (-2)
+
+int64_t rfc_sum(const int32_t *values, size_t len);
+
+/* Matches the #[repr(C)] struct RfcStats */
+typedef struct {
+    size_t count;
+    int32_t min;
+    int32_t max;
+} RfcStats;
+
+RfcStats rfc_stats(const int32_t *values, size_t len);
+
+/* Opaque: only Rust knows what is inside */
+typedef struct RfcCounter RfcCounter;
+
+RfcCounter *rfc_counter_new(void);
+int32_t rfc_counter_add(RfcCounter *counter, int64_t amount);
+int64_t rfc_counter_total(const RfcCounter *counter);
+uint32_t rfc_counter_additions(const RfcCounter *counter);
+void rfc_counter_free(RfcCounter *counter);
+
+/* The returned string belongs to the caller: free it with rfc_string_free,
+ * never with free() */
+char *rfc_greet(const char *name);
+void rfc_string_free(char *text);
+
+int32_t rfc_divide(int32_t a, int32_t b, int32_t *out);
+
+#endif
@@ -0,0 +1,265 @@
+//! A Rust library with a C interface, for the C program in `c/main.c`.
+//!
+//! Every function here is `extern "C"` (C calling convention) and
+//! `#[no_mangle]` (keep the exact name, so C's linker can find it). The
+//! matching declarations are in `c/rust_from_c.h`, written by hand; for
+//! bigger libraries the `cbindgen` tool generates the header from the Rust
+//! code.
+//!
+//! The rules this library follows at the boundary:
+//! - Only C-compatible types cross: integers, floats, raw pointers, and
+//!   `#[repr(C)]` structs.
+//! - Memory is freed by the side that allocated it, so everything Rust
+//!   hands out has a matching `rfc_*_free` function.
+//! - Null pointers are checked, never trusted.
+//! - Panics never unwind into C; they are caught and turned into an
+//!   error code.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic;
+
+/// Status codes returned by functions that can fail.
+pub const RFC_OK: i32 = 0;
+pub const RFC_NULL_POINTER: i32 = -1;
+pub const RFC_PANIC: i32 = -2;
+
+/// Adds up `len` integers.
+///
+/// # Safety
+///
+/// `values` must point at `len` initialized `int32_t`s, or be null (which
+/// counts as empty).
+#[no_mangle]
+pub unsafe extern "C" fn rfc_sum(values: *const i32, len: usize) -> i64 {
+    if values.is_null() {
+        return 0;
+    }
+    // SAFETY: the caller promised `len` readable values
+    let values = unsafe { std::slice::from_raw_parts(values, len) };
+    values.iter().map(|&value| value as i64).sum()
+}
+
+/// Summary statistics, returned to C by value.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RfcStats {
+    pub count: usize,
+    pub min: i32,
+    pub max: i32,
+}
+
+/// Counts, minimum and maximum of `len` integers. For no values, `count`
+/// is 0 and `min`/`max` are 0 too.
+///
+/// # Safety
+///
+/// Same as [`rfc_sum`].
+#[no_mangle]
+pub unsafe extern "C" fn rfc_stats(values: *const i32, len: usize) -> RfcStats {
+    if values.is_null() || len == 0 {
+        return RfcStats {
+            count: 0,
+            min: 0,
+            max: 0,
+        };
+    }
+    // SAFETY: the caller promised `len` readable values
+    let values = unsafe { std::slice::from_raw_parts(values, len) };
+    RfcStats {
+        count: len,
+        min: *values.iter().min().unwrap(),
+        max: *values.iter().max().unwrap(),
+    }
+}
+
+// ===== An opaque handle =====
+
+/// A running total. C never sees inside it: it only holds a pointer and
+/// passes it back to the `rfc_counter_*` functions. (The header declares
+/// `typedef struct RfcCounter RfcCounter;` with no fields.)
+pub struct RfcCounter {
+    total: i64,
+    additions: u32,
+}
+
+/// Creates a counter. The caller owns it and must free it with
+/// [`rfc_counter_free`].
+#[no_mangle]
+pub extern "C" fn rfc_counter_new() -> *mut RfcCounter {
+    // Box::into_raw gives up Rust's ownership: nothing frees the counter
+    // until rfc_counter_free turns the pointer back into a Box.
+    Box::into_raw(Box::new(RfcCounter {
+        total: 0,
+        additions: 0,
+    }))
+}
+
+/// Adds `amount` to the counter.
+///
+/// # Safety
+///
+/// `counter` must come from [`rfc_counter_new`] and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rfc_counter_add(counter: *mut RfcCounter, amount: i64) -> i32 {
+    // SAFETY: the caller promised a live counter (or null, checked here)
+    let Some(counter) = (unsafe { counter.as_mut() }) else {
+        return RFC_NULL_POINTER;
+    };
+    counter.total += amount;
+    counter.additions += 1;
+    RFC_OK
+}
+
+/// The counter's total, or 0 for a null counter.
+///
+/// # Safety
+///
+/// Same as [`rfc_counter_add`].
+#[no_mangle]
+pub unsafe extern "C" fn rfc_counter_total(counter: *const RfcCounter) -> i64 {
+    // SAFETY: the caller promised a live counter (or null)
+    unsafe { counter.as_ref() }.map_or(0, |counter| counter.total)
+}
+
+/// How many times [`rfc_counter_add`] succeeded on this counter.
+///
+/// # Safety
+///
+/// Same as [`rfc_counter_add`].
+#[no_mangle]
+pub unsafe extern "C" fn rfc_counter_additions(counter: *const RfcCounter) -> u32 {
+    // SAFETY: the caller promised a live counter (or null)
+    unsafe { counter.as_ref() }.map_or(0, |counter| counter.additions)
+}
+
+/// Frees a counter. Passing null does nothing, like C's `free`.
+///
+/// # Safety
+///
+/// `counter` must come from [`rfc_counter_new`] and must not be used, or
+/// freed again, afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rfc_counter_free(counter: *mut RfcCounter) {
+    if !counter.is_null() {
+        // SAFETY: the pointer came from Box::into_raw and is freed once
+        drop(unsafe { Box::from_raw(counter) });
+    }
+}
+
+// ===== Strings =====
+
+/// Returns a new string "Hello, <name>!" that the caller owns and must
+/// free with [`rfc_string_free`]. Returns null if `name` is null or not
+/// valid UTF-8.
+///
+/// # Safety
+///
+/// `name` must be a NUL-terminated string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn rfc_greet(name: *const c_char) -> *mut c_char {
+    if name.is_null() {
+        return std::ptr::null_mut();
+    }
+    // SAFETY: the caller promised a NUL-terminated string
+    let Ok(name) = unsafe { CStr::from_ptr(name) }.to_str() else {
+        return std::ptr::null_mut();
+    };
+    // The name can't contain a NUL byte (C would have stopped there), so
+    // CString::new can't fail
+    CString::new(format!("Hello, {name}!"))
+        .expect("no interior NUL")
+        .into_raw()
+}
+
+/// Frees a string returned by this library. Passing null does nothing.
+///
+/// Strings from C's `malloc` must NOT be passed here: Rust's allocator
+/// and C's are not the same.
+///
+/// # Safety
+///
+/// `text` must come from this library and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rfc_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: the pointer came from CString::into_raw and is freed once
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+// ===== Panic safety =====
+
+/// Divides `a` by `b`, writing the result to `*out`.
+///
+/// Integer division panics in Rust when `b` is 0 (or for `i32::MIN / -1`).
+/// Unwinding into C would abort the whole program, so the division runs
+/// inside `catch_unwind` and a panic becomes [`RFC_PANIC`].
+///
+/// # Safety
+///
+/// `out` must be a valid pointer to an `int32_t`, or null.
+#[no_mangle]
+pub unsafe extern "C" fn rfc_divide(a: i32, b: i32, out: *mut i32) -> i32 {
+    if out.is_null() {
+        return RFC_NULL_POINTER;
+    }
+    match panic::catch_unwind(|| a / b) {
+        Ok(quotient) => {
+            // SAFETY: checked non-null above; the caller promised it's valid
+            unsafe { out.write(quotient) };
+            RFC_OK
+        }
+        Err(_) => RFC_PANIC,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_round_trip() {
+        let counter = rfc_counter_new();
+        unsafe {
+            assert_eq!(rfc_counter_add(counter, 5), RFC_OK);
+            assert_eq!(rfc_counter_add(counter, -2), RFC_OK);
+            assert_eq!(rfc_counter_total(counter), 3);
+            assert_eq!(rfc_counter_additions(counter), 2);
+            rfc_counter_free(counter);
+        }
+    }
+
+    #[test]
+    fn null_pointers_are_rejected() {
+        unsafe {
+            assert_eq!(rfc_sum(std::ptr::null(), 3), 0);
+            assert_eq!(rfc_counter_add(std::ptr::null_mut(), 1), RFC_NULL_POINTER);
+            assert!(rfc_greet(std::ptr::null()).is_null());
+            assert_eq!(rfc_divide(1, 1, std::ptr::null_mut()), RFC_NULL_POINTER);
+            rfc_counter_free(std::ptr::null_mut());
+            rfc_string_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn greeting_is_owned_by_the_caller() {
+        let name = CString::new("Ferris").unwrap();
+        unsafe {
+            let greeting = rfc_greet(name.as_ptr());
+            assert_eq!(CStr::from_ptr(greeting).to_str(), Ok("Hello, Ferris!"));
+            rfc_string_free(greeting);
+        }
+    }
+
+    #[test]
+    fn panics_become_error_codes() {
+        let mut out = 0;
+        unsafe {
+            assert_eq!(rfc_divide(7, 2, &mut out), RFC_OK);
+            assert_eq!(out, 3);
+            assert_eq!(rfc_divide(7, 0, &mut out), RFC_PANIC);
+            assert_eq!(out, 3, "a failed call leaves *out alone");
+        }
+    }
+}
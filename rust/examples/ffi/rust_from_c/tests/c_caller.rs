@@ -0,0 +1,61 @@
+// Builds c/main.c against the cdylib and checks what it prints: the real
+// C-calls-Rust round trip. Needs a C compiler (`cc`) on the PATH.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Builds the shared library and returns the directory it is in.
+///
+/// `cargo test` only builds the Rust (rlib) form of the library, so build
+/// the cdylib separately, in its own target directory: the outer cargo
+/// still holds the lock on the normal one.
+fn build_cdylib() -> PathBuf {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cdylib");
+    let built = Command::new(env!("CARGO"))
+        .args(["build", "--quiet", "--lib", "--manifest-path"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("cannot run cargo");
+    assert!(built.success(), "building the cdylib failed");
+    target_dir.join("debug")
+}
+
+#[test]
+#[cfg(unix)]
+fn c_program_calls_the_rust_library() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = build_cdylib();
+    let program = lib_dir.join("rust_from_c_main");
+
+    let compiled = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(crate_dir.join("c/main.c"))
+        .arg("-I")
+        .arg(crate_dir.join("c"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-lrust_from_c")
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .expect("cannot run the C compiler");
+    assert!(compiled.success(), "c/main.c failed to compile");
+
+    let output = Command::new(&program)
+        .output()
+        .expect("cannot run the C program");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "sum: 12\n\
+         stats: count=4 min=-2 max=9\n\
+         counter: total=12 after 4 additions\n\
+         greeting: Hello, C!\n\
+         10 / 3: status=0 quotient=3\n\
+         10 / 0: status=-2 (RFC_PANIC is -2)\n"
+    );
+    // The panic message itself goes to stderr, like any Rust panic
+    assert!(String::from_utf8_lossy(&output.stderr).contains("attempt to divide by zero"));
+}
@@ -0,0 +1,107 @@
+// File I/O 1: Reading and Writing Files
+// Demonstrates fs::read_to_string, fs::write, OpenOptions, and io::Error kinds
+//
+// Concepts: fs::read_to_string, fs::write, OpenOptions, io::ErrorKind
+// Difficulty: beginner
+// Minutes: 10
+//
+// For a small file, reading or writing it whole is the simplest thing: one
+// call, and the file is opened, used, and closed. Everything can fail (the
+// file is missing, the disk is full, we lack permission), so every call
+// returns io::Result, and io::Error::kind() says what went wrong.
+
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::PathBuf;
+
+fn main() -> io::Result<()> {
+    // A scratch directory of our own, so we never touch a real file
+    let dir: PathBuf = env::temp_dir().join(format!("file-io-01-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let notes = dir.join("notes.txt");
+
+    // fs::write creates the file, or replaces what was in it
+    fs::write(&notes, "first line\nsecond line\n")?;
+    let text = fs::read_to_string(&notes)?;
+    println!("read {} bytes, {} lines", text.len(), text.lines().count());
+
+    // OpenOptions picks how to open: here, add to the end instead of
+    // replacing
+    let mut file = OpenOptions::new().append(true).open(&notes)?;
+    writeln!(file, "third line")?;
+    // The file is closed when `file` goes out of scope; drop closes it now
+    drop(file);
+    println!(
+        "last line: {:?}",
+        fs::read_to_string(&notes)?.lines().last()
+    );
+
+    // create_new refuses to overwrite: a safe way to claim a file name
+    match OpenOptions::new().write(true).create_new(true).open(&notes) {
+        Ok(_) => println!("created a new file?!"),
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+            println!("create_new: the file already exists")
+        }
+        Err(err) => return Err(err),
+    }
+
+    // Not every file is text. fs::read gives the raw bytes, and
+    // read_to_string fails on bytes that aren't UTF-8.
+    let image = dir.join("pixels.bin");
+    fs::write(&image, [0x89, b'P', b'N', b'G', 0xff, 0x00])?;
+    println!("bytes: {:?}", fs::read(&image)?);
+    let err = fs::read_to_string(&image).unwrap_err();
+    println!("as text: {:?}", err.kind());
+
+    // File::open + read_to_end is what fs::read does, by hand
+    let mut bytes = Vec::new();
+    File::open(&image)?.read_to_end(&mut bytes)?;
+    println!("read_to_end got {} bytes", bytes.len());
+
+    // The most common error: the file isn't there. Match on kind() to
+    // handle it and pass every other error on.
+    let config = match fs::read_to_string(dir.join("missing.toml")) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::from("(defaults)"),
+        Err(err) => return Err(err),
+    };
+    println!("config: {}", config);
+
+    // The error message alone doesn't say which file it was about; add the
+    // path yourself when you report it
+    if let Err(err) = fs::read("no/such/dir/file.txt") {
+        println!("cannot read no/such/dir/file.txt: {:?}", err.kind());
+    }
+
+    // Metadata without reading the contents
+    let metadata = fs::metadata(&notes)?;
+    println!(
+        "notes.txt: {} bytes, is_file = {}",
+        metadata.len(),
+        metadata.is_file()
+    );
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - fs::read_to_string / fs::read / fs::write handle a whole file at once
+ * - OpenOptions chooses append, create, create_new, truncate...
+ * - Files close when they're dropped
+ * - io::Error::kind() tells NotFound from AlreadyExists from the rest
+ * - Error messages don't include the path, so add it when reporting
+ */
+
+// EXPECTED:
+// read 23 bytes, 2 lines
+// last line: Some("third line")
+// create_new: the file already exists
+// bytes: [137, 80, 78, 71, 255, 0]
+// as text: InvalidData
+// read_to_end got 6 bytes
+// config: (defaults)
+// cannot read no/such/dir/file.txt: NotFound
+// notes.txt: 34 bytes, is_file = true
@@ -0,0 +1,106 @@
+// File I/O 2: Buffered Reading and Writing
+// Demonstrates BufReader, BufWriter, lines(), and read_line for big files
+//
+// Concepts: BufReader, BufWriter, lines, read_line
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Every read or write on a File is a system call, which is slow next to
+// ordinary code. A BufReader fetches a big block at a time and hands it
+// out in small pieces; a BufWriter collects small writes and sends them in
+// big blocks. Reading line by line also means a huge file never has to fit
+// in memory.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+
+fn main() -> io::Result<()> {
+    let dir = env::temp_dir().join(format!("file-io-02-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("access.log");
+
+    // Writing 100,000 lines: without the BufWriter, that's 100,000 system
+    // calls; with it, a few dozen
+    let mut out = BufWriter::new(File::create(&path)?);
+    for i in 0..100_000 {
+        let status = if i % 1000 == 0 { 500 } else { 200 };
+        writeln!(out, "GET /page/{} {}", i % 50, status)?;
+    }
+    // Dropping a BufWriter flushes what's left, but can't report an error
+    // from doing so. Flush explicitly to find out.
+    out.flush()?;
+    drop(out);
+    println!("log is {} bytes", fs::metadata(&path)?.len());
+
+    // lines() yields one io::Result<String> per line, without the '\n'
+    let reader = BufReader::new(File::open(&path)?);
+    let mut errors = 0;
+    for line in reader.lines() {
+        let line = line?;
+        if line.ends_with(" 500") {
+            errors += 1;
+        }
+    }
+    println!("server errors: {}", errors);
+
+    // lines() allocates a new String for every line. read_line appends to
+    // a String we own, so one buffer is reused for the whole file.
+    let mut reader = BufReader::new(File::open(&path)?);
+    let mut line = String::new();
+    let mut longest = 0;
+    loop {
+        line.clear();
+        // 0 bytes read means the end of the file
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        longest = longest.max(line.trim_end().len());
+    }
+    println!("longest line: {} characters", longest);
+
+    // BufRead works on anything buffered, not only files. A byte slice is
+    // one, which makes line-based code easy to test.
+    let words = count_words("one two\nthree\n\nfour five six\n".as_bytes())?;
+    println!("words per line: {:?}", words);
+    let words = count_words(BufReader::new(File::open(&path)?))?;
+    println!("words in the log: {}", words.iter().sum::<usize>());
+
+    // write_report only needs Write, so a Vec<u8> can stand in for a file
+    let mut report = Vec::new();
+    write_report(&mut report, errors)?;
+    print!("{}", String::from_utf8_lossy(&report));
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/// Counts the words on each line of any buffered reader.
+fn count_words(reader: impl BufRead) -> io::Result<Vec<usize>> {
+    reader
+        .lines()
+        .map(|line| line.map(|line| line.split_whitespace().count()))
+        .collect()
+}
+
+fn write_report(out: &mut impl Write, errors: usize) -> io::Result<()> {
+    writeln!(out, "== report ==")?;
+    writeln!(out, "{} errors", errors)
+}
+
+/*
+ * Key Concepts:
+ * - BufReader and BufWriter turn many small reads/writes into few big ones
+ * - lines() streams a file line by line; read_line reuses one String
+ * - Call flush() on a BufWriter to see errors from the last write
+ * - Code written against BufRead / Write works on files, slices, and Vecs
+ */
+
+// EXPECTED:
+// log is 1680000 bytes
+// server errors: 100
+// longest line: 16 characters
+// words per line: [2, 1, 0, 3]
+// words in the log: 300000
+// == report ==
+// 100 errors
@@ -0,0 +1,149 @@
+// File I/O 3: Walking Directories
+// Demonstrates fs::read_dir, recursion over a tree, and symlink-safe metadata
+//
+// Concepts: fs::read_dir, recursion, symlink_metadata
+// Difficulty: intermediate
+// Minutes: 15
+//
+// fs::read_dir lists one directory. To see a whole tree we recurse into
+// every subdirectory we find. Two details matter: read_dir returns entries
+// in no particular order (sort them if the order matters), and a symbolic
+// link can point back up the tree, so a walker that follows links can loop
+// forever.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Builds a small project-like tree to walk.
+fn make_tree(root: &Path) -> io::Result<()> {
+    fs::create_dir_all(root.join("src/bin"))?;
+    fs::create_dir_all(root.join("docs"))?;
+    fs::create_dir_all(root.join("target/debug"))?;
+    fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n")?;
+    fs::write(root.join("src/main.rs"), "fn main() {}\n")?;
+    fs::write(
+        root.join("src/lib.rs"),
+        "pub fn answer() -> u32 {\n    42\n}\n",
+    )?;
+    fs::write(root.join("src/bin/tool.rs"), "fn main() {}\n")?;
+    fs::write(root.join("docs/guide.md"), "# Guide\n")?;
+    fs::write(root.join("target/debug/demo"), vec![0u8; 4096])?;
+    Ok(())
+}
+
+/// The entries of `dir`, sorted by name so the output is the same on every
+/// system.
+fn sorted_entries(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// Prints the tree under `dir` like the `tree` command.
+fn print_tree(dir: &Path, depth: usize) -> io::Result<()> {
+    for path in sorted_entries(dir)? {
+        let name = path.file_name().unwrap().to_string_lossy();
+        // symlink_metadata describes a link itself; fs::metadata would
+        // follow it. Walkers should not follow links by default.
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.is_dir() {
+            println!("{}{}/", "  ".repeat(depth), name);
+            print_tree(&path, depth + 1)?;
+        } else {
+            println!("{}{} ({} bytes)", "  ".repeat(depth), name, metadata.len());
+        }
+    }
+    Ok(())
+}
+
+/// Collects every file under `dir` whose extension is `ext`, skipping the
+/// directories in `skip`. The relative paths make the output portable.
+fn find_files(
+    root: &Path,
+    dir: &Path,
+    ext: &str,
+    skip: &[&str],
+    found: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        // DirEntry::file_type is usually free: it comes with the listing,
+        // without another system call, and it doesn't follow links
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            if !skip.iter().any(|name| entry.file_name() == *name) {
+                find_files(root, &path, ext, skip, found)?;
+            }
+        } else if file_type.is_file() && path.extension().is_some_and(|e| e == ext) {
+            found.push(path.strip_prefix(root).unwrap().to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let root = env::temp_dir().join(format!("file-io-03-{}", std::process::id()));
+    make_tree(&root)?;
+
+    println!("demo/");
+    print_tree(&root, 1)?;
+
+    let mut rust_files = Vec::new();
+    find_files(&root, &root, "rs", &["target"], &mut rust_files)?;
+    rust_files.sort();
+    // A Path prints with the platform's separator (`src\main.rs` on Windows),
+    // so join the components with '/' to get the same output everywhere
+    for file in &rust_files {
+        let parts: Vec<_> = file.iter().map(|part| part.to_string_lossy()).collect();
+        println!("found {}", parts.join("/"));
+    }
+
+    // Reading a directory that isn't there is an ordinary io::Error
+    match fs::read_dir(root.join("nope")) {
+        Ok(_) => println!("it exists?"),
+        Err(err) => println!("read_dir: {:?}", err.kind()),
+    }
+
+    // remove_dir_all deletes a whole tree; remove_dir only empty ones
+    println!(
+        "remove_dir on a full directory fails: {}",
+        fs::remove_dir(root.join("src")).is_err()
+    );
+    fs::remove_dir_all(&root)?;
+    println!("cleaned up: {}", !root.exists());
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - fs::read_dir lists a directory's entries, in no guaranteed order
+ * - Recursion walks a tree: descend into every directory entry
+ * - DirEntry::file_type and fs::symlink_metadata don't follow links
+ * - Skip directories you don't care about (target/, .git/) while walking
+ * - remove_dir_all removes a tree, remove_dir only an empty directory
+ */
+
+// EXPECTED:
+// demo/
+//   Cargo.toml (24 bytes)
+//   docs/
+//     guide.md (8 bytes)
+//   src/
+//     bin/
+//       tool.rs (13 bytes)
+//     lib.rs (34 bytes)
+//     main.rs (13 bytes)
+//   target/
+//     debug/
+//       demo (4096 bytes)
+// found src/bin/tool.rs
+// found src/lib.rs
+// found src/main.rs
+// read_dir: NotFound
+// remove_dir on a full directory fails: true
+// cleaned up: true
@@ -0,0 +1,123 @@
+// File I/O 4: Paths Across Platforms
+// Demonstrates Path and PathBuf, components, extensions, and OsStr
+//
+// Concepts: Path, PathBuf, components, extensions, OsStr
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Paths are not strings. On Unix a path is any bytes except NUL; on
+// Windows it's UTF-16 that may not be valid, with `\` as the separator and
+// drive letters like `C:`. Path (borrowed, like &str) and PathBuf (owned,
+// like String) hide those differences, as long as we build paths with
+// join() instead of gluing strings together with "/".
+
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR};
+
+fn main() {
+    // join uses the platform's separator
+    let config = Path::new("settings").join("app").join("config.toml");
+    println!("separator is '{}' here", MAIN_SEPARATOR);
+    println!("joined: {}", config.display());
+
+    // The parts of a path, without any string slicing
+    println!("file_name: {:?}", config.file_name());
+    println!("file_stem: {:?}", config.file_stem());
+    println!("extension: {:?}", config.extension());
+    println!("parent:    {}", config.parent().unwrap().display());
+    println!(
+        "with_extension: {}",
+        config.with_extension("json").display()
+    );
+
+    // A PathBuf can grow and shrink in place
+    let mut path = PathBuf::from("logs");
+    path.push("2024");
+    path.push("march.log");
+    path.set_extension("txt");
+    println!("built: {}", path.display());
+    path.pop();
+    println!("after pop: {}", path.display());
+
+    // Careful: joining an absolute path replaces everything before it
+    let root = if cfg!(windows) { r"C:\data" } else { "/data" };
+    let replaced = Path::new("relative").join(root);
+    println!(
+        "join with an absolute path replaces: {}",
+        replaced == Path::new(root)
+    );
+
+    // components() splits a path properly: `a//b/./c/` has three normal
+    // parts. This is also the portable way to print a path with '/'.
+    let messy = Path::new("a//b/./c/");
+    let parts: Vec<_> = messy
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+    println!("components: {:?}", parts);
+    // `..` is kept, because resolving it needs the real file system: if b
+    // is a symlink, a/b/.. isn't a
+    let up = Path::new("a/b/../c");
+    let parent_dirs = up
+        .components()
+        .filter(|part| *part == Component::ParentDir)
+        .count();
+    println!("`..` components in a/b/../c: {}", parent_dirs);
+
+    // starts_with and strip_prefix compare whole components, not text
+    let file = Path::new("project/src/main.rs");
+    println!(
+        "starts with project/src: {}",
+        file.starts_with("project/src")
+    );
+    println!("starts with proj: {}", file.starts_with("proj"));
+    println!(
+        "relative: {:?}",
+        file.strip_prefix("project")
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+    );
+
+    // A file name is an &OsStr. Turning it into &str can fail, because not
+    // every path is valid Unicode. to_string_lossy never fails; it swaps
+    // bad bytes for '�', which is fine for display but not for reopening
+    // the file.
+    let name: &OsStr = config.file_name().unwrap();
+    match name.to_str() {
+        Some(text) => println!("as &str: {}", text.to_uppercase()),
+        None => println!("not valid Unicode: {}", name.to_string_lossy()),
+    }
+
+    // Extensions are OsStr too. To match them regardless of case ("JPG" is
+    // as much a JPEG as "jpg"), get a &str first
+    let photos = ["a.JPG", "b.jpg", "c.png", "README"];
+    let jpegs: Vec<_> = photos
+        .iter()
+        .filter(|name| {
+            Path::new(name)
+                .extension()
+                .and_then(OsStr::to_str)
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("jpg"))
+        })
+        .collect();
+    println!("jpegs: {:?}", jpegs);
+
+    // The current directory and the temp directory come from the OS.
+    // is_absolute depends on the platform's rules (`/x` isn't absolute on
+    // Windows, which needs a drive).
+    println!("temp dir is absolute: {}", env::temp_dir().is_absolute());
+    println!(
+        "current dir is absolute: {}",
+        env::current_dir().unwrap().is_absolute()
+    );
+}
+
+/*
+ * Key Concepts:
+ * - Path is a borrowed path, PathBuf an owned one
+ * - join() and push() use the platform's separator; never glue with "/"
+ * - file_name, file_stem, extension, parent, with_extension pick paths apart
+ * - components() compares and splits paths properly
+ * - Paths are OsStr, not str: to_str() can fail, display() and
+ *   to_string_lossy() are for showing paths to people
+ */
@@ -0,0 +1,128 @@
+// Formats 1: TOML and YAML
+// Demonstrates the same structs written as TOML and as YAML, and what each format can't say
+//
+// Concepts: serde formats, toml, serde_yaml, tables, arrays of tables, null, round trips
+// Difficulty: intermediate
+// Minutes: 15
+//
+// The serde chapter wrote everything as JSON, but Serialize and
+// Deserialize don't know about JSON: the derives describe a type as
+// structs, sequences, strings, and numbers, and each format crate decides
+// how to write those down. So the same Inventory goes to any format with
+// one function call, and the differences are all in what the formats
+// themselves allow.
+//
+// TOML is made for config files that people edit (Cargo.toml is one). Its
+// top level is always a table of keys, a list of structs becomes an
+// "array of tables" written [[items]], and it has no null: toml leaves a
+// None field out, and reads a missing Option field back as None.
+//
+// YAML is JSON's more readable superset, common for CI pipelines and
+// Kubernetes: indentation instead of braces, `-` for list items, `null`
+// for None. serde_yaml, the usual crate, is no longer maintained; forks
+// such as serde_yaml_ng keep the same API.
+
+use catalog::{sample, Inventory, Item};
+use learn_rust_core::section;
+use std::collections::BTreeMap;
+
+fn main() {
+    let inventory = sample();
+
+    section("TOML");
+    let toml_text = toml::to_string(&inventory).unwrap();
+    print!("{toml_text}");
+    let back: Inventory = toml::from_str(&toml_text).unwrap();
+    println!("round trip: {}", back == inventory);
+
+    section("YAML");
+    let yaml_text = serde_yaml::to_string(&inventory).unwrap();
+    print!("{yaml_text}");
+    let back: Inventory = serde_yaml::from_str(&yaml_text).unwrap();
+    println!("round trip: {}", back == inventory);
+
+    section("what TOML can't say");
+    // A bare list is fine in JSON and YAML, but a TOML document is a table
+    let items: &[Item] = &inventory.items;
+    println!("a list: {}", toml::to_string(items).unwrap_err());
+    // So is a plain value at the top
+    println!("a number: {}", toml::to_string(&42).unwrap_err());
+    // And there is no null: toml leaves a None out, so "bo" is gone
+    let ages: BTreeMap<&str, Option<u32>> = BTreeMap::from([("ana", Some(41)), ("bo", None)]);
+    println!("a map with a None: {:?}", toml::to_string(&ages).unwrap());
+
+    section("what YAML guesses");
+    // An unquoted YAML value is whatever it looks like, but serde asks for
+    // the type the struct has, so 1e3 can still be a name
+    let text =
+        "warehouse: 0042\nitems:\n- sku: NO\n  name: 1e3\n  price_cents: 100\n  quantity: 1\n";
+    let parsed: Inventory = serde_yaml::from_str(text).unwrap();
+    println!(
+        "warehouse {:?}, sku {:?}, name {:?}",
+        parsed.warehouse, parsed.items[0].sku, parsed.items[0].name
+    );
+    // Without a struct, the guesses are all there is. serde_yaml follows
+    // YAML 1.2, where yes and NO are strings; YAML 1.1 read them as
+    // booleans, which turned Norway's country code into false.
+    let untyped: serde_yaml::Value = serde_yaml::from_str("[0042, NO, yes, 1e3, ~]").unwrap();
+    println!("untyped: {untyped:?}");
+}
+
+/*
+ * Key Concepts:
+ * - One pair of derives works with every serde format crate
+ * - TOML: the top level is a table, a Vec of structs is [[items]], and there is no null
+ * - toml leaves None values out, so a map loses its keys that hold None
+ * - YAML: JSON's structure with indentation, comments, and null
+ * - Untyped YAML guesses what a value is; deserializing into a struct asks for a type
+ */
+
+// EXPECTED:
+// -- TOML --
+// warehouse = "Lisbon"
+//
+// [[items]]
+// sku = "MUG-01"
+// name = "Mug"
+// price_cents = 950
+// quantity = 40
+//
+// [[items]]
+// sku = "TEE-04"
+// name = "T-shirt, large"
+// price_cents = 2000
+// quantity = 12
+// note = "last of the old print"
+//
+// [[items]]
+// sku = "PEN-12"
+// name = "Pen"
+// price_cents = 150
+// quantity = 300
+// round trip: true
+// -- YAML --
+// warehouse: Lisbon
+// items:
+// - sku: MUG-01
+//   name: Mug
+//   price_cents: 950
+//   quantity: 40
+//   note: null
+// - sku: TEE-04
+//   name: T-shirt, large
+//   price_cents: 2000
+//   quantity: 12
+//   note: last of the old print
+// - sku: PEN-12
+//   name: Pen
+//   price_cents: 150
+//   quantity: 300
+//   note: null
+// round trip: true
+// -- what TOML can't say --
+// a list: unsupported rust type
+// a number: unsupported rust type
+// a map with a None: "ana = 41\n"
+// -- what YAML guesses --
+// warehouse "0042", sku "NO", name "1e3"
+// untyped: Sequence [String("0042"), String("NO"), String("yes"), Number(1000.0), Null]
@@ -0,0 +1,131 @@
+// Formats 2: Binary Formats with bincode
+// Demonstrates bincode's compact bytes, its two integer encodings, and why it needs both sides to agree
+//
+// Concepts: binary serialization, bincode, varint, self-describing formats, schema changes
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Text formats spend bytes on being readable: every key is written out,
+// every number is digits, and there are quotes, braces, and newlines in
+// between. A binary format such as bincode writes only the values, in the
+// order the struct declares its fields: a string is its length and then
+// its bytes, a number is the number, an Option is a 0 or a 1 and then the
+// value. It is smaller and faster, for programs that talk to themselves:
+// a cache, a save file, messages between two copies of one service.
+//
+// The catch is that the bytes don't say what they are. bincode can only
+// read them back as the exact type that wrote them: add a field, reorder
+// two, or change a u32 to a u64, and old bytes are misread or rejected.
+// JSON, TOML, and YAML are "self-describing": the keys are in the data,
+// so a reader can skip what it doesn't know.
+//
+// bincode 2 has configurations. The standard one writes small integers
+// in fewer bytes (a "varint": 0 to 250 take one byte); the legacy one
+// writes every u32 as 4 bytes and every u64 as 8, like bincode 1 did.
+
+use bincode::config;
+use catalog::{sample, Inventory, Item};
+use learn_rust_core::section;
+use serde::{Deserialize, Serialize};
+
+fn hex(bytes: &[u8]) -> String {
+    let bytes: Vec<String> = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    bytes.join(" ")
+}
+
+/// An Item as a newer version of the program might declare it.
+#[derive(Debug, Serialize, Deserialize)]
+struct ItemV2 {
+    sku: String,
+    name: String,
+    price_cents: u64,
+    quantity: u32,
+    note: Option<String>,
+    weight_grams: Option<u32>,
+}
+
+/// What catalog::Item would be if it left out None notes, as a JSON API
+/// might want.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SkippingItem {
+    sku: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    quantity: u32,
+}
+
+fn main() {
+    let inventory = sample();
+    let pen = &inventory.items[2];
+
+    section("one item, byte by byte");
+    let bytes = bincode::serde::encode_to_vec(pen, config::standard()).unwrap();
+    println!("{pen:?}");
+    // 06 and "PEN-12", 03 and "Pen", 150 in one byte (96), 300 in three
+    // (fb says two bytes follow: 2c 01), and 00 for None
+    println!("{} bytes: {}", bytes.len(), hex(&bytes));
+    let json = serde_json::to_vec(pen).unwrap();
+    println!("as JSON: {} bytes", json.len());
+
+    section("standard and legacy");
+    let standard = bincode::serde::encode_to_vec(&inventory, config::standard()).unwrap();
+    let legacy = bincode::serde::encode_to_vec(&inventory, config::legacy()).unwrap();
+    println!("standard, with varints: {} bytes", standard.len());
+    println!("legacy, fixed-size:     {} bytes", legacy.len());
+    // The configuration is part of the format: both sides must use the
+    // same one. This time the wrong one doesn't even fail: 8 bytes in, it
+    // has read a warehouse of six zero bytes and no items, and stops.
+    let (misread, read): (Inventory, usize) =
+        bincode::serde::decode_from_slice(&legacy, config::standard()).unwrap();
+    println!("legacy bytes read as standard: {misread:?} after {read} bytes");
+
+    section("a changed struct");
+    let (item, read): (Item, usize) =
+        bincode::serde::decode_from_slice(&bytes, config::standard()).unwrap();
+    println!("read back {} bytes: {}", read, item == *pen);
+    // One more field, and yesterday's bytes run out before it
+    let newer = bincode::serde::decode_from_slice::<ItemV2, _>(&bytes, config::standard());
+    println!("as ItemV2: {}", newer.unwrap_err());
+    // In JSON the keys say what is missing, and a missing Option is None
+    let v2: ItemV2 = serde_json::from_slice(&json).unwrap();
+    println!("JSON as ItemV2: weight {:?}", v2.weight_grams);
+
+    section("skipped fields");
+    // Leaving a field out is fine where keys say which field is which,
+    // but bincode reads the next bytes as the note
+    let item = SkippingItem {
+        sku: "MUG-01".to_string(),
+        note: None,
+        quantity: 40,
+    };
+    let bytes = bincode::serde::encode_to_vec(&item, config::standard()).unwrap();
+    let back = bincode::serde::decode_from_slice::<SkippingItem, _>(&bytes, config::standard());
+    println!("{} bytes: {}", bytes.len(), hex(&bytes));
+    println!("read back: {}", back.unwrap_err());
+}
+
+/*
+ * Key Concepts:
+ * - bincode writes the values only, in field order: small and fast, but not readable
+ * - Its bytes aren't self-describing: the reader must have exactly the writer's type
+ * - Adding, removing, or reordering fields breaks old data; version it yourself
+ * - standard() uses varints, legacy() fixed-size integers; both sides must agree
+ * - skip_serializing_if and other "leave it out" attributes only work with keyed formats
+ */
+
+// EXPECTED:
+// -- one item, byte by byte --
+// Item { sku: "PEN-12", name: "Pen", price_cents: 150, quantity: 300, note: None }
+// 16 bytes: 06 50 45 4e 2d 31 32 03 50 65 6e 96 fb 2c 01 00
+// as JSON: 74 bytes
+// -- standard and legacy --
+// standard, with varints: 89 bytes
+// legacy, fixed-size:     176 bytes
+// legacy bytes read as standard: Inventory { warehouse: "\0\0\0\0\0\0", items: [] } after 8 bytes
+// -- a changed struct --
+// read back 16 bytes: true
+// as ItemV2: UnexpectedEnd { additional: 1 }
+// JSON as ItemV2: weight None
+// -- skipped fields --
+// 8 bytes: 06 4d 55 47 2d 30 31 28
+// read back: UnexpectedVariant { type_name: "Option<T>", allowed: Range { min: 0, max: 1 }, found: 40 }
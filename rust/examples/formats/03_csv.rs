@@ -0,0 +1,131 @@
+// Formats 3: CSV
+// Demonstrates writing and reading CSV records with the csv crate, and reporting the rows that are wrong
+//
+// Concepts: csv, headers, quoting, records, flat structs, row errors, positions
+// Difficulty: intermediate
+// Minutes: 15
+//
+// CSV is the format of spreadsheets and data exports: one table, a header
+// row of column names, then one row per record, with commas in between.
+// It has no nesting and no types. Every field is text, a value with a
+// comma or a quote in it is wrapped in quotes, and an empty field is the
+// closest CSV gets to null.
+//
+// The csv crate reads and writes rows through serde, so a flat struct is
+// a row and its field names are the header. A struct with a Vec or
+// another struct inside isn't, which is why the catalog library writes
+// its inventory as rows that repeat the warehouse instead.
+//
+// Real CSV files are often made by hand or by other programs, so they
+// have wrong rows: a missing column, a number that isn't one. Each error
+// of the csv crate knows the line it happened on and, for a value that
+// doesn't parse, which field it was, which is what a good error message
+// needs. One bad row shouldn't hide the others, so read them all and
+// report every one.
+
+use catalog::{sample, Item};
+use learn_rust_core::section;
+use serde::Deserialize;
+
+/// The same columns as an Item, without the note, for reading what
+/// people typed.
+#[derive(Debug, Deserialize)]
+struct Stock {
+    sku: String,
+    quantity: u32,
+}
+
+const TYPED: &str = "\
+sku,quantity
+MUG-01,40
+TEE-04,twelve
+PEN-12
+CUP-02,-3
+BAG-07,5
+";
+
+fn main() {
+    let inventory = sample();
+
+    section("writing");
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for item in &inventory.items {
+        writer.serialize(item).unwrap();
+    }
+    let text = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+    // The header comes from the field names; the comma in "T-shirt, large"
+    // gets it quoted, and None is an empty field
+    print!("{text}");
+
+    section("reading");
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    println!("headers: {:?}", reader.headers().unwrap());
+    for item in reader.deserialize::<Item>() {
+        let item = item.unwrap();
+        println!(
+            "{} x{} at {} cents, note {:?}",
+            item.name, item.quantity, item.price_cents, item.note
+        );
+    }
+    // Without a type, a row is a StringRecord: just the text of each field
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    let record = reader.records().next().unwrap().unwrap();
+    println!("untyped: {record:?}");
+
+    section("only flat structs");
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    println!("{}", writer.serialize(&inventory).unwrap_err());
+
+    section("bad rows");
+    let mut reader = csv::Reader::from_reader(TYPED.as_bytes());
+    let mut stock = Vec::new();
+    let mut problems = Vec::new();
+    for row in reader.deserialize::<Stock>() {
+        match row {
+            Ok(row) => stock.push(row),
+            Err(error) => problems.push(error),
+        }
+    }
+    println!(
+        "read {} rows: {:?}",
+        stock.len(),
+        stock
+            .iter()
+            .map(|row| format!("{} x{}", row.sku, row.quantity))
+            .collect::<Vec<_>>()
+    );
+    for error in &problems {
+        // Each message has the record, its line, and the field's index;
+        // error.position() and error.kind() have them as values
+        println!("{error}");
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A CSV file is one table: a header row and one row per record, all text
+ * - csv::Writer::serialize writes a flat struct as a row, named by its fields
+ * - csv::Reader::deserialize reads rows back as a type; records() as plain text
+ * - Quoting is automatic; None is an empty field; nesting is an error
+ * - Each error knows its position: keep reading, and report every bad row with its line
+ */
+
+// EXPECTED:
+// -- writing --
+// sku,name,price_cents,quantity,note
+// MUG-01,Mug,950,40,
+// TEE-04,"T-shirt, large",2000,12,last of the old print
+// PEN-12,Pen,150,300,
+// -- reading --
+// headers: StringRecord(["sku", "name", "price_cents", "quantity", "note"])
+// Mug x40 at 950 cents, note None
+// T-shirt, large x12 at 2000 cents, note Some("last of the old print")
+// Pen x300 at 150 cents, note None
+// untyped: StringRecord(["MUG-01", "Mug", "950", "40", ""])
+// -- only flat structs --
+// CSV write error: cannot serialize sequence container inside struct when writing headers from structs
+// -- bad rows --
+// read 2 rows: ["MUG-01 x40", "BAG-07 x5"]
+// CSV deserialize error: record 2 (line: 3, byte: 23): field 1: invalid digit found in string
+// CSV error: record 3 (line: 4, byte: 37): found record with 1 fields, but the previous record has 2 fields
+// CSV deserialize error: record 4 (line: 5, byte: 44): field 1: invalid digit found in string
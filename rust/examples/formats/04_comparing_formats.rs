@@ -0,0 +1,89 @@
+// Formats 4: Comparing Formats
+// Demonstrates the same inventory in five formats, measured by size, and how to time their round trips
+//
+// Concepts: output size, round trips, text vs binary, choosing a format, criterion
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Which format to use is mostly decided by who reads the data. A person
+// editing settings wants TOML or YAML. Another program, or another
+// language, wants JSON, which everything can read. A spreadsheet wants
+// CSV. Only your own program reading its own data can take bincode,
+// and get bytes that are smaller and faster to read and write.
+//
+// This example prints the sizes, which are the same on every machine.
+// Speed isn't, so it is measured by the benchmarks in benches/formats.rs,
+// which time a full round trip (encode, then decode) of a large inventory
+// in each format:
+//
+//     cargo bench -p formats-examples --bench formats
+//     cargo run -p tutor -- bench formats
+//
+// The second compares the numbers with baselines.json, one run on one
+// machine. There a thousand items took about 0.1 ms with bincode, a
+// quarter of a millisecond with JSON or CSV, and ten times that with YAML
+// or TOML, whose parsers do more work for each byte.
+
+use catalog::{large, sample, Format, Inventory};
+use learn_rust_core::section;
+
+fn sizes(inventory: &Inventory) {
+    let json = Format::Json.encode(inventory).unwrap().len();
+    for format in Format::ALL {
+        let bytes = format.encode(inventory).unwrap();
+        let back = format.decode(&bytes).unwrap();
+        let kind = if format.is_text() { "text" } else { "binary" };
+        let percent = bytes.len() * 100 / json;
+        println!(
+            "{:<8}{kind:<8}{:>8} bytes {percent:>4}% of JSON   round trip {}",
+            format.to_string(),
+            bytes.len(),
+            if back == *inventory {
+                "ok"
+            } else {
+                "LOST DATA"
+            }
+        );
+    }
+}
+
+fn main() {
+    section("three items");
+    sizes(&sample());
+
+    section("a thousand items");
+    // The keys are written once per item in every text format but CSV,
+    // which names its columns once in the header
+    sizes(&large(1000));
+
+    section("pretty printing");
+    // Readable JSON costs bytes too: indentation and a newline per value
+    let inventory = large(1000);
+    let compact = serde_json::to_vec(&inventory).unwrap().len();
+    let pretty = serde_json::to_vec_pretty(&inventory).unwrap().len();
+    println!("JSON {compact} bytes, pretty JSON {pretty} bytes");
+}
+
+/*
+ * Key Concepts:
+ * - Choose by the reader: people (TOML, YAML), other programs (JSON), spreadsheets (CSV)
+ * - Binary formats are smallest and fastest, but only for readers that share your types
+ * - Text formats repeat every key; CSV names them once, and can only be flat
+ * - Measure speed with a benchmark, on your data, on your machine
+ */
+
+// EXPECTED:
+// -- three items --
+// JSON    text         286 bytes  100% of JSON   round trip ok
+// TOML    text         278 bytes   97% of JSON   round trip ok
+// YAML    text         274 bytes   95% of JSON   round trip ok
+// bincode binary        89 bytes   31% of JSON   round trip ok
+// CSV     text         159 bytes   55% of JSON   round trip ok
+// -- a thousand items --
+// JSON    text       95179 bytes  100% of JSON   round trip ok
+// TOML    text       96176 bytes  101% of JSON   round trip ok
+// YAML    text       93504 bytes   98% of JSON   round trip ok
+// bincode binary     37431 bytes   39% of JSON   round trip ok
+// CSV     text       46861 bytes   49% of JSON   round trip ok
+// -- pretty printing --
+// JSON 95179 bytes, pretty JSON 145191 bytes
@@ -0,0 +1,30 @@
+//! A round trip through each format: encode an inventory of a thousand
+//! items, then decode it again. The text formats spend most of the time
+//! in their parsers; bincode copies values with little to decide.
+
+use catalog::{large, Format};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+
+fn round_trip(c: &mut Criterion) {
+    let mut group = c.benchmark_group("round_trip");
+    let inventory = large(1000);
+    for format in Format::ALL {
+        let id = BenchmarkId::new(format.to_string().to_lowercase(), 1000);
+        group.bench_with_input(id, &inventory, |b, inventory| {
+            b.iter(|| {
+                let bytes = format.encode(black_box(inventory)).unwrap();
+                format.decode(&bytes).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20).warm_up_time(Duration::from_millis(300)).measurement_time(Duration::from_secs(1));
+    targets = round_trip
+}
+criterion_main!(benches);
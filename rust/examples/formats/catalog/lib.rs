@@ -0,0 +1,196 @@
+//! One data model, an [`Inventory`], and one way to write it in each of
+//! five formats and read it back: [`Format::encode`] and
+//! [`Format::decode`]. The examples print what each format makes of it,
+//! the tests check that every format gives back what it was given, and
+//! the benchmarks time the round trips.
+//!
+//! ```
+//! use catalog::{sample, Format};
+//!
+//! let inventory = sample();
+//! for format in Format::ALL {
+//!     let bytes = format.encode(&inventory).unwrap();
+//!     assert_eq!(format.decode(&bytes).unwrap(), inventory);
+//! }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The only error type the formats have in common.
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Everything one warehouse has in stock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    pub warehouse: String,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub sku: String,
+    pub name: String,
+    pub price_cents: u64,
+    pub quantity: u32,
+    /// TOML has no null, so toml leaves a None out altogether.
+    pub note: Option<String>,
+}
+
+/// The three items the examples print.
+pub fn sample() -> Inventory {
+    Inventory {
+        warehouse: "Lisbon".to_string(),
+        items: vec![
+            Item {
+                sku: "MUG-01".to_string(),
+                name: "Mug".to_string(),
+                price_cents: 950,
+                quantity: 40,
+                note: None,
+            },
+            Item {
+                sku: "TEE-04".to_string(),
+                name: "T-shirt, large".to_string(),
+                price_cents: 2000,
+                quantity: 12,
+                note: Some("last of the old print".to_string()),
+            },
+            Item {
+                sku: "PEN-12".to_string(),
+                name: "Pen".to_string(),
+                price_cents: 150,
+                quantity: 300,
+                note: None,
+            },
+        ],
+    }
+}
+
+/// `count` made-up items, every third one with a note, for the
+/// benchmarks.
+pub fn large(count: usize) -> Inventory {
+    let items = (0..count)
+        .map(|i| Item {
+            sku: format!("SKU-{i:05}"),
+            name: format!("Item number {i}"),
+            price_cents: 100 + (i as u64 * 37) % 10_000,
+            quantity: (i as u32 * 7) % 500,
+            note: (i % 3 == 0).then(|| format!("restock after {}", i % 28 + 1)),
+        })
+        .collect();
+    Inventory {
+        warehouse: "Porto".to_string(),
+        items,
+    }
+}
+
+/// The same rows as an [`Inventory`]'s items, with the warehouse on every
+/// row: a CSV file is a single table, so whatever is outside the table
+/// has to be repeated in it, or lost.
+#[derive(Debug, Serialize, Deserialize)]
+struct Row {
+    warehouse: String,
+    sku: String,
+    name: String,
+    price_cents: u64,
+    quantity: u32,
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+    Yaml,
+    Bincode,
+    Csv,
+}
+
+impl Format {
+    pub const ALL: [Format; 5] = [
+        Format::Json,
+        Format::Toml,
+        Format::Yaml,
+        Format::Bincode,
+        Format::Csv,
+    ];
+
+    /// Whether a person can read it, and edit it with a text editor.
+    pub fn is_text(self) -> bool {
+        self != Format::Bincode
+    }
+
+    pub fn encode(self, inventory: &Inventory) -> Result<Vec<u8>, BoxError> {
+        Ok(match self {
+            Format::Json => serde_json::to_vec(inventory)?,
+            Format::Toml => toml::to_string(inventory)?.into_bytes(),
+            Format::Yaml => serde_yaml::to_string(inventory)?.into_bytes(),
+            Format::Bincode => {
+                bincode::serde::encode_to_vec(inventory, bincode::config::standard())?
+            }
+            Format::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                for item in &inventory.items {
+                    writer.serialize(Row {
+                        warehouse: inventory.warehouse.clone(),
+                        sku: item.sku.clone(),
+                        name: item.name.clone(),
+                        price_cents: item.price_cents,
+                        quantity: item.quantity,
+                        note: item.note.clone(),
+                    })?;
+                }
+                writer.into_inner()?
+            }
+        })
+    }
+
+    /// Reads back what [`encode`](Format::encode) wrote. A CSV file with no
+    /// rows has nowhere to keep the warehouse, so it comes back empty.
+    pub fn decode(self, bytes: &[u8]) -> Result<Inventory, BoxError> {
+        Ok(match self {
+            Format::Json => serde_json::from_slice(bytes)?,
+            Format::Toml => toml::from_str(std::str::from_utf8(bytes)?)?,
+            Format::Yaml => serde_yaml::from_slice(bytes)?,
+            Format::Bincode => {
+                let (inventory, _read) =
+                    bincode::serde::decode_from_slice(bytes, bincode::config::standard())?;
+                inventory
+            }
+            Format::Csv => {
+                let mut inventory = Inventory {
+                    warehouse: String::new(),
+                    items: Vec::new(),
+                };
+                for row in csv::Reader::from_reader(bytes).deserialize() {
+                    let row: Row = row?;
+                    inventory.warehouse = row.warehouse;
+                    inventory.items.push(Item {
+                        sku: row.sku,
+                        name: row.name,
+                        price_cents: row.price_cents,
+                        quantity: row.quantity,
+                        note: row.note,
+                    });
+                }
+                inventory
+            }
+        })
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Format::Json => "JSON",
+            Format::Toml => "TOML",
+            Format::Yaml => "YAML",
+            Format::Bincode => "bincode",
+            Format::Csv => "CSV",
+        };
+        f.write_str(name)
+    }
+}
@@ -0,0 +1,49 @@
+// Every format has to give back exactly what it was given, for the
+// sample the examples print and for the larger inventories the
+// benchmarks use, with and without notes.
+
+use catalog::{large, sample, Format, Inventory};
+
+fn assert_round_trips(inventory: &Inventory) {
+    for format in Format::ALL {
+        let bytes = format.encode(inventory).unwrap();
+        let back = format
+            .decode(&bytes)
+            .unwrap_or_else(|error| panic!("{format} cannot read what it wrote: {error}"));
+        assert_eq!(&back, inventory, "{format} changed the inventory");
+    }
+}
+
+#[test]
+fn every_format_round_trips_the_sample() {
+    assert_round_trips(&sample());
+}
+
+#[test]
+fn every_format_round_trips_a_large_inventory() {
+    assert_round_trips(&large(200));
+}
+
+#[test]
+fn text_that_needs_quoting_survives() {
+    let mut inventory = sample();
+    inventory.warehouse = "Lisbon, \"north\" dock\n2".to_string();
+    inventory.items[0].name = "key = value # not a comment".to_string();
+    inventory.items[1].note = Some("- not a list\n: not a map".to_string());
+    assert_round_trips(&inventory);
+}
+
+#[test]
+fn bincode_is_the_smallest_and_text_formats_are_text() {
+    let inventory = large(100);
+    let size = |format: Format| format.encode(&inventory).unwrap().len();
+    for format in Format::ALL {
+        assert!(size(Format::Bincode) <= size(format), "{format} is smaller");
+        let bytes = format.encode(&inventory).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&bytes).is_ok(),
+            format.is_text(),
+            "{format}"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// Fuzzing 1: A Parser with a Planted Panic
+// Demonstrates why parsers of untrusted bytes need fuzzing, not just tests
+//
+// Concepts: fuzzing, parsers, untrusted input
+// Difficulty: advanced
+// Minutes: 5
+//
+// `packet/lib.rs` parses a tiny binary format: "PK", a version, a record
+// count, and then records of (tag, length, value). It returns an error
+// for every malformed input it thought of: a wrong magic number, a short
+// header, a record that runs past the end, an unknown tag, a name that
+// isn't UTF-8. Its unit tests check all of those, and they pass.
+//
+// And yet one input of six bytes makes it panic. Anything that parses
+// bytes from a file or a socket gets inputs nobody wrote a test for, and
+// a panic there is a crash, or a denial of service. A fuzzer finds such
+// inputs by running the parser millions of times on mutated bytes,
+// steered by which branches each input reaches. The next example runs it;
+// this one only shows the inputs the author thought of.
+//
+// Try to spot the bug before the fuzzer does. (The answer is at the end
+// of the Key Concepts.)
+
+use packet::{parse, Packet, ParseError};
+
+fn show(label: &str, bytes: &[u8]) {
+    match parse(bytes) {
+        Ok(packet) => println!("{:<14} {:?}", label, packet),
+        Err(error) => println!("{:<14} error: {}", label, error),
+    }
+}
+
+fn main() {
+    let packet = Packet {
+        name: Some("sensor-7".to_string()),
+        port: Some(8080),
+        flags: vec![1, 0, 1],
+    };
+    let bytes = packet.to_bytes();
+    println!("{} bytes: {:02x?}", bytes.len(), bytes);
+    show("round trip", &bytes);
+    println!();
+
+    // Every malformed input the author thought of is an error, not a panic
+    show("empty", b"");
+    show("wrong magic", b"GIF89a");
+    show("version 2", b"PK\x02\x00");
+    show("cut short", &bytes[..9]);
+    show("left over", b"PK\x01\x00!");
+    show("unknown tag", b"PK\x01\x01\x09\x00");
+    show("bad name", b"PK\x01\x01\x01\x01\xff");
+
+    // The errors are values the caller can match on
+    let rejected = matches!(parse(b"PK\x01\x01\x09\x00"), Err(ParseError::UnknownTag(9)));
+    println!("\nunknown tags are rejected: {}", rejected);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let port = parse(&bytes)?.port;
+    // error[E0277]: the `?` operator can only be used in a function that returns `Result` or `Option`
+}
+
+/*
+ * Key Concepts:
+ * - Code that parses untrusted bytes must return errors, never panic
+ * - Unit tests only cover the malformed inputs someone thought of
+ * - A fuzzer mutates inputs, guided by coverage, looking for crashes
+ * - Indexing (value[0]) panics when out of bounds; get() or a pattern doesn't
+ * - The bug: a port record whose length is 0 or 1 indexes past its value
+ */
+
+// EXPECTED:
+// 23 bytes: [50, 4b, 01, 03, 01, 08, 73, 65, 6e, 73, 6f, 72, 2d, 37, 02, 02, 1f, 90, 03, 03, 01, 00, 01]
+// round trip     Packet { name: Some("sensor-7"), port: Some(8080), flags: [1, 0, 1] }
+//
+// empty          error: the packet ends too early
+// wrong magic    error: not a packet: it doesn't start with "PK"
+// version 2      error: unsupported version 2
+// cut short      error: the packet ends too early
+// left over      error: extra bytes after the last record: 1
+// unknown tag    error: unknown record tag 9
+// bad name       error: the name is not valid UTF-8
+//
+// unknown tags are rejected: true
@@ -0,0 +1,83 @@
+// Fuzzing 2: Fuzz Targets, Corpora, and Artifacts
+// Demonstrates fuzz_target!, the seed corpus, crash artifacts, and `cargo fuzz`
+//
+// Concepts: fuzz_target!, seed corpus, crash artifacts, cargo fuzz
+// Difficulty: advanced
+// Minutes: 5
+//
+// A fuzz target is a function from bytes to nothing, which must not
+// panic. `fuzz/fuzz_targets/parse_packet.rs` is ours:
+//
+//     fuzz_target!(|data: &[u8]| {
+//         if let Ok(packet) = packet::parse(data) {
+//             let again = packet::parse(&packet.to_bytes()).expect("...");
+//             assert_eq!(again, packet);
+//         }
+//     });
+//
+// Errors are fine; a panic, or a packet that doesn't survive a round trip,
+// is a bug. `cargo +nightly fuzz run parse_packet`, from this chapter's
+// directory, builds the target with coverage instrumentation and hands it
+// to libFuzzer, which starts from the *corpus* in `fuzz/corpus/
+// parse_packet/`: a few valid packets, one per file. It mutates them,
+// keeps each mutation that reaches new code as a new corpus entry, and
+// stops at the first crash, saving the input that caused it in
+// `fuzz/artifacts/parse_packet/`. Ours finds the planted bug in well under
+// a second.
+//
+// The stored inputs double as a regression test. `cargo run -p tutor --
+// fuzz` replays every file in the corpus and the artifacts, with no
+// nightly compiler needed, and then fuzzes for 30 more seconds if cargo
+// fuzz is installed. It passes once the parser is fixed. This program
+// plays the part of the corpus replay, for the seeds only: each must parse
+// and round-trip, and each prefix of a seed must be a clean error.
+
+use std::fs;
+use std::path::Path;
+
+use packet::parse;
+
+fn main() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus/parse_packet");
+    let mut seeds: Vec<_> = fs::read_dir(&corpus)
+        .expect("the corpus is committed")
+        .map(|entry| entry.expect("a corpus entry").path())
+        .collect();
+    seeds.sort();
+
+    for path in seeds {
+        let bytes = fs::read(&path).expect("a readable seed");
+        let name = path.file_name().unwrap().to_string_lossy();
+        let packet = parse(&bytes).expect("every seed is a valid packet");
+        let round_trip = parse(&packet.to_bytes()) == Ok(packet.clone());
+        let prefixes_rejected = (0..bytes.len()).all(|end| parse(&bytes[..end]).is_err());
+        println!(
+            "{:<6} {:>2} bytes  round trip: {}  prefixes rejected: {}",
+            name,
+            bytes.len(),
+            round_trip,
+            prefixes_rejected
+        );
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let packet = parse("PK\x01\x00");
+    // error[E0308]: mismatched types (expected `&[u8]`, found `&str`)
+}
+
+/*
+ * Key Concepts:
+ * - A fuzz target takes &[u8] (or an Arbitrary type) and must not panic
+ * - Assert properties inside it too: round trips, invariants, agreement with an oracle
+ * - The corpus seeds the search; libFuzzer grows it with inputs that reach new code
+ * - A crashing input is saved in artifacts/; commit it and replay it as a regression test
+ * - `cargo fuzz` needs a nightly compiler; replaying stored inputs doesn't
+ */
+
+// EXPECTED:
+// empty   4 bytes  round trip: true  prefixes rejected: true
+// flags   9 bytes  round trip: true  prefixes rejected: true
+// full   23 bytes  round trip: true  prefixes rejected: true
+// name   13 bytes  round trip: true  prefixes rejected: true
+// port    8 bytes  round trip: true  prefixes rejected: true
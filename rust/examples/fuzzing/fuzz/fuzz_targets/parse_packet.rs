@@ -0,0 +1,16 @@
+//! Feeds arbitrary bytes to `packet::parse`. libFuzzer calls the closure
+//! millions of times, with inputs it mutates from the corpus, and treats
+//! a panic as a crash.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Errors are fine: garbage in, Err out. A panic is the bug.
+    if let Ok(packet) = packet::parse(data) {
+        // Whatever parses must also survive a round trip
+        let again = packet::parse(&packet.to_bytes()).expect("an encoded packet parses");
+        assert_eq!(again, packet);
+    }
+});
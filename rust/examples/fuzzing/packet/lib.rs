@@ -0,0 +1,195 @@
+//! A parser for a tiny binary packet format, for the fuzzing examples.
+//!
+//! ```text
+//! packet := "PK" version count record*     count records, version 1
+//! record := tag length value               one byte each for tag and length
+//!
+//! tag 1: name   UTF-8 text
+//! tag 2: port   a big-endian u16
+//! tag 3: flags  any bytes
+//!
+//! A later record replaces an earlier one with the same tag.
+//! ```
+//!
+//! It has a bug: one kind of input makes `parse` panic instead of
+//! returning an error. The unit tests at the bottom all pass anyway, which
+//! is the point. Let the fuzzer find the input, then fix the parser and
+//! check it with `cargo run -p tutor -- fuzz`.
+
+use std::fmt;
+
+/// The first two bytes of every packet.
+pub const MAGIC: &[u8; 2] = b"PK";
+
+const NAME: u8 = 1;
+const PORT: u8 = 2;
+const FLAGS: u8 = 3;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Packet {
+    pub name: Option<String>,
+    pub port: Option<u16>,
+    pub flags: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The input doesn't start with [`MAGIC`].
+    BadMagic,
+    UnsupportedVersion(u8),
+    /// The input ended in the middle of the header or a record.
+    Truncated,
+    /// Bytes were left over after the last record.
+    TrailingBytes(usize),
+    UnknownTag(u8),
+    /// A record's value has the wrong size for its tag.
+    BadLength {
+        tag: u8,
+        length: usize,
+    },
+    InvalidName,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadMagic => write!(f, "not a packet: it doesn't start with \"PK\""),
+            ParseError::UnsupportedVersion(version) => write!(f, "unsupported version {}", version),
+            ParseError::Truncated => write!(f, "the packet ends too early"),
+            ParseError::TrailingBytes(count) => {
+                write!(f, "extra bytes after the last record: {}", count)
+            }
+            ParseError::UnknownTag(tag) => write!(f, "unknown record tag {}", tag),
+            ParseError::BadLength { tag, length } => {
+                write!(f, "record {} can't be {} bytes long", tag, length)
+            }
+            ParseError::InvalidName => write!(f, "the name is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Packet {
+    /// Encodes the packet, one record per field that is set.
+    ///
+    /// Panics if the name or the flags are longer than 255 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut records: Vec<(u8, Vec<u8>)> = Vec::new();
+        if let Some(name) = &self.name {
+            records.push((NAME, name.as_bytes().to_vec()));
+        }
+        if let Some(port) = self.port {
+            records.push((PORT, port.to_be_bytes().to_vec()));
+        }
+        if !self.flags.is_empty() {
+            records.push((FLAGS, self.flags.clone()));
+        }
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(1);
+        bytes.push(records.len() as u8);
+        for (tag, value) in records {
+            let length = u8::try_from(value.len()).expect("a record holds at most 255 bytes");
+            bytes.extend([tag, length]);
+            bytes.extend(value);
+        }
+        bytes
+    }
+}
+
+pub fn parse(input: &[u8]) -> Result<Packet, ParseError> {
+    let Some((header, mut rest)) = input.split_first_chunk::<4>() else {
+        return Err(if input.starts_with(&MAGIC[..input.len().min(2)]) {
+            ParseError::Truncated
+        } else {
+            ParseError::BadMagic
+        });
+    };
+    let [m1, m2, version, count] = *header;
+    if [m1, m2] != *MAGIC {
+        return Err(ParseError::BadMagic);
+    }
+    if version != 1 {
+        return Err(ParseError::UnsupportedVersion(version));
+    }
+
+    let mut packet = Packet::default();
+    for _ in 0..count {
+        let [tag, length, ref tail @ ..] = *rest else {
+            return Err(ParseError::Truncated);
+        };
+        let length = usize::from(length);
+        if tail.len() < length {
+            return Err(ParseError::Truncated);
+        }
+        let (value, tail) = tail.split_at(length);
+        rest = tail;
+
+        match tag {
+            NAME => {
+                let name = std::str::from_utf8(value).map_err(|_| ParseError::InvalidName)?;
+                packet.name = Some(name.to_string());
+            }
+            PORT => {
+                packet.port = Some(u16::from_be_bytes([value[0], value[1]]));
+            }
+            FLAGS => packet.flags = value.to_vec(),
+            tag => return Err(ParseError::UnknownTag(tag)),
+        }
+    }
+    if !rest.is_empty() {
+        return Err(ParseError::TrailingBytes(rest.len()));
+    }
+    Ok(packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Packet {
+        Packet {
+            name: Some("sensor-7".to_string()),
+            port: Some(8080),
+            flags: vec![1, 0, 1],
+        }
+    }
+
+    #[test]
+    fn parses_what_it_encodes() {
+        assert_eq!(parse(&sample().to_bytes()), Ok(sample()));
+        assert_eq!(parse(&Packet::default().to_bytes()), Ok(Packet::default()));
+    }
+
+    #[test]
+    fn encodes_the_documented_format() {
+        let packet = Packet {
+            port: Some(0x1f90),
+            ..Packet::default()
+        };
+        assert_eq!(packet.to_bytes(), b"PK\x01\x01\x02\x02\x1f\x90");
+    }
+
+    #[test]
+    fn rejects_bad_headers() {
+        assert_eq!(parse(b""), Err(ParseError::Truncated));
+        assert_eq!(parse(b"PK"), Err(ParseError::Truncated));
+        assert_eq!(parse(b"GIF89a"), Err(ParseError::BadMagic));
+        assert_eq!(parse(b"PK\x02\x00"), Err(ParseError::UnsupportedVersion(2)));
+    }
+
+    #[test]
+    fn rejects_bad_records() {
+        let bytes = sample().to_bytes();
+        for end in 4..bytes.len() {
+            assert_eq!(parse(&bytes[..end]), Err(ParseError::Truncated));
+        }
+        assert_eq!(parse(b"PK\x01\x00!"), Err(ParseError::TrailingBytes(1)));
+        assert_eq!(parse(b"PK\x01\x01\x09\x00"), Err(ParseError::UnknownTag(9)));
+        assert_eq!(
+            parse(b"PK\x01\x01\x01\x01\xff"),
+            Err(ParseError::InvalidName)
+        );
+    }
+}
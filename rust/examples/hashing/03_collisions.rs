@@ -0,0 +1,154 @@
+// Hashing 3: Collisions and a Naive Hasher
+// Demonstrates what hash collisions cost a HashMap, by plugging in bad hashers and counting the key comparisons each lookup makes
+//
+// Concepts: Hasher, BuildHasher, BuildHasherDefault, collisions, SipHash, RandomState, HashDoS
+// Difficulty: advanced
+// Minutes: 20
+//
+// A HashMap doesn't hash keys itself. Its third type parameter, the
+// `S` in `HashMap<K, V, S>`, is a `BuildHasher`: something that makes a
+// fresh `Hasher` for each key. The key's `Hash` impl feeds the hasher
+// bytes and numbers with `write`, and `finish` turns what it was fed
+// into the u64 the map uses. Any type that implements `Hasher` and
+// `Default` can be plugged in with `BuildHasherDefault<ItsType>`.
+//
+// That makes it easy to try bad ones. `SumHasher` adds up the bytes it
+// is fed, so "user0123" and "user0321" collide, and `FirstByteHasher`
+// only keeps the very first byte, so every key below, all starting with
+// "user", gets the same hash. The map still works with them: a
+// collision only means more keys to compare with `==` before finding the
+// right one. To see how many more, the keys count every comparison.
+//
+// With a good hasher, a lookup compares about once: the map goes
+// straight to the one key with that hash. With every hash the same, the
+// map has to walk past, on average, half the keys in it, and a lookup
+// that should take the same time at any size takes time proportional
+// to the size, like searching a Vec. Filling such a map is quadratic.
+//
+// Someone who knows a map's hash function can choose keys that collide,
+// and send a server a few thousand of them to make it crawl: a HashDoS
+// attack. So std's default, `RandomState`, hashes with SipHash using
+// keys picked at random for each map, and nobody outside can predict
+// which keys collide. It is why a HashMap iterates in a different order
+// each run, and why this program uses `BuildHasherDefault<DefaultHasher>`
+// instead, the same SipHash with fixed keys, to print the same thing
+// every time. For keys no attacker controls, crates like rustc-hash and
+// ahash trade that protection for speed.
+
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher, Hasher, RandomState};
+
+/// Adds up every byte it is fed: keys with the same bytes in any order
+/// collide.
+#[derive(Default)]
+struct SumHasher(u64);
+
+impl Hasher for SumHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 += u64::from(byte);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Keeps only the first byte it is fed: keys that start alike collide.
+#[derive(Default)]
+struct FirstByteHasher(Option<u8>);
+
+impl Hasher for FirstByteHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        if self.0.is_none() {
+            self.0 = bytes.first().copied();
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0.map_or(0, u64::from)
+    }
+}
+
+thread_local! {
+    /// How many times two keys have been compared.
+    static COMPARISONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// A String key that counts its comparisons.
+#[allow(clippy::derived_hash_with_manual_eq)] // eq still compares the whole String
+#[derive(Hash, Eq)]
+struct Key(String);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        COMPARISONS.set(COMPARISONS.get() + 1);
+        self.0 == other.0
+    }
+}
+
+const KEYS: u32 = 1000;
+
+fn names() -> impl Iterator<Item = String> {
+    (0..KEYS).map(|n| format!("user{n:04}"))
+}
+
+/// Fills a map using `S` with every name, then looks each one up,
+/// printing how many distinct hashes they got and the comparisons per
+/// lookup.
+fn measure<S: BuildHasher + Default>(label: &str) {
+    let build = S::default();
+    let hashes: HashSet<u64> = names().map(|name| build.hash_one(Key(name))).collect();
+
+    let mut map: HashMap<Key, u32, S> = HashMap::default();
+    for (n, name) in names().enumerate() {
+        map.insert(Key(name), n as u32);
+    }
+    COMPARISONS.set(0);
+    for name in names() {
+        assert!(map.contains_key(&Key(name)));
+    }
+    let per_lookup = COMPARISONS.get() as f64 / f64::from(KEYS);
+    println!(
+        "{label:<12} {:>5} distinct hashes  {per_lookup:>6.1} comparisons per lookup",
+        hashes.len()
+    );
+}
+
+fn main() {
+    println!("{KEYS} keys, user0000 to user{:04}:", KEYS - 1);
+    measure::<BuildHasherDefault<DefaultHasher>>("SipHash");
+    measure::<BuildHasherDefault<SumHasher>>("sum of bytes");
+    measure::<BuildHasherDefault<FirstByteHasher>>("first byte");
+
+    // Two maps with RandomState hash the same key differently
+    let (a, b) = (RandomState::new(), RandomState::new());
+    println!(
+        "\ntwo RandomStates agree on the hash of \"user0000\": {}",
+        a.hash_one("user0000") == b.hash_one("user0000")
+    );
+
+    // This would cause an error:
+    // let mut map: HashMap<&str, u32, FirstByteHasher> = HashMap::default();
+    // map.insert("user0000", 0);
+    // error[E0599]: the method `insert` exists for struct `HashMap<&str, u32, FirstByteHasher>`, but its trait bounds were not satisfied
+}
+
+/*
+ * Key Concepts:
+ * - HashMap<K, V, S> gets its hashers from S, a BuildHasher; BuildHasherDefault<H> makes one from any Default Hasher
+ * - A collision costs extra == comparisons, not wrong answers
+ * - When every key collides, a lookup degrades to a linear search, and filling the map to quadratic time
+ * - RandomState's per-map random keys defend against HashDoS, and shuffle iteration order
+ * - A faster, unkeyed hasher is fine for keys no attacker chooses
+ */
+
+// EXPECTED:
+// 1000 keys, user0000 to user0999:
+// SipHash       1000 distinct hashes     1.0 comparisons per lookup
+// sum of bytes    28 distinct hashes   237.0 comparisons per lookup
+// first byte       1 distinct hashes   500.5 comparisons per lookup
+//
+// two RandomStates agree on the hash of "user0000": false
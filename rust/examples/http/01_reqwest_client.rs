@@ -0,0 +1,233 @@
+// HTTP 1: Making Requests With reqwest
+// Demonstrates a shared Client, query parameters, JSON bodies, and HTTP errors
+//
+// Concepts: reqwest, Client, query parameters, JSON bodies, HTTP errors
+// Difficulty: intermediate
+// Minutes: 20
+//
+// reqwest is the usual HTTP client in async Rust. A request is built step
+// by step (method, URL, query, headers, body), sent with `.send().await`,
+// and answered with a Response whose body we read as text or decode as
+// JSON. One rule surprises most people: a 404 or a 500 is not an Err. The
+// server did answer, so send() succeeds, and checking the status is our
+// job.
+
+use std::error::Error;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::extract::Query;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SearchParams {
+    q: String,
+    page: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SearchResults {
+    query: String,
+    page: u32,
+    hits: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NewUser {
+    name: String,
+    email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct User {
+    id: u64,
+    name: String,
+    email: String,
+}
+
+/// A stand-in for a public test API like httpbin.org, so the example runs
+/// offline and answers the same way every time. The next example looks at
+/// writing servers properly.
+async fn spawn_test_api() -> std::io::Result<SocketAddr> {
+    async fn search(Query(params): Query<SearchParams>) -> Json<SearchResults> {
+        let crates = ["axum", "reqwest", "rustls", "serde", "tokio"];
+        let hits = crates
+            .iter()
+            .filter(|name| name.contains(params.q.as_str()))
+            .map(|name| name.to_string())
+            .collect();
+        Json(SearchResults {
+            query: params.q,
+            page: params.page.unwrap_or(1),
+            hits,
+        })
+    }
+
+    async fn create_user(
+        Json(user): Json<NewUser>,
+    ) -> Result<(StatusCode, Json<User>), StatusCode> {
+        if user.name.is_empty() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        Ok((
+            StatusCode::CREATED,
+            Json(User {
+                id: 1,
+                name: user.name,
+                email: user.email,
+            }),
+        ))
+    }
+
+    async fn slow() -> &'static str {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        "finally"
+    }
+
+    let app = Router::new()
+        .route("/search", get(search))
+        .route("/users", post(create_user))
+        .route("/slow", get(slow));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    Ok(addr)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let addr = spawn_test_api().await?;
+    let base = format!("http://{}", addr);
+
+    // Build one Client and reuse it: it keeps a pool of open connections,
+    // so later requests to the same server skip the TCP handshake
+    let client = Client::builder()
+        .timeout(Duration::from_secs(5))
+        .user_agent("learn-rust-http/0.1")
+        .build()?;
+
+    // GET with query parameters. query() percent-encodes the values, which
+    // gluing strings into the URL by hand would get wrong.
+    let response = client
+        .get(format!("{}/search", base))
+        .query(&[("q", "r"), ("page", "2")])
+        .send()
+        .await?;
+    println!(
+        "GET {}?{}",
+        response.url().path(),
+        response.url().query().unwrap_or("")
+    );
+    println!("  status: {}", response.status());
+    // json() reads the whole body and decodes it, consuming the response
+    let results: SearchResults = response.json().await?;
+    println!("  {:?}", results);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    println!("{}", response.status());
+    // error[E0382]: borrow of moved value: `response`
+
+    // Any Serialize type works as query parameters, too
+    let params = SearchParams {
+        q: "tokio & friends".to_string(),
+        page: None,
+    };
+    let response = client
+        .get(format!("{}/search", base))
+        .query(&params)
+        .send()
+        .await?;
+    println!("encoded query: {}", response.url().query().unwrap_or(""));
+
+    // POST with a JSON body: json() serializes it and sets Content-Type
+    let new_user = NewUser {
+        name: "Ferris".to_string(),
+        email: "ferris@example.com".to_string(),
+    };
+    let response = client
+        .post(format!("{}/users", base))
+        .json(&new_user)
+        .send()
+        .await?;
+    println!("POST /users");
+    println!("  status: {}", response.status());
+    println!(
+        "  content-type: {:?}",
+        response.headers()[reqwest::header::CONTENT_TYPE]
+    );
+    let user: User = response.json().await?;
+    println!("  {:?}", user);
+
+    // A 4xx answer is still Ok(response)...
+    let response = client
+        .post(format!("{}/users", base))
+        .json(&serde_json::json!({ "name": "", "email": "nobody@example.com" }))
+        .send()
+        .await?;
+    println!("empty name: status {}", response.status());
+    // ...until error_for_status turns it into an Err
+    match response.error_for_status() {
+        Ok(_) => println!("  accepted?"),
+        Err(error) => println!("  error_for_status: {:?}", error.status()),
+    }
+
+    // A body that doesn't fit NewUser is rejected by axum before our
+    // handler runs, and a path with no route is a plain 404
+    let response = client
+        .post(format!("{}/users", base))
+        .json(&serde_json::json!({ "name": "Ferris" }))
+        .send()
+        .await?;
+    println!("missing email: status {}", response.status());
+    let response = client.get(format!("{}/nope", base)).send().await?;
+    println!("unknown path: status {}", response.status());
+
+    // Real errors come from the connection itself. A per-request timeout
+    // overrides the client's.
+    let error = client
+        .get(format!("{}/slow", base))
+        .timeout(Duration::from_millis(100))
+        .send()
+        .await
+        .unwrap_err();
+    println!("slow endpoint: is_timeout = {}", error.is_timeout());
+
+    let closed = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+    let error = client
+        .get(format!("http://{}/", closed))
+        .send()
+        .await
+        .unwrap_err();
+    println!("closed port: is_connect = {}", error.is_connect());
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - Build one reqwest::Client and reuse it; it pools connections
+ * - .query() and .json() build the URL and body, with encoding done right
+ * - response.json::<T>() decodes the body with serde and consumes the response
+ * - 4xx and 5xx answers are Ok; check status() or call error_for_status()
+ * - Timeouts and refused connections are the errors: is_timeout(), is_connect()
+ */
+
+// EXPECTED:
+// GET /search?q=r&page=2
+//   status: 200 OK
+//   SearchResults { query: "r", page: 2, hits: ["reqwest", "rustls", "serde"] }
+// encoded query: q=tokio+%26+friends
+// POST /users
+//   status: 201 Created
+//   content-type: "application/json"
+//   User { id: 1, name: "Ferris", email: "ferris@example.com" }
+// empty name: status 400 Bad Request
+//   error_for_status: Some(400)
+// missing email: status 422 Unprocessable Entity
+// unknown path: status 404 Not Found
+// slow endpoint: is_timeout = true
+// closed port: is_connect = true
@@ -0,0 +1,485 @@
+// HTTP 2: A JSON Service With axum
+// Demonstrates routing, extractors, shared state, and JSON errors
+//
+// Concepts: axum, routing, extractors, shared state, JSON errors
+// Difficulty: intermediate
+// Minutes: 45
+//
+// An axum app is a Router that maps a method and a path to a handler. A
+// handler is an ordinary async fn: its arguments are extractors, which pull
+// typed values out of the request (Path, Query, Json, State), and its
+// return value is anything that implements IntoResponse. If an extractor
+// can't make sense of the request, axum answers with an error status and
+// the handler never runs.
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Todo {
+    id: u64,
+    title: String,
+    done: bool,
+}
+
+#[derive(Deserialize)]
+struct NewTodo {
+    title: String,
+}
+
+/// A PATCH body: only the fields that are present change.
+#[derive(Deserialize)]
+struct TodoChanges {
+    title: Option<String>,
+    done: Option<bool>,
+}
+
+/// `GET /todos?done=false&limit=10`; both parameters are optional.
+#[derive(Deserialize)]
+struct ListFilter {
+    done: Option<bool>,
+    limit: Option<usize>,
+}
+
+#[derive(Default)]
+struct Store {
+    next_id: u64,
+    todos: BTreeMap<u64, Todo>,
+}
+
+/// Every handler gets a clone of this. A std Mutex is fine as long as no
+/// handler holds the lock across an `.await`.
+type AppState = Arc<Mutex<Store>>;
+
+/// The ways a request can go wrong, each with its status code.
+#[derive(Debug)]
+enum ApiError {
+    NotFound(u64),
+    EmptyTitle,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::NotFound(id) => (StatusCode::NOT_FOUND, format!("no todo with id {}", id)),
+            ApiError::EmptyTitle => (
+                StatusCode::BAD_REQUEST,
+                "title must not be empty".to_string(),
+            ),
+        };
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+async fn health() -> &'static str {
+    "ok"
+}
+
+async fn list_todos(
+    State(state): State<AppState>,
+    Query(filter): Query<ListFilter>,
+) -> Json<Vec<Todo>> {
+    let store = state.lock().unwrap();
+    let todos = store
+        .todos
+        .values()
+        .filter(|todo| filter.done.is_none_or(|done| todo.done == done))
+        .take(filter.limit.unwrap_or(usize::MAX))
+        .cloned()
+        .collect();
+    Json(todos)
+}
+
+async fn create_todo(
+    State(state): State<AppState>,
+    Json(new): Json<NewTodo>,
+) -> Result<(StatusCode, Json<Todo>), ApiError> {
+    let title = new.title.trim();
+    if title.is_empty() {
+        return Err(ApiError::EmptyTitle);
+    }
+    let mut store = state.lock().unwrap();
+    store.next_id += 1;
+    let todo = Todo {
+        id: store.next_id,
+        title: title.to_string(),
+        done: false,
+    };
+    store.todos.insert(todo.id, todo.clone());
+    Ok((StatusCode::CREATED, Json(todo)))
+}
+
+async fn get_todo(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Result<Json<Todo>, ApiError> {
+    let store = state.lock().unwrap();
+    store
+        .todos
+        .get(&id)
+        .cloned()
+        .map(Json)
+        .ok_or(ApiError::NotFound(id))
+}
+
+// The body extractor (Json) must come last: it consumes the request body,
+// and the others only look at the head
+async fn update_todo(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(changes): Json<TodoChanges>,
+) -> Result<Json<Todo>, ApiError> {
+    let mut store = state.lock().unwrap();
+    let todo = store.todos.get_mut(&id).ok_or(ApiError::NotFound(id))?;
+    if let Some(title) = changes.title {
+        if title.trim().is_empty() {
+            return Err(ApiError::EmptyTitle);
+        }
+        todo.title = title.trim().to_string();
+    }
+    if let Some(done) = changes.done {
+        todo.done = done;
+    }
+    Ok(Json(todo.clone()))
+}
+
+async fn delete_todo(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+) -> Result<StatusCode, ApiError> {
+    let mut store = state.lock().unwrap();
+    match store.todos.remove(&id) {
+        Some(_) => Ok(StatusCode::NO_CONTENT),
+        None => Err(ApiError::NotFound(id)),
+    }
+}
+
+// This would cause an error:
+#[cfg(feature = "broken")]
+async fn body_first(Json(changes): Json<TodoChanges>, Path(id): Path<u64>) -> String {
+    format!("{} {:?}", id, changes.done)
+}
+// error[E0277]: the trait bound `fn(Json<TodoChanges>, Path<u64>) -> ... {body_first}: Handler<_, _>` is not satisfied
+// (reported where the handler is passed to a route; Json has to be last)
+
+/// The whole service. Building it in a function lets the tests start
+/// exactly the app that main serves.
+fn app() -> Router {
+    let router = Router::new()
+        .route("/health", get(health))
+        .route("/todos", get(list_todos).post(create_todo))
+        .route(
+            "/todos/{id}",
+            get(get_todo).patch(update_todo).delete(delete_todo),
+        );
+    #[cfg(feature = "broken")]
+    let router = router.route("/broken", axum::routing::post(body_first));
+    router.with_state(AppState::default())
+}
+
+/// Serves `app()` on a free port until `stop` fires, then finishes the
+/// requests in flight and returns.
+async fn serve(
+    stop: oneshot::Receiver<()>,
+) -> std::io::Result<(SocketAddr, tokio::task::JoinHandle<std::io::Result<()>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let server = axum::serve(listener, app()).with_graceful_shutdown(async {
+        let _ = stop.await;
+    });
+    Ok((addr, tokio::spawn(async move { server.await })))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (stop, stopped) = oneshot::channel();
+    let (addr, server) = serve(stopped).await?;
+    let base = format!("http://{}", addr);
+    let client = reqwest::Client::new();
+
+    let health = client.get(format!("{}/health", base)).send().await?;
+    println!(
+        "GET /health -> {} {:?}",
+        health.status(),
+        health.text().await?
+    );
+
+    for title in ["write the chapter", "test the service", "  "] {
+        let response = client
+            .post(format!("{}/todos", base))
+            .json(&serde_json::json!({ "title": title }))
+            .send()
+            .await?;
+        println!(
+            "POST /todos {:?} -> {} {}",
+            title,
+            response.status(),
+            response.text().await?
+        );
+    }
+
+    let response = client
+        .patch(format!("{}/todos/1", base))
+        .json(&serde_json::json!({ "done": true }))
+        .send()
+        .await?;
+    println!(
+        "PATCH /todos/1 -> {} {}",
+        response.status(),
+        response.text().await?
+    );
+
+    let pending: Vec<Todo> = client
+        .get(format!("{}/todos", base))
+        .query(&[("done", "false")])
+        .send()
+        .await?
+        .json()
+        .await?;
+    println!("GET /todos?done=false -> {:?}", pending);
+
+    let response = client.delete(format!("{}/todos/2", base)).send().await?;
+    println!("DELETE /todos/2 -> {}", response.status());
+    let response = client.get(format!("{}/todos/2", base)).send().await?;
+    println!(
+        "GET /todos/2 -> {} {}",
+        response.status(),
+        response.text().await?
+    );
+
+    // Path<u64> can't parse "abc", so axum rejects it before get_todo runs
+    let response = client.get(format!("{}/todos/abc", base)).send().await?;
+    println!("GET /todos/abc -> {}", response.status());
+
+    let _ = stop.send(());
+    server.await??;
+    println!("server shut down");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Client;
+    use serde_json::{json, Value};
+
+    /// A running service on its own port, stopped when dropped.
+    struct TestServer {
+        base: String,
+        client: Client,
+        _stop: oneshot::Sender<()>,
+    }
+
+    impl TestServer {
+        async fn start() -> TestServer {
+            let (stop, stopped) = oneshot::channel();
+            let (addr, _) = serve(stopped).await.unwrap();
+            TestServer {
+                base: format!("http://{}", addr),
+                client: Client::new(),
+                _stop: stop,
+            }
+        }
+
+        fn url(&self, path: &str) -> String {
+            format!("{}{}", self.base, path)
+        }
+
+        async fn create(&self, title: &str) -> Todo {
+            let response = self
+                .client
+                .post(self.url("/todos"))
+                .json(&json!({ "title": title }))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            response.json().await.unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn health_says_ok() {
+        let server = TestServer::start().await;
+        let response = server
+            .client
+            .get(server.url("/health"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+
+    #[tokio::test]
+    async fn created_todos_can_be_fetched() {
+        let server = TestServer::start().await;
+        let todo = server.create("  buy milk ").await;
+        assert_eq!(
+            todo,
+            Todo {
+                id: 1,
+                title: "buy milk".to_string(),
+                done: false
+            }
+        );
+        let fetched: Todo = server
+            .client
+            .get(server.url("/todos/1"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(fetched, todo);
+    }
+
+    #[tokio::test]
+    async fn list_filters_and_limits() {
+        let server = TestServer::start().await;
+        for title in ["a", "b", "c"] {
+            server.create(title).await;
+        }
+        server
+            .client
+            .patch(server.url("/todos/2"))
+            .json(&json!({ "done": true }))
+            .send()
+            .await
+            .unwrap();
+
+        let get = |query: &'static [(&'static str, &'static str)]| {
+            let request = server.client.get(server.url("/todos")).query(query);
+            async move {
+                let todos: Vec<Todo> = request.send().await.unwrap().json().await.unwrap();
+                todos.into_iter().map(|todo| todo.title).collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(get(&[]).await, ["a", "b", "c"]);
+        assert_eq!(get(&[("done", "true")]).await, ["b"]);
+        assert_eq!(get(&[("done", "false")]).await, ["a", "c"]);
+        assert_eq!(get(&[("limit", "2")]).await, ["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn update_changes_only_the_given_fields() {
+        let server = TestServer::start().await;
+        server.create("draft").await;
+        let response = server
+            .client
+            .patch(server.url("/todos/1"))
+            .json(&json!({ "title": "final" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let todo: Todo = response.json().await.unwrap();
+        assert_eq!((todo.title.as_str(), todo.done), ("final", false));
+    }
+
+    #[tokio::test]
+    async fn deleted_todos_are_gone() {
+        let server = TestServer::start().await;
+        server.create("temporary").await;
+        let delete = || server.client.delete(server.url("/todos/1")).send();
+        assert_eq!(delete().await.unwrap().status(), StatusCode::NO_CONTENT);
+        assert_eq!(delete().await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn errors_are_json_with_a_status() {
+        let server = TestServer::start().await;
+        let response = server
+            .client
+            .get(server.url("/todos/7"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body, json!({ "error": "no todo with id 7" }));
+
+        let response = server
+            .client
+            .post(server.url("/todos"))
+            .json(&json!({ "title": "   " }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn axum_rejects_requests_the_extractors_cannot_read() {
+        let server = TestServer::start().await;
+        // The JSON is fine, but it isn't a NewTodo
+        let wrong_shape = server
+            .client
+            .post(server.url("/todos"))
+            .json(&json!({ "name": "no title" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(wrong_shape.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        // Without a JSON Content-Type, Json<T> won't even try
+        let not_json = server
+            .client
+            .post(server.url("/todos"))
+            .body(r#"{"title": "x"}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(not_json.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let bad_id = server
+            .client
+            .get(server.url("/todos/abc"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(bad_id.status(), StatusCode::BAD_REQUEST);
+
+        // The route exists, but not for this method
+        let wrong_method = server
+            .client
+            .put(server.url("/todos"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(wrong_method.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Router::route maps a path and methods to handlers; `{id}` captures a segment
+ * - Extractors (State, Path, Query, Json) turn the request into typed arguments
+ * - The body extractor goes last, because it consumes the request
+ * - Return anything that implements IntoResponse, like (StatusCode, Json<T>)
+ * - An error enum with IntoResponse keeps status codes in one place
+ * - with_graceful_shutdown stops the server once in-flight requests finish
+ */
+
+// EXPECTED:
+// GET /health -> 200 OK "ok"
+// POST /todos "write the chapter" -> 201 Created {"id":1,"title":"write the chapter","done":false}
+// POST /todos "test the service" -> 201 Created {"id":2,"title":"test the service","done":false}
+// POST /todos "  " -> 400 Bad Request {"error":"title must not be empty"}
+// PATCH /todos/1 -> 200 OK {"id":1,"title":"write the chapter","done":true}
+// GET /todos?done=false -> [Todo { id: 2, title: "test the service", done: false }]
+// DELETE /todos/2 -> 204 No Content
+// GET /todos/2 -> 404 Not Found {"error":"no todo with id 2"}
+// GET /todos/abc -> 400 Bad Request
+// server shut down
@@ -0,0 +1,75 @@
+// Input 1: Reading a Line from stdin
+// Demonstrates read_line, the newline it keeps, and reading until the input ends
+//
+// Concepts: stdin, read_line, trim, flush, end of input, lines
+// Difficulty: beginner
+// Minutes: 10
+//
+// A program reads what the user types from standard input, stdin. In
+// Rust, io::stdin().read_line(&mut buffer) waits for a whole line and
+// appends it to a String you own, newline and all, and returns how many
+// bytes it read. Zero bytes means the input has ended: the user pressed
+// Ctrl-D (Ctrl-Z then Enter on Windows), or stdin is a file or a pipe
+// that has run out. Run it and type, or give it its input up front:
+//
+//     cargo run -p input-examples --bin 01_read_line
+//     printf 'Ferris\none\ntwo\n' | cargo run -p input-examples --bin 01_read_line
+//
+// The tutor runs every example with nothing on stdin, so the output at
+// the bottom is what it prints when the input ends straight away.
+
+use std::io::{self, BufRead, Write};
+
+fn main() -> io::Result<()> {
+    // print! doesn't end the line, and stdout only writes out whole lines
+    // by itself; flush() makes the question appear before we wait
+    print!("What's your name? ");
+    io::stdout().flush()?;
+
+    let mut name = String::new();
+    let bytes = io::stdin().read_line(&mut name)?;
+    if bytes == 0 {
+        println!("(no input)");
+    } else {
+        // The line still ends in "\n" (or "\r\n" on Windows)
+        println!("read {bytes} bytes: {name:?}");
+        // trim() borrows the part without the whitespace around it
+        let name = name.trim();
+        if name.is_empty() {
+            println!("Hello, whoever you are!");
+        } else {
+            println!("Hello, {name}!");
+        }
+    }
+
+    // lines() is read_line in a loop, with the newlines taken off. It
+    // stops at the end of the input, and each line can fail on its own
+    // (with invalid UTF-8, say), so each one is an io::Result<String>.
+    // lock() takes stdin for ourselves, instead of once per line.
+    println!("Now type some lines, and end the input when you're done:");
+    let mut count = 0;
+    let mut characters = 0;
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        count += 1;
+        characters += line.chars().count();
+        println!("{count:>3} | {line}");
+    }
+    println!("{count} line(s), {characters} character(s)");
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - io::stdin().read_line(&mut s) appends a line to s, including its newline
+ * - It returns the number of bytes read; 0 means the input has ended
+ * - trim() (or trim_end()) removes the newline before you use the line
+ * - Flush stdout after print! so a prompt shows before the program waits
+ * - stdin().lock().lines() reads line by line until the end of the input
+ * - Piping text into a program (`printf ... | cargo run`) is typing without a keyboard
+ */
+
+// EXPECTED:
+// What's your name? (no input)
+// Now type some lines, and end the input when you're done:
+// 0 line(s), 0 character(s)
@@ -0,0 +1,93 @@
+// Input 3: Asking Again Until the Answer Is Valid
+// Demonstrates a loop that re-prompts on bad input, written so it can be tested
+//
+// Concepts: loop, BufRead, Write, closures as parsers, testing input code
+// Difficulty: intermediate
+// Minutes: 15
+//
+// A program that asks a question shouldn't crash, or carry on with
+// nonsense, when the answer is wrong; it should say what's wrong and ask
+// again. That loop is the same for every question: print it, read a
+// line, end if the input ends, parse, and either return the answer or
+// print the problem and go round again. So it is written once, in this
+// chapter's library (`ask/lib.rs`):
+//
+//     ask_until(&mut input, &mut output, "How old are you?", number_in(0..=150))
+//
+// The parser is a closure, returning Ok(answer) or Err(what's wrong), so
+// each question brings its own rules. And the input and output are any
+// BufRead and Write, not stdin and stdout: main() passes the real ones,
+// and the first part of this example, like the library's tests, passes a
+// byte string of answers and collects what would have been printed. The
+// chapter's `tests/` go one step further and type into the binaries
+// themselves, through a pipe:
+//
+//     printf 'old\n200\n30\nmaybe\ny\n' | cargo run -p input-examples --bin 03_asking_again
+
+use std::io;
+
+use ask::{ask_until, number_in, yes_or_no};
+use learn_rust_core::section;
+
+fn main() -> io::Result<()> {
+    section("scripted answers");
+    let mut script = "old\n200\n30\n".as_bytes();
+    let mut transcript = Vec::new();
+    let age = ask_until(
+        &mut script,
+        &mut transcript,
+        "How old are you?",
+        number_in(0..=150),
+    )?;
+    // What the user would have seen, without what they typed
+    print!("{}", String::from_utf8_lossy(&transcript));
+    println!("=> {age:?}");
+
+    section("your answers");
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
+    let Some(age) = ask_until(
+        &mut input,
+        &mut output,
+        "How old are you?",
+        number_in(0..=150),
+    )?
+    else {
+        println!("(no answer)");
+        return Ok(());
+    };
+    let Some(sure) = ask_until(
+        &mut input,
+        &mut output,
+        &format!("{age}, really?"),
+        yes_or_no,
+    )?
+    else {
+        println!("(no answer)");
+        return Ok(());
+    };
+    if sure {
+        println!("In ten years you'll be {}.", age + 10);
+    } else {
+        println!("Then we'll never know.");
+    }
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - Loop until the answer parses, saying what was wrong each time
+ * - Treat the end of the input as its own case: there is no answer coming
+ * - Write the loop once and pass each question's rules in as a closure
+ * - Take `impl BufRead` and `impl Write`, so tests can pass bytes and a Vec
+ * - let-else handles "no answer" and keeps the happy path unindented
+ */
+
+// EXPECTED:
+// -- scripted answers --
+// How old are you? "old" is not a number; try again.
+// How old are you? 200 is not between 0 and 150; try again.
+// How old are you? => Some(30)
+// -- your answers --
+// How old are you? (no answer)
@@ -0,0 +1,111 @@
+// Input 4: A Guessing Game
+// Demonstrates everything in this chapter together: prompts, parsing, retries, and the end of input
+//
+// Concepts: game loop, Ordering, cmp, command-line arguments, seeding from the clock
+// Difficulty: intermediate
+// Minutes: 15
+//
+// The program thinks of a number from 1 to 100 and you have seven
+// guesses; after each wrong one it says whether the number is bigger or
+// smaller. Seven is enough if you halve what's left every time, since
+// 2^7 = 128 > 100. An answer that isn't a number from 1 to 100 is asked
+// again, and doesn't cost a guess.
+//
+// The secret comes from the clock, so it is different every game. Pass
+// one as an argument to choose it yourself; that is how the chapter's
+// tests play a game whose ending they know:
+//
+//     cargo run -p input-examples --bin 04_guessing_game
+//     printf '50\n25\n37\n' | cargo run -p input-examples --bin 04_guessing_game -- 37
+//
+// (Real programs would use the rand crate for this. The clock is good
+// enough for a game, and keeps the chapter free of dependencies.)
+
+use std::cmp::Ordering;
+use std::env;
+use std::io;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ask::{ask_until, number_in};
+
+const LOWEST: u32 = 1;
+const HIGHEST: u32 = 100;
+const GUESSES: u32 = 7;
+
+/// The number to guess: the first argument, if there is one, or else
+/// whatever the clock's nanoseconds come to.
+fn secret() -> Result<u32, String> {
+    if let Some(argument) = env::args().nth(1) {
+        return number_in(LOWEST..=HIGHEST)(&argument);
+    }
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(0);
+    Ok(nanos % HIGHEST + LOWEST)
+}
+
+fn play(secret: u32) -> io::Result<()> {
+    println!("I'm thinking of a number from {LOWEST} to {HIGHEST}. You have {GUESSES} guesses.");
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
+
+    for guess_number in 1..=GUESSES {
+        let question = format!("Guess {guess_number}:");
+        let Some(guess) = ask_until(
+            &mut input,
+            &mut output,
+            &question,
+            number_in(LOWEST..=HIGHEST),
+        )?
+        else {
+            println!("(no more input; the number stays a secret)");
+            return Ok(());
+        };
+        // cmp() gives an Ordering, and match makes us handle all three
+        match guess.cmp(&secret) {
+            Ordering::Less => println!("Bigger than {guess}."),
+            Ordering::Greater => println!("Smaller than {guess}."),
+            Ordering::Equal => {
+                let guesses = if guess_number == 1 {
+                    "guess"
+                } else {
+                    "guesses"
+                };
+                println!("You got it in {guess_number} {guesses}!");
+                return Ok(());
+            }
+        }
+    }
+    println!("Out of guesses! It was {secret}.");
+    Ok(())
+}
+
+fn main() {
+    let secret = match secret() {
+        Ok(secret) => secret,
+        Err(problem) => {
+            eprintln!("error: {problem}");
+            process::exit(2);
+        }
+    };
+    if let Err(error) = play(secret) {
+        eprintln!("error: {error}");
+        process::exit(1);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A game loop: ask, parse, compare, answer, until won, lost, or out of input
+ * - a.cmp(&b) returns Ordering::Less, Equal, or Greater; match covers all three
+ * - Invalid answers are asked again and don't count as a turn
+ * - A way to fix the "random" part (here, an argument) makes the program testable
+ * - Wrong arguments exit with a message on stderr and a non-zero status
+ */
+
+// EXPECTED:
+// I'm thinking of a number from 1 to 100. You have 7 guesses.
+// Guess 1: (no more input; the number stays a secret)
@@ -0,0 +1,111 @@
+//! Asking the user something until the answer makes sense. [`ask_until`]
+//! prints a question, reads a line, and hands it to a parser; if the
+//! parser says what is wrong with it, it prints that and asks again.
+//! [`number_in`] and [`yes_or_no`] are two such parsers.
+//!
+//! Everything takes its input as `impl BufRead` and its output as
+//! `impl Write`, not stdin and stdout themselves, so the same code reads
+//! from the keyboard in the examples and from a byte string in tests:
+//!
+//! ```
+//! let mut input = "lots\n7\n".as_bytes();
+//! let mut output = Vec::new();
+//! let answer = ask::ask_until(&mut input, &mut output, "How many?", ask::number_in(1..=10));
+//! assert_eq!(answer.unwrap(), Some(7));
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "How many? \"lots\" is not a number; try again.\nHow many? "
+//! );
+//! ```
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Prints `question` and reads answers from `input` until `parse` accepts
+/// one, printing why it didn't after each one it rejects. The answer is
+/// trimmed before it is parsed. Returns `None` if the input ends first,
+/// which is how a program learns that the user pressed Ctrl-D, or that
+/// nobody is typing at all.
+pub fn ask_until<T>(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+    mut parse: impl FnMut(&str) -> Result<T, String>,
+) -> io::Result<Option<T>> {
+    let mut line = String::new();
+    loop {
+        write!(output, "{question} ")?;
+        // stdout only writes out a whole line by itself, and the question
+        // has no newline, so without this the user wouldn't see it
+        output.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        match parse(line.trim()) {
+            Ok(answer) => return Ok(Some(answer)),
+            Err(problem) => writeln!(output, "{problem}; try again.")?,
+        }
+    }
+}
+
+/// A parser for [`ask_until`] that accepts a number in `range`.
+pub fn number_in<T>(range: RangeInclusive<T>) -> impl Fn(&str) -> Result<T, String>
+where
+    T: FromStr + PartialOrd + Display,
+{
+    move |answer| {
+        let number: T = answer
+            .parse()
+            .map_err(|_| format!("{answer:?} is not a number"))?;
+        if range.contains(&number) {
+            Ok(number)
+        } else {
+            Err(format!(
+                "{number} is not between {} and {}",
+                range.start(),
+                range.end()
+            ))
+        }
+    }
+}
+
+/// A parser for [`ask_until`] that accepts y, yes, n, or no, in any case.
+pub fn yes_or_no(answer: &str) -> Result<bool, String> {
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" => Ok(true),
+        "n" | "no" => Ok(false),
+        _ => Err(format!("{answer:?} is not yes or no")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_answers_are_explained_and_asked_again() {
+        let mut input = " 0 \n11\n\n 3 \n".as_bytes();
+        let mut output = Vec::new();
+        let answer = ask_until(&mut input, &mut output, "n?", number_in(1..=10)).unwrap();
+        assert_eq!(answer, Some(3));
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "n? 0 is not between 1 and 10; try again.\n\
+             n? 11 is not between 1 and 10; try again.\n\
+             n? \"\" is not a number; try again.\n\
+             n? "
+        );
+    }
+
+    #[test]
+    fn the_end_of_the_input_is_no_answer() {
+        let mut input = "maybe\n".as_bytes();
+        let answer = ask_until(&mut input, &mut Vec::new(), "Sure?", yes_or_no).unwrap();
+        assert_eq!(answer, None);
+        assert_eq!(yes_or_no("YES"), Ok(true));
+        assert_eq!(yes_or_no("n"), Ok(false));
+    }
+}
@@ -0,0 +1,129 @@
+// Each test runs one of the chapter's binaries the way a user would, with
+// the answers typed in up front: it writes them to the program's stdin
+// through a pipe, closes it, and checks everything printed back, prompts
+// included. Cargo builds the binaries before the tests and says where
+// they are in CARGO_BIN_EXE_<name>.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Runs `program` with `args`, typing `input`, and waits for it to end.
+fn run(program: &str, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("the example should start");
+    // Dropping stdin closes it, which the program sees as the end of input
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(input.as_bytes()).unwrap();
+    drop(stdin);
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn read_line_greets_by_name_and_numbers_the_lines() {
+    let output = run(
+        env!("CARGO_BIN_EXE_01_read_line"),
+        &[],
+        "  Ferris \r\none\ntwo lines\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "What's your name? read 11 bytes: \"  Ferris \\r\\n\"\n\
+         Hello, Ferris!\n\
+         Now type some lines, and end the input when you're done:\n  \
+           1 | one\n  \
+           2 | two lines\n\
+         2 line(s), 12 character(s)\n"
+    );
+}
+
+#[test]
+fn bad_numbers_are_reported_on_stderr_and_skipped() {
+    let output = run(
+        env!("CARGO_BIN_EXE_02_parsing_input"),
+        &[],
+        "1\n 2 \nthree\n\n40\n99999999999\n",
+    );
+    assert!(output.status.success());
+    assert!(stdout(&output).ends_with("-- adding up stdin --\n3 number(s), total 43\n"));
+    assert_eq!(
+        stderr(&output),
+        "line 3: skipping \"three\": it is not a whole number\n\
+         line 6: skipping \"99999999999\": it is too big for an i32\n"
+    );
+}
+
+#[test]
+fn invalid_answers_are_asked_again() {
+    let output = run(
+        env!("CARGO_BIN_EXE_03_asking_again"),
+        &[],
+        "old\n200\n30\nmaybe\ny\n",
+    );
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let (_, answers) = stdout.split_once("-- your answers --\n").unwrap();
+    assert_eq!(
+        answers,
+        "How old are you? \"old\" is not a number; try again.\n\
+         How old are you? 200 is not between 0 and 150; try again.\n\
+         How old are you? 30, really? \"maybe\" is not yes or no; try again.\n\
+         30, really? In ten years you'll be 40.\n"
+    );
+}
+
+#[test]
+fn the_guessing_game_narrows_in_on_the_number() {
+    let output = run(
+        env!("CARGO_BIN_EXE_04_guessing_game"),
+        &["37"],
+        "50\nfifty\n25\n0\n37\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "I'm thinking of a number from 1 to 100. You have 7 guesses.\n\
+         Guess 1: Smaller than 50.\n\
+         Guess 2: \"fifty\" is not a number; try again.\n\
+         Guess 2: Bigger than 25.\n\
+         Guess 3: 0 is not between 1 and 100; try again.\n\
+         Guess 3: You got it in 3 guesses!\n"
+    );
+}
+
+#[test]
+fn the_guessing_game_ends_after_seven_wrong_guesses() {
+    let output = run(
+        env!("CARGO_BIN_EXE_04_guessing_game"),
+        &["100"],
+        &"1\n".repeat(8),
+    );
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    assert_eq!(stdout.matches("Bigger than 1.").count(), 7);
+    assert!(stdout.ends_with("Guess 7: Bigger than 1.\nOut of guesses! It was 100.\n"));
+}
+
+#[test]
+fn the_guessing_game_stops_when_the_input_ends() {
+    let output = run(env!("CARGO_BIN_EXE_04_guessing_game"), &["5"], "9\n");
+    assert!(output.status.success());
+    assert!(stdout(&output).ends_with("Guess 2: (no more input; the number stays a secret)\n"));
+
+    let output = run(env!("CARGO_BIN_EXE_04_guessing_game"), &["101"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stderr(&output), "error: 101 is not between 1 and 100\n");
+}
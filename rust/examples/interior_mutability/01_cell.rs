@@ -0,0 +1,120 @@
+// Interior Mutability 1: Cell<T>
+// Demonstrates Cell::get, set, replace, take, and when &self must mutate
+//
+// Concepts: Cell, get, set, replace, take
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Rust's rule is "many readers or one writer", checked at compile time.
+// Sometimes a value that is shared, and so only reachable through &self,
+// still needs to change: a counter of how often a method ran, a cached
+// answer, a flag. Cell<T> allows that for values we can copy in and out
+// whole. Since nobody can ever hold a reference *into* a Cell, changing
+// its contents can't invalidate a reference, and no checks are needed.
+
+use std::cell::Cell;
+
+/// A parser that counts how many times it was asked to parse, even
+/// though parsing only needs &self.
+struct Parser {
+    calls: Cell<u32>,
+}
+
+impl Parser {
+    fn parse(&self, text: &str) -> Option<i64> {
+        self.calls.set(self.calls.get() + 1);
+        text.trim().parse().ok()
+    }
+}
+
+/// A node that remembers whether anyone has looked at it.
+#[derive(Default)]
+struct Node {
+    name: String,
+    visited: Cell<bool>,
+}
+
+fn visit_all(nodes: &[Node]) {
+    for node in nodes {
+        // Only &Node here, yet we can flip the flag
+        node.visited.set(true);
+    }
+}
+
+fn main() {
+    let parser = Parser {
+        calls: Cell::new(0),
+    };
+    let shared = &parser;
+    let also_shared = &parser;
+    println!("{:?}", shared.parse("42"));
+    println!("{:?}", also_shared.parse("nope"));
+    println!("calls: {}", parser.calls.get());
+
+    // get() copies the value out, so it needs T: Copy. replace() and take()
+    // work for any T by swapping a whole value in.
+    let name = Cell::new(String::from("draft"));
+    let old = name.replace(String::from("final"));
+    println!("replaced {:?}", old);
+    println!("took {:?}, left {:?}", name.take(), name.take());
+
+    let nodes = vec![
+        Node {
+            name: "a".into(),
+            ..Default::default()
+        },
+        Node {
+            name: "b".into(),
+            ..Default::default()
+        },
+    ];
+    println!(
+        "before: {:?}",
+        nodes.iter().map(|n| n.visited.get()).collect::<Vec<_>>()
+    );
+    visit_all(&nodes);
+    println!(
+        "after: {:?}",
+        nodes
+            .iter()
+            .map(|n| (n.name.as_str(), n.visited.get()))
+            .collect::<Vec<_>>()
+    );
+
+    // With a &mut, there is no need for the Cell tricks at all
+    let mut counter = Cell::new(10);
+    *counter.get_mut() += 1;
+    println!("get_mut: {}", counter.into_inner());
+
+    // Cell is free: it has the same size as what it holds
+    println!(
+        "size of Cell<u64>: {} bytes",
+        std::mem::size_of::<Cell<u64>>()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    std::thread::scope(|s| {
+        s.spawn(|| parser.parse("1"));
+    });
+    // error[E0277]: `Cell<u32>` cannot be shared between threads safely
+}
+
+/*
+ * Key Concepts:
+ * - Cell<T> lets code with only &self change a value, by moving whole values in and out
+ * - get() needs T: Copy; set(), replace(), take() work for any T
+ * - No references into a Cell exist, so it needs no runtime checks
+ * - Cell is not Sync: it's for one thread (threads use atomics or Mutex)
+ */
+
+// EXPECTED:
+// Some(42)
+// None
+// calls: 2
+// replaced "draft"
+// took "final", left ""
+// before: [false, false]
+// after: [("a", true), ("b", true)]
+// get_mut: 11
+// size of Cell<u64>: 8 bytes
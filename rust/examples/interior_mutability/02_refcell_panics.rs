@@ -0,0 +1,149 @@
+// Interior Mutability 2: RefCell and Runtime Borrow Panics
+// Demonstrates how RefCell borrows fail at runtime, and how to avoid it
+//
+// Concepts: RefCell, BorrowMutError, try_borrow
+// Difficulty: intermediate
+// Minutes: 10
+//
+// RefCell<T> hands out real references into its value, so it must enforce
+// "many readers or one writer" itself, counting borrows as the program
+// runs. Break the rule and borrow_mut() panics. The compiler can't warn
+// us, so the bugs are the kind that tests don't hit: a guard kept alive a
+// little too long, or a callback that reaches back into the object that
+// is calling it. This example triggers both and then fixes them.
+
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Runs `f` and reports whether it panicked, and with what message.
+fn attempt(label: &str, f: impl FnOnce()) {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(()) => println!("{}: ok", label),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| payload.downcast_ref::<&str>().copied())
+                .unwrap_or("?");
+            println!("{}: panicked: {}", label, message);
+        }
+    }
+}
+
+// A listener is handed the bus along with the event
+type Listener = fn(&Bus, &str);
+
+/// Sends every event to its listeners. A listener is handed the bus, so
+/// it can react by subscribing more listeners or sending more events.
+#[derive(Default)]
+struct Bus {
+    listeners: RefCell<Vec<Listener>>,
+    log: RefCell<Vec<String>>,
+}
+
+impl Bus {
+    fn subscribe(&self, listener: Listener) {
+        self.listeners.borrow_mut().push(listener);
+    }
+
+    /// Bug: holds the borrow of `listeners` while calling them.
+    fn send_holding_borrow(&self, event: &str) {
+        for listener in self.listeners.borrow().iter() {
+            listener(self, event);
+        }
+    }
+
+    /// Fix: copy the list out, and let the borrow end, before calling.
+    fn send(&self, event: &str) {
+        let listeners = self.listeners.borrow().clone();
+        for listener in listeners {
+            listener(self, event);
+        }
+    }
+}
+
+fn log_event(bus: &Bus, event: &str) {
+    bus.log.borrow_mut().push(event.to_string());
+}
+
+fn subscribe_on_first(bus: &Bus, event: &str) {
+    if event == "first" {
+        bus.subscribe(log_event);
+    }
+}
+
+fn main() {
+    // Keep the default panic messages out of the output
+    panic::set_hook(Box::new(|_| {}));
+
+    let scores = RefCell::new(vec![10, 20]);
+
+    // A guard lives until the end of its scope. Here `first` is still
+    // alive when we ask for a mutable borrow.
+    attempt("write while a reader lives", || {
+        let first = scores.borrow();
+        scores.borrow_mut().push(first[0]);
+    });
+
+    // Copying the value out ends the borrow first
+    attempt("copy, then write", || {
+        let first = scores.borrow()[0];
+        scores.borrow_mut().push(first);
+    });
+
+    // The same bug hides in a match or if let on a borrow: the guard lives
+    // for the whole statement
+    attempt("borrow in a match scrutinee", || {
+        match scores.borrow().len() {
+            3 => scores.borrow_mut().clear(),
+            _ => scores.borrow_mut().push(0),
+        }
+    });
+    println!("scores: {:?}", scores.borrow());
+
+    // try_borrow_mut() turns the panic into a Result
+    let reader = scores.borrow();
+    match scores.try_borrow_mut() {
+        Ok(_) => println!("try_borrow_mut: got it"),
+        Err(error) => println!("try_borrow_mut: {}", error),
+    }
+    drop(reader);
+
+    // Re-entrancy: a listener subscribes while the bus iterates over its
+    // listeners, which needs borrow_mut() during the borrow()
+    let bus = Bus::default();
+    bus.subscribe(subscribe_on_first);
+    attempt("subscribe during send (holding the borrow)", || {
+        bus.send_holding_borrow("first")
+    });
+
+    let bus = Bus::default();
+    bus.subscribe(subscribe_on_first);
+    attempt("subscribe during send (copied list)", || bus.send("first"));
+    bus.send("second");
+    println!("logged: {:?}", bus.log.borrow());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let inner: &mut Vec<i32> = scores.borrow_mut();
+    // error[E0308]: mismatched types (expected `&mut Vec<i32>`, found `RefMut<'_, Vec<{integer}>>`)
+}
+
+/*
+ * Key Concepts:
+ * - RefCell counts borrows at runtime; a conflicting borrow_mut() panics
+ * - A Ref/RefMut guard lives until its scope ends, including a whole match
+ * - Copy or clone out what you need so the borrow ends early
+ * - Callbacks that reach back into their caller are the classic trap
+ * - try_borrow()/try_borrow_mut() report conflicts as a Result
+ */
+
+// EXPECTED:
+// write while a reader lives: panicked: RefCell already borrowed
+// copy, then write: ok
+// borrow in a match scrutinee: panicked: RefCell already borrowed
+// scores: [10, 20, 10]
+// try_borrow_mut: RefCell already borrowed
+// subscribe during send (holding the borrow): panicked: RefCell already borrowed
+// subscribe during send (copied list): ok
+// logged: ["second"]
@@ -0,0 +1,120 @@
+// Interior Mutability 3: Lazy Initialization
+// Demonstrates OnceCell, OnceLock, LazyLock, and thread_local!
+//
+// Concepts: OnceCell, OnceLock, LazyLock, thread_local!
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Some values are expensive to build, or can't be built at compile time,
+// but once built they never change: a parsed configuration, a compiled
+// regex, a lookup table. A `static` must be initialized with a constant,
+// so these need a cell that starts empty and is filled exactly once, on
+// first use. OnceCell does that for one thread, OnceLock for many, and
+// LazyLock bundles the cell with the function that fills it.
+
+use std::cell::{Cell, OnceCell};
+use std::collections::HashMap;
+use std::sync::{LazyLock, OnceLock};
+use std::thread;
+
+/// Built on first use by whichever thread gets there first; every other
+/// thread waits for it and then sees the same map.
+static UNITS: LazyLock<HashMap<&'static str, f64>> = LazyLock::new(|| {
+    println!("  (building the unit table)");
+    HashMap::from([("km", 1000.0), ("m", 1.0), ("cm", 0.01), ("mi", 1609.344)])
+});
+
+/// Set once at startup, from something only known at runtime.
+static APP_NAME: OnceLock<String> = OnceLock::new();
+
+fn to_meters(value: f64, unit: &str) -> Option<f64> {
+    UNITS.get(unit).map(|factor| value * factor)
+}
+
+/// A struct that computes one of its fields only if someone asks.
+struct Document {
+    text: String,
+    word_count: OnceCell<usize>,
+}
+
+impl Document {
+    fn word_count(&self) -> usize {
+        *self.word_count.get_or_init(|| {
+            println!("  (counting words)");
+            self.text.split_whitespace().count()
+        })
+    }
+}
+
+thread_local! {
+    // Each thread has its own copy, so a plain Cell is enough
+    static CALLS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn tracked() -> u32 {
+    CALLS.with(|calls| {
+        calls.set(calls.get() + 1);
+        calls.get()
+    })
+}
+
+fn main() {
+    println!("before the first use");
+    println!("5 km = {:?} m", to_meters(5.0, "km"));
+    println!("3 mi = {:?} m", to_meters(3.0, "mi"));
+
+    // The first set() wins; later ones get their value back as an error
+    APP_NAME.set("converter".to_string()).unwrap();
+    println!("second set: {:?}", APP_NAME.set("other".to_string()));
+    println!("app name: {:?}", APP_NAME.get());
+
+    let doc = Document {
+        text: "the quick brown fox".to_string(),
+        word_count: OnceCell::new(),
+    };
+    println!("words: {}", doc.word_count());
+    println!("words again: {}", doc.word_count());
+
+    // Many threads race to use UNITS; it was built once, above, and every
+    // thread agrees on its contents
+    let results: Vec<Option<f64>> = thread::scope(|s| {
+        let handles: Vec<_> = ["m", "cm", "km", "ly"]
+            .into_iter()
+            .map(|unit| s.spawn(move || to_meters(2.0, unit)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    println!("from threads: {:?}", results);
+
+    // thread_local: this thread's counter is separate from the others
+    tracked();
+    tracked();
+    let other = thread::spawn(tracked).join().unwrap();
+    println!("main thread: {}, new thread: {}", tracked(), other);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    static CACHE: OnceCell<String> = OnceCell::new();
+    // error[E0277]: `OnceCell<String>` cannot be shared between threads safely
+}
+
+/*
+ * Key Concepts:
+ * - OnceCell: filled at most once, on one thread; get_or_init computes on demand
+ * - OnceLock: the same for statics and threads; set() once, get() anywhere
+ * - LazyLock: a static with its initializer, run on first use
+ * - thread_local!: a separate value per thread, so Cell/RefCell are fine inside
+ */
+
+// EXPECTED:
+// before the first use
+//   (building the unit table)
+// 5 km = Some(5000.0) m
+// 3 mi = Some(4828.032) m
+// second set: Err("other")
+// app name: Some("converter")
+//   (counting words)
+// words: 4
+// words again: 4
+// from threads: [Some(2.0), Some(0.02), Some(2000.0), None]
+// main thread: 3, new thread: 1
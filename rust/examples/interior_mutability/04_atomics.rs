@@ -0,0 +1,158 @@
+// Interior Mutability 4: Atomics
+// Demonstrates AtomicUsize, fetch_add, compare_exchange, fetch_max, and Ordering
+//
+// Concepts: AtomicUsize, fetch_add, compare_exchange, fetch_max, Ordering
+// Difficulty: advanced
+// Minutes: 15
+//
+// Atomics are the thread-safe Cell for integers and bools: a shared &self
+// can change them, and the hardware guarantees that each operation
+// happens as one indivisible step. The catch is the word *each*. Reading a
+// counter and then storing the value plus one is two steps, and another
+// thread can slip in between them, so its update is lost. The fix is an
+// operation that reads and writes in one step: fetch_add, fetch_max, or,
+// for anything else, a compare_exchange loop.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+const THREADS: usize = 8;
+const PER_THREAD: usize = 10_000;
+
+/// Runs `work` on THREADS threads at once, PER_THREAD times each.
+fn hammer(work: impl Fn(usize) + Sync) {
+    thread::scope(|s| {
+        for t in 0..THREADS {
+            let work = &work;
+            s.spawn(move || {
+                for i in 0..PER_THREAD {
+                    work(t * PER_THREAD + i);
+                }
+            });
+        }
+    });
+}
+
+/// Hands out unique ids, but never more than `limit` of them.
+struct Tickets {
+    next: AtomicUsize,
+    limit: usize,
+}
+
+impl Tickets {
+    fn take(&self) -> Option<usize> {
+        // fetch_add alone could run past the limit. Instead: read, decide,
+        // and write only if nobody changed the value in the meantime;
+        // otherwise try again with the value they left.
+        let mut current = self.next.load(Ordering::Relaxed);
+        loop {
+            if current >= self.limit {
+                return None;
+            }
+            match self.next.compare_exchange_weak(
+                current,
+                current + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(current),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+fn main() {
+    let expected = THREADS * PER_THREAD;
+
+    // One indivisible read-modify-write per increment: nothing is lost
+    let hits = AtomicUsize::new(0);
+    hammer(|_| {
+        hits.fetch_add(1, Ordering::Relaxed);
+    });
+    println!(
+        "fetch_add: {} of {}",
+        hits.load(Ordering::Relaxed),
+        expected
+    );
+
+    // The same counter with a separate load and store. How many updates
+    // vanish changes from run to run, so we only report whether any did.
+    let racy = AtomicUsize::new(0);
+    hammer(|_| {
+        let seen = racy.load(Ordering::Relaxed);
+        racy.store(seen + 1, Ordering::Relaxed);
+    });
+    let counted = racy.load(Ordering::Relaxed);
+    println!(
+        "load + store: never more than expected: {}",
+        counted <= expected
+    );
+
+    // fetch_max keeps a running maximum, again in one step
+    let peak = AtomicU64::new(0);
+    hammer(|i| {
+        peak.fetch_max((i % 977) as u64, Ordering::Relaxed);
+    });
+    println!("peak: {}", peak.load(Ordering::Relaxed));
+
+    // compare_exchange for rules no single fetch_ operation expresses
+    let tickets = Tickets {
+        next: AtomicUsize::new(0),
+        limit: 1_000,
+    };
+    let taken = AtomicUsize::new(0);
+    hammer(|_| {
+        if tickets.take().is_some() {
+            taken.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    println!("tickets handed out: {}", taken.load(Ordering::Relaxed));
+
+    // A flag to stop a worker. Release on the store and Acquire on the
+    // load also make everything written before the store visible after it.
+    let stop = AtomicBool::new(false);
+    let result = AtomicU64::new(0);
+    thread::scope(|s| {
+        s.spawn(|| {
+            while !stop.load(Ordering::Acquire) {
+                thread::yield_now();
+            }
+            println!(
+                "worker saw the flag, result is {}",
+                result.load(Ordering::Relaxed)
+            );
+        });
+        thread::sleep(std::time::Duration::from_millis(5));
+        result.store(42, Ordering::Relaxed);
+        stop.store(true, Ordering::Release);
+    });
+
+    // A Mutex does the same job for data atomics can't hold, at more cost
+    let total = Mutex::new(0usize);
+    hammer(|_| *total.lock().unwrap() += 1);
+    println!("mutex: {} of {}", total.into_inner().unwrap(), expected);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let next: usize = hits + 1;
+    // error[E0369]: cannot add `{integer}` to `AtomicUsize`
+}
+
+/*
+ * Key Concepts:
+ * - Atomics change shared integers and bools safely through &self
+ * - Each call is indivisible; a load followed by a store is not
+ * - fetch_add, fetch_max, swap... are single read-modify-write steps
+ * - compare_exchange in a loop builds any other update
+ * - Relaxed for lone counters; Release/Acquire when other data is published too
+ */
+
+// EXPECTED:
+// fetch_add: 80000 of 80000
+// load + store: never more than expected: true
+// peak: 976
+// tickets handed out: 1000
+// worker saw the flag, result is 42
+// mutex: 80000 of 80000
@@ -0,0 +1,87 @@
+// Lifetimes 1: Lifetime Elision
+// Demonstrates the rules that let you leave lifetimes out most of the time
+//
+// Concepts: lifetimes, elision rules
+// Difficulty: intermediate
+// Minutes: 5
+//
+// Every reference has a lifetime: the stretch of code where it is valid.
+// Usually the compiler works the lifetimes out for you ("elision").
+//
+// To see the real compiler error for the broken variant, run:
+//   rustc --cfg 'feature="broken"' 01_elision.rs
+// or, from the rust/ directory:
+//   cargo run -p tutor -- run lifetimes/01_elision --broken
+
+fn main() {
+    let sentence = String::from("lifetimes are just scopes");
+
+    // Rule 1 + 2: one reference in, so the output borrows from it
+    let word = first_word(&sentence);
+    println!("first word: {}", word);
+
+    // Rule 3: methods that take &self return references tied to self
+    let book = Book {
+        title: String::from("The Rust Book"),
+    };
+    println!("title: {}", book.title());
+
+    // Functions that don't return references need no lifetimes at all
+    println!("same length? {}", same_length("abc", "xyz"));
+
+    #[cfg(feature = "broken")]
+    println!("longest: {}", longest("short", "much longer"));
+}
+
+// What we write:
+fn first_word(s: &str) -> &str {
+    s.split(' ').next().unwrap_or("")
+}
+// What the compiler sees after elision:
+//   fn first_word<'a>(s: &'a str) -> &'a str
+
+struct Book {
+    title: String,
+}
+
+impl Book {
+    // Elided to: fn title<'a>(&'a self) -> &'a str
+    fn title(&self) -> &str {
+        &self.title
+    }
+}
+
+// Two reference parameters, but no reference returned: nothing to infer
+fn same_length(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+}
+
+// The broken variant: two reference inputs and a reference output.
+// Elision can't tell if the result borrows from `x` or from `y`:
+//
+// error[E0106]: missing lifetime specifier
+//   = help: this function's return type contains a borrowed value, but the
+//           signature does not say whether it is borrowed from `x` or `y`
+//
+// Example 2 (02_explicit_annotations.rs) shows how to fix it.
+#[cfg(feature = "broken")]
+fn longest(x: &str, y: &str) -> &str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+/*
+ * The Three Elision Rules:
+ * 1. Each reference parameter gets its own lifetime
+ * 2. If there is exactly ONE input lifetime, outputs get that lifetime
+ * 3. If one input is &self or &mut self, outputs get self's lifetime
+ * If the rules can't decide, you must write the lifetimes yourself (E0106)
+ */
+
+// EXPECTED:
+// first word: lifetimes
+// title: The Rust Book
+// same length? true
@@ -0,0 +1,81 @@
+// Lifetimes 2: Explicit Lifetime Annotations
+// Demonstrates writing 'a yourself when the compiler can't infer it
+//
+// Concepts: lifetime annotations, 'a, returning references
+// Difficulty: intermediate
+// Minutes: 5
+//
+// To see the real compiler error for the broken variant, run:
+//   rustc --cfg 'feature="broken"' 02_explicit_annotations.rs
+// or, from the rust/ directory:
+//   cargo run -p tutor -- run lifetimes/02_explicit_annotations --broken
+
+fn main() {
+    // Both inputs live long enough, so the result is fine to use
+    let a = String::from("long string is long");
+    let b = String::from("xyz");
+    println!("longest: {}", longest(&a, &b));
+
+    // The result is valid only as long as the SHORTER-lived input
+    let outer = String::from("outer value");
+    {
+        let inner = String::from("inner");
+        let result = longest(&outer, &inner);
+        println!("longest inside the block: {}", result); // OK: inner still alive
+    }
+
+    // Annotations only describe relationships; here the output only
+    // depends on `x`, so `y` gets an unrelated lifetime
+    let prefix = String::from("prefix");
+    let shown;
+    {
+        let temporary = String::from("temporary");
+        shown = first_of(&prefix, &temporary);
+    } // temporary is dropped, but `shown` borrows from `prefix` only
+    println!("first_of: {}", shown);
+
+    #[cfg(feature = "broken")]
+    {
+        let result;
+        {
+            let short_lived = String::from("short");
+            result = longest(&outer, &short_lived);
+        } // short_lived is dropped here...
+        println!("result: {}", result); // ...but result might point into it!
+        // error[E0597]: `short_lived` does not live long enough
+        //   - "borrowed value does not live long enough"
+        //   - "`short_lived` dropped here while still borrowed"
+        //   - "borrow later used here" points at this println!
+    }
+}
+
+// "The returned reference lives at least as long as BOTH x and y"
+// In practice 'a becomes the overlap of the two lifetimes.
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+// Different lifetimes for unrelated inputs. ('b could be left out: each
+// elided input lifetime gets one of its own)
+#[allow(clippy::needless_lifetimes)]
+fn first_of<'a, 'b>(x: &'a str, _y: &'b str) -> &'a str {
+    x
+}
+
+/*
+ * Key Concepts:
+ * - Lifetime parameters are written 'a, 'b, ... and declared like generics
+ * - They don't change how long anything lives; they describe relationships
+ * - &'a str in both inputs and output means "the output is valid while
+ *   both inputs are"
+ * - Using a reference after its source is dropped is error E0597
+ */
+
+// EXPECTED:
+// longest: long string is long
+// longest inside the block: outer value
+// first_of: prefix
@@ -0,0 +1,77 @@
+// Lifetimes 3: Structs That Hold References
+// Demonstrates why a struct containing a reference needs a lifetime
+//
+// Concepts: structs holding references, lifetime parameters
+// Difficulty: intermediate
+// Minutes: 5
+//
+// To see the real compiler errors for the broken variant, run:
+//   rustc --cfg 'feature="broken"' 03_struct_references.rs
+// or, from the rust/ directory:
+//   cargo run -p tutor -- run lifetimes/03_struct_references --broken
+
+// "An Excerpt can't outlive the text its `part` points into"
+struct Excerpt<'a> {
+    part: &'a str,
+}
+
+impl<'a> Excerpt<'a> {
+    // Elision rule 3: the returned &str borrows from self
+    fn announce(&self, announcement: &str) -> &str {
+        println!("Attention please: {}", announcement);
+        self.part
+    }
+
+    // Returning the inner reference with its ORIGINAL lifetime 'a lets
+    // callers keep it even after the Excerpt itself is gone
+    fn into_part(self) -> &'a str {
+        self.part
+    }
+}
+
+fn main() {
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    let first_sentence = novel.split('.').next().unwrap_or("");
+    let excerpt = Excerpt {
+        part: first_sentence,
+    };
+    println!("excerpt: {}", excerpt.announce("a quote!"));
+
+    let part = excerpt.into_part(); // excerpt is gone, the &str lives on
+    println!("still valid: {}", part);
+
+    #[cfg(feature = "broken")]
+    {
+        let dangling;
+        {
+            let book = String::from("A short-lived book. The end.");
+            dangling = Excerpt { part: &book };
+        } // book is dropped here while the Excerpt still points into it
+        println!("{}", dangling.part);
+        // error[E0597]: `book` does not live long enough
+    }
+}
+
+// The broken variant also shows the struct WITHOUT a lifetime:
+//
+// error[E0106]: missing lifetime specifier
+//   help: consider introducing a named lifetime parameter: `NoLifetime<'a>`
+#[cfg(feature = "broken")]
+struct NoLifetime {
+    part: &str,
+}
+
+/*
+ * Key Concepts:
+ * - A struct that stores a reference must declare a lifetime parameter
+ * - Excerpt<'a> means "this value is only valid while 'a is"
+ * - The compiler stops you from keeping a struct around longer than the
+ *   data it borrows (E0597)
+ * - Owning the data (String instead of &str) avoids lifetimes entirely,
+ *   at the cost of copying
+ */
+
+// EXPECTED:
+// Attention please: a quote!
+// excerpt: Call me Ishmael
+// still valid: Call me Ishmael
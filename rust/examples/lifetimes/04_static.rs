@@ -0,0 +1,78 @@
+// Lifetimes 4: The 'static Lifetime
+// Demonstrates references that live for the entire program
+//
+// Concepts: 'static, string literals, T: 'static
+// Difficulty: intermediate
+// Minutes: 5
+//
+// To see the real compiler error for the broken variant, run:
+//   rustc --cfg 'feature="broken"' 04_static.rs
+// or, from the rust/ directory:
+//   cargo run -p tutor -- run lifetimes/04_static --broken
+
+use std::thread;
+
+// Statics live in the program's binary, so references to them are 'static
+static GREETING: &str = "Hello from a static!";
+
+fn main() {
+    // String literals are baked into the binary: their type is &'static str
+    let literal: &'static str = "I live forever";
+    println!("{}", literal);
+    println!("{}", GREETING);
+
+    // Returning a literal is fine: it never goes away
+    println!("status: {}", status_message(404));
+
+    // T: 'static means "T holds no short-lived references" - it does NOT
+    // mean the value lives forever. Owned values like String qualify.
+    let owned = String::from("moved into the thread");
+    let handle = thread::spawn(move || {
+        println!("thread says: {}", owned);
+    });
+    handle.join().unwrap();
+
+    // Box::leak turns an owned value into a &'static on purpose
+    // (the memory is never freed - use sparingly!)
+    let leaked: &'static str = Box::leak(String::from("leaked").into_boxed_str());
+    println!("{}", leaked);
+
+    #[cfg(feature = "broken")]
+    println!("{}", dangling());
+}
+
+fn status_message(code: u16) -> &'static str {
+    match code {
+        200 => "OK",
+        404 => "Not Found",
+        _ => "Unknown",
+    }
+}
+
+// The broken variant: writing 'static doesn't make a local live longer.
+//
+// error[E0515]: cannot return reference to local variable `text`
+//   - "returns a reference to data owned by the current function"
+//
+// The fix is to return the owned String instead: -> String
+#[cfg(feature = "broken")]
+fn dangling() -> &'static str {
+    let text = String::from("I'm dropped at the end of this function");
+    &text
+}
+
+/*
+ * Key Concepts:
+ * - 'static references are valid for the whole run of the program
+ * - String literals and `static` items are &'static
+ * - A `T: 'static` bound (as in thread::spawn) means T borrows nothing
+ *   short-lived; owned types like String satisfy it
+ * - Annotating a reference as 'static can't extend a local's life (E0515)
+ */
+
+// EXPECTED:
+// I live forever
+// Hello from a static!
+// status: Not Found
+// thread says: moved into the thread
+// leaked
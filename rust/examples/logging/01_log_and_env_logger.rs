@@ -0,0 +1,108 @@
+// Logging 1: The log Facade and env_logger
+// Demonstrates the level macros, targets, filtering from an environment variable
+//
+// Concepts: log, env_logger, levels, targets, filtering
+// Difficulty: beginner
+// Minutes: 10
+//
+// `log` is a facade: libraries call its macros (error!, warn!, info!,
+// debug!, trace!) without knowing where the messages go. The program picks
+// one logger at startup, here env_logger, which prints to the terminal and
+// decides what to show from an environment variable. Until a logger is
+// installed, every log call does nothing at all.
+
+use std::io::Write;
+
+use env_logger::{Builder, Env, Target};
+use log::{debug, error, info, log_enabled, trace, warn, Level};
+
+/// Pretend storage code. A library would log like this: with a target of
+/// its own, so the program can turn its messages up or down separately.
+fn load_user(id: u32) -> Option<&'static str> {
+    debug!(target: "app::db", "SELECT name FROM users WHERE id = {}", id);
+    let name = match id {
+        1 => Some("ana"),
+        2 => Some("ben"),
+        _ => None,
+    };
+    trace!(target: "app::db", "row for {}: {:?}", id, name);
+    name
+}
+
+/// Something expensive enough that we only want to do it if it's shown.
+fn cache_report() -> String {
+    (1..=3)
+        .map(|n| format!("shard{}=ok", n))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn main() {
+    // env_logger normally reads RUST_LOG. This example reads LOG_DEMO, so
+    // a RUST_LOG in your shell doesn't change its output; try
+    // `LOG_DEMO=trace` or `LOG_DEMO=warn` when you run it. The default
+    // shows info and up, plus debug for the "app::db" target.
+    let env = Env::new().filter_or("LOG_DEMO", "info,app::db=debug");
+    Builder::from_env(env)
+        // The default format has a timestamp and goes to stderr. A fixed
+        // format on stdout keeps the output below the same on every run.
+        .format(|buf, record| {
+            writeln!(
+                buf,
+                "{:<5} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            )
+        })
+        .target(Target::Stdout)
+        .init();
+
+    // Every macro takes format! arguments. The target defaults to the
+    // module path; here we name it, to keep the output short.
+    info!(target: "app", "starting up");
+    for id in [1, 3] {
+        match load_user(id) {
+            Some(name) => info!(target: "app", "user {} is {}", id, name),
+            None => warn!(target: "app", "no user {}", id),
+        }
+    }
+
+    // The arguments of a filtered-out call are never evaluated, but work
+    // done *before* the call is. log_enabled! asks first.
+    if log_enabled!(target: "app", Level::Debug) {
+        debug!(target: "app", "cache: {}", cache_report());
+    } else {
+        println!("(skipped building the cache report)");
+    }
+
+    error!(target: "app", "giving up after {} retries", 3);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    info!(target: "app", "user {} is {}", 1);
+    // error: 2 positional arguments in format string, but there is 1 argument
+
+    // Filtering happens per record, so a message at a disabled level costs
+    // one comparison. log::max_level is the quickest check of all.
+    println!("most verbose level on: {}", log::max_level());
+}
+
+/*
+ * Key Concepts:
+ * - log is only a facade; a logger like env_logger must be installed once
+ * - Levels from error! (most important) to trace! (most verbose)
+ * - `target:` names where a message comes from; it defaults to the module
+ * - Filters like "info,app::db=debug" set a level overall and per target
+ * - log_enabled! guards work that's only needed if the message is shown
+ */
+
+// EXPECTED:
+// INFO  app: starting up
+// DEBUG app::db: SELECT name FROM users WHERE id = 1
+// INFO  app: user 1 is ana
+// DEBUG app::db: SELECT name FROM users WHERE id = 3
+// WARN  app: no user 3
+// (skipped building the cache report)
+// ERROR app: giving up after 3 retries
+// most verbose level on: DEBUG
@@ -0,0 +1,132 @@
+// Logging 2: tracing Events, Fields, and Spans
+// Demonstrates structured fields, spans, #[instrument], and EnvFilter
+//
+// Concepts: tracing, structured fields, spans, #[instrument], EnvFilter
+// Difficulty: intermediate
+// Minutes: 10
+//
+// tracing looks like log (it has info!, debug!, and the rest) but records
+// two things log can't. Events carry typed key-value fields instead of one
+// formatted string, so a tool can search for `user_id = 7` without parsing
+// text. And spans mark a stretch of work, like one request: every event
+// inside a span is shown with the span's fields, so we know which request
+// an event belongs to.
+
+use tracing::{debug, info, info_span, instrument, warn, Level};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+
+#[derive(Debug)]
+struct Item {
+    sku: &'static str,
+    cents: u64,
+}
+
+/// `#[instrument]` wraps the whole function in a span named after it, with
+/// its arguments as fields. `skip` leaves out ones too big to log, and
+/// `fields` adds our own.
+#[instrument(skip(items), fields(count = items.len()))]
+fn total(customer: &str, items: &[Item]) -> u64 {
+    let sum = items.iter().map(|item| item.cents).sum();
+    // `?` records a field with its Debug format, `%` with its Display
+    let skus: Vec<_> = items.iter().map(|item| item.sku).collect();
+    debug!(?skus, sum, "added up");
+    sum
+}
+
+#[instrument(ret)]
+fn discount(cents: u64, code: Option<&str>) -> u64 {
+    match code {
+        Some("HALF") => cents / 2,
+        Some(other) => {
+            warn!(code = other, "unknown discount code");
+            cents
+        }
+        None => cents,
+    }
+}
+
+fn checkout(request_id: u32, customer: &str, code: Option<&str>) {
+    // A span by hand: entered() makes it current until the guard drops
+    let _request = info_span!("request", id = request_id).entered();
+    info!(%customer, "checkout started");
+    let items = [
+        Item {
+            sku: "MUG-01",
+            cents: 950,
+        },
+        Item {
+            sku: "TEE-04",
+            cents: 2000,
+        },
+    ];
+    let cents = discount(total(customer, &items), code);
+    info!(cents, "checkout done");
+}
+
+fn main() {
+    // EnvFilter understands the same directives as env_logger. It reads
+    // TRACING_DEMO here (usually RUST_LOG) and falls back to DEBUG, so try
+    // `TRACING_DEMO=info` or `TRACING_DEMO=trace`.
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::DEBUG.into())
+        .with_env_var("TRACING_DEMO")
+        .from_env_lossy();
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        // Without the clock and colours, and on stdout, the output is the
+        // same every run
+        .without_time()
+        .with_ansi(false)
+        .with_target(false)
+        .with_writer(std::io::stdout)
+        .init();
+
+    info!(version = "1.2.0", "shop starting");
+    checkout(1, "ana", Some("HALF"));
+    checkout(2, "ben", Some("FREE"));
+
+    // Spans nest, and events outside every span have no prefix
+    let outer = info_span!("batch", size = 2);
+    outer.in_scope(|| {
+        let _inner = info_span!("job", n = 1).entered();
+        info!("inside two spans");
+    });
+    info!("outside again");
+
+    // Events that are filtered out cost almost nothing: the level check
+    // happens before any field is recorded
+    tracing::event!(Level::TRACE, secret = "never shown", "too verbose");
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    info!(items = [Item { sku: "X", cents: 1 }], "no Value impl");
+    // error[E0277]: the trait bound `[Item; 1]: Value` is not satisfied (use `?` or `%`)
+
+    // init() also installed a bridge for the log crate, so libraries that
+    // still use log show up here too
+    log::info!("a message from a library that uses log");
+}
+
+/*
+ * Key Concepts:
+ * - Events have fields: info!(user = "ana", count = 3, "message")
+ * - `?field` records Debug, `%field` records Display
+ * - Spans give context; entered()/in_scope make one current
+ * - #[instrument] makes a span per call, with the arguments as fields
+ * - EnvFilter filters by level and target, from an env var or a string
+ */
+
+// EXPECTED:
+//  INFO shop starting version="1.2.0"
+//  INFO request{id=1}: checkout started customer=ana
+// DEBUG request{id=1}:total{customer="ana" count=2}: added up skus=["MUG-01", "TEE-04"] sum=2950
+//  INFO request{id=1}:discount{cents=2950 code=Some("HALF")}: return=1475
+//  INFO request{id=1}: checkout done cents=1475
+//  INFO request{id=2}: checkout started customer=ben
+// DEBUG request{id=2}:total{customer="ben" count=2}: added up skus=["MUG-01", "TEE-04"] sum=2950
+//  WARN request{id=2}:discount{cents=2950 code=Some("FREE")}: unknown discount code code="FREE"
+//  INFO request{id=2}:discount{cents=2950 code=Some("FREE")}: return=2950
+//  INFO request{id=2}: checkout done cents=2950
+//  INFO batch{size=2}:job{n=1}: inside two spans
+//  INFO outside again
+//  INFO a message from a library that uses log
@@ -0,0 +1,94 @@
+// Logging 3: Instrumenting Async Code
+// Demonstrates #[instrument] on async fns, .instrument() on futures and tasks
+//
+// Concepts: #[instrument] on async fns, Instrument, tasks
+// Difficulty: intermediate
+// Minutes: 10
+//
+// With threads, "the current span" can live in a thread-local. Async code
+// breaks that: one thread runs many tasks, switching between them at every
+// .await, so a span entered with `.entered()` would leak into whatever task
+// runs next. The fix is to attach the span to the future itself:
+// `#[instrument]` does that for an async fn, and `.instrument(span)` for
+// any future, including one handed to tokio::spawn.
+
+use std::time::Duration;
+
+use tracing::{info, info_span, instrument, Instrument};
+
+/// Pretend network call. The span is entered each time the future is
+/// polled and left at every .await, so it never leaks.
+#[instrument]
+async fn fetch(url: &'static str, delay_ms: u64) -> usize {
+    info!("sending");
+    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+    let bytes = url.len() * 100;
+    info!(bytes, "received");
+    bytes
+}
+
+#[instrument(skip_all, fields(pages = urls.len()))]
+async fn crawl(urls: &[(&'static str, u64)]) -> usize {
+    // Both fetches run at once, interleaving at their .awaits. Each one's
+    // events still carry its own span.
+    let (a, b) = tokio::join!(fetch(urls[0].0, urls[0].1), fetch(urls[1].0, urls[1].1));
+    info!(total = a + b, "crawl finished");
+    a + b
+}
+
+// Paused time makes sleeps finish instantly, in order of their deadlines,
+// so the interleaving below is the same on every run
+#[tokio::main(flavor = "current_thread", start_paused = true)]
+async fn main() {
+    tracing_subscriber::fmt()
+        .without_time()
+        .with_ansi(false)
+        .with_target(false)
+        .with_writer(std::io::stdout)
+        .init();
+
+    crawl(&[("https://a.example/slow", 30), ("https://b.example", 10)]).await;
+
+    // A spawned task doesn't inherit the caller's span: it runs later,
+    // maybe on another thread. Attach one with .instrument().
+    let job = tokio::spawn(
+        async {
+            info!("working in the background");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            info!("background work done");
+        }
+        .instrument(info_span!("job", id = 42)),
+    );
+    job.await.unwrap();
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    tokio::spawn(async {
+        let guard = info_span!("wrong").entered();
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        drop(guard);
+    });
+    // error: future cannot be sent between threads safely (the guard of an
+    // entered span is !Send, and holding it across .await is the bug)
+
+    info!("all done");
+}
+
+/*
+ * Key Concepts:
+ * - Never hold an `.entered()` span guard across an .await
+ * - #[instrument] on an async fn attaches the span to its future
+ * - .instrument(span) does the same for any future, like a spawned task
+ * - Concurrent futures keep their own spans even as they interleave
+ * - Paused tokio time makes timing-dependent output deterministic
+ */
+
+// EXPECTED:
+//  INFO crawl{pages=2}:fetch{url="https://a.example/slow" delay_ms=30}: sending
+//  INFO crawl{pages=2}:fetch{url="https://b.example" delay_ms=10}: sending
+//  INFO crawl{pages=2}:fetch{url="https://b.example" delay_ms=10}: received bytes=1700
+//  INFO crawl{pages=2}:fetch{url="https://a.example/slow" delay_ms=30}: received bytes=2200
+//  INFO crawl{pages=2}: crawl finished total=3900
+//  INFO job{id=42}: working in the background
+//  INFO job{id=42}: background work done
+//  INFO all done
@@ -0,0 +1,275 @@
+// Logging 4: Capturing Events in Tests
+// Demonstrates a custom Layer, field visitors, and scoped subscribers
+//
+// Concepts: tracing Layer, field visitors, scoped subscribers
+// Difficulty: advanced
+// Minutes: 25
+//
+// Logs are output too: an audit trail, a warning an operator relies on.
+// To test them, we install a subscriber that stores events instead of
+// printing them. tracing-subscriber builds subscribers from layers, and a
+// Layer only needs `on_event` to see every event with its fields and the
+// spans around it. `with_default` installs a subscriber just for one
+// closure, on the current thread, so tests running in parallel each see
+// their own events.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::{info, info_span, instrument, warn, Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+
+/// The code under test: it logs a warning for every failed login, and an
+/// error (with the account locked) after three in a row.
+#[derive(Default)]
+struct Logins {
+    failures: BTreeMap<String, u32>,
+}
+
+impl Logins {
+    #[instrument(skip(self, password_ok), fields(attempt))]
+    fn attempt(&mut self, user: &str, password_ok: bool) -> bool {
+        if password_ok {
+            self.failures.remove(user);
+            info!(user, "logged in");
+            return true;
+        }
+        let count = self.failures.entry(user.to_string()).or_default();
+        *count += 1;
+        // Fill in a field the span declared up front
+        tracing::Span::current().record("attempt", *count);
+        if *count >= 3 {
+            tracing::error!(user, failures = *count, "account locked");
+        } else {
+            warn!(user, failures = *count, "wrong password");
+        }
+        false
+    }
+}
+
+/// One event as the capture layer saw it.
+#[derive(Debug, Clone, PartialEq)]
+struct Captured {
+    level: Level,
+    message: String,
+    fields: BTreeMap<String, String>,
+    /// The names of the spans around the event, outermost first
+    spans: Vec<String>,
+}
+
+impl fmt::Display for Captured {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {} {:?}",
+            self.level,
+            self.spans.join(">"),
+            self.message,
+            self.fields
+        )
+    }
+}
+
+/// Records every field of an event as text. The message is the field
+/// called "message".
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for FieldVisitor {
+    // Every other record_* method (str, u64, bool...) falls back to this
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let text = format!("{:?}", value);
+        if field.name() == "message" {
+            self.message = text;
+        } else {
+            self.fields.insert(field.name().to_string(), text);
+        }
+    }
+
+    // Without this, a &str would be recorded with its quotes
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .insert(field.name().to_string(), value.to_string());
+    }
+}
+
+/// A layer that keeps every event in a shared list.
+#[derive(Clone, Default)]
+struct CaptureLayer {
+    events: Arc<Mutex<Vec<Captured>>>,
+}
+
+impl CaptureLayer {
+    fn events(&self) -> Vec<Captured> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl<S> Layer<S> for CaptureLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let spans = ctx
+            .event_scope(event)
+            .map(|scope| {
+                scope
+                    .from_root()
+                    .map(|span| span.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.events.lock().unwrap().push(Captured {
+            level: *event.metadata().level(),
+            message: visitor.message,
+            fields: visitor.fields,
+            spans,
+        });
+    }
+}
+
+/// Runs `f` with a fresh capture layer installed and returns its events.
+fn capture(f: impl FnOnce()) -> Vec<Captured> {
+    let layer = CaptureLayer::default();
+    let subscriber = tracing_subscriber::registry().with(layer.clone());
+    tracing::subscriber::with_default(subscriber, f);
+    layer.events()
+}
+
+fn main() {
+    let events = capture(|| {
+        let mut logins = Logins::default();
+        let _session = info_span!("session").entered();
+        for ok in [false, false, true, false, false, false] {
+            logins.attempt("ana", ok);
+        }
+    });
+    for event in &events {
+        println!("{}", event);
+    }
+
+    // Nothing was installed outside capture(), so this goes nowhere
+    info!("nobody is listening");
+    println!("captured {} events", events.len());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    tracing::subscriber::with_default(CaptureLayer::default(), || {});
+    // error[E0277]: the trait bound `CaptureLayer: Subscriber` is not satisfied (a layer needs a registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(events: &[Captured]) -> Vec<Level> {
+        events.iter().map(|event| event.level).collect()
+    }
+
+    #[test]
+    fn a_good_password_logs_one_info() {
+        let events = capture(|| {
+            Logins::default().attempt("ana", true);
+        });
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, Level::INFO);
+        assert_eq!(events[0].message, "logged in");
+        assert_eq!(events[0].fields["user"], "ana");
+    }
+
+    #[test]
+    fn each_failure_warns_with_a_count() {
+        let events = capture(|| {
+            let mut logins = Logins::default();
+            logins.attempt("ben", false);
+            logins.attempt("ben", false);
+        });
+        assert_eq!(levels(&events), [Level::WARN, Level::WARN]);
+        assert_eq!(events[1].fields["failures"], "2");
+        assert_eq!(events[1].spans, ["attempt"]);
+    }
+
+    #[test]
+    fn the_third_failure_locks_the_account() {
+        let events = capture(|| {
+            let mut logins = Logins::default();
+            for _ in 0..3 {
+                logins.attempt("cy", false);
+            }
+        });
+        let locked = events.last().unwrap();
+        assert_eq!(locked.level, Level::ERROR);
+        assert_eq!(locked.message, "account locked");
+        assert_eq!(locked.fields["failures"], "3");
+    }
+
+    #[test]
+    fn a_success_resets_the_count() {
+        let events = capture(|| {
+            let mut logins = Logins::default();
+            for ok in [false, false, true, false] {
+                logins.attempt("dee", ok);
+            }
+        });
+        assert_eq!(
+            levels(&events),
+            [Level::WARN, Level::WARN, Level::INFO, Level::WARN]
+        );
+        assert_eq!(events[3].fields["failures"], "1");
+    }
+
+    #[test]
+    fn events_know_their_spans() {
+        let events = capture(|| {
+            let _outer = info_span!("request").entered();
+            Logins::default().attempt("eve", true);
+        });
+        assert_eq!(events[0].spans, ["request", "attempt"]);
+    }
+
+    #[test]
+    fn users_are_counted_separately() {
+        let events = capture(|| {
+            let mut logins = Logins::default();
+            logins.attempt("fay", false);
+            logins.attempt("gus", false);
+        });
+        let counts: Vec<_> = events
+            .iter()
+            .map(|event| {
+                (
+                    event.fields["user"].as_str(),
+                    event.fields["failures"].as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(counts, [("fay", "1"), ("gus", "1")]);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A Layer's on_event sees each event, its metadata, and its spans
+ * - A Visit implementation reads an event's fields; "message" is one of them
+ * - registry().with(layer) turns layers into a Subscriber
+ * - with_default installs a subscriber for one closure on one thread
+ * - Assert on levels and fields, not on formatted text
+ */
+
+// EXPECTED:
+// WARN [session>attempt] wrong password {"failures": "1", "user": "ana"}
+// WARN [session>attempt] wrong password {"failures": "2", "user": "ana"}
+// INFO [session>attempt] logged in {"user": "ana"}
+// WARN [session>attempt] wrong password {"failures": "1", "user": "ana"}
+// WARN [session>attempt] wrong password {"failures": "2", "user": "ana"}
+// ERROR [session>attempt] account locked {"failures": "3", "user": "ana"}
+// captured 6 events
@@ -0,0 +1,101 @@
+// Macros 2: Fragment Specifiers
+// Demonstrates expr, ident, ty, literal, block, pat, and tt
+//
+// Concepts: expr, ident, ty, literal, block, pat, tt
+// Difficulty: intermediate
+// Minutes: 10
+
+// $name:ident captures an identifier, so a macro can DEFINE things
+macro_rules! make_getter {
+    ($field:ident: $ty:ty) => {
+        fn $field(&self) -> $ty {
+            self.$field.clone()
+        }
+    };
+}
+
+#[derive(Debug)]
+struct Book {
+    title: String,
+    pages: u32,
+}
+
+impl Book {
+    make_getter!(title: String);
+    make_getter!(pages: u32);
+}
+
+// $name:ty captures a type
+macro_rules! zero_of {
+    ($t:ty) => {
+        <$t>::default()
+    };
+}
+
+// $name:literal accepts only literals like 1, "text", or true
+macro_rules! repeat_str {
+    ($s:literal, $n:literal) => {
+        $s.repeat($n)
+    };
+}
+
+// $name:block captures a { ... } block; here it is timed
+macro_rules! timed {
+    ($label:literal, $body:block) => {{
+        let start = std::time::Instant::now();
+        let result = $body;
+        println!("{} took {:?}", $label, start.elapsed());
+        result
+    }};
+}
+
+// $name:pat captures a pattern, usable anywhere patterns are
+macro_rules! is_match {
+    ($value:expr, $pattern:pat) => {
+        match $value {
+            $pattern => true,
+            _ => false,
+        }
+    };
+}
+
+// $name:tt is a single "token tree": one token, or a whole (), [], or {}
+// group. It is the most flexible fragment, used to pass tokens through.
+macro_rules! first_token {
+    ($first:tt $($rest:tt)*) => {
+        stringify!($first)
+    };
+}
+
+fn main() {
+    let book = Book {
+        title: String::from("The Rust Programming Language"),
+        pages: 560,
+    };
+    println!("{} has {} pages", book.title(), book.pages());
+
+    let (a, b, c): (i32, String, bool) = (zero_of!(i32), zero_of!(String), zero_of!(bool));
+    println!("defaults: {:?}, {:?}, {:?}", a, b, c);
+
+    println!("{}", repeat_str!("ab", 3));
+    // This would cause an error:
+    // let n = 3; println!("{}", repeat_str!("ab", n));
+    // error: no rules expected `n`
+
+    let sum = timed!("summing", { (1..=1000u64).sum::<u64>() });
+    println!("sum = {}", sum);
+
+    println!("is Some(3) Some(_)? {}", is_match!(Some(3), Some(_)));
+    println!("is 7 in 1..=5? {}", is_match!(7, 1..=5));
+
+    println!("first token of `(a b) c d`: {}", first_token!((a b) c d));
+}
+
+/*
+ * Key Concepts:
+ * - expr: an expression     ident: a name      ty: a type
+ * - literal: 1, "s", true   block: { ... }     pat: a pattern
+ * - tt: any single token tree, the catch-all
+ * - ident lets macros generate items like functions and fields
+ * - The fragment decides what the caller may pass
+ */
@@ -0,0 +1,89 @@
+// Macros 3: Repetition
+// Demonstrates $(...),* and $(...);+ repeats, trailing commas, and counting
+//
+// Concepts: repetition, separators, trailing commas, counting
+// Difficulty: intermediate
+// Minutes: 10
+
+// $( ... ),* means "zero or more of this, separated by commas".
+// In the expansion, $( ... )* repeats once for every match.
+macro_rules! my_vec {
+    // my_vec![value; count], like vec![0; 5]
+    ($value:expr; $count:expr) => {
+        std::vec::from_elem($value, $count)
+    };
+    // my_vec![a, b, c], with an optional trailing comma: $(,)?
+    ($($item:expr),* $(,)?) => {{
+        #[allow(unused_mut)] // an empty my_vec![] never pushes
+        let mut v = Vec::new();
+        $(
+            v.push($item);
+        )*
+        v
+    }};
+}
+
+// `+` means "one or more", and any token can be the separator
+macro_rules! sum {
+    ($($n:expr);+) => {
+        0 $(+ $n)+
+    };
+}
+
+// Counting repetitions: expand each item to `1usize` and add them
+macro_rules! count {
+    () => { 0usize };
+    ($head:tt $($tail:tt)*) => { 1usize + count!($($tail)*) };
+}
+
+// Several captures can repeat together, in step
+macro_rules! print_pairs {
+    ($($key:ident = $value:expr),* $(,)?) => {
+        $(
+            println!("{:>6} = {:?}", stringify!($key), $value);
+        )*
+    };
+}
+
+fn main() {
+    let empty: Vec<i32> = my_vec![];
+    let one = my_vec![1];
+    let many = my_vec![1, 2, 3, 4,]; // trailing comma is fine
+    let zeros = my_vec![0; 5];
+    println!("{:?} {:?} {:?} {:?}", empty, one, many, zeros);
+
+    println!("sum!(1; 2; 3) = {}", sum!(1; 2; 3));
+    println!("count!(a b c d) = {}", count!(a b c d));
+
+    let width = 80;
+    print_pairs! {
+        width = width,
+        height = 24,
+        title = "terminal",
+    };
+
+    // The same separator rules apply in the real vec! macro
+    let words = vec!["repeat", "after", "me"];
+    println!("{:?}", words);
+    // This would cause an error:
+    // let bad = my_vec![1 2 3];
+    // error: no rules expected `2`
+}
+
+/*
+ * Key Concepts:
+ * - $( ... ),* matches zero or more, comma-separated
+ * - $( ... ),+ requires at least one; any token can separate
+ * - $(,)? accepts an optional trailing comma
+ * - In the expansion, $( ... )* repeats per match
+ * - Captures that repeat together expand together
+ */
+
+// EXPECTED:
+// [] [1] [1, 2, 3, 4] [0, 0, 0, 0, 0]
+// sum!(1; 2; 3) = 6
+// count!(a b c d) = 4
+//  width = 80
+// height = 24
+//  title = "terminal"
+// ["repeat", "after", "me"]
@@ -0,0 +1,145 @@
+// Macros 5: A Small DSL for HashMaps
+// Demonstrates custom syntax built from everything in this chapter
+//
+// Concepts: macro DSLs, repetition, custom syntax
+// Difficulty: intermediate
+// Minutes: 15
+
+use std::collections::HashMap;
+
+// Step 1: hashmap! { key => value, ... }, like vec! but for maps.
+// `=>` is just tokens here; a macro may ask for any punctuation.
+macro_rules! hashmap {
+    ($($key:expr => $value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut map = HashMap::new();
+        $(
+            map.insert($key, $value);
+        )*
+        map
+    }};
+}
+
+// Step 2: a config DSL. Sections contain `name: value` settings; the
+// macro turns it into a HashMap<String, HashMap<String, String>>.
+//
+//     config! {
+//         [server]
+//         host: "localhost",
+//         port: 8080,
+//     }
+macro_rules! config {
+    ($([$section:ident] $($key:ident : $value:expr),* $(,)?)*) => {{
+        #[allow(unused_mut)]
+        let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+        $(
+            let section = sections.entry(stringify!($section).to_string()).or_default();
+            $(
+                section.insert(stringify!($key).to_string(), $value.to_string());
+            )*
+        )*
+        sections
+    }};
+}
+
+// Step 3: nested maps, building on hashmap!. A value that is itself a
+// `{ ... }` group is turned into an inner map, recursively.
+macro_rules! tree {
+    ({ $($key:literal : $value:tt),* $(,)? }) => {
+        Node::Branch(hashmap! { $($key.to_string() => tree!($value)),* })
+    };
+    ($leaf:expr) => {
+        Node::Leaf($leaf.to_string())
+    };
+}
+
+#[derive(Debug)]
+enum Node {
+    Leaf(String),
+    Branch(HashMap<String, Node>),
+}
+
+impl Node {
+    // Follow a path like "database.primary.port"
+    fn get(&self, path: &str) -> Option<&str> {
+        let mut node = self;
+        for part in path.split('.') {
+            match node {
+                Node::Branch(children) => node = children.get(part)?,
+                Node::Leaf(_) => return None,
+            }
+        }
+        match node {
+            Node::Leaf(value) => Some(value),
+            Node::Branch(_) => None,
+        }
+    }
+}
+
+fn sorted<V: std::fmt::Debug>(map: &HashMap<String, V>) -> Vec<String> {
+    let mut entries: Vec<String> = map.iter().map(|(k, v)| format!("{}={:?}", k, v)).collect();
+    entries.sort();
+    entries
+}
+
+fn main() {
+    let ages = hashmap! {
+        "ferris" => 8,
+        "corro" => 5,
+    };
+    let mut names: Vec<_> = ages.iter().collect();
+    names.sort();
+    println!("ages: {:?}", names);
+    let empty: HashMap<&str, i32> = hashmap! {};
+    println!("empty map has {} entries", empty.len());
+
+    let settings = config! {
+        [server]
+        host: "localhost",
+        port: 8080,
+
+        [logging]
+        level: "debug",
+        color: true,
+    };
+    let mut section_names: Vec<_> = settings.keys().collect();
+    section_names.sort();
+    for name in section_names {
+        println!("[{}] {:?}", name, sorted(&settings[name]));
+    }
+
+    let tree = tree!({
+        "name": "demo",
+        "database": {
+            "primary": { "host": "db1", "port": 5432 },
+            "replica": { "host": "db2", "port": 5433 },
+        },
+    });
+    for path in [
+        "name",
+        "database.primary.port",
+        "database.replica.host",
+        "database.missing",
+    ] {
+        println!("{:<22} -> {:?}", path, tree.get(path));
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A macro can define its own syntax from ordinary tokens (=>, :, [ ])
+ * - Nested repetitions $( ... $( ... )* )* handle sections of entries
+ * - Macros can call other macros, and themselves, to build nested data
+ * - $value:tt lets a value be either a single token or a { ... } group
+ * - DSLs are compiled away: the result is ordinary code
+ */
+
+// EXPECTED:
+// ages: [("corro", 5), ("ferris", 8)]
+// empty map has 0 entries
+// [logging] ["color=\"true\"", "level=\"debug\""]
+// [server] ["host=\"localhost\"", "port=\"8080\""]
+// name                   -> Some("demo")
+// database.primary.port  -> Some("5432")
+// database.replica.host  -> Some("db2")
+// database.missing       -> None
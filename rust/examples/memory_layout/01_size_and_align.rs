@@ -0,0 +1,192 @@
+// Memory Layout 1: Size and Alignment
+// Demonstrates size_of, align_of, size_of_val, fat pointers, and zero-sized types
+//
+// Concepts: size_of, align_of, size_of_val, fat pointers, zero-sized types
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Every type has a size, the bytes one value takes, and an alignment: its
+// address must be a multiple of that number. A u32 is 4 bytes and must
+// start at a multiple of 4, because that is what the hardware reads
+// fastest (or, on some machines, at all). std::mem::size_of::<T>() and
+// align_of::<T>() tell us both, at compile time, and a type's size is
+// always a multiple of its alignment, so that the values in an array all
+// line up.
+//
+// Pointers to things whose size isn't known at compile time are "fat":
+// a &[T] carries its length, a &str its length in bytes, a &dyn Trait a
+// pointer to its vtable. Types with nothing in them take no bytes at all,
+// which is why a HashSet can be a HashMap<T, ()> without wasting memory.
+//
+// The numbers printed are for a 64-bit target. The tests state what is
+// true on every target, in terms of usize, and `cargo test` checks them.
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of, size_of_val};
+
+use learn_rust_core::section;
+
+/// Prints a type's size and alignment, with the type as written.
+macro_rules! layout {
+    ($t:ty) => {
+        println!(
+            "{:<16} size {:>2}  align {:>2}",
+            stringify!($t),
+            size_of::<$t>(),
+            align_of::<$t>()
+        )
+    };
+}
+
+/// A marker type: no fields, no bytes.
+struct Marker;
+
+fn main() {
+    section("numbers");
+    layout!(u8);
+    layout!(u16);
+    layout!(u32);
+    layout!(u64);
+    layout!(f64);
+    layout!(bool);
+    layout!(char);
+    layout!(usize);
+
+    section("compounds");
+    layout!([u32; 3]);
+    layout!((u8, u32));
+    layout!(Marker);
+    layout!(());
+    layout!(PhantomData<u64>);
+
+    section("pointers");
+    layout!(&u8);
+    layout!(Box<u64>);
+    layout!(&[u8]);
+    layout!(&str);
+    layout!(&dyn Display);
+    layout!(String);
+    layout!(Vec<u8>);
+
+    // size_of_val measures the value behind a reference, even an unsized one
+    let numbers = [1u32, 2, 3, 4, 5];
+    let slice: &[u32] = &numbers[1..4];
+    section("values");
+    println!("size_of_val(\"hello\")   {}", size_of_val("hello"));
+    println!("size_of_val(&numbers[1..4]) {}", size_of_val(slice));
+    let text = String::from("hello, world");
+    println!(
+        "a String of {} bytes: {} on the stack, {} on the heap",
+        text.len(),
+        size_of_val(&text),
+        text.capacity()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let size = size_of::<str>();
+    // error[E0277]: the size for values of type `str` cannot be known at compilation time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORD: usize = size_of::<usize>();
+
+    #[test]
+    fn fixed_width_numbers_have_fixed_sizes() {
+        assert_eq!(size_of::<u8>(), 1);
+        assert_eq!(size_of::<u16>(), 2);
+        assert_eq!(size_of::<u32>(), 4);
+        assert_eq!(size_of::<u64>(), 8);
+        assert_eq!(size_of::<bool>(), 1);
+        // A char is any Unicode scalar value, which needs 21 bits
+        assert_eq!(size_of::<char>(), 4);
+    }
+
+    #[test]
+    fn sizes_are_multiples_of_alignments() {
+        fn check<T>() {
+            assert_eq!(size_of::<T>() % align_of::<T>(), 0);
+        }
+        check::<u8>();
+        check::<u64>();
+        check::<(u8, u32)>();
+        check::<(u64, u8)>();
+        check::<[u16; 3]>();
+        check::<String>();
+    }
+
+    #[test]
+    fn arrays_have_no_gaps() {
+        assert_eq!(size_of::<[u32; 3]>(), 3 * size_of::<u32>());
+        assert_eq!(size_of::<[(u8, u32); 4]>(), 4 * size_of::<(u8, u32)>());
+    }
+
+    #[test]
+    fn thin_and_fat_pointers() {
+        assert_eq!(size_of::<&u8>(), WORD);
+        assert_eq!(size_of::<Box<u64>>(), WORD);
+        assert_eq!(size_of::<&[u8]>(), 2 * WORD);
+        assert_eq!(size_of::<&str>(), 2 * WORD);
+        assert_eq!(size_of::<&dyn Display>(), 2 * WORD);
+        // Pointer, capacity, length
+        assert_eq!(size_of::<Vec<u8>>(), 3 * WORD);
+        assert_eq!(size_of::<String>(), 3 * WORD);
+    }
+
+    #[test]
+    fn empty_types_take_no_space() {
+        assert_eq!(size_of::<Marker>(), 0);
+        assert_eq!(size_of::<()>(), 0);
+        assert_eq!(size_of::<PhantomData<u64>>(), 0);
+        assert_eq!(size_of::<[u64; 0]>(), 0);
+        assert_eq!(size_of::<(u32, ())>(), size_of::<u32>());
+    }
+
+    #[test]
+    fn size_of_val_sees_through_references() {
+        assert_eq!(size_of_val("héllo"), 6);
+        assert_eq!(size_of_val(&[0u16; 5][..]), 10);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - size_of::<T>() is the bytes per value; align_of::<T>() where it may start
+ * - Size is always a multiple of alignment, so arrays need no gaps
+ * - &[T], &str, and &dyn Trait are fat: a pointer plus a length or vtable
+ * - String and Vec are three words on the stack; their contents are on the heap
+ * - Zero-sized types (unit, empty structs, PhantomData) take no memory
+ */
+
+// EXPECTED:
+// -- numbers --
+// u8               size  1  align  1
+// u16              size  2  align  2
+// u32              size  4  align  4
+// u64              size  8  align  8
+// f64              size  8  align  8
+// bool             size  1  align  1
+// char             size  4  align  4
+// usize            size  8  align  8
+// -- compounds --
+// [u32; 3]         size 12  align  4
+// (u8, u32)        size  8  align  4
+// Marker           size  0  align  1
+// ()               size  0  align  1
+// PhantomData<u64> size  0  align  1
+// -- pointers --
+// &u8              size  8  align  8
+// Box<u64>         size  8  align  8
+// &[u8]            size 16  align  8
+// &str             size 16  align  8
+// &dyn Display     size 16  align  8
+// String           size 24  align  8
+// Vec<u8>          size 24  align  8
+// -- values --
+// size_of_val("hello")   5
+// size_of_val(&numbers[1..4]) 12
+// a String of 12 bytes: 24 on the stack, 12 on the heap
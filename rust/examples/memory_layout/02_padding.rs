@@ -0,0 +1,218 @@
+// Memory Layout 2: Padding, Field Order, and #[repr(C)]
+// Demonstrates padding bytes, offset_of!, field reordering, repr(C), and repr(packed)
+//
+// Concepts: padding, offset_of!, field reordering, repr(C), repr(packed)
+// Difficulty: intermediate
+// Minutes: 20
+//
+// Every field must sit at a multiple of its own alignment, so a u8
+// followed by a u32 leaves three unused bytes between them: padding. The
+// struct as a whole is padded at the end too, up to a multiple of its
+// largest alignment, so that the next one in an array lines up.
+//
+// How much padding there is depends on the order of the fields. A plain
+// Rust struct lets the compiler pick the order, and it picks one that
+// wastes little. #[repr(C)] keeps the declared order, as C does, which is
+// what code shared with C (or written to disk byte for byte) needs; the
+// price is the padding the order implies. #[repr(packed)] removes the
+// padding altogether, and with it the alignment, so its fields can't even
+// be borrowed.
+//
+// std::mem::offset_of! says where each field starts. The offsets of a
+// repr(C) struct are guaranteed; a plain struct's are up to the compiler.
+
+use std::mem::{align_of, offset_of, size_of};
+
+/// Whatever order the compiler likes.
+#[allow(dead_code)]
+struct Rusty {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+/// The declared order: a, 3 bytes of padding, b, c, 2 bytes of padding.
+#[repr(C)]
+#[allow(dead_code)]
+struct InOrder {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+/// The same fields, declared largest first: only 1 byte of padding.
+#[repr(C)]
+#[allow(dead_code)]
+struct LargestFirst {
+    b: u32,
+    c: u16,
+    a: u8,
+}
+
+/// No padding, and an alignment of 1.
+#[repr(C, packed)]
+#[allow(dead_code)]
+struct Packed {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+/// The bytes a type with fields of `field_sizes` spends on padding.
+fn padding<T>(field_sizes: &[usize]) -> usize {
+    size_of::<T>() - field_sizes.iter().sum::<usize>()
+}
+
+fn main() {
+    let fields = [size_of::<u8>(), size_of::<u32>(), size_of::<u16>()];
+    println!(
+        "fields u8, u32, u16: {} bytes of data",
+        fields.iter().sum::<usize>()
+    );
+    let layouts = [
+        (
+            "Rusty",
+            size_of::<Rusty>(),
+            align_of::<Rusty>(),
+            padding::<Rusty>(&fields),
+        ),
+        (
+            "InOrder",
+            size_of::<InOrder>(),
+            align_of::<InOrder>(),
+            padding::<InOrder>(&fields),
+        ),
+        (
+            "LargestFirst",
+            size_of::<LargestFirst>(),
+            align_of::<LargestFirst>(),
+            padding::<LargestFirst>(&fields),
+        ),
+        (
+            "Packed",
+            size_of::<Packed>(),
+            align_of::<Packed>(),
+            padding::<Packed>(&fields),
+        ),
+    ];
+    for (name, size, align, padding) in layouts {
+        println!(
+            "{:<13} size {:>2}  align {}  padding {}",
+            name, size, align, padding
+        );
+    }
+    println!();
+
+    println!(
+        "InOrder offsets:      a {}, b {}, c {}",
+        offset_of!(InOrder, a),
+        offset_of!(InOrder, b),
+        offset_of!(InOrder, c)
+    );
+    println!(
+        "LargestFirst offsets: b {}, c {}, a {}",
+        offset_of!(LargestFirst, b),
+        offset_of!(LargestFirst, c),
+        offset_of!(LargestFirst, a)
+    );
+    println!(
+        "Packed offsets:       a {}, b {}, c {}",
+        offset_of!(Packed, a),
+        offset_of!(Packed, b),
+        offset_of!(Packed, c)
+    );
+    println!();
+
+    // Padding is paid once per value, so it adds up in big collections
+    let count = 1_000_000;
+    println!(
+        "a million InOrder: {} MB, a million LargestFirst: {} MB",
+        count * size_of::<InOrder>() / 1_000_000,
+        count * size_of::<LargestFirst>() / 1_000_000
+    );
+
+    // Fields of a packed struct can be copied out, but not borrowed
+    let packed = Packed { a: 1, b: 2, c: 3 };
+    let b = packed.b;
+    println!("packed.b = {}", b);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let b = &packed.b;
+    // error[E0793]: reference to field of packed struct is unaligned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repr_c_keeps_the_declared_order() {
+        assert_eq!(offset_of!(InOrder, a), 0);
+        assert_eq!(offset_of!(InOrder, b), 4);
+        assert_eq!(offset_of!(InOrder, c), 8);
+        assert_eq!(size_of::<InOrder>(), 12);
+        assert_eq!(align_of::<InOrder>(), 4);
+    }
+
+    #[test]
+    fn declaring_largest_first_saves_padding() {
+        assert_eq!(size_of::<LargestFirst>(), 8);
+        assert_eq!(padding::<LargestFirst>(&[1, 4, 2]), 1);
+    }
+
+    #[test]
+    fn rust_may_reorder_but_never_does_worse() {
+        // The exact layout isn't promised, only that it is valid
+        assert!(size_of::<Rusty>() <= size_of::<InOrder>());
+        assert!(size_of::<Rusty>() >= 7);
+        assert_eq!(align_of::<Rusty>(), align_of::<u32>());
+        assert_eq!(size_of::<Rusty>() % align_of::<Rusty>(), 0);
+    }
+
+    #[test]
+    fn packed_has_no_padding_and_no_alignment() {
+        assert_eq!(size_of::<Packed>(), 7);
+        assert_eq!(align_of::<Packed>(), 1);
+        assert_eq!(offset_of!(Packed, b), 1);
+    }
+
+    #[test]
+    fn padding_at_the_end_keeps_arrays_aligned() {
+        #[repr(C)]
+        struct Tail {
+            _wide: u64,
+            _narrow: u8,
+        }
+        // 9 bytes of fields, rounded up to u64's alignment (8 on most
+        // targets, 4 on some 32-bit ones)
+        assert_eq!(
+            size_of::<Tail>(),
+            9usize.next_multiple_of(align_of::<u64>())
+        );
+        assert_eq!(size_of::<[Tail; 2]>(), 2 * size_of::<Tail>());
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Each field starts at a multiple of its alignment; the gaps are padding
+ * - A struct's size is rounded up to its alignment, for arrays
+ * - Plain Rust structs may reorder fields to reduce padding; the order isn't promised
+ * - #[repr(C)] keeps the declared order: needed for C and for byte-exact formats
+ * - #[repr(packed)] drops padding and alignment; its fields can't be borrowed
+ */
+
+// EXPECTED:
+// fields u8, u32, u16: 7 bytes of data
+// Rusty         size  8  align 4  padding 1
+// InOrder       size 12  align 4  padding 5
+// LargestFirst  size  8  align 4  padding 1
+// Packed        size  7  align 1  padding 0
+//
+// InOrder offsets:      a 0, b 4, c 8
+// LargestFirst offsets: b 0, c 4, a 6
+// Packed offsets:       a 0, b 1, c 5
+//
+// a million InOrder: 12 MB, a million LargestFirst: 8 MB
+// packed.b = 2
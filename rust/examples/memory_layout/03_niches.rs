@@ -0,0 +1,202 @@
+// Memory Layout 3: Enums and Niches
+// Demonstrates enum tags, niche optimization, and why Option<Box<T>> is pointer-sized
+//
+// Concepts: enum tags, niche optimization, Option<Box<T>>
+// Difficulty: intermediate
+// Minutes: 20
+//
+// An enum stores which variant it is in a tag, next to the largest
+// variant's data, so Option<u32> is a tag plus a u32: 8 bytes, with the
+// padding. But some types have values that can never occur. A reference
+// or a Box is never null, a NonZeroU32 is never 0, a bool is only ever 0
+// or 1. Those impossible values are a *niche*, and the compiler can use
+// one to mean "None" instead of adding a tag. Option<&T>, Option<Box<T>>,
+// and Option<NonZeroU32> are exactly as big as what they wrap.
+//
+// That is why Rust has no null pointers and pays nothing for it: an
+// Option<Box<Node>> is a nullable pointer in memory, with the compiler
+// checking every use. For references, Box, and the NonZero types, the
+// standard library *guarantees* the optimization; the tests below check
+// it on every target. Other niches (like bool's, or Vec's non-null
+// pointer) are used today without a promise.
+
+use std::mem::{align_of, size_of};
+use std::num::NonZeroU32;
+
+use learn_rust_core::{demo, section};
+
+/// Prints a type's size and alignment, with the type as written.
+macro_rules! layout {
+    ($t:ty) => {
+        println!(
+            "{:<26} size {:>2}  align {}",
+            stringify!($t),
+            size_of::<$t>(),
+            align_of::<$t>()
+        )
+    };
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[allow(dead_code)]
+enum Shape {
+    Circle(f64),
+    Rectangle(f64, f64),
+}
+
+/// A list node whose `next` is "maybe a pointer", with no tag.
+#[allow(dead_code)]
+struct Node {
+    value: u32,
+    next: Option<Box<Node>>,
+}
+
+/// An explicit tag type, and explicit tag values.
+#[repr(u8)]
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+enum Status {
+    Ok = 0,
+    NotFound = 4,
+    Busy = 9,
+}
+
+fn main() {
+    section("a tag is added");
+    layout!(u32);
+    layout!(Option<u32>);
+    layout!(f64);
+    layout!(Option<f64>);
+    layout!(Shape);
+
+    section("a niche is used");
+    layout!(&u8);
+    layout!(Option<&u8>);
+    layout!(Box<u64>);
+    layout!(Option<Box<u64>>);
+    layout!(NonZeroU32);
+    layout!(Option<NonZeroU32>);
+    layout!(Vec<u8>);
+    layout!(Option<Vec<u8>>);
+    layout!(Option<Box<Node>>);
+
+    section("small enums");
+    layout!(Direction);
+    layout!(Option<Direction>);
+    layout!(bool);
+    layout!(Option<bool>);
+    layout!(Option<Option<bool>>);
+    layout!(Status);
+
+    // A fieldless enum's tag is a number we can read with `as`
+    let statuses = [Status::Ok, Status::NotFound, Status::Busy];
+    let tags: Vec<u8> = statuses.iter().map(|&status| status as u8).collect();
+    println!("\nStatus tags: {:?}", tags);
+
+    // NonZeroU32::new is how a u32 gets into the niche-friendly type
+    demo!(NonZeroU32::new(7));
+    demo!(NonZeroU32::new(0));
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let count: NonZeroU32 = 7;
+    // error[E0308]: mismatched types (expected `NonZero<u32>`, found integer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The guaranteed cases: the Option costs nothing
+    #[test]
+    fn option_of_a_pointer_is_pointer_sized() {
+        assert_eq!(size_of::<Option<&u8>>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<&mut String>>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<Box<u64>>>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<Box<Node>>>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<Box<()>>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn option_of_non_zero_is_free() {
+        assert_eq!(size_of::<Option<NonZeroU32>>(), size_of::<u32>());
+        assert_eq!(size_of::<Option<std::num::NonZeroU8>>(), size_of::<u8>());
+    }
+
+    #[test]
+    fn option_of_fat_pointers_is_free_too() {
+        assert_eq!(size_of::<Option<&str>>(), size_of::<&str>());
+        assert_eq!(size_of::<Option<Box<[u8]>>>(), size_of::<Box<[u8]>>());
+    }
+
+    // Without a niche, the tag takes space (plus padding)
+    #[test]
+    fn types_without_a_niche_need_a_tag() {
+        assert!(size_of::<Option<u32>>() > size_of::<u32>());
+        assert!(size_of::<Option<f64>>() > size_of::<f64>());
+        assert!(size_of::<Option<usize>>() > size_of::<usize>());
+    }
+
+    #[test]
+    fn repr_u8_sets_the_tag() {
+        assert_eq!(size_of::<Status>(), 1);
+        assert_eq!(Status::Busy as u8, 9);
+    }
+
+    #[test]
+    fn data_carrying_enums_hold_their_largest_variant() {
+        assert!(size_of::<Shape>() >= 2 * size_of::<f64>());
+        assert_eq!(size_of::<Shape>() % align_of::<f64>(), 0);
+    }
+
+    #[test]
+    fn a_node_is_a_value_and_a_nullable_pointer() {
+        assert!(size_of::<Node>() <= 2 * size_of::<usize>());
+    }
+}
+
+/*
+ * Key Concepts:
+ * - An enum stores a tag beside its largest variant: Option<u32> is 8 bytes
+ * - A niche is an impossible bit pattern (null, zero, 2 in a bool) that can mean None
+ * - Option<&T>, Option<Box<T>>, and Option<NonZeroU32> are guaranteed free
+ * - That is how Rust has no null pointers and pays nothing for it
+ * - #[repr(u8)] fixes a fieldless enum's tag type; `as u8` reads the tag
+ */
+
+// EXPECTED:
+// -- a tag is added --
+// u32                        size  4  align 4
+// Option<u32>                size  8  align 4
+// f64                        size  8  align 8
+// Option<f64>                size 16  align 8
+// Shape                      size 24  align 8
+// -- a niche is used --
+// &u8                        size  8  align 8
+// Option<&u8>                size  8  align 8
+// Box<u64>                   size  8  align 8
+// Option<Box<u64>>           size  8  align 8
+// NonZeroU32                 size  4  align 4
+// Option<NonZeroU32>         size  4  align 4
+// Vec<u8>                    size 24  align 8
+// Option<Vec<u8>>            size 24  align 8
+// Option<Box<Node>>          size  8  align 8
+// -- small enums --
+// Direction                  size  1  align 1
+// Option<Direction>          size  1  align 1
+// bool                       size  1  align 1
+// Option<bool>               size  1  align 1
+// Option<Option<bool>>       size  1  align 1
+// Status                     size  1  align 1
+//
+// Status tags: [0, 4, 9]
+// NonZeroU32::new(7) = Some(7)
+// NonZeroU32::new(0) = None
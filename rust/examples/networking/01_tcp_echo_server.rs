@@ -0,0 +1,170 @@
+// Networking 1: A Blocking TCP Echo Server
+// Demonstrates TcpListener, accept(), a thread per client, and shutdown()
+//
+// Concepts: TcpListener, accept, thread per client, shutdown
+// Difficulty: intermediate
+// Minutes: 15
+//
+// A TCP server binds a listener to an address, then accepts connections
+// one after another. Each accepted connection is a TcpStream, which reads
+// and writes like a file. These calls block: accept() waits for a client,
+// and read() waits for data. A server that handled clients in its accept
+// loop could serve only one at a time, so each client gets a thread.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::thread;
+
+/// Sends every line from the client straight back, until the client closes
+/// its side of the connection. Returns how many lines it echoed.
+fn handle_client(stream: TcpStream) -> io::Result<usize> {
+    // try_clone gives a second handle to the same socket, so one can be
+    // wrapped in a BufReader while the other writes. (`io::copy(&mut &stream,
+    // &mut &stream)` would echo raw bytes without splitting lines.)
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream);
+    let mut lines = 0;
+    // lines() ends when the client shuts down its writing side: read()
+    // returns 0 bytes, the TCP way of saying "end of file"
+    for line in reader.lines() {
+        writeln!(writer, "{}", line?)?;
+        lines += 1;
+    }
+    // Printed while the connection is still open, so it comes before the
+    // client sees the end of the reply
+    println!(
+        "  server: echoed {} lines to {}",
+        lines,
+        writer.peer_addr()?.ip()
+    );
+    Ok(lines)
+}
+
+/// Accepts `clients` connections, each on its own thread, and returns once
+/// all of them are done. A real server would loop forever instead.
+fn serve(listener: TcpListener, clients: usize) -> io::Result<()> {
+    let mut workers = Vec::new();
+    for stream in listener.incoming().take(clients) {
+        let stream = stream?;
+        workers.push(thread::spawn(move || handle_client(stream)));
+        // This would cause an error:
+        #[cfg(feature = "broken")]
+        println!("accepted {:?}", stream.peer_addr());
+        // error[E0382]: borrow of moved value: `stream`
+    }
+    for worker in workers {
+        // join() is Err only if the thread panicked; `?` then passes on
+        // the io::Error the client's handler returned, if any
+        worker.join().expect("a client thread panicked")?;
+    }
+    Ok(())
+}
+
+/// Connects, sends `lines`, and returns everything the server sent back.
+fn talk(addr: std::net::SocketAddr, lines: &[&str]) -> io::Result<String> {
+    let mut stream = TcpStream::connect(addr)?;
+    for line in lines {
+        writeln!(stream, "{}", line)?;
+    }
+    // Closing our writing half tells the server we're done, while we can
+    // still read its replies
+    stream.shutdown(Shutdown::Write)?;
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply)?;
+    Ok(reply)
+}
+
+fn main() -> io::Result<()> {
+    // Port 0 asks the OS for any free port, so the example never collides
+    // with something already running. 127.0.0.1 only accepts connections
+    // from this machine; 0.0.0.0 would accept them from the network too.
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    // The port differs from run to run, so it's checked rather than printed
+    println!(
+        "listening on {}, port picked: {}",
+        addr.ip(),
+        addr.port() != 0
+    );
+
+    let server = thread::spawn(move || serve(listener, 2));
+
+    for name in ["alice", "bob"] {
+        let reply = talk(addr, &[&format!("hello from {}", name), "bye"])?;
+        print!("{} got back:\n{}", name, reply);
+    }
+
+    server.join().expect("the server panicked")?;
+    println!("server stopped after 2 clients");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Starts `serve` on a free port and returns its address.
+    fn start(clients: usize) -> (std::net::SocketAddr, thread::JoinHandle<io::Result<()>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        (addr, thread::spawn(move || serve(listener, clients)))
+    }
+
+    #[test]
+    fn echoes_every_line() {
+        let (addr, server) = start(1);
+        let reply = talk(addr, &["one", "two", "three"]).unwrap();
+        assert_eq!(reply, "one\ntwo\nthree\n");
+        server.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn a_client_that_sends_nothing_gets_nothing() {
+        let (addr, server) = start(1);
+        assert_eq!(talk(addr, &[]).unwrap(), "");
+        server.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn serves_clients_at_the_same_time() {
+        let (addr, server) = start(2);
+        // The first client connects and stays connected...
+        let mut first = TcpStream::connect(addr).unwrap();
+        writeln!(first, "still here").unwrap();
+
+        // ...and the second is answered anyway, because it has its own thread
+        let second = TcpStream::connect(addr).unwrap();
+        writeln!(&second, "ping").unwrap();
+        let mut line = String::new();
+        BufReader::new(&second).read_line(&mut line).unwrap();
+        assert_eq!(line, "ping\n");
+
+        drop(second);
+        first.shutdown(Shutdown::Write).unwrap();
+        let mut reply = String::new();
+        first.read_to_string(&mut reply).unwrap();
+        assert_eq!(reply, "still here\n");
+        server.join().unwrap().unwrap();
+    }
+}
+
+/*
+ * Key Concepts:
+ * - TcpListener::bind + incoming() accept connections; port 0 picks a free one
+ * - A TcpStream implements Read and Write, and every call blocks
+ * - One thread per client keeps a slow client from stalling the others
+ * - shutdown(Shutdown::Write) sends "end of file" but keeps reading open
+ * - try_clone gives separate handles for reading and writing
+ */
+
+// EXPECTED:
+// listening on 127.0.0.1, port picked: true
+//   server: echoed 2 lines to 127.0.0.1
+// alice got back:
+// hello from alice
+// bye
+//   server: echoed 2 lines to 127.0.0.1
+// bob got back:
+// hello from bob
+// bye
+// server stopped after 2 clients
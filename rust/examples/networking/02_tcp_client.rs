@@ -0,0 +1,169 @@
+// Networking 2: A TCP Client That Copes With Failure
+// Demonstrates connect_timeout, read/write timeouts, and networking errors
+//
+// Concepts: TcpStream, connect_timeout, read and write timeouts, io errors
+// Difficulty: intermediate
+// Minutes: 15
+//
+// A client can't trust the other end. The server may not be running, it
+// may be on a machine that never answers, or it may accept the connection
+// and then say nothing. Blocking calls without a timeout would wait
+// forever in the last two cases, so a real client sets one on every step
+// and treats each io::Error by its kind.
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+/// Sends `message` to `addr` and returns the whole reply, giving up on any
+/// step that takes longer than `timeout`.
+fn echo_once(addr: SocketAddr, message: &str, timeout: Duration) -> io::Result<String> {
+    // connect() could wait a minute or more for a dead address
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    // Send small writes at once instead of waiting to batch them (Nagle's
+    // algorithm); it matters for chatty request/response protocols
+    stream.set_nodelay(true)?;
+
+    // write_all keeps writing until every byte is sent: one write() may
+    // send only part of the buffer
+    stream.write_all(message.as_bytes())?;
+    stream.shutdown(Shutdown::Write)?;
+
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply)?;
+    Ok(reply)
+}
+
+/// A server that echoes the bytes of every connection it accepts, forever.
+fn spawn_echo_server() -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            thread::spawn(move || {
+                // Reading and writing the same socket through two `&TcpStream`s
+                let _ = io::copy(&mut &stream, &mut &stream);
+            });
+        }
+    });
+    Ok(addr)
+}
+
+/// A server that accepts connections and never says a word.
+fn spawn_silent_server() -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    thread::spawn(move || {
+        // Keeping the streams alive keeps the connections open
+        let mut open = Vec::new();
+        for stream in listener.incoming() {
+            open.push(stream);
+        }
+    });
+    Ok(addr)
+}
+
+/// An address on this machine that nothing is listening on.
+fn unused_addr() -> io::Result<SocketAddr> {
+    // Bind to get a free port, then drop the listener to close it again
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    listener.local_addr()
+}
+
+/// Names the error kinds a client meets most.
+fn describe(error: &io::Error) -> &'static str {
+    match error.kind() {
+        io::ErrorKind::ConnectionRefused => "refused: nothing is listening there",
+        // Which of the two a timed-out read reports depends on the platform
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => "timed out: no answer in time",
+        io::ErrorKind::ConnectionReset => "reset: the other end went away",
+        _ => "some other error",
+    }
+}
+
+fn main() -> io::Result<()> {
+    let timeout = Duration::from_millis(500);
+
+    let echo = spawn_echo_server()?;
+    let reply = echo_once(echo, "ping", timeout)?;
+    println!("echo server replied {:?}", reply);
+
+    // A closed port on this machine answers straight away with a refusal
+    match echo_once(unused_addr()?, "ping", timeout) {
+        Ok(reply) => println!("unexpected reply {:?}", reply),
+        Err(error) => println!("closed port: {}", describe(&error)),
+    }
+
+    // The silent server accepts us, so only the read timeout saves us
+    let silent = spawn_silent_server()?;
+    match echo_once(silent, "ping", Duration::from_millis(200)) {
+        Ok(reply) => println!("unexpected reply {:?}", reply),
+        Err(error) => println!("silent server: {}", describe(&error)),
+    }
+
+    // A host name can resolve to several addresses (IPv4 and IPv6), which
+    // to_socket_addrs returns for connect() to try one by one
+    let all_loopback = ("localhost", echo.port())
+        .to_socket_addrs()?
+        .all(|addr| addr.ip().is_loopback());
+    println!("localhost resolves to loopback only: {}", all_loopback);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let stream: TcpStream = TcpStream::connect_timeout("127.0.0.1:80", timeout)?;
+    // error[E0308]: mismatched types (connect_timeout wants a &SocketAddr, not a &str)
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn echoes_the_whole_message() {
+        let addr = spawn_echo_server().unwrap();
+        assert_eq!(echo_once(addr, "hello", TIMEOUT).unwrap(), "hello");
+        // The server serves any number of clients, one connection each
+        assert_eq!(echo_once(addr, "again", TIMEOUT).unwrap(), "again");
+        assert_eq!(echo_once(addr, "", TIMEOUT).unwrap(), "");
+    }
+
+    #[test]
+    fn a_closed_port_refuses_the_connection() {
+        let error = echo_once(unused_addr().unwrap(), "hello", TIMEOUT).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ConnectionRefused);
+    }
+
+    #[test]
+    fn a_silent_server_times_out() {
+        let addr = spawn_silent_server().unwrap();
+        let error = echo_once(addr, "hello", Duration::from_millis(100)).unwrap_err();
+        assert!(
+            matches!(
+                error.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ),
+            "got {error:?}"
+        );
+    }
+}
+
+/*
+ * Key Concepts:
+ * - connect_timeout, set_read_timeout, and set_write_timeout bound every wait
+ * - write_all sends a whole buffer; a single write() may not
+ * - ErrorKind tells failures apart: ConnectionRefused, TimedOut, WouldBlock
+ * - A timed-out read is WouldBlock on Unix and TimedOut on Windows
+ * - to_socket_addrs resolves host names, possibly to several addresses
+ */
+
+// EXPECTED:
+// echo server replied "ping"
+// closed port: refused: nothing is listening there
+// silent server: timed out: no answer in time
+// localhost resolves to loopback only: true
@@ -0,0 +1,175 @@
+// Networking 3: UDP Datagrams
+// Demonstrates UdpSocket, send_to/recv_from, message boundaries, and connect()
+//
+// Concepts: UdpSocket, send_to, recv_from, message boundaries, connect
+// Difficulty: intermediate
+// Minutes: 15
+//
+// UDP has no connections. A socket sends self-contained datagrams to any
+// address and receives them from anyone. Each send arrives as exactly one
+// receive, never split or merged the way a TCP byte stream can be, but a
+// datagram may arrive twice, out of order, or not at all. There is no
+// "end of file" either, so the two sides agree on a message that means
+// "stop".
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::str;
+use std::thread;
+use std::time::Duration;
+
+/// Answers every datagram with its text in upper case, until one says
+/// "quit". Returns how many it answered.
+fn run_receiver(socket: UdpSocket) -> io::Result<usize> {
+    // A datagram larger than the buffer is cut short and the rest is lost,
+    // so size it for the largest message the protocol allows
+    let mut buf = [0u8; 512];
+    let mut answered = 0;
+    loop {
+        let (len, from) = socket.recv_from(&mut buf)?;
+        let text = str::from_utf8(&buf[..len])
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        if text == "quit" {
+            return Ok(answered);
+        }
+        socket.send_to(text.to_uppercase().as_bytes(), from)?;
+        answered += 1;
+    }
+}
+
+/// Binds a receiver to a free port and runs it on a thread.
+fn spawn_receiver() -> io::Result<(SocketAddr, thread::JoinHandle<io::Result<usize>>)> {
+    let socket = UdpSocket::bind("127.0.0.1:0")?;
+    let addr = socket.local_addr()?;
+    Ok((addr, thread::spawn(move || run_receiver(socket))))
+}
+
+/// Receives one datagram as a String.
+fn recv_text(socket: &UdpSocket) -> io::Result<String> {
+    let mut buf = [0u8; 512];
+    let len = socket.recv(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
+
+fn main() -> io::Result<()> {
+    let (receiver, handle) = spawn_receiver()?;
+
+    // The sender binds too: its port is the return address on each datagram
+    let sender = UdpSocket::bind("127.0.0.1:0")?;
+    // Nothing guarantees an answer, so don't wait for one forever
+    sender.set_read_timeout(Some(Duration::from_secs(2)))?;
+
+    // Three sends are three datagrams, and three receives get them back
+    // one by one. Over TCP the same writes could arrive as one read.
+    for word in ["one", "two", "three"] {
+        sender.send_to(word.as_bytes(), receiver)?;
+        println!("sent {:?}, got {:?}", word, recv_text(&sender)?);
+    }
+
+    // connect() on a UDP socket only remembers a default peer: send() and
+    // recv() then skip the address, and datagrams from others are dropped
+    sender.connect(receiver)?;
+    sender.send(b"connected")?;
+    println!("after connect, got {:?}", recv_text(&sender)?);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let reply: String = sender.recv(&mut [0u8; 512])?;
+    // error[E0308]: `?` operator has incompatible types (recv returns the length, not the data)
+
+    sender.send(b"quit")?;
+    let answered = handle.join().expect("the receiver panicked")?;
+    println!("receiver answered {} datagrams", answered);
+
+    // Nobody is left to answer, so the read timeout ends the wait. A
+    // connected socket may hear sooner: on some systems the "port
+    // unreachable" the OS sends back shows up as ConnectionRefused.
+    sender.set_read_timeout(Some(Duration::from_millis(300)))?;
+    sender.send(b"anyone?")?;
+    match recv_text(&sender) {
+        Ok(reply) => println!("unexpected reply {:?}", reply),
+        Err(error) => match error.kind() {
+            io::ErrorKind::WouldBlock
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::ConnectionRefused => println!("no answer, as expected"),
+            _ => return Err(error),
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> UdpSocket {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        socket
+    }
+
+    #[test]
+    fn answers_each_datagram_in_upper_case() {
+        let (addr, handle) = spawn_receiver().unwrap();
+        let socket = client();
+        for word in ["hello", "udp", "Mixed Case"] {
+            socket.send_to(word.as_bytes(), addr).unwrap();
+            assert_eq!(recv_text(&socket).unwrap(), word.to_uppercase());
+        }
+        socket.send_to(b"quit", addr).unwrap();
+        assert_eq!(handle.join().unwrap().unwrap(), 3);
+    }
+
+    #[test]
+    fn datagrams_keep_their_boundaries() {
+        let (addr, handle) = spawn_receiver().unwrap();
+        let socket = client();
+        socket.send_to(b"ab", addr).unwrap();
+        socket.send_to(b"cd", addr).unwrap();
+        // Two replies, never one "ABCD"
+        assert_eq!(recv_text(&socket).unwrap(), "AB");
+        assert_eq!(recv_text(&socket).unwrap(), "CD");
+        socket.send_to(b"quit", addr).unwrap();
+        assert_eq!(handle.join().unwrap().unwrap(), 2);
+    }
+
+    #[test]
+    fn answers_several_senders() {
+        let (addr, handle) = spawn_receiver().unwrap();
+        let (first, second) = (client(), client());
+        first.send_to(b"first", addr).unwrap();
+        second.send_to(b"second", addr).unwrap();
+        // Each reply goes back to the address its datagram came from
+        assert_eq!(recv_text(&first).unwrap(), "FIRST");
+        assert_eq!(recv_text(&second).unwrap(), "SECOND");
+        first.send_to(b"quit", addr).unwrap();
+        assert_eq!(handle.join().unwrap().unwrap(), 2);
+    }
+
+    #[test]
+    fn invalid_utf8_stops_the_receiver() {
+        let (addr, handle) = spawn_receiver().unwrap();
+        client().send_to(&[0xff, 0xfe], addr).unwrap();
+        let error = handle.join().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - UdpSocket::bind, then send_to/recv_from: no connection, no accept()
+ * - One send is one datagram; its boundaries survive, its delivery doesn't
+ * - recv_from reports the sender's address, which is where replies go
+ * - connect() sets a default peer for send() and recv()
+ * - With no end of file, a protocol needs its own "stop" message and timeouts
+ */
+
+// EXPECTED:
+// sent "one", got "ONE"
+// sent "two", got "TWO"
+// sent "three", got "THREE"
+// after connect, got "CONNECTED"
+// receiver answered 4 datagrams
+// no answer, as expected
@@ -0,0 +1,131 @@
+// No std 1: core, alloc, and std
+// Demonstrates the three layers of Rust's standard library, what each needs from the machine, and formatting with no heap
+//
+// Concepts: core, alloc, std, formatting without a heap
+// Difficulty: advanced
+// Minutes: 10
+//
+// "The standard library" is three crates stacked on each other:
+//
+// - core needs nothing: no heap, no operating system. Option, Result,
+//   iterators, slices, str and char, fmt, Cell, atomics, mem, ptr. It is
+//   all a microcontroller, a bootloader, or a kernel gets for free.
+// - alloc adds the types that own memory on the heap: Box, Vec, String,
+//   Rc, BTreeMap. It needs a global allocator, which std provides on top
+//   of the OS, and a bare-metal program must bring itself.
+// - std adds what needs an operating system: files, sockets, threads,
+//   clocks, stdin and stdout, env vars, and HashMap, whose default hasher
+//   seeds itself from the OS's randomness. It also re-exports core and
+//   alloc, so std::vec::Vec *is* alloc::vec::Vec.
+//
+// A crate marked #![no_std] links only core (and alloc, if it says
+// `extern crate alloc;`). A library that can live with that runs
+// everywhere, which is why so many crates on crates.io are no_std with an
+// optional `std` feature: `ringbuf/` is one, and 02_ring_buffer uses it.
+//
+// This file is an ordinary std program, so it can use all three and show
+// where things really live.
+
+// std programs don't link alloc by name unless they ask
+extern crate alloc;
+
+use core::fmt::{self, Write};
+use std::any::type_name;
+
+/// Formats into a fixed array on the stack: core::fmt::Write needs nothing
+/// but a place to put the bytes, so no_std code can still use write!.
+struct StackWriter<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> StackWriter<N> {
+    fn new() -> Self {
+        StackWriter {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only whole &strs are ever copied in, so this is valid UTF-8
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl<const N: usize> Write for StackWriter<N> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.len + text.len();
+        if end > N {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+fn main() {
+    // Where the familiar types are defined
+    println!("std::vec::Vec<u8>        is {}", type_name::<Vec<u8>>());
+    println!("std::string::String      is {}", type_name::<String>());
+    println!("std::option::Option<u8>  is {}", type_name::<Option<u8>>());
+    println!(
+        "BTreeMap<u8, u8>         is {}",
+        type_name::<std::collections::BTreeMap<u8, u8>>()
+    );
+    println!(
+        "HashMap<u8, u8>          is {}",
+        type_name::<std::collections::HashMap<u8, u8>>()
+    );
+
+    // The same Vec, named through alloc
+    let from_alloc: alloc::vec::Vec<u8> = alloc::vec![1, 2, 3];
+    let from_std: std::vec::Vec<u8> = from_alloc;
+    println!("\nalloc's Vec is std's Vec: {from_std:?}");
+
+    // core alone: iterators, slices, char methods, checked arithmetic
+    let digits = "2024-06-01".chars().filter(char::is_ascii_digit).count();
+    let largest = [3_u8, 250, 7].iter().copied().max();
+    println!(
+        "core only: {digits} digits, largest {largest:?}, 250 + 10 = {:?}",
+        250_u8.checked_add(10)
+    );
+
+    // write! with no heap: into 32 bytes on the stack
+    let mut line = StackWriter::<32>::new();
+    write!(line, "{} sensors, {:.1} C", 3, 21.456).unwrap();
+    println!("\nformatted on the stack: {:?}", line.as_str());
+    let mut tiny = StackWriter::<8>::new();
+    let result = write!(tiny, "far too long for eight bytes");
+    println!("into 8 bytes: {result:?}, kept {:?}", tiny.as_str());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let readings: core::vec::Vec<u8> = core::vec::Vec::new();
+    }
+    // error[E0433]: cannot find `vec` in `core`
+}
+
+/*
+ * Key Concepts:
+ * - core: the language's types and traits, needing no heap and no OS
+ * - alloc: Box, Vec, String, Rc, BTreeMap, given a global allocator
+ * - std: core + alloc + the operating system (files, threads, time, HashMap)
+ * - #![no_std] crates link only core, so they run anywhere, including bare metal
+ * - core::fmt::Write lets no_std code format into any buffer, even a stack array
+ */
+
+// EXPECTED:
+// std::vec::Vec<u8>        is alloc::vec::Vec<u8>
+// std::string::String      is alloc::string::String
+// std::option::Option<u8>  is core::option::Option<u8>
+// BTreeMap<u8, u8>         is alloc::collections::btree::map::BTreeMap<u8, u8>
+// HashMap<u8, u8>          is std::collections::hash::map::HashMap<u8, u8>
+//
+// alloc's Vec is std's Vec: [1, 2, 3]
+// core only: 8 digits, largest Some(250), 250 + 10 = None
+//
+// formatted on the stack: "3 sensors, 21.5 C"
+// into 8 bytes: Err(Error), kept ""
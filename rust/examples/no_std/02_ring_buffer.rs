@@ -0,0 +1,142 @@
+// No std 2: A Ring Buffer with No Allocator
+// Demonstrates a #![no_std] library, its capacity as a const generic, an optional std feature, and panic_handler
+//
+// Concepts: #![no_std], const generics, optional std feature, panic_handler
+// Difficulty: advanced
+// Minutes: 15
+//
+// `ringbuf/src/lib.rs` is a queue whose storage is an array inside the
+// struct, used in a circle: values go in at the tail and come out at the
+// head, and both wrap around the end of the array. It never allocates,
+// so it needs nothing from alloc or std, and its first line says so:
+//
+//     #![cfg_attr(not(feature = "std"), no_std)]
+//
+// That is, no_std unless the `std` feature is on. The feature adds the
+// parts that only make sense with an OS (its Full error becomes a
+// std::error::Error, and a buffer of bytes an io::Write), and this
+// package turns it on. A microcontroller project depends on it without
+// the feature and gets plain core. Building for a target with no std at
+// all proves the crate doesn't sneak any in:
+//
+//     rustup target add thumbv7em-none-eabihf
+//     cargo build -p ringbuf --target thumbv7em-none-eabihf
+//
+// A whole program without std has two more jobs, which `firmware/` does:
+// it has no main() for std's runtime to call (#![no_main]), and it must
+// define what a panic does, with a #[panic_handler] fn(&PanicInfo) -> !.
+//
+// The buffer's tests run on the host, where the test harness has std, in
+// a crate of their own: `cargo test -p ringbuf-host-tests`.
+
+use std::error::Error;
+use std::io::Write;
+use std::mem::size_of;
+
+use ringbuf::{Full, RingBuffer};
+
+/// `new` is a const fn, so a buffer can be a static, made at compile time.
+/// (To change one, firmware would put it behind a Mutex or a critical
+/// section.)
+static BOOT_LOG: RingBuffer<u8, 4> = RingBuffer::new();
+
+fn record(readings: &[i16]) -> Result<i32, Box<dyn Error>> {
+    let mut buffer = RingBuffer::<i16, 3>::new();
+    for &reading in readings {
+        buffer.push(reading)?; // Full<i16> converts into Box<dyn Error>
+    }
+    Ok(buffer.iter().map(|&r| i32::from(r)).sum())
+}
+
+fn main() {
+    // First in, first out, with a fixed capacity
+    let mut queue = RingBuffer::<char, 3>::new();
+    for c in ['a', 'b', 'c'] {
+        queue.push(c).unwrap();
+    }
+    println!("queue {queue:?}, full: {}", queue.is_full());
+    println!("push 'd': {:?}", queue.push('d'));
+    println!(
+        "pop: {:?}, then push 'd': {:?}",
+        queue.pop(),
+        queue.push('d')
+    );
+    println!("queue {queue:?}, oldest {:?}", queue.peek());
+
+    // push_overwrite keeps the newest values, as a sensor log would
+    let mut last = RingBuffer::<i16, 4>::new();
+    for reading in [20, 21, 23, 22, 25, 24] {
+        let evicted = last.push_overwrite(reading);
+        println!("reading {reading}: keep {last:?}, dropped {evicted:?}");
+    }
+
+    // A full buffer is an error the caller can handle; with std, a real one
+    println!("\n3 readings into 3 slots: {:?}", record(&[1, 2, 3]));
+    match record(&[1, 2, 3, 4]) {
+        Ok(sum) => println!("4 readings: {sum}"),
+        Err(error) => println!("4 readings into 3 slots: {error}"),
+    }
+    let Full(rejected) = RingBuffer::<u8, 0>::new().push(9).unwrap_err();
+    println!("a zero-capacity buffer gives back {rejected}");
+
+    // With std, a buffer of bytes is an io::Write
+    let mut bytes = RingBuffer::<u8, 16>::new();
+    write!(bytes, "t={}", 21.5).unwrap();
+    let text: String = bytes.iter().map(|&b| char::from(b)).collect();
+    println!("\nwritten as bytes: {text:?}");
+    println!(
+        "write_all past the end: {:?}",
+        bytes.write_all(b" and then some").map_err(|e| e.kind())
+    );
+
+    // Its size is its capacity, fixed at compile time
+    println!(
+        "\na static buffer: capacity {}, empty: {}",
+        BOOT_LOG.capacity(),
+        BOOT_LOG.is_empty()
+    );
+    println!(
+        "size_of RingBuffer<u8, 64>: {} bytes, RingBuffer<u32, 64>: {}",
+        size_of::<RingBuffer<u8, 64>>(),
+        size_of::<RingBuffer<u32, 64>>()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let small: RingBuffer<u8, 4> = RingBuffer::<u8, 8>::new();
+    }
+    // error[E0308]: mismatched types
+}
+
+/*
+ * Key Concepts:
+ * - A ring buffer reuses a fixed array in a circle: no allocation, ever
+ * - A const generic capacity makes the size part of the type, known at compile time
+ * - #![cfg_attr(not(feature = "std"), no_std)] keeps std support optional
+ * - A const fn constructor lets the buffer initialize a static
+ * - A no_std program also needs #![no_main] and a #[panic_handler]
+ * - Building for a target with no std proves a crate doesn't use it
+ */
+
+// EXPECTED:
+// queue ['a', 'b', 'c'], full: true
+// push 'd': Err(Full('d'))
+// pop: Some('a'), then push 'd': Ok(())
+// queue ['b', 'c', 'd'], oldest Some('b')
+// reading 20: keep [20], dropped None
+// reading 21: keep [20, 21], dropped None
+// reading 23: keep [20, 21, 23], dropped None
+// reading 22: keep [20, 21, 23, 22], dropped None
+// reading 25: keep [21, 23, 22, 25], dropped Some(20)
+// reading 24: keep [23, 22, 25, 24], dropped Some(21)
+//
+// 3 readings into 3 slots: Ok(6)
+// 4 readings into 3 slots: the ring buffer is full
+// a zero-capacity buffer gives back 9
+//
+// written as bytes: "t=21.5"
+// write_all past the end: Err(WriteZero)
+//
+// a static buffer: capacity 4, empty: true
+// size_of RingBuffer<u8, 64>: 144 bytes, RingBuffer<u32, 64>: 528
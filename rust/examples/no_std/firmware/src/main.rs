@@ -0,0 +1,65 @@
+//! A program for a microcontroller: no operating system, no std, no heap.
+//!
+//! `#![no_std]` leaves only `core`, and with it two things std used to
+//! provide that a program can't do without:
+//!
+//! - **where it starts.** std's runtime calls `main`; here nothing does, so
+//!   `#![no_main]` says there isn't one, and the program exports the symbol
+//!   the linker starts at. On a real board, a crate like cortex-m-rt
+//!   provides it, and the reset vector that points at it.
+//! - **what a panic does.** std prints the message and unwinds the stack.
+//!   Without it, the program must say, once, in a `#[panic_handler]`. Here
+//!   it halts; firmware might log the message over a serial port, then reset.
+//!
+//! The ring buffer crate works unchanged, because it only ever asked for
+//! `core`.
+
+#![no_std]
+#![no_main]
+
+use core::hint::spin_loop;
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use ringbuf::RingBuffer;
+
+/// What the program has computed, where a debugger can read it.
+static CHECKSUM: AtomicU32 = AtomicU32::new(0);
+
+/// Bytes as they might arrive from a serial port.
+const INPUT: &[u8] = b"hello from a chip with 256 KiB of flash";
+
+/// Where the processor starts. `no_mangle` keeps the name the linker
+/// looks for.
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    // Lives on the stack: 32 bytes and two counters, no allocator needed
+    let mut received = RingBuffer::<u8, 32>::new();
+    let mut checksum: u32 = 0;
+    for &byte in INPUT {
+        if received.push(byte).is_err() {
+            // Full: handle what's queued, the way a main loop would
+            while let Some(byte) = received.pop() {
+                checksum = checksum.wrapping_mul(31).wrapping_add(u32::from(byte));
+            }
+            let _ = received.push(byte);
+        }
+    }
+    while let Some(byte) = received.pop() {
+        checksum = checksum.wrapping_mul(31).wrapping_add(u32::from(byte));
+    }
+    CHECKSUM.store(checksum, Ordering::Relaxed);
+
+    // There is nothing to return to
+    loop {
+        spin_loop();
+    }
+}
+
+/// Called on any panic: an out-of-bounds index, an unwrap on None, ...
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {
+        spin_loop();
+    }
+}
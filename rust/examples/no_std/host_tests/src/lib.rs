@@ -0,0 +1,2 @@
+//! Nothing here: the tests are in `tests/`. A cargo package needs a library
+//! or a binary, so this is the library.
@@ -0,0 +1,98 @@
+//! The ring buffer's behavior, tested on the host.
+
+use ringbuf::{Full, RingBuffer};
+
+#[test]
+fn values_come_out_in_the_order_they_went_in() {
+    let mut buffer = RingBuffer::<i32, 4>::new();
+    for n in 1..=3 {
+        buffer.push(n).unwrap();
+    }
+    assert_eq!(buffer.pop(), Some(1));
+    assert_eq!(buffer.pop(), Some(2));
+    assert_eq!(buffer.pop(), Some(3));
+    assert_eq!(buffer.pop(), None);
+}
+
+#[test]
+fn a_full_buffer_hands_the_value_back() {
+    let mut buffer = RingBuffer::<&str, 2>::new();
+    buffer.push("a").unwrap();
+    buffer.push("b").unwrap();
+    assert!(buffer.is_full());
+    assert_eq!(buffer.push("c"), Err(Full("c")));
+    assert_eq!(buffer.len(), 2);
+}
+
+#[test]
+fn the_storage_is_reused_around_the_end() {
+    let mut buffer = RingBuffer::<u32, 3>::new();
+    let mut popped = Vec::new();
+    for n in 0..100 {
+        buffer.push(n).unwrap();
+        if buffer.is_full() {
+            popped.push(buffer.pop().unwrap());
+        }
+    }
+    popped.extend(std::iter::from_fn(|| buffer.pop()));
+    assert_eq!(popped, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn push_overwrite_keeps_the_newest_values() {
+    let mut buffer = RingBuffer::<u32, 3>::new();
+    let evicted: Vec<_> = (1..=5).filter_map(|n| buffer.push_overwrite(n)).collect();
+    assert_eq!(evicted, [1, 2]);
+    assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [3, 4, 5]);
+}
+
+#[test]
+fn peek_and_iter_do_not_remove_anything() {
+    let mut buffer = RingBuffer::<char, 4>::new();
+    for c in "rust".chars() {
+        buffer.push(c).unwrap();
+    }
+    buffer.pop();
+    buffer.push('!').unwrap();
+    assert_eq!(buffer.peek(), Some(&'u'));
+    assert_eq!(buffer.iter().collect::<String>(), "ust!");
+    assert_eq!(format!("{buffer:?}"), "['u', 's', 't', '!']");
+    assert_eq!(buffer.len(), 4);
+}
+
+#[test]
+fn clear_drops_every_value() {
+    use std::rc::Rc;
+
+    let value = Rc::new(());
+    let mut buffer = RingBuffer::<Rc<()>, 8>::new();
+    for _ in 0..5 {
+        buffer.push(Rc::clone(&value)).unwrap();
+    }
+    assert_eq!(Rc::strong_count(&value), 6);
+    buffer.clear();
+    assert!(buffer.is_empty());
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn a_static_buffer_needs_no_allocation() {
+    // `new` is a const fn, so it can initialize a static
+    static EMPTY: RingBuffer<u64, 16> = RingBuffer::new();
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.capacity(), 16);
+    // The values are inline: the size is the array plus two counters
+    assert_eq!(
+        std::mem::size_of::<RingBuffer<u8, 16>>(),
+        std::mem::size_of::<[Option<u8>; 16]>() + 2 * std::mem::size_of::<usize>()
+    );
+}
+
+#[test]
+fn a_zero_capacity_buffer_holds_nothing() {
+    let mut buffer = RingBuffer::<u8, 0>::new();
+    assert!(buffer.is_full() && buffer.is_empty());
+    assert_eq!(buffer.push(1), Err(Full(1)));
+    assert_eq!(buffer.push_overwrite(2), Some(2));
+    assert_eq!(buffer.pop(), None);
+}
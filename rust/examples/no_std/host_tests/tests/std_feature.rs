@@ -0,0 +1,41 @@
+//! What the `std` feature adds, which only exists because this crate turns
+//! it on.
+
+use std::error::Error;
+use std::io::{ErrorKind, Write};
+
+use ringbuf::{Full, RingBuffer};
+
+#[test]
+fn full_is_a_std_error() {
+    let error: Box<dyn Error> = Box::new(Full(7));
+    assert_eq!(error.to_string(), "the ring buffer is full");
+}
+
+#[test]
+fn question_mark_converts_full_into_a_boxed_error() {
+    fn fill() -> Result<(), Box<dyn Error>> {
+        let mut buffer = RingBuffer::<u8, 1>::new();
+        buffer.push(1)?;
+        buffer.push(2)?;
+        Ok(())
+    }
+    assert!(fill().is_err());
+}
+
+#[test]
+fn a_byte_buffer_is_an_io_writer() {
+    let mut buffer = RingBuffer::<u8, 16>::new();
+    write!(buffer, "{}+{}={}", 2, 2, 4).unwrap();
+    let bytes: Vec<u8> = buffer.iter().copied().collect();
+    assert_eq!(bytes, b"2+2=4");
+}
+
+#[test]
+fn writing_stops_when_the_buffer_is_full() {
+    let mut buffer = RingBuffer::<u8, 4>::new();
+    assert_eq!(buffer.write(b"abcdef").unwrap(), 4);
+    assert_eq!(buffer.write(b"g").unwrap(), 0);
+    let error = buffer.write_all(b"h").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::WriteZero);
+}
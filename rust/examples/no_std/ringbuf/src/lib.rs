@@ -0,0 +1,173 @@
+//! A fixed-capacity ring buffer for targets without std or an allocator.
+//!
+//! `#![no_std]` means the crate links only `core`: language items,
+//! `Option`, `Result`, iterators, slices, `fmt`, atomics. There is no heap,
+//! so no `Vec` or `String`, and no operating system, so no files, threads,
+//! or `println!`. What's left is enough for a queue whose storage is an
+//! array inside the struct, reused in a circle:
+//!
+//! ```text
+//! capacity 5; push a b c d e, pop a b c, push f g:
+//!
+//!     slots: [ f | g | _ | d | e ]
+//!                          ^ head, the oldest; pop() gives d, e, f, g
+//! ```
+//!
+//! The capacity is a const generic, so it is part of the type and the
+//! compiler knows the buffer's size: `RingBuffer<u8, 64>` lives on the
+//! stack or in a `static`, and never allocates.
+//!
+//! The `std` feature, off by default, adds what only makes sense with an
+//! operating system: [`Full`] becomes a `std::error::Error`, and a buffer
+//! of bytes becomes a `std::io::Write`. `cfg_attr` below is what makes the
+//! crate no_std only when that feature is off.
+//!
+//! ```
+//! use ringbuf::RingBuffer;
+//!
+//! let mut buffer = RingBuffer::<u32, 2>::new();
+//! buffer.push(1).unwrap();
+//! buffer.push(2).unwrap();
+//! assert!(buffer.push(3).is_err());
+//! assert_eq!(buffer.pop(), Some(1));
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::fmt;
+
+/// A first-in, first-out queue of at most `N` values, stored inline.
+///
+/// Each slot is an `Option<T>`, which keeps this crate free of `unsafe`; a
+/// version with `MaybeUninit<T>` slots would save the tag (see
+/// `unsafe/05_tiny_vec`).
+pub struct RingBuffer<T, const N: usize> {
+    slots: [Option<T>; N],
+    /// Where the oldest value is.
+    head: usize,
+    len: usize,
+}
+
+/// The error from [`RingBuffer::push`] on a full buffer. It hands the value
+/// back, since there is nowhere else for it to go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Full<T>(pub T);
+
+impl<T, const N: usize> RingBuffer<T, N> {
+    /// An empty buffer. `const`, so it can initialize a `static`.
+    pub const fn new() -> Self {
+        RingBuffer {
+            slots: [const { None }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Adds `value` at the back, or gives it back if the buffer is full.
+    pub fn push(&mut self, value: T) -> Result<(), Full<T>> {
+        if self.is_full() {
+            return Err(Full(value));
+        }
+        let tail = (self.head + self.len) % N;
+        self.slots[tail] = Some(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Adds `value` at the back, dropping the oldest value to make room if
+    /// the buffer is full, and returns that value. What a log of the last
+    /// `N` events wants.
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        if N == 0 {
+            return Some(value);
+        }
+        let evicted = if self.is_full() { self.pop() } else { None };
+        // Can't fail now: there is room
+        let _ = self.push(value);
+        evicted
+    }
+
+    /// Removes and returns the oldest value.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = self.slots[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        value
+    }
+
+    /// The oldest value, without removing it.
+    pub fn peek(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.slots[self.head].as_ref()
+    }
+
+    /// The values from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.len).filter_map(move |i| self.slots[(self.head + i) % N].as_ref())
+    }
+
+    /// Drops every value.
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+        self.head = 0;
+    }
+}
+
+impl<T, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for RingBuffer<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> fmt::Display for Full<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the ring buffer is full")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for Full<T> {}
+
+/// Writes as many bytes as there is room for. A full buffer accepts none,
+/// which `write_all` reports as an error of kind `WriteZero`.
+#[cfg(feature = "std")]
+impl<const N: usize> std::io::Write for RingBuffer<u8, N> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        let room = N - self.len;
+        for &byte in &bytes[..bytes.len().min(room)] {
+            let _ = self.push(byte);
+        }
+        Ok(bytes.len().min(room))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,328 @@
+// Operators 1: Money with +, -, <, Display and FromStr
+// Demonstrates the std traits behind Rust's operators, formatting, and parsing, on one small type
+//
+// Concepts: std::ops, Add, Sub, Neg, Mul, AddAssign, PartialOrd, Display, FromStr
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Operators in Rust are traits. `a + b` is `Add::add(a, b)`, `a < b` is
+// `PartialOrd::lt(&a, &b)`, `format!("{a}")` calls `Display::fmt`, and
+// `"1.50".parse::<T>()` calls `FromStr::from_str`. Implement the trait
+// for your own type and the syntax works for it, with nothing special
+// about the built-in types except that std implements the traits for
+// them already.
+//
+// Java has no operator overloading at all: BigDecimal and money libraries
+// are `a.add(b)` and `a.compareTo(b) < 0`. Go doesn't either, so it is
+// `a.Add(b)` there too. What Java does have is `toString()` and
+// `Comparable`, which are Display and Ord under other names; Go's
+// `String() string` method is Display as well.
+//
+// Money is a good test of when NOT to implement a trait, too. Two amounts
+// in different currencies can't be added or compared, so PartialOrd says
+// "no order" for them, and there is no `Sum` impl: the sum of no amounts
+// at all would need a currency to be in.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+}
+
+impl Currency {
+    fn symbol(self) -> char {
+        match self {
+            Currency::Usd => '$',
+            Currency::Eur => '€',
+            Currency::Gbp => '£',
+        }
+    }
+
+    fn from_symbol(symbol: char) -> Option<Currency> {
+        [Currency::Usd, Currency::Eur, Currency::Gbp]
+            .into_iter()
+            .find(|currency| currency.symbol() == symbol)
+    }
+}
+
+/// An amount in cents, so that 0.10 + 0.20 is exactly 0.30.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Money {
+    cents: i64,
+    currency: Currency,
+}
+
+impl Money {
+    fn new(cents: i64, currency: Currency) -> Money {
+        Money { cents, currency }
+    }
+
+    /// Addition that reports a currency mismatch instead of panicking.
+    fn checked_add(self, other: Money) -> Option<Money> {
+        (self.currency == other.currency)
+            .then(|| Money::new(self.cents + other.cents, self.currency))
+    }
+}
+
+// `type Output` is what `a + b` evaluates to. It doesn't have to be Self:
+// Instant - Instant is a Duration.
+impl Add for Money {
+    type Output = Money;
+
+    /// Panics if the currencies differ, the way `+` on integers panics on
+    /// overflow in a debug build: it's a bug in the caller.
+    fn add(self, other: Money) -> Money {
+        match self.checked_add(other) {
+            Some(sum) => sum,
+            None => panic!("cannot add {:?} to {:?}", other.currency, self.currency),
+        }
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        self + -other
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money::new(-self.cents, self.currency)
+    }
+}
+
+/// `price * 3`. The two sides of an operator can be different types.
+impl Mul<i64> for Money {
+    type Output = Money;
+
+    fn mul(self, times: i64) -> Money {
+        Money::new(self.cents * times, self.currency)
+    }
+}
+
+/// `3 * price` is a different impl, on i64: nothing makes `*` symmetric.
+impl Mul<Money> for i64 {
+    type Output = Money;
+
+    fn mul(self, money: Money) -> Money {
+        money * self
+    }
+}
+
+/// `total += price`. Without it, `+=` doesn't compile, even with Add.
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+// PartialOrd, not Ord: some pairs have no order. The derived PartialEq
+// already says that $1 and €1 aren't equal, and partial_cmp has to agree
+// with it, returning Some(Equal) exactly when == is true.
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Money) -> Option<std::cmp::Ordering> {
+        if self.currency == other.currency {
+            Some(self.cents.cmp(&other.cents))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        let cents = self.cents.unsigned_abs();
+        let text = format!(
+            "{sign}{}{}.{:02}",
+            self.currency.symbol(),
+            cents / 100,
+            cents % 100
+        );
+        // `pad` applies whatever width and alignment the caller asked for,
+        // like `{:>8}`; `write!` on its own would ignore them
+        f.pad(&text)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum ParseMoneyError {
+    Empty,
+    UnknownCurrency(char),
+    InvalidAmount(String),
+}
+
+impl fmt::Display for ParseMoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMoneyError::Empty => write!(f, "no amount given"),
+            ParseMoneyError::UnknownCurrency(symbol) => {
+                write!(f, "unknown currency symbol {symbol:?}")
+            }
+            ParseMoneyError::InvalidAmount(text) => {
+                write!(f, "{text:?} is not an amount like 12.34")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMoneyError {}
+
+/// Parses what Display prints: an optional minus, a currency symbol, and
+/// an amount with no decimals or exactly two.
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(text: &str) -> Result<Money, ParseMoneyError> {
+        let text = text.trim();
+        let (negative, rest) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let mut chars = rest.chars();
+        let symbol = chars.next().ok_or(ParseMoneyError::Empty)?;
+        let currency =
+            Currency::from_symbol(symbol).ok_or(ParseMoneyError::UnknownCurrency(symbol))?;
+        let amount = chars.as_str();
+
+        let invalid = || ParseMoneyError::InvalidAmount(amount.to_string());
+        let (units, cents) = amount.split_once('.').unwrap_or((amount, "00"));
+        let all_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+        if !all_digits(units) || !all_digits(cents) || cents.len() != 2 {
+            return Err(invalid());
+        }
+        let units: i64 = units.parse().map_err(|_| invalid())?;
+        let cents: i64 = cents.parse().map_err(|_| invalid())?;
+        let total = units
+            .checked_mul(100)
+            .and_then(|c| c.checked_add(cents))
+            .ok_or_else(invalid)?;
+        Ok(Money::new(if negative { -total } else { total }, currency))
+    }
+}
+
+fn main() {
+    use Currency::*;
+
+    println!("-- arithmetic --");
+    let coffee = Money::new(350, Usd);
+    let bagel = Money::new(225, Usd);
+    let mut total = coffee + bagel;
+    println!("coffee + bagel = {total}");
+    total += coffee * 2;
+    println!("plus two more coffees = {total}");
+    println!("change from $20 = {}", Money::new(2000, Usd) - total);
+    println!("a refund: {}", -bagel);
+    println!("3 * bagel = {}", 3 * bagel);
+    println!(
+        "$0.10 + $0.20 = {}",
+        Money::new(10, Usd) + Money::new(20, Usd)
+    );
+    let euros = Money::new(100, Eur);
+    println!("checked $3.50 + €1.00: {:?}", coffee.checked_add(euros));
+    // `coffee + euros` compiles, and panics with "cannot add Eur to Usd"
+
+    println!("-- comparing --");
+    println!("coffee > bagel: {}", coffee > bagel);
+    println!("coffee == $3.50: {}", coffee == Money::new(350, Usd));
+    println!(
+        "$1.00 vs €1.00: {:?}",
+        Money::new(100, Usd).partial_cmp(&euros)
+    );
+    // No order means every comparison is false, both ways round
+    let one_dollar = Money::new(100, Usd);
+    println!(
+        "$1.00 < €1.00: {}, $1.00 > €1.00: {}, $1.00 == €1.00: {}",
+        one_dollar < euros,
+        one_dollar > euros,
+        one_dollar == euros
+    );
+    let mut prices = [coffee, bagel, Money::new(99, Usd), Money::new(1250, Usd)];
+    // sort() needs Ord; sort_by with partial_cmp works, if every pair has an order
+    prices.sort_by(|a, b| a.partial_cmp(b).expect("all in dollars"));
+    let sorted: Vec<String> = prices.iter().map(Money::to_string).collect();
+    println!("sorted: {}", sorted.join(" "));
+    // No Sum impl, but fold can start from a zero in the right currency
+    let sum = prices
+        .iter()
+        .fold(Money::new(0, Usd), |sum, &price| sum + price);
+    println!("sum: {sum}");
+
+    println!("-- display --");
+    for money in [
+        Money::new(5, Gbp),
+        Money::new(-1999, Eur),
+        Money::new(123_456, Usd),
+    ] {
+        println!("[{money:>10}] [{money:<10}] {money:?}");
+    }
+
+    println!("-- parsing --");
+    for text in [
+        "$12.34",
+        "-€0.50",
+        " £7 ",
+        "$12.3",
+        "¥100",
+        "",
+        "$1,000.00",
+        "$.50",
+    ] {
+        match text.parse::<Money>() {
+            Ok(money) => println!("{text:?} -> {money}"),
+            Err(error) => println!("{text:?} -> error: {error}"),
+        }
+    }
+    let round_trip: Money = Money::new(-1999, Eur).to_string().parse().unwrap();
+    println!("round trip: {round_trip}");
+}
+
+/*
+ * Key Concepts:
+ * - Each operator is a trait in std::ops: Add for +, Neg for unary -, AddAssign for +=
+ * - `type Output` is the result type, and the right-hand side can be another type
+ * - PartialOrd returns Option<Ordering>, so a pair can have no order at all
+ * - Display is for people; use f.pad so width and alignment still work
+ * - FromStr powers str::parse, with an error type of your own in `type Err`
+ * - Java and Go have no operator overloading; toString()/String() are Display
+ */
+
+// EXPECTED:
+// -- arithmetic --
+// coffee + bagel = $5.75
+// plus two more coffees = $12.75
+// change from $20 = $7.25
+// a refund: -$2.25
+// 3 * bagel = $6.75
+// $0.10 + $0.20 = $0.30
+// checked $3.50 + €1.00: None
+// -- comparing --
+// coffee > bagel: true
+// coffee == $3.50: true
+// $1.00 vs €1.00: None
+// $1.00 < €1.00: false, $1.00 > €1.00: false, $1.00 == €1.00: false
+// sorted: $0.99 $2.25 $3.50 $12.50
+// sum: $19.24
+// -- display --
+// [     £0.05] [£0.05     ] Money { cents: 5, currency: Gbp }
+// [   -€19.99] [-€19.99   ] Money { cents: -1999, currency: Eur }
+// [  $1234.56] [$1234.56  ] Money { cents: 123456, currency: Usd }
+// -- parsing --
+// "$12.34" -> $12.34
+// "-€0.50" -> -€0.50
+// " £7 " -> £7.00
+// "$12.3" -> error: "12.3" is not an amount like 12.34
+// "¥100" -> error: unknown currency symbol '¥'
+// "" -> error: no amount given
+// "$1,000.00" -> error: "1,000.00" is not an amount like 12.34
+// "$.50" -> error: ".50" is not an amount like 12.34
+// round trip: -€19.99
@@ -0,0 +1,219 @@
+// Panics 1: panic! or Result?
+// Demonstrates which failures should return an error and which should panic
+//
+// Concepts: panic!, Result, unwrap and expect, panic payloads, panic hooks
+// Difficulty: intermediate
+// Minutes: 15
+//
+// A Result is for failures the caller can expect and do something about:
+// a file that isn't there, input that doesn't parse, a server that doesn't
+// answer. A panic is for bugs: a broken promise between two pieces of
+// code, an index that was supposed to be in range, a state that "can't
+// happen". Panicking unwinds the thread's stack, dropping everything on
+// it, and ends the thread; when that thread is main, the program exits
+// with status 101.
+//
+// The question to ask is "whose mistake is it?". If it is the input's,
+// return an error and let the caller decide. If it is the program's,
+// there is nothing sensible to do at runtime: panic, loudly, and fix it.
+
+use std::any::Any;
+use std::fmt;
+use std::panic;
+
+/// Why a piece of user input is not a percentage.
+#[derive(Debug, PartialEq)]
+enum PercentError {
+    NotANumber(String),
+    OutOfRange(u32),
+}
+
+impl fmt::Display for PercentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PercentError::NotANumber(text) => write!(f, "{:?} is not a number", text),
+            PercentError::OutOfRange(n) => write!(f, "{} is more than 100", n),
+        }
+    }
+}
+
+/// Reads a percentage typed by a user. Typos are normal, so they are an
+/// error the caller can show, not a panic.
+fn parse_percent(text: &str) -> Result<u8, PercentError> {
+    let text = text.trim().trim_end_matches('%');
+    let n: u32 = text
+        .parse()
+        .map_err(|_| PercentError::NotANumber(text.to_string()))?;
+    if n > 100 {
+        return Err(PercentError::OutOfRange(n));
+    }
+    Ok(n as u8)
+}
+
+/// Counts percentages in ten buckets: 0-9, 10-19, ..., 90-100.
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [u32; 10],
+}
+
+impl Histogram {
+    /// # Panics
+    ///
+    /// If `percent` is more than 100. Callers get their percentages from
+    /// `parse_percent`, so a bigger one is a bug in the caller.
+    fn add(&mut self, percent: u8) {
+        assert!(
+            percent <= 100,
+            "a percentage is at most 100, got {}",
+            percent
+        );
+        let bucket = (percent as usize / 10).min(9);
+        self.buckets[bucket] += 1;
+    }
+}
+
+/// The message of a panic, from its payload: `panic!` with a format
+/// string makes a String, with a plain literal a `&'static str`, and
+/// `panic_any` anything at all.
+fn message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else {
+        "(not a string)"
+    }
+}
+
+/// Runs `f`, printing the panic message if it panics.
+fn attempt(what: &str, f: impl FnOnce() + panic::UnwindSafe) {
+    match panic::catch_unwind(f) {
+        Ok(()) => println!("{}: no panic", what),
+        Err(payload) => println!("{}: panicked with {:?}", what, message(&*payload)),
+    }
+}
+
+fn main() {
+    // Keep the default panic messages out of the output
+    panic::set_hook(Box::new(|_| {}));
+
+    // ===== Bad input is an error =====
+    for input in ["42", " 7% ", "142", "forty"] {
+        match parse_percent(input) {
+            Ok(percent) => println!("{:?} -> {}%", input, percent),
+            Err(error) => println!("{:?} -> error: {}", input, error),
+        }
+    }
+
+    // ===== A broken promise is a panic =====
+    let mut histogram = Histogram::default();
+    for input in ["5", "15", "99", "100", "oops"] {
+        // The caller handles the Result, so add() only sees valid input
+        if let Ok(percent) = parse_percent(input) {
+            histogram.add(percent);
+        }
+    }
+    println!("buckets: {:?}", histogram.buckets);
+    attempt("add(150)", || Histogram::default().add(150));
+
+    // Standard library functions draw the same line: `get` returns an
+    // Option for an index that might be out of range, `[]` panics
+    let counts = histogram.buckets.to_vec();
+    println!("get(12): {:?}", counts.get(12));
+    attempt("counts[12]", move || {
+        println!("never printed: {}", counts[12])
+    });
+
+    // ===== unwrap and expect =====
+    // Both turn an Err (or None) into a panic: use them when an error
+    // would be a bug. expect says which promise was broken
+    let default: u8 = parse_percent("50").expect("the default is a valid percentage");
+    println!("default: {}%", default);
+    attempt("unwrap", || {
+        parse_percent("abc").unwrap();
+    });
+    attempt("expect", || {
+        parse_percent("200").expect("the slider only goes up to 100");
+    });
+
+    // ===== Payloads =====
+    // A panic carries a value, usually its message, which catch_unwind
+    // hands back as a Box<dyn Any + Send>
+    attempt("a literal", || panic!("a literal"));
+    attempt("a format string", || panic!("{} + {} = {}", 2, 2, 5));
+    attempt("panic_any(42)", || panic::panic_any(42));
+
+    // ===== Hooks =====
+    // The hook runs first, while the panicking thread's stack is still
+    // intact, and prints the familiar "thread 'main' panicked at" line
+    // unless it is replaced. Logging crates install one that logs instead.
+    panic::set_hook(Box::new(|info| {
+        let thread = std::thread::current();
+        let name = thread.name().unwrap_or("unnamed");
+        println!(
+            "  hook: thread '{}' panicked: {}",
+            name,
+            message(info.payload())
+        );
+    }));
+    attempt("with a hook", || panic!("disk full"));
+    let _ = panic::take_hook();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad_input_is_an_error() {
+        assert_eq!(parse_percent("12%"), Ok(12));
+        assert_eq!(parse_percent("101"), Err(PercentError::OutOfRange(101)));
+        assert_eq!(
+            parse_percent("ten"),
+            Err(PercentError::NotANumber("ten".to_string()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "a percentage is at most 100")]
+    fn adding_a_bad_percentage_is_a_bug() {
+        Histogram::default().add(101);
+    }
+
+    #[test]
+    fn messages_come_from_either_kind_of_payload() {
+        let literal = panic::catch_unwind(|| panic!("plain")).unwrap_err();
+        let formatted = panic::catch_unwind(|| panic!("{}", 1)).unwrap_err();
+        let other = panic::catch_unwind(|| panic::panic_any(1.5)).unwrap_err();
+        assert_eq!(message(&*literal), "plain");
+        assert_eq!(message(&*formatted), "1");
+        assert_eq!(message(&*other), "(not a string)");
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Return a Result when the failure is the input's fault; panic when it is a bug
+ * - Document when a function panics under a `# Panics` heading
+ * - unwrap and expect turn errors into panics; expect says which promise broke
+ * - The panic payload is usually a String or a &'static str
+ * - The panic hook runs first; set_hook replaces the default message
+ */
+
+// EXPECTED:
+// "42" -> 42%
+// " 7% " -> 7%
+// "142" -> error: 142 is more than 100
+// "forty" -> error: "forty" is not a number
+// buckets: [1, 1, 0, 0, 0, 0, 0, 0, 0, 2]
+// add(150): panicked with "a percentage is at most 100, got 150"
+// get(12): None
+// counts[12]: panicked with "index out of bounds: the len is 10 but the index is 12"
+// default: 50%
+// unwrap: panicked with "called `Result::unwrap()` on an `Err` value: NotANumber(\"abc\")"
+// expect: panicked with "the slider only goes up to 100: OutOfRange(200)"
+// a literal: panicked with "a literal"
+// a format string: panicked with "2 + 2 = 5"
+// panic_any(42): panicked with "(not a string)"
+//   hook: thread 'main' panicked: disk full
+// with a hook: panicked with "disk full"
@@ -0,0 +1,266 @@
+// Panics 2: Catching Panics at Boundaries
+// Demonstrates stopping a panic where one part of a program meets another
+//
+// Concepts: catch_unwind, JoinHandle::join, UnwindSafe, resume_unwind, extern "C"
+// Difficulty: advanced
+// Minutes: 20
+//
+// A panic unwinds until it reaches the top of its thread. Usually that is
+// what we want: the thread is in a state nobody planned for, and ending
+// it is the safe thing to do. But some code is a boundary, where one
+// failing piece shouldn't take the rest down with it:
+// - a thread: its panic ends only that thread, and comes back to whoever
+//   joins it as an Err
+// - a job runner or a server, which runs code it didn't write and should
+//   report that one job failed and carry on
+// - a function called from C, where unwinding into C's stack frames is
+//   undefined behavior (Rust aborts instead)
+// std::panic::catch_unwind is the tool for the last two. It is not a
+// try/catch for error handling: errors are for Result, and a panic with
+// `panic = "abort"` (see 03_abort_or_unwind) can't be caught at all.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+fn message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else {
+        "(not a string)"
+    }
+}
+
+// ===== A job runner =====
+
+/// How one job ended.
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Done(u32),
+    Panicked(String),
+}
+
+/// Runs every job, even after one of them panics.
+///
+/// `catch_unwind` wants a closure that is UnwindSafe: one that can't leave
+/// something it borrows half-changed for the code after the catch to
+/// see. A `&mut` or a `&Cell` isn't, because the job might panic halfway
+/// through an update. The jobs here own everything they touch, so
+/// wrapping them in AssertUnwindSafe ("I checked") is true.
+fn run_all(jobs: Vec<Box<dyn FnOnce() -> u32>>) -> Vec<Outcome> {
+    jobs.into_iter()
+        .map(|job| match panic::catch_unwind(AssertUnwindSafe(job)) {
+            Ok(value) => Outcome::Done(value),
+            Err(payload) => Outcome::Panicked(message(&*payload).to_string()),
+        })
+        .collect()
+}
+
+// ===== A function for C =====
+
+/// Divides for a C caller, who gets a status code instead of a panic:
+/// 0 with the result in `out`, or -1 if anything went wrong.
+///
+/// Since Rust 1.81 a panic that tries to unwind out of an `extern "C"`
+/// function aborts the process; catch_unwind turns it into an error code
+/// that C understands. examples/ffi/04_callbacks_and_panics.rs does the
+/// same for callbacks that C calls.
+extern "C" fn checked_div(a: i32, b: i32, out: *mut i32) -> i32 {
+    match panic::catch_unwind(|| a / b) {
+        Ok(value) => {
+            // SAFETY: callers pass a pointer to a live, writable i32
+            unsafe { *out = value };
+            0
+        }
+        Err(_) => -1,
+    }
+}
+
+// ===== Logging on the way through =====
+
+/// Runs `f`, counting it in `active` while it runs. If it panics, notes
+/// that and lets the panic carry on with resume_unwind, so the caller
+/// still sees it.
+fn counted<T>(active: &Cell<u32>, f: impl FnOnce() -> T) -> T {
+    active.set(active.get() + 1);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    active.set(active.get() - 1);
+    match result {
+        Ok(value) => value,
+        Err(payload) => {
+            println!("  counted: a job panicked, {} still active", active.get());
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+fn main() {
+    // Keep the default panic messages out of the output
+    panic::set_hook(Box::new(|_| {}));
+
+    // ===== Threads =====
+    // A panicking thread ends alone; join() returns its payload as an Err
+    let worker = thread::spawn(|| -> u32 {
+        let readings: Vec<u32> = Vec::new();
+        readings.iter().sum::<u32>() / readings.len() as u32
+    });
+    match worker.join() {
+        Ok(average) => println!("average: {}", average),
+        Err(payload) => println!("the worker panicked: {:?}", message(&*payload)),
+    }
+    println!("main carries on");
+
+    // ===== Jobs =====
+    let words: Vec<&str> = "one two".split(' ').collect();
+    let jobs: Vec<Box<dyn FnOnce() -> u32>> = vec![
+        Box::new(|| 2 + 2),
+        Box::new(move || words[5].len() as u32),
+        Box::new(|| "17".parse().unwrap()),
+        Box::new(|| "seventeen".parse().unwrap()),
+        Box::new(|| 40 + 2),
+    ];
+    for (i, outcome) in run_all(jobs).iter().enumerate() {
+        println!("job {}: {:?}", i, outcome);
+    }
+
+    // ===== FFI =====
+    // A function pointer, the way C would hold it
+    let divide: extern "C" fn(i32, i32, *mut i32) -> i32 = checked_div;
+    for (a, b) in [(84, 2), (1, 0), (i32::MIN, -1)] {
+        let mut out = 0;
+        match divide(a, b, &mut out) {
+            0 => println!("{} / {} = {}", a, b, out),
+            status => println!("{} / {} failed with status {}", a, b, status),
+        }
+    }
+
+    // ===== resume_unwind =====
+    let active = Cell::new(0);
+    let total = counted(&active, || 1 + 1);
+    println!("counted job returned {}, {} active", total, active.get());
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        counted(&active, || panic!("out of disk"));
+    }));
+    let payload = result.unwrap_err();
+    println!("the caller still saw {:?}", message(&*payload));
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let mut total = 0;
+        let _ = panic::catch_unwind(|| total += 1);
+    }
+    // error[E0277]: the type `&mut i32` may not be safely transferred across an unwind boundary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn one_panicking_job_does_not_stop_the_others() {
+        let jobs: Vec<Box<dyn FnOnce() -> u32>> = vec![
+            Box::new(|| 1),
+            Box::new(|| panic!("job two")),
+            Box::new(|| 3),
+        ];
+        let outcomes = run_all(jobs);
+        assert_eq!(
+            outcomes,
+            [
+                Outcome::Done(1),
+                Outcome::Panicked("job two".to_string()),
+                Outcome::Done(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn a_job_releases_what_it_owns_when_it_panics() {
+        // Each job holds a clone of the Arc; unwinding drops it
+        let shared = Arc::new(AtomicU32::new(0));
+        let jobs: Vec<Box<dyn FnOnce() -> u32>> = (0..4u32)
+            .map(|i| {
+                let shared = Arc::clone(&shared);
+                Box::new(move || {
+                    shared.fetch_add(1, Ordering::SeqCst);
+                    if i % 2 == 1 {
+                        panic!("job {}", i);
+                    }
+                    i
+                }) as Box<dyn FnOnce() -> u32>
+            })
+            .collect();
+        run_all(jobs);
+        assert_eq!(shared.load(Ordering::SeqCst), 4);
+        assert_eq!(Arc::strong_count(&shared), 1, "every clone was dropped");
+    }
+
+    #[test]
+    fn a_thread_that_panics_unlocks_its_mutex() {
+        let data = Arc::new(Mutex::new(vec![1, 2]));
+        let handle = {
+            let data = Arc::clone(&data);
+            thread::spawn(move || {
+                let mut guard = data.lock().unwrap();
+                guard.push(3);
+                panic!("crash while holding the lock");
+            })
+        };
+        assert!(handle.join().is_err());
+        // The guard was dropped as the thread unwound: the lock is free,
+        // and marked poisoned so we know the data may be half-updated
+        assert!(data.is_poisoned());
+        let guard = data.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        assert_eq!(*guard, [1, 2, 3]);
+    }
+
+    #[test]
+    fn the_c_function_turns_a_panic_into_a_status() {
+        let mut out = 7;
+        assert_eq!(checked_div(9, 3, &mut out), 0);
+        assert_eq!(out, 3);
+        assert_eq!(checked_div(9, 0, &mut out), -1);
+        assert_eq!(out, 3, "out is left alone on failure");
+    }
+
+    #[test]
+    fn counted_lets_the_panic_through_and_still_counts_down() {
+        let active = Cell::new(0);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            counted(&active, || -> u32 { panic!("boom") })
+        }));
+        assert_eq!(message(&*result.unwrap_err()), "boom");
+        assert_eq!(active.get(), 0);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A thread's panic comes back from join() as Err(payload)
+ * - catch_unwind stops a panic at a boundary: a job runner, a server, a C caller
+ * - Unwinding out of an extern "C" fn aborts; catch it and return an error code
+ * - UnwindSafe rules out closures that could leave borrowed data half-changed;
+ *   AssertUnwindSafe overrides it when you've checked
+ * - resume_unwind re-raises a caught panic after cleaning up or logging
+ */
+
+// EXPECTED:
+// the worker panicked: "attempt to divide by zero"
+// main carries on
+// job 0: Done(4)
+// job 1: Panicked("index out of bounds: the len is 2 but the index is 5")
+// job 2: Done(17)
+// job 3: Panicked("called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }")
+// job 4: Done(42)
+// 84 / 2 = 42
+// 1 / 0 failed with status -1
+// -2147483648 / -1 failed with status -1
+// counted job returned 2, 0 active
+//   counted: a job panicked, 0 still active
+// the caller still saw "out of disk"
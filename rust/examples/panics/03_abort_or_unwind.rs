@@ -0,0 +1,192 @@
+// Panics 3: Unwinding or Aborting
+// Demonstrates what `panic = "abort"` changes, and when a panic aborts anyway
+//
+// Concepts: panic strategies, panic = "abort", std::process::abort, panics in Drop
+// Difficulty: advanced
+// Minutes: 15
+//
+// By default a panic unwinds: it walks back up the stack, running every
+// destructor on the way, until something catches it or the thread ends.
+// A profile in Cargo.toml can pick the other strategy:
+//
+//     [profile.release]
+//     panic = "abort"
+//
+// Then a panic prints its message and ends the whole process on the spot.
+// What that trades:
+// - binaries get smaller, and a little faster, without the tables and
+//   cleanup code that unwinding needs
+// - no destructors run: buffered writes aren't flushed, temporary files
+//   and lock files stay behind
+// - catch_unwind catches nothing, so a panicking thread or job takes the
+//   whole program down
+// - tests still unwind (the test harness catches each test's panic),
+//   whatever the profile says
+// Embedded programs, and servers with a supervisor that restarts them,
+// often choose abort. A library doesn't get to choose: the strategy
+// belongs to the final binary, so library code must work with both.
+//
+// Even when panics unwind, a few things abort: std::process::abort, a
+// panic in a destructor that is running because of another panic, and a
+// panic that tries to unwind out of an `extern "C"` function. This
+// example runs copies of itself to watch each of them end from outside.
+
+use std::env;
+use std::process::{self, Command, ExitStatus};
+use std::thread;
+
+/// Says when it is dropped, like a temporary file cleaning up would.
+struct Noisy(&'static str);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        println!("    drop: {}", self.0);
+    }
+}
+
+/// A destructor that fails: the worst time is while already unwinding.
+struct PanicsInDrop;
+
+impl Drop for PanicsInDrop {
+    fn drop(&mut self) {
+        println!("    drop: panicking: {}", thread::panicking());
+        panic!("the destructor failed too");
+    }
+}
+
+extern "C" fn called_from_c() {
+    panic!("a panic inside an extern \"C\" fn");
+}
+
+/// What the copy of this program does, by its first argument.
+fn child(mode: &str) {
+    let _file = Noisy("temporary file removed");
+    println!("    {}", mode);
+    match mode {
+        "unwind" => panic!("unwinding"),
+        "abort" => process::abort(),
+        "double" => {
+            let _guard = PanicsInDrop;
+            panic!("the first panic");
+        }
+        "extern" => called_from_c(),
+        _ => println!("    nothing to do"),
+    }
+}
+
+/// How a process ended, as a shell would put it.
+fn describe(status: ExitStatus) -> String {
+    if let Some(code) = status.code() {
+        return format!("exit code {}", code);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            let name = if signal == 6 { " (SIGABRT)" } else { "" };
+            return format!("killed by signal {}{}", signal, name);
+        }
+    }
+    status.to_string()
+}
+
+/// Runs this example again in `mode`, and shows how it ended and what it
+/// printed. Its panic messages go to stderr, which is left out.
+fn run_copy(mode: &str, title: &str) {
+    let me = env::current_exe().expect("the example knows where it is");
+    let output = Command::new(me)
+        .arg(mode)
+        .output()
+        .expect("the example can run itself");
+    println!("{}: {}", title, describe(output.status));
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+}
+
+fn main() {
+    if let Some(mode) = env::args().nth(1) {
+        child(&mode);
+        return;
+    }
+
+    let strategy = if cfg!(panic = "unwind") {
+        "unwind"
+    } else {
+        "abort"
+    };
+    println!("panics in this build {}\n", strategy);
+
+    // Unwinding runs the destructor, then exits with 101
+    run_copy("unwind", "a panic");
+    // abort() ends the process at once: nothing is dropped
+    run_copy("abort", "process::abort()");
+    // A destructor that panics while unwinding can't be unwound from, so
+    // the process aborts, and the file is never removed
+    run_copy("double", "a panic during a panic");
+    // The same goes for a panic that reaches an extern "C" fn's caller
+    run_copy("extern", "a panic out of extern \"C\"");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+
+    /// Sets its flag when dropped, noting whether a panic was unwinding.
+    struct Flag<'a>(&'a Cell<Option<bool>>);
+
+    impl Drop for Flag<'_> {
+        fn drop(&mut self) {
+            self.0.set(Some(thread::panicking()));
+        }
+    }
+
+    #[test]
+    fn tests_unwind_and_run_destructors() {
+        let dropped = Cell::new(None);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _flag = Flag(&dropped);
+            panic!("boom");
+        }));
+        assert!(result.is_err());
+        assert_eq!(dropped.get(), Some(true), "dropped during unwinding");
+
+        let flag = Flag(&dropped);
+        drop(flag);
+        assert_eq!(dropped.get(), Some(false), "dropped normally");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn describes_exit_codes_and_signals() {
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(describe(ExitStatus::from_raw(101 << 8)), "exit code 101");
+        assert_eq!(
+            describe(ExitStatus::from_raw(6)),
+            "killed by signal 6 (SIGABRT)"
+        );
+    }
+}
+
+/*
+ * Key Concepts:
+ * - panic = "unwind" (the default) runs destructors; panic = "abort" doesn't
+ * - abort makes binaries smaller but catch_unwind useless and cleanup skipped
+ * - The final binary picks the strategy, so libraries must cope with both
+ * - A panic in Drop during unwinding, or out of extern "C", always aborts
+ * - A panicking main exits with code 101; an abort dies of SIGABRT
+ */
+
+// EXPECTED:
+// panics in this build unwind
+//
+// a panic: exit code 101
+//     unwind
+//     drop: temporary file removed
+// process::abort(): killed by signal 6 (SIGABRT)
+//     abort
+// a panic during a panic: killed by signal 6 (SIGABRT)
+//     double
+//     drop: panicking: true
+// a panic out of extern "C": killed by signal 6 (SIGABRT)
+//     extern
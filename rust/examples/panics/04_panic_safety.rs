@@ -0,0 +1,304 @@
+// Panics 4: Panic-Safe Code
+// Demonstrates keeping a data structure valid when code it calls panics
+//
+// Concepts: panic safety, invariants, guards, thread::panicking, strong and basic guarantees
+// Difficulty: advanced
+// Minutes: 20
+//
+// Code that calls code it doesn't control, such as a closure, a trait
+// method like Ord::cmp, or a callback, has to expect that the call may
+// panic. The panic unwinds through our function in the middle of its
+// work, and if a catch_unwind further up lets the program carry on, our
+// data structure is still there, however far the work had got. Being
+// panic safe means it is still valid then. There are two levels:
+// - basic: nothing leaks and the invariants hold, though some of the
+//   work may have happened
+// - strong: the operation happened completely or not at all
+// As with cleanup on errors (see examples/drop/03_guards.rs), a guard
+// whose Drop puts things right is how to get either.
+
+use std::cell::RefCell;
+use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+// ===== Giving resources back =====
+
+/// A pool of connections that are lent out one at a time.
+struct Pool<T> {
+    free: RefCell<Vec<T>>,
+}
+
+/// A connection on loan. It goes back to the pool when the lease is
+/// dropped, which happens while unwinding too.
+struct Lease<'a, T> {
+    pool: &'a Pool<T>,
+    item: Option<T>,
+}
+
+impl<T> Pool<T> {
+    fn new(items: Vec<T>) -> Self {
+        Pool {
+            free: RefCell::new(items),
+        }
+    }
+
+    fn lease(&self) -> Option<Lease<'_, T>> {
+        let item = self.free.borrow_mut().pop()?;
+        Some(Lease {
+            pool: self,
+            item: Some(item),
+        })
+    }
+
+    fn available(&self) -> usize {
+        self.free.borrow().len()
+    }
+}
+
+impl<T> Deref for Lease<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.item.as_ref().expect("only taken in drop")
+    }
+}
+
+impl<T> DerefMut for Lease<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.item.as_mut().expect("only taken in drop")
+    }
+}
+
+impl<T> Drop for Lease<'_, T> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            println!("  lease: returned while unwinding");
+        }
+        if let Some(item) = self.item.take() {
+            self.pool.free.borrow_mut().push(item);
+        }
+    }
+}
+
+// ===== Keeping an invariant =====
+
+/// Scores with a running total. The invariant: `total` is always the sum
+/// of `values`.
+#[derive(Debug, Clone)]
+struct Scores {
+    values: Vec<u32>,
+    total: u32,
+}
+
+impl Scores {
+    fn new(values: Vec<u32>) -> Self {
+        let total = values.iter().sum();
+        Scores { values, total }
+    }
+
+    fn is_consistent(&self) -> bool {
+        self.total == self.values.iter().sum::<u32>()
+    }
+
+    /// Not panic safe: if `f` panics, the value it was working on has
+    /// already been taken out of the total and is never added back.
+    fn update_all_naively(&mut self, mut f: impl FnMut(&mut u32)) {
+        for value in &mut self.values {
+            self.total -= *value;
+            f(value);
+            self.total += *value;
+        }
+    }
+
+    /// Basic guarantee: a guard recounts the total on the way out, however
+    /// the loop ends. The values updated before a panic stay updated.
+    fn update_all(&mut self, mut f: impl FnMut(&mut u32)) {
+        struct Recount<'a>(&'a mut Scores);
+
+        impl Drop for Recount<'_> {
+            fn drop(&mut self) {
+                self.0.total = self.0.values.iter().sum();
+            }
+        }
+
+        let guard = Recount(self);
+        for value in &mut guard.0.values {
+            f(value);
+        }
+    }
+
+    /// Strong guarantee: update a copy, and only swap it in once every
+    /// call has returned. A panic leaves `self` exactly as it was.
+    fn update_all_or_nothing(&mut self, f: impl FnMut(&mut u32)) {
+        let mut copy = self.clone();
+        copy.update_all(f);
+        *self = copy;
+    }
+}
+
+/// Doubles a score, except that it panics rather than touch a 13.
+fn superstitious(value: &mut u32) {
+    if *value == 13 {
+        panic!("refusing to double 13");
+    }
+    *value *= 2;
+}
+
+fn main() {
+    // Keep the default panic messages out of the output
+    panic::set_hook(Box::new(|_| {}));
+
+    let pool = Pool::new(vec!["db-1".to_string(), "db-2".to_string()]);
+    println!("available: {}", pool.available());
+    {
+        let mut first = pool.lease().unwrap();
+        first.push_str(" (busy)");
+        let _second = pool.lease().unwrap();
+        println!(
+            "both out, available: {}, none left: {}",
+            pool.available(),
+            pool.lease().is_none()
+        );
+    }
+    println!("back after the scope: {}", pool.available());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let connection = pool.lease().unwrap();
+        panic!("query failed on {}", *connection);
+    }));
+    println!(
+        "panicked: {}, available: {}",
+        result.is_err(),
+        pool.available()
+    );
+
+    // Each version hits the same panic on the third value
+    let start = vec![1, 2, 13, 4];
+
+    let mut scores = Scores::new(start.clone());
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        scores.update_all_naively(superstitious)
+    }));
+    println!(
+        "naively:        {:?}, consistent: {}",
+        scores,
+        scores.is_consistent()
+    );
+
+    let mut scores = Scores::new(start.clone());
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| scores.update_all(superstitious)));
+    println!(
+        "basic:          {:?}, consistent: {}",
+        scores,
+        scores.is_consistent()
+    );
+
+    let mut scores = Scores::new(start);
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        scores.update_all_or_nothing(superstitious)
+    }));
+    println!(
+        "all or nothing: {:?}, consistent: {}",
+        scores,
+        scores.is_consistent()
+    );
+
+    let mut scores = Scores::new(vec![1, 2, 3]);
+    scores.update_all_or_nothing(superstitious);
+    println!("no panic:       {:?}", scores);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_lease_goes_back_when_its_borrower_panics() {
+        let pool = Pool::new(vec![1, 2, 3]);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _a = pool.lease().unwrap();
+            let _b = pool.lease().unwrap();
+            assert_eq!(pool.available(), 1);
+            panic!("the borrower failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(pool.available(), 3);
+    }
+
+    #[test]
+    fn a_lease_goes_back_with_its_changes() {
+        let pool = Pool::new(vec![String::from("conn")]);
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut lease = pool.lease().unwrap();
+            lease.push_str(" (used)");
+            panic!("after using it");
+        }));
+        assert_eq!(*pool.lease().unwrap(), "conn (used)");
+    }
+
+    #[test]
+    fn the_naive_update_breaks_the_total() {
+        let mut scores = Scores::new(vec![1, 13, 1]);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            scores.update_all_naively(superstitious)
+        }));
+        assert!(result.is_err());
+        assert!(!scores.is_consistent(), "this is the bug the guard fixes");
+    }
+
+    #[test]
+    fn the_guarded_update_keeps_the_total_through_a_panic() {
+        let mut scores = Scores::new(vec![1, 2, 13, 4]);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| scores.update_all(superstitious)));
+        assert!(result.is_err());
+        assert_eq!(
+            scores.values,
+            [2, 4, 13, 4],
+            "the work before the panic stays"
+        );
+        assert_eq!(scores.total, 23);
+    }
+
+    #[test]
+    fn the_all_or_nothing_update_changes_nothing_on_a_panic() {
+        let mut scores = Scores::new(vec![1, 2, 13, 4]);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            scores.update_all_or_nothing(superstitious)
+        }));
+        assert!(result.is_err());
+        assert_eq!(scores.values, [1, 2, 13, 4]);
+        assert_eq!(scores.total, 20);
+    }
+
+    #[test]
+    fn updates_without_a_panic_all_happen() {
+        let mut scores = Scores::new(vec![1, 2, 3]);
+        scores.update_all(superstitious);
+        assert_eq!(scores.values, [2, 4, 6]);
+        assert_eq!(scores.total, 12);
+        scores.update_all_or_nothing(|value| *value += 1);
+        assert_eq!(scores.total, 15);
+        assert!(scores.is_consistent());
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Any call into code you don't control may panic halfway through your work
+ * - Basic guarantee: invariants hold and nothing leaks; strong: all or nothing
+ * - A guard's Drop restores the invariant or returns the resource on every exit
+ * - Work on a copy and swap it in at the end for the strong guarantee
+ * - thread::panicking() tells a destructor that it runs during unwinding
+ */
+
+// EXPECTED:
+// available: 2
+// both out, available: 0, none left: true
+// back after the scope: 2
+//   lease: returned while unwinding
+// panicked: true, available: 2
+// naively:        Scores { values: [2, 4, 13, 4], total: 10 }, consistent: false
+// basic:          Scores { values: [2, 4, 13, 4], total: 23 }, consistent: true
+// all or nothing: Scores { values: [1, 2, 13, 4], total: 20 }, consistent: true
+// no panic:       Scores { values: [2, 4, 6], total: 12 }
@@ -0,0 +1,173 @@
+// Patterns 1: Builder
+// Demonstrates a builder with chained by-value setters and a validating build()
+//
+// Concepts: builder pattern, by-value setters, validation
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Rust has no default arguments and no overloading, so a constructor with
+// many optional settings turns into a long list of parameters that are
+// easy to pass in the wrong order. A builder collects the settings one
+// named method at a time, fills in defaults for the rest, and checks the
+// whole configuration once, in build(), which returns a Result.
+//
+// Java uses the same pattern, usually as a static nested Builder class
+// with setters that return `this`. Go more often uses a config struct
+// whose zero values are the defaults, or "functional options" passed to
+// NewServer. Rust's version takes and returns the builder by value, so a
+// chain of calls needs no `mut` variable and no `this`.
+
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Server {
+    host: String,
+    port: u16,
+    workers: usize,
+    timeout: Duration,
+    tls: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum BuildError {
+    MissingHost,
+    NoWorkers,
+    PrivilegedPort(u16),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingHost => write!(f, "a host is required"),
+            BuildError::NoWorkers => write!(f, "at least one worker is required"),
+            BuildError::PrivilegedPort(port) => write!(f, "port {} needs root", port),
+        }
+    }
+}
+
+/// Everything is optional until build(); only the host has no default.
+#[derive(Debug, Default)]
+struct ServerBuilder {
+    host: Option<String>,
+    port: Option<u16>,
+    workers: Option<usize>,
+    timeout: Option<Duration>,
+    tls: bool,
+}
+
+impl Server {
+    fn builder() -> ServerBuilder {
+        ServerBuilder::default()
+    }
+}
+
+impl ServerBuilder {
+    fn host(mut self, host: &str) -> Self {
+        self.host = Some(host.to_string());
+        self
+    }
+
+    fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    fn workers(mut self, workers: usize) -> Self {
+        self.workers = Some(workers);
+        self
+    }
+
+    fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn tls(mut self) -> Self {
+        self.tls = true;
+        self
+    }
+
+    /// Applies the defaults and checks the combination as a whole.
+    fn build(self) -> Result<Server, BuildError> {
+        let host = self.host.ok_or(BuildError::MissingHost)?;
+        let port = self.port.unwrap_or(if self.tls { 8443 } else { 8080 });
+        if port < 1024 {
+            return Err(BuildError::PrivilegedPort(port));
+        }
+        let workers = self.workers.unwrap_or(4);
+        if workers == 0 {
+            return Err(BuildError::NoWorkers);
+        }
+        Ok(Server {
+            host,
+            port,
+            workers,
+            timeout: self.timeout.unwrap_or(Duration::from_secs(30)),
+            tls: self.tls,
+        })
+    }
+}
+
+fn main() {
+    // Only what differs from the defaults is spelled out
+    let server = Server::builder().host("localhost").build().unwrap();
+    println!("{:?}", server);
+
+    let secure = Server::builder()
+        .host("example.com")
+        .tls()
+        .workers(16)
+        .timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    println!(
+        "{}:{} tls={} workers={} timeout={:?}",
+        secure.host, secure.port, secure.tls, secure.workers, secure.timeout
+    );
+
+    // Mistakes come back as errors instead of half-built servers
+    for attempt in [
+        Server::builder().port(9000),
+        Server::builder().host("localhost").port(80),
+        Server::builder().host("localhost").workers(0),
+    ] {
+        match attempt.build() {
+            Ok(server) => println!("built {:?}", server),
+            Err(error) => println!("error: {}", error),
+        }
+    }
+
+    // Settings that depend on a condition: rebind the builder
+    let debug = true;
+    let mut builder = Server::builder().host("127.0.0.1");
+    if debug {
+        builder = builder.workers(1);
+    }
+    println!("debug workers: {}", builder.build().unwrap().workers);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let builder = Server::builder().host("a");
+        let first = builder.build();
+        let second = builder.port(9000).build();
+    }
+    // error[E0382]: use of moved value: `builder`
+}
+
+/*
+ * Key Concepts:
+ * - A builder replaces long parameter lists with named, optional settings
+ * - By-value setters (mut self -> Self) chain without a mutable variable
+ * - build() applies defaults and validates once, returning a Result
+ * - Java: a nested Builder class; Go: config structs or functional options
+ */
+
+// EXPECTED:
+// Server { host: "localhost", port: 8080, workers: 4, timeout: 30s, tls: false }
+// example.com:8443 tls=true workers=16 timeout=5s
+// error: a host is required
+// error: port 80 needs root
+// error: at least one worker is required
+// debug workers: 1
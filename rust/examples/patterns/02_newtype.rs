@@ -0,0 +1,133 @@
+// Patterns 2: Newtype
+// Demonstrates wrapping a type in a one-field struct for safety and new impls
+//
+// Concepts: newtype pattern, type safety, new impls
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Two ids that are both u64 can be swapped by accident, and the compiler
+// can't tell. Wrapping each in its own tuple struct, `struct UserId(u64)`,
+// makes them different types at no cost: the wrapper has the same size
+// and layout as what it wraps. A newtype can also restrict what is
+// allowed (an Email that was validated once, when it was made), and it
+// lets us implement a trait from another crate for a type from another
+// crate, which the orphan rule otherwise forbids.
+//
+// Go's defined types, `type UserID int64`, give the same protection for
+// free, and are the closest match. Java has no zero-cost equivalent: a
+// wrapper class or record adds an object per value, so Java code often
+// leaves ids as plain longs.
+
+use std::fmt;
+use std::ops::Add;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct UserId(u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct OrderId(u64);
+
+fn cancel_order(user: UserId, order: OrderId) -> String {
+    format!("user {} cancels order {}", user.0, order.0)
+}
+
+/// Units as types: adding meters to meters is fine, meters to feet is not.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Feet(f64);
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+impl From<Feet> for Meters {
+    fn from(feet: Feet) -> Meters {
+        Meters(feet.0 * 0.3048)
+    }
+}
+
+/// Can only be made through `parse`, so every Email in the program has
+/// already been checked. The field is private to keep it that way.
+#[derive(Debug, Clone, PartialEq)]
+struct Email(String);
+
+impl Email {
+    fn parse(text: &str) -> Result<Email, String> {
+        match text.split_once('@') {
+            Some((user, domain)) if !user.is_empty() && domain.contains('.') => {
+                Ok(Email(text.to_lowercase()))
+            }
+            _ => Err(format!("not an email address: {:?}", text)),
+        }
+    }
+
+    fn domain(&self) -> &str {
+        self.0.split_once('@').map_or("", |(_, domain)| domain)
+    }
+}
+
+/// Display and Vec both come from std, so `impl Display for Vec<String>`
+/// isn't allowed here. A local wrapper is.
+struct Csv(Vec<String>);
+
+impl fmt::Display for Csv {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.join(","))
+    }
+}
+
+fn main() {
+    let user = UserId(7);
+    let order = OrderId(1042);
+    println!("{}", cancel_order(user, order));
+
+    let run = Meters(400.0) + Meters(5000.0);
+    let climb: Meters = Feet(1000.0).into();
+    println!("run: {:?}, climb: {:.1} m", run, climb.0);
+    println!("run is longer: {}", run > climb);
+
+    for text in ["Ana@Example.com", "not-an-email", "@example.com"] {
+        match Email::parse(text) {
+            Ok(email) => println!("ok: {:?} at {}", email, email.domain()),
+            Err(error) => println!("error: {}", error),
+        }
+    }
+
+    let row = Csv(vec!["id".into(), "name".into(), "email".into()]);
+    println!("csv: {}", row);
+
+    println!(
+        "UserId is {} bytes, like u64",
+        std::mem::size_of::<UserId>()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    println!("{}", cancel_order(order, user));
+    // error[E0308]: arguments to this function are incorrect
+}
+
+/*
+ * Key Concepts:
+ * - struct UserId(u64) is a new type with the cost of a u64
+ * - Distinct types turn mixed-up arguments and units into compile errors
+ * - A private field plus a checking constructor keeps values valid
+ * - Newtypes get around the orphan rule for foreign traits on foreign types
+ * - Go: `type UserID int64`; Java: a wrapper class, with an allocation
+ */
+
+// EXPECTED:
+// user 7 cancels order 1042
+// run: Meters(5400.0), climb: 304.8 m
+// run is longer: true
+// ok: Email("ana@example.com") at example.com
+// error: not an email address: "not-an-email"
+// error: not an email address: "@example.com"
+// csv: id,name,email
+// UserId is 8 bytes, like u64
@@ -0,0 +1,138 @@
+// Patterns 3: Typestate
+// Demonstrates encoding an object's state in its type, so wrong calls don't compile
+//
+// Concepts: typestate, zero-sized types, compile-time state
+// Difficulty: advanced
+// Minutes: 10
+//
+// Many APIs have an order: connect, then log in, then send. Most languages
+// check that order at runtime, with a state field and an error (or an
+// exception) when a method is called too early. In Rust the state can be a
+// type parameter instead. Each transition consumes the old value and
+// returns one with a new state type, and each method exists only on the
+// states where it makes sense, so calling send() before logging in is a
+// compile error rather than a bug report.
+//
+// In Java or Go the usual version is a state enum checked at the top of
+// each method, throwing IllegalStateException or returning an error.
+// Separate classes per state would work there too, but nothing stops code
+// from keeping and reusing the old object; Rust's moves do.
+
+use std::marker::PhantomData;
+
+// The states. They hold no data and exist only as type parameters.
+struct Disconnected;
+struct Connected;
+struct Authenticated;
+
+struct Connection<State> {
+    address: String,
+    log: Vec<String>,
+    // Uses the type parameter without storing a value of it
+    state: PhantomData<State>,
+}
+
+impl<State> Connection<State> {
+    /// Moves everything into a connection with a different state type.
+    fn into_state<Next>(self, entry: String) -> Connection<Next> {
+        let mut log = self.log;
+        log.push(entry);
+        Connection {
+            address: self.address,
+            log,
+            state: PhantomData,
+        }
+    }
+
+    /// Available in every state.
+    fn history(&self) -> &[String] {
+        &self.log
+    }
+}
+
+impl Connection<Disconnected> {
+    fn new(address: &str) -> Self {
+        Connection {
+            address: address.to_string(),
+            log: Vec::new(),
+            state: PhantomData,
+        }
+    }
+
+    fn connect(self) -> Connection<Connected> {
+        let entry = format!("connected to {}", self.address);
+        self.into_state(entry)
+    }
+}
+
+impl Connection<Connected> {
+    /// Logging in can fail; the caller gets the connection back either way.
+    fn login(self, password: &str) -> Result<Connection<Authenticated>, Connection<Connected>> {
+        if password == "hunter2" {
+            Ok(self.into_state("logged in".to_string()))
+        } else {
+            let mut failed = self;
+            failed.log.push("login refused".to_string());
+            Err(failed)
+        }
+    }
+}
+
+impl Connection<Authenticated> {
+    fn send(&mut self, message: &str) {
+        self.log.push(format!("sent {:?}", message));
+    }
+
+    fn disconnect(self) -> Connection<Disconnected> {
+        self.into_state("disconnected".to_string())
+    }
+}
+
+fn main() {
+    let connection = Connection::new("db.local:5432").connect();
+
+    // A wrong password hands back the Connected connection to retry with
+    let Err(connection) = connection.login("guess") else {
+        panic!("the wrong password was accepted");
+    };
+    let Ok(mut session) = connection.login("hunter2") else {
+        panic!("the right password was refused");
+    };
+    session.send("SELECT 1");
+    session.send("SELECT 2");
+
+    let closed = session.disconnect();
+    for entry in closed.history() {
+        println!("{}", entry);
+    }
+
+    // The states cost nothing at runtime
+    println!(
+        "same size in every state: {}",
+        std::mem::size_of::<Connection<Disconnected>>()
+            == std::mem::size_of::<Connection<Authenticated>>()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    Connection::new("db.local:5432").connect().send("too early");
+    // error[E0599]: no method named `send` found for struct `Connection<Connected>` in the current scope
+}
+
+/*
+ * Key Concepts:
+ * - A type parameter (with zero-sized marker types) records the state
+ * - Transitions take self by value and return the next state's type
+ * - Methods live in impl blocks for the states that allow them
+ * - PhantomData<State> uses a type parameter without storing a value
+ * - Java/Go check the state at runtime; here a wrong call doesn't compile
+ */
+
+// EXPECTED:
+// connected to db.local:5432
+// login refused
+// logged in
+// sent "SELECT 1"
+// sent "SELECT 2"
+// disconnected
+// same size in every state: true
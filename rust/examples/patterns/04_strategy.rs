@@ -0,0 +1,164 @@
+// Patterns 4: Strategy
+// Demonstrates swapping behavior at runtime with trait objects and closures
+//
+// Concepts: strategy pattern, trait objects, closures
+// Difficulty: intermediate
+// Minutes: 15
+//
+// The strategy pattern separates *what* a piece of code does from one
+// step of *how* it does it: a checkout that applies "some discount",
+// chosen per customer. The step becomes a trait, each variant a type that
+// implements it, and the checkout keeps a Box<dyn Discount>, so the
+// choice can be made, stored, and changed while the program runs. When a
+// strategy is a single function, a boxed closure says the same with less
+// ceremony. When the choice is known at compile time, a generic parameter
+// avoids the indirection altogether.
+//
+// This is the Java version almost line for line: an interface, classes
+// implementing it, and since Java 8 often just a lambda. Go uses an
+// interface or, just as often, a func-typed field.
+
+/// One way of turning a subtotal (in cents) into the price to pay.
+trait Discount {
+    fn apply(&self, cents: u64) -> u64;
+    fn describe(&self) -> String;
+}
+
+struct NoDiscount;
+
+struct Percent(u64);
+
+/// Take `off` cents off orders of at least `over` cents.
+struct Threshold {
+    over: u64,
+    off: u64,
+}
+
+impl Discount for NoDiscount {
+    fn apply(&self, cents: u64) -> u64 {
+        cents
+    }
+
+    fn describe(&self) -> String {
+        "full price".to_string()
+    }
+}
+
+impl Discount for Percent {
+    fn apply(&self, cents: u64) -> u64 {
+        cents - cents * self.0 / 100
+    }
+
+    fn describe(&self) -> String {
+        format!("{}% off", self.0)
+    }
+}
+
+impl Discount for Threshold {
+    fn apply(&self, cents: u64) -> u64 {
+        if cents >= self.over {
+            cents - self.off
+        } else {
+            cents
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("{} off orders over {}", self.off, self.over)
+    }
+}
+
+/// Holds whichever strategy it was given; the type is decided at runtime.
+struct Checkout {
+    discount: Box<dyn Discount>,
+}
+
+impl Checkout {
+    fn total(&self, items: &[u64]) -> u64 {
+        self.discount.apply(items.iter().sum())
+    }
+}
+
+/// Picks a strategy from data, the way a config file or a database would.
+fn discount_for(customer: &str) -> Box<dyn Discount> {
+    match customer {
+        "staff" => Box::new(Percent(30)),
+        "member" => Box::new(Threshold {
+            over: 5000,
+            off: 500,
+        }),
+        _ => Box::new(NoDiscount),
+    }
+}
+
+/// A one-method strategy is just a function.
+struct Rounding {
+    round: Box<dyn Fn(u64) -> u64>,
+}
+
+/// The compile-time version: one copy of this function per strategy type,
+/// with no Box and no dynamic call.
+fn total_with<D: Discount>(discount: &D, items: &[u64]) -> u64 {
+    discount.apply(items.iter().sum())
+}
+
+fn main() {
+    let basket = [1999, 2500, 1250];
+
+    for customer in ["guest", "member", "staff"] {
+        let checkout = Checkout {
+            discount: discount_for(customer),
+        };
+        println!(
+            "{:<6} {:<28} {}",
+            customer,
+            checkout.discount.describe(),
+            checkout.total(&basket)
+        );
+    }
+
+    // The strategy can be replaced on an existing value
+    let mut checkout = Checkout {
+        discount: Box::new(NoDiscount),
+    };
+    checkout.discount = Box::new(Percent(10));
+    println!("after switching: {}", checkout.total(&basket));
+
+    let strategies = [
+        Rounding {
+            round: Box::new(|cents| cents),
+        },
+        Rounding {
+            round: Box::new(|cents| cents / 100 * 100),
+        },
+        Rounding {
+            round: Box::new(|cents| cents.div_ceil(100) * 100),
+        },
+    ];
+    let rounded: Vec<u64> = strategies.iter().map(|r| (r.round)(5749)).collect();
+    println!("rounded: {:?}", rounded);
+
+    println!("generic: {}", total_with(&Percent(50), &basket));
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let all: Vec<dyn Discount> = Vec::new();
+    // error[E0277]: the size for values of type `dyn Discount` cannot be known at compilation time
+}
+
+/*
+ * Key Concepts:
+ * - A strategy is a trait; each variant is a type implementing it
+ * - Box<dyn Trait> lets the choice be made and changed at runtime
+ * - A single-function strategy can be a boxed closure, Box<dyn Fn(..)>
+ * - Generics pick the strategy at compile time, without dynamic dispatch
+ * - Java: an interface or a lambda; Go: an interface or a func field
+ */
+
+// EXPECTED:
+// guest  full price                   5749
+// member 500 off orders over 5000     5249
+// staff  30% off                      4025
+// after switching: 5175
+// rounded: [5749, 5700, 5800]
+// generic: 2875
@@ -0,0 +1,145 @@
+// Patterns 5: RAII Guards
+// Demonstrates guards that undo their setup in Drop, and borrow what they guard
+//
+// Concepts: RAII, guards, Drop, borrowing the guarded value
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Some work comes in pairs: open and close, indent and dedent, lock and
+// unlock. A guard makes the second half automatic. Its constructor does
+// the first half and its Drop does the second, so the pair can't be
+// split by an early return, a `?`, or a panic. A guard that holds a
+// mutable borrow of what it guards adds something more: while it is
+// alive, the borrow checker won't let anyone use the guarded value any
+// other way, so the inner and outer levels can't get mixed up.
+//
+// Java writes the second half in a finally block, or implements
+// AutoCloseable and relies on try-with-resources. Go writes
+// `defer f.Close()` right after the setup. Both work until someone forgets
+// them; a Rust guard needs no extra line at the call site. The drop
+// chapter has more guard examples, including cleanup during a panic.
+
+use std::ops::{Deref, DerefMut};
+
+/// Builds an indented outline.
+#[derive(Default)]
+struct Outline {
+    lines: Vec<String>,
+    depth: usize,
+}
+
+impl Outline {
+    fn line(&mut self, text: &str) {
+        self.lines
+            .push(format!("{}{}", "  ".repeat(self.depth), text));
+    }
+
+    /// Writes a heading and indents everything until the guard is dropped.
+    fn section(&mut self, heading: &str) -> Section<'_> {
+        self.line(heading);
+        self.depth += 1;
+        Section { outline: self }
+    }
+}
+
+/// While a Section is alive, lines go one level deeper.
+struct Section<'a> {
+    outline: &'a mut Outline,
+}
+
+impl Drop for Section<'_> {
+    fn drop(&mut self) {
+        self.outline.depth -= 1;
+    }
+}
+
+// Deref lets a section be used like the outline it borrows, so sections
+// can be nested by calling section() on a section
+impl Deref for Section<'_> {
+    type Target = Outline;
+
+    fn deref(&self) -> &Outline {
+        self.outline
+    }
+}
+
+impl DerefMut for Section<'_> {
+    fn deref_mut(&mut self) -> &mut Outline {
+        self.outline
+    }
+}
+
+/// Describes a recipe; a missing step returns early, and the indentation
+/// is still undone.
+fn recipe(outline: &mut Outline, steps: &[&str]) -> Result<(), String> {
+    let mut section = outline.section("Recipe");
+    section.line("ingredients: flour, water");
+    let mut method = section.section("Method");
+    for (n, step) in steps.iter().enumerate() {
+        if step.is_empty() {
+            return Err(format!("step {} is missing", n + 1));
+        }
+        method.line(&format!("{}. {}", n + 1, step));
+    }
+    Ok(())
+}
+
+fn main() {
+    let mut outline = Outline::default();
+    outline.line("Cookbook");
+    {
+        let mut bread = outline.section("Bread");
+        bread.line("takes 3 hours");
+        {
+            let mut steps = bread.section("Steps");
+            steps.line("mix");
+            steps.line("knead");
+        } // "Steps" ends here
+        bread.line("bake at 220°C");
+    } // and "Bread" here
+    outline.line("Index");
+
+    let result = recipe(&mut outline, &["mix", "", "bake"]);
+    println!("recipe: {:?}", result);
+    outline.line("back at depth 0");
+
+    for line in &outline.lines {
+        println!("{}", line);
+    }
+    println!("depth at the end: {}", outline.depth);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let mut inner = outline.section("Inner");
+        outline.line("outer, while inner is open");
+        inner.line("inner");
+    }
+    // error[E0499]: cannot borrow `outline` as mutable more than once at a time
+}
+
+/*
+ * Key Concepts:
+ * - A guard pairs setup (in its constructor) with teardown (in Drop)
+ * - Teardown can't be forgotten, on any path out of the scope
+ * - A guard holding &mut makes the guarded value unusable except through it
+ * - Deref/DerefMut let a guard be used like the value it guards
+ * - Java: finally or try-with-resources; Go: defer
+ */
+
+// EXPECTED:
+// recipe: Err("step 2 is missing")
+// Cookbook
+// Bread
+//   takes 3 hours
+//   Steps
+//     mix
+//     knead
+//   bake at 220°C
+// Index
+// Recipe
+//   ingredients: flour, water
+//   Method
+//     1. mix
+// back at depth 0
+// depth at the end: 0
@@ -0,0 +1,98 @@
+// Proc Macro Lesson 1: Using a Derive Macro
+// Demonstrates #[derive(Describe)] from our own procedural macro crate
+//
+// Concepts: procedural macros, custom derive
+// Difficulty: advanced
+// Minutes: 10
+//
+// The macro itself is in `describe_derive/src/lib.rs`. Read this file
+// first to see what it does, then that one to see how.
+
+use describe_derive::Describe;
+
+// #[derive(Describe)] runs our macro at compile time. It reads this struct
+// definition and generates an `impl User { fn describe(&self) ... }`.
+#[derive(Describe)]
+struct User {
+    name: String,
+    age: u32,
+    // A helper attribute, understood only by the Describe derive
+    #[describe(skip)]
+    password: String,
+}
+
+#[derive(Describe)]
+struct Meters(f64);
+
+#[derive(Describe)]
+struct Marker;
+
+#[allow(dead_code)]
+#[derive(Describe)]
+enum Shape {
+    Circle { radius: f64 },
+    Square(f64),
+    Dot,
+}
+
+// Generic types work too; the macro adds `T: Debug` for us
+#[derive(Describe)]
+struct Labelled<T> {
+    label: &'static str,
+    value: T,
+}
+
+fn main() {
+    let user = User {
+        name: String::from("ann"),
+        age: 30,
+        password: String::from("hunter2"),
+    };
+    println!("{}", user.describe());
+    println!(
+        "(the password really is {} characters)",
+        user.password.len()
+    );
+
+    println!("{}", Meters(5.5).describe());
+    println!("{}", Marker.describe());
+
+    for shape in [
+        Shape::Circle { radius: 1.0 },
+        Shape::Square(2.0),
+        Shape::Dot,
+    ] {
+        println!("{}", shape.describe());
+    }
+
+    let reading = Labelled {
+        label: "temperature",
+        value: vec![20.5, 21.0],
+    };
+    println!("{}", reading.describe());
+
+    // Misusing the macro gives a normal compiler error, pointing at the
+    // user's code, because the macro returns `compile_error!` tokens.
+    // This would cause an error:
+    // #[derive(Describe)] union Bits { int: u32, float: f32 }
+    // error: Describe cannot be derived for unions
+}
+
+/*
+ * Key Concepts:
+ * - A derive macro generates extra code from a type definition
+ * - It lives in a separate crate with `proc-macro = true`
+ * - Helper attributes like #[describe(skip)] configure the derive
+ * - Generated code is checked like any other code
+ * - Errors from the macro show up as ordinary compiler errors
+ */
+
+// EXPECTED:
+// User is a struct with 3 fields: name = "ann", age = 30, password = <hidden>
+// (the password really is 7 characters)
+// Meters is a tuple struct with 1 field: 0 = 5.5
+// Marker is a unit struct
+// Shape::Circle is variant 1 of 3 of enum Shape with 1 field: radius = 1.0
+// Shape::Square is variant 2 of 3 of enum Shape with 1 field: 0 = 2.0
+// Shape::Dot is variant 3 of 3 of enum Shape
+// Labelled is a struct with 2 fields: label = "temperature", value = [20.5, 21.0]
@@ -0,0 +1,88 @@
+// Proc Macro Lesson 2: What the Macro Expands To
+// Demonstrates the code a derive generates, written out by hand
+//
+// Concepts: macro expansion, generated code
+// Difficulty: advanced
+// Minutes: 10
+//
+// To see the real expansion of 01_derive_describe.rs, install cargo-expand
+// (`cargo install cargo-expand`) and run, from the rust/ directory:
+//     cargo expand -p proc-macro-lesson --bin 01_derive_describe
+
+use describe_derive::Describe;
+
+// This struct uses the derive...
+#[derive(Describe)]
+struct Derived {
+    x: i32,
+    y: i32,
+}
+
+// ...and this one has the same impl written by hand, roughly what the
+// macro produces. (The real output spells every path in full, like
+// ::std::string::String, so it works even if the user's crate has its own
+// type called String.)
+struct HandWritten {
+    x: i32,
+    y: i32,
+}
+
+impl HandWritten {
+    pub fn describe(&self) -> String {
+        fn describe_parts(intro: String, parts: Vec<String>) -> String {
+            match parts.len() {
+                0 => intro,
+                1 => format!("{} with 1 field: {}", intro, parts[0]),
+                n => format!("{} with {} fields: {}", intro, n, parts.join(", ")),
+            }
+        }
+        // The macro binds each field to a generated name...
+        let HandWritten {
+            x: field_0,
+            y: field_1,
+        } = self;
+        // ...and formats one "name = value" part per field
+        let parts: Vec<String> = vec![
+            format!("{} = {:?}", "x", field_0),
+            format!("{} = {:?}", "y", field_1),
+        ];
+        // The intro is computed while the macro runs, so it is a plain
+        // string literal in the output
+        describe_parts(String::from("HandWritten is a struct"), parts)
+    }
+}
+
+fn main() {
+    let derived = Derived { x: 1, y: 2 };
+    let by_hand = HandWritten { x: 1, y: 2 };
+    println!("{}", derived.describe());
+    println!("{}", by_hand.describe());
+
+    // The steps a derive macro goes through:
+    //   1. The compiler passes the struct's tokens to derive_describe().
+    //   2. syn parses them into a DeriveInput: name, generics, fields.
+    //   3. quote! builds new tokens (the impl block) from that data.
+    //   4. The compiler inserts those tokens after the struct and compiles
+    //      them as if you had typed them yourself.
+    //
+    // Macros run at COMPILE time. At run time, describe() is an ordinary
+    // method; there is no macro left.
+    let same_shape = derived.describe().replace("Derived", "X")
+        == by_hand.describe().replace("HandWritten", "X");
+    println!("\nDerived and hand-written versions agree: {same_shape}");
+}
+
+/*
+ * Key Concepts:
+ * - A derive expands into ordinary Rust code placed next to the type
+ * - syn parses the input tokens; quote! produces the output tokens
+ * - Values known at compile time (names, counts) become literals
+ * - Generated code uses full paths (::std::...) to avoid name clashes
+ * - cargo expand shows the exact expansion
+ */
+
+// EXPECTED:
+// Derived is a struct with 2 fields: x = 1, y = 2
+// HandWritten is a struct with 2 fields: x = 1, y = 2
+//
+// Derived and hand-written versions agree: true
@@ -0,0 +1,219 @@
+//! `#[derive(Describe)]`: generates a `describe()` method that explains a
+//! value in plain English, field by field.
+//!
+//! ```text
+//! #[derive(Describe)]
+//! struct User { name: String, #[describe(skip)] password: String }
+//!
+//! user.describe()
+//! // "User is a struct with 2 fields: name = \"ann\", password = <hidden>"
+//! ```
+//!
+//! A derive macro is a function from tokens to tokens. The compiler hands
+//! us the item the `#[derive]` is on, and we hand back NEW items (an
+//! `impl` block) to add next to it. The original item is left untouched.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam};
+
+/// The entry point the compiler calls. `attributes(describe)` registers
+/// `#[describe(...)]` as a helper attribute, so it is allowed on fields of
+/// types that derive `Describe`.
+#[proc_macro_derive(Describe, attributes(describe))]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    // Errors become a `compile_error!` at the right spot in the user's code
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The real work, written against `proc_macro2` so it can be unit tested.
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let kind = match &data.fields {
+                Fields::Named(_) => "struct",
+                Fields::Unnamed(_) => "tuple struct",
+                Fields::Unit => "unit struct",
+            };
+            let (pattern, parts) = destructure(quote!(Self), &data.fields)?;
+            let intro = format!("{name} is a {kind}");
+            quote! {
+                let #pattern = self;
+                let parts: ::std::vec::Vec<::std::string::String> = ::std::vec![#(#parts),*];
+                describe_parts(::std::string::String::from(#intro), parts)
+            }
+        }
+        Data::Enum(data) => {
+            let total = data.variants.len();
+            let arms = data
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    let variant_name = &variant.ident;
+                    let (pattern, parts) = destructure(quote!(Self::#variant_name), &variant.fields)?;
+                    let intro = format!(
+                        "{name}::{variant_name} is variant {} of {total} of enum {name}",
+                        index + 1
+                    );
+                    Ok(quote! {
+                        #pattern => {
+                            let parts: ::std::vec::Vec<::std::string::String> = ::std::vec![#(#parts),*];
+                            describe_parts(::std::string::String::from(#intro), parts)
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            if arms.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Describe cannot be derived for an enum with no variants",
+                ));
+            }
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "Describe cannot be derived for unions",
+            ));
+        }
+    };
+
+    // Every type parameter must be printable with {:?}, so add `T: Debug`
+    // to the generics before splitting them for the impl header.
+    let mut generics = input.generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::std::fmt::Debug));
+        }
+    }
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Explains this value in plain English. Generated by
+            /// `#[derive(Describe)]`.
+            pub fn describe(&self) -> ::std::string::String {
+                fn describe_parts(
+                    intro: ::std::string::String,
+                    parts: ::std::vec::Vec<::std::string::String>,
+                ) -> ::std::string::String {
+                    match parts.len() {
+                        0 => intro,
+                        1 => ::std::format!("{} with 1 field: {}", intro, parts[0]),
+                        n => ::std::format!("{} with {} fields: {}", intro, n, parts.join(", ")),
+                    }
+                }
+                #body
+            }
+        }
+    })
+}
+
+/// Builds a pattern that binds every field (`Self { a: field_0, .. }`) and
+/// one `"name = value"` expression per field.
+fn destructure(
+    path: TokenStream2,
+    fields: &Fields,
+) -> syn::Result<(TokenStream2, Vec<TokenStream2>)> {
+    let mut bindings = Vec::new();
+    let mut parts = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let binding = format_ident!("field_{}", index);
+        let label = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+        parts.push(if is_skipped(field)? {
+            quote!(::std::format!("{} = <hidden>", #label))
+        } else {
+            quote!(::std::format!("{} = {:?}", #label, #binding))
+        });
+        bindings.push(binding);
+    }
+
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+        Fields::Unit => quote!(#path),
+    };
+    Ok((pattern, parts))
+}
+
+/// Whether a field carries `#[describe(skip)]`.
+fn is_skipped(field: &syn::Field) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in &field.attrs {
+        if attr.path().is_ident("describe") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip`"))
+                }
+            })?;
+        }
+    }
+    Ok(skip)
+}
+
+// Unit tests run the expansion directly, without a compiler in the loop:
+// tokens in, tokens (or an error) out.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_an_inherent_describe_method() {
+        let input: DeriveInput = parse_quote! {
+            struct Point { x: i32, y: i32 }
+        };
+        let output = expand(&input).unwrap().to_string();
+        assert!(output.starts_with("impl Point"), "{output}");
+        assert!(output.contains("pub fn describe (& self)"), "{output}");
+        assert!(output.contains("\"Point is a struct\""), "{output}");
+    }
+
+    #[test]
+    fn adds_debug_bounds_to_type_parameters() {
+        let input: DeriveInput = parse_quote! {
+            struct Wrapper<T> { inner: T }
+        };
+        let output = expand(&input).unwrap().to_string();
+        assert!(
+            output.starts_with("impl < T : :: std :: fmt :: Debug > Wrapper < T >"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn rejects_unions() {
+        let input: DeriveInput = parse_quote! {
+            union Bits { int: u32, float: f32 }
+        };
+        let error = expand(&input).unwrap_err();
+        assert_eq!(error.to_string(), "Describe cannot be derived for unions");
+    }
+
+    #[test]
+    fn rejects_unknown_helper_arguments() {
+        let input: DeriveInput = parse_quote! {
+            struct Secret { #[describe(hide)] value: String }
+        };
+        assert_eq!(expand(&input).unwrap_err().to_string(), "expected `skip`");
+    }
+}
@@ -0,0 +1,99 @@
+// Integration tests for #[derive(Describe)]: they use the macro exactly as
+// a downstream crate would, and check what the generated method returns.
+
+use describe_derive::Describe;
+
+#[derive(Describe)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Describe)]
+struct Pair(u8, &'static str);
+
+#[derive(Describe)]
+struct Empty;
+
+#[derive(Describe)]
+struct Account {
+    owner: String,
+    #[describe(skip)]
+    pin: u16,
+}
+
+#[allow(dead_code)]
+#[derive(Describe)]
+enum Light {
+    Off,
+    Dimmed(u8),
+    Colored { red: u8, green: u8, blue: u8 },
+}
+
+#[derive(Describe)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[test]
+fn named_struct() {
+    assert_eq!(
+        Point { x: 1, y: -2 }.describe(),
+        "Point is a struct with 2 fields: x = 1, y = -2"
+    );
+}
+
+#[test]
+fn tuple_struct() {
+    assert_eq!(
+        Pair(7, "seven").describe(),
+        "Pair is a tuple struct with 2 fields: 0 = 7, 1 = \"seven\""
+    );
+}
+
+#[test]
+fn unit_struct() {
+    assert_eq!(Empty.describe(), "Empty is a unit struct");
+}
+
+#[test]
+fn skipped_fields_are_hidden() {
+    let account = Account {
+        owner: String::from("bo"),
+        pin: 1234,
+    };
+    assert_eq!(
+        account.describe(),
+        "Account is a struct with 2 fields: owner = \"bo\", pin = <hidden>"
+    );
+    assert_eq!(account.pin, 1234);
+}
+
+#[test]
+fn enum_variants() {
+    assert_eq!(
+        Light::Off.describe(),
+        "Light::Off is variant 1 of 3 of enum Light"
+    );
+    assert_eq!(
+        Light::Dimmed(40).describe(),
+        "Light::Dimmed is variant 2 of 3 of enum Light with 1 field: 0 = 40"
+    );
+    assert_eq!(
+        Light::Colored {
+            red: 255,
+            green: 0,
+            blue: 10
+        }
+        .describe(),
+        "Light::Colored is variant 3 of 3 of enum Light with 3 fields: red = 255, green = 0, blue = 10"
+    );
+}
+
+#[test]
+fn generic_struct() {
+    assert_eq!(
+        Wrapper { inner: Some('x') }.describe(),
+        "Wrapper is a struct with 1 field: inner = Some('x')"
+    );
+}
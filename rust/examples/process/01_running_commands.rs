@@ -0,0 +1,134 @@
+// Process 1: Running Another Program
+// Demonstrates std::process::Command, output(), status(), and what can go wrong
+//
+// Concepts: Command, arguments, output, status, ExitStatus, stdout and stderr, NotFound
+// Difficulty: intermediate
+// Minutes: 15
+//
+// std::process::Command describes a program to start: its name, its
+// arguments, and later its environment and where its input and output go.
+// Then one of three methods starts it:
+//
+//   output()  runs it to the end and collects its stdout and stderr
+//   status()  runs it to the end, with our stdout and stderr, and returns
+//             only how it exited
+//   spawn()   starts it and returns at once (the next example)
+//
+// Which program to run is looked up in PATH, the way a shell does, so
+// `Command::new("git")` works wherever `git` does in a terminal. To give
+// the same output everywhere, this example mostly runs itself: started
+// with `child` as its first argument, it plays the other program instead.
+
+use std::env;
+use std::io;
+use std::process::{self, Command};
+
+/// What this program does when another copy of it starts it as a child.
+fn child(role: &str, args: &[String]) {
+    match role {
+        // Print each argument on its own line, between brackets, to show
+        // exactly what arrived
+        "echo" => {
+            for arg in args {
+                println!("[{arg}]");
+            }
+        }
+        "fail" => {
+            println!("about to fail");
+            eprintln!("something went wrong");
+            process::exit(3);
+        }
+        _ => unreachable!("no child role {role}"),
+    }
+}
+
+/// A command that runs this same program as a child in `role`.
+fn this_program(role: &str) -> io::Result<Command> {
+    let mut command = Command::new(env::current_exe()?);
+    command.args(["child", role]);
+    Ok(command)
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let [first, role, rest @ ..] = args.as_slice() {
+        if first == "child" {
+            child(role, rest);
+            return Ok(());
+        }
+    }
+
+    // Arguments go to the program as they are, one by one. There is no
+    // shell in between, so spaces, quotes, `;`, and `*` mean nothing
+    // special, and a file name with spaces in it stays one argument.
+    println!("output():");
+    let output = this_program("echo")?
+        .arg("one")
+        .arg("two words")
+        .args(["*.rs", "; rm -rf /"])
+        .output()?;
+    // stdout arrives as bytes: a program can print anything, not only UTF-8
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    println!("exited successfully: {}", output.status.success());
+
+    // A program that fails still runs; how it exited is in its status. By
+    // convention 0 means success and anything else is a failure, and
+    // errors go to stderr, not stdout.
+    println!("\nA failing program:");
+    let output = this_program("fail")?.output()?;
+    println!("stdout: {:?}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {:?}", String::from_utf8_lossy(&output.stderr));
+    match output.status.code() {
+        Some(0) => println!("it worked"),
+        Some(code) => println!("it failed with exit code {code}"),
+        // On Unix, a program killed by a signal has no exit code
+        None => println!("it was killed by a signal"),
+    }
+
+    // status() doesn't capture anything: the child writes straight to our
+    // stdout and stderr, so its lines appear between ours
+    println!("\nstatus():");
+    let status = this_program("echo")?.arg("printed by the child").status()?;
+    println!("status: {:?}", status.code());
+
+    // A program that can't be started at all is an Err, not a status
+    println!("\nA program that doesn't exist:");
+    match Command::new("no-such-program-here").output() {
+        Ok(output) => println!("it ran?! {:?}", output.status),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            println!("not found: is it installed, and on PATH?")
+        }
+        Err(error) => println!("couldn't start it: {error}"),
+    }
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - Command::new(program).arg(..).args(..) describes what to run
+ * - output() waits and captures stdout and stderr; status() waits and shares ours
+ * - Arguments are passed as they are, with no shell to split or expand them
+ * - stdout and stderr are bytes; String::from_utf8_lossy turns them into text
+ * - ExitStatus: success(), and code(), which is None when a signal killed it
+ * - A missing program is an io::Error of kind NotFound, before anything runs
+ */
+
+// EXPECTED:
+// output():
+// [one]
+// [two words]
+// [*.rs]
+// [; rm -rf /]
+// exited successfully: true
+//
+// A failing program:
+// stdout: "about to fail\n"
+// stderr: "something went wrong\n"
+// it failed with exit code 3
+//
+// status():
+// [printed by the child]
+// status: Some(0)
+//
+// A program that doesn't exist:
+// not found: is it installed, and on PATH?
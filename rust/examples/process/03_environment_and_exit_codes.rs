@@ -0,0 +1,153 @@
+// Process 3: Environment Variables, Working Directories, and Exit Codes
+// Demonstrates what a child inherits, how to change it, and how programs report failure
+//
+// Concepts: env, env_remove, env_clear, current_dir, ExitCode, process::exit
+// Difficulty: intermediate
+// Minutes: 15
+//
+// A child starts with a copy of its parent's environment variables and
+// working directory. Command can change both for one child, without
+// touching our own: env() sets a variable, env_remove() unsets one,
+// env_clear() starts from nothing, and current_dir() picks the directory
+// it starts in. That is how a build tool passes settings to compilers,
+// or a test runs a program in a scratch directory.
+//
+// Going the other way, a program tells whoever started it how things went
+// with its exit code: 0 for success, anything else for a failure, and
+// what each number means is up to the program (grep exits with 1 for "no
+// match" and 2 for a real error). In Rust, main() can return an ExitCode,
+// or std::process::exit(code) stops the program on the spot.
+
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::process::{self, Command, ExitCode};
+
+/// The variable this example passes to its children.
+const GREETING: &str = "LEARN_RUST_GREETING";
+
+/// What this program does when another copy of it starts it as a child.
+fn child(role: &str, arg: Option<&str>) -> ExitCode {
+    match role {
+        "env" => {
+            let greeting = env::var(GREETING).unwrap_or_else(|_| "(not set)".to_string());
+            println!("  {GREETING} = {greeting}");
+            println!("  PATH is set: {}", env::var_os("PATH").is_some());
+            ExitCode::SUCCESS
+        }
+        "pwd" => match env::current_dir() {
+            Ok(dir) => {
+                println!("{}", dir.display());
+                ExitCode::SUCCESS
+            }
+            Err(_) => ExitCode::FAILURE,
+        },
+        // Returning an ExitCode from main() exits with it, after running
+        // the destructors of everything main() still owns
+        "return" => ExitCode::from(arg.and_then(|code| code.parse().ok()).unwrap_or(1)),
+        // process::exit() stops right here: nothing else runs, not even
+        // destructors, so buffered output that isn't flushed is lost
+        "exit" => process::exit(arg.and_then(|code| code.parse().ok()).unwrap_or(1)),
+        _ => unreachable!("no child role {role}"),
+    }
+}
+
+fn this_program(role: &str) -> io::Result<Command> {
+    let mut command = Command::new(env::current_exe()?);
+    command.args(["child", role]);
+    Ok(command)
+}
+
+fn run() -> io::Result<()> {
+    println!("What the child inherits:");
+    this_program("env")?.status()?;
+
+    println!("With env():");
+    this_program("env")?.env(GREETING, "hello").status()?;
+
+    // Each Command has its own changes; ours stays as it was
+    println!(
+        "Our own {GREETING} is still set: {}",
+        env::var_os(GREETING).is_some()
+    );
+
+    // Without PATH the child can't find other programs by name; we started
+    // it by its full path, so it still runs
+    println!("With env_remove(\"PATH\"):");
+    this_program("env")?.env_remove("PATH").status()?;
+
+    println!("With env_clear():");
+    this_program("env")?
+        .env_clear()
+        .env(GREETING, "nothing else")
+        .status()?;
+
+    println!("\nWith current_dir():");
+    let scratch = env::temp_dir().canonicalize()?;
+    let output = this_program("pwd")?.current_dir(&scratch).output()?;
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim_end());
+    println!("  the child ran in the temp directory: {}", dir == scratch);
+
+    println!("\nExit codes:");
+    for (role, code) in [("return", "0"), ("return", "2"), ("exit", "42")] {
+        let status = this_program(role)?.arg(code).status()?;
+        let verdict = if status.success() {
+            "success"
+        } else {
+            "failure"
+        };
+        println!("  {role}({code}): code {:?}, a {verdict}", status.code());
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let [first, role, rest @ ..] = args.as_slice() {
+        if first == "child" {
+            return child(role, rest.first().map(String::as_str));
+        }
+    }
+    // The usual shape of main(): do the work, and turn an error into a
+    // message on stderr and a failing exit code
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A child inherits our environment variables and working directory
+ * - env(), env_remove(), env_clear(), and current_dir() change them for one child only
+ * - Exit code 0 means success; what other codes mean is up to each program
+ * - Return ExitCode from main() to exit cleanly with a code of your choice
+ * - process::exit() ends at once, skipping destructors
+ * - status.code() and status.success() read the code on the parent's side
+ */
+
+// EXPECTED:
+// What the child inherits:
+//   LEARN_RUST_GREETING = (not set)
+//   PATH is set: true
+// With env():
+//   LEARN_RUST_GREETING = hello
+//   PATH is set: true
+// Our own LEARN_RUST_GREETING is still set: false
+// With env_remove("PATH"):
+//   LEARN_RUST_GREETING = (not set)
+//   PATH is set: false
+// With env_clear():
+//   LEARN_RUST_GREETING = nothing else
+//   PATH is set: false
+//
+// With current_dir():
+//   the child ran in the temp directory: true
+//
+// Exit codes:
+//   return(0): code Some(0), a success
+//   return(2): code Some(2), a failure
+//   exit(42): code Some(42), a failure
@@ -0,0 +1,142 @@
+// Process 4: Shells and Pipelines
+// Demonstrates running a shell command, why untrusted text must not go into one, and piping programs together
+//
+// Concepts: sh -c, cmd /C, shell injection, Stdio::from, pipelines, cfg
+// Difficulty: advanced
+// Minutes: 15
+//
+// Command runs a program directly, so `|`, `>`, `*`, and `$HOME` in its
+// arguments are just characters. Those are the shell's features: to use
+// them, run the shell itself and give it a script, `sh -c "..."` on Unix
+// or `cmd /C "..."` on Windows.
+//
+// That convenience comes with a trap. A script built with format!() from
+// text you don't control, a file name or a form field, is code: if the
+// text contains `;` or `$(...)`, the shell runs whatever follows. This is
+// shell injection, and the fix is not clever quoting but not using a
+// shell: pass the text as an argument, where it can only ever be text.
+//
+// A pipeline doesn't need a shell either. Stdio::from(child.stdout) makes
+// one child's stdout another's stdin, which is all `a | b` does: both run
+// at once, and the second reads what the first writes, as it writes it.
+
+use std::env;
+use std::io::{self, BufRead};
+use std::process::{Command, Stdio};
+
+/// What this program does when another copy of it starts it as a child.
+fn child(role: &str, args: &[String]) -> io::Result<()> {
+    match role {
+        "greet" => println!("hello {}", args.join(" ")),
+        "numbers" => {
+            for number in 1..=5 {
+                println!("{number}");
+            }
+        }
+        "sum" => {
+            let mut total = 0;
+            for line in io::stdin().lock().lines() {
+                total += line?.trim().parse::<i32>().unwrap_or(0);
+            }
+            println!("{total}");
+        }
+        _ => unreachable!("no child role {role}"),
+    }
+    Ok(())
+}
+
+fn this_program(role: &str) -> io::Result<Command> {
+    let mut command = Command::new(env::current_exe()?);
+    command.args(["child", role]);
+    Ok(command)
+}
+
+/// A command that runs `script` in the system's shell.
+fn shell(script: &str) -> Command {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut command = Command::new(shell);
+    command.arg(flag).arg(script);
+    command
+}
+
+/// What `command` printed, trimmed, or why it couldn't run.
+fn run(command: &mut Command) -> String {
+    match command.output() {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string(),
+        Err(error) => format!("couldn't run it: {error}"),
+    }
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let [first, role, rest @ ..] = args.as_slice() {
+        if first == "child" {
+            return child(role, rest);
+        }
+    }
+
+    println!("The shell runs a script:");
+    println!("  {}", run(&mut shell("echo hello from a shell")));
+
+    // To the shell, `;` ends one command and starts the next. (cmd has
+    // other separators, so on Windows the name is only printed.)
+    println!("\nShell injection:");
+    let name = "world; echo this ran too";
+    let script = format!("echo hello {name}");
+    println!("  in a script:    {:?}", run(&mut shell(&script)));
+    // No shell: the name is one argument, whatever is in it
+    println!(
+        "  as an argument: {:?}",
+        run(this_program("greet")?.arg(name))
+    );
+
+    println!("\nA pipeline, numbers | sum:");
+    let mut numbers = this_program("numbers")?.stdout(Stdio::piped()).spawn()?;
+    let pipe = numbers.stdout.take().expect("stdout is piped");
+    let sum = this_program("sum")?
+        .stdin(Stdio::from(pipe))
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let output = sum.wait_with_output()?;
+    numbers.wait()?;
+    println!("  {}", String::from_utf8_lossy(&output.stdout).trim_end());
+
+    // The same pipeline as a shell script. The path is quoted in case it
+    // has spaces; a path with a quote in it would break the script, which
+    // is the problem above again
+    println!("\nThe same pipeline, as a shell script:");
+    let exe = env::current_exe()?;
+    let script = format!("\"{0}\" child numbers | \"{0}\" child sum", exe.display());
+    println!("  {}", run(&mut shell(&script)));
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - Command runs a program directly; `|`, `>`, `*`, and `$VAR` need a shell
+ * - sh -c "script" (or cmd /C) runs a shell command when you really want one
+ * - Never build a shell script from untrusted text: that is shell injection
+ * - Pass untrusted text as an argument instead, where it stays text
+ * - Stdio::from(child.stdout) pipes one child into another, like `a | b`
+ * - cfg!(windows) and cfg!(unix) pick the right program for each system
+ */
+
+// EXPECTED:
+// The shell runs a script:
+//   hello from a shell
+//
+// Shell injection:
+//   in a script:    "hello world\nthis ran too"
+//   as an argument: "hello world; echo this ran too"
+//
+// A pipeline, numbers | sum:
+//   15
+//
+// The same pipeline, as a shell script:
+//   15
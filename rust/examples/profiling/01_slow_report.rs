@@ -0,0 +1,85 @@
+// Profiling 1: A Slow Report
+// Demonstrates finding out where a program spends its time with a sampling profiler and a flamegraph
+//
+// Concepts: sampling profilers, flamegraphs, pprof, profiling an optimized build, quadratic time
+// Difficulty: intermediate
+// Minutes: 20
+//
+// This program reads a web server's log and prints each distinct request
+// once, numbered, in the order they first appear. The code is in
+// `reports/lib.rs`, under `slow`. On the 2,000 lines `cargo run` gives
+// it, it finishes at once. Give it 40,000:
+//
+//     cargo run --release -p profiling-examples --bin 01_slow_report -- 40000
+//
+// and it takes seconds. Nothing in the code says which part is slow, and
+// guessing is how an afternoon goes into speeding up the part that
+// wasn't. A profiler measures instead.
+//
+// A sampling profiler interrupts the program many times a second and
+// writes down the call stack it was in. A function that takes a lot of
+// the time is on the stack in a lot of the samples. A flamegraph draws
+// every sample at once: each box is a function, as wide as the share of
+// samples it was on the stack in, with the functions it called stacked
+// above it. The wide boxes near the top are where the time went.
+//
+//     cargo run -p tutor -- profile slow_report
+//
+// builds this program with optimizations, runs it on 40,000 lines under
+// pprof, and writes the flamegraph to
+// target/tutor/profiles/01_slow_report.svg. Open it in a browser, where
+// hovering over a box shows its share and clicking one zooms in. Above
+// main stand two towers. One is slow::unique, and nearly all of it is the
+// `contains` it calls, comparing strings: for every line, it looks
+// through every distinct line found so far. The other is slow::render,
+// nearly all of it in format!(), copying the report so far to add each
+// line to it. Both are quadratic, and both show up only at sizes
+// the quick test never reached.
+//
+// Profile what you ship. A debug build is slow everywhere, so its
+// flamegraph is full of costs that the optimizer removes. The tutor
+// builds with the `profiling` profile from the workspace's Cargo.toml: a
+// release build that keeps the symbols the profiler needs to name each
+// function.
+
+use std::env;
+
+use reports::{log_lines, print_summary, sampling, slow};
+
+fn main() {
+    let count: usize = env::args()
+        .nth(1)
+        .map_or(2_000, |arg| arg.parse().expect("the argument is a number of lines"));
+    let lines = log_lines(count);
+
+    // Only this part is sampled: making up the log isn't what we're
+    // measuring
+    let report = sampling::profiled(|| {
+        let unique = slow::unique(&lines);
+        slow::render(&unique)
+    });
+    print_summary(count, &report);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let count: usize = env::args().nth(1).unwrap_or(2_000);
+    // error[E0308]: mismatched types
+}
+
+/*
+ * Key Concepts:
+ * - Measure before optimizing: the slow part is rarely where it looks to be
+ * - A sampling profiler records the call stack many times a second
+ * - In a flamegraph, width is time: look for the wide boxes near the top
+ * - Profile an optimized build with symbols, not a debug build
+ * - Code that is instant on small inputs can still be quadratic
+ */
+
+// EXPECTED:
+// 2000 log lines, 858 distinct requests
+//      1  POST /items/245 404
+//      2  GET /items/239 200
+//      3  POST /items/242 404
+//    ...
+//    858  POST /items/150 404
+// 23203 bytes of report
@@ -0,0 +1,81 @@
+// Profiling 2: The Rewrite
+// Demonstrates fixing what a flamegraph points to, and checking the fix with a second profile
+//
+// Concepts: HashSet for membership, String::with_capacity, fmt::Write, comparing profiles, knowing when to stop
+// Difficulty: intermediate
+// Minutes: 15
+//
+// The flamegraph of 01_slow_report had two wide towers, and `fast` in
+// `reports/lib.rs` rewrites the two functions under them, one change
+// each:
+// - unique() remembers the lines it has seen in a HashSet instead of
+//   searching the Vec of them. `contains` on a Vec compares against
+//   every element; `insert` on a HashSet hashes the line once and looks
+//   in one place, and returns whether the line was new, which is the
+//   question filter() asks. The set holds `&str`s borrowed from the
+//   input, so no line is cloned just to be remembered.
+// - render() writes every line into one String with writeln!, which
+//   appends in place through the fmt::Write trait, and sizes the string
+//   up front with with_capacity, since each line's length is known.
+//   Nothing is copied, and there is one allocation.
+//
+// The output is the same, line for line, as the tests in `reports/`
+// check: a faster program that gives a different answer is a different
+// program.
+//
+//     cargo run -p tutor -- profile slow_report
+//
+// profiles 01, then this rewrite on the same 40,000 lines, and compares
+// their times. The rewrite may well finish before the profiler takes
+// more than a sample or two, which is a result in itself. To see where
+// its time goes now, give it ten times the lines:
+//
+//     cargo run -p tutor -- profile fast_report
+//
+// and open target/tutor/profiles/02_fast_report.svg next to the first
+// flamegraph. The two towers are gone. What's left is spread out:
+// hashing, cloning the lines that are kept, formatting. No box stands
+// out any more, which is the sign to stop. From here, each change buys a
+// few percent, and makes the code harder to read.
+
+use std::env;
+
+use reports::{fast, log_lines, print_summary, sampling};
+
+fn main() {
+    let count: usize = env::args()
+        .nth(1)
+        .map_or(2_000, |arg| arg.parse().expect("the argument is a number of lines"));
+    let lines = log_lines(count);
+
+    let report = sampling::profiled(|| {
+        let unique = fast::unique(&lines);
+        fast::render(&unique)
+    });
+    print_summary(count, &report);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let seen: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    #[cfg(feature = "broken")]
+    seen.insert(lines[0].as_str());
+    // error[E0596]: cannot borrow `seen` as mutable, as it is not declared as mutable
+}
+
+/*
+ * Key Concepts:
+ * - Fix the widest box first, and only that: one change, then profile again
+ * - HashSet::insert answers "was this new?" in one lookup, where Vec::contains scans
+ * - writeln! into a String appends in place; with_capacity allocates once
+ * - The rewrite must give the same output, or it isn't a rewrite
+ * - A flat flamegraph, with nothing wide, means the easy wins are gone
+ */
+
+// EXPECTED:
+// 2000 log lines, 858 distinct requests
+//      1  POST /items/245 404
+//      2  GET /items/239 200
+//      3  POST /items/242 404
+//    ...
+//    858  POST /items/150 404
+// 23203 bytes of report
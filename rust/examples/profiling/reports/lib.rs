@@ -0,0 +1,183 @@
+//! The code the profiling chapter profiles: a report of the distinct
+//! requests in a web server's log, written twice. `slow` is the way it
+//! often gets written first, and `fast` is the rewrite its flamegraph
+//! points to. The numbered examples run one each, under the profiler in
+//! `sampling` when `tutor profile` asks for it.
+//!
+//! ```
+//! let lines = reports::log_lines(20);
+//! let unique = reports::fast::unique(&lines);
+//! assert_eq!(reports::slow::unique(&lines), unique);
+//! assert_eq!(reports::slow::render(&unique), reports::fast::render(&unique));
+//! ```
+
+/// `count` lines of a made-up access log, like `GET /items/17 200`, the
+/// same ones on every run. Most requests appear more than once.
+pub fn log_lines(count: usize) -> Vec<String> {
+    let items = (count as u64 / 8).max(1);
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..count)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let method = if state & 1 == 0 { "GET" } else { "POST" };
+            let status = if state & 2 == 0 { 200 } else { 404 };
+            format!("{method} /items/{} {status}", (state >> 8) % items)
+        })
+        .collect()
+}
+
+/// What both examples print: the size of the report and a few of its
+/// lines, so the two can be seen to agree.
+pub fn print_summary(count: usize, report: &str) {
+    let lines: Vec<&str> = report.lines().collect();
+    println!("{count} log lines, {} distinct requests", lines.len());
+    for line in lines.iter().take(3) {
+        println!("{line}");
+    }
+    if let Some(last) = lines.get(3..).and_then(|rest| rest.last()) {
+        println!("   ...");
+        println!("{last}");
+    }
+    println!("{} bytes of report", report.len());
+}
+
+/// The first version. Each function is `#[inline(never)]` so that it
+/// gets a box of its own in the flamegraph: the optimizer would otherwise
+/// fold it into its caller.
+pub mod slow {
+    /// The distinct lines, in the order they first appear. Each line is
+    /// looked for in everything kept so far, which is quadratic in the
+    /// number of lines.
+    #[inline(never)]
+    pub fn unique(lines: &[String]) -> Vec<String> {
+        let mut unique = Vec::new();
+        for line in lines {
+            if !unique.contains(line) {
+                unique.push(line.clone());
+            }
+        }
+        unique
+    }
+
+    /// One numbered line per request. format!() copies the whole report
+    /// so far to add a line, which is quadratic in its length.
+    #[inline(never)]
+    pub fn render(unique: &[String]) -> String {
+        let mut report = String::new();
+        for (i, line) in unique.iter().enumerate() {
+            report = format!("{report}{:>6}  {line}\n", i + 1);
+        }
+        report
+    }
+}
+
+/// The rewrite: the same results, in linear time.
+pub mod fast {
+    use std::collections::HashSet;
+    use std::fmt::Write;
+
+    /// The same lines, with a HashSet of the ones seen so far: one hash
+    /// and one lookup per line. The set borrows the lines, so only the
+    /// ones kept are cloned.
+    #[inline(never)]
+    pub fn unique(lines: &[String]) -> Vec<String> {
+        let mut seen = HashSet::with_capacity(lines.len());
+        lines
+            .iter()
+            .filter(|line| seen.insert(line.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// The same report, written into one string sized up front: each
+    /// line is its number padded to six, two spaces, the request, and a
+    /// newline.
+    #[inline(never)]
+    pub fn render(unique: &[String]) -> String {
+        let length = unique.iter().map(|line| line.len() + 9).sum();
+        let mut report = String::with_capacity(length);
+        for (i, line) in unique.iter().enumerate() {
+            writeln!(report, "{:>6}  {line}", i + 1).expect("writing to a String cannot fail");
+        }
+        report
+    }
+}
+
+/// Running code under a sampling profiler.
+pub mod sampling {
+    use std::path::Path;
+
+    /// `tutor profile` sets this to the path of the flamegraph to write.
+    pub const FLAMEGRAPH_VAR: &str = "TUTOR_FLAMEGRAPH";
+
+    /// Runs `work`. When FLAMEGRAPH_VAR is set, samples it while it runs
+    /// and writes the flamegraph there; otherwise it's an ordinary call.
+    pub fn profiled<T>(work: impl FnOnce() -> T) -> T {
+        match std::env::var_os(FLAMEGRAPH_VAR) {
+            Some(path) => sample(work, Path::new(&path)),
+            None => work(),
+        }
+    }
+
+    #[cfg(unix)]
+    fn sample<T>(work: impl FnOnce() -> T, path: &Path) -> T {
+        // 997 times a second rather than 1000, so the samples don't fall
+        // in step with anything the program does every millisecond
+        let guard = pprof::ProfilerGuardBuilder::default()
+            .frequency(997)
+            .build();
+        let result = work();
+        let written = guard
+            .and_then(|guard| guard.report().build())
+            .map_err(|error| error.to_string())
+            .and_then(|report| {
+                let file = std::fs::File::create(path).map_err(|error| error.to_string())?;
+                report.flamegraph(file).map_err(|error| error.to_string())
+            });
+        if let Err(error) = written {
+            eprintln!("no flamegraph: {error}");
+        }
+        result
+    }
+
+    #[cfg(not(unix))]
+    fn sample<T>(work: impl FnOnce() -> T, _path: &Path) -> T {
+        eprintln!("no flamegraph: pprof runs on Linux and macOS only");
+        work()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A rewrite that is faster but wrong is no rewrite, so both versions
+    // must agree on every input before either is profiled.
+
+    #[test]
+    fn both_versions_agree() {
+        for count in [0, 1, 2, 100, 1000] {
+            let lines = log_lines(count);
+            let unique = fast::unique(&lines);
+            assert_eq!(slow::unique(&lines), unique);
+            assert_eq!(slow::render(&unique), fast::render(&unique));
+        }
+    }
+
+    #[test]
+    fn requests_repeat() {
+        let lines = log_lines(1000);
+        let unique = fast::unique(&lines);
+        assert!(unique.len() < lines.len() / 2, "{} distinct", unique.len());
+        assert_eq!(unique[0], lines[0]);
+    }
+
+    #[test]
+    fn the_fast_report_never_grows() {
+        let report = fast::render(&fast::unique(&log_lines(1000)));
+        assert_eq!(report.capacity(), report.len());
+    }
+}
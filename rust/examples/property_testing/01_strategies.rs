@@ -0,0 +1,195 @@
+// Property Testing 1: Strategies
+// Demonstrates any::<T>(), ranges, collections, regex strings, and combinators
+//
+// Concepts: proptest, any::<T>(), ranges, collections, regex strategies, combinators
+// Difficulty: intermediate
+// Minutes: 15
+//
+// An example-based test checks the inputs we thought of. A property test
+// states something that must hold for *every* input, and proptest goes
+// looking for an input where it doesn't. To do that it needs to know what
+// the inputs look like, and that is a strategy: a recipe for random
+// values of one type. Ranges are strategies, and so are regular
+// expressions (for strings), `any::<T>()`, collections of other
+// strategies, and whatever prop_map, prop_filter, and prop_oneof! build from
+// them.
+//
+// A property test can only find bugs among the values its strategy
+// generates, so choosing the strategy is half the work. The last lines
+// below show why: vectors of `any::<i32>()` practically never contain the
+// same number twice, so a test with them never tests duplicates.
+//
+// main() draws samples by hand, with a fixed seed so that the output is
+// the same on every run. The tests at the bottom use the same strategies
+// the usual way, through the proptest! macro.
+
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::{Config, TestRng, TestRunner};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Circle { radius: u32 },
+    Square { side: u32 },
+}
+
+/// Ticket codes like "abc-42".
+const CODE: &str = "[a-z]{3}-[0-9]{2}";
+
+/// Circles three times as often as squares, with sizes from 1 to 99.
+fn shape() -> impl Strategy<Value = Shape> {
+    prop_oneof![
+        3 => (1..100u32).prop_map(|radius| Shape::Circle { radius }),
+        1 => (1..100u32).prop_map(|side| Shape::Square { side }),
+    ]
+}
+
+/// A runner with a fixed seed, and no file of past failures.
+fn runner() -> TestRunner {
+    let config = Config {
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let algorithm = config.rng_algorithm;
+    TestRunner::new_with_rng(config, TestRng::deterministic_rng(algorithm))
+}
+
+/// Draws `count` values from `strategy`. new_tree() returns a ValueTree,
+/// which can shrink its value too; current() is the value itself.
+fn samples<S: Strategy>(strategy: S, count: usize) -> Vec<S::Value> {
+    let mut runner = runner();
+    (0..count)
+        .map(|_| {
+            strategy
+                .new_tree(&mut runner)
+                .expect("the strategy can generate a value")
+                .current()
+        })
+        .collect()
+}
+
+fn has_duplicates(values: &[i32]) -> bool {
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    sorted.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+fn main() {
+    // A range generates values in the range, as a test with it would see
+    let digits = samples(0..10u32, 1000);
+    println!(
+        "0..10: min {}, max {}",
+        digits.iter().min().unwrap(),
+        digits.iter().max().unwrap()
+    );
+
+    // Collections take a strategy for the elements and a range of sizes
+    let lists = samples(prop::collection::vec(0..10u32, 2..5), 1000);
+    let mut lengths: Vec<usize> = lists.iter().map(Vec::len).collect();
+    lengths.sort();
+    lengths.dedup();
+    println!("vec(0..10, 2..5): lengths {:?}", lengths);
+
+    // A &str is a regular expression, and generates strings that match it
+    let codes = samples(CODE, 1000);
+    let well_formed = codes.iter().all(|code| {
+        let (letters, digits) = code.split_once('-').unwrap();
+        letters.len() == 3
+            && letters.bytes().all(|b| b.is_ascii_lowercase())
+            && digits.len() == 2
+            && digits.bytes().all(|b| b.is_ascii_digit())
+    });
+    println!("{:?}: all well-formed: {}", CODE, well_formed);
+
+    // prop_map transforms values; prop_filter throws some away
+    let evens = samples((0..50u32).prop_map(|n| n * 2), 1000);
+    println!(
+        "prop_map(n * 2): all even: {}",
+        evens.iter().all(|n| n % 2 == 0)
+    );
+    let odds = samples((0..100u32).prop_filter("must be odd", |n| n % 2 == 1), 1000);
+    println!(
+        "prop_filter(odd): all odd: {}",
+        odds.iter().all(|n| n % 2 == 1)
+    );
+
+    // prop_oneof! picks one of several strategies, with optional weights
+    let shapes = samples(shape(), 1000);
+    let circles = shapes
+        .iter()
+        .filter(|shape| matches!(shape, Shape::Circle { .. }))
+        .count();
+    println!("shapes: {} circles, {} squares", circles, 1000 - circles);
+
+    // What the strategy doesn't generate, the property never sees
+    let wide = samples(prop::collection::vec(any::<i32>(), 0..100), 1000);
+    let narrow = samples(prop::collection::vec(0..10i32, 0..100), 1000);
+    println!(
+        "lists with a repeated value, any::<i32>(): {} of 1000",
+        wide.iter().filter(|list| has_duplicates(list)).count()
+    );
+    println!(
+        "lists with a repeated value, 0..10: {} of 1000",
+        narrow.iter().filter(|list| has_duplicates(list)).count()
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let shapes = samples(any::<Shape>(), 10);
+    // error[E0277]: the trait bound `Shape: Arbitrary` is not satisfied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        // Each `name in strategy` is one input; proptest runs the body
+        // with 256 generated inputs by default
+        #[test]
+        fn ranges_stay_in_bounds(n in 0..10u32, m in 5..=7u32) {
+            prop_assert!(n < 10);
+            prop_assert!((5..=7).contains(&m));
+        }
+
+        #[test]
+        fn codes_match_their_pattern(code in CODE) {
+            prop_assert_eq!(code.len(), 6);
+            prop_assert_eq!(code.as_bytes()[3], b'-');
+        }
+
+        #[test]
+        fn shapes_have_positive_sizes(shape in shape()) {
+            let size = match shape {
+                Shape::Circle { radius } => radius,
+                Shape::Square { side } => side,
+            };
+            prop_assert!((1..100).contains(&size));
+        }
+    }
+
+    #[test]
+    fn a_narrow_range_repeats_values() {
+        let lists = samples(prop::collection::vec(0..10i32, 20..30), 100);
+        assert!(lists.iter().all(|list| has_duplicates(list)));
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A property must hold for every input; proptest searches for one where it doesn't
+ * - A strategy generates inputs: ranges, regex strings, any::<T>(), collections
+ * - prop_map, prop_filter, and prop_oneof! build new strategies from old ones
+ * - A property only sees what its strategy generates: pick inputs that can hit the bug
+ * - proptest! { #[test] fn name(x in strategy) { ... } } turns it into a test
+ */
+
+// EXPECTED:
+// 0..10: min 0, max 9
+// vec(0..10, 2..5): lengths [2, 3, 4]
+// "[a-z]{3}-[0-9]{2}": all well-formed: true
+// prop_map(n * 2): all even: true
+// prop_filter(odd): all odd: true
+// shapes: 738 circles, 262 squares
+// lists with a repeated value, any::<i32>(): 0 of 1000
+// lists with a repeated value, 0..10: 951 of 1000
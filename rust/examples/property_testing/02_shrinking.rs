@@ -0,0 +1,229 @@
+// Property Testing 2: Shrinking
+// Demonstrates how proptest turns a random failure into a small one
+//
+// Concepts: proptest, shrinking, minimal failing inputs
+// Difficulty: intermediate
+// Minutes: 20
+//
+// The first input that breaks a property is random, and usually big: a
+// 13-element list, a 14-letter string. Reading it tells us little about
+// the bug. So before reporting a failure, proptest *shrinks* the input:
+// it tries simpler versions of it (smaller numbers, shorter collections,
+// earlier characters), keeps each one that still fails, and stops when
+// nothing simpler fails. What it reports is that last, minimal input.
+//
+// "Minimal" means that no single simplification still fails, not that no
+// smaller failing input exists anywhere: the list below shrinks to two
+// numbers summing to exactly 100, which can't be shrunk any further one
+// step at a time, although [100] would fail as well.
+//
+// main() runs each failing property by hand, with a fixed seed, and prints
+// the first failure next to the one proptest reports. In a proptest!
+// test, the report is the panic message: "minimal failing input: n = 334".
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestError, TestRng, TestRunner};
+
+/// A runner with a fixed seed, and no file of past failures.
+fn runner() -> TestRunner {
+    let config = Config {
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let algorithm = config.rng_algorithm;
+    TestRunner::new_with_rng(config, TestRng::deterministic_rng(algorithm))
+}
+
+/// Runs `property` on inputs from `strategy`. Returns the first input that
+/// failed, the input proptest shrank it to, and how many runs that took.
+fn first_and_minimal<S>(
+    strategy: S,
+    property: impl Fn(&S::Value) -> bool,
+) -> Option<(S::Value, S::Value, usize)>
+where
+    S: Strategy,
+    S::Value: Clone + Debug,
+{
+    let first = RefCell::new(None);
+    let tries = RefCell::new(0);
+    let result = runner().run(&strategy, |value| {
+        *tries.borrow_mut() += 1;
+        if property(&value) {
+            return Ok(());
+        }
+        first.borrow_mut().get_or_insert_with(|| value.clone());
+        Err(TestCaseError::fail(format!(
+            "{:?} breaks the property",
+            value
+        )))
+    });
+    match result {
+        Err(TestError::Fail(_, minimal)) => {
+            Some((first.into_inner()?, minimal, tries.into_inner()))
+        }
+        _ => None,
+    }
+}
+
+fn report<S>(claim: &str, strategy: S, property: impl Fn(&S::Value) -> bool)
+where
+    S: Strategy,
+    S::Value: Clone + Debug,
+{
+    println!("{}", claim);
+    match first_and_minimal(strategy, property) {
+        Some((first, minimal, tries)) => {
+            println!("  first failure: {:?}", first);
+            println!("  shrunk to:     {:?} (after {} runs)", minimal, tries);
+        }
+        None => println!("  held for every input tried"),
+    }
+}
+
+/// Meant to remove every `target`, but steps past the element after each
+/// one it removes.
+fn remove_all(values: &[i32], target: i32) -> Vec<i32> {
+    let mut values = values.to_vec();
+    let mut i = 0;
+    while i < values.len() {
+        if values[i] == target {
+            values.remove(i);
+        }
+        i += 1;
+    }
+    values
+}
+
+fn main() {
+    // Numbers shrink by halving the distance to the smallest one
+    report("n * 3 < 1000, for n in 0..10000", 0..10000u32, |&n| {
+        n * 3 < 1000
+    });
+
+    // Strings shrink by dropping characters and moving them toward 'a'
+    report(
+        "no string contains \"ab\", for [a-c]{0,20}",
+        "[a-c]{0,20}",
+        |s: &String| !s.contains("ab"),
+    );
+
+    // Collections drop elements, then shrink the ones that are left
+    report(
+        "every list sums to less than 100",
+        prop::collection::vec(0..100u32, 0..20),
+        |list: &Vec<u32>| list.iter().sum::<u32>() < 100,
+    );
+
+    // A real bug. With any::<i32>() the property holds, because a random
+    // list practically never contains a random target; with 0..4 the bug
+    // is found, and the shrunk input shows what it takes: the target twice
+    // in a row
+    report(
+        "remove_all leaves no target behind, for any::<i32>()",
+        (prop::collection::vec(any::<i32>(), 0..20), any::<i32>()),
+        |(list, target): &(Vec<i32>, i32)| !remove_all(list, *target).contains(target),
+    );
+    report(
+        "remove_all leaves no target behind, for 0..4",
+        (prop::collection::vec(0..4i32, 0..20), 0..4i32),
+        |(list, target): &(Vec<i32>, i32)| !remove_all(list, *target).contains(target),
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let result = runner().run(&(0..10u32), |n| n < 5);
+    // error[E0308]: mismatched types (expected `Result<(), TestCaseError>`, found `bool`)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_shrink_to_the_boundary() {
+        let (first, minimal, _) = first_and_minimal(0..10000u32, |&n| n * 3 < 1000).unwrap();
+        assert!(first >= 334);
+        assert_eq!(minimal, 334);
+    }
+
+    #[test]
+    fn shrunk_lists_cannot_lose_an_element() {
+        let (_, minimal, _) = first_and_minimal(prop::collection::vec(0..100u32, 0..20), |list| {
+            list.iter().sum::<u32>() < 100
+        })
+        .unwrap();
+        assert_eq!(minimal.iter().sum::<u32>(), 100);
+        for skip in 0..minimal.len() {
+            let rest: u32 = minimal
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != skip)
+                .map(|(_, n)| n)
+                .sum();
+            assert!(rest < 100);
+        }
+    }
+
+    #[test]
+    fn the_remove_all_bug_shrinks_to_a_repeated_target() {
+        let strategy = (prop::collection::vec(0..4i32, 0..20), 0..4i32);
+        let (_, (list, target), _) = first_and_minimal(strategy, |(list, target)| {
+            !remove_all(list, *target).contains(target)
+        })
+        .unwrap();
+        assert_eq!(list, [target, target]);
+    }
+
+    proptest! {
+        // Properties that do hold, despite the bug
+        #[test]
+        fn remove_all_keeps_everything_else(
+            list in prop::collection::vec(0..4i32, 0..20),
+            target in 0..4i32,
+        ) {
+            let kept = remove_all(&list, target);
+            let others: Vec<i32> = list.iter().copied().filter(|&n| n != target).collect();
+            let kept_others: Vec<i32> = kept.iter().copied().filter(|&n| n != target).collect();
+            prop_assert_eq!(kept_others, others);
+        }
+
+        #[test]
+        fn remove_all_removes_a_single_target(
+            list in prop::collection::vec(1..4i32, 0..20),
+            position in any::<prop::sample::Index>(),
+        ) {
+            let mut list = list;
+            list.insert(position.index(list.len() + 1), 0);
+            prop_assert!(!remove_all(&list, 0).contains(&0));
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - The first failing input is random; proptest shrinks it before reporting it
+ * - Shrinking keeps trying simpler inputs that still fail, until none does
+ * - Numbers move toward zero, strings toward fewer and earlier characters,
+ *   collections toward fewer and smaller elements
+ * - The result is minimal one step at a time, not necessarily the smallest possible
+ * - A small counterexample usually points straight at the bug
+ */
+
+// EXPECTED:
+// n * 3 < 1000, for n in 0..10000
+//   first failure: 6991
+//   shrunk to:     334 (after 14 runs)
+// no string contains "ab", for [a-c]{0,20}
+//   first failure: "cbabbbcccacaac"
+//   shrunk to:     "ab" (after 19 runs)
+// every list sums to less than 100
+//   first failure: [20, 80, 58, 50, 18, 94, 32, 97, 34, 75, 66, 62, 32]
+//   shrunk to:     [38, 62] (after 29 runs)
+// remove_all leaves no target behind, for any::<i32>()
+//   held for every input tried
+// remove_all leaves no target behind, for 0..4
+//   first failure: ([0, 3, 2, 2, 0, 3, 1, 3, 1, 3, 2, 2, 1], 2)
+//   shrunk to:     ([2, 2], 2) (after 22 runs)
@@ -0,0 +1,201 @@
+// Property Testing 3: Invariants Worth Testing
+// Demonstrates oracles, idempotence, round trips, and output invariants
+//
+// Concepts: oracles, idempotence, round trips, invariants
+// Difficulty: intermediate
+// Minutes: 20
+//
+// "Write a property" is easier said than done when the function has no
+// simple formula to compare against. A few shapes of property cover most
+// code, though, and each needs only a line or two:
+//
+// - An oracle: compare with a slow or simple version known to be right.
+//   Our merge sort must agree with the standard library's sort.
+// - Idempotence: doing it twice is the same as doing it once. Sorting a
+//   sorted list, trimming a trimmed string, normalizing a normal path.
+// - A round trip: decoding what was encoded gives back the original.
+//   It catches most encoder and decoder bugs with one assertion.
+// - An invariant of the output: whatever the input, the result is sorted,
+//   or twice as long, or only uses certain characters.
+//
+// The last property below is wrong on purpose. from_hex() accepts capital
+// letters and to_hex() never writes them, so the round trip only works in
+// one direction, and proptest says so with a two-character string.
+
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestCaseResult, TestError, TestRng, TestRunner};
+
+fn merge_sort(values: &[i32]) -> Vec<i32> {
+    if values.len() <= 1 {
+        return values.to_vec();
+    }
+    let (left, right) = values.split_at(values.len() / 2);
+    let (left, right) = (merge_sort(left), merge_sort(right));
+    let mut merged = Vec::with_capacity(values.len());
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        if left[i] <= right[j] {
+            merged.push(left[i]);
+            i += 1;
+        } else {
+            merged.push(right[j]);
+            j += 1;
+        }
+    }
+    merged.extend_from_slice(&left[i..]);
+    merged.extend_from_slice(&right[j..]);
+    merged
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// Each property is an ordinary function: prop_assert! returns an error
+// instead of panicking, so proptest can shrink the input and try again
+
+fn agrees_with_std_sort(values: Vec<i32>) -> TestCaseResult {
+    let mut expected = values.clone();
+    expected.sort();
+    prop_assert_eq!(merge_sort(&values), expected);
+    Ok(())
+}
+
+fn sorting_is_idempotent(values: Vec<i32>) -> TestCaseResult {
+    let once = merge_sort(&values);
+    prop_assert_eq!(merge_sort(&once), once);
+    Ok(())
+}
+
+fn hex_round_trips(bytes: Vec<u8>) -> TestCaseResult {
+    prop_assert_eq!(from_hex(&to_hex(&bytes)), Some(bytes));
+    Ok(())
+}
+
+fn hex_is_twice_as_long_and_lowercase(bytes: Vec<u8>) -> TestCaseResult {
+    let hex = to_hex(&bytes);
+    prop_assert_eq!(hex.len(), bytes.len() * 2);
+    prop_assert!(hex
+        .bytes()
+        .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)));
+    Ok(())
+}
+
+/// Wrong: from_hex() also reads uppercase, which to_hex() doesn't write.
+fn hex_round_trips_from_text(text: String) -> TestCaseResult {
+    let bytes = from_hex(&text).expect("the strategy only generates valid hex");
+    prop_assert_eq!(to_hex(&bytes), text);
+    Ok(())
+}
+
+/// Hex strings of whole bytes, in either case.
+const HEX: &str = "([0-9a-fA-F]{2}){0,10}";
+
+fn check<S>(name: &str, strategy: S, property: impl Fn(S::Value) -> TestCaseResult)
+where
+    S: Strategy,
+{
+    let config = Config {
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let algorithm = config.rng_algorithm;
+    let mut runner = TestRunner::new_with_rng(config, TestRng::deterministic_rng(algorithm));
+    match runner.run(&strategy, property) {
+        Ok(()) => println!("ok      {}", name),
+        Err(TestError::Fail(_, minimal)) => {
+            println!("FAILED  {}, minimal failing input: {:?}", name, minimal)
+        }
+        Err(error) => println!("ERROR   {}: {}", name, error),
+    }
+}
+
+fn main() {
+    let lists = || prop::collection::vec(any::<i32>(), 0..50);
+    let bytes = || prop::collection::vec(any::<u8>(), 0..50);
+
+    check(
+        "merge_sort agrees with std's sort",
+        lists(),
+        agrees_with_std_sort,
+    );
+    check(
+        "sorting twice is sorting once",
+        lists(),
+        sorting_is_idempotent,
+    );
+    check("from_hex(to_hex(bytes)) == bytes", bytes(), hex_round_trips);
+    check(
+        "to_hex is twice as long and lowercase",
+        bytes(),
+        hex_is_twice_as_long_and_lowercase,
+    );
+    check(
+        "to_hex(from_hex(text)) == text",
+        HEX,
+        hex_round_trips_from_text,
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    prop_assert_eq!(to_hex(&[255]), "ff");
+    // error[E0308]: mismatched types (prop_assert! returns an Err, and main returns ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The same properties as tests. A failing case would be reported
+    // shrunk, and saved in proptest-regressions/ to be tried first next time
+    proptest! {
+        #[test]
+        fn merge_sort_is_correct(values in prop::collection::vec(any::<i32>(), 0..50)) {
+            agrees_with_std_sort(values.clone())?;
+            sorting_is_idempotent(values)?;
+        }
+
+        #[test]
+        fn hex_encoding_is_correct(bytes in prop::collection::vec(any::<u8>(), 0..50)) {
+            hex_round_trips(bytes.clone())?;
+            hex_is_twice_as_long_and_lowercase(bytes)?;
+        }
+
+        // The wrong property is right for lowercase input
+        #[test]
+        fn lowercase_hex_round_trips(text in "([0-9a-f]{2}){0,10}") {
+            hex_round_trips_from_text(text)?;
+        }
+
+        #[test]
+        fn odd_lengths_are_rejected(text in "[0-9a-f]([0-9a-f]{2}){0,10}") {
+            prop_assert_eq!(from_hex(&text), None);
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Oracle: agree with a simpler implementation that is known to be right
+ * - Idempotence: f(f(x)) == f(x) for sorting, trimming, normalizing
+ * - Round trip: decode(encode(x)) == x; check which direction really holds
+ * - Output invariants: sorted, a certain length, a certain alphabet
+ * - prop_assert! returns Err(TestCaseError), so properties return TestCaseResult
+ */
+
+// EXPECTED:
+// ok      merge_sort agrees with std's sort
+// ok      sorting twice is sorting once
+// ok      from_hex(to_hex(bytes)) == bytes
+// ok      to_hex is twice as long and lowercase
+// FAILED  to_hex(from_hex(text)) == text, minimal failing input: "0A"
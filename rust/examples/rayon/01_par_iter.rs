@@ -0,0 +1,125 @@
+// Rayon 1: From iter to par_iter
+// Demonstrates par_iter, into_par_iter, ordered collect, and what a closure must be to run in parallel
+//
+// Concepts: rayon, par_iter, into_par_iter, Send, Sync
+// Difficulty: intermediate
+// Minutes: 10
+//
+// rayon turns an iterator pipeline into a parallel one by changing one
+// word: `iter()` becomes `par_iter()`, `into_iter()` becomes
+// `into_par_iter()`. Behind the call is a pool of threads, one per CPU,
+// that split the input into pieces, each working through its own share,
+// and taking over pieces from busy threads when they run out ("work
+// stealing"). map, filter, sum, and collect mean what they always meant,
+// and collect() even keeps the order of the input.
+//
+// What changes is what the closures may do. They run on several threads
+// at once, so whatever they capture must be safe to share (Sync), and
+// they can't mutate anything they capture: the compiler checks both. A
+// pipeline that only reads its input and returns values is already in
+// that shape; one that pushes into a Vec from inside for_each is not.
+//
+// Parallel isn't free. Splitting the work and joining the results costs
+// time, so a pipeline that does a little work per item can be slower in
+// parallel; `cargo bench -p rayon-examples` shows by how much on your
+// machine. This program only checks that the answers agree.
+
+use std::collections::HashSet;
+
+use rayon::prelude::*;
+
+use pipelines::{is_prime, numbers, par_primes_below, par_sum_of_squares, primes_below};
+
+fn main() {
+    let numbers = numbers(1_000_000);
+
+    // The same pipeline twice, one word apart
+    let sequential: u64 = numbers.iter().map(|n| n * n).sum();
+    let parallel: u64 = numbers.par_iter().map(|n| n * n).sum();
+    println!("sum of squares:  {sequential}");
+    println!("in parallel:     {parallel}");
+    println!("library version: {}", par_sum_of_squares(&numbers));
+
+    // filter + collect on a range; the result is in order
+    let primes = par_primes_below(100_000);
+    println!(
+        "\n{} primes below 100,000, the last three {:?}",
+        primes.len(),
+        &primes[primes.len() - 3..]
+    );
+    println!("same as sequential: {}", primes == primes_below(100_000));
+
+    // Other adaptors work the same way
+    let largest = numbers.par_iter().copied().filter(|&n| is_prime(n)).max();
+    let has_two = numbers.par_iter().any(|&n| n == 2);
+    let distinct: HashSet<u64> = numbers.par_iter().map(|n| n % 1000).collect();
+    println!("\nlargest prime in the input: {largest:?}");
+    println!("is 2 in the input: {has_two}");
+    println!("distinct last three digits: {}", distinct.len());
+
+    // Results, not side effects: collect instead of pushing from for_each
+    let squares: Vec<u64> = (1..=10u64).into_par_iter().map(|n| n * n).collect();
+    println!("\nsquares: {squares:?}");
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let mut found = Vec::new();
+        numbers.par_iter().for_each(|&n| found.push(n));
+    }
+    // error[E0596]: cannot borrow `found` as mutable, as it is a captured variable in a `Fn` closure
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_and_sequential_agree() {
+        let numbers = numbers(10_000);
+        let sequential: Vec<u64> = numbers
+            .iter()
+            .map(|n| n * 3)
+            .filter(|n| n % 2 == 0)
+            .collect();
+        let parallel: Vec<u64> = numbers
+            .par_iter()
+            .map(|n| n * 3)
+            .filter(|n| n % 2 == 0)
+            .collect();
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn reductions_agree() {
+        let numbers = numbers(10_000);
+        assert_eq!(numbers.par_iter().max(), numbers.iter().max());
+        assert_eq!(
+            numbers.par_iter().filter(|&&n| is_prime(n)).count(),
+            numbers.iter().filter(|&&n| is_prime(n)).count()
+        );
+    }
+}
+
+/*
+ * Key Concepts:
+ * - iter() -> par_iter(), into_iter() -> into_par_iter(): the rest of the pipeline stays
+ * - A pool of one thread per CPU splits the input and steals work when idle
+ * - collect() keeps the input's order; sum, max, count, any reduce as before
+ * - Closures must be Fn and Send + Sync: return values instead of mutating captures
+ * - Parallelism has overhead; measure before assuming it helps
+ */
+
+// EXPECTED:
+// sum of squares:  333905047169030727
+// in parallel:     333905047169030727
+// library version: 333905047169030727
+//
+// 9592 primes below 100,000, the last three [99971, 99989, 99991]
+// same as sequential: true
+//
+// largest prime in the input: Some(999983)
+// is 2 in the input: true
+// distinct last three digits: 1000
+//
+// squares: [1, 4, 9, 16, 25, 36, 49, 64, 81, 100]
@@ -0,0 +1,134 @@
+// Rayon 2: Divide and Conquer with join
+// Demonstrates rayon::join, splitting a slice with split_at_mut, and a sequential cutoff
+//
+// Concepts: rayon::join, split_at_mut, divide and conquer, sequential cutoff
+// Difficulty: advanced
+// Minutes: 10
+//
+// Not every problem is an iterator. A quicksort splits its slice around a
+// pivot and sorts the two halves, which don't overlap, so they could be
+// sorted at the same time. rayon::join(a, b) runs two closures, possibly
+// on two threads, and returns both results once both are done. If no
+// thread is free, the current one runs b itself after a: join says "these
+// may run in parallel", not "start a thread", so calling it at every level
+// of a recursion is fine.
+//
+// The borrow checker is what makes this safe. split_at_mut hands out two
+// &mut slices that can't overlap, each closure gets one, and join may
+// only return once both closures are done, so neither borrow outlives the
+// call. Two closures borrowing the same slice mutably don't compile.
+//
+// Very small pieces aren't worth sending anywhere, so the parallel sort
+// switches to the sequential one below a cutoff. Where the cutoff should
+// be is a question for the benchmarks. (This quicksort is for reading;
+// the one to use is the slice method par_sort, from rayon's prelude.)
+
+use pipelines::{numbers, par_quicksort, quicksort, SEQUENTIAL_CUTOFF};
+
+/// Sums a slice by splitting it in two until the pieces are small.
+fn par_sum(values: &[u64]) -> u64 {
+    if values.len() <= SEQUENTIAL_CUTOFF {
+        return values.iter().sum();
+    }
+    let (left, right) = values.split_at(values.len() / 2);
+    let (a, b) = rayon::join(|| par_sum(left), || par_sum(right));
+    a + b
+}
+
+/// The n-th Fibonacci number, the slow recursive way, in parallel near the top.
+fn fibonacci(n: u32) -> u64 {
+    if n < 2 {
+        return n as u64;
+    }
+    if n < 20 {
+        return fibonacci(n - 1) + fibonacci(n - 2);
+    }
+    let (a, b) = rayon::join(|| fibonacci(n - 1), || fibonacci(n - 2));
+    a + b
+}
+
+fn main() {
+    // join returns both results
+    let (evens, odds) = rayon::join(
+        || (0..1000).filter(|n| n % 2 == 0).count(),
+        || (0..1000).filter(|n| n % 2 == 1).count(),
+    );
+    println!("evens {evens}, odds {odds}");
+
+    let values = numbers(200_000);
+    println!("\nsum by halves: {}", par_sum(&values));
+    println!("sum in order:  {}", values.iter().sum::<u64>());
+
+    println!("\nfibonacci(30) = {}", fibonacci(30));
+
+    // Two &mut halves, sorted at the same time
+    let mut sequential = values.clone();
+    let mut parallel = values.clone();
+    quicksort(&mut sequential);
+    par_quicksort(&mut parallel);
+    println!(
+        "\nsorted {} numbers, first {:?}, last {:?}",
+        parallel.len(),
+        &parallel[..3],
+        &parallel[parallel.len() - 3..]
+    );
+    println!("same as sequential: {}", parallel == sequential);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let mut data = vec![3, 1, 2];
+        rayon::join(|| data.sort(), || data.reverse());
+    }
+    // error[E0499]: cannot borrow `data` as mutable more than once at a time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_sum_matches_iter_sum() {
+        for size in [0, 1, SEQUENTIAL_CUTOFF + 1, 100_000] {
+            let values = numbers(size);
+            assert_eq!(par_sum(&values), values.iter().sum::<u64>());
+        }
+    }
+
+    #[test]
+    fn fibonacci_is_right_on_both_sides_of_the_cutoff() {
+        assert_eq!(fibonacci(10), 55);
+        assert_eq!(fibonacci(25), 75_025);
+    }
+
+    #[test]
+    fn par_quicksort_handles_sorted_and_reversed_input() {
+        let expected: Vec<u64> = (0..20_000).collect();
+        let mut sorted = expected.clone();
+        let mut reversed: Vec<u64> = expected.iter().rev().copied().collect();
+        par_quicksort(&mut sorted);
+        par_quicksort(&mut reversed);
+        assert_eq!(sorted, expected);
+        assert_eq!(reversed, expected);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - rayon::join(a, b) may run a and b in parallel and returns both results
+ * - It is cheap enough to call at every level of a recursion
+ * - split_at / split_at_mut give each closure its own, non-overlapping half
+ * - The borrow checker rejects two closures mutating the same data
+ * - Below a cutoff, do the work sequentially: tiny tasks cost more than they save
+ */
+
+// EXPECTED:
+// evens 500, odds 500
+//
+// sum by halves: 100342353610
+// sum in order:  100342353610
+//
+// fibonacci(30) = 832040
+//
+// sorted 200000 numbers, first [5, 7, 18], last [999985, 999994, 999998]
+// same as sequential: true
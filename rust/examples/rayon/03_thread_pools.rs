@@ -0,0 +1,152 @@
+// Rayon 3: Thread Pools and Scopes
+// Demonstrates ThreadPoolBuilder, install, current_num_threads, scope, and spawn
+//
+// Concepts: ThreadPoolBuilder, install, current_num_threads, scope, spawn
+// Difficulty: advanced
+// Minutes: 15
+//
+// par_iter and join run on rayon's global pool, which starts on first use
+// with one thread per CPU (or RAYON_NUM_THREADS, if that is set). Usually
+// that is what we want. Sometimes it isn't: a server may want its
+// background number crunching kept to a few threads, or a test may want
+// a known number of them. ThreadPoolBuilder builds a pool of our own, and
+// pool.install(|| ...) runs a closure inside it, so that every par_iter
+// and join called from that closure uses the pool's threads instead.
+//
+// rayon::scope is the other tool here. Inside a scope, s.spawn(...)
+// starts a task that may borrow from the surrounding function, because
+// the scope doesn't return until every task spawned in it has finished,
+// just like std::thread::scope. It suits work that isn't shaped like an
+// iterator: a few different jobs, or tasks that spawn more tasks.
+//
+// Which thread runs what, and in which order, changes from run to run, so
+// this program prints totals rather than thread ids.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+use pipelines::{is_prime, numbers};
+
+fn main() {
+    // A pool of exactly four threads, whatever the machine has
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(4)
+        .thread_name(|index| format!("cruncher-{index}"))
+        .build()
+        .expect("failed to start the thread pool");
+    println!("pool threads: {}", pool.current_num_threads());
+
+    // install runs the closure on the pool; rayon calls inside it use the pool
+    let primes = pool.install(|| {
+        println!("inside install: {} threads", rayon::current_num_threads());
+        (0..50_000u64)
+            .into_par_iter()
+            .filter(|&n| is_prime(n))
+            .count()
+    });
+    println!("primes below 50,000: {primes}");
+
+    // The same count on a one-thread pool: slower, same answer
+    let single = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+    let again = single.install(|| {
+        (0..50_000u64)
+            .into_par_iter()
+            .filter(|&n| is_prime(n))
+            .count()
+    });
+    println!("on one thread:       {again}");
+
+    // A scope: spawned tasks may borrow `values`, `total`, and `largest`
+    let values = numbers(100_000);
+    let total = AtomicUsize::new(0);
+    let largest = Mutex::new(0);
+    pool.scope(|s| {
+        for chunk in values.chunks(25_000) {
+            let (total, largest) = (&total, &largest);
+            s.spawn(move |_| {
+                total.fetch_add(chunk.len(), Ordering::Relaxed);
+                let chunk_max = chunk.iter().copied().max().unwrap_or(0);
+                let mut largest = largest.lock().unwrap();
+                *largest = (*largest).max(chunk_max);
+            });
+        }
+    });
+    // Every task has finished by the time scope returns
+    println!(
+        "\nscope saw {} values, the largest {}",
+        total.into_inner(),
+        largest.into_inner().unwrap()
+    );
+    println!("checked sequentially:  {}", values.iter().max().unwrap());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let counter = std::rc::Rc::new(0);
+        pool.install(|| println!("{counter}"));
+    }
+    // error[E0277]: `Rc<i32>` cannot be shared between threads safely
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_uses_the_pools_threads() {
+        let pool = ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+        assert_eq!(pool.install(rayon::current_num_threads), 3);
+    }
+
+    #[test]
+    fn every_spawned_task_finishes_before_scope_returns() {
+        let count = AtomicUsize::new(0);
+        rayon::scope(|s| {
+            for _ in 0..100 {
+                s.spawn(|_| {
+                    count.fetch_add(1, Ordering::Relaxed);
+                });
+            }
+        });
+        assert_eq!(count.into_inner(), 100);
+    }
+
+    #[test]
+    fn pools_of_any_size_agree() {
+        let count = |threads| {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                (0..5_000u64)
+                    .into_par_iter()
+                    .filter(|&n| is_prime(n))
+                    .count()
+            })
+        };
+        assert_eq!(count(1), 669);
+        assert_eq!(count(4), 669);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - par_iter and join use a global pool with one thread per CPU (RAYON_NUM_THREADS overrides)
+ * - ThreadPoolBuilder::new().num_threads(n).build() makes a pool of our own
+ * - pool.install(|| ...) runs rayon code inside that pool instead
+ * - rayon::scope / pool.scope let spawned tasks borrow, and wait for all of them
+ * - Anything shared with the pool must be Send + Sync: Rc isn't, Arc is
+ */
+
+// EXPECTED:
+// pool threads: 4
+// inside install: 4 threads
+// primes below 50,000: 5133
+// on one thread:       5133
+//
+// scope saw 100000 values, the largest 999998
+// checked sequentially:  999998
@@ -0,0 +1,137 @@
+// Rayon 4: A Parallel Word Count
+// Demonstrates counting words across a directory of files with par_iter, fold, and reduce
+//
+// Concepts: par_iter, fold, reduce, parallel file processing
+// Difficulty: advanced
+// Minutes: 10
+//
+// Counting the words in many files is the classic parallel job: each file
+// can be read and counted on its own, and only the totals need combining.
+// The obvious parallel version, every thread adding into one shared
+// HashMap behind a Mutex, works, but the threads spend their time waiting
+// for the lock. rayon's fold and reduce avoid the lock altogether:
+//
+//     fold:   each thread counts the files it gets into a map of its own
+//     reduce: the maps are merged, two at a time, until one is left
+//
+// Reading a file can fail, so the library uses try_fold and try_reduce,
+// which stop at the first error and return it. The texts are in `texts/`
+// next to this file; add some of your own and run it again (with --release
+// and a few hundred files, the difference starts to show).
+//
+// The word counts come back in a HashMap, whose order changes from run to
+// run, so the program sorts them before printing.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use rayon::prelude::*;
+
+use pipelines::{count_words, most_common, par_word_count, text_files, word_count};
+
+fn main() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("texts");
+    let paths = text_files(&dir).expect("cannot list the texts");
+    println!("{} files in texts/:", paths.len());
+    for path in &paths {
+        println!("  {}", path.file_name().unwrap().to_string_lossy());
+    }
+
+    let sequential = word_count(&paths).expect("cannot read the texts");
+    let parallel = par_word_count(&paths).expect("cannot read the texts");
+    let total: usize = parallel.values().sum();
+    println!("\n{total} words, {} different", parallel.len());
+    println!("sequential and parallel agree: {}", sequential == parallel);
+
+    println!("\nmost common:");
+    for (word, count) in most_common(&parallel, 8) {
+        println!("  {word:<8} {count}");
+    }
+
+    // The version with a lock: right answer, but every word waits its turn
+    let shared = Mutex::new(HashMap::new());
+    paths.par_iter().for_each(|path| {
+        let text = std::fs::read_to_string(path).unwrap();
+        let mut counts = HashMap::new();
+        count_words(&text, &mut counts);
+        let mut shared = shared.lock().unwrap();
+        for (word, count) in counts {
+            *shared.entry(word).or_insert(0) += count;
+        }
+    });
+    println!(
+        "\nwith a Mutex instead: agrees {}",
+        shared.into_inner().unwrap() == parallel
+    );
+
+    // Errors come back like the sequential version's
+    let mut missing = paths.clone();
+    missing.push(dir.join("missing.txt"));
+    match par_word_count(&missing) {
+        Ok(_) => println!("missing.txt was found?"),
+        Err(err) => println!("with a missing file: {:?}", err.kind()),
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        let mut counts = HashMap::new();
+        paths.par_iter().for_each(|path| {
+            count_words(&std::fs::read_to_string(path).unwrap(), &mut counts);
+        });
+    }
+    // error[E0596]: cannot borrow `counts` as mutable, as it is a captured variable in a `Fn` closure
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_texts_count_the_same_both_ways() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("texts");
+        let paths = text_files(&dir).unwrap();
+        let counts = par_word_count(&paths).unwrap();
+        assert_eq!(counts, word_count(&paths).unwrap());
+        assert!(counts.contains_key("the"));
+    }
+
+    #[test]
+    fn no_files_means_no_words() {
+        assert!(par_word_count(&[]).unwrap().is_empty());
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Files can be counted independently: a natural fit for par_iter
+ * - fold gives each thread its own accumulator; reduce merges them, with no locks
+ * - try_fold / try_reduce stop at the first error and return it
+ * - A Mutex-wrapped shared map also works, but serializes the threads
+ * - HashMap order isn't stable: sort before printing or comparing output
+ */
+
+// EXPECTED:
+// 5 files in texts/:
+//   fog.txt
+//   garden.txt
+//   river.txt
+//   station.txt
+//   winter.txt
+//
+// 196 words, 99 different
+// sequential and parallel agree: true
+//
+// most common:
+//   the      41
+//   and      14
+//   on       8
+//   city     4
+//   in       4
+//   snow     4
+//   to       4
+//   harbor   3
+//
+// with a Mutex instead: agrees true
+// with a missing file: NotFound
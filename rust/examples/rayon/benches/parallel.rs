@@ -0,0 +1,82 @@
+//! Each pipeline sequentially and with rayon, on a small input and a large
+//! one. The parallel versions win only when there is enough work per call
+//! to pay for splitting it up, and only on a machine with more than one
+//! CPU: on one, they measure pure overhead.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rayon::prelude::*;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::Duration;
+
+fn sums(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sum_of_squares");
+    for size in [1_000, 1_000_000] {
+        let numbers = pipelines::numbers(size);
+        group.bench_with_input(BenchmarkId::new("iter", size), &numbers, |b, numbers| {
+            b.iter(|| pipelines::sum_of_squares(black_box(numbers)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("par_iter", size),
+            &numbers,
+            |b, numbers| b.iter(|| pipelines::par_sum_of_squares(black_box(numbers))),
+        );
+    }
+    group.finish();
+}
+
+fn primes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("primes_below");
+    for limit in [1_000, 100_000] {
+        group.bench_with_input(BenchmarkId::new("iter", limit), &limit, |b, &limit| {
+            b.iter(|| pipelines::primes_below(black_box(limit)))
+        });
+        group.bench_with_input(BenchmarkId::new("par_iter", limit), &limit, |b, &limit| {
+            b.iter(|| pipelines::par_primes_below(black_box(limit)))
+        });
+    }
+    group.finish();
+}
+
+fn sorts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sort");
+    for size in [1_000, 200_000] {
+        let numbers = pipelines::numbers(size);
+        group.bench_with_input(
+            BenchmarkId::new("quicksort", size),
+            &numbers,
+            |b, numbers| b.iter(|| pipelines::quicksort(&mut numbers.clone())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("par_quicksort", size),
+            &numbers,
+            |b, numbers| b.iter(|| pipelines::par_quicksort(&mut numbers.clone())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("par_sort", size),
+            &numbers,
+            |b, numbers| b.iter(|| numbers.clone().par_sort()),
+        );
+    }
+    group.finish();
+}
+
+fn word_counts(c: &mut Criterion) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("texts");
+    let paths = pipelines::text_files(&dir).unwrap();
+    let mut group = c.benchmark_group("word_count");
+    group.bench_function("sequential", |b| {
+        b.iter(|| pipelines::word_count(black_box(&paths)).unwrap())
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| pipelines::par_word_count(black_box(&paths)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20).warm_up_time(Duration::from_millis(300)).measurement_time(Duration::from_secs(1));
+    targets = sums, primes, sorts, word_counts
+}
+criterion_main!(benches);
@@ -0,0 +1,216 @@
+//! Sequential pipelines and their rayon versions, for the rayon chapter.
+//!
+//! Each pair returns the same result, which the tests check; the numbered
+//! examples show how one becomes the other, and `benches/parallel.rs` times
+//! them against each other.
+//!
+//! ```
+//! let numbers: Vec<u64> = (1..=100).collect();
+//! assert_eq!(pipelines::sum_of_squares(&numbers), pipelines::par_sum_of_squares(&numbers));
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+/// Below this many elements, sorting in parallel isn't worth the overhead.
+pub const SEQUENTIAL_CUTOFF: usize = 2048;
+
+pub fn sum_of_squares(numbers: &[u64]) -> u64 {
+    numbers.iter().map(|n| n * n).sum()
+}
+
+/// The same pipeline with `iter` changed to `par_iter`.
+pub fn par_sum_of_squares(numbers: &[u64]) -> u64 {
+    numbers.par_iter().map(|n| n * n).sum()
+}
+
+/// Whether `n` is prime, by trial division: slow on purpose, so there is
+/// work worth sharing out.
+pub fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+pub fn primes_below(limit: u64) -> Vec<u64> {
+    (0..limit).filter(|&n| is_prime(n)).collect()
+}
+
+/// Parallel, and still in order: collect() puts each piece back in place.
+pub fn par_primes_below(limit: u64) -> Vec<u64> {
+    (0..limit)
+        .into_par_iter()
+        .filter(|&n| is_prime(n))
+        .collect()
+}
+
+/// Quicksort. Sorts the two sides of the pivot one after the other.
+pub fn quicksort<T: Ord + Send>(values: &mut [T]) {
+    if values.len() <= 1 {
+        return;
+    }
+    let middle = partition(values);
+    let (left, right) = values.split_at_mut(middle);
+    quicksort(left);
+    quicksort(&mut right[1..]);
+}
+
+/// Quicksort, sorting both sides at once with rayon::join. Small slices are
+/// sorted sequentially, where splitting them up would cost more than it
+/// saves.
+pub fn par_quicksort<T: Ord + Send>(values: &mut [T]) {
+    if values.len() <= SEQUENTIAL_CUTOFF {
+        return quicksort(values);
+    }
+    let middle = partition(values);
+    let (left, right) = values.split_at_mut(middle);
+    rayon::join(|| par_quicksort(left), || par_quicksort(&mut right[1..]));
+}
+
+/// Moves the middle element to its sorted position, with everything
+/// smaller before it, and returns that position.
+fn partition<T: Ord>(values: &mut [T]) -> usize {
+    let last = values.len() - 1;
+    values.swap(values.len() / 2, last);
+    let mut store = 0;
+    for i in 0..last {
+        if values[i] < values[last] {
+            values.swap(i, store);
+            store += 1;
+        }
+    }
+    values.swap(store, last);
+    store
+}
+
+/// The `.txt` files in `dir`, sorted by name.
+pub fn text_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Adds the words of `text`, lowercased and without punctuation, to `counts`.
+pub fn count_words(text: &str, counts: &mut HashMap<String, usize>) {
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        if !word.is_empty() {
+            *counts.entry(word.to_lowercase()).or_default() += 1;
+        }
+    }
+}
+
+/// How often each word appears in `paths`, one file after another.
+pub fn word_count(paths: &[PathBuf]) -> io::Result<HashMap<String, usize>> {
+    let mut counts = HashMap::new();
+    for path in paths {
+        count_words(&fs::read_to_string(path)?, &mut counts);
+    }
+    Ok(counts)
+}
+
+/// The same count, with the files read and counted in parallel. Each
+/// thread folds the files it gets into a map of its own, and reduce()
+/// merges the maps, so no thread ever waits on a lock.
+pub fn par_word_count(paths: &[PathBuf]) -> io::Result<HashMap<String, usize>> {
+    paths
+        .par_iter()
+        .map(fs::read_to_string)
+        .try_fold(HashMap::new, |mut counts, text| {
+            count_words(&text?, &mut counts);
+            Ok::<_, io::Error>(counts)
+        })
+        .try_reduce(HashMap::new, |mut total, counts| {
+            for (word, count) in counts {
+                *total.entry(word).or_default() += count;
+            }
+            Ok(total)
+        })
+}
+
+/// The `n` most common words, most common first, ties in alphabetical order.
+pub fn most_common(counts: &HashMap<String, usize>, n: usize) -> Vec<(&str, usize)> {
+    let mut words: Vec<(&str, usize)> = counts
+        .iter()
+        .map(|(word, &count)| (word.as_str(), count))
+        .collect();
+    words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    words.truncate(n);
+    words
+}
+
+/// Numbers for the examples and benchmarks, the same ones on every run.
+pub fn numbers(count: usize) -> Vec<u64> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..count)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % 1_000_000
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_sums_agree() {
+        let numbers = numbers(100_000);
+        assert_eq!(par_sum_of_squares(&numbers), sum_of_squares(&numbers));
+    }
+
+    #[test]
+    fn parallel_primes_are_in_order() {
+        assert_eq!(par_primes_below(10_000), primes_below(10_000));
+        assert_eq!(primes_below(20), [2, 3, 5, 7, 11, 13, 17, 19]);
+    }
+
+    #[test]
+    fn both_quicksorts_sort() {
+        let mut expected = numbers(50_000);
+        let mut sequential = expected.clone();
+        let mut parallel = expected.clone();
+        expected.sort();
+        quicksort(&mut sequential);
+        par_quicksort(&mut parallel);
+        assert_eq!(sequential, expected);
+        assert_eq!(parallel, expected);
+    }
+
+    #[test]
+    fn word_counts_agree() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("texts");
+        let paths = text_files(&dir).unwrap();
+        assert!(!paths.is_empty());
+        assert_eq!(par_word_count(&paths).unwrap(), word_count(&paths).unwrap());
+    }
+
+    #[test]
+    fn a_missing_file_is_an_error() {
+        let paths = [PathBuf::from("no such file.txt")];
+        assert!(word_count(&paths).is_err());
+        assert!(par_word_count(&paths).is_err());
+    }
+
+    #[test]
+    fn words_are_lowercased_and_stripped() {
+        let mut counts = HashMap::new();
+        count_words("The cat, the hat. THE end!", &mut counts);
+        assert_eq!(counts["the"], 3);
+        assert_eq!(most_common(&counts, 2), [("the", 3), ("cat", 1)]);
+    }
+}
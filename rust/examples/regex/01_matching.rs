@@ -0,0 +1,103 @@
+// Regex 1: Matching
+// Demonstrates Regex::new, is_match, find, find_iter, and compiling once
+//
+// Concepts: Regex::new, is_match, find, find_iter, compiling once
+// Difficulty: intermediate
+// Minutes: 10
+//
+// A regular expression describes a set of strings: `\d{4}` is any four
+// digits, `colou?r` is "color" or "colour". The regex crate compiles the
+// pattern into an automaton once, then runs it over text in time linear in
+// the text's length, whatever the pattern. That rules out features like
+// backreferences, which can take exponential time, but it means a regex
+// from a user can't hang the program.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+// Compiling is the slow part, so a regex used in a loop or a function
+// called often is built once. LazyLock builds it on first use.
+static WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\w+\b").unwrap());
+
+fn main() {
+    // Raw strings (r"...") keep backslashes as they are, so `\d` reaches
+    // the regex instead of being a (nonexistent) string escape
+    let year = Regex::new(r"\d{4}").unwrap();
+    for text in ["Rust 1.0 came out in 2015", "no year here", "12345"] {
+        println!(
+            "{:<28} is_match: {}",
+            format!("{:?}", text),
+            year.is_match(text)
+        );
+    }
+
+    // Without anchors a regex matches anywhere; ^ and $ pin it to the ends
+    let whole_year = Regex::new(r"^\d{4}$").unwrap();
+    println!("^\\d{{4}}$ on \"12345\": {}", whole_year.is_match("12345"));
+
+    // find() returns the leftmost match, with its byte offsets
+    let text = "order #4411 shipped on 2024-03-09";
+    if let Some(m) = Regex::new(r"#\d+").unwrap().find(text) {
+        println!("found {:?} at {}..{}", m.as_str(), m.start(), m.end());
+    }
+
+    // find_iter() walks every match, left to right, never overlapping
+    let numbers: Vec<&str> = Regex::new(r"\d+")
+        .unwrap()
+        .find_iter(text)
+        .map(|m| m.as_str())
+        .collect();
+    println!("every number: {:?}", numbers);
+
+    // Flags go at the start of the pattern: (?i) ignores case
+    let rust = Regex::new(r"(?i)\brust\b").unwrap();
+    let mentions = rust.find_iter("Rust, rust, RUST, and rusty").count();
+    println!("mentions of rust: {}", mentions);
+
+    // \w and \b understand Unicode by default
+    let words: Vec<&str> = WORD
+        .find_iter("naïve café, 東京!")
+        .map(|m| m.as_str())
+        .collect();
+    println!("words: {:?}", words);
+
+    // A bad pattern is an error, not a panic, which matters when the
+    // pattern comes from a user
+    let typed = String::from(r"(\d+");
+    match Regex::new(&typed) {
+        Ok(_) => println!("compiled?"),
+        Err(error) => {
+            let message = error.to_string();
+            println!(
+                "bad pattern: {}",
+                message.lines().last().unwrap_or_default()
+            );
+        }
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let found = Regex::new(r"\d+").is_match("42");
+    // error[E0599]: no method named `is_match` found for enum `Result<T, E>` in the current scope
+}
+
+/*
+ * Key Concepts:
+ * - Regex::new compiles a pattern and returns Result: patterns can be wrong
+ * - is_match answers yes or no; find and find_iter return Match values
+ * - A Match has as_str(), start(), and end() as byte offsets
+ * - ^ $ \b anchor a match; (?i) and other flags change how it matches
+ * - Compile once (LazyLock) instead of in every call
+ */
+
+// EXPECTED:
+// "Rust 1.0 came out in 2015"  is_match: true
+// "no year here"               is_match: false
+// "12345"                      is_match: true
+// ^\d{4}$ on "12345": false
+// found "#4411" at 6..11
+// every number: ["4411", "2024", "03", "09"]
+// mentions of rust: 3
+// words: ["naïve", "café", "東京"]
+// bad pattern: error: unclosed group
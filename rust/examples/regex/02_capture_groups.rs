@@ -0,0 +1,88 @@
+// Regex 2: Capture Groups
+// Demonstrates captures, numbered and named groups, captures_iter, and extract
+//
+// Concepts: captures, named groups, captures_iter, extract
+// Difficulty: intermediate
+// Minutes: 5
+//
+// Parentheses in a pattern do two things: they group, so `(ab)+` repeats
+// "ab", and they capture, remembering which part of the text the group
+// matched. Group 0 is the whole match and the rest are numbered by their
+// opening parenthesis. Naming them, `(?<year>\d{4})`, keeps code readable
+// when the pattern changes.
+
+use regex::Regex;
+
+fn main() {
+    // Numbered groups: caps[1] is the first group's text. Indexing panics
+    // if the group didn't take part in the match; get() returns an Option.
+    let date = Regex::new(r"(\d{4})-(\d{2})-(\d{2})").unwrap();
+    let caps = date.captures("released 2015-05-15, finally").unwrap();
+    println!("whole match: {}", &caps[0]);
+    println!("year {}, month {}, day {}", &caps[1], &caps[2], &caps[3]);
+
+    // Named groups, read by name
+    let version = Regex::new(r"v(?<major>\d+)\.(?<minor>\d+)(?:\.(?<patch>\d+))?").unwrap();
+    for text in ["tagged v1.82.0", "tagged v2.1"] {
+        let caps = version.captures(text).unwrap();
+        // An optional group that didn't match is None
+        let patch = caps.name("patch").map_or("none", |m| m.as_str());
+        println!(
+            "{:<14} -> major {}, minor {}, patch {}",
+            text, &caps["major"], &caps["minor"], patch
+        );
+    }
+
+    // captures_iter() gives the groups of every match in turn
+    let pair = Regex::new(r"(?<key>\w+)=(?<value>[^;]*)").unwrap();
+    let cookie = "theme=dark; lang=en; session=";
+    for caps in pair.captures_iter(cookie) {
+        println!("  {} = {:?}", &caps["key"], &caps["value"]);
+    }
+
+    // (?:...) groups without capturing, so the numbering skips it
+    let repeated = Regex::new(r"(?:ab)+(c)").unwrap();
+    let caps = repeated.captures("xxababc").unwrap();
+    println!("groups: {}, group 1: {}", caps.len(), &caps[1]);
+
+    // extract() turns a pattern whose groups always match into an array
+    let point = Regex::new(r"\((-?\d+), (-?\d+)\)").unwrap();
+    let points: Vec<(i32, i32)> = point
+        .captures_iter("path: (0, 0) (3, -4) (10, 2)")
+        .map(|caps| {
+            let (_, [x, y]) = caps.extract();
+            (x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect();
+    println!("points: {:?}", points);
+
+    // The names, in order, are on the Regex itself
+    let names: Vec<&str> = version.capture_names().flatten().collect();
+    println!("version groups: {:?}", names);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let major: &str = version.captures("v1.2").unwrap().name("major");
+    // error[E0308]: mismatched types (name() returns Option<Match>)
+}
+
+/*
+ * Key Concepts:
+ * - (...) captures; caps[0] is the whole match, caps[1] the first group
+ * - (?<name>...) names a group, read with caps["name"] or caps.name("name")
+ * - get()/name() return Option: an optional group may not have matched
+ * - (?:...) groups without capturing
+ * - captures_iter() for every match; extract() for fixed-shape patterns
+ */
+
+// EXPECTED:
+// whole match: 2015-05-15
+// year 2015, month 05, day 15
+// tagged v1.82.0 -> major 1, minor 82, patch 0
+// tagged v2.1    -> major 2, minor 1, patch none
+//   theme = "dark"
+//   lang = "en"
+//   session = ""
+// groups: 2, group 1: c
+// points: [(0, 0), (3, -4), (10, 2)]
+// version groups: ["major", "minor", "patch"]
@@ -0,0 +1,94 @@
+// Regex 3: Replacing and Splitting
+// Demonstrates replace, replace_all, $group references, closures, and split
+//
+// Concepts: replace, replace_all, $group references, split
+// Difficulty: intermediate
+// Minutes: 10
+//
+// replace_all finds every match and builds a new string with each one
+// swapped for a replacement. The replacement can refer to the match's
+// groups, as `$1` or `${name}`, or be a closure that computes it. Either
+// way the result is a Cow<str>: when nothing matched there was nothing to
+// replace, so we get the original text back without a copy.
+
+use std::borrow::Cow;
+
+use regex::{Captures, NoExpand, Regex};
+
+fn main() {
+    // replace() changes the first match only; replace_all() every one
+    let spaces = Regex::new(r"\s+").unwrap();
+    let messy = "too   many \t spaces";
+    println!("replace:     {:?}", spaces.replace(messy, " "));
+    println!("replace_all: {:?}", spaces.replace_all(messy, " "));
+
+    // Groups in the replacement: reorder a date from M/D/YYYY to ISO
+    let us_date = Regex::new(r"(?<m>\d{1,2})/(?<d>\d{1,2})/(?<y>\d{4})").unwrap();
+    let text = "due 3/9/2024, paid 12/31/2024";
+    println!("{}", us_date.replace_all(text, "${y}-${m}-${d}"));
+
+    // `$name` reads as long a name as it can, so `$1x` is group "1x";
+    // braces end the name
+    let word = Regex::new(r"(\w+)").unwrap();
+    println!("{}", word.replace_all("a b", "${1}x"));
+
+    // A closure computes each replacement from the Captures
+    let price = Regex::new(r"\$(\d+)\.(\d{2})").unwrap();
+    let doubled = price.replace_all("tea $3.50, cake $4.25", |caps: &Captures| {
+        let cents: u32 = caps[1].parse::<u32>().unwrap() * 100 + caps[2].parse::<u32>().unwrap();
+        format!("${}.{:02}", cents * 2 / 100, cents * 2 % 100)
+    });
+    println!("{}", doubled);
+
+    // NoExpand inserts the text as it is, `$` included
+    let name = Regex::new(r"NAME").unwrap();
+    println!("{}", name.replace_all("hello NAME", NoExpand("$USER")));
+
+    // Cow tells us whether anything changed
+    for text in ["unchanged", "one  two"] {
+        let kind = match spaces.replace_all(text, " ") {
+            Cow::Borrowed(_) => "borrowed",
+            Cow::Owned(_) => "owned",
+        };
+        println!("{:?} -> {}", text, kind);
+    }
+
+    // split() cuts the text at every match
+    let separator = Regex::new(r"\s*[,;]\s*").unwrap();
+    let fields: Vec<&str> = separator.split("a , b;c ;  d").collect();
+    println!("fields: {:?}", fields);
+
+    // Redacting: keep the shape of what was hidden
+    let email = Regex::new(r"(?<user>[\w.]+)@(?<host>[\w.]+)").unwrap();
+    let log = "login ana.lima@example.com, then bo@test.org";
+    let redacted = email.replace_all(log, |caps: &Captures| {
+        format!("{}***@{}", &caps["user"][..1], &caps["host"])
+    });
+    println!("{}", redacted);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let fixed: String = spaces.replace_all(messy, " ");
+    // error[E0308]: mismatched types (expected `String`, found `Cow<'_, str>`)
+}
+
+/*
+ * Key Concepts:
+ * - replace() changes the first match, replace_all() every match
+ * - $1 and ${name} in the replacement refer to groups; use braces to end a name
+ * - A closure taking &Captures computes each replacement
+ * - NoExpand inserts text literally; results are Cow<str>, borrowed if unchanged
+ * - split() breaks text apart wherever the regex matches
+ */
+
+// EXPECTED:
+// replace:     "too many \t spaces"
+// replace_all: "too many spaces"
+// due 2024-3-9, paid 2024-12-31
+// ax bx
+// tea $7.00, cake $8.50
+// hello $USER
+// "unchanged" -> borrowed
+// "one  two" -> owned
+// fields: ["a", "b", "c", "d"]
+// login a***@example.com, then b***@test.org
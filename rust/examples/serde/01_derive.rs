@@ -0,0 +1,128 @@
+// Serde 1: Deriving Serialize and Deserialize
+// Demonstrates turning structs into JSON and back, and what errors look like
+//
+// Concepts: Serialize, Deserialize, serde_json, deserialization errors
+// Difficulty: intermediate
+// Minutes: 10
+//
+// serde itself knows nothing about JSON. It defines two traits, Serialize
+// and Deserialize, and every format (serde_json, toml, bincode, ...) is a
+// separate crate that works with any type implementing them. The derives
+// write those implementations for us, field by field.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Book {
+    title: String,
+    year: u16,
+    authors: Vec<String>,
+    // Option becomes `null` when it's None
+    isbn: Option<String>,
+}
+
+// A field's type must implement the traits too, so nested structs derive
+// them as well
+#[derive(Debug, Serialize, Deserialize)]
+struct Library {
+    name: String,
+    books: Vec<Book>,
+}
+
+// This would cause an error:
+#[cfg(feature = "broken")]
+#[derive(Serialize)]
+struct Timer {
+    started: std::time::Instant,
+}
+// error[E0277]: the trait bound `Instant: serde::Serialize` is not satisfied
+
+fn main() {
+    let book = Book {
+        title: String::from("The Rust Programming Language"),
+        year: 2018,
+        authors: vec![String::from("Steve Klabnik"), String::from("Carol Nichols")],
+        isbn: None,
+    };
+
+    // Rust value -> JSON text
+    let json = serde_json::to_string(&book).unwrap();
+    println!("{}", json);
+
+    // JSON text -> Rust value. The target type tells serde what to expect,
+    // so it has to be written down (here with a type annotation).
+    let back: Book = serde_json::from_str(&json).unwrap();
+    println!("round trip equal: {}", back == book);
+
+    // to_string_pretty indents nested values
+    let library = Library {
+        name: String::from("Shelf"),
+        books: vec![back],
+    };
+    println!("{}", serde_json::to_string_pretty(&library).unwrap());
+
+    // Wrong input is an Err, never a panic. The message says what was
+    // expected, and where.
+    let errors = [
+        r#"{"title": "Dune", "year": "1965", "authors": [], "isbn": null}"#,
+        r#"{"title": "Dune", "authors": [], "isbn": null}"#,
+        r#"{"title": "Dune", "year": 1965, "authors": []"#,
+    ];
+    for text in errors {
+        match serde_json::from_str::<Book>(text) {
+            Ok(book) => println!("parsed {:?}", book),
+            Err(err) => println!("error: {}", err),
+        }
+    }
+    // A missing Option field is fine: it becomes None
+    let short: Book =
+        serde_json::from_str(r#"{"title": "Dune", "year": 1965, "authors": []}"#).unwrap();
+    println!("isbn when missing: {:?}", short.isbn);
+
+    // When the shape isn't known in advance, serde_json::Value holds any
+    // JSON. The json! macro builds one with JSON syntax.
+    let value = serde_json::json!({
+        "name": "ferris",
+        "tags": ["crab", "mascot"],
+        "age": 9
+    });
+    println!("name = {}, first tag = {}", value["name"], value["tags"][0]);
+    // Indexing something that isn't there gives Null instead of panicking
+    println!("missing = {}", value["nope"]["deeper"]);
+    // as_* methods get a typed value out, if the JSON has the right type
+    println!("age + 1 = {:?}", value["age"].as_u64().map(|age| age + 1));
+}
+
+/*
+ * Key Concepts:
+ * - #[derive(Serialize, Deserialize)] implements serde's traits for a type
+ * - serde_json::to_string / to_string_pretty turn values into JSON
+ * - serde_json::from_str needs the target type, and returns a Result
+ * - Option fields accept null or a missing key
+ * - serde_json::Value and json! handle JSON of unknown shape
+ */
+
+// EXPECTED:
+// {"title":"The Rust Programming Language","year":2018,"authors":["Steve Klabnik","Carol Nichols"],"isbn":null}
+// round trip equal: true
+// {
+//   "name": "Shelf",
+//   "books": [
+//     {
+//       "title": "The Rust Programming Language",
+//       "year": 2018,
+//       "authors": [
+//         "Steve Klabnik",
+//         "Carol Nichols"
+//       ],
+//       "isbn": null
+//     }
+//   ]
+// }
+// error: invalid type: string "1965", expected u16 at line 1 column 32
+// error: missing field `year` at line 1 column 46
+// error: EOF while parsing an object at line 1 column 45
+// isbn when missing: None
+// name = "ferris", first tag = "crab"
+// missing = null
+// age + 1 = Some(10)
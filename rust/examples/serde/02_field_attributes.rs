@@ -0,0 +1,143 @@
+// Serde 2: Field Attributes
+// Demonstrates renaming, defaults, skipping, flattening, and custom field code
+//
+// Concepts: rename, default, skip, flatten, with
+// Difficulty: intermediate
+// Minutes: 10
+//
+// JSON written by other programs rarely uses Rust's naming style or has
+// exactly the fields we want. #[serde(...)] attributes adjust the derived
+// code, so the Rust struct can stay idiomatic while the JSON is whatever it
+// has to be.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+// rename_all changes every field name: `display_name` <-> `displayName`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    display_name: String,
+    // rename sets one name; `type` is a keyword in Rust, so this is common
+    #[serde(rename = "type")]
+    kind: String,
+    // default fills in Default::default() (here `false`) when the key is
+    // missing, instead of failing
+    #[serde(default)]
+    is_admin: bool,
+    // skip_serializing_if leaves the key out of the JSON entirely
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<String>,
+    // skip: never read or written. Deserializing uses Default.
+    #[serde(skip)]
+    session_cache: Option<String>,
+}
+
+fn default_port() -> u16 {
+    8080
+}
+
+// deny_unknown_fields turns a typo in the input into an error instead of
+// silently ignoring it
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ServerConfig {
+    host: String,
+    // default can name a function for a value other than Default's
+    #[serde(default = "default_port")]
+    port: u16,
+    // alias accepts an old name too, when reading
+    #[serde(alias = "timeout")]
+    timeout_secs: u64,
+    // deserialize_with runs our own function for one field
+    #[serde(deserialize_with = "comma_separated")]
+    mirrors: Vec<String>,
+}
+
+/// Reads `"a, b, c"` as `["a", "b", "c"]`. It deserializes a plain String
+/// first, then does its own work; errors go through serde's error type.
+fn comma_separated<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    if text.trim().is_empty() {
+        return Err(serde::de::Error::custom("expected at least one mirror"));
+    }
+    Ok(text
+        .split(',')
+        .map(|part| part.trim().to_string())
+        .collect())
+}
+
+// flatten merges the fields of another struct (or a map) into this one.
+// A map collects every key the struct doesn't name.
+#[derive(Debug, Serialize, Deserialize)]
+struct Event {
+    id: u32,
+    #[serde(flatten)]
+    extra: BTreeMap<String, serde_json::Value>,
+}
+
+fn main() {
+    let user: User = serde_json::from_str(r#"{"displayName": "Ferris", "type": "crab"}"#).unwrap();
+    println!("{:?}", user);
+    println!("{}", serde_json::to_string(&user).unwrap());
+
+    let admin = User {
+        display_name: String::from("Root"),
+        kind: String::from("human"),
+        is_admin: true,
+        roles: vec![String::from("ops")],
+        session_cache: Some(String::from("secret")),
+    };
+    // session_cache is skipped, roles is written because it isn't empty
+    println!("{}", serde_json::to_string(&admin).unwrap());
+    println!("cache is kept in memory: {:?}", admin.session_cache);
+
+    let config: ServerConfig = serde_json::from_str(
+        r#"{"host": "example.com", "timeout": 30, "mirrors": "eu.example.com, us.example.com"}"#,
+    )
+    .unwrap();
+    println!(
+        "{}:{}, timeout {}s, mirrors {:?}",
+        config.host, config.port, config.timeout_secs, config.mirrors
+    );
+
+    let typo = serde_json::from_str::<ServerConfig>(
+        r#"{"host": "example.com", "prot": 80, "timeout_secs": 5, "mirrors": "a"}"#,
+    );
+    println!("typo: {}", typo.unwrap_err());
+    let no_mirrors = serde_json::from_str::<ServerConfig>(
+        r#"{"host": "example.com", "timeout_secs": 5, "mirrors": " "}"#,
+    );
+    println!("custom: {}", no_mirrors.unwrap_err());
+
+    let event: Event =
+        serde_json::from_str(r#"{"id": 7, "source": "sensor", "reading": 21.5}"#).unwrap();
+    println!("id {} with extra keys {:?}", event.id, event.extra.keys());
+    // Serializing puts the flattened keys back at the top level
+    println!("{}", serde_json::to_string(&event).unwrap());
+}
+
+/*
+ * Key Concepts:
+ * - rename / rename_all map Rust names to the JSON's names
+ * - default and default = "fn" make keys optional
+ * - skip and skip_serializing_if control what gets written
+ * - deny_unknown_fields rejects keys the struct doesn't know
+ * - deserialize_with plugs a custom function into one field
+ * - flatten merges another struct or a catch-all map into the object
+ */
+
+// EXPECTED:
+// User { display_name: "Ferris", kind: "crab", is_admin: false, roles: [], session_cache: None }
+// {"displayName":"Ferris","type":"crab","isAdmin":false}
+// {"displayName":"Root","type":"human","isAdmin":true,"roles":["ops"]}
+// cache is kept in memory: Some("secret")
+// example.com:8080, timeout 30s, mirrors ["eu.example.com", "us.example.com"]
+// typo: unknown field `prot`, expected one of `host`, `port`, `timeout`, `timeout_secs`, `mirrors` at line 1 column 30
+// custom: expected at least one mirror at line 1 column 58
+// id 7 with extra keys ["reading", "source"]
+// {"id":7,"reading":21.5,"source":"sensor"}
@@ -0,0 +1,133 @@
+// Serde 3: Enum Representations
+// Demonstrates the four ways serde can write an enum as JSON
+//
+// Concepts: externally tagged, internally tagged, adjacently tagged, untagged
+// Difficulty: intermediate
+// Minutes: 10
+//
+// JSON has no enums, so serde has to pick a shape. The default wraps the
+// data in an object keyed by the variant's name; the attributes below
+// pick the other common conventions, usually to match an existing API.
+
+use learn_rust_core::section;
+use serde::{Deserialize, Serialize};
+
+// Externally tagged (the default): {"Variant": data}, or "Variant" for a
+// unit variant
+#[derive(Debug, Serialize, Deserialize)]
+enum Shape {
+    Circle { radius: f64 },
+    Square(f64),
+    Point,
+}
+
+// Internally tagged: the tag is a field inside the object. This is the
+// usual shape of messages in a JSON API.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Message {
+    Join { user: String },
+    Say { user: String, text: String },
+    Leave { user: String },
+}
+
+// Adjacently tagged: tag and content side by side. Unlike internal tags,
+// this works for variants whose data isn't an object, like Stream(u32).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data")]
+enum Source {
+    File(String),
+    Stream(u32),
+    Stdin,
+}
+
+// Untagged: no tag at all. serde tries each variant in order and keeps the
+// first that fits, so a number becomes Number and text becomes Text.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Setting {
+    Flag(bool),
+    Number(i64),
+    Text(String),
+    List(Vec<Setting>),
+}
+
+// Internal tags only work for variants that hold an object (or nothing).
+// This compiles, but serializing Count(3) fails at runtime, because there is no
+// object to put the "type" field in:
+// #[serde(tag = "type")]
+// enum Broken { Count(u32) }
+// cannot serialize tagged newtype variant Broken::Count containing an integer
+
+fn main() {
+    section("external (default)");
+    for shape in [
+        Shape::Circle { radius: 1.5 },
+        Shape::Square(2.0),
+        Shape::Point,
+    ] {
+        println!("{}", serde_json::to_string(&shape).unwrap());
+    }
+
+    section("internal: tag = \"type\"");
+    let message = Message::Say {
+        user: String::from("ana"),
+        text: String::from("hi"),
+    };
+    println!("{}", serde_json::to_string(&message).unwrap());
+    let incoming = [
+        r#"{"type": "join", "user": "bo"}"#,
+        r#"{"user": "bo", "type": "leave"}"#,
+        r#"{"type": "shout", "user": "bo"}"#,
+    ];
+    for text in incoming {
+        match serde_json::from_str::<Message>(text) {
+            Ok(message) => println!("{:?}", message),
+            Err(err) => println!("error: {}", err),
+        }
+    }
+
+    section("adjacent: tag = \"kind\", content = \"data\"");
+    for source in [
+        Source::File(String::from("log.txt")),
+        Source::Stream(3),
+        Source::Stdin,
+    ] {
+        println!("{}", serde_json::to_string(&source).unwrap());
+    }
+
+    section("untagged");
+    let settings: Vec<Setting> = serde_json::from_str(r#"[true, 42, "dark", [1, "two"]]"#).unwrap();
+    println!("{:?}", settings);
+    // 1.5 is not a bool, an i64, a string, or a list. With no tag to go
+    // by, the error can't say which variant was meant.
+    let error = serde_json::from_str::<Setting>("1.5").unwrap_err();
+    println!("error: {}", error);
+}
+
+/*
+ * Key Concepts:
+ * - By default an enum is {"Variant": data} (externally tagged)
+ * - #[serde(tag = "...")] puts the variant name inside the object
+ * - #[serde(tag = "...", content = "...")] keeps tag and data apart
+ * - #[serde(untagged)] has no tag; the first variant that fits wins
+ * - An unknown tag is a clear error, which makes tags safer than untagged
+ */
+
+// EXPECTED:
+// -- external (default) --
+// {"Circle":{"radius":1.5}}
+// {"Square":2.0}
+// "Point"
+// -- internal: tag = "type" --
+// {"type":"say","user":"ana","text":"hi"}
+// Join { user: "bo" }
+// Leave { user: "bo" }
+// error: unknown variant `shout`, expected one of `join`, `say`, `leave` at line 1 column 16
+// -- adjacent: tag = "kind", content = "data" --
+// {"kind":"File","data":"log.txt"}
+// {"kind":"Stream","data":3}
+// {"kind":"Stdin"}
+// -- untagged --
+// [Flag(true), Number(42), Text("dark"), List([Number(1), Text("two")])]
+// error: data did not match any variant of untagged enum Setting
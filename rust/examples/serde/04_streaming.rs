@@ -0,0 +1,138 @@
+// Serde 4: Streaming Large JSON
+// Demonstrates reading and writing JSON piece by piece instead of all at once
+//
+// Concepts: StreamDeserializer, serializing piece by piece, large inputs
+// Difficulty: advanced
+// Minutes: 10
+//
+// from_str needs the whole document in memory, and Vec<T> needs every item
+// at once. For a log with a million lines, or one huge array, that's a
+// waste: we only ever look at one item at a time. serde_json can work on
+// readers and writers, hand out values as a stream, and let a Visitor see
+// an array's elements one by one.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+
+use serde::de::{Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Request {
+    path: String,
+    status: u16,
+    bytes: u64,
+}
+
+/// What we want to know about a list of requests. Nothing in it grows with
+/// the number of requests.
+#[derive(Debug, Default)]
+struct Totals {
+    requests: u64,
+    errors: u64,
+    bytes: u64,
+}
+
+/// A Visitor is told about the JSON as the deserializer reads it. Asked for
+/// a sequence, it pulls one element at a time out of SeqAccess, adds it to
+/// the totals, and drops it.
+struct TotalsVisitor;
+
+impl<'de> Visitor<'de> for TotalsVisitor {
+    type Value = Totals;
+
+    // Used in the error message when the JSON isn't an array
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of requests")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Totals, A::Error> {
+        let mut totals = Totals::default();
+        while let Some(request) = seq.next_element::<Request>()? {
+            totals.requests += 1;
+            totals.errors += u64::from(request.status >= 500);
+            totals.bytes += request.bytes;
+        }
+        Ok(totals)
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // Newline-delimited JSON: one value per line, common for logs. A
+    // StreamDeserializer yields them one at a time from any reader.
+    let log = r#"{"path": "/", "status": 200, "bytes": 512}
+{"path": "/login", "status": 302, "bytes": 0}
+{"path": "/api", "status": 503, "bytes": 19}
+{"path": "/api", "status": "oops", "bytes": 19}
+{"path": "/never-reached", "status": 200, "bytes": 1}"#;
+    let stream = serde_json::Deserializer::from_reader(log.as_bytes()).into_iter::<Request>();
+    for (line, result) in stream.enumerate() {
+        match result {
+            Ok(request) => println!("line {}: {} {}", line + 1, request.status, request.path),
+            // After an error the stream can't find its footing again, so
+            // it ends
+            Err(err) => println!("line {}: {}", line + 1, err),
+        }
+    }
+
+    // Writing: to_writer serializes straight into a writer, so a large
+    // document is never built up as one String. BufWriter batches the
+    // small writes into few system calls.
+    let path = std::env::temp_dir().join(format!("serde-streaming-{}.json", std::process::id()));
+    let mut out = BufWriter::new(File::create(&path)?);
+    out.write_all(b"[")?;
+    for i in 0..100_000u64 {
+        if i > 0 {
+            out.write_all(b",")?;
+        }
+        let request = Request {
+            path: format!("/item/{}", i % 100),
+            status: if i % 250 == 0 { 500 } else { 200 },
+            bytes: i % 1000,
+        };
+        serde_json::to_writer(&mut out, &request)?;
+    }
+    out.write_all(b"]")?;
+    // Dropping a BufWriter flushes it too, but silently ignores errors
+    out.flush()?;
+    drop(out);
+    println!("wrote {} bytes of JSON", fs::metadata(&path)?.len());
+
+    // Reading: with a Visitor, memory use stays flat however long the array
+    // is. Compare: `let all: Vec<Request> = serde_json::from_reader(...)`
+    // keeps all 100,000 requests alive at once.
+    let reader = BufReader::new(File::open(&path)?);
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let totals = deserializer.deserialize_seq(TotalsVisitor)?;
+    // end() checks that nothing but whitespace follows the array
+    deserializer.end()?;
+    println!("{:?}", totals);
+
+    let not_an_array = serde_json::Deserializer::from_str(r#"{"path": "/"}"#)
+        .deserialize_seq(TotalsVisitor)
+        .unwrap_err();
+    println!("error: {}", not_an_array);
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - from_reader and to_writer work on any Read / Write, without a String
+ * - Wrap files in BufReader / BufWriter; flush() reports write errors
+ * - into_iter::<T>() streams newline-delimited or back-to-back values
+ * - A Visitor with SeqAccess handles one array element at a time
+ * - end() rejects trailing garbage after the value
+ */
+
+// EXPECTED:
+// line 1: 200 /
+// line 2: 302 /login
+// line 3: 503 /api
+// line 4: invalid type: string "oops", expected u16 at line 4 column 33
+// wrote 4479001 bytes of JSON
+// Totals { requests: 100000, errors: 400, bytes: 49950000 }
+// error: invalid type: map, expected an array of requests at line 1 column 0
@@ -0,0 +1,100 @@
+// Signals 1: Ctrl-C with the ctrlc Crate
+// Demonstrates catching Ctrl-C and SIGTERM with the ctrlc crate, and using a flag to stop a job loop between jobs instead of in the middle of one
+//
+// Concepts: SIGINT, SIGTERM, ctrlc::set_handler, AtomicBool as a stop flag, exit code 130
+// Difficulty: intermediate
+// Minutes: 15
+//
+// Pressing Ctrl-C doesn't stop a program directly: the terminal sends it
+// a signal, SIGINT, and by default the operating system ends a process
+// that gets one on the spot. `kill PID`, `docker stop`, and systemd send
+// SIGTERM instead, with the same default. Either way the program stops
+// wherever it was: halfway through writing a file, say, leaving it cut off.
+//
+// A program can ask to handle a signal itself. What a raw signal handler
+// may do is very limited, because it interrupts the program anywhere,
+// even in the middle of `malloc`: it can't lock, allocate, or print.
+// The ctrlc crate works around that. Its real handler only wakes up a
+// thread of ctrlc's own, and that thread runs the closure you give
+// `set_handler`, where everything is allowed. With the "termination"
+// feature, as in this chapter's Cargo.toml, SIGTERM runs it too.
+//
+// The usual closure just sets a flag. The program checks it between
+// jobs, finishes the one it's on, and stops cleanly. A second Ctrl-C
+// means someone doesn't want to wait, so that one quits at once. The exit
+// code 130, 128 + the number of SIGINT, tells a shell or a script that
+// the program was interrupted, even though it cleaned up first.
+//
+// (SIGKILL, `kill -9`, can't be handled: that one always ends the process
+// on the spot. Graceful shutdown is for the signals that ask.)
+//
+// Run it and press Ctrl-C during a job, then run it and press it twice.
+// Left alone, it does all five jobs.
+
+use std::process::{self, ExitCode};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const JOBS: u32 = 5;
+
+/// How long each job takes.
+const JOB_TIME: Duration = Duration::from_millis(250);
+
+fn main() -> ExitCode {
+    let stopping = Arc::new(AtomicBool::new(false));
+
+    // The closure runs on ctrlc's thread, so what it shares with the rest
+    // of the program has to be Send, like anything given to thread::spawn.
+    let flag = Arc::clone(&stopping);
+    ctrlc::set_handler(move || {
+        if flag.swap(true, Ordering::SeqCst) {
+            println!("Ctrl-C again: quitting now");
+            process::exit(130);
+        }
+        println!("Ctrl-C: stopping after this job (press it again to quit now)");
+    })
+    .expect("no other handler is installed");
+
+    // The handler needs Send: an Rc can't cross to ctrlc's thread
+    // This would cause an error:
+    // let pressed = std::rc::Rc::new(std::cell::Cell::new(false));
+    // ctrlc::set_handler(move || pressed.set(true));
+    // error[E0277]: `Rc<Cell<bool>>` cannot be sent between threads safely
+
+    for job in 1..=JOBS {
+        if stopping.load(Ordering::SeqCst) {
+            println!("Stopped after {} of {JOBS} jobs.", job - 1);
+            return ExitCode::from(130);
+        }
+        println!("job {job} of {JOBS}: started");
+        thread::sleep(JOB_TIME);
+        println!("job {job} of {JOBS}: done");
+    }
+    println!("All {JOBS} jobs done.");
+    ExitCode::SUCCESS
+}
+
+/*
+ * Key Concepts:
+ * - Ctrl-C sends SIGINT, and kill sends SIGTERM; by default either ends the process wherever it is
+ * - ctrlc::set_handler runs its closure on a thread of its own, where anything is allowed
+ * - The "termination" feature makes the same handler catch SIGTERM
+ * - The handler sets an AtomicBool, and the work loop checks it between jobs
+ * - A second Ctrl-C should quit at once; exit code 130 says "interrupted"
+ * - SIGKILL can't be caught
+ */
+
+// EXPECTED:
+// job 1 of 5: started
+// job 1 of 5: done
+// job 2 of 5: started
+// job 2 of 5: done
+// job 3 of 5: started
+// job 3 of 5: done
+// job 4 of 5: started
+// job 4 of 5: done
+// job 5 of 5: started
+// job 5 of 5: done
+// All 5 jobs done.
@@ -0,0 +1,161 @@
+// Signals 2: Shutting Down an Async Service
+// Demonstrates waiting for Ctrl-C or SIGTERM with tokio::signal, telling tasks to stop taking work through a watch channel, and draining the work already in flight
+//
+// Concepts: tokio::signal::ctrl_c, signal::unix::signal, SignalKind, watch channel, select!, draining a JoinSet
+// Difficulty: advanced
+// Minutes: 20
+//
+// In async code, tokio turns a signal into something to `.await`:
+// `tokio::signal::ctrl_c()` completes on Ctrl-C, everywhere, and on Unix
+// `signal(SignalKind::terminate())` gives a stream of SIGTERMs to
+// `recv()` from. `select!` over the two waits for whichever comes first.
+//
+// The service here is a shop: the intake task accepts an order every
+// 100ms and spawns a task to ship it, which takes a second. When a
+// signal arrives, shutting down in order means:
+//
+// 1. Stop taking new work. The intake task waits in a `select!` for
+//    either its next order or a change on a `watch` channel, the usual
+//    way to tell any number of tasks "stop now": every receiver sees the
+//    value the sender last sent.
+// 2. Finish the work already accepted. Every shipping task is in a
+//    JoinSet, and awaiting `join_next` until it returns None waits for
+//    the last of them. A customer whose order was accepted gets it.
+// 3. Only then return from main. Returning earlier drops the runtime,
+//    which cancels every task still running, halfway through or not.
+//
+// One catch: a signal handler is installed when `signal()` is called,
+// but `ctrl_c()` only installs its handler when first polled. Until a
+// handler is in place, Ctrl-C still kills the process the default way,
+// so a service should start listening before it says it's ready.
+//
+// So that it ends by itself when the tutor runs it, the example presses
+// a pretend Ctrl-C after the fifth order. Pass --wait to make it wait
+// for a real Ctrl-C or SIGTERM instead:
+//
+//     cargo run -p signals-examples --bin 02_tokio_signal -- --wait
+
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{watch, Notify};
+use tokio::task::JoinSet;
+use tokio::time;
+
+/// How often a customer places an order.
+const ORDER_EVERY: Duration = Duration::from_millis(100);
+
+/// How long an order takes to ship.
+const SHIPPING_TIME: Duration = Duration::from_secs(1);
+
+/// After how many orders the pretend Ctrl-C comes.
+const PRETEND_AFTER: u32 = 5;
+
+/// Resolves with the name of the signal, on Ctrl-C or, on Unix, SIGTERM.
+#[cfg(unix)]
+fn shutdown_signal() -> impl std::future::Future<Output = &'static str> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    // Created here, synchronously, so the handlers are installed before
+    // main says it's ready.
+    let mut interrupt = signal(SignalKind::interrupt()).expect("can listen for SIGINT");
+    let mut terminate = signal(SignalKind::terminate()).expect("can listen for SIGTERM");
+    async move {
+        tokio::select! {
+            _ = interrupt.recv() => "Ctrl-C",
+            _ = terminate.recv() => "SIGTERM",
+        }
+    }
+}
+
+/// Resolves with the name of the signal, on Ctrl-C.
+#[cfg(not(unix))]
+fn shutdown_signal() -> impl std::future::Future<Output = &'static str> {
+    async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("can listen for Ctrl-C");
+        "Ctrl-C"
+    }
+}
+
+async fn ship(order: u32) -> u32 {
+    time::sleep(SHIPPING_TIME).await;
+    order
+}
+
+/// Accepts orders until told to stop, and returns the ones still being
+/// shipped. Calls `pretend` after the fifth.
+async fn take_orders(mut stopping: watch::Receiver<bool>, pretend: Arc<Notify>) -> JoinSet<u32> {
+    let mut shipping = JoinSet::new();
+    for order in 1.. {
+        tokio::select! {
+            // Err means the sender is gone: nobody is left to say stop
+            _ = stopping.changed() => break,
+            () = time::sleep(ORDER_EVERY) => {}
+        }
+        println!("order {order} accepted");
+        shipping.spawn(ship(order));
+        if order == PRETEND_AFTER {
+            pretend.notify_one();
+        }
+    }
+    shipping
+}
+
+#[tokio::main]
+async fn main() {
+    let wait = env::args().any(|arg| arg == "--wait");
+    let signal = shutdown_signal();
+    let pretend = Arc::new(Notify::new());
+    if wait {
+        println!("Taking orders. Press Ctrl-C to stop.");
+    }
+
+    let (stop, stopping) = watch::channel(false);
+    let intake = tokio::spawn(take_orders(stopping, Arc::clone(&pretend)));
+
+    let reason = tokio::select! {
+        name = signal => name,
+        () = pretend.notified(), if !wait => "pretend Ctrl-C",
+    };
+    println!("{reason}: no more orders.");
+    // Every receiver sees this, however many there are
+    stop.send(true).expect("the intake task is listening");
+
+    let mut shipping = intake.await.expect("the intake task doesn't panic");
+    println!("Waiting for the {} orders in flight...", shipping.len());
+    let mut shipped = Vec::new();
+    while let Some(order) = shipping.join_next().await {
+        shipped.push(order.expect("shipping doesn't panic"));
+    }
+    // They finish about in order, but nothing guarantees it
+    shipped.sort();
+    println!("Shipped orders {shipped:?}. Bye!");
+
+    // recv() takes &mut self: each call moves the stream along
+    // This would cause an error:
+    // let interrupt = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt()).unwrap();
+    // interrupt.recv().await;
+    // error[E0596]: cannot borrow `interrupt` as mutable, as it is not declared as mutable
+}
+
+/*
+ * Key Concepts:
+ * - tokio::signal::ctrl_c() completes on Ctrl-C; signal(SignalKind::terminate()) yields SIGTERMs on Unix
+ * - A handler is only in place once the listener exists: install it before announcing readiness
+ * - A watch channel tells every task at once that it's time to stop
+ * - Stop taking new work first, then drain the work in flight, e.g. by emptying a JoinSet
+ * - Returning from main drops the runtime and cancels whatever tasks are left
+ */
+
+// EXPECTED:
+// order 1 accepted
+// order 2 accepted
+// order 3 accepted
+// order 4 accepted
+// order 5 accepted
+// pretend Ctrl-C: no more orders.
+// Waiting for the 5 orders in flight...
+// Shipped orders [1, 2, 3, 4, 5]. Bye!
@@ -0,0 +1,164 @@
+// Signals 3: A Grace Period for Stragglers
+// Demonstrates giving running tasks a deadline to finish after a shutdown signal, with tokio::time::timeout, and aborting the ones that miss it
+//
+// Concepts: time::timeout, grace period, JoinSet::abort_all, JoinError::is_cancelled, cooperative cancellation
+// Difficulty: advanced
+// Minutes: 15
+//
+// Draining the work in flight, as in the last example, waits for it
+// however long it takes. That's a problem when a task never finishes: it
+// is stuck on a server that stopped answering, or it just ignores the
+// request to stop. Whoever sent SIGTERM won't wait forever either;
+// `docker stop` sends SIGKILL ten seconds later, and systemd after ninety.
+//
+// So a shutdown gets a grace period. `time::timeout(GRACE, drain)` waits
+// for the drain, or for GRACE, whichever is shorter, and returns Err if
+// time ran out. Then `abort_all` cancels whatever is left in the JoinSet:
+// each of those tasks stops at its next `.await`, and joining one gives a
+// JoinError whose `is_cancelled()` is true. The program can say which
+// ones it gave up on, and exit while it still has the chance.
+//
+// The tasks here show the range of behaviors. report finishes on its
+// own, before the signal. upload checks the watch channel between chunks,
+// and stops at the next one. cache takes the signal as its cue to save
+// what it has, which takes a moment but fits in the grace period. legacy
+// ignores the channel altogether, and gets aborted.
+//
+// Like the last example, it pretends Ctrl-C was pressed, 200ms in,
+// unless it's run with --wait.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tokio::time;
+
+/// How long the tasks get to finish after the signal.
+const GRACE: Duration = Duration::from_millis(500);
+
+/// When the pretend Ctrl-C comes.
+const PRETEND_AFTER: Duration = Duration::from_millis(200);
+
+/// Resolves with the name of the signal, on Ctrl-C or, on Unix, SIGTERM.
+#[cfg(unix)]
+fn shutdown_signal() -> impl std::future::Future<Output = &'static str> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut interrupt = signal(SignalKind::interrupt()).expect("can listen for SIGINT");
+    let mut terminate = signal(SignalKind::terminate()).expect("can listen for SIGTERM");
+    async move {
+        tokio::select! {
+            _ = interrupt.recv() => "Ctrl-C",
+            _ = terminate.recv() => "SIGTERM",
+        }
+    }
+}
+
+/// Resolves with the name of the signal, on Ctrl-C.
+#[cfg(not(unix))]
+fn shutdown_signal() -> impl std::future::Future<Output = &'static str> {
+    async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("can listen for Ctrl-C");
+        "Ctrl-C"
+    }
+}
+
+/// Done in 100ms, whatever happens.
+async fn report() -> &'static str {
+    time::sleep(Duration::from_millis(100)).await;
+    println!("report: done");
+    "report"
+}
+
+/// Sends chunk after chunk, checking between them whether to stop.
+async fn upload(mut stopping: watch::Receiver<bool>) -> &'static str {
+    loop {
+        tokio::select! {
+            _ = stopping.changed() => break,
+            () = time::sleep(Duration::from_millis(50)) => {} // one chunk
+        }
+    }
+    println!("upload: stopped between chunks");
+    "upload"
+}
+
+/// Keeps a cache in memory, and saves it to disk before stopping.
+async fn cache(mut stopping: watch::Receiver<bool>) -> &'static str {
+    let _ = stopping.changed().await;
+    time::sleep(Duration::from_millis(150)).await; // saving
+    println!("cache: saved to disk");
+    "cache"
+}
+
+/// Never looks at the channel, and takes a minute.
+async fn legacy() -> &'static str {
+    time::sleep(Duration::from_secs(60)).await;
+    println!("legacy: done");
+    "legacy"
+}
+
+#[tokio::main]
+async fn main() {
+    let wait = env::args().any(|arg| arg == "--wait");
+    let signal = shutdown_signal();
+    let (stop, stopping) = watch::channel(false);
+
+    let mut tasks = JoinSet::new();
+    tasks.spawn(report());
+    tasks.spawn(upload(stopping.clone()));
+    tasks.spawn(cache(stopping.clone()));
+    tasks.spawn(legacy());
+    let mut running: BTreeSet<&str> = ["report", "upload", "cache", "legacy"].into();
+    if wait {
+        println!("Started 4 tasks. Press Ctrl-C to stop them.");
+    }
+
+    let reason = tokio::select! {
+        name = signal => name,
+        () = time::sleep(PRETEND_AFTER), if !wait => "pretend Ctrl-C",
+    };
+    println!("{reason}: stopping, with {}ms to spare.", GRACE.as_millis());
+    let _ = stop.send(true);
+
+    let drain = async {
+        while let Some(finished) = tasks.join_next().await {
+            running.remove(finished.expect("the tasks don't panic"));
+        }
+    };
+    if time::timeout(GRACE, drain).await.is_err() {
+        println!("Out of time. Aborting {running:?}.");
+        tasks.abort_all();
+        while let Some(aborted) = tasks.join_next().await {
+            assert!(aborted.unwrap_err().is_cancelled());
+        }
+    }
+    println!("Bye!");
+
+    // `drain` borrows `tasks` until the timeout is done with it
+    // This would cause an error:
+    // let drain = async { while tasks.join_next().await.is_some() {} };
+    // tasks.abort_all();
+    // drain.await;
+    // error[E0499]: cannot borrow `tasks` as mutable more than once at a time
+}
+
+/*
+ * Key Concepts:
+ * - A task that never finishes would hold a drain up forever: give the drain a deadline
+ * - time::timeout(GRACE, future) returns Err when the time runs out first
+ * - JoinSet::abort_all cancels the stragglers at their next .await; joining them gives a cancelled JoinError
+ * - Tasks that watch the stop channel can finish early, or use the moment to save their work
+ * - Whoever sent SIGTERM sends SIGKILL if the program takes too long
+ */
+
+// EXPECTED:
+// report: done
+// pretend Ctrl-C: stopping, with 500ms to spare.
+// upload: stopped between chunks
+// cache: saved to disk
+// Out of time. Aborting {"legacy"}.
+// Bye!
@@ -0,0 +1,164 @@
+// Each test starts one of the chapter's binaries, waits until it is
+// working, sends it a signal with `kill`, the way Ctrl-C and `docker stop`
+// would, and checks that it winds down in order. Signals are a Unix
+// thing, and so are these tests.
+
+#![cfg(unix)]
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, ChildStdout, Command, ExitStatus, Stdio};
+
+/// A running example, and what it has printed so far.
+struct Example {
+    child: Child,
+    stdout: BufReader<ChildStdout>,
+    seen: Vec<String>,
+}
+
+impl Example {
+    fn start(program: &str, args: &[&str]) -> Example {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("the example should start");
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Example {
+            child,
+            stdout,
+            seen: Vec::new(),
+        }
+    }
+
+    /// Reads lines until one equal to `wanted`.
+    fn wait_for(&mut self, wanted: &str) {
+        loop {
+            let mut line = String::new();
+            if self.stdout.read_line(&mut line).unwrap() == 0 {
+                panic!(
+                    "the example ended before printing {wanted:?}: {:?}",
+                    self.seen
+                );
+            }
+            let line = line.trim_end().to_string();
+            self.seen.push(line.clone());
+            if line == wanted {
+                return;
+            }
+        }
+    }
+
+    fn signal(&self, name: &str) {
+        let status = Command::new("kill")
+            .arg(format!("-{name}"))
+            .arg(self.child.id().to_string())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// Waits for the example to exit, returning the lines it printed
+    /// after the ones already seen.
+    fn finish(mut self) -> (ExitStatus, Vec<String>) {
+        let mut rest = String::new();
+        self.stdout.read_to_string(&mut rest).unwrap();
+        let status = self.child.wait().unwrap();
+        (status, rest.lines().map(String::from).collect())
+    }
+}
+
+#[test]
+fn ctrlc_finishes_the_current_job_then_stops() {
+    let mut example = Example::start(env!("CARGO_BIN_EXE_01_ctrlc"), &[]);
+    example.wait_for("job 2 of 5: started");
+    example.signal("INT");
+    let (status, rest) = example.finish();
+    assert_eq!(status.code(), Some(130));
+    assert_eq!(
+        rest,
+        [
+            "Ctrl-C: stopping after this job (press it again to quit now)",
+            "job 2 of 5: done",
+            "Stopped after 2 of 5 jobs.",
+        ]
+    );
+}
+
+#[test]
+fn ctrlc_handles_sigterm_too() {
+    let mut example = Example::start(env!("CARGO_BIN_EXE_01_ctrlc"), &[]);
+    example.wait_for("job 1 of 5: started");
+    example.signal("TERM");
+    let (status, rest) = example.finish();
+    assert_eq!(status.code(), Some(130));
+    assert_eq!(rest.last().unwrap(), "Stopped after 1 of 5 jobs.");
+}
+
+#[test]
+fn a_second_ctrl_c_quits_without_finishing_the_job() {
+    let mut example = Example::start(env!("CARGO_BIN_EXE_01_ctrlc"), &[]);
+    example.wait_for("job 1 of 5: started");
+    example.signal("INT");
+    example.wait_for("Ctrl-C: stopping after this job (press it again to quit now)");
+    example.signal("INT");
+    let (status, rest) = example.finish();
+    assert_eq!(status.code(), Some(130));
+    assert_eq!(rest, ["Ctrl-C again: quitting now"]);
+}
+
+/// The numbers of the orders in `lines` that say `order N accepted`.
+fn accepted(lines: &[String]) -> Vec<u32> {
+    lines
+        .iter()
+        .filter_map(|line| line.strip_prefix("order ")?.strip_suffix(" accepted"))
+        .map(|number| number.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn tokio_signal_ships_every_order_it_accepted() {
+    for signal in ["INT", "TERM"] {
+        let mut example = Example::start(env!("CARGO_BIN_EXE_02_tokio_signal"), &["--wait"]);
+        example.wait_for("order 2 accepted");
+        example.signal(signal);
+        let seen = example.seen.clone();
+        let (status, rest) = example.finish();
+        assert!(status.success());
+
+        let name = if signal == "INT" { "Ctrl-C" } else { "SIGTERM" };
+        let stop = format!("{name}: no more orders.");
+        let at = rest.iter().position(|line| *line == stop).expect(&stop);
+        // An order may slip in before the signal is seen, but none after
+        let orders: Vec<String> = seen.into_iter().chain(rest[..at].iter().cloned()).collect();
+        let orders = accepted(&orders);
+        assert_eq!(orders, (1..=orders.len() as u32).collect::<Vec<_>>());
+        let count = orders.len();
+        assert!(count >= 2);
+        assert_eq!(
+            rest[at + 1..],
+            [
+                format!("Waiting for the {count} orders in flight..."),
+                format!("Shipped orders {orders:?}. Bye!"),
+            ]
+        );
+    }
+}
+
+#[test]
+fn grace_period_aborts_only_the_task_that_ignores_the_signal() {
+    let mut example = Example::start(env!("CARGO_BIN_EXE_03_grace_period"), &["--wait"]);
+    example.wait_for("report: done");
+    example.signal("TERM");
+    let (status, rest) = example.finish();
+    assert!(status.success());
+    assert_eq!(
+        rest,
+        [
+            "SIGTERM: stopping, with 500ms to spare.",
+            "upload: stopped between chunks",
+            "cache: saved to disk",
+            "Out of time. Aborting {\"legacy\"}.",
+            "Bye!",
+        ]
+    );
+}
@@ -0,0 +1,104 @@
+// Smart Pointers 1: Box<T>
+// Demonstrates heap allocation, recursive types, and trait objects with Box
+//
+// Concepts: Box, heap allocation, recursive types, trait objects
+// Difficulty: intermediate
+// Minutes: 10
+
+use std::mem::size_of;
+
+use learn_rust_core::demo;
+
+// A recursive type: a list is either empty or a value followed by a list.
+// Without Box, the compiler can't know how big a List is (it contains
+// itself!). A Box is always pointer-sized, which breaks the recursion.
+#[derive(Debug)]
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+// This would cause an error:
+// enum Broken { Cons(i32, Broken), Nil }
+// error[E0072]: recursive type `Broken` has infinite size
+
+use List::{Cons, Nil};
+
+impl List {
+    fn sum(&self) -> i32 {
+        match self {
+            Cons(value, rest) => value + rest.sum(),
+            Nil => 0,
+        }
+    }
+}
+
+fn from_slice(values: &[i32]) -> List {
+    match values {
+        [] => Nil,
+        [first, rest @ ..] => Cons(*first, Box::new(from_slice(rest))),
+    }
+}
+
+fn main() {
+    // Box::new moves a value onto the heap; the Box itself (a pointer)
+    // lives on the stack and frees the heap memory when it is dropped.
+    let boxed = Box::new(5);
+    println!("boxed = {}, doubled = {}", boxed, *boxed * 2); // * dereferences
+
+    // A big value in a Box: moving the Box copies 8 bytes, not 8 KB
+    let big = Box::new([0u8; 8192]);
+    println!(
+        "[u8; 8192] is {} bytes; Box<[u8; 8192]> is {} bytes",
+        size_of::<[u8; 8192]>(),
+        size_of::<Box<[u8; 8192]>>()
+    );
+    let moved = big;
+    println!("moved box still holds {} bytes", moved.len());
+
+    let list = demo!(from_slice(&[1, 2, 3]));
+    demo!(list.sum());
+
+    // Box<dyn Trait> holds values of different types behind one type
+    let printers: Vec<Box<dyn Fn() -> String>> = vec![
+        Box::new(|| "a closure".to_string()),
+        Box::new(|| format!("another closure, {} + {} = {}", 2, 2, 4)),
+    ];
+    for printer in &printers {
+        println!("{}", printer());
+    }
+    // When `list`, `moved`, and `printers` go out of scope, every Box frees
+    // its heap allocation automatically. No free(), no garbage collector.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_box_is_the_size_of_a_pointer() {
+        assert_eq!(size_of::<Box<[u64; 1000]>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn recursive_list_sums_its_values() {
+        assert_eq!(from_slice(&[]).sum(), 0);
+        assert_eq!(from_slice(&[4, 5, 6]).sum(), 15);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Box<T> stores T on the heap and owns it
+ * - The Box is freed (and its contents dropped) when it goes out of scope
+ * - Recursive types need indirection, and Box provides it
+ * - Box<dyn Trait> stores values of different concrete types
+ */
+
+// EXPECTED:
+// boxed = 5, doubled = 10
+// [u8; 8192] is 8192 bytes; Box<[u8; 8192]> is 8 bytes
+// moved box still holds 8192 bytes
+// from_slice(&[1, 2, 3]) = Cons(1, Cons(2, Cons(3, Nil)))
+// list.sum() = 6
+// a closure
+// another closure, 2 + 2 = 4
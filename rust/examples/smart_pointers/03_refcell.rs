@@ -0,0 +1,102 @@
+// Smart Pointers 3: RefCell<T> and Interior Mutability
+// Demonstrates borrow checking at runtime and Rc<RefCell<T>>
+//
+// Concepts: RefCell, runtime borrow checking, Rc<RefCell<T>>
+// Difficulty: intermediate
+// Minutes: 10
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// A logger that is shared by several parts of a program, all of which
+// need to add messages: shared AND mutable.
+#[derive(Default)]
+struct Log {
+    messages: Vec<String>,
+}
+
+fn record(log: &Rc<RefCell<Log>>, message: &str) {
+    // borrow_mut() hands out a mutable borrow even though we only have a
+    // shared reference to the RefCell. The usual rules (many readers OR
+    // one writer) still apply, but they are checked when the code RUNS.
+    log.borrow_mut().messages.push(message.to_string());
+}
+
+fn main() {
+    let cell = RefCell::new(vec![1, 2, 3]);
+    cell.borrow_mut().push(4); // mutate through a shared binding
+    println!("contents: {:?}", cell.borrow());
+
+    // Several readers at once are fine
+    {
+        let a = cell.borrow();
+        let b = cell.borrow();
+        println!("two readers: {} and {} items", a.len(), b.len());
+    }
+
+    // A writer while a reader exists breaks the rules. The compiler can't
+    // see it, so RefCell catches it at runtime: try_borrow_mut reports the
+    // conflict, and borrow_mut would panic with "already borrowed".
+    {
+        let reader = cell.borrow();
+        println!(
+            "borrow_mut while reading succeeds? {}",
+            cell.try_borrow_mut().is_ok()
+        );
+        drop(reader);
+    }
+    println!(
+        "borrow_mut after the reader is gone? {}",
+        cell.try_borrow_mut().is_ok()
+    );
+
+    // Rc<RefCell<T>>: many owners, each of which can mutate
+    let log = Rc::new(RefCell::new(Log::default()));
+    let from_parser = Rc::clone(&log);
+    let from_network = Rc::clone(&log);
+    record(&from_parser, "parsed 3 files");
+    record(&from_network, "sent 2 requests");
+    record(&log, "done");
+    for message in &log.borrow().messages {
+        println!("log: {}", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_owner_sees_the_same_mutations() {
+        let log = Rc::new(RefCell::new(Log::default()));
+        let other = Rc::clone(&log);
+        record(&other, "one");
+        record(&log, "two");
+        assert_eq!(other.borrow().messages, ["one", "two"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn writing_while_reading_panics() {
+        let cell = RefCell::new(0);
+        let _reader = cell.borrow();
+        *cell.borrow_mut() += 1;
+    }
+}
+
+/*
+ * Key Concepts:
+ * - RefCell<T> moves borrow checking from compile time to run time
+ * - borrow() / borrow_mut() return guards; conflicts panic
+ * - try_borrow_mut() reports a conflict instead of panicking
+ * - Rc<RefCell<T>> = shared ownership + mutation (single-threaded)
+ */
+
+// EXPECTED:
+// contents: [1, 2, 3, 4]
+// two readers: 4 and 4 items
+// borrow_mut while reading succeeds? false
+// borrow_mut after the reader is gone? true
+// log: parsed 3 files
+// log: sent 2 requests
+// log: done
@@ -0,0 +1,132 @@
+// Smart Pointers 4: A Tree with Parent Links
+// Demonstrates Weak<T> for back-references that don't keep values alive
+//
+// Concepts: Weak, parent pointers, trees
+// Difficulty: intermediate
+// Minutes: 10
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+// Parents OWN their children (strong Rc), but children only REFER to their
+// parent (Weak). If both directions were strong, parent and child would
+// keep each other alive forever.
+#[derive(Debug)]
+struct Node {
+    name: String,
+    parent: RefCell<Weak<Node>>,
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+impl Node {
+    fn new(name: &str) -> Rc<Node> {
+        Rc::new(Node {
+            name: name.to_string(),
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(Vec::new()),
+        })
+    }
+
+    fn add_child(parent: &Rc<Node>, child: Rc<Node>) {
+        // Rc::downgrade makes a Weak pointer: it bumps the weak count, not
+        // the strong count, so it doesn't keep the parent alive.
+        *child.parent.borrow_mut() = Rc::downgrade(parent);
+        parent.children.borrow_mut().push(child);
+    }
+
+    // Walk up the tree. upgrade() turns a Weak back into an Rc, or gives
+    // None if the value has already been dropped.
+    fn path(&self) -> String {
+        match self.parent.borrow().upgrade() {
+            Some(parent) => format!("{}/{}", parent.path(), self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+fn counts(label: &str, node: &Rc<Node>) {
+    println!(
+        "{:<8} strong = {}, weak = {}",
+        label,
+        Rc::strong_count(node),
+        Rc::weak_count(node)
+    );
+}
+
+fn main() {
+    let leaf = Node::new("main.rs");
+    counts("leaf", &leaf);
+
+    {
+        let src = Node::new("src");
+        Node::add_child(&src, Rc::clone(&leaf));
+        let root = Node::new("project");
+        Node::add_child(&root, Rc::clone(&src));
+
+        println!("path: {}", leaf.path());
+        counts("leaf", &leaf); // owned by us and by src
+        counts("src", &src); // owned by us and by root; leaf points back weakly
+        counts("root", &root);
+    } // root and src go out of scope here and are freed
+
+    // The leaf survived because we still own it, but its parent is gone
+    counts("leaf", &leaf);
+    println!(
+        "parent after the tree was dropped: {:?}",
+        leaf.parent
+            .borrow()
+            .upgrade()
+            .map(|parent| parent.name.clone())
+    );
+    println!("path: {}", leaf.path());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parents_own_children_but_not_the_other_way_round() {
+        let parent = Node::new("parent");
+        let child = Node::new("child");
+        Node::add_child(&parent, Rc::clone(&child));
+
+        assert_eq!(Rc::strong_count(&parent), 1);
+        assert_eq!(Rc::weak_count(&parent), 1);
+        assert_eq!(Rc::strong_count(&child), 2);
+        assert_eq!(Rc::weak_count(&child), 0);
+        assert_eq!(child.path(), "parent/child");
+    }
+
+    #[test]
+    fn dropping_the_parent_frees_it_even_though_children_point_back() {
+        let child = Node::new("child");
+        let weak_parent = {
+            let parent = Node::new("parent");
+            Node::add_child(&parent, Rc::clone(&child));
+            Rc::downgrade(&parent)
+        };
+        assert!(weak_parent.upgrade().is_none());
+        assert_eq!(Rc::strong_count(&child), 1);
+        assert_eq!(child.path(), "child");
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Weak<T> points at an Rc value without owning it
+ * - Rc::downgrade creates a Weak; upgrade() returns Option<Rc<T>>
+ * - strong_count decides when a value is freed; weak_count doesn't
+ * - Use strong pointers for ownership (parent -> child) and Weak for
+ *   back-references (child -> parent)
+ */
+
+// EXPECTED:
+// leaf     strong = 1, weak = 0
+// path: project/src/main.rs
+// leaf     strong = 2, weak = 0
+// src      strong = 2, weak = 1
+// root     strong = 1, weak = 1
+// leaf     strong = 1, weak = 0
+// parent after the tree was dropped: None
+// path: main.rs
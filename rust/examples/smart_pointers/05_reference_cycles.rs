@@ -0,0 +1,176 @@
+// Smart Pointers 5: Reference Cycles and How Weak Breaks Them
+// Demonstrates a leaking doubly-linked list and the same list fixed with Weak
+//
+// Concepts: reference cycles, memory leaks, Weak
+// Difficulty: advanced
+// Minutes: 15
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+thread_local! {
+    // Counts how many nodes have been dropped, so we can SEE leaks
+    static DROPPED: Cell<usize> = const { Cell::new(0) };
+}
+
+fn dropped() -> usize {
+    DROPPED.with(Cell::get)
+}
+
+// ===== Version 1: strong pointers in both directions (leaks!) =====
+struct LeakyNode {
+    value: i32,
+    next: RefCell<Option<Rc<LeakyNode>>>,
+    prev: RefCell<Option<Rc<LeakyNode>>>,
+}
+
+impl Drop for LeakyNode {
+    fn drop(&mut self) {
+        DROPPED.with(|count| count.set(count.get() + 1));
+    }
+}
+
+fn leaky_list(values: &[i32]) -> Rc<LeakyNode> {
+    let nodes: Vec<Rc<LeakyNode>> = values
+        .iter()
+        .map(|&value| {
+            Rc::new(LeakyNode {
+                value,
+                next: RefCell::new(None),
+                prev: RefCell::new(None),
+            })
+        })
+        .collect();
+    for pair in nodes.windows(2) {
+        *pair[0].next.borrow_mut() = Some(Rc::clone(&pair[1]));
+        *pair[1].prev.borrow_mut() = Some(Rc::clone(&pair[0])); // the cycle
+    }
+    Rc::clone(&nodes[0])
+}
+
+// ===== Version 2: next is strong, prev is Weak (no leak) =====
+struct Node {
+    value: i32,
+    next: RefCell<Option<Rc<Node>>>,
+    prev: RefCell<Weak<Node>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        DROPPED.with(|count| count.set(count.get() + 1));
+    }
+}
+
+fn list(values: &[i32]) -> Rc<Node> {
+    let nodes: Vec<Rc<Node>> = values
+        .iter()
+        .map(|&value| {
+            Rc::new(Node {
+                value,
+                next: RefCell::new(None),
+                prev: RefCell::new(Weak::new()),
+            })
+        })
+        .collect();
+    for pair in nodes.windows(2) {
+        *pair[0].next.borrow_mut() = Some(Rc::clone(&pair[1]));
+        *pair[1].prev.borrow_mut() = Rc::downgrade(&pair[0]);
+    }
+    Rc::clone(&nodes[0])
+}
+
+fn main() {
+    // Every middle node of the leaky list is owned by BOTH neighbours.
+    // When we drop the head, its strong count goes from 2 to 1 (the second
+    // node still points back at it), so nothing is ever freed.
+    let head = leaky_list(&[1, 2, 3]);
+    let second = head.next.borrow().clone().unwrap();
+    println!(
+        "leaky: head = {}, second = {}, back to {}",
+        head.value,
+        second.value,
+        second.prev.borrow().as_ref().unwrap().value
+    );
+    println!("leaky: head strong_count = {}", Rc::strong_count(&head));
+    let before = dropped();
+    drop(second);
+    drop(head);
+    println!(
+        "leaky: nodes freed after dropping the head: {}",
+        dropped() - before
+    );
+
+    // The fixed list: back-links are Weak, so only `next` owns anything.
+    // Dropping the head frees it, which drops its `next`, and so on.
+    let head = list(&[1, 2, 3]);
+    let second = head.next.borrow().clone().unwrap();
+    println!(
+        "fixed: head = {}, second = {}, back to {}",
+        head.value,
+        second.value,
+        second.prev.borrow().upgrade().unwrap().value
+    );
+    println!(
+        "fixed: head strong_count = {}, weak_count = {}",
+        Rc::strong_count(&head),
+        Rc::weak_count(&head)
+    );
+    let before = dropped();
+    drop(second);
+    drop(head);
+    println!(
+        "fixed: nodes freed after dropping the head: {}",
+        dropped() - before
+    );
+    // Rust guarantees memory SAFETY, not freedom from leaks. Cycles of Rc
+    // are safe but leak; designing ownership as a tree (with Weak for the
+    // way back) avoids them.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strong_cycles_are_never_freed() {
+        let before = dropped();
+        let head = leaky_list(&[1, 2, 3, 4]);
+        assert_eq!(Rc::strong_count(&head), 2); // us + the second node
+        drop(head);
+        assert_eq!(dropped() - before, 0);
+    }
+
+    #[test]
+    fn weak_back_links_let_the_whole_list_be_freed() {
+        let before = dropped();
+        let head = list(&[1, 2, 3, 4]);
+        assert_eq!(Rc::strong_count(&head), 1);
+        assert_eq!(Rc::weak_count(&head), 1); // the second node's prev
+        drop(head);
+        assert_eq!(dropped() - before, 4);
+    }
+
+    #[test]
+    fn weak_links_still_lead_back_while_the_list_is_alive() {
+        let head = list(&[10, 20]);
+        let second = head.next.borrow().clone().unwrap();
+        let back = second.prev.borrow().upgrade().unwrap();
+        assert!(Rc::ptr_eq(&back, &head));
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Two Rc values pointing at each other form a cycle that is never freed
+ * - A leak is memory-safe, so the compiler won't stop you
+ * - Break cycles by making one direction Weak
+ * - Counting drops (or watching strong_count) reveals leaks
+ */
+
+// EXPECTED:
+// leaky: head = 1, second = 2, back to 1
+// leaky: head strong_count = 2
+// leaky: nodes freed after dropping the head: 0
+// fixed: head = 1, second = 2, back to 1
+// fixed: head strong_count = 1, weak_count = 1
+// fixed: nodes freed after dropping the head: 3
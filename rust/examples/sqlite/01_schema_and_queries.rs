@@ -0,0 +1,139 @@
+// SQLite 1: Schemas and Parameterized Queries
+// Demonstrates Connection, execute_batch, params!, query_row, and query_map
+//
+// Concepts: rusqlite, Connection, execute_batch, params!, query_row, query_map
+// Difficulty: intermediate
+// Minutes: 10
+//
+// SQLite is a whole SQL database in a library: no server, just a file (or,
+// here, memory). rusqlite wraps it. We send SQL text with `?` placeholders
+// and hand the values over separately, so SQLite never mistakes a value for
+// SQL. Building the SQL with format! instead is how SQL injection happens.
+
+use rusqlite::{named_params, params, Connection, OptionalExtension, Result};
+
+fn create_schema(conn: &Connection) -> Result<()> {
+    // execute_batch runs several statements, but can't take parameters
+    conn.execute_batch(
+        "CREATE TABLE books (
+             id     INTEGER PRIMARY KEY,
+             title  TEXT NOT NULL,
+             author TEXT NOT NULL,
+             year   INTEGER NOT NULL,
+             rating REAL
+         );
+         CREATE INDEX books_by_author ON books (author);",
+    )
+}
+
+fn main() -> Result<()> {
+    // open("library.db") would use a file; the in-memory database vanishes
+    // when the connection closes, which suits examples and tests
+    let conn = Connection::open_in_memory()?;
+    create_schema(&conn)?;
+
+    // execute() runs one statement and returns how many rows it changed
+    let books = [
+        (
+            "The Rust Programming Language",
+            "Klabnik & Nichols",
+            2018,
+            Some(4.7),
+        ),
+        ("Programming Rust", "Blandy & Orendorff", 2017, Some(4.6)),
+        ("Rust for Rustaceans", "Gjengset", 2021, Some(4.8)),
+        ("Zero To Production", "Palmieri", 2022, None),
+    ];
+    for (title, author, year, rating) in books {
+        let changed = conn.execute(
+            "INSERT INTO books (title, author, year, rating) VALUES (?1, ?2, ?3, ?4)",
+            params![title, author, year, rating],
+        )?;
+        println!("inserted {} row, id {}", changed, conn.last_insert_rowid());
+    }
+
+    // query_row for exactly one row; the closure picks the columns apart,
+    // and get() converts each one to the Rust type we ask for
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM books", [], |row| row.get(0))?;
+    println!("{} books", count);
+
+    // A value full of quotes is just a value, because it's a parameter
+    let sneaky = "x'; DROP TABLE books; --";
+    let found: Option<i64> = conn
+        .query_row("SELECT id FROM books WHERE title = ?1", [sneaky], |row| {
+            row.get(0)
+        })
+        // optional() turns "no rows" into None instead of an error
+        .optional()?;
+    println!("title {:?}: {:?}", sneaky, found);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    conn.execute("DELETE FROM books WHERE year < ?1", 2020)?;
+    // error[E0277]: the trait bound `{integer}: Params` is not satisfied (use [2020] or params![2020])
+
+    // prepare() compiles a statement once; query_map runs it and maps
+    // every row. Named parameters read better when there are several.
+    let mut recent =
+        conn.prepare("SELECT title, year FROM books WHERE year >= :since ORDER BY year DESC")?;
+    let rows = recent.query_map(named_params! { ":since": 2018 }, |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+    println!("since 2018:");
+    for row in rows {
+        let (title, year) = row?;
+        println!("  {} ({})", title, year);
+    }
+
+    // NULL comes back as None when we ask for an Option
+    let mut ratings = conn.prepare("SELECT title, rating FROM books ORDER BY id")?;
+    let unrated: Vec<String> = ratings
+        .query_map([], |row| Ok((row.get(0)?, row.get::<_, Option<f64>>(1)?)))?
+        .filter_map(|row| match row {
+            Ok((title, None)) => Some(Ok(title)),
+            Ok(_) => None,
+            Err(error) => Some(Err(error)),
+        })
+        .collect::<Result<_>>()?;
+    println!("unrated: {:?}", unrated);
+
+    // UPDATE and DELETE report the rows they touched, which is how to tell
+    // "nothing matched" from success
+    let updated = conn.execute(
+        "UPDATE books SET rating = ?1 WHERE author = ?2",
+        params![4.9, "Palmieri"],
+    )?;
+    let deleted = conn.execute("DELETE FROM books WHERE year < ?1", [2000])?;
+    println!("updated {}, deleted {}", updated, deleted);
+
+    // Mistakes in SQL surface when the statement is prepared
+    match conn.prepare("SELECT nope FROM books") {
+        Ok(_) => println!("it prepared?"),
+        Err(error) => println!("bad SQL: {}", error),
+    }
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - Connection::open (a file) or open_in_memory (gone on close)
+ * - execute_batch for schema scripts, execute for one statement with params
+ * - `?1` / `:name` placeholders with params! / named_params!, never format!
+ * - query_row for one row (+ optional() for "maybe none"), query_map for many
+ * - Asking for Option<T> maps NULL to None
+ */
+
+// EXPECTED:
+// inserted 1 row, id 1
+// inserted 1 row, id 2
+// inserted 1 row, id 3
+// inserted 1 row, id 4
+// 4 books
+// title "x'; DROP TABLE books; --": None
+// since 2018:
+//   Zero To Production (2022)
+//   Rust for Rustaceans (2021)
+//   The Rust Programming Language (2018)
+// unrated: ["Zero To Production"]
+// updated 1, deleted 0
+// bad SQL: no such column: nope in SELECT nope FROM books at offset 7
@@ -0,0 +1,138 @@
+// SQLite 2: Transactions
+// Demonstrates Transaction, commit, rollback on drop, and constraints
+//
+// Concepts: Transaction, commit, rollback on drop, constraints
+// Difficulty: intermediate
+// Minutes: 10
+//
+// A transfer between two accounts is two UPDATEs. If the second one fails,
+// the first must not stay behind, or money appears out of nowhere. A
+// transaction groups statements so that they all happen or none do. In
+// rusqlite, a Transaction that is dropped without commit() rolls back,
+// so an early return through `?` undoes everything for us.
+
+use rusqlite::{params, Connection, Result, Transaction};
+
+fn setup(conn: &Connection) -> Result<()> {
+    // The CHECK constraint makes SQLite itself refuse a negative balance
+    conn.execute_batch(
+        "CREATE TABLE accounts (
+             name    TEXT PRIMARY KEY,
+             balance INTEGER NOT NULL CHECK (balance >= 0)
+         );
+         INSERT INTO accounts VALUES ('alice', 100), ('bob', 50);",
+    )
+}
+
+fn balances(conn: &Connection) -> Result<String> {
+    let mut stmt = conn.prepare("SELECT name, balance FROM accounts ORDER BY name")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(format!(
+                "{}={}",
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?
+            ))
+        })?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(rows.join(", "))
+}
+
+/// Moves `amount` from one account to another, or changes nothing.
+fn transfer(conn: &mut Connection, from: &str, to: &str, amount: i64) -> Result<()> {
+    // transaction() borrows the connection mutably until commit or drop
+    let tx = conn.transaction()?;
+    // Credit first, so that a failing debit has something to undo
+    tx.execute(
+        "UPDATE accounts SET balance = balance + ?1 WHERE name = ?2",
+        params![amount, to],
+    )?;
+    tx.execute(
+        "UPDATE accounts SET balance = balance - ?1 WHERE name = ?2",
+        params![amount, from],
+    )?;
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    conn.execute("DELETE FROM accounts", [])?;
+    // error[E0502]: cannot borrow `*conn` as immutable because it is also borrowed as mutable
+
+    tx.commit()
+}
+
+/// Adds an account, opening with a bonus booked in the same transaction.
+/// Taking `&Transaction` lets a helper join a transaction it didn't start.
+fn open_account(tx: &Transaction, name: &str, bonus: i64) -> Result<()> {
+    tx.execute("INSERT INTO accounts VALUES (?1, 0)", [name])?;
+    tx.execute(
+        "UPDATE accounts SET balance = balance + ?1 WHERE name = ?2",
+        params![bonus, name],
+    )?;
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let mut conn = Connection::open_in_memory()?;
+    setup(&conn)?;
+    println!("start:     {}", balances(&conn)?);
+
+    transfer(&mut conn, "alice", "bob", 30)?;
+    println!("after 30:  {}", balances(&conn)?);
+
+    // alice has 70, so the debit breaks the CHECK constraint. bob's credit
+    // already ran, and the rollback takes it back.
+    match transfer(&mut conn, "alice", "bob", 500) {
+        Ok(()) => println!("transferred 500?"),
+        Err(error) => println!("500 fails: {}", error),
+    }
+    println!("after 500: {}", balances(&conn)?);
+
+    // Several helpers in one transaction, committed together
+    let tx = conn.transaction()?;
+    open_account(&tx, "carol", 10)?;
+    open_account(&tx, "dave", 10)?;
+    tx.commit()?;
+    println!("opened:    {}", balances(&conn)?);
+
+    // An explicit rollback, for when the code, not SQL, decides to give up
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM accounts", [])?;
+    let left: i64 = tx.query_row("SELECT COUNT(*) FROM accounts", [], |row| row.get(0))?;
+    println!("inside the transaction: {} accounts", left);
+    tx.rollback()?;
+    println!("rolled back: {}", balances(&conn)?);
+
+    // Without a transaction, each statement commits on its own. That's
+    // also why bulk inserts are slow outside one: one disk sync per row.
+    let tx = conn.transaction()?;
+    {
+        // prepare_cached reuses the compiled statement across calls
+        let mut insert = tx.prepare_cached("INSERT INTO accounts VALUES (?1, ?2)")?;
+        for n in 0..1000 {
+            insert.execute(params![format!("user{:04}", n), 1])?;
+        }
+    }
+    tx.commit()?;
+    let total: i64 = conn.query_row("SELECT SUM(balance) FROM accounts", [], |row| row.get(0))?;
+    println!("after a bulk insert, the bank holds {}", total);
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - conn.transaction() starts one; tx.commit() makes its changes stick
+ * - Dropping a Transaction rolls back, so `?` inside one is safe
+ * - Constraints (CHECK, NOT NULL, UNIQUE) make SQLite reject bad data
+ * - The borrow checker stops us from using the connection behind tx's back
+ * - Group bulk writes in one transaction: it's far faster
+ */
+
+// EXPECTED:
+// start:     alice=100, bob=50
+// after 30:  alice=70, bob=80
+// 500 fails: CHECK constraint failed: balance >= 0
+// after 500: alice=70, bob=80
+// opened:    alice=70, bob=80, carol=10, dave=10
+// inside the transaction: 0 accounts
+// rolled back: alice=70, bob=80, carol=10, dave=10
+// after a bulk insert, the bank holds 1170
@@ -0,0 +1,217 @@
+// SQLite 3: Mapping Rows to Structs
+// Demonstrates row mapping functions, columns by name, and ToSql/FromSql
+//
+// Concepts: row mapping, columns by name, ToSql, FromSql
+// Difficulty: intermediate
+// Minutes: 20
+//
+// Tuples of columns get unwieldy fast. The usual shape is one function
+// per struct that turns a Row into a value, used by every query that
+// selects those columns. Our own types can go in and out of SQL too, by
+// implementing ToSql and FromSql, so an enum is stored as text and read
+// back as the enum.
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::{params, Connection, Result, Row};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Todo,
+    Doing,
+    Done,
+}
+
+impl ToSql for Status {
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        let text = match self {
+            Status::Todo => "todo",
+            Status::Doing => "doing",
+            Status::Done => "done",
+        };
+        Ok(text.into())
+    }
+}
+
+impl FromSql for Status {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "todo" => Ok(Status::Todo),
+            "doing" => Ok(Status::Doing),
+            "done" => Ok(Status::Done),
+            other => Err(FromSqlError::Other(
+                format!("unknown status {:?}", other).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Task {
+    id: i64,
+    title: String,
+    status: Status,
+    /// NULL when the task isn't assigned to anyone
+    assignee: Option<String>,
+}
+
+impl Task {
+    /// The columns every query for tasks selects, in one place
+    const COLUMNS: &'static str = "id, title, status, assignee";
+
+    /// Reads a Task out of a row of `COLUMNS`. Getting columns by name
+    /// keeps working if the order changes.
+    fn from_row(row: &Row) -> Result<Task> {
+        Ok(Task {
+            id: row.get("id")?,
+            title: row.get("title")?,
+            status: row.get("status")?,
+            assignee: row.get("assignee")?,
+        })
+    }
+}
+
+/// A project with its tasks, put together from two tables.
+#[derive(Debug)]
+struct Project {
+    name: String,
+    tasks: Vec<Task>,
+}
+
+fn setup(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE projects (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+         CREATE TABLE tasks (
+             id         INTEGER PRIMARY KEY,
+             project_id INTEGER NOT NULL REFERENCES projects (id),
+             title      TEXT NOT NULL,
+             status     TEXT NOT NULL,
+             assignee   TEXT
+         );
+         INSERT INTO projects (name) VALUES ('book'), ('website');",
+    )?;
+    let tasks = [
+        (1, "outline", Status::Done, Some("ana")),
+        (1, "first draft", Status::Doing, Some("ana")),
+        (1, "cover art", Status::Todo, None),
+        (2, "pick a theme", Status::Todo, Some("ben")),
+    ];
+    for (project, title, status, assignee) in tasks {
+        // Status goes in as a parameter like any other value, via ToSql
+        conn.execute(
+            "INSERT INTO tasks (project_id, title, status, assignee) VALUES (?1, ?2, ?3, ?4)",
+            params![project, title, status, assignee],
+        )?;
+    }
+    Ok(())
+}
+
+fn tasks_with_status(conn: &Connection, status: Status) -> Result<Vec<Task>> {
+    let sql = format!(
+        "SELECT {} FROM tasks WHERE status = ?1 ORDER BY id",
+        Task::COLUMNS
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    // A fn that takes &Row can be passed straight to query_map
+    let tasks = stmt.query_map([status], Task::from_row)?;
+    tasks.collect()
+}
+
+fn load_project(conn: &Connection, name: &str) -> Result<Project> {
+    let id: i64 = conn.query_row("SELECT id FROM projects WHERE name = ?1", [name], |row| {
+        row.get(0)
+    })?;
+    let sql = format!(
+        "SELECT {} FROM tasks WHERE project_id = ?1 ORDER BY id",
+        Task::COLUMNS
+    );
+    let tasks = conn
+        .prepare(&sql)?
+        .query_map([id], Task::from_row)?
+        .collect::<Result<_>>()?;
+    Ok(Project {
+        name: name.to_string(),
+        tasks,
+    })
+}
+
+fn main() -> Result<()> {
+    let conn = Connection::open_in_memory()?;
+    setup(&conn)?;
+
+    println!("todo:");
+    for task in tasks_with_status(&conn, Status::Todo)? {
+        println!("  {:?}", task);
+    }
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let task: Task = conn.query_row("SELECT * FROM tasks", [], |row| row.get(0))?;
+    // error[E0277]: the trait bound `Task: FromSql` is not satisfied
+
+    let book = load_project(&conn, "book")?;
+    println!("project {}:", book.name);
+    for task in &book.tasks {
+        let who = task.assignee.as_deref().unwrap_or("nobody");
+        println!("  #{} {} [{:?}, {}]", task.id, task.title, task.status, who);
+    }
+
+    // A join with GROUP BY can do the counting in SQL instead of in Rust
+    let mut stmt = conn.prepare(
+        "SELECT p.name, COUNT(t.id), SUM(t.status = 'done')
+         FROM projects p LEFT JOIN tasks t ON t.project_id = p.id
+         GROUP BY p.id ORDER BY p.name",
+    )?;
+    let summary = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+        ))
+    })?;
+    for row in summary {
+        let (name, total, done) = row?;
+        println!("{}: {}/{} done", name, done, total);
+    }
+
+    // A value FromSql can't read is an error at get(), not a panic
+    conn.execute(
+        "INSERT INTO tasks (project_id, title, status) VALUES (2, 'typo', 'dnoe')",
+        [],
+    )?;
+    let result: Result<Status> =
+        conn.query_row("SELECT status FROM tasks WHERE title = 'typo'", [], |row| {
+            row.get(0)
+        });
+    match result {
+        Ok(status) => println!("read {:?}?", status),
+        Err(error) => println!("bad status: {}", error),
+    }
+
+    // Asking for the wrong type fails the same way
+    let wrong: Result<i64> =
+        conn.query_row("SELECT title FROM tasks LIMIT 1", [], |row| row.get(0));
+    println!("title as i64: {}", wrong.is_err());
+    Ok(())
+}
+
+/*
+ * Key Concepts:
+ * - One `fn from_row(&Row) -> Result<T>` per struct, reused by every query
+ * - row.get("name") picks a column by name; Option<T> reads NULL as None
+ * - ToSql/FromSql let custom types be parameters and column values
+ * - Conversions that fail are errors, so a bad row can't crash the program
+ * - Joins and GROUP BY let the database do work before rows reach Rust
+ */
+
+// EXPECTED:
+// todo:
+//   Task { id: 3, title: "cover art", status: Todo, assignee: None }
+//   Task { id: 4, title: "pick a theme", status: Todo, assignee: Some("ben") }
+// project book:
+//   #1 outline [Done, ana]
+//   #2 first draft [Doing, ana]
+//   #3 cover art [Todo, nobody]
+// book: 1/3 done
+// website: 0/1 done
+// bad status: Conversion error from type Text at index: 0, unknown status "dnoe"
+// title as i64: true
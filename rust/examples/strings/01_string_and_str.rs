@@ -0,0 +1,116 @@
+// Strings 1: String and &str
+// Demonstrates owned Strings, borrowed &str slices, and converting between them
+//
+// Concepts: String, &str, conversions
+// Difficulty: beginner
+// Minutes: 10
+//
+// Rust has two main string types. A String owns its text: a growable,
+// heap-allocated buffer of UTF-8 bytes, like Vec<u8> with a promise about
+// its contents. A &str borrows text that lives somewhere else: inside a
+// String, in the program's binary (every string literal), or in any other
+// buffer. Functions that only read text take &str, so they accept both;
+// functions that keep text or build it return String.
+
+/// Reads only, so it borrows: works with literals and Strings alike.
+fn shout(text: &str) -> String {
+    format!("{}!", text.to_uppercase())
+}
+
+/// Keeps the text, so it takes ownership; callers with a &str convert.
+struct Label {
+    text: String,
+}
+
+impl Label {
+    // impl Into<String> lets callers pass either without writing .to_string()
+    fn new(text: impl Into<String>) -> Self {
+        Label { text: text.into() }
+    }
+}
+
+/// Returns part of its input, so the result borrows from it.
+fn first_word(text: &str) -> &str {
+    text.split_whitespace().next().unwrap_or("")
+}
+
+fn main() {
+    // A literal is a &'static str: it lives in the binary for the whole run
+    let literal: &'static str = "hello world";
+
+    // Turning it into a String copies the bytes onto the heap
+    let mut owned: String = literal.to_string();
+    owned.push_str(", again");
+    println!("{} / {}", literal, owned);
+
+    // &String turns into &str automatically (deref coercion), so one
+    // function serves both
+    println!("{} {}", shout(literal), shout(&owned));
+
+    // A String is three words on the stack: pointer, length, capacity.
+    // A &str is two: pointer and length.
+    println!(
+        "sizes: String {} bytes, &str {} bytes",
+        std::mem::size_of::<String>(),
+        std::mem::size_of::<&str>()
+    );
+    println!(
+        "len {}, capacity >= len: {}",
+        owned.len(),
+        owned.capacity() >= owned.len()
+    );
+
+    // Slices point into the String; no copying
+    let word = first_word(&owned);
+    println!("first word: {:?}", word);
+    println!(
+        "same memory: {}",
+        std::ptr::eq(word.as_ptr(), owned.as_ptr())
+    );
+
+    // Either type goes into a function that wants a String
+    let a = Label::new("from a literal");
+    let b = Label::new(owned.clone());
+    println!("{:?} / {:?}", a.text, b.text);
+
+    // as_str() and &s[..] borrow a String's whole text
+    let whole: &str = owned.as_str();
+    println!("{}", whole == &owned[..]);
+
+    // Comparisons work across the two types
+    println!("String == &str: {}", owned == "hello world, again");
+
+    // Other string types for other jobs: OsString for file names and
+    // arguments, CString for C, Cow<str> for "borrowed unless changed"
+    let path = std::path::Path::new("notes.txt");
+    println!("path as str: {:?}", path.to_str());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let word: &str = {
+        let temporary = String::from("gone soon");
+        first_word(&temporary)
+    };
+    // error[E0597]: `temporary` does not live long enough
+}
+
+/*
+ * Key Concepts:
+ * - String owns growable UTF-8 text; &str borrows text stored elsewhere
+ * - String literals are &'static str
+ * - Take &str to read, return String to hand over new text
+ * - &String coerces to &str; to_string()/into() go the other way, copying
+ * - A slice of a String points into it and can't outlive it
+ */
+
+// EXPECTED:
+// hello world / hello world, again
+// HELLO WORLD! HELLO WORLD, AGAIN!
+// sizes: String 24 bytes, &str 16 bytes
+// len 18, capacity >= len: true
+// first word: "hello"
+// same memory: true
+// "from a literal" / "hello world, again"
+// true
+// String == &str: true
+// path as str: Some("notes.txt")
@@ -0,0 +1,117 @@
+// Strings 2: Bytes, Chars, and Grapheme Clusters
+// Demonstrates len(), bytes(), chars(), char_indices(), and graphemes()
+//
+// Concepts: len, bytes, chars, char_indices, graphemes
+// Difficulty: intermediate
+// Minutes: 10
+//
+// "How long is this string?" has three answers. len() counts UTF-8 bytes,
+// which is what memory and files care about. chars() yields Unicode scalar
+// values, where é may be one char or an e plus a combining accent. What a
+// reader sees as one character, a flag or a family emoji or that é, is a
+// grapheme cluster, and std doesn't know about those: the
+// unicode-segmentation crate does. Pick the unit that fits the question.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+fn describe(label: &str, text: &str) {
+    println!(
+        "{:<10} bytes {:>2}, chars {:>2}, graphemes {:>2}",
+        label,
+        text.len(),
+        text.chars().count(),
+        text.graphemes(true).count()
+    );
+}
+
+fn main() {
+    describe("ascii", "hello");
+    describe("accented", "café");
+    // The same word with a combining acute accent (U+0301) after the e
+    describe("combining", "cafe\u{301}");
+    describe("cjk", "日本語");
+    describe("emoji", "👍🏽");
+    describe("flag", "🇯🇵");
+
+    // Each char is 1 to 4 bytes in UTF-8
+    for c in ['a', 'é', '€', '🦀'] {
+        println!(
+            "{:?} is U+{:04X}, {} byte(s): {:?}",
+            c,
+            c as u32,
+            c.len_utf8(),
+            c.to_string().as_bytes()
+        );
+    }
+
+    // char_indices() gives each char with the byte offset it starts at,
+    // the only offsets it is safe to slice at
+    let word = "naïve";
+    let offsets: Vec<(usize, char)> = word.char_indices().collect();
+    println!("char_indices: {:?}", offsets);
+
+    // The two spellings of café look alike but aren't equal
+    println!(
+        "\"café\" == \"cafe\\u{{301}}\": {}",
+        "café" == "cafe\u{301}"
+    );
+
+    // Reversing by chars breaks the combining accent onto the wrong letter;
+    // reversing by graphemes keeps it
+    let text = "ae\u{301}o";
+    let by_chars: String = text.chars().rev().collect();
+    let by_graphemes: String = text.graphemes(true).rev().collect();
+    println!("chars reversed:     {:?}", by_chars);
+    println!("graphemes reversed: {:?}", by_graphemes);
+
+    // Case changes can change the length, too
+    let german = "straße";
+    println!(
+        "{} -> {} ({} -> {} chars)",
+        german,
+        german.to_uppercase(),
+        german.chars().count(),
+        german.to_uppercase().chars().count()
+    );
+
+    // Bytes are the escape hatch for ASCII-only work
+    let count = "a1b22c333".bytes().filter(u8::is_ascii_digit).count();
+    println!("ascii digits: {}", count);
+
+    // Word boundaries come from the same crate
+    let words: Vec<&str> = "Hello, wörld! It's 3.5°C.".unicode_words().collect();
+    println!("words: {:?}", words);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let third = word[2];
+    // error[E0277]: the type `str` cannot be indexed by `{integer}`
+}
+
+/*
+ * Key Concepts:
+ * - len() is bytes; chars() are Unicode scalar values; graphemes are what people see
+ * - A char takes 1 to 4 bytes in UTF-8; char_indices() gives safe offsets
+ * - Equal-looking text can differ (composed é vs e + combining accent)
+ * - Operations on chars can split graphemes apart; unicode-segmentation keeps them
+ * - Strings can't be indexed by position, because positions are ambiguous
+ */
+
+// EXPECTED:
+// ascii      bytes  5, chars  5, graphemes  5
+// accented   bytes  5, chars  4, graphemes  4
+// combining  bytes  6, chars  5, graphemes  4
+// cjk        bytes  9, chars  3, graphemes  3
+// emoji      bytes  8, chars  2, graphemes  1
+// flag       bytes  8, chars  2, graphemes  1
+// 'a' is U+0061, 1 byte(s): [97]
+// 'é' is U+00E9, 2 byte(s): [195, 169]
+// '€' is U+20AC, 3 byte(s): [226, 130, 172]
+// '🦀' is U+1F980, 4 byte(s): [240, 159, 166, 128]
+// char_indices: [(0, 'n'), (1, 'a'), (2, 'ï'), (4, 'v'), (5, 'e')]
+// "café" == "cafe\u{301}": false
+// chars reversed:     "o\u{301}ea"
+// graphemes reversed: "oe\u{301}a"
+// straße -> STRASSE (6 -> 7 chars)
+// ascii digits: 6
+// words: ["Hello", "wörld", "It's", "3.5", "C"]
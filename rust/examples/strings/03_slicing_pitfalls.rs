@@ -0,0 +1,100 @@
+// Strings 3: Slicing Pitfalls
+// Demonstrates byte-range slicing, char boundaries, get(), and panics
+//
+// Concepts: byte ranges, char boundaries, get, panics
+// Difficulty: intermediate
+// Minutes: 10
+//
+// `&text[a..b]` slices by byte offsets, and it checks at runtime that both
+// ends fall on a char boundary: cutting a multi-byte char in half would
+// leave invalid UTF-8, so it panics instead. Code that slices at offsets
+// it computed itself ("the first 10 bytes") works in every test written in
+// English and crashes on the first name with an accent. The fixes: find
+// offsets with char_indices() or find(), or use get(), which returns None
+// instead of panicking.
+
+use std::panic;
+
+/// Tries `&text[range]` and reports a panic instead of crashing.
+fn try_slice(text: &str, start: usize, end: usize) {
+    let result = panic::catch_unwind(|| text[start..end].to_string());
+    match result {
+        Ok(slice) => println!("{:?}[{}..{}] = {:?}", text, start, end, slice),
+        Err(_) => println!("{:?}[{}..{}] panicked", text, start, end),
+    }
+}
+
+fn main() {
+    // Keep the panic messages out of the output; we print our own
+    panic::set_hook(Box::new(|_| {}));
+
+    // ASCII: every byte is a char, so every offset is a boundary
+    try_slice("hello", 0, 3);
+    // 'é' is two bytes (1..3): slicing through it panics
+    try_slice("héllo", 0, 2);
+    try_slice("héllo", 0, 3);
+    // Emoji are four bytes
+    try_slice("🦀 rust", 0, 1);
+    try_slice("🦀 rust", 0, 4);
+
+    // is_char_boundary() says where cutting is allowed
+    let name = "Zoë";
+    let boundaries: Vec<usize> = (0..=name.len())
+        .filter(|&i| name.is_char_boundary(i))
+        .collect();
+    println!("boundaries of {:?}: {:?}", name, boundaries);
+
+    // get() is the non-panicking slice
+    println!("get(0..3): {:?}", name.get(0..3));
+    println!("get(0..2): {:?}", name.get(0..2));
+    println!("get(0..9): {:?}", name.get(0..9));
+
+    // Offsets from find() are always boundaries, since they are where a
+    // match starts
+    let email = "renée@example.com";
+    if let Some(at) = email.find('@') {
+        println!("user {:?}, domain {:?}", &email[..at], &email[at + 1..]);
+    }
+
+    // "The first 3 characters" means counting chars, not bytes
+    let first_three: String = "naïve".chars().take(3).collect();
+    println!("first three chars: {:?}", first_three);
+    let end = "naïve"
+        .char_indices()
+        .nth(3)
+        .map_or("naïve".len(), |(i, _)| i);
+    println!("as a slice: {:?}", &"naïve"[..end]);
+
+    // Splitting and trimming work on chars and patterns, so they're safe
+    let parts: Vec<&str> = "α,β,γ".split(',').collect();
+    println!("split: {:?}, trimmed: {:?}", parts, "  ünïcode  ".trim());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let first: char = name[0..1];
+    // error[E0308]: mismatched types (expected `char`, found `str`)
+}
+
+/*
+ * Key Concepts:
+ * - &text[a..b] is a byte range, and panics if an end splits a char
+ * - is_char_boundary(), char_indices(), and find() give offsets that are safe
+ * - get(a..b) returns None instead of panicking
+ * - "First n characters" means chars().take(n), not [..n]
+ * - Test string code with non-ASCII input
+ */
+
+// EXPECTED:
+// "hello"[0..3] = "hel"
+// "héllo"[0..2] panicked
+// "héllo"[0..3] = "hé"
+// "🦀 rust"[0..1] panicked
+// "🦀 rust"[0..4] = "🦀"
+// boundaries of "Zoë": [0, 1, 2, 4]
+// get(0..3): None
+// get(0..2): Some("Zo")
+// get(0..9): None
+// user "renée", domain "example.com"
+// first three chars: "naï"
+// as a slice: "naï"
+// split: ["α", "β", "γ"], trimmed: "ünïcode"
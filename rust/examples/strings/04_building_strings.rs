@@ -0,0 +1,111 @@
+// Strings 4: Building Strings Efficiently
+// Demonstrates push_str, with_capacity, format!, write!, join, and collect
+//
+// Concepts: push_str, with_capacity, format!, write!, join, collect
+// Difficulty: intermediate
+// Minutes: 10
+//
+// A String grows like a Vec: when it runs out of room it allocates a
+// bigger buffer and copies everything over. Building text piece by piece
+// is cheap as long as we append to one String instead of creating a new
+// one for every piece. `s = s + &x` and `format!("{}{}", s, x)` in a loop
+// look alike, but the second copies the whole string every time.
+
+use std::fmt::Write;
+
+fn main() {
+    // push and push_str append in place
+    let mut greeting = String::from("hello");
+    greeting.push(',');
+    greeting.push_str(" world");
+    println!("{}", greeting);
+
+    // + takes the String by value and appends to it, so no copy is made
+    // of the left side; the right side must be a &str
+    let name = String::from("ana");
+    let message = greeting + ", from " + &name;
+    println!("{}", message);
+
+    // with_capacity allocates once when we know roughly how much is coming
+    let mut csv = String::with_capacity(64);
+    let start = csv.capacity();
+    for (i, item) in ["apples", "pears", "plums"].iter().enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        csv.push_str(item);
+    }
+    println!("{} (capacity unchanged: {})", csv, csv.capacity() == start);
+
+    // write! appends formatted text to a String without a temporary; it
+    // needs the fmt::Write trait in scope, and returns a Result that can't
+    // actually fail for a String
+    let mut table = String::new();
+    for (fruit, price) in [("apples", 1.5), ("pears", 2.25)] {
+        writeln!(table, "{:<8}{:>6.2}", fruit, price).unwrap();
+    }
+    print!("{}", table);
+
+    // join and collect build a string from many pieces at once
+    let words = ["one", "two", "three"];
+    println!("{}", words.join(" + "));
+    let initials: String = words.iter().filter_map(|w| w.chars().next()).collect();
+    println!("initials: {}", initials);
+    println!("{}", ["ab"; 3].concat());
+    println!("{}", "-".repeat(12));
+
+    // Counting how often each approach reallocates shows the difference
+    let mut appended = String::new();
+    let mut formatted = String::new();
+    let (mut grew, mut copied) = (0, 0);
+    for i in 0..100 {
+        let before = appended.capacity();
+        write!(appended, "{} ", i).unwrap();
+        if appended.capacity() != before {
+            grew += 1;
+        }
+        // A brand-new String every time round
+        formatted = format!("{}{} ", formatted, i);
+        copied += 1;
+    }
+    println!("same text: {}", appended == formatted);
+    println!(
+        "appending reallocated {} times, format! built {} strings",
+        grew, copied
+    );
+
+    // Replacing and case changes create a new String; mutating methods
+    // like retain() and truncate() work in place
+    let mut shouty = "Hello, World!".to_uppercase();
+    shouty.retain(|c| c.is_alphabetic());
+    shouty.truncate(5);
+    println!("{}", shouty);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let both = "con" + "cat";
+    // error[E0369]: cannot add `&str` to `&str`
+}
+
+/*
+ * Key Concepts:
+ * - push/push_str/write! append to one String in place
+ * - String + &str reuses the left String; a loop of format! copies every time
+ * - with_capacity avoids reallocating when the size is roughly known
+ * - join, concat, repeat, and collect::<String>() build from many pieces
+ * - fmt::Write makes write!/writeln! work on a String
+ */
+
+// EXPECTED:
+// hello, world
+// hello, world, from ana
+// apples,pears,plums (capacity unchanged: true)
+// apples    1.50
+// pears     2.25
+// one + two + three
+// initials: ott
+// ababab
+// ------------
+// same text: true
+// appending reallocated 7 times, format! built 100 strings
+// HELLO
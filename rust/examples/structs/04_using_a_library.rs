@@ -0,0 +1,88 @@
+// Structs 4: Using a Library Split into Modules
+// Demonstrates consuming a crate's public API, `pub use` re-exports, and
+//
+// Concepts: library crates, public APIs, pub use re-exports
+// Difficulty: beginner
+// Minutes: 5
+// modules that live in their own files
+//
+// The `inventory` crate is in the `inventory/` folder next to this file.
+// Open `inventory/lib.rs` first: it declares the modules and chooses what
+// to re-export.
+
+// Thanks to `pub use` in lib.rs, these come straight from the crate root
+use inventory::{Item, Sku, StockError, Warehouse};
+// `report` is a public module, so its functions are reached through it
+use inventory::report;
+
+fn sku(code: &str) -> Sku {
+    Sku::parse(code).expect("example SKUs are valid")
+}
+
+fn main() {
+    let mut warehouse = Warehouse::new();
+    warehouse.receive(Item::new("Rust book", sku("BOOK-001"), 3999), 5);
+    warehouse.receive(Item::new("Crab plush", sku("TOY-042"), 1250), 12);
+    warehouse.receive(Item::new("Rust book", sku("BOOK-001"), 3999), 3); // restock
+    println!("{}\n", report::summary(&warehouse));
+
+    // Invalid SKUs are rejected by the only constructor there is
+    println!("Sku::parse(\"oops\") = {:?}", Sku::parse("oops"));
+
+    match warehouse.ship(&sku("TOY-042"), 20) {
+        Ok(()) => println!("shipped 20 plushies"),
+        Err(error) => println!("could not ship: {}", error),
+    }
+    let missing = warehouse.ship(&sku("MUG-007"), 1);
+    println!("shipping a mug: {:?}", missing);
+    assert_eq!(missing, Err(StockError::UnknownSku(sku("MUG-007"))));
+
+    warehouse.ship(&sku("BOOK-001"), 2).unwrap();
+    warehouse.discount(20);
+    println!("\nAfter shipping 2 books and a 20% sale:");
+    println!("{}", report::summary(&warehouse));
+    println!("books left: {}", warehouse.quantity(&sku("BOOK-001")));
+
+    // The library's privacy rules apply to us, too.
+    let book = Item::new("Notebook", sku("BOOK-002"), 499);
+    println!("\n{} costs {} cents", book.name, book.price_cents());
+    // This would cause an error:
+    // book.price_cents = 1;
+    // error[E0616]: field `price_cents` of struct `Item` is private
+
+    // pub(crate) means "public inside inventory", which we are not
+    // This would cause an error:
+    // let mut book = book; book.set_price(1);
+    // error[E0624]: method `set_price` is private
+
+    // The modules themselves are private; only the re-exports are public
+    // This would cause an error:
+    // let _ = inventory::item::Item::new("x", sku("X-1"), 1);
+    // error[E0603]: module `item` is private
+}
+
+/*
+ * Key Concepts:
+ * - `mod name;` loads a module from name.rs (or name/mod.rs)
+ * - lib.rs is the crate root and defines the public API
+ * - `pub use` re-exports items so users get short, stable paths
+ * - Private modules, fields, and helpers stay hidden from other crates
+ * - pub(crate) items are shared inside the crate but not outside it
+ */
+
+// EXPECTED:
+// BOOK-001   Rust book      8 x $39.99
+// TOY-042    Crab plush    12 x $12.50
+// total value: $469.92
+//
+// Sku::parse("oops") = None
+// could not ship: only 12 of TOY-042 left
+// shipping a mug: Err(UnknownSku(Sku("MUG-007")))
+//
+// After shipping 2 books and a 20% sale:
+// BOOK-001   Rust book      6 x $31.99
+// TOY-042    Crab plush    12 x $10.00
+// total value: $311.94
+// books left: 6
+//
+// Notebook costs 499 cents
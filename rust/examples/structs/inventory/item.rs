@@ -0,0 +1,61 @@
+//! Things the warehouse can hold.
+
+use std::fmt;
+
+/// A stock-keeping unit: the code printed on a product's label.
+///
+/// A tuple struct around a `String`, so a SKU can't be mixed up with any
+/// other piece of text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sku(String);
+
+impl Sku {
+    /// Parses a SKU like `"BOOK-001"`: upper-case letters, a dash, digits.
+    pub fn parse(code: &str) -> Option<Sku> {
+        let (letters, digits) = code.split_once('-')?;
+        let valid = !letters.is_empty()
+            && !digits.is_empty()
+            && letters.chars().all(|c| c.is_ascii_uppercase())
+            && digits.chars().all(|c| c.is_ascii_digit());
+        valid.then(|| Sku(code.to_string()))
+    }
+}
+
+impl fmt::Display for Sku {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A product with a name and a price.
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub name: String,
+    sku: Sku,
+    /// Prices are whole cents, so they never suffer from rounding.
+    price_cents: u32,
+}
+
+impl Item {
+    pub fn new(name: &str, sku: Sku, price_cents: u32) -> Item {
+        Item {
+            name: name.to_string(),
+            sku,
+            price_cents,
+        }
+    }
+
+    pub fn sku(&self) -> &Sku {
+        &self.sku
+    }
+
+    pub fn price_cents(&self) -> u32 {
+        self.price_cents
+    }
+
+    /// Visible to the rest of this crate (the warehouse applies discounts),
+    /// but not to users of the library.
+    pub(crate) fn set_price(&mut self, price_cents: u32) {
+        self.price_cents = price_cents;
+    }
+}
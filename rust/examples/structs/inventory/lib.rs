@@ -0,0 +1,26 @@
+//! A tiny library used by `04_using_a_library.rs`.
+//!
+//! The crate root (this file) decides what the outside world sees. The
+//! code itself is split across one file per module:
+//!
+//! ```text
+//! inventory/
+//! ├── lib.rs        <- crate root: declares modules, re-exports
+//! ├── item.rs       <- mod item
+//! ├── warehouse.rs  <- mod warehouse
+//! └── report.rs     <- pub mod report
+//! ```
+
+// `mod item;` (with a semicolon) tells the compiler to load the module
+// from `item.rs` next to this file. The modules are private...
+mod item;
+mod warehouse;
+
+// ...but `pub use` re-exports the useful types at the top of the crate, so
+// users write `inventory::Item` instead of `inventory::item::Item`, and we
+// stay free to reorganize the files later.
+pub use item::{Item, Sku};
+pub use warehouse::{StockError, Warehouse};
+
+// A public module: its path is part of the API (`inventory::report::...`).
+pub mod report;
@@ -0,0 +1,34 @@
+//! Human-readable summaries of a warehouse.
+
+use crate::Warehouse;
+
+/// Total value of everything in stock, in cents.
+pub fn total_value(warehouse: &Warehouse) -> u64 {
+    warehouse
+        .items()
+        .map(|(item, quantity)| u64::from(item.price_cents()) * u64::from(quantity))
+        .sum()
+}
+
+/// One line per item, then the total.
+pub fn summary(warehouse: &Warehouse) -> String {
+    let mut lines: Vec<String> = warehouse
+        .items()
+        .map(|(item, quantity)| {
+            format!(
+                "{:<10} {:<12} {:>3} x {}",
+                item.sku().to_string(),
+                item.name,
+                quantity,
+                dollars(u64::from(item.price_cents()))
+            )
+        })
+        .collect();
+    lines.push(format!("total value: {}", dollars(total_value(warehouse))));
+    lines.join("\n")
+}
+
+// Private helper: not part of the library's API at all
+fn dollars(cents: u64) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
@@ -0,0 +1,78 @@
+//! Keeping track of how many of each item are in stock.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+// `crate::` starts a path at the crate root; `super::` would start at the
+// parent module. Both name the same `Item` here.
+use crate::item::{Item, Sku};
+
+/// Why a stock change was refused.
+#[derive(Debug, PartialEq)]
+pub enum StockError {
+    UnknownSku(Sku),
+    NotEnough { sku: Sku, available: u32 },
+}
+
+impl fmt::Display for StockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StockError::UnknownSku(sku) => write!(f, "no item with SKU {}", sku),
+            StockError::NotEnough { sku, available } => {
+                write!(f, "only {} of {} left", available, sku)
+            }
+        }
+    }
+}
+
+/// Items and their quantities, ordered by SKU.
+#[derive(Debug, Default)]
+pub struct Warehouse {
+    stock: BTreeMap<Sku, (Item, u32)>,
+}
+
+impl Warehouse {
+    pub fn new() -> Warehouse {
+        Warehouse::default()
+    }
+
+    /// Adds `quantity` of `item`, on top of any already in stock.
+    pub fn receive(&mut self, item: Item, quantity: u32) {
+        self.stock.entry(item.sku().clone()).or_insert((item, 0)).1 += quantity;
+    }
+
+    /// Takes `quantity` of an item out of stock.
+    pub fn ship(&mut self, sku: &Sku, quantity: u32) -> Result<(), StockError> {
+        let (_, available) = self
+            .stock
+            .get_mut(sku)
+            .ok_or_else(|| StockError::UnknownSku(sku.clone()))?;
+        if *available < quantity {
+            return Err(StockError::NotEnough {
+                sku: sku.clone(),
+                available: *available,
+            });
+        }
+        *available -= quantity;
+        Ok(())
+    }
+
+    /// Cuts the price of every item by `percent`.
+    pub fn discount(&mut self, percent: u32) {
+        for (item, _) in self.stock.values_mut() {
+            let price = item.price_cents() * (100 - percent.min(100)) / 100;
+            item.set_price(price);
+        }
+    }
+
+    pub fn quantity(&self, sku: &Sku) -> u32 {
+        self.stock.get(sku).map_or(0, |(_, quantity)| *quantity)
+    }
+
+    /// Every item with its quantity, in SKU order.
+    pub fn items(&self) -> impl Iterator<Item = (&Item, u32)> {
+        self.stock
+            .values()
+            .map(|(item, quantity)| (item, *quantity))
+    }
+}
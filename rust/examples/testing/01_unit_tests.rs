@@ -0,0 +1,124 @@
+// Testing 1: Unit Tests
+// Demonstrates #[test], the assert macros, and the #[cfg(test)] module
+//
+// Concepts: #[test], assert macros, #[cfg(test)]
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Run this file's tests with:
+//     cargo test -p testing-examples --bin 01_unit_tests
+
+fn celsius_to_fahrenheit(celsius: f64) -> f64 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+fn initials(name: &str) -> String {
+    name.split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .collect()
+}
+
+// Private functions can be unit tested too: the tests module is a child of
+// this module, and children can see their parent's private items.
+fn is_vowel(c: char) -> bool {
+    matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn main() {
+    println!("100°C = {}°F", celsius_to_fahrenheit(100.0));
+    println!(
+        "initials of Grace Brewster Hopper: {}",
+        initials("Grace Brewster Hopper")
+    );
+    println!("is 'E' a vowel? {}", is_vowel('E'));
+    println!("\nThis file is mostly about its tests. Run them with:");
+    println!("    cargo test -p testing-examples --bin 01_unit_tests");
+}
+
+// #[cfg(test)] means "only compile this when running tests": the tests
+// don't end up in the normal program at all.
+#[cfg(test)]
+mod tests {
+    // Bring everything from the parent module into scope
+    use super::*;
+
+    // Any function marked #[test] is a test. It passes if it returns
+    // without panicking.
+    #[test]
+    fn boiling_point() {
+        // assert_eq!(left, right) panics, printing both values, if they differ
+        assert_eq!(celsius_to_fahrenheit(100.0), 212.0);
+    }
+
+    #[test]
+    fn freezing_point() {
+        assert_eq!(celsius_to_fahrenheit(0.0), 32.0);
+    }
+
+    #[test]
+    fn initials_take_the_first_letter_of_each_word() {
+        assert_eq!(initials("Ada Lovelace"), "AL");
+        // assert_ne! checks that two values are different
+        assert_ne!(initials("Ada Lovelace"), "ada");
+    }
+
+    #[test]
+    fn initials_ignore_extra_spaces() {
+        // Every assert macro accepts a custom message, formatted like println!
+        let result = initials("  Alan   Turing ");
+        assert_eq!(result, "AT", "extra spaces should not add initials");
+    }
+
+    #[test]
+    fn vowels_are_case_insensitive() {
+        // assert! takes any boolean expression
+        assert!(is_vowel('a'));
+        assert!(is_vowel('U'));
+        assert!(!is_vowel('z'), "z is not a vowel");
+    }
+
+    // Floating point results are rarely exact, so compare with a tolerance
+    #[test]
+    fn body_temperature_is_close_enough() {
+        let fahrenheit = celsius_to_fahrenheit(37.2);
+        assert!((fahrenheit - 98.96).abs() < 1e-9, "got {}", fahrenheit);
+    }
+
+    // #[ignore] skips a test unless you ask for it with
+    //     cargo test -p testing-examples -- --ignored
+    // Handy for slow tests you don't want on every run.
+    #[test]
+    #[ignore = "slow: checks a million conversions"]
+    fn round_trips_a_million_values() {
+        for i in 0..1_000_000 {
+            let celsius = i as f64 / 1000.0;
+            let back = (celsius_to_fahrenheit(celsius) - 32.0) * 5.0 / 9.0;
+            assert!((back - celsius).abs() < 1e-9);
+        }
+    }
+}
+
+// A failing assert_eq! prints something like:
+//
+//     ---- tests::boiling_point stdout ----
+//     thread 'tests::boiling_point' panicked at 01_unit_tests.rs:42:9:
+//     assertion `left == right` failed
+//       left: 211.0
+//      right: 212.0
+
+/*
+ * Key Concepts:
+ * - #[test] marks a test function; panicking means failing
+ * - assert!, assert_eq!, assert_ne! check conditions and print details
+ * - #[cfg(test)] mod tests keeps tests out of the normal build
+ * - Unit tests live next to the code and can test private functions
+ * - #[ignore] skips slow tests unless run with -- --ignored
+ */
+
+// EXPECTED:
+// 100°C = 212°F
+// initials of Grace Brewster Hopper: GBH
+// is 'E' a vowel? true
+//
+// This file is mostly about its tests. Run them with:
+//     cargo test -p testing-examples --bin 01_unit_tests
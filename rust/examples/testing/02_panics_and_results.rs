@@ -0,0 +1,139 @@
+// Testing 2: Testing Panics and Errors
+// Demonstrates #[should_panic], tests that return Result, and testing errors
+//
+// Concepts: #[should_panic], tests returning Result, testing errors
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Run this file's tests with:
+//     cargo test -p testing-examples --bin 02_panics_and_results
+
+#[derive(Debug, PartialEq)]
+enum WithdrawError {
+    Insufficient { balance: u64, requested: u64 },
+    Zero,
+}
+
+struct Account {
+    balance: u64,
+}
+
+impl Account {
+    // Opening an account with a silly deposit is a programming mistake,
+    // so it panics instead of returning an error
+    fn open(deposit: u64) -> Account {
+        if deposit > 1_000_000 {
+            panic!("deposit of {} is over the limit", deposit);
+        }
+        Account { balance: deposit }
+    }
+
+    // Running out of money is an expected situation, so it's an error
+    fn withdraw(&mut self, amount: u64) -> Result<u64, WithdrawError> {
+        if amount == 0 {
+            return Err(WithdrawError::Zero);
+        }
+        if amount > self.balance {
+            return Err(WithdrawError::Insufficient {
+                balance: self.balance,
+                requested: amount,
+            });
+        }
+        self.balance -= amount;
+        Ok(self.balance)
+    }
+}
+
+fn parse_amount(text: &str) -> Result<u64, String> {
+    text.trim()
+        .parse()
+        .map_err(|_| format!("{:?} is not an amount", text))
+}
+
+fn main() {
+    let mut account = Account::open(100);
+    println!("withdraw 30: {:?}", account.withdraw(30));
+    println!("withdraw 500: {:?}", account.withdraw(500));
+    println!("parse \" 42 \": {:?}", parse_amount(" 42 "));
+    println!("\nRun the tests with:");
+    println!("    cargo test -p testing-examples --bin 02_panics_and_results");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // #[should_panic] inverts the test: it passes only if the body panics
+    #[test]
+    #[should_panic]
+    fn huge_deposits_panic() {
+        Account::open(5_000_000);
+    }
+
+    // `expected` also checks the panic message contains this text, so the
+    // test can't pass because of some OTHER, unrelated panic
+    #[test]
+    #[should_panic(expected = "over the limit")]
+    fn huge_deposits_panic_with_a_clear_message() {
+        Account::open(5_000_000);
+    }
+
+    // Errors are values, so compare them like any other value
+    #[test]
+    fn overdrawing_reports_the_balance() {
+        let mut account = Account::open(10);
+        assert_eq!(
+            account.withdraw(25),
+            Err(WithdrawError::Insufficient {
+                balance: 10,
+                requested: 25
+            })
+        );
+    }
+
+    // matches! is handy when you only care about the variant
+    #[test]
+    fn withdrawing_nothing_is_an_error() {
+        let mut account = Account::open(10);
+        assert!(matches!(account.withdraw(0), Err(WithdrawError::Zero)));
+    }
+
+    // A test can return Result. Err means failure, and `?` works inside,
+    // which keeps tests with several fallible steps short.
+    #[test]
+    fn withdraw_parsed_amounts() -> Result<(), String> {
+        let mut account = Account::open(100);
+        let amount = parse_amount("40")?;
+        let left = account.withdraw(amount).map_err(|e| format!("{:?}", e))?;
+        assert_eq!(left, 60);
+        Ok(())
+    }
+
+    // is_err() / unwrap_err() test the error path of functions returning Result
+    #[test]
+    fn rejects_text_that_is_not_a_number() {
+        let error = parse_amount("ten").unwrap_err();
+        assert!(
+            error.contains("not an amount"),
+            "unexpected error: {}",
+            error
+        );
+    }
+}
+
+/*
+ * Key Concepts:
+ * - #[should_panic] passes only when the test panics
+ * - should_panic(expected = "...") also checks the message
+ * - Compare Result values directly with assert_eq!
+ * - Tests can return Result<(), E> and use ?
+ * - Test the error paths, not just the happy path
+ */
+
+// EXPECTED:
+// withdraw 30: Ok(70)
+// withdraw 500: Err(Insufficient { balance: 70, requested: 500 })
+// parse " 42 ": Ok(42)
+//
+// Run the tests with:
+//     cargo test -p testing-examples --bin 02_panics_and_results
@@ -0,0 +1,54 @@
+// Testing 3: Doc Tests
+// Demonstrates examples in documentation that cargo test runs
+//
+// Concepts: doc tests, documentation examples
+// Difficulty: intermediate
+// Minutes: 5
+//
+// Open `stats/lib.rs`: every ``` block in its /// comments is a test.
+// Run just the doc tests with:
+//     cargo test -p testing-examples --doc
+
+use stats::{mean, median, normalize};
+
+fn main() {
+    let scores = [72.0, 85.0, 90.0, 66.0, 85.0];
+    println!("mean:   {:?}", mean(&scores));
+    println!("median: {:?}", median(&scores));
+
+    let shares = normalize(&[1.0, 1.0, 2.0]);
+    println!("normalized: {:?}", shares);
+
+    // Doc comments come in two flavours:
+    //   ///  documents the item below it (a function, struct, ...)
+    //   //!  documents the enclosing item (the crate or module itself)
+    //
+    // Inside them, a code block is compiled and run as a test. Annotations
+    // after the opening ``` change how:
+    //   ```should_panic  the example must panic
+    //   ```no_run        compile it, but don't run it (e.g. network code)
+    //   ```ignore        don't even compile it
+    //   ```text          not Rust at all, just text
+    //
+    // `cargo doc --open` renders the same comments as HTML, with the
+    // examples included, so good docs and good tests are the same work.
+    println!("\nRead stats/lib.rs, then run the doc tests with:");
+    println!("    cargo test -p testing-examples --doc");
+}
+
+/*
+ * Key Concepts:
+ * - Code blocks in /// and //! comments are compiled and run as tests
+ * - Doc tests use the crate like an outside user would
+ * - `# ` hides setup lines from the rendered docs
+ * - should_panic, no_run, and ignore change how a block is tested
+ * - Examples in docs can't go stale, because they're tested
+ */
+
+// EXPECTED:
+// mean:   Some(79.6)
+// median: Some(85.0)
+// normalized: [0.25, 0.25, 0.5]
+//
+// Read stats/lib.rs, then run the doc tests with:
+//     cargo test -p testing-examples --doc
@@ -0,0 +1,57 @@
+// Testing 4: Integration Tests
+// Demonstrates the tests/ directory and testing through the public API
+//
+// Concepts: tests/ directory, integration tests, public API
+// Difficulty: intermediate
+// Minutes: 5
+//
+// Look at `tests/stats_api.rs` and `tests/common/mod.rs`, then run:
+//     cargo test -p testing-examples --test stats_api
+
+use stats::Summary;
+
+fn main() {
+    // Unit tests (01, 02) sit INSIDE the code they test and can reach
+    // private items. Integration tests sit OUTSIDE, in tests/, and use the
+    // library exactly as a user would: only `pub` items, via `use stats::`.
+    //
+    //     examples/testing/
+    //     ├── stats/lib.rs          <- the library (with unit + doc tests)
+    //     └── tests/
+    //         ├── stats_api.rs      <- an integration test crate
+    //         └── common/mod.rs     <- helpers shared between test files
+    //
+    // Integration tests catch a different class of bug: the pieces each
+    // work, but don't fit together, or the public API is awkward to use.
+    let mut summary = Summary::default();
+    for value in [4.0, 8.0, 15.0, 16.0, 23.0, 42.0] {
+        summary.add(value);
+    }
+    println!(
+        "{} values from {:?} to {:?}",
+        summary.count(),
+        summary.min(),
+        summary.max()
+    );
+
+    // Binaries (like this file) can't be used from integration tests; only
+    // libraries can. That's one reason real projects keep main.rs thin and
+    // put the logic in lib.rs.
+    println!("\nRun the integration tests with:");
+    println!("    cargo test -p testing-examples --test stats_api");
+}
+
+/*
+ * Key Concepts:
+ * - Files in tests/ are integration tests, each compiled as its own crate
+ * - Integration tests can only use the library's public API
+ * - Shared helpers go in tests/common/mod.rs
+ * - `cargo test --test name` runs a single integration test file
+ * - Keep logic in a library so it can be integration tested
+ */
+
+// EXPECTED:
+// 6 values from Some(4.0) to Some(42.0)
+//
+// Run the integration tests with:
+//     cargo test -p testing-examples --test stats_api
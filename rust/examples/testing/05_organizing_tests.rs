@@ -0,0 +1,140 @@
+// Testing 5: Organizing Tests
+// Demonstrates nested test modules, helpers, table-driven tests, and filters
+//
+// Concepts: test modules, helpers, table-driven tests, test filters
+// Difficulty: intermediate
+// Minutes: 10
+//
+// Run this file's tests with:
+//     cargo test -p testing-examples --bin 05_organizing_tests
+
+#[derive(Debug, Clone, PartialEq)]
+struct Cart {
+    items: Vec<(String, u32)>, // (name, price in cents)
+    coupon: Option<u32>,       // percent off
+}
+
+impl Cart {
+    fn new() -> Cart {
+        Cart {
+            items: Vec::new(),
+            coupon: None,
+        }
+    }
+
+    fn add(&mut self, name: &str, price: u32) {
+        self.items.push((name.to_string(), price));
+    }
+
+    fn apply_coupon(&mut self, percent: u32) -> Result<(), String> {
+        if percent == 0 || percent > 50 {
+            return Err(format!("{}% is not a valid coupon", percent));
+        }
+        self.coupon = Some(percent);
+        Ok(())
+    }
+
+    fn total(&self) -> u32 {
+        let subtotal: u32 = self.items.iter().map(|(_, price)| price).sum();
+        match self.coupon {
+            Some(percent) => subtotal - subtotal * percent / 100,
+            None => subtotal,
+        }
+    }
+}
+
+fn main() {
+    let mut cart = Cart::new();
+    cart.add("book", 2000);
+    cart.add("pen", 150);
+    cart.apply_coupon(10).unwrap();
+    println!("{:?} -> total {} cents", cart, cart.total());
+    println!("\nRun the tests with:");
+    println!("    cargo test -p testing-examples --bin 05_organizing_tests");
+    println!("Only the coupon tests:");
+    println!("    cargo test -p testing-examples --bin 05_organizing_tests coupons");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Helpers (a.k.a. fixtures) build the common starting point, so each
+    // test only spells out what makes it different
+    fn cart_with(prices: &[u32]) -> Cart {
+        let mut cart = Cart::new();
+        for (i, &price) in prices.iter().enumerate() {
+            cart.add(&format!("item{}", i), price);
+        }
+        cart
+    }
+
+    // Nested modules group related tests. Their names become part of the
+    // test path (tests::totals::empty_cart_is_free), and
+    // `cargo test totals` runs only the tests whose path contains "totals".
+    mod totals {
+        use super::*;
+
+        #[test]
+        fn empty_cart_is_free() {
+            assert_eq!(Cart::new().total(), 0);
+        }
+
+        #[test]
+        fn adds_up_every_item() {
+            assert_eq!(cart_with(&[100, 250, 5]).total(), 355);
+        }
+    }
+
+    mod coupons {
+        use super::*;
+
+        // Table-driven test: many cases, one loop. The message says which
+        // case failed, since the test name alone can't.
+        #[test]
+        fn discounts_the_total() {
+            let cases = [(10, 900), (25, 750), (50, 500)];
+            for (percent, expected) in cases {
+                let mut cart = cart_with(&[1000]);
+                cart.apply_coupon(percent).unwrap();
+                assert_eq!(cart.total(), expected, "with a {}% coupon", percent);
+            }
+        }
+
+        #[test]
+        fn rejects_out_of_range_coupons() {
+            for percent in [0, 51, 100] {
+                let mut cart = cart_with(&[1000]);
+                assert!(
+                    cart.apply_coupon(percent).is_err(),
+                    "{}% was accepted",
+                    percent
+                );
+                assert_eq!(cart.coupon, None, "a rejected coupon must not stick");
+            }
+        }
+    }
+}
+
+// Useful `cargo test` flags (everything after `--` goes to the test binary):
+//   cargo test NAME              only tests whose path contains NAME
+//   cargo test -- --nocapture    show println! output from passing tests
+//   cargo test -- --ignored      run only the #[ignore] tests
+//   cargo test -- --test-threads=1   run tests one at a time
+
+/*
+ * Key Concepts:
+ * - Nested modules group tests and make them easy to filter
+ * - Helper functions build common test data
+ * - Table-driven tests cover many cases with one assertion loop
+ * - Include the failing case in assertion messages
+ * - cargo test NAME filters by test path
+ */
+
+// EXPECTED:
+// Cart { items: [("book", 2000), ("pen", 150)], coupon: Some(10) } -> total 1935 cents
+//
+// Run the tests with:
+//     cargo test -p testing-examples --bin 05_organizing_tests
+// Only the coupon tests:
+//     cargo test -p testing-examples --bin 05_organizing_tests coupons
@@ -0,0 +1,121 @@
+//! Small statistics helpers, used by the testing chapter.
+//!
+//! Every code block in these doc comments is a **doc test**: `cargo test`
+//! compiles and runs it, so the examples in the documentation can never
+//! silently go out of date.
+//!
+//! ```
+//! let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+//! assert_eq!(stats::mean(&data), Some(5.0));
+//! assert_eq!(stats::median(&data), Some(4.5));
+//! ```
+
+/// The average of `values`, or `None` if there are none.
+///
+/// ```
+/// assert_eq!(stats::mean(&[1.0, 2.0, 3.0]), Some(2.0));
+/// assert_eq!(stats::mean(&[]), None);
+/// ```
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// The middle value of `values` once sorted (the average of the two middle
+/// values for an even count), or `None` if there are none.
+///
+/// Lines starting with `# ` are compiled but hidden from the rendered
+/// docs, which keeps examples focused:
+///
+/// ```
+/// # use stats::median;
+/// assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+/// assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+/// ```
+pub fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        Some((sorted[middle - 1] + sorted[middle]) / 2.0)
+    } else {
+        Some(sorted[middle])
+    }
+}
+
+/// Scales `values` so they add up to 1.0.
+///
+/// # Panics
+///
+/// Panics if the values add up to zero. A `should_panic` doc test proves
+/// it:
+///
+/// ```should_panic
+/// stats::normalize(&[0.0, 0.0]);
+/// ```
+pub fn normalize(values: &[f64]) -> Vec<f64> {
+    let total: f64 = values.iter().sum();
+    assert!(total != 0.0, "cannot normalize values that sum to zero");
+    values.iter().map(|value| value / total).collect()
+}
+
+/// A running summary that can be fed one value at a time.
+///
+/// Doc tests can use `?` too, if they end with an `Ok` value:
+///
+/// ```
+/// use stats::Summary;
+///
+/// let mut summary = Summary::default();
+/// for text in ["3", "5", "10"] {
+///     summary.add(text.parse()?);
+/// }
+/// assert_eq!(summary.count(), 3);
+/// assert_eq!(summary.max(), Some(10.0));
+/// # Ok::<(), std::num::ParseFloatError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Summary {
+    count: usize,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Summary {
+    pub fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+}
+
+// Libraries have unit tests too, right next to the code, exactly like in
+// 01_unit_tests.rs.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_does_not_change_the_input() {
+        let values = [3.0, 1.0, 2.0];
+        median(&values);
+        assert_eq!(values, [3.0, 1.0, 2.0]);
+    }
+}
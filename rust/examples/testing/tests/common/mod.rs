@@ -0,0 +1,16 @@
+// Helpers shared by the integration tests.
+//
+// Files directly in `tests/` are each compiled as a separate test crate.
+// Putting shared code in `tests/common/mod.rs` (a subdirectory) keeps cargo
+// from treating it as a test crate of its own.
+
+pub fn sample_scores() -> Vec<f64> {
+    vec![56.0, 72.0, 72.0, 85.0, 99.0]
+}
+
+pub fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "expected {expected}, got {actual}"
+    );
+}
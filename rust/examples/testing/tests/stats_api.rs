@@ -0,0 +1,42 @@
+// Integration tests for the `stats` library.
+//
+// These live outside the library, in `tests/`, and can only use its PUBLIC
+// API, exactly like another crate would. Each file here is its own crate.
+
+mod common;
+
+use stats::{mean, median, normalize, Summary};
+
+#[test]
+fn mean_and_median_agree_with_hand_calculations() {
+    let scores = common::sample_scores();
+    common::assert_close(mean(&scores).unwrap(), 76.8);
+    assert_eq!(median(&scores), Some(72.0));
+}
+
+#[test]
+fn empty_input_has_no_statistics() {
+    assert_eq!(mean(&[]), None);
+    assert_eq!(median(&[]), None);
+    let summary = Summary::default();
+    assert_eq!(
+        (summary.count(), summary.min(), summary.max()),
+        (0, None, None)
+    );
+}
+
+#[test]
+fn normalized_values_sum_to_one() {
+    let shares = normalize(&common::sample_scores());
+    common::assert_close(shares.iter().sum(), 1.0);
+}
+
+#[test]
+fn summary_tracks_the_extremes() {
+    let mut summary = Summary::default();
+    for score in common::sample_scores() {
+        summary.add(score);
+    }
+    assert_eq!(summary.min(), Some(56.0));
+    assert_eq!(summary.max(), Some(99.0));
+}
@@ -0,0 +1,106 @@
+// Time 1: Instant and Duration
+// Demonstrates std::time::{Instant, Duration, SystemTime} and measuring work
+//
+// Concepts: Instant, Duration, SystemTime, measuring work
+// Difficulty: beginner
+// Minutes: 10
+//
+// std has two clocks. Instant is monotonic: it only goes forward, so the
+// difference between two Instants is a trustworthy elapsed time, but an
+// Instant means nothing outside the running program. SystemTime is the
+// wall clock: it can be printed and stored, but it can jump when the
+// system's time is corrected, so it is the wrong tool for timing code.
+// Both measure spans as a Duration.
+
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Runs `f` and returns its result with how long it took.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn main() {
+    // Durations are built from any unit and do exact arithmetic
+    let frame = Duration::from_millis(16) + Duration::from_micros(667);
+    println!("a frame: {:?}", frame);
+    println!("60 frames: {:?}", frame * 60);
+    println!("in seconds: {}", Duration::from_millis(2500).as_secs_f64());
+    println!("whole seconds: {}", Duration::from_millis(2500).as_secs());
+
+    // A Duration can't be negative: subtracting too much is an error to
+    // handle, or saturates at zero
+    let short = Duration::from_secs(1);
+    println!(
+        "checked 1s - 2s: {:?}",
+        short.checked_sub(Duration::from_secs(2))
+    );
+    println!(
+        "saturating 1s - 2s: {:?}",
+        short.saturating_sub(Duration::from_secs(2))
+    );
+
+    // Measuring: elapsed() is Instant::now() minus the start. The exact
+    // numbers differ on every run, so we only check what is guaranteed.
+    let (sum, took) = timed(|| {
+        thread::sleep(Duration::from_millis(20));
+        (1..=1_000u64).sum::<u64>()
+    });
+    println!(
+        "sum {} took at least 20ms: {}",
+        sum,
+        took >= Duration::from_millis(20)
+    );
+
+    // Deadlines: compare Instants
+    let deadline = Instant::now() + Duration::from_millis(30);
+    let mut rounds = 0;
+    while Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(10));
+        rounds += 1;
+    }
+    println!("worked until the deadline: {}", (1..=3).contains(&rounds));
+
+    // Formatting a Duration for people is up to us
+    let uptime = Duration::from_secs(93_784);
+    let (days, rest) = (uptime.as_secs() / 86_400, uptime.as_secs() % 86_400);
+    println!(
+        "uptime: {}d {:02}:{:02}:{:02}",
+        days,
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    );
+
+    // SystemTime is "now" as the wall clock sees it, usually stored as
+    // the time since the Unix epoch (1970-01-01 00:00:00 UTC)
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    println!("after 2020: {}", since_epoch.as_secs() > 1_577_836_800);
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let later: Instant = Instant::now() + 5;
+    // error[E0308]: mismatched types (expected `Duration`, found integer)
+}
+
+/*
+ * Key Concepts:
+ * - Duration: a span of time, built from_secs/millis/micros, never negative
+ * - Instant: a monotonic clock for elapsed times and deadlines
+ * - SystemTime: the wall clock, for timestamps; it can jump, so don't time with it
+ * - checked_sub/saturating_sub for arithmetic that could go below zero
+ */
+
+// EXPECTED:
+// a frame: 16.667ms
+// 60 frames: 1.00002s
+// in seconds: 2.5
+// whole seconds: 2
+// checked 1s - 2s: None
+// saturating 1s - 2s: 0ns
+// sum 500500 took at least 20ms: true
+// worked until the deadline: true
+// uptime: 1d 02:03:04
+// after 2020: true
@@ -0,0 +1,106 @@
+// Time 2: Parsing and Formatting Dates
+// Demonstrates chrono's NaiveDate, NaiveDateTime, DateTime, and format strings
+//
+// Concepts: chrono, NaiveDate, NaiveDateTime, DateTime, format strings
+// Difficulty: intermediate
+// Minutes: 10
+//
+// std has no calendar: no months, no time zones, no way to print a date.
+// chrono fills that gap. Its types say how much they know: a NaiveDate is
+// a day on the calendar, a NaiveDateTime adds a time of day, and neither
+// knows where in the world it is. A DateTime<Tz> is an exact moment, with
+// a time zone (or offset) to show it in. Parsing and formatting share one
+// strftime-style language: %Y is the year, %m the month, and so on.
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Timelike, Utc};
+
+fn main() {
+    // Constructing checks the calendar: there's no 30th of February
+    let release = NaiveDate::from_ymd_opt(2015, 5, 15).unwrap();
+    println!("release: {}", release);
+    println!("2023-02-29: {:?}", NaiveDate::from_ymd_opt(2023, 2, 29));
+    println!(
+        "{} was a {:?}, day {} of the year",
+        release,
+        release.weekday(),
+        release.ordinal()
+    );
+
+    // ISO 8601 (YYYY-MM-DD) is what FromStr expects
+    let parsed: NaiveDate = "2024-02-29".parse().unwrap();
+    println!("parsed: {} (leap year: {})", parsed, parsed.leap_year());
+
+    // Other layouts need a format string, for parsing and for printing
+    let us = NaiveDate::parse_from_str("07/04/1976", "%m/%d/%Y").unwrap();
+    println!("{}", us.format("%A, %-d %B %Y"));
+    println!("{}", us.format("%d.%m.%y"));
+
+    let meeting = NaiveDateTime::parse_from_str("2024-03-09 14:30", "%Y-%m-%d %H:%M").unwrap();
+    println!(
+        "meeting at {:02}h{:02}, {}",
+        meeting.hour(),
+        meeting.minute(),
+        meeting.format("%a %b %e %I:%M %p")
+    );
+
+    // A parse error says what didn't fit
+    match NaiveDate::parse_from_str("2024-13-01", "%Y-%m-%d") {
+        Ok(date) => println!("parsed {}", date),
+        Err(error) => println!("2024-13-01: {}", error),
+    }
+    match NaiveDate::parse_from_str("2024-03", "%Y-%m-%d") {
+        Ok(date) => println!("parsed {}", date),
+        Err(error) => println!("2024-03: {}", error),
+    }
+
+    // RFC 3339 timestamps carry their offset, so they are exact moments
+    let stamp = DateTime::parse_from_rfc3339("2024-03-09T14:30:00+05:30").unwrap();
+    println!(
+        "offset {}, in UTC {}",
+        stamp.offset(),
+        stamp.with_timezone(&Utc)
+    );
+    println!("as RFC 3339: {}", stamp.to_rfc3339());
+    println!("as RFC 2822: {}", stamp.to_rfc2822());
+
+    // Unix timestamps are seconds since 1970-01-01 UTC
+    let epoch_plus = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    println!("1700000000 is {}", epoch_plus);
+    println!("and back: {}", epoch_plus.timestamp());
+
+    // A NaiveDateTime becomes a moment once we say which offset it's in
+    let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    let local = meeting.and_local_timezone(tokyo).unwrap();
+    println!("{} in Tokyo is {}", meeting, local.with_timezone(&Utc));
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let moment: DateTime<Utc> = meeting;
+    // error[E0308]: mismatched types (expected `DateTime<Utc>`, found `NaiveDateTime`)
+}
+
+/*
+ * Key Concepts:
+ * - NaiveDate/NaiveDateTime: calendar values without a time zone
+ * - DateTime<Tz>: an exact moment, shown in some time zone or offset
+ * - parse_from_str and format share strftime codes: %Y %m %d %H %M %A ...
+ * - from_ymd_opt and friends return None for dates that don't exist
+ * - RFC 3339 and Unix timestamps are the usual formats to store and exchange
+ */
+
+// EXPECTED:
+// release: 2015-05-15
+// 2023-02-29: None
+// 2015-05-15 was a Fri, day 135 of the year
+// parsed: 2024-02-29 (leap year: true)
+// Sunday, 4 July 1976
+// 04.07.76
+// meeting at 14h30, Sat Mar  9 02:30 PM
+// 2024-13-01: input is out of range
+// 2024-03: premature end of input
+// offset +05:30, in UTC 2024-03-09 09:00:00 UTC
+// as RFC 3339: 2024-03-09T14:30:00+05:30
+// as RFC 2822: Sat, 9 Mar 2024 14:30:00 +0530
+// 1700000000 is 2023-11-14 22:13:20 UTC
+// and back: 1700000000
+// 2024-03-09 14:30:00 in Tokyo is 2024-03-09 05:30:00 UTC
@@ -0,0 +1,118 @@
+// Time 3: Time Zones
+// Demonstrates Utc, FixedOffset, chrono-tz zones, and daylight saving time
+//
+// Concepts: Utc, FixedOffset, chrono-tz, daylight saving time
+// Difficulty: intermediate
+// Minutes: 10
+//
+// An offset like +02:00 is a fixed distance from UTC. A time zone like
+// Europe/Berlin is a set of rules deciding which offset applies when:
+// +01:00 in winter, +02:00 in summer, and different rules in past years.
+// chrono-tz compiles the IANA time zone database into the program, so
+// every named zone is a value like `Tz::Europe__Berlin`. The usual advice
+// follows: store and compute in UTC, convert to a zone only to show a time
+// to a person, or to read one they typed.
+
+use chrono::{DateTime, NaiveDate, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+
+fn show(moment: DateTime<Utc>, zones: &[Tz]) {
+    for zone in zones {
+        let local = moment.with_timezone(zone);
+        println!(
+            "  {:<20} {} ({})",
+            zone.name(),
+            local.format("%Y-%m-%d %H:%M"),
+            local.format("%Z %:z")
+        );
+    }
+}
+
+fn main() {
+    let zones = [Tz::America__New_York, Tz::Europe__Berlin, Tz::Asia__Kolkata];
+
+    // One moment, shown in several zones
+    let launch = Utc.with_ymd_and_hms(2024, 1, 15, 17, 0, 0).unwrap();
+    println!("{} in winter:", launch);
+    show(launch, &zones);
+    let summer = Utc.with_ymd_and_hms(2024, 7, 15, 17, 0, 0).unwrap();
+    println!("{} in summer:", summer);
+    show(summer, &zones);
+
+    // Zones parse from their IANA names
+    let zone: Tz = "Australia/Sydney".parse().unwrap();
+    println!("Sydney: {}", launch.with_timezone(&zone).format("%H:%M %Z"));
+    println!("Mars/Olympus: {:?}", "Mars/Olympus".parse::<Tz>().is_ok());
+
+    // Local times a person types can be ambiguous or impossible. On
+    // 2024-03-31 Berlin's clocks jumped from 02:00 to 03:00, and on
+    // 2024-10-27 they went from 03:00 back to 02:00.
+    let berlin = Tz::Europe__Berlin;
+    let gap = NaiveDate::from_ymd_opt(2024, 3, 31)
+        .unwrap()
+        .and_hms_opt(2, 30, 0)
+        .unwrap();
+    let overlap = NaiveDate::from_ymd_opt(2024, 10, 27)
+        .unwrap()
+        .and_hms_opt(2, 30, 0)
+        .unwrap();
+    println!("02:30 on 03-31: {:?}", berlin.from_local_datetime(&gap));
+    let twice = berlin.from_local_datetime(&overlap);
+    println!(
+        "02:30 on 10-27: earliest {}, latest {}",
+        twice.earliest().unwrap().format("%H:%M %Z"),
+        twice.latest().unwrap().format("%H:%M %Z")
+    );
+
+    // Adding 24 hours isn't always "the same time tomorrow". Arithmetic on
+    // a zoned DateTime happens on the exact moment.
+    let evening = berlin.with_ymd_and_hms(2024, 3, 30, 20, 0, 0).unwrap();
+    let plus_day = evening + TimeDelta::hours(24);
+    println!(
+        "{} + 24h = {}",
+        evening.format("%m-%d %H:%M %Z"),
+        plus_day.format("%m-%d %H:%M %Z")
+    );
+
+    // Adding a calendar day goes through the local date instead
+    let tomorrow = evening
+        .date_naive()
+        .succ_opt()
+        .unwrap()
+        .and_time(evening.time())
+        .and_local_timezone(berlin)
+        .unwrap();
+    println!("same time tomorrow: {}", tomorrow.format("%m-%d %H:%M %Z"));
+    println!("which is {} hours later", (tomorrow - evening).num_hours());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let mixed = launch - evening;
+    // error[E0277]: cannot subtract `DateTime<Tz>` from `DateTime<Utc>`
+}
+
+/*
+ * Key Concepts:
+ * - An offset is fixed; a zone is rules that pick the offset for each date
+ * - with_timezone shows the same moment somewhere else
+ * - from_local_datetime can give None (a gap) or two answers (an overlap)
+ * - Adding a TimeDelta moves the exact moment; calendar days go via the date
+ * - Store and compute in UTC, use zones at the edges
+ */
+
+// EXPECTED:
+// 2024-01-15 17:00:00 UTC in winter:
+//   America/New_York     2024-01-15 12:00 (EST -05:00)
+//   Europe/Berlin        2024-01-15 18:00 (CET +01:00)
+//   Asia/Kolkata         2024-01-15 22:30 (IST +05:30)
+// 2024-07-15 17:00:00 UTC in summer:
+//   America/New_York     2024-07-15 13:00 (EDT -04:00)
+//   Europe/Berlin        2024-07-15 19:00 (CEST +02:00)
+//   Asia/Kolkata         2024-07-15 22:30 (IST +05:30)
+// Sydney: 04:00 AEDT
+// Mars/Olympus: false
+// 02:30 on 03-31: None
+// 02:30 on 10-27: earliest 02:30 CEST, latest 02:30 CET
+// 03-30 20:00 CET + 24h = 03-31 21:00 CEST
+// same time tomorrow: 03-31 20:00 CEST
+// which is 23 hours later
@@ -0,0 +1,124 @@
+// Time 4: Date Arithmetic
+// Demonstrates TimeDelta, Months, Days, weekdays, and iterating over dates
+//
+// Concepts: TimeDelta, Months, Days, weekdays, date iteration
+// Difficulty: intermediate
+// Minutes: 10
+//
+// "One month later" has no fixed length: January has 31 days, February 28
+// or 29. chrono keeps two kinds of amounts apart. A TimeDelta is an exact
+// length of time (seconds, or days of exactly 24 hours); Months and Days
+// are calendar steps, which clamp to the end of a shorter month. The
+// difference of two dates is a TimeDelta, so counting days between them
+// is one subtraction.
+
+use chrono::{Datelike, Days, Months, NaiveDate, TimeDelta, Weekday};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// The first `weekday` on or after `from`.
+fn next_weekday(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    from + Days::new(ahead as u64)
+}
+
+/// Whole years from `born` to `on`, or 0 if `on` comes first.
+fn age(born: NaiveDate, on: NaiveDate) -> u32 {
+    on.years_since(born).unwrap_or(0)
+}
+
+fn main() {
+    let start = date(2024, 1, 31);
+
+    // TimeDelta: exact amounts, which can be negative
+    println!("{} + 30 days = {}", start, start + TimeDelta::days(30));
+    println!("{} - 1 week = {}", start, start - TimeDelta::weeks(1));
+
+    // Months: calendar steps, clamped to the month's last day
+    for months in 1..=3 {
+        let later = start.checked_add_months(Months::new(months)).unwrap();
+        println!("{} + {} month(s) = {}", start, months, later);
+    }
+    let leap = date(2024, 2, 29);
+    println!("{} + 1 year = {}", leap, leap + Months::new(12));
+
+    // The difference of two dates
+    let christmas = date(2024, 12, 25);
+    let today = date(2024, 10, 14);
+    let until = christmas - today;
+    println!(
+        "days until christmas: {} ({} weeks)",
+        until.num_days(),
+        until.num_weeks()
+    );
+    println!("and back: {}", (today - christmas).num_days());
+
+    // Weekdays
+    println!("{} is a {}", today, today.weekday());
+    println!("next friday: {}", next_weekday(today, Weekday::Fri));
+    println!("next monday: {}", next_weekday(today, Weekday::Mon));
+
+    // iter_days() walks forward one day at a time
+    let weekend_days = today
+        .iter_days()
+        .take_while(|day| *day < christmas)
+        .filter(|day| matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+        .count();
+    println!("weekend days before christmas: {}", weekend_days);
+
+    // The last day of a month: the day before the first of the next one
+    for month in [2, 4, 12] {
+        let first = date(2024, month, 1);
+        let last = first + Months::new(1) - Days::new(1);
+        println!("last day of 2024-{:02}: {}", month, last.day());
+    }
+
+    // years_since counts whole years, for ages and anniversaries
+    println!("age: {}", age(date(1990, 10, 15), today));
+    println!(
+        "age a day later: {}",
+        age(date(1990, 10, 15), date(2024, 10, 15))
+    );
+
+    // Overflow is an Option, not a panic, with the checked_ methods
+    println!(
+        "far future: {:?}",
+        NaiveDate::MAX.checked_add_days(Days::new(1))
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let later = today + 30;
+    // error[E0277]: cannot add `{integer}` to `NaiveDate`
+}
+
+/*
+ * Key Concepts:
+ * - TimeDelta is an exact span; Days and Months are calendar steps
+ * - Adding Months clamps: January 31st + 1 month is February's last day
+ * - Subtracting dates gives a TimeDelta: num_days(), num_weeks()
+ * - weekday(), iter_days(), and years_since() answer calendar questions
+ * - checked_ methods return None instead of overflowing
+ */
+
+// EXPECTED:
+// 2024-01-31 + 30 days = 2024-03-01
+// 2024-01-31 - 1 week = 2024-01-24
+// 2024-01-31 + 1 month(s) = 2024-02-29
+// 2024-01-31 + 2 month(s) = 2024-03-31
+// 2024-01-31 + 3 month(s) = 2024-04-30
+// 2024-02-29 + 1 year = 2025-02-28
+// days until christmas: 72 (10 weeks)
+// and back: -72
+// 2024-10-14 is a Mon
+// next friday: 2024-10-18
+// next monday: 2024-10-14
+// weekend days before christmas: 20
+// last day of 2024-02: 29
+// last day of 2024-04: 30
+// last day of 2024-12: 31
+// age: 33
+// age a day later: 34
+// far future: None
@@ -0,0 +1,99 @@
+// Traits 1: Defining and Implementing Traits
+// Demonstrates describing shared behavior that many types can provide
+//
+// Concepts: traits, impl Trait for Type, shared behavior
+// Difficulty: intermediate
+// Minutes: 10
+//
+// A trait is a promise: "any type that implements me has these methods".
+// It's similar to an interface in Java or Go.
+
+// The trait lists method signatures without bodies
+trait Speak {
+    fn name(&self) -> String;
+    fn sound(&self) -> String;
+}
+
+struct Dog;
+
+struct Robot {
+    model: u32,
+}
+
+// Each type supplies its own implementation
+impl Speak for Dog {
+    fn name(&self) -> String {
+        String::from("Dog")
+    }
+
+    fn sound(&self) -> String {
+        String::from("Woof!")
+    }
+}
+
+impl Speak for Robot {
+    fn name(&self) -> String {
+        format!("Robot #{}", self.model)
+    }
+
+    fn sound(&self) -> String {
+        String::from("Beep boop.")
+    }
+}
+
+// You can implement your own traits for types you didn't write
+impl Speak for i32 {
+    fn name(&self) -> String {
+        format!("The number {}", self)
+    }
+
+    fn sound(&self) -> String {
+        "...".repeat(*self as usize)
+    }
+}
+
+fn main() {
+    let dog = Dog;
+    let robot = Robot { model: 42 };
+
+    println!("{} says {}", dog.name(), dog.sound());
+    println!("{} says {}", robot.name(), robot.sound());
+    println!("{} says {}", 3.name(), 3.sound());
+
+    // Standard library traits work the same way: Display controls {}
+    let point = Point { x: 1, y: 2 };
+    println!("point = {}", point);
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// This would cause an error:
+// struct Cat;
+// impl Speak for Cat {
+//     fn name(&self) -> String { String::from("Cat") }
+// }
+// error[E0046]: not all trait items implemented, missing: `sound`
+
+/*
+ * Key Concepts:
+ * - `trait Name { fn method(&self); }` declares shared behavior
+ * - `impl Trait for Type { ... }` provides it for one type
+ * - Every required method must be implemented (E0046)
+ * - You can implement your traits for any type, including i32
+ * - Standard traits like Display plug your types into the language
+ */
+
+// EXPECTED:
+// Dog says Woof!
+// Robot #42 says Beep boop.
+// The number 3 says .........
+// point = (1, 2)
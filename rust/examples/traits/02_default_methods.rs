@@ -0,0 +1,72 @@
+// Traits 2: Default Methods
+// Demonstrates traits that provide behavior for free
+//
+// Concepts: default methods, overriding
+// Difficulty: intermediate
+// Minutes: 5
+
+trait Summary {
+    // Required: every implementor must write this one
+    fn author(&self) -> String;
+
+    // Provided: a default body that implementors can keep or override
+    fn summarize(&self) -> String {
+        format!("(Read more from {}...)", self.author())
+    }
+}
+
+struct Tweet {
+    username: String,
+    content: String,
+}
+
+struct Article {
+    title: String,
+    author: String,
+}
+
+// Tweet only writes the required method and keeps the default summarize
+impl Summary for Tweet {
+    fn author(&self) -> String {
+        format!("@{}", self.username)
+    }
+}
+
+// Article overrides the default with something more specific
+impl Summary for Article {
+    fn author(&self) -> String {
+        self.author.clone()
+    }
+
+    fn summarize(&self) -> String {
+        format!("{}, by {}", self.title, self.author())
+    }
+}
+
+fn main() {
+    let tweet = Tweet {
+        username: String::from("rustlang"),
+        content: String::from("Rust 2024 is here!"),
+    };
+    let article = Article {
+        title: String::from("Traits in Depth"),
+        author: String::from("Ferris"),
+    };
+
+    println!("Tweet:   {}", tweet.summarize()); // Uses the default
+    println!("         \"{}\"", tweet.content);
+    println!("Article: {}", article.summarize()); // Uses the override
+}
+
+/*
+ * Key Concepts:
+ * - A trait method with a body is a default implementation
+ * - Implementors get defaults for free and may override them
+ * - Default methods can call the trait's required methods
+ * - This keeps the "must implement" list small
+ */
+
+// EXPECTED:
+// Tweet:   (Read more from @rustlang...)
+//          "Rust 2024 is here!"
+// Article: Traits in Depth, by Ferris
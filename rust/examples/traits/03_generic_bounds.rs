@@ -0,0 +1,93 @@
+// Traits 3: Generic Functions with Trait Bounds
+// Demonstrates writing ONE function that works for many types
+//
+// Concepts: generics, trait bounds, where clauses
+// Difficulty: intermediate
+// Minutes: 5
+
+use std::fmt::Display;
+
+trait Area {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+struct Circle(f64);
+
+impl Area for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+impl Area for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.0 * self.0
+    }
+}
+
+// T can be ANY type... as long as it implements Area
+fn print_area<T: Area>(shape: &T) {
+    println!("area = {:.2}", shape.area());
+}
+
+// Several bounds are combined with +
+fn show_twice<T: Display + Clone>(value: T) {
+    let copy = value.clone();
+    println!("{} and {}", value, copy);
+}
+
+// `where` clauses keep long bounds readable
+fn larger<T, U>(a: &T, b: &U) -> f64
+where
+    T: Area,
+    U: Area,
+{
+    a.area().max(b.area())
+}
+
+// Bounds also unlock operators: PartialOrd gives us `>`
+fn largest<T: PartialOrd + Copy>(items: &[T]) -> T {
+    let mut largest = items[0];
+    for &item in items {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+fn main() {
+    print_area(&Square(3.0));
+    print_area(&Circle(1.0));
+
+    show_twice("hello");
+    show_twice(42);
+
+    println!("larger area = {:.2}", larger(&Square(2.0), &Circle(1.5)));
+
+    println!("largest number = {}", largest(&[34, 50, 25, 100, 65]));
+    println!("largest char = {}", largest(&['y', 'm', 'a', 'q']));
+
+    // This would cause an error:
+    // print_area(&"not a shape");
+    // error[E0277]: the trait bound `&str: Area` is not satisfied
+}
+
+/*
+ * Key Concepts:
+ * - <T: Trait> means "any type T that implements Trait"
+ * - Combine bounds with +, or move them into a `where` clause
+ * - The compiler generates a specialized copy per concrete type
+ *   (monomorphization), so generics cost nothing at runtime
+ * - Calling with a type that lacks the trait is error E0277
+ */
+
+// EXPECTED:
+// area = 9.00
+// area = 3.14
+// hello and hello
+// 42 and 42
+// larger area = 7.07
+// largest number = 100
+// largest char = y
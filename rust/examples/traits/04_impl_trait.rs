@@ -0,0 +1,60 @@
+// Traits 4: impl Trait
+// Demonstrates a shorter way to accept and return "something that implements a trait"
+//
+// Concepts: impl Trait arguments, impl Trait return types
+// Difficulty: intermediate
+// Minutes: 5
+
+use std::fmt::Display;
+
+// In argument position, impl Trait is shorthand for a generic bound:
+// this is the same as fn announce<T: Display>(item: T)
+fn announce(item: impl Display) {
+    println!("Announcing: {}", item);
+}
+
+// In return position it hides the concrete type from the caller.
+// The caller only knows "it's an iterator of u32s".
+fn evens(limit: u32) -> impl Iterator<Item = u32> {
+    (0..limit).filter(|n| n % 2 == 0)
+}
+
+// Closures have types you can't even write down - impl Fn to the rescue
+fn make_adder(amount: i32) -> impl Fn(i32) -> i32 {
+    move |x| x + amount
+}
+
+fn main() {
+    announce("a string slice");
+    announce(7);
+    announce(3.5);
+
+    let numbers: Vec<u32> = evens(10).collect();
+    println!("evens below 10: {:?}", numbers);
+
+    let add_five = make_adder(5);
+    println!("2 + 5 = {}", add_five(2));
+}
+
+// This would cause an error:
+// fn pick(flag: bool) -> impl Display {
+//     if flag { 1 } else { "one" }
+// }
+// error[E0308]: `if` and `else` have incompatible types
+// A function returning impl Trait must still return ONE concrete type.
+// To return different types, use Box<dyn Trait> (see 05_dyn_trait.rs).
+
+/*
+ * Key Concepts:
+ * - `impl Trait` in arguments = a generic parameter with that bound
+ * - `impl Trait` in return types = "some single type, not named here"
+ * - Perfect for iterators and closures, whose types are unwieldy
+ * - All return paths must produce the same concrete type
+ */
+
+// EXPECTED:
+// Announcing: a string slice
+// Announcing: 7
+// Announcing: 3.5
+// evens below 10: [0, 2, 4, 6, 8]
+// 2 + 5 = 7
@@ -0,0 +1,87 @@
+// Traits 5: Trait Objects with dyn Trait
+// Demonstrates storing DIFFERENT types together behind one trait
+//
+// Concepts: trait objects, dyn Trait, dynamic dispatch
+// Difficulty: intermediate
+// Minutes: 5
+
+trait Animal {
+    fn name(&self) -> String;
+    fn speak(&self) -> String;
+}
+
+struct Cat;
+struct Cow {
+    spots: u32,
+}
+
+impl Animal for Cat {
+    fn name(&self) -> String {
+        String::from("Cat")
+    }
+    fn speak(&self) -> String {
+        String::from("Meow")
+    }
+}
+
+impl Animal for Cow {
+    fn name(&self) -> String {
+        format!("Cow with {} spots", self.spots)
+    }
+    fn speak(&self) -> String {
+        String::from("Moo")
+    }
+}
+
+// &dyn Animal: "a reference to some type that implements Animal,
+// decided at runtime"
+fn introduce(animal: &dyn Animal) {
+    println!("{} says {}", animal.name(), animal.speak());
+}
+
+// Trait objects let a function return different types
+fn adopt(wants_quiet: bool) -> Box<dyn Animal> {
+    if wants_quiet {
+        Box::new(Cat)
+    } else {
+        Box::new(Cow { spots: 12 })
+    }
+}
+
+fn main() {
+    // A Vec can only hold one type... Box<dyn Animal> IS one type
+    let barn: Vec<Box<dyn Animal>> = vec![Box::new(Cat), Box::new(Cow { spots: 3 })];
+    for animal in &barn {
+        introduce(animal.as_ref());
+    }
+
+    let pet = adopt(true);
+    println!("Adopted: {}", pet.name());
+
+    // How big is a trait object reference? A data pointer + a vtable pointer
+    println!(
+        "size of &Cat = {} bytes, size of &dyn Animal = {} bytes",
+        std::mem::size_of::<&Cat>(),
+        std::mem::size_of::<&dyn Animal>()
+    );
+
+    // This would cause an error:
+    // let barn = vec![Cat, Cow { spots: 3 }];
+    // error[E0308]: mismatched types (expected `Cat`, found `Cow`)
+}
+
+/*
+ * Key Concepts:
+ * - dyn Trait is "some type implementing Trait", chosen at runtime
+ * - It always lives behind a pointer: &dyn Trait or Box<dyn Trait>
+ * - Method calls go through a vtable (dynamic dispatch): a tiny cost
+ *   in exchange for a lot of flexibility
+ * - Generics (static dispatch) = one copy per type, decided at compile time
+ * - Trait objects (dynamic dispatch) = one copy, decided at runtime
+ */
+
+// EXPECTED:
+// Cat says Meow
+// Cow with 3 spots says Moo
+// Adopted: Cat
+// size of &Cat = 8 bytes, size of &dyn Animal = 16 bytes
@@ -0,0 +1,202 @@
+// Traits 6: Putting It Together - A Plugin-Style Shape Registry
+// Demonstrates a program that loads behaviors by name through Box<dyn Shape>
+//
+// Concepts: Box<dyn Trait>, registries, lookup by name
+// Difficulty: advanced
+// Minutes: 15
+//
+// Each "plugin" is a type implementing Shape. The registry maps a name to
+// a constructor, so new shapes can be added without touching the code
+// that uses them.
+
+use std::collections::HashMap;
+use std::fmt;
+
+trait Shape {
+    fn name(&self) -> &str;
+    fn area(&self) -> f64;
+    fn perimeter(&self) -> f64;
+
+    // Default method shared by every plugin
+    fn describe(&self) -> String {
+        format!(
+            "{:<9} area = {:>7.2}  perimeter = {:>6.2}",
+            self.name(),
+            self.area(),
+            self.perimeter()
+        )
+    }
+}
+
+struct Circle {
+    radius: f64,
+}
+
+struct Rectangle {
+    width: f64,
+    height: f64,
+}
+
+struct Triangle {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl Shape for Circle {
+    fn name(&self) -> &str {
+        "circle"
+    }
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+    fn perimeter(&self) -> f64 {
+        2.0 * std::f64::consts::PI * self.radius
+    }
+}
+
+impl Shape for Rectangle {
+    fn name(&self) -> &str {
+        "rectangle"
+    }
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+    fn perimeter(&self) -> f64 {
+        2.0 * (self.width + self.height)
+    }
+}
+
+impl Shape for Triangle {
+    fn name(&self) -> &str {
+        "triangle"
+    }
+    fn area(&self) -> f64 {
+        // Heron's formula
+        let s = self.perimeter() / 2.0;
+        (s * (s - self.a) * (s - self.b) * (s - self.c)).sqrt()
+    }
+    fn perimeter(&self) -> f64 {
+        self.a + self.b + self.c
+    }
+}
+
+// A constructor takes a list of numbers and builds a boxed shape
+type Constructor = fn(&[f64]) -> Result<Box<dyn Shape>, String>;
+
+struct Registry {
+    constructors: HashMap<&'static str, Constructor>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        Registry {
+            constructors: HashMap::new(),
+        }
+    }
+
+    // "Installing a plugin" is just adding a constructor
+    fn register(&mut self, name: &'static str, constructor: Constructor) {
+        self.constructors.insert(name, constructor);
+    }
+
+    fn build(&self, name: &str, args: &[f64]) -> Result<Box<dyn Shape>, String> {
+        match self.constructors.get(name) {
+            Some(constructor) => constructor(args),
+            None => Err(format!("no plugin named '{}'", name)),
+        }
+    }
+}
+
+// Plain functions can be stored as values of type `fn(...)`
+fn make_circle(args: &[f64]) -> Result<Box<dyn Shape>, String> {
+    match args {
+        [radius] => Ok(Box::new(Circle { radius: *radius })),
+        _ => Err(String::from("circle needs 1 number: radius")),
+    }
+}
+
+fn make_rectangle(args: &[f64]) -> Result<Box<dyn Shape>, String> {
+    match args {
+        [width, height] => Ok(Box::new(Rectangle {
+            width: *width,
+            height: *height,
+        })),
+        _ => Err(String::from("rectangle needs 2 numbers: width height")),
+    }
+}
+
+fn make_triangle(args: &[f64]) -> Result<Box<dyn Shape>, String> {
+    match args {
+        [a, b, c] if a + b > *c && a + c > *b && b + c > *a => Ok(Box::new(Triangle {
+            a: *a,
+            b: *b,
+            c: *c,
+        })),
+        [_, _, _] => Err(String::from("those sides can't form a triangle")),
+        _ => Err(String::from("triangle needs 3 numbers: a b c")),
+    }
+}
+
+// Display for a trait object: works for every plugin at once
+impl fmt::Display for dyn Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.describe())
+    }
+}
+
+fn main() {
+    let mut registry = Registry::new();
+    registry.register("circle", make_circle);
+    registry.register("rectangle", make_rectangle);
+    registry.register("triangle", make_triangle);
+
+    // Imagine these lines came from a config file or user input
+    let requests = [
+        "circle 1.5",
+        "rectangle 3 4",
+        "triangle 3 4 5",
+        "triangle 1 1 10",
+        "hexagon 2",
+    ];
+
+    let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
+    for request in requests {
+        let mut parts = request.split_whitespace();
+        let name = parts.next().unwrap_or("");
+        let args: Vec<f64> = parts.filter_map(|part| part.parse().ok()).collect();
+
+        match registry.build(name, &args) {
+            Ok(shape) => shapes.push(shape),
+            Err(message) => println!("skipping '{}': {}", request, message),
+        }
+    }
+
+    println!();
+    for shape in &shapes {
+        println!("{}", shape);
+    }
+
+    let total: f64 = shapes.iter().map(|shape| shape.area()).sum();
+    println!("\ntotal area of {} shapes = {:.2}", shapes.len(), total);
+}
+
+/*
+ * Key Concepts:
+ * - Box<dyn Shape> lets one Vec hold circles, rectangles, and triangles
+ * - A registry of constructors decouples "what exists" from "what is used"
+ * - Default methods (describe) give every plugin shared behavior
+ * - `impl Display for dyn Shape` adds behavior to all trait objects at once
+ * - This is the same idea as plugin systems in Java (interfaces + factories)
+ *   and Go (interfaces + registration maps)
+ */
+
+// EXPECTED:
+// skipping 'triangle 1 1 10': those sides can't form a triangle
+// skipping 'hexagon 2': no plugin named 'hexagon'
+//
+// circle    area =    7.07  perimeter =   9.42
+// rectangle area =   12.00  perimeter =  14.00
+// triangle  area =    6.00  perimeter =  12.00
+//
+// total area of 3 shapes = 25.07
@@ -0,0 +1,284 @@
+// Trees and Graphs 1: A Binary Search Tree with Box
+// Demonstrates a tree that owns its nodes through Option<Box<Node>>, with insert, search, and delete
+//
+// Concepts: Box, Option<Box<T>>, recursive types, &mut Option, take(), binary search trees
+// Difficulty: intermediate
+// Minutes: 20
+//
+// In Java or Go a tree node holds references to its children, and the
+// garbage collector cleans up whatever nobody points at any more. In Rust
+// the simplest tree is one in which every node OWNS its children: a child
+// is an Option<Box<Node>>, either no child or a box on the heap that is
+// freed when its parent is. There is exactly one owner for each node, so
+// there are no cycles and nothing to collect.
+//
+// A binary search tree keeps keys in order: everything in a node's left
+// subtree is smaller than its key, everything in the right one larger.
+// Searching follows one path from the root, so it takes time
+// proportional to the tree's height, which is about log2(n) for a tree
+// filled in random order and n for one filled in sorted order.
+//
+// Changing the tree is where ownership shows. Walking down to the place
+// where a key goes needs a `&mut Option<Box<Node>>`, a mutable borrow of
+// the slot a node lives in, so that the slot itself can be filled or
+// emptied. Option::take() moves a node out of its slot and leaves None
+// behind, which is how a node is removed without ever having two owners.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use learn_rust_core::section;
+
+#[derive(Debug)]
+struct Node {
+    key: i32,
+    left: Tree,
+    right: Tree,
+}
+
+/// A tree is a slot that is empty or holds a node, and so is each of a
+/// node's children.
+type Tree = Option<Box<Node>>;
+
+#[derive(Debug, Default)]
+struct Bst {
+    root: Tree,
+    len: usize,
+}
+
+impl Bst {
+    /// Adds `key`, and returns false if it was there already.
+    fn insert(&mut self, key: i32) -> bool {
+        // `slot` moves down the tree: first the root, then a child of the
+        // node it held, until it reaches an empty one
+        let mut slot = &mut self.root;
+        while let Some(node) = slot {
+            slot = match key.cmp(&node.key) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return false,
+            };
+        }
+        *slot = Some(Box::new(Node {
+            key,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+        true
+    }
+
+    fn contains(&self, key: i32) -> bool {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// Removes `key`, and returns whether it was there.
+    fn remove(&mut self, key: i32) -> bool {
+        let mut slot = &mut self.root;
+        // Find the slot that holds the key. The `as_ref` check comes first
+        // so that the borrow of `slot` ends before we move it down.
+        while slot.as_ref().is_some_and(|node| node.key != key) {
+            let node = slot.as_mut().unwrap();
+            slot = if key < node.key {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+        }
+        let Some(mut node) = slot.take() else {
+            return false;
+        };
+        *slot = match (node.left.take(), node.right.take()) {
+            // No children, or one: the child (or nothing) takes its place
+            (None, None) => None,
+            (Some(child), None) | (None, Some(child)) => Some(child),
+            // Two: the smallest key on the right takes its place, since
+            // it is larger than everything on the left
+            (Some(left), Some(right)) => {
+                let (smallest, rest) = take_min(right);
+                Some(Box::new(Node {
+                    key: smallest,
+                    left: Some(left),
+                    right: rest,
+                }))
+            }
+        };
+        self.len -= 1;
+        true
+    }
+
+    fn height(&self) -> usize {
+        fn height(tree: &Tree) -> usize {
+            match tree {
+                Some(node) => 1 + height(&node.left).max(height(&node.right)),
+                None => 0,
+            }
+        }
+        height(&self.root)
+    }
+
+    /// The keys in order: left subtree, the node, right subtree.
+    fn keys(&self) -> Vec<i32> {
+        fn walk(tree: &Tree, keys: &mut Vec<i32>) {
+            if let Some(node) = tree {
+                walk(&node.left, keys);
+                keys.push(node.key);
+                walk(&node.right, keys);
+            }
+        }
+        let mut keys = Vec::with_capacity(self.len);
+        walk(&self.root, &mut keys);
+        keys
+    }
+}
+
+/// Removes the smallest key from a (non-empty) subtree, and returns it
+/// with what is left of the subtree.
+fn take_min(mut node: Box<Node>) -> (i32, Tree) {
+    match node.left.take() {
+        // No left child: this is the smallest, and its right subtree moves up
+        None => (node.key, node.right.take()),
+        Some(left) => {
+            let (smallest, rest) = take_min(left);
+            node.left = rest;
+            (smallest, Some(node))
+        }
+    }
+}
+
+/// Draws the tree sideways: the root on the left, larger keys above.
+impl fmt::Display for Bst {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn draw(tree: &Tree, depth: usize, f: &mut fmt::Formatter) -> fmt::Result {
+            if let Some(node) = tree {
+                draw(&node.right, depth + 1, f)?;
+                writeln!(f, "{}{}", "    ".repeat(depth), node.key)?;
+                draw(&node.left, depth + 1, f)?;
+            }
+            Ok(())
+        }
+        draw(&self.root, 0, f)
+    }
+}
+
+fn main() {
+    section("inserting");
+    let mut tree = Bst::default();
+    for key in [50, 30, 70, 20, 40, 60, 80, 35] {
+        tree.insert(key);
+    }
+    println!("inserted 40 again: {}", tree.insert(40));
+    print!("{tree}");
+    println!("{} keys, height {}", tree.len, tree.height());
+    println!("in order: {:?}", tree.keys());
+    println!(
+        "contains 60: {}, contains 65: {}",
+        tree.contains(60),
+        tree.contains(65)
+    );
+
+    section("removing");
+    // A leaf, a node with one child, and the root, which has two
+    for key in [20, 30, 50] {
+        tree.remove(key);
+        println!("without {key}: {:?}", tree.keys());
+    }
+    println!("removed 99: {}", tree.remove(99));
+    print!("{tree}");
+
+    section("order matters");
+    // Sorted input makes every node a right child: a linked list, as slow
+    // to search as one
+    let mut sorted = Bst::default();
+    for key in 1..=100 {
+        sorted.insert(key);
+    }
+    let mut shuffled = Bst::default();
+    for i in 0..100 {
+        // 37 and 100 share no factor, so this visits 1..=100 out of order
+        shuffled.insert(i * 37 % 100 + 1);
+    }
+    println!("1 to 100 in order:   height {}", sorted.height());
+    println!("1 to 100 shuffled:   height {}", shuffled.height());
+    println!("same keys: {}", sorted.keys() == shuffled.keys());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(keys: &[i32]) -> Bst {
+        let mut tree = Bst::default();
+        for &key in keys {
+            tree.insert(key);
+        }
+        tree
+    }
+
+    #[test]
+    fn keys_come_out_sorted_and_without_duplicates() {
+        let tree = tree(&[5, 3, 8, 3, 1, 9, 5]);
+        assert_eq!(tree.keys(), [1, 3, 5, 8, 9]);
+        assert_eq!(tree.len, 5);
+    }
+
+    #[test]
+    fn removing_every_kind_of_node_keeps_the_rest() {
+        let keys = [50, 30, 70, 20, 40, 60, 80, 35, 45, 65];
+        for &gone in &keys {
+            let mut tree = tree(&keys);
+            assert!(tree.remove(gone));
+            let mut expected: Vec<i32> = keys.iter().copied().filter(|&key| key != gone).collect();
+            expected.sort();
+            assert_eq!(tree.keys(), expected, "after removing {gone}");
+            assert!(!tree.contains(gone));
+            assert!(!tree.remove(gone));
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Option<Box<Node>> is an owned, nullable child: no GC, no cycles, freed with its parent
+ * - Walk down with a &mut Option<Box<Node>> "slot" to fill or empty it in place
+ * - Option::take() moves a node out and leaves None, so removing never breaks ownership
+ * - Deleting a node with two children: replace its key with the smallest on its right
+ * - A BST is only as fast as it is short; sorted input makes it a linked list
+ */
+
+// EXPECTED:
+// -- inserting --
+// inserted 40 again: false
+//         80
+//     70
+//         60
+// 50
+//         40
+//             35
+//     30
+//         20
+// 8 keys, height 4
+// in order: [20, 30, 35, 40, 50, 60, 70, 80]
+// contains 60: true, contains 65: false
+// -- removing --
+// without 20: [30, 35, 40, 50, 60, 70, 80]
+// without 30: [35, 40, 50, 60, 70, 80]
+// without 50: [35, 40, 60, 70, 80]
+// removed 99: false
+//         80
+//     70
+// 60
+//     40
+//         35
+// -- order matters --
+// 1 to 100 in order:   height 100
+// 1 to 100 shuffled:   height 11
+// same keys: true
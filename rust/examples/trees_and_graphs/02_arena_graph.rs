@@ -0,0 +1,229 @@
+// Trees and Graphs 2: Graphs in an Arena
+// Demonstrates a graph whose nodes live in a Vec and refer to each other by index
+//
+// Concepts: arenas, index-based adjacency lists, newtype ids, Rc<RefCell> vs indices, parent links
+// Difficulty: intermediate
+// Minutes: 20
+//
+// A tree can own its children, but a graph can't: a node may have many
+// nodes pointing at it, and the edges may go round in a cycle. The
+// version a Java or Go programmer writes first, nodes holding references
+// to their neighbours, becomes Rc<RefCell<Node>> in Rust: a reference
+// count on every node, a runtime borrow check on every access, Weak
+// pointers to break the cycles, and a leak when one is missed.
+//
+// The idiomatic alternative is an arena. All the nodes live in one Vec,
+// owned by the graph, and a node refers to another by its index in that
+// Vec. An edge is just a number, so cycles are no problem: nothing owns
+// anything else, and the whole graph is freed at once when the Vec is.
+// Each node's outgoing edges are a list of indices, an "adjacency list".
+//
+// Wrapping the index in a newtype, NodeId, keeps it from being mixed up
+// with other numbers. The price is that a NodeId is only meaningful in
+// the graph that made it; crates such as petgraph do the same with more
+// features, and slotmap adds ids that notice when their node was removed.
+
+use std::fmt;
+
+use learn_rust_core::section;
+
+/// A node's position in its graph's arena.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct NodeId(usize);
+
+#[derive(Debug)]
+struct Node<T> {
+    value: T,
+    /// The nodes this one has an edge to.
+    edges: Vec<NodeId>,
+}
+
+/// A directed graph: an edge goes from one node to another. An
+/// undirected edge is one each way.
+#[derive(Debug)]
+struct Graph<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> Graph<T> {
+    fn new() -> Self {
+        Graph { nodes: Vec::new() }
+    }
+
+    fn add_node(&mut self, value: T) -> NodeId {
+        self.nodes.push(Node {
+            value,
+            edges: Vec::new(),
+        });
+        NodeId(self.nodes.len() - 1)
+    }
+
+    fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.nodes[from.0].edges.push(to);
+    }
+
+    fn value(&self, id: NodeId) -> &T {
+        &self.nodes[id.0].value
+    }
+
+    fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes[id.0].edges.iter().copied()
+    }
+
+    /// Every node's id, in the order they were added.
+    fn ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    /// How many edges point at each node.
+    fn in_degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.nodes.len()];
+        for node in &self.nodes {
+            for to in &node.edges {
+                degrees[to.0] += 1;
+            }
+        }
+        degrees
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Graph<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for id in self.ids() {
+            let names: Vec<String> = self
+                .neighbours(id)
+                .map(|to| self.value(to).to_string())
+                .collect();
+            writeln!(f, "{:<10}-> {}", self.value(id), names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A tree in an arena is a graph with one parent per node, and the
+/// links in both directions are plain indices: no Weak, no RefCell.
+#[derive(Debug)]
+struct Folder {
+    name: &'static str,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+fn path(folders: &[Folder], mut id: NodeId) -> String {
+    let mut names = vec![folders[id.0].name];
+    while let Some(parent) = folders[id.0].parent {
+        names.push(folders[parent.0].name);
+        id = parent;
+    }
+    names.reverse();
+    names.join("/")
+}
+
+fn main() {
+    section("a graph");
+    // Who follows whom; the edges go round in a cycle, and that's fine
+    let mut follows = Graph::new();
+    let ana = follows.add_node("ana");
+    let bo = follows.add_node("bo");
+    let cy = follows.add_node("cy");
+    let dee = follows.add_node("dee");
+    follows.add_edge(ana, bo);
+    follows.add_edge(ana, cy);
+    follows.add_edge(bo, cy);
+    follows.add_edge(cy, ana);
+    follows.add_edge(dee, cy);
+    print!("{follows}");
+    println!("{} is {:?}", follows.value(cy), cy);
+    let followers = follows.in_degrees();
+    for id in follows.ids() {
+        println!("{} has {} follower(s)", follows.value(id), followers[id.0]);
+    }
+
+    section("changing a node");
+    // Mutating a node is an ordinary &mut borrow of the Vec, checked at
+    // compile time; with Rc<RefCell<Node>> it is borrow_mut() at run time
+    let mut scores: Graph<u32> = Graph::new();
+    let a = scores.add_node(1);
+    let b = scores.add_node(2);
+    scores.add_edge(a, b);
+    scores.add_edge(b, a);
+    // Ids are Copy, so reading the neighbours first and writing after is
+    // easy; holding a &Node while changing another would not be
+    let next: Vec<NodeId> = scores.neighbours(a).collect();
+    for id in next {
+        scores.nodes[id.0].value *= 10;
+    }
+    println!("a = {}, b = {}", scores.value(a), scores.value(b));
+
+    section("a tree with parent links");
+    let mut folders: Vec<Folder> = Vec::new();
+    let mut add = |name, parent: Option<NodeId>| {
+        let id = NodeId(folders.len());
+        folders.push(Folder {
+            name,
+            parent,
+            children: Vec::new(),
+        });
+        if let Some(parent) = parent {
+            folders[parent.0].children.push(id);
+        }
+        id
+    };
+    let root = add("project", None);
+    let src = add("src", Some(root));
+    add("tests", Some(root));
+    let bin = add("bin", Some(src));
+    let main_rs = add("main.rs", Some(bin));
+    println!("{}", path(&folders, main_rs));
+    let children: Vec<&str> = folders[root.0]
+        .children
+        .iter()
+        .map(|id| folders[id.0].name)
+        .collect();
+    println!("{} contains {children:?}", folders[root.0].name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges_are_directed_and_may_form_cycles() {
+        let mut graph = Graph::new();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        graph.add_edge(a, b);
+        graph.add_edge(b, a);
+        graph.add_edge(a, a);
+        assert_eq!(graph.neighbours(a).collect::<Vec<_>>(), [b, a]);
+        assert_eq!(graph.neighbours(b).collect::<Vec<_>>(), [a]);
+        assert_eq!(graph.in_degrees(), [2, 1]);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - Graphs don't fit single ownership; Rc<RefCell<>> works but costs counts, runtime checks, and leaks
+ * - An arena: the graph owns every node in a Vec, and nodes refer to each other by index
+ * - An adjacency list is each node's Vec of neighbour indices
+ * - A newtype id (NodeId) keeps indices from being mixed up with other numbers
+ * - Parent links in an arena tree are plain indices too: no Weak needed
+ * - Collect the ids you need, then mutate: Copy ids make the borrow checker easy to satisfy
+ */
+
+// EXPECTED:
+// -- a graph --
+// ana       -> bo, cy
+// bo        -> cy
+// cy        -> ana
+// dee       -> cy
+// cy is NodeId(2)
+// ana has 1 follower(s)
+// bo has 1 follower(s)
+// cy has 3 follower(s)
+// dee has 0 follower(s)
+// -- changing a node --
+// a = 1, b = 20
+// -- a tree with parent links --
+// project/src/bin/main.rs
+// project contains ["src", "tests"]
@@ -0,0 +1,264 @@
+// Trees and Graphs 3: Traversals as Iterators
+// Demonstrates breadth-first and depth-first search as Iterator types, and what each one is good for
+//
+// Concepts: BFS, DFS, VecDeque, explicit stacks, visited sets, Iterator, shortest paths
+// Difficulty: intermediate
+// Minutes: 20
+//
+// Most graph algorithms start by visiting the nodes reachable from one of
+// them. Breadth-first search (BFS) visits them in rings: the start, then
+// its neighbours, then theirs. It keeps the nodes still to visit in a
+// queue, first in first out, and finds every node by a shortest path,
+// counted in edges. Depth-first search (DFS) follows one path as far as
+// it goes before backing up. It keeps a stack, last in first out, and is
+// the one behind cycle detection and topological sorting.
+//
+// In both, a graph with cycles would be walked forever without a record
+// of the nodes already seen. With an arena that record is just a
+// Vec<bool> indexed by node.
+//
+// Writing a traversal as an Iterator, rather than as a function that
+// takes a callback, hands the caller every Iterator method: take(),
+// find(), filter(), collect(), stopping early. The search's state (the
+// queue or the stack, and the seen list) lives in the iterator struct,
+// and next() does one step. Recursion would keep the stack on the call
+// stack instead, which can't be paused between two nodes, and overflows
+// on a long enough path.
+
+use std::collections::VecDeque;
+
+use learn_rust_core::section;
+
+/// An arena graph as in 02_arena_graph, with plain usize ids to keep the
+/// example short.
+struct Graph {
+    names: Vec<&'static str>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// The graph with these nodes and directed edges.
+    fn new(names: &[&'static str], edges: &[(usize, usize)]) -> Graph {
+        let mut graph = Graph {
+            names: names.to_vec(),
+            edges: vec![Vec::new(); names.len()],
+        };
+        for &(from, to) in edges {
+            graph.edges[from].push(to);
+        }
+        graph
+    }
+
+    fn bfs(&self, start: usize) -> Bfs<'_> {
+        let mut seen = vec![false; self.names.len()];
+        seen[start] = true;
+        Bfs {
+            graph: self,
+            queue: VecDeque::from([(start, 0)]),
+            seen,
+        }
+    }
+
+    fn dfs(&self, start: usize) -> Dfs<'_> {
+        Dfs {
+            graph: self,
+            stack: vec![start],
+            seen: vec![false; self.names.len()],
+        }
+    }
+
+    fn names(&self, ids: impl IntoIterator<Item = usize>) -> Vec<&'static str> {
+        ids.into_iter().map(|id| self.names[id]).collect()
+    }
+}
+
+/// Yields each node reachable from the start, with its distance in edges.
+struct Bfs<'g> {
+    graph: &'g Graph,
+    queue: VecDeque<(usize, usize)>,
+    seen: Vec<bool>,
+}
+
+impl Iterator for Bfs<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, distance) = self.queue.pop_front()?;
+        for &next in &self.graph.edges[node] {
+            // Marking a node when it is queued, not when it is visited,
+            // keeps it from being queued twice
+            if !self.seen[next] {
+                self.seen[next] = true;
+                self.queue.push_back((next, distance + 1));
+            }
+        }
+        Some((node, distance))
+    }
+}
+
+/// Yields each node reachable from the start, deepest paths first.
+struct Dfs<'g> {
+    graph: &'g Graph,
+    stack: Vec<usize>,
+    seen: Vec<bool>,
+}
+
+impl Iterator for Dfs<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        // A node can be on the stack more than once, so it is marked when
+        // it is visited, and the later copies are skipped
+        while let Some(node) = self.stack.pop() {
+            if self.seen[node] {
+                continue;
+            }
+            self.seen[node] = true;
+            // Pushed in reverse, so the first neighbour is visited first
+            self.stack.extend(self.graph.edges[node].iter().rev());
+            return Some(node);
+        }
+        None
+    }
+}
+
+/// A shortest path from `from` to `to`: a BFS that remembers how it
+/// reached each node, followed back from the end.
+fn shortest_path(graph: &Graph, from: usize, to: usize) -> Option<Vec<usize>> {
+    let mut came_from: Vec<Option<usize>> = vec![None; graph.names.len()];
+    let mut queue = VecDeque::from([from]);
+    let mut seen = vec![false; graph.names.len()];
+    seen[from] = true;
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            let mut path = vec![to];
+            while let Some(previous) = came_from[*path.last().unwrap()] {
+                path.push(previous);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &next in &graph.edges[node] {
+            if !seen[next] {
+                seen[next] = true;
+                came_from[next] = Some(node);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+fn main() {
+    // Stations and the one-way lines between them; there is a loop
+    // between hub, park, and mill
+    let names = ["dock", "hub", "park", "mill", "tower", "quay", "farm"];
+    let edges = [
+        (0, 1),
+        (1, 2),
+        (1, 3),
+        (2, 4),
+        (3, 2),
+        (2, 1),
+        (4, 5),
+        (3, 5),
+        (6, 0),
+    ];
+    let graph = Graph::new(&names, &edges);
+
+    section("breadth first");
+    for (node, distance) in graph.bfs(0) {
+        println!("{distance} {}", graph.names[node]);
+    }
+
+    section("depth first");
+    println!("{:?}", graph.names(graph.dfs(0)));
+    // It's an Iterator, so stopping early is free: the search does only
+    // as much work as the first match needs
+    let first_far = graph.bfs(0).find(|&(_, distance)| distance >= 3);
+    println!(
+        "first stop 3 lines away: {:?}",
+        first_far.map(|(node, _)| graph.names[node])
+    );
+    let reachable = graph.dfs(1).count();
+    println!("from hub, {reachable} of {} stations", names.len());
+    // farm has no lines in, so nothing else reaches it
+    println!(
+        "farm reachable from dock: {}",
+        graph.dfs(0).any(|node| node == 6)
+    );
+
+    section("shortest paths");
+    for (from, to) in [(0, 5), (6, 4), (5, 0)] {
+        match shortest_path(&graph, from, to) {
+            Some(path) => println!(
+                "{} to {}: {}",
+                names[from],
+                names[to],
+                graph.names(path).join(" -> ")
+            ),
+            None => println!("{} to {}: no way", names[from], names[to]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diamond() -> Graph {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3, and 3 -> 0 back to the start
+        Graph::new(
+            &["a", "b", "c", "d"],
+            &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 0)],
+        )
+    }
+
+    #[test]
+    fn bfs_visits_in_rings_and_each_node_once() {
+        let visited: Vec<(usize, usize)> = diamond().bfs(0).collect();
+        assert_eq!(visited, [(0, 0), (1, 1), (2, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn dfs_goes_deep_first_and_stops_at_cycles() {
+        assert_eq!(diamond().dfs(0).collect::<Vec<_>>(), [0, 1, 3, 2]);
+        assert_eq!(diamond().dfs(3).collect::<Vec<_>>(), [3, 0, 1, 2]);
+    }
+
+    #[test]
+    fn shortest_paths_count_edges() {
+        let graph = diamond();
+        assert_eq!(shortest_path(&graph, 1, 2), Some(vec![1, 3, 0, 2]));
+        assert_eq!(shortest_path(&graph, 2, 2), Some(vec![2]));
+        let lonely = Graph::new(&["x", "y"], &[]);
+        assert_eq!(shortest_path(&lonely, 0, 1), None);
+    }
+}
+
+/*
+ * Key Concepts:
+ * - BFS: a VecDeque queue, visits by distance, finds shortest paths in edges
+ * - DFS: a Vec stack, goes deep first; the basis of cycle detection and topological sort
+ * - A seen list (Vec<bool> in an arena) keeps cycles from looping forever
+ * - A traversal as an Iterator keeps its state in a struct, and gets find(), take(), any() for free
+ * - An explicit stack instead of recursion can pause between nodes and can't overflow
+ */
+
+// EXPECTED:
+// -- breadth first --
+// 0 dock
+// 1 hub
+// 2 park
+// 2 mill
+// 3 tower
+// 3 quay
+// -- depth first --
+// ["dock", "hub", "park", "tower", "quay", "mill"]
+// first stop 3 lines away: Some("tower")
+// from hub, 5 of 7 stations
+// farm reachable from dock: false
+// -- shortest paths --
+// dock to quay: dock -> hub -> mill -> quay
+// farm to tower: farm -> dock -> hub -> park -> tower
+// quay to dock: no way
@@ -0,0 +1,86 @@
+// Typestate 1: A Request Builder Whose Mistakes Don't Compile
+// Demonstrates NoUrl -> HasUrl -> Ready states, with compile-fail tests as proof
+//
+// Concepts: typestate, builders, compile-fail tests
+// Difficulty: advanced
+// Minutes: 5
+//
+// The builder is in the `request_builder/` folder next to this file. A
+// request needs a URL and a method, in that order, and only then can it
+// be built. Its state is a type parameter, so `build()` simply doesn't
+// exist on a `RequestBuilder<NoUrl>` or a `RequestBuilder<HasUrl>`, and
+// setting the URL twice, or the method twice, is just as impossible.
+//
+// A claim that something "doesn't compile" deserves a test, and the
+// programs in `tests/compile_fail/` are those tests: trybuild compiles
+// each one and checks that it fails with the error recorded next to it.
+
+use std::time::Duration;
+
+use request_builder::{Request, RequestBuilder};
+
+fn show(request: &Request) {
+    println!("{:?} {}", request.method, request.url);
+    for (name, value) in &request.headers {
+        println!("  {}: {}", name, value);
+    }
+    if let Some(body) = &request.body {
+        println!("  body: {}", body);
+    }
+    println!("  timeout: {:?}", request.timeout);
+}
+
+fn main() {
+    // Headers and timeouts can be set in any state, before or after the URL
+    let search = RequestBuilder::new()
+        .header("Accept", "application/json")
+        .url("https://api.example.com/search")
+        .query("q", "rust")
+        .get()
+        .query("page", "2")
+        .build();
+    show(&search);
+
+    let create = RequestBuilder::new()
+        .url("https://api.example.com/users")
+        .timeout(Duration::from_secs(5))
+        .post(r#"{"name":"ana"}"#)
+        .header("Content-Type", "application/json")
+        .build();
+    show(&create);
+
+    // Each step returns a new type, so optional steps rebind the variable;
+    // this works because the step doesn't change the state
+    let token = Some("secret");
+    let mut delete = RequestBuilder::new().url("https://api.example.com/users/7");
+    if let Some(token) = token {
+        delete = delete.header("Authorization", &format!("Bearer {}", token));
+    }
+    show(&delete.delete().build());
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    let early = RequestBuilder::new().url("https://example.com").build();
+    // error[E0599]: no method named `build` found for struct `RequestBuilder<HasUrl>` in the current scope
+}
+
+/*
+ * Key Concepts:
+ * - Each state is a type holding what is known so far; no Options to unwrap
+ * - Methods exist only on states that allow them: build() only on Ready
+ * - `impl<S: UrlSet>` shares a method between some states; the trait is sealed
+ * - Private fields stop other crates from forging a state
+ * - trybuild compile-fail tests prove that invalid transitions stay invalid
+ */
+
+// EXPECTED:
+// Get https://api.example.com/search?q=rust&page=2
+//   Accept: application/json
+//   timeout: 30s
+// Post https://api.example.com/users
+//   Content-Type: application/json
+//   body: {"name":"ana"}
+//   timeout: 5s
+// Delete https://api.example.com/users/7
+//   Authorization: Bearer secret
+//   timeout: 30s
@@ -0,0 +1,177 @@
+//! A request builder used by `01_request_builder.rs`, whose states are
+//! types.
+//!
+//! A request needs a URL and a method before it can be built, and a URL
+//! can only be set once. The builder goes through three states:
+//!
+//! ```text
+//! RequestBuilder<NoUrl>  --url()-->  RequestBuilder<HasUrl>  --get()/post()-->  RequestBuilder<Ready>  --build()-->  Request
+//! ```
+//!
+//! Each state is a struct holding what is known so far, so there are no
+//! `Option` fields to unwrap at the end, and each method is defined only
+//! for the states where it makes sense. The tests in
+//! `tests/compile_fail/` are programs that misuse the builder; they pass
+//! by failing to compile.
+
+use std::time::Duration;
+
+/// The starting state: nothing is known yet.
+#[derive(Debug)]
+pub struct NoUrl;
+
+/// A URL has been set; next comes the method.
+#[derive(Debug)]
+pub struct HasUrl {
+    url: String,
+}
+
+/// Everything a request needs is there, so it can be built.
+#[derive(Debug)]
+pub struct Ready {
+    url: String,
+    method: Method,
+    body: Option<String>,
+}
+
+mod sealed {
+    /// Private to this crate, so other crates can't implement it, and so
+    /// can't implement [`UrlSet`](super::UrlSet) either.
+    pub trait Sealed {
+        fn url_mut(&mut self) -> &mut String;
+    }
+
+    impl Sealed for super::HasUrl {
+        fn url_mut(&mut self) -> &mut String {
+            &mut self.url
+        }
+    }
+
+    impl Sealed for super::Ready {
+        fn url_mut(&mut self) -> &mut String {
+            &mut self.url
+        }
+    }
+}
+
+/// The states that have a URL. Sealed: the set of states is fixed here.
+pub trait UrlSet: sealed::Sealed {}
+
+impl UrlSet for HasUrl {}
+impl UrlSet for Ready {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Delete,
+    Post,
+    Put,
+}
+
+/// What the builder produces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    pub timeout: Duration,
+}
+
+/// Builds a [`Request`]; `State` says how far along it is.
+#[derive(Debug)]
+pub struct RequestBuilder<State> {
+    state: State,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+impl RequestBuilder<NoUrl> {
+    pub fn new() -> Self {
+        RequestBuilder {
+            state: NoUrl,
+            headers: Vec::new(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn url(self, url: &str) -> RequestBuilder<HasUrl> {
+        self.map_state(|NoUrl| HasUrl {
+            url: url.to_string(),
+        })
+    }
+}
+
+impl Default for RequestBuilder<NoUrl> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Available in every state.
+impl<State> RequestBuilder<State> {
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Turns the current state into the next one, keeping the settings.
+    fn map_state<Next>(self, next: impl FnOnce(State) -> Next) -> RequestBuilder<Next> {
+        RequestBuilder {
+            state: next(self.state),
+            headers: self.headers,
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// Available once there is a URL, before or after choosing the method.
+impl<State: UrlSet> RequestBuilder<State> {
+    pub fn query(mut self, key: &str, value: &str) -> Self {
+        let url = self.state.url_mut();
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(key);
+        url.push('=');
+        url.push_str(value);
+        self
+    }
+}
+
+impl RequestBuilder<HasUrl> {
+    pub fn get(self) -> RequestBuilder<Ready> {
+        self.ready(Method::Get, None)
+    }
+
+    pub fn delete(self) -> RequestBuilder<Ready> {
+        self.ready(Method::Delete, None)
+    }
+
+    pub fn post(self, body: &str) -> RequestBuilder<Ready> {
+        self.ready(Method::Post, Some(body.to_string()))
+    }
+
+    pub fn put(self, body: &str) -> RequestBuilder<Ready> {
+        self.ready(Method::Put, Some(body.to_string()))
+    }
+
+    fn ready(self, method: Method, body: Option<String>) -> RequestBuilder<Ready> {
+        self.map_state(|HasUrl { url }| Ready { url, method, body })
+    }
+}
+
+impl RequestBuilder<Ready> {
+    pub fn build(self) -> Request {
+        Request {
+            method: self.state.method,
+            url: self.state.url,
+            headers: self.headers,
+            body: self.state.body,
+            timeout: self.timeout,
+        }
+    }
+}
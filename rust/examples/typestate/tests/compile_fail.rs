@@ -0,0 +1,9 @@
+// Every program in compile_fail/ misuses the request builder. The test
+// passes only if each one fails to compile with the error in its .stderr
+// file, which proves the builder's states rule those mistakes out.
+
+#[test]
+fn invalid_transitions_do_not_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/*.rs");
+}
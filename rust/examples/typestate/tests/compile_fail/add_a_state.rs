@@ -0,0 +1,8 @@
+// UrlSet is sealed: only the library's own states can implement it.
+use request_builder::UrlSet;
+
+struct Skipped;
+
+impl UrlSet for Skipped {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+// A URL alone isn't enough: get(), post(), put(), or delete() comes first.
+use request_builder::RequestBuilder;
+
+fn main() {
+    let _request = RequestBuilder::new().url("https://example.com").build();
+}
@@ -0,0 +1,6 @@
+// There is no URL yet, so there is nothing to build.
+use request_builder::RequestBuilder;
+
+fn main() {
+    let _request = RequestBuilder::new().build();
+}
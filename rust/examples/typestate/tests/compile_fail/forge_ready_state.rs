@@ -0,0 +1,11 @@
+// The states' fields are private, so a Ready state can't be made by hand
+// to skip the steps before it.
+use request_builder::{Method, Ready};
+
+fn main() {
+    let _ready = Ready {
+        url: "https://example.com".to_string(),
+        method: Method::Get,
+        body: None,
+    };
+}
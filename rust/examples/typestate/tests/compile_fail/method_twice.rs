@@ -0,0 +1,9 @@
+// Once the method is chosen, the builder is Ready and can't change it.
+use request_builder::RequestBuilder;
+
+fn main() {
+    let _builder = RequestBuilder::new()
+        .url("https://example.com")
+        .get()
+        .post("{}");
+}
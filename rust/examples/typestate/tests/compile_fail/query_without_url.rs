@@ -0,0 +1,6 @@
+// query() needs a state with a URL to add the parameter to.
+use request_builder::RequestBuilder;
+
+fn main() {
+    let _builder = RequestBuilder::new().query("page", "2");
+}
@@ -0,0 +1,8 @@
+// Each transition consumes the old builder, so it can't be used again.
+use request_builder::RequestBuilder;
+
+fn main() {
+    let start = RequestBuilder::new();
+    let _first = start.url("https://example.com");
+    let _second = start.url("https://example.org");
+}
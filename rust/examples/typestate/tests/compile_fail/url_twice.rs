@@ -0,0 +1,8 @@
+// url() only exists before a URL has been set.
+use request_builder::RequestBuilder;
+
+fn main() {
+    let _builder = RequestBuilder::new()
+        .url("https://example.com")
+        .url("https://example.org");
+}
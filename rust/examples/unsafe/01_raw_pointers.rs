@@ -0,0 +1,111 @@
+// Unsafe 1: Raw Pointers
+// Demonstrates *const T and *mut T, and why dereferencing them is unsafe
+//
+// Concepts: *const T, *mut T, dereferencing raw pointers
+// Difficulty: advanced
+// Minutes: 10
+//
+// References (&T, &mut T) come with guarantees the compiler checks: never
+// null, always pointing at a live value, never a &mut alongside another
+// reference. Raw pointers drop ALL of those guarantees. Making one is
+// safe; reading or writing through one is `unsafe`, because then YOU are
+// the one promising the guarantees hold.
+//
+// Check this file for undefined behavior with:
+//   cargo +nightly miri run -p unsafe-examples --bin 01_raw_pointers
+
+fn main() {
+    // ===== Creating raw pointers is safe =====
+    let mut number = 5;
+    let writable = &raw mut number; // *mut i32
+                                    // A *const made FROM the *mut, so both are "the same" pointer
+    let read_only = writable as *const i32;
+    println!("number lives at {:p}", read_only);
+
+    // A raw pointer can hold any address at all. This one is never
+    // dereferenced, so it is harmless to create.
+    let nowhere = std::ptr::without_provenance::<i32>(0x1234);
+    println!("a made-up pointer: {:p}", nowhere);
+
+    // ===== Dereferencing needs unsafe =====
+    // SAFETY: both pointers come from `number`, which is alive for the
+    // whole block, and nothing else uses `number` while we write through
+    // `writable` and then read through `read_only`.
+    unsafe {
+        *writable += 1;
+        println!("read through the pointer: {}", *read_only);
+    }
+    println!("number is now {}", number);
+    // Where the pointers come from matters. Had we written
+    //   let read_only = &raw const number;
+    //   let writable = &raw mut number;
+    // then taking `writable` would have invalidated `read_only`, and the
+    // read above would be undefined behavior. Miri reports:
+    //   error: Undefined Behavior: trying to retag from <...> for SharedReadOnly
+    //   permission ..., but that tag does not exist in the borrow stack
+
+    // This would cause an error:
+    // println!("{}", *read_only);
+    // error[E0133]: dereference of raw pointer is unsafe and requires unsafe function or block
+
+    // ===== Null pointers =====
+    // References can't be null, but raw pointers can, so check first
+    let maybe: *const i32 = std::ptr::null();
+    if maybe.is_null() {
+        println!("the pointer is null; not touching it");
+    }
+    // `as_ref` turns a possibly-null pointer into an Option<&T>
+    // SAFETY: `read_only` is either null or points at the live `number`
+    match unsafe { read_only.as_ref() } {
+        Some(value) => println!("as_ref gave Some({})", value),
+        None => println!("as_ref gave None"),
+    }
+
+    // ===== Pointer arithmetic =====
+    let primes = [2, 3, 5, 7, 11];
+    let start = primes.as_ptr();
+    let mut total = 0;
+    for i in 0..primes.len() {
+        // SAFETY: `i < primes.len()`, so `start.add(i)` stays inside the
+        // array, and the array outlives the loop.
+        total += unsafe { *start.add(i) };
+    }
+    println!("sum of primes via pointers: {}", total);
+    // Going one PAST the end is allowed to compute (`start.add(5)`), but
+    // reading it is undefined behavior. Miri reports:
+    //   error: Undefined Behavior: memory access failed: attempting to access
+    //   4 bytes, but got alloc... which is at or beyond the end of the allocation
+
+    // ===== Aliasing: the rule raw pointers let you break =====
+    let mut value = 10;
+    let reference = &mut value;
+    // A raw pointer made from a &mut borrows through it...
+    let ptr = &raw mut *reference;
+    // SAFETY: `ptr` came from `reference`, and `reference` isn't used
+    // while we write through `ptr`.
+    unsafe {
+        *ptr += 1;
+    }
+    // ...and using the &mut again is fine, but it ends `ptr`'s turn: from
+    // here on, writing through `ptr` would be undefined behavior.
+    *reference += 1;
+    println!("value ends at {}", value);
+    // The mirror-image mistake: make `ptr` from `value` FIRST, then take
+    // `&mut value`. The new &mut must be the only way to reach `value`, so
+    // it invalidates `ptr`, and any later use of `ptr` is UB. It compiles
+    // fine, which is exactly why Miri exists:
+    //   let ptr = &raw mut value;
+    //   let reference = &mut value;
+    //   unsafe { *ptr += 1 }
+    //   Miri: Undefined Behavior: attempting a read access using <...>, but
+    //   that tag does not exist in the borrow stack for this location
+}
+
+/*
+ * Key Concepts:
+ * - Raw pointers (*const T, *mut T) have none of the guarantees of references
+ * - Creating a raw pointer is safe; dereferencing one requires `unsafe`
+ * - `unsafe` doesn't turn off the borrow checker: it lets you make promises
+ * - Each unsafe block gets a SAFETY comment saying why it is sound
+ * - Miri catches undefined behavior that the compiler cannot
+ */
@@ -0,0 +1,103 @@
+// Unsafe 2: Unsafe Functions
+// Demonstrates `unsafe fn`, documenting safety contracts, and calling them
+//
+// Concepts: unsafe fn, safety contracts, unsafe blocks
+// Difficulty: advanced
+// Minutes: 10
+//
+// An `unsafe fn` is a function with a precondition the compiler cannot
+// check. The caller must uphold it, so every call goes in an `unsafe`
+// block. By convention the precondition is written in a `# Safety`
+// section of the doc comment.
+//
+// Check this file for undefined behavior with:
+//   cargo +nightly miri run -p unsafe-examples --bin 02_unsafe_fn
+
+use learn_rust_core::demo;
+
+/// Returns the element at `index` without a bounds check.
+///
+/// # Safety
+///
+/// `index` must be less than `values.len()`.
+unsafe fn get_unchecked(values: &[i32], index: usize) -> i32 {
+    // Even inside an unsafe fn, unsafe operations get their own block
+    // (the `unsafe_op_in_unsafe_fn` lint asks for this in edition 2024).
+    // SAFETY: the caller promised `index < values.len()`.
+    unsafe { *values.as_ptr().add(index) }
+}
+
+/// Views `len` values starting at `ptr` as a slice.
+///
+/// # Safety
+///
+/// - `ptr` must point at `len` initialized, consecutive `i32`s.
+/// - That memory must not be written to while the slice is in use.
+unsafe fn view<'a>(ptr: *const i32, len: usize) -> &'a [i32] {
+    // SAFETY: passed straight on to the caller; see the contract above.
+    unsafe { std::slice::from_raw_parts(ptr, len) }
+}
+
+/// A SAFE function built on the unsafe one. It checks the precondition
+/// itself, so callers can't get it wrong and need no unsafe block.
+fn checked_get(values: &[i32], index: usize) -> Option<i32> {
+    if index < values.len() {
+        // SAFETY: we just checked `index < values.len()`.
+        Some(unsafe { get_unchecked(values, index) })
+    } else {
+        None
+    }
+}
+
+fn main() {
+    let values = [10, 20, 30, 40];
+
+    // ===== Calling an unsafe fn =====
+    // SAFETY: 2 < 4
+    let third = unsafe { get_unchecked(&values, 2) };
+    println!("third value: {}", third);
+
+    // This would cause an error:
+    // let third = get_unchecked(&values, 2);
+    // error[E0133]: call to unsafe function `get_unchecked` is unsafe and requires unsafe function or block
+
+    // Calling it with index 10 would compile, and then read whatever memory
+    // happens to come after the array. Miri reports:
+    //   error: Undefined Behavior: in-bounds pointer arithmetic failed
+
+    // ===== The safe wrapper =====
+    demo!(checked_get(&values, 1));
+    demo!(checked_get(&values, 10));
+
+    // ===== Standard library unsafe fns =====
+    // SAFETY: `values.as_ptr()` points at 4 initialized i32s, and `values`
+    // is not modified while `middle` is in use. We view 2 of them.
+    let middle = unsafe { view(values.as_ptr().add(1), 2) };
+    println!("middle two: {:?}", middle);
+
+    // Many std types offer an unchecked twin of a checked method
+    let text = "héllo";
+    let bytes = text.as_bytes();
+    // SAFETY: the bytes came from a &str, so they are valid UTF-8
+    let again = unsafe { std::str::from_utf8_unchecked(bytes) };
+    println!("round trip: {}", again);
+    // The checked version costs a scan of the bytes, but can never be wrong
+    println!("checked: {:?}", std::str::from_utf8(&bytes[..2]));
+}
+
+/*
+ * Key Concepts:
+ * - `unsafe fn` marks a function whose caller must uphold a contract
+ * - The contract goes in a `# Safety` doc section
+ * - Calls to unsafe fns must be inside an `unsafe` block
+ * - Wrap unsafe fns in safe functions that check the contract themselves
+ * - Prefer the checked std method unless profiling says otherwise
+ */
+
+// EXPECTED:
+// third value: 30
+// checked_get(&values, 1) = Some(20)
+// checked_get(&values, 10) = None
+// middle two: [20, 30]
+// round trip: héllo
+// checked: Err(Utf8Error { valid_up_to: 1, error_len: None })
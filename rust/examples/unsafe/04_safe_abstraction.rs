@@ -0,0 +1,103 @@
+// Unsafe 4: Building a Safe Abstraction
+// Demonstrates split_at_mut: a safe API whose insides have to be unsafe
+//
+// Concepts: split_at_mut, safe abstractions over unsafe code
+// Difficulty: advanced
+// Minutes: 10
+//
+// Most unsafe code in Rust lives inside a function with a safe signature.
+// The function checks (or arranges) everything the unsafe code relies on,
+// so no caller, however careless, can trigger undefined behavior. That is
+// what people mean by "a sound abstraction".
+//
+// Check this file for undefined behavior with:
+//   cargo +nightly miri run -p unsafe-examples --bin 04_safe_abstraction
+
+/// Splits a slice into two non-overlapping mutable halves at `mid`.
+///
+/// Panics if `mid > values.len()`.
+fn split_at_mut(values: &mut [i32], mid: usize) -> (&mut [i32], &mut [i32]) {
+    let len = values.len();
+    let ptr = values.as_mut_ptr();
+    // This assert is what makes the function sound. Delete it and a
+    // caller could pass mid = 100 from SAFE code and get a slice of memory
+    // that isn't ours. Safe functions must not rely on callers being nice.
+    assert!(mid <= len, "mid {} is past the end ({})", mid, len);
+
+    // SAFETY:
+    // - `ptr..ptr+mid` and `ptr+mid..ptr+len` are both inside `values`,
+    //   because mid <= len.
+    // - The two ranges don't overlap, so the two &mut slices never alias.
+    // - Both borrow from `values` (same lifetime in the signature), so
+    //   neither can outlive the original slice.
+    unsafe {
+        (
+            std::slice::from_raw_parts_mut(ptr, mid),
+            std::slice::from_raw_parts_mut(ptr.add(mid), len - mid),
+        )
+    }
+}
+
+// Why it needs unsafe at all: the borrow checker sees two &mut borrows of
+// `values` and can't tell that they cover different halves.
+#[cfg(feature = "broken")]
+fn split_at_mut_safe(values: &mut [i32], mid: usize) -> (&mut [i32], &mut [i32]) {
+    (&mut values[..mid], &mut values[mid..])
+}
+// error[E0499]: cannot borrow `*values` as mutable more than once at a time
+
+/// Swaps the first and last elements, using split_at_mut.
+fn swap_ends(values: &mut [i32]) {
+    if values.len() < 2 {
+        return;
+    }
+    let last = values.len() - 1;
+    let (front, back) = split_at_mut(values, last);
+    std::mem::swap(&mut front[0], &mut back[0]);
+}
+
+fn main() {
+    let mut numbers = [1, 2, 3, 4, 5, 6];
+
+    // Callers use it like any other safe function
+    let (left, right) = split_at_mut(&mut numbers, 2);
+    left[0] = 100;
+    right[0] = 300;
+    println!("left: {:?}, right: {:?}", left, right);
+    println!("numbers: {:?}", numbers);
+
+    swap_ends(&mut numbers);
+    println!("after swap_ends: {:?}", numbers);
+
+    // The edges are fine: one half is simply empty
+    let (empty, all) = split_at_mut(&mut numbers, 0);
+    println!("empty: {:?}, all: {:?}", empty, all);
+
+    // And misuse is a clean panic, never undefined behavior:
+    //   split_at_mut(&mut numbers, 100);
+    //   panicked: mid 100 is past the end (6)
+
+    // The real thing: the standard library's slice::split_at_mut is
+    // written exactly like this.
+    let mut letters = ['a', 'b', 'c'];
+    let (a, bc) = letters.split_at_mut(1);
+    a[0] = 'A';
+    bc[1] = 'C';
+    println!("std::split_at_mut: {:?}", letters);
+}
+
+/*
+ * Key Concepts:
+ * - Wrap unsafe code in functions with safe signatures
+ * - The safe function must check everything the unsafe code assumes
+ * - A sound API cannot cause UB no matter how safe code calls it
+ * - Lifetimes in the signature tie the results to the input
+ * - Misuse should panic (or return an error), never corrupt memory
+ */
+
+// EXPECTED:
+// left: [100, 2], right: [300, 4, 5, 6]
+// numbers: [100, 2, 300, 4, 5, 6]
+// after swap_ends: [6, 2, 300, 4, 5, 100]
+// empty: [], all: [6, 2, 300, 4, 5, 100]
+// std::split_at_mut: ['A', 'b', 'C']
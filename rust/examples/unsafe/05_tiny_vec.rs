@@ -0,0 +1,280 @@
+// Unsafe 5: A Tiny Vec
+// Demonstrates a growable array built on raw allocation, with a safe API
+//
+// Concepts: raw allocation, Layout, safe APIs over unsafe code
+// Difficulty: advanced
+// Minutes: 25
+//
+// Vec<T> is three numbers: a pointer to a heap buffer, how many slots the
+// buffer has (capacity) and how many are filled (length). Here we build
+// our own, TinyVec<T>, directly on the allocator. It is the classic
+// example of unsafe code hiding behind a safe interface.
+//
+// Check this file (and its tests) for undefined behavior with:
+//   cargo +nightly miri run -p unsafe-examples --bin 05_tiny_vec
+//   cargo +nightly miri test -p unsafe-examples --bin 05_tiny_vec
+
+use std::alloc::{self, Layout};
+use std::ops::{Deref, DerefMut};
+use std::ptr::{self, NonNull};
+
+/// A growable array of `T`.
+///
+/// The invariants every method relies on, and must keep true:
+/// 1. If `cap > 0`, `ptr` points at an allocation of exactly
+///    `Layout::array::<T>(cap)`; if `cap == 0`, nothing is allocated.
+/// 2. `len <= cap`.
+/// 3. The first `len` slots are initialized; the rest are not.
+///
+/// The fields are private, so only the code in this file can break them.
+pub struct TinyVec<T> {
+    ptr: NonNull<T>,
+    cap: usize,
+    len: usize,
+}
+
+impl<T> TinyVec<T> {
+    pub fn new() -> Self {
+        // Zero-sized types (like `()`) need special handling that would
+        // double the size of this example, so we simply refuse them.
+        assert!(
+            size_of::<T>() != 0,
+            "TinyVec does not support zero-sized types"
+        );
+        // A dangling, well-aligned pointer: never dereferenced while cap == 0
+        TinyVec {
+            ptr: NonNull::dangling(),
+            cap: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.len == self.cap {
+            self.grow();
+        }
+        // SAFETY: after grow(), len < cap, so slot `len` is inside the
+        // allocation (invariant 1) and uninitialized (invariant 3).
+        // `ptr::write` doesn't drop the old "value" there, which matters:
+        // there isn't one.
+        unsafe {
+            ptr::write(self.ptr.as_ptr().add(self.len), value);
+        }
+        self.len += 1; // slot `len` is now initialized: invariant 3 holds
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: slot `len` was initialized. We just shrank `len`, so
+        // nothing will read or drop it again: reading moves the value out.
+        unsafe { Some(ptr::read(self.ptr.as_ptr().add(self.len))) }
+    }
+
+    /// Doubles the capacity (or makes room for 4 to start with).
+    fn grow(&mut self) {
+        let new_cap = if self.cap == 0 { 4 } else { self.cap * 2 };
+        let new_layout = Layout::array::<T>(new_cap).expect("capacity overflow");
+
+        let new_ptr = if self.cap == 0 {
+            // SAFETY: the layout has a non-zero size (T isn't zero-sized
+            // and new_cap > 0).
+            unsafe { alloc::alloc(new_layout) }
+        } else {
+            let old_layout = Layout::array::<T>(self.cap).unwrap();
+            // SAFETY: `ptr` was allocated with `old_layout` (invariant 1)
+            // and the new size is non-zero. realloc moves the initialized
+            // elements for us if it has to.
+            unsafe { alloc::realloc(self.ptr.as_ptr().cast(), old_layout, new_layout.size()) }
+        };
+
+        // A null pointer means the allocator is out of memory
+        self.ptr = match NonNull::new(new_ptr.cast::<T>()) {
+            Some(ptr) => ptr,
+            None => alloc::handle_alloc_error(new_layout),
+        };
+        self.cap = new_cap; // invariant 1 holds for the new buffer
+    }
+}
+
+impl<T> Default for TinyVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Deref to a slice gives us indexing, iteration, len-checked get(), sort,
+// and the rest of the slice API for free.
+impl<T> Deref for TinyVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: the first `len` slots are initialized (invariant 3), and
+        // `ptr` is non-null and aligned even when nothing is allocated.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for TinyVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: as in deref; `&mut self` means no one else can see them
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for TinyVec<T> {
+    fn drop(&mut self) {
+        // Drop the elements first (pop reads them out one by one)...
+        while self.pop().is_some() {}
+        // ...then hand the buffer back
+        if self.cap > 0 {
+            // SAFETY: allocated with exactly this layout (invariant 1)
+            unsafe {
+                alloc::dealloc(
+                    self.ptr.as_ptr().cast(),
+                    Layout::array::<T>(self.cap).unwrap(),
+                );
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut words: TinyVec<String> = TinyVec::new();
+    println!("new: len {}, capacity {}", words.len(), words.capacity());
+
+    for word in ["unsafe", "code", "behind", "a", "safe", "api"] {
+        words.push(word.to_string());
+        println!(
+            "pushed {:<8} len {}, capacity {}",
+            word,
+            words.len(),
+            words.capacity()
+        );
+    }
+
+    // Slice methods through Deref
+    println!("words[2] = {}", words[2]);
+    println!("get(10) = {:?}", words.get(10));
+    words.sort();
+    println!("sorted: {:?}", &words[..]);
+
+    println!("popped: {:?}", words.pop());
+    println!("joined: {}", words.join(" "));
+
+    // Out-of-bounds indexing panics inside the slice, just like Vec; our
+    // unsafe code never sees a bad index.
+    //   words[99]  // panic: index out of bounds: the len is 5 but the index is 99
+
+    // When `words` goes out of scope, Drop frees the Strings AND the buffer.
+    // Under Miri, forgetting either one is reported as a memory leak.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn push_and_pop_are_last_in_first_out() {
+        let mut values = TinyVec::new();
+        for i in 0..10 {
+            values.push(i);
+        }
+        assert_eq!(values.len(), 10);
+        assert_eq!(&values[..], &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(values.pop(), Some(9));
+        assert_eq!(values.pop(), Some(8));
+        assert_eq!(values.len(), 8);
+    }
+
+    #[test]
+    fn capacity_doubles() {
+        let mut values = TinyVec::new();
+        assert_eq!(values.capacity(), 0);
+        values.push(1u8);
+        assert_eq!(values.capacity(), 4);
+        for i in 0..4 {
+            values.push(i);
+        }
+        assert_eq!(values.capacity(), 8);
+    }
+
+    #[test]
+    fn pop_on_empty_is_none() {
+        let mut values: TinyVec<i32> = TinyVec::new();
+        assert!(values.is_empty());
+        assert_eq!(values.pop(), None);
+    }
+
+    /// Counts its own drops, so we can check each element is dropped once.
+    struct Noisy(Rc<Cell<usize>>);
+
+    impl Drop for Noisy {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn every_element_is_dropped_exactly_once() {
+        let drops = Rc::new(Cell::new(0));
+        let mut values = TinyVec::new();
+        for _ in 0..5 {
+            values.push(Noisy(Rc::clone(&drops)));
+        }
+        drop(values.pop());
+        assert_eq!(drops.get(), 1);
+        drop(values);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn elements_survive_reallocation() {
+        let mut words = TinyVec::new();
+        for i in 0..20 {
+            words.push(i.to_string());
+        }
+        words[0].push('!');
+        assert_eq!(words[0], "0!");
+        assert_eq!(words[19], "19");
+    }
+}
+
+/*
+ * Key Concepts:
+ * - A safe type over unsafe internals must write down its invariants
+ * - Private fields stop outside code from breaking those invariants
+ * - ptr::write/ptr::read move values in and out of raw memory
+ * - Drop must drop the live elements AND free the allocation
+ * - Miri checks the whole thing, tests included, for UB and leaks
+ */
+
+// EXPECTED:
+// new: len 0, capacity 0
+// pushed unsafe   len 1, capacity 4
+// pushed code     len 2, capacity 4
+// pushed behind   len 3, capacity 4
+// pushed a        len 4, capacity 4
+// pushed safe     len 5, capacity 8
+// pushed api      len 6, capacity 8
+// words[2] = behind
+// get(10) = None
+// sorted: ["a", "api", "behind", "code", "safe", "unsafe"]
+// popped: Some("unsafe")
+// joined: a api behind code safe
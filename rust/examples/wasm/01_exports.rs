@@ -0,0 +1,97 @@
+// Wasm 1: Exporting Functions to JavaScript
+// Demonstrates #[wasm_bindgen] functions, which types can cross into JS, and calling the same code natively
+//
+// Concepts: #[wasm_bindgen], types across the JS boundary, native builds
+// Difficulty: advanced
+// Minutes: 10
+//
+// WebAssembly is a compact bytecode that browsers (and Node) run at close
+// to native speed, in a sandbox: a module can only touch its own linear
+// memory, and it can only pass numbers across the boundary. Rust compiles
+// to it with `--target wasm32-unknown-unknown`.
+//
+// Numbers alone would make for an awkward API, so wasm-bindgen writes the
+// glue. Put #[wasm_bindgen] on a pub fn in a library, and wasm-pack
+// generates a JS module with a function of the same name that:
+//
+// - copies a JS string into the module's memory as UTF-8 (JS strings are
+//   UTF-16), and hands Rust a &str to it,
+// - calls the Rust function,
+// - and turns the String it returns into a JS string, freeing the copy.
+//
+// The library behind this chapter, `textprimes/lib.rs`, exports reverse()
+// and, in 02_sieve, a prime sieve. wasm-pack builds them for the browser:
+//
+//     cargo run -p tutor -- build wasm
+//
+// and `www/index.html` calls them:
+//
+//     import init, { reverse } from "../pkg/textprimes.js";
+//     await init();
+//     reverse("WebAssembly");   // "ylbmessAbeW"
+//
+// Outside of wasm the attribute changes nothing, so this file calls the
+// same functions as ordinary Rust, and `cargo test` tests them natively.
+//
+// Only types wasm-bindgen knows how to convert can cross: integers and
+// floats, bool, char, &str and String, Vec and slices of numbers (typed
+// arrays in JS), Option of those, exported structs, and JsValue for
+// anything JS. A HashMap, say, has no JS counterpart it converts to.
+
+use textprimes::reverse;
+
+fn main() {
+    for text in ["WebAssembly", "racecar", "día", ""] {
+        println!("reverse({text:?}) = {:?}", reverse(text));
+    }
+
+    // One char at a time, not one byte: "día" survives, but a letter
+    // written as two chars (e + a combining accent) comes apart
+    let composed = "e\u{301}";
+    println!(
+        "\n{composed:?} is {} chars, {} bytes",
+        composed.chars().count(),
+        composed.len()
+    );
+    println!(
+        "reversed, the accent comes first: {:?}",
+        reverse(composed).chars().collect::<Vec<_>>()
+    );
+
+    // Reversing twice is the identity
+    let text = "Hello from Rust, in the browser";
+    println!("\ntwice: {:?}", reverse(&reverse(text)));
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        use std::collections::HashMap;
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen]
+        pub fn total(counts: HashMap<String, u32>) -> u32 {
+            counts.values().sum()
+        }
+    }
+    // error[E0277]: the trait bound `HashMap<String, u32>: FromWasmAbi` is not satisfied
+}
+
+/*
+ * Key Concepts:
+ * - wasm32-unknown-unknown is a target like any other; wasm-pack builds a library for it
+ * - #[wasm_bindgen] on a pub fn exports it, with generated JS glue for the arguments
+ * - Strings are copied across the boundary: UTF-16 in JS, UTF-8 in the module
+ * - Only convertible types cross; the compiler rejects the rest
+ * - Outside of wasm the same functions are plain Rust, tested with cargo test
+ */
+
+// EXPECTED:
+// reverse("WebAssembly") = "ylbmessAbeW"
+// reverse("racecar") = "racecar"
+// reverse("día") = "aíd"
+// reverse("") = ""
+//
+// "e\u{301}" is 2 chars, 3 bytes
+// reversed, the accent comes first: ['\u{301}', 'e']
+//
+// twice: "Hello from Rust, in the browser"
@@ -0,0 +1,94 @@
+// Wasm 2: A Prime Sieve as a JavaScript Class
+// Demonstrates exporting a struct and its methods, Vec<u32> as a Uint32Array, and who frees what
+//
+// Concepts: exported structs, methods, Uint32Array, memory ownership
+// Difficulty: advanced
+// Minutes: 10
+//
+// #[wasm_bindgen] on a struct and on its impl block exports a JS class.
+// The struct itself stays inside the module's memory; the JS object only
+// holds a pointer to it, and each method call passes that pointer back:
+//
+//     const sieve = new Sieve(1000000);   // #[wasm_bindgen(constructor)]
+//     sieve.count();                      // 78498
+//     sieve.isPrime(999983);              // js_name = isPrime
+//     sieve.limit;                        // a getter, not a method
+//     sieve.free();                       // drops the Rust value
+//
+// JS's garbage collector doesn't know about Rust's memory, so a class
+// instance that is never freed leaks its Vec until the page goes away.
+// wasm-bindgen can register a finalizer for it, but the rule is the same
+// as C's: whoever allocated it says when it's done.
+//
+// A Vec<u32> that is returned is copied into a fresh Uint32Array, and the
+// Rust side is dropped; the sieve's own Vec<bool> never leaves the module.
+// A public field of an exported struct becomes a JS property, which JS
+// reads by copying it out, so it must be Copy: a String field needs
+// `#[wasm_bindgen(getter_with_clone)]` instead.
+//
+// The sieve of Eratosthenes itself crosses out the multiples of each
+// prime, starting at its square, since smaller multiples were crossed out
+// by smaller primes.
+
+use textprimes::{primes_up_to, Sieve};
+
+fn main() {
+    println!("primes up to 50: {:?}", primes_up_to(50));
+
+    let sieve = Sieve::new(1_000_000);
+    println!("\nup to {}: {} primes", sieve.limit(), sieve.count());
+    for n in [999_983, 999_999, 1_000_001] {
+        println!("isPrime({n}) = {}", sieve.is_prime(n));
+    }
+
+    // What JS's Uint32Array would hold: 4 bytes per prime
+    let primes = sieve.primes();
+    println!(
+        "\nthe last three: {:?}, {} bytes as a Uint32Array",
+        &primes[primes.len() - 3..],
+        primes.len() * std::mem::size_of::<u32>()
+    );
+
+    // The gaps between primes grow, slowly
+    let widest = primes
+        .windows(2)
+        .max_by_key(|pair| pair[1] - pair[0])
+        .unwrap();
+    println!(
+        "widest gap below a million: {} after {}",
+        widest[1] - widest[0],
+        widest[0]
+    );
+
+    // This would cause an error:
+    #[cfg(feature = "broken")]
+    {
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen]
+        pub struct Named {
+            pub name: String,
+        }
+    }
+    // error[E0277]: the trait bound `String: Copy` is not satisfied
+}
+
+/*
+ * Key Concepts:
+ * - #[wasm_bindgen] on a struct and its impl exports a JS class with those methods
+ * - #[wasm_bindgen(constructor)], getter, and js_name shape the JS side of the API
+ * - The struct lives in the module's memory; JS must call free() to drop it
+ * - Returned Vec<u32> is copied into a Uint32Array; internal data never crosses
+ * - Public fields of exported structs must be Copy, or use getter_with_clone
+ */
+
+// EXPECTED:
+// primes up to 50: [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]
+//
+// up to 1000000: 78498 primes
+// isPrime(999983) = true
+// isPrime(999999) = false
+// isPrime(1000001) = false
+//
+// the last three: [999961, 999979, 999983], 313992 bytes as a Uint32Array
+// widest gap below a million: 114 after 492113
@@ -0,0 +1,36 @@
+//! The exported functions again, this time compiled to WebAssembly and run
+//! by wasm-bindgen-test's runner in Node, with no browser window:
+//!
+//! ```text
+//! wasm-pack test --node examples/wasm
+//! ```
+//!
+//! To run them in a headless browser instead (`wasm-pack test --headless
+//! --firefox`), add `wasm_bindgen_test_configure!(run_in_browser);`.
+//! Built for any other target, this file is empty.
+
+#![cfg(target_arch = "wasm32")]
+
+use textprimes::{primes_up_to, reverse, Sieve};
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn reverse_round_trips_through_js_strings() {
+    assert_eq!(reverse("WebAssembly"), "ylbmessAbeW");
+    assert_eq!(reverse("día"), "aíd");
+}
+
+#[wasm_bindgen_test]
+fn the_sieve_gives_the_same_primes_as_natively() {
+    assert_eq!(primes_up_to(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    assert_eq!(Sieve::new(1_000_000).count(), 78_498);
+}
+
+#[wasm_bindgen_test]
+fn a_sieve_answers_for_numbers_up_to_its_limit() {
+    let sieve = Sieve::new(100);
+    assert_eq!(sieve.limit(), 100);
+    assert!(sieve.is_prime(97));
+    assert!(!sieve.is_prime(99));
+    assert!(!sieve.is_prime(101));
+}
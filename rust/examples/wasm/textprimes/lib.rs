@@ -0,0 +1,130 @@
+//! The functions the WebAssembly chapter exports to JavaScript.
+//!
+//! `#[wasm_bindgen]` on a function or a type makes it callable from JS:
+//! wasm-pack compiles the crate to `wasm32-unknown-unknown` and generates a
+//! JS module that converts the arguments on the way in and the result on
+//! the way out. Outside of wasm the attribute changes nothing, so the same
+//! functions are ordinary Rust too, tested here with `cargo test`.
+//!
+//! ```
+//! assert_eq!(textprimes::reverse("wasm"), "msaw");
+//! assert_eq!(textprimes::primes_up_to(10), [2, 3, 5, 7]);
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+/// `text` reversed, one `char` at a time, so that characters outside
+/// ASCII survive. A JS string arrives as UTF-16 and is copied into the
+/// module's memory as UTF-8; the result is copied back out.
+#[wasm_bindgen]
+pub fn reverse(text: &str) -> String {
+    text.chars().rev().collect()
+}
+
+/// The primes up to and including `limit`. JS receives a `Uint32Array`,
+/// copied out of the module's memory.
+#[wasm_bindgen(js_name = primesUpTo)]
+pub fn primes_up_to(limit: u32) -> Vec<u32> {
+    Sieve::new(limit).primes()
+}
+
+/// The sieve of Eratosthenes up to `limit`. In JS it is a class:
+/// `new Sieve(100)` creates one inside the module's memory and the JS object
+/// holds a pointer to it, so `sieve.free()` is how JS drops it.
+#[wasm_bindgen]
+pub struct Sieve {
+    /// `composite[n]` for every `n` up to the limit.
+    composite: Vec<bool>,
+}
+
+#[wasm_bindgen]
+impl Sieve {
+    #[wasm_bindgen(constructor)]
+    pub fn new(limit: u32) -> Sieve {
+        let len = limit as usize + 1;
+        let mut composite = vec![false; len];
+        composite[0] = true;
+        if len > 1 {
+            composite[1] = true;
+        }
+        let mut n = 2;
+        while n * n < len {
+            if !composite[n] {
+                for multiple in (n * n..len).step_by(n) {
+                    composite[multiple] = true;
+                }
+            }
+            n += 1;
+        }
+        Sieve { composite }
+    }
+
+    /// The largest number the sieve knows about.
+    #[wasm_bindgen(getter)]
+    pub fn limit(&self) -> u32 {
+        (self.composite.len() - 1) as u32
+    }
+
+    /// Whether `n` is prime; `false` for anything past the limit.
+    #[wasm_bindgen(js_name = isPrime)]
+    pub fn is_prime(&self, n: u32) -> bool {
+        self.composite.get(n as usize) == Some(&false)
+    }
+
+    /// How many primes there are up to the limit.
+    pub fn count(&self) -> u32 {
+        self.composite
+            .iter()
+            .filter(|&&composite| !composite)
+            .count() as u32
+    }
+
+    pub fn primes(&self) -> Vec<u32> {
+        (0..=self.limit()).filter(|&n| self.is_prime(n)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverses_characters_not_bytes() {
+        assert_eq!(reverse("hello"), "olleh");
+        assert_eq!(reverse("día"), "aíd");
+        assert_eq!(reverse(""), "");
+    }
+
+    #[test]
+    fn finds_the_primes_below_thirty() {
+        assert_eq!(primes_up_to(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+
+    #[test]
+    fn the_limit_itself_is_included() {
+        assert_eq!(primes_up_to(7), [2, 3, 5, 7]);
+        assert!(Sieve::new(49).primes().ends_with(&[47]));
+        assert!(!Sieve::new(49).is_prime(49));
+    }
+
+    #[test]
+    fn tiny_limits_have_no_primes() {
+        assert!(primes_up_to(0).is_empty());
+        assert!(primes_up_to(1).is_empty());
+        assert_eq!(primes_up_to(2), [2]);
+    }
+
+    #[test]
+    fn counts_agree_with_known_values() {
+        assert_eq!(Sieve::new(100).count(), 25);
+        assert_eq!(Sieve::new(1_000_000).count(), 78_498);
+    }
+
+    #[test]
+    fn numbers_past_the_limit_are_not_prime() {
+        let sieve = Sieve::new(10);
+        assert_eq!(sieve.limit(), 10);
+        assert!(sieve.is_prime(7));
+        assert!(!sieve.is_prime(11));
+    }
+}
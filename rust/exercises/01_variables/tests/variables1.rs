@@ -0,0 +1,9 @@
+#[test]
+fn counts_up_to_the_limit() {
+    assert_eq!(count_to(3), 3, "count_to(3) should count 1, 2, 3");
+}
+
+#[test]
+fn counting_to_zero_does_nothing() {
+    assert_eq!(count_to(0), 0, "count_to(0) should never enter the loop");
+}
@@ -0,0 +1,9 @@
+#[test]
+fn counts_every_space() {
+    assert_eq!(count_spaces("a b c"), 2, "\"a b c\" has two spaces");
+}
+
+#[test]
+fn text_without_spaces() {
+    assert_eq!(count_spaces("rust"), 0, "\"rust\" has no spaces");
+}
@@ -0,0 +1,9 @@
+#[test]
+fn max_points_is_one_hundred_thousand() {
+    assert_eq!(MAX_POINTS, 100_000, "MAX_POINTS should be 100,000");
+}
+
+#[test]
+fn spending_points() {
+    assert_eq!(points_left(25_000), 75_000, "100,000 - 25,000 leaves 75,000");
+}
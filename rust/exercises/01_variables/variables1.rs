@@ -0,0 +1,17 @@
+// Exercise: Variables 1 - Making a variable mutable
+// Related example: examples/02_variables.rs
+//
+// `count_to` wants to add 1 to `count` on every loop iteration, but Rust
+// variables are immutable by default, so this doesn't compile yet.
+//
+// TODO: Make `count` mutable so the loop can change it.
+//
+// Check your work with: cargo run -p tutor -- check variables1
+
+pub fn count_to(limit: u32) -> u32 {
+    let count = 0; // TODO: something is missing here
+    for _ in 0..limit {
+        count += 1;
+    }
+    count
+}
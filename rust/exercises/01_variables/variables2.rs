@@ -0,0 +1,17 @@
+// Exercise: Variables 2 - Shadowing to change a type
+// Related example: examples/02_variables.rs
+//
+// `count_spaces` collects every space in `text` into a String, but the
+// function promises to return a *number*. Mutation can't change a
+// variable's type - shadowing can.
+//
+// TODO: Shadow `spaces` with its length so the function returns a usize.
+//       (Don't rename the variable - reuse the name with another `let`.)
+//
+// Check your work with: cargo run -p tutor -- check variables2
+
+pub fn count_spaces(text: &str) -> usize {
+    let spaces: String = text.chars().filter(|c| *c == ' ').collect();
+    // TODO: add one line here
+    spaces
+}
@@ -0,0 +1,15 @@
+// Exercise: Variables 3 - Constants
+//
+// Constants are like immutable variables, but they are declared with
+// `const`, live for the whole program, and ALWAYS need a type annotation.
+//
+// TODO: Fix the declaration of MAX_POINTS: give it the type `u32` and the
+//       value one hundred thousand (tip: you can write it as `100_000`).
+//
+// Check your work with: cargo run -p tutor -- check variables3
+
+const MAX_POINTS = 0;
+
+pub fn points_left(spent: u32) -> u32 {
+    MAX_POINTS - spent
+}
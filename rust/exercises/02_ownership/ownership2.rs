@@ -0,0 +1,15 @@
+// Exercise: Ownership 2 - Taking ownership and giving it back
+//
+// `exclaim` takes ownership of a String, changes it, and hands it back to
+// the caller. Owning a value doesn't automatically let you change it: the
+// binding still has to be declared mutable.
+//
+// TODO: Let `exclaim` modify its parameter. Only the function signature
+//       needs to change.
+//
+// Check your work with: cargo run -p tutor -- check ownership2
+
+pub fn exclaim(text: String) -> String {
+    text.push('!');
+    text
+}
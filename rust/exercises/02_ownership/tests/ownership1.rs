@@ -0,0 +1,6 @@
+#[test]
+fn both_greetings_use_the_name() {
+    let (hello, goodbye) = greetings(String::from("Ferris"));
+    assert_eq!(hello, "Hello, Ferris!");
+    assert_eq!(goodbye, "Goodbye, Ferris!");
+}
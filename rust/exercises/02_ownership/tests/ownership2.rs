@@ -0,0 +1,9 @@
+#[test]
+fn adds_an_exclamation_mark() {
+    assert_eq!(exclaim(String::from("hello")), "hello!");
+}
+
+#[test]
+fn works_on_empty_text() {
+    assert_eq!(exclaim(String::new()), "!");
+}
@@ -0,0 +1,19 @@
+// Exercise: Borrowing 1 - Looking without taking
+//
+// `calculate_length` takes ownership of its String, so after calling it
+// `describe` can no longer use `text`. A function that only needs to *read*
+// a value should borrow it instead.
+//
+// TODO: Change `calculate_length` to take a reference (`&String` or, even
+//       better, `&str`) and update the call site to pass `&text`.
+//
+// Check your work with: cargo run -p tutor -- check borrowing1
+
+pub fn calculate_length(text: String) -> usize {
+    text.len()
+}
+
+pub fn describe(text: String) -> String {
+    let length = calculate_length(text);
+    format!("'{text}' is {length} bytes long")
+}
@@ -0,0 +1,11 @@
+#[test]
+fn describes_the_text() {
+    assert_eq!(describe(String::from("hello")), "'hello' is 5 bytes long");
+}
+
+#[test]
+fn length_only_borrows() {
+    let text = String::from("borrow");
+    assert_eq!(calculate_length(&text), 6);
+    assert_eq!(text, "borrow", "the caller should still own `text`");
+}
@@ -0,0 +1,14 @@
+#[test]
+fn suffix_is_added_in_place() {
+    let mut name = String::from("main");
+    add_suffix(&mut name);
+    assert_eq!(name, "main.rs");
+}
+
+#[test]
+fn suffix_can_be_added_twice() {
+    let mut name = String::from("lib");
+    add_suffix(&mut name);
+    add_suffix(&mut name);
+    assert_eq!(name, "lib.rs.rs");
+}
@@ -0,0 +1,27 @@
+// Exercise: Structs 1 - Methods on a struct
+//
+// `Rectangle` compiles fine, but its methods return placeholder values.
+// This time the compiler can't help you: the hidden tests will tell you
+// which answers are wrong.
+//
+// TODO: Implement `area` and `can_hold`.
+//
+// Check your work with: cargo run -p tutor -- check structs1
+
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    /// Width times height.
+    pub fn area(&self) -> u32 {
+        0 // TODO
+    }
+
+    /// True if `other` fits completely inside `self`.
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        let _ = other;
+        false // TODO
+    }
+}
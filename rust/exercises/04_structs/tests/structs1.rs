@@ -0,0 +1,19 @@
+#[test]
+fn area_multiplies_the_sides() {
+    let rect = Rectangle { width: 30, height: 50 };
+    assert_eq!(rect.area(), 1500, "a 30x50 rectangle has an area of 1500");
+}
+
+#[test]
+fn bigger_rectangle_holds_smaller_one() {
+    let big = Rectangle { width: 8, height: 7 };
+    let small = Rectangle { width: 5, height: 1 };
+    assert!(big.can_hold(&small), "an 8x7 rectangle can hold a 5x1 one");
+}
+
+#[test]
+fn smaller_rectangle_cannot_hold_bigger_one() {
+    let big = Rectangle { width: 8, height: 7 };
+    let small = Rectangle { width: 5, height: 1 };
+    assert!(!small.can_hold(&big), "a 5x1 rectangle cannot hold an 8x7 one");
+}
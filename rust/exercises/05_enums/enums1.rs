@@ -0,0 +1,24 @@
+// Exercise: Enums 1 - Exhaustive matching
+//
+// A `match` must handle every possible variant of an enum. Forget one and
+// the compiler refuses to build (error E0004, "non-exhaustive patterns").
+//
+// TODO: Handle the missing coins. A dime is worth 10 cents and a quarter
+//       is worth 25 cents.
+//
+// Check your work with: cargo run -p tutor -- check enums1
+
+pub enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+pub fn value_in_cents(coin: Coin) -> u32 {
+    match coin {
+        Coin::Penny => 1,
+        Coin::Nickel => 5,
+        // TODO: two variants are missing
+    }
+}
@@ -0,0 +1,7 @@
+#[test]
+fn every_coin_has_a_value() {
+    assert_eq!(value_in_cents(Coin::Penny), 1);
+    assert_eq!(value_in_cents(Coin::Nickel), 5);
+    assert_eq!(value_in_cents(Coin::Dime), 10);
+    assert_eq!(value_in_cents(Coin::Quarter), 25);
+}
@@ -0,0 +1,54 @@
+fn run(commands: &[Command]) -> Player {
+    commands
+        .iter()
+        .fold(Player::Stopped, |player, &command| player.handle(command))
+}
+
+#[test]
+fn pause_keeps_the_track() {
+    assert_eq!(
+        run(&[Command::Play(3), Command::Pause]),
+        Player::Paused { track: 3 }
+    );
+}
+
+#[test]
+fn resume_continues_the_same_track() {
+    assert_eq!(
+        run(&[Command::Play(3), Command::Pause, Command::Resume]),
+        Player::Playing { track: 3 }
+    );
+}
+
+#[test]
+fn pause_and_resume_do_nothing_when_stopped() {
+    assert_eq!(run(&[Command::Pause]), Player::Stopped);
+    assert_eq!(run(&[Command::Resume]), Player::Stopped);
+}
+
+#[test]
+fn resume_does_nothing_while_playing() {
+    assert_eq!(
+        run(&[Command::Play(1), Command::Resume]),
+        Player::Playing { track: 1 }
+    );
+}
+
+#[test]
+fn play_and_stop_still_work_from_paused() {
+    assert_eq!(
+        run(&[Command::Play(1), Command::Pause, Command::Play(2)]),
+        Player::Playing { track: 2 }
+    );
+    assert_eq!(
+        run(&[Command::Play(1), Command::Pause, Command::Stop]),
+        Player::Stopped
+    );
+}
+
+#[test]
+fn describes_every_state() {
+    assert_eq!(Player::Stopped.describe(), "stopped");
+    assert_eq!(Player::Playing { track: 4 }.describe(), "playing track 4");
+    assert_eq!(Player::Paused { track: 4 }.describe(), "paused on track 4");
+}
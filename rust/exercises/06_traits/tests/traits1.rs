@@ -0,0 +1,8 @@
+#[test]
+fn article_summary() {
+    let article = Article {
+        title: String::from("Rust 101"),
+        author: String::from("Ferris"),
+    };
+    assert_eq!(article.summarize(), "Rust 101, by Ferris");
+}
@@ -0,0 +1,9 @@
+#[test]
+fn dogs_use_the_default() {
+    assert_eq!(Dog.describe(), "This is a dog.");
+}
+
+#[test]
+fn cats_use_the_default() {
+    assert_eq!(Cat.describe(), "This is a cat.");
+}
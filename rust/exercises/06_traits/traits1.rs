@@ -0,0 +1,20 @@
+// Exercise: Traits 1 - Implementing a trait
+//
+// A trait describes behavior that many types can share. Here `Summary`
+// promises a `summarize` method, but `Article` doesn't implement it yet.
+//
+// TODO: Implement `Summary` for `Article`. The summary should look like
+//       "<title>, by <author>".
+//
+// Check your work with: cargo run -p tutor -- check traits1
+
+pub trait Summary {
+    fn summarize(&self) -> String;
+}
+
+pub struct Article {
+    pub title: String,
+    pub author: String,
+}
+
+// TODO: impl Summary for Article { ... }
@@ -0,0 +1,23 @@
+// Exercise: Collections 1 - From loop to pipeline
+// Related example: examples/collections/05_iterators.rs
+//
+// Here is a function written with a loop:
+//
+//     let mut total = 0;
+//     for n in numbers {
+//         if n % 2 == 0 {
+//             total += n * n;
+//         }
+//     }
+//     total
+//
+// TODO: Rewrite `sum_of_even_squares` as a single iterator chain
+//       (no `for`, no `mut`). The hidden tests compare your version with
+//       the loop above on lots of inputs.
+//
+// Check your work with: cargo run -p tutor -- check collections1
+
+pub fn sum_of_even_squares(numbers: &[i64]) -> i64 {
+    let _ = numbers;
+    todo!("numbers.iter()...")
+}
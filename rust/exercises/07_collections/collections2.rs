@@ -0,0 +1,22 @@
+// Exercise: Collections 2 - Transforming into a new Vec
+// Related example: examples/collections/05_iterators.rs
+//
+// The loop version:
+//
+//     let mut result = Vec::new();
+//     for word in text.split_whitespace() {
+//         if word.len() > 3 {
+//             result.push(word.to_uppercase());
+//         }
+//     }
+//     result
+//
+// TODO: Rewrite `long_words_shouted` as an iterator chain ending in
+//       `.collect()`.
+//
+// Check your work with: cargo run -p tutor -- check collections2
+
+pub fn long_words_shouted(text: &str) -> Vec<String> {
+    let _ = text;
+    todo!("text.split_whitespace()...")
+}
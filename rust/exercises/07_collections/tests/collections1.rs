@@ -0,0 +1,35 @@
+fn reference(numbers: &[i64]) -> i64 {
+    let mut total = 0;
+    for n in numbers {
+        if n % 2 == 0 {
+            total += n * n;
+        }
+    }
+    total
+}
+
+#[test]
+fn matches_the_loop_on_a_simple_list() {
+    let numbers = [1, 2, 3, 4, 5, 6];
+    assert_eq!(sum_of_even_squares(&numbers), reference(&numbers));
+}
+
+#[test]
+fn matches_the_loop_on_edge_cases() {
+    for numbers in [&[][..], &[7], &[-4, -3, 0], &[10, 20, 30]] {
+        assert_eq!(
+            sum_of_even_squares(numbers),
+            reference(numbers),
+            "different result for {:?}",
+            numbers
+        );
+    }
+}
+
+#[test]
+fn matches_the_loop_on_many_ranges() {
+    for end in 0..50 {
+        let numbers: Vec<i64> = (-end..end).collect();
+        assert_eq!(sum_of_even_squares(&numbers), reference(&numbers));
+    }
+}
@@ -0,0 +1,27 @@
+fn reference(text: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    for word in text.split_whitespace() {
+        if word.len() > 3 {
+            result.push(word.to_uppercase());
+        }
+    }
+    result
+}
+
+#[test]
+fn keeps_only_long_words_in_order() {
+    let text = "the quick brown fox jumps";
+    assert_eq!(long_words_shouted(text), reference(text));
+}
+
+#[test]
+fn matches_the_loop_on_edge_cases() {
+    for text in ["", "a bb ccc", "four", "  extra   spaces  here ", "Ünïcode wörds"] {
+        assert_eq!(
+            long_words_shouted(text),
+            reference(text),
+            "different result for {:?}",
+            text
+        );
+    }
+}
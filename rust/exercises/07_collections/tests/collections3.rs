@@ -0,0 +1,27 @@
+fn reference(text: &str) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for c in text.chars() {
+        if c.is_alphabetic() {
+            *counts.entry(c.to_ascii_lowercase()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[test]
+fn counts_letters_ignoring_case() {
+    let text = "Hello, World";
+    assert_eq!(letter_counts(text), reference(text));
+}
+
+#[test]
+fn matches_the_loop_on_edge_cases() {
+    for text in ["", "123 !?", "aAaA", "the quick brown fox jumps over the lazy dog"] {
+        assert_eq!(
+            letter_counts(text),
+            reference(text),
+            "different result for {:?}",
+            text
+        );
+    }
+}
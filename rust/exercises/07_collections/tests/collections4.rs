@@ -0,0 +1,25 @@
+fn reference(words: &[&str]) -> BTreeMap<usize, BTreeSet<String>> {
+    let mut groups: BTreeMap<usize, BTreeSet<String>> = BTreeMap::new();
+    for word in words {
+        let word = word.to_lowercase();
+        groups.entry(word.len()).or_default().insert(word);
+    }
+    groups
+}
+
+#[test]
+fn groups_words_by_length() {
+    let words = ["go", "rust", "java", "c"];
+    assert_eq!(group_by_length(&words), reference(&words));
+}
+
+#[test]
+fn duplicates_and_case_collapse() {
+    let words = ["Rust", "rust", "RUST", "Go", "go"];
+    assert_eq!(group_by_length(&words), reference(&words));
+}
+
+#[test]
+fn empty_input_gives_empty_map() {
+    assert_eq!(group_by_length(&[]), reference(&[]));
+}
@@ -0,0 +1,38 @@
+// Exercise: Closures 1 - A memoizing cache
+// Related example: examples/closures/05_closures_in_structs.rs
+//
+// Some functions are slow, and we call them with the same argument over
+// and over. A memoizing cache wraps the function: the first time it sees
+// an argument it calls the function and remembers the result; after that
+// it answers from memory without calling the function again.
+//
+// TODO: Implement `Cache::new`, which stores the closure and starts with
+//       nothing remembered.
+// TODO: Implement `Cache::get`. If `arg` has been seen before, return the
+//       remembered value. Otherwise call the closure, remember its result,
+//       and return it.
+//
+// Check your work with: cargo run -p tutor -- check closures1
+
+use std::collections::HashMap;
+
+pub struct Cache<F>
+where
+    F: Fn(u64) -> u64,
+{
+    function: F,
+    values: HashMap<u64, u64>,
+}
+
+impl<F> Cache<F>
+where
+    F: Fn(u64) -> u64,
+{
+    pub fn new(function: F) -> Cache<F> {
+        todo!()
+    }
+
+    pub fn get(&mut self, arg: u64) -> u64 {
+        todo!()
+    }
+}
@@ -0,0 +1,42 @@
+use std::cell::Cell;
+
+#[test]
+fn returns_what_the_function_returns() {
+    let mut cache = Cache::new(|n| n * n);
+    assert_eq!(cache.get(3), 9);
+    assert_eq!(cache.get(12), 144);
+}
+
+#[test]
+fn calls_the_function_once_per_argument() {
+    let calls = Cell::new(0);
+    let mut cache = Cache::new(|n| {
+        calls.set(calls.get() + 1);
+        n + 1
+    });
+    for _ in 0..5 {
+        assert_eq!(cache.get(7), 8);
+    }
+    assert_eq!(calls.get(), 1, "the function should run only the first time");
+}
+
+#[test]
+fn different_arguments_are_computed_separately() {
+    let calls = Cell::new(0);
+    let mut cache = Cache::new(|n| {
+        calls.set(calls.get() + 1);
+        n * 10
+    });
+    assert_eq!(cache.get(1), 10);
+    assert_eq!(cache.get(2), 20);
+    assert_eq!(cache.get(1), 10);
+    assert_eq!(cache.get(2), 20);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn works_with_closures_that_capture() {
+    let offset = 100;
+    let mut cache = Cache::new(move |n| n + offset);
+    assert_eq!(cache.get(5), 105);
+}
@@ -0,0 +1,30 @@
+// Exercise: Testing 1 - Tests that catch bugs
+// Related example: examples/testing/01_unit_tests.rs
+//
+// This time the code is already correct. YOUR job is to write the tests.
+//
+// How does the tutor know your tests are any good? It secretly plants
+// small bugs in `is_leap_year` (one at a time) and runs your tests against
+// each buggy copy. Every bug must make at least one of your tests fail.
+// A test suite that passes no matter what the code does tests nothing!
+//
+// The rules for leap years:
+//   - years divisible by 4 are leap years (2024),
+//   - except years divisible by 100, which are not (1900),
+//   - except years divisible by 400, which are (2000).
+//
+// TODO: Write tests in the `tests` module below until every planted bug
+//       is caught. Don't change `is_leap_year` itself.
+//
+// Check your work with: cargo run -p tutor -- check testing1
+
+pub fn is_leap_year(year: u32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TODO: add #[test] functions here
+}
@@ -0,0 +1,53 @@
+// Exercise: Testing 2 - Boundaries and panics
+// Related example: examples/testing/02_panics_and_results.rs
+//
+// As in testing1, the code is correct and you write the tests. The tutor
+// plants bugs in the code below and checks that your tests catch each one.
+//
+// Some of these bugs hide at the edges: absolute zero itself is a valid
+// temperature, but anything colder is not. And a function that is supposed
+// to panic needs a test proving that it does.
+//
+// TODO: Write tests until every planted bug is caught. Think about:
+//       - normal conversions (a few known values, e.g. 100°C = 212°F),
+//       - the exact boundary at ABSOLUTE_ZERO,
+//       - the panic below it (#[should_panic]).
+//       Don't change the code above the tests module.
+//
+// Check your work with: cargo run -p tutor -- check testing2
+
+pub const ABSOLUTE_ZERO: f64 = -273.15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Temperature {
+    celsius: f64,
+}
+
+impl Temperature {
+    /// Panics if `celsius` is colder than absolute zero.
+    pub fn from_celsius(celsius: f64) -> Temperature {
+        if celsius < ABSOLUTE_ZERO {
+            panic!("{}°C is colder than absolute zero", celsius);
+        }
+        Temperature { celsius }
+    }
+
+    pub fn celsius(&self) -> f64 {
+        self.celsius
+    }
+
+    pub fn fahrenheit(&self) -> f64 {
+        self.celsius * 9.0 / 5.0 + 32.0
+    }
+
+    pub fn is_freezing(&self) -> bool {
+        self.celsius <= 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TODO: add #[test] functions here
+}
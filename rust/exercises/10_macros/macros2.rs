@@ -0,0 +1,24 @@
+// Exercise: Macros 2 - Any number of items
+// Related example: examples/macros/03_repetition.rs
+//
+// Write your own version of `vec!`. The tests call it in all of these
+// ways, and each must build the same Vec that `vec!` would:
+//
+//     my_vec![]
+//     my_vec![1]
+//     my_vec![1, 2, 3]
+//     my_vec![1, 2, 3,]          (trailing comma)
+//     my_vec![0; 4]              (four zeros)
+//
+// TODO: Add the rules. You'll need a repetition `$( ... ),*`, an optional
+//       trailing comma `$(,)?`, and a separate rule for the `value; count`
+//       form. Rules are tried in order, so think about which comes first.
+//
+// Check your work with: cargo run -p tutor -- check macros2
+
+macro_rules! my_vec {
+    // TODO: only `my_vec![]` works so far
+    () => {
+        Vec::new()
+    };
+}
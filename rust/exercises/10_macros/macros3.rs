@@ -0,0 +1,29 @@
+// Exercise: Macros 3 - A HashMap literal
+// Related example: examples/macros/05_hashmap_dsl.rs
+//
+// Rust has `vec![...]` but no built-in literal for maps. Write one:
+//
+//     let ages = hashmap! {
+//         "ferris" => 8,
+//         "corro" => 5,
+//     };
+//
+// Keys and values can be any expressions (`1 + 1 => "two"` is fine), the
+// trailing comma is optional, and `hashmap! {}` makes an empty map. If a
+// key appears twice, the later value wins, just like calling `insert`
+// twice.
+//
+// TODO: Write the `hashmap!` macro. It should expand to a block that
+//       creates a `HashMap`, inserts every pair, and returns the map.
+//
+// Check your work with: cargo run -p tutor -- check macros3
+
+#[allow(unused_imports)]
+use std::collections::HashMap;
+
+macro_rules! hashmap {
+    // TODO: match `key => value` pairs separated by commas
+    () => {
+        todo!()
+    };
+}
@@ -0,0 +1,29 @@
+#[test]
+fn squares_literals() {
+    assert_eq!(square!(3), 9);
+    assert_eq!(square!(-4), 16);
+    assert_eq!(square!(1.5), 2.25);
+}
+
+#[test]
+fn squares_whole_expressions() {
+    assert_eq!(square!(2 + 3), 25, "square!(2 + 3) should be (2 + 3) * (2 + 3)");
+    let x = 7;
+    assert_eq!(square!(x - 1), 36);
+}
+
+#[test]
+fn works_with_method_calls() {
+    assert_eq!(square!("four".len()), 16);
+}
+
+#[test]
+fn evaluates_its_argument_once() {
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls * 10
+    };
+    assert_eq!(square!(next()), 100);
+    assert_eq!(calls, 1, "the expression was evaluated {} times", calls);
+}
@@ -0,0 +1,38 @@
+#[test]
+fn empty() {
+    let v: Vec<i32> = my_vec![];
+    assert_eq!(v, Vec::<i32>::new());
+}
+
+#[test]
+fn one_item() {
+    assert_eq!(my_vec![42], vec![42]);
+}
+
+#[test]
+fn several_items() {
+    assert_eq!(my_vec![1, 2, 3], vec![1, 2, 3]);
+    assert_eq!(my_vec!["a", "b"], vec!["a", "b"]);
+}
+
+#[test]
+fn trailing_comma() {
+    assert_eq!(my_vec![1, 2, 3,], vec![1, 2, 3]);
+}
+
+#[test]
+fn items_can_be_expressions() {
+    let base = 10;
+    assert_eq!(
+        my_vec![base + 1, base * 2, String::from("x").len()],
+        vec![11, 20, 1]
+    );
+}
+
+#[test]
+fn repeated_value() {
+    assert_eq!(my_vec![0; 4], vec![0, 0, 0, 0]);
+    assert_eq!(my_vec![String::from("hi"); 2], vec!["hi", "hi"]);
+    let n = 3;
+    assert_eq!(my_vec![true; n], vec![true; 3]);
+}
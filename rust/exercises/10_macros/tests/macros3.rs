@@ -0,0 +1,48 @@
+#[test]
+fn empty_map() {
+    let map: HashMap<&str, i32> = hashmap! {};
+    assert!(map.is_empty());
+}
+
+#[test]
+fn single_pair() {
+    let map = hashmap! { "one" => 1 };
+    assert_eq!(map.len(), 1);
+    assert_eq!(map["one"], 1);
+}
+
+#[test]
+fn several_pairs_with_a_trailing_comma() {
+    let map = hashmap! {
+        "ferris" => 8,
+        "corro" => 5,
+    };
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["ferris"], 8);
+    assert_eq!(map["corro"], 5);
+}
+
+#[test]
+fn keys_and_values_can_be_expressions() {
+    let map = hashmap! {
+        1 + 1 => "two".to_string(),
+        3 * 3 => format!("{}", "nine"),
+    };
+    assert_eq!(map[&2], "two");
+    assert_eq!(map[&9], "nine");
+}
+
+#[test]
+fn later_values_win() {
+    let map = hashmap! { "k" => 1, "k" => 2 };
+    assert_eq!(map.len(), 1);
+    assert_eq!(map["k"], 2);
+}
+
+#[test]
+fn builds_a_real_hashmap() {
+    let map: HashMap<String, Vec<u8>> = hashmap! {
+        String::from("bytes") => vec![1, 2, 3],
+    };
+    assert_eq!(map.get("bytes"), Some(&vec![1, 2, 3]));
+}
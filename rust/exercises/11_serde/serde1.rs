@@ -0,0 +1,82 @@
+// Exercise: Serde 1 - A messy order payload
+// Related examples: examples/serde/02_field_attributes.rs,
+//                   examples/serde/03_enum_representations.rs
+//
+// A shop's API sends its orders as JSON like this:
+//
+//     {
+//       "orderId": "A-1001",
+//       "placedAt": "2024-03-01T09:30:00Z",
+//       "customer": { "full_name": "Ana Lima", "e-mail": "ana@example.com" },
+//       "lineItems": [
+//         { "sku": "MUG-01", "qty": 2, "price_cents": 950 },
+//         { "sku": "TEE-04", "price_cents": 2000, "giftWrap": true }
+//       ],
+//       "status": "SHIPPED",
+//       "coupon": null,
+//       "trackingUrl": "https://track.example.com/A-1001"
+//     }
+//
+// The API has grown over the years, and it shows:
+// - the order's keys are camelCase, but the customer's are snake_case,
+//   and the email is under "e-mail"
+// - a line item's "qty" is left out when it is 1, and "giftWrap" is only
+//   there when it is true
+// - "coupon" is null, or missing altogether
+// - the status is one of "PENDING", "SHIPPED", "DELIVERED", "CANCELLED"
+// - orders from before 2020 say "order_id" instead of "orderId"
+// - there are more keys (like "trackingUrl") that we don't need
+//
+// The Rust types below are how we want to work with orders, so keep their
+// names and fields as they are.
+//
+// TODO: Add #[serde(...)] attributes until `parse_order` reads every kind of
+//       payload above. If a default needs a function, add one.
+// TODO: Implement `Order::total_cents`: every line item's price times its
+//       quantity, added up.
+//
+// Check your work with: cargo run -p tutor -- check serde1
+
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Order {
+    pub id: String,
+    pub placed_at: String,
+    pub customer: Customer,
+    pub line_items: Vec<LineItem>,
+    pub status: Status,
+    pub coupon: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Customer {
+    pub full_name: String,
+    pub email: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct LineItem {
+    pub sku: String,
+    pub quantity: u32,
+    pub price_cents: u64,
+    pub gift_wrap: bool,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+pub enum Status {
+    Pending,
+    Shipped,
+    Delivered,
+    Cancelled,
+}
+
+pub fn parse_order(json: &str) -> Result<Order, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+impl Order {
+    pub fn total_cents(&self) -> u64 {
+        todo!()
+    }
+}
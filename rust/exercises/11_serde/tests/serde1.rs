@@ -0,0 +1,114 @@
+const FULL: &str = r#"{
+    "orderId": "A-1001",
+    "placedAt": "2024-03-01T09:30:00Z",
+    "customer": { "full_name": "Ana Lima", "e-mail": "ana@example.com" },
+    "lineItems": [
+        { "sku": "MUG-01", "qty": 2, "price_cents": 950 },
+        { "sku": "TEE-04", "price_cents": 2000, "giftWrap": true }
+    ],
+    "status": "SHIPPED",
+    "coupon": null,
+    "trackingUrl": "https://track.example.com/A-1001"
+}"#;
+
+/// A small valid order, with `extra` spliced in as more top-level keys.
+fn order_with(extra: &str) -> String {
+    format!(
+        r#"{{
+            "orderId": "B-7",
+            "placedAt": "2024-05-05T12:00:00Z",
+            "customer": {{ "full_name": "Bo", "e-mail": "bo@example.com" }},
+            "lineItems": [],
+            "status": "PENDING"{extra}
+        }}"#
+    )
+}
+
+#[test]
+fn parses_the_full_payload() {
+    let order = parse_order(FULL).expect("the payload from the top of the file should parse");
+    assert_eq!(
+        order,
+        Order {
+            id: String::from("A-1001"),
+            placed_at: String::from("2024-03-01T09:30:00Z"),
+            customer: Customer {
+                full_name: String::from("Ana Lima"),
+                email: String::from("ana@example.com"),
+            },
+            line_items: vec![
+                LineItem {
+                    sku: String::from("MUG-01"),
+                    quantity: 2,
+                    price_cents: 950,
+                    gift_wrap: false,
+                },
+                LineItem {
+                    sku: String::from("TEE-04"),
+                    quantity: 1,
+                    price_cents: 2000,
+                    gift_wrap: true,
+                },
+            ],
+            status: Status::Shipped,
+            coupon: None,
+        }
+    );
+}
+
+#[test]
+fn coupon_may_be_a_string_null_or_missing() {
+    let with = parse_order(&order_with(r#", "coupon": "SPRING10""#)).unwrap();
+    assert_eq!(with.coupon.as_deref(), Some("SPRING10"));
+    let null = parse_order(&order_with(r#", "coupon": null"#)).unwrap();
+    assert_eq!(null.coupon, None);
+    let missing = parse_order(&order_with("")).unwrap();
+    assert_eq!(missing.coupon, None);
+}
+
+#[test]
+fn reads_every_status() {
+    for (text, status) in [
+        ("PENDING", Status::Pending),
+        ("SHIPPED", Status::Shipped),
+        ("DELIVERED", Status::Delivered),
+        ("CANCELLED", Status::Cancelled),
+    ] {
+        let json = order_with("").replace("\"PENDING\"", &format!("\"{text}\""));
+        assert_eq!(parse_order(&json).unwrap().status, status, "for {text}");
+    }
+}
+
+#[test]
+fn rejects_a_status_it_does_not_know() {
+    let json = order_with("").replace("\"PENDING\"", "\"LOST\"");
+    let error = parse_order(&json).expect_err("LOST is not a status");
+    assert!(
+        error.to_string().contains("unknown variant `LOST`"),
+        "unexpected error: {error}"
+    );
+}
+
+#[test]
+fn accepts_the_old_order_id_key() {
+    let json = order_with("").replace("\"orderId\"", "\"order_id\"");
+    let order = parse_order(&json).expect("pre-2020 orders use `order_id`");
+    assert_eq!(order.id, "B-7");
+}
+
+#[test]
+fn still_requires_the_email() {
+    let json = order_with("").replace(r#", "e-mail": "bo@example.com""#, "");
+    let error = parse_order(&json).expect_err("an order without an email is broken");
+    assert!(
+        error.to_string().contains("missing field `e-mail`"),
+        "unexpected error: {error}"
+    );
+}
+
+#[test]
+fn total_multiplies_by_quantity() {
+    let order = parse_order(FULL).unwrap();
+    assert_eq!(order.total_cents(), 2 * 950 + 2000);
+    assert_eq!(parse_order(&order_with("")).unwrap().total_cents(), 0);
+}
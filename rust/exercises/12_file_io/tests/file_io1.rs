@@ -0,0 +1,133 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A directory tree in the temp directory, deleted again when dropped.
+struct Fixture {
+    root: PathBuf,
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Builds this tree (sizes in bytes):
+///
+/// ```text
+/// a.txt        100
+/// big.bin     5000
+/// docs/
+///   guide.md   300
+///   notes/
+///     n.txt     50
+/// empty/
+/// src/
+///   bin/
+///     tool.rs  500
+///   lib.rs    2000
+///   main.rs   1000
+/// tie.txt      100
+/// ```
+fn fixture(test: &str) -> Fixture {
+    let root = env::temp_dir().join(format!("file_io1-{}-{test}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let files = [
+        ("a.txt", 100),
+        ("big.bin", 5000),
+        ("docs/guide.md", 300),
+        ("docs/notes/n.txt", 50),
+        ("src/bin/tool.rs", 500),
+        ("src/lib.rs", 2000),
+        ("src/main.rs", 1000),
+        ("tie.txt", 100),
+    ];
+    for (name, size) in files {
+        let path = root.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, vec![b'x'; size]).unwrap();
+    }
+    fs::create_dir_all(root.join("empty")).unwrap();
+    Fixture { root }
+}
+
+fn usage(name: &str, bytes: u64) -> Usage {
+    Usage {
+        name: name.to_string(),
+        bytes,
+    }
+}
+
+#[test]
+fn a_file_is_its_length() {
+    let tree = fixture("file");
+    assert_eq!(total_size(&tree.root.join("big.bin")).unwrap(), 5000);
+}
+
+#[test]
+fn a_directory_adds_up_everything_under_it() {
+    let tree = fixture("directory");
+    assert_eq!(total_size(&tree.root.join("docs")).unwrap(), 350);
+    assert_eq!(total_size(&tree.root.join("src")).unwrap(), 3500);
+    assert_eq!(total_size(&tree.root.join("empty")).unwrap(), 0);
+    assert_eq!(total_size(&tree.root).unwrap(), 9050);
+}
+
+#[test]
+fn a_missing_path_is_an_error() {
+    let tree = fixture("missing");
+    let error = total_size(&tree.root.join("nope")).expect_err("there is no `nope`");
+    assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn summarize_sorts_largest_first_then_by_name() {
+    let tree = fixture("summarize");
+    assert_eq!(
+        summarize(&tree.root).unwrap(),
+        vec![
+            usage("big.bin", 5000),
+            usage("src", 3500),
+            usage("docs", 350),
+            usage("a.txt", 100),
+            usage("tie.txt", 100),
+            usage("empty", 0),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn symbolic_links_are_not_followed() {
+    let tree = fixture("symlinks");
+    // A link back up the tree: following it would never end
+    std::os::unix::fs::symlink(&tree.root, tree.root.join("src/loop")).unwrap();
+    std::os::unix::fs::symlink(tree.root.join("big.bin"), tree.root.join("big-link")).unwrap();
+    assert_eq!(total_size(&tree.root.join("src")).unwrap(), 3500);
+    let entries = summarize(&tree.root).unwrap();
+    assert!(
+        entries.contains(&usage("big-link", 0)),
+        "a link counts as 0 bytes, got {entries:?}"
+    );
+}
+
+#[test]
+fn human_size_picks_a_unit() {
+    assert_eq!(human_size(0), "0 B");
+    assert_eq!(human_size(1023), "1023 B");
+    assert_eq!(human_size(1024), "1.0 KiB");
+    assert_eq!(human_size(1536), "1.5 KiB");
+    assert_eq!(human_size(5000), "4.9 KiB");
+    assert_eq!(human_size(20 * 1024 * 1024), "20.0 MiB");
+    assert_eq!(human_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+}
+
+#[test]
+fn report_lists_every_entry() {
+    let tree = fixture("report");
+    assert_eq!(
+        report(Path::new(&tree.root)).unwrap(),
+        "   4.9 KiB  big.bin\n   3.4 KiB  src\n     350 B  docs\n     100 B  a.txt\n     100 B  tie.txt\n       0 B  empty\n"
+    );
+}
@@ -0,0 +1,93 @@
+// Exercise: SQLite 1 - A migrations runner
+// Related example: examples/sqlite/02_transactions.rs
+//
+// A program's schema changes over its life: a table here, a column there.
+// Migrations are those changes as numbered SQL scripts that every
+// database runs once, in order. A database remembers which ones it has
+// run in a table:
+//
+//     CREATE TABLE schema_migrations (
+//         version INTEGER PRIMARY KEY,
+//         name    TEXT NOT NULL
+//     )
+//
+// so that the same list of migrations brings a fresh database and a
+// year-old one to the same schema.
+//
+// TODO: Implement `current_version`: the highest version in
+//       schema_migrations, or 0 if nothing has run yet (or the table isn't
+//       there at all).
+// TODO: Implement `migrate`:
+//       - first check that the versions strictly increase, and return
+//         `MigrationError::OutOfOrder` without running anything if not
+//       - create schema_migrations if it doesn't exist
+//       - run every migration newer than the current version, in order.
+//         Each one runs in its own transaction, together with its row in
+//         schema_migrations: if its SQL fails, neither stays, and you
+//         return `MigrationError::Failed` (migrations before it stay run)
+//       - return the versions that ran
+//
+// Check your work with: cargo run -p tutor -- check sqlite1
+
+use rusqlite::Connection;
+use std::fmt;
+
+/// One step in the life of the schema.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Numbers the steps: each must be larger than the last.
+    pub version: u32,
+    /// A short description, stored with the version.
+    pub name: &'static str,
+    /// One or more SQL statements.
+    pub sql: &'static str,
+}
+
+/// Why `migrate` gave up.
+#[derive(Debug)]
+pub enum MigrationError {
+    /// `version` doesn't come after the one before it in the list.
+    OutOfOrder { version: u32 },
+    /// The SQL of migration `version` failed, and it was rolled back.
+    Failed {
+        version: u32,
+        source: rusqlite::Error,
+    },
+    /// Anything else the database complained about.
+    Sqlite(rusqlite::Error),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::OutOfOrder { version } => {
+                write!(f, "migration {version} is out of order")
+            }
+            MigrationError::Failed { version, source } => {
+                write!(f, "migration {version} failed: {source}")
+            }
+            MigrationError::Sqlite(source) => write!(f, "database error: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl From<rusqlite::Error> for MigrationError {
+    fn from(error: rusqlite::Error) -> Self {
+        MigrationError::Sqlite(error)
+    }
+}
+
+/// The newest migration `conn` has run, or 0 for none.
+pub fn current_version(conn: &Connection) -> rusqlite::Result<u32> {
+    todo!()
+}
+
+/// Runs the migrations `conn` hasn't seen yet and returns their versions.
+pub fn migrate(
+    conn: &mut Connection,
+    migrations: &[Migration],
+) -> Result<Vec<u32>, MigrationError> {
+    todo!()
+}
@@ -0,0 +1,147 @@
+const MIGRATIONS: [Migration; 3] = [
+    Migration {
+        version: 1,
+        name: "create users",
+        sql: "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);",
+    },
+    Migration {
+        version: 2,
+        name: "add email",
+        sql: "ALTER TABLE users ADD COLUMN email TEXT;
+              CREATE UNIQUE INDEX users_by_email ON users (email);",
+    },
+    Migration {
+        version: 5,
+        name: "create posts",
+        sql: "CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, body TEXT);",
+    },
+];
+
+fn fresh() -> Connection {
+    Connection::open_in_memory().unwrap()
+}
+
+fn table_exists(conn: &Connection, name: &str) -> bool {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [name],
+        |row| row.get::<_, i64>(0),
+    )
+    .unwrap()
+        == 1
+}
+
+fn recorded(conn: &Connection) -> Vec<(u32, String)> {
+    let mut stmt = conn
+        .prepare("SELECT version, name FROM schema_migrations ORDER BY version")
+        .unwrap();
+    stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap()
+}
+
+#[test]
+fn a_fresh_database_is_at_version_0() {
+    let conn = fresh();
+    assert_eq!(current_version(&conn).unwrap(), 0);
+}
+
+#[test]
+fn runs_every_migration_in_order() {
+    let mut conn = fresh();
+    assert_eq!(migrate(&mut conn, &MIGRATIONS).unwrap(), vec![1, 2, 5]);
+    assert_eq!(current_version(&conn).unwrap(), 5);
+    // The schema is really there
+    conn.execute(
+        "INSERT INTO users (name, email) VALUES ('ana', 'ana@example.com')",
+        [],
+    )
+    .unwrap();
+    assert!(table_exists(&conn, "posts"));
+}
+
+#[test]
+fn records_each_migration_it_runs() {
+    let mut conn = fresh();
+    migrate(&mut conn, &MIGRATIONS).unwrap();
+    assert_eq!(
+        recorded(&conn),
+        vec![
+            (1, "create users".to_string()),
+            (2, "add email".to_string()),
+            (5, "create posts".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn running_again_does_nothing() {
+    let mut conn = fresh();
+    migrate(&mut conn, &MIGRATIONS).unwrap();
+    assert_eq!(migrate(&mut conn, &MIGRATIONS).unwrap(), Vec::<u32>::new());
+    assert_eq!(recorded(&conn).len(), 3);
+}
+
+#[test]
+fn runs_only_the_new_migrations() {
+    let mut conn = fresh();
+    assert_eq!(migrate(&mut conn, &MIGRATIONS[..1]).unwrap(), vec![1]);
+    assert_eq!(current_version(&conn).unwrap(), 1);
+    // Version 1 again would fail: `users` already exists
+    assert_eq!(migrate(&mut conn, &MIGRATIONS).unwrap(), vec![2, 5]);
+    assert_eq!(current_version(&conn).unwrap(), 5);
+}
+
+#[test]
+fn an_empty_list_still_works() {
+    let mut conn = fresh();
+    assert_eq!(migrate(&mut conn, &[]).unwrap(), Vec::<u32>::new());
+    assert_eq!(current_version(&conn).unwrap(), 0);
+}
+
+#[test]
+fn a_failing_migration_is_rolled_back_alone() {
+    let mut conn = fresh();
+    let migrations = [
+        MIGRATIONS[0],
+        Migration {
+            version: 2,
+            name: "half works",
+            // The first statement is fine, the second is not
+            sql: "CREATE TABLE tags (name TEXT);
+                  INSERT INTO nowhere VALUES (1);",
+        },
+        MIGRATIONS[2],
+    ];
+    match migrate(&mut conn, &migrations) {
+        Err(MigrationError::Failed { version: 2, .. }) => {}
+        other => panic!("expected migration 2 to fail, got {other:?}"),
+    }
+    // Migration 1 stays, all of 2 is undone, and 5 never ran
+    assert_eq!(current_version(&conn).unwrap(), 1);
+    assert!(table_exists(&conn, "users"));
+    assert!(!table_exists(&conn, "tags"));
+    assert!(!table_exists(&conn, "posts"));
+    assert_eq!(recorded(&conn), vec![(1, "create users".to_string())]);
+
+    // Once the broken migration is fixed, the rest run
+    assert_eq!(migrate(&mut conn, &MIGRATIONS).unwrap(), vec![2, 5]);
+}
+
+#[test]
+fn out_of_order_versions_run_nothing() {
+    let mut conn = fresh();
+    let shuffled = [MIGRATIONS[0], MIGRATIONS[2], MIGRATIONS[1]];
+    match migrate(&mut conn, &shuffled) {
+        Err(MigrationError::OutOfOrder { version: 2 }) => {}
+        other => panic!("expected version 2 to be out of order, got {other:?}"),
+    }
+    let repeated = [MIGRATIONS[0], MIGRATIONS[0]];
+    match migrate(&mut conn, &repeated) {
+        Err(MigrationError::OutOfOrder { version: 1 }) => {}
+        other => panic!("expected the second version 1 to be out of order, got {other:?}"),
+    }
+    assert_eq!(current_version(&conn).unwrap(), 0);
+    assert!(!table_exists(&conn, "users"));
+}
@@ -0,0 +1,81 @@
+// Exercise: CLI 1 - A word counter
+// Related example: examples/cli/02_clap_derive.rs
+//
+// This exercise is a whole program: a small `wc`. Its tests don't call
+// your functions, they run the binary with arguments and input and look at
+// what it prints and how it exits, the way a user or a script would.
+//
+//     cli1 [-l|--lines] [-w|--words] [-c|--chars] [FILE]...
+//
+// The flags pick which counts to show; with none of them, all three are
+// shown. Each FILE gets one line from `row`, and when there is more than
+// one file a last line adds them up under the name "total". Without any
+// FILE, cli1 counts its standard input and prints one row without a name.
+//
+// TODO: Declare the arguments in `Cli` with clap's derive API. clap then
+//       rejects unknown flags by itself, with exit code 2.
+// TODO: Implement `main`:
+//       - a file that can't be read prints `cli1: <FILE>: <error>` to
+//         stderr; the other files are still counted
+//       - exit with ExitCode::FAILURE if any file failed, or SUCCESS
+//
+// Check your work with: cargo run -p tutor -- check cli1
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+/// Count lines, words, and characters.
+#[derive(Debug, Parser)]
+#[command(name = "cli1")]
+struct Cli {
+    // TODO: the three flags, and the files
+}
+
+/// The counts for one input.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Counts {
+    lines: usize,
+    words: usize,
+    chars: usize,
+}
+
+impl Counts {
+    fn add(&mut self, other: Counts) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.chars += other.chars;
+    }
+}
+
+fn count(text: &str) -> Counts {
+    Counts {
+        lines: text.lines().count(),
+        words: text.split_whitespace().count(),
+        chars: text.chars().count(),
+    }
+}
+
+/// One line of output: each count `show` asks for (lines, words, chars),
+/// right-aligned, then the name if there is one.
+fn row(counts: Counts, show: [bool; 3], name: Option<&str>) -> String {
+    let values = [counts.lines, counts.words, counts.chars];
+    let mut line: String = values
+        .iter()
+        .zip(show)
+        .filter(|(_, shown)| *shown)
+        .map(|(value, _)| format!("{:>8}", value))
+        .collect();
+    if let Some(name) = name {
+        line.push(' ');
+        line.push_str(name);
+    }
+    line
+}
+
+fn main() -> ExitCode {
+    todo!()
+}
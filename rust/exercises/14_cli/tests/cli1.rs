@@ -0,0 +1,152 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+/// A directory of text files in the temp directory, deleted again when
+/// dropped.
+struct Fixture {
+    root: PathBuf,
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Builds a directory with:
+///
+/// ```text
+/// poem.txt   2 lines, 6 words, 31 chars
+/// one.txt    1 line,  1 word,   4 chars
+/// café.txt   1 line,  2 words,  9 chars (11 bytes)
+/// empty.txt  nothing
+/// ```
+fn fixture(test: &str) -> Fixture {
+    let root = env::temp_dir().join(format!("cli1-{}-{test}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    let files = [
+        ("poem.txt", "roses are red\nviolets are blue\n"),
+        ("one.txt", "one\n"),
+        ("café.txt", "café olé\n"),
+        ("empty.txt", ""),
+    ];
+    for (name, text) in files {
+        fs::write(root.join(name), text).unwrap();
+    }
+    Fixture { root }
+}
+
+/// cli1, run in the fixture's directory so that file names stay short.
+fn cli1(fixture: &Fixture) -> Command {
+    let mut command = cargo_bin_cmd!("cli1");
+    command.current_dir(&fixture.root);
+    command
+}
+
+#[test]
+fn one_file_shows_all_counts_and_its_name() {
+    let fixture = fixture("one_file");
+    cli1(&fixture)
+        .arg("poem.txt")
+        .assert()
+        .success()
+        .stdout("       2       6      31 poem.txt\n");
+}
+
+#[test]
+fn flags_pick_the_columns() {
+    let fixture = fixture("flags");
+    cli1(&fixture)
+        .args(["-l", "poem.txt"])
+        .assert()
+        .success()
+        .stdout("       2 poem.txt\n");
+    cli1(&fixture)
+        .args(["--words", "--chars", "poem.txt"])
+        .assert()
+        .success()
+        .stdout("       6      31 poem.txt\n");
+    cli1(&fixture)
+        .args(["-lw", "poem.txt"])
+        .assert()
+        .success()
+        .stdout("       2       6 poem.txt\n");
+}
+
+#[test]
+fn chars_are_not_bytes() {
+    let fixture = fixture("chars");
+    cli1(&fixture)
+        .args(["-c", "café.txt"])
+        .assert()
+        .success()
+        .stdout("       9 café.txt\n");
+}
+
+#[test]
+fn several_files_add_a_total() {
+    let fixture = fixture("total");
+    cli1(&fixture)
+        .args(["poem.txt", "one.txt", "empty.txt"])
+        .assert()
+        .success()
+        .stdout(concat!(
+            "       2       6      31 poem.txt\n",
+            "       1       1       4 one.txt\n",
+            "       0       0       0 empty.txt\n",
+            "       3       7      35 total\n",
+        ));
+}
+
+#[test]
+fn without_files_it_reads_stdin() {
+    let fixture = fixture("stdin");
+    cli1(&fixture)
+        .write_stdin("a b c\nd\n")
+        .assert()
+        .success()
+        .stdout("       2       4       8\n");
+}
+
+#[test]
+fn a_missing_file_is_reported_and_the_rest_still_counted() {
+    let fixture = fixture("missing");
+    cli1(&fixture)
+        .args(["-l", "nope.txt", "one.txt"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::starts_with("cli1: nope.txt: "))
+        .stdout("       1 one.txt\n       1 total\n");
+}
+
+#[test]
+fn an_unknown_flag_is_a_usage_error() {
+    let fixture = fixture("unknown");
+    cli1(&fixture)
+        .args(["--bytes", "one.txt"])
+        .assert()
+        .code(2)
+        .stdout("")
+        .stderr(predicate::str::contains("--bytes"));
+}
+
+#[test]
+fn help_describes_the_flags() {
+    let fixture = fixture("help");
+    cli1(&fixture)
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("--lines")
+                .and(predicate::str::contains("--words"))
+                .and(predicate::str::contains("--chars")),
+        );
+}
@@ -0,0 +1,54 @@
+// Exercise: Regex 1 - Parsing an access log
+// Related example: examples/regex/02_capture_groups.rs
+//
+// Web servers like Apache and nginx write one line per request in the
+// "Common Log Format":
+//
+//     127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /index.html HTTP/1.0" 200 2326
+//
+// That is: the client's address, an identity nobody uses (always "-"),
+// the user (or "-"), the time in brackets, the request line in quotes,
+// the status code, and the size of the response in bytes ("-" when there
+// was no body).
+//
+// TODO: Implement `parse_line` with a regex that has a named group for
+//       every field of `Request`. A line that doesn't match the format
+//       exactly gives None.
+// TODO: Implement `parse_log`: parse every line of `text`, skipping blank
+//       ones, and return the requests together with the numbers (counting
+//       from 1) of the lines that couldn't be parsed.
+//
+// Check your work with: cargo run -p tutor -- check regex1
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// One line of the log, taken apart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub client: String,
+    /// None when the log says "-".
+    pub user: Option<String>,
+    /// As written in the brackets, e.g. "10/Oct/2000:13:55:36 -0700".
+    pub time: String,
+    pub method: String,
+    pub path: String,
+    pub protocol: String,
+    pub status: u16,
+    /// None when the log says "-".
+    pub bytes: Option<u64>,
+}
+
+/// Compiled once, the first time it's used.
+static LINE: LazyLock<Regex> = LazyLock::new(|| {
+    // TODO: the pattern
+    Regex::new(r"").unwrap()
+});
+
+pub fn parse_line(line: &str) -> Option<Request> {
+    todo!()
+}
+
+pub fn parse_log(text: &str) -> (Vec<Request>, Vec<usize>) {
+    todo!()
+}
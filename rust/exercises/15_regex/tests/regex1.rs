@@ -0,0 +1,114 @@
+const SAMPLE: &str = r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326
+192.168.1.20 - - [10/Oct/2000:13:56:01 -0700] "POST /login HTTP/1.1" 302 -
+10.0.0.7 - - [10/Oct/2000:13:57:12 -0700] "GET /missing?page=2 HTTP/1.1" 404 512
+
+this line is not a log line
+::1 - admin [11/Oct/2000:00:00:00 +0000] "DELETE /posts/17 HTTP/2.0" 204 0
+"#;
+
+fn request(client: &str, method: &str, path: &str, status: u16, bytes: Option<u64>) -> Request {
+    Request {
+        client: client.to_string(),
+        user: None,
+        time: "10/Oct/2000:13:56:01 -0700".to_string(),
+        method: method.to_string(),
+        path: path.to_string(),
+        protocol: "HTTP/1.1".to_string(),
+        status,
+        bytes,
+    }
+}
+
+#[test]
+fn parses_every_field() {
+    let line =
+        r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326"#;
+    assert_eq!(
+        parse_line(line),
+        Some(Request {
+            client: "127.0.0.1".to_string(),
+            user: Some("frank".to_string()),
+            time: "10/Oct/2000:13:55:36 -0700".to_string(),
+            method: "GET".to_string(),
+            path: "/apache_pb.gif".to_string(),
+            protocol: "HTTP/1.0".to_string(),
+            status: 200,
+            bytes: Some(2326),
+        })
+    );
+}
+
+#[test]
+fn dashes_are_missing_values() {
+    let line = r#"192.168.1.20 - - [10/Oct/2000:13:56:01 -0700] "POST /login HTTP/1.1" 302 -"#;
+    assert_eq!(
+        parse_line(line),
+        Some(request("192.168.1.20", "POST", "/login", 302, None))
+    );
+}
+
+#[test]
+fn paths_keep_their_query_strings() {
+    let line = r#"10.0.0.7 - - [10/Oct/2000:13:56:01 -0700] "GET /missing?page=2&sort=new HTTP/1.1" 404 512"#;
+    let parsed = parse_line(line).unwrap();
+    assert_eq!(parsed.path, "/missing?page=2&sort=new");
+    assert_eq!(parsed.status, 404);
+}
+
+#[test]
+fn ipv6_clients_work_too() {
+    let line = r#"::1 - admin [11/Oct/2000:00:00:00 +0000] "DELETE /posts/17 HTTP/2.0" 204 0"#;
+    let parsed = parse_line(line).unwrap();
+    assert_eq!(parsed.client, "::1");
+    assert_eq!(parsed.user.as_deref(), Some("admin"));
+    assert_eq!(parsed.bytes, Some(0));
+}
+
+#[test]
+fn malformed_lines_are_none() {
+    let lines = [
+        "this line is not a log line",
+        // no brackets around the time
+        r#"127.0.0.1 - - 10/Oct/2000:13:55:36 -0700 "GET / HTTP/1.0" 200 1"#,
+        // a status that isn't three digits
+        r#"127.0.0.1 - - [10/Oct/2000:13:55:36 -0700] "GET / HTTP/1.0" OK 1"#,
+        // the request line isn't quoted
+        r#"127.0.0.1 - - [10/Oct/2000:13:55:36 -0700] GET / HTTP/1.0 200 1"#,
+        // something left over at the end
+        r#"127.0.0.1 - - [10/Oct/2000:13:55:36 -0700] "GET / HTTP/1.0" 200 1 extra"#,
+    ];
+    for line in lines {
+        assert_eq!(parse_line(line), None, "{line}");
+    }
+}
+
+#[test]
+fn parse_log_collects_the_requests() {
+    let (requests, _) = parse_log(SAMPLE);
+    let summary: Vec<(&str, u16)> = requests
+        .iter()
+        .map(|request| (request.path.as_str(), request.status))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("/apache_pb.gif", 200),
+            ("/login", 302),
+            ("/missing?page=2", 404),
+            ("/posts/17", 204),
+        ]
+    );
+}
+
+#[test]
+fn parse_log_reports_bad_lines_by_number() {
+    let (_, bad) = parse_log(SAMPLE);
+    // Line 4 is blank, which doesn't count as bad
+    assert_eq!(bad, [5]);
+}
+
+#[test]
+fn an_empty_log_is_empty() {
+    assert_eq!(parse_log(""), (vec![], vec![]));
+    assert_eq!(parse_log("\n\n"), (vec![], vec![]));
+}
@@ -0,0 +1,134 @@
+const HOLIDAYS: &str = "
+# Germany, late 2024
+2024-10-03 German Unity Day
+2024-12-25 Christmas Day
+2024-12-26 Second Day of Christmas
+
+# New Year
+2025-01-01 New Year's Day
+";
+
+fn date(text: &str) -> NaiveDate {
+    text.parse().unwrap()
+}
+
+fn holidays() -> Vec<NaiveDate> {
+    parse_holidays(HOLIDAYS).unwrap()
+}
+
+#[test]
+fn parses_the_holiday_file() {
+    assert_eq!(
+        holidays(),
+        [
+            date("2024-10-03"),
+            date("2024-12-25"),
+            date("2024-12-26"),
+            date("2025-01-01"),
+        ]
+    );
+}
+
+#[test]
+fn a_bad_date_is_an_error() {
+    assert!(parse_holidays("2024-10-03 fine\n2024-13-01 no such month\n").is_err());
+    assert!(parse_holidays("Christmas 2024-12-25\n").is_err());
+}
+
+#[test]
+fn weekends_and_holidays_are_not_business_days() {
+    let holidays = holidays();
+    // Wednesday 2024-10-02 to Tuesday 2024-10-08
+    let week: Vec<bool> = date("2024-10-02")
+        .iter_days()
+        .take(7)
+        .map(|day| is_business_day(day, &holidays))
+        .collect();
+    assert_eq!(week, [true, false, true, false, false, true, true]);
+}
+
+#[test]
+fn counts_business_days_in_a_plain_week() {
+    // Monday to the next Monday: Tuesday to Friday, then Monday
+    assert_eq!(
+        business_days_between(date("2024-10-14"), date("2024-10-21"), &[]),
+        5
+    );
+    assert_eq!(
+        business_days_between(date("2024-10-14"), date("2024-10-14"), &[]),
+        0
+    );
+}
+
+#[test]
+fn counting_skips_holidays() {
+    let holidays = holidays();
+    // Monday 12-23 to Friday 12-27: 24, 27 (25 and 26 are holidays)
+    assert_eq!(
+        business_days_between(date("2024-12-23"), date("2024-12-27"), &holidays),
+        2
+    );
+    // Across the new year
+    assert_eq!(
+        business_days_between(date("2024-12-20"), date("2025-01-03"), &holidays),
+        7
+    );
+}
+
+#[test]
+fn backwards_is_negative() {
+    assert_eq!(
+        business_days_between(date("2024-10-21"), date("2024-10-14"), &[]),
+        -5
+    );
+}
+
+#[test]
+fn from_a_weekend() {
+    // Saturday to Monday is one business day, Saturday to Sunday none
+    assert_eq!(
+        business_days_between(date("2024-10-19"), date("2024-10-21"), &[]),
+        1
+    );
+    assert_eq!(
+        business_days_between(date("2024-10-19"), date("2024-10-20"), &[]),
+        0
+    );
+}
+
+#[test]
+fn adding_business_days() {
+    let holidays = holidays();
+    assert_eq!(
+        add_business_days(date("2024-10-14"), 5, &holidays),
+        date("2024-10-21")
+    );
+    // Friday + 1 is Monday
+    assert_eq!(
+        add_business_days(date("2024-10-18"), 1, &holidays),
+        date("2024-10-21")
+    );
+    // Over Christmas and New Year
+    assert_eq!(
+        add_business_days(date("2024-12-23"), 5, &holidays),
+        date("2025-01-02")
+    );
+    assert_eq!(
+        add_business_days(date("2024-10-19"), 0, &holidays),
+        date("2024-10-19")
+    );
+}
+
+#[test]
+fn adding_and_counting_agree() {
+    let holidays = holidays();
+    let start = date("2024-09-27");
+    for days in 0..70 {
+        let end = add_business_days(start, days, &holidays);
+        assert_eq!(
+            business_days_between(start, end, &holidays),
+            days as i64,
+            "{start} + {days}"
+        );
+    }
+}
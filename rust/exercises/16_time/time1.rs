@@ -0,0 +1,40 @@
+// Exercise: Time 1 - Business days
+// Related example: examples/time/04_date_arithmetic.rs
+//
+// "Delivery in 5 business days" skips weekends and public holidays. The
+// holidays change from country to country and year to year, so they come
+// from a file, one per line:
+//
+//     # Germany, 2024
+//     2024-10-03 German Unity Day
+//     2024-12-25 Christmas Day
+//
+// TODO: Implement `parse_holidays`: every line that isn't blank or a
+//       `#` comment starts with a date; the rest of the line (its name) is
+//       ignored. Return the dates, or the first parse error.
+// TODO: Implement `is_business_day`: Monday to Friday, and not a holiday.
+// TODO: Implement `business_days_between`: how many business days come
+//       after `start`, up to and including `end`. If `end` is before
+//       `start`, the same count, negated.
+// TODO: Implement `add_business_days`: the date `days` business days
+//       after `date`. Adding 0 gives `date` back, even on a weekend.
+//
+// Check your work with: cargo run -p tutor -- check time1
+
+use chrono::{Datelike, NaiveDate, ParseError, Weekday};
+
+pub fn parse_holidays(text: &str) -> Result<Vec<NaiveDate>, ParseError> {
+    todo!()
+}
+
+pub fn is_business_day(date: NaiveDate, holidays: &[NaiveDate]) -> bool {
+    todo!()
+}
+
+pub fn business_days_between(start: NaiveDate, end: NaiveDate, holidays: &[NaiveDate]) -> i64 {
+    todo!()
+}
+
+pub fn add_business_days(date: NaiveDate, days: u32, holidays: &[NaiveDate]) -> NaiveDate {
+    todo!()
+}
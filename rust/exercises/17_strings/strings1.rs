@@ -0,0 +1,30 @@
+// Exercise: Strings 1 - Truncating safely
+// Related example: examples/strings/03_slicing_pitfalls.rs
+//
+// Database columns, SMS messages, and log lines often have a limit in
+// bytes. The obvious `&text[..max]` works until a multi-byte character
+// sits across the limit, and then it panics. These functions cut text
+// down to size without ever splitting a character.
+//
+// TODO: Implement `truncate_bytes`: the longest prefix of `text` that is
+//       at most `max_bytes` long and ends on a char boundary.
+// TODO: Implement `truncate_chars`: the first `max_chars` chars of `text`
+//       (all of it, if it is shorter), as a slice.
+// TODO: Implement `ellipsize`: `text` unchanged if it has at most
+//       `max_chars` chars; otherwise its first `max_chars - 1` chars
+//       followed by '…', so the result has exactly `max_chars` chars. With
+//       `max_chars` 0 there is no room for anything, not even the '…'.
+//
+// Check your work with: cargo run -p tutor -- check strings1
+
+pub fn truncate_bytes(text: &str, max_bytes: usize) -> &str {
+    todo!()
+}
+
+pub fn truncate_chars(text: &str, max_chars: usize) -> &str {
+    todo!()
+}
+
+pub fn ellipsize(text: &str, max_chars: usize) -> String {
+    todo!()
+}
@@ -0,0 +1,68 @@
+#[test]
+fn ascii_truncates_at_the_limit() {
+    assert_eq!(truncate_bytes("hello world", 5), "hello");
+    assert_eq!(truncate_bytes("hello", 10), "hello");
+    assert_eq!(truncate_bytes("hello", 0), "");
+}
+
+#[test]
+fn never_splits_a_char() {
+    // 'é' is bytes 1 and 2
+    assert_eq!(truncate_bytes("héllo", 2), "h");
+    assert_eq!(truncate_bytes("héllo", 3), "hé");
+    // '🦀' is 4 bytes
+    assert_eq!(truncate_bytes("🦀🦀", 7), "🦀");
+    assert_eq!(truncate_bytes("🦀🦀", 3), "");
+}
+
+#[test]
+fn every_byte_limit_is_safe() {
+    let text = "naïve café — 日本語 🦀";
+    for max in 0..=text.len() + 2 {
+        let cut = truncate_bytes(text, max);
+        assert!(cut.len() <= max, "{max}");
+        assert!(text.starts_with(cut), "{max}");
+        // The longest possible: one more char wouldn't fit
+        if cut.len() < text.len() {
+            let next = text[cut.len()..].chars().next().unwrap();
+            assert!(cut.len() + next.len_utf8() > max, "{max}");
+        }
+    }
+}
+
+#[test]
+fn truncate_chars_counts_chars() {
+    assert_eq!(truncate_chars("日本語のテキスト", 3), "日本語");
+    assert_eq!(truncate_chars("naïve", 3), "naï");
+    assert_eq!(truncate_chars("short", 10), "short");
+    assert_eq!(truncate_chars("anything", 0), "");
+}
+
+#[test]
+fn truncate_chars_borrows_from_the_input() {
+    let text = String::from("borrowed");
+    let cut = truncate_chars(&text, 3);
+    assert!(std::ptr::eq(cut.as_ptr(), text.as_ptr()));
+}
+
+#[test]
+fn short_text_is_not_ellipsized() {
+    assert_eq!(ellipsize("fits", 4), "fits");
+    assert_eq!(ellipsize("", 3), "");
+}
+
+#[test]
+fn long_text_ends_in_an_ellipsis() {
+    assert_eq!(ellipsize("hello world", 6), "hello…");
+    assert_eq!(ellipsize("Ünïcödé", 4), "Ünï…");
+    assert_eq!(ellipsize("ab", 1), "…");
+    assert_eq!(ellipsize("ab", 0), "");
+}
+
+#[test]
+fn ellipsized_text_has_exactly_max_chars() {
+    let text = "🦀 Rust 🦀 is fun";
+    for max in 0..text.chars().count() {
+        assert_eq!(ellipsize(text, max).chars().count(), max, "{max}");
+    }
+}
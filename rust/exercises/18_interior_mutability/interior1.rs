@@ -0,0 +1,59 @@
+// Exercise: Interior Mutability 1 - Lost updates
+// Related example: examples/interior_mutability/04_atomics.rs
+//
+// A web server shares one `Stats` between all of its worker threads, and
+// each of them calls `record` after every request. The code below
+// compiles, and it is right as long as there is only one thread: every
+// test that uses one thread passes. With many threads at once, some of
+// the updates disappear.
+//
+// TODO: Find out why, and fix `record` so that no update is ever lost,
+//       however many threads call it at the same time. It must stay
+//       lock-free: no Mutex.
+//
+// Check your work with: cargo run -p tutor -- check interior1
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// What `Stats` has counted so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Snapshot {
+    pub requests: u64,
+    pub errors: u64,
+    pub slowest_ms: u64,
+}
+
+/// Counters that any number of threads can update through a shared &Stats.
+#[derive(Debug, Default)]
+pub struct Stats {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    slowest_ms: AtomicU64,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts one request, whether it failed, and how long it took.
+    pub fn record(&self, ok: bool, millis: u64) {
+        let requests = self.requests.load(Ordering::Relaxed);
+        self.requests.store(requests + 1, Ordering::Relaxed);
+        if !ok {
+            let errors = self.errors.load(Ordering::Relaxed);
+            self.errors.store(errors + 1, Ordering::Relaxed);
+        }
+        if millis > self.slowest_ms.load(Ordering::Relaxed) {
+            self.slowest_ms.store(millis, Ordering::Relaxed);
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            slowest_ms: self.slowest_ms.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// Exercise: Interior Mutability 2 - Permits without a lock
+// Related example: examples/interior_mutability/04_atomics.rs
+//
+// `Permits` limits how much of something can be in use at once: say, at
+// most 10 connections to a database, shared by all the threads of a
+// program. A thread that wants `n` of them calls `try_acquire(n)`, and
+// gets them only if that many are still available. When it is done, it
+// gives them back with `release(n)`.
+//
+// TODO: Implement `try_acquire` and `release` with atomic operations only.
+//       However many threads call them at once, the permits in use must
+//       never add up to more than the capacity, and no permit may get
+//       lost or created. Think about what can happen between checking
+//       that enough permits are available and taking them.
+//
+// The stress tests run many threads at once to provoke races, but a race
+// only shows when the timing is unlucky, and on a machine with few cores
+// that is rare. Passing them is not a proof: convince yourself, too, that
+// no interleaving of two threads can break the rules.
+//
+// Check your work with: cargo run -p tutor -- check interior2
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug)]
+pub struct Permits {
+    available: AtomicUsize,
+}
+
+impl Permits {
+    pub fn new(capacity: usize) -> Self {
+        Permits {
+            available: AtomicUsize::new(capacity),
+        }
+    }
+
+    /// Takes `n` permits if at least that many are available.
+    pub fn try_acquire(&self, n: usize) -> bool {
+        todo!()
+    }
+
+    /// Gives back `n` permits taken earlier.
+    pub fn release(&self, n: usize) {
+        todo!()
+    }
+
+    pub fn available(&self) -> usize {
+        self.available.load(Ordering::Relaxed)
+    }
+}
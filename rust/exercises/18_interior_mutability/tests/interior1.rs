@@ -0,0 +1,78 @@
+use std::sync::Barrier;
+use std::thread;
+
+const THREADS: u64 = 8;
+const PER_THREAD: u64 = 100_000;
+/// Lost updates need unlucky timing, so the stress tests try many times.
+const ATTEMPTS: usize = 40;
+
+/// Calls `record` from THREADS threads at once. Thread `t` records
+/// requests that take `t`, `t + THREADS`, `t + 2 * THREADS`... ms, and
+/// every third one fails.
+fn hammer(stats: &Stats) {
+    let start = Barrier::new(THREADS as usize);
+    thread::scope(|s| {
+        for t in 0..THREADS {
+            let start = &start;
+            s.spawn(move || {
+                // Start together, so that the threads really overlap
+                start.wait();
+                for i in 0..PER_THREAD {
+                    stats.record(i % 3 != 0, i * THREADS + t);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn starts_at_zero() {
+    assert_eq!(Stats::new().snapshot(), Snapshot::default());
+}
+
+#[test]
+fn counts_on_one_thread() {
+    let stats = Stats::new();
+    stats.record(true, 12);
+    stats.record(false, 80);
+    stats.record(true, 35);
+    assert_eq!(
+        stats.snapshot(),
+        Snapshot {
+            requests: 3,
+            errors: 1,
+            slowest_ms: 80,
+        }
+    );
+}
+
+#[test]
+fn no_request_is_lost_under_load() {
+    for _ in 0..ATTEMPTS {
+        let stats = Stats::new();
+        hammer(&stats);
+        assert_eq!(stats.snapshot().requests, THREADS * PER_THREAD);
+    }
+}
+
+#[test]
+fn no_error_is_lost_under_load() {
+    for _ in 0..ATTEMPTS {
+        let stats = Stats::new();
+        hammer(&stats);
+        // i % 3 == 0 for a third of the values of i, rounded up
+        assert_eq!(stats.snapshot().errors, THREADS * PER_THREAD.div_ceil(3));
+    }
+}
+
+#[test]
+fn the_slowest_request_wins_under_load() {
+    for _ in 0..ATTEMPTS {
+        let stats = Stats::new();
+        hammer(&stats);
+        assert_eq!(
+            stats.snapshot().slowest_ms,
+            (PER_THREAD - 1) * THREADS + THREADS - 1
+        );
+    }
+}
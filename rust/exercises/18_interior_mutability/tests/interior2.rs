@@ -0,0 +1,91 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Barrier;
+use std::thread;
+
+const THREADS: usize = 8;
+const ROUNDS: usize = 100_000;
+/// Races need unlucky timing, so the stress tests try many times.
+const ATTEMPTS: usize = 20;
+
+#[test]
+fn acquires_while_permits_last() {
+    let permits = Permits::new(3);
+    assert!(permits.try_acquire(2));
+    assert!(permits.try_acquire(1));
+    assert!(!permits.try_acquire(1));
+    assert_eq!(permits.available(), 0);
+}
+
+#[test]
+fn a_request_that_is_too_big_takes_nothing() {
+    let permits = Permits::new(3);
+    assert!(!permits.try_acquire(4));
+    assert_eq!(permits.available(), 3);
+    assert!(permits.try_acquire(3));
+}
+
+#[test]
+fn released_permits_can_be_acquired_again() {
+    let permits = Permits::new(2);
+    assert!(permits.try_acquire(2));
+    permits.release(1);
+    assert_eq!(permits.available(), 1);
+    assert!(permits.try_acquire(1));
+    assert!(!permits.try_acquire(1));
+}
+
+#[test]
+fn never_more_in_use_than_the_capacity_under_load() {
+    let capacity = 5;
+    let permits = Permits::new(capacity);
+    let in_use = AtomicUsize::new(0);
+    let most = AtomicUsize::new(0);
+    let start = Barrier::new(THREADS);
+    for _ in 0..ATTEMPTS {
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let (permits, in_use, most, start) = (&permits, &in_use, &most, &start);
+                s.spawn(move || {
+                    start.wait();
+                    for round in 0..ROUNDS {
+                        let n = 1 + (t + round) % 3;
+                        if permits.try_acquire(n) {
+                            let now = in_use.fetch_add(n, Ordering::SeqCst) + n;
+                            most.fetch_max(now, Ordering::SeqCst);
+                            in_use.fetch_sub(n, Ordering::SeqCst);
+                            permits.release(n);
+                        }
+                    }
+                });
+            }
+        });
+    }
+    assert!(
+        most.load(Ordering::SeqCst) <= capacity,
+        "{} permits were in use at once",
+        most.load(Ordering::SeqCst)
+    );
+}
+
+#[test]
+fn no_permit_is_lost_or_created_under_load() {
+    for _ in 0..ATTEMPTS {
+        let permits = Permits::new(4);
+        let start = Barrier::new(THREADS);
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let (permits, start) = (&permits, &start);
+                s.spawn(move || {
+                    start.wait();
+                    for round in 0..ROUNDS {
+                        let n = 1 + (t + round) % 2;
+                        if permits.try_acquire(n) {
+                            permits.release(n);
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(permits.available(), 4);
+    }
+}
@@ -0,0 +1,51 @@
+fn sample() -> Matrix<2, 3> {
+    Matrix::new([[1, 2, 3], [4, 5, 6]])
+}
+
+#[test]
+fn transpose_swaps_rows_and_columns() {
+    // The annotation checks the shape: this only compiles as a 3×2 matrix
+    let turned: Matrix<3, 2> = sample().transpose();
+    assert_eq!(turned, Matrix::new([[1, 4], [2, 5], [3, 6]]));
+}
+
+#[test]
+fn transpose_moves_every_cell() {
+    let m = sample();
+    let t = m.transpose();
+    for row in 0..2 {
+        for col in 0..3 {
+            assert_eq!(t.get(col, row), m.get(row, col));
+        }
+    }
+}
+
+#[test]
+fn transposing_twice_gives_the_original_back() {
+    assert_eq!(sample().transpose().transpose(), sample());
+    let column = Matrix::new([[7], [8], [9]]);
+    assert_eq!(column.transpose(), Matrix::new([[7, 8, 9]]));
+}
+
+#[test]
+fn identity_has_ones_on_the_diagonal() {
+    assert_eq!(
+        Matrix::<3, 3>::identity(),
+        Matrix::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]])
+    );
+    assert_eq!(Matrix::<1, 1>::identity(), Matrix::new([[1]]));
+}
+
+#[test]
+fn identity_leaves_a_product_unchanged() {
+    let m = sample();
+    assert_eq!(Matrix::<2, 2>::identity() * m, m);
+    assert_eq!(m * Matrix::<3, 3>::identity(), m);
+}
+
+#[test]
+fn the_transpose_of_a_product_reverses_the_order() {
+    let a = sample();
+    let b = Matrix::new([[1, 0], [2, 1], [0, 3]]);
+    assert_eq!((a * b).transpose(), b.transpose() * a.transpose());
+}
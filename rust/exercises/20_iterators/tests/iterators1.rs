@@ -0,0 +1,61 @@
+/// The largest Fibonacci number that fits in a u64, and its position.
+const LAST: u64 = 12_200_160_415_121_876_738;
+const COUNT: usize = 94;
+
+#[test]
+fn starts_with_zero_and_one() {
+    let first: Vec<u64> = Fibonacci::new().take(10).collect();
+    assert_eq!(first, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+}
+
+#[test]
+fn every_number_is_the_sum_of_the_two_before() {
+    let numbers: Vec<u64> = Fibonacci::new().take(60).collect();
+    for three in numbers.windows(3) {
+        assert_eq!(three[2], three[0] + three[1]);
+    }
+}
+
+#[test]
+fn each_iterator_starts_from_the_beginning() {
+    let mut first = Fibonacci::new();
+    first.nth(20);
+    assert_eq!(Fibonacci::new().next(), Some(0));
+    assert_eq!(first.next(), Some(10_946));
+}
+
+#[test]
+fn works_with_adapters() {
+    // The even-valued terms below four million, summed
+    let even_sum: u64 = Fibonacci::new()
+        .take_while(|&n| n < 4_000_000)
+        .filter(|n| n % 2 == 0)
+        .sum();
+    assert_eq!(even_sum, 4_613_732);
+
+    let first_big = Fibonacci::new().position(|n| n >= 1_000);
+    assert_eq!(first_big, Some(17));
+
+    let labelled: Vec<(usize, u64)> = Fibonacci::new()
+        .enumerate()
+        .skip(5)
+        .step_by(5)
+        .take(3)
+        .collect();
+    assert_eq!(labelled, [(5, 5), (10, 55), (15, 610)]);
+}
+
+#[test]
+fn ends_with_the_last_number_that_fits() {
+    assert_eq!(Fibonacci::new().count(), COUNT);
+    assert_eq!(Fibonacci::new().last(), Some(LAST));
+    assert_eq!(Fibonacci::new().max(), Some(LAST));
+}
+
+#[test]
+fn stays_finished_once_it_has_ended() {
+    let mut numbers = Fibonacci::new();
+    numbers.by_ref().for_each(drop);
+    assert_eq!(numbers.next(), None);
+    assert_eq!(numbers.next(), None);
+}
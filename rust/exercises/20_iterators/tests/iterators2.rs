@@ -0,0 +1,109 @@
+fn collect<T>(items: &[T], size: usize, step: usize) -> Vec<&[T]> {
+    ChunkedWindows::new(items, size, step).collect()
+}
+
+const DIGITS: [u8; 7] = [0, 1, 2, 3, 4, 5, 6];
+
+#[test]
+fn step_one_matches_windows() {
+    for size in 1..=8 {
+        let expected: Vec<&[u8]> = DIGITS.windows(size).collect();
+        assert_eq!(collect(&DIGITS, size, 1), expected, "size {}", size);
+    }
+}
+
+#[test]
+fn step_equal_to_size_gives_only_full_chunks() {
+    assert_eq!(collect(&DIGITS, 3, 3), [&[0, 1, 2][..], &[3, 4, 5]]);
+    assert_eq!(collect(&DIGITS, 7, 7), [&DIGITS[..]]);
+}
+
+#[test]
+fn a_bigger_step_skips_items() {
+    assert_eq!(collect(&DIGITS, 2, 3), [&[0, 1][..], &[3, 4]]);
+    assert_eq!(collect(&DIGITS, 1, 4), [&[0][..], &[4]]);
+}
+
+#[test]
+fn too_few_items_give_no_windows() {
+    let empty: [u8; 0] = [];
+    assert!(collect(&empty, 1, 1).is_empty());
+    assert!(collect(&DIGITS, 8, 1).is_empty());
+    assert_eq!(ChunkedWindows::new(&DIGITS, 8, 2).len(), 0);
+}
+
+#[test]
+#[should_panic]
+fn a_size_of_zero_panics() {
+    ChunkedWindows::new(&DIGITS, 0, 1);
+}
+
+#[test]
+#[should_panic]
+fn a_step_of_zero_panics() {
+    ChunkedWindows::new(&DIGITS, 2, 0);
+}
+
+#[test]
+fn runs_backwards() {
+    let backwards: Vec<&[u8]> = ChunkedWindows::new(&DIGITS, 3, 2).rev().collect();
+    assert_eq!(backwards, [&[4, 5, 6][..], &[2, 3, 4], &[0, 1, 2]]);
+    // The last window is the last FULL one, not one ending at the end
+    let last = ChunkedWindows::new(&DIGITS, 2, 3).next_back();
+    assert_eq!(last, Some(&[3, 4][..]));
+}
+
+#[test]
+fn both_ends_meet_in_the_middle() {
+    let mut windows = ChunkedWindows::new(&DIGITS, 2, 2);
+    assert_eq!(windows.next(), Some(&[0, 1][..]));
+    assert_eq!(windows.next_back(), Some(&[4, 5][..]));
+    assert_eq!(windows.next_back(), Some(&[2, 3][..]));
+    assert_eq!(windows.next(), None);
+    assert_eq!(windows.next_back(), None);
+}
+
+#[test]
+fn len_is_exact_and_counts_down() {
+    let mut windows = ChunkedWindows::new(&DIGITS, 3, 1);
+    assert_eq!(windows.len(), 5);
+    assert_eq!(windows.size_hint(), (5, Some(5)));
+    windows.next();
+    windows.next_back();
+    assert_eq!(windows.len(), 3);
+    windows.by_ref().for_each(drop);
+    assert_eq!(windows.len(), 0);
+    for (size, step) in [(1, 1), (2, 3), (3, 2), (4, 4), (7, 1), (6, 5)] {
+        let windows = ChunkedWindows::new(&DIGITS, size, step);
+        let len = windows.len();
+        assert_eq!(len, windows.count(), "size {} step {}", size, step);
+    }
+}
+
+#[test]
+fn works_with_adapters() {
+    let words = ["a", "b", "c", "d", "e"].map(String::from);
+    let joined: Vec<String> = ChunkedWindows::new(&words, 2, 1)
+        .map(|pair| pair.concat())
+        .collect();
+    assert_eq!(joined, ["ab", "bc", "cd", "de"]);
+
+    let sums: Vec<u32> = ChunkedWindows::new(&DIGITS, 3, 3)
+        .map(|chunk| chunk.iter().map(|&d| u32::from(d)).sum())
+        .collect();
+    assert_eq!(sums, [3, 12]);
+
+    let numbered: Vec<(usize, &[u8])> = ChunkedWindows::new(&DIGITS, 2, 2)
+        .rev()
+        .enumerate()
+        .skip(1)
+        .collect();
+    assert_eq!(numbered, [(1, &[2, 3][..]), (2, &[0, 1])]);
+
+    let zipped = ChunkedWindows::new(&DIGITS, 1, 1).zip(ChunkedWindows::new(&DIGITS, 2, 2).rev());
+    assert_eq!(zipped.count(), 3);
+
+    let mut peek = ChunkedWindows::new(&DIGITS, 3, 2).peekable();
+    assert_eq!(peek.peek(), Some(&&[0, 1, 2][..]));
+    assert_eq!(peek.nth(2), Some(&[4, 5, 6][..]));
+}
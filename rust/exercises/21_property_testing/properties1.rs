@@ -0,0 +1,73 @@
+// Exercise: Property Testing 1 - Properties that catch a bug
+// Related example: examples/property_testing/03_invariants.rs
+//
+// `encode` compresses bytes with run-length encoding: each run of equal
+// bytes becomes two bytes, a count and the byte, so b"aaab" becomes
+// [3, b'a', 1, b'b']. `decode` turns that back into the original bytes.
+//
+// The code has a bug, and the example tests at the bottom don't catch it.
+// Don't go looking for it: write properties, and let proptest find it.
+// The tutor checks your properties twice. They must fail on the code as
+// it is, and pass on the same code with the bug fixed, so each one has to
+// be something that is true of a correct encoder.
+//
+// TODO: Write properties in the proptest! block until the tutor says they
+//       catch the bug (and the second bug it plants in the fixed code).
+//       Think about:
+//       - the round trip: what should decode(encode(data)) give back?
+//       - what a good encoding looks like: when may two pairs in a row
+//         hold the same byte?
+//       - which inputs you need: what doesn't any::<Vec<u8>>() generate?
+//       Don't change the code above the tests module.
+//
+// Check your work with: cargo run -p tutor -- check properties1
+
+pub fn encode(data: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    let mut bytes = data.iter().copied().peekable();
+    while let Some(byte) = bytes.next() {
+        let mut run: u8 = 1;
+        while bytes.next_if_eq(&byte).is_some() {
+            run += 1;
+        }
+        encoded.push(run);
+        encoded.push(byte);
+    }
+    encoded
+}
+
+/// A trailing byte without a partner is ignored.
+pub fn decode(encoded: &[u8]) -> Vec<u8> {
+    encoded
+        .chunks_exact(2)
+        .flat_map(|pair| std::iter::repeat_n(pair[1], usize::from(pair[0])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn encodes_runs() {
+        assert_eq!(encode(b"aaab"), [3, b'a', 1, b'b']);
+        assert_eq!(encode(b""), []);
+    }
+
+    #[test]
+    fn decodes_what_it_encodes() {
+        for data in [&b"hello"[..], b"zzzzzzzzzz", b"abcabc"] {
+            assert_eq!(decode(&encode(data)), data);
+        }
+    }
+
+    proptest! {
+        // TODO: add properties here, like
+        //
+        // #[test]
+        // fn name(data in any::<Vec<u8>>()) {
+        //     prop_assert!(...);
+        // }
+    }
+}
@@ -0,0 +1,82 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+/// process1 with `args`, reading `input`.
+fn process1(args: &[&str], input: &str) -> Command {
+    let mut command = cargo_bin_cmd!("process1");
+    command.args(args).write_stdin(input);
+    command
+}
+
+#[test]
+fn all_the_items_go_to_one_run() {
+    process1(&["echo"], "a b\n  c\n\nd\n")
+        .assert()
+        .success()
+        .stdout("a b c d\n");
+}
+
+#[test]
+fn the_fixed_arguments_come_first() {
+    process1(&["echo", "items:", "-n"], "x y\n")
+        .assert()
+        .success()
+        .stdout("items: -n x y\n");
+}
+
+#[test]
+fn max_splits_the_items_into_batches() {
+    process1(&["-n", "2", "echo"], "a b c d e")
+        .assert()
+        .success()
+        .stdout("a b\nc d\ne\n");
+}
+
+#[test]
+fn each_run_knows_its_batch_number() {
+    process1(
+        &["-n", "3", "sh", "-c", "echo \"$PROCESS1_BATCH: $*\"", "sh"],
+        "1 2 3 4 5 6 7",
+    )
+    .assert()
+    .success()
+    .stdout("1: 1 2 3\n2: 4 5 6\n3: 7\n");
+}
+
+#[test]
+fn no_items_means_no_runs() {
+    process1(&["echo", "never"], " \n\n")
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn a_failed_run_gives_123_after_the_rest_have_run() {
+    // Fails for the item b only
+    process1(&["-n", "1", "sh", "-c", "echo $1; test $1 != b", "sh"], "a b c")
+        .assert()
+        .code(123)
+        .stdout("a\nb\nc\n");
+}
+
+#[test]
+fn a_command_that_cannot_start_gives_127() {
+    process1(&["no-such-command-for-process1"], "a\nb\n")
+        .assert()
+        .code(127)
+        .stdout("")
+        .stderr(predicate::str::starts_with(
+            "process1: no-such-command-for-process1: ",
+        ));
+}
+
+#[test]
+fn usage_errors_give_1() {
+    process1(&[], "a").assert().code(1).stderr(predicate::str::contains("usage"));
+    process1(&["-n", "0", "echo"], "a")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("above 0"));
+}
@@ -0,0 +1,119 @@
+fn products(json: &str) -> Vec<Product> {
+    serde_json::from_str(json).expect("csv_to_json should return a JSON array of products")
+}
+
+fn product(sku: &str, name: &str, price_cents: u64, quantity: u32) -> Product {
+    Product {
+        sku: sku.to_string(),
+        name: name.to_string(),
+        price_cents,
+        quantity,
+    }
+}
+
+fn error(line: u64, column: Option<&str>, message: &str) -> RowError {
+    RowError {
+        line,
+        column: column.map(str::to_string),
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn converts_a_clean_file() {
+    let json =
+        csv_to_json("sku,name,price_cents,quantity\nMUG-01,Mug,950,40\nPEN-12,Pen,150,300\n")
+            .expect("every row is fine");
+    assert_eq!(
+        products(&json),
+        [
+            product("MUG-01", "Mug", 950, 40),
+            product("PEN-12", "Pen", 150, 300)
+        ]
+    );
+    assert!(json.contains('\n'), "the JSON should be pretty-printed");
+}
+
+#[test]
+fn a_header_alone_is_an_empty_list() {
+    assert_eq!(
+        products(&csv_to_json("sku,name,price_cents,quantity\n").unwrap()),
+        []
+    );
+}
+
+#[test]
+fn spaces_quotes_and_column_order_do_not_matter() {
+    let input = "quantity, price_cents, sku, name, colour\n\
+                 12, 2000, TEE-04,\"T-shirt, large\", blue\n";
+    assert_eq!(
+        products(&csv_to_json(input).unwrap()),
+        [product("TEE-04", "T-shirt, large", 2000, 12)]
+    );
+}
+
+#[test]
+fn a_bad_value_names_its_line_and_column() {
+    let input = "sku,name,price_cents,quantity\nMUG-01,Mug,950,40\nTEE-04,T-shirt,twenty,12\n";
+    assert_eq!(
+        csv_to_json(input),
+        Err(vec![error(
+            3,
+            Some("price_cents"),
+            "invalid digit found in string"
+        )])
+    );
+}
+
+#[test]
+fn a_short_row_says_how_many_fields_it_has() {
+    let input = "sku,name,price_cents,quantity\nMUG-01,Mug,950\n";
+    assert_eq!(
+        csv_to_json(input),
+        Err(vec![error(2, None, "expected 4 fields, found 3")])
+    );
+}
+
+#[test]
+fn every_bad_row_is_reported_in_order() {
+    let input = "sku,name,price_cents,quantity\n\
+                 MUG-01,Mug,950,40\n\
+                 TEE-04,T-shirt,2000,-1\n\
+                 PEN-12,Pen\n\
+                 CUP-02,Cup,350,8\n\
+                 BAG-07,Bag,9.99,5\n";
+    assert_eq!(
+        csv_to_json(input),
+        Err(vec![
+            error(3, Some("quantity"), "invalid digit found in string"),
+            error(4, None, "expected 4 fields, found 2"),
+            error(6, Some("price_cents"), "invalid digit found in string"),
+        ])
+    );
+}
+
+#[test]
+fn a_missing_column_is_reported_once_on_the_header() {
+    let input = "sku,title,price_cents\nMUG-01,Mug,950\nPEN-12,Pen,lots\n";
+    assert_eq!(
+        csv_to_json(input),
+        Err(vec![
+            error(1, Some("name"), "missing column"),
+            error(1, Some("quantity"), "missing column"),
+        ])
+    );
+}
+
+#[test]
+fn errors_display_their_position() {
+    let errors =
+        csv_to_json("sku,name,price_cents,quantity\nMUG-01,Mug\nX,Y,1,many\n").unwrap_err();
+    let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            "line 2: expected 4 fields, found 2",
+            "line 3, column quantity: invalid digit found in string",
+        ]
+    );
+}
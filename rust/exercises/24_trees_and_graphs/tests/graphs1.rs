@@ -0,0 +1,118 @@
+/// A graph of tasks named by letters, with an edge for each pair.
+fn graph(names: &str, edges: &[(usize, usize)]) -> Graph {
+    let mut graph = Graph::new();
+    for name in names.chars() {
+        graph.add_node(&name.to_string());
+    }
+    for &(from, to) in edges {
+        graph.add_edge(NodeId(from), NodeId(to));
+    }
+    graph
+}
+
+/// Checks that `cycle` really is one: every node follows the one before
+/// it along an edge, the last one leads back to the first, and no node is
+/// in it twice.
+fn assert_is_cycle(graph: &Graph, cycle: &[NodeId]) {
+    assert!(!cycle.is_empty(), "a cycle has at least one node");
+    for (i, &node) in cycle.iter().enumerate() {
+        let next = cycle[(i + 1) % cycle.len()];
+        assert!(
+            graph.neighbours(node).contains(&next),
+            "{cycle:?} is not a cycle: there is no edge from {} to {}",
+            graph.name(node),
+            graph.name(next)
+        );
+    }
+    let mut sorted = cycle.to_vec();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), cycle.len(), "{cycle:?} visits a node twice");
+}
+
+fn names(graph: &Graph, cycle: &[NodeId]) -> Vec<String> {
+    let mut names: Vec<String> = cycle.iter().map(|&id| graph.name(id).to_string()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn a_graph_without_edges_has_no_cycle() {
+    assert!(!graph("", &[]).has_cycle());
+    assert!(!graph("abc", &[]).has_cycle());
+    assert_eq!(graph("abc", &[]).find_cycle(), None);
+}
+
+#[test]
+fn a_chain_has_no_cycle() {
+    let graph = graph("abcd", &[(0, 1), (1, 2), (2, 3)]);
+    assert!(!graph.has_cycle());
+}
+
+#[test]
+fn a_diamond_is_not_a_cycle() {
+    // d is reached twice, along two paths, but nothing leads back
+    let graph = graph("abcd", &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+    assert_eq!(graph.find_cycle(), None);
+}
+
+#[test]
+fn a_node_can_need_itself() {
+    let graph = graph("ab", &[(0, 1), (1, 1)]);
+    let cycle = graph.find_cycle().expect("b -> b is a cycle");
+    assert_eq!(cycle, [NodeId(1)]);
+}
+
+#[test]
+fn two_tasks_that_need_each_other() {
+    let graph = graph("ab", &[(0, 1), (1, 0)]);
+    let cycle = graph.find_cycle().expect("a -> b -> a is a cycle");
+    assert_is_cycle(&graph, &cycle);
+    assert_eq!(names(&graph, &cycle), ["a", "b"]);
+}
+
+#[test]
+fn the_cycle_leaves_out_the_path_that_led_to_it() {
+    // a -> b -> c -> d -> b: a is on the way, but not in the cycle
+    let graph = graph("abcd", &[(0, 1), (1, 2), (2, 3), (3, 1)]);
+    let cycle = graph.find_cycle().expect("b -> c -> d -> b is a cycle");
+    assert_is_cycle(&graph, &cycle);
+    assert_eq!(names(&graph, &cycle), ["b", "c", "d"]);
+}
+
+#[test]
+fn a_cycle_that_the_first_node_cannot_reach() {
+    // a -> b, and separately c -> d -> e -> c
+    let graph = graph("abcde", &[(0, 1), (2, 3), (3, 4), (4, 2)]);
+    let cycle = graph.find_cycle().expect("c -> d -> e -> c is a cycle");
+    assert_is_cycle(&graph, &cycle);
+    assert_eq!(names(&graph, &cycle), ["c", "d", "e"]);
+}
+
+#[test]
+fn a_finished_part_of_the_graph_is_not_mistaken_for_a_cycle() {
+    // d is searched from b first; c's edge to d later is not a cycle
+    let graph = graph("abcd", &[(0, 1), (1, 3), (0, 2), (2, 3), (3, 0)]);
+    assert!(graph.has_cycle(), "d -> a closes a cycle");
+    let graph = self::graph("abcd", &[(0, 1), (1, 3), (0, 2), (2, 3), (2, 1)]);
+    assert!(!graph.has_cycle());
+}
+
+#[test]
+fn a_big_graph_with_one_edge_back() {
+    // A ladder of 300 tasks, each needing the next two, with no cycle...
+    let mut edges = Vec::new();
+    for i in 0..298 {
+        edges.push((i, i + 1));
+        edges.push((i, i + 2));
+    }
+    edges.push((298, 299));
+    let names = "x".repeat(300);
+    assert!(!graph(&names, &edges).has_cycle());
+    // ...until the last one needs the 100th
+    edges.push((299, 100));
+    let graph = graph(&names, &edges);
+    let cycle = graph.find_cycle().expect("299 -> 100 closes a cycle");
+    assert_is_cycle(&graph, &cycle);
+    assert!(cycle.contains(&NodeId(100)) && cycle.contains(&NodeId(299)));
+}
@@ -0,0 +1,78 @@
+fn tree(values: &[i32]) -> Bst<i32> {
+    let mut tree = Bst::new();
+    for &value in values {
+        tree.insert(value);
+    }
+    tree
+}
+
+#[test]
+fn a_new_tree_is_empty() {
+    let tree: Bst<i32> = Bst::new();
+    assert!(tree.is_empty());
+    assert_eq!(tree.in_order(), Vec::<&i32>::new());
+    assert!(!tree.contains(&1));
+}
+
+#[test]
+fn insert_puts_smaller_values_on_the_left() {
+    let tree = tree(&[50, 30, 70, 20, 40]);
+    let root = tree
+        .root
+        .as_ref()
+        .expect("the first value should be the root");
+    assert_eq!(root.value, 50);
+    let left = root.left.as_ref().expect("30 goes left of 50");
+    assert_eq!(left.value, 30);
+    assert_eq!(left.left.as_ref().map(|node| node.value), Some(20));
+    assert_eq!(left.right.as_ref().map(|node| node.value), Some(40));
+    assert_eq!(root.right.as_ref().map(|node| node.value), Some(70));
+    assert_eq!(tree.height(), 3);
+}
+
+#[test]
+fn insert_reports_duplicates_and_keeps_one() {
+    let mut tree = tree(&[5, 3, 8]);
+    assert!(!tree.insert(3), "3 is already in the tree");
+    assert!(tree.insert(4));
+    assert_eq!(tree.len(), 4);
+    assert_eq!(tree.in_order(), [&3, &4, &5, &8]);
+}
+
+#[test]
+fn contains_finds_every_inserted_value_and_nothing_else() {
+    let values = [41, 7, 93, 15, 62, 2, 88, 30];
+    let tree = tree(&values);
+    for value in 0..100 {
+        assert_eq!(
+            tree.contains(&value),
+            values.contains(&value),
+            "contains({value})"
+        );
+    }
+}
+
+#[test]
+fn in_order_sorts_whatever_order_values_came_in() {
+    let values: Vec<i32> = (0..200).map(|i| i * 73 % 200).collect();
+    let tree = tree(&values);
+    let sorted: Vec<i32> = tree.in_order().into_iter().copied().collect();
+    assert_eq!(sorted, (0..200).collect::<Vec<_>>());
+    assert_eq!(tree.len(), 200);
+}
+
+#[test]
+fn works_for_any_ordered_type() {
+    let mut words = Bst::new();
+    for word in ["pear", "apple", "fig", "apple", "kiwi"] {
+        words.insert(word.to_string());
+    }
+    assert_eq!(words.in_order(), ["apple", "fig", "kiwi", "pear"]);
+    assert!(words.contains(&"fig".to_string()));
+}
+
+#[test]
+fn sorted_input_makes_a_tall_tree() {
+    let tree = tree(&(1..=50).collect::<Vec<_>>());
+    assert_eq!(tree.height(), 50);
+}
@@ -0,0 +1,109 @@
+fn tree(values: &[i32]) -> Bst<i32> {
+    let mut tree = Bst::new();
+    for &value in values {
+        tree.insert(value);
+    }
+    tree
+}
+
+fn values(tree: &Bst<i32>) -> Vec<i32> {
+    tree.in_order().into_iter().copied().collect()
+}
+
+const VALUES: [i32; 10] = [50, 30, 70, 20, 40, 60, 80, 35, 45, 65];
+
+#[test]
+fn pop_first_takes_values_out_smallest_first() {
+    let mut tree = tree(&VALUES);
+    let mut popped = Vec::new();
+    while let Some(value) = tree.pop_first() {
+        popped.push(value);
+    }
+    let mut sorted = VALUES.to_vec();
+    sorted.sort();
+    assert_eq!(popped, sorted);
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn pop_first_keeps_the_smallest_nodes_right_child() {
+    // 10 is the smallest and has a right child, 15, which must move up
+    let mut tree = tree(&[20, 10, 30, 15, 12]);
+    assert_eq!(tree.pop_first(), Some(10));
+    assert_eq!(values(&tree), [12, 15, 20, 30]);
+}
+
+#[test]
+fn removing_a_leaf() {
+    let mut tree = tree(&VALUES);
+    assert_eq!(tree.remove(&20), Some(20));
+    assert_eq!(values(&tree), [30, 35, 40, 45, 50, 60, 65, 70, 80]);
+    assert_eq!(tree.len(), 9);
+}
+
+#[test]
+fn removing_a_node_with_one_child() {
+    let mut tree = tree(&VALUES);
+    // 60 only has a right child, 65
+    assert_eq!(tree.remove(&60), Some(60));
+    assert_eq!(values(&tree), [20, 30, 35, 40, 45, 50, 65, 70, 80]);
+}
+
+#[test]
+fn removing_a_node_with_two_children() {
+    let mut tree = tree(&VALUES);
+    assert_eq!(tree.remove(&30), Some(30));
+    assert_eq!(values(&tree), [20, 35, 40, 45, 50, 60, 65, 70, 80]);
+    // The root has two children too
+    assert_eq!(tree.remove(&50), Some(50));
+    assert_eq!(values(&tree), [20, 35, 40, 45, 60, 65, 70, 80]);
+    let root = tree.root.as_ref().unwrap();
+    assert_eq!(
+        root.value, 60,
+        "the smallest value on the right replaces the root"
+    );
+}
+
+#[test]
+fn removing_something_that_is_not_there() {
+    let mut tree = tree(&VALUES);
+    assert_eq!(tree.remove(&55), None);
+    assert_eq!(tree.len(), 10);
+    let mut empty: Bst<i32> = Bst::new();
+    assert_eq!(empty.remove(&1), None);
+    assert_eq!(empty.pop_first(), None);
+}
+
+#[test]
+fn removing_each_value_in_turn_keeps_all_the_others() {
+    for &gone in &VALUES {
+        let mut tree = tree(&VALUES);
+        assert_eq!(tree.remove(&gone), Some(gone));
+        let mut expected: Vec<i32> = VALUES.iter().copied().filter(|&v| v != gone).collect();
+        expected.sort();
+        assert_eq!(values(&tree), expected, "after removing {gone}");
+        assert_eq!(tree.remove(&gone), None, "{gone} was removed already");
+    }
+}
+
+#[test]
+fn removing_everything_in_a_scrambled_order() {
+    let all: Vec<i32> = (0..100).map(|i| i * 37 % 100).collect();
+    let mut tree = tree(&all);
+    for (i, value) in all.iter().rev().enumerate() {
+        assert_eq!(tree.remove(value), Some(*value));
+        assert_eq!(tree.len(), 99 - i);
+    }
+    assert!(tree.is_empty());
+    assert!(tree.root.is_none());
+}
+
+#[test]
+fn removes_values_that_do_not_implement_copy() {
+    let mut tree = Bst::new();
+    for word in ["kiwi", "apple", "pear", "fig"] {
+        tree.insert(word.to_string());
+    }
+    assert_eq!(tree.remove(&"kiwi".to_string()), Some("kiwi".to_string()));
+    assert_eq!(tree.in_order(), ["apple", "fig", "pear"]);
+}
@@ -0,0 +1,70 @@
+// Exercise: Linked Lists 1 - A stack of boxes
+// Related example: examples/trees_and_graphs/01_binary_search_tree.rs
+//
+// In Java or Go a linked list is a few lines: every node points at the
+// next one, and the garbage collector sorts out who owns what. In Rust a
+// node has to own the node after it, and the type says so:
+//
+//     type Link<T> = Option<Box<Node<T>>>;
+//
+// A Box because a Node can't contain a whole Node (its size would never
+// end), and an Option because the last node has no next one. The list
+// owns its head, the head owns the next node, and so on: there is always
+// exactly one owner, so there are no cycles and nothing to collect.
+//
+// That makes a singly linked list a stack. Adding or removing at the head
+// is cheap, and the head is the only end the list can reach directly.
+//
+// This is the first of three steps after "Learning Rust With Entirely Too
+// Many Linked Lists" (https://rust-unofficial.github.io/too-many-lists/),
+// which goes much further if you enjoy this.
+//
+// TODO: Implement `push`, which puts a value on top of the stack. The new
+//       node's `next` is the old head: move that out of `self.head` with
+//       `Option::take()`, which leaves None behind.
+// TODO: Implement `pop`, which takes the top value off again; None if the
+//       stack is empty.
+//
+// Check your work with: cargo run -p tutor -- check lists1
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+#[derive(Debug)]
+pub struct Stack<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn push(&mut self, value: T) {
+        todo!()
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        todo!()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
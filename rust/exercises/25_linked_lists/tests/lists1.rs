@@ -0,0 +1,75 @@
+#[test]
+fn a_new_stack_is_empty() {
+    let mut stack: Stack<i32> = Stack::new();
+    assert!(stack.is_empty());
+    assert_eq!(stack.len(), 0);
+    assert_eq!(stack.pop(), None);
+}
+
+#[test]
+fn push_puts_the_value_at_the_head() {
+    let mut stack = Stack::new();
+    stack.push(1);
+    stack.push(2);
+    let head = stack
+        .head
+        .as_ref()
+        .expect("the stack has a head after a push");
+    assert_eq!(head.value, 2, "the last value pushed is at the head");
+    let next = head
+        .next
+        .as_ref()
+        .expect("the head's next node is the old head");
+    assert_eq!(next.value, 1);
+    assert!(
+        next.next.is_none(),
+        "the first value pushed is the last node"
+    );
+    assert_eq!(stack.len(), 2);
+}
+
+#[test]
+fn pop_returns_values_last_in_first_out() {
+    let mut stack = Stack::new();
+    for value in 1..=3 {
+        stack.push(value);
+    }
+    assert_eq!(stack.pop(), Some(3));
+    assert_eq!(stack.pop(), Some(2));
+    assert_eq!(stack.pop(), Some(1));
+    assert_eq!(stack.pop(), None);
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn push_and_pop_mixed() {
+    let mut stack = Stack::new();
+    stack.push('a');
+    stack.push('b');
+    assert_eq!(stack.pop(), Some('b'));
+    stack.push('c');
+    assert_eq!(stack.len(), 2);
+    assert_eq!(stack.pop(), Some('c'));
+    assert_eq!(stack.pop(), Some('a'));
+    assert_eq!(stack.pop(), None);
+    stack.push('d');
+    assert_eq!(stack.pop(), Some('d'));
+}
+
+#[test]
+fn works_for_values_that_do_not_implement_copy() {
+    let mut stack = Stack::new();
+    stack.push(String::from("first"));
+    stack.push(String::from("second"));
+    assert_eq!(stack.pop().as_deref(), Some("second"));
+    assert_eq!(stack.pop().as_deref(), Some("first"));
+}
+
+#[test]
+fn a_popped_node_leaves_nothing_behind() {
+    let mut stack = Stack::new();
+    stack.push(vec![1, 2, 3]);
+    assert_eq!(stack.pop(), Some(vec![1, 2, 3]));
+    assert!(stack.head.is_none());
+    assert_eq!(stack.len(), 0);
+}
@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+
+#[test]
+fn peek_sees_the_top_value_and_leaves_it() {
+    let mut stack = Stack::new();
+    assert_eq!(stack.peek(), None);
+    stack.push(1);
+    stack.push(2);
+    assert_eq!(stack.peek(), Some(&2));
+    assert_eq!(stack.peek(), Some(&2), "peeking twice sees the same value");
+    assert_eq!(stack.len(), 2);
+    assert_eq!(stack.pop(), Some(2));
+    assert_eq!(stack.peek(), Some(&1));
+}
+
+#[test]
+fn peek_mut_changes_the_top_value_in_place() {
+    let mut stack = Stack::new();
+    assert_eq!(stack.peek_mut(), None);
+    stack.push(String::from("draft"));
+    stack.push(String::from("notes"));
+    if let Some(top) = stack.peek_mut() {
+        top.push_str(", edited");
+    }
+    assert_eq!(stack.peek().map(String::as_str), Some("notes, edited"));
+    *stack.peek_mut().unwrap() = String::from("replaced");
+    assert_eq!(stack.pop().as_deref(), Some("replaced"));
+    assert_eq!(stack.pop().as_deref(), Some("draft"));
+}
+
+thread_local! {
+    static DROPPED: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+    static DEPTHS: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A value that notes its number down when it's dropped, and roughly
+/// where the stack pointer was at the time.
+struct Probe(u32);
+
+impl Drop for Probe {
+    fn drop(&mut self) {
+        let marker = 0u8;
+        let address = std::hint::black_box(&marker) as *const u8 as usize;
+        DROPPED.with(|dropped| dropped.borrow_mut().push(self.0));
+        DEPTHS.with(|depths| depths.borrow_mut().push(address));
+    }
+}
+
+fn probes(count: u32) -> Stack<Probe> {
+    DROPPED.with(|dropped| dropped.borrow_mut().clear());
+    DEPTHS.with(|depths| depths.borrow_mut().clear());
+    let mut stack = Stack::new();
+    for i in 0..count {
+        stack.push(Probe(i));
+    }
+    stack
+}
+
+#[test]
+fn dropping_the_stack_drops_every_value_once() {
+    let stack = probes(100);
+    drop(stack);
+    let mut dropped = DROPPED.with(|dropped| dropped.take());
+    assert_eq!(dropped.len(), 100, "every value is dropped, and only once");
+    dropped.sort();
+    assert_eq!(dropped, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn dropping_the_stack_drops_values_from_the_top() {
+    let stack = probes(5);
+    drop(stack);
+    assert_eq!(DROPPED.with(|dropped| dropped.take()), [4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn popped_and_remaining_values_are_both_dropped() {
+    let mut stack = probes(4);
+    drop(stack.pop());
+    drop(stack);
+    assert_eq!(DROPPED.with(|dropped| dropped.take()), [3, 2, 1, 0]);
+}
+
+#[test]
+fn dropping_a_long_stack_does_not_go_deeper_and_deeper() {
+    // A recursive drop calls itself once per node, so each value is
+    // dropped a little further down the call stack than the one before.
+    // A loop drops all of them from about the same place. (A list long
+    // enough to actually overflow the stack would crash these tests.)
+    let stack = probes(2_000);
+    drop(stack);
+    let depths = DEPTHS.with(|depths| depths.take());
+    let spread = depths.iter().max().unwrap() - depths.iter().min().unwrap();
+    assert!(
+        spread < 8 * 1024,
+        "the values were dropped as much as {spread} bytes apart on the \
+         stack: the nodes are still dropped recursively"
+    );
+}
+
+#[test]
+fn a_stack_can_be_dropped_after_being_emptied() {
+    let mut stack = probes(3);
+    while stack.pop().is_some() {}
+    assert_eq!(DROPPED.with(|dropped| dropped.take()), [2, 1, 0]);
+    drop(stack);
+    assert!(DROPPED.with(|dropped| dropped.borrow().is_empty()));
+}
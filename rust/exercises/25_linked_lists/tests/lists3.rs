@@ -0,0 +1,105 @@
+fn stack(values: &[i32]) -> Stack<i32> {
+    let mut stack = Stack::new();
+    for &value in values {
+        stack.push(value);
+    }
+    stack
+}
+
+#[test]
+fn into_iter_yields_the_values_from_the_top() {
+    let stack = stack(&[1, 2, 3]);
+    let values: Vec<i32> = stack.into_iter().collect();
+    assert_eq!(values, [3, 2, 1]);
+}
+
+#[test]
+fn into_iter_on_an_empty_stack() {
+    let stack: Stack<i32> = Stack::new();
+    assert_eq!(stack.into_iter().next(), None);
+}
+
+#[test]
+fn into_iter_can_stop_half_way() {
+    let mut values = stack(&[1, 2, 3, 4]).into_iter();
+    assert_eq!(values.next(), Some(4));
+    assert_eq!(values.next(), Some(3));
+    // The rest are dropped with the iterator
+}
+
+#[test]
+fn iter_borrows_and_leaves_the_stack_as_it_was() {
+    let stack = stack(&[1, 2, 3]);
+    let values: Vec<&i32> = stack.iter().collect();
+    assert_eq!(values, [&3, &2, &1]);
+    assert_eq!(stack.len(), 3);
+    assert_eq!(stack.peek(), Some(&3));
+    // Two iterators can borrow the stack at once
+    let sums: Vec<i32> = stack
+        .iter()
+        .zip(stack.iter().skip(1))
+        .map(|(a, b)| a + b)
+        .collect();
+    assert_eq!(sums, [5, 3]);
+}
+
+#[test]
+fn iter_on_an_empty_stack() {
+    let stack: Stack<String> = Stack::new();
+    assert_eq!(stack.iter().next(), None);
+}
+
+#[test]
+fn iter_mut_changes_every_value_in_place() {
+    let mut stack = stack(&[1, 2, 3]);
+    for value in stack.iter_mut() {
+        *value *= 10;
+    }
+    assert_eq!(stack.pop(), Some(30));
+    assert_eq!(stack.pop(), Some(20));
+    assert_eq!(stack.pop(), Some(10));
+    let mut empty: Stack<i32> = Stack::new();
+    assert_eq!(empty.iter_mut().next(), None);
+}
+
+#[test]
+fn iter_mut_references_can_all_be_kept_at_once() {
+    let mut stack = stack(&[1, 2, 3]);
+    let mut references: Vec<&mut i32> = stack.iter_mut().collect();
+    *references[0] = 7;
+    *references[2] = 9;
+    assert_eq!(stack.iter().copied().collect::<Vec<_>>(), [7, 2, 9]);
+}
+
+#[test]
+fn for_loops_work_three_ways() {
+    let mut stack = stack(&[1, 2, 3]);
+    let mut seen = Vec::new();
+    for value in &stack {
+        seen.push(*value);
+    }
+    assert_eq!(seen, [3, 2, 1]);
+    for value in &mut stack {
+        *value += 1;
+    }
+    let mut owned = Vec::new();
+    for value in stack {
+        owned.push(value);
+    }
+    assert_eq!(owned, [4, 3, 2]);
+}
+
+#[test]
+fn works_with_values_that_do_not_implement_copy() {
+    let mut stack = Stack::new();
+    for word in ["one", "two", "three"] {
+        stack.push(word.to_string());
+    }
+    let lengths: Vec<usize> = stack.iter().map(String::len).collect();
+    assert_eq!(lengths, [5, 3, 3]);
+    for word in stack.iter_mut() {
+        word.make_ascii_uppercase();
+    }
+    let words: Vec<String> = stack.into_iter().collect();
+    assert_eq!(words, ["THREE", "TWO", "ONE"]);
+}
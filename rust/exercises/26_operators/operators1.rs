@@ -0,0 +1,138 @@
+// Exercise: Operators 1 - Money that adds up
+// Related example: examples/operators/01_money.rs
+//
+// An invoicing tool keeps amounts as whole cents with a currency, and
+// wants to write `subtotal + tax`, `total -= discount`, `a < b` and
+// `"12.50 EUR".parse()` like it would with numbers. Each of those is a
+// std trait, and `Money` implements none of them yet.
+//
+// The rules:
+// - adding or subtracting amounts in different currencies is a bug in the
+//   caller, so `+` and `-` panic with a message that names both currencies
+// - comparing them is not a bug, but there's no answer: `partial_cmp`
+//   returns None, so `<`, `>`, `<=` and `>=` are all false
+// - Display writes the amount with two decimals and the currency code
+//   after it: "12.50 EUR", "-0.05 USD". It honours a width, so that
+//   `{:>12}` lines amounts up in a column.
+// - FromStr reads what Display writes, and the whole number on its own:
+//   "12.50 EUR", "7 USD", "-3.10 GBP". Anything else is one of the
+//   errors in ParseMoneyError.
+//
+// TODO: Implement Add, Sub, Neg and SubAssign.
+// TODO: Implement PartialOrd.
+// TODO: Implement Display for Money.
+// TODO: Implement FromStr for Money.
+//
+// Check your work with: cargo run -p tutor -- check operators1
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 3] = [Currency::Usd, Currency::Eur, Currency::Gbp];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Money {
+    pub cents: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(cents: i64, currency: Currency) -> Money {
+        Money { cents, currency }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseMoneyError {
+    /// Nothing but whitespace.
+    Empty,
+    /// An amount with no currency after it, like "12.50".
+    MissingCurrency,
+    /// A currency code that isn't in `Currency::ALL`, like "12.50 JPY".
+    UnknownCurrency(String),
+    /// An amount that isn't digits, with no decimals or exactly two, and
+    /// an optional minus in front: "12.5", "1,000", "ten", "$5".
+    InvalidAmount(String),
+}
+
+impl fmt::Display for ParseMoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMoneyError::Empty => write!(f, "no amount given"),
+            ParseMoneyError::MissingCurrency => write!(f, "no currency given"),
+            ParseMoneyError::UnknownCurrency(code) => write!(f, "unknown currency {code:?}"),
+            ParseMoneyError::InvalidAmount(text) => write!(f, "{text:?} is not an amount"),
+        }
+    }
+}
+
+impl std::error::Error for ParseMoneyError {}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        todo!()
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        todo!()
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        todo!()
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        todo!()
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+        todo!()
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        todo!()
+    }
+}
+
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(text: &str) -> Result<Money, ParseMoneyError> {
+        todo!()
+    }
+}
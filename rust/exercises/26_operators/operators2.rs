@@ -0,0 +1,156 @@
+// Exercise: Operators 2 - A matrix that behaves like one
+// Related example: examples/operators/02_matrix.rs
+//
+// A small linear algebra helper for a game keeps integer matrices in one
+// Vec, row after row. Callers want the usual notation: `m[(row, col)]`
+// to read and write a cell, `&a + &b` and `&a * &b`, `for row in &m` to
+// go through the rows, and `"1 2\n3 4".parse()` to write one down.
+//
+// The operators take references, so that using a matrix in a sum doesn't
+// move it away. Sizes that don't fit together are bugs, so they panic:
+// - `m[(row, col)]` outside the matrix, with "out of bounds" in the message
+// - `&a + &b` unless both have the same shape
+// - `&a * &b` unless `a` has as many columns as `b` has rows
+//
+// TODO: Implement Index and IndexMut, so `m[(row, col)]` works both ways.
+// TODO: Implement Add and Mul for &Matrix.
+// TODO: Implement IntoIterator for &Matrix and &mut Matrix, yielding each
+//       row as a slice. No iterator type of your own is needed: look at
+//       what slices already have.
+// TODO: Implement Display: one row per line, each number right-aligned in
+//       a column as wide as the widest number in the matrix, with one
+//       space between columns, and no newline after the last row.
+// TODO: Implement FromStr: one row per line, numbers separated by
+//       whitespace. Blank lines are skipped, and don't count as rows.
+//       Report the first problem, with rows and columns counted from 1.
+//
+// Check your work with: cargo run -p tutor -- check operators2
+
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    /// Row after row, `cols` numbers each.
+    data: Vec<i64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix {
+        assert!(rows > 0 && cols > 0, "a matrix has at least one cell");
+        Matrix {
+            rows,
+            cols,
+            data: vec![0; rows * cols],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseMatrixError {
+    /// No numbers at all.
+    Empty,
+    /// Something that isn't an i64, at this row and column.
+    BadNumber {
+        row: usize,
+        col: usize,
+        text: String,
+    },
+    /// A row whose length differs from the first row's.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseMatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMatrixError::Empty => write!(f, "no numbers"),
+            ParseMatrixError::BadNumber { row, col, text } => {
+                write!(f, "row {row}, column {col}: {text:?} is not a number")
+            }
+            ParseMatrixError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row}: expected {expected} numbers, found {found}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseMatrixError {}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = i64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &i64 {
+        todo!()
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut i64 {
+        todo!()
+    }
+}
+
+impl Add for &Matrix {
+    type Output = Matrix;
+
+    fn add(self, other: &Matrix) -> Matrix {
+        todo!()
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, other: &Matrix) -> Matrix {
+        todo!()
+    }
+}
+
+impl<'a> IntoIterator for &'a Matrix {
+    type Item = &'a [i64];
+    type IntoIter = std::slice::Chunks<'a, i64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        todo!()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Matrix {
+    type Item = &'a mut [i64];
+    type IntoIter = std::slice::ChunksMut<'a, i64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        todo!()
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        todo!()
+    }
+}
+
+impl FromStr for Matrix {
+    type Err = ParseMatrixError;
+
+    fn from_str(text: &str) -> Result<Matrix, ParseMatrixError> {
+        todo!()
+    }
+}
@@ -0,0 +1,180 @@
+use Currency::*;
+
+fn usd(cents: i64) -> Money {
+    Money::new(cents, Usd)
+}
+
+fn eur(cents: i64) -> Money {
+    Money::new(cents, Eur)
+}
+
+#[test]
+fn adds_and_subtracts_amounts_in_one_currency() {
+    assert_eq!(usd(1250) + usd(199), usd(1449));
+    assert_eq!(usd(1250) - usd(199), usd(1051));
+    assert_eq!(usd(100) - usd(250), usd(-150));
+    assert_eq!(eur(10) + eur(20), eur(30));
+}
+
+#[test]
+fn negates() {
+    assert_eq!(-usd(500), usd(-500));
+    assert_eq!(-(-eur(1)), eur(1));
+    assert_eq!((-usd(0)).cents, 0);
+}
+
+#[test]
+fn sub_assign_takes_off_in_place() {
+    let mut balance = eur(10_000);
+    balance -= eur(2_550);
+    balance -= eur(50);
+    assert_eq!(balance, eur(7_400));
+}
+
+#[test]
+#[should_panic(expected = "cannot add EUR to USD")]
+fn adding_different_currencies_panics() {
+    let _ = usd(100) + eur(100);
+}
+
+#[test]
+#[should_panic(expected = "EUR")]
+fn subtracting_different_currencies_panics() {
+    let mut balance = usd(100);
+    balance -= eur(100);
+}
+
+#[test]
+fn compares_amounts_in_one_currency() {
+    assert!(usd(100) < usd(101));
+    assert!(usd(-5) < usd(0));
+    assert!(eur(300) >= eur(300));
+    assert!(eur(300) > eur(299));
+    assert_eq!(usd(100).partial_cmp(&usd(100)), Some(Ordering::Equal));
+    assert_eq!(usd(7).partial_cmp(&usd(9)), Some(Ordering::Less));
+}
+
+#[test]
+fn amounts_in_different_currencies_have_no_order() {
+    assert_eq!(usd(100).partial_cmp(&eur(100)), None);
+    assert_eq!(eur(1).partial_cmp(&usd(1_000_000)), None);
+    assert!(!(usd(1) < eur(2)));
+    assert!(!(usd(1) > eur(2)));
+    assert!(!(usd(1) <= eur(1)));
+    assert!(!(usd(1) >= eur(1)));
+}
+
+#[test]
+fn finds_the_biggest_with_partial_ord() {
+    let amounts = [usd(250), usd(1999), usd(-300), usd(1998)];
+    let biggest = amounts
+        .iter()
+        .copied()
+        .reduce(|a, b| if b > a { b } else { a });
+    assert_eq!(biggest, Some(usd(1999)));
+}
+
+#[test]
+fn displays_two_decimals_and_the_code() {
+    assert_eq!(usd(1250).to_string(), "12.50 USD");
+    assert_eq!(eur(7).to_string(), "0.07 EUR");
+    assert_eq!(Money::new(100_000, Gbp).to_string(), "1000.00 GBP");
+    assert_eq!(usd(0).to_string(), "0.00 USD");
+}
+
+#[test]
+fn displays_negative_amounts_with_a_minus() {
+    assert_eq!(usd(-5).to_string(), "-0.05 USD");
+    assert_eq!(eur(-1999).to_string(), "-19.99 EUR");
+}
+
+#[test]
+fn display_honours_width_and_alignment() {
+    assert_eq!(format!("{:>12}|", usd(1250)), "   12.50 USD|");
+    assert_eq!(format!("{:<12}|", eur(-5)), "-0.05 EUR   |");
+    assert_eq!(format!("{:^11}", usd(100)), " 1.00 USD  ");
+    assert_eq!(
+        format!("{:3}", usd(1250)),
+        "12.50 USD",
+        "a width that is too small changes nothing"
+    );
+}
+
+#[test]
+fn parses_what_display_writes() {
+    for money in [
+        usd(1250),
+        eur(-5),
+        Money::new(100_000, Gbp),
+        usd(0),
+        eur(-1999),
+    ] {
+        assert_eq!(money.to_string().parse::<Money>(), Ok(money), "{money}");
+    }
+}
+
+#[test]
+fn parses_whole_amounts_and_spaces() {
+    assert_eq!("7 USD".parse::<Money>(), Ok(usd(700)));
+    assert_eq!("-3 EUR".parse::<Money>(), Ok(eur(-300)));
+    assert_eq!("  12.50   EUR \n".parse::<Money>(), Ok(eur(1250)));
+    assert_eq!("0.99 GBP".parse::<Money>(), Ok(Money::new(99, Gbp)));
+}
+
+#[test]
+fn parse_reports_a_missing_amount_or_currency() {
+    assert_eq!("".parse::<Money>(), Err(ParseMoneyError::Empty));
+    assert_eq!("   ".parse::<Money>(), Err(ParseMoneyError::Empty));
+    assert_eq!(
+        "12.50".parse::<Money>(),
+        Err(ParseMoneyError::MissingCurrency)
+    );
+}
+
+#[test]
+fn parse_reports_an_unknown_currency() {
+    assert_eq!(
+        "12.50 JPY".parse::<Money>(),
+        Err(ParseMoneyError::UnknownCurrency("JPY".to_string()))
+    );
+    assert_eq!(
+        "12.50 usd".parse::<Money>(),
+        Err(ParseMoneyError::UnknownCurrency("usd".to_string())),
+        "codes are upper case"
+    );
+}
+
+#[test]
+fn parse_reports_an_invalid_amount() {
+    for amount in [
+        "12.5", "12.505", "1,000.00", "ten", "$5", "--1", "-", ".50", "5.", "+5", "1e3",
+    ] {
+        assert_eq!(
+            format!("{amount} USD").parse::<Money>(),
+            Err(ParseMoneyError::InvalidAmount(amount.to_string())),
+            "{amount:?}"
+        );
+    }
+}
+
+#[test]
+fn parse_rejects_amounts_too_big_for_the_cents() {
+    let huge = "99999999999999999999 USD";
+    assert_eq!(
+        huge.parse::<Money>(),
+        Err(ParseMoneyError::InvalidAmount(
+            "99999999999999999999".to_string()
+        ))
+    );
+    // The biggest that fits still parses
+    let biggest = format!("{}.{:02} USD", i64::MAX / 100, i64::MAX % 100);
+    assert_eq!(biggest.parse::<Money>(), Ok(usd(i64::MAX)));
+}
+
+#[test]
+fn parse_errors_can_be_shown_to_a_person() {
+    let error = "12.50 JPY".parse::<Money>().unwrap_err();
+    assert_eq!(error.to_string(), "unknown currency \"JPY\"");
+    let error: Box<dyn std::error::Error> = Box::new("ten USD".parse::<Money>().unwrap_err());
+    assert_eq!(error.to_string(), "\"ten\" is not an amount");
+}
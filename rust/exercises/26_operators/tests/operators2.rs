@@ -0,0 +1,207 @@
+fn matrix(text: &str) -> Matrix {
+    text.parse()
+        .expect("the tests only use matrices that parse")
+}
+
+#[test]
+fn index_reads_and_writes_cells() {
+    let mut m = Matrix::zeros(2, 3);
+    m[(0, 0)] = 1;
+    m[(1, 2)] = 6;
+    m[(1, 2)] += 1;
+    assert_eq!(m[(0, 0)], 1);
+    assert_eq!(m[(1, 2)], 7);
+    assert_eq!(m[(0, 2)], 0);
+    assert_eq!(m.data, [1, 0, 0, 0, 0, 7], "cells are stored row after row");
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn index_past_the_last_row_panics() {
+    let m = Matrix::zeros(2, 3);
+    let _ = m[(2, 0)];
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn index_past_the_last_column_panics() {
+    // (0, 3) would be cell 3 of the Vec, which exists: it's (1, 0)
+    let m = Matrix::zeros(2, 3);
+    let _ = m[(0, 3)];
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn index_mut_out_of_bounds_panics() {
+    let mut m = Matrix::zeros(2, 2);
+    m[(0, 2)] = 1;
+}
+
+#[test]
+fn adds_cell_by_cell_without_moving_anything() {
+    let a = matrix("1 2\n3 4");
+    let b = matrix("10 20\n30 40");
+    assert_eq!(&a + &b, matrix("11 22\n33 44"));
+    // Both are still there to use again
+    assert_eq!(&(&a + &b) + &a, matrix("12 24\n36 48"));
+}
+
+#[test]
+#[should_panic(expected = "cannot add")]
+fn adding_different_shapes_panics() {
+    let _ = &Matrix::zeros(2, 3) + &Matrix::zeros(3, 2);
+}
+
+#[test]
+fn multiplies_rows_by_columns() {
+    let a = matrix("1 2\n3 4");
+    let b = matrix("5 6\n7 8");
+    assert_eq!(&a * &b, matrix("19 22\n43 50"));
+    assert_eq!(&b * &a, matrix("23 34\n31 46"), "order matters");
+    let identity = matrix("1 0\n0 1");
+    assert_eq!(&a * &identity, a);
+}
+
+#[test]
+fn multiplies_matrices_of_different_shapes() {
+    let row = matrix("1 2 3");
+    let column = matrix("4\n5\n6");
+    assert_eq!(&row * &column, matrix("32"));
+    let outer = &column * &row;
+    assert_eq!((outer.rows(), outer.cols()), (3, 3));
+    assert_eq!(outer, matrix("4 8 12\n5 10 15\n6 12 18"));
+}
+
+#[test]
+#[should_panic(expected = "cannot multiply")]
+fn multiplying_shapes_that_do_not_fit_panics() {
+    let _ = &Matrix::zeros(2, 3) * &Matrix::zeros(2, 3);
+}
+
+#[test]
+fn for_loops_go_through_the_rows() {
+    let m = matrix("1 2 3\n4 5 6");
+    let mut rows = Vec::new();
+    for row in &m {
+        rows.push(row.to_vec());
+    }
+    assert_eq!(rows, [vec![1, 2, 3], vec![4, 5, 6]]);
+    let sums: Vec<i64> = (&m).into_iter().map(|row| row.iter().sum()).collect();
+    assert_eq!(sums, [6, 15]);
+    assert_eq!((&m).into_iter().len(), 2);
+}
+
+#[test]
+fn for_loops_can_change_the_rows() {
+    let mut m = matrix("1 2\n3 4\n5 6");
+    for row in &mut m {
+        row.reverse();
+    }
+    assert_eq!(m, matrix("2 1\n4 3\n6 5"));
+    for (i, row) in (&mut m).into_iter().enumerate() {
+        row[0] *= 10_i64.pow(i as u32);
+    }
+    assert_eq!(m, matrix("2 1\n40 3\n600 5"));
+}
+
+#[test]
+fn displays_rows_in_aligned_columns() {
+    assert_eq!(matrix("1 2\n3 4").to_string(), "1 2\n3 4");
+    assert_eq!(
+        matrix("1 -20 3\n400 5 6").to_string(),
+        "  1 -20   3\n400   5   6"
+    );
+    assert_eq!(matrix("7").to_string(), "7");
+    assert_eq!(matrix("1 2 3").to_string(), "1 2 3");
+}
+
+#[test]
+fn parses_what_display_writes() {
+    let mut m = Matrix::zeros(3, 2);
+    m[(0, 1)] = -15;
+    m[(2, 0)] = 1234;
+    assert_eq!(m.to_string().parse::<Matrix>(), Ok(m));
+}
+
+#[test]
+fn parses_any_whitespace_and_skips_blank_lines() {
+    let m = matrix("\n  1\t 2 \n\n   \n3   4\n");
+    assert_eq!((m.rows(), m.cols()), (2, 2));
+    assert_eq!(m, matrix("1 2\n3 4"));
+    assert_eq!(matrix("-1 +2"), matrix("-1 2"));
+}
+
+#[test]
+fn parse_reports_an_empty_matrix() {
+    assert_eq!("".parse::<Matrix>(), Err(ParseMatrixError::Empty));
+    assert_eq!("\n  \n\t\n".parse::<Matrix>(), Err(ParseMatrixError::Empty));
+}
+
+#[test]
+fn parse_reports_where_a_bad_number_is() {
+    assert_eq!(
+        "1 2\n3 x4".parse::<Matrix>(),
+        Err(ParseMatrixError::BadNumber {
+            row: 2,
+            col: 2,
+            text: "x4".to_string()
+        })
+    );
+    assert_eq!(
+        "1.5 2".parse::<Matrix>(),
+        Err(ParseMatrixError::BadNumber {
+            row: 1,
+            col: 1,
+            text: "1.5".to_string()
+        })
+    );
+    // Blank lines don't count as rows
+    assert_eq!(
+        "1 2\n\n3 99999999999999999999".parse::<Matrix>(),
+        Err(ParseMatrixError::BadNumber {
+            row: 2,
+            col: 2,
+            text: "99999999999999999999".to_string()
+        })
+    );
+}
+
+#[test]
+fn parse_reports_a_ragged_row() {
+    assert_eq!(
+        "1 2 3\n4 5 6\n7 8".parse::<Matrix>(),
+        Err(ParseMatrixError::RaggedRow {
+            row: 3,
+            expected: 3,
+            found: 2
+        })
+    );
+    assert_eq!(
+        "1\n2 3".parse::<Matrix>(),
+        Err(ParseMatrixError::RaggedRow {
+            row: 2,
+            expected: 1,
+            found: 2
+        })
+    );
+}
+
+#[test]
+fn parse_reports_the_first_problem() {
+    assert_eq!(
+        "1 2\n3\n4 y".parse::<Matrix>(),
+        Err(ParseMatrixError::RaggedRow {
+            row: 2,
+            expected: 2,
+            found: 1
+        })
+    );
+}
+
+#[test]
+fn parse_errors_can_be_shown_to_a_person() {
+    let error = "1 2\n3 x".parse::<Matrix>().unwrap_err();
+    assert_eq!(error.to_string(), "row 2, column 2: \"x\" is not a number");
+    let error: Box<dyn std::error::Error> = Box::new("1 2\n3".parse::<Matrix>().unwrap_err());
+    assert_eq!(error.to_string(), "row 2: expected 2 numbers, found 1");
+}
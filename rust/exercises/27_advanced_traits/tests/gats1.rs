@@ -0,0 +1,117 @@
+fn series(start: i64, step: i64, len: usize) -> Series {
+    Series { start, step, len }
+}
+
+/// Every item, through the trait alone, as the report generator sees them.
+fn listed<'c, C>(collection: &'c C) -> Vec<String>
+where
+    C: Collection,
+    C::Item<'c>: std::fmt::Display,
+{
+    collection.iter().map(|item| item.to_string()).collect()
+}
+
+#[test]
+fn a_vec_lends_its_items() {
+    let names = vec![String::from("ada"), String::from("grace")];
+    assert_eq!(Collection::len(&names), 2);
+    let first: Option<&String> = Collection::get(&names, 0);
+    assert!(std::ptr::eq(first.unwrap(), &names[0]), "a borrow, not a copy");
+    assert_eq!(Collection::get(&names, 1).map(String::as_str), Some("grace"));
+    assert_eq!(Collection::get(&names, 2), None);
+    assert_eq!(listed(&names), ["ada", "grace"]);
+}
+
+#[test]
+fn a_vec_gets_the_default_methods() {
+    let numbers = vec![3, 1, 4];
+    assert!(!Collection::is_empty(&numbers));
+    assert_eq!(Collection::first(&numbers), Some(&3));
+    assert_eq!(Collection::last(&numbers), Some(&4));
+
+    let empty: Vec<u8> = Vec::new();
+    assert!(Collection::is_empty(&empty));
+    assert_eq!(Collection::first(&empty), None);
+    assert_eq!(Collection::last(&empty), None);
+}
+
+#[test]
+fn a_series_computes_its_items() {
+    let evens = series(0, 2, 5);
+    assert_eq!(evens.len(), 5);
+    let third: Option<i64> = evens.get(2);
+    assert_eq!(third, Some(4));
+    assert_eq!(evens.get(5), None);
+    assert_eq!(evens.iter().collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+    assert_eq!(evens.first(), Some(0));
+    assert_eq!(evens.last(), Some(8));
+}
+
+#[test]
+fn a_series_can_count_down_or_stand_still() {
+    assert_eq!(series(10, -3, 4).iter().collect::<Vec<_>>(), [10, 7, 4, 1]);
+    assert_eq!(series(7, 0, 3).iter().collect::<Vec<_>>(), [7, 7, 7]);
+    let empty = series(1, 1, 0);
+    assert!(empty.is_empty());
+    assert_eq!(empty.iter().next(), None);
+    assert_eq!(empty.last(), None);
+}
+
+#[test]
+fn a_series_iterator_borrows_nothing() {
+    let mut walk = {
+        let short_lived = series(1, 1, 3);
+        short_lived.iter()
+    };
+    assert_eq!(walk.next(), Some(1));
+    assert_eq!(walk.by_ref().count(), 2);
+    assert_eq!(walk.next(), None);
+}
+
+#[test]
+fn words_lend_slices_of_their_text() {
+    let words = Words::new("  the quick\tbrown\n fox ");
+    assert_eq!(words.len(), 4);
+    let second: Option<&str> = words.get(1);
+    assert_eq!(second, Some("quick"));
+    assert_eq!(words.get(4), None);
+    assert_eq!(words.first(), Some("the"));
+    assert_eq!(words.last(), Some("fox"));
+    assert_eq!(listed(&words), ["the", "quick", "brown", "fox"]);
+    assert!(Words::new(" \n ").is_empty());
+}
+
+#[test]
+fn largest_works_on_all_three() {
+    let scores = vec![12, 40, 7, 40];
+    let best = largest(&scores).unwrap();
+    assert_eq!(*best, 40);
+    assert!(std::ptr::eq(best, &scores[3]), "the last of equal items");
+
+    assert_eq!(largest(&series(-5, 2, 4)), Some(1));
+    assert_eq!(largest(&series(9, -1, 3)), Some(9));
+    assert_eq!(largest(&Words::new("pear apple zucchini fig")), Some("zucchini"));
+}
+
+#[test]
+fn largest_of_nothing_is_none() {
+    assert_eq!(largest(&Vec::<String>::new()), None);
+    assert_eq!(largest(&series(0, 1, 0)), None);
+    assert_eq!(largest(&Words::new("")), None);
+}
+
+#[test]
+fn position_finds_the_first_match() {
+    let tags = vec![String::from("a"), String::from("b"), String::from("a")];
+    assert_eq!(position(&tags, &String::from("a")), Some(0));
+    assert_eq!(position(&tags, &String::from("b")), Some(1));
+    assert_eq!(position(&tags, &String::from("z")), None);
+
+    assert_eq!(position(&series(100, 25, 10), 175), Some(3));
+    assert_eq!(position(&series(100, 25, 10), 110), None);
+
+    let words = Words::new("to be or not to be");
+    assert_eq!(position(&words, "be"), Some(1));
+    assert_eq!(position(&words, "not"), Some(3));
+    assert_eq!(position(&words, "maybe"), None);
+}
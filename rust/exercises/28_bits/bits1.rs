@@ -0,0 +1,106 @@
+// Exercise: Bits and Bytes 1 - Decoding a status register
+// Related example: examples/bits/02_bit_flags.rs
+//
+// A board with sixteen sensors on it reports their status over a serial
+// bus, one 16-bit status word per reading, big-endian. Each word packs
+// three things into its bits:
+//
+//     bit  15      14      13           12        11..8     7..0
+//          READY   ERROR   LOW_BATTERY  reserved  channel   level
+//
+// - the top three bits are flags, held as a Flags set: READY is bit 2 of
+//   a Flags, ERROR bit 1, LOW_BATTERY bit 0
+// - bit 12 is reserved and always 0; a word with it set is garbage
+// - the channel is a number from 0 to 15, and the level from 0 to 255
+//
+// So 0x8A2C is READY, channel 10, level 44 (0x2C).
+//
+// TODO: Implement BitOr for Flags, and Flags::contains.
+// TODO: Write Status::decode: pull out each field with a shift and a mask,
+//       and refuse a word with the reserved bit set.
+// TODO: Write Status::encode, the same in reverse. A channel above 15
+//       doesn't fit in four bits: panic with a message that says
+//       "channel", rather than losing its top bits.
+// TODO: Write decode_all, which decodes a capture of raw bytes off the
+//       bus, two bytes per word, big-endian.
+//
+// Check your work with: cargo run -p tutor -- check bits1
+
+use std::fmt;
+use std::ops::BitOr;
+
+/// Some of READY, ERROR, and LOW_BATTERY, as the three low bits of a u8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Flags(u8);
+
+impl Flags {
+    pub const READY: Flags = Flags(0b100);
+    pub const ERROR: Flags = Flags(0b010);
+    pub const LOW_BATTERY: Flags = Flags(0b001);
+
+    pub const fn empty() -> Flags {
+        Flags(0)
+    }
+
+    /// Whether every flag in `other` is set in `self`.
+    pub fn contains(self, other: Flags) -> bool {
+        todo!()
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Flags;
+
+    fn bitor(self, other: Flags) -> Flags {
+        todo!()
+    }
+}
+
+/// One decoded status word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Status {
+    pub flags: Flags,
+    /// 0 to 15
+    pub channel: u8,
+    pub level: u8,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegisterError {
+    /// The reserved bit was set, in the word at this index (0 for
+    /// Status::decode, which only has one).
+    ReservedBit { index: usize },
+    /// A capture of this many bytes, which isn't a whole number of words.
+    OddLength(usize),
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegisterError::ReservedBit { index } => {
+                write!(f, "word {index} has the reserved bit set")
+            }
+            RegisterError::OddLength(len) => {
+                write!(f, "{len} bytes is not a whole number of 16-bit words")
+            }
+        }
+    }
+}
+
+/// Bit 12, which is always 0 in a real status word.
+const RESERVED: u16 = 1 << 12;
+
+impl Status {
+    pub fn decode(word: u16) -> Result<Status, RegisterError> {
+        todo!()
+    }
+
+    pub fn encode(&self) -> u16 {
+        todo!()
+    }
+}
+
+/// Decodes every word in `bytes`, big-endian, two bytes each.
+pub fn decode_all(bytes: &[u8]) -> Result<Vec<Status>, RegisterError> {
+    todo!()
+}
@@ -0,0 +1,109 @@
+// Exercise: Bits and Bytes 2 - Parsing a packet
+// Related example: examples/bits/04_binary_header.rs
+//
+// A game server and its clients send each other packets over UDP. Every
+// packet is a 12-byte header and then the payload, with every integer
+// big-endian:
+//
+//     offset  size  field
+//          0     2  u16: the magic number 0xC0DE
+//          2     1  u8: the version, 1 or 2
+//          3     1  u8: flags; only bits 0 (RELIABLE) and 1 (COMPRESSED) exist
+//          4     2  u16: the sequence number
+//          6     2  u16: how many bytes of payload follow the header
+//          8     4  u32: the checksum of the payload
+//         12     n  the payload
+//
+// The checksum starts at 0, and for each payload byte becomes
+// `checksum * 31 + byte`, wrapping around on overflow, as Java's
+// String.hashCode does.
+//
+// A packet comes off the network, so it can be anything. parse() checks,
+// in this order, and reports the first problem:
+// - fewer than 12 bytes: TooShort, with how many a header needs
+// - the wrong magic number, version, or flags: BadMagic,
+//   UnsupportedVersion, UnknownFlags, with the value found
+// - fewer payload bytes than the header says: TooShort, with how many
+//   the whole packet needs
+// - more bytes than that: TrailingBytes, with how many are left over
+// - a checksum that doesn't match: BadChecksum, with both values
+//
+// TODO: Write checksum().
+// TODO: Write parse(). The payload is borrowed from the input: no copying.
+// TODO: Write Packet::to_bytes(), which parse() must read back unchanged.
+//       A payload too long for the length field is an error, not a panic.
+//
+// Check your work with: cargo run -p tutor -- check bits2
+
+use std::fmt;
+
+pub const MAGIC: u16 = 0xC0DE;
+pub const HEADER_LEN: usize = 12;
+
+pub const RELIABLE: u8 = 1 << 0;
+pub const COMPRESSED: u8 = 1 << 1;
+
+/// A parsed packet. `payload` points into the bytes it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet<'a> {
+    pub version: u8,
+    pub flags: u8,
+    pub sequence: u16,
+    pub payload: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PacketError {
+    /// The packet needs `needed` bytes, and only `got` arrived.
+    TooShort {
+        needed: usize,
+        got: usize,
+    },
+    BadMagic(u16),
+    UnsupportedVersion(u8),
+    UnknownFlags(u8),
+    /// This many bytes came after the payload.
+    TrailingBytes(usize),
+    BadChecksum {
+        expected: u32,
+        found: u32,
+    },
+    /// A payload of this many bytes doesn't fit in the u16 length field.
+    PayloadTooLong(usize),
+}
+
+impl fmt::Display for PacketError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PacketError::TooShort { needed, got } => {
+                write!(f, "packet too short: needed {needed} bytes, got {got}")
+            }
+            PacketError::BadMagic(magic) => write!(f, "bad magic number {magic:#06x}"),
+            PacketError::UnsupportedVersion(version) => write!(f, "unsupported version {version}"),
+            PacketError::UnknownFlags(flags) => write!(f, "unknown flags {flags:#010b}"),
+            PacketError::TrailingBytes(count) => write!(f, "{count} bytes after the payload"),
+            PacketError::BadChecksum { expected, found } => {
+                write!(
+                    f,
+                    "checksum should be {expected:#010x}, header says {found:#010x}"
+                )
+            }
+            PacketError::PayloadTooLong(len) => write!(f, "a {len}-byte payload is too long"),
+        }
+    }
+}
+
+/// The payload checksum: `checksum * 31 + byte` for each byte, wrapping.
+pub fn checksum(payload: &[u8]) -> u32 {
+    todo!()
+}
+
+pub fn parse(bytes: &[u8]) -> Result<Packet<'_>, PacketError> {
+    todo!()
+}
+
+impl Packet<'_> {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PacketError> {
+        todo!()
+    }
+}
@@ -0,0 +1,91 @@
+/// Four readings captured off the bus, as bytes.
+const CAPTURE: [u8; 8] = [
+    0x8a, 0x2c, // READY, channel 10, level 44
+    0xc3, 0xff, // READY | ERROR, channel 3, level 255
+    0x20, 0x00, // LOW_BATTERY, channel 0, level 0
+    0x0f, 0x80, // no flags, channel 15, level 128
+];
+
+fn status(flags: Flags, channel: u8, level: u8) -> Status {
+    Status { flags, channel, level }
+}
+
+#[test]
+fn flags_combine_and_contain() {
+    let both = Flags::READY | Flags::ERROR;
+    assert!(both.contains(Flags::READY));
+    assert!(both.contains(Flags::ERROR));
+    assert!(both.contains(Flags::READY | Flags::ERROR));
+    assert!(!both.contains(Flags::LOW_BATTERY));
+    assert!(!both.contains(Flags::ERROR | Flags::LOW_BATTERY));
+    assert!(both.contains(Flags::empty()));
+    assert!(!Flags::empty().contains(Flags::READY));
+    assert_eq!(Flags::LOW_BATTERY | Flags::LOW_BATTERY, Flags::LOW_BATTERY);
+}
+
+#[test]
+fn decodes_each_field() {
+    assert_eq!(Status::decode(0x8a2c), Ok(status(Flags::READY, 10, 44)));
+    assert_eq!(Status::decode(0xc3ff), Ok(status(Flags::READY | Flags::ERROR, 3, 255)));
+    assert_eq!(Status::decode(0x2000), Ok(status(Flags::LOW_BATTERY, 0, 0)));
+    assert_eq!(Status::decode(0x0f80), Ok(status(Flags::empty(), 15, 128)));
+    assert_eq!(
+        Status::decode(0xefff),
+        Ok(status(Flags::READY | Flags::ERROR | Flags::LOW_BATTERY, 15, 255))
+    );
+}
+
+#[test]
+fn refuses_the_reserved_bit() {
+    assert_eq!(Status::decode(0x1000), Err(RegisterError::ReservedBit { index: 0 }));
+    assert_eq!(Status::decode(0xffff), Err(RegisterError::ReservedBit { index: 0 }));
+}
+
+#[test]
+fn encodes_back_to_the_same_word() {
+    assert_eq!(status(Flags::READY, 10, 44).encode(), 0x8a2c);
+    assert_eq!(status(Flags::empty(), 0, 0).encode(), 0x0000);
+    assert_eq!(status(Flags::READY | Flags::ERROR | Flags::LOW_BATTERY, 15, 255).encode(), 0xefff);
+    for word in [0x8a2c, 0xc3ff, 0x2000, 0x0f80, 0x4501, 0x0000] {
+        assert_eq!(Status::decode(word).unwrap().encode(), word, "{word:#06x}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "channel")]
+fn a_channel_too_big_for_four_bits_panics() {
+    status(Flags::READY, 16, 0).encode();
+}
+
+#[test]
+fn decodes_a_capture() {
+    assert_eq!(
+        decode_all(&CAPTURE),
+        Ok(vec![
+            status(Flags::READY, 10, 44),
+            status(Flags::READY | Flags::ERROR, 3, 255),
+            status(Flags::LOW_BATTERY, 0, 0),
+            status(Flags::empty(), 15, 128),
+        ])
+    );
+    assert_eq!(decode_all(&[]), Ok(vec![]));
+}
+
+#[test]
+fn the_capture_is_big_endian() {
+    // The same two bytes the other way round are a different word
+    assert_eq!(decode_all(&[0x2c, 0x8a]), Ok(vec![status(Flags::LOW_BATTERY, 12, 0x8a)]));
+}
+
+#[test]
+fn says_which_word_in_a_capture_is_bad() {
+    let mut capture = CAPTURE.to_vec();
+    capture[4] |= 0x10;
+    assert_eq!(decode_all(&capture), Err(RegisterError::ReservedBit { index: 2 }));
+}
+
+#[test]
+fn refuses_half_a_word() {
+    assert_eq!(decode_all(&CAPTURE[..7]), Err(RegisterError::OddLength(7)));
+    assert_eq!(decode_all(&[0x8a]), Err(RegisterError::OddLength(1)));
+}
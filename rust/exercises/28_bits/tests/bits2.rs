@@ -0,0 +1,169 @@
+/// Version 1, RELIABLE, sequence 258, payload "ping".
+const PING: [u8; 16] = [
+    0xc0, 0xde, // magic
+    0x01, // version
+    0x01, // flags: RELIABLE
+    0x01, 0x02, // sequence 258
+    0x00, 0x04, // 4 bytes of payload
+    0x00, 0x34, 0x81, 0x72, // checksum of "ping"
+    b'p', b'i', b'n', b'g',
+];
+
+/// Version 2, RELIABLE | COMPRESSED, sequence 65535, no payload.
+const EMPTY: [u8; 12] = [
+    0xc0, 0xde, 0x02, 0x03, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// Version 1, no flags, sequence 7, payload "hello, world". Its checksum
+/// wraps around many times.
+const HELLO: [u8; 24] = [
+    0xc0, 0xde, 0x01, 0x00, 0x00, 0x07, 0x00, 0x0c, 0xd9, 0xd1, 0x15, 0x8c, b'h', b'e', b'l',
+    b'l', b'o', b',', b' ', b'w', b'o', b'r', b'l', b'd',
+];
+
+/// PING with one byte replaced.
+fn ping_with(offset: usize, byte: u8) -> Vec<u8> {
+    let mut bytes = PING.to_vec();
+    bytes[offset] = byte;
+    bytes
+}
+
+#[test]
+fn checksums_wrap_around() {
+    assert_eq!(checksum(b""), 0);
+    assert_eq!(checksum(b"a"), 97);
+    assert_eq!(checksum(b"ab"), 97 * 31 + 98);
+    assert_eq!(checksum(b"ping"), 0x0034_8172);
+    assert_eq!(checksum(b"hello, world"), 0xd9d1_158c);
+    assert_eq!(checksum(&[0xff; 1000]), 0x26b9_b180);
+}
+
+#[test]
+fn parses_the_fixtures() {
+    assert_eq!(
+        parse(&PING),
+        Ok(Packet {
+            version: 1,
+            flags: RELIABLE,
+            sequence: 258,
+            payload: b"ping",
+        })
+    );
+    assert_eq!(
+        parse(&EMPTY),
+        Ok(Packet {
+            version: 2,
+            flags: RELIABLE | COMPRESSED,
+            sequence: u16::MAX,
+            payload: b"",
+        })
+    );
+    assert_eq!(parse(&HELLO).unwrap().payload, b"hello, world");
+    assert_eq!(parse(&HELLO).unwrap().flags, 0);
+}
+
+#[test]
+fn the_payload_is_borrowed_from_the_input() {
+    let packet = parse(&PING).unwrap();
+    assert!(std::ptr::eq(packet.payload.as_ptr(), PING[12..].as_ptr()));
+}
+
+#[test]
+fn refuses_a_short_header() {
+    assert_eq!(parse(&[]), Err(PacketError::TooShort { needed: 12, got: 0 }));
+    assert_eq!(parse(&PING[..11]), Err(PacketError::TooShort { needed: 12, got: 11 }));
+}
+
+#[test]
+fn refuses_a_bad_magic_version_or_flags() {
+    assert_eq!(parse(&ping_with(0, 0xca)), Err(PacketError::BadMagic(0xcade)));
+    assert_eq!(parse(&ping_with(1, 0xc0)), Err(PacketError::BadMagic(0xc0c0)));
+    assert_eq!(parse(&ping_with(2, 0)), Err(PacketError::UnsupportedVersion(0)));
+    assert_eq!(parse(&ping_with(2, 3)), Err(PacketError::UnsupportedVersion(3)));
+    assert_eq!(parse(&ping_with(3, 0b100)), Err(PacketError::UnknownFlags(0b100)));
+    assert_eq!(parse(&ping_with(3, 0x81)), Err(PacketError::UnknownFlags(0x81)));
+}
+
+#[test]
+fn checks_the_header_before_the_length() {
+    // A wrong magic number wins over a cut-off payload
+    let mut bytes = ping_with(0, 0);
+    bytes.truncate(13);
+    assert_eq!(parse(&bytes), Err(PacketError::BadMagic(0x00de)));
+}
+
+#[test]
+fn refuses_a_cut_off_payload() {
+    assert_eq!(parse(&PING[..14]), Err(PacketError::TooShort { needed: 16, got: 14 }));
+    assert_eq!(parse(&PING[..12]), Err(PacketError::TooShort { needed: 16, got: 12 }));
+    // The length says 0x0104 bytes: the big-endian reading of 01 04
+    assert_eq!(parse(&ping_with(6, 0x01)), Err(PacketError::TooShort { needed: 272, got: 16 }));
+}
+
+#[test]
+fn refuses_bytes_after_the_payload() {
+    let mut bytes = PING.to_vec();
+    bytes.extend_from_slice(b"!!");
+    assert_eq!(parse(&bytes), Err(PacketError::TrailingBytes(2)));
+    // A length that is too small leaves the rest of the payload behind
+    assert_eq!(parse(&ping_with(7, 0x03)), Err(PacketError::TrailingBytes(1)));
+}
+
+#[test]
+fn refuses_a_wrong_checksum() {
+    assert_eq!(
+        parse(&ping_with(11, 0x73)),
+        Err(PacketError::BadChecksum {
+            expected: 0x0034_8172,
+            found: 0x0034_8173,
+        })
+    );
+    // So does a payload that changed on the way
+    assert_eq!(
+        parse(&ping_with(15, b'G')),
+        Err(PacketError::BadChecksum {
+            expected: checksum(b"pinG"),
+            found: 0x0034_8172,
+        })
+    );
+}
+
+#[test]
+fn writes_the_fixtures_back() {
+    let ping = Packet {
+        version: 1,
+        flags: RELIABLE,
+        sequence: 258,
+        payload: b"ping",
+    };
+    assert_eq!(ping.to_bytes(), Ok(PING.to_vec()));
+    assert_eq!(parse(&EMPTY).unwrap().to_bytes(), Ok(EMPTY.to_vec()));
+    assert_eq!(parse(&HELLO).unwrap().to_bytes(), Ok(HELLO.to_vec()));
+}
+
+#[test]
+fn round_trips_the_largest_payload() {
+    let payload = vec![0xab; 65_535];
+    let packet = Packet {
+        version: 2,
+        flags: COMPRESSED,
+        sequence: 1,
+        payload: &payload,
+    };
+    let bytes = packet.to_bytes().unwrap();
+    assert_eq!(bytes.len(), 12 + 65_535);
+    assert_eq!(&bytes[6..8], &[0xff, 0xff]);
+    assert_eq!(parse(&bytes), Ok(packet));
+}
+
+#[test]
+fn refuses_to_write_a_payload_too_long_for_the_header() {
+    let payload = vec![0; 65_536];
+    let packet = Packet {
+        version: 1,
+        flags: 0,
+        sequence: 0,
+        payload: &payload,
+    };
+    assert_eq!(packet.to_bytes(), Err(PacketError::PayloadTooLong(65_536)));
+}
@@ -0,0 +1,136 @@
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Hashes every key to 0, so that every key collides with every other.
+#[derive(Default)]
+struct Collide;
+
+impl Hasher for Collide {
+    fn write(&mut self, _bytes: &[u8]) {}
+
+    fn finish(&self) -> u64 {
+        0
+    }
+}
+
+fn colliding() -> OpenMap<&'static str, u32, BuildHasherDefault<Collide>> {
+    OpenMap::with_hasher(BuildHasherDefault::default())
+}
+
+#[test]
+fn inserts_and_gets() {
+    let mut map = OpenMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert("one", 1), None);
+    assert_eq!(map.insert("two", 2), None);
+    assert_eq!(map.get("one"), Some(&1));
+    assert_eq!(map.get("two"), Some(&2));
+    assert_eq!(map.get("three"), None);
+    assert!(map.contains_key("two"));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn insert_replaces_and_returns_the_old_value() {
+    let mut map = OpenMap::new();
+    map.insert("score", 10);
+    assert_eq!(map.insert("score", 25), Some(10));
+    assert_eq!(map.get("score"), Some(&25));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn string_keys_are_found_by_str() {
+    let mut map: OpenMap<String, usize> = OpenMap::new();
+    map.insert("Blue".to_string(), 10);
+    let text = "Blue team";
+    assert_eq!(map.get(&text[..4]), Some(&10));
+    assert_eq!(map.remove("Blue"), Some(10));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn colliding_keys_are_all_found() {
+    let mut map = colliding();
+    for (n, key) in ["a", "b", "c", "d", "e"].into_iter().enumerate() {
+        map.insert(key, n as u32);
+    }
+    for (n, key) in ["a", "b", "c", "d", "e"].into_iter().enumerate() {
+        assert_eq!(map.get(key), Some(&(n as u32)), "key {key}");
+    }
+    assert_eq!(map.get("f"), None);
+}
+
+#[test]
+fn remove_leaves_later_colliding_keys_findable() {
+    let mut map = colliding();
+    map.insert("a", 1);
+    map.insert("b", 2);
+    map.insert("c", 3);
+    assert_eq!(map.remove("b"), Some(2));
+    assert_eq!(map.remove("b"), None);
+    // "c" sits past the slot "b" left: the search must step over it
+    assert_eq!(map.get("c"), Some(&3));
+    assert_eq!(map.get("a"), Some(&1));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn inserting_after_a_remove_does_not_duplicate_a_key() {
+    let mut map = colliding();
+    map.insert("a", 1);
+    map.insert("b", 2);
+    map.remove("a");
+    // The tombstone where "a" was comes first, but "b" is further along
+    assert_eq!(map.insert("b", 20), Some(2));
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.remove("b"), Some(20));
+    assert_eq!(map.get("b"), None);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn grows_to_keep_a_quarter_of_the_slots_free() {
+    let mut map = OpenMap::new();
+    for n in 0..1000 {
+        map.insert(n, n * n);
+        assert!(
+            map.len() * 4 <= map.capacity() * 3,
+            "{} entries in {} slots",
+            map.len(),
+            map.capacity()
+        );
+    }
+    assert_eq!(map.len(), 1000);
+    for n in 0..1000 {
+        assert_eq!(map.get(&n), Some(&(n * n)));
+    }
+}
+
+#[test]
+fn tombstones_are_cleared_without_growing() {
+    let mut map = OpenMap::new();
+    // Every insert into a fresh slot uses one up, and every remove leaves
+    // a tombstone behind: without rebuilding, the table would fill up
+    for n in 0..10_000 {
+        map.insert(n, n);
+        assert_eq!(map.remove(&n), Some(n));
+    }
+    assert!(map.is_empty());
+    assert_eq!(map.capacity(), 8, "a map that never holds more than one entry needn't grow");
+}
+
+#[test]
+fn colliding_keys_survive_rebuilds() {
+    let mut map = colliding();
+    let keys = ["k0", "k1", "k2", "k3", "k4", "k5", "k6", "k7", "k8", "k9"];
+    for (n, key) in keys.into_iter().enumerate() {
+        map.insert(key, n as u32);
+    }
+    for key in &keys[..5] {
+        map.remove(key);
+    }
+    for (n, key) in keys.into_iter().enumerate().skip(5) {
+        assert_eq!(map.get(key), Some(&(n as u32)), "key {key}");
+    }
+    assert_eq!(map.len(), 5);
+}
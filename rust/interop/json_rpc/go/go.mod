@@ -0,0 +1,3 @@
+module jsonrpcclient
+
+go 1.21
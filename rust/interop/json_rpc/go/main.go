@@ -0,0 +1,175 @@
+// Rust-Go Interop: A Go Client for a Rust JSON-RPC Server
+// Demonstrates calling a service through a shared contract instead of shared memory
+//
+// The server is ../src/main.rs; ../contract.json says what it answers.
+// From the rust/ directory, with the server running:
+//     cargo run -p json-rpc-interop -- 127.0.0.1:7878
+//     cd interop/json_rpc/go && go run . 127.0.0.1:7878
+//
+// Only the standard library: encoding/json does the JSON, and the
+// structs below are contract.json's params and results written in Go.
+
+package main
+
+import (
+    "bufio"
+    "encoding/json"
+    "fmt"
+    "net"
+    "os"
+)
+
+// ===== The protocol =====
+
+type request struct {
+    JSONRPC string `json:"jsonrpc"`
+    ID      int    `json:"id"`
+    Method  string `json:"method"`
+    Params  any    `json:"params"`
+}
+
+type response struct {
+    ID     int             `json:"id"`
+    Result json.RawMessage `json:"result"`
+    Error  *rpcError       `json:"error"`
+}
+
+// rpcError is a response's "error" member, and a Go error too.
+type rpcError struct {
+    Code    int64  `json:"code"`
+    Message string `json:"message"`
+}
+
+func (e *rpcError) Error() string {
+    return fmt.Sprintf("%d %s", e.Code, e.Message)
+}
+
+// client sends one request per line and reads one response per line.
+type client struct {
+    conn   net.Conn
+    lines  *bufio.Scanner
+    nextID int
+}
+
+func dial(address string) (*client, error) {
+    conn, err := net.Dial("tcp", address)
+    if err != nil {
+        return nil, err
+    }
+    return &client{conn: conn, lines: bufio.NewScanner(conn)}, nil
+}
+
+// call sends method(params) and decodes the result into result, which
+// must be a pointer. A JSON-RPC error comes back as an *rpcError.
+func (c *client) call(method string, params any, result any) error {
+    c.nextID++
+    line, err := json.Marshal(request{JSONRPC: "2.0", ID: c.nextID, Method: method, Params: params})
+    if err != nil {
+        return err
+    }
+    if _, err := c.conn.Write(append(line, '\n')); err != nil {
+        return err
+    }
+    if !c.lines.Scan() {
+        if err := c.lines.Err(); err != nil {
+            return err
+        }
+        return fmt.Errorf("server closed the connection")
+    }
+    var reply response
+    if err := json.Unmarshal(c.lines.Bytes(), &reply); err != nil {
+        return err
+    }
+    if reply.ID != c.nextID {
+        return fmt.Errorf("response for request %d, expected %d", reply.ID, c.nextID)
+    }
+    if reply.Error != nil {
+        return reply.Error
+    }
+    return json.Unmarshal(reply.Result, result)
+}
+
+// ===== The methods, typed =====
+
+type operands struct {
+    A int64 `json:"a"`
+    B int64 `json:"b"`
+}
+
+type division struct {
+    Quotient  int64 `json:"quotient"`
+    Remainder int64 `json:"remainder"`
+}
+
+type wordCount struct {
+    Words   int    `json:"words"`
+    Longest string `json:"longest"`
+}
+
+func (c *client) Add(a, b int64) (int64, error) {
+    var sum int64
+    err := c.call("add", operands{a, b}, &sum)
+    return sum, err
+}
+
+func (c *client) Divide(a, b int64) (division, error) {
+    var result division
+    err := c.call("divide", operands{a, b}, &result)
+    return result, err
+}
+
+func (c *client) WordCount(text string) (wordCount, error) {
+    var result wordCount
+    err := c.call("word_count", map[string]string{"text": text}, &result)
+    return result, err
+}
+
+func main() {
+    address := "127.0.0.1:7878"
+    if len(os.Args) > 1 {
+        address = os.Args[1]
+    }
+    c, err := dial(address)
+    if err != nil {
+        fmt.Fprintln(os.Stderr, "cannot connect:", err)
+        os.Exit(1)
+    }
+    defer c.conn.Close()
+
+    sum, err := c.Add(2, 40)
+    report(fmt.Sprintf("add(2, 40) = %d", sum), "add(2, 40)", err)
+
+    for _, pair := range [][2]int64{{17, 5}, {-7, 2}, {1, 0}} {
+        label := fmt.Sprintf("divide(%d, %d)", pair[0], pair[1])
+        d, err := c.Divide(pair[0], pair[1])
+        report(fmt.Sprintf("%s = %d remainder %d", label, d.Quotient, d.Remainder), label, err)
+    }
+
+    text := "the quick brown fox"
+    count, err := c.WordCount(text)
+    label := fmt.Sprintf("word_count(%q)", text)
+    report(fmt.Sprintf("%s = %d words, longest %q", label, count.Words, count.Longest), label, err)
+
+    // Requests the typed methods can't make: the server's errors for them
+    var ignored any
+    report("", "sqrt(2)", c.call("sqrt", map[string]int{"x": 2}, &ignored))
+    report("", `add(2, "forty")`, c.call("add", map[string]any{"a": 2, "b": "forty"}, &ignored))
+}
+
+// report prints ok, or what failed if err isn't nil.
+func report(ok, label string, err error) {
+    if err != nil {
+        fmt.Printf("%s failed: %v\n", label, err)
+    } else {
+        fmt.Println(ok)
+    }
+}
+
+/*
+ * Key Concepts:
+ * - The contract file, not shared code, is what the two sides agree on
+ * - Go structs with json tags are the contract's params and results
+ * - One JSON value per line makes the framing a bufio.Scanner
+ * - JSON-RPC errors arrive as data and become ordinary Go errors
+ * - Checking the response id catches a client and server out of step
+ */
@@ -0,0 +1,302 @@
+// Rust-Java Interop: A Java Client for a Rust JSON-RPC Server
+// Demonstrates calling a service through a shared contract instead of shared memory
+//
+// The server is ../src/main.rs; ../contract.json says what it answers.
+// From the rust/ directory, with the server running:
+//     cargo run -p json-rpc-interop -- 127.0.0.1:7878
+//     javac -d /tmp/rpc interop/json_rpc/java/RpcClient.java
+//     java -cp /tmp/rpc RpcClient 127.0.0.1:7878
+//
+// Only the JDK. It has no JSON library, so Json below is a small one:
+// enough for this protocol's objects, strings, and integers.
+
+import java.io.BufferedReader;
+import java.io.IOException;
+import java.io.InputStreamReader;
+import java.io.OutputStreamWriter;
+import java.io.Writer;
+import java.net.Socket;
+import java.nio.charset.StandardCharsets;
+import java.util.ArrayList;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+
+public class RpcClient implements AutoCloseable {
+    // ===== The protocol =====
+
+    // A response's "error" member, thrown as an exception
+    static final class RpcException extends Exception {
+        final long code;
+
+        RpcException(long code, String message) {
+            super(message);
+            this.code = code;
+        }
+
+        @Override
+        public String toString() {
+            return code + " " + getMessage();
+        }
+    }
+
+    private final Socket socket;
+    private final BufferedReader reader;
+    private final Writer writer;
+    private long nextId = 0;
+
+    RpcClient(String host, int port) throws IOException {
+        socket = new Socket(host, port);
+        reader = new BufferedReader(new InputStreamReader(socket.getInputStream(), StandardCharsets.UTF_8));
+        writer = new OutputStreamWriter(socket.getOutputStream(), StandardCharsets.UTF_8);
+    }
+
+    // Sends method(params), one line out, and returns the result from the
+    // one line that comes back
+    Object call(String method, Map<String, Object> params) throws IOException, RpcException {
+        nextId++;
+        Map<String, Object> request = new LinkedHashMap<>();
+        request.put("jsonrpc", "2.0");
+        request.put("id", nextId);
+        request.put("method", method);
+        request.put("params", params);
+        writer.write(Json.write(request) + "\n");
+        writer.flush();
+
+        String line = reader.readLine();
+        if (line == null) {
+            throw new IOException("server closed the connection");
+        }
+        Map<?, ?> response = (Map<?, ?>) Json.parse(line);
+        if (!Long.valueOf(nextId).equals(response.get("id"))) {
+            throw new IOException("response for request " + response.get("id") + ", expected " + nextId);
+        }
+        if (response.get("error") instanceof Map<?, ?> error) {
+            throw new RpcException((Long) error.get("code"), (String) error.get("message"));
+        }
+        return response.get("result");
+    }
+
+    @Override
+    public void close() throws IOException {
+        socket.close();
+    }
+
+    // ===== The methods, typed =====
+
+    record Division(long quotient, long remainder) {}
+
+    record WordCount(long words, String longest) {}
+
+    long add(long a, long b) throws IOException, RpcException {
+        return (Long) call("add", Map.of("a", a, "b", b));
+    }
+
+    Division divide(long a, long b) throws IOException, RpcException {
+        Map<?, ?> result = (Map<?, ?>) call("divide", Map.of("a", a, "b", b));
+        return new Division((Long) result.get("quotient"), (Long) result.get("remainder"));
+    }
+
+    WordCount wordCount(String text) throws IOException, RpcException {
+        Map<?, ?> result = (Map<?, ?>) call("word_count", Map.of("text", text));
+        return new WordCount((Long) result.get("words"), (String) result.get("longest"));
+    }
+
+    // A call that either prints its result or says how it failed
+    interface Call {
+        String run() throws IOException, RpcException;
+    }
+
+    static void report(String label, Call call) throws IOException {
+        try {
+            System.out.println(call.run());
+        } catch (RpcException e) {
+            System.out.println(label + " failed: " + e);
+        }
+    }
+
+    public static void main(String[] args) throws IOException {
+        String address = args.length > 0 ? args[0] : "127.0.0.1:7878";
+        int colon = address.lastIndexOf(':');
+        String host = address.substring(0, colon);
+        int port = Integer.parseInt(address.substring(colon + 1));
+
+        try (RpcClient client = new RpcClient(host, port)) {
+            report("add(2, 40)", () -> "add(2, 40) = " + client.add(2, 40));
+
+            long[][] pairs = {{17, 5}, {-7, 2}, {1, 0}};
+            for (long[] pair : pairs) {
+                String label = "divide(" + pair[0] + ", " + pair[1] + ")";
+                report(label, () -> {
+                    Division d = client.divide(pair[0], pair[1]);
+                    return label + " = " + d.quotient() + " remainder " + d.remainder();
+                });
+            }
+
+            String text = "the quick brown fox";
+            String label = "word_count(" + Json.quote(text) + ")";
+            report(label, () -> {
+                WordCount count = client.wordCount(text);
+                return label + " = " + count.words() + " words, longest " + Json.quote(count.longest());
+            });
+
+            // Requests the typed methods can't make: the server's errors for them
+            report("sqrt(2)", () -> "" + client.call("sqrt", Map.of("x", 2L)));
+            report("add(2, \"forty\")", () -> "" + client.call("add", Map.of("a", 2L, "b", "forty")));
+        }
+    }
+
+    // ===== JSON =====
+
+    static final class Json {
+        static String write(Object value) {
+            StringBuilder out = new StringBuilder();
+            write(value, out);
+            return out.toString();
+        }
+
+        private static void write(Object value, StringBuilder out) {
+            if (value instanceof Map<?, ?> map) {
+                out.append('{');
+                boolean first = true;
+                for (Map.Entry<?, ?> entry : map.entrySet()) {
+                    if (!first) {
+                        out.append(',');
+                    }
+                    first = false;
+                    out.append(quote((String) entry.getKey())).append(':');
+                    write(entry.getValue(), out);
+                }
+                out.append('}');
+            } else if (value instanceof String text) {
+                out.append(quote(text));
+            } else {
+                // Long, Integer, Boolean, and null all print as JSON does
+                out.append(value);
+            }
+        }
+
+        static String quote(String text) {
+            StringBuilder out = new StringBuilder("\"");
+            for (char c : text.toCharArray()) {
+                if (c == '"' || c == '\\') {
+                    out.append('\\').append(c);
+                } else if (c < 0x20) {
+                    out.append(String.format("\\u%04x", (int) c));
+                } else {
+                    out.append(c);
+                }
+            }
+            return out.append('"').toString();
+        }
+
+        static Object parse(String text) {
+            return new Parser(text).value();
+        }
+
+        private static final class Parser {
+            private final String text;
+            private int pos = 0;
+
+            Parser(String text) {
+                this.text = text;
+            }
+
+            Object value() {
+                skipSpace();
+                char c = text.charAt(pos);
+                if (c == '{') {
+                    Map<String, Object> object = new LinkedHashMap<>();
+                    pos++;
+                    skipSpace();
+                    while (text.charAt(pos) != '}') {
+                        skipSpace();
+                        String key = string();
+                        skipSpace();
+                        pos++; // ':'
+                        object.put(key, value());
+                        skipSpace();
+                        if (text.charAt(pos) == ',') {
+                            pos++;
+                        }
+                    }
+                    pos++;
+                    return object;
+                } else if (c == '[') {
+                    List<Object> array = new ArrayList<>();
+                    pos++;
+                    skipSpace();
+                    while (text.charAt(pos) != ']') {
+                        array.add(value());
+                        skipSpace();
+                        if (text.charAt(pos) == ',') {
+                            pos++;
+                        }
+                    }
+                    pos++;
+                    return array;
+                } else if (c == '"') {
+                    return string();
+                } else if (text.startsWith("true", pos)) {
+                    pos += 4;
+                    return Boolean.TRUE;
+                } else if (text.startsWith("false", pos)) {
+                    pos += 5;
+                    return Boolean.FALSE;
+                } else if (text.startsWith("null", pos)) {
+                    pos += 4;
+                    return null;
+                } else {
+                    // The contract only has integers
+                    int start = pos;
+                    while (pos < text.length() && (Character.isDigit(text.charAt(pos)) || text.charAt(pos) == '-')) {
+                        pos++;
+                    }
+                    return Long.parseLong(text.substring(start, pos));
+                }
+            }
+
+            private String string() {
+                StringBuilder out = new StringBuilder();
+                pos++; // the opening quote
+                while (text.charAt(pos) != '"') {
+                    char c = text.charAt(pos++);
+                    if (c != '\\') {
+                        out.append(c);
+                        continue;
+                    }
+                    char escaped = text.charAt(pos++);
+                    switch (escaped) {
+                        case 'n' -> out.append('\n');
+                        case 't' -> out.append('\t');
+                        case 'r' -> out.append('\r');
+                        case 'b' -> out.append('\b');
+                        case 'f' -> out.append('\f');
+                        case 'u' -> {
+                            out.append((char) Integer.parseInt(text.substring(pos, pos + 4), 16));
+                            pos += 4;
+                        }
+                        default -> out.append(escaped); // \" \\ \/
+                    }
+                }
+                pos++;
+                return out.toString();
+            }
+
+            private void skipSpace() {
+                while (pos < text.length() && Character.isWhitespace(text.charAt(pos))) {
+                    pos++;
+                }
+            }
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - The contract file, not shared code, is what the two sides agree on
+ * - Records are the contract's results, typed, on the Java side
+ * - One JSON value per line makes the framing a BufferedReader.readLine()
+ * - JSON-RPC errors arrive as data and become checked exceptions
+ * - Checking the response id catches a client and server out of step
+ */
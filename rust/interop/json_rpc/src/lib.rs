@@ -0,0 +1,315 @@
+//! A small calculator service, spoken as JSON-RPC 2.0 over TCP.
+//!
+//! `contract.json`, next to Cargo.toml, is the API contract: the methods,
+//! their params and results, and the error codes. The Go client in go/
+//! and the Java client in java/ are written against that file, not
+//! against this code, and tests/contract.rs replays its examples against
+//! the running server, so a change on either side that breaks the other
+//! shows up as a failing test.
+//!
+//! The framing is one JSON value per line, both ways. A request is
+//!
+//! ```text
+//! {"jsonrpc":"2.0","id":1,"method":"add","params":{"a":2,"b":40}}
+//! ```
+//!
+//! and its response carries the same id, with either a `result` or an
+//! `error`:
+//!
+//! ```text
+//! {"jsonrpc":"2.0","id":1,"result":42}
+//! {"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"division by zero"}}
+//! ```
+//!
+//! A request without an id is a notification: the server runs it and
+//! sends nothing back.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The methods the server answers, in the order contract.json lists them.
+pub const METHODS: [&str; 3] = ["add", "divide", "word_count"];
+
+// ===== Error codes =====
+// The first four are JSON-RPC's own; -32000 to -32099 are left for the
+// application.
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const ARITHMETIC_ERROR: i64 = -32000;
+
+/// The `error` member of a failed response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    /// Absent for a notification. `Some(Value::Null)` is an id of null,
+    /// which the spec allows and which still gets a response.
+    #[serde(default, deserialize_with = "present")]
+    id: Option<Value>,
+}
+
+/// Tells `"id": null` apart from no id at all.
+fn present<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+// ===== The methods =====
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Operands {
+    a: i64,
+    b: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Text {
+    text: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Division {
+    quotient: i64,
+    remainder: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct WordCount {
+    words: usize,
+    /// The first of the longest words, or "" for no words.
+    longest: String,
+}
+
+fn add(Operands { a, b }: Operands) -> Result<i64, RpcError> {
+    a.checked_add(b)
+        .ok_or_else(|| RpcError::new(ARITHMETIC_ERROR, "overflow"))
+}
+
+/// Rounds toward zero, as Rust, Go, and Java all do, so -7 / 2 is -3
+/// remainder -1.
+fn divide(Operands { a, b }: Operands) -> Result<Division, RpcError> {
+    match (a.checked_div(b), a.checked_rem(b)) {
+        (Some(quotient), Some(remainder)) => Ok(Division {
+            quotient,
+            remainder,
+        }),
+        _ if b == 0 => Err(RpcError::new(ARITHMETIC_ERROR, "division by zero")),
+        _ => Err(RpcError::new(ARITHMETIC_ERROR, "overflow")),
+    }
+}
+
+fn word_count(Text { text }: Text) -> Result<WordCount, RpcError> {
+    let mut count = WordCount {
+        words: 0,
+        longest: String::new(),
+    };
+    for word in text.split_whitespace() {
+        count.words += 1;
+        if word.chars().count() > count.longest.chars().count() {
+            count.longest = word.to_string();
+        }
+    }
+    Ok(count)
+}
+
+/// Decodes the params into the method's own type, runs it, and encodes
+/// the result. This is the one place a method's types meet JSON.
+fn call<P, R>(params: Value, method: fn(P) -> Result<R, RpcError>) -> Result<Value, RpcError>
+where
+    P: DeserializeOwned,
+    R: Serialize,
+{
+    let params = serde_json::from_value(params)
+        .map_err(|error| RpcError::new(INVALID_PARAMS, format!("invalid params: {error}")))?;
+    let result = method(params)?;
+    Ok(serde_json::to_value(result).expect("results always serialize"))
+}
+
+fn dispatch(method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "add" => call(params, add),
+        "divide" => call(params, divide),
+        "word_count" => call(params, word_count),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("method not found: {method}"),
+        )),
+    }
+}
+
+// ===== Requests and responses =====
+
+/// Answers one line of input: the response line, without its newline, or
+/// None for a notification.
+pub fn handle_line(line: &str) -> Option<String> {
+    let (id, outcome) = match serde_json::from_str::<Value>(line) {
+        Err(error) => (
+            Value::Null,
+            Err(RpcError::new(PARSE_ERROR, format!("parse error: {error}"))),
+        ),
+        Ok(value) => {
+            // Echo the id back even when the rest of the request is wrong,
+            // so the client can tell which of its requests failed
+            let id = value.get("id").cloned().unwrap_or(Value::Null);
+            match serde_json::from_value::<Request>(value) {
+                Err(error) => (
+                    id,
+                    Err(RpcError::new(
+                        INVALID_REQUEST,
+                        format!("invalid request: {error}"),
+                    )),
+                ),
+                Ok(request) if request.jsonrpc != "2.0" => (
+                    id,
+                    Err(RpcError::new(
+                        INVALID_REQUEST,
+                        "invalid request: jsonrpc must be \"2.0\"",
+                    )),
+                ),
+                Ok(request) => {
+                    let outcome = dispatch(&request.method, request.params);
+                    (request.id?, outcome)
+                }
+            }
+        }
+    };
+    let response = match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    };
+    Some(response.to_string())
+}
+
+/// Answers requests on one connection until the client hangs up.
+pub fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&line) {
+            writeln!(writer, "{response}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Accepts connections forever, each on its own thread.
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || {
+            if let Err(error) = handle_connection(stream) {
+                eprintln!("connection failed: {error}");
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(line: &str) -> Value {
+        serde_json::from_str(&handle_line(line).expect("a response")).unwrap()
+    }
+
+    #[test]
+    fn a_call_gets_its_result_and_id_back() {
+        assert_eq!(
+            response(r#"{"jsonrpc":"2.0","id":7,"method":"add","params":{"a":2,"b":40}}"#),
+            json!({ "jsonrpc": "2.0", "id": 7, "result": 42 })
+        );
+        assert_eq!(
+            response(
+                r#"{"jsonrpc":"2.0","id":"w","method":"word_count","params":{"text":"a bb cc"}}"#
+            ),
+            json!({ "jsonrpc": "2.0", "id": "w", "result": { "words": 3, "longest": "bb" } })
+        );
+    }
+
+    #[test]
+    fn division_rounds_toward_zero() {
+        let divided = |a, b| divide(Operands { a, b });
+        assert_eq!(
+            divided(-7, 2),
+            Ok(Division {
+                quotient: -3,
+                remainder: -1
+            })
+        );
+        assert_eq!(
+            divided(7, -2),
+            Ok(Division {
+                quotient: -3,
+                remainder: 1
+            })
+        );
+        assert_eq!(divided(1, 0).unwrap_err().message, "division by zero");
+        assert_eq!(divided(i64::MIN, -1).unwrap_err().message, "overflow");
+    }
+
+    #[test]
+    fn bad_requests_get_the_standard_codes() {
+        let code = |line: &str| response(line)["error"]["code"].as_i64().unwrap();
+        assert_eq!(code("{not json"), PARSE_ERROR);
+        assert_eq!(code(r#"{"jsonrpc":"2.0","id":1}"#), INVALID_REQUEST);
+        assert_eq!(
+            code(r#"{"jsonrpc":"1.0","id":1,"method":"add"}"#),
+            INVALID_REQUEST
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc":"2.0","id":1,"method":"sqrt"}"#),
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc":"2.0","id":1,"method":"add","params":{"a":1}}"#),
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            code(r#"{"jsonrpc":"2.0","id":1,"method":"add","params":{"a":1,"b":2,"c":3}}"#),
+            INVALID_PARAMS
+        );
+        assert_eq!(response("[1]")["id"], Value::Null);
+    }
+
+    #[test]
+    fn notifications_get_no_response() {
+        assert_eq!(
+            handle_line(r#"{"jsonrpc":"2.0","method":"add","params":{"a":1,"b":2}}"#),
+            None
+        );
+        assert!(handle_line(
+            r#"{"jsonrpc":"2.0","id":null,"method":"add","params":{"a":1,"b":2}}"#
+        )
+        .is_some());
+    }
+}
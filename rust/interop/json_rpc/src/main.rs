@@ -0,0 +1,19 @@
+//! The calculator server. Listens on the address given, or 127.0.0.1:7878:
+//!
+//!     cargo run -p json-rpc-interop -- 127.0.0.1:0
+//!
+//! Port 0 picks a free port. The first line of output says which one, so
+//! a program that starts the server (like the tests) can read it.
+
+use std::io::{self, Write};
+use std::net::TcpListener;
+
+fn main() -> io::Result<()> {
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:7878".to_string());
+    let listener = TcpListener::bind(&address)?;
+    println!("listening on {}", listener.local_addr()?);
+    io::stdout().flush()?;
+    json_rpc_interop::serve(listener)
+}
@@ -0,0 +1,88 @@
+// Runs the Go and Java clients against the server and checks that both
+// print the same transcript. A client whose toolchain isn't installed is
+// skipped, with a note on stderr (`cargo test -- --nocapture` shows it).
+
+mod common;
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use common::Server;
+
+/// What both clients print. They make the same calls in the same order,
+/// each from its own typed wrappers over contract.json.
+const TRANSCRIPT: &str = "\
+add(2, 40) = 42
+divide(17, 5) = 3 remainder 2
+divide(-7, 2) = -3 remainder -1
+divide(1, 0) failed: -32000 division by zero
+word_count(\"the quick brown fox\") = 4 words, longest \"quick\"
+sqrt(2) failed: -32601 method not found: sqrt
+add(2, \"forty\") failed: -32602 invalid params: invalid type: string \"forty\", expected i64
+";
+
+/// Whether `tool` runs, checked the way bench_harness checks for Go and
+/// Java.
+fn is_installed(tool: &str, version_flag: &str) -> bool {
+    Command::new(tool)
+        .arg(version_flag)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn crate_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn assert_transcript(output: std::process::Output) {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), TRANSCRIPT);
+}
+
+#[test]
+fn the_go_client_follows_the_contract() {
+    if !is_installed("go", "version") {
+        eprintln!("skipped: `go` not found");
+        return;
+    }
+    let server = Server::start();
+    let output = Command::new("go")
+        .args(["run", "."])
+        .arg(&server.address)
+        .current_dir(crate_dir().join("go"))
+        .output()
+        .expect("cannot run go");
+    assert_transcript(output);
+}
+
+#[test]
+fn the_java_client_follows_the_contract() {
+    if !is_installed("javac", "-version") {
+        eprintln!("skipped: `javac` not found");
+        return;
+    }
+    let server = Server::start();
+    let classes = Path::new(env!("CARGO_TARGET_TMPDIR")).join("rpc-classes");
+    let compiled = Command::new("javac")
+        .arg("-d")
+        .arg(&classes)
+        .arg(crate_dir().join("java/RpcClient.java"))
+        .status()
+        .expect("cannot run javac");
+    assert!(compiled.success(), "RpcClient.java failed to compile");
+
+    let output = Command::new("java")
+        .arg("-cp")
+        .arg(&classes)
+        .arg("RpcClient")
+        .arg(&server.address)
+        .output()
+        .expect("cannot run java");
+    assert_transcript(output);
+}
@@ -0,0 +1,149 @@
+// Checks the server against contract.json: the same methods, every
+// example answered as written, and params of the wrong type, or missing,
+// refused. This is the Rust client; the Go and Java ones are in
+// clients.rs.
+
+mod common;
+
+use std::collections::BTreeSet;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+use serde_json::{json, Map, Value};
+
+use common::Server;
+use json_rpc_interop::{INVALID_PARAMS, METHODS, METHOD_NOT_FOUND};
+
+fn contract() -> Value {
+    let text = include_str!("../contract.json");
+    serde_json::from_str(text).expect("contract.json is not valid JSON")
+}
+
+fn methods(contract: &Value) -> &Map<String, Value> {
+    contract["methods"]
+        .as_object()
+        .expect("methods is an object")
+}
+
+struct Client {
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+    next_id: u64,
+}
+
+impl Client {
+    fn connect(server: &Server) -> Client {
+        let stream = TcpStream::connect(&server.address).expect("cannot connect");
+        Client {
+            writer: stream.try_clone().unwrap(),
+            reader: BufReader::new(stream),
+            next_id: 0,
+        }
+    }
+
+    /// Sends a request and returns the whole response.
+    fn call(&mut self, method: &str, params: &Value) -> Value {
+        self.next_id += 1;
+        let request =
+            json!({ "jsonrpc": "2.0", "id": self.next_id, "method": method, "params": params });
+        writeln!(self.writer, "{request}").unwrap();
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        let response: Value = serde_json::from_str(&line).expect("the response is not JSON");
+        assert_eq!(response["jsonrpc"], "2.0");
+        assert_eq!(response["id"], self.next_id);
+        response
+    }
+}
+
+/// A value of the wrong type for a param the contract says is `kind`.
+fn wrong_type(kind: &str) -> Value {
+    match kind {
+        "integer" => json!("7"),
+        "string" => json!(7),
+        other => panic!("contract.json uses a type this test doesn't know: {other}"),
+    }
+}
+
+#[test]
+fn the_server_has_the_contracts_methods() {
+    let contract = contract();
+    let listed: BTreeSet<&str> = methods(&contract).keys().map(String::as_str).collect();
+    assert_eq!(listed, BTreeSet::from(METHODS));
+
+    let server = Server::start();
+    let mut client = Client::connect(&server);
+    let response = client.call("no_such_method", &json!({}));
+    assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+}
+
+#[test]
+fn every_example_is_answered_as_written() {
+    let contract = contract();
+    let server = Server::start();
+    let mut client = Client::connect(&server);
+    for (method, spec) in methods(&contract) {
+        let allowed = spec["errors"].as_array().unwrap();
+        for example in spec["examples"].as_array().unwrap() {
+            let response = client.call(method, &example["params"]);
+            let context = format!("{method}({})", example["params"]);
+            if let Some(error) = example.get("error") {
+                assert_eq!(&response["error"], error, "{context}");
+                assert!(
+                    allowed.contains(&error["code"]),
+                    "{context}: code not listed in errors"
+                );
+                assert!(contract["errors"].get(error["code"].to_string()).is_some());
+            } else {
+                assert_eq!(response["result"], example["result"], "{context}");
+                assert!(response.get("error").is_none(), "{context}");
+            }
+        }
+    }
+}
+
+#[test]
+fn params_of_the_wrong_shape_are_refused() {
+    let contract = contract();
+    let server = Server::start();
+    let mut client = Client::connect(&server);
+    for (method, spec) in methods(&contract) {
+        let params = spec["params"].as_object().unwrap();
+        let valid = &spec["examples"][0]["params"];
+        for (name, kind) in params {
+            let mut wrong = valid.clone();
+            wrong[name] = wrong_type(kind.as_str().unwrap());
+            let response = client.call(method, &wrong);
+            assert_eq!(
+                response["error"]["code"], INVALID_PARAMS,
+                "{method}({wrong})"
+            );
+
+            let mut missing = valid.clone();
+            missing.as_object_mut().unwrap().remove(name);
+            let response = client.call(method, &missing);
+            assert_eq!(
+                response["error"]["code"], INVALID_PARAMS,
+                "{method}({missing})"
+            );
+        }
+        let mut extra = valid.clone();
+        extra["unexpected"] = json!(1);
+        let response = client.call(method, &extra);
+        assert_eq!(
+            response["error"]["code"], INVALID_PARAMS,
+            "{method}({extra})"
+        );
+    }
+}
+
+#[test]
+fn connections_are_served_at_the_same_time() {
+    let server = Server::start();
+    let mut first = Client::connect(&server);
+    let mut second = Client::connect(&server);
+    // The first connection is still open while the second is answered
+    assert_eq!(first.call("add", &json!({ "a": 1, "b": 2 }))["result"], 3);
+    assert_eq!(second.call("add", &json!({ "a": 3, "b": 4 }))["result"], 7);
+    assert_eq!(first.call("add", &json!({ "a": 5, "b": 6 }))["result"], 11);
+}
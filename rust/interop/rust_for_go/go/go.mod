@@ -0,0 +1,4 @@
+module rustforgo
+
+// unsafe.StringData and unsafe.SliceData need Go 1.20 or newer
+go 1.21
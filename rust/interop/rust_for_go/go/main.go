@@ -0,0 +1,43 @@
+// Rust-Go Interop: Calling a Rust Library from Go
+// Demonstrates using Rust functions through the wrappers in rustlib.go
+//
+// From the rust/ directory:
+//     cargo build --release -p rust-for-go
+//     cd interop/rust_for_go/go && go run .
+
+package main
+
+import "fmt"
+
+func main() {
+    sentence := "Go and Rust can share a process"
+
+    // Strings: borrowed on the way in, copied on the way out
+    fmt.Println("words:", WordCount(sentence))
+    fmt.Println("reversed:", ReverseWords(sentence))
+    fmt.Printf("empty string reversed: %q\n", ReverseWords(""))
+
+    // Slices
+    fmt.Println("sum:", Sum([]int64{10, 20, 30, 40}))
+    fmt.Println("sum of nil:", Sum(nil))
+    fmt.Println("primes below 30:", PrimesBelow(30))
+
+    // Errors: Rust returns a status code, the wrapper returns a Go error
+    for _, text := range []string{"1234", " -7 ", "twelve", "\xff"} {
+        value, err := ParseInt(text)
+        if err != nil {
+            fmt.Printf("ParseInt(%q): error: %v\n", text, err)
+        } else {
+            fmt.Printf("ParseInt(%q) = %d\n", text, value)
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - cgo lets Go call any library with a C interface, including Rust's
+ * - The Rust crate is a cdylib with extern "C" functions and a C header
+ * - Go lends strings/slices as pointer + length; Rust must not keep them
+ * - Rust-owned results are copied into Go, then freed by Rust
+ * - Status codes become Go errors in the wrapper layer
+ */
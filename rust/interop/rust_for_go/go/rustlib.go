@@ -0,0 +1,103 @@
+// Go wrappers around the Rust library in ../src/lib.rs.
+// Demonstrates cgo: calling C-compatible functions and marshalling data
+//
+// Build the Rust library first, from the rust/ directory:
+//     cargo build --release -p rust-for-go
+// The #cgo lines below point at cargo's output (rust/target/release).
+
+package main
+
+/*
+#cgo CFLAGS: -I${SRCDIR}/../include
+#cgo LDFLAGS: -L${SRCDIR}/../../../target/release -lrust_for_go
+#cgo linux LDFLAGS: -Wl,-rpath,${SRCDIR}/../../../target/release
+#cgo darwin LDFLAGS: -Wl,-rpath,${SRCDIR}/../../../target/release
+#include "rust_for_go.h"
+*/
+import "C"
+
+import (
+    "errors"
+    "unsafe"
+)
+
+// ===== Marshalling helpers =====
+
+// strArg lends a Go string to Rust without copying it. This is allowed
+// because the bytes contain no Go pointers and Rust doesn't keep them
+// after the call returns.
+func strArg(s string) (*C.uint8_t, C.size_t) {
+    return (*C.uint8_t)(unsafe.Pointer(unsafe.StringData(s))), C.size_t(len(s))
+}
+
+// int64sArg lends a Go slice to Rust the same way. A nil or empty slice
+// sends a nil pointer with length 0, which the Rust side handles.
+func int64sArg(values []int64) (*C.int64_t, C.size_t) {
+    return (*C.int64_t)(unsafe.Pointer(unsafe.SliceData(values))), C.size_t(len(values))
+}
+
+// takeString copies a Rust-owned string into Go memory, then gives the
+// original back to Rust. Go's garbage collector knows nothing about
+// memory Rust allocated, so it must never be freed any other way.
+func takeString(s C.RfgString) string {
+    defer C.rfg_string_free(s)
+    return C.GoStringN((*C.char)(unsafe.Pointer(s.ptr)), C.int(s.len))
+}
+
+// takeUint64s copies a Rust-owned list into a Go slice, then frees it.
+func takeUint64s(v C.RfgVecU64) []uint64 {
+    defer C.rfg_vec_u64_free(v)
+    if v.len == 0 {
+        return nil
+    }
+    view := unsafe.Slice((*uint64)(unsafe.Pointer(v.ptr)), int(v.len))
+    return append([]uint64(nil), view...)
+}
+
+// ===== The Go-friendly API =====
+
+var (
+    ErrInvalidUTF8 = errors.New("text is not valid UTF-8")
+    ErrParse       = errors.New("not an integer")
+    ErrRustPanic   = errors.New("the Rust library panicked")
+)
+
+// WordCount counts whitespace-separated words.
+func WordCount(text string) int {
+    ptr, n := strArg(text)
+    return int(C.rfg_word_count(ptr, n))
+}
+
+// ReverseWords returns the words of text in reverse order.
+func ReverseWords(text string) string {
+    ptr, n := strArg(text)
+    return takeString(C.rfg_reverse_words(ptr, n))
+}
+
+// Sum adds up the values.
+func Sum(values []int64) int64 {
+    ptr, n := int64sArg(values)
+    return int64(C.rfg_sum(ptr, n))
+}
+
+// PrimesBelow lists the primes smaller than limit.
+func PrimesBelow(limit uint64) []uint64 {
+    return takeUint64s(C.rfg_primes_below(C.uint64_t(limit)))
+}
+
+// ParseInt parses a decimal integer, turning Rust's status codes into Go
+// errors.
+func ParseInt(text string) (int64, error) {
+    ptr, n := strArg(text)
+    var out C.int64_t
+    switch C.rfg_parse_int(ptr, n, &out) {
+    case C.RFG_OK:
+        return int64(out), nil
+    case C.RFG_INVALID_UTF8:
+        return 0, ErrInvalidUTF8
+    case C.RFG_PARSE_ERROR:
+        return 0, ErrParse
+    default:
+        return 0, ErrRustPanic
+    }
+}
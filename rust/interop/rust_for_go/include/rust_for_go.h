@@ -0,0 +1,40 @@
+/* C declarations for the functions in ../src/lib.rs, for cgo to read.
+ * Written by hand to match the Rust signatures exactly; cbindgen can
+ * generate this file instead for bigger libraries. */
+#ifndef RUST_FOR_GO_H
+#define RUST_FOR_GO_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define RFG_OK 0
+#define RFG_INVALID_UTF8 1
+#define RFG_PARSE_ERROR 2
+#define RFG_NULL_POINTER 3
+#define RFG_PANIC 4
+
+/* Owned by Rust. Copy it, then give it back with the matching _free. */
+typedef struct {
+    uint8_t *ptr;
+    size_t len;
+    size_t cap;
+} RfgString;
+
+typedef struct {
+    uint64_t *ptr;
+    size_t len;
+    size_t cap;
+} RfgVecU64;
+
+/* Strings go in as (pointer, length): Go strings have no NUL terminator */
+size_t rfg_word_count(const uint8_t *text, size_t len);
+RfgString rfg_reverse_words(const uint8_t *text, size_t len);
+void rfg_string_free(RfgString text);
+
+int64_t rfg_sum(const int64_t *values, size_t len);
+RfgVecU64 rfg_primes_below(uint64_t limit);
+void rfg_vec_u64_free(RfgVecU64 values);
+
+int32_t rfg_parse_int(const uint8_t *text, size_t len, int64_t *out);
+
+#endif
@@ -0,0 +1,219 @@
+//! Functions for the Go program in `go/`, exported with a C interface.
+//!
+//! cgo only speaks C, so everything here is `extern "C"` and
+//! `#[no_mangle]`, and only C-compatible types cross the boundary. The C
+//! declarations Go sees are in `include/rust_for_go.h`; the Go-friendly
+//! wrappers around them are in `go/rustlib.go`.
+//!
+//! Strings and slices travel as pointer + length pairs, using the helpers
+//! in [`marshal`]. No function lets a panic unwind into Go: [`guard`]
+//! catches it and returns a fallback value instead.
+
+pub mod marshal;
+
+use std::panic::{self, UnwindSafe};
+
+use marshal::{borrow_slice, borrow_str, RfgString, RfgVecU64};
+
+/// Status codes for functions that can fail. Mirrored in the header.
+pub const RFG_OK: i32 = 0;
+pub const RFG_INVALID_UTF8: i32 = 1;
+pub const RFG_PARSE_ERROR: i32 = 2;
+pub const RFG_NULL_POINTER: i32 = 3;
+pub const RFG_PANIC: i32 = 4;
+
+/// Runs `body`, returning `fallback` if it panics. A panic unwinding into
+/// Go's stack would abort the whole Go program.
+fn guard<T>(fallback: T, body: impl FnOnce() -> T + UnwindSafe) -> T {
+    panic::catch_unwind(body).unwrap_or(fallback)
+}
+
+/// Counts the whitespace-separated words in a UTF-8 string. Invalid UTF-8
+/// counts as no words.
+///
+/// # Safety
+///
+/// `text` must point at `len` readable bytes (any pointer if `len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn rfg_word_count(text: *const u8, len: usize) -> usize {
+    guard(0, || {
+        // SAFETY: the caller promised `len` readable bytes
+        unsafe { borrow_str(text, len) }.map_or(0, |text| text.split_whitespace().count())
+    })
+}
+
+/// Returns the words of `text` in reverse order, joined by single spaces.
+/// Go owns the result and must free it with [`rfg_string_free`].
+///
+/// # Safety
+///
+/// Same as [`rfg_word_count`].
+#[no_mangle]
+pub unsafe extern "C" fn rfg_reverse_words(text: *const u8, len: usize) -> RfgString {
+    guard(RfgString::from(String::new()), || {
+        // SAFETY: the caller promised `len` readable bytes
+        let text = unsafe { borrow_slice(text, len) };
+        // Invalid UTF-8 is replaced with U+FFFD rather than rejected
+        let text = String::from_utf8_lossy(text);
+        let reversed: Vec<&str> = text.split_whitespace().rev().collect();
+        RfgString::from(reversed.join(" "))
+    })
+}
+
+/// Frees a string returned by this library.
+///
+/// # Safety
+///
+/// `text` must come from this library, and be freed only once.
+#[no_mangle]
+pub unsafe extern "C" fn rfg_string_free(text: RfgString) {
+    // SAFETY: the caller promised it came from us and is freed once
+    drop(unsafe { text.into_string() });
+}
+
+/// Adds up `len` integers, wrapping on overflow (like Go's `+`).
+///
+/// # Safety
+///
+/// `values` must point at `len` initialized `int64_t`s (any pointer if
+/// `len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn rfg_sum(values: *const i64, len: usize) -> i64 {
+    guard(0, || {
+        // SAFETY: the caller promised `len` readable values
+        let values = unsafe { borrow_slice(values, len) };
+        values
+            .iter()
+            .fold(0i64, |total, &value| total.wrapping_add(value))
+    })
+}
+
+/// Every prime below `limit`, in order, found with a sieve. Go owns the
+/// result and must free it with [`rfg_vec_u64_free`].
+#[no_mangle]
+pub extern "C" fn rfg_primes_below(limit: u64) -> RfgVecU64 {
+    guard(RfgVecU64::from(Vec::new()), || {
+        let limit = limit as usize;
+        let mut is_prime = vec![true; limit];
+        let mut primes = Vec::new();
+        for n in 2..limit {
+            if is_prime[n] {
+                primes.push(n as u64);
+                for multiple in (n * n..limit).step_by(n) {
+                    is_prime[multiple] = false;
+                }
+            }
+        }
+        RfgVecU64::from(primes)
+    })
+}
+
+/// Frees a list returned by this library.
+///
+/// # Safety
+///
+/// `values` must come from this library, and be freed only once.
+#[no_mangle]
+pub unsafe extern "C" fn rfg_vec_u64_free(values: RfgVecU64) {
+    // SAFETY: the caller promised it came from us and is freed once
+    drop(unsafe { values.into_vec() });
+}
+
+/// Parses a decimal integer, writing it to `*out`. Returns [`RFG_OK`] or
+/// one of the error codes, which Go turns into an `error`.
+///
+/// # Safety
+///
+/// `text` as in [`rfg_word_count`]; `out` must be a valid pointer, or null.
+#[no_mangle]
+pub unsafe extern "C" fn rfg_parse_int(text: *const u8, len: usize, out: *mut i64) -> i32 {
+    if out.is_null() {
+        return RFG_NULL_POINTER;
+    }
+    guard(RFG_PANIC, || {
+        // SAFETY: the caller promised `len` readable bytes
+        let Ok(text) = (unsafe { borrow_str(text, len) }) else {
+            return RFG_INVALID_UTF8;
+        };
+        match text.trim().parse::<i64>() {
+            Ok(value) => {
+                // SAFETY: checked non-null above; the caller promised valid
+                unsafe { out.write(value) };
+                RFG_OK
+            }
+            Err(_) => RFG_PARSE_ERROR,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_borrowed_in_and_owned_out() {
+        let text = "go calls rust";
+        unsafe {
+            assert_eq!(rfg_word_count(text.as_ptr(), text.len()), 3);
+            let reversed = rfg_reverse_words(text.as_ptr(), text.len());
+            assert_eq!(reversed.into_string(), "rust calls go");
+        }
+    }
+
+    #[test]
+    fn empty_input_never_touches_the_pointer() {
+        // A nil Go slice arrives as a null pointer with length 0
+        unsafe {
+            assert_eq!(rfg_word_count(std::ptr::null(), 0), 0);
+            assert_eq!(rfg_sum(std::ptr::null(), 0), 0);
+            rfg_string_free(rfg_reverse_words(std::ptr::null(), 0));
+        }
+    }
+
+    #[test]
+    fn invalid_utf8_is_handled() {
+        let bytes = b"ok \xff";
+        let mut out = 0;
+        unsafe {
+            assert_eq!(rfg_word_count(bytes.as_ptr(), bytes.len()), 0);
+            assert_eq!(
+                rfg_reverse_words(bytes.as_ptr(), bytes.len()).into_string(),
+                "\u{FFFD} ok"
+            );
+            assert_eq!(
+                rfg_parse_int(bytes.as_ptr(), bytes.len(), &mut out),
+                RFG_INVALID_UTF8
+            );
+        }
+    }
+
+    #[test]
+    fn sums_wrap_like_go() {
+        let values = [i64::MAX, 1, 5];
+        assert_eq!(
+            unsafe { rfg_sum(values.as_ptr(), values.len()) },
+            i64::MIN + 5
+        );
+    }
+
+    #[test]
+    fn primes_round_trip() {
+        let primes = unsafe { rfg_primes_below(20).into_vec() };
+        assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19]);
+        assert!(unsafe { rfg_primes_below(0).into_vec() }.is_empty());
+    }
+
+    #[test]
+    fn parse_int_reports_errors() {
+        let mut out = 0;
+        unsafe {
+            assert_eq!(rfg_parse_int(" -42 ".as_ptr(), 5, &mut out), RFG_OK);
+            assert_eq!(out, -42);
+            assert_eq!(rfg_parse_int("4x".as_ptr(), 2, &mut out), RFG_PARSE_ERROR);
+            assert_eq!(
+                rfg_parse_int("1".as_ptr(), 1, std::ptr::null_mut()),
+                RFG_NULL_POINTER
+            );
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Helpers for moving strings and slices between Go and Rust.
+//!
+//! Going IN (Go to Rust), data is borrowed: Go passes a pointer and a
+//! length, Rust reads it during the call and keeps nothing. Go strings are
+//! not NUL-terminated, so there are no C strings involved at all.
+//!
+//! Coming OUT (Rust to Go), data is owned: Rust returns an [`RfgString`]
+//! or [`RfgVecU64`] that it allocated, Go copies it into Go memory, and
+//! then hands it back to the matching `rfg_*_free` function so Rust's
+//! allocator frees it.
+
+use std::str::Utf8Error;
+
+/// Views borrowed bytes as a slice.
+///
+/// Go's empty strings and nil slices may come with a null (or otherwise
+/// unusable) pointer, but `std::slice::from_raw_parts` needs a non-null,
+/// aligned pointer even for length 0, so length 0 never touches `ptr`.
+///
+/// # Safety
+///
+/// If `len > 0`, `ptr` must point at `len` initialized values that stay
+/// unchanged for the lifetime `'a`.
+pub unsafe fn borrow_slice<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if len == 0 {
+        &[]
+    } else {
+        // SAFETY: passed on to the caller; see the contract above
+        unsafe { std::slice::from_raw_parts(ptr, len) }
+    }
+}
+
+/// Views borrowed bytes as a `&str`, checking they are UTF-8.
+///
+/// Go strings are USUALLY UTF-8, but Go doesn't enforce it.
+///
+/// # Safety
+///
+/// Same as [`borrow_slice`].
+pub unsafe fn borrow_str<'a>(ptr: *const u8, len: usize) -> Result<&'a str, Utf8Error> {
+    // SAFETY: same contract
+    std::str::from_utf8(unsafe { borrow_slice(ptr, len) })
+}
+
+/// A string owned by Rust, handed to Go. Go must pass it to
+/// `rfg_string_free` exactly once, after copying it.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RfgString {
+    pub ptr: *mut u8,
+    pub len: usize,
+    /// Needed to give the allocation back to Rust; Go never reads it.
+    pub cap: usize,
+}
+
+impl From<String> for RfgString {
+    fn from(text: String) -> Self {
+        // ManuallyDrop: the String must not free its buffer here, because
+        // Go is going to read it
+        let mut text = std::mem::ManuallyDrop::new(text);
+        RfgString {
+            ptr: text.as_mut_ptr(),
+            len: text.len(),
+            cap: text.capacity(),
+        }
+    }
+}
+
+impl RfgString {
+    /// Turns this back into the String it came from.
+    ///
+    /// # Safety
+    ///
+    /// `self` must come from `RfgString::from`, and not be used again.
+    pub unsafe fn into_string(self) -> String {
+        // SAFETY: the three parts came from a String, unchanged
+        unsafe { String::from_raw_parts(self.ptr, self.len, self.cap) }
+    }
+}
+
+/// A list of numbers owned by Rust, handed to Go. Go must pass it to
+/// `rfg_vec_u64_free` exactly once, after copying it.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RfgVecU64 {
+    pub ptr: *mut u64,
+    pub len: usize,
+    pub cap: usize,
+}
+
+impl From<Vec<u64>> for RfgVecU64 {
+    fn from(values: Vec<u64>) -> Self {
+        let mut values = std::mem::ManuallyDrop::new(values);
+        RfgVecU64 {
+            ptr: values.as_mut_ptr(),
+            len: values.len(),
+            cap: values.capacity(),
+        }
+    }
+}
+
+impl RfgVecU64 {
+    /// Turns this back into the Vec it came from.
+    ///
+    /// # Safety
+    ///
+    /// `self` must come from `RfgVecU64::from`, and not be used again.
+    pub unsafe fn into_vec(self) -> Vec<u64> {
+        // SAFETY: the three parts came from a Vec, unchanged
+        unsafe { Vec::from_raw_parts(self.ptr, self.len, self.cap) }
+    }
+}
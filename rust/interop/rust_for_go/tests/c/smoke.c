@@ -0,0 +1,37 @@
+/* Calls every function in include/rust_for_go.h the way cgo does: strings
+ * as (pointer, length) with no NUL terminator, owned results freed by the
+ * library. Checks the header matches the Rust code, without needing Go. */
+#include <stdio.h>
+#include <string.h>
+
+#include "rust_for_go.h"
+
+int main(void) {
+    const char *text = "one two three";
+    const uint8_t *bytes = (const uint8_t *)text;
+
+    printf("words: %zu\n", rfg_word_count(bytes, strlen(text)));
+
+    RfgString reversed = rfg_reverse_words(bytes, strlen(text));
+    printf("reversed: %.*s\n", (int)reversed.len, (const char *)reversed.ptr);
+    rfg_string_free(reversed);
+
+    int64_t values[] = {1, 2, 3};
+    printf("sum: %lld\n", (long long)rfg_sum(values, 3));
+    printf("sum of nothing: %lld\n", (long long)rfg_sum(NULL, 0));
+
+    RfgVecU64 primes = rfg_primes_below(12);
+    printf("primes:");
+    for (size_t i = 0; i < primes.len; i++) {
+        printf(" %llu", (unsigned long long)primes.ptr[i]);
+    }
+    printf("\n");
+    rfg_vec_u64_free(primes);
+
+    int64_t parsed = 0;
+    int32_t status = rfg_parse_int((const uint8_t *)"99", 2, &parsed);
+    printf("parse 99: status=%d value=%lld\n", status, (long long)parsed);
+    status = rfg_parse_int((const uint8_t *)"x", 1, &parsed);
+    printf("parse x: status=%d\n", status);
+    return 0;
+}
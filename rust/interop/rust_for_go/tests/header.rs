@@ -0,0 +1,63 @@
+// Compiles tests/c/smoke.c against the cdylib and include/rust_for_go.h,
+// so a mismatch between the header and the Rust code is caught without a
+// Go toolchain. Needs a C compiler (`cc`) on the PATH.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Builds the shared library and returns the directory it is in.
+///
+/// `cargo test` only builds the Rust (rlib) form of the library, so build
+/// the cdylib separately, in its own target directory: the outer cargo
+/// still holds the lock on the normal one.
+fn build_cdylib() -> PathBuf {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cdylib");
+    let built = Command::new(env!("CARGO"))
+        .args(["build", "--quiet", "--lib", "--manifest-path"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("cannot run cargo");
+    assert!(built.success(), "building the cdylib failed");
+    target_dir.join("debug")
+}
+
+#[test]
+#[cfg(unix)]
+fn header_matches_the_library() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = build_cdylib();
+    let program = lib_dir.join("rust_for_go_smoke");
+
+    let compiled = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(crate_dir.join("tests/c/smoke.c"))
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-lrust_for_go")
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .expect("cannot run the C compiler");
+    assert!(compiled.success(), "tests/c/smoke.c failed to compile");
+
+    let output = Command::new(&program)
+        .output()
+        .expect("cannot run the smoke test");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "words: 3\n\
+         reversed: three two one\n\
+         sum: 6\n\
+         sum of nothing: 0\n\
+         primes: 2 3 5 7 11\n\
+         parse 99: status=0 value=99\n\
+         parse x: status=2\n"
+    );
+}
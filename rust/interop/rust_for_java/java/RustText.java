@@ -0,0 +1,73 @@
+// Rust-Java Interop: Native Methods Implemented in Rust
+// Demonstrates calling Rust from Java through JNI
+//
+// A `native` method has no Java body: the JVM looks for a C function
+// named Java_<class>_<method> in a loaded library. Here those functions
+// are written in Rust, in ../src/lib.rs.
+
+import java.util.Arrays;
+
+public class RustText {
+    // Loads librust_for_java.so (or .dylib / .dll) from java.library.path
+    static {
+        System.loadLibrary("rust_for_java");
+    }
+
+    // ===== Native methods (implemented in Rust) =====
+
+    // Strings: Java's UTF-16 strings are converted for Rust and back
+    static native String reverseWords(String text);
+
+    // Arrays: Rust copies the elements out, or writes them back
+    static native long sum(int[] values);
+    static native int[] primesBelow(int limit);
+    static native void doubleAll(int[] values);
+
+    // Exceptions: Rust throws IllegalArgumentException for bad input
+    static native int parsePositive(String text);
+
+    // Panics: a Rust panic turns into a RuntimeException
+    static native int divide(int a, int b);
+
+    public static void main(String[] args) {
+        System.out.println("reversed: " + reverseWords("Java calls into Rust"));
+
+        int[] numbers = {3, 1, 4, 1, 5};
+        System.out.println("sum: " + sum(numbers));
+        doubleAll(numbers);
+        System.out.println("doubled: " + Arrays.toString(numbers));
+        System.out.println("primes below 20: " + Arrays.toString(primesBelow(20)));
+
+        for (String text : new String[] {"42", "-3", "many"}) {
+            try {
+                System.out.println("parsePositive(\"" + text + "\") = " + parsePositive(text));
+            } catch (IllegalArgumentException e) {
+                System.out.println("parsePositive(\"" + text + "\") threw: " + e.getMessage());
+            }
+        }
+
+        try {
+            reverseWords(null);
+        } catch (NullPointerException e) {
+            System.out.println("reverseWords(null) threw: " + e.getMessage());
+        }
+
+        System.out.println("divide(7, 2) = " + divide(7, 2));
+        try {
+            divide(1, 0);
+        } catch (RuntimeException e) {
+            System.out.println("divide(1, 0) threw: " + e.getMessage());
+        }
+    }
+}
+
+/*
+ * Key Concepts:
+ * - `native` methods are implemented outside Java, here in Rust
+ * - System.loadLibrary finds the library on java.library.path
+ * - The Rust function name encodes the class and method: Java_RustText_sum
+ * - Exceptions thrown from Rust surface as ordinary Java exceptions
+ *
+ * To compile: javac -d /tmp/jni RustText.java
+ * To run: java -Djava.library.path=<rust/target/debug> -cp /tmp/jni RustText
+ */
@@ -0,0 +1,212 @@
+//! Native methods for `java/RustText.java`, implemented with the `jni`
+//! crate.
+//!
+//! The JVM finds each method by its symbol name: `RustText.sum(int[])`
+//! becomes `Java_RustText_sum`. Every function takes a [`JNIEnv`] (the
+//! handle for talking to the JVM) and the class it was called on, then the
+//! Java arguments.
+//!
+//! Errors never cross as Rust values. Instead a function throws a Java
+//! exception with [`JNIEnv::throw_new`] and returns a placeholder value,
+//! which Java ignores because the exception is already on its way. The
+//! [`run`] helper does this for every method, and also turns panics into a
+//! `RuntimeException`, since unwinding into the JVM would abort it.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use jni::objects::{JClass, JIntArray, JObject, JString};
+use jni::sys::{jint, jintArray, jlong, jstring};
+use jni::JNIEnv;
+
+/// Why a native method couldn't return normally.
+enum Failure {
+    /// Throw a new exception of this class (e.g. `java/lang/IllegalArgumentException`).
+    Throw {
+        class: &'static str,
+        message: String,
+    },
+    /// A JNI call failed. If it failed because Java threw, the exception
+    /// is already pending and just needs to propagate.
+    Jni(jni::errors::Error),
+}
+
+impl From<jni::errors::Error> for Failure {
+    fn from(error: jni::errors::Error) -> Self {
+        Failure::Jni(error)
+    }
+}
+
+type NativeResult<T> = Result<T, Failure>;
+
+fn illegal_argument(message: impl Into<String>) -> Failure {
+    Failure::Throw {
+        class: "java/lang/IllegalArgumentException",
+        message: message.into(),
+    }
+}
+
+/// Runs a native method body, converting failures and panics into Java
+/// exceptions. `placeholder` is returned to Java when something is thrown.
+fn run<'local, T>(
+    env: &mut JNIEnv<'local>,
+    placeholder: T,
+    body: impl FnOnce(&mut JNIEnv<'local>) -> NativeResult<T>,
+) -> T {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| body(env)));
+    let (class, message) = match outcome {
+        Ok(Ok(value)) => return value,
+        // Java already threw (e.g. OutOfMemoryError); let it propagate
+        Ok(Err(Failure::Jni(jni::errors::Error::JavaException))) => return placeholder,
+        Ok(Err(Failure::Jni(error))) => ("java/lang/RuntimeException", error.to_string()),
+        Ok(Err(Failure::Throw { class, message })) => (class, message),
+        Err(payload) => {
+            let reason = payload
+                .downcast_ref::<&str>()
+                .map(|reason| reason.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown cause".to_string());
+            (
+                "java/lang/RuntimeException",
+                format!("Rust panicked: {reason}"),
+            )
+        }
+    };
+    // If even throwing fails there is nothing more we can do
+    let _ = env.throw_new(class, message);
+    placeholder
+}
+
+/// Converts a Java `String` argument, throwing NullPointerException for null.
+fn read_string(env: &mut JNIEnv, text: &JString) -> NativeResult<String> {
+    if text.is_null() {
+        return Err(Failure::Throw {
+            class: "java/lang/NullPointerException",
+            message: "text is null".to_string(),
+        });
+    }
+    // Java strings are UTF-16 (stored as "modified UTF-8" by JNI);
+    // get_string converts to an ordinary Rust String
+    Ok(env.get_string(text)?.into())
+}
+
+/// Copies a Java `int[]` into a Vec.
+fn read_ints(env: &mut JNIEnv, values: &JIntArray) -> NativeResult<Vec<i32>> {
+    if values.is_null() {
+        return Err(Failure::Throw {
+            class: "java/lang/NullPointerException",
+            message: "array is null".to_string(),
+        });
+    }
+    let len = env.get_array_length(values)? as usize;
+    let mut buffer = vec![0; len];
+    env.get_int_array_region(values, 0, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// `static native String reverseWords(String text)`
+#[no_mangle]
+pub extern "system" fn Java_RustText_reverseWords<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    text: JString<'local>,
+) -> jstring {
+    run(&mut env, JObject::null().into_raw(), |env| {
+        let text = read_string(env, &text)?;
+        let reversed: Vec<&str> = text.split_whitespace().rev().collect();
+        // new_string creates a Java String; into_raw hands the reference
+        // to the JVM as the return value
+        Ok(env.new_string(reversed.join(" "))?.into_raw())
+    })
+}
+
+/// `static native long sum(int[] values)`
+#[no_mangle]
+pub extern "system" fn Java_RustText_sum<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    values: JIntArray<'local>,
+) -> jlong {
+    run(&mut env, 0, |env| {
+        let values = read_ints(env, &values)?;
+        Ok(values.iter().map(|&value| value as i64).sum())
+    })
+}
+
+/// `static native int[] primesBelow(int limit)`
+#[no_mangle]
+pub extern "system" fn Java_RustText_primesBelow<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    limit: jint,
+) -> jintArray {
+    run(&mut env, JObject::null().into_raw(), |env| {
+        let limit = usize::try_from(limit).map_err(|_| illegal_argument("limit is negative"))?;
+        let mut is_prime = vec![true; limit];
+        let mut primes = Vec::new();
+        for n in 2..limit {
+            if is_prime[n] {
+                primes.push(n as jint);
+                for multiple in (n * n..limit).step_by(n) {
+                    is_prime[multiple] = false;
+                }
+            }
+        }
+        // Allocate a Java array, then copy the Rust values into it
+        let array = env.new_int_array(primes.len() as jint)?;
+        env.set_int_array_region(&array, 0, &primes)?;
+        Ok(array.into_raw())
+    })
+}
+
+/// `static native void doubleAll(int[] values)`: changes the Java array.
+#[no_mangle]
+pub extern "system" fn Java_RustText_doubleAll<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    values: JIntArray<'local>,
+) {
+    run(&mut env, (), |env| {
+        // Rust works on a copy; set_int_array_region writes it back.
+        // Java arrays live on the JVM's garbage-collected heap, so Rust
+        // never holds a plain pointer into them.
+        let mut copy = read_ints(env, &values)?;
+        for value in &mut copy {
+            *value = value.wrapping_mul(2);
+        }
+        env.set_int_array_region(&values, 0, &copy)?;
+        Ok(())
+    })
+}
+
+/// `static native int parsePositive(String text)`: throws
+/// IllegalArgumentException unless `text` is a positive integer.
+#[no_mangle]
+pub extern "system" fn Java_RustText_parsePositive<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    text: JString<'local>,
+) -> jint {
+    run(&mut env, 0, |env| {
+        let text = read_string(env, &text)?;
+        match text.trim().parse::<jint>() {
+            Ok(value) if value > 0 => Ok(value),
+            Ok(value) => Err(illegal_argument(format!("{value} is not positive"))),
+            Err(error) => Err(illegal_argument(format!(
+                "{text:?} is not a number: {error}"
+            ))),
+        }
+    })
+}
+
+/// `static native int divide(int a, int b)`: integer division, which
+/// panics in Rust when `b` is 0. [`run`] turns the panic into a
+/// RuntimeException.
+#[no_mangle]
+pub extern "system" fn Java_RustText_divide<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    a: jint,
+    b: jint,
+) -> jint {
+    run(&mut env, 0, |_env| Ok(a / b))
+}
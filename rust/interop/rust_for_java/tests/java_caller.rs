@@ -0,0 +1,64 @@
+// Compiles java/RustText.java, runs it against this crate's shared library,
+// and checks what it prints. Needs a JDK (`javac` and `java`) on the PATH.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Builds the shared library and returns the directory it is in.
+///
+/// `cargo test` doesn't build the cdylib for an integration test, so build
+/// it separately, in its own target directory: the outer cargo still holds
+/// the lock on the normal one.
+fn build_cdylib() -> PathBuf {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cdylib");
+    let built = Command::new(env!("CARGO"))
+        .args(["build", "--quiet", "--lib", "--manifest-path"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("cannot run cargo");
+    assert!(built.success(), "building the cdylib failed");
+    target_dir.join("debug")
+}
+
+#[test]
+fn java_calls_the_native_methods() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = build_cdylib();
+    let classes = Path::new(env!("CARGO_TARGET_TMPDIR")).join("java-classes");
+
+    let compiled = Command::new("javac")
+        .arg("-d")
+        .arg(&classes)
+        .arg(crate_dir.join("java/RustText.java"))
+        .status()
+        .expect("cannot run javac");
+    assert!(compiled.success(), "RustText.java failed to compile");
+
+    let output = Command::new("java")
+        .arg(format!("-Djava.library.path={}", lib_dir.display()))
+        .arg("-cp")
+        .arg(&classes)
+        .arg("RustText")
+        .output()
+        .expect("cannot run java");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "reversed: Rust into calls Java\n\
+         sum: 14\n\
+         doubled: [6, 2, 8, 2, 10]\n\
+         primes below 20: [2, 3, 5, 7, 11, 13, 17, 19]\n\
+         parsePositive(\"42\") = 42\n\
+         parsePositive(\"-3\") threw: -3 is not positive\n\
+         parsePositive(\"many\") threw: \"many\" is not a number: invalid digit found in string\n\
+         reverseWords(null) threw: text is null\n\
+         divide(7, 2) = 3\n\
+         divide(1, 0) threw: Rust panicked: attempt to divide by zero\n"
+    );
+}
@@ -0,0 +1,51 @@
+//! `cargo run -p chat --bin client [ADDRESS]` (default: 127.0.0.1:8080).
+//!
+//! Prints what the server sends and sends what you type, both at once:
+//! the same `select!` loop as the server's, from the other side.
+
+use std::process;
+
+use tokio::io;
+use tokio::net::TcpStream;
+
+use chat::frame::{write_line, LineReader};
+
+#[tokio::main]
+async fn main() {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| chat::DEFAULT_ADDR.to_string());
+    if let Err(err) = chat(&addr).await {
+        eprintln!("{addr}: {err}");
+        process::exit(1);
+    }
+}
+
+async fn chat(addr: &str) -> io::Result<()> {
+    let stream = TcpStream::connect(addr).await?;
+    let (reader, mut writer) = stream.into_split();
+    let mut server = LineReader::new(reader);
+    let mut keyboard = LineReader::new(io::stdin());
+    let mut typing = true;
+
+    loop {
+        tokio::select! {
+            line = server.next_line() => match line? {
+                Some(line) => println!("{line}"),
+                None => {
+                    println!("The server closed the connection.");
+                    return Ok(());
+                }
+            },
+            // Once stdin is closed (Ctrl+D), only listen until the server
+            // hangs up.
+            line = keyboard.next_line(), if typing => match line? {
+                Some(line) => write_line(&mut writer, &line).await?,
+                None => {
+                    typing = false;
+                    write_line(&mut writer, "/quit").await?;
+                }
+            },
+        }
+    }
+}
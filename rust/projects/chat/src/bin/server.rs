@@ -0,0 +1,87 @@
+//! `cargo run -p chat --bin server [ADDRESS]` (default: 127.0.0.1:8080).
+//!
+//! Ctrl-C (or SIGTERM) shuts the server down: every client is told
+//! goodbye, and any that can't be within [`GRACE`] are cut off.
+
+use std::future::Future;
+use std::io;
+use std::process;
+use std::time::Duration;
+
+use tokio::net::TcpListener;
+
+/// How long the clients get to be told goodbye.
+const GRACE: Duration = Duration::from_secs(2);
+
+#[tokio::main]
+async fn main() {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| chat::DEFAULT_ADDR.to_string());
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("cannot listen on {addr}: {err}");
+            process::exit(1);
+        }
+    };
+    // Listen for the signals before saying we're ready: until then, Ctrl-C
+    // kills the process the default way, without a goodbye.
+    let signal = shutdown_signal().unwrap_or_else(|err| {
+        eprintln!("cannot handle Ctrl-C: {err}");
+        process::exit(1);
+    });
+    let addr = listener.local_addr().expect("the listener is bound");
+    println!("Chat server listening on {addr}. Connect with `cargo run -p chat --bin client`.");
+
+    let shutdown = async {
+        let name = signal.await;
+        println!("Got {name}, shutting down.");
+    };
+    match chat::server::run_until(listener, shutdown, GRACE).await {
+        Ok(ended) if ended.dropped == 0 => {
+            println!("Said goodbye to {}. Bye!", clients(ended.clients));
+        }
+        Ok(ended) => println!(
+            "Said goodbye to {}, and cut off {} after {}s. Bye!",
+            clients(ended.clients - ended.dropped),
+            clients(ended.dropped),
+            GRACE.as_secs()
+        ),
+        Err(err) => {
+            eprintln!("the server stopped: {err}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Resolves with the signal's name on Ctrl-C, or on SIGTERM on Unix.
+#[cfg(unix)]
+fn shutdown_signal() -> io::Result<impl Future<Output = &'static str>> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+    Ok(async move {
+        tokio::select! {
+            _ = interrupt.recv() => "Ctrl-C",
+            _ = terminate.recv() => "SIGTERM",
+        }
+    })
+}
+
+/// Resolves with the signal's name on Ctrl-C.
+#[cfg(not(unix))]
+fn shutdown_signal() -> io::Result<impl Future<Output = &'static str>> {
+    Ok(async {
+        let _ = tokio::signal::ctrl_c().await;
+        "Ctrl-C"
+    })
+}
+
+fn clients(count: usize) -> String {
+    match count {
+        1 => "1 client".to_string(),
+        _ => format!("{count} clients"),
+    }
+}
@@ -0,0 +1,116 @@
+//! Splitting a byte stream into lines.
+//!
+//! One `read` from a socket can return half a message, or three and a bit:
+//! TCP keeps the bytes in order but knows nothing about where messages
+//! start and end. So the protocol needs framing, and the simplest frame is
+//! a line ending in `\n`. [`LineReader`] keeps whatever it has read past
+//! the last complete line in a buffer until the rest arrives.
+//!
+//! It is also cancellation safe: every byte read is stored in `self`
+//! before the next `.await`, so when `tokio::select!` drops a pending
+//! [`LineReader::next_line`] in favor of another branch, nothing is lost.
+//! (`AsyncBufReadExt::read_line` doesn't promise that.)
+
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// The longest line a peer may send, in bytes. Without a limit, a client
+/// that never sends `\n` would make the buffer grow until memory runs out.
+pub const MAX_LINE: usize = 4096;
+
+/// Reads `\n`-terminated lines from `reader`.
+pub struct LineReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> LineReader<R> {
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader {
+            reader,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// The next line, without its line ending, or `None` once the peer has
+    /// closed the connection.
+    pub async fn next_line(&mut self) -> io::Result<Option<String>> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).collect();
+                return decode(line).map(Some);
+            }
+            if self.buffer.len() > MAX_LINE {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("a line is longer than {MAX_LINE} bytes"),
+                ));
+            }
+
+            let mut chunk = [0; 1024];
+            let read = self.reader.read(&mut chunk).await?;
+            if read == 0 {
+                // The peer is gone. A last line without `\n` still counts.
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                return decode(std::mem::take(&mut self.buffer)).map(Some);
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
+
+fn decode(mut line: Vec<u8>) -> io::Result<String> {
+    while line
+        .last()
+        .is_some_and(|&byte| byte == b'\n' || byte == b'\r')
+    {
+        line.pop();
+    }
+    String::from_utf8(line)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "a line is not valid UTF-8"))
+}
+
+/// Writes `line` and the `\n` that ends it.
+pub async fn write_line(writer: &mut (impl AsyncWrite + Unpin), line: &str) -> io::Result<()> {
+    let mut frame = String::with_capacity(line.len() + 1);
+    frame.push_str(line);
+    frame.push('\n');
+    writer.write_all(frame.as_bytes()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn joins_and_splits_reads_into_lines() {
+        // Lines split across writes, and several lines in one write.
+        let (mut client, server) = tokio::io::duplex(64);
+        let mut lines = LineReader::new(server);
+        tokio::spawn(async move {
+            for part in ["hel", "lo\r\nwor", "ld\nand ", "goodbye"] {
+                client.write_all(part.as_bytes()).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+
+        assert_eq!(lines.next_line().await.unwrap().as_deref(), Some("hello"));
+        assert_eq!(lines.next_line().await.unwrap().as_deref(), Some("world"));
+        assert_eq!(
+            lines.next_line().await.unwrap().as_deref(),
+            Some("and goodbye")
+        );
+        assert_eq!(lines.next_line().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn refuses_endless_lines() {
+        let endless = vec![b'a'; MAX_LINE * 2];
+        let mut lines = LineReader::new(endless.as_slice());
+        let err = lines.next_line().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,15 @@
+//! A chat room over TCP.
+//!
+//! - [`frame`]: TCP delivers a stream of bytes, not messages; this module
+//!   cuts the stream into lines, the "frames" of the chat protocol.
+//! - [`server`]: one task per connected client, all sharing the set of
+//!   names in the room and a `tokio::sync::broadcast` channel of messages.
+//!
+//! The client is `src/bin/client.rs`. Any line-based tool works as a client
+//! too: `nc 127.0.0.1 8080`.
+
+pub mod frame;
+pub mod server;
+
+/// Where the server listens, and the client connects, by default.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8080";
@@ -0,0 +1,227 @@
+//! The chat server.
+//!
+//! Every client gets its own task, which first asks for a name and then
+//! does two things at once with `tokio::select!`: it reads what the client
+//! types and broadcasts it, and it forwards what everyone else broadcasts
+//! to the client. The tasks share one [`Room`]:
+//!
+//! - the names in use, behind a `std::sync::Mutex`. A blocking mutex is
+//!   fine in async code as long as the lock is never held across an
+//!   `.await`, and here every use is a single quick call.
+//! - a `broadcast::Sender`. Each task calls `subscribe` for its own
+//!   receiver, and every message sent reaches every receiver.
+//! - a `watch::Receiver` that turns `true` when the server shuts down.
+//!   Every task waits for that in its `select!` too, says goodbye to its
+//!   client, and returns.
+//!
+//! [`run_until`] keeps the tasks in a `JoinSet`, so that after telling
+//! them to stop it can wait for them, for a grace period, and abort the
+//! ones that haven't finished by then: a task stuck writing to a client
+//! that stopped reading would otherwise hold the server up forever.
+
+use std::collections::BTreeSet;
+use std::future::{self, Future};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+
+use crate::frame::{write_line, LineReader};
+
+/// How many messages a slow client may fall behind before it misses some.
+const BACKLOG: usize = 100;
+
+/// The longest name a client may pick.
+const MAX_NAME: usize = 20;
+
+/// What every client is told when the server shuts down.
+pub const GOODBYE: &str = "* The server is shutting down. Bye!";
+
+/// A line of chat, and who sent it.
+#[derive(Debug, Clone)]
+struct Message {
+    /// The sender's address, so its task can skip its own messages.
+    from: SocketAddr,
+    text: String,
+}
+
+/// What the client tasks share.
+struct Room {
+    names: Mutex<BTreeSet<String>>,
+    messages: broadcast::Sender<Message>,
+    /// Turns `true` when the server shuts down.
+    stopping: watch::Receiver<bool>,
+}
+
+impl Room {
+    fn broadcast(&self, from: SocketAddr, text: String) {
+        // `send` only fails when nobody is subscribed, and then nobody is
+        // listening anyway.
+        let _ = self.messages.send(Message { from, text });
+    }
+}
+
+/// How a server run by [`run_until`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shutdown {
+    /// The clients connected when the shutdown began.
+    pub clients: usize,
+    /// How many of them were cut off at the end of the grace period,
+    /// instead of told goodbye.
+    pub dropped: usize,
+}
+
+/// Accepts clients on `listener` forever.
+pub async fn run(listener: TcpListener) -> io::Result<()> {
+    run_until(listener, future::pending(), Duration::ZERO).await?;
+    Ok(())
+}
+
+/// Accepts clients on `listener` until `shutdown` completes. Then closes
+/// the listener, tells every client goodbye, and waits up to `grace` for
+/// their tasks to finish before aborting the rest.
+pub async fn run_until(
+    listener: TcpListener,
+    shutdown: impl Future<Output = ()>,
+    grace: Duration,
+) -> io::Result<Shutdown> {
+    let (stop, stopping) = watch::channel(false);
+    let room = Arc::new(Room {
+        names: Mutex::new(BTreeSet::new()),
+        messages: broadcast::channel(BACKLOG).0,
+        stopping,
+    });
+    let mut clients = JoinSet::new();
+    let mut shutdown = std::pin::pin!(shutdown);
+    loop {
+        tokio::select! {
+            () = &mut shutdown => break,
+            accepted = listener.accept() => {
+                let (stream, addr) = accepted?;
+                let room = Arc::clone(&room);
+                clients.spawn(async move {
+                    if let Err(err) = handle(stream, addr, &room).await {
+                        eprintln!("{addr}: {err}");
+                    }
+                });
+            }
+            // Forget the clients that have left, so the set doesn't grow
+            // for as long as the server runs.
+            Some(_) = clients.join_next(), if !clients.is_empty() => {}
+        }
+    }
+    drop(listener);
+
+    let connected = clients.len();
+    // The room holds a receiver, so this can't fail.
+    let _ = stop.send(true);
+    let _ = timeout(grace, async {
+        while clients.join_next().await.is_some() {}
+    })
+    .await;
+    let dropped = clients.len();
+    clients.shutdown().await;
+    Ok(Shutdown {
+        clients: connected,
+        dropped,
+    })
+}
+
+/// Talks to one client, from asking its name until it leaves.
+async fn handle(stream: TcpStream, addr: SocketAddr, room: &Room) -> io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = LineReader::new(reader);
+    let mut stopping = room.stopping.clone();
+
+    write_line(&mut writer, "Welcome! What's your name?").await?;
+    let name = loop {
+        // `changed` also fails once the sender is gone: stop then, too.
+        let line = tokio::select! {
+            line = lines.next_line() => line?,
+            _ = stopping.changed() => return write_line(&mut writer, GOODBYE).await,
+        };
+        let Some(line) = line else {
+            return Ok(());
+        };
+        let name = line.trim();
+        let problem = if name.is_empty() || name.len() > MAX_NAME {
+            format!("Names have 1 to {MAX_NAME} characters. Try again:")
+        } else if name.contains(char::is_whitespace) {
+            "Names can't have spaces. Try again:".to_string()
+        } else if !room.names.lock().unwrap().insert(name.to_string()) {
+            format!("{name} is taken. Pick another name:")
+        } else {
+            break name.to_string();
+        };
+        write_line(&mut writer, &problem).await?;
+    };
+
+    // Subscribe before saying hello, so nothing sent after the greeting
+    // can be missed.
+    let mut messages = room.messages.subscribe();
+    write_line(&mut writer, &greeting(&name, room)).await?;
+    room.broadcast(addr, format!("* {name} joined"));
+
+    let result = async {
+        loop {
+            tokio::select! {
+                line = lines.next_line() => match line? {
+                    None => return Ok(()),
+                    Some(line) => match line.trim() {
+                        "/quit" => return Ok(()),
+                        "/who" => {
+                            let names = room.names.lock().unwrap().iter().cloned().collect::<Vec<_>>();
+                            write_line(&mut writer, &format!("* here: {}", names.join(", "))).await?;
+                        }
+                        "" => {}
+                        _ => room.broadcast(addr, format!("{name}: {line}")),
+                    },
+                },
+                message = messages.recv() => match message {
+                    Ok(message) if message.from == addr => {}
+                    Ok(message) => write_line(&mut writer, &message.text).await?,
+                    Err(RecvError::Lagged(missed)) => {
+                        write_line(&mut writer, &format!("* you missed {missed} messages")).await?;
+                    }
+                    // The room is gone: the server is shutting down.
+                    Err(RecvError::Closed) => return Ok(()),
+                },
+                _ = stopping.changed() => return write_line(&mut writer, GOODBYE).await,
+            }
+        }
+    }
+    .await;
+
+    // Leave the room however the conversation ended, even by an error.
+    room.names.lock().unwrap().remove(&name);
+    // Everyone is leaving when the server shuts down; no need to say so.
+    if !*stopping.borrow() {
+        room.broadcast(addr, format!("* {name} left"));
+    }
+    result
+}
+
+fn greeting(name: &str, room: &Room) -> String {
+    let names = room.names.lock().unwrap();
+    let others: Vec<&str> = names
+        .iter()
+        .map(String::as_str)
+        .filter(|other| *other != name)
+        .collect();
+    if others.is_empty() {
+        format!(
+            "Hi {name}! You're the first one here. Type /who to see who's here, /quit to leave."
+        )
+    } else {
+        format!(
+            "Hi {name}! Also here: {}. Type /who to see who's here, /quit to leave.",
+            others.join(", ")
+        )
+    }
+}
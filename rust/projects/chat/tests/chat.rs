@@ -0,0 +1,304 @@
+//! Starts a chat server on localhost and connects clients to it.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpSocket, TcpStream};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+use chat::frame::{write_line, LineReader, MAX_LINE};
+use chat::server::{Shutdown, GOODBYE};
+
+/// Long enough for any message on localhost, short enough to fail fast.
+const PATIENCE: Duration = Duration::from_secs(5);
+
+async fn start() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(chat::server::run(listener));
+    addr
+}
+
+/// A server that shuts down, with `grace`, when the sender is used.
+async fn start_stoppable(
+    grace: Duration,
+) -> (SocketAddr, oneshot::Sender<()>, JoinHandle<Shutdown>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stop, stopped) = oneshot::channel();
+    let server = tokio::spawn(async move {
+        let shutdown = async {
+            let _ = stopped.await;
+        };
+        chat::server::run_until(listener, shutdown, grace)
+            .await
+            .unwrap()
+    });
+    (addr, stop, server)
+}
+
+struct Client {
+    lines: LineReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl Client {
+    async fn connect(addr: SocketAddr) -> Client {
+        let (reader, writer) = TcpStream::connect(addr).await.unwrap().into_split();
+        let mut client = Client {
+            lines: LineReader::new(reader),
+            writer,
+        };
+        assert_eq!(client.recv().await, "Welcome! What's your name?");
+        client
+    }
+
+    /// Connects and picks `name`, returning the greeting too.
+    async fn join(addr: SocketAddr, name: &str) -> (Client, String) {
+        let mut client = Client::connect(addr).await;
+        client.send(name).await;
+        let greeting = client.recv().await;
+        assert!(greeting.starts_with(&format!("Hi {name}!")), "{greeting}");
+        (client, greeting)
+    }
+
+    async fn send(&mut self, line: &str) {
+        write_line(&mut self.writer, line).await.unwrap();
+    }
+
+    /// Checks that the server has hung up.
+    async fn assert_closed(&mut self) {
+        let end = timeout(PATIENCE, self.lines.next_line()).await.unwrap();
+        assert!(matches!(end, Ok(None)), "expected the end, got {end:?}");
+    }
+
+    async fn recv(&mut self) -> String {
+        timeout(PATIENCE, self.lines.next_line())
+            .await
+            .expect("the server didn't answer in time")
+            .unwrap()
+            .expect("the server closed the connection")
+    }
+}
+
+#[tokio::test]
+async fn messages_reach_everyone_else() {
+    let addr = start().await;
+    let (mut alice, greeting) = Client::join(addr, "alice").await;
+    assert!(greeting.contains("You're the first one here"));
+    let (mut bob, greeting) = Client::join(addr, "bob").await;
+    assert!(greeting.contains("Also here: alice."));
+    assert_eq!(alice.recv().await, "* bob joined");
+
+    alice.send("hi bob").await;
+    assert_eq!(bob.recv().await, "alice: hi bob");
+    bob.send("hi alice").await;
+    // Alice's own message wasn't sent back to her: bob's comes first.
+    assert_eq!(alice.recv().await, "bob: hi alice");
+}
+
+#[tokio::test]
+async fn everyone_hears_joins_and_leaves() {
+    let addr = start().await;
+    let (mut alice, _) = Client::join(addr, "alice").await;
+    let (mut bob, _) = Client::join(addr, "bob").await;
+    let (carol, _) = Client::join(addr, "carol").await;
+    assert_eq!(alice.recv().await, "* bob joined");
+    assert_eq!(alice.recv().await, "* carol joined");
+    assert_eq!(bob.recv().await, "* carol joined");
+
+    bob.send("/quit").await;
+    assert_eq!(alice.recv().await, "* bob left");
+    // Dropping the connection counts as leaving, too.
+    drop(carol);
+    assert_eq!(alice.recv().await, "* carol left");
+
+    alice.send("/who").await;
+    assert_eq!(alice.recv().await, "* here: alice");
+}
+
+#[tokio::test]
+async fn names_must_be_unique_and_sensible() {
+    let addr = start().await;
+    let (_alice, _) = Client::join(addr, "alice").await;
+
+    let mut other = Client::connect(addr).await;
+    other.send("alice").await;
+    assert_eq!(other.recv().await, "alice is taken. Pick another name:");
+    other.send("alice cooper").await;
+    assert_eq!(other.recv().await, "Names can't have spaces. Try again:");
+    other.send("").await;
+    assert!(other.recv().await.starts_with("Names have 1 to"));
+    other.send("alice2").await;
+    assert!(other.recv().await.starts_with("Hi alice2!"));
+}
+
+#[tokio::test]
+async fn a_client_that_never_ends_its_line_is_dropped() {
+    let addr = start().await;
+    let (mut alice, _) = Client::join(addr, "alice").await;
+    let (mut rambler, _) = Client::join(addr, "rambler").await;
+    assert_eq!(alice.recv().await, "* rambler joined");
+
+    rambler
+        .writer
+        .write_all(&vec![b'a'; MAX_LINE * 2])
+        .await
+        .unwrap();
+    assert_eq!(alice.recv().await, "* rambler left");
+    // The server hung up on the rambler: either a clean end of stream or,
+    // because it left unread data behind, a reset.
+    let end = timeout(PATIENCE, rambler.lines.next_line()).await.unwrap();
+    assert!(!matches!(end, Ok(Some(_))));
+}
+
+#[tokio::test]
+async fn the_client_binary_sends_what_is_typed() {
+    let addr = start().await;
+    let (mut alice, _) = Client::join(addr, "alice").await;
+
+    // The client reads "dave" and "hello!" from stdin, then sees it close
+    // and quits.
+    let client = tokio::task::spawn_blocking(move || {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new(env!("CARGO_BIN_EXE_client"))
+            .arg(addr.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"dave\nhello!\n")
+            .unwrap();
+        child.wait_with_output().unwrap()
+    });
+
+    assert_eq!(alice.recv().await, "* dave joined");
+    assert_eq!(alice.recv().await, "dave: hello!");
+    assert_eq!(alice.recv().await, "* dave left");
+
+    let output = client.await.unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("Welcome! What's your name?\nHi dave! Also here: alice."),
+        "{stdout}"
+    );
+}
+
+#[tokio::test]
+async fn shutting_down_says_goodbye_to_everyone() {
+    let (addr, stop, server) = start_stoppable(PATIENCE).await;
+    let (mut alice, _) = Client::join(addr, "alice").await;
+    let (mut bob, _) = Client::join(addr, "bob").await;
+    assert_eq!(alice.recv().await, "* bob joined");
+    // Still picking a name
+    let mut carol = Client::connect(addr).await;
+
+    stop.send(()).unwrap();
+    for client in [&mut alice, &mut bob, &mut carol] {
+        // Nobody hears that the others left, only the goodbye.
+        assert_eq!(client.recv().await, GOODBYE);
+        client.assert_closed().await;
+    }
+    assert_eq!(
+        server.await.unwrap(),
+        Shutdown {
+            clients: 3,
+            dropped: 0
+        }
+    );
+    assert!(TcpStream::connect(addr).await.is_err());
+}
+
+#[tokio::test]
+async fn clients_that_stop_reading_are_cut_off_after_the_grace_period() {
+    let grace = Duration::from_millis(200);
+    let (addr, stop, server) = start_stoppable(grace).await;
+
+    // A client with a tiny receive buffer that never reads: once the
+    // buffers between it and the server are full, its task is stuck
+    // writing to it.
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_recv_buffer_size(4096).unwrap();
+    let (reader, mut writer) = socket.connect(addr).await.unwrap().into_split();
+    write_line(&mut writer, "lurker").await.unwrap();
+    let mut newcomer = Client::connect(addr).await;
+    let (mut talker, _) = Client::join(addr, "talker").await;
+    // Far more than any socket buffers hold
+    let line = "a".repeat(MAX_LINE - 100);
+    for _ in 0..8000 {
+        talker.send(&line).await;
+    }
+
+    let started = std::time::Instant::now();
+    stop.send(()).unwrap();
+    assert_eq!(newcomer.recv().await, GOODBYE);
+    newcomer.assert_closed().await;
+    assert_eq!(
+        server.await.unwrap(),
+        Shutdown {
+            clients: 3,
+            dropped: 1
+        }
+    );
+    assert!(started.elapsed() >= grace);
+    drop(reader);
+}
+
+#[cfg(unix)]
+#[test]
+fn ctrl_c_stops_the_server_binary_in_order() {
+    use std::io::{BufRead, BufReader, Read};
+    use std::process::{Command, Stdio};
+
+    let mut server = Command::new(env!("CARGO_BIN_EXE_server"))
+        .arg("127.0.0.1:0")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = BufReader::new(server.stdout.take().unwrap());
+    let mut ready = String::new();
+    stdout.read_line(&mut ready).unwrap();
+    // "Chat server listening on 127.0.0.1:PORT. Connect with ..."
+    let addr: SocketAddr = ready
+        .strip_prefix("Chat server listening on ")
+        .and_then(|rest| rest.split_once(". "))
+        .unwrap_or_else(|| panic!("unexpected first line: {ready:?}"))
+        .0
+        .parse()
+        .unwrap();
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let (mut alice, _) = Client::join(addr, "alice").await;
+        let mut bob = Client::connect(addr).await;
+
+        let status = Command::new("kill")
+            .args(["-INT", &server.id().to_string()])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        for client in [&mut alice, &mut bob] {
+            assert_eq!(client.recv().await, GOODBYE);
+            client.assert_closed().await;
+        }
+    });
+
+    let mut rest = String::new();
+    stdout.read_to_string(&mut rest).unwrap();
+    assert!(server.wait().unwrap().success());
+    assert_eq!(
+        rest,
+        "Got Ctrl-C, shutting down.\nSaid goodbye to 2 clients. Bye!\n"
+    );
+}
@@ -0,0 +1,257 @@
+//! A log-structured key-value store.
+//!
+//! All data lives in one file, and the file is only ever appended to:
+//! setting a key appends a record with the new value, and removing one
+//! appends a "tombstone" record. An in-memory index maps each live key to
+//! the position of its latest record, so a read is one seek and one read.
+//! Opening the store rebuilds the index by reading the log from the start.
+//!
+//! Old values stay in the file until [`KvStore::compact`] rewrites it with
+//! only the live records; it also runs by itself once more than half the
+//! file is stale.
+//!
+//! Why append-only? Because it makes crashes easy to reason about. A write
+//! that was interrupted can only have damaged the end of the file, never a
+//! record that was already there; see [`KvStore::open`] for how that end is
+//! recovered. The format of each record is in [`record`].
+
+pub mod record;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use record::Next;
+
+/// Compaction only pays off once there is a fair amount to reclaim.
+const COMPACT_MIN_STALE: u64 = 1024 * 1024;
+
+/// Where a key's latest record is in the log.
+#[derive(Debug, Clone, Copy)]
+struct Location {
+    offset: u64,
+    len: u64,
+}
+
+pub struct KvStore {
+    path: PathBuf,
+    /// Opened for appending: every write goes to the end.
+    writer: File,
+    /// A second handle, for seeking around to read values.
+    reader: File,
+    index: BTreeMap<Vec<u8>, Location>,
+    /// The length of the log, which is also where the next record goes.
+    end: u64,
+    /// Bytes of records that later records have made obsolete.
+    stale: u64,
+    /// Bytes cut off the end of the log when it was opened.
+    truncated: u64,
+}
+
+impl KvStore {
+    /// Opens the store in the file at `path`, creating it if needed.
+    ///
+    /// If the last record is damaged, the log is cut off before it. That
+    /// record was being written when the program stopped, and its `set` or
+    /// `remove` never returned, so nobody was told it had succeeded. (A
+    /// damaged record in the middle of the file can only come from a bad
+    /// disk; it is treated the same way, and everything after it is lost.)
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<KvStore> {
+        let path = path.into();
+        // A compaction that didn't finish; the log itself is still whole.
+        match fs::remove_file(compact_path(&path)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+
+        let writer = OpenOptions::new().append(true).create(true).open(&path)?;
+        let reader = File::open(&path)?;
+        let mut store = KvStore {
+            path,
+            writer,
+            reader,
+            index: BTreeMap::new(),
+            end: 0,
+            stale: 0,
+            truncated: 0,
+        };
+
+        let mut log = BufReader::new(File::open(&store.path)?);
+        loop {
+            match record::read(&mut log)? {
+                Next::Record(record, len) => {
+                    let location = Location {
+                        offset: store.end,
+                        len,
+                    };
+                    store.end += len;
+                    match record.value {
+                        Some(_) => store.replace(record.key, location),
+                        None => store.forget(&record.key, len),
+                    }
+                }
+                Next::End => break,
+                Next::Damaged => {
+                    let file_len = store.writer.metadata()?.len();
+                    store.truncated = file_len - store.end;
+                    store.writer.set_len(store.end)?;
+                    break;
+                }
+            }
+        }
+        Ok(store)
+    }
+
+    /// The value of `key`, if it is set.
+    pub fn get(&mut self, key: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        let Some(location) = self.index.get(key).copied() else {
+            return Ok(None);
+        };
+        self.reader.seek(SeekFrom::Start(location.offset))?;
+        // Reading checks the checksum again, which catches a file that was
+        // damaged after it was opened.
+        match record::read(&mut (&self.reader).take(location.len))? {
+            Next::Record(record, _) => Ok(record.value),
+            Next::End | Next::Damaged => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the record at byte {} is damaged", location.offset),
+            )),
+        }
+    }
+
+    /// Sets `key` to `value`.
+    pub fn set(&mut self, key: &[u8], value: &[u8]) -> io::Result<()> {
+        let location = self.append(&record::encode(key, Some(value))?)?;
+        self.replace(key.to_vec(), location);
+        self.compact_if_worth_it()
+    }
+
+    /// Removes `key`, returning whether it was set.
+    pub fn remove(&mut self, key: &[u8]) -> io::Result<bool> {
+        if !self.index.contains_key(key) {
+            return Ok(false);
+        }
+        let location = self.append(&record::encode(key, None)?)?;
+        self.forget(key, location.len);
+        self.compact_if_worth_it()?;
+        Ok(true)
+    }
+
+    /// Rewrites the log with only the latest record of each live key.
+    ///
+    /// The new log is written to a separate file, flushed to disk, and then
+    /// renamed over the old one. A rename replaces a file in one step, so
+    /// whenever a crash happens, the store is either all old log or all new.
+    pub fn compact(&mut self) -> io::Result<()> {
+        let compact_path = compact_path(&self.path);
+        let mut compacted = File::create(&compact_path)?;
+        let mut index = BTreeMap::new();
+        let mut end = 0;
+        for (key, location) in &self.index {
+            // Records are copied byte for byte: no need to decode them.
+            let mut record = vec![0; location.len as usize];
+            self.reader.seek(SeekFrom::Start(location.offset))?;
+            self.reader.read_exact(&mut record)?;
+            compacted.write_all(&record)?;
+            index.insert(
+                key.clone(),
+                Location {
+                    offset: end,
+                    len: location.len,
+                },
+            );
+            end += location.len;
+        }
+        compacted.sync_all()?;
+        drop(compacted);
+        fs::rename(&compact_path, &self.path)?;
+
+        self.writer = OpenOptions::new().append(true).open(&self.path)?;
+        self.reader = File::open(&self.path)?;
+        self.index = index;
+        self.end = end;
+        self.stale = 0;
+        Ok(())
+    }
+
+    /// Waits until everything written so far is on disk.
+    ///
+    /// Writes go to the operating system right away, so they survive the
+    /// program crashing, but the OS may hold them in memory for a while. Call
+    /// this before telling anyone that a write is safe from power loss, too.
+    pub fn sync(&self) -> io::Result<()> {
+        self.writer.sync_data()
+    }
+
+    /// The keys that are set, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &[u8]> {
+        self.index.keys().map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// The size of the log file, in bytes.
+    pub fn log_len(&self) -> u64 {
+        self.end
+    }
+
+    /// How many bytes of the log a compaction would reclaim.
+    pub fn stale_bytes(&self) -> u64 {
+        self.stale
+    }
+
+    /// How many bytes of damaged records `open` cut off the end of the log.
+    pub fn truncated_bytes(&self) -> u64 {
+        self.truncated
+    }
+
+    fn append(&mut self, record: &[u8]) -> io::Result<Location> {
+        // One `write_all` per record: a crash leaves at most this record
+        // half written.
+        self.writer.write_all(record)?;
+        let location = Location {
+            offset: self.end,
+            len: record.len() as u64,
+        };
+        self.end += location.len;
+        Ok(location)
+    }
+
+    /// Points `key` at a new record, making its previous one stale.
+    fn replace(&mut self, key: Vec<u8>, location: Location) {
+        if let Some(old) = self.index.insert(key, location) {
+            self.stale += old.len;
+        }
+    }
+
+    /// Drops `key` from the index after a tombstone of `tombstone_len`
+    /// bytes. The tombstone is stale too: compaction simply leaves the key
+    /// out.
+    fn forget(&mut self, key: &[u8], tombstone_len: u64) {
+        self.stale += tombstone_len;
+        if let Some(old) = self.index.remove(key) {
+            self.stale += old.len;
+        }
+    }
+
+    fn compact_if_worth_it(&mut self) -> io::Result<()> {
+        if self.stale >= COMPACT_MIN_STALE && self.stale * 2 > self.end {
+            self.compact()?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a compaction writes the new log before it replaces the old one.
+fn compact_path(path: &Path) -> PathBuf {
+    let mut compact = path.as_os_str().to_owned();
+    compact.push(".compact");
+    PathBuf::from(compact)
+}
@@ -0,0 +1,86 @@
+//! A command line for the store.
+//!
+//! ```text
+//! kvstore FILE set KEY VALUE
+//! kvstore FILE get KEY
+//! kvstore FILE rm KEY
+//! kvstore FILE list          every key, in order
+//! kvstore FILE compact       rewrite the log without stale records
+//! kvstore FILE stats         how big the log is, and how much is stale
+//! ```
+
+use std::io;
+use std::process::ExitCode;
+
+use kvstore::KvStore;
+
+const USAGE: &str =
+    "usage: kvstore FILE (set KEY VALUE | get KEY | rm KEY | list | compact | stats)";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((file, command)) = args.split_first() else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+    match run(file, command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{file}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(file: &str, command: &[String]) -> io::Result<bool> {
+    let mut store = KvStore::open(file)?;
+    if store.truncated_bytes() > 0 {
+        eprintln!(
+            "note: cut {} bytes of an unfinished write off the end of the log",
+            store.truncated_bytes()
+        );
+    }
+
+    let command: Vec<&str> = command.iter().map(String::as_str).collect();
+    match command.as_slice() {
+        ["set", key, value] => {
+            store.set(key.as_bytes(), value.as_bytes())?;
+            store.sync()?;
+        }
+        ["get", key] => match store.get(key.as_bytes())? {
+            Some(value) => println!("{}", String::from_utf8_lossy(&value)),
+            None => {
+                eprintln!("{key} is not set");
+                return Ok(false);
+            }
+        },
+        ["rm", key] => {
+            if !store.remove(key.as_bytes())? {
+                eprintln!("{key} is not set");
+                return Ok(false);
+            }
+            store.sync()?;
+        }
+        ["list"] => {
+            for key in store.keys() {
+                println!("{}", String::from_utf8_lossy(key));
+            }
+        }
+        ["compact"] => {
+            let before = store.log_len();
+            store.compact()?;
+            println!("{before} -> {} bytes", store.log_len());
+        }
+        ["stats"] => {
+            println!("keys:  {}", store.len());
+            println!("log:   {} bytes", store.log_len());
+            println!("stale: {} bytes", store.stale_bytes());
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
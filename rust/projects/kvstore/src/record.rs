@@ -0,0 +1,197 @@
+//! The on-disk format of one log record.
+//!
+//! ```text
+//! offset  size  field
+//!      0     4  checksum    CRC-32 of everything after it
+//!      4     4  key length  little-endian u32
+//!      8     4  value len   little-endian u32, or TOMBSTONE for a removal
+//!     12     k  key bytes
+//!   12+k     v  value bytes
+//! ```
+//!
+//! Every number has a fixed size and byte order, so the file reads the same
+//! on every machine, and a record's length is known from its header alone.
+//! The checksum is how a reader tells a complete record from one that was
+//! only half written when the power went out.
+
+use std::io::{self, Read};
+
+/// Size of the fixed part of a record.
+pub const HEADER_LEN: u64 = 12;
+
+/// The value length that marks a removed key.
+pub const TOMBSTONE: u32 = u32::MAX;
+
+/// A decoded record. `value` is `None` for a removal.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Record {
+    pub key: Vec<u8>,
+    pub value: Option<Vec<u8>>,
+}
+
+/// Encodes a record for `key`, setting it to `value` or, with `None`,
+/// removing it.
+pub fn encode(key: &[u8], value: Option<&[u8]>) -> io::Result<Vec<u8>> {
+    let too_long = |what| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("the {what} is too long"),
+        )
+    };
+    let key_len = u32::try_from(key.len()).map_err(|_| too_long("key"))?;
+    let value_len = match value {
+        // TOMBSTONE itself can't be a length.
+        Some(value) => u32::try_from(value.len())
+            .ok()
+            .filter(|&len| len != TOMBSTONE)
+            .ok_or_else(|| too_long("value"))?,
+        None => TOMBSTONE,
+    };
+
+    let mut record =
+        Vec::with_capacity(HEADER_LEN as usize + key.len() + value.map_or(0, <[u8]>::len));
+    record.extend_from_slice(&[0; 4]);
+    record.extend_from_slice(&key_len.to_le_bytes());
+    record.extend_from_slice(&value_len.to_le_bytes());
+    record.extend_from_slice(key);
+    record.extend_from_slice(value.unwrap_or_default());
+    let checksum = crc32(&record[4..]);
+    record[..4].copy_from_slice(&checksum.to_le_bytes());
+    Ok(record)
+}
+
+/// What the next record in a log turned out to be.
+#[derive(Debug)]
+pub enum Next {
+    /// A complete record, and how many bytes it took up.
+    Record(Record, u64),
+    /// The clean end of the log.
+    End,
+    /// A record that was cut short or fails its checksum.
+    Damaged,
+}
+
+/// Reads the record at the reader's position.
+pub fn read(reader: &mut impl Read) -> io::Result<Next> {
+    let mut header = [0; HEADER_LEN as usize];
+    match read_full(reader, &mut header)? {
+        0 => return Ok(Next::End),
+        n if n < header.len() => return Ok(Next::Damaged),
+        _ => {}
+    }
+    let checksum = u32::from_le_bytes(header[0..4].try_into().unwrap());
+    let key_len = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    let value_len = u32::from_le_bytes(header[8..12].try_into().unwrap());
+
+    let body_len = u64::from(key_len)
+        + if value_len == TOMBSTONE {
+            0
+        } else {
+            u64::from(value_len)
+        };
+    // Read through `take`, so a damaged length can't make us allocate 4 GiB.
+    let mut body = Vec::new();
+    reader.take(body_len).read_to_end(&mut body)?;
+    if (body.len() as u64) < body_len {
+        return Ok(Next::Damaged);
+    }
+
+    let mut hasher = Crc32::new();
+    hasher.update(&header[4..]);
+    hasher.update(&body);
+    if hasher.finish() != checksum {
+        return Ok(Next::Damaged);
+    }
+    let value = (value_len != TOMBSTONE).then(|| body.split_off(key_len as usize));
+    Ok(Next::Record(
+        Record { key: body, value },
+        HEADER_LEN + body_len,
+    ))
+}
+
+/// Like `read_exact`, but returns how much it read instead of failing at
+/// the end of the file.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+/// CRC-32 (the one zip and PNG use), computed bit by bit. A lookup table
+/// would be faster; this shows the idea in a few lines.
+struct Crc32(u32);
+
+impl Crc32 {
+    fn new() -> Crc32 {
+        Crc32(!0)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u32::from(byte);
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        !self.0
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut hasher = Crc32::new();
+    hasher.update(bytes);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_the_standard_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn records_read_back_as_written() {
+        let mut log = encode(b"name", Some(b"Ferris")).unwrap();
+        log.extend(encode(b"name", None).unwrap());
+        let mut reader = log.as_slice();
+
+        let Next::Record(record, len) = read(&mut reader).unwrap() else {
+            panic!("expected a record");
+        };
+        assert_eq!(record.key, b"name");
+        assert_eq!(record.value.as_deref(), Some(&b"Ferris"[..]));
+        assert_eq!(len, HEADER_LEN + 10);
+        let Next::Record(record, _) = read(&mut reader).unwrap() else {
+            panic!("expected a record");
+        };
+        assert_eq!(record.value, None);
+        assert!(matches!(read(&mut reader).unwrap(), Next::End));
+    }
+
+    #[test]
+    fn damage_is_detected() {
+        let record = encode(b"key", Some(b"value")).unwrap();
+        let mut flipped = record.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            read(&mut flipped.as_slice()).unwrap(),
+            Next::Damaged
+        ));
+        let cut = &record[..record.len() - 1];
+        assert!(matches!(read(&mut &cut[..]).unwrap(), Next::Damaged));
+    }
+}
@@ -0,0 +1,121 @@
+//! Property tests: proptest generates random sequences of operations, runs
+//! them against the store and against a `BTreeMap`, and checks that the two
+//! always agree. When a sequence fails, proptest shrinks it to the shortest
+//! one that still fails before reporting it.
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+
+use proptest::prelude::*;
+
+use kvstore::KvStore;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Set(Vec<u8>, Vec<u8>),
+    Remove(Vec<u8>),
+    Compact,
+    /// Close the store and open it again from the file.
+    Reopen,
+}
+
+/// Few distinct keys, so the same key is often set and removed again.
+fn key() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(0u8..4, 0..3)
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => (key(), prop::collection::vec(any::<u8>(), 0..64)).prop_map(|(k, v)| Op::Set(k, v)),
+        2 => key().prop_map(Op::Remove),
+        1 => Just(Op::Compact),
+        1 => Just(Op::Reopen),
+    ]
+}
+
+fn assert_same(store: &mut KvStore, model: &BTreeMap<Vec<u8>, Vec<u8>>) {
+    let keys: Vec<&[u8]> = store.keys().collect();
+    let expected: Vec<&[u8]> = model.keys().map(Vec::as_slice).collect();
+    assert_eq!(keys, expected);
+    for (key, value) in model {
+        assert_eq!(store.get(key).unwrap().as_ref(), Some(value));
+    }
+}
+
+proptest! {
+    #[test]
+    fn behaves_like_a_map(ops in prop::collection::vec(op(), 1..60)) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.log");
+        let mut store = KvStore::open(&path).unwrap();
+        let mut model = BTreeMap::new();
+
+        for op in ops {
+            match op {
+                Op::Set(key, value) => {
+                    store.set(&key, &value).unwrap();
+                    model.insert(key, value);
+                }
+                Op::Remove(key) => {
+                    prop_assert_eq!(store.remove(&key).unwrap(), model.remove(&key).is_some());
+                }
+                Op::Compact => {
+                    store.compact().unwrap();
+                    prop_assert_eq!(store.stale_bytes(), 0);
+                }
+                Op::Reopen => {
+                    drop(store);
+                    store = KvStore::open(&path).unwrap();
+                    prop_assert_eq!(store.truncated_bytes(), 0);
+                }
+            }
+            assert_same(&mut store, &model);
+        }
+
+        let log_len = store.log_len();
+        drop(store);
+        let mut store = KvStore::open(&path).unwrap();
+        prop_assert_eq!(store.log_len(), log_len);
+        assert_same(&mut store, &model);
+    }
+
+    /// A crash can stop a write at any byte. Whatever byte that is, opening
+    /// the store again gives the state after the last complete write.
+    #[test]
+    fn survives_a_crash_at_any_byte(
+        writes in prop::collection::vec((key(), prop::option::of(prop::collection::vec(any::<u8>(), 0..32))), 1..30),
+        cut in any::<prop::sample::Index>(),
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.log");
+        let mut store = KvStore::open(&path).unwrap();
+
+        // The state of the store after each write, and the log length then.
+        let mut model = BTreeMap::new();
+        let mut states = vec![(0, model.clone())];
+        for (key, value) in writes {
+            match value {
+                Some(value) => {
+                    store.set(&key, &value).unwrap();
+                    model.insert(key, value);
+                }
+                None => {
+                    store.remove(&key).unwrap();
+                    model.remove(&key);
+                }
+            }
+            states.push((store.log_len(), model.clone()));
+        }
+        drop(store);
+
+        let full_len = states.last().unwrap().0;
+        let cut = cut.index(full_len as usize + 1) as u64;
+        OpenOptions::new().write(true).open(&path).unwrap().set_len(cut).unwrap();
+
+        let mut store = KvStore::open(&path).unwrap();
+        let (len, expected) = states.iter().rev().find(|(len, _)| *len <= cut).unwrap();
+        prop_assert_eq!(store.log_len(), *len);
+        prop_assert_eq!(store.truncated_bytes(), cut - len);
+        assert_same(&mut store, expected);
+    }
+}
@@ -0,0 +1,46 @@
+//! The library half of minigrep. The stages in `project.toml` walk you
+//! through it one function at a time; each `todo!()` is yours to replace.
+
+use std::error::Error;
+
+/// What to search for, and where.
+pub struct Config {
+    pub query: String,
+    pub file_path: String,
+    pub ignore_case: bool,
+}
+
+impl Config {
+    /// Builds a `Config` from the command line: the program's name, then
+    /// the query, then the file path.
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, &'static str> {
+        // TODO (stage 1): skip the program name, then take the query and the
+        // file path. Return an error if either is missing.
+        // TODO (stage 5): set `ignore_case` when the IGNORE_CASE environment
+        // variable is set (to anything).
+        todo!()
+    }
+}
+
+/// Reads the file and prints every line that matches the query.
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    // TODO (stage 2): read the whole file into a `String`. If it can't be
+    // read, return the error instead of panicking (`?` does that).
+    // TODO (stage 3): print each line that `search` finds, instead of the
+    // whole file.
+    // TODO (stage 4): use `search_case_insensitive` when
+    // `config.ignore_case` is set.
+    todo!()
+}
+
+/// The lines of `contents` that contain `query`.
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    // TODO (stage 3)
+    todo!()
+}
+
+/// Like `search`, but "Rust" also matches "rust" and "RUST".
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    // TODO (stage 4)
+    todo!()
+}
@@ -0,0 +1,28 @@
+// minigrep: print the lines of a file that contain a word.
+//
+//     cargo run -- frog poem.txt
+//
+// Most of the work happens in `lib.rs`. `main` only collects the arguments,
+// hands them to the library, and reports what went wrong.
+//
+// Check your progress with `cargo run -p tutor -- project minigrep` from
+// the `rust/` directory.
+
+use std::env;
+use std::process;
+
+use minigrep::Config;
+
+fn main() {
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        // TODO (stage 6): error messages belong on standard error.
+        println!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    if let Err(err) = minigrep::run(config) {
+        // TODO (stage 6): here too.
+        println!("Application error: {err}");
+        process::exit(1);
+    }
+}
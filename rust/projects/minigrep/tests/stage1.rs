@@ -0,0 +1,33 @@
+//! Stage 1: parse the arguments with `Config::build`.
+
+use minigrep::Config;
+
+fn args(list: &[&str]) -> impl Iterator<Item = String> {
+    list.iter()
+        .map(|arg| arg.to_string())
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+#[test]
+fn takes_the_query_and_then_the_file_path() {
+    let config = Config::build(args(&["minigrep", "frog", "poem.txt"])).unwrap();
+    assert_eq!(config.query, "frog");
+    assert_eq!(config.file_path, "poem.txt");
+}
+
+#[test]
+fn skips_the_program_name() {
+    let config = Config::build(args(&["./target/debug/minigrep", "to", "notes.txt"])).unwrap();
+    assert_eq!(config.query, "to");
+}
+
+#[test]
+fn fails_without_a_file_path() {
+    assert!(Config::build(args(&["minigrep", "frog"])).is_err());
+}
+
+#[test]
+fn fails_without_any_arguments() {
+    assert!(Config::build(args(&["minigrep"])).is_err());
+}
@@ -0,0 +1,23 @@
+//! Stage 2: read the file in `run`, and return an error if it can't be read.
+
+use minigrep::Config;
+
+fn config(file_path: &str) -> Config {
+    Config {
+        query: String::from("frog"),
+        file_path: file_path.to_string(),
+        ignore_case: false,
+    }
+}
+
+#[test]
+fn reads_a_file_that_exists() {
+    let poem = concat!(env!("CARGO_MANIFEST_DIR"), "/poem.txt");
+    assert!(minigrep::run(config(poem)).is_ok());
+}
+
+#[test]
+fn returns_an_error_for_a_missing_file() {
+    // `run` should hand the error back to `main`, not panic.
+    assert!(minigrep::run(config("no/such/file.txt")).is_err());
+}
@@ -0,0 +1,50 @@
+//! Stage 3: find the matching lines with `search`, and print them.
+
+use std::process::Command;
+
+const CONTENTS: &str = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+#[test]
+fn finds_one_line() {
+    assert_eq!(
+        minigrep::search("fast", CONTENTS),
+        vec!["safe, fast, productive."]
+    );
+}
+
+#[test]
+fn finds_every_matching_line_in_order() {
+    assert_eq!(
+        minigrep::search("st", CONTENTS),
+        vec!["Rust:", "safe, fast, productive.", "Trust me."]
+    );
+}
+
+#[test]
+fn finds_nothing_when_nothing_matches() {
+    assert!(minigrep::search("slow", CONTENTS).is_empty());
+}
+
+#[test]
+fn is_case_sensitive() {
+    assert_eq!(minigrep::search("rust", CONTENTS), vec!["Trust me."]);
+}
+
+#[test]
+fn prints_only_the_matching_lines() {
+    let output = Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(["frog", "poem.txt"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env_remove("IGNORE_CASE")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "How public, like a frog\n"
+    );
+}
@@ -0,0 +1,28 @@
+//! Stage 4: search without caring about case, with `search_case_insensitive`.
+
+const CONTENTS: &str = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+#[test]
+fn ignores_the_case_of_the_contents() {
+    assert_eq!(
+        minigrep::search_case_insensitive("rust", CONTENTS),
+        vec!["Rust:", "Trust me."]
+    );
+}
+
+#[test]
+fn ignores_the_case_of_the_query() {
+    assert_eq!(
+        minigrep::search_case_insensitive("PICK", CONTENTS),
+        vec!["Pick three."]
+    );
+}
+
+#[test]
+fn finds_nothing_when_nothing_matches() {
+    assert!(minigrep::search_case_insensitive("slow", CONTENTS).is_empty());
+}
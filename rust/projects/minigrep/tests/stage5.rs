@@ -0,0 +1,36 @@
+//! Stage 5: switch on case-insensitive search with the IGNORE_CASE
+//! environment variable.
+//!
+//! These tests run the program instead of calling `Config::build`, because
+//! changing the environment of the test process would affect the other
+//! tests running next to it.
+
+use std::process::Command;
+
+fn minigrep(query: &str, ignore_case: bool) -> String {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_minigrep"));
+    command
+        .args([query, "poem.txt"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"));
+    if ignore_case {
+        command.env("IGNORE_CASE", "1");
+    } else {
+        command.env_remove("IGNORE_CASE");
+    }
+    let output = command.output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn is_case_sensitive_by_default() {
+    assert_eq!(minigrep("how", false), "");
+}
+
+#[test]
+fn ignores_case_when_ignore_case_is_set() {
+    assert_eq!(
+        minigrep("how", true),
+        "How dreary to be somebody!\nHow public, like a frog\n"
+    );
+}
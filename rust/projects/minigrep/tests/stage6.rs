@@ -0,0 +1,40 @@
+//! Stage 6: print errors to standard error, so that redirecting the output
+//! (`minigrep frog poem.txt > found.txt`) only captures the results.
+
+use std::process::{Command, Output};
+
+fn minigrep(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minigrep"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env_remove("IGNORE_CASE")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn reports_missing_arguments_on_stderr() {
+    let output = minigrep(&["frog"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty(), "nothing should go to stdout");
+    assert!(!output.stderr.is_empty(), "the error should go to stderr");
+}
+
+#[test]
+fn reports_a_missing_file_on_stderr() {
+    let output = minigrep(&["frog", "no/such/file.txt"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty(), "nothing should go to stdout");
+    assert!(!output.stderr.is_empty(), "the error should go to stderr");
+}
+
+#[test]
+fn still_prints_results_on_stdout() {
+    let output = minigrep(&["frog", "poem.txt"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "How public, like a frog\n"
+    );
+    assert!(output.stderr.is_empty());
+}
@@ -0,0 +1,320 @@
+//! The rules of snake.
+//!
+//! The board is `width` by `height` cells, with `(0, 0)` in the top left.
+//! Every tick, the snake moves one cell in its direction. Eating food makes
+//! it one cell longer; running into a wall or into itself ends the game.
+
+use std::collections::VecDeque;
+
+/// Which way the snake is heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// Whether the game is going on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Playing,
+    Paused,
+    /// The snake crashed.
+    Over,
+    /// The snake fills the whole board.
+    Won,
+}
+
+/// What a tick did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Nothing happened: the game is paused or over.
+    Nothing,
+    Moved,
+    Ate,
+    Crashed,
+    Won,
+}
+
+/// A cell on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl Point {
+    pub fn new(x: u16, y: u16) -> Point {
+        Point { x, y }
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    width: u16,
+    height: u16,
+    /// The head is at the front.
+    snake: VecDeque<Point>,
+    direction: Direction,
+    /// The direction asked for since the last tick. Turns take effect on the
+    /// next tick, so two quick key presses can't fold the snake back onto
+    /// itself within one move.
+    next_direction: Direction,
+    food: Point,
+    score: u32,
+    state: State,
+    /// State of the random number generator that places the food.
+    seed: u64,
+}
+
+impl Game {
+    /// A game on a `width` by `height` board, with a three-cell snake in
+    /// the middle heading right. The same `seed` places the food the same
+    /// way every time.
+    ///
+    /// # Panics
+    ///
+    /// If the board is smaller than 5 by 1, where the snake doesn't fit.
+    pub fn new(width: u16, height: u16, seed: u64) -> Game {
+        assert!(width >= 5 && height >= 1, "the board is too small");
+        let (x, y) = (width / 2, height / 2);
+        let mut game = Game {
+            width,
+            height,
+            snake: VecDeque::from([Point::new(x, y), Point::new(x - 1, y), Point::new(x - 2, y)]),
+            direction: Direction::Right,
+            next_direction: Direction::Right,
+            food: Point::new(0, 0),
+            score: 0,
+            state: State::Playing,
+            seed: seed | 1,
+        };
+        game.place_food();
+        game
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// The snake's cells, head first.
+    pub fn snake(&self) -> impl Iterator<Item = Point> + '_ {
+        self.snake.iter().copied()
+    }
+
+    pub fn head(&self) -> Point {
+        self.snake[0]
+    }
+
+    pub fn food(&self) -> Point {
+        self.food
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Asks the snake to turn on the next tick. Turning back the way it came
+    /// is ignored.
+    pub fn turn(&mut self, direction: Direction) {
+        if direction != self.direction.opposite() {
+            self.next_direction = direction;
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.state = match self.state {
+            State::Playing => State::Paused,
+            State::Paused => State::Playing,
+            finished => finished,
+        };
+    }
+
+    /// Moves the snake one cell.
+    pub fn tick(&mut self) -> Event {
+        if self.state != State::Playing {
+            return Event::Nothing;
+        }
+        self.direction = self.next_direction;
+        let Some(next) = self.step(self.head(), self.direction) else {
+            self.state = State::Over;
+            return Event::Crashed;
+        };
+
+        let eating = next == self.food;
+        if !eating {
+            // The tail moves out of the way before the head moves in, so
+            // chasing your own tail is fine.
+            self.snake.pop_back();
+        }
+        if self.snake.contains(&next) {
+            self.state = State::Over;
+            return Event::Crashed;
+        }
+        self.snake.push_front(next);
+
+        if !eating {
+            return Event::Moved;
+        }
+        self.score += 1;
+        if self.snake.len() == usize::from(self.width) * usize::from(self.height) {
+            self.state = State::Won;
+            return Event::Won;
+        }
+        self.place_food();
+        Event::Ate
+    }
+
+    /// The cell next to `from` in `direction`, or `None` past a wall.
+    fn step(&self, from: Point, direction: Direction) -> Option<Point> {
+        let Point { x, y } = from;
+        let (x, y) = match direction {
+            Direction::Up => (Some(x), y.checked_sub(1)),
+            Direction::Down => (Some(x), y.checked_add(1).filter(|&y| y < self.height)),
+            Direction::Left => (x.checked_sub(1), Some(y)),
+            Direction::Right => (x.checked_add(1).filter(|&x| x < self.width), Some(y)),
+        };
+        Some(Point::new(x?, y?))
+    }
+
+    /// Puts the food on a random free cell.
+    fn place_food(&mut self) {
+        let free: Vec<Point> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Point::new(x, y)))
+            .filter(|cell| !self.snake.contains(cell))
+            .collect();
+        let index = (self.random() % free.len() as u64) as usize;
+        self.food = free[index];
+    }
+
+    /// xorshift64: not much of a random number generator, but plenty for
+    /// placing food, and no dependency.
+    fn random(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A game with the food moved to `food`, out of the way or not.
+    fn game_with_food(width: u16, height: u16, food: Point) -> Game {
+        let mut game = Game::new(width, height, 7);
+        game.food = food;
+        game
+    }
+
+    #[test]
+    fn the_snake_moves_forward_every_tick() {
+        let mut game = game_with_food(10, 5, Point::new(0, 0));
+        assert_eq!(game.head(), Point::new(5, 2));
+        assert_eq!(game.tick(), Event::Moved);
+        assert_eq!(game.head(), Point::new(6, 2));
+        assert_eq!(game.snake().count(), 3);
+    }
+
+    #[test]
+    fn turning_around_is_ignored() {
+        let mut game = game_with_food(10, 5, Point::new(0, 0));
+        game.turn(Direction::Left);
+        game.tick();
+        assert_eq!(game.head(), Point::new(6, 2));
+
+        game.turn(Direction::Up);
+        game.tick();
+        assert_eq!(game.head(), Point::new(6, 1));
+    }
+
+    #[test]
+    fn a_second_turn_before_the_tick_cannot_reverse_the_snake() {
+        let mut game = game_with_food(10, 5, Point::new(0, 0));
+        // Heading right, Up and then Left are pressed before the next tick.
+        // Left is checked against the direction the snake is still facing,
+        // so it is ignored instead of folding the snake back on itself.
+        game.turn(Direction::Up);
+        game.turn(Direction::Left);
+        assert_eq!(game.tick(), Event::Moved);
+        assert_eq!(game.head(), Point::new(5, 1));
+    }
+
+    #[test]
+    fn eating_grows_the_snake_and_moves_the_food() {
+        let mut game = game_with_food(10, 5, Point::new(6, 2));
+        assert_eq!(game.tick(), Event::Ate);
+        assert_eq!(game.score(), 1);
+        assert_eq!(game.snake().count(), 4);
+        assert!(game.snake().all(|cell| cell != game.food()));
+    }
+
+    #[test]
+    fn hitting_a_wall_ends_the_game() {
+        let mut game = game_with_food(10, 5, Point::new(0, 0));
+        for _ in 0..4 {
+            assert_eq!(game.tick(), Event::Moved);
+        }
+        assert_eq!(game.head(), Point::new(9, 2));
+        assert_eq!(game.tick(), Event::Crashed);
+        assert_eq!(game.state(), State::Over);
+        assert_eq!(game.tick(), Event::Nothing);
+    }
+
+    #[test]
+    fn running_into_itself_ends_the_game() {
+        let mut game = game_with_food(10, 5, Point::new(6, 2));
+        game.tick();
+        game.food = Point::new(7, 2);
+        game.tick();
+        game.food = Point::new(0, 0);
+        // Five cells long now, so a tight U-turn bites the body.
+        game.turn(Direction::Up);
+        assert_eq!(game.tick(), Event::Moved);
+        game.turn(Direction::Left);
+        assert_eq!(game.tick(), Event::Moved);
+        game.turn(Direction::Down);
+        assert_eq!(game.tick(), Event::Crashed);
+    }
+
+    #[test]
+    fn pausing_stops_the_clock() {
+        let mut game = game_with_food(10, 5, Point::new(0, 0));
+        game.toggle_pause();
+        assert_eq!(game.tick(), Event::Nothing);
+        assert_eq!(game.head(), Point::new(5, 2));
+        game.toggle_pause();
+        assert_eq!(game.tick(), Event::Moved);
+    }
+
+    #[test]
+    fn filling_the_board_wins() {
+        let mut game = game_with_food(5, 1, Point::new(3, 0));
+        assert_eq!(game.tick(), Event::Ate);
+        assert_eq!(game.food(), Point::new(4, 0));
+        assert_eq!(game.tick(), Event::Won);
+        assert_eq!(game.state(), State::Won);
+        assert_eq!(game.tick(), Event::Nothing);
+    }
+}
@@ -0,0 +1,11 @@
+//! The game of snake, without a terminal in sight.
+//!
+//! Everything that decides what happens lives in [`game`]: where the snake
+//! is, where it is going, what it ate, whether it crashed. It never reads a
+//! key or draws a character, so its tests can play a game one tick at a
+//! time and check every rule. `main.rs` is the thin layer around it that
+//! does talk to the terminal.
+
+pub mod game;
+
+pub use game::{Direction, Event, Game, Point, State};
@@ -0,0 +1,181 @@
+//! Plays snake in the terminal.
+//!
+//! A game loop has a fixed rhythm: the snake moves once per tick, however
+//! fast or slow the player presses keys. Between ticks we wait for input,
+//! but never past the next tick: `event::poll` is given exactly the time
+//! that is left, so reading keys never holds up the game.
+//!
+//! The terminal is put in raw mode (keys arrive one by one, unechoed, and
+//! Ctrl+C is just a key) and switched to its alternate screen. [`Terminal`]
+//! undoes both when it is dropped, so the shell is back to normal however
+//! the game ends, even by a panic.
+
+use std::io::{self, Stdout, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crossterm::event::{self, Event as TermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Print, Stylize};
+use crossterm::{cursor, queue, terminal};
+
+use snake::{Direction, Game, Point, State};
+
+/// The time between ticks at the start. It gets shorter as the snake grows.
+const START_TICK: Duration = Duration::from_millis(160);
+const FASTEST_TICK: Duration = Duration::from_millis(60);
+
+/// What the player asked for.
+enum Action {
+    Turn(Direction),
+    Pause,
+    Restart,
+    Quit,
+}
+
+fn main() -> io::Result<()> {
+    let mut terminal = Terminal::enter()?;
+    let (columns, rows) = terminal::size()?;
+    // Each cell is two characters wide, so the board looks square. Leave
+    // room for the walls and the score line.
+    let width = (columns.saturating_sub(2) / 2).clamp(5, 30);
+    let height = rows.saturating_sub(3).clamp(5, 20);
+
+    let mut game = Game::new(width, height, seed());
+    let mut next_tick = Instant::now();
+    loop {
+        draw(&mut terminal.out, &game)?;
+
+        let now = Instant::now();
+        if now >= next_tick {
+            game.tick();
+            next_tick = now + tick_length(game.score());
+            continue;
+        }
+        // Wait for a key, but only until the next tick is due.
+        if !event::poll(next_tick - now)? {
+            continue;
+        }
+        match action(event::read()?) {
+            Some(Action::Turn(direction)) => game.turn(direction),
+            Some(Action::Pause) => game.toggle_pause(),
+            Some(Action::Restart) if matches!(game.state(), State::Over | State::Won) => {
+                game = Game::new(width, height, seed());
+            }
+            Some(Action::Restart) | None => {}
+            Some(Action::Quit) => break,
+        }
+    }
+    drop(terminal);
+    println!("Final score: {}", game.score());
+    Ok(())
+}
+
+fn tick_length(score: u32) -> Duration {
+    START_TICK
+        .saturating_sub(Duration::from_millis(5) * score)
+        .max(FASTEST_TICK)
+}
+
+fn action(event: TermEvent) -> Option<Action> {
+    let TermEvent::Key(KeyEvent {
+        code,
+        modifiers,
+        kind: KeyEventKind::Press,
+        ..
+    }) = event
+    else {
+        // Key releases (on terminals that report them), mouse, resizes...
+        return None;
+    };
+    Some(match code {
+        KeyCode::Up | KeyCode::Char('w') => Action::Turn(Direction::Up),
+        KeyCode::Down | KeyCode::Char('s') => Action::Turn(Direction::Down),
+        KeyCode::Left | KeyCode::Char('a') => Action::Turn(Direction::Left),
+        KeyCode::Right | KeyCode::Char('d') => Action::Turn(Direction::Right),
+        KeyCode::Char('p') | KeyCode::Char(' ') => Action::Pause,
+        KeyCode::Char('r') => Action::Restart,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        // In raw mode Ctrl+C doesn't stop the program; we have to.
+        KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+        _ => return None,
+    })
+}
+
+/// Draws the whole frame. `queue!` only buffers the commands; the single
+/// `flush` at the end sends them, so the frame appears all at once.
+fn draw(out: &mut Stdout, game: &Game) -> io::Result<()> {
+    let (width, height) = (game.width(), game.height());
+    queue!(out, cursor::MoveTo(0, 0))?;
+
+    let wall = "██".repeat(usize::from(width) + 2);
+    queue!(
+        out,
+        Print(wall.as_str().dark_grey()),
+        cursor::MoveToNextLine(1)
+    )?;
+    for y in 0..height {
+        queue!(out, Print("██".dark_grey()))?;
+        for x in 0..width {
+            let cell = Point::new(x, y);
+            if cell == game.head() {
+                queue!(out, Print("██".green().bold()))?;
+            } else if game.snake().any(|part| part == cell) {
+                queue!(out, Print("██".dark_green()))?;
+            } else if cell == game.food() {
+                queue!(out, Print("()".red()))?;
+            } else {
+                queue!(out, Print("  "))?;
+            }
+        }
+        queue!(out, Print("██".dark_grey()), cursor::MoveToNextLine(1))?;
+    }
+    queue!(
+        out,
+        Print(wall.as_str().dark_grey()),
+        cursor::MoveToNextLine(1)
+    )?;
+
+    let status = match game.state() {
+        State::Playing => "arrows/WASD steer · P pause · Q quit",
+        State::Paused => "paused · P to carry on",
+        State::Over => "crashed! · R to play again · Q quit",
+        State::Won => "you filled the board! · R to play again · Q quit",
+    };
+    queue!(
+        out,
+        terminal::Clear(terminal::ClearType::CurrentLine),
+        Print(format!("score {}  ·  {status}", game.score()))
+    )?;
+    out.flush()
+}
+
+/// Seeds the food placement from the clock, so every game is different.
+fn seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(1, |elapsed| elapsed.as_nanos() as u64)
+}
+
+/// The terminal in game mode; dropping it puts the terminal back.
+struct Terminal {
+    out: Stdout,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Terminal> {
+        terminal::enable_raw_mode()?;
+        let mut out = io::stdout();
+        queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        queue!(out, terminal::Clear(terminal::ClearType::All))?;
+        out.flush()?;
+        Ok(Terminal { out })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // Nothing useful to do if these fail: we're leaving anyway.
+        let _ = queue!(self.out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = self.out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
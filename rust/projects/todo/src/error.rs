@@ -0,0 +1,45 @@
+//! What can go wrong, as one enum.
+//!
+//! A library returns errors the caller can inspect (`match` on the
+//! variant), rather than strings; `main` decides how to show them. Each
+//! variant keeps the underlying error as its [`source`](std::error::Error::source),
+//! so `anyhow` in `main` prints the whole chain.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    /// No task has this id.
+    NoSuchTask(u32),
+    /// The tasks file could not be read or written.
+    Io { path: PathBuf, source: io::Error },
+    /// The tasks file exists but isn't valid JSON for a [`TodoList`](crate::TodoList).
+    Corrupt {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoSuchTask(id) => write!(f, "there is no task {id}"),
+            Error::Io { path, .. } => write!(f, "cannot access {}", path.display()),
+            Error::Corrupt { path, .. } => {
+                write!(f, "{} is not a valid tasks file", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::NoSuchTask(_) => None,
+            Error::Io { source, .. } => Some(source),
+            Error::Corrupt { source, .. } => Some(source),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! The library half of the `todo` command.
+//!
+//! [`list`] is the tasks themselves, plain data with no idea where it is
+//! stored; [`store`] reads and writes it as JSON. Keeping the two apart is
+//! what makes both easy to test: the list needs no files at all, and the
+//! store is tested in temporary directories. `main.rs` only turns command
+//! line arguments into calls to these two.
+
+pub mod error;
+pub mod list;
+pub mod store;
+
+pub use error::Error;
+pub use list::{Task, TodoList};
+pub use store::Store;
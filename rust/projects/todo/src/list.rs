@@ -0,0 +1,113 @@
+//! The tasks, and everything you can do to them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// One thing to do.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Task {
+    pub id: u32,
+    pub title: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// Every task, in the order they were added.
+///
+/// This struct *is* the file format: serde turns it into JSON like
+/// `{"next_id": 3, "tasks": [{"id": 1, "title": "...", "done": false}]}`.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoList {
+    /// The id the next task gets. Ids are never reused, so `todo done 2`
+    /// can't hit a newer task after task 2 was removed.
+    next_id: u32,
+    tasks: Vec<Task>,
+}
+
+impl TodoList {
+    pub fn new() -> TodoList {
+        TodoList::default()
+    }
+
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
+    }
+
+    /// Adds a task and returns it.
+    pub fn add(&mut self, title: &str) -> &Task {
+        self.next_id = self.next_id.max(1);
+        let task = Task {
+            id: self.next_id,
+            title: title.to_string(),
+            done: false,
+        };
+        self.next_id += 1;
+        self.tasks.push(task);
+        &self.tasks[self.tasks.len() - 1]
+    }
+
+    /// Marks task `id` as done.
+    pub fn complete(&mut self, id: u32) -> Result<&Task, Error> {
+        let task = self
+            .tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or(Error::NoSuchTask(id))?;
+        task.done = true;
+        Ok(task)
+    }
+
+    /// Removes task `id` and returns it.
+    pub fn remove(&mut self, id: u32) -> Result<Task, Error> {
+        let index = self
+            .tasks
+            .iter()
+            .position(|task| task.id == id)
+            .ok_or(Error::NoSuchTask(id))?;
+        Ok(self.tasks.remove(index))
+    }
+
+    /// Removes every finished task, returning how many there were.
+    pub fn clear_done(&mut self) -> usize {
+        let before = self.tasks.len();
+        self.tasks.retain(|task| !task.done);
+        before - self.tasks.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_start_at_one_and_are_never_reused() {
+        let mut list = TodoList::new();
+        assert_eq!(list.add("first").id, 1);
+        assert_eq!(list.add("second").id, 2);
+        list.remove(2).unwrap();
+        assert_eq!(list.add("third").id, 3);
+    }
+
+    #[test]
+    fn completing_and_clearing() {
+        let mut list = TodoList::new();
+        list.add("write tests");
+        list.add("make them pass");
+        assert!(list.complete(1).unwrap().done);
+        assert_eq!(list.clear_done(), 1);
+        let titles: Vec<&str> = list
+            .tasks()
+            .iter()
+            .map(|task| task.title.as_str())
+            .collect();
+        assert_eq!(titles, ["make them pass"]);
+    }
+
+    #[test]
+    fn unknown_ids_are_errors() {
+        let mut list = TodoList::new();
+        assert!(matches!(list.complete(7), Err(Error::NoSuchTask(7))));
+        assert!(matches!(list.remove(7), Err(Error::NoSuchTask(7))));
+    }
+}
@@ -0,0 +1,121 @@
+//! `todo`: keep a to-do list in the terminal.
+//!
+//! ```text
+//! todo add Water the plants   add a task
+//! todo list                   show the tasks still to do
+//!     --all                    include the finished ones
+//! todo done 2                 mark task 2 as done
+//! todo remove 2               delete task 2
+//! todo clear                  delete every finished task
+//! ```
+//!
+//! The tasks live in `~/.todo.json`; use `--file` or `TODO_FILE` to keep
+//! them somewhere else (a list per project, say).
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+
+use todo::{Store, Task};
+
+#[derive(Parser)]
+#[command(name = "todo", version, about = "Keep a to-do list in the terminal")]
+struct Cli {
+    /// The JSON file the tasks are kept in (default: ~/.todo.json).
+    #[arg(long, global = true, env = "TODO_FILE")]
+    file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Add a task.
+    Add {
+        /// What to do; several words are joined with spaces.
+        #[arg(required = true)]
+        title: Vec<String>,
+    },
+    /// Show the tasks still to do.
+    List {
+        /// Show finished tasks too.
+        #[arg(long)]
+        all: bool,
+    },
+    /// Mark a task as done.
+    Done { id: u32 },
+    /// Delete a task.
+    Remove { id: u32 },
+    /// Delete every finished task.
+    Clear,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            // `{:#}` prints the error with its causes: "cannot access
+            // tasks.json: Permission denied (os error 13)".
+            eprintln!("error: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let path = match cli.file {
+        Some(path) => path,
+        None => default_file()?,
+    };
+    let store = Store::new(path);
+    let mut list = store.load()?;
+
+    match cli.command {
+        Command::Add { title } => {
+            let task = list.add(&title.join(" "));
+            println!("Added task {}: {}", task.id, task.title);
+        }
+        Command::List { all } => {
+            let shown: Vec<&Task> = list
+                .tasks()
+                .iter()
+                .filter(|task| all || !task.done)
+                .collect();
+            if shown.is_empty() {
+                println!("Nothing to do!");
+            }
+            for task in shown {
+                let mark = if task.done { "x" } else { " " };
+                println!("[{mark}] {:>3}  {}", task.id, task.title);
+            }
+            // Listing changes nothing, so there's nothing to save.
+            return Ok(());
+        }
+        Command::Done { id } => {
+            let task = list.complete(id)?;
+            println!("Done: {}", task.title);
+        }
+        Command::Remove { id } => {
+            let task = list.remove(id)?;
+            println!("Removed: {}", task.title);
+        }
+        Command::Clear => {
+            let cleared = list.clear_done();
+            let tasks = if cleared == 1 { "task" } else { "tasks" };
+            println!("Cleared {cleared} finished {tasks}.");
+        }
+    }
+    store.save(&list)?;
+    Ok(())
+}
+
+/// `~/.todo.json`.
+fn default_file() -> Result<PathBuf> {
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .context("cannot find your home directory; pass --file or set TODO_FILE")?;
+    Ok(PathBuf::from(home).join(".todo.json"))
+}
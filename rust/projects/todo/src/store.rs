@@ -0,0 +1,115 @@
+//! Saving the list as JSON, and reading it back.
+//!
+//! Two details matter for a file that holds someone's data. A missing file
+//! is not an error, just an empty list: that's how every user starts. And
+//! saving never overwrites the file in place: the new contents go to a
+//! temporary file next to it, which is then renamed over the old one. A
+//! crash halfway through leaves the old file intact instead of half a new
+//! one.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{Error, TodoList};
+
+/// Where the tasks are kept.
+#[derive(Debug, Clone)]
+pub struct Store {
+    path: PathBuf,
+}
+
+impl Store {
+    pub fn new(path: impl Into<PathBuf>) -> Store {
+        Store { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the list, or returns an empty one if there is no file yet.
+    pub fn load(&self) -> Result<TodoList, Error> {
+        let json = match fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(TodoList::new()),
+            Err(source) => return Err(self.io_error(source)),
+        };
+        serde_json::from_str(&json).map_err(|source| Error::Corrupt {
+            path: self.path.clone(),
+            source,
+        })
+    }
+
+    /// Replaces the file's contents with `list`.
+    pub fn save(&self, list: &TodoList) -> Result<(), Error> {
+        let json = serde_json::to_string_pretty(list).expect("a TodoList is always valid JSON");
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|source| self.io_error(source))?;
+        }
+
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+        let write = || -> io::Result<()> {
+            let mut file = fs::File::create(&temporary)?;
+            file.write_all(json.as_bytes())?;
+            file.write_all(b"\n")?;
+            // Make sure the data is on disk before the rename makes it official.
+            file.sync_all()?;
+            fs::rename(&temporary, &self.path)
+        };
+        write().map_err(|source| {
+            let _ = fs::remove_file(&temporary);
+            self.io_error(source)
+        })
+    }
+
+    fn io_error(&self, source: io::Error) -> Error {
+        Error::Io {
+            path: self.path.clone(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_missing_file_is_an_empty_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path().join("tasks.json"));
+        assert_eq!(store.load().unwrap(), TodoList::new());
+    }
+
+    #[test]
+    fn saved_tasks_load_back_the_same() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path().join("nested/folders/tasks.json"));
+        let mut list = TodoList::new();
+        list.add("buy milk");
+        list.add("call mum");
+        list.complete(2).unwrap();
+
+        store.save(&list).unwrap();
+        assert_eq!(store.load().unwrap(), list);
+        // Nothing is left behind but the file itself.
+        let files = fs::read_dir(dir.path().join("nested/folders"))
+            .unwrap()
+            .count();
+        assert_eq!(files, 1);
+    }
+
+    #[test]
+    fn a_corrupt_file_is_reported_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tasks.json");
+        fs::write(&path, "{ not json").unwrap();
+
+        let err = Store::new(&path).load().unwrap_err();
+        assert!(matches!(&err, Error::Corrupt { path: reported, .. } if *reported == path));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
+    }
+}
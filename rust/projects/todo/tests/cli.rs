@@ -0,0 +1,98 @@
+//! Runs the `todo` binary against a tasks file in a temporary directory, so
+//! the tests never touch the real `~/.todo.json` and can't see each other's
+//! tasks.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn todo(file: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_todo"))
+        .arg("--file")
+        .arg(file)
+        .args(args)
+        .env_remove("TODO_FILE")
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "todo failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn tasks_are_kept_between_runs() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("tasks.json");
+
+    assert_eq!(
+        stdout(&todo(&file, &["add", "Water", "the", "plants"])),
+        "Added task 1: Water the plants\n"
+    );
+    stdout(&todo(&file, &["add", "Feed the cat"]));
+    stdout(&todo(&file, &["done", "1"]));
+
+    assert_eq!(stdout(&todo(&file, &["list"])), "[ ]   2  Feed the cat\n");
+    assert_eq!(
+        stdout(&todo(&file, &["list", "--all"])),
+        "[x]   1  Water the plants\n[ ]   2  Feed the cat\n"
+    );
+}
+
+#[test]
+fn clear_removes_finished_tasks() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("tasks.json");
+    stdout(&todo(&file, &["add", "one"]));
+    stdout(&todo(&file, &["add", "two"]));
+    stdout(&todo(&file, &["done", "2"]));
+
+    assert_eq!(
+        stdout(&todo(&file, &["clear"])),
+        "Cleared 1 finished task.\n"
+    );
+    assert_eq!(stdout(&todo(&file, &["list", "--all"])), "[ ]   1  one\n");
+}
+
+#[test]
+fn an_empty_list_says_so() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = todo(&dir.path().join("tasks.json"), &["list"]);
+    assert_eq!(stdout(&output), "Nothing to do!\n");
+    // Reading an empty list doesn't create the file.
+    assert!(!dir.path().join("tasks.json").exists());
+}
+
+#[test]
+fn unknown_tasks_are_reported_on_stderr() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = todo(&dir.path().join("tasks.json"), &["done", "42"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "error: there is no task 42\n"
+    );
+}
+
+#[test]
+fn a_corrupt_file_is_reported_with_its_cause() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("tasks.json");
+    std::fs::write(&file, r#"{"next_id": 1, "tasks": "none"}"#).unwrap();
+
+    let output = todo(&file, &["add", "anything"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("is not a valid tasks file"), "{stderr}");
+    assert!(stderr.contains("expected a sequence"), "{stderr}");
+    // The file is left as it was, for the user to look at.
+    assert_eq!(
+        std::fs::read_to_string(&file).unwrap(),
+        r#"{"next_id": 1, "tasks": "none"}"#
+    );
+}
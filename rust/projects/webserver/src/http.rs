@@ -0,0 +1,219 @@
+//! Just enough HTTP/1.1 to serve a few pages.
+//!
+//! A request starts with a request line (`GET /index.html HTTP/1.1`), then
+//! one `Name: value` header per line, then an empty line. Lines end in
+//! `\r\n`. This server ignores request bodies and answers every request
+//! with `Connection: close`, so each connection carries exactly one request.
+
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+
+/// The longest request line or header line we accept, in bytes.
+const MAX_LINE: u64 = 8 * 1024;
+/// The most headers we accept in one request.
+const MAX_HEADERS: usize = 100;
+
+/// The parts of a request the server looks at.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub version: String,
+    pub headers: Vec<(String, String)>,
+}
+
+/// Why a request couldn't be read.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The client closed the connection without sending anything.
+    Closed,
+    /// Reading from the connection failed, or timed out.
+    Io(io::Error),
+    /// The client sent something that isn't an HTTP request.
+    Malformed(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Closed => f.write_str("the connection closed before a request arrived"),
+            ParseError::Io(err) => write!(f, "cannot read the request: {err}"),
+            ParseError::Malformed(why) => write!(f, "malformed request: {why}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        ParseError::Io(err)
+    }
+}
+
+impl Request {
+    /// Reads a request line and its headers from `reader`.
+    pub fn read_from(reader: &mut impl BufRead) -> Result<Request, ParseError> {
+        let request_line = match read_line(reader)? {
+            Some(line) => line,
+            None => return Err(ParseError::Closed),
+        };
+        let mut parts = request_line.split(' ');
+        let (Some(method), Some(path), Some(version), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ParseError::Malformed(format!(
+                "expected `METHOD /path HTTP/1.1`, got `{request_line}`"
+            )));
+        };
+        if !version.starts_with("HTTP/") || !path.starts_with('/') {
+            return Err(ParseError::Malformed(format!(
+                "expected `METHOD /path HTTP/1.1`, got `{request_line}`"
+            )));
+        }
+
+        let mut headers = Vec::new();
+        loop {
+            let line = read_line(reader)?
+                .ok_or_else(|| ParseError::Malformed("the headers never ended".to_string()))?;
+            if line.is_empty() {
+                break;
+            }
+            if headers.len() == MAX_HEADERS {
+                return Err(ParseError::Malformed(format!(
+                    "more than {MAX_HEADERS} headers"
+                )));
+            }
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| ParseError::Malformed(format!("`{line}` is not a header")))?;
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+
+        Ok(Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            version: version.to_string(),
+            headers,
+        })
+    }
+
+    /// The value of the header called `name`, which is case-insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Reads one line without its line ending, or `None` at the end of the
+/// stream.
+fn read_line(reader: &mut impl BufRead) -> Result<Option<String>, ParseError> {
+    let mut line = String::new();
+    // `take` stops a client from sending one endless line.
+    let read = reader.by_ref().take(MAX_LINE).read_line(&mut line)?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with('\n') {
+        return Err(ParseError::Malformed(format!(
+            "a line is longer than {MAX_LINE} bytes, or the request was cut off"
+        )));
+    }
+    let line = line.trim_end_matches('\n').trim_end_matches('\r');
+    Ok(Some(line.to_string()))
+}
+
+/// A status, a content type, and a body.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub reason: &'static str,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn html(status: u16, reason: &'static str, body: &str) -> Response {
+        Response {
+            status,
+            reason,
+            content_type: "text/html; charset=utf-8",
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    pub fn text(status: u16, reason: &'static str, body: &str) -> Response {
+        Response {
+            status,
+            reason,
+            content_type: "text/plain; charset=utf-8",
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    /// Writes the status line, the headers, and the body to `writer`.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            self.reason,
+            self.content_type,
+            self.body.len()
+        )?;
+        writer.write_all(&self.body)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Request, ParseError> {
+        Request::read_from(&mut text.as_bytes())
+    }
+
+    #[test]
+    fn parses_the_request_line_and_headers() {
+        let request =
+            parse("GET /sleep HTTP/1.1\r\nHost: localhost:7878\r\nUser-Agent: test\r\n\r\n")
+                .unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/sleep");
+        assert_eq!(request.version, "HTTP/1.1");
+        assert_eq!(request.header("host"), Some("localhost:7878"));
+        assert_eq!(request.header("Accept"), None);
+    }
+
+    #[test]
+    fn rejects_what_isnt_http() {
+        assert!(matches!(
+            parse("hello\r\n\r\n"),
+            Err(ParseError::Malformed(_))
+        ));
+        assert!(matches!(
+            parse("GET / HTTP/1.1\r\nno colon here\r\n\r\n"),
+            Err(ParseError::Malformed(_))
+        ));
+        assert!(matches!(
+            parse("GET / HTTP/1.1\r\nHost: x\r\n"),
+            Err(ParseError::Malformed(_))
+        ));
+        assert!(matches!(parse(""), Err(ParseError::Closed)));
+    }
+
+    #[test]
+    fn writes_a_response_with_its_length() {
+        let mut out = Vec::new();
+        Response::text(404, "Not Found", "nope")
+            .write_to(&mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: 4\r\nConnection: close\r\n\r\nnope"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! A small multi-threaded web server.
+//!
+//! Read it in this order:
+//!
+//! 1. [`pool`]: a [`ThreadPool`] that runs jobs on a fixed number of worker
+//!    threads, and waits for them when it is dropped.
+//! 2. [`http`]: parsing a request from a TCP stream and writing a response.
+//! 3. [`server`]: the accept loop that ties the two together, the
+//!    [`ShutdownHandle`] that stops it, and [`Draining`], what is left to
+//!    answer once it has.
+
+pub mod http;
+pub mod pool;
+pub mod server;
+
+pub use pool::ThreadPool;
+pub use server::{Draining, Server, ShutdownHandle};
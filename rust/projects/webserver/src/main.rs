@@ -0,0 +1,70 @@
+//! Serves http://127.0.0.1:7878, or the address given as the first
+//! argument, with four worker threads.
+//!
+//! Open http://127.0.0.1:7878/sleep in one tab and http://127.0.0.1:7878 in
+//! another: the second page loads right away, because another worker
+//! answers it. Press Ctrl-C (or send the process SIGTERM) to shut the
+//! server down gracefully: it stops accepting connections, answers the
+//! requests it already has, and gives up on any still running after
+//! [`GRACE`]. A second Ctrl-C quits at once.
+
+use std::env;
+use std::process;
+use std::time::Duration;
+
+use webserver::Server;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// How long the requests in flight get to finish after Ctrl-C.
+const GRACE: Duration = Duration::from_secs(2);
+
+fn main() {
+    let addr = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDR.to_string());
+    let server = Server::bind(&addr, 4).unwrap_or_else(|err| {
+        eprintln!("cannot start the server on {addr}: {err}");
+        process::exit(1);
+    });
+    let addr = server.local_addr().expect("the server is listening");
+    let handle = server.shutdown_handle().expect("the server is listening");
+
+    // ctrlc runs the handler on a thread of its own, not inside the signal
+    // handler, so it may lock, allocate, and print like any other code.
+    let mut interrupted = false;
+    let installed = ctrlc::set_handler(move || {
+        if interrupted {
+            // Whoever pressed Ctrl-C twice doesn't want to wait.
+            process::exit(130);
+        }
+        interrupted = true;
+        handle.shutdown();
+    });
+    if let Err(err) = installed {
+        eprintln!("cannot handle Ctrl-C: {err}");
+        process::exit(1);
+    }
+    println!("Listening on http://{addr}. Press Ctrl-C to stop.");
+
+    let draining = server.run_until_shutdown();
+    println!(
+        "Shutting down: {} in progress.",
+        requests(draining.in_flight())
+    );
+    match draining.finish(GRACE) {
+        0 => println!("Every request answered. Bye!"),
+        unfinished => println!(
+            "Gave up on {} after {}s. Bye!",
+            requests(unfinished),
+            GRACE.as_secs()
+        ),
+    }
+}
+
+fn requests(count: usize) -> String {
+    match count {
+        1 => "1 request".to_string(),
+        _ => format!("{count} requests"),
+    }
+}
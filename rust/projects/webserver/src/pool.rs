@@ -0,0 +1,199 @@
+//! A fixed-size pool of worker threads.
+//!
+//! Spawning a thread per connection would let a burst of requests create
+//! thousands of threads. Instead, a handful of workers share one channel of
+//! jobs: each worker locks the receiving end just long enough to take the
+//! next job, then runs it. Dropping the pool closes the channel, and the
+//! workers finish the jobs still queued before they exit.
+//!
+//! Waiting for every job can take forever if one of them hangs, so
+//! [`ThreadPool::shutdown`] waits only so long, and reports how many jobs
+//! were still unfinished when it gave up.
+
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Runs closures on a fixed number of threads.
+pub struct ThreadPool {
+    workers: Vec<JoinHandle<()>>,
+    /// `None` once the pool is being dropped.
+    sender: Option<mpsc::Sender<Job>>,
+    /// How many jobs are queued or running.
+    pending: Arc<AtomicUsize>,
+}
+
+/// A pool needs at least one thread.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PoolCreationError;
+
+impl fmt::Display for PoolCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a thread pool needs at least one thread")
+    }
+}
+
+impl Error for PoolCreationError {}
+
+impl ThreadPool {
+    /// Creates a pool of `size` threads.
+    pub fn build(size: usize) -> Result<ThreadPool, PoolCreationError> {
+        if size == 0 {
+            return Err(PoolCreationError);
+        }
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let pending = Arc::new(AtomicUsize::new(0));
+        let workers = (0..size)
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                let pending = Arc::clone(&pending);
+                thread::spawn(move || loop {
+                    // The guard is dropped at the end of this statement, so
+                    // other workers can take jobs while this one runs.
+                    let message = receiver.lock().unwrap().recv();
+                    match message {
+                        Ok(job) => {
+                            // A panicking job shouldn't take its worker down
+                            // with it; the panic message is printed already.
+                            let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                            pending.fetch_sub(1, Ordering::SeqCst);
+                        }
+                        // The pool was dropped and the queue is empty.
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+        Ok(ThreadPool {
+            workers,
+            sender: Some(sender),
+            pending,
+        })
+    }
+
+    /// How many threads the pool runs jobs on.
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// How many jobs are queued or running.
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// Queues `job` to run on the next free worker.
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if let Some(sender) = &self.sender {
+            self.pending.fetch_add(1, Ordering::SeqCst);
+            // Workers only stop once the sender is gone, so this can't fail.
+            sender.send(Box::new(job)).unwrap();
+        }
+    }
+
+    /// Stops taking jobs and waits up to `grace` for the queued ones to
+    /// finish. Returns how many hadn't by then: their workers are left
+    /// running, and end with the process if nothing else stops them.
+    pub fn shutdown(mut self, grace: Duration) -> usize {
+        drop(self.sender.take());
+        let deadline = Instant::now() + grace;
+        // JoinHandle::join can't be given a timeout, so poll instead.
+        while self.workers.iter().any(|worker| !worker.is_finished()) {
+            if Instant::now() >= deadline {
+                // Dropping a JoinHandle detaches its thread.
+                self.workers.clear();
+                return self.pending();
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        0
+    }
+}
+
+impl Drop for ThreadPool {
+    /// Waits for every queued job to finish.
+    fn drop(&mut self) {
+        // Closing the channel ends each worker's loop once the queue is empty.
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn needs_at_least_one_thread() {
+        assert_eq!(ThreadPool::build(0).err(), Some(PoolCreationError));
+        assert_eq!(ThreadPool::build(3).unwrap().size(), 3);
+    }
+
+    #[test]
+    fn dropping_the_pool_waits_for_every_job() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::build(4).unwrap();
+        for _ in 0..100 {
+            let done = Arc::clone(&done);
+            pool.execute(move || {
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        drop(pool);
+        assert_eq!(done.load(Ordering::SeqCst), 100);
+    }
+
+    #[test]
+    fn a_panicking_job_does_not_kill_its_worker() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::build(1).unwrap();
+        pool.execute(|| panic!("this job fails on purpose"));
+        let after = Arc::clone(&done);
+        pool.execute(move || {
+            after.fetch_add(1, Ordering::SeqCst);
+        });
+        drop(pool);
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn shutdown_waits_for_quick_jobs() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::build(2).unwrap();
+        for _ in 0..10 {
+            let done = Arc::clone(&done);
+            pool.execute(move || {
+                thread::sleep(Duration::from_millis(5));
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        assert_eq!(pool.shutdown(Duration::from_secs(5)), 0);
+        assert_eq!(done.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn shutdown_gives_up_on_jobs_that_run_too_long() {
+        let pool = ThreadPool::build(2).unwrap();
+        let (release, stuck) = mpsc::channel::<()>();
+        // Blocks until `release` is dropped, long after the grace period
+        pool.execute(move || {
+            let _ = stuck.recv();
+        });
+        pool.execute(|| {});
+        let started = Instant::now();
+        assert_eq!(pool.shutdown(Duration::from_millis(100)), 1);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        drop(release);
+    }
+}
@@ -0,0 +1,164 @@
+//! The accept loop: every connection is handed to the thread pool, which
+//! reads the request, routes it, and writes the response.
+//!
+//! Stopping is the tricky part. `TcpListener::incoming` blocks until the
+//! next connection arrives, so a [`ShutdownHandle`] sets a flag and then
+//! connects to the server itself to wake the loop up. The loop sees the
+//! flag and returns, and dropping the pool waits for the requests already
+//! accepted. [`Server::run_until_shutdown`] returns before that wait, as a
+//! [`Draining`] server, so the caller can give up on requests that take
+//! too long.
+
+use std::io::{self, BufReader};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::http::{ParseError, Request, Response};
+use crate::pool::ThreadPool;
+
+/// How long `GET /sleep` takes to answer, to make concurrency visible.
+pub const SLEEP: Duration = Duration::from_millis(500);
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+const HELLO: &str = include_str!("../static/hello.html");
+const NOT_FOUND: &str = include_str!("../static/404.html");
+
+/// A listening socket and the threads that answer it.
+pub struct Server {
+    listener: TcpListener,
+    pool: ThreadPool,
+    stopping: Arc<AtomicBool>,
+}
+
+/// A [`Server`] that has stopped accepting connections, still answering
+/// the ones it had. Dropping it waits for all of them.
+pub struct Draining {
+    pool: ThreadPool,
+}
+
+/// Stops a [`Server`] from another thread.
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    stopping: Arc<AtomicBool>,
+    addr: SocketAddr,
+}
+
+impl Server {
+    /// Listens on `addr` (port 0 picks a free port), answering with
+    /// `threads` worker threads.
+    pub fn bind(addr: impl ToSocketAddrs, threads: usize) -> io::Result<Server> {
+        let pool = ThreadPool::build(threads)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            pool,
+            stopping: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// The address the server is listening on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn shutdown_handle(&self) -> io::Result<ShutdownHandle> {
+        let mut addr = self.local_addr()?;
+        // A server listening on every interface can be reached on loopback.
+        if addr.ip().is_unspecified() {
+            addr.set_ip(match addr.ip() {
+                IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            });
+        }
+        Ok(ShutdownHandle {
+            stopping: Arc::clone(&self.stopping),
+            addr,
+        })
+    }
+
+    /// Answers connections until [`ShutdownHandle::shutdown`] is called.
+    /// Returns once every accepted request has been answered.
+    pub fn run(self) {
+        // Dropping the pool waits for its workers.
+        drop(self.run_until_shutdown());
+    }
+
+    /// Answers connections until [`ShutdownHandle::shutdown`] is called,
+    /// then closes the listener and returns the requests still being
+    /// answered.
+    pub fn run_until_shutdown(self) -> Draining {
+        let Server {
+            listener,
+            pool,
+            stopping,
+        } = self;
+        for stream in listener.incoming() {
+            if stopping.load(Ordering::SeqCst) {
+                break;
+            }
+            match stream {
+                Ok(stream) => pool.execute(|| handle_connection(stream)),
+                Err(err) => eprintln!("cannot accept a connection: {err}"),
+            }
+        }
+        Draining { pool }
+    }
+}
+
+impl Draining {
+    /// How many accepted requests are waiting for a worker or being
+    /// answered.
+    pub fn in_flight(&self) -> usize {
+        self.pool.pending()
+    }
+
+    /// Waits up to `grace` for the requests in flight to be answered.
+    /// Returns how many still weren't.
+    pub fn finish(self, grace: Duration) -> usize {
+        self.pool.shutdown(grace)
+    }
+}
+
+impl ShutdownHandle {
+    /// Tells the server to stop accepting connections. Requests it has
+    /// already accepted are still answered.
+    pub fn shutdown(&self) {
+        if !self.stopping.swap(true, Ordering::SeqCst) {
+            // Wake the accept loop up. If this fails, the server is gone.
+            let _ = TcpStream::connect(self.addr);
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) {
+    // Without a timeout, a client that never sends a request would tie up
+    // a worker forever.
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let response = match Request::read_from(&mut BufReader::new(&stream)) {
+        Ok(request) => route(&request),
+        Err(ParseError::Malformed(why)) => Response::text(400, "Bad Request", &format!("{why}\n")),
+        // Nobody is left to answer, or the client went quiet.
+        Err(ParseError::Closed | ParseError::Io(_)) => return,
+    };
+    if let Err(err) = response.write_to(&mut stream) {
+        eprintln!("cannot send a response: {err}");
+    }
+}
+
+/// Picks the response for `request`.
+pub fn route(request: &Request) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => Response::html(200, "OK", HELLO),
+        ("GET", "/sleep") => {
+            thread::sleep(SLEEP);
+            Response::html(200, "OK", HELLO)
+        }
+        (_, "/" | "/sleep") => Response::text(405, "Method Not Allowed", "only GET works here\n"),
+        _ => Response::html(404, "Not Found", NOT_FOUND),
+    }
+}
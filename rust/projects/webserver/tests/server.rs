@@ -0,0 +1,156 @@
+//! Starts real servers on localhost and talks to them over TCP.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use webserver::server::SLEEP;
+use webserver::{Server, ShutdownHandle};
+
+/// A server on a free port, running on its own thread.
+struct Running {
+    addr: SocketAddr,
+    handle: ShutdownHandle,
+    thread: JoinHandle<()>,
+}
+
+fn start(threads: usize) -> Running {
+    let server = Server::bind("127.0.0.1:0", threads).unwrap();
+    let addr = server.local_addr().unwrap();
+    let handle = server.shutdown_handle().unwrap();
+    let thread = thread::spawn(move || server.run());
+    Running {
+        addr,
+        handle,
+        thread,
+    }
+}
+
+impl Running {
+    fn stop(self) {
+        self.handle.shutdown();
+        self.thread.join().unwrap();
+    }
+}
+
+/// Sends `request` as is and returns the status code and the body.
+fn send(addr: SocketAddr, request: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status, body.to_string())
+}
+
+fn get(addr: SocketAddr, path: &str) -> (u16, String) {
+    send(
+        addr,
+        &format!("GET {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n"),
+    )
+}
+
+#[test]
+fn serves_the_home_page() {
+    let server = start(2);
+    let (status, body) = get(server.addr, "/");
+    assert_eq!(status, 200);
+    assert!(body.contains("<h1>Hello!</h1>"));
+    server.stop();
+}
+
+#[test]
+fn answers_unknown_paths_with_404() {
+    let server = start(2);
+    let (status, body) = get(server.addr, "/missing");
+    assert_eq!(status, 404);
+    assert!(body.contains("Oops!"));
+    server.stop();
+}
+
+#[test]
+fn answers_other_methods_with_405() {
+    let server = start(2);
+    let (status, _) = send(server.addr, "POST / HTTP/1.1\r\nContent-Length: 0\r\n\r\n");
+    assert_eq!(status, 405);
+    server.stop();
+}
+
+#[test]
+fn answers_garbage_with_400() {
+    let server = start(2);
+    let (status, body) = send(server.addr, "hello there\r\n\r\n");
+    assert_eq!(status, 400);
+    assert!(body.contains("METHOD /path"));
+    server.stop();
+}
+
+#[test]
+fn handles_requests_concurrently() {
+    let server = start(4);
+    let started = Instant::now();
+    let clients: Vec<_> = (0..4)
+        .map(|_| {
+            let addr = server.addr;
+            thread::spawn(move || get(addr, "/sleep").0)
+        })
+        .collect();
+    for client in clients {
+        assert_eq!(client.join().unwrap(), 200);
+    }
+    // One after the other, four requests would take 4 * SLEEP.
+    assert!(
+        started.elapsed() < SLEEP * 2,
+        "four slow requests on four threads took {:?}",
+        started.elapsed()
+    );
+    server.stop();
+}
+
+#[test]
+fn a_single_thread_answers_one_request_at_a_time() {
+    let server = start(1);
+    let started = Instant::now();
+    let clients: Vec<_> = (0..2)
+        .map(|_| {
+            let addr = server.addr;
+            thread::spawn(move || get(addr, "/sleep").0)
+        })
+        .collect();
+    for client in clients {
+        assert_eq!(client.join().unwrap(), 200);
+    }
+    assert!(started.elapsed() >= SLEEP * 2);
+    server.stop();
+}
+
+#[test]
+fn a_slow_request_does_not_hold_up_a_fast_one() {
+    let server = start(2);
+    let addr = server.addr;
+    let slow = thread::spawn(move || get(addr, "/sleep").0);
+    thread::sleep(Duration::from_millis(50));
+
+    let started = Instant::now();
+    assert_eq!(get(server.addr, "/").0, 200);
+    assert!(started.elapsed() < SLEEP);
+
+    assert_eq!(slow.join().unwrap(), 200);
+    server.stop();
+}
+
+#[test]
+fn shutting_down_finishes_the_requests_in_progress() {
+    let server = start(2);
+    let addr = server.addr;
+    let slow = thread::spawn(move || get(addr, "/sleep").0);
+    // Give the server time to accept the slow request.
+    thread::sleep(Duration::from_millis(100));
+
+    server.stop();
+    assert_eq!(slow.join().unwrap(), 200);
+    // The listener is closed once `run` has returned.
+    assert!(TcpStream::connect(addr).is_err());
+}
@@ -0,0 +1,117 @@
+//! Starts the webserver binary, sends it signals the way Ctrl-C and `kill`
+//! would, and checks what it says on the way out.
+
+#![cfg(unix)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The binary, listening on a free port.
+struct Process {
+    child: Child,
+    stdout: BufReader<ChildStdout>,
+    addr: SocketAddr,
+}
+
+fn start() -> Process {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_webserver"))
+        .arg("127.0.0.1:0")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+    // "Listening on http://127.0.0.1:PORT. Press Ctrl-C to stop."
+    let addr = line
+        .strip_prefix("Listening on http://")
+        .and_then(|rest| rest.split_once(". "))
+        .unwrap_or_else(|| panic!("unexpected first line: {line:?}"))
+        .0
+        .parse()
+        .unwrap();
+    Process {
+        child,
+        stdout,
+        addr,
+    }
+}
+
+impl Process {
+    fn signal(&self, name: &str) {
+        let status = Command::new("kill")
+            .arg(format!("-{name}"))
+            .arg(self.child.id().to_string())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// Waits for the process to exit, returning the rest of its output.
+    fn finish(mut self) -> String {
+        let mut rest = String::new();
+        self.stdout.read_to_string(&mut rest).unwrap();
+        assert!(self.child.wait().unwrap().success());
+        rest
+    }
+}
+
+fn get(addr: SocketAddr, path: &str) -> u16 {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response.split(' ').nth(1).unwrap().parse().unwrap()
+}
+
+#[test]
+fn ctrl_c_finishes_the_requests_in_progress() {
+    let server = start();
+    let addr = server.addr;
+    let slow = thread::spawn(move || get(addr, "/sleep"));
+    // Give the server time to accept the slow request.
+    thread::sleep(Duration::from_millis(100));
+
+    server.signal("INT");
+    assert_eq!(slow.join().unwrap(), 200);
+    assert_eq!(
+        server.finish(),
+        "Shutting down: 1 request in progress.\nEvery request answered. Bye!\n"
+    );
+    assert!(TcpStream::connect(addr).is_err());
+}
+
+#[test]
+fn sigterm_shuts_down_an_idle_server() {
+    let server = start();
+    assert_eq!(get(server.addr, "/"), 200);
+    server.signal("TERM");
+    assert_eq!(
+        server.finish(),
+        "Shutting down: 0 requests in progress.\nEvery request answered. Bye!\n"
+    );
+}
+
+#[test]
+fn gives_up_on_requests_that_outlast_the_grace_period() {
+    let server = start();
+    // A client that connects and never sends a request keeps its worker
+    // waiting for the whole read timeout, longer than the grace period.
+    let _silent = TcpStream::connect(server.addr).unwrap();
+    thread::sleep(Duration::from_millis(100));
+
+    let started = Instant::now();
+    server.signal("INT");
+    assert_eq!(
+        server.finish(),
+        "Shutting down: 1 request in progress.\nGave up on 1 request after 2s. Bye!\n"
+    );
+    let waited = started.elapsed();
+    assert!(
+        waited >= Duration::from_secs(2) && waited < Duration::from_secs(4),
+        "shutting down took {waited:?}",
+    );
+}
@@ -0,0 +1,20 @@
+// Puzzle 1: Greet Twice
+//
+// Goal: make this compile and print the greeting twice.
+// Rules: no `.clone()`, `.to_owned()`, or `.to_string()`.
+//
+// Check your answer with `cargo run -p tutor -- puzzle moved_name`.
+
+fn greet(name: String) {
+    println!("Hello, {}!", name);
+}
+
+fn main() {
+    let name = String::from("Ferris");
+    greet(name);
+    greet(name);
+}
+
+// EXPECTED:
+// Hello, Ferris!
+// Hello, Ferris!
@@ -0,0 +1,18 @@
+// Puzzle 2: The Biggest Score
+//
+// Goal: make this compile without changing what it prints.
+// Rules: no `.clone()`, `.cloned()`, `.copied()`, or `.to_vec()`.
+//
+// Check your answer with `cargo run -p tutor -- puzzle push_while_borrowed`.
+
+fn main() {
+    let mut scores = vec![3, 8, 2];
+    let largest = scores.iter().max().unwrap();
+    scores.push(10);
+    println!("largest before the push: {}", largest);
+    println!("scores: {:?}", scores);
+}
+
+// EXPECTED:
+// largest before the push: 8
+// scores: [3, 8, 2, 10]
@@ -0,0 +1,24 @@
+// Puzzle 3: The Longest Word
+//
+// Goal: make `longest` compile. It must keep returning a borrowed `&str`
+// that points into one of its arguments.
+// Rules: no `String` anywhere, and no `.to_string()` or `.to_owned()`.
+//
+// Check your answer with `cargo run -p tutor -- puzzle longest`.
+
+fn longest(x: &str, y: &str) -> &str {
+    if x.len() >= y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+fn main() {
+    let first = "borrow";
+    let second = "checker";
+    println!("longer: {}", longest(first, second));
+}
+
+// EXPECTED:
+// longer: checker
@@ -0,0 +1,20 @@
+// Puzzle 4: A Greeting That Outlives Its Function
+//
+// Goal: make `make_greeting` compile.
+// Rules: no `unsafe`, no `Box`, and no `.leak()`. The greeting has to be
+// built at runtime, so a string literal won't do.
+//
+// Check your answer with `cargo run -p tutor -- puzzle dangling_greeting`.
+
+fn make_greeting(name: &str) -> &str {
+    let greeting = format!("Hello, {}!", name);
+    &greeting
+}
+
+fn main() {
+    let greeting = make_greeting("Ferris");
+    println!("{}", greeting);
+}
+
+// EXPECTED:
+// Hello, Ferris!
@@ -0,0 +1,36 @@
+// Puzzle 5: Two Players, One Vec
+//
+// Goal: give each player their points.
+// Rules: `add_points` must keep its signature. No `.clone()`, no `unsafe`,
+// and no `Rc`, `RefCell`, or `Cell`.
+//
+// Check your answer with `cargo run -p tutor -- puzzle two_players`.
+
+struct Player {
+    name: String,
+    score: u32,
+}
+
+fn add_points(player: &mut Player, points: u32) {
+    player.score += points;
+}
+
+fn main() {
+    let mut players = vec![
+        Player { name: String::from("Ada"), score: 0 },
+        Player { name: String::from("Grace"), score: 0 },
+    ];
+
+    let first = &mut players[0];
+    let second = &mut players[1];
+    add_points(first, 10);
+    add_points(second, 5);
+
+    for player in &players {
+        println!("{}: {}", player.name, player.score);
+    }
+}
+
+// EXPECTED:
+// Ada: 10
+// Grace: 5
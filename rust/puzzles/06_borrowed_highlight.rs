@@ -0,0 +1,27 @@
+// Puzzle 6: A Struct That Borrows
+//
+// Goal: make `Highlight` compile. It should point into the text, not copy it.
+// Rules: no `String` in the struct or its methods, and no `.to_string()`,
+// `.to_owned()`, or `.clone()`.
+//
+// Check your answer with `cargo run -p tutor -- puzzle borrowed_highlight`.
+
+struct Highlight {
+    text: &str,
+}
+
+impl Highlight {
+    fn length(&self) -> usize {
+        self.text.len()
+    }
+}
+
+fn main() {
+    let book = "Call me Ishmael. Some years ago...";
+    let first_sentence = book.split('.').next().unwrap_or("");
+    let highlight = Highlight { text: first_sentence };
+    println!("{} ({} bytes)", highlight.text, highlight.length());
+}
+
+// EXPECTED:
+// Call me Ishmael (15 bytes)
@@ -0,0 +1,17 @@
+// Exercise: Ownership 1 - A value has exactly one owner
+// Related example: examples/03_ownership.rs
+//
+// `let first = name;` MOVES the String out of `name` and into `first`.
+// After the move, `name` can't be used anymore - the compiler reports
+// error E0382 ("borrow of moved value").
+//
+// TODO: Keep both greetings working. Make a copy of `name` for the second
+//       greeting (hint: Strings have a `.clone()` method).
+//
+// Check your work with: cargo run -p tutor -- check ownership1
+
+pub fn greetings(name: String) -> (String, String) {
+    let second = name.clone();
+    let first = name;
+    (format!("Hello, {first}!"), format!("Goodbye, {second}!"))
+}
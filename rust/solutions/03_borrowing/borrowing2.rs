@@ -0,0 +1,12 @@
+// Exercise: Borrowing 2 - Mutable references
+//
+// A shared reference (`&T`) lets you read a value. To change a value you
+// don't own you need a mutable reference (`&mut T`).
+//
+// TODO: Change the parameter of `add_suffix` so it can modify the String.
+//
+// Check your work with: cargo run -p tutor -- check borrowing2
+
+pub fn add_suffix(name: &mut String) {
+    name.push_str(".rs");
+}
@@ -0,0 +1,52 @@
+// Exercise: Enums 2 - Extending a state machine
+// Related example: examples/enums/05_state_machine.rs
+//
+// A music player is either stopped or playing a track. Users have asked
+// for a pause button: pausing keeps the current track, and resuming
+// carries on playing it.
+//
+// TODO: Add a `Paused { track: u32 }` variant to `Player`.
+// TODO: In `handle`, make `Pause` go from Playing to Paused, and `Resume`
+//       go from Paused back to Playing (same track). Pausing or resuming
+//       in any other state changes nothing.
+// TODO: `describe` has no wildcard arm, so the compiler will tell you it
+//       needs a case for the new state. Describe a paused player as
+//       "paused on track N".
+//
+// Check your work with: cargo run -p tutor -- check enums2
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Player {
+    Stopped,
+    Playing { track: u32 },
+    Paused { track: u32 },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Command {
+    Play(u32),
+    Pause,
+    Resume,
+    Stop,
+}
+
+impl Player {
+    pub fn handle(self, command: Command) -> Player {
+        match (self, command) {
+            (_, Command::Play(track)) => Player::Playing { track },
+            (_, Command::Stop) => Player::Stopped,
+            (Player::Playing { track }, Command::Pause) => Player::Paused { track },
+            (Player::Paused { track }, Command::Resume) => Player::Playing { track },
+            // Commands that don't apply leave the player as it was
+            (state, _) => state,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Player::Stopped => String::from("stopped"),
+            Player::Playing { track } => format!("playing track {}", track),
+            Player::Paused { track } => format!("paused on track {}", track),
+        }
+    }
+}
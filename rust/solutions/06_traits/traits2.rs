@@ -0,0 +1,33 @@
+// Exercise: Traits 2 - Default methods
+//
+// A trait can provide a default implementation that every implementor gets
+// for free. `Dog` and `Cat` only implement `name`, so `describe` needs a
+// default body or the code won't compile.
+//
+// TODO: Give `describe` a default implementation that returns
+//       "This is <name>." using `self.name()`.
+//
+// Check your work with: cargo run -p tutor -- check traits2
+
+pub trait Describe {
+    fn name(&self) -> String;
+
+    fn describe(&self) -> String {
+        format!("This is {}.", self.name())
+    }
+}
+
+pub struct Dog;
+pub struct Cat;
+
+impl Describe for Dog {
+    fn name(&self) -> String {
+        String::from("a dog")
+    }
+}
+
+impl Describe for Cat {
+    fn name(&self) -> String {
+        String::from("a cat")
+    }
+}
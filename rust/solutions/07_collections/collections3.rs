@@ -0,0 +1,28 @@
+// Exercise: Collections 3 - Counting with fold
+// Related example: examples/collections/02_hashmap.rs
+//
+// The loop version:
+//
+//     let mut counts = HashMap::new();
+//     for c in text.chars() {
+//         if c.is_alphabetic() {
+//             *counts.entry(c.to_ascii_lowercase()).or_insert(0) += 1;
+//         }
+//     }
+//     counts
+//
+// TODO: Rewrite `letter_counts` with an iterator chain. `fold` can carry
+//       the HashMap through the pipeline as its accumulator.
+//
+// Check your work with: cargo run -p tutor -- check collections3
+
+use std::collections::HashMap;
+
+pub fn letter_counts(text: &str) -> HashMap<char, usize> {
+    text.chars()
+        .filter(|c| c.is_alphabetic())
+        .fold(HashMap::new(), |mut counts, c| {
+            *counts.entry(c.to_ascii_lowercase()).or_insert(0) += 1;
+            counts
+        })
+}
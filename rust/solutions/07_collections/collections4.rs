@@ -0,0 +1,30 @@
+// Exercise: Collections 4 - Grouping into a sorted map
+// Related example: examples/collections/04_btreemap.rs
+//
+// The loop version:
+//
+//     let mut groups: BTreeMap<usize, BTreeSet<String>> = BTreeMap::new();
+//     for word in words {
+//         let word = word.to_lowercase();
+//         groups.entry(word.len()).or_default().insert(word);
+//     }
+//     groups
+//
+// Words are grouped by length; each group is a sorted set, so duplicates
+// disappear and the output order is predictable.
+//
+// TODO: Rewrite `group_by_length` without a `for` loop.
+//
+// Check your work with: cargo run -p tutor -- check collections4
+
+use std::collections::{BTreeMap, BTreeSet};
+
+pub fn group_by_length(words: &[&str]) -> BTreeMap<usize, BTreeSet<String>> {
+    words
+        .iter()
+        .map(|word| word.to_lowercase())
+        .fold(BTreeMap::new(), |mut groups, word| {
+            groups.entry(word.len()).or_default().insert(word);
+            groups
+        })
+}
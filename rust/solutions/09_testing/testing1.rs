@@ -0,0 +1,45 @@
+// Exercise: Testing 1 - Tests that catch bugs
+// Related example: examples/testing/01_unit_tests.rs
+//
+// This time the code is already correct. YOUR job is to write the tests.
+//
+// How does the tutor know your tests are any good? It secretly plants
+// small bugs in `is_leap_year` (one at a time) and runs your tests against
+// each buggy copy. Every bug must make at least one of your tests fail.
+// A test suite that passes no matter what the code does tests nothing!
+//
+// The rules for leap years:
+//   - years divisible by 4 are leap years (2024),
+//   - except years divisible by 100, which are not (1900),
+//   - except years divisible by 400, which are (2000).
+//
+// TODO: Write tests in the `tests` module below until every planted bug
+//       is caught. Don't change `is_leap_year` itself.
+//
+// Check your work with: cargo run -p tutor -- check testing1
+
+pub fn is_leap_year(year: u32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn years_divisible_by_four_are_leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(!is_leap_year(2022));
+        assert!(!is_leap_year(2023));
+    }
+
+    #[test]
+    fn centuries_are_not_leap_years() {
+        assert!(!is_leap_year(1900));
+    }
+
+    #[test]
+    fn every_fourth_century_is_a_leap_year() {
+        assert!(is_leap_year(2000));
+    }
+}
@@ -0,0 +1,22 @@
+// Exercise: Macros 1 - One expression
+// Related example: examples/macros/01_first_macro.rs
+//
+// `square!(x)` should produce x * x for any expression x, including
+// `square!(2 + 3)`, which must be 25. (A C-style text macro would give
+// 2 + 3 * 2 + 3 = 11; Rust macros work on whole expressions instead.)
+//
+// The argument must also be evaluated only ONCE: `square!(next_number())`
+// should call `next_number` a single time.
+//
+// TODO: Replace the placeholder rule so that `square!(EXPRESSION)` works.
+//       Hint: capture it with `$x:expr`, then store it in a variable
+//       inside a `{{ ... }}` block.
+//
+// Check your work with: cargo run -p tutor -- check macros1
+
+macro_rules! square {
+    ($x:expr) => {{
+        let x = $x;
+        x * x
+    }};
+}
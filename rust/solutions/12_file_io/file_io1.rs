@@ -0,0 +1,92 @@
+// Exercise: File I/O 1 - A tiny `du`
+// Related example: examples/file_io/03_walking_directories.rs
+//
+// `du` ("disk usage") tells you what is taking up the space in a
+// directory. Ours lists every entry directly inside a directory, with the
+// total size of all the files at or under it, largest first:
+//
+//       4.9 KiB  big.bin
+//       3.4 KiB  src
+//         350 B  docs
+//           0 B  empty
+//
+// `report` is already written; it needs the three functions below.
+//
+// TODO: Implement `total_size`. A file counts its own length; a directory
+//       counts everything inside it, however deep. Don't follow symbolic
+//       links (they could loop back up the tree): a link counts as 0.
+//       Hint: fs::symlink_metadata doesn't follow links, fs::metadata does.
+// TODO: Implement `summarize`: one `Usage` per entry of `dir`, sorted by
+//       size, largest first. Entries of the same size go by name.
+// TODO: Implement `human_size`: below 1024 bytes, the number and "B";
+//       above that, KiB, MiB, or GiB with one decimal ("1.5 KiB").
+//
+// Check your work with: cargo run -p tutor -- check file_io1
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How much space one entry of a directory takes up.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Usage {
+    /// The entry's file name, like `src`.
+    pub name: String,
+    /// The bytes in every file at or under the entry.
+    pub bytes: u64,
+}
+
+/// The size of `path` in bytes: a file's length, or everything under a
+/// directory. Symbolic links count as 0.
+pub fn total_size(path: &Path) -> io::Result<u64> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_file() {
+        return Ok(metadata.len());
+    }
+    if !metadata.is_dir() {
+        return Ok(0);
+    }
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        total += total_size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+/// One `Usage` per entry in `dir`, largest first, then by name.
+pub fn summarize(dir: &Path) -> io::Result<Vec<Usage>> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        entries.push(Usage {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            bytes: total_size(&entry.path())?,
+        });
+    }
+    entries.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// A byte count for people: `512 B`, `1.5 KiB`, `20.0 MiB`.
+pub fn human_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    for unit in ["KiB", "MiB"] {
+        if size < 1024.0 {
+            return format!("{size:.1} {unit}");
+        }
+        size /= 1024.0;
+    }
+    format!("{size:.1} GiB")
+}
+
+/// The whole listing, one line per entry, as at the top of this file.
+pub fn report(dir: &Path) -> io::Result<String> {
+    let mut out = String::new();
+    for usage in summarize(dir)? {
+        out.push_str(&format!("{:>10}  {}\n", human_size(usage.bytes), usage.name));
+    }
+    Ok(out)
+}
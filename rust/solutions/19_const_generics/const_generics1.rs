@@ -0,0 +1,66 @@
+// Exercise: Const Generics 1 - Transpose and identity
+// Related example: examples/const_generics/02_matrix.rs
+//
+// `Matrix<R, C>` has R rows and C columns, and its sizes are part of its
+// type, so multiplying matrices of the wrong sizes doesn't compile. Two
+// things are missing: turning a matrix on its side, and the identity
+// matrix, which leaves any matrix unchanged when multiplied with it.
+//
+// TODO: Implement `transpose`: row r of the result is column r of `self`,
+//       so a `Matrix<R, C>` becomes a `Matrix<C, R>`.
+// TODO: Implement `identity` for square matrices: ones on the diagonal
+//       (row == col) and zeros everywhere else.
+//
+// Check your work with: cargo run -p tutor -- check const_generics1
+
+use std::ops::Mul;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    cells: [[i64; C]; R],
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    pub fn new(cells: [[i64; C]; R]) -> Self {
+        Matrix { cells }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> i64 {
+        self.cells[row][col]
+    }
+
+    pub fn transpose(&self) -> Matrix<C, R> {
+        let mut cells = [[0; R]; C];
+        for (row, values) in self.cells.iter().enumerate() {
+            for (col, &value) in values.iter().enumerate() {
+                cells[col][row] = value;
+            }
+        }
+        Matrix { cells }
+    }
+}
+
+// Only square matrices have an identity, so this impl is for Matrix<N, N>
+impl<const N: usize> Matrix<N, N> {
+    pub fn identity() -> Self {
+        let mut cells = [[0; N]; N];
+        for (i, row) in cells.iter_mut().enumerate() {
+            row[i] = 1;
+        }
+        Matrix { cells }
+    }
+}
+
+impl<const R: usize, const C: usize, const K: usize> Mul<Matrix<C, K>> for Matrix<R, C> {
+    type Output = Matrix<R, K>;
+
+    fn mul(self, other: Matrix<C, K>) -> Matrix<R, K> {
+        let mut cells = [[0; K]; R];
+        for (r, row) in cells.iter_mut().enumerate() {
+            for (k, cell) in row.iter_mut().enumerate() {
+                *cell = (0..C).map(|c| self.cells[r][c] * other.cells[c][k]).sum();
+            }
+        }
+        Matrix { cells }
+    }
+}
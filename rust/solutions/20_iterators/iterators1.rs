@@ -0,0 +1,45 @@
+// Exercise: Iterators 1 - Fibonacci
+// Related example: examples/collections/05_iterators.rs
+//
+// Anything with a `next` method is an iterator, and implementing that one
+// method gives a type all the others for free: map, filter, take, zip,
+// sum, and the rest. This iterator yields the Fibonacci numbers 0, 1, 1,
+// 2, 3, 5, 8... Each number is the sum of the two before it.
+//
+// TODO: Give `Fibonacci` the fields it needs, and implement `new`.
+// TODO: Implement `Iterator` for `Fibonacci`, yielding `u64`s. The
+//       numbers soon outgrow a u64; instead of overflowing, the iterator
+//       ends after the last one that fits, and then keeps returning None.
+//
+// Check your work with: cargo run -p tutor -- check iterators1
+
+pub struct Fibonacci {
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl Fibonacci {
+    pub fn new() -> Self {
+        Fibonacci {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Default for Fibonacci {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let value = self.current?;
+        self.current = self.next;
+        self.next = self.next.and_then(|next| next.checked_add(value));
+        Some(value)
+    }
+}
@@ -0,0 +1,85 @@
+// Exercise: Iterators 2 - Windows with a step
+// Related example: examples/collections/05_iterators.rs
+//
+// `slice.windows(n)` yields every run of n neighbours, and
+// `slice.chunks(n)` cuts the slice into pieces. `ChunkedWindows` does
+// both and everything in between: windows of `size` items whose starts
+// are `step` apart. Size 3 and step 1 are windows, size 3 and step 3 are
+// chunks, and size 2 and step 3 skip an item between windows. Only full
+// windows count: if there isn't room for `size` more items, it stops.
+//
+// A well-behaved iterator over a slice can also run backwards and knows
+// how many items it has left, which is what `rev()` and `len()` need.
+//
+// TODO: Add whatever fields you need besides the three given, and
+//       implement `new`. It panics if `size` or `step` is 0, the way
+//       `windows(0)` does.
+// TODO: Implement `Iterator`, yielding windows front to back.
+// TODO: Implement `DoubleEndedIterator`, yielding them back to front.
+//       The two ends can be used together, and they meet in the middle:
+//       no window is ever returned twice.
+// TODO: Implement `ExactSizeIterator`. Its `len` comes from `size_hint`,
+//       so that's the method to implement, and it must be exact.
+//
+// Check your work with: cargo run -p tutor -- check iterators2
+
+pub struct ChunkedWindows<'a, T> {
+    items: &'a [T],
+    size: usize,
+    step: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> ChunkedWindows<'a, T> {
+    pub fn new(items: &'a [T], size: usize, step: usize) -> Self {
+        assert!(size > 0, "size must not be 0");
+        assert!(step > 0, "step must not be 0");
+        let back = if items.len() < size {
+            0
+        } else {
+            (items.len() - size) / step + 1
+        };
+        ChunkedWindows {
+            items,
+            size,
+            step,
+            front: 0,
+            back,
+        }
+    }
+
+    fn window(&self, index: usize) -> &'a [T] {
+        let start = index * self.step;
+        &self.items[start..start + self.size]
+    }
+}
+
+impl<'a, T> Iterator for ChunkedWindows<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.window(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.back - self.front;
+        (left, Some(left))
+    }
+}
+
+impl<T> DoubleEndedIterator for ChunkedWindows<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.window(self.back))
+    }
+}
+
+impl<T> ExactSizeIterator for ChunkedWindows<'_, T> {}
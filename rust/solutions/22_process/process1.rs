@@ -0,0 +1,120 @@
+// Exercise: Process 1 - A tiny `xargs`
+// Related example: examples/process/01_running_commands.rs
+//
+// `xargs` turns its input into arguments: `ls *.log | xargs rm` runs
+// `rm` with every file name it reads. Ours reads whitespace-separated
+// items from stdin and runs a command with them:
+//
+//     process1 [-n MAX] COMMAND [ARG]...
+//
+// COMMAND runs with its ARGs first and then the items. With `-n MAX` it
+// runs once per batch of at most MAX items, one batch after another;
+// without it, once with all of them. With no items it doesn't run at all.
+// Each run gets the environment variable PROCESS1_BATCH, the number of
+// its batch (1, 2, ...), and writes straight to our stdout and stderr.
+//
+// Like xargs, process1 exits with:
+//   0    when every run succeeded
+//   123  when any run failed (the rest still run)
+//   127  when COMMAND can't be started; it prints
+//        `process1: COMMAND: <error>` to stderr and stops
+//   1    for a usage error (already handled by `parse_args`)
+//
+// TODO: Implement `batches`.
+// TODO: Implement `run_batch` with std::process::Command.
+// TODO: Implement `run_all`, which picks the exit code.
+//
+// The tests run echo and sh, so they need a Unix system (or WSL).
+//
+// Check your work with: cargo run -p tutor -- check process1
+
+use std::env;
+use std::io::{self, Read};
+use std::process::{Command, ExitCode, ExitStatus};
+
+const USAGE: &str = "usage: process1 [-n MAX] COMMAND [ARG]...";
+
+/// What the command line asked for.
+#[derive(Debug, PartialEq)]
+struct Options {
+    /// The most items per run, or `None` for all of them at once.
+    max: Option<usize>,
+    command: String,
+    args: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut first = args.next().ok_or(USAGE)?;
+    let mut max = None;
+    if first == "-n" {
+        let value = args.next().ok_or(USAGE)?;
+        match value.parse::<usize>() {
+            Ok(n) if n > 0 => max = Some(n),
+            _ => return Err(format!("-n needs a number above 0, not {value:?}")),
+        }
+        first = args.next().ok_or(USAGE)?;
+    }
+    Ok(Options {
+        max,
+        command: first,
+        args: args.collect(),
+    })
+}
+
+/// `items` split into runs of at most `max`, or all in one run. No items
+/// means no runs.
+fn batches(items: &[String], max: Option<usize>) -> Vec<&[String]> {
+    if items.is_empty() {
+        return Vec::new();
+    }
+    match max {
+        Some(max) => items.chunks(max).collect(),
+        None => vec![items],
+    }
+}
+
+/// Runs the command once with `batch`, the `number`th batch, and waits
+/// for it to finish.
+fn run_batch(options: &Options, batch: &[String], number: usize) -> io::Result<ExitStatus> {
+    Command::new(&options.command)
+        .args(&options.args)
+        .args(batch)
+        .env("PROCESS1_BATCH", number.to_string())
+        .status()
+}
+
+/// Runs every batch of `items` in turn, and returns process1's exit code.
+fn run_all(options: &Options, items: &[String]) -> ExitCode {
+    let mut failed = false;
+    for (index, batch) in batches(items, options.max).into_iter().enumerate() {
+        match run_batch(options, batch, index + 1) {
+            Ok(status) => failed |= !status.success(),
+            Err(error) => {
+                eprintln!("process1: {}: {error}", options.command);
+                return ExitCode::from(127);
+            }
+        }
+    }
+    if failed {
+        ExitCode::from(123)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(problem) => {
+            eprintln!("process1: {problem}");
+            return ExitCode::from(1);
+        }
+    };
+    let mut input = String::new();
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("process1: stdin: {error}");
+        return ExitCode::from(1);
+    }
+    let items: Vec<String> = input.split_whitespace().map(String::from).collect();
+    run_all(&options, &items)
+}
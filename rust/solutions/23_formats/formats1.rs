@@ -0,0 +1,142 @@
+// Exercise: Formats 1 - From CSV to JSON, and every bad row
+// Related example: examples/formats/03_csv.rs
+//
+// A supplier sends its price list as a CSV file, typed by hand into a
+// spreadsheet:
+//
+//     sku, name, price_cents, quantity
+//     MUG-01, Mug, 950, 40
+//     TEE-04,"T-shirt, large", 2000, 12
+//
+// and the shop's website wants it as a JSON array of products. Converting
+// the good rows is a few lines with the csv and serde_json crates. The
+// real work is the bad rows: whoever sends the file needs to fix all of
+// them, so the converter reports every one, not just the first, and says
+// exactly where each problem is.
+//
+// - The columns can come in any order, and there may be more of them.
+// - The values may have spaces around them, but a quoted value starts
+//   right after its comma: CSV reads ` "a, b"` as two fields.
+// - If the header lacks one of COLUMNS, there is one RowError per
+//   missing column, on line 1, and nothing else is reported.
+// - Otherwise every row that can't be read is a RowError with its line
+//   (the header is line 1). A value that doesn't parse also has the name
+//   of its column and the parse error's own message; a row with the wrong
+//   number of fields says how many it should have and how many it has.
+// - If there are any errors, they are the result, in the order of the
+//   rows; if there are none, the result is the JSON.
+//
+// TODO: Implement `row_error`, which turns one of the csv crate's errors
+//       into a RowError with help from the header.
+// TODO: Implement `csv_to_json`.
+//
+// Check your work with: cargo run -p tutor -- check formats1
+
+use std::fmt;
+
+use csv::{ErrorKind, StringRecord};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Product {
+    pub sku: String,
+    pub name: String,
+    pub price_cents: u64,
+    pub quantity: u32,
+}
+
+/// The columns a price list must have.
+pub const COLUMNS: [&str; 4] = ["sku", "name", "price_cents", "quantity"];
+
+/// What is wrong with one row of the file.
+#[derive(Debug, PartialEq)]
+pub struct RowError {
+    /// The line the row starts on; the header is line 1.
+    pub line: u64,
+    /// The column of the value that is wrong, if it is one value.
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(f, "line {}, column {column}: {}", self.line, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+/// The RowError for `error`, which reading a row of a file with these
+/// `headers` returned.
+///
+/// - `ErrorKind::Deserialize { pos, err }`: `err.field()` is the index of
+///   the value in the row, and `err.kind()` displays as the bare message,
+///   e.g. "invalid digit found in string".
+/// - `ErrorKind::UnequalLengths { pos, expected_len, len }`: the message
+///   is "expected 4 fields, found 3".
+/// - Anything else: its position's line if it has one (0 if not), and
+///   the error's own message.
+fn row_error(error: &csv::Error, headers: &StringRecord) -> RowError {
+    let line = error.position().map_or(0, |position| position.line());
+    match error.kind() {
+        ErrorKind::Deserialize { err, .. } => RowError {
+            line,
+            column: err
+                .field()
+                .and_then(|index| headers.get(index as usize))
+                .map(str::to_string),
+            message: err.kind().to_string(),
+        },
+        ErrorKind::UnequalLengths {
+            expected_len, len, ..
+        } => RowError {
+            line,
+            column: None,
+            message: format!("expected {expected_len} fields, found {len}"),
+        },
+        _ => RowError {
+            line,
+            column: None,
+            message: error.to_string(),
+        },
+    }
+}
+
+/// The products in `input`, a CSV file with a header, as pretty-printed
+/// JSON; or what is wrong with it.
+pub fn csv_to_json(input: &str) -> Result<String, Vec<RowError>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(error) => return Err(vec![row_error(&error, &StringRecord::new())]),
+    };
+
+    let missing: Vec<RowError> = COLUMNS
+        .iter()
+        .filter(|column| !headers.iter().any(|header| header == **column))
+        .map(|column| RowError {
+            line: 1,
+            column: Some(column.to_string()),
+            message: "missing column".to_string(),
+        })
+        .collect();
+    if !missing.is_empty() {
+        return Err(missing);
+    }
+
+    let mut products = Vec::new();
+    let mut errors = Vec::new();
+    for row in reader.deserialize::<Product>() {
+        match row {
+            Ok(product) => products.push(product),
+            Err(error) => errors.push(row_error(&error, &headers)),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(serde_json::to_string_pretty(&products).expect("a list of products is always valid JSON"))
+}
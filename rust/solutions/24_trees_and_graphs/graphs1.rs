@@ -0,0 +1,120 @@
+// Exercise: Graphs 1 - Finding a cycle
+// Related example: examples/trees_and_graphs/03_traversals.rs
+//
+// A build tool keeps its tasks in a directed graph: an edge from `a` to
+// `b` means that `a` needs `b` to be done first. If the edges go round in
+// a cycle, no task in it can ever start, and the tool should say which
+// tasks those are instead of waiting forever.
+//
+// The graph is an arena, as in examples/trees_and_graphs/02_arena_graph.rs.
+// Depth-first search finds cycles, but "have I seen this node before?" is
+// not enough: in a diamond (a -> b -> d, a -> c -> d) the search reaches
+// d twice without any cycle. What matters is whether a node is still on
+// the current path. Give each node one of three states:
+// - not visited yet
+// - on the path: its search has started and hasn't finished
+// - done: it, and everything it leads to, has been searched
+// An edge to a node that is on the path closes a cycle.
+//
+// TODO: Implement `find_cycle`: the nodes of one cycle, in the order the
+//       edges go, starting from any of them; None if there is no cycle.
+//       Search from every node, not just the first, since parts of the
+//       graph may not be reachable from it.
+// TODO: Implement `has_cycle` with `find_cycle`.
+//
+// Check your work with: cargo run -p tutor -- check graphs1
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub usize);
+
+#[derive(Debug, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    edges: Vec<Vec<NodeId>>,
+}
+
+/// Where a node is in the search; see the top of the file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    New,
+    OnPath,
+    Done,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    pub fn add_node(&mut self, name: &str) -> NodeId {
+        self.names.push(name.to_string());
+        self.edges.push(Vec::new());
+        NodeId(self.names.len() - 1)
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.edges[from.0].push(to);
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id.0]
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> &[NodeId] {
+        &self.edges[id.0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        let mut states = vec![State::New; self.len()];
+        let mut path = Vec::new();
+        for start in 0..self.len() {
+            if states[start] == State::New {
+                if let Some(cycle) = self.search(NodeId(start), &mut states, &mut path) {
+                    return Some(cycle);
+                }
+            }
+        }
+        None
+    }
+
+    /// Searches from `node`, with `path` the nodes that led here.
+    fn search(
+        &self,
+        node: NodeId,
+        states: &mut [State],
+        path: &mut Vec<NodeId>,
+    ) -> Option<Vec<NodeId>> {
+        states[node.0] = State::OnPath;
+        path.push(node);
+        for &next in self.neighbours(node) {
+            match states[next.0] {
+                State::OnPath => {
+                    // The cycle is the part of the path from `next` on
+                    let start = path.iter().position(|&id| id == next).unwrap();
+                    return Some(path[start..].to_vec());
+                }
+                State::New => {
+                    if let Some(cycle) = self.search(next, states, path) {
+                        return Some(cycle);
+                    }
+                }
+                State::Done => {}
+            }
+        }
+        path.pop();
+        states[node.0] = State::Done;
+        None
+    }
+}
@@ -0,0 +1,108 @@
+// Exercise: Trees 1 - Growing a search tree
+// Related example: examples/trees_and_graphs/01_binary_search_tree.rs
+//
+// `Bst<T>` is a binary search tree that owns its nodes: every child is an
+// `Option<Box<Node<T>>>`. Everything in a node's left subtree is smaller
+// than the node's value, everything in its right subtree larger, and no
+// value is in the tree twice. In Java you would write it with nulls and
+// references; here, reaching into the tree to change it means borrowing
+// the `Option` slot a node lives in.
+//
+// TODO: Implement `insert`: walk down from the root, left for smaller and
+//       right for larger, and put a new node in the empty slot you reach.
+//       Return false, and change nothing, if the value is already there.
+// TODO: Implement `contains`.
+// TODO: Implement `in_order`: references to every value, smallest first.
+//
+// Check your work with: cargo run -p tutor -- check trees1
+
+use std::cmp::Ordering;
+
+#[derive(Debug)]
+pub struct Node<T> {
+    pub value: T,
+    pub left: Option<Box<Node<T>>>,
+    pub right: Option<Box<Node<T>>>,
+}
+
+#[derive(Debug)]
+pub struct Bst<T> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+}
+
+impl<T: Ord> Bst<T> {
+    pub fn new() -> Self {
+        Bst { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of nodes on the longest path from the root down.
+    pub fn height(&self) -> usize {
+        fn height<T>(tree: &Option<Box<Node<T>>>) -> usize {
+            match tree {
+                Some(node) => 1 + height(&node.left).max(height(&node.right)),
+                None => 0,
+            }
+        }
+        height(&self.root)
+    }
+
+    /// Adds `value` to the tree, and returns whether it was new.
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut slot = &mut self.root;
+        while let Some(node) = slot {
+            slot = match value.cmp(&node.value) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return false,
+            };
+        }
+        *slot = Some(Box::new(Node {
+            value,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+        true
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// Every value in the tree, in order.
+    pub fn in_order(&self) -> Vec<&T> {
+        fn walk<'a, T>(tree: &'a Option<Box<Node<T>>>, values: &mut Vec<&'a T>) {
+            if let Some(node) = tree {
+                walk(&node.left, values);
+                values.push(&node.value);
+                walk(&node.right, values);
+            }
+        }
+        let mut values = Vec::with_capacity(self.len);
+        walk(&self.root, &mut values);
+        values
+    }
+}
+
+impl<T: Ord> Default for Bst<T> {
+    fn default() -> Self {
+        Bst::new()
+    }
+}
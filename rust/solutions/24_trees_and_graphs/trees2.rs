@@ -0,0 +1,141 @@
+// Exercise: Trees 2 - Taking values out again
+// Related example: examples/trees_and_graphs/01_binary_search_tree.rs
+//
+// The search tree from trees1, with insert and in_order done, loses
+// values now. Removing a node means giving its slot to something else
+// without ever letting a node have two owners: `Option::take()` moves a
+// node out of its slot and leaves None behind, and a slot can then be
+// given a new value with `*slot = ...`.
+//
+// There are three cases for the node that holds the value:
+// - it has no children: its slot becomes None
+// - it has one child: the child moves up into its slot
+// - it has two: the smallest value of its right subtree takes its place,
+//   because that value is larger than everything on the left and smaller
+//   than everything else on the right
+//
+// TODO: Implement `pop_min`, which removes the smallest value in a
+//       subtree and returns it. The smallest node has no left child, but
+//       it may have a right one.
+// TODO: Implement `remove`, with `pop_min` for the two-children case.
+//       Return the value that was removed, or None if it wasn't there.
+//
+// Check your work with: cargo run -p tutor -- check trees2
+
+use std::cmp::Ordering;
+
+type Tree<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+pub struct Node<T> {
+    pub value: T,
+    pub left: Tree<T>,
+    pub right: Tree<T>,
+}
+
+#[derive(Debug)]
+pub struct Bst<T> {
+    root: Tree<T>,
+    len: usize,
+}
+
+impl<T: Ord> Bst<T> {
+    pub fn new() -> Self {
+        Bst { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut slot = &mut self.root;
+        while let Some(node) = slot {
+            slot = match value.cmp(&node.value) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return false,
+            };
+        }
+        *slot = Some(Box::new(Node {
+            value,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+        true
+    }
+
+    pub fn in_order(&self) -> Vec<&T> {
+        fn walk<'a, T>(tree: &'a Tree<T>, values: &mut Vec<&'a T>) {
+            if let Some(node) = tree {
+                walk(&node.left, values);
+                values.push(&node.value);
+                walk(&node.right, values);
+            }
+        }
+        let mut values = Vec::with_capacity(self.len);
+        walk(&self.root, &mut values);
+        values
+    }
+
+    /// Removes the smallest value in the tree and returns it.
+    pub fn pop_first(&mut self) -> Option<T> {
+        let value = pop_min(&mut self.root)?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Removes `value` from the tree, and returns it if it was there.
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let mut slot = &mut self.root;
+        // Check with a shared borrow first, so that the mutable one can
+        // move down into `slot`
+        while slot.as_ref().is_some_and(|node| node.value != *value) {
+            let node = slot.as_mut().unwrap();
+            slot = if *value < node.value {
+                &mut node.left
+            } else {
+                &mut node.right
+            };
+        }
+        let mut node = slot.take()?;
+        *slot = match (node.left.take(), node.right.take()) {
+            (None, None) => None,
+            (Some(child), None) | (None, Some(child)) => Some(child),
+            (Some(left), Some(right)) => {
+                let mut right = Some(right);
+                let successor = pop_min(&mut right).expect("the right subtree isn't empty");
+                Some(Box::new(Node {
+                    value: successor,
+                    left: Some(left),
+                    right,
+                }))
+            }
+        };
+        self.len -= 1;
+        Some(node.value)
+    }
+}
+
+impl<T: Ord> Default for Bst<T> {
+    fn default() -> Self {
+        Bst::new()
+    }
+}
+
+/// Removes the smallest value from the subtree in `slot`, and returns it;
+/// None if the subtree is empty.
+fn pop_min<T>(slot: &mut Tree<T>) -> Option<T> {
+    let mut slot = slot;
+    while slot.as_ref()?.left.is_some() {
+        slot = &mut slot.as_mut().unwrap().left;
+    }
+    let mut node = slot.take()?;
+    *slot = node.right.take();
+    Some(node.value)
+}
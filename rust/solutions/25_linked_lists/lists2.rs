@@ -0,0 +1,93 @@
+// Exercise: Linked Lists 2 - Peeking, and letting go
+// Related example: examples/drop/01_drop_order.rs
+//
+// The stack from lists1, with push and pop done, gets two new ways in and
+// one way out.
+//
+// `peek` looks at the top value without taking it, and `peek_mut` lets
+// the caller change it in place. They return an Option of a reference
+// into the head node, and `Option::as_ref()`, `as_deref()` and `map()`
+// turn the `&Link<T>` in `self.head` into one without moving anything.
+//
+// Dropping is the way out. Without a Drop impl of its own, a Stack is
+// dropped the way the compiler does it for every struct: the head Box
+// drops its Node, which drops its `next` Box, which drops the next
+// Node... one nested call per node. On a long enough list that recursion
+// runs out of stack and the program crashes, so a list should drop its
+// nodes in a loop instead, one at a time.
+//
+// TODO: Implement `peek` and `peek_mut`.
+// TODO: Implement Drop for Stack so that it frees its nodes with a loop
+//       that never holds more than one node at a time, however long the
+//       list is.
+//
+// Check your work with: cargo run -p tutor -- check lists2
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+#[derive(Debug)]
+pub struct Stack<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn push(&mut self, value: T) {
+        let node = Box::new(Node {
+            value,
+            next: self.head.take(),
+        });
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.next;
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.value)
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            // Unhook the rest of the list first, so that dropping `node`
+            // at the end of this pass drops just the one node
+            link = node.next.take();
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// Exercise: Linked Lists 3 - Iterating without taking
+// Related example: examples/collections/05_iterators.rs
+//
+// The stack from lists2 can push, pop, peek and drop itself. What it
+// can't do yet is work with a `for` loop, and there are three ways a loop
+// might want it, just like a Vec:
+// - `into_iter()` takes the stack and yields its values
+// - `iter()` borrows it and yields `&T`
+// - `iter_mut()` borrows it mutably and yields `&mut T`
+//
+// `IntoIter` is the easy one: it owns the stack and pops it. The other
+// two can't pop anything, so they keep a reference to the node they'll
+// yield next, and each call to `next` moves that reference one node on.
+// `Option::as_deref()` turns the `&Option<Box<Node<T>>>` in a node into
+// the `Option<&Node<T>>` such an iterator keeps.
+//
+// `IterMut` is where the borrow checker gets involved: a `&mut` can't be
+// copied, so `next` has to move the one it holds out of `self.next`
+// before it can split it into the value and the rest of the list.
+//
+// TODO: Implement `next` for `IntoIter`, `Iter` and `IterMut`, from the
+//       top of the stack down.
+// TODO: Implement `iter` and `iter_mut`.
+// TODO: Implement `into_iter` for `Stack<T>`, `&Stack<T>` and
+//       `&mut Stack<T>`, which is what `for value in stack`,
+//       `for value in &stack` and `for value in &mut stack` call.
+//
+// Check your work with: cargo run -p tutor -- check lists3
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+#[derive(Debug)]
+pub struct Stack<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn push(&mut self, value: T) {
+        let node = Box::new(Node {
+            value,
+            next: self.head.take(),
+        });
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.next;
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.value)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+        }
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+/// The values of a stack, popped one by one.
+pub struct IntoIter<T>(Stack<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+}
+
+/// References to the values of a stack, from the top down.
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.value)
+    }
+}
+
+/// Mutable references to the values of a stack, from the top down.
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+        Some(&mut node.value)
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Stack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
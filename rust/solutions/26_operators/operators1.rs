@@ -0,0 +1,189 @@
+// Exercise: Operators 1 - Money that adds up
+// Related example: examples/operators/01_money.rs
+//
+// An invoicing tool keeps amounts as whole cents with a currency, and
+// wants to write `subtotal + tax`, `total -= discount`, `a < b` and
+// `"12.50 EUR".parse()` like it would with numbers. Each of those is a
+// std trait, and `Money` implements none of them yet.
+//
+// The rules:
+// - adding or subtracting amounts in different currencies is a bug in the
+//   caller, so `+` and `-` panic with a message that names both currencies
+// - comparing them is not a bug, but there's no answer: `partial_cmp`
+//   returns None, so `<`, `>`, `<=` and `>=` are all false
+// - Display writes the amount with two decimals and the currency code
+//   after it: "12.50 EUR", "-0.05 USD". It honours a width, so that
+//   `{:>12}` lines amounts up in a column.
+// - FromStr reads what Display writes, and the whole number on its own:
+//   "12.50 EUR", "7 USD", "-3.10 GBP". Anything else is one of the
+//   errors in ParseMoneyError.
+//
+// TODO: Implement Add, Sub, Neg and SubAssign.
+// TODO: Implement PartialOrd.
+// TODO: Implement Display for Money.
+// TODO: Implement FromStr for Money.
+//
+// Check your work with: cargo run -p tutor -- check operators1
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 3] = [Currency::Usd, Currency::Eur, Currency::Gbp];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Money {
+    pub cents: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(cents: i64, currency: Currency) -> Money {
+        Money { cents, currency }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseMoneyError {
+    /// Nothing but whitespace.
+    Empty,
+    /// An amount with no currency after it, like "12.50".
+    MissingCurrency,
+    /// A currency code that isn't in `Currency::ALL`, like "12.50 JPY".
+    UnknownCurrency(String),
+    /// An amount that isn't digits, with no decimals or exactly two, and
+    /// an optional minus in front: "12.5", "1,000", "ten", "$5".
+    InvalidAmount(String),
+}
+
+impl fmt::Display for ParseMoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMoneyError::Empty => write!(f, "no amount given"),
+            ParseMoneyError::MissingCurrency => write!(f, "no currency given"),
+            ParseMoneyError::UnknownCurrency(code) => write!(f, "unknown currency {code:?}"),
+            ParseMoneyError::InvalidAmount(text) => write!(f, "{text:?} is not an amount"),
+        }
+    }
+}
+
+impl std::error::Error for ParseMoneyError {}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        assert_eq!(
+            self.currency,
+            other.currency,
+            "cannot add {} to {}",
+            other.currency.code(),
+            self.currency.code()
+        );
+        Money::new(self.cents + other.cents, self.currency)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        assert_eq!(
+            self.currency,
+            other.currency,
+            "cannot subtract {} from {}",
+            other.currency.code(),
+            self.currency.code()
+        );
+        Money::new(self.cents - other.cents, self.currency)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money::new(-self.cents, self.currency)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.cents.cmp(&other.cents))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        let cents = self.cents.unsigned_abs();
+        let text = format!(
+            "{sign}{}.{:02} {}",
+            cents / 100,
+            cents % 100,
+            self.currency.code()
+        );
+        f.pad(&text)
+    }
+}
+
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(text: &str) -> Result<Money, ParseMoneyError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ParseMoneyError::Empty);
+        }
+        let (amount, code) = text
+            .split_once(char::is_whitespace)
+            .ok_or(ParseMoneyError::MissingCurrency)?;
+        let code = code.trim();
+        let currency = Currency::ALL
+            .into_iter()
+            .find(|currency| currency.code() == code)
+            .ok_or_else(|| ParseMoneyError::UnknownCurrency(code.to_string()))?;
+
+        let invalid = || ParseMoneyError::InvalidAmount(amount.to_string());
+        let (negative, digits) = match amount.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, amount),
+        };
+        let (units, cents) = digits.split_once('.').unwrap_or((digits, "00"));
+        let all_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+        if !all_digits(units) || !all_digits(cents) || cents.len() != 2 {
+            return Err(invalid());
+        }
+        let units: i64 = units.parse().map_err(|_| invalid())?;
+        let cents: i64 = cents.parse().map_err(|_| invalid())?;
+        let total = units
+            .checked_mul(100)
+            .and_then(|total| total.checked_add(cents))
+            .ok_or_else(invalid)?;
+        Ok(Money::new(if negative { -total } else { total }, currency))
+    }
+}
@@ -0,0 +1,200 @@
+// Exercise: Advanced Traits 1 - One trait for borrowed and computed items
+// Related example: examples/advanced_traits/02_lending_iterator.rs
+//
+// A report generator reads columns from three kinds of storage: a Vec it
+// holds, a Series of numbers it computes when asked, and the words of a
+// block of text. It wants one Collection trait that counts, indexes, and
+// walks all three, so that `largest` and `position` are written once.
+//
+// The trouble is the items. A Vec hands out `&T`, borrowed from itself; a
+// Series makes each value on the spot and hands it over owned; Words
+// lends out `&str` slices of its text. A plain associated type can't say
+// "borrowed from the collection", because it has no lifetime to borrow
+// for. A generic associated type can:
+//
+//     type Item<'a> where Self: 'a;
+//
+// For Vec<T> that is `&'a T`, for Words `&'a str`, and for Series just
+// `i64`, which ignores 'a. `Iter<'a>` is the iterator that iter() returns,
+// and its items are `Item<'a>`; std already has one for two of the three.
+//
+// The rules:
+// - get() returns None past the end, like slice::get
+// - a Series is `len` numbers: start, start + step, start + 2 * step, ...
+// - Words are split on whitespace, like str::split_whitespace
+// - largest() returns the largest item, or None when the collection is
+//   empty; among equal items, the last one, as Iterator::max does
+// - position() returns the index of the first item equal to `wanted`
+//
+// TODO: Implement Collection for Vec<T>. The types given only let the file
+//       compile; pick the real ones.
+// TODO: Implement Collection for Series, and Iterator for SeriesIter.
+// TODO: Implement Collection for Words.
+// TODO: Write `largest` and `position`, once, for any Collection.
+//
+// Check your work with: cargo run -p tutor -- check gats1
+
+/// Something with a length, whose items can be looked up by index or
+/// walked in order.
+pub trait Collection {
+    /// What get() and iter() hand out: a borrow of the collection for
+    /// `'a`, or a value made on the spot.
+    type Item<'a>
+    where
+        Self: 'a;
+
+    /// What iter() returns.
+    type Iter<'a>: Iterator<Item = Self::Item<'a>>
+    where
+        Self: 'a;
+
+    fn len(&self) -> usize;
+
+    fn get(&self, index: usize) -> Option<Self::Item<'_>>;
+
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn first(&self) -> Option<Self::Item<'_>> {
+        self.get(0)
+    }
+
+    fn last(&self) -> Option<Self::Item<'_>> {
+        self.get(self.len().checked_sub(1)?)
+    }
+}
+
+impl<T> Collection for Vec<T> {
+    type Item<'a>
+        = &'a T
+    where
+        Self: 'a;
+
+    type Iter<'a>
+        = std::slice::Iter<'a, T>
+    where
+        Self: 'a;
+
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+}
+
+/// `len` numbers, `step` apart, from `start`. None of them is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Series {
+    pub start: i64,
+    pub step: i64,
+    pub len: usize,
+}
+
+/// Walks a Series. It copies the Series, so it borrows nothing.
+#[derive(Debug, Clone)]
+pub struct SeriesIter {
+    series: Series,
+    next: usize,
+}
+
+impl Iterator for SeriesIter {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let value = self.series.get(self.next)?;
+        self.next += 1;
+        Some(value)
+    }
+}
+
+impl Collection for Series {
+    type Item<'a>
+        = i64
+    where
+        Self: 'a;
+
+    type Iter<'a>
+        = SeriesIter
+    where
+        Self: 'a;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Option<i64> {
+        (index < self.len).then(|| self.start + self.step * index as i64)
+    }
+
+    fn iter(&self) -> SeriesIter {
+        SeriesIter {
+            series: *self,
+            next: 0,
+        }
+    }
+}
+
+/// The words of a text, split on whitespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Words {
+    text: String,
+}
+
+impl Words {
+    pub fn new(text: &str) -> Self {
+        Words {
+            text: text.to_string(),
+        }
+    }
+}
+
+impl Collection for Words {
+    type Item<'a>
+        = &'a str
+    where
+        Self: 'a;
+
+    type Iter<'a>
+        = std::str::SplitWhitespace<'a>
+    where
+        Self: 'a;
+
+    fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    fn get(&self, index: usize) -> Option<&str> {
+        self.iter().nth(index)
+    }
+
+    fn iter(&self) -> std::str::SplitWhitespace<'_> {
+        self.text.split_whitespace()
+    }
+}
+
+/// The largest item; among equal ones, the last.
+pub fn largest<'c, C>(collection: &'c C) -> Option<C::Item<'c>>
+where
+    C: Collection,
+    C::Item<'c>: Ord,
+{
+    collection.iter().max()
+}
+
+/// The index of the first item equal to `wanted`.
+pub fn position<'c, C>(collection: &'c C, wanted: C::Item<'c>) -> Option<usize>
+where
+    C: Collection,
+    C::Item<'c>: PartialEq,
+{
+    collection.iter().position(|item| item == wanted)
+}
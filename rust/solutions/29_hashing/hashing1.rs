@@ -0,0 +1,202 @@
+// Exercise: Hashing 1 - An open-addressing map
+// Related example: examples/hashing/03_collisions.rs
+//
+// std's HashMap keeps its entries in one flat array of slots, and when a
+// key's slot is taken it tries the next one along. That is open
+// addressing. Build a small map that works the same way:
+//
+//     slots: [Empty, Full("b", 2), Full("a", 1), Deleted, Empty, ...]
+//                     ^ home of "b"  ^ home of "a" was taken, so "a" went here
+//
+// - A key's home slot is its hash modulo the number of slots (home() does
+//   that). From there, look at each slot in turn, wrapping around from
+//   the last slot to the first: linear probing.
+// - Looking for a key stops at the slot that holds it, or at an Empty
+//   slot, which means it isn't in the map.
+// - Removing a key can't just empty its slot: a key that collided with it
+//   and went further along would no longer be found. It leaves a Deleted
+//   slot, a tombstone, which lookups step over. Inserting may reuse one.
+// - An insert must first check whether the key is already there, and
+//   replace its value (returning the old one, as HashMap::insert does).
+// - Before an insert that would leave more than 3/4 of the slots used,
+//   full or deleted, rebuild the table without the tombstones. Give it
+//   twice as many slots if at least half the current ones are full, or
+//   as many as now if they aren't: then it was mostly tombstones, and
+//   clearing them out is enough. Because of that rule, there is always
+//   an Empty slot, and every search ends.
+//
+// get, contains_key, and remove take the key borrowed, like HashMap's
+// do, so an OpenMap<String, V> can be searched with a &str.
+//
+// TODO: Write find(), which the others build on.
+// TODO: Write get(), insert(), and remove().
+// TODO: Write rebuild().
+//
+// Check your work with: cargo run -p tutor -- check hashing1
+
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash, RandomState};
+
+/// How many slots a new map starts with.
+const INITIAL_SLOTS: usize = 8;
+
+enum Slot<K, V> {
+    Empty,
+    /// A tombstone, where a removed entry was.
+    Deleted,
+    Full(K, V),
+}
+
+pub struct OpenMap<K, V, S = RandomState> {
+    slots: Vec<Slot<K, V>>,
+    /// How many slots are Full.
+    len: usize,
+    /// How many slots are Full or Deleted.
+    used: usize,
+    hasher: S,
+}
+
+impl<K: Hash + Eq, V> OpenMap<K, V> {
+    pub fn new() -> Self {
+        OpenMap::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V> Default for OpenMap<K, V> {
+    fn default() -> Self {
+        OpenMap::new()
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> OpenMap<K, V, S> {
+    /// A map that hashes its keys with `hasher`.
+    pub fn with_hasher(hasher: S) -> Self {
+        OpenMap {
+            slots: empty_slots(INITIAL_SLOTS),
+            len: 0,
+            used: 0,
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How many slots the table has.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// The slot where the search for `key` starts.
+    fn home<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+        (self.hasher.hash_one(key) % self.slots.len() as u64) as usize
+    }
+
+    /// The index of the slot holding `key`, if it is in the map.
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut index = self.home(key);
+        loop {
+            match &self.slots[index] {
+                Slot::Empty => return None,
+                Slot::Full(found, _) if found.borrow() == key => return Some(index),
+                Slot::Full(..) | Slot::Deleted => index = (index + 1) % self.slots.len(),
+            }
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match &self.slots[self.find(key)?] {
+            Slot::Full(_, value) => Some(value),
+            Slot::Empty | Slot::Deleted => unreachable!("find only returns full slots"),
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaced, if
+    /// the key was already there.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(index) = self.find(&key) {
+            let Slot::Full(_, old) = &mut self.slots[index] else {
+                unreachable!("find only returns full slots");
+            };
+            return Some(std::mem::replace(old, value));
+        }
+
+        if (self.used + 1) * 4 > self.slots.len() * 3 {
+            let slots = if self.len * 2 >= self.slots.len() {
+                self.slots.len() * 2
+            } else {
+                self.slots.len()
+            };
+            self.rebuild(slots);
+        }
+
+        let mut index = self.home(&key);
+        while let Slot::Full(..) = self.slots[index] {
+            index = (index + 1) % self.slots.len();
+        }
+        if let Slot::Empty = self.slots[index] {
+            self.used += 1;
+        }
+        self.slots[index] = Slot::Full(key, value);
+        self.len += 1;
+        None
+    }
+
+    /// Removes `key`, returning its value, if it was there.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        let Slot::Full(_, value) = std::mem::replace(&mut self.slots[index], Slot::Deleted) else {
+            unreachable!("find only returns full slots");
+        };
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Moves every entry into a new table of `slots` slots, leaving the
+    /// tombstones behind.
+    fn rebuild(&mut self, slots: usize) {
+        let old = std::mem::replace(&mut self.slots, empty_slots(slots));
+        self.len = 0;
+        self.used = 0;
+        for slot in old {
+            if let Slot::Full(key, value) = slot {
+                let mut index = self.home(&key);
+                while let Slot::Full(..) = self.slots[index] {
+                    index = (index + 1) % self.slots.len();
+                }
+                self.slots[index] = Slot::Full(key, value);
+                self.len += 1;
+                self.used += 1;
+            }
+        }
+    }
+}
+
+fn empty_slots<K, V>(count: usize) -> Vec<Slot<K, V>> {
+    (0..count).map(|_| Slot::Empty).collect()
+}
@@ -0,0 +1,267 @@
+# What the tutor says, in English. Every other file in this directory has
+# the same messages; a message missing from one is shown in English.
+#
+# The syntax is Fluent's: https://projectfluent.org/fluent/guide/
+# Emoji and backticks are part of the message.
+
+## tutor run and tutor next
+
+difficulty = { $level ->
+    [beginner] beginner
+    [intermediate] intermediate
+   *[advanced] advanced
+}
+run-compile-error = ❌ { $name } does not compile yet. Read the errors above and try again.
+run-success = ✅ { $name } ran successfully. Run `tutor next` to continue.
+run-failed = ❌ { $name } compiled but exited with an error.
+run-timed-out = ❌ { $name } was still running after { $seconds } seconds, so it was stopped.
+run-wrong-output = ❌ { $name } ran, but its `// EXPECTED:` block says it should print:
+run-broken = ▶ { $name } with feature = "broken"
+run-broken-errors = ☝️  These are the errors the comments in { $name } talk about.
+run-no-broken = { $name } has no broken variant: it compiles either way.
+course-finished = 🎉 You've reached the end of the course. Nice work!
+prerequisites-missing = 🔒 { $name } builds on lessons you haven't finished yet:
+prerequisites-advice = Run those first, or pass --ignore-prerequisites to start anyway.
+
+## tutor check
+
+check-title = 🔍 Checking { $name } [{ $topic }] ({ $path })
+check-compile-error = ❌ { $name } does not compile yet. Look for the `// TODO` comments.
+check-done = 🎉 { $name } is done!
+check-failing = { $failing ->
+    [one] 1 of { $total } assertions still needs fixing.
+   *[other] { $failing } of { $total } assertions still need fixing.
+}
+check-stuck = 💡 Stuck? Run `tutor hint { $name }`.
+check-all-compile-error = does not compile
+check-all-pass = { $total ->
+    [one] its test passes
+   *[other] all { $total } tests pass
+}
+check-all-failing = { $failing } of { $total } tests failing
+check-all-summary = { $done }/{ $total } exercises complete.
+check-fix-missing = `{ $find }` is missing from it. Only change the tests!
+check-code-under-test = the code under test
+check-fixed-code = your tests, on the fixed code
+check-fixed-code-failing = the tests above fail even with the bug fixed, so it's the tests that are wrong
+check-bug-as-given = the bug in the code as given
+check-caught-by = caught by { $test }
+check-missed-bug = your tests pass on the code as given, so they miss its bug
+check-your-tests = your tests
+check-no-tests = there are no #[test] functions yet, so no bug can be caught
+check-mutant = bug { $number }: { $description }
+check-mutant-missing = the code this bug changes is missing from the code under test. Only change the tests!
+check-mutant-survived = your tests still pass with this bug planted
+
+## tutor hint
+
+hint-none = There are no hints for { $name } yet. Re-read the comments at the top of the file!
+hint-title = 💡 Hints for { $name }
+hint-nudge = Nudge
+hint-explanation = Explanation
+hint-solution = Solution
+hint-all-shown = That's every hint there is. You've got this!
+hint-more = Still stuck? Run `tutor hint { $name }` again for more help.
+
+## tutor quiz
+
+quiz-list = 🧠 Quizzes
+quiz-best = best { $percent }%
+quiz-not-taken = not taken yet
+quiz-list-advice = Run `tutor quiz <chapter>` to take one.
+quiz-title = 🧠 Quiz: { $chapter } ({ $total ->
+    [one] 1 question
+   *[other] { $total } questions
+})
+quiz-question = Question { $number }/{ $total }
+quiz-pick-letter = Type one of the letters above.
+quiz-type-output = Type the output, then an empty line:
+quiz-correct = ✅ Correct!
+quiz-wrong = ❌ Not quite. The answer is:
+quiz-score = Score: { $correct }/{ $total }
+
+## tutor status
+
+status-title = 📊 Your progress
+status-overall = overall
+status-no-streak = 🔥 No streak yet: practice today to start one.
+status-streak = 🔥 { $current }-day streak (best: { $best ->
+    [one] 1 day
+   *[other] { $best } days
+})
+status-streak-at-risk = 🔥 { $current }-day streak (best: { $best ->
+    [one] 1 day
+   *[other] { $best } days
+}). Practice today to keep it going!
+status-achievements = 🏆 Achievements: { $unlocked } of { $total }
+achievement-unlocked = 🏆 Achievement unlocked: { $title } ({ $description })
+achievement-first_example = Hello, world
+achievement-first_example-description = Run your first example
+achievement-first_fix = Compiler whisperer
+achievement-first_fix-description = Fix your first compile error
+achievement-first_exercise = First steps
+achievement-first_exercise-description = Finish an exercise
+achievement-busy_day = Busy day
+achievement-busy_day-description = Finish 10 exercises in one day
+achievement-ownership_without_hints = Borrow checker's friend
+achievement-ownership_without_hints-description = Complete the ownership chapter without a single hint
+achievement-quiz_ace = Top marks
+achievement-quiz_ace-description = Answer every question of a quiz right
+achievement-week_streak = On a roll
+achievement-week_streak-description = Practice 7 days in a row
+achievement-every_exercise = Finisher
+achievement-every_exercise-description = Finish every exercise
+
+## tutor review
+
+review-when = { $days ->
+    [0] today
+    [1] tomorrow
+   *[other] in { $days } days
+}
+review-ago = { $days ->
+    [0] today
+    [1] yesterday
+   *[other] { $days } days ago
+}
+review-nothing-today = ✅ Nothing to review today. The next review is { $when }.
+review-nothing-yet = Nothing to review yet: finish an exercise or take a quiz, and it comes up here the next day.
+review-title = 🔁 Review: { $count } of { $due } due today
+review-next = 🗓  Next review { $when }.
+review-done = Reviewed { $count ->
+    [one] 1 item
+   *[other] { $count } items
+}.
+review-more-due = { $left } more are due today; run `tutor review` again for them.
+review-exercise = Exercise { $name }:
+review-think = How did you solve it? Think it through, then press Enter to see the solution.
+review-remembered = How well did you remember it? 0 (not at all) to 5 (perfectly):
+review-pick-number = Type a number from 0 to 5.
+review-schedule-title = 🔁 Review schedule
+review-column-chapter = chapter
+review-column-items = items
+review-column-due = due
+review-column-last = last practiced
+review-never = never
+review-nothing-due = Nothing is due today; the next review is { $when }.
+review-due = { $due ->
+    [one] 1 item is due today.
+   *[other] { $due } items are due today.
+} Run `tutor review` to go through them.
+
+## tutor errors and tutor explain
+
+errors-list-title = 📚 Compiler errors with explanations:
+errors-list-advice = Run `tutor errors <code>` to read about one.
+errors-example = This program causes it:
+errors-lesson = 📖 Lesson { $name } ({ $title }) covers this. Run `tutor run { $name }` to revisit it.
+errors-see-lesson = 📖 See the `{ $lesson }` lesson.
+errors-rustc-explain = For the full story, run `rustc --explain { $code }`.
+errors-suggest = 📚 Not sure what these errors mean?
+errors-suggestion = { $code }: { $title } (`tutor errors { $code }`, taught in { $lesson })
+explain-title = 📖 { $name }, explained
+explain-no-notes = 📖 { $name } has no explanations yet, so here is just its source.
+
+## tutor lint
+
+lint-running = 📎 Running clippy on { $name }
+lint-compile-error = ❌ { $name } does not compile. Fix the errors first.
+lint-wrong-output = ❌ Clippy is happy, but { $name } no longer prints what its `// EXPECTED:` block says:
+lint-broken = ❌ Clippy is happy, but { $name } doesn't run cleanly any more. Try `tutor run { $name }`.
+lint-clean = ✅ Clippy has nothing to say about { $name }, and it still works.
+lint-places = { $count ->
+    [one] 1 place
+   *[other] { $count } places
+}
+lint-before = Before:
+lint-after = After:
+lint-left = { $warnings ->
+    [one] 1 warning
+   *[other] { $warnings } warnings
+} from { $lints ->
+    [one] 1 lint
+   *[other] { $lints } lints
+} left. Edit { $path } and run `tutor lint { $name }` again.
+
+## tutor compare
+
+compare-list-title = 🔀 Lessons available in all three languages:
+compare-list-advice = Run `tutor compare <topic>` to see one side by side.
+compare-about = About
+compare-code = Code
+compare-notes = Notes
+
+## tutor puzzle
+
+puzzle-list-title = 🧩 Borrow-checker puzzles
+puzzle-list-advice = Edit a file in puzzles/, then run `tutor puzzle <name>` to check it.
+puzzle-no-hint = There's no hint for { $name } yet.
+puzzle-rule-forbid = no { $items }
+puzzle-rule-keep = keep `{ $signature }` as it is
+puzzle-not-allowed = { $what } is not allowed
+puzzle-keep-signature = `{ $name }` must keep its original signature
+puzzle-keep-fn = `fn { $name }` must stay in the program
+puzzle-violation = 🚫 line { $line }: { $message }
+puzzle-rules-broken = That breaks the puzzle's rules. Find another way!
+puzzle-solved = ✅ Solved! It compiles, keeps the rules, and prints the right thing.
+puzzle-compile-error = ❌ { $name } doesn't compile yet.
+puzzle-stuck = 💡 Stuck? Run `tutor puzzle { $name } --hint`.
+puzzle-wrong-output = ❌ It compiles, but it should print:
+puzzle-failed = ❌ It compiles, but exits with an error.
+puzzle-timed-out = ❌ It compiles, but was still running after { $seconds } seconds.
+
+## tutor project
+
+project-list-title = 🛠️  Projects
+project-stages = { $done }/{ $total } stages
+project-list-advice = Run `tutor project <name>` to work on one.
+project-stage = 🛠️  { $name }, stage { $number } of { $total }: { $title }
+project-stage-passed = ✅ Stage { $number }: { $title }
+project-compile-error = ❌ { $name } does not compile yet.
+project-stage-done = 🎉 Stage { $number } is done!
+project-next-stage = Next up, stage { $number }: { $title } (`tutor project { $name } --stage { $number }`)
+project-failing = { $failing ->
+    [one] 1 of { $total } tests still fails.
+   *[other] { $failing } of { $total } tests still fail.
+}
+project-finished = 🎉 You've built all of { $name }!
+
+## tutor solution and tutor watch
+
+solution-same = ✅ Your { $name } is the same as the reference solution.
+solution-title = 📖 { $name }: your attempt next to the solution
+solution-your-attempt = { $path } (your attempt)
+solution-applied = 📝 Replaced { $path } with the solution. Your attempt is saved at { $backup }.
+solution-apply-advice = Run `cargo run -p tutor -- solution { $name } --apply` to replace your attempt with it.
+watch-title = 👀 Watching { $dir } (Ctrl-C to quit)
+watch-next = ✅ { $name } is done! On to the next one.
+watch-all-done = 🎉 Every exercise passes. You've finished them all!
+
+## tutor play and tutor predict
+
+play-opening = 📝 Opening { $file } in { $editor }…
+play-again = Press Enter to edit it again, or type q to quit:
+play-saved = The playground is saved; `tutor play --resume` picks up where you left off.
+play-compile-error = ❌ The playground does not compile. Read the errors above and try again.
+play-running = ▶ Running it
+play-success = ✅ It ran successfully.
+play-failed = ❌ It compiled but exited with an error.
+play-timed-out = ❌ It was still running after { $seconds } seconds, so it was stopped.
+predict-title = 🔮 What does this print? (from { $name })
+predict-prompt = Type your prediction, then an empty line:
+predict-right = ✅ Spot on! It prints:
+predict-wrong = ❌ Not quite. Your prediction (-) against what it printed (+):
+
+## tutor ui
+
+ui-welcome = Welcome! Pick a lesson and press Enter.
+ui-keys = ↑↓/jk move · Enter run or check · h hint · Tab next pane · PgUp/PgDn scroll · q quit
+ui-lessons = Lessons
+ui-output = Output
+ui-hints = Hints
+ui-press-enter = Press Enter to run this lesson.
+ui-example-hints = Examples have no hints; their comments explain them.
+ui-builds-on = Builds on:
+ui-press-h = Press h for hint { $number } of { $total }.
+ui-hint-revealed = 💡 Hint { $number } of { $total } for { $name }.
+ui-stuck = Stuck? Press h.
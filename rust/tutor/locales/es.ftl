@@ -0,0 +1,267 @@
+# Lo que dice el tutor, en español. Tiene los mismos mensajes que en.ftl;
+# los que falten aquí se muestran en inglés.
+#
+# La sintaxis es la de Fluent: https://projectfluent.org/fluent/guide/
+# Los emoji y las comillas invertidas son parte del mensaje.
+
+## tutor run y tutor next
+
+difficulty = { $level ->
+    [beginner] principiante
+    [intermediate] intermedio
+   *[advanced] avanzado
+}
+run-compile-error = ❌ { $name } todavía no compila. Lee los errores de arriba y vuelve a intentarlo.
+run-success = ✅ { $name } se ejecutó correctamente. Ejecuta `tutor next` para continuar.
+run-failed = ❌ { $name } compiló, pero terminó con un error.
+run-timed-out = ❌ { $name } seguía ejecutándose después de { $seconds } segundos, así que se detuvo.
+run-wrong-output = ❌ { $name } se ejecutó, pero su bloque `// EXPECTED:` dice que debería imprimir:
+run-broken = ▶ { $name } con feature = "broken"
+run-broken-errors = ☝️  Estos son los errores de los que hablan los comentarios de { $name }.
+run-no-broken = { $name } no tiene variante rota: compila de las dos maneras.
+course-finished = 🎉 Has llegado al final del curso. ¡Buen trabajo!
+prerequisites-missing = 🔒 { $name } se basa en lecciones que aún no has terminado:
+prerequisites-advice = Hazlas primero, o pasa --ignore-prerequisites para empezar de todos modos.
+
+## tutor check
+
+check-title = 🔍 Comprobando { $name } [{ $topic }] ({ $path })
+check-compile-error = ❌ { $name } todavía no compila. Busca los comentarios `// TODO`.
+check-done = 🎉 ¡{ $name } está terminado!
+check-failing = { $failing ->
+    [one] A 1 de { $total } aserciones todavía le falta un arreglo.
+   *[other] A { $failing } de { $total } aserciones todavía les falta un arreglo.
+}
+check-stuck = 💡 ¿Atascado? Ejecuta `tutor hint { $name }`.
+check-all-compile-error = no compila
+check-all-pass = { $total ->
+    [one] su prueba pasa
+   *[other] pasan las { $total } pruebas
+}
+check-all-failing = { $failing } de { $total } pruebas fallan
+check-all-summary = { $done }/{ $total } ejercicios completados.
+check-fix-missing = Falta `{ $find }` en él. ¡Cambia solo las pruebas!
+check-code-under-test = el código bajo prueba
+check-fixed-code = tus pruebas, sobre el código corregido
+check-fixed-code-failing = las pruebas de arriba fallan incluso con el fallo corregido, así que son las pruebas las que están mal
+check-bug-as-given = el fallo del código tal como viene
+check-caught-by = lo detecta { $test }
+check-missed-bug = tus pruebas pasan con el código tal como viene, así que no detectan su fallo
+check-your-tests = tus pruebas
+check-no-tests = todavía no hay funciones #[test], así que no se puede detectar ningún fallo
+check-mutant = fallo { $number }: { $description }
+check-mutant-missing = Falta en el código bajo prueba el código que cambia este fallo. ¡Cambia solo las pruebas!
+check-mutant-survived = tus pruebas siguen pasando con este fallo plantado
+
+## tutor hint
+
+hint-none = Todavía no hay pistas para { $name }. ¡Vuelve a leer los comentarios al principio del archivo!
+hint-title = 💡 Pistas para { $name }
+hint-nudge = Empujón
+hint-explanation = Explicación
+hint-solution = Solución
+hint-all-shown = Esas son todas las pistas. ¡Tú puedes!
+hint-more = ¿Sigues atascado? Ejecuta `tutor hint { $name }` otra vez para más ayuda.
+
+## tutor quiz
+
+quiz-list = 🧠 Cuestionarios
+quiz-best = mejor { $percent } %
+quiz-not-taken = sin hacer
+quiz-list-advice = Ejecuta `tutor quiz <capítulo>` para hacer uno.
+quiz-title = 🧠 Cuestionario: { $chapter } ({ $total ->
+    [one] 1 pregunta
+   *[other] { $total } preguntas
+})
+quiz-question = Pregunta { $number }/{ $total }
+quiz-pick-letter = Escribe una de las letras de arriba.
+quiz-type-output = Escribe la salida y luego una línea vacía:
+quiz-correct = ✅ ¡Correcto!
+quiz-wrong = ❌ No exactamente. La respuesta es:
+quiz-score = Puntuación: { $correct }/{ $total }
+
+## tutor status
+
+status-title = 📊 Tu progreso
+status-overall = total
+status-no-streak = 🔥 Todavía no tienes racha: practica hoy para empezar una.
+status-streak = 🔥 Racha de { $current } días (la mejor: { $best ->
+    [one] 1 día
+   *[other] { $best } días
+})
+status-streak-at-risk = 🔥 Racha de { $current } días (la mejor: { $best ->
+    [one] 1 día
+   *[other] { $best } días
+}). ¡Practica hoy para no perderla!
+status-achievements = 🏆 Logros: { $unlocked } de { $total }
+achievement-unlocked = 🏆 Logro desbloqueado: { $title } ({ $description })
+achievement-first_example = Hola, mundo
+achievement-first_example-description = Ejecuta tu primer ejemplo
+achievement-first_fix = Encantador de compiladores
+achievement-first_fix-description = Corrige tu primer error de compilación
+achievement-first_exercise = Primeros pasos
+achievement-first_exercise-description = Termina un ejercicio
+achievement-busy_day = Día ajetreado
+achievement-busy_day-description = Termina 10 ejercicios en un día
+achievement-ownership_without_hints = Amigo del borrow checker
+achievement-ownership_without_hints-description = Completa el capítulo de ownership sin una sola pista
+achievement-quiz_ace = Matrícula de honor
+achievement-quiz_ace-description = Responde bien todas las preguntas de un cuestionario
+achievement-week_streak = En racha
+achievement-week_streak-description = Practica 7 días seguidos
+achievement-every_exercise = Meta alcanzada
+achievement-every_exercise-description = Termina todos los ejercicios
+
+## tutor review
+
+review-when = { $days ->
+    [0] hoy
+    [1] mañana
+   *[other] dentro de { $days } días
+}
+review-ago = { $days ->
+    [0] hoy
+    [1] ayer
+   *[other] hace { $days } días
+}
+review-nothing-today = ✅ Hoy no hay nada que repasar. El próximo repaso es { $when }.
+review-nothing-yet = Todavía no hay nada que repasar: termina un ejercicio o haz un cuestionario, y aparecerá aquí al día siguiente.
+review-title = 🔁 Repaso: { $count } de { $due } para hoy
+review-next = 🗓  Próximo repaso { $when }.
+review-done = { $count ->
+    [one] Repasaste 1 elemento.
+   *[other] Repasaste { $count } elementos.
+}
+review-more-due = Quedan { $left } para hoy; ejecuta `tutor review` otra vez para verlos.
+review-exercise = Ejercicio { $name }:
+review-think = ¿Cómo lo resolviste? Piénsalo y luego pulsa Enter para ver la solución.
+review-remembered = ¿Qué tan bien lo recordabas? De 0 (nada) a 5 (perfectamente):
+review-pick-number = Escribe un número del 0 al 5.
+review-schedule-title = 🔁 Calendario de repaso
+review-column-chapter = capítulo
+review-column-items = elementos
+review-column-due = para hoy
+review-column-last = última práctica
+review-never = nunca
+review-nothing-due = Hoy no toca nada; el próximo repaso es { $when }.
+review-due = { $due ->
+    [one] Hoy toca repasar 1 elemento.
+   *[other] Hoy toca repasar { $due } elementos.
+} Ejecuta `tutor review` para verlos.
+
+## tutor errors and tutor explain
+
+errors-list-title = 📚 Errores del compilador con explicación:
+errors-list-advice = Ejecuta `tutor errors <código>` para leer sobre uno.
+errors-example = Este programa lo provoca:
+errors-lesson = 📖 La lección { $name } ({ $title }) lo explica. Ejecuta `tutor run { $name }` para repasarla.
+errors-see-lesson = 📖 Consulta la lección `{ $lesson }`.
+errors-rustc-explain = Para la historia completa, ejecuta `rustc --explain { $code }`.
+errors-suggest = 📚 ¿No sabes qué significan estos errores?
+errors-suggestion = { $code }: { $title } (`tutor errors { $code }`, se enseña en { $lesson })
+explain-title = 📖 { $name }, explicado
+explain-no-notes = 📖 { $name } todavía no tiene explicaciones, así que aquí está solo su código.
+
+## tutor lint
+
+lint-running = 📎 Ejecutando clippy sobre { $name }
+lint-compile-error = ❌ { $name } no compila. Corrige los errores primero.
+lint-wrong-output = ❌ Clippy está contento, pero { $name } ya no imprime lo que dice su bloque `// EXPECTED:`:
+lint-broken = ❌ Clippy está contento, pero { $name } ya no se ejecuta sin errores. Prueba `tutor run { $name }`.
+lint-clean = ✅ Clippy no tiene nada que decir sobre { $name }, y sigue funcionando.
+lint-places = { $count ->
+    [one] 1 lugar
+   *[other] { $count } lugares
+}
+lint-before = Antes:
+lint-after = Después:
+lint-left = Quedan { $warnings ->
+    [one] 1 aviso
+   *[other] { $warnings } avisos
+} de { $lints ->
+    [one] 1 lint
+   *[other] { $lints } lints
+}. Edita { $path } y ejecuta `tutor lint { $name }` otra vez.
+
+## tutor compare
+
+compare-list-title = 🔀 Lecciones disponibles en los tres lenguajes:
+compare-list-advice = Ejecuta `tutor compare <tema>` para ver uno en paralelo.
+compare-about = De qué trata
+compare-code = Código
+compare-notes = Notas
+
+## tutor puzzle
+
+puzzle-list-title = 🧩 Acertijos del borrow checker
+puzzle-list-advice = Edita un archivo de puzzles/ y luego ejecuta `tutor puzzle <nombre>` para comprobarlo.
+puzzle-no-hint = Todavía no hay pista para { $name }.
+puzzle-rule-forbid = sin { $items }
+puzzle-rule-keep = deja `{ $signature }` como está
+puzzle-not-allowed = { $what } no está permitido
+puzzle-keep-signature = `{ $name }` debe conservar su firma original
+puzzle-keep-fn = `fn { $name }` debe seguir en el programa
+puzzle-violation = 🚫 línea { $line }: { $message }
+puzzle-rules-broken = Eso rompe las reglas del acertijo. ¡Busca otra manera!
+puzzle-solved = ✅ ¡Resuelto! Compila, respeta las reglas e imprime lo correcto.
+puzzle-compile-error = ❌ { $name } todavía no compila.
+puzzle-stuck = 💡 ¿Atascado? Ejecuta `tutor puzzle { $name } --hint`.
+puzzle-wrong-output = ❌ Compila, pero debería imprimir:
+puzzle-failed = ❌ Compila, pero termina con un error.
+puzzle-timed-out = ❌ Compila, pero seguía ejecutándose después de { $seconds } segundos.
+
+## tutor project
+
+project-list-title = 🛠️  Proyectos
+project-stages = { $done }/{ $total } etapas
+project-list-advice = Ejecuta `tutor project <nombre>` para trabajar en uno.
+project-stage = 🛠️  { $name }, etapa { $number } de { $total }: { $title }
+project-stage-passed = ✅ Etapa { $number }: { $title }
+project-compile-error = ❌ { $name } todavía no compila.
+project-stage-done = 🎉 ¡La etapa { $number } está terminada!
+project-next-stage = A continuación, la etapa { $number }: { $title } (`tutor project { $name } --stage { $number }`)
+project-failing = { $failing ->
+    [one] 1 de { $total } pruebas sigue fallando.
+   *[other] { $failing } de { $total } pruebas siguen fallando.
+}
+project-finished = 🎉 ¡Construiste { $name } entero!
+
+## tutor solution and tutor watch
+
+solution-same = ✅ Tu { $name } es igual a la solución de referencia.
+solution-title = 📖 { $name }: tu intento junto a la solución
+solution-your-attempt = { $path } (tu intento)
+solution-applied = 📝 { $path } se reemplazó por la solución. Tu intento está guardado en { $backup }.
+solution-apply-advice = Ejecuta `cargo run -p tutor -- solution { $name } --apply` para reemplazar tu intento por ella.
+watch-title = 👀 Vigilando { $dir } (Ctrl-C para salir)
+watch-next = ✅ ¡{ $name } está terminado! Vamos con el siguiente.
+watch-all-done = 🎉 Todos los ejercicios pasan. ¡Los terminaste todos!
+
+## tutor play and tutor predict
+
+play-opening = 📝 Abriendo { $file } en { $editor }…
+play-again = Pulsa Enter para editarlo otra vez, o escribe q para salir:
+play-saved = El playground está guardado; `tutor play --resume` continúa donde lo dejaste.
+play-compile-error = ❌ El playground no compila. Lee los errores de arriba e inténtalo de nuevo.
+play-running = ▶ Ejecutándolo
+play-success = ✅ Se ejecutó correctamente.
+play-failed = ❌ Compiló, pero terminó con un error.
+play-timed-out = ❌ Seguía ejecutándose después de { $seconds } segundos, así que se detuvo.
+predict-title = 🔮 ¿Qué imprime esto? (de { $name })
+predict-prompt = Escribe tu predicción y luego una línea vacía:
+predict-right = ✅ ¡Exacto! Imprime:
+predict-wrong = ❌ No exactamente. Tu predicción (-) frente a lo que imprimió (+):
+
+## tutor ui
+
+ui-welcome = ¡Bienvenido! Elige una lección y pulsa Enter.
+ui-keys = ↑↓/jk mover · Enter ejecutar o comprobar · h pista · Tab siguiente panel · RePág/AvPág desplazar · q salir
+ui-lessons = Lecciones
+ui-output = Salida
+ui-hints = Pistas
+ui-press-enter = Pulsa Enter para ejecutar esta lección.
+ui-example-hints = Los ejemplos no tienen pistas; sus comentarios los explican.
+ui-builds-on = Se basa en:
+ui-press-h = Pulsa h para ver la pista { $number } de { $total }.
+ui-hint-revealed = 💡 Pista { $number } de { $total } para { $name }.
+ui-stuck = ¿Atascado? Pulsa h.
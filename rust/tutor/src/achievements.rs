@@ -0,0 +1,253 @@
+//! Achievements and the daily streak, to keep learners coming back.
+//!
+//! An achievement is unlocked the first time the learner's progress
+//! qualifies for it, and stays unlocked. The tutor checks after every
+//! command (and after every run in `tutor ui`) and celebrates the new
+//! ones. The streak counts the days in a row the learner ran, checked, or
+//! reviewed something; it is still alive until a whole day goes by
+//! without practice.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::course::Course;
+use crate::exercise::Exercise;
+use crate::i18n;
+use crate::progress::{self, Kind, Progress, SECONDS_PER_DAY};
+use crate::t;
+
+/// The chapter to finish without hints for [`NO_HINTS`].
+const NO_HINTS_CHAPTER: &str = "ownership";
+/// How many exercises to finish in one day for `busy_day`.
+const BUSY_DAY: usize = 10;
+/// How many days in a row make a `week_streak`.
+const WEEK: u32 = 7;
+
+const NO_HINTS: &str = "ownership_without_hints";
+
+/// An achievement. Its title and description are the messages
+/// `achievement-<id>` and `achievement-<id>-description` in
+/// `tutor/locales/`.
+pub struct Achievement {
+    /// The key in [`Progress::achievements`]; never change it.
+    pub id: &'static str,
+}
+
+impl Achievement {
+    pub fn title(&self) -> String {
+        i18n::current().text(&format!("achievement-{}", self.id), &[])
+    }
+
+    pub fn description(&self) -> String {
+        i18n::current().text(&format!("achievement-{}-description", self.id), &[])
+    }
+}
+
+pub const ALL: &[Achievement] = &[
+    Achievement {
+        id: "first_example",
+    },
+    Achievement { id: "first_fix" },
+    Achievement {
+        id: "first_exercise",
+    },
+    Achievement { id: "busy_day" },
+    Achievement { id: NO_HINTS },
+    Achievement { id: "quiz_ace" },
+    Achievement { id: "week_streak" },
+    Achievement {
+        id: "every_exercise",
+    },
+];
+
+/// The days in a row the learner has practiced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    /// Up to today, or up to yesterday if there's been no practice today
+    /// yet; 0 if neither.
+    pub current: u32,
+    /// The longest streak so far.
+    pub best: u32,
+    pub practiced_today: bool,
+}
+
+pub fn streak(progress: &Progress) -> Streak {
+    streak_on(&progress.active_days, progress::today())
+}
+
+fn streak_on(days: &BTreeSet<u64>, today: u64) -> Streak {
+    let practiced_today = days.contains(&today);
+    let mut current = 0;
+    let mut day = if practiced_today {
+        Some(today)
+    } else {
+        today.checked_sub(1)
+    };
+    while let Some(active) = day.filter(|day| days.contains(day)) {
+        current += 1;
+        day = active.checked_sub(1);
+    }
+
+    let (mut best, mut run, mut previous) = (0, 0, None);
+    for &day in days {
+        run = if previous.map(|previous: u64| previous + 1) == Some(day) {
+            run + 1
+        } else {
+            1
+        };
+        best = best.max(run);
+        previous = Some(day);
+    }
+    Streak {
+        current,
+        best,
+        practiced_today,
+    }
+}
+
+/// Unlocks every achievement `progress` now qualifies for, and returns
+/// the ones that weren't unlocked before.
+pub fn unlock(
+    course: &Course,
+    exercises: &[Exercise],
+    progress: &mut Progress,
+) -> Vec<&'static Achievement> {
+    let new: Vec<&Achievement> = ALL
+        .iter()
+        .filter(|achievement| !progress.achievements.contains_key(achievement.id))
+        .filter(|achievement| earned(achievement.id, course, exercises, progress))
+        .collect();
+    let now = progress::now();
+    for achievement in &new {
+        progress
+            .achievements
+            .insert(achievement.id.to_string(), now);
+    }
+    new
+}
+
+/// Prints a line for each newly unlocked achievement.
+pub fn announce(new: &[&Achievement]) {
+    for achievement in new {
+        println!("\n{}", unlocked(achievement));
+    }
+}
+
+/// "Achievement unlocked", with the achievement's title and description.
+pub fn unlocked(achievement: &Achievement) -> String {
+    t!(
+        "achievement-unlocked",
+        title = achievement.title(),
+        description = achievement.description().to_lowercase()
+    )
+}
+
+/// Whether `progress` qualifies for the achievement `id`.
+fn earned(id: &str, course: &Course, exercises: &[Exercise], progress: &Progress) -> bool {
+    let done = |kind: Kind, name: &str| progress.is_completed(kind, name);
+    match id {
+        "first_example" => course
+            .examples
+            .iter()
+            .any(|example| done(Kind::Example, &example.name)),
+        "first_fix" => progress.compile_errors_fixed > 0,
+        "first_exercise" => exercises
+            .iter()
+            .any(|exercise| done(Kind::Exercise, &exercise.name)),
+        "busy_day" => busiest_day(progress) >= BUSY_DAY,
+        NO_HINTS => {
+            let examples: Vec<_> = course
+                .examples
+                .iter()
+                .filter(|example| example.topic() == NO_HINTS_CHAPTER)
+                .collect();
+            let chapter: Vec<_> = exercises
+                .iter()
+                .filter(|exercise| exercise.topic == NO_HINTS_CHAPTER)
+                .collect();
+            !chapter.is_empty()
+                && examples
+                    .iter()
+                    .all(|example| done(Kind::Example, &example.name))
+                && chapter.iter().all(|exercise| {
+                    progress
+                        .get(Kind::Exercise, &exercise.name)
+                        .is_some_and(|record| {
+                            record.completed_at.is_some() && record.hints_used == 0
+                        })
+                })
+        }
+        "quiz_ace" => progress
+            .quizzes
+            .values()
+            .any(|quiz| quiz.best_percent == 100),
+        "week_streak" => streak(progress).best >= WEEK,
+        "every_exercise" => {
+            !exercises.is_empty()
+                && exercises
+                    .iter()
+                    .all(|exercise| done(Kind::Exercise, &exercise.name))
+        }
+        _ => false,
+    }
+}
+
+/// The most exercises finished on any one day.
+fn busiest_day(progress: &Progress) -> usize {
+    let mut per_day: BTreeMap<u64, usize> = BTreeMap::new();
+    for record in progress.exercises.values() {
+        if let Some(completed) = record.completed_at {
+            *per_day.entry(completed / SECONDS_PER_DAY).or_default() += 1;
+        }
+    }
+    per_day.into_values().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_streak_lasts_until_a_day_is_missed() {
+        let days: BTreeSet<u64> = [1, 2, 3, 10, 11].into_iter().collect();
+        assert_eq!(
+            streak_on(&days, 11),
+            Streak {
+                current: 2,
+                best: 3,
+                practiced_today: true
+            }
+        );
+        assert_eq!(streak_on(&days, 12).current, 2);
+        assert!(!streak_on(&days, 12).practiced_today);
+        assert_eq!(streak_on(&days, 13).current, 0);
+        assert_eq!(streak_on(&BTreeSet::new(), 5).best, 0);
+    }
+
+    #[test]
+    fn exercises_count_towards_the_day_they_were_finished() {
+        let mut progress = Progress::default();
+        for (name, at) in [("a", 10), ("b", 20), ("c", SECONDS_PER_DAY + 5)] {
+            progress.exercises.insert(
+                name.to_string(),
+                progress::Record {
+                    completed_at: Some(at),
+                    ..progress::Record::default()
+                },
+            );
+        }
+        assert_eq!(busiest_day(&progress), 2);
+    }
+
+    #[test]
+    fn every_achievement_has_a_title_and_a_description() {
+        for &(code, _) in i18n::LANGUAGES {
+            let language = i18n::Language::new(code).unwrap();
+            for achievement in ALL {
+                let title = format!("achievement-{}", achievement.id);
+                let description = format!("{title}-description");
+                assert_ne!(language.text(&title, &[]), title, "{code}");
+                assert_ne!(language.text(&description, &[]), description, "{code}");
+            }
+        }
+    }
+}
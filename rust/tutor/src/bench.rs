@@ -0,0 +1,273 @@
+//! `tutor bench`: run a chapter's criterion benchmarks and compare them with
+//! the baselines committed next to them.
+//!
+//! A chapter with benchmarks is a cargo package with a `benches/` folder:
+//!
+//! ```text
+//! examples/benchmarking/
+//! ├── Cargo.toml        <- one [[bench]] per file, with harness = false
+//! ├── benches/maps.rs   <- criterion benchmarks
+//! └── baselines.json    <- mean times from one run, on one machine
+//! ```
+//!
+//! Criterion keeps its own results under `target/`, which isn't committed,
+//! and compares each run with the previous one. `baselines.json` is a copy
+//! of one run's means that travels with the repository, so a learner can
+//! see how their machine, or their changes, compare.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::course::{file_stem, sorted_entries};
+
+/// A change within this fraction of the baseline is reported as noise.
+pub const NOISE: f64 = 0.10;
+
+/// The benchmarks of one chapter.
+#[derive(Debug)]
+pub struct Suite {
+    /// The chapter's directory name, e.g. `benchmarking`.
+    pub chapter: String,
+    pub dir: PathBuf,
+    /// File stems of `benches/*.rs`, e.g. `maps`.
+    pub benches: Vec<String>,
+}
+
+/// The mean time of one benchmark in one run.
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    /// Criterion's id, e.g. `strings/join/1000`.
+    pub id: String,
+    /// The benchmarks this one is ranked against: same group, same input.
+    pub group: String,
+    pub mean_ns: f64,
+}
+
+/// The contents of `baselines.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baselines {
+    /// Where the numbers were measured, e.g. `linux x86_64, 4 CPUs`.
+    pub machine: String,
+    /// Mean time in nanoseconds, by benchmark id.
+    pub means_ns: BTreeMap<String, f64>,
+}
+
+/// The parts of criterion's `benchmark.json` we use.
+#[derive(Deserialize)]
+struct BenchmarkFile {
+    full_id: String,
+    group_id: String,
+    value_str: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+impl Suite {
+    pub fn baselines_path(&self) -> PathBuf {
+        self.dir.join("baselines.json")
+    }
+
+    /// The committed baselines, or `None` if there are none yet.
+    pub fn baselines(&self) -> Result<Option<Baselines>> {
+        let path = self.baselines_path();
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+        let baselines = serde_json::from_str(&text)
+            .with_context(|| format!("{} is not valid", path.display()))?;
+        Ok(Some(baselines))
+    }
+}
+
+/// Finds every `<root>/examples/*/` package with a `benches/` folder.
+pub fn discover(root: &Path) -> Result<Vec<Suite>> {
+    let mut suites = Vec::new();
+    for dir in sorted_entries(&root.join("examples"))? {
+        let benches_dir = dir.join("benches");
+        if !dir.join("Cargo.toml").is_file() || !benches_dir.is_dir() {
+            continue;
+        }
+        let mut benches = Vec::new();
+        for path in sorted_entries(&benches_dir)? {
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                benches.push(file_stem(&path)?);
+            }
+        }
+        suites.push(Suite {
+            chapter: file_stem(&dir)?,
+            dir,
+            benches,
+        });
+    }
+    Ok(suites)
+}
+
+/// Runs `suite`'s benchmarks (only those whose id matches `filter`, if
+/// given) with criterion's results kept under `build_dir`, and returns the
+/// measurements of this run. Criterion prints one line per benchmark as it
+/// goes. Returns the compiler's diagnostics instead if they don't build.
+pub fn run(
+    suite: &Suite,
+    filter: Option<&str>,
+    build_dir: &Path,
+) -> Result<Result<Vec<Measurement>, String>> {
+    let home = build_dir.join("criterion").join(&suite.chapter);
+    let started = SystemTime::now();
+    let mut command = Command::new("cargo");
+    command
+        .args(["bench", "--quiet", "--color=always"])
+        .arg("--manifest-path")
+        .arg(suite.dir.join("Cargo.toml"));
+    for bench in &suite.benches {
+        command.args(["--bench", bench]);
+    }
+    command.args(["--", "--quiet", "--noplot"]);
+    if let Some(filter) = filter {
+        command.arg(filter);
+    }
+    let output = command
+        .env("CRITERION_HOME", &home)
+        .stdout(std::process::Stdio::inherit())
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+    if !output.status.success() {
+        return Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()));
+    }
+
+    let mut measurements = Vec::new();
+    collect(&home, started, &mut measurements)?;
+    // Side by side with the benchmarks they compete with
+    measurements.sort_by(|a, b| (&a.group, &a.id).cmp(&(&b.group, &b.id)));
+    Ok(Ok(measurements))
+}
+
+/// Reads every `new/` result under `dir` written since `since`; older ones
+/// are from earlier runs, of benchmarks this run's filter left out.
+fn collect(dir: &Path, since: SystemTime, measurements: &mut Vec<Measurement>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let estimates_path = dir.join("new").join("estimates.json");
+    if estimates_path.is_file() && fs::metadata(&estimates_path)?.modified()? >= since {
+        let benchmark: BenchmarkFile = read_json(&dir.join("new").join("benchmark.json"))?;
+        let estimates: Estimates = read_json(&estimates_path)?;
+        let group = match benchmark.value_str {
+            Some(value) => format!("{}/{value}", benchmark.group_id),
+            None => benchmark.group_id,
+        };
+        measurements.push(Measurement {
+            id: benchmark.full_id,
+            group,
+            mean_ns: estimates.mean.point_estimate,
+        });
+    }
+    for entry in sorted_entries(dir)? {
+        // criterion's HTML report has nothing we need
+        if entry.is_dir() && entry.file_name().is_some_and(|name| name != "report") {
+            collect(&entry, since, measurements)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("{} is not valid", path.display()))
+}
+
+/// Describes this machine, for the `machine` field of the baselines.
+pub fn this_machine() -> String {
+    let cpus = std::thread::available_parallelism().map_or(1, |count| count.get());
+    format!(
+        "{} {}, {cpus} CPU{}",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        if cpus == 1 { "" } else { "s" }
+    )
+}
+
+/// Writes `measurements` to `suite`'s baselines, keeping entries for the
+/// benchmarks this run didn't include.
+pub fn save_baselines(suite: &Suite, measurements: &[Measurement]) -> Result<()> {
+    let mut baselines = suite.baselines()?.unwrap_or_default();
+    baselines.machine = this_machine();
+    for measurement in measurements {
+        baselines.means_ns.insert(
+            measurement.id.clone(),
+            (measurement.mean_ns * 10.0).round() / 10.0,
+        );
+    }
+    let path = suite.baselines_path();
+    let text = serde_json::to_string_pretty(&baselines)? + "\n";
+    fs::write(&path, text).with_context(|| format!("cannot write {}", path.display()))
+}
+
+/// A Markdown table of `measurements`: each one's mean, how it ranks in its
+/// group, and how it compares with `baselines`.
+pub fn render(measurements: &[Measurement], baselines: Option<&Baselines>) -> String {
+    let mut out = String::from(
+        "| Benchmark | Mean | vs fastest | Baseline | Change |\n\
+         |-----------|-----:|-----------:|---------:|--------|\n",
+    );
+    for measurement in measurements {
+        let fastest = measurements
+            .iter()
+            .filter(|other| other.group == measurement.group)
+            .map(|other| other.mean_ns)
+            .fold(f64::INFINITY, f64::min);
+        let (baseline, change) = match baselines.and_then(|b| b.means_ns.get(&measurement.id)) {
+            Some(&baseline) => (human(baseline), change(measurement.mean_ns, baseline)),
+            None => (String::new(), "no baseline".to_string()),
+        };
+        out.push_str(&format!(
+            "| {} | {} | {:.2}× | {baseline} | {change} |\n",
+            measurement.id,
+            human(measurement.mean_ns),
+            measurement.mean_ns / fastest
+        ));
+    }
+    out
+}
+
+/// E.g. `+12.5% slower`, or `-0.8% (noise)` when within [`NOISE`].
+fn change(mean_ns: f64, baseline_ns: f64) -> String {
+    let change = mean_ns / baseline_ns - 1.0;
+    let verdict = if change.abs() < NOISE {
+        "(noise)"
+    } else if change > 0.0 {
+        "slower"
+    } else {
+        "faster"
+    };
+    format!("{:+.1}% {verdict}", change * 100.0)
+}
+
+/// A time in nanoseconds with a unit that keeps it readable, e.g. `4.12 µs`.
+fn human(nanos: f64) -> String {
+    if nanos >= 1e9 {
+        format!("{:.2} s", nanos / 1e9)
+    } else if nanos >= 1e6 {
+        format!("{:.2} ms", nanos / 1e6)
+    } else if nanos >= 1e3 {
+        format!("{:.2} µs", nanos / 1e3)
+    } else {
+        format!("{nanos:.1} ns")
+    }
+}
@@ -0,0 +1,1138 @@
+//! `tutor borrows`: a timeline of what happens to each variable in a
+//! lesson's functions, line by line: where it is created, borrowed, moved,
+//! and dropped.
+//!
+//! The file is parsed with `syn`, and the analysis knows nothing about
+//! types, so it is only meant for the small functions of the ownership
+//! chapters. Where the borrow checker would look at a type, it guesses:
+//! - a variable moves when it is assigned or passed by value only if it
+//!   clearly owns its value: it is declared as a `String`, a `Vec` or
+//!   another of [`OWNED_TYPES`], or starts out as `String::from(...)`,
+//!   `vec![...]`, `format!(...)` and the like; anything else might be
+//!   Copy, and is only used
+//! - `println!` and the other formatting macros borrow their arguments,
+//!   and so do comparisons
+//! - a method call borrows its receiver, mutably for methods like `push`
+//!   ([`MUT_METHODS`]); a few like `into_iter` take it by value
+//! - `let r = &x;` (or `x.iter()`, `&x[1..]`, ...) keeps `x` borrowed
+//!   until the last line that uses `r`, the way non-lexical lifetimes do;
+//!   so does calling a function of the same file that returns a reference
+//!
+//! Closures, and the bindings of `match` arms and `if let`, are followed
+//! as plain uses without columns of their own.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, Pat, Stmt, Type, UnOp};
+
+/// Methods that take their receiver by `&mut`.
+const MUT_METHODS: &[&str] = &[
+    "push",
+    "push_str",
+    "pop",
+    "insert",
+    "remove",
+    "clear",
+    "extend",
+    "truncate",
+    "sort",
+    "sort_by",
+    "sort_by_key",
+    "dedup",
+    "reverse",
+    "retain",
+    "drain",
+    "append",
+    "iter_mut",
+    "get_mut",
+    "as_mut",
+    "entry",
+    "last_mut",
+    "first_mut",
+    "values_mut",
+    "make_ascii_uppercase",
+    "make_ascii_lowercase",
+    "swap",
+];
+
+/// Methods that take their receiver by value.
+const CONSUMING_METHODS: &[&str] = &[
+    "into_iter",
+    "into_bytes",
+    "into_boxed_str",
+    "into_inner",
+    "into_keys",
+    "into_values",
+    "into",
+    "unwrap",
+    "expect",
+    "unwrap_or",
+    "unwrap_or_default",
+];
+
+/// Methods whose result still borrows the receiver, so that
+/// `let r = x.iter();` keeps `x` borrowed while `r` is used.
+const BORROWING_METHODS: &[&str] = &[
+    "iter",
+    "iter_mut",
+    "as_str",
+    "as_slice",
+    "as_ref",
+    "as_mut",
+    "as_bytes",
+    "get",
+    "get_mut",
+    "first",
+    "last",
+    "first_mut",
+    "last_mut",
+    "chars",
+    "bytes",
+    "lines",
+    "split_whitespace",
+    "split",
+    "trim",
+    "keys",
+    "values",
+    "values_mut",
+    "entry",
+];
+
+/// Methods that return a new owned value, which moves like any other.
+const OWNING_METHODS: &[&str] = &[
+    "to_string",
+    "to_owned",
+    "to_vec",
+    "to_uppercase",
+    "to_lowercase",
+    "repeat",
+    "replace",
+    "collect",
+];
+
+/// Macros whose arguments are borrowed, the way `format!` borrows them.
+const FORMAT_MACROS: &[&str] = &[
+    "print",
+    "println",
+    "eprint",
+    "eprintln",
+    "format",
+    "write",
+    "writeln",
+    "panic",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "todo",
+    "unimplemented",
+    "unreachable",
+];
+
+/// Types that own their contents and are not Copy, besides the ones the
+/// file declares without deriving Copy.
+const OWNED_TYPES: &[&str] = &[
+    "String", "Vec", "Box", "HashMap", "HashSet", "BTreeMap", "BTreeSet", "VecDeque", "Rc", "Arc",
+    "PathBuf",
+];
+
+/// The codes to draw, and the legend that explains them.
+const LEGEND: &str = "* created   | alive   & borrowed   m borrowed mutably   . used   \
+                      > moved   = assigned   x dropped   ! rejected by the compiler";
+
+/// What a line of code does to a variable.
+///
+/// When one line does several things to the same variable, the timeline
+/// shows the one that comes last in this list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Event {
+    Dropped,
+    Used,
+    Assigned,
+    Borrowed,
+    BorrowedMut,
+    Moved,
+    Created,
+    /// Used while moved, or while a borrow it conflicts with is alive.
+    Rejected,
+}
+
+impl Event {
+    fn symbol(self) -> char {
+        match self {
+            Event::Created => '*',
+            Event::Used => '.',
+            Event::Borrowed => '&',
+            Event::BorrowedMut => 'm',
+            Event::Moved => '>',
+            Event::Assigned => '=',
+            Event::Dropped => 'x',
+            Event::Rejected => '!',
+        }
+    }
+}
+
+/// One variable: a column of the timeline.
+#[derive(Debug)]
+pub struct Variable {
+    /// The name, with a `'` for each earlier variable it shadows.
+    pub name: String,
+    owned: bool,
+    moved: Option<usize>,
+}
+
+/// `holder` borrows `target` from `line` until `holder` is last used.
+#[derive(Debug)]
+struct Hold {
+    holder: usize,
+    target: usize,
+    mutable: bool,
+    line: usize,
+}
+
+/// The timeline of one function.
+#[derive(Debug)]
+pub struct Timeline {
+    pub function: String,
+    /// The lines of the `fn` keyword and of the closing brace.
+    pub start: usize,
+    pub end: usize,
+    pub variables: Vec<Variable>,
+    /// `(line, variable, event)`, in the order they were found.
+    pub events: Vec<(usize, usize, Event)>,
+    /// Why each rejected use is rejected, by line.
+    pub notes: Vec<(usize, String)>,
+    holds: Vec<Hold>,
+}
+
+impl Timeline {
+    /// The event shown for `variable` on `line`, if anything happens to it.
+    pub fn event(&self, line: usize, variable: usize) -> Option<Event> {
+        self.events
+            .iter()
+            .filter(|&&(at, var, _)| at == line && var == variable)
+            .map(|&(_, _, event)| event)
+            .max()
+    }
+
+    /// The borrow that `holder` keeps alive over these lines, if any.
+    fn region(&self, hold: &Hold) -> (usize, usize) {
+        let last_use = self
+            .events
+            .iter()
+            .filter(|&&(_, var, event)| var == hold.holder && event != Event::Dropped)
+            .map(|&(line, _, _)| line)
+            .max()
+            .unwrap_or(hold.line);
+        (hold.line, last_use.max(hold.line))
+    }
+
+    /// Marks the uses of a borrowed variable that conflict with the
+    /// borrow, now that every line is known.
+    fn check_holds(&mut self) {
+        let mut rejected = Vec::new();
+        for hold in &self.holds {
+            let (start, end) = self.region(hold);
+            for &(line, var, event) in &self.events {
+                if var != hold.target || line <= start || line > end {
+                    continue;
+                }
+                let conflicts = match event {
+                    Event::Moved | Event::Assigned | Event::BorrowedMut => true,
+                    Event::Used | Event::Borrowed => hold.mutable,
+                    _ => false,
+                };
+                if conflicts {
+                    let how = if hold.mutable { "mutably " } else { "" };
+                    rejected.push((
+                        line,
+                        var,
+                        format!(
+                            "`{}` is {} while `{}` still borrows it {how}(until line {end})",
+                            self.variables[var].name,
+                            describe(event),
+                            self.variables[hold.holder].name,
+                        ),
+                    ));
+                }
+            }
+        }
+        for (line, var, note) in rejected {
+            self.events.push((line, var, Event::Rejected));
+            self.notes.push((line, note));
+        }
+        self.notes.sort();
+    }
+}
+
+fn describe(event: Event) -> &'static str {
+    match event {
+        Event::Moved => "moved",
+        Event::Assigned => "assigned to",
+        Event::BorrowedMut => "borrowed mutably",
+        Event::Borrowed => "borrowed",
+        _ => "used",
+    }
+}
+
+/// How an expression is used by the code around it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Access {
+    Move,
+    Borrow,
+    BorrowMut,
+    Use,
+}
+
+/// Walks one function, keeping track of which names are in scope.
+struct Walker<'a> {
+    timeline: Timeline,
+    /// Each scope's names, innermost last. None hides an outer variable
+    /// behind a binding that has no column, like a closure parameter.
+    scopes: Vec<Vec<(String, Option<usize>)>>,
+    /// The functions in the file that return a reference, which borrows
+    /// from the arguments they are called with.
+    lending: &'a [String],
+    /// [`OWNED_TYPES`] and the types the file declares without Copy.
+    owned_types: &'a [String],
+    /// The functions in the file that return one of them.
+    owning: &'a [String],
+}
+
+impl Walker<'_> {
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|(bound, _)| bound == name)
+            .and_then(|&(_, variable)| variable)
+    }
+
+    fn declare(&mut self, name: &str, line: usize, owned: bool) -> usize {
+        let shadowed = self
+            .timeline
+            .variables
+            .iter()
+            .filter(|variable| variable.name.trim_end_matches('\'') == name)
+            .count();
+        let index = self.timeline.variables.len();
+        self.timeline.variables.push(Variable {
+            name: format!("{name}{}", "'".repeat(shadowed)),
+            owned,
+            moved: None,
+        });
+        self.scopes
+            .last_mut()
+            .expect("there is always a scope")
+            .push((name.to_string(), Some(index)));
+        self.timeline.events.push((line, index, Event::Created));
+        index
+    }
+
+    fn hide(&mut self, pat: &Pat) {
+        let mut names = Vec::new();
+        bindings(pat, &mut names);
+        let scope = self.scopes.last_mut().expect("there is always a scope");
+        scope.extend(names.into_iter().map(|(name, _)| (name, None)));
+    }
+
+    /// Closes the innermost scope at `line`, dropping what it still owns.
+    fn close(&mut self, line: usize) {
+        let scope = self.scopes.pop().expect("there is always a scope");
+        for variable in scope.into_iter().rev().filter_map(|(_, variable)| variable) {
+            if self.timeline.variables[variable].moved.is_none() {
+                self.timeline.events.push((line, variable, Event::Dropped));
+            }
+        }
+    }
+
+    fn event(&mut self, line: usize, variable: usize, event: Event) {
+        let state = &mut self.timeline.variables[variable];
+        match (state.moved, event) {
+            (Some(moved), Event::Used | Event::Borrowed | Event::BorrowedMut | Event::Moved) => {
+                let note = format!(
+                    "`{}` is {} after it moved on line {moved}",
+                    state.name,
+                    describe(event)
+                );
+                self.timeline.events.push((line, variable, Event::Rejected));
+                self.timeline.notes.push((line, note));
+                return;
+            }
+            (_, Event::Moved) => state.moved = Some(line),
+            (_, Event::Assigned) => state.moved = None,
+            _ => {}
+        }
+        self.timeline.events.push((line, variable, event));
+    }
+
+    fn access(&mut self, name: &str, line: usize, access: Access) {
+        let Some(variable) = self.lookup(name) else {
+            return;
+        };
+        let event = match access {
+            Access::Move if self.timeline.variables[variable].owned => Event::Moved,
+            Access::Move => Event::Used,
+            Access::Borrow => Event::Borrowed,
+            Access::BorrowMut => Event::BorrowedMut,
+            Access::Use => Event::Used,
+        };
+        self.event(line, variable, event);
+    }
+
+    fn block(&mut self, block: &syn::Block) {
+        self.scopes.push(Vec::new());
+        for stmt in &block.stmts {
+            self.stmt(stmt);
+        }
+        self.close(block.brace_token.span.close().start().line);
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Local(local) => {
+                let line = local.let_token.span.start().line;
+                let mut owned = false;
+                let mut hold = None;
+                if let Some(init) = &local.init {
+                    self.expr(&init.expr, Access::Move);
+                    if let Some((_, diverge)) = &init.diverge {
+                        self.expr(diverge, Access::Move);
+                    }
+                    owned = self.is_owned(&init.expr);
+                    hold = self.borrow_target(&init.expr);
+                }
+                let mut names = Vec::new();
+                bindings(&local.pat, &mut names);
+                let single = names.len() == 1;
+                for (name, ty) in names {
+                    let owned = match ty {
+                        Some(ty) => self.is_owned_type(ty),
+                        None => owned,
+                    };
+                    let holder = self.declare(&name, line, owned);
+                    if let (true, Some((target, mutable))) = (single, hold) {
+                        self.timeline.holds.push(Hold {
+                            holder,
+                            target,
+                            mutable,
+                            line,
+                        });
+                    }
+                }
+            }
+            Stmt::Expr(expr, _) => self.expr(expr, Access::Move),
+            Stmt::Macro(stmt) => self.mac(&stmt.mac),
+            Stmt::Item(_) => {}
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr, access: Access) {
+        let line = expr.span().start().line;
+        match expr {
+            Expr::Path(path) => {
+                if let Some(ident) = path.path.get_ident() {
+                    self.access(&ident.to_string(), line, access);
+                }
+            }
+            Expr::Reference(reference) => {
+                let access = match reference.mutability {
+                    Some(_) => Access::BorrowMut,
+                    None => Access::Borrow,
+                };
+                self.expr(&reference.expr, access);
+            }
+            // Moving a field out, or copying it, leaves the rest in place
+            Expr::Field(field) => {
+                let access = match access {
+                    Access::Move => Access::Use,
+                    other => other,
+                };
+                self.expr(&field.base, access);
+            }
+            Expr::Index(index) => {
+                let access = match access {
+                    Access::BorrowMut => Access::BorrowMut,
+                    _ => Access::Borrow,
+                };
+                self.expr(&index.expr, access);
+                self.expr(&index.index, Access::Use);
+            }
+            Expr::MethodCall(call) => {
+                let method = call.method.to_string();
+                let receiver = if MUT_METHODS.contains(&method.as_str()) {
+                    Access::BorrowMut
+                } else if CONSUMING_METHODS.contains(&method.as_str()) {
+                    Access::Move
+                } else {
+                    Access::Borrow
+                };
+                self.expr(&call.receiver, receiver);
+                for arg in &call.args {
+                    self.expr(arg, Access::Move);
+                }
+            }
+            Expr::Call(call) => {
+                self.expr(&call.func, Access::Use);
+                for arg in &call.args {
+                    self.expr(arg, Access::Move);
+                }
+            }
+            Expr::Macro(mac) => self.mac(&mac.mac),
+            Expr::Assign(assign) => {
+                self.expr(&assign.right, Access::Move);
+                self.place(&assign.left);
+            }
+            Expr::Binary(binary) => {
+                if is_compound_assignment(&binary.op) {
+                    self.expr(&binary.right, Access::Move);
+                    self.place(&binary.left);
+                } else {
+                    let access = if is_comparison(&binary.op) {
+                        Access::Borrow
+                    } else {
+                        Access::Move
+                    };
+                    self.expr(&binary.left, access);
+                    self.expr(&binary.right, access);
+                }
+            }
+            Expr::Unary(unary) => {
+                let access = match unary.op {
+                    UnOp::Deref(_) => Access::Use,
+                    _ => access,
+                };
+                self.expr(&unary.expr, access);
+            }
+            Expr::Paren(paren) => self.expr(&paren.expr, access),
+            Expr::Group(group) => self.expr(&group.expr, access),
+            Expr::Block(block) => self.block(&block.block),
+            Expr::Unsafe(block) => self.block(&block.block),
+            Expr::Async(block) => self.block(&block.block),
+            Expr::Loop(looped) => self.block(&looped.body),
+            Expr::If(branch) => {
+                self.scopes.push(Vec::new());
+                self.condition(&branch.cond);
+                self.block(&branch.then_branch);
+                self.scopes.pop();
+                if let Some((_, otherwise)) = &branch.else_branch {
+                    self.expr(otherwise, access);
+                }
+            }
+            Expr::While(looped) => {
+                self.scopes.push(Vec::new());
+                self.condition(&looped.cond);
+                self.block(&looped.body);
+                self.scopes.pop();
+            }
+            Expr::ForLoop(looped) => {
+                self.expr(&looped.expr, Access::Move);
+                self.scopes.push(Vec::new());
+                let mut names = Vec::new();
+                bindings(&looped.pat, &mut names);
+                let line = looped.for_token.span.start().line;
+                for (name, ty) in names {
+                    let owned = ty.is_some_and(|ty| self.is_owned_type(ty));
+                    self.declare(&name, line, owned);
+                }
+                self.block(&looped.body);
+                self.close(looped.body.brace_token.span.close().start().line);
+            }
+            Expr::Match(matched) => {
+                self.expr(&matched.expr, Access::Use);
+                for arm in &matched.arms {
+                    self.scopes.push(Vec::new());
+                    self.hide(&arm.pat);
+                    if let Some((_, guard)) = &arm.guard {
+                        self.expr(guard, Access::Use);
+                    }
+                    self.expr(&arm.body, Access::Move);
+                    self.scopes.pop();
+                }
+            }
+            Expr::Let(binding) => {
+                self.expr(&binding.expr, Access::Use);
+                self.hide(&binding.pat);
+            }
+            Expr::Closure(closure) => {
+                self.scopes.push(Vec::new());
+                for input in &closure.inputs {
+                    self.hide(input);
+                }
+                let access = match closure.capture {
+                    Some(_) => Access::Move,
+                    None => Access::Use,
+                };
+                self.expr(&closure.body, access);
+                self.scopes.pop();
+            }
+            Expr::Return(returned) => {
+                if let Some(value) = &returned.expr {
+                    self.expr(value, Access::Move);
+                }
+            }
+            Expr::Break(broken) => {
+                if let Some(value) = &broken.expr {
+                    self.expr(value, Access::Move);
+                }
+            }
+            Expr::Tuple(tuple) => {
+                for elem in &tuple.elems {
+                    self.expr(elem, Access::Move);
+                }
+            }
+            Expr::Array(array) => {
+                for elem in &array.elems {
+                    self.expr(elem, Access::Move);
+                }
+            }
+            Expr::Repeat(repeat) => {
+                self.expr(&repeat.expr, Access::Move);
+                self.expr(&repeat.len, Access::Use);
+            }
+            Expr::Struct(built) => {
+                for field in &built.fields {
+                    self.expr(&field.expr, Access::Move);
+                }
+                if let Some(rest) = &built.rest {
+                    self.expr(rest, Access::Use);
+                }
+            }
+            Expr::Try(tried) => self.expr(&tried.expr, Access::Move),
+            Expr::Await(awaited) => self.expr(&awaited.base, Access::Move),
+            Expr::Cast(cast) => self.expr(&cast.expr, Access::Use),
+            Expr::Range(range) => {
+                for end in [&range.start, &range.end].into_iter().flatten() {
+                    self.expr(end, Access::Use);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn condition(&mut self, cond: &Expr) {
+        self.expr(cond, Access::Use);
+    }
+
+    /// The left-hand side of `=` or `+=`.
+    fn place(&mut self, place: &Expr) {
+        let line = place.span().start().line;
+        match place {
+            Expr::Path(path) => {
+                if let Some(variable) = path
+                    .path
+                    .get_ident()
+                    .and_then(|ident| self.lookup(&ident.to_string()))
+                {
+                    self.event(line, variable, Event::Assigned);
+                }
+            }
+            Expr::Field(field) => self.place(&field.base),
+            Expr::Index(index) => {
+                self.expr(&index.expr, Access::BorrowMut);
+                self.expr(&index.index, Access::Use);
+            }
+            other => self.expr(other, Access::Use),
+        }
+    }
+
+    fn mac(&mut self, mac: &syn::Macro) {
+        let Some(name) = mac.path.get_ident().map(|ident| ident.to_string()) else {
+            return;
+        };
+        let parser = Punctuated::<Expr, syn::Token![,]>::parse_terminated;
+        let Ok(args) = mac.parse_body_with(parser) else {
+            return;
+        };
+        if name == "vec" || name == "dbg" {
+            for arg in &args {
+                self.expr(arg, Access::Move);
+            }
+        } else if FORMAT_MACROS.contains(&name.as_str()) {
+            for (i, arg) in args.iter().enumerate() {
+                if i == 0 && (name == "write" || name == "writeln") {
+                    self.expr(arg, Access::BorrowMut);
+                } else if let Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(text),
+                    ..
+                }) = arg
+                {
+                    let line = text.span().start().line;
+                    for captured in captures(&text.value()) {
+                        self.access(&captured, line, Access::Borrow);
+                    }
+                } else {
+                    self.expr(arg, Access::Borrow);
+                }
+            }
+        }
+    }
+
+    /// Whether the value of `expr` clearly owns something, and so moves.
+    fn is_owned(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Macro(mac) => ["vec", "format"]
+                .iter()
+                .any(|name| mac.mac.path.is_ident(name)),
+            Expr::Call(call) => match &*call.func {
+                Expr::Path(func) => {
+                    self.is_owned_path(&func.path)
+                        || func
+                            .path
+                            .get_ident()
+                            .is_some_and(|name| self.owning.contains(&name.to_string()))
+                }
+                _ => false,
+            },
+            Expr::Struct(built) => self.is_owned_path(&built.path),
+            Expr::MethodCall(call) => {
+                let method = call.method.to_string();
+                OWNING_METHODS.contains(&method.as_str())
+                    || (method == "clone" && self.is_owned(&call.receiver))
+            }
+            // `text + "!"` reuses the String on the left
+            Expr::Binary(binary) => {
+                matches!(binary.op, BinOp::Add(_)) && self.is_owned(&binary.left)
+            }
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.lookup(&ident.to_string()))
+                .is_some_and(|variable| self.timeline.variables[variable].owned),
+            Expr::Paren(paren) => self.is_owned(&paren.expr),
+            _ => false,
+        }
+    }
+
+    /// The variable that the value of `expr` borrows, and whether mutably.
+    fn borrow_target(&self, expr: &Expr) -> Option<(usize, bool)> {
+        match expr {
+            Expr::Reference(reference) => {
+                Some((self.base(&reference.expr)?, reference.mutability.is_some()))
+            }
+            Expr::MethodCall(call) => {
+                let method = call.method.to_string();
+                if !BORROWING_METHODS.contains(&method.as_str()) {
+                    return None;
+                }
+                Some((
+                    self.base(&call.receiver)?,
+                    MUT_METHODS.contains(&method.as_str()),
+                ))
+            }
+            Expr::Call(call) => {
+                let Expr::Path(func) = &*call.func else {
+                    return None;
+                };
+                let name = func.path.get_ident()?.to_string();
+                if !self.lending.contains(&name) {
+                    return None;
+                }
+                call.args.iter().find_map(|arg| self.borrow_target(arg))
+            }
+            Expr::Paren(paren) => self.borrow_target(&paren.expr),
+            _ => None,
+        }
+    }
+
+    fn is_owned_type(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Path(path) => self.is_owned_path(&path.path),
+            Type::Tuple(tuple) => tuple.elems.iter().any(|elem| self.is_owned_type(elem)),
+            Type::Array(array) => self.is_owned_type(&array.elem),
+            Type::Paren(paren) => self.is_owned_type(&paren.elem),
+            _ => false,
+        }
+    }
+
+    /// Whether a path like `String` or `Vec<u8>` names an owned type (or,
+    /// like `String::from`, is a function of one).
+    fn is_owned_path(&self, path: &syn::Path) -> bool {
+        let len = path.segments.len();
+        path.segments
+            .iter()
+            .skip(len.saturating_sub(2))
+            .any(|segment| self.owned_types.contains(&segment.ident.to_string()))
+    }
+
+    /// The variable at the root of a place like `x`, `x.field` or `x[1..]`.
+    fn base(&self, expr: &Expr) -> Option<usize> {
+        match expr {
+            Expr::Path(path) => self.lookup(&path.path.get_ident()?.to_string()),
+            Expr::Field(field) => self.base(&field.base),
+            Expr::Index(index) => self.base(&index.expr),
+            Expr::Paren(paren) => self.base(&paren.expr),
+            _ => None,
+        }
+    }
+}
+
+/// The names a pattern binds, each with its type annotation if it has one.
+fn bindings<'p>(pat: &'p Pat, names: &mut Vec<(String, Option<&'p Type>)>) {
+    match pat {
+        Pat::Ident(ident) => names.push((ident.ident.to_string(), None)),
+        Pat::Type(typed) => {
+            let start = names.len();
+            bindings(&typed.pat, names);
+            for name in &mut names[start..] {
+                name.1 = Some(&typed.ty);
+            }
+        }
+        Pat::Tuple(tuple) => tuple.elems.iter().for_each(|elem| bindings(elem, names)),
+        Pat::TupleStruct(tuple) => tuple.elems.iter().for_each(|elem| bindings(elem, names)),
+        Pat::Struct(fields) => fields
+            .fields
+            .iter()
+            .for_each(|field| bindings(&field.pat, names)),
+        Pat::Slice(slice) => slice.elems.iter().for_each(|elem| bindings(elem, names)),
+        Pat::Reference(reference) => bindings(&reference.pat, names),
+        Pat::Paren(paren) => bindings(&paren.pat, names),
+        _ => {}
+    }
+}
+
+fn is_comparison(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Eq(_) | BinOp::Ne(_) | BinOp::Lt(_) | BinOp::Le(_) | BinOp::Gt(_) | BinOp::Ge(_)
+    )
+}
+
+fn is_compound_assignment(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
+
+/// Whether `attr` is a `#[derive(...)]` that includes Copy.
+fn derives_copy(attr: &syn::Attribute) -> bool {
+    let mut copy = false;
+    if attr.path().is_ident("derive") {
+        let _ = attr.parse_nested_meta(|meta| {
+            copy |= meta.path.is_ident("Copy");
+            Ok(())
+        });
+    }
+    copy
+}
+
+/// The variables a format string captures by name, like `x` in `"{x:?}"`.
+fn captures(format: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = format;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let end = rest.find('}').unwrap_or(rest.len());
+        let name = rest[..end].split(':').next().unwrap_or("");
+        if name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        {
+            names.push(name.to_string());
+        }
+        rest = &rest[end..];
+    }
+    names
+}
+
+/// The timelines of the functions in `source` that have variables.
+pub fn analyze(source: &str) -> Result<Vec<Timeline>> {
+    let file = syn::parse_file(source)?;
+    let mut owned_types: Vec<String> = OWNED_TYPES.iter().map(|name| name.to_string()).collect();
+    for item in &file.items {
+        let (attrs, ident) = match item {
+            syn::Item::Struct(item) => (&item.attrs, &item.ident),
+            syn::Item::Enum(item) => (&item.attrs, &item.ident),
+            _ => continue,
+        };
+        if !attrs.iter().any(derives_copy) {
+            owned_types.push(ident.to_string());
+        }
+    }
+    // The file's functions that return a reference, or an owned value
+    let (mut lending, mut owning) = (Vec::new(), Vec::new());
+    for item in &file.items {
+        if let syn::Item::Fn(function) = item {
+            if let syn::ReturnType::Type(_, ty) = &function.sig.output {
+                let name = function.sig.ident.to_string();
+                match &**ty {
+                    Type::Reference(_) => lending.push(name),
+                    Type::Path(path)
+                        if path
+                            .path
+                            .segments
+                            .iter()
+                            .any(|segment| owned_types.contains(&segment.ident.to_string())) =>
+                    {
+                        owning.push(name)
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    let mut timelines = Vec::new();
+    for item in &file.items {
+        let syn::Item::Fn(function) = item else {
+            continue;
+        };
+        let mut walker = Walker {
+            timeline: Timeline {
+                function: function.sig.ident.to_string(),
+                start: function.sig.fn_token.span.start().line,
+                end: function.block.brace_token.span.close().start().line,
+                variables: Vec::new(),
+                events: Vec::new(),
+                notes: Vec::new(),
+                holds: Vec::new(),
+            },
+            scopes: vec![Vec::new()],
+            lending: &lending,
+            owned_types: &owned_types,
+            owning: &owning,
+        };
+        for input in &function.sig.inputs {
+            if let syn::FnArg::Typed(typed) = input {
+                let mut names = Vec::new();
+                bindings(&typed.pat, &mut names);
+                for (name, ty) in names {
+                    let owned = ty.is_some_and(|ty| walker.is_owned_type(ty));
+                    walker.declare(&name, walker.timeline.start, owned);
+                }
+            }
+        }
+        walker.block(&function.block);
+        walker.close(walker.timeline.end);
+        walker.timeline.check_holds();
+        if !walker.timeline.variables.is_empty() {
+            timelines.push(walker.timeline);
+        }
+    }
+    Ok(timelines)
+}
+
+/// Draws `timeline`: one row per line of code, one column per variable.
+pub fn render(timeline: &Timeline, source: &str) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let widths: Vec<usize> = timeline
+        .variables
+        .iter()
+        .map(|variable| variable.name.chars().count())
+        .collect();
+    let mut out = String::new();
+    let header: Vec<String> = timeline.variables.iter().map(|v| v.name.clone()).collect();
+    let _ = writeln!(out, "line | {} | code", header.join(" "));
+
+    let mut alive = vec![false; timeline.variables.len()];
+    for line in timeline.start..=timeline.end {
+        let code = lines.get(line - 1).map_or("", |code| code.trim());
+        let events: Vec<Option<Event>> = (0..timeline.variables.len())
+            .map(|variable| timeline.event(line, variable))
+            .collect();
+        if events.iter().all(Option::is_none) && (code.is_empty() || code.starts_with("//")) {
+            continue;
+        }
+        let mut cells = Vec::new();
+        for (variable, event) in events.iter().enumerate() {
+            let symbol = match event {
+                Some(event) => event.symbol(),
+                None if !alive[variable] => ' ',
+                None => held(timeline, line, variable).unwrap_or('|'),
+            };
+            match event {
+                Some(Event::Created | Event::Assigned) => alive[variable] = true,
+                Some(Event::Moved | Event::Dropped) => alive[variable] = false,
+                _ => {}
+            }
+            cells.push(format!("{symbol:<width$}", width = widths[variable]));
+        }
+        let _ = writeln!(out, "{line:>4} | {} | {code}", cells.join(" "));
+    }
+    for (line, note) in &timeline.notes {
+        let _ = writeln!(out, "! line {line}: {note}");
+    }
+    out
+}
+
+/// `&` or `m` if another variable holds a borrow of `variable` on `line`.
+fn held(timeline: &Timeline, line: usize, variable: usize) -> Option<char> {
+    timeline
+        .holds
+        .iter()
+        .filter(|hold| hold.target == variable)
+        .find(|hold| {
+            let (start, end) = timeline.region(hold);
+            start < line && line <= end
+        })
+        .map(|hold| if hold.mutable { 'm' } else { '&' })
+}
+
+/// Prints the timelines of the functions in `path` (or only of `function`).
+pub fn print(path: &Path, function: Option<&str>) -> Result<()> {
+    let source =
+        std::fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let timelines = analyze(&source).with_context(|| format!("cannot parse {}", path.display()))?;
+    let timelines: Vec<&Timeline> = timelines
+        .iter()
+        .filter(|timeline| function.is_none_or(|name| timeline.function == name))
+        .collect();
+    if timelines.is_empty() {
+        match function {
+            Some(name) => bail!("{} has no function `{name}` with variables", path.display()),
+            None => bail!("{} has no functions with variables", path.display()),
+        }
+    }
+    for timeline in timelines {
+        println!(
+            "🔍 fn {} ({}:{})\n",
+            timeline.function,
+            path.display(),
+            timeline.start
+        );
+        println!("{}", render(timeline, &source));
+    }
+    println!("{LEGEND}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeline(source: &str) -> Timeline {
+        analyze(source).unwrap().remove(0)
+    }
+
+    fn variable(timeline: &Timeline, name: &str) -> usize {
+        timeline
+            .variables
+            .iter()
+            .position(|variable| variable.name == name)
+            .unwrap_or_else(|| panic!("no variable `{name}`"))
+    }
+
+    #[test]
+    fn copies_stay_and_moves_end_the_line() {
+        let t = timeline(
+            "fn main() {\n\
+             let a = 5;\n\
+             let b = a;\n\
+             let s = String::from(\"hi\");\n\
+             let t = s;\n\
+             }\n",
+        );
+        let (a, s, tee) = (variable(&t, "a"), variable(&t, "s"), variable(&t, "t"));
+        assert_eq!(t.event(3, a), Some(Event::Used));
+        assert_eq!(t.event(5, s), Some(Event::Moved));
+        assert_eq!(t.event(6, tee), Some(Event::Dropped));
+        assert_eq!(t.event(6, s), None, "a moved value isn't dropped again");
+        assert!(t.notes.is_empty());
+    }
+
+    #[test]
+    fn a_use_after_a_move_is_rejected() {
+        let t = timeline(
+            "fn main() {\n\
+             let s = String::from(\"hi\");\n\
+             take(s);\n\
+             println!(\"{}\", s);\n\
+             }\n",
+        );
+        assert_eq!(t.event(4, variable(&t, "s")), Some(Event::Rejected));
+        assert_eq!(
+            t.notes,
+            [(4, "`s` is borrowed after it moved on line 3".to_string())]
+        );
+    }
+
+    #[test]
+    fn a_held_borrow_lasts_until_its_last_use() {
+        let source = "fn main() {\n\
+                      let mut v = vec![1, 2];\n\
+                      let first = &v[0];\n\
+                      v.len();\n\
+                      println!(\"{first}\");\n\
+                      v.push(3);\n\
+                      }\n";
+        let t = timeline(source);
+        let v = variable(&t, "v");
+        assert_eq!(t.event(4, v), Some(Event::Borrowed), "reading is fine");
+        assert_eq!(
+            t.event(6, v),
+            Some(Event::BorrowedMut),
+            "the borrow is over"
+        );
+        assert!(t.notes.is_empty());
+        let drawn = render(&t, source);
+        assert!(drawn.contains("   5 | & &     | println"), "{drawn}");
+
+        let t = timeline(&source.replace("v.len()", "v.push(0)"));
+        assert_eq!(t.event(4, variable(&t, "v")), Some(Event::Rejected));
+        assert_eq!(t.notes.len(), 1);
+        assert!(
+            t.notes[0].1.contains("while `first` still borrows it"),
+            "{:?}",
+            t.notes
+        );
+    }
+
+    #[test]
+    fn a_function_returning_a_reference_lends_its_argument() {
+        let timelines = analyze(
+            "fn first(s: &str) -> &str { &s[..1] }\n\
+             fn main() {\n\
+             let mut s = String::from(\"hi\");\n\
+             let f = first(&s);\n\
+             s.clear();\n\
+             take(f);\n\
+             take(f);\n\
+             }\n",
+        )
+        .unwrap();
+        let t = timelines.iter().find(|t| t.function == "main").unwrap();
+        assert_eq!(t.event(5, variable(t, "s")), Some(Event::Rejected));
+        assert_eq!(
+            t.event(7, variable(t, "f")),
+            Some(Event::Used),
+            "references are Copy"
+        );
+    }
+
+    #[test]
+    fn shadowing_and_blocks() {
+        let source = "fn main() {\n\
+                      let word = String::from(\"a\");\n\
+                      let word = trip(word);\n\
+                      {\n\
+                      let inner = 1;\n\
+                      }\n\
+                      }\n";
+        let t = timeline(source);
+        let names: Vec<&str> = t.variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["word", "word'", "inner"]);
+        assert_eq!(t.event(3, 0), Some(Event::Moved));
+        assert_eq!(t.event(6, 2), Some(Event::Dropped));
+        assert_eq!(t.event(7, 1), Some(Event::Dropped));
+        assert_eq!(captures("{word} {{x}} {0} {n:>5} {}"), ["word", "n"]);
+    }
+}
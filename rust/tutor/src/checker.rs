@@ -0,0 +1,677 @@
+//! Compiles an exercise together with its hidden tests and reports which
+//! assertions still fail.
+//!
+//! The learner's file and the hidden tests are stitched together with
+//! `include!` in a generated wrapper, built with `rustc --test`, and the
+//! resulting test binary's output is parsed back into per-test results.
+//! Exercises in a topic with a `Cargo.toml` need crates, so their wrapper is
+//! built with cargo instead, as the library of a generated package. A
+//! binary exercise is that package's binary, and its tests are an
+//! integration test, so they can run it.
+//!
+//! Some exercises turn this around: the learner writes the tests. Those are
+//! mutation tested. Each mutant from the topic's `tests/mutants.toml`
+//! plants a bug in a copy of the exercise, and the learner's tests must
+//! fail on every copy. A mutant is reported by its number and description
+//! only, never by the change it makes, so the list stays hidden. An
+//! exercise can also come with its bug already planted and a `fix` for it:
+//! then the tests must pass on a fixed copy and fail on the code as given,
+//! and any mutants are planted in the fixed copy.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::compiler;
+use crate::errors::{self, ErrorCode};
+use crate::exercise::{Exercise, Fix, Mutant};
+use crate::t;
+
+/// Module name the hidden tests are wrapped in; stripped from test names.
+const TEST_MODULE: &str = "hidden_tests";
+
+/// The result of checking one exercise.
+#[derive(Debug)]
+pub enum Report {
+    /// The exercise (or its tests) doesn't compile; holds the diagnostics.
+    CompileError(String),
+    /// The exercise compiled and its tests ran.
+    Tested(Vec<TestResult>),
+}
+
+/// The outcome of a single hidden test.
+#[derive(Debug)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+    /// The panic message (usually the failed assertion) for failing tests.
+    pub message: Option<String>,
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        match self {
+            Report::CompileError(_) => false,
+            Report::Tested(results) => results.iter().all(|result| result.passed),
+        }
+    }
+
+    /// The tests that still fail.
+    pub fn failures(&self) -> impl Iterator<Item = &TestResult> {
+        let results = match self {
+            Report::CompileError(_) => &[][..],
+            Report::Tested(results) => results.as_slice(),
+        };
+        results.iter().filter(|result| !result.passed)
+    }
+}
+
+/// Builds and tests `exercise`, placing artifacts under `build_dir`.
+pub fn check(exercise: &Exercise, build_dir: &Path) -> Result<Report> {
+    let dir = build_dir.join("exercises");
+    fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+
+    if let Some(fix) = &exercise.fix {
+        return check_fix(exercise, &dir, fix);
+    }
+    let report = run_tests(exercise, &exercise.path, &dir, &exercise.name)?;
+    match report {
+        Report::Tested(results) if !exercise.mutants.is_empty() => Ok(Report::Tested(
+            check_mutants(exercise, &dir, &exercise.path, results)?,
+        )),
+        report => Ok(report),
+    }
+}
+
+/// Runs clippy on the learner's code, built the way [`check`] builds it
+/// but without the hidden tests. Returns how many warnings there are, or
+/// `None` if the code doesn't compile.
+pub fn clippy(exercise: &Exercise, build_dir: &Path) -> Result<Option<usize>> {
+    let dir = build_dir.join("exercises");
+    fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    let name = format!("{}_clippy", exercise.name);
+    let wrapper = dir.join(format!("{name}.rs"));
+    // Macros are only used by the tests that aren't there
+    let source = format!(
+        "#![allow(unused_macros)]\n{}",
+        wrapper_source(&exercise.path, None)?
+    );
+    fs::write(&wrapper, source).with_context(|| format!("cannot write {}", wrapper.display()))?;
+
+    // Both report one JSON diagnostic per line: cargo wraps each in a
+    // `compiler-message` on stdout, clippy-driver writes them bare to stderr
+    let diagnostics: Vec<serde_json::Value> = match &exercise.manifest {
+        Some(manifest) => {
+            let package_manifest = write_package(exercise, manifest, &wrapper, &dir, &name)?;
+            let target = if exercise.binary {
+                vec!["--bin", &exercise.name]
+            } else {
+                vec!["--lib", "--tests"]
+            };
+            let output = Command::new("cargo")
+                .arg("clippy")
+                .arg("--quiet")
+                .arg("--message-format=json")
+                .arg("--manifest-path")
+                .arg(&package_manifest)
+                .arg("--target-dir")
+                .arg(dir.join("cargo"))
+                .args(&target)
+                .output()
+                .context("failed to launch cargo clippy; install clippy with `rustup component add clippy`")?;
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+                .filter(|message| message["reason"] == "compiler-message")
+                .map(|message| message["message"].clone())
+                .collect()
+        }
+        None => {
+            let output = Command::new("clippy-driver")
+                .arg("--edition=2021")
+                .arg("--error-format=json")
+                .arg("--emit=metadata")
+                .arg("--test")
+                .arg("--crate-name")
+                .arg(&name)
+                .arg("--out-dir")
+                .arg(&dir)
+                .arg(&wrapper)
+                .output()
+                .context("failed to launch clippy-driver; install clippy with `rustup component add clippy`")?;
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        }
+    };
+
+    // The "N warnings emitted" summaries have no spans
+    let count = |level: &str| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic["level"] == level
+                    && diagnostic["spans"]
+                        .as_array()
+                        .is_some_and(|spans| !spans.is_empty())
+            })
+            .count()
+    };
+    if count("error") > 0 {
+        return Ok(None);
+    }
+    Ok(Some(count("warning")))
+}
+
+/// Checks an exercise whose code has a bug on purpose: the learner's tests
+/// must pass once `fix` is applied, and at least one of them must fail on
+/// the code as given. That result goes between the learner's own tests and
+/// the mutants, which are planted in the fixed copy.
+fn check_fix(exercise: &Exercise, dir: &Path, fix: &Fix) -> Result<Report> {
+    let source = fs::read_to_string(&exercise.path)
+        .with_context(|| format!("cannot read {}", exercise.path.display()))?;
+    if !source.contains(&fix.find) {
+        return Ok(Report::Tested(vec![TestResult {
+            name: t!("check-code-under-test"),
+            passed: false,
+            message: Some(t!("check-fix-missing", find = fix.find.as_str())),
+        }]));
+    }
+
+    // The code as given first, so that compile errors point at the
+    // learner's own file
+    let as_given = match run_tests(exercise, &exercise.path, dir, &exercise.name)? {
+        Report::Tested(results) => results,
+        report => return Ok(report),
+    };
+
+    let crate_name = format!("{}_fixed", exercise.name);
+    let fixed = dir.join(format!("{crate_name}.rs"));
+    fs::write(&fixed, source.replacen(&fix.find, &fix.replace, 1))
+        .with_context(|| format!("cannot write {}", fixed.display()))?;
+    let mut results = match run_tests(exercise, &fixed, dir, &crate_name)? {
+        Report::Tested(results) => results,
+        Report::CompileError(diagnostics) => {
+            bail!(
+                "the fixed copy of {} does not compile:\n{diagnostics}",
+                exercise.name
+            )
+        }
+    };
+    if results.iter().any(|result| !result.passed) {
+        results.push(TestResult {
+            name: t!("check-fixed-code"),
+            passed: false,
+            message: Some(t!("check-fixed-code-failing")),
+        });
+        return Ok(Report::Tested(results));
+    }
+
+    let mut results = check_mutants(exercise, dir, &fixed, results)?;
+    let caught = as_given.into_iter().find(|result| !result.passed);
+    let bug = TestResult {
+        name: t!("check-bug-as-given"),
+        passed: caught.is_some(),
+        message: Some(match caught {
+            Some(test) => match test.message {
+                Some(message) => format!(
+                    "{}:\n{}",
+                    t!("check-caught-by", test = test.name.as_str()),
+                    failure_summary(&message)
+                ),
+                None => t!("check-caught-by", test = test.name.as_str()),
+            },
+            None => t!("check-missed-bug"),
+        }),
+    };
+    results.insert(results.len() - exercise.mutants.len(), bug);
+    Ok(Report::Tested(results))
+}
+
+/// The start of a failing test's message, which is enough to show what
+/// caught the bug. A proptest failure is reported from `Test failed:` on;
+/// the lines before it are panics from the inputs it tried while
+/// shrinking.
+fn failure_summary(message: &str) -> String {
+    const LINES: usize = 6;
+    let report = message
+        .find("Test failed:")
+        .map_or(message, |start| &message[start..]);
+    let lines: Vec<&str> = report.lines().collect();
+    if lines.len() <= LINES {
+        return report.to_string();
+    }
+    format!(
+        "{}\n... ({} more lines)",
+        lines[..LINES].join("\n"),
+        lines.len() - LINES
+    )
+}
+
+/// Runs the learner's tests against every mutant of `source`, adding one
+/// result per mutant: it passes when the mutant was caught (some test
+/// failed).
+fn check_mutants(
+    exercise: &Exercise,
+    dir: &Path,
+    source: &Path,
+    mut results: Vec<TestResult>,
+) -> Result<Vec<TestResult>> {
+    if results.iter().any(|result| !result.passed) {
+        // Tests that fail on the correct code would "catch" every mutant.
+        return Ok(results);
+    }
+    if results.is_empty() {
+        results.push(TestResult {
+            name: t!("check-your-tests"),
+            passed: false,
+            message: Some(t!("check-no-tests")),
+        });
+    }
+
+    let source =
+        fs::read_to_string(source).with_context(|| format!("cannot read {}", source.display()))?;
+    for (index, mutant) in exercise.mutants.iter().enumerate() {
+        results.push(check_mutant(exercise, dir, &source, index + 1, mutant)?);
+    }
+    Ok(results)
+}
+
+fn check_mutant(
+    exercise: &Exercise,
+    dir: &Path,
+    source: &str,
+    number: usize,
+    mutant: &Mutant,
+) -> Result<TestResult> {
+    let name = t!(
+        "check-mutant",
+        number = number,
+        description = mutant.description.as_str()
+    );
+    if !source.contains(&mutant.find) {
+        return Ok(TestResult {
+            name,
+            passed: false,
+            message: Some(t!("check-mutant-missing")),
+        });
+    }
+
+    let crate_name = format!("{}_mutant{number}", exercise.name);
+    let path = dir.join(format!("{crate_name}.rs"));
+    fs::write(&path, source.replacen(&mutant.find, &mutant.replace, 1))
+        .with_context(|| format!("cannot write {}", path.display()))?;
+    let caught = match run_tests(exercise, &path, dir, &crate_name)? {
+        Report::Tested(results) => results.iter().any(|result| !result.passed),
+        Report::CompileError(diagnostics) => {
+            bail!(
+                "{name} of {} does not compile:\n{diagnostics}",
+                exercise.name
+            )
+        }
+    };
+    Ok(TestResult {
+        name,
+        passed: caught,
+        message: (!caught).then(|| t!("check-mutant-survived")),
+    })
+}
+
+/// Compiles `source` (the exercise, or a mutated copy of it) with the
+/// exercise's hidden tests and runs the result.
+fn run_tests(exercise: &Exercise, source: &Path, dir: &Path, name: &str) -> Result<Report> {
+    let wrapper = dir.join(format!("{name}_check.rs"));
+    // A binary's tests are compiled on their own, next to it
+    let tests = (!exercise.binary)
+        .then_some(exercise.tests.as_deref())
+        .flatten();
+    fs::write(&wrapper, wrapper_source(source, tests)?)
+        .with_context(|| format!("cannot write {}", wrapper.display()))?;
+    if let Some(manifest) = &exercise.manifest {
+        return run_cargo_tests(exercise, manifest, &wrapper, dir, name);
+    }
+
+    let binary = dir.join(format!("{name}{}", std::env::consts::EXE_SUFFIX));
+    let args = ["--test", "--crate-name", name];
+    if let Err(diagnostics) = compiler::rustc(&wrapper, &binary, &args)? {
+        return Ok(Report::CompileError(diagnostics));
+    }
+
+    let output = Command::new(&binary)
+        .args(["--color", "never", "--test-threads", "1"])
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .with_context(|| format!("failed to run {}", binary.display()))?;
+    Ok(Report::Tested(parse_test_output(&String::from_utf8_lossy(
+        &output.stdout,
+    ))))
+}
+
+/// Builds the wrapper as the library of a package that has the
+/// dependencies from the topic's `manifest`, then runs its tests. For a
+/// binary exercise the wrapper is the package's binary instead, named
+/// after the exercise (mutants too) so that tests can find it with
+/// `CARGO_BIN_EXE_<name>`, and the tests are an integration test.
+fn run_cargo_tests(
+    exercise: &Exercise,
+    manifest: &Path,
+    wrapper: &Path,
+    dir: &Path,
+    name: &str,
+) -> Result<Report> {
+    let package_manifest = write_package(exercise, manifest, wrapper, dir, name)?;
+    let target_dir = dir.join("cargo");
+    let target = if exercise.binary {
+        vec!["--test", &exercise.name]
+    } else {
+        vec!["--lib"]
+    };
+    let cargo = |args: &[&str]| {
+        let mut command = Command::new("cargo");
+        command
+            .arg("test")
+            .arg("--manifest-path")
+            .arg(&package_manifest)
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .args(&target)
+            .args(args);
+        command
+    };
+
+    // Build first, so a compile error isn't mistaken for a test run.
+    let build = cargo(&["--no-run", "--quiet", "--color=always"])
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+    if !build.status.success() {
+        return Ok(Report::CompileError(
+            String::from_utf8_lossy(&build.stderr).into_owned(),
+        ));
+    }
+
+    // proptest would otherwise save each failure it finds next to the
+    // exercise, and replay it on every later check
+    let output = cargo(&["--", "--color", "never", "--test-threads", "1"])
+        .env("RUST_BACKTRACE", "0")
+        .env("PROPTEST_DISABLE_FAILURE_PERSISTENCE", "1")
+        .output()
+        .with_context(|| format!("failed to run the tests of {name}"))?;
+    Ok(Report::Tested(parse_test_output(&String::from_utf8_lossy(
+        &output.stdout,
+    ))))
+}
+
+/// Writes the package that builds `wrapper` with the dependencies from the
+/// topic's `manifest`, and returns the path of its `Cargo.toml`.
+///
+/// There is one package per topic, rewritten for every check, so its
+/// `Cargo.lock` and the compiled dependencies are kept between checks.
+fn write_package(
+    exercise: &Exercise,
+    manifest: &Path,
+    wrapper: &Path,
+    dir: &Path,
+    name: &str,
+) -> Result<PathBuf> {
+    let text = fs::read_to_string(manifest)
+        .with_context(|| format!("cannot read {}", manifest.display()))?;
+    let topic: toml::Table =
+        toml::from_str(&text).with_context(|| format!("{} is not valid", manifest.display()))?;
+    let wrapper_path = toml::Value::from(absolute(wrapper)?);
+    let mut targets = if exercise.binary {
+        format!(
+            "[[bin]]\nname = \"{}\"\npath = {wrapper_path}\n",
+            exercise.name
+        )
+    } else {
+        format!("[lib]\nname = \"{name}\"\npath = {wrapper_path}\n")
+    };
+    if let (true, Some(tests)) = (exercise.binary, &exercise.tests) {
+        targets.push_str(&format!(
+            "\n[[test]]\nname = \"{}\"\npath = {}\n",
+            exercise.name,
+            toml::Value::from(absolute(tests)?)
+        ));
+    }
+    // `[workspace]` keeps the package out of the `rust/` workspace it sits
+    // under.
+    let mut package: toml::Table = toml::from_str(&format!(
+        "[package]\nname = \"{}-check\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+         {targets}\n[workspace]\n",
+        exercise.topic,
+    ))
+    .context("cannot generate a package for the exercise")?;
+    for table in ["dependencies", "dev-dependencies"] {
+        if let Some(dependencies) = topic.get(table) {
+            package.insert(table.to_string(), dependencies.clone());
+        }
+    }
+
+    let package_dir = dir.join(format!("{}-check", exercise.topic));
+    fs::create_dir_all(&package_dir)
+        .with_context(|| format!("cannot create {}", package_dir.display()))?;
+    let package_manifest = package_dir.join("Cargo.toml");
+    fs::write(&package_manifest, package.to_string())
+        .with_context(|| format!("cannot write {}", package_manifest.display()))?;
+
+    Ok(package_manifest)
+}
+
+/// Generates a crate root that pulls in `source` and, if given, the hidden
+/// `tests`.
+fn wrapper_source(source: &Path, tests: Option<&Path>) -> Result<String> {
+    // Half-finished exercises are full of code nothing calls yet.
+    let mut source = format!(
+        "#![allow(dead_code)]\n\ninclude!({:?});\n",
+        absolute(source)?
+    );
+    if let Some(tests) = tests {
+        source.push_str(&format!(
+            "\n#[cfg(test)]\nmod {TEST_MODULE} {{\n    #[allow(unused_imports)]\n    use super::*;\n\n    include!({:?});\n}}\n",
+            absolute(tests)?
+        ));
+    }
+    Ok(source)
+}
+
+fn absolute(path: &Path) -> Result<String> {
+    let path = fs::canonicalize(path).with_context(|| format!("cannot find {}", path.display()))?;
+    path.to_str()
+        .map(str::to_string)
+        .with_context(|| format!("{} is not valid UTF-8", path.display()))
+}
+
+/// Parses libtest's human-readable output.
+///
+/// We rely on two parts of it: the `test <name> ... ok|FAILED` status lines,
+/// and the `---- <name> stdout ----` sections that hold each failure's panic.
+pub fn parse_test_output(stdout: &str) -> Vec<TestResult> {
+    let mut results = Vec::new();
+    for line in stdout.lines() {
+        let Some(rest) = line.strip_prefix("test ") else {
+            continue;
+        };
+        if let Some((name, status)) = rest.rsplit_once(" ... ") {
+            // `#[should_panic]` tests are listed as `name - should panic`.
+            let name = name.strip_suffix(" - should panic").unwrap_or(name);
+            results.push(TestResult {
+                name: display_name(name),
+                passed: status == "ok",
+                message: None,
+            });
+        }
+    }
+
+    let mut current: Option<(String, Vec<&str>)> = None;
+    let mut messages = Vec::new();
+    for line in stdout.lines() {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        {
+            messages.extend(current.take());
+            current = Some((display_name(name), Vec::new()));
+        } else if line == "failures:" || line.starts_with("test result:") {
+            messages.extend(current.take());
+        } else if let Some((_, lines)) = &mut current {
+            let noise = line.starts_with("thread '")
+                || line.starts_with("note: run with `RUST_BACKTRACE")
+                || line.trim().is_empty();
+            if !noise {
+                lines.push(line);
+            }
+        }
+    }
+    messages.extend(current);
+
+    for (name, lines) in messages {
+        if let Some(result) = results.iter_mut().find(|result| result.name == name) {
+            result.message = Some(lines.join("\n"));
+        }
+    }
+    results
+}
+
+fn display_name(name: &str) -> String {
+    name.strip_prefix(TEST_MODULE)
+        .and_then(|rest| rest.strip_prefix("::"))
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Prints a checked exercise's diagnostics or per-test results.
+pub fn print_report(exercise: &Exercise, report: &Report, errors: &[ErrorCode]) {
+    match report {
+        Report::CompileError(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!(
+                "{}",
+                t!("check-compile-error", name = exercise.name.as_str())
+            );
+            errors::suggest(errors, diagnostics);
+        }
+        Report::Tested(results) => {
+            print_results(results);
+            let failing = report.failures().count();
+            if failing == 0 {
+                println!("\n{}", t!("check-done", name = exercise.name.as_str()));
+            } else {
+                println!(
+                    "\n{}",
+                    t!("check-failing", failing = failing, total = results.len())
+                );
+            }
+        }
+    }
+    if !report.passed() && exercise.hints.is_some() {
+        println!("{}", t!("check-stuck", name = exercise.name.as_str()));
+    }
+}
+
+/// Prints one line per test, with the failure message under failing ones.
+pub fn print_results(results: &[TestResult]) {
+    for result in results {
+        let mark = if result.passed { "✅" } else { "❌" };
+        println!("{mark} {}", result.name);
+        if let Some(message) = &result.message {
+            for line in message.lines() {
+                println!("     {line}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What libtest prints for a run with a passing test, a
+    /// `#[should_panic]` test that panicked and one that didn't, an ignored
+    /// test, and a failed assertion.
+    const OUTPUT: &str = "
+running 5 tests
+test hidden_tests::adds ... ok
+test hidden_tests::rejects_empty - should panic ... ok
+test hidden_tests::rounds - should panic ... FAILED
+test hidden_tests::slow ... ignored, takes a minute
+test hidden_tests::subtracts ... FAILED
+
+failures:
+
+---- hidden_tests::rounds stdout ----
+note: test did not panic as expected at src/lib.rs:31:8
+---- hidden_tests::subtracts stdout ----
+
+thread 'hidden_tests::subtracts' panicked at src/lib.rs:10:5:
+assertion `left == right` failed: 3 - 2
+  left: 2
+ right: 1
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    hidden_tests::rounds
+    hidden_tests::subtracts
+
+test result: FAILED. 2 passed; 2 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+    #[test]
+    fn status_lines_give_one_result_per_test() {
+        let results = parse_test_output(OUTPUT);
+        let statuses: Vec<(&str, bool)> = results
+            .iter()
+            .map(|result| (result.name.as_str(), result.passed))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("adds", true),
+                ("rejects_empty", true),
+                ("rounds", false),
+                // An ignored test hasn't passed: the exercise isn't done
+                ("slow", false),
+                ("subtracts", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn each_failure_gets_the_message_from_its_stdout_section() {
+        let results = parse_test_output(OUTPUT);
+        let message = |name: &str| {
+            results
+                .iter()
+                .find(|result| result.name == name)
+                .and_then(|result| result.message.as_deref())
+        };
+        assert_eq!(
+            message("subtracts"),
+            Some("assertion `left == right` failed: 3 - 2\n  left: 2\n right: 1")
+        );
+        assert_eq!(
+            message("rounds"),
+            Some("note: test did not panic as expected at src/lib.rs:31:8")
+        );
+        assert_eq!(message("adds"), None);
+        assert_eq!(message("slow"), None);
+    }
+
+    #[test]
+    fn output_without_tests_gives_no_results() {
+        let output = "\nrunning 0 tests\n\ntest result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n";
+        assert!(parse_test_output(output).is_empty());
+    }
+
+    #[test]
+    fn a_proptest_failure_is_summarized_from_test_failed_on() {
+        let message = "shrinking panic 1\nshrinking panic 2\nTest failed: reverse twice.\nminimal failing input: v = [0, 1]";
+        assert_eq!(
+            failure_summary(message),
+            "Test failed: reverse twice.\nminimal failing input: v = [0, 1]"
+        );
+    }
+}
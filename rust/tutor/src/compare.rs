@@ -0,0 +1,258 @@
+//! `tutor compare`: the Rust, Go, and Java versions of a lesson, side by side.
+//!
+//! The versions are listed in `compare.toml` at the top of the repository.
+//! Every example file follows the same layout (a `//` comment saying what
+//! it demonstrates, the code, then a `/* ... */` block of notes), so each
+//! file is split into those three sections and the sections are lined up
+//! across the columns.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::explain;
+use crate::runner::EXPECTED_MARKER;
+use crate::t;
+
+/// Space between two columns.
+const GUTTER: &str = " │ ";
+const MIN_COLUMN_WIDTH: usize = 20;
+
+/// Everything in `compare.toml`.
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(rename = "topic")]
+    topics: Vec<Topic>,
+}
+
+/// One lesson and where each language's version of it lives.
+#[derive(Debug, Deserialize)]
+pub struct Topic {
+    pub name: String,
+    pub title: String,
+    rust: Option<PathBuf>,
+    go: Option<PathBuf>,
+    java: Option<PathBuf>,
+    /// Passed to every version by `tutor equivalence`.
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub stdin: Option<String>,
+    /// Whether the versions print the same thing; `false` for a lesson
+    /// where each language shows off something of its own.
+    #[serde(default = "same_output")]
+    pub same_output: bool,
+}
+
+fn same_output() -> bool {
+    true
+}
+
+/// One language's column: its heading and its three sections.
+struct Column {
+    heading: String,
+    sections: [Vec<String>; 3],
+}
+
+/// Reads `compare.toml` from the repository that `rust_root` (the `rust/`
+/// directory) belongs to, resolving its paths.
+pub fn load_topics(rust_root: &Path) -> Result<Vec<Topic>> {
+    let repo = rust_root.parent().unwrap_or(rust_root);
+    let path = repo.join("compare.toml");
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let manifest: Manifest =
+        toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+    Ok(manifest
+        .topics
+        .into_iter()
+        .map(|topic| Topic {
+            rust: topic.rust.map(|file| repo.join(file)),
+            go: topic.go.map(|file| repo.join(file)),
+            java: topic.java.map(|file| repo.join(file)),
+            ..topic
+        })
+        .collect())
+}
+
+/// Looks up a topic by name (`variables`) or by the stem of one of its
+/// files (`02_variables`).
+pub fn find<'a>(topics: &'a [Topic], query: &str) -> Option<&'a Topic> {
+    topics.iter().find(|topic| {
+        topic.name == query
+            || topic
+                .files()
+                .iter()
+                .filter_map(|(_, file)| file.as_deref()?.file_stem())
+                .any(|stem| stem == OsStr::new(query))
+    })
+}
+
+impl Topic {
+    /// Each language, with its version of the lesson if there is one.
+    pub fn files(&self) -> [(&'static str, &Option<PathBuf>); 3] {
+        [("Rust", &self.rust), ("Go", &self.go), ("Java", &self.java)]
+    }
+}
+
+/// Prints the topic's versions in columns that fit in `width` characters.
+pub fn print(topic: &Topic, width: usize) -> Result<()> {
+    let mut columns = Vec::new();
+    for (language, file) in topic.files() {
+        columns.push(match file {
+            Some(path) => {
+                let source = fs::read_to_string(path)
+                    .with_context(|| format!("cannot read {}", path.display()))?;
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                Column {
+                    heading: format!("{language}: {name}"),
+                    sections: split(&source),
+                }
+            }
+            None => Column {
+                heading: language.to_string(),
+                sections: [
+                    vec![format!("(no {language} version yet)")],
+                    Vec::new(),
+                    Vec::new(),
+                ],
+            },
+        });
+    }
+    if columns.is_empty() {
+        bail!("{} lists no files", topic.name);
+    }
+
+    let column_width =
+        width.saturating_sub(GUTTER.chars().count() * (columns.len() - 1)) / columns.len();
+    let column_width = column_width.max(MIN_COLUMN_WIDTH);
+    let total_width = column_width * columns.len() + GUTTER.chars().count() * (columns.len() - 1);
+
+    println!("🔀 {}: {}\n", topic.name, topic.title);
+    let headings: Vec<Vec<String>> = columns
+        .iter()
+        .map(|column| vec![column.heading.clone()])
+        .collect();
+    print_rows(&headings, column_width);
+    let sections = [t!("compare-about"), t!("compare-code"), t!("compare-notes")];
+    for (index, title) in sections.iter().enumerate() {
+        let rule = format!("── {title} ");
+        let fill = total_width.saturating_sub(rule.chars().count());
+        println!("{rule}{}", "─".repeat(fill));
+        let cells: Vec<Vec<String>> = columns
+            .iter()
+            .map(|column| wrap(&column.sections[index], column_width))
+            .collect();
+        print_rows(&cells, column_width);
+    }
+    Ok(())
+}
+
+/// Prints the cells of each column next to each other, padding the shorter
+/// columns with blank lines so the next section starts on the same row.
+fn print_rows(columns: &[Vec<String>], width: usize) {
+    let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+    for row in 0..height {
+        let line = columns
+            .iter()
+            .map(|cells| {
+                let cell = cells.get(row).map_or("", String::as_str);
+                format!("{cell}{}", " ".repeat(width - cell.chars().count()))
+            })
+            .collect::<Vec<_>>()
+            .join(GUTTER);
+        println!("{}", line.trim_end());
+    }
+}
+
+/// Breaks lines longer than `width` into several, so no code is cut off.
+/// Continuation lines keep the original line's indentation.
+fn wrap(lines: &[String], width: usize) -> Vec<String> {
+    let mut wrapped = Vec::new();
+    for line in lines {
+        let chars: Vec<char> = line.replace('\t', "    ").chars().collect();
+        if chars.len() <= width {
+            wrapped.push(chars.into_iter().collect());
+            continue;
+        }
+        let indent = chars
+            .iter()
+            .take_while(|c| **c == ' ')
+            .count()
+            .min(width / 2);
+        let (first, rest) = chars.split_at(width);
+        wrapped.push(first.iter().collect());
+        for chunk in rest.chunks(width - indent) {
+            wrapped.push(" ".repeat(indent) + &chunk.iter().collect::<String>());
+        }
+    }
+    wrapped
+}
+
+/// Splits an example into the comment header, the code, and the closing
+/// `/* ... */` notes, stripping the comment markers from the first and last.
+fn split(source: &str) -> [Vec<String>; 3] {
+    let mut lines: Vec<&str> = source
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim_start().starts_with(explain::MARKER))
+        .collect();
+    // An expected-output block isn't part of the lesson itself.
+    if let Some(marker) = lines.iter().position(|line| line.trim() == EXPECTED_MARKER) {
+        lines.truncate(marker);
+    }
+    let header_end = lines
+        .iter()
+        .position(|line| !line.trim_start().starts_with("//"))
+        .unwrap_or(lines.len());
+    let last = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .unwrap_or(0);
+    let notes_start = if lines.get(last).is_some_and(|line| line.ends_with("*/")) {
+        lines[header_end..=last]
+            .iter()
+            .rposition(|line| line.trim_start().starts_with("/*"))
+            .map(|offset| header_end + offset)
+    } else {
+        None
+    };
+    let notes_start = notes_start.unwrap_or(lines.len());
+
+    let about = lines[..header_end]
+        .iter()
+        .map(|line| strip_marker(line, "//"))
+        .collect();
+    let code = trim_blank(&lines[header_end..notes_start])
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+    let notes = lines[notes_start..]
+        .iter()
+        .filter(|line| !matches!(line.trim(), "/*" | "*/"))
+        .map(|line| strip_marker(line, "*"))
+        .collect();
+    [about, code, notes]
+}
+
+/// `// Example 1` becomes `Example 1`; ` * - item` becomes `- item`.
+fn strip_marker(line: &str, marker: &str) -> String {
+    let line = line.trim_start();
+    let line = line.strip_prefix(marker).unwrap_or(line);
+    line.strip_prefix(' ').unwrap_or(line).to_string()
+}
+
+fn trim_blank<'a>(lines: &'a [&'a str]) -> &'a [&'a str] {
+    let start = lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(start, |end| end + 1);
+    &lines[start..end]
+}
@@ -0,0 +1,167 @@
+//! Compiling and running a single example with `rustc`.
+//!
+//! Examples are standalone files, so we invoke `rustc` directly instead of
+//! going through cargo — exactly what a learner would do by hand. The one
+//! exception is a chapter that is its own cargo package (see
+//! [`crate::course`]); those are built with `cargo build`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::course::Example;
+use crate::runner;
+
+/// What happened when we tried to build and run an example.
+#[derive(Debug)]
+pub enum Outcome {
+    /// `rustc` rejected the file; holds the compiler's diagnostics.
+    CompileError(String),
+    /// The program ran; holds its captured output and whether it exited cleanly.
+    Ran {
+        success: bool,
+        stdout: String,
+        stderr: String,
+    },
+    /// The program was killed after running for [`runner::TIMEOUT`].
+    TimedOut { stdout: String, stderr: String },
+    /// The program ran cleanly but didn't print what its `// EXPECTED:`
+    /// block says.
+    WrongOutput {
+        expected: String,
+        stdout: String,
+        stderr: String,
+    },
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        matches!(self, Outcome::Ran { success: true, .. })
+    }
+}
+
+/// Compiles `example` into `build_dir` and returns the path of the binary,
+/// or the compiler's stderr if compilation failed.
+pub fn compile(example: &Example, build_dir: &Path) -> Result<Result<PathBuf, String>> {
+    if let Some(manifest) = &example.manifest {
+        return cargo_build(manifest, example, &[]);
+    }
+    let binary = build_dir.join(format!("{}{}", example.name, std::env::consts::EXE_SUFFIX));
+    Ok(rustc(&example.path, &binary, &[])?.map(|()| binary))
+}
+
+/// Compiles `example` with `--cfg feature="broken"`, which switches on the
+/// deliberately failing code some examples carry so learners can read the
+/// real compiler errors. Returns the diagnostics, or `None` if it compiled.
+pub fn compile_broken(example: &Example, build_dir: &Path) -> Result<Option<String>> {
+    if let Some(manifest) = &example.manifest {
+        // Chapter packages declare a `broken` feature for the same purpose.
+        return Ok(cargo_build(manifest, example, &["--features", "broken"])?.err());
+    }
+    let binary = build_dir.join(format!(
+        "{}-broken{}",
+        example.name,
+        std::env::consts::EXE_SUFFIX
+    ));
+    Ok(rustc(&example.path, &binary, &["--cfg", "feature=\"broken\""])?.err())
+}
+
+/// Runs `rustc` on a single source file, writing the result to `output`.
+///
+/// `extra_args` are passed through unchanged (e.g. `--test`). On failure the
+/// compiler's diagnostics are returned as the inner error.
+pub fn rustc(source: &Path, output: &Path, extra_args: &[&str]) -> Result<Result<(), String>> {
+    if let Some(dir) = output.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+
+    let result = Command::new("rustc")
+        .arg("--edition=2021")
+        .arg("--color=always")
+        .args(extra_args)
+        .arg("-o")
+        .arg(output)
+        .arg(source)
+        .output()
+        .context("failed to launch rustc; is Rust installed and on your PATH?")?;
+
+    if result.status.success() {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(String::from_utf8_lossy(&result.stderr).into_owned()))
+    }
+}
+
+/// One line of `cargo build --message-format=json` output. We only care
+/// about the artifact that carries the built binary.
+#[derive(Deserialize)]
+struct CargoMessage {
+    executable: Option<PathBuf>,
+}
+
+/// Builds the `[[bin]]` named after `example`'s file from the package at
+/// `manifest`, returning the binary's path or the compiler's diagnostics.
+pub(crate) fn cargo_build(
+    manifest: &Path,
+    example: &Example,
+    extra_args: &[&str],
+) -> Result<Result<PathBuf, String>> {
+    let bin = crate::course::file_stem(&example.path)?;
+    let result = Command::new("cargo")
+        .args(["build", "--quiet", "--color=always"])
+        .arg("--message-format=json-render-diagnostics")
+        .arg("--manifest-path")
+        .arg(manifest)
+        .args(["--bin", &bin])
+        .args(extra_args)
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+
+    if !result.status.success() {
+        return Ok(Err(String::from_utf8_lossy(&result.stderr).into_owned()));
+    }
+    String::from_utf8_lossy(&result.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .find_map(|message| message.executable)
+        .map(Ok)
+        .with_context(|| format!("cargo did not report a binary for {}", example.name))
+}
+
+/// Compiles and then runs `example`, capturing everything it prints and
+/// checking it against the example's `// EXPECTED:` block, if it has one.
+pub fn build_and_run(example: &Example, build_dir: &Path) -> Result<Outcome> {
+    let binary = match compile(example, build_dir)? {
+        Ok(binary) => binary,
+        Err(diagnostics) => return Ok(Outcome::CompileError(diagnostics)),
+    };
+
+    let run = runner::run(&mut Command::new(&binary), runner::TIMEOUT)?;
+    let Some(status) = run.status else {
+        return Ok(Outcome::TimedOut {
+            stdout: run.stdout,
+            stderr: run.stderr,
+        });
+    };
+    if status.success() {
+        let source = fs::read_to_string(&example.path)
+            .with_context(|| format!("cannot read {}", example.path.display()))?;
+        if let Some(expected) = runner::expected_output(&source) {
+            if !runner::output_matches(&run.stdout, &expected) {
+                return Ok(Outcome::WrongOutput {
+                    expected,
+                    stdout: run.stdout,
+                    stderr: run.stderr,
+                });
+            }
+        }
+    }
+    Ok(Outcome::Ran {
+        success: status.success(),
+        stdout: run.stdout,
+        stderr: run.stderr,
+    })
+}
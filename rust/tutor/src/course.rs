@@ -0,0 +1,190 @@
+//! Discovery of the examples that make up the course.
+//!
+//! Every `.rs` file under `examples/` is one step of the course. Files are
+//! ordered by their numeric prefix (`01_hello_world.rs` comes before
+//! `02_variables.rs`), so adding a lesson is as simple as dropping in a new
+//! numbered file.
+//!
+//! Larger topics live in their own subdirectory (`examples/lifetimes/`).
+//! Their examples are named `<group>/<file stem>`, e.g.
+//! `lifetimes/01_elision`, and come after the top-level files.
+//!
+//! A group that needs crates from crates.io (`examples/async/`), ships
+//! tests (`examples/smart_pointers/`), or includes a library
+//! (`examples/structs/`) is a cargo package instead: it has its own
+//! `Cargo.toml` declaring one `[[bin]]` per numbered file, and its examples
+//! are built with cargo rather than `rustc`. A package's `build.rs` (as in
+//! `examples/ffi/`) is not an example.
+//!
+//! Every other chapter has a `Cargo.toml` too, so that one `cargo build`
+//! builds the whole course, but marks itself
+//! `[package.metadata.tutor] standalone = true`: its files need nothing
+//! but std, and are still compiled one at a time with `rustc`.
+//!
+//! Prerequisites in `lessons.toml` (see [`crate::manifest`]) can move an
+//! example later than its number alone would put it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::manifest::Manifest;
+
+/// A single runnable example file.
+#[derive(Debug, Clone)]
+pub struct Example {
+    /// File stem, e.g. `02_variables`, prefixed with the group directory
+    /// for grouped examples (`lifetimes/01_elision`). Used as the example's id.
+    pub name: String,
+    /// The subdirectory a grouped example lives in, e.g. `lifetimes`.
+    pub group: Option<String>,
+    /// Absolute path to the source file.
+    pub path: PathBuf,
+    /// The group's `Cargo.toml`, if the example is built as one of the
+    /// package's binaries rather than on its own.
+    pub manifest: Option<PathBuf>,
+}
+
+impl Example {
+    /// The name without its numeric prefix, e.g. `variables`.
+    pub fn short_name(&self) -> &str {
+        let stem = self.name.rsplit('/').next().unwrap_or(&self.name);
+        match stem.split_once('_') {
+            Some((prefix, rest)) if prefix.chars().all(|c| c.is_ascii_digit()) => rest,
+            _ => stem,
+        }
+    }
+
+    /// The chapter this example belongs to: its group, or its own short name.
+    pub fn topic(&self) -> &str {
+        self.group.as_deref().unwrap_or_else(|| self.short_name())
+    }
+}
+
+/// All examples found under a course root, in teaching order.
+#[derive(Debug)]
+pub struct Course {
+    pub root: PathBuf,
+    pub examples: Vec<Example>,
+    /// Titles and prerequisites from `lessons.toml`.
+    pub lessons: Manifest,
+}
+
+impl Course {
+    /// Scans `<root>/examples` for example files and orders them by the
+    /// prerequisites in `<root>/lessons.toml`.
+    pub fn discover(root: &Path) -> Result<Self> {
+        let root = fs::canonicalize(root)
+            .with_context(|| format!("cannot find the course at {}", root.display()))?;
+        let dir = root.join("examples");
+        let mut examples = Vec::new();
+        let mut groups = Vec::new();
+
+        for path in sorted_entries(&dir)? {
+            if path.is_dir() {
+                groups.push(path);
+            } else if is_rust_file(&path) {
+                examples.push(Example {
+                    name: file_stem(&path)?,
+                    group: None,
+                    path,
+                    manifest: None,
+                });
+            }
+        }
+        for group_dir in groups {
+            let group = file_stem(&group_dir)?;
+            let manifest = package_manifest(&group_dir)?;
+            for path in sorted_entries(&group_dir)? {
+                let is_build_script = manifest.is_some() && path.ends_with("build.rs");
+                if is_rust_file(&path) && !is_build_script {
+                    examples.push(Example {
+                        name: format!("{group}/{}", file_stem(&path)?),
+                        group: Some(group.clone()),
+                        path,
+                        manifest: manifest.clone(),
+                    });
+                }
+            }
+        }
+
+        if examples.is_empty() {
+            bail!("no examples found in {}", dir.display());
+        }
+        let lessons = Manifest::load(&root)?;
+        let examples = lessons.sort(examples)?;
+        Ok(Course {
+            root,
+            examples,
+            lessons,
+        })
+    }
+
+    /// Looks up an example by full name (`02_variables`), number (`02` or
+    /// `2`), or short name (`variables`, or `concurrency/loom` in a group).
+    pub fn find(&self, query: &str) -> Option<usize> {
+        let number = query.parse::<u32>().ok();
+        self.examples.iter().position(|example| {
+            example.name == query
+                || example.short_name() == query
+                || example
+                    .group
+                    .as_ref()
+                    .is_some_and(|group| query == format!("{group}/{}", example.short_name()))
+                || (example.group.is_none()
+                    && number.is_some_and(|n| {
+                        example.name.split('_').next() == Some(&format!("{n:02}"))
+                    }))
+        })
+    }
+
+    /// Where compiled example binaries are placed.
+    pub fn build_dir(&self) -> PathBuf {
+        self.root.join("target").join("tutor")
+    }
+}
+
+pub(crate) fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| format!("cannot read {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// The `Cargo.toml` a group's examples are built with, if they need one. A
+/// chapter marked `[package.metadata.tutor] standalone = true` is a package
+/// only so that cargo builds and tests it with the rest of the workspace;
+/// its files are still compiled one at a time with rustc.
+fn package_manifest(dir: &Path) -> Result<Option<PathBuf>> {
+    let path = dir.join("Cargo.toml");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let manifest: toml::Table =
+        toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+    let standalone = manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("tutor"))
+        .and_then(|tutor| tutor.get("standalone"))
+        .and_then(|standalone| standalone.as_bool())
+        .unwrap_or(false);
+    Ok((!standalone).then_some(path))
+}
+
+fn is_rust_file(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == "rs")
+}
+
+/// The file stem (or directory name) as a `String`.
+pub(crate) fn file_stem(path: &Path) -> Result<String> {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(str::to_string)
+        .with_context(|| format!("{} is not a valid UTF-8 name", path.display()))
+}
@@ -0,0 +1,127 @@
+//! `tutor docs`: build a package's API documentation with rustdoc, then
+//! open it in a browser.
+//!
+//! The package is `learn-rust-core` unless the learner names another.
+//! Rustdoc's warnings, a broken intra-doc link most of all, are errors
+//! here, so that `cargo xtask verify` catches docs that point nowhere.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// The package documented when none is named: the library the chapter
+/// packages share.
+pub const DEFAULT_PACKAGE: &str = "learn-rust-core";
+
+/// One line of `cargo doc --message-format=json` output.
+#[derive(Deserialize)]
+struct Message {
+    reason: String,
+    #[serde(default)]
+    target: Option<Target>,
+    #[serde(default)]
+    filenames: Vec<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    kind: Vec<String>,
+}
+
+/// Runs rustdoc on `package` from the workspace at `root`, returning the
+/// front page of its library's docs (of its first binary's, if it has no
+/// library), or what cargo printed if it failed.
+pub fn build(root: &Path, package: &str) -> Result<Result<PathBuf, String>> {
+    let mut flags = env::var("RUSTDOCFLAGS").unwrap_or_default();
+    flags.push_str(" -D warnings");
+    let output = Command::new("cargo")
+        .args([
+            "doc",
+            "--no-deps",
+            "--message-format=json-render-diagnostics",
+        ])
+        .args(["-p", package])
+        .env("RUSTDOCFLAGS", flags.trim())
+        .current_dir(root)
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+    if !output.status.success() {
+        return Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    index_page(&stdout)
+        .map(Ok)
+        .with_context(|| format!("cargo did not say where the docs for {package} went"))
+}
+
+/// The `index.html` among the artifacts in cargo's JSON messages,
+/// preferring a library's.
+fn index_page(messages: &str) -> Option<PathBuf> {
+    let pages: Vec<(bool, PathBuf)> = messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Message>(line).ok())
+        .filter(|message| message.reason == "compiler-artifact")
+        .flat_map(|message| {
+            let lib = message
+                .target
+                .is_some_and(|target| target.kind.iter().any(|kind| kind.contains("lib")));
+            message
+                .filenames
+                .into_iter()
+                .filter(|path| path.ends_with("index.html"))
+                .map(move |path| (lib, path))
+        })
+        .collect();
+    pages
+        .iter()
+        .find(|(lib, _)| *lib)
+        .or(pages.first())
+        .map(|(_, path)| path.clone())
+}
+
+/// Opens `page` with the platform's opener, returning whether it started.
+pub fn open(page: &Path) -> bool {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(page)
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_library_page_comes_before_the_binaries() {
+        let messages = r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"01_slow"},"filenames":["/t/doc/01_slow/index.html"]}
+{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"reports"},"filenames":["/t/doc/reports/index.html"]}
+{"reason":"build-finished","success":true}"#;
+        assert_eq!(
+            index_page(messages),
+            Some(PathBuf::from("/t/doc/reports/index.html"))
+        );
+    }
+
+    #[test]
+    fn a_package_of_binaries_opens_on_the_first() {
+        let messages = r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"01_a"},"filenames":["/t/doc/01_a/index.html"]}
+{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"02_b"},"filenames":["/t/doc/02_b/index.html"]}"#;
+        assert_eq!(
+            index_page(messages),
+            Some(PathBuf::from("/t/doc/01_a/index.html"))
+        );
+        assert_eq!(index_page(""), None);
+    }
+}
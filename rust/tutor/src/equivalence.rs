@@ -0,0 +1,227 @@
+//! `tutor equivalence`: checks that a lesson's Rust, Go, and Java versions
+//! print the same thing, so the side-by-side lessons stay in sync.
+//!
+//! Every topic in `compare.toml` with at least two versions is checked.
+//! Each version runs with the topic's `args` and `stdin`, its output is
+//! normalized the way golden files are (see [`crate::golden`]), and every
+//! version is compared with the first one: Rust when there is a Rust
+//! version. A topic whose versions teach different things, and so print
+//! different things, says `same_output = false` and is skipped.
+//!
+//! The Go version runs with `go run`, the Java version with `java File.java`
+//! (Java 11 or later). A language whose toolchain isn't installed is skipped
+//! rather than failed, so the check still runs on a machine with only Rust.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+
+use crate::compare::Topic;
+use crate::compiler;
+use crate::course::Course;
+use crate::golden;
+use crate::runner;
+
+/// What running one language's version of a topic came to.
+#[derive(Debug, PartialEq, Eq)]
+enum Version {
+    /// It ran cleanly; holds its normalized output.
+    Ran(String),
+    /// It didn't build or didn't run cleanly; says why.
+    Failed(String),
+    /// Its toolchain isn't installed; says which.
+    Skipped(String),
+}
+
+/// Checks every topic in `topics` that has more than one version.
+pub fn check(course: &Course, topics: &[Topic]) -> Result<bool> {
+    let (mut checked, mut failed) = (0, Vec::new());
+    for topic in topics {
+        let files: Vec<(&str, &Path)> = topic
+            .files()
+            .into_iter()
+            .filter_map(|(language, file)| Some((language, file.as_deref()?)))
+            .collect();
+        if files.len() < 2 {
+            continue;
+        }
+        if !topic.same_output {
+            println!(
+                "⏭  {} is not checked: its versions print different things",
+                topic.name
+            );
+            continue;
+        }
+        checked += 1;
+
+        let mut outputs = Vec::new();
+        let mut problems = Vec::new();
+        let mut skipped = Vec::new();
+        for (language, file) in files {
+            match run(course, topic, language, file)? {
+                Version::Ran(output) => outputs.push((language, output)),
+                Version::Failed(problem) => problems.push(format!("{language} {problem}")),
+                Version::Skipped(reason) => skipped.push(format!("{language}: {reason}")),
+            }
+        }
+        if let Some((reference, expected)) = outputs.first() {
+            for (language, output) in &outputs[1..] {
+                if !runner::output_matches(output, expected) {
+                    problems.push(format!(
+                        "{language} prints something other than {reference}:\n{}",
+                        runner::output_diff(expected, output).trim_end()
+                    ));
+                }
+            }
+        }
+
+        let skipped = if skipped.is_empty() {
+            String::new()
+        } else {
+            format!(" (skipped {})", skipped.join(", "))
+        };
+        if !problems.is_empty() {
+            println!("❌ {}{skipped}", topic.name);
+            for problem in &problems {
+                println!("   {problem}");
+            }
+            failed.push(&topic.name);
+        } else if outputs.len() < 2 {
+            println!("⏭  {}: nothing to compare{skipped}", topic.name);
+        } else {
+            let languages: Vec<&str> = outputs.iter().map(|(language, _)| *language).collect();
+            println!(
+                "✅ {}: {} print the same{skipped}",
+                topic.name,
+                languages.join(", ")
+            );
+        }
+    }
+
+    if failed.is_empty() {
+        if checked > 1 {
+            println!("\nAll {checked} topics print the same in every language.");
+        }
+        return Ok(true);
+    }
+    println!("\nThese need attention:");
+    for name in failed {
+        println!("  - {name}");
+    }
+    Ok(false)
+}
+
+/// Builds and runs `language`'s version of `topic`, which is in `file`.
+fn run(course: &Course, topic: &Topic, language: &str, file: &Path) -> Result<Version> {
+    let mut command = match language {
+        "Rust" => {
+            let example = course.examples.iter().find(|example| {
+                example.path == file || example.path.canonicalize().ok() == file.canonicalize().ok()
+            });
+            let Some(example) = example else {
+                return Ok(Version::Failed(format!(
+                    "is {}, which isn't one of the course's examples",
+                    file.display()
+                )));
+            };
+            match compiler::compile(example, &course.build_dir())? {
+                Ok(binary) => Command::new(binary),
+                Err(_) => {
+                    return Ok(Version::Failed(format!(
+                        "does not compile; see `tutor run {}`",
+                        example.name
+                    )))
+                }
+            }
+        }
+        "Go" => {
+            if !is_installed("go", "version") {
+                return Ok(Version::Skipped("`go` not found".to_string()));
+            }
+            let mut command = Command::new("go");
+            command.arg("run").arg(file);
+            command
+        }
+        _ => {
+            if !is_installed("java", "-version") {
+                return Ok(Version::Skipped("`java` not found".to_string()));
+            }
+            let mut command = Command::new("java");
+            command.arg(file);
+            command
+        }
+    };
+    command.args(&topic.args);
+    let run = runner::run_with_input(&mut command, topic.stdin.as_deref(), runner::TIMEOUT)?;
+    Ok(outcome(run, &course.root))
+}
+
+/// The normalized output of `run`, or what went wrong.
+fn outcome(run: runner::Run, root: &Path) -> Version {
+    match run.status {
+        Some(status) if status.success() => Version::Ran(golden::normalize(&run.stdout, root)),
+        Some(status) => {
+            let stderr = run.stderr.trim_end();
+            let detail = stderr.lines().last().unwrap_or("no error output");
+            Version::Failed(format!("exited with {status}: {detail}"))
+        }
+        None => Version::Failed(format!(
+            "was still running after {} seconds",
+            runner::TIMEOUT.as_secs()
+        )),
+    }
+}
+
+fn is_installed(tool: &str, version_flag: &str) -> bool {
+    Command::new(tool)
+        .arg(version_flag)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finished(shell: &str) -> runner::Run {
+        runner::run(Command::new("sh").args(["-c", shell]), runner::TIMEOUT).unwrap()
+    }
+
+    #[test]
+    fn output_is_normalized_before_it_is_compared() {
+        let root = Path::new("/home/learner/course/rust");
+        let crlf = outcome(
+            finished("printf 'Hello\\r\\nread /home/learner/course/rust/x  \\r\\n\\n'"),
+            root,
+        );
+        let lf = outcome(finished("printf 'Hello\\nread <root>/x\\n'"), root);
+        let (Version::Ran(crlf), Version::Ran(lf)) = (crlf, lf) else {
+            panic!("both should have run");
+        };
+        assert!(runner::output_matches(&crlf, &lf));
+    }
+
+    #[test]
+    fn a_failing_version_says_why() {
+        assert_eq!(
+            outcome(
+                finished("echo ok; echo 'bad input' >&2; exit 3"),
+                Path::new("/")
+            ),
+            Version::Failed("exited with exit status: 3: bad input".to_string())
+        );
+        let input = runner::run_with_input(
+            Command::new("sh").args(["-c", "read name; echo \"Hi, $name\""]),
+            Some("Ferris\n"),
+            runner::TIMEOUT,
+        )
+        .unwrap();
+        assert_eq!(
+            outcome(input, Path::new("/")),
+            Version::Ran("Hi, Ferris\n".to_string())
+        );
+    }
+}
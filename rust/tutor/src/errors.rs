@@ -0,0 +1,144 @@
+//! `tutor errors`: what the common compiler errors mean, and which lesson
+//! teaches the idea behind them.
+//!
+//! The entries live in `rust/errors.toml`. Besides the command itself, the
+//! exercise checker uses them: when an exercise doesn't compile, every code
+//! in its diagnostics that has an entry is suggested to the learner.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::course::Course;
+use crate::t;
+
+/// One entry in `errors.toml`.
+#[derive(Debug, Deserialize)]
+pub struct ErrorCode {
+    /// The code as rustc prints it, e.g. `E0502`.
+    pub code: String,
+    pub title: String,
+    /// The example that teaches the underlying idea.
+    pub lesson: String,
+    explanation: String,
+    /// The smallest program that causes the error.
+    example: String,
+    fix: String,
+    /// `example`, fixed.
+    fixed: String,
+}
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(default, rename = "error")]
+    errors: Vec<ErrorCode>,
+}
+
+/// Reads `<root>/errors.toml`. A missing file just means no entries.
+pub fn load(root: &Path) -> Result<Vec<ErrorCode>> {
+    let path = root.join("errors.toml");
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let file: File =
+        toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+    Ok(file.errors)
+}
+
+/// Looks up a code, written as rustc prints it (`E0502`) or just by its
+/// number (`0502`, `502`).
+pub fn find<'a>(errors: &'a [ErrorCode], query: &str) -> Option<&'a ErrorCode> {
+    let digits = query.trim().trim_start_matches(['E', 'e']);
+    let number = digits.parse::<u32>().ok()?;
+    let code = format!("E{number:04}");
+    errors.iter().find(|error| error.code == code)
+}
+
+pub fn print(course: &Course, error: &ErrorCode) {
+    println!("📚 {}: {}\n", error.code, error.title);
+    println!("{}\n", error.explanation.trim());
+    println!("{}\n", t!("errors-example"));
+    print_code(&error.example);
+    println!("\n🔧 {}\n", error.fix);
+    print_code(&error.fixed);
+    match course
+        .find(&error.lesson)
+        .map(|index| &course.examples[index])
+    {
+        Some(example) => {
+            let title = course
+                .lessons
+                .get(&example.name)
+                .map_or(example.name.as_str(), |lesson| lesson.title.as_str());
+            println!(
+                "\n{}",
+                t!("errors-lesson", name = example.name.as_str(), title = title)
+            );
+        }
+        None => println!(
+            "\n{}",
+            t!("errors-see-lesson", lesson = error.lesson.as_str())
+        ),
+    }
+    println!(
+        "   {}",
+        t!("errors-rustc-explain", code = error.code.as_str())
+    );
+}
+
+/// Points the learner to the entries for the error codes in `diagnostics`.
+pub fn suggest(errors: &[ErrorCode], diagnostics: &str) {
+    let found = found_in(errors, diagnostics);
+    if found.is_empty() {
+        return;
+    }
+    println!("\n{}", t!("errors-suggest"));
+    for error in found {
+        println!("   {}", suggestion(error));
+    }
+}
+
+/// One line of [`suggest`]: the error, its title, and where it's taught.
+pub fn suggestion(error: &ErrorCode) -> String {
+    t!(
+        "errors-suggestion",
+        code = error.code.as_str(),
+        title = error.title.as_str(),
+        lesson = error.lesson.as_str()
+    )
+}
+
+/// The entries for the error codes in `diagnostics`, in order of appearance.
+pub fn found_in<'a>(errors: &'a [ErrorCode], diagnostics: &str) -> Vec<&'a ErrorCode> {
+    codes_in(diagnostics)
+        .iter()
+        .filter_map(|code| find(errors, code))
+        .collect()
+}
+
+/// The distinct `[E....]` codes in compiler output, in order of appearance.
+fn codes_in(diagnostics: &str) -> Vec<String> {
+    let mut codes: Vec<String> = Vec::new();
+    for (at, _) in diagnostics.match_indices("[E") {
+        let rest = &diagnostics[at + 1..];
+        let code = rest.get(..5).filter(|code| {
+            code[1..].chars().all(|c| c.is_ascii_digit()) && rest[5..].starts_with(']')
+        });
+        if let Some(code) = code {
+            if !codes.iter().any(|known| known == code) {
+                codes.push(code.to_string());
+            }
+        }
+    }
+    codes
+}
+
+fn print_code(code: &str) {
+    for line in code.trim_matches('\n').lines() {
+        println!("    {line}");
+    }
+}
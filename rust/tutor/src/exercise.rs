@@ -0,0 +1,229 @@
+//! Discovery of the exercises under `rust/exercises/`.
+//!
+//! Exercises are grouped into numbered topic directories:
+//!
+//! ```text
+//! exercises/
+//! ├── 01_variables/
+//! │   ├── info.toml            <- metadata (hints, fixes, ...) for each exercise
+//! │   ├── variables1.rs        <- the file the learner edits
+//! │   └── tests/variables1.rs  <- hidden tests the checker compiles in
+//! └── 09_testing/
+//!     └── tests/mutants.toml   <- hidden bugs the learner's own tests must catch
+//! ```
+//!
+//! A translation of `info.toml` (`info.es.toml`, see [`crate::i18n`]) holds
+//! only hints; an exercise it leaves out keeps its English ones.
+//!
+//! A topic whose exercises need crates from crates.io (`11_serde/`) also has
+//! a `Cargo.toml`. Its `[dependencies]` are what the checker builds those
+//! exercises with. An exercise it lists as a `[[bin]]` is a whole program,
+//! and its tests run the program instead of calling its functions.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::course::{file_stem, sorted_entries};
+use crate::i18n;
+
+/// One exercise file and its hidden tests.
+#[derive(Debug, Clone)]
+pub struct Exercise {
+    /// File stem, e.g. `variables1`. Used as the exercise's id.
+    pub name: String,
+    /// Topic the exercise belongs to, e.g. `variables`.
+    pub topic: String,
+    /// The file the learner edits.
+    pub path: PathBuf,
+    /// Tests compiled alongside the exercise, if the exercise has any.
+    pub tests: Option<PathBuf>,
+    /// The topic's `Cargo.toml`, if its exercises need dependencies.
+    pub manifest: Option<PathBuf>,
+    /// Whether the exercise is a `[[bin]]` of the topic's `Cargo.toml`.
+    pub binary: bool,
+    /// Hints from the topic's `info.toml`, if the exercise has any.
+    pub hints: Option<Hints>,
+    /// Bugs the learner's own tests must catch, for exercises where the
+    /// learner writes the tests.
+    pub mutants: Vec<Mutant>,
+    /// For exercises whose code has a bug on purpose: the change that
+    /// fixes it. The learner's tests must pass on the fixed code and fail
+    /// on the code as given.
+    pub fix: Option<Fix>,
+}
+
+/// Progressively more revealing help for one exercise.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Hints {
+    /// A gentle push in the right direction.
+    pub nudge: String,
+    /// What is going on and why.
+    pub explanation: String,
+    /// The exact change, as a diff.
+    pub solution: String,
+}
+
+impl Hints {
+    /// The hint levels in the order they are revealed.
+    pub fn levels(&self) -> [(&'static str, &str); 3] {
+        [
+            ("Nudge", &self.nudge),
+            ("Explanation", &self.explanation),
+            ("Solution", &self.solution),
+        ]
+    }
+}
+
+/// A deliberate bug: replacing `find` with `replace` in the exercise must
+/// make at least one of the learner's tests fail.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Mutant {
+    /// What the bug does, the only part of it the learner is shown.
+    pub description: String,
+    pub find: String,
+    pub replace: String,
+}
+
+/// The fix for a deliberate bug: replacing `find` with `replace` in the
+/// exercise makes it correct. It has no description, because naming the
+/// bug is the learner's job.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fix {
+    pub find: String,
+    pub replace: String,
+}
+
+/// One entry in a topic's `info.toml`.
+#[derive(Debug, Deserialize)]
+struct Info {
+    hints: Option<Hints>,
+    fix: Option<Fix>,
+}
+
+/// One exercise's entry in a translated `info.toml`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TranslatedInfo {
+    hints: Hints,
+}
+
+/// Finds every exercise under `<root>/exercises`, in teaching order.
+///
+/// A missing `exercises/` directory simply means there are no exercises.
+pub fn discover(root: &Path) -> Result<Vec<Exercise>> {
+    let dir = root.join("exercises");
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut topics = sorted_entries(&dir)?;
+    topics.retain(|path| path.is_dir());
+
+    let mut exercises = Vec::new();
+    for topic_dir in topics {
+        let dir_name = file_stem(&topic_dir)?;
+        let topic = match dir_name.split_once('_') {
+            Some((prefix, rest)) if prefix.chars().all(|c| c.is_ascii_digit()) => rest,
+            _ => dir_name.as_str(),
+        }
+        .to_string();
+        let mut info = load_info(&topic_dir)?;
+        let mut mutants = load_mutants(&topic_dir)?;
+        let manifest = topic_dir.join("Cargo.toml");
+        let manifest = manifest.is_file().then_some(manifest);
+        let binaries = match &manifest {
+            Some(manifest) => binary_names(manifest)?,
+            None => Vec::new(),
+        };
+
+        for path in sorted_entries(&topic_dir)? {
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                let name = file_stem(&path)?;
+                let tests = topic_dir.join("tests").join(format!("{name}.rs"));
+                let (hints, fix) = match info.remove(&name) {
+                    Some(info) => (info.hints, info.fix),
+                    None => (None, None),
+                };
+                let mutants = mutants.remove(&name).unwrap_or_default();
+                exercises.push(Exercise {
+                    topic: topic.clone(),
+                    path,
+                    tests: tests.is_file().then_some(tests),
+                    manifest: manifest.clone(),
+                    binary: binaries.contains(&name),
+                    hints,
+                    mutants,
+                    fix,
+                    name,
+                });
+            }
+        }
+    }
+    Ok(exercises)
+}
+
+/// Looks up an exercise by name.
+pub fn find<'a>(exercises: &'a [Exercise], name: &str) -> Option<&'a Exercise> {
+    exercises.iter().find(|exercise| exercise.name == name)
+}
+
+/// The names of the `[[bin]]` targets in a topic's `Cargo.toml`.
+fn binary_names(manifest: &Path) -> Result<Vec<String>> {
+    let text = fs::read_to_string(manifest)
+        .with_context(|| format!("cannot read {}", manifest.display()))?;
+    let manifest: toml::Table =
+        toml::from_str(&text).with_context(|| format!("{} is not valid", manifest.display()))?;
+    let Some(bins) = manifest.get("bin").and_then(toml::Value::as_array) else {
+        return Ok(Vec::new());
+    };
+    Ok(bins
+        .iter()
+        .filter_map(|bin| bin.get("name")?.as_str())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Reads `<topic_dir>/info.toml`, which is optional, with the hints from
+/// its translation into the current language.
+fn load_info(topic_dir: &Path) -> Result<BTreeMap<String, Info>> {
+    let path = topic_dir.join("info.toml");
+    if !path.is_file() {
+        return Ok(BTreeMap::new());
+    }
+    let mut info: BTreeMap<String, Info> = read_toml(&path)?;
+    if let Some(translation) = i18n::current().localized(&path) {
+        let hints: BTreeMap<String, TranslatedInfo> = read_toml(&translation)?;
+        for (name, translated) in hints {
+            let Some(info) = info.get_mut(&name) else {
+                bail!(
+                    "{} has hints for `{name}`, which isn't in info.toml",
+                    translation.display()
+                );
+            };
+            info.hints = Some(translated.hints);
+        }
+    }
+    Ok(info)
+}
+
+/// Reads `<topic_dir>/tests/mutants.toml`, which is optional. It sits
+/// with the hidden tests rather than in `info.toml`, out of the learner's
+/// way, so that their tests aim at the behavior and not at the list.
+fn load_mutants(topic_dir: &Path) -> Result<BTreeMap<String, Vec<Mutant>>> {
+    let path = topic_dir.join("tests").join("mutants.toml");
+    if !path.is_file() {
+        return Ok(BTreeMap::new());
+    }
+    read_toml(&path)
+}
+
+fn read_toml<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))
+}
@@ -0,0 +1,241 @@
+//! `tutor explain`: an example's source with explanations woven in.
+//!
+//! Explanations are written at the end of an example, before its
+//! `// EXPECTED:` block, as `//#` comments. Each block starts with a
+//! backquoted piece of code and explains the first line containing it:
+//!
+//! ```text
+//! //# `let x = 5;`
+//! //# `let` binds the value 5 to the name `x`. Without `mut` the binding
+//! //# is immutable.
+//! //#
+//! //# `let mut y = 10;`
+//! //# ...
+//! ```
+//!
+//! Blocks are in the order of the code: each one is searched for from the
+//! line the previous one explained onwards. When explaining, the blocks are shown
+//! right below the line they describe; everywhere else (`compare`, the
+//! book) they are left out with [`strip`].
+
+use std::io::IsTerminal;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::course::Example;
+use crate::runner::EXPECTED_MARKER;
+use crate::t;
+
+/// Starts every line of an explanation block.
+pub const MARKER: &str = "//#";
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// ANSI colors for each kind of token.
+const KEYWORD: &str = "\x1b[35m";
+const STRING: &str = "\x1b[32m";
+const COMMENT: &str = "\x1b[90m";
+const NUMBER: &str = "\x1b[36m";
+const MACRO: &str = "\x1b[34m";
+const TYPE: &str = "\x1b[33m";
+const NOTE: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// One explanation block.
+#[derive(Debug)]
+struct Note {
+    /// The code it explains.
+    anchor: String,
+    text: Vec<String>,
+}
+
+/// `source` without its explanation blocks.
+pub fn strip(source: &str) -> String {
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with(MARKER))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+/// Prints `example` with its explanations. Colors are used when printing
+/// to a terminal, unless `NO_COLOR` is set.
+pub fn print(example: &Example) -> Result<()> {
+    let source = std::fs::read_to_string(&example.path)
+        .with_context(|| format!("cannot read {}", example.path.display()))?;
+    let notes = notes(&source, &example.path)?;
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+
+    let mut lines: Vec<&str> = source
+        .lines()
+        .take_while(|line| line.trim() != EXPECTED_MARKER)
+        .filter(|line| !line.trim_start().starts_with(MARKER))
+        .collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    // Where each note goes: after the first matching line at or after the
+    // previous note's line.
+    let mut placed = Vec::with_capacity(notes.len());
+    let mut from = 0;
+    for note in &notes {
+        let Some(offset) = lines[from..]
+            .iter()
+            .position(|line| line.contains(note.anchor.as_str()))
+        else {
+            bail!(
+                "{} explains `{}`, but no line after the previous explanation contains it",
+                example.path.display(),
+                note.anchor
+            );
+        };
+        from += offset;
+        placed.push(from);
+    }
+
+    if notes.is_empty() {
+        println!("{}\n", t!("explain-no-notes", name = example.name.as_str()));
+    } else {
+        println!("{}\n", t!("explain-title", name = example.name.as_str()));
+    }
+    let width = lines.len().to_string().len();
+    let mut in_comment = false;
+    for (index, line) in lines.iter().enumerate() {
+        let code = if color {
+            highlight(line, &mut in_comment)
+        } else {
+            line.to_string()
+        };
+        println!("{:>width$} │ {code}", index + 1);
+        for note in placed
+            .iter()
+            .zip(&notes)
+            .filter(|(at, _)| **at == index)
+            .map(|(_, note)| note)
+        {
+            let (bold, reset) = if color { (NOTE, RESET) } else { ("", "") };
+            println!("{:>width$} │", "");
+            for (number, text) in note.text.iter().enumerate() {
+                let bullet = if number == 0 { "💬" } else { "  " };
+                println!("{:>width$} │   {bullet} {bold}{text}{reset}", "");
+            }
+            println!("{:>width$} │", "");
+        }
+    }
+    Ok(())
+}
+
+/// The explanation blocks in `source`, in order.
+fn notes(source: &str, path: &Path) -> Result<Vec<Note>> {
+    let mut notes: Vec<Note> = Vec::new();
+    let mut in_block = false;
+    for line in source.lines() {
+        let Some(text) = line.trim_start().strip_prefix(MARKER) else {
+            in_block = false;
+            continue;
+        };
+        let text = text.strip_prefix(' ').unwrap_or(text).trim_end();
+        if text.is_empty() {
+            in_block = false;
+        } else if in_block {
+            if let Some(note) = notes.last_mut() {
+                note.text.push(text.to_string());
+            }
+        } else {
+            let Some(anchor) = text
+                .strip_prefix('`')
+                .and_then(|rest| rest.strip_suffix('`'))
+            else {
+                bail!(
+                    "{}: an explanation must start with the code it explains, in backquotes: {line}",
+                    path.display()
+                );
+            };
+            notes.push(Note {
+                anchor: anchor.to_string(),
+                text: Vec::new(),
+            });
+            in_block = true;
+        }
+    }
+    Ok(notes)
+}
+
+/// Colors one line of Rust. `in_comment` carries an open `/* ... */`
+/// comment over to the next line.
+pub(crate) fn highlight(line: &str, in_comment: &mut bool) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    let paint = |out: &mut String, color: &str, text: &[char]| {
+        out.push_str(color);
+        out.extend(text);
+        out.push_str(RESET);
+    };
+    while i < chars.len() {
+        let rest = &chars[i..];
+        if *in_comment {
+            let end = (i..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .map_or(chars.len(), |j| j + 2);
+            *in_comment = end == chars.len() && !line.ends_with("*/");
+            paint(&mut out, COMMENT, &chars[i..end]);
+            i = end;
+        } else if rest.starts_with(&['/', '/']) {
+            paint(&mut out, COMMENT, rest);
+            break;
+        } else if rest.starts_with(&['/', '*']) {
+            *in_comment = true;
+            paint(&mut out, COMMENT, &chars[i..i + 2]);
+            i += 2;
+        } else if chars[i] == '"' {
+            let mut end = i + 1;
+            while end < chars.len() && chars[end] != '"' {
+                end += if chars[end] == '\\' { 2 } else { 1 };
+            }
+            let end = (end + 1).min(chars.len());
+            paint(&mut out, STRING, &chars[i..end]);
+            i = end;
+        } else if chars[i] == '\'' && rest.get(2) == Some(&'\'') {
+            paint(&mut out, STRING, &chars[i..i + 3]);
+            i += 3;
+        } else if chars[i].is_ascii_digit() {
+            let end = (i..chars.len())
+                .find(|&j| {
+                    !(chars[j].is_ascii_alphanumeric() || chars[j] == '_' || chars[j] == '.')
+                })
+                .unwrap_or(chars.len());
+            paint(&mut out, NUMBER, &chars[i..end]);
+            i = end;
+        } else if chars[i].is_alphabetic() || chars[i] == '_' {
+            let end = (i..chars.len())
+                .find(|&j| !(chars[j].is_alphanumeric() || chars[j] == '_'))
+                .unwrap_or(chars.len());
+            let word: String = chars[i..end].iter().collect();
+            if chars.get(end) == Some(&'!') {
+                paint(&mut out, MACRO, &chars[i..=end]);
+                i = end + 1;
+                continue;
+            }
+            if KEYWORDS.contains(&word.as_str()) {
+                paint(&mut out, KEYWORD, &chars[i..end]);
+            } else if word.starts_with(char::is_uppercase) {
+                paint(&mut out, TYPE, &chars[i..end]);
+            } else {
+                out.push_str(&word);
+            }
+            i = end;
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    out
+}
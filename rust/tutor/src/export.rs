@@ -0,0 +1,438 @@
+//! `tutor export`: the course and the learner's progress in one zip file,
+//! to hand in to a teacher or to carry on with on another machine, where
+//! `tutor import` reads it back.
+//!
+//! ```text
+//! learn-rust.zip
+//! ├── bundle.json        <- what this is: when it was made, how much is done
+//! ├── progress.json      <- the learner's progress file, as it was
+//! ├── work/exercises/    <- every exercise file as the learner left it
+//! ├── golden/            <- the recorded output of every example
+//! ├── quizzes/           <- the quiz banks
+//! └── site/index.html    <- the `tutor serve` pages, as files, for any browser
+//! ```
+//!
+//! The site needs nothing but the zip: unpack it and open
+//! `site/index.html`. Importing merges the bundle's progress into this
+//! machine's (see [`Progress::merge`]) and puts the exercise files back,
+//! after saving any local file it replaces.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::course::{sorted_entries, Course};
+use crate::exercise::Exercise;
+use crate::progress::{self, Kind, Progress};
+use crate::{serve, state};
+
+/// The version of the bundle layout. A tutor refuses bundles from a newer
+/// one, whose files it might misread.
+pub const FORMAT: u32 = 1;
+
+/// The contents of `bundle.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Info {
+    pub format: u32,
+    /// Seconds since the Unix epoch.
+    pub exported_at: u64,
+    pub tutor_version: String,
+    /// The example the learner was on.
+    pub current: Option<String>,
+    pub examples_done: usize,
+    pub examples: usize,
+    pub exercises_done: usize,
+    pub exercises: usize,
+}
+
+/// What `tutor import` takes from a bundle.
+#[derive(Debug)]
+pub struct Bundle {
+    pub info: Info,
+    pub progress: Progress,
+    /// Exercise files, by their path relative to the course root.
+    pub work: BTreeMap<PathBuf, String>,
+}
+
+/// What importing a bundle changed.
+#[derive(Debug, Default)]
+pub struct Imported {
+    /// Examples and exercises done after the import that weren't before.
+    pub newly_done: usize,
+    /// Exercise files replaced with the bundle's, and where the local
+    /// version of each was saved.
+    pub replaced: Vec<(PathBuf, PathBuf)>,
+    /// Where this machine's progress was saved first, if it had any.
+    pub progress_backup: Option<PathBuf>,
+}
+
+/// Writes the bundle for `course`, `exercises`, and `progress` to `output`.
+pub fn export(
+    course: Course,
+    exercises: Vec<Exercise>,
+    progress: &Progress,
+    output: &Path,
+) -> Result<Info> {
+    let info = Info {
+        format: FORMAT,
+        exported_at: progress::now(),
+        tutor_version: env!("CARGO_PKG_VERSION").to_string(),
+        current: state::load_current()?,
+        examples_done: course
+            .examples
+            .iter()
+            .filter(|example| progress.is_completed(Kind::Example, &example.name))
+            .count(),
+        examples: course.examples.len(),
+        exercises_done: exercises
+            .iter()
+            .filter(|exercise| progress.is_completed(Kind::Exercise, &exercise.name))
+            .count(),
+        exercises: exercises.len(),
+    };
+    if let Some(dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+    let file =
+        File::create(output).with_context(|| format!("cannot create {}", output.display()))?;
+    let mut bundle = Writer::new(file);
+
+    bundle.add(
+        "bundle.json",
+        &(serde_json::to_string_pretty(&info)? + "\n"),
+    )?;
+    let progress_json = serde_json::to_string_pretty(progress)? + "\n";
+    bundle.add("progress.json", &progress_json)?;
+    for exercise in &exercises {
+        let relative = exercise
+            .path
+            .strip_prefix(&course.root)
+            .unwrap_or(&exercise.path);
+        bundle.add_file(&format!("work/{}", slashed(relative)), &exercise.path)?;
+    }
+    bundle.add_dir(&course.root.join("golden"), "golden")?;
+    bundle.add_dir(&course.root.join("quizzes"), "quizzes")?;
+    for (path, html) in serve::files(course, exercises)? {
+        bundle.add(&format!("site/{path}"), &html)?;
+    }
+    bundle.add("site/progress.json", &progress_json)?;
+
+    bundle
+        .zip
+        .finish()
+        .with_context(|| format!("cannot finish {}", output.display()))?;
+    Ok(info)
+}
+
+/// Reads the parts of the bundle at `path` that `tutor import` restores.
+pub fn read(path: &Path) -> Result<Bundle> {
+    let file = File::open(path).with_context(|| format!("cannot open {}", path.display()))?;
+    read_from(file)
+        .with_context(|| format!("{} is not a bundle from `tutor export`", path.display()))
+}
+
+fn read_from(reader: impl Read + Seek) -> Result<Bundle> {
+    let mut archive = ZipArchive::new(reader)?;
+    let info: Info = serde_json::from_str(&entry(&mut archive, "bundle.json")?)?;
+    if info.format > FORMAT {
+        bail!(
+            "it was made by a newer tutor (bundle format {}, this one reads {FORMAT})",
+            info.format
+        );
+    }
+    // The bundle may come from an older tutor, with an older progress file
+    let progress = Progress::from_json(&entry(&mut archive, "progress.json")?)
+        .context("its progress.json is not a valid progress file")?;
+    let mut work = BTreeMap::new();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        // enclosed_name() refuses `..` and absolute paths, which could
+        // otherwise write anywhere
+        let Some(relative) = file
+            .enclosed_name()
+            .and_then(|name| name.strip_prefix("work").ok().map(Path::to_path_buf))
+        else {
+            continue;
+        };
+        if file.is_dir() {
+            continue;
+        }
+        let mut text = String::new();
+        file.read_to_string(&mut text)
+            .with_context(|| format!("cannot read {}", file.name()))?;
+        work.insert(relative, text);
+    }
+    Ok(Bundle {
+        info,
+        progress,
+        work,
+    })
+}
+
+fn entry(archive: &mut ZipArchive<impl Read + Seek>, name: &str) -> Result<String> {
+    let mut file = archive
+        .by_name(name)
+        .with_context(|| format!("it has no {name}"))?;
+    let mut text = String::new();
+    file.read_to_string(&mut text)
+        .with_context(|| format!("cannot read {name}"))?;
+    Ok(text)
+}
+
+/// Merges the bundle's progress into `progress` and, unless
+/// `progress_only`, writes its exercise files over the local ones. Only
+/// files of exercises this course has are written; each local file that
+/// differs is saved in `backup_dir` first. Every import saves its own
+/// copies, so a later one never overwrites them.
+pub fn import(
+    course: &Course,
+    exercises: &[Exercise],
+    bundle: Bundle,
+    progress_only: bool,
+    progress: &mut Progress,
+    backup_dir: &Path,
+) -> Result<Imported> {
+    let mut imported = Imported::default();
+
+    let path = Progress::path()?;
+    if path.is_file() {
+        let backup = unused_path(path.with_extension("json.before-import"));
+        fs::copy(&path, &backup)
+            .with_context(|| format!("cannot save your progress to {}", backup.display()))?;
+        imported.progress_backup = Some(backup);
+    }
+    let done = |progress: &Progress| {
+        let examples = course
+            .examples
+            .iter()
+            .filter(|example| progress.is_completed(Kind::Example, &example.name));
+        let exercises = exercises
+            .iter()
+            .filter(|exercise| progress.is_completed(Kind::Exercise, &exercise.name));
+        examples.count() + exercises.count()
+    };
+    let before = done(progress);
+    progress.merge(bundle.progress);
+    imported.newly_done = done(progress) - before;
+
+    if let Some(current) = &bundle.info.current {
+        if course.find(current).is_some() {
+            state::save_current(current)?;
+        }
+    }
+
+    if progress_only {
+        return Ok(imported);
+    }
+    for exercise in exercises {
+        let relative = exercise
+            .path
+            .strip_prefix(&course.root)
+            .unwrap_or(&exercise.path);
+        let Some(theirs) = bundle.work.get(relative) else {
+            continue;
+        };
+        let mine = fs::read_to_string(&exercise.path).unwrap_or_default();
+        if mine == *theirs {
+            continue;
+        }
+        fs::create_dir_all(backup_dir)
+            .with_context(|| format!("cannot create {}", backup_dir.display()))?;
+        let backup = unused_path(backup_dir.join(format!("{}.rs", exercise.name)));
+        fs::write(&backup, &mine)
+            .with_context(|| format!("cannot save your attempt to {}", backup.display()))?;
+        fs::write(&exercise.path, theirs)
+            .with_context(|| format!("cannot write {}", exercise.path.display()))?;
+        imported.replaced.push((relative.to_path_buf(), backup));
+    }
+    Ok(imported)
+}
+
+/// `path` if nothing is there yet, or else the first of `<stem>.2.<ext>`,
+/// `<stem>.3.<ext>`, ... that is free: `progress.json.before-import`
+/// becomes `progress.json.2.before-import`.
+fn unused_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+    let name =
+        |part: Option<&std::ffi::OsStr>| part.map(|part| part.to_string_lossy().into_owned());
+    let stem = name(path.file_stem()).unwrap_or_default();
+    let extension = name(path.extension());
+    (2..)
+        .map(|copy| {
+            path.with_file_name(match &extension {
+                Some(extension) => format!("{stem}.{copy}.{extension}"),
+                None => format!("{stem}.{copy}"),
+            })
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("some copy number is free")
+}
+
+/// A zip file being written, with every entry compressed.
+struct Writer<W: Write + Seek> {
+    zip: ZipWriter<W>,
+    options: SimpleFileOptions,
+}
+
+impl<W: Write + Seek> Writer<W> {
+    fn new(inner: W) -> Self {
+        Writer {
+            zip: ZipWriter::new(inner),
+            options: SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+        }
+    }
+
+    fn add(&mut self, name: &str, text: &str) -> Result<()> {
+        self.zip
+            .start_file(name, self.options)
+            .with_context(|| format!("cannot add {name}"))?;
+        self.zip
+            .write_all(text.as_bytes())
+            .with_context(|| format!("cannot add {name}"))
+    }
+
+    fn add_file(&mut self, name: &str, path: &Path) -> Result<()> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+        self.add(name, &text)
+    }
+
+    /// Adds every file under `dir`, named `prefix/<path under dir>`.
+    fn add_dir(&mut self, dir: &Path, prefix: &str) -> Result<()> {
+        if !dir.is_dir() {
+            return Ok(());
+        }
+        for path in sorted_entries(dir)? {
+            let name = format!(
+                "{prefix}/{}",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            );
+            if path.is_dir() {
+                self.add_dir(&path, &name)?;
+            } else {
+                self.add_file(&name, &path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `path` with forward slashes, as zip entry names have them everywhere.
+fn slashed(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn info() -> Info {
+        Info {
+            format: FORMAT,
+            exported_at: 1_700_000_000,
+            tutor_version: "0.1.0".to_string(),
+            current: Some("02_variables".to_string()),
+            examples_done: 1,
+            examples: 2,
+            exercises_done: 0,
+            exercises: 1,
+        }
+    }
+
+    fn bundle(info: &Info, extra: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+        let mut writer = Writer::new(Cursor::new(Vec::new()));
+        writer
+            .add("bundle.json", &serde_json::to_string(info).unwrap())
+            .unwrap();
+        writer.add("progress.json", "{}").unwrap();
+        for (name, text) in extra {
+            writer.add(name, text).unwrap();
+        }
+        let mut cursor = writer.zip.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn work_files_come_back_by_their_path() {
+        let contents = bundle(
+            &info(),
+            &[
+                (
+                    "work/exercises/01_variables/variables1.rs",
+                    "fn main() {}\n",
+                ),
+                ("site/index.html", "<html></html>"),
+            ],
+        );
+        let read = read_from(contents).unwrap();
+        assert_eq!(read.info, info());
+        assert_eq!(
+            read.work,
+            BTreeMap::from([(
+                PathBuf::from("exercises/01_variables/variables1.rs"),
+                "fn main() {}\n".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn paths_out_of_the_course_are_ignored() {
+        let contents = bundle(&info(), &[("work/../../.bashrc", "rm -rf ~\n")]);
+        assert!(read_from(contents).unwrap().work.is_empty());
+    }
+
+    #[test]
+    fn a_newer_format_is_refused() {
+        let newer = Info {
+            format: FORMAT + 1,
+            ..info()
+        };
+        let error = read_from(bundle(&newer, &[])).unwrap_err();
+        assert!(error.to_string().contains("newer tutor"), "{error}");
+    }
+
+    #[test]
+    fn a_backup_never_overwrites_an_earlier_one() {
+        let dir = std::env::temp_dir().join(format!("tutor-export-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let progress = dir.join("progress.json.before-import");
+        assert_eq!(unused_path(progress.clone()), progress);
+        fs::write(&progress, "first").unwrap();
+        let second = unused_path(progress.clone());
+        assert_eq!(second, dir.join("progress.json.2.before-import"));
+        fs::write(&second, "second").unwrap();
+        assert_eq!(
+            unused_path(progress),
+            dir.join("progress.json.3.before-import")
+        );
+
+        let attempt = dir.join("variables1.rs");
+        fs::write(&attempt, "mine").unwrap();
+        assert_eq!(unused_path(attempt), dir.join("variables1.2.rs"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn entry_names_use_forward_slashes() {
+        let path: PathBuf = ["exercises", "01_variables", "variables1.rs"]
+            .iter()
+            .collect();
+        assert_eq!(slashed(&path), "exercises/01_variables/variables1.rs");
+    }
+}
@@ -0,0 +1,261 @@
+//! `tutor verify --all-features-matrix`: build each package that has
+//! cargo features once for every combination of them.
+//!
+//! A build of the whole workspace compiles each crate once, with every
+//! feature any package asked for (feature unification), so a package that
+//! forgot to ask for something it uses still builds there. Building one
+//! package on its own, with `--no-default-features` and each subset of its
+//! features, is how to find out:
+//!
+//! ```text
+//! cargo build --manifest-path examples/features/Cargo.toml --all-targets \
+//!     --no-default-features --features async
+//! ```
+//!
+//! Each combination is shown next to what it resolves to: the features it
+//! turns on, directly or through other features, and the optional
+//! dependencies among them. `broken` is left out of the matrix, since its
+//! code is meant not to compile.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::course::sorted_entries;
+
+/// Features the matrix doesn't vary: `default` is toggled with
+/// `--no-default-features` instead, and `broken` never compiles.
+const NOT_IN_MATRIX: [&str; 2] = ["default", "broken"];
+
+/// A package under `examples/` with features of its own.
+#[derive(Debug)]
+pub struct Package {
+    /// The package's directory under `examples/`, e.g. `no_std/ringbuf`.
+    pub name: String,
+    pub manifest: PathBuf,
+    /// The `[features]` table: each feature and what it turns on.
+    pub features: BTreeMap<String, Vec<String>>,
+    /// The dependencies marked `optional = true`.
+    pub optional_dependencies: BTreeSet<String>,
+}
+
+/// One build in the matrix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combination {
+    /// Whether the default features are on; false is
+    /// `--no-default-features`.
+    pub default: bool,
+    /// What `--features` asks for.
+    pub features: Vec<String>,
+}
+
+impl Combination {
+    /// The arguments that ask cargo for this combination.
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if !self.default {
+            args.push("--no-default-features".to_string());
+        }
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(","));
+        }
+        args
+    }
+}
+
+impl fmt::Display for Combination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args = self.args();
+        if args.is_empty() {
+            write!(f, "(default features)")
+        } else {
+            write!(f, "{}", args.join(" "))
+        }
+    }
+}
+
+/// What a combination turns on once every feature it names has been
+/// followed to the features and dependencies that one lists.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Resolved {
+    pub features: BTreeSet<String>,
+    pub dependencies: BTreeSet<String>,
+}
+
+impl fmt::Display for Resolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.features.is_empty() {
+            write!(f, "(no features)")?;
+        } else {
+            let features: Vec<&str> = self.features.iter().map(String::as_str).collect();
+            write!(f, "{}", features.join(", "))?;
+        }
+        if !self.dependencies.is_empty() {
+            let dependencies: Vec<&str> = self.dependencies.iter().map(String::as_str).collect();
+            write!(f, " + {}", dependencies.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Package {
+    /// The features the matrix turns on and off.
+    pub fn optional_features(&self) -> Vec<&str> {
+        self.features
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !NOT_IN_MATRIX.contains(name))
+            .collect()
+    }
+
+    /// Every subset of the optional features, with the default features
+    /// and, if the package has any, without them.
+    pub fn combinations(&self) -> Vec<Combination> {
+        let optional = self.optional_features();
+        let defaults: &[bool] = if self.features.contains_key("default") {
+            &[false, true]
+        } else {
+            &[true]
+        };
+        let mut combinations = Vec::new();
+        for &default in defaults {
+            for mask in 0..1u32 << optional.len() {
+                let features = optional
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| mask & (1 << bit) != 0)
+                    .map(|(_, name)| name.to_string())
+                    .collect();
+                combinations.push(Combination { default, features });
+            }
+        }
+        combinations
+    }
+
+    /// Follows `combination` through the `[features]` table. An entry is
+    /// another feature, `dep:name` for an optional dependency, or
+    /// `name/feature` for a dependency's feature (which also turns on
+    /// `name` if it is optional, unless written `name?/feature`).
+    pub fn resolve(&self, combination: &Combination) -> Resolved {
+        let mut resolved = Resolved::default();
+        let mut pending = combination.features.clone();
+        if combination.default && self.features.contains_key("default") {
+            pending.push("default".to_string());
+        }
+        while let Some(entry) = pending.pop() {
+            if let Some(dependency) = entry.strip_prefix("dep:") {
+                resolved.dependencies.insert(dependency.to_string());
+            } else if let Some((dependency, _)) = entry.split_once('/') {
+                if !dependency.ends_with('?') && self.optional_dependencies.contains(dependency) {
+                    resolved.dependencies.insert(dependency.to_string());
+                }
+            } else if let Some(enables) = self.features.get(&entry) {
+                if resolved.features.insert(entry) {
+                    pending.extend(enables.iter().cloned());
+                }
+            } else if self.optional_dependencies.contains(&entry) {
+                // An optional dependency is also a feature of the same name,
+                // unless some feature mentions it as `dep:name`
+                resolved.dependencies.insert(entry);
+            }
+        }
+        resolved
+    }
+}
+
+/// Finds the packages in `<root>/examples/*/` and `<root>/examples/*/*/`
+/// with features besides `broken`, leaving out those that are workspaces
+/// of their own (they build for other targets, or with other tools).
+pub fn discover(root: &Path) -> Result<Vec<Package>> {
+    let examples = root.join("examples");
+    let mut dirs = Vec::new();
+    for dir in sorted_entries(&examples)? {
+        if dir.is_dir() {
+            dirs.extend(sorted_entries(&dir)?.into_iter().filter(|sub| sub.is_dir()));
+            dirs.push(dir);
+        }
+    }
+    dirs.sort();
+
+    let mut packages = Vec::new();
+    for dir in dirs {
+        let manifest = dir.join("Cargo.toml");
+        if !manifest.is_file() {
+            continue;
+        }
+        let text = fs::read_to_string(&manifest)
+            .with_context(|| format!("cannot read {}", manifest.display()))?;
+        let table: toml::Table = toml::from_str(&text)
+            .with_context(|| format!("{} is not valid", manifest.display()))?;
+        if table.contains_key("workspace") {
+            continue;
+        }
+        let features: BTreeMap<String, Vec<String>> = match table.get("features") {
+            Some(features) => features
+                .clone()
+                .try_into()
+                .with_context(|| format!("{} has an invalid [features]", manifest.display()))?,
+            None => continue,
+        };
+        let optional_dependencies = table
+            .get("dependencies")
+            .and_then(|deps| deps.as_table())
+            .into_iter()
+            .flatten()
+            .filter(|(_, spec)| spec.get("optional").and_then(|o| o.as_bool()) == Some(true))
+            .map(|(name, _)| name.clone())
+            .collect();
+        let name = dir
+            .strip_prefix(&examples)
+            .unwrap_or(&dir)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let package = Package {
+            name,
+            manifest,
+            features,
+            optional_dependencies,
+        };
+        if !package.optional_features().is_empty() {
+            packages.push(package);
+        }
+    }
+    Ok(packages)
+}
+
+/// The packages `query` names: one by its name, or every package in a
+/// chapter (`no_std` for `no_std/ringbuf`).
+pub fn find<'a>(packages: &'a [Package], query: &str) -> Vec<&'a Package> {
+    packages
+        .iter()
+        .filter(|package| {
+            package.name == query
+                || package
+                    .name
+                    .strip_prefix(query)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .collect()
+}
+
+/// Builds every target of `package` (binaries, tests, examples) with
+/// `combination`, returning the compiler's diagnostics if it fails.
+pub fn build(package: &Package, combination: &Combination) -> Result<Result<(), String>> {
+    let output = Command::new("cargo")
+        .args(["build", "--quiet", "--color=always", "--all-targets"])
+        .arg("--manifest-path")
+        .arg(&package.manifest)
+        .args(combination.args())
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+    if output.status.success() {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()))
+    }
+}
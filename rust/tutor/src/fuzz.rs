@@ -0,0 +1,179 @@
+//! `tutor fuzz`: replay a fuzz target's stored inputs, then fuzz it for a
+//! bounded time.
+//!
+//! Fuzz targets use the layout `cargo fuzz init` creates, in a `fuzz/`
+//! package next to the chapter whose code they exercise:
+//!
+//! ```text
+//! examples/fuzzing/fuzz/
+//! ├── Cargo.toml
+//! ├── fuzz_targets/parse_packet.rs   <- one fuzz_target! per file
+//! ├── corpus/parse_packet/           <- seed inputs, one per file
+//! └── artifacts/parse_packet/        <- inputs that crashed it once
+//! ```
+//!
+//! Replaying needs no nightly compiler: a libfuzzer-sys target built by
+//! plain cargo runs each file it is given once, and exits non-zero if the
+//! target panics. Fuzzing does need one, and `cargo fuzz`, so it is skipped
+//! with an explanation when they aren't installed.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use crate::course::{file_stem, sorted_entries};
+use crate::runner::{self, TIMEOUT};
+
+/// One fuzz target.
+#[derive(Debug)]
+pub struct Target {
+    /// File name without `.rs`, e.g. `parse_packet`.
+    pub name: String,
+    /// The chapter it belongs to, e.g. `fuzzing`.
+    pub chapter: String,
+    /// The `fuzz/` package it is part of.
+    pub dir: PathBuf,
+}
+
+/// What happened to one stored input.
+#[derive(Debug)]
+pub struct Replay {
+    pub input: PathBuf,
+    /// The panic message, if the target crashed (or ran too long).
+    pub crash: Option<String>,
+}
+
+impl Target {
+    /// The stored inputs: the corpus first, then earlier crashes.
+    pub fn inputs(&self) -> Result<Vec<PathBuf>> {
+        let mut inputs = Vec::new();
+        for kind in ["corpus", "artifacts"] {
+            let dir = self.dir.join(kind).join(&self.name);
+            if dir.is_dir() {
+                inputs.extend(
+                    sorted_entries(&dir)?
+                        .into_iter()
+                        .filter(|path| path.is_file()),
+                );
+            }
+        }
+        Ok(inputs)
+    }
+}
+
+/// Finds the fuzz target in every `<root>/examples/*/fuzz/` package.
+pub fn discover(root: &Path) -> Result<Vec<Target>> {
+    let mut targets = Vec::new();
+    for chapter in sorted_entries(&root.join("examples"))? {
+        let dir = chapter.join("fuzz");
+        if !dir.join("Cargo.toml").is_file() {
+            continue;
+        }
+        for path in sorted_entries(&dir.join("fuzz_targets"))? {
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                targets.push(Target {
+                    name: file_stem(&path)?,
+                    chapter: file_stem(&chapter)?,
+                    dir: dir.clone(),
+                });
+            }
+        }
+    }
+    Ok(targets)
+}
+
+/// Looks up a target by name.
+pub fn find<'a>(targets: &'a [Target], name: &str) -> Option<&'a Target> {
+    targets.iter().find(|target| target.name == name)
+}
+
+/// Builds `target` with plain cargo, placing artifacts under `build_dir`,
+/// and runs it on each stored input. Returns the compiler's diagnostics
+/// instead if it doesn't build.
+pub fn replay(target: &Target, build_dir: &Path) -> Result<Result<Vec<Replay>, String>> {
+    let target_dir = build_dir.join("fuzz").join(&target.chapter);
+    let build = Command::new("cargo")
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(target.dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .args(["--bin", &target.name, "--quiet", "--color=always"])
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+    if !build.status.success() {
+        return Ok(Err(String::from_utf8_lossy(&build.stderr).into_owned()));
+    }
+
+    let binary =
+        target_dir
+            .join("debug")
+            .join(format!("{}{}", target.name, std::env::consts::EXE_SUFFIX));
+    let mut replays = Vec::new();
+    for input in target.inputs()? {
+        let run = runner::run(
+            Command::new(&binary).arg(&input).env("RUST_BACKTRACE", "0"),
+            TIMEOUT,
+        )?;
+        let crash = match run.status {
+            Some(status) if status.success() => None,
+            Some(_) => Some(panic_message(&run.stderr)),
+            None => Some(format!("still running after {}s", TIMEOUT.as_secs())),
+        };
+        replays.push(Replay { input, crash });
+    }
+    Ok(Ok(replays))
+}
+
+/// The "panicked at" line and the message after it, from libFuzzer's
+/// output. Falls back to its summary line for crashes that aren't panics.
+fn panic_message(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr.lines().collect();
+    if let Some(at) = lines.iter().position(|line| line.contains("panicked at")) {
+        let location = lines[at].split("panicked at ").nth(1).unwrap_or_default();
+        let message = lines.get(at + 1).copied().unwrap_or_default();
+        return format!("{message} ({})", location.trim_end_matches(':'));
+    }
+    lines
+        .iter()
+        .find(|line| line.starts_with("SUMMARY:"))
+        .map_or_else(|| "crashed".to_string(), |line| line.to_string())
+}
+
+/// Whether `cargo fuzz` is installed.
+pub fn cargo_fuzz_available() -> bool {
+    Command::new("cargo")
+        .args(["fuzz", "--version"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Runs `cargo +nightly fuzz run` on `target` for `seconds`, with its output
+/// going straight to the terminal. New inputs are written to a scratch
+/// corpus under `build_dir`, so the stored corpus stays as committed; a
+/// crash is saved in `artifacts/`, where the next replay picks it up.
+/// Returns whether it ran that long without finding a crash.
+pub fn fuzz(target: &Target, seconds: u64, build_dir: &Path) -> Result<bool> {
+    let scratch = build_dir
+        .join("fuzz")
+        .join(&target.chapter)
+        .join("corpus")
+        .join(&target.name);
+    std::fs::create_dir_all(&scratch)
+        .with_context(|| format!("cannot create {}", scratch.display()))?;
+    let chapter_dir = target.dir.parent().context("a fuzz package has a parent")?;
+    let status = Command::new("cargo")
+        .args(["+nightly", "fuzz", "run", &target.name])
+        .arg(&scratch)
+        .arg(target.dir.join("corpus").join(&target.name))
+        .arg("--")
+        .arg(format!("-max_total_time={seconds}"))
+        .current_dir(chapter_dir)
+        .env("RUST_BACKTRACE", "0")
+        .status()
+        .context("failed to launch cargo fuzz")?;
+    Ok(status.success())
+}
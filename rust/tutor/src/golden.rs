@@ -0,0 +1,439 @@
+//! Golden files: the recorded output of every example, in `golden/`.
+//!
+//! `tutor record` runs each example and writes what it printed to
+//! `golden/<example>.txt`; `tutor check-output` runs them again and shows
+//! where the output has changed since. An example's output should only
+//! change on purpose, so when it does, record it again and commit the new
+//! golden file with the change that caused it.
+//!
+//! The output is normalized before it is written or compared, so that it
+//! comes out the same on every run: durations become `<duration>`,
+//! addresses `0x<address>`, commit hashes `<hash>`, and the course's own
+//! directory `<root>`. Examples without an `// EXPECTED:` block print in
+//! an order that can change from run to run (threads, timers), so their
+//! lines may come in any order. An example whose output depends on the
+//! machine it runs on has a golden file that says so instead:
+//!
+//! ```text
+//! # not recorded: prints how many cores the machine has
+//! ```
+//!
+//! `record` leaves such a file alone, and `check-output` skips the example.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::compiler::{self, Outcome};
+use crate::course::{Course, Example};
+use crate::runner;
+
+/// Where the golden files live, under the course root.
+pub const DIR: &str = "golden";
+
+/// How a golden file that holds no output starts.
+const NOT_RECORDED: &str = "# not recorded:";
+
+/// Records the output of `examples`. When `everything` is set (they are
+/// all the examples), golden files left over from examples that no longer
+/// exist are deleted too.
+pub fn record(course: &Course, examples: &[Example], everything: bool) -> Result<bool> {
+    let (mut failed, mut skipped) = (Vec::new(), 0);
+    for example in examples {
+        let path = path(course, example);
+        if let Some(reason) = not_recorded(&path)? {
+            println!("⏭  {} is not recorded: {reason}", example.name);
+            skipped += 1;
+            continue;
+        }
+        // Twice, to catch output that normalizing doesn't make repeatable
+        let first = match output(course, example)? {
+            Ok(output) => output,
+            Err(problem) => {
+                println!("❌ {} {problem}", example.name);
+                failed.push(&example.name);
+                continue;
+            }
+        };
+        let unordered = is_unordered(example)?;
+        if let Ok(second) = output(course, example)? {
+            if !same(&first, &second, unordered) {
+                println!(
+                    "❌ {} printed something different the second time:\n{}",
+                    example.name,
+                    diff(&first, &second, unordered)
+                );
+                println!(
+                    "   If that can't be helped, write `{NOT_RECORDED} <why>` in {}.",
+                    relative(course, &path).display()
+                );
+                failed.push(&example.name);
+                continue;
+            }
+        }
+
+        let old = fs::read_to_string(&path).ok();
+        if old.as_deref() == Some(first.as_str()) {
+            println!("✅ {} (unchanged)", example.name);
+            continue;
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        }
+        fs::write(&path, &first).with_context(|| format!("cannot write {}", path.display()))?;
+        let verb = if old.is_some() { "updated" } else { "recorded" };
+        println!(
+            "✅ {} ({verb} {})",
+            example.name,
+            relative(course, &path).display()
+        );
+    }
+
+    if everything {
+        for name in stale(course)? {
+            let path = course.root.join(DIR).join(format!("{name}.txt"));
+            fs::remove_file(&path).with_context(|| format!("cannot delete {}", path.display()))?;
+            println!(
+                "🗑  removed {}, which belongs to no example",
+                relative(course, &path).display()
+            );
+        }
+    }
+    summarize(examples.len() - skipped, &failed, "examples recorded")
+}
+
+/// Runs `examples` and compares what they print with their golden files.
+/// When `everything` is set, golden files that belong to no example count
+/// as failures too.
+pub fn check(course: &Course, examples: &[Example], everything: bool) -> Result<bool> {
+    let (mut failed, mut skipped) = (Vec::new(), 0);
+    for example in examples {
+        let path = path(course, example);
+        if let Some(reason) = not_recorded(&path)? {
+            println!("⏭  {} is not recorded: {reason}", example.name);
+            skipped += 1;
+            continue;
+        }
+        let Ok(golden) = fs::read_to_string(&path) else {
+            println!(
+                "❌ {} has no golden file; run `cargo run -p tutor -- record {}`",
+                example.name, example.name
+            );
+            failed.push(&example.name);
+            continue;
+        };
+        let unordered = is_unordered(example)?;
+        match output(course, example)? {
+            Ok(output) if same(&golden, &output, unordered) => println!("✅ {}", example.name),
+            Ok(output) => {
+                println!(
+                    "❌ {} prints something other than {}:\n{}",
+                    example.name,
+                    relative(course, &path).display(),
+                    diff(&golden, &output, unordered)
+                );
+                println!(
+                    "   If the change is intended, run `cargo run -p tutor -- record {}`.",
+                    example.name
+                );
+                failed.push(&example.name);
+            }
+            Err(problem) => {
+                println!("❌ {} {problem}", example.name);
+                failed.push(&example.name);
+            }
+        }
+    }
+
+    let stale = if everything {
+        stale(course)?
+    } else {
+        Vec::new()
+    };
+    for name in &stale {
+        println!("❌ {DIR}/{name}.txt belongs to no example; run `cargo run -p tutor -- record`");
+        failed.push(name);
+    }
+    summarize(
+        examples.len() - skipped,
+        &failed,
+        "recorded examples match their golden files",
+    )
+}
+
+/// `golden/lifetimes/01_elision.txt` for `lifetimes/01_elision`.
+pub fn path(course: &Course, example: &Example) -> PathBuf {
+    course.root.join(DIR).join(format!("{}.txt", example.name))
+}
+
+fn relative<'a>(course: &Course, path: &'a Path) -> &'a Path {
+    path.strip_prefix(&course.root).unwrap_or(path)
+}
+
+/// Why the example with the golden file at `path` isn't recorded, if it
+/// isn't.
+fn not_recorded(path: &Path) -> Result<Option<String>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    Ok(text
+        .strip_prefix(NOT_RECORDED)
+        .map(|reason| reason.lines().next().unwrap_or_default().trim().to_string()))
+}
+
+/// Whether `example`'s lines may come out in any order: it has no
+/// `// EXPECTED:` block, since its output isn't the same every run.
+fn is_unordered(example: &Example) -> Result<bool> {
+    let source = fs::read_to_string(&example.path)
+        .with_context(|| format!("cannot read {}", example.path.display()))?;
+    Ok(runner::expected_output(&source).is_none())
+}
+
+/// What `example` printed, normalized, or why it didn't run cleanly.
+/// Whether that matches its `// EXPECTED:` block is for `tutor verify` to
+/// say; here it is the output all the same.
+fn output(course: &Course, example: &Example) -> Result<Result<String, String>> {
+    Ok(
+        match compiler::build_and_run(example, &course.build_dir())? {
+            Outcome::Ran {
+                success: true,
+                stdout,
+                ..
+            }
+            | Outcome::WrongOutput { stdout, .. } => Ok(normalize(&stdout, &course.root)),
+            Outcome::Ran { .. } => Err("exited with an error; see `tutor run`".to_string()),
+            Outcome::CompileError(_) => Err("does not compile; see `tutor run`".to_string()),
+            Outcome::TimedOut { .. } => Err(format!(
+                "was still running after {} seconds",
+                runner::TIMEOUT.as_secs()
+            )),
+        },
+    )
+}
+
+fn same(expected: &str, actual: &str, unordered: bool) -> bool {
+    if unordered {
+        return runner::output_matches(&sorted(actual), &sorted(expected));
+    }
+    runner::output_matches(actual, expected)
+}
+
+fn diff(expected: &str, actual: &str, unordered: bool) -> String {
+    if unordered {
+        return runner::output_diff(&sorted(expected), &sorted(actual));
+    }
+    runner::output_diff(expected, actual)
+}
+
+fn sorted(text: &str) -> String {
+    let mut lines: Vec<&str> = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines.sort_unstable();
+    lines.join("\n")
+}
+
+/// The golden files, by example name, that no example has.
+fn stale(course: &Course) -> Result<Vec<String>> {
+    let dir = course.root.join(DIR);
+    let mut names = Vec::new();
+    if dir.is_dir() {
+        collect(&dir, "", &mut names)?;
+    }
+    names.retain(|name| !course.examples.iter().any(|example| example.name == *name));
+    Ok(names)
+}
+
+/// The names of the golden files under `dir`, prefixed with `prefix`.
+fn collect(dir: &Path, prefix: &str, names: &mut Vec<String>) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        let Some(stem) = path.file_stem().map(|stem| stem.to_string_lossy()) else {
+            continue;
+        };
+        if path.is_dir() {
+            collect(&path, &format!("{prefix}{stem}/"), names)?;
+        } else if path.extension().is_some_and(|ext| ext == "txt") {
+            names.push(format!("{prefix}{stem}"));
+        }
+    }
+    names.sort();
+    Ok(())
+}
+
+fn summarize(total: usize, failed: &[&String], done: &str) -> Result<bool> {
+    if failed.is_empty() {
+        if total > 1 {
+            println!("\nAll {total} {done}.");
+        }
+        return Ok(true);
+    }
+    println!("\nThese need attention:");
+    for name in failed {
+        println!("  - {name}");
+    }
+    Ok(false)
+}
+
+/// `stdout` with everything that changes from run to run or machine to
+/// machine replaced by a placeholder, and a final newline.
+pub fn normalize(stdout: &str, root: &Path) -> String {
+    let root = root.display().to_string();
+    let mut normalized = String::new();
+    for line in stdout.lines() {
+        normalized.push_str(normalize_line(&line.trim_end().replace(&root, "<root>")).trim_end());
+        normalized.push('\n');
+    }
+    normalized
+}
+
+fn normalize_line(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut normalized = String::new();
+    let mut at = 0;
+    while at < chars.len() {
+        let starts_word = at == 0 || !chars[at - 1].is_alphanumeric();
+        if starts_word && is_port(&chars[..at], &chars[at..]) {
+            let len = chars[at..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            normalized.push_str("<port>");
+            at += len;
+            continue;
+        }
+        match volatile(&chars[at..]).filter(|_| starts_word) {
+            Some((len, placeholder)) => {
+                if placeholder == DURATION {
+                    // `[   5 ms]` and `[ 105 ms]` both become `[ <duration>]`
+                    let kept = normalized.trim_end().len();
+                    if kept < normalized.len() {
+                        normalized.truncate(kept);
+                        normalized.push(' ');
+                    }
+                }
+                normalized.push_str(placeholder);
+                at += len;
+            }
+            None => {
+                normalized.push(chars[at]);
+                at += 1;
+            }
+        }
+    }
+    normalized
+}
+
+const DURATION: &str = "<duration>";
+
+/// Whether `after` starts with the port of an address that `before` ends
+/// with the host of: `127.0.0.1:`, `localhost:`, or `[::1]:`.
+fn is_port(before: &[char], after: &[char]) -> bool {
+    let Some((':', before)) = before.split_last().map(|(last, rest)| (*last, rest)) else {
+        return false;
+    };
+    let digits = after.iter().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 || after.get(digits).is_some_and(|c| c.is_alphanumeric()) {
+        return false;
+    }
+    let start = before
+        .iter()
+        .rposition(|c| !(c.is_ascii_alphanumeric() || *c == '.'))
+        .map_or(0, |at| at + 1);
+    let host: String = before[start..].iter().collect();
+    let ipv4 = host.chars().filter(|c| *c == '.').count() == 3
+        && host.chars().all(|c| c.is_ascii_digit() || c == '.');
+    before.last() == Some(&']') || ipv4 || host == "localhost"
+}
+
+/// The length of the duration, address, or hash that `text` starts with,
+/// and what to replace it with.
+fn volatile(text: &[char]) -> Option<(usize, &'static str)> {
+    let word = text.iter().take_while(|c| c.is_alphanumeric()).count();
+    if text.starts_with(&['0', 'x']) {
+        let digits = text[2..word].iter().all(char::is_ascii_hexdigit);
+        if digits && word >= 14 {
+            return Some((word, "0x<address>"));
+        }
+    }
+    // Not `0b1010...`, which is a number written in binary
+    let letters = &text[..word];
+    if (12..=40).contains(&word)
+        && !text.starts_with(&['0', 'b'])
+        && letters.iter().all(char::is_ascii_hexdigit)
+        && letters.iter().any(char::is_ascii_digit)
+        && letters.iter().any(char::is_ascii_alphabetic)
+    {
+        return Some((word, "<hash>"));
+    }
+    duration(text).map(|len| (len, DURATION))
+}
+
+/// The length of the duration `text` starts with: `12 ms`, `1.5s`.
+fn duration(text: &[char]) -> Option<usize> {
+    let digits = |from: usize| {
+        text[from..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+    };
+    let mut len = digits(0);
+    if len == 0 {
+        return None;
+    }
+    if text.get(len) == Some(&'.') && text.get(len + 1).is_some_and(char::is_ascii_digit) {
+        len += 1 + digits(len + 1);
+    }
+    if text.get(len) == Some(&' ') {
+        len += 1;
+    }
+    ["ns", "µs", "us", "ms", "s"].into_iter().find_map(|unit| {
+        let unit: Vec<char> = unit.chars().collect();
+        let end = len + unit.len();
+        let ends_word = !text.get(end).is_some_and(|c| c.is_alphanumeric());
+        (text[len..].starts_with(&unit) && ends_word).then_some(end)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn what_changes_between_runs_is_normalized() {
+        let stdout = "\
+[   1 ms] tick 1
+[ 101 ms] tick 2
+summing took 5.227µs (Duration { 1.5s })
+number lives at 0x7fff4259a564, a made-up pointer at 0x1234, bits 0b00111111110000000000000000000000
+built 8364e474903d from /home/me/rust/examples/cli
+listening on 127.0.0.1:43361 and [::1]:8080 at 12:30:45
+8 threads x 10,000 increments, 3 seconds, x86_64
+";
+        assert_eq!(
+            normalize(stdout, Path::new("/home/me/rust")),
+            "\
+[ <duration>] tick 1
+[ <duration>] tick 2
+summing took <duration> (Duration { <duration> })
+number lives at 0x<address>, a made-up pointer at 0x1234, bits 0b00111111110000000000000000000000
+built <hash> from <root>/examples/cli
+listening on 127.0.0.1:<port> and [::1]:<port> at 12:30:45
+8 threads x 10,000 increments, 3 seconds, x86_64
+"
+        );
+    }
+
+    #[test]
+    fn unordered_output_only_needs_the_same_lines() {
+        assert!(same("a\nb\n", "b\na", true));
+        assert!(!same("a\nb\n", "b\na", false));
+        assert!(!same("a\nb\n", "a\nc\n", true));
+    }
+}
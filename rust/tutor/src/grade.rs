@@ -0,0 +1,493 @@
+//! `tutor grade`: checks every exercise and scores every quiz without
+//! asking anything, for a teacher grading a learner's copy of the course.
+//!
+//! Each exercise is worth 100 points:
+//!
+//! ```text
+//! tests   70 × the share of its hidden tests (or planted bugs) that pass
+//! hints   20 × the share of its hints that were never revealed
+//! clippy  10 if clippy warns about nothing it doesn't also find in the
+//!            reference solution
+//! ```
+//!
+//! The hint and clippy points only count once every test passes, so an
+//! untouched exercise scores 0. A quiz scores the percentage of questions
+//! answered correctly. A chapter scores the average of its exercises and
+//! its quiz, and the whole course the average of every exercise and quiz.
+//!
+//! Quiz answers come from a TOML file with, for each chapter, one answer
+//! per question in the order of its bank: the text of the right choice, or
+//! what the snippet prints:
+//!
+//! ```toml
+//! variables = ["mut", "6", "Shadowing makes a new variable"]
+//! ```
+//!
+//! Without one, each quiz is scored from the learner's latest attempt at
+//! it in their progress file; a quiz never taken scores 0.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::checker::{self, Report as CheckReport};
+use crate::course::Course;
+use crate::exercise::Exercise;
+use crate::progress::{Kind, Progress};
+use crate::quiz;
+use crate::solution;
+
+const TEST_POINTS: f64 = 70.0;
+const HINT_POINTS: f64 = 20.0;
+const CLIPPY_POINTS: f64 = 10.0;
+
+/// The graded course.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// Seconds since the Unix epoch.
+    pub graded_at: u64,
+    /// Out of 100.
+    pub score: u32,
+    pub chapters: Vec<Chapter>,
+}
+
+/// One chapter's exercises and quiz.
+#[derive(Debug, Serialize)]
+pub struct Chapter {
+    pub name: String,
+    /// Out of 100.
+    pub score: u32,
+    pub exercises: Vec<ExerciseGrade>,
+    pub quiz: Option<QuizGrade>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExerciseGrade {
+    pub name: String,
+    pub compiles: bool,
+    pub tests_passed: usize,
+    pub tests_total: usize,
+    pub hints_used: usize,
+    pub hints_available: usize,
+    /// Clippy's warnings beyond those about the reference solution, which
+    /// come from code the exercise provides. `None` unless every test
+    /// passes: clippy only runs on working code.
+    pub clippy_warnings: Option<usize>,
+    /// Out of 100.
+    pub score: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuizGrade {
+    pub correct: usize,
+    pub total: usize,
+    /// Where the answers came from.
+    pub source: QuizSource,
+    /// Out of 100.
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuizSource {
+    /// The answers file.
+    Answers,
+    /// The learner's latest attempt, from their progress file.
+    Progress,
+    /// Neither has anything for this quiz.
+    NotTaken,
+}
+
+/// Grades every exercise and quiz, printing a line per exercise as it
+/// goes, since checking them all takes a while.
+pub fn grade(
+    course: &Course,
+    exercises: &[Exercise],
+    progress: &Progress,
+    answers: Option<&Path>,
+) -> Result<Report> {
+    let answers = answers.map(load_answers).transpose()?;
+    let quizzes = quiz::chapters(&course.root)?;
+    if let Some(answers) = &answers {
+        if let Some(unknown) = answers.keys().find(|chapter| !quizzes.contains(chapter)) {
+            bail!("the answers file has answers for `{unknown}`, which has no quiz");
+        }
+    }
+
+    // Chapters in the order of the exercises, then those with only a quiz
+    let mut chapters: Vec<Chapter> = Vec::new();
+    for exercise in exercises {
+        let grade = grade_exercise(course, exercise, progress)?;
+        println!(
+            "{} {:<15} {:>3}/100",
+            if grade.score == 100 { "✅" } else { "📝" },
+            exercise.name,
+            grade.score
+        );
+        match chapters
+            .iter_mut()
+            .find(|chapter| chapter.name == exercise.topic)
+        {
+            Some(chapter) => chapter.exercises.push(grade),
+            None => chapters.push(Chapter {
+                name: exercise.topic.clone(),
+                score: 0,
+                exercises: vec![grade],
+                quiz: None,
+            }),
+        }
+    }
+    for name in &quizzes {
+        let bank = quiz::load(&course.root, name)?;
+        let grade = match answers.as_ref().and_then(|answers| answers.get(name)) {
+            Some(replies) => quiz_grade(quiz::grade(&bank, replies), QuizSource::Answers),
+            None => match progress.quizzes.get(name) {
+                Some(record) => quiz_grade(
+                    quiz::Score {
+                        correct: record.last_score,
+                        total: record.last_total,
+                    },
+                    QuizSource::Progress,
+                ),
+                None => quiz_grade(
+                    quiz::Score {
+                        correct: 0,
+                        total: bank.questions.len(),
+                    },
+                    QuizSource::NotTaken,
+                ),
+            },
+        };
+        match chapters.iter_mut().find(|chapter| chapter.name == *name) {
+            Some(chapter) => chapter.quiz = Some(grade),
+            None => chapters.push(Chapter {
+                name: name.clone(),
+                score: 0,
+                exercises: Vec::new(),
+                quiz: Some(grade),
+            }),
+        }
+    }
+
+    let mut all = Vec::new();
+    for chapter in &mut chapters {
+        let scores = chapter_scores(chapter);
+        chapter.score = average(&scores);
+        all.extend(scores);
+    }
+    let graded_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    Ok(Report {
+        graded_at,
+        score: average(&all),
+        chapters,
+    })
+}
+
+fn grade_exercise(
+    course: &Course,
+    exercise: &Exercise,
+    progress: &Progress,
+) -> Result<ExerciseGrade> {
+    let report = checker::check(exercise, &course.build_dir())?;
+    let (compiles, tests_total) = match &report {
+        CheckReport::CompileError(_) => (false, 0),
+        CheckReport::Tested(results) => (true, results.len()),
+    };
+    let tests_passed = tests_total - report.failures().count();
+    let clippy_warnings = if report.passed() {
+        let reference = Exercise {
+            path: solution::path(&course.root, exercise),
+            ..exercise.clone()
+        };
+        let expected = if reference.path.is_file() {
+            checker::clippy(&reference, &course.build_dir())?.unwrap_or(0)
+        } else {
+            0
+        };
+        checker::clippy(exercise, &course.build_dir())?
+            .map(|warnings| warnings.saturating_sub(expected))
+    } else {
+        None
+    };
+    let hints_available = exercise
+        .hints
+        .as_ref()
+        .map_or(0, |hints| hints.levels().len());
+    let hints_used = progress
+        .get(Kind::Exercise, &exercise.name)
+        .map_or(0, |record| record.hints_used);
+    Ok(ExerciseGrade {
+        name: exercise.name.clone(),
+        compiles,
+        tests_passed,
+        tests_total,
+        hints_used,
+        hints_available,
+        clippy_warnings,
+        score: exercise_score(
+            tests_passed,
+            tests_total,
+            hints_used,
+            hints_available,
+            clippy_warnings,
+        ),
+    })
+}
+
+/// The exercise's points out of 100, as described at the top of the file.
+fn exercise_score(
+    passed: usize,
+    total: usize,
+    hints_used: usize,
+    hints_available: usize,
+    clippy_warnings: Option<usize>,
+) -> u32 {
+    if total == 0 {
+        return 0;
+    }
+    let mut points = TEST_POINTS * passed as f64 / total as f64;
+    if passed == total {
+        points += match hints_available {
+            0 => HINT_POINTS,
+            available => {
+                HINT_POINTS * available.saturating_sub(hints_used) as f64 / available as f64
+            }
+        };
+        if clippy_warnings == Some(0) {
+            points += CLIPPY_POINTS;
+        }
+    }
+    points.round() as u32
+}
+
+fn quiz_grade(score: quiz::Score, source: QuizSource) -> QuizGrade {
+    QuizGrade {
+        correct: score.correct,
+        total: score.total,
+        source,
+        score: (score.correct * 100).checked_div(score.total).unwrap_or(0) as u32,
+    }
+}
+
+fn chapter_scores(chapter: &Chapter) -> Vec<u32> {
+    chapter
+        .exercises
+        .iter()
+        .map(|exercise| exercise.score)
+        .chain(chapter.quiz.as_ref().map(|quiz| quiz.score))
+        .collect()
+}
+
+fn average(scores: &[u32]) -> u32 {
+    if scores.is_empty() {
+        return 0;
+    }
+    (scores.iter().map(|&score| f64::from(score)).sum::<f64>() / scores.len() as f64).round() as u32
+}
+
+/// Reads an answers file, as described at the top of the file.
+fn load_answers(path: &Path) -> Result<BTreeMap<String, Vec<String>>> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    toml::from_str(&text).with_context(|| {
+        format!(
+            "{} should list each chapter's answers, like `variables = [\"mut\", \"6\"]`",
+            path.display()
+        )
+    })
+}
+
+/// Prints a score per chapter, and the total.
+pub fn print(report: &Report) {
+    println!("\n📊 Grades\n");
+    for chapter in &report.chapters {
+        let solved = chapter
+            .exercises
+            .iter()
+            .filter(|exercise| {
+                exercise.tests_total > 0 && exercise.tests_passed == exercise.tests_total
+            })
+            .count();
+        let mut details = Vec::new();
+        if !chapter.exercises.is_empty() {
+            details.push(format!(
+                "{solved}/{} exercises solved",
+                chapter.exercises.len()
+            ));
+        }
+        if let Some(quiz) = &chapter.quiz {
+            details.push(match quiz.source {
+                QuizSource::NotTaken => String::from("quiz not taken"),
+                _ => format!("quiz {}/{}", quiz.correct, quiz.total),
+            });
+        }
+        println!(
+            "  {:<22} {:>3}/100   {}",
+            chapter.name,
+            chapter.score,
+            details.join(", ")
+        );
+    }
+    println!("\n  {:<22} {:>3}/100", "Overall", report.score);
+}
+
+/// Writes the report as JSON and as a web page, to whichever paths are
+/// given.
+pub fn export(report: &Report, json: Option<&Path>, html: Option<&Path>) -> Result<()> {
+    if let Some(path) = json {
+        write(path, &to_json(report)?)?;
+    }
+    if let Some(path) = html {
+        write(path, &to_html(report))?;
+    }
+    Ok(())
+}
+
+fn write(path: &Path, text: &str) -> Result<()> {
+    fs::write(path, text).with_context(|| format!("cannot write {}", path.display()))?;
+    println!("📝 Wrote {}", path.display());
+    Ok(())
+}
+
+pub fn to_json(report: &Report) -> Result<String> {
+    Ok(serde_json::to_string_pretty(report)? + "\n")
+}
+
+/// A standalone page with the same tables as [`print`], plus every
+/// exercise's details.
+pub fn to_html(report: &Report) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Learn Rust: grades</title>\n<style>\n\
+         body { font-family: system-ui, sans-serif; max-width: 50rem; margin: 2rem auto; padding: 0 1rem; }\n\
+         table { border-collapse: collapse; width: 100%; margin-bottom: 2rem; }\n\
+         th, td { text-align: left; padding: 0.3rem 0.6rem; border-bottom: 1px solid #ddd; }\n\
+         td.score { text-align: right; font-variant-numeric: tabular-nums; }\n\
+         .full { color: #1a7f37; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    let _ = writeln!(
+        html,
+        "<h1>Grades</h1>\n<p>Overall score: <strong>{}/100</strong></p>",
+        report.score
+    );
+
+    html.push_str("<table>\n<tr><th>Chapter</th><th>Score</th></tr>\n");
+    for chapter in &report.chapters {
+        let _ = writeln!(
+            html,
+            "<tr><td><a href=\"#{name}\">{name}</a></td>{}</tr>",
+            score_cell(chapter.score),
+            name = escape(&chapter.name)
+        );
+    }
+    html.push_str("</table>\n");
+
+    for chapter in &report.chapters {
+        let _ = writeln!(
+            html,
+            "<h2 id=\"{name}\">{name}: {}/100</h2>",
+            chapter.score,
+            name = escape(&chapter.name)
+        );
+        html.push_str("<table>\n<tr><th></th><th>Tests</th><th>Hints used</th><th>Clippy</th><th>Score</th></tr>\n");
+        for exercise in &chapter.exercises {
+            let tests = if exercise.compiles {
+                format!("{}/{}", exercise.tests_passed, exercise.tests_total)
+            } else {
+                String::from("does not compile")
+            };
+            let clippy = match exercise.clippy_warnings {
+                None => String::from("–"),
+                Some(0) => String::from("clean"),
+                Some(1) => String::from("1 warning"),
+                Some(n) => format!("{n} warnings"),
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{tests}</td><td>{}/{}</td><td>{clippy}</td>{}</tr>",
+                escape(&exercise.name),
+                exercise.hints_used,
+                exercise.hints_available,
+                score_cell(exercise.score)
+            );
+        }
+        if let Some(quiz) = &chapter.quiz {
+            let answers = match quiz.source {
+                QuizSource::NotTaken => String::from("not taken"),
+                QuizSource::Answers => format!("{}/{} correct", quiz.correct, quiz.total),
+                QuizSource::Progress => {
+                    format!("{}/{} correct (latest attempt)", quiz.correct, quiz.total)
+                }
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>quiz</td><td colspan=\"3\">{answers}</td>{}</tr>",
+                score_cell(quiz.score)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn score_cell(score: u32) -> String {
+    let class = if score == 100 { "score full" } else { "score" };
+    format!("<td class=\"{class}\">{score}</td>")
+}
+
+/// `text` made safe to put in HTML, also inside attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_unsolved_exercise_scores_only_its_tests() {
+        assert_eq!(exercise_score(0, 4, 0, 3, None), 0);
+        assert_eq!(exercise_score(2, 4, 0, 3, None), 35);
+        assert_eq!(exercise_score(0, 0, 0, 3, None), 0);
+    }
+
+    #[test]
+    fn a_solved_exercise_loses_points_for_hints_and_clippy_warnings() {
+        assert_eq!(exercise_score(4, 4, 0, 3, Some(0)), 100);
+        assert_eq!(exercise_score(4, 4, 3, 3, Some(0)), 80);
+        assert_eq!(exercise_score(4, 4, 1, 2, Some(2)), 80);
+        assert_eq!(exercise_score(4, 4, 0, 0, Some(0)), 100);
+    }
+
+    #[test]
+    fn chapters_average_their_exercises_and_quiz() {
+        let chapter = Chapter {
+            name: String::from("variables"),
+            score: 0,
+            exercises: Vec::new(),
+            quiz: Some(quiz_grade(
+                quiz::Score {
+                    correct: 1,
+                    total: 2,
+                },
+                QuizSource::Answers,
+            )),
+        };
+        let mut scores = chapter_scores(&chapter);
+        scores.push(100);
+        assert_eq!(average(&scores), 75);
+        assert_eq!(average(&[]), 0);
+    }
+}
@@ -0,0 +1,252 @@
+//! The tutor in other languages: `tutor --lang es`.
+//!
+//! What the tutor itself says lives in Fluent files, one per language,
+//! built into the binary:
+//!
+//! ```text
+//! tutor/locales/
+//! ├── en.ftl    <- every message, the one every other language falls back to
+//! └── es.ftl
+//! ```
+//!
+//! The course's own text is translated file by file. A translation sits
+//! next to the English file with the language code before its extension,
+//! and is used when it exists:
+//!
+//! ```text
+//! lessons.es.toml                    <- lesson titles, by id
+//! quizzes/variables.es.toml          <- a whole question bank
+//! exercises/01_variables/info.es.toml <- hints, by exercise
+//! lessons/01-hello-world.es.md       <- a reading
+//! ```
+//!
+//! Anything a translation leaves out, a message, a title, an exercise's
+//! hints, is shown in English.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{bail, Result};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentValue;
+
+/// The language everything is written in first.
+pub const ENGLISH: &str = "en";
+
+/// The languages the tutor's messages come in, and their Fluent source.
+pub const LANGUAGES: &[(&str, &str)] = &[
+    (ENGLISH, include_str!("../locales/en.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+];
+
+static CURRENT: OnceLock<Language> = OnceLock::new();
+
+/// The messages in one language, with English behind them.
+pub struct Language {
+    code: &'static str,
+    /// The language's own bundle first, then English's.
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Language {
+    /// Loads the messages for `code`, e.g. `es`.
+    pub fn new(code: &str) -> Result<Self> {
+        let Some(&(code, _)) = LANGUAGES.iter().find(|(known, _)| *known == code) else {
+            let known: Vec<&str> = LANGUAGES.iter().map(|(code, _)| *code).collect();
+            bail!(
+                "the tutor has no messages in `{code}`; use one of: {}",
+                known.join(", ")
+            );
+        };
+        let mut bundles = vec![bundle(code)?];
+        if code != ENGLISH {
+            bundles.push(bundle(ENGLISH)?);
+        }
+        Ok(Language { code, bundles })
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// The message `id` with `args` filled in, from the first bundle that
+    /// has it. A message no bundle has comes out as its id, so that a typo
+    /// shows up on screen rather than as nothing.
+    pub fn text(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        for bundle in &self.bundles {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                .into_owned();
+        }
+        id.to_string()
+    }
+
+    /// The translation of the course file at `path`, if this language has
+    /// one: `quizzes/variables.toml` becomes `quizzes/variables.es.toml`.
+    pub fn localized(&self, path: &Path) -> Option<PathBuf> {
+        if self.code == ENGLISH {
+            return None;
+        }
+        let translated = translation_path(path, self.code);
+        translated.is_file().then_some(translated)
+    }
+}
+
+fn bundle(code: &'static str) -> Result<FluentBundle<FluentResource>> {
+    let source = LANGUAGES
+        .iter()
+        .find_map(|(known, source)| (*known == code).then_some(*source))
+        .unwrap_or_default();
+    let resource = FluentResource::try_new(source.to_string())
+        .map_err(|(_, errors)| anyhow::anyhow!("locales/{code}.ftl is not valid: {errors:?}"))?;
+    let id: LanguageIdentifier = code.parse()?;
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // The marks Fluent puts around arguments for right-to-left text show up
+    // as stray characters in a terminal
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        bail!("locales/{code}.ftl defines a message twice: {errors:?}");
+    }
+    Ok(bundle)
+}
+
+/// Makes `code` the language of everything the tutor prints from now on.
+/// Only the first call counts.
+pub fn set(code: &str) -> Result<()> {
+    let language = Language::new(code)?;
+    let _ = CURRENT.set(language);
+    Ok(())
+}
+
+/// The language chosen with [`set`], or English if none was.
+pub fn current() -> &'static Language {
+    CURRENT.get_or_init(|| Language::new(ENGLISH).expect("the English messages are valid"))
+}
+
+/// Where the `code` translation of the file at `path` goes.
+pub fn translation_path(path: &Path, code: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}.{code}.{}", ext.to_string_lossy()),
+        None => format!("{stem}.{code}"),
+    };
+    path.with_file_name(name)
+}
+
+/// Whether `path` is a translation of another file, rather than one of
+/// the English files a directory is listed for.
+pub fn is_translation(path: &Path) -> bool {
+    let Some(language) = path
+        .file_stem()
+        .map(Path::new)
+        .and_then(Path::extension)
+        .and_then(|ext| ext.to_str())
+    else {
+        return false;
+    };
+    LANGUAGES
+        .iter()
+        .any(|(code, _)| *code != ENGLISH && *code == language)
+}
+
+/// A message in the current language: `t!("quiz-score", correct = 3, total = 4)`.
+#[macro_export]
+macro_rules! t {
+    ($id:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::current().text(
+            $id,
+            &[$((stringify!($name), $crate::i18n::FluentValue::from($value))),*],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The ids of the messages in a Fluent file: the lines that start
+    /// with a name and an `=`.
+    fn ids(source: &str) -> Vec<&str> {
+        let mut ids: Vec<&str> = source
+            .lines()
+            .filter_map(|line| line.split_once(" ="))
+            .map(|(id, _)| id)
+            .filter(|id| id.starts_with(|c: char| c.is_ascii_lowercase()))
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn every_language_has_the_same_messages_as_english() {
+        let english = ids(LANGUAGES[0].1);
+        for (code, source) in &LANGUAGES[1..] {
+            assert_eq!(ids(source), english, "locales/{code}.ftl");
+            Language::new(code).unwrap();
+        }
+    }
+
+    #[test]
+    fn messages_take_arguments_and_plurals() {
+        let failing = |language: &Language, failing: usize| {
+            language.text(
+                "check-failing",
+                &[
+                    ("failing", FluentValue::from(failing)),
+                    ("total", FluentValue::from(4)),
+                ],
+            )
+        };
+        let english = Language::new(ENGLISH).unwrap();
+        assert_eq!(
+            failing(&english, 1),
+            "1 of 4 assertions still needs fixing."
+        );
+        assert_eq!(failing(&english, 3), "3 of 4 assertions still need fixing.");
+        let spanish = Language::new("es").unwrap();
+        assert_eq!(
+            spanish.text(
+                "quiz-score",
+                &[
+                    ("correct", FluentValue::from(1)),
+                    ("total", FluentValue::from(4))
+                ]
+            ),
+            "Puntuación: 1/4"
+        );
+
+        let when = |days: u64| spanish.text("review-when", &[("days", FluentValue::from(days))]);
+        assert_eq!(when(0), "hoy");
+        assert_eq!(when(1), "mañana");
+        assert_eq!(when(6), "dentro de 6 días");
+    }
+
+    #[test]
+    fn a_missing_message_falls_back_to_english_then_to_its_id() {
+        let spanish = Language::new("es").unwrap();
+        assert_eq!(spanish.text("no-such-message", &[]), "no-such-message");
+        assert!(Language::new("xx").is_err());
+    }
+
+    #[test]
+    fn translations_sit_next_to_the_english_file() {
+        let english = Path::new("quizzes/variables.toml");
+        let spanish = translation_path(english, "es");
+        assert_eq!(spanish, Path::new("quizzes/variables.es.toml"));
+        assert!(is_translation(&spanish));
+        assert!(!is_translation(english));
+        assert!(!is_translation(Path::new("lessons/01-hello-world.md")));
+        assert!(!is_translation(Path::new("Cargo.toml")));
+    }
+}
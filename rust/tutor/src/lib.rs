@@ -0,0 +1,44 @@
+//! The tutor's building blocks: discovering the course, compiling and
+//! running examples, and keeping track of the learner's progress. The
+//! `tutor` binary is the command-line front end; `bookgen` reuses the same
+//! course discovery and page parsing to build the book.
+
+pub mod achievements;
+pub mod bench;
+pub mod borrows;
+pub mod checker;
+pub mod compare;
+pub mod compiler;
+pub mod course;
+pub mod docs;
+pub mod equivalence;
+pub mod errors;
+pub mod exercise;
+pub mod explain;
+pub mod export;
+pub mod features;
+pub mod fuzz;
+pub mod golden;
+pub mod grade;
+pub mod i18n;
+pub mod lint;
+pub mod loom;
+pub mod manifest;
+pub mod page;
+pub mod play;
+pub mod predict;
+pub mod profile;
+pub mod progress;
+pub mod project;
+pub mod puzzle;
+pub mod quiz;
+pub mod review;
+pub mod runner;
+pub mod scaffold;
+pub mod serve;
+pub mod solution;
+pub mod state;
+pub mod status;
+pub mod ui;
+pub mod wasm;
+pub mod watch;
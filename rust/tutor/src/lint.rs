@@ -0,0 +1,181 @@
+//! `tutor lint`: run clippy on an example and walk through its warnings.
+//!
+//! The `examples/clippy/` chapter is made of programs that compile and work
+//! but are written the long way. `rust/lints.toml` explains each lint they
+//! trigger with a before/after pair; the learner applies the fixes until
+//! clippy is quiet and the program still prints its `// EXPECTED:` output.
+//! Any single-file example can be linted, with or without walkthroughs.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::compiler::{self, Outcome};
+use crate::course::{Course, Example};
+use crate::t;
+
+/// One entry in `lints.toml`.
+#[derive(Debug, Deserialize)]
+pub struct Lint {
+    /// The clippy lint, without the `clippy::` prefix.
+    pub name: String,
+    /// The example it is taught in.
+    pub lesson: String,
+    why: String,
+    before: String,
+    after: String,
+}
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(default, rename = "lint")]
+    lints: Vec<Lint>,
+}
+
+/// A diagnostic in rustc's JSON format.
+#[derive(Deserialize)]
+struct Diagnostic {
+    message: String,
+    code: Option<Code>,
+    level: String,
+    #[serde(default)]
+    spans: Vec<serde_json::Value>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Code {
+    code: String,
+}
+
+/// Reads `<root>/lints.toml`. A missing file just means no walkthroughs.
+pub fn load(root: &Path) -> Result<Vec<Lint>> {
+    let path = root.join("lints.toml");
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let file: File =
+        toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+    Ok(file.lints)
+}
+
+/// Lints `example`, explaining every warning. Returns whether the example
+/// is clean and still prints what it should.
+pub fn lint(course: &Course, example: &Example, lints: &[Lint]) -> Result<bool> {
+    if let Some(manifest) = &example.manifest {
+        bail!(
+            "`tutor lint` works on single-file examples; for {} run `cargo clippy --manifest-path {}`",
+            example.name,
+            manifest.display()
+        );
+    }
+    let taught: Vec<&Lint> = lints
+        .iter()
+        .filter(|lint| lint.lesson == example.name)
+        .collect();
+    println!("{}\n", t!("lint-running", name = example.name.as_str()));
+
+    let out_dir = course.build_dir().join("lint");
+    fs::create_dir_all(&out_dir).with_context(|| format!("cannot create {}", out_dir.display()))?;
+    let mut command = Command::new("clippy-driver");
+    command
+        .arg("--edition=2021")
+        .arg("--error-format=json")
+        .arg("--json=diagnostic-rendered-ansi")
+        .arg("--emit=metadata")
+        .arg("--out-dir")
+        .arg(&out_dir);
+    // Switch on the lessons' lints, including those clippy leaves off.
+    for lint in &taught {
+        command.arg("-W").arg(format!("clippy::{}", lint.name));
+    }
+    let output = command.arg(&example.path).output().context(
+        "failed to launch clippy-driver; install clippy with `rustup component add clippy`",
+    )?;
+    let diagnostics: Vec<Diagnostic> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .filter(|diagnostic: &Diagnostic| !diagnostic.spans.is_empty())
+        .collect();
+
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.level == "error")
+    {
+        for diagnostic in &diagnostics {
+            eprint!("{}", diagnostic.rendered.as_deref().unwrap_or_default());
+        }
+        println!("{}", t!("lint-compile-error", name = example.name.as_str()));
+        return Ok(false);
+    }
+
+    // Group the warnings by lint, in the order they first appear.
+    let mut groups: Vec<(String, Vec<&Diagnostic>)> = Vec::new();
+    for diagnostic in &diagnostics {
+        let name = diagnostic
+            .code
+            .as_ref()
+            .map_or_else(|| diagnostic.message.clone(), |code| code.code.clone());
+        match groups.iter_mut().find(|(known, _)| *known == name) {
+            Some((_, group)) => group.push(diagnostic),
+            None => groups.push((name, vec![diagnostic])),
+        }
+    }
+
+    if groups.is_empty() {
+        let outcome = compiler::build_and_run(example, &course.build_dir())?;
+        if let Outcome::WrongOutput { expected, .. } = &outcome {
+            println!(
+                "{}\n\n{expected}",
+                t!("lint-wrong-output", name = example.name.as_str())
+            );
+            return Ok(false);
+        }
+        if !outcome.passed() {
+            println!("{}", t!("lint-broken", name = example.name.as_str()));
+            return Ok(false);
+        }
+        println!("{}", t!("lint-clean", name = example.name.as_str()));
+        return Ok(true);
+    }
+
+    for (number, (name, group)) in groups.iter().enumerate() {
+        let places = t!("lint-places", count = group.len());
+        println!("── {}. {name} ({places}) ──\n", number + 1);
+        for diagnostic in group {
+            eprint!("{}", diagnostic.rendered.as_deref().unwrap_or_default());
+        }
+        let short = name.strip_prefix("clippy::").unwrap_or(name);
+        if let Some(lint) = taught.iter().find(|lint| lint.name == short) {
+            println!("💡 {}\n", lint.why.trim());
+            println!("   {}", t!("lint-before"));
+            print_code(&lint.before);
+            println!("   {}", t!("lint-after"));
+            print_code(&lint.after);
+            println!();
+        }
+    }
+    let warnings: usize = groups.iter().map(|(_, group)| group.len()).sum();
+    println!(
+        "{}",
+        t!(
+            "lint-left",
+            warnings = warnings,
+            lints = groups.len(),
+            path = example.path.display().to_string(),
+            name = example.name.as_str()
+        )
+    );
+    Ok(false)
+}
+
+fn print_code(code: &str) {
+    for line in code.trim_matches('\n').lines() {
+        println!("     {line}");
+    }
+}
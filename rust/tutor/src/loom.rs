@@ -0,0 +1,72 @@
+//! Model checking an example's concurrency with [loom](https://docs.rs/loom).
+//!
+//! A loom test runs its body inside `loom::model`, which replaces the
+//! example's threads and atomics with loom's and runs the body once for
+//! every interleaving of its threads that can change the outcome. Such
+//! tests only exist when built with `--cfg loom`, which is what switches
+//! the example over to loom's types (see `examples/concurrency/07_loom.rs`),
+//! so a normal `cargo test` skips them. `tutor verify` runs them after the
+//! example itself.
+//!
+//! `--cfg loom` applies to every crate in the build, so the models are
+//! built into a target directory of their own rather than rebuilding the
+//! chapter's usual one.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::course::{file_stem, Example};
+
+/// Whether `example` has loom tests: it is part of a cargo package (loom is
+/// a crate) and calls `loom::model`.
+pub fn has_models(example: &Example) -> Result<bool> {
+    if example.manifest.is_none() {
+        return Ok(false);
+    }
+    let source = fs::read_to_string(&example.path)
+        .with_context(|| format!("cannot read {}", example.path.display()))?;
+    Ok(source.contains("loom::model("))
+}
+
+/// Builds `example`'s tests with `--cfg loom` under `build_dir` and runs
+/// them. Returns the names of the tests that passed, or else what the tests
+/// printed (or the compiler's diagnostics, if they didn't build).
+pub fn check(example: &Example, build_dir: &Path) -> Result<Result<Vec<String>, String>> {
+    let Some(manifest) = &example.manifest else {
+        return Ok(Ok(Vec::new()));
+    };
+    let bin = file_stem(&example.path)?;
+    let output = Command::new("cargo")
+        // Exploring every interleaving is slow without optimizations
+        .args(["test", "--release", "--color=always"])
+        .arg("--manifest-path")
+        .arg(manifest)
+        .arg("--target-dir")
+        .arg(build_dir.join("loom"))
+        .args(["--bin", &bin])
+        .env("RUSTFLAGS", "--cfg loom")
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if output.status.success() {
+        Ok(Ok(passed_tests(&stdout)))
+    } else if stdout.is_empty() {
+        // The tests didn't build; the diagnostics are on stderr
+        Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()))
+    } else {
+        Ok(Err(stdout))
+    }
+}
+
+/// The names in libtest's `test <name> ... ok` lines.
+pub(crate) fn passed_tests(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("test ")?.strip_suffix(" ... ok"))
+        .map(|name| name.trim_end_matches(" - should panic").to_string())
+        .collect()
+}
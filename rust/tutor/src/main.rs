@@ -0,0 +1,1552 @@
+//! `tutor` turns the files in `rust/examples/` into an interactive course.
+//!
+//! ```text
+//! tutor run [EXAMPLE]   compile and run one example (default: the current one)
+//!     --broken           show the compiler errors of the example's broken variant
+//! tutor next            move on to the next example and run it
+//!     --ignore-prerequisites  start even if lessons it builds on aren't done
+//! tutor verify [EXAMPLE] compile and run every example (or one), in order,
+//!                        and model check those with loom tests
+//!     --all              keep going after a failure and list every failing example
+//!     --all-features-matrix  build each package with every combination of its features
+//! tutor record [EXAMPLE] run every example (or one) and save its output in golden/
+//! tutor check-output [EXAMPLE] compare every example's output with its golden file
+//! tutor check [EXERCISE] check exercises against their hidden tests
+//! tutor watch [EXERCISE] re-check the active exercise every time you save
+//! tutor status           show how much of each chapter you've completed
+//! tutor ui               browse the lessons, run them, and read hints on one screen
+//! tutor serve            read the lessons and your progress in a browser
+//!     --port <PORT>      where to listen on localhost (default: 4000)
+//! tutor export           zip up the lessons, your work, and your progress, with web pages
+//!     --output <FILE>    where to write it (default: learn-rust.zip)
+//! tutor import <BUNDLE>  carry on from an exported bundle on this machine
+//!     --progress-only    merge the progress, but leave the exercise files alone
+//! tutor grade            check every exercise and score every quiz, for a teacher
+//!     --answers <FILE>   quiz answers to score (default: the latest attempts)
+//!     --progress <FILE>  the learner's progress.json, for the hints they used
+//!     --json <FILE>      also write the report as JSON
+//!     --html <FILE>      also write the report as a web page
+//! tutor hint [EXERCISE]  reveal the next hint for an exercise
+//! tutor solution <EXERCISE> diff your attempt against the reference solution
+//!     --apply            replace your attempt with the solution (it is saved first)
+//! tutor errors [CODE]  explain a compiler error code and where it's taught
+//! tutor explain [EXAMPLE] read an example with explanations next to its code
+//! tutor borrows [FILE]  draw when each variable is created, borrowed, moved, and dropped
+//!     --function <NAME>  draw only this function
+//! tutor lint [EXAMPLE] run clippy on an example and walk through its warnings
+//! tutor compare [TOPIC]  show a lesson's Rust, Go, and Java versions side by side
+//! tutor equivalence [TOPIC] check that a lesson's versions all print the same
+//! tutor quiz [CHAPTER]   answer a few questions about a chapter
+//! tutor review           go over the quiz questions and exercises due for review today
+//!     --list             show what is due, chapter by chapter, instead
+//! tutor predict [SNIPPET] guess what a snippet prints, then run it
+//! tutor play             write any code in your editor, then run it
+//!     --template <EXAMPLE>  start from a copy of an example
+//!     --resume           reopen the playground as you left it
+//! tutor puzzle [PUZZLE] fix a program that doesn't compile, under rules
+//!     --hint             show a hint for the puzzle
+//! tutor project [PROJECT] build a bigger program stage by stage
+//!     --stage <N>        check only stage N
+//! tutor fuzz [TARGET]    replay a fuzz target's stored inputs, then fuzz it
+//!     --seconds <N>      how long to fuzz for (0: only replay)
+//! tutor bench [FILTER]   run the criterion benchmarks and compare with baselines
+//!     --save-baseline    make this run the new committed baseline
+//! tutor profile <EXAMPLE> [ARGS]  sample an optimized build into a flamegraph,
+//!                        then its rewrite, and compare their times
+//! tutor docs [PACKAGE]  build a package's API docs with rustdoc and open them
+//!                        (default: learn-rust-core)
+//!     --no-open          only build them, and say where they are
+//! tutor build [CHAPTER]  build a WebAssembly chapter with wasm-pack and test it in Node
+//! tutor new-lesson <CHAPTER> <NAME>  add the files for a new lesson, full of TODOs
+//!     --title <TITLE>    the title in lessons.toml (default: NAME as a sentence)
+//!     --difficulty <D>   beginner, intermediate, or advanced
+//! ```
+//!
+//! Every command takes `--lang <LANG>` (or `LEARN_RUST_LANG`) to talk in
+//! another language, falling back to English wherever there is no
+//! translation; see `i18n.rs`.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+
+use tutor::{
+    achievements, bench, borrows, checker, compare, compiler, course, docs, equivalence, errors,
+    exercise, explain, export, features, fuzz, golden, grade, i18n, lint, loom, manifest, play,
+    predict, profile, progress, project, puzzle, quiz, review, runner, scaffold, serve, solution,
+    state, status, ui, wasm, watch,
+};
+
+use checker::Report;
+use compiler::Outcome;
+use course::Course;
+use exercise::Exercise;
+use progress::{Kind, Progress};
+use tutor::t;
+
+#[derive(Parser)]
+#[command(
+    name = "tutor",
+    version,
+    about = "Walk through the Rust examples one step at a time"
+)]
+struct Cli {
+    /// Path to the `rust/` directory of this repository.
+    #[arg(long, global = true, env = "LEARN_RUST_ROOT")]
+    root: Option<PathBuf>,
+
+    /// The language to talk in, e.g. `es`. Lessons, hints, and quizzes
+    /// without a translation are shown in English.
+    #[arg(long, global = true, env = "LEARN_RUST_LANG", default_value = i18n::ENGLISH)]
+    lang: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compile and run an example (by name, number, or the current one).
+    Run {
+        example: Option<String>,
+        /// Compile the example's `feature = "broken"` variant to study its errors.
+        #[arg(long)]
+        broken: bool,
+        /// Run it even if lessons it requires (see `lessons.toml`) aren't complete.
+        #[arg(long)]
+        ignore_prerequisites: bool,
+    },
+    /// Advance to the next example and run it.
+    Next {
+        /// Run it even if lessons it requires (see `lessons.toml`) aren't complete.
+        #[arg(long)]
+        ignore_prerequisites: bool,
+    },
+    /// Compile and run every example in order, stopping at the first failure,
+    /// and check the output of those with an `// EXPECTED:` block. Examples
+    /// with loom tests also have those run.
+    Verify {
+        /// Verify only this example, e.g. `concurrency/loom`.
+        example: Option<String>,
+        /// Don't stop at the first failure; report every failing example.
+        #[arg(long)]
+        all: bool,
+        /// Instead of running examples, build every package that has cargo
+        /// features (or only those in EXAMPLE's chapter) once with each
+        /// combination of them.
+        #[arg(long)]
+        all_features_matrix: bool,
+    },
+    /// Run every example (or one) and save what it prints in
+    /// `golden/<example>.txt`, after normalizing timings and addresses.
+    Record { example: Option<String> },
+    /// Run every example (or one) and show where its output differs from
+    /// its golden file.
+    CheckOutput { example: Option<String> },
+    /// Check one exercise (or all of them) against its hidden tests.
+    Check { exercise: Option<String> },
+    /// Re-check the active exercise whenever a file under `exercises/` changes.
+    Watch { exercise: Option<String> },
+    /// Show completion per chapter.
+    Status,
+    /// Browse the lessons with their source, output, and hints side by
+    /// side, running and checking them from the keyboard.
+    Ui,
+    /// Serve the lessons, your progress, and your quiz results as web
+    /// pages on localhost.
+    Serve {
+        /// The port to listen on.
+        #[arg(long, default_value_t = 4000)]
+        port: u16,
+    },
+    /// Write the lessons, quizzes, recorded outputs, your exercise files,
+    /// and your progress into one zip, with the `serve` pages in it to
+    /// open in any browser: to hand in, or to carry to another machine.
+    Export {
+        #[arg(long, default_value = "learn-rust.zip")]
+        output: PathBuf,
+    },
+    /// Merge the progress in a bundle from `tutor export` into yours and
+    /// put its exercise files in place, saving any file it replaces.
+    Import {
+        bundle: PathBuf,
+        /// Merge the progress only, leaving the exercise files as they are.
+        #[arg(long)]
+        progress_only: bool,
+    },
+    /// Check every exercise and score every quiz without asking anything,
+    /// and give each chapter a score out of 100.
+    Grade {
+        /// A TOML file with each quiz's answers, in question order (default:
+        /// score each quiz from the learner's latest attempt).
+        #[arg(long)]
+        answers: Option<PathBuf>,
+        /// The learner's `progress.json`, if it isn't this machine's own.
+        #[arg(long)]
+        progress: Option<PathBuf>,
+        /// Also write the report to this file as JSON.
+        #[arg(long)]
+        json: Option<PathBuf>,
+        /// Also write the report to this file as an HTML page.
+        #[arg(long)]
+        html: Option<PathBuf>,
+    },
+    /// Reveal the next hint for an exercise (default: the first unfinished one).
+    Hint { exercise: Option<String> },
+    /// Show how your attempt at an exercise differs from its reference
+    /// solution.
+    Solution {
+        exercise: String,
+        /// Replace your attempt with the solution. Your attempt is saved
+        /// under `target/tutor/attempts/` first.
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Explain a compiler error code such as E0502 (with no code, list the
+    /// codes that have explanations).
+    Errors { code: Option<String> },
+    /// Show an example's source with its explanations next to the lines
+    /// they describe (default: the current example).
+    Explain { example: Option<String> },
+    /// Draw a timeline of when each variable in a file's functions is
+    /// created, borrowed, moved, and dropped (the file can also be an
+    /// example's name; default: the current example).
+    Borrows {
+        file: Option<String>,
+        /// Draw only this function.
+        #[arg(long)]
+        function: Option<String>,
+    },
+    /// Run clippy on an example and explain how to fix each warning
+    /// (default: the current example).
+    Lint { example: Option<String> },
+    /// Show the Rust, Go, and Java versions of a lesson side by side
+    /// (with no topic, list the topics).
+    Compare {
+        topic: Option<String>,
+        /// How many characters wide the output may be.
+        #[arg(long, env = "COLUMNS", default_value_t = 120)]
+        width: usize,
+    },
+    /// Run the Rust, Go, and Java versions of every lesson that has more
+    /// than one (or of one topic) and check that they print the same.
+    Equivalence { topic: Option<String> },
+    /// Take a chapter's quiz (with no chapter, list the quizzes).
+    Quiz {
+        chapter: Option<String>,
+        /// Ask at most this many questions.
+        #[arg(long)]
+        count: Option<usize>,
+        /// Shuffle the questions the same way every time.
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Go over the quiz questions and finished exercises that are due for
+    /// review today, spaced out further each time you remember them.
+    Review {
+        /// Review at most this many items.
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+        /// Show how many items each chapter has and how many are due,
+        /// without reviewing any.
+        #[arg(long)]
+        list: bool,
+        /// Shuffle the choices the same way every time.
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Guess what a snippet from the examples prints, then see if you were
+    /// right (default: a random snippet).
+    Predict { snippet: Option<String> },
+    /// Open a scratch file in $EDITOR, then compile and run it every time
+    /// the editor closes.
+    Play {
+        /// Start from a copy of this example instead of an empty `main`.
+        #[arg(long, conflicts_with = "resume")]
+        template: Option<String>,
+        /// Reopen the playground as you left it last time.
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Check a borrow-checker puzzle (with no puzzle, list them).
+    Puzzle {
+        puzzle: Option<String>,
+        /// Show the puzzle's hint instead of checking it.
+        #[arg(long)]
+        hint: bool,
+    },
+    /// Check a project's stages, in order, up to the first unfinished one
+    /// (with no project, list them).
+    Project {
+        project: Option<String>,
+        /// Check only this stage.
+        #[arg(long)]
+        stage: Option<usize>,
+    },
+    /// Replay the stored inputs of a fuzz target (default: every target),
+    /// then fuzz it for a while if `cargo fuzz` is installed.
+    Fuzz {
+        target: Option<String>,
+        /// How many seconds to fuzz each target for; 0 only replays.
+        #[arg(long, default_value_t = 30)]
+        seconds: u64,
+    },
+    /// Run the criterion benchmarks (only those whose id contains the
+    /// filter, if given) and show them next to the committed baselines.
+    Bench {
+        filter: Option<String>,
+        /// Write this run's times to `baselines.json`.
+        #[arg(long)]
+        save_baseline: bool,
+    },
+    /// Build an example with optimizations, run it under a sampling
+    /// profiler, and write a flamegraph of it; then do the same for the
+    /// rewrite its chapter names, and compare their times.
+    Profile {
+        example: String,
+        /// Arguments for the program, instead of the ones its chapter gives.
+        args: Vec<String>,
+    },
+    /// Build the API documentation of a package in the workspace with
+    /// rustdoc, failing on a broken link, and open it in a browser.
+    Docs {
+        /// The cargo package, e.g. `profiling-examples`.
+        #[arg(default_value = docs::DEFAULT_PACKAGE)]
+        package: String,
+        /// Build the docs without opening them.
+        #[arg(long)]
+        no_open: bool,
+    },
+    /// Build a WebAssembly chapter (default: every one) for the browser
+    /// with wasm-pack, then run its tests as WebAssembly in Node.
+    Build { chapter: Option<String> },
+    /// Add a lesson to a chapter (a new chapter, if none has that name):
+    /// the next numbered example, a matching exercise and its hints, and
+    /// its entry in `lessons.toml`, each with TODOs to fill in.
+    NewLesson {
+        chapter: String,
+        /// The file name, without the number, e.g. `pattern_guards`.
+        name: String,
+        /// The lesson's title (default: the name as a sentence).
+        #[arg(long)]
+        title: Option<String>,
+        /// beginner, intermediate, or advanced (default: that of the
+        /// chapter's last lesson).
+        #[arg(long)]
+        difficulty: Option<manifest::Difficulty>,
+    },
+}
+
+fn main() -> ExitCode {
+    match try_main() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns `Ok(false)` when an example failed, so the process exits non-zero.
+fn try_main() -> Result<bool> {
+    let cli = Cli::parse();
+    i18n::set(&cli.lang)?;
+    let root = cli
+        .root
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."));
+    let course = Course::discover(&root)?;
+    let mut progress = Progress::load()?;
+
+    let passed = match cli.command {
+        Command::Run {
+            example,
+            broken,
+            ignore_prerequisites,
+        } => {
+            let index = match example {
+                Some(query) => find(&course, &query)?,
+                None => current_index(&course)?,
+            };
+            if broken {
+                return show_broken(&course, index);
+            }
+            if !ignore_prerequisites && !prerequisites_met(&course, index, &progress) {
+                return Ok(false);
+            }
+            state::save_current(&course.examples[index].name)?;
+            run_one(&course, index, &mut progress)?
+        }
+        Command::Next {
+            ignore_prerequisites,
+        } => {
+            let index = match state::load_current()? {
+                Some(name) => match course.find(&name) {
+                    Some(index) => index + 1,
+                    None => 0,
+                },
+                None => 0,
+            };
+            if index >= course.examples.len() {
+                println!("{}", t!("course-finished"));
+                return Ok(true);
+            }
+            if !ignore_prerequisites && !prerequisites_met(&course, index, &progress) {
+                return Ok(false);
+            }
+            state::save_current(&course.examples[index].name)?;
+            run_one(&course, index, &mut progress)?
+        }
+        Command::Verify {
+            example,
+            all,
+            all_features_matrix: true,
+        } => verify_feature_matrix(&course, example.as_deref(), all)?,
+        Command::Verify { example, all, .. } => {
+            let examples = match example {
+                Some(query) => std::slice::from_ref(&course.examples[find(&course, &query)?]),
+                None => &course.examples[..],
+            };
+            verify_all(&course, examples, &mut progress, all)?
+        }
+        Command::Record { example } => {
+            let examples = match &example {
+                Some(query) => std::slice::from_ref(&course.examples[find(&course, query)?]),
+                None => &course.examples[..],
+            };
+            golden::record(&course, examples, example.is_none())?
+        }
+        Command::CheckOutput { example } => {
+            let examples = match &example {
+                Some(query) => std::slice::from_ref(&course.examples[find(&course, query)?]),
+                None => &course.examples[..],
+            };
+            golden::check(&course, examples, example.is_none())?
+        }
+        Command::Check { exercise } => {
+            let exercises = exercise::discover(&course.root)?;
+            match exercise {
+                Some(name) => match exercise::find(&exercises, &name) {
+                    Some(exercise) => check_one(&course, exercise, &mut progress)?,
+                    None => bail!("no exercise named `{name}`"),
+                },
+                None => check_all(&course, &exercises, &mut progress)?,
+            }
+        }
+        Command::Watch { exercise } => {
+            let exercises = exercise::discover(&course.root)?;
+            if let Some(name) = &exercise {
+                if exercise::find(&exercises, name).is_none() {
+                    bail!("no exercise named `{name}`");
+                }
+            }
+            watch::watch(&course, &exercises, exercise.as_deref(), &mut progress)?
+        }
+        Command::Status => {
+            let exercises = exercise::discover(&course.root)?;
+            status::print(&course, &exercises, &progress);
+            true
+        }
+        Command::Ui => {
+            let exercises = exercise::discover(&course.root)?;
+            ui::run(&course, &exercises, &mut progress)?
+        }
+        Command::Serve { port } => {
+            let exercises = exercise::discover(&course.root)?;
+            return serve::serve(course, exercises, port);
+        }
+        Command::Export { output } => {
+            let exercises = exercise::discover(&course.root)?;
+            let info = export::export(course, exercises, &progress, &output)?;
+            println!(
+                "📦 Wrote {}: {}/{} examples and {}/{} exercises done, with your exercise files.",
+                output.display(),
+                info.examples_done,
+                info.examples,
+                info.exercises_done,
+                info.exercises
+            );
+            println!(
+                "Unzip it and open site/index.html to read it in a browser, or run \
+                 `tutor import {}` on another machine to carry on there.",
+                output.display()
+            );
+            return Ok(true);
+        }
+        Command::Import {
+            bundle,
+            progress_only,
+        } => {
+            let exercises = exercise::discover(&course.root)?;
+            let contents = export::read(&bundle)?;
+            println!(
+                "📥 {}: {}/{} examples and {}/{} exercises done when it was exported.",
+                bundle.display(),
+                contents.info.examples_done,
+                contents.info.examples,
+                contents.info.exercises_done,
+                contents.info.exercises
+            );
+            let imported = export::import(
+                &course,
+                &exercises,
+                contents,
+                progress_only,
+                &mut progress,
+                &course.build_dir().join("attempts"),
+            )?;
+            println!(
+                "✅ Merged its progress: {} newly done.",
+                imported.newly_done
+            );
+            if let Some(backup) = &imported.progress_backup {
+                println!(
+                    "   Your progress before the import is saved at {}.",
+                    backup.display()
+                );
+            }
+            for (path, backup) in &imported.replaced {
+                println!(
+                    "📝 Replaced {} with the bundle's. Yours is saved at {}.",
+                    path.display(),
+                    backup.display()
+                );
+            }
+            true
+        }
+        Command::Grade {
+            answers,
+            progress: learner,
+            json,
+            html,
+        } => {
+            let exercises = exercise::discover(&course.root)?;
+            let learner = match learner {
+                Some(path) if !path.is_file() => bail!("there is no {}", path.display()),
+                Some(path) => Progress::load_from(&path)?,
+                None => Progress::load()?,
+            };
+            let report = grade::grade(&course, &exercises, &learner, answers.as_deref())?;
+            grade::print(&report);
+            grade::export(&report, json.as_deref(), html.as_deref())?;
+            true
+        }
+        Command::Hint { exercise } => {
+            let exercises = exercise::discover(&course.root)?;
+            let exercise = match exercise {
+                Some(name) => match exercise::find(&exercises, &name) {
+                    Some(exercise) => exercise,
+                    None => bail!("no exercise named `{name}`"),
+                },
+                None => match exercises
+                    .iter()
+                    .find(|exercise| !progress.is_completed(Kind::Exercise, &exercise.name))
+                {
+                    Some(exercise) => exercise,
+                    None => bail!("every exercise is complete; name one to see its hints"),
+                },
+            };
+            show_hint(exercise, &mut progress)
+        }
+        Command::Solution { exercise, apply } => {
+            let exercises = exercise::discover(&course.root)?;
+            let Some(exercise) = exercise::find(&exercises, &exercise) else {
+                bail!("no exercise named `{exercise}`");
+            };
+            let differs = solution::print(&course.root, exercise)?;
+            if apply && differs {
+                let backup =
+                    solution::apply(&course.root, exercise, &course.build_dir().join("attempts"))?;
+                println!(
+                    "\n{}",
+                    t!(
+                        "solution-applied",
+                        path = exercise.path.display().to_string(),
+                        backup = backup.display().to_string()
+                    )
+                );
+            } else if differs {
+                println!(
+                    "\n{}",
+                    t!("solution-apply-advice", name = exercise.name.as_str())
+                );
+            }
+            true
+        }
+        Command::Errors { code } => {
+            let known = errors::load(&course.root)?;
+            match code {
+                Some(code) => match errors::find(&known, &code) {
+                    Some(error) => errors::print(&course, error),
+                    None => bail!(
+                        "there are no notes on `{code}` yet; `rustc --explain {code}` has the official explanation"
+                    ),
+                },
+                None => {
+                    println!("{}\n", t!("errors-list-title"));
+                    for error in &known {
+                        println!("  {}  {}", error.code, error.title);
+                    }
+                    println!("\n{}", t!("errors-list-advice"));
+                }
+            }
+            true
+        }
+        Command::Explain { example } => {
+            let index = match example {
+                Some(query) => find(&course, &query)?,
+                None => current_index(&course)?,
+            };
+            explain::print(&course.examples[index])?;
+            true
+        }
+        Command::Borrows { file, function } => {
+            let path = match file {
+                Some(file) if Path::new(&file).is_file() => PathBuf::from(file),
+                Some(query) => course.examples[find(&course, &query)?].path.clone(),
+                None => course.examples[current_index(&course)?].path.clone(),
+            };
+            borrows::print(&path, function.as_deref())?;
+            true
+        }
+        Command::Lint { example } => {
+            let index = match example {
+                Some(query) => find(&course, &query)?,
+                None => current_index(&course)?,
+            };
+            let example = &course.examples[index];
+            let clean = lint::lint(&course, example, &lint::load(&course.root)?)?;
+            progress.record_attempt(Kind::Example, &example.name, clean);
+            clean
+        }
+        Command::Compare { topic, width } => {
+            let topics = compare::load_topics(&course.root)?;
+            match topic {
+                Some(query) => match compare::find(&topics, &query) {
+                    Some(topic) => compare::print(topic, width)?,
+                    None => bail!("no topic named `{query}`; run `tutor compare` to list them"),
+                },
+                None => {
+                    println!("{}\n", t!("compare-list-title"));
+                    for topic in &topics {
+                        println!("  {:<14} {}", topic.name, topic.title);
+                    }
+                    println!("\n{}", t!("compare-list-advice"));
+                }
+            }
+            true
+        }
+        Command::Equivalence { topic } => {
+            let topics = compare::load_topics(&course.root)?;
+            let topics = match &topic {
+                Some(query) => match compare::find(&topics, query) {
+                    Some(topic) => std::slice::from_ref(topic),
+                    None => bail!("no topic named `{query}`; run `tutor compare` to list them"),
+                },
+                None => &topics[..],
+            };
+            equivalence::check(&course, topics)?
+        }
+        Command::Quiz {
+            chapter,
+            count,
+            seed,
+        } => match chapter {
+            Some(chapter) => {
+                let bank = quiz::load(&course.root, &chapter)?;
+                let mut rng = match seed {
+                    Some(seed) => fastrand::Rng::with_seed(seed),
+                    None => fastrand::Rng::new(),
+                };
+                let score = quiz::run(&bank, count, &mut rng)?;
+                progress.record_quiz(&chapter, score.correct, score.total);
+                true
+            }
+            None => {
+                println!("{}\n", t!("quiz-list"));
+                for chapter in quiz::chapters(&course.root)? {
+                    match progress.quizzes.get(&chapter) {
+                        Some(record) => println!(
+                            "  {chapter:<16} {}",
+                            t!("quiz-best", percent = record.best_percent)
+                        ),
+                        None => println!("  {chapter:<16} {}", t!("quiz-not-taken")),
+                    }
+                }
+                println!("\n{}", t!("quiz-list-advice"));
+                true
+            }
+        },
+        Command::Review { count, list, seed } => {
+            let exercises = exercise::discover(&course.root)?;
+            let cards = review::cards(&course.root, &exercises, &progress)?;
+            if list {
+                review::print_schedule(&cards);
+            } else {
+                let mut rng = match seed {
+                    Some(seed) => fastrand::Rng::with_seed(seed),
+                    None => fastrand::Rng::new(),
+                };
+                review::review(&cards, &mut progress, count, &mut rng)?;
+            }
+            true
+        }
+        Command::Play { template, resume } => {
+            let template = match template {
+                Some(query) => Some(&course.examples[find(&course, &query)?]),
+                None => None,
+            };
+            play::play(&course, template, resume)?
+        }
+        Command::Predict { snippet } => {
+            let snippets = predict::load(&course.root)?;
+            let chosen = match &snippet {
+                Some(name) => snippets.iter().find(|snippet| &snippet.name == name),
+                None => fastrand::choice(&snippets),
+            };
+            match chosen {
+                Some(snippet) => predict::play(&course, snippet)?,
+                None => {
+                    let names: Vec<&str> = snippets.iter().map(|s| s.name.as_str()).collect();
+                    bail!(
+                        "no snippet named `{}`; try one of: {}",
+                        snippet.unwrap_or_default(),
+                        names.join(", ")
+                    );
+                }
+            }
+        }
+        Command::Puzzle { puzzle, hint } => {
+            let puzzles = puzzle::discover(&course.root)?;
+            match puzzle {
+                Some(name) => {
+                    let Some(puzzle) = puzzles.iter().find(|puzzle| puzzle.name == name) else {
+                        bail!("no puzzle named `{name}`; run `tutor puzzle` to list them");
+                    };
+                    if hint {
+                        match &puzzle.rules.hint {
+                            Some(hint) => println!("💡 {hint}"),
+                            None => {
+                                println!("{}", t!("puzzle-no-hint", name = puzzle.name.as_str()))
+                            }
+                        }
+                        return Ok(true);
+                    }
+                    let solved = puzzle::check(&course, puzzle, &errors::load(&course.root)?)?;
+                    progress.record_attempt(Kind::Puzzle, &puzzle.name, solved);
+                    solved
+                }
+                None => {
+                    println!("{}\n", t!("puzzle-list-title"));
+                    for puzzle in &puzzles {
+                        let mark = if progress.is_completed(Kind::Puzzle, &puzzle.name) {
+                            "✅"
+                        } else {
+                            "⬜"
+                        };
+                        println!("{mark} {}", puzzle.name);
+                        for rule in puzzle.rules.describe() {
+                            println!("     {rule}");
+                        }
+                    }
+                    println!("\n{}", t!("puzzle-list-advice"));
+                    true
+                }
+            }
+        }
+        Command::Project { project, stage } => {
+            let projects = project::discover(&course.root)?;
+            match project {
+                Some(name) => match project::find(&projects, &name) {
+                    Some(project) => check_project(&course, project, stage, &mut progress)?,
+                    None => bail!("no project named `{name}`; run `tutor project` to list them"),
+                },
+                None => {
+                    println!("{}\n", t!("project-list-title"));
+                    for project in &projects {
+                        let done = (1..=project.stages.len())
+                            .filter(|&number| {
+                                progress
+                                    .is_completed(Kind::ProjectStage, &project.stage_key(number))
+                            })
+                            .count();
+                        println!(
+                            "  {:<12} {}  {}",
+                            project.name,
+                            t!("project-stages", done = done, total = project.stages.len()),
+                            project.title
+                        );
+                        println!("  {:<12} {}", "", project.summary);
+                    }
+                    println!("\n{}", t!("project-list-advice"));
+                    true
+                }
+            }
+        }
+        Command::Fuzz { target, seconds } => {
+            let targets = fuzz::discover(&course.root)?;
+            let chosen: Vec<&fuzz::Target> = match &target {
+                Some(name) => match fuzz::find(&targets, name) {
+                    Some(target) => vec![target],
+                    None => bail!("no fuzz target named `{name}`"),
+                },
+                None => targets.iter().collect(),
+            };
+            let mut passed = true;
+            for target in chosen {
+                passed &= fuzz_one(&course, target, seconds)?;
+            }
+            passed
+        }
+        Command::Bench {
+            filter,
+            save_baseline,
+        } => {
+            let mut passed = true;
+            for suite in bench::discover(&course.root)? {
+                passed &= bench_one(&course, &suite, filter.as_deref(), save_baseline)?;
+            }
+            passed
+        }
+        Command::Profile { example, args } => {
+            let index = find(&course, &example)?;
+            profile_one(&course, &course.examples[index], &args)?
+        }
+        Command::Docs { package, no_open } => docs_one(&course, &package, no_open)?,
+        Command::Build { chapter } => {
+            let packages = wasm::discover(&course.root)?;
+            let chosen: Vec<&wasm::Package> = match &chapter {
+                Some(name) => match wasm::find(&packages, name) {
+                    Some(package) => vec![package],
+                    None => bail!("no WebAssembly chapter named `{name}`"),
+                },
+                None => packages.iter().collect(),
+            };
+            if !chosen.is_empty() && !wasm::wasm_pack_available() {
+                println!(
+                    "Building for the browser needs the wasm32 target and wasm-pack:\n\n    \
+                     rustup target add wasm32-unknown-unknown\n    cargo install wasm-pack\n\n\
+                     and Node to run the tests. Then run `tutor build` again."
+                );
+                false
+            } else {
+                let mut passed = true;
+                for package in chosen {
+                    passed &= build_one(package)?;
+                }
+                passed
+            }
+        }
+        Command::NewLesson {
+            chapter,
+            name,
+            title,
+            difficulty,
+        } => {
+            let request = scaffold::Request {
+                chapter: &chapter,
+                name: &name,
+                title: title.as_deref(),
+                difficulty,
+            };
+            let scaffold = scaffold::new_lesson(&course, &request)?;
+            println!("✨ Added {}:\n", scaffold.id);
+            for (path, created) in &scaffold.files {
+                let verb = if *created { "created" } else { "updated" };
+                println!("   {verb} {}", path.display());
+            }
+            println!(
+                "\nFill in the TODOs, then check the lesson with\n\n    \
+                 cargo run -p tutor -- verify {id}\n    \
+                 cargo run -p checker -- {id}\n    \
+                 cargo run -p tutor -- check {exercise}   # fails until the exercise is solved",
+                id = scaffold.id,
+                exercise = scaffold.exercise,
+            );
+            true
+        }
+    };
+
+    let exercises = exercise::discover(&course.root)?;
+    achievements::announce(&achievements::unlock(&course, &exercises, &mut progress));
+    progress.save()?;
+    Ok(passed)
+}
+
+fn find(course: &Course, query: &str) -> Result<usize> {
+    match course.find(query) {
+        Some(index) => Ok(index),
+        None => bail!("no example named `{query}`"),
+    }
+}
+
+/// The example saved in the state file, or the first one for a fresh start.
+fn current_index(course: &Course) -> Result<usize> {
+    Ok(state::load_current()?
+        .and_then(|name| course.find(&name))
+        .unwrap_or(0))
+}
+
+/// Checks that every lesson the example requires is complete, explaining
+/// what's missing if not.
+fn prerequisites_met(course: &Course, index: usize, progress: &Progress) -> bool {
+    let example = &course.examples[index];
+    let missing: Vec<&String> = course
+        .lessons
+        .requires(&example.name)
+        .iter()
+        .filter(|id| !progress.is_completed(Kind::Example, id))
+        .collect();
+    if missing.is_empty() {
+        return true;
+    }
+    println!(
+        "{}\n",
+        t!("prerequisites-missing", name = example.name.as_str())
+    );
+    for id in missing {
+        match course.lessons.get(id) {
+            Some(lesson) => println!("   - {id} ({})", lesson.title),
+            None => println!("   - {id}"),
+        }
+    }
+    println!("\n{}", t!("prerequisites-advice"));
+    false
+}
+
+fn run_one(course: &Course, index: usize, progress: &mut Progress) -> Result<bool> {
+    let example = &course.examples[index];
+    print!(
+        "▶ [{}/{}] {}",
+        index + 1,
+        course.examples.len(),
+        example.name
+    );
+    match course.lessons.get(&example.name) {
+        Some(lesson) => println!(
+            ": {}\n  {} · {}\n",
+            lesson.title,
+            t!("difficulty", level = lesson.difficulty.to_string()),
+            lesson.tags.join(", ")
+        ),
+        None => println!("\n"),
+    }
+
+    let outcome = compiler::build_and_run(example, &course.build_dir())?;
+    progress.record_attempt(Kind::Example, &example.name, outcome.passed());
+    let compiled = !matches!(outcome, Outcome::CompileError(_));
+    progress.record_build(Kind::Example, &example.name, compiled);
+    match &outcome {
+        Outcome::CompileError(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!("{}", t!("run-compile-error", name = example.name.as_str()));
+        }
+        Outcome::Ran {
+            success,
+            stdout,
+            stderr,
+        } => {
+            print!("{stdout}");
+            eprint!("{stderr}");
+            if *success {
+                println!("\n{}", t!("run-success", name = example.name.as_str()));
+            } else {
+                println!("\n{}", t!("run-failed", name = example.name.as_str()));
+            }
+        }
+        Outcome::TimedOut { stdout, stderr } => {
+            print!("{stdout}");
+            eprint!("{stderr}");
+            println!(
+                "\n{}",
+                t!(
+                    "run-timed-out",
+                    name = example.name.as_str(),
+                    seconds = runner::TIMEOUT.as_secs()
+                )
+            );
+        }
+        Outcome::WrongOutput {
+            expected,
+            stdout,
+            stderr,
+        } => {
+            print!("{stdout}");
+            eprint!("{stderr}");
+            println!(
+                "\n{}\n\n{expected}",
+                t!("run-wrong-output", name = example.name.as_str())
+            );
+        }
+    }
+    Ok(outcome.passed())
+}
+
+fn show_broken(course: &Course, index: usize) -> Result<bool> {
+    let example = &course.examples[index];
+    println!("{}\n", t!("run-broken", name = example.name.as_str()));
+    match compiler::compile_broken(example, &course.build_dir())? {
+        Some(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!("{}", t!("run-broken-errors", name = example.name.as_str()));
+        }
+        None => println!("{}", t!("run-no-broken", name = example.name.as_str())),
+    }
+    Ok(true)
+}
+
+/// Builds and runs `examples`, then runs the loom tests of those that have
+/// them. Stops at the first failure unless `keep_going`, in which case
+/// every failure is listed at the end.
+fn verify_all(
+    course: &Course,
+    examples: &[course::Example],
+    progress: &mut Progress,
+    keep_going: bool,
+) -> Result<bool> {
+    let mut failed = Vec::new();
+    for example in examples {
+        if let Some(problem) = header_problem(course, example)? {
+            println!("❌ {} {problem}", example.name);
+            if !keep_going {
+                return Ok(false);
+            }
+            failed.push(&example.name);
+            continue;
+        }
+        let outcome = compiler::build_and_run(example, &course.build_dir())?;
+        let problem = match outcome {
+            Outcome::Ran { success: true, .. } => match verify_models(course, example)? {
+                Ok(note) => {
+                    progress.mark_completed(Kind::Example, &example.name);
+                    println!("✅ {}{note}", example.name);
+                    continue;
+                }
+                Err(problem) => problem,
+            },
+            Outcome::Ran { stderr, .. } => format!("exited with an error:\n{stderr}"),
+            Outcome::CompileError(diagnostics) => format!("failed to compile:\n{diagnostics}"),
+            Outcome::TimedOut { .. } => format!(
+                "was still running after {} seconds",
+                runner::TIMEOUT.as_secs()
+            ),
+            Outcome::WrongOutput {
+                expected, stdout, ..
+            } => format!(
+                "printed something other than its `// EXPECTED:` block:\n{}",
+                runner::output_diff(&expected, &stdout)
+            ),
+        };
+        println!("❌ {} {problem}", example.name);
+        if !keep_going {
+            return Ok(false);
+        }
+        failed.push(&example.name);
+    }
+
+    if failed.is_empty() {
+        if examples.len() > 1 {
+            println!("\nAll {} examples compile and run.", examples.len());
+        }
+        return Ok(true);
+    }
+    println!("\n{} of {} examples failed:", failed.len(), examples.len());
+    for name in failed {
+        println!("  - {name}");
+    }
+    Ok(false)
+}
+
+fn verify_feature_matrix(course: &Course, chapter: Option<&str>, keep_going: bool) -> Result<bool> {
+    let packages = features::discover(&course.root)?;
+    let chosen: Vec<&features::Package> = match chapter {
+        Some(name) => match features::find(&packages, name) {
+            found if found.is_empty() => bail!("no package with cargo features in `{name}`"),
+            found => found,
+        },
+        None => packages.iter().collect(),
+    };
+
+    let mut failed = Vec::new();
+    let mut builds = 0;
+    for package in chosen {
+        let combinations = package.combinations();
+        println!(
+            "🧮 {}: {} feature combinations",
+            package.name,
+            combinations.len()
+        );
+        let width = combinations
+            .iter()
+            .map(|combination| combination.to_string().len())
+            .max()
+            .unwrap_or(0);
+        for combination in &combinations {
+            builds += 1;
+            let resolved = package.resolve(combination);
+            match features::build(package, combination)? {
+                Ok(()) => println!("✅ {:<width$}  {resolved}", combination.to_string()),
+                Err(diagnostics) => {
+                    eprintln!("{diagnostics}");
+                    println!("❌ {:<width$}  {resolved}", combination.to_string());
+                    if !keep_going {
+                        return Ok(false);
+                    }
+                    failed.push(format!("{} {combination}", package.name));
+                }
+            }
+        }
+        println!();
+    }
+
+    if failed.is_empty() {
+        println!("All {builds} feature combinations build.");
+        return Ok(true);
+    }
+    println!("{} of {builds} feature combinations failed:", failed.len());
+    for name in failed {
+        println!("  - {name}");
+    }
+    Ok(false)
+}
+
+/// What is wrong with the metadata at the top of `example`, if anything.
+/// Its difficulty must also agree with `lessons.toml`.
+fn header_problem(course: &Course, example: &course::Example) -> Result<Option<String>> {
+    let source = std::fs::read_to_string(&example.path)
+        .with_context(|| format!("cannot read {}", example.path.display()))?;
+    let repo = course.root.parent().unwrap_or(&course.root);
+    // `::checker` is the checker crate, not `tutor::checker`.
+    let problems = match ::checker::check_header(&source, repo) {
+        Ok(header) => course
+            .lessons
+            .get(&example.name)
+            .filter(|lesson| lesson.difficulty.to_string() != header.difficulty.as_str())
+            .map(|lesson| {
+                format!(
+                    "`// Difficulty: {}` should be {}, as in lessons.toml",
+                    header.difficulty, lesson.difficulty
+                )
+            })
+            .into_iter()
+            .collect(),
+        Err(problems) => problems,
+    };
+    if problems.is_empty() {
+        return Ok(None);
+    }
+    let list: Vec<String> = problems
+        .iter()
+        .map(|problem| format!("  - {problem}"))
+        .collect();
+    Ok(Some(format!(
+        "has a malformed header (see CONTRIBUTING.md):\n{}",
+        list.join("\n")
+    )))
+}
+
+/// Runs `example`'s loom tests, if it has any. Returns what to add to its
+/// ✅ line, or why it failed.
+fn verify_models(course: &Course, example: &course::Example) -> Result<Result<String, String>> {
+    if !loom::has_models(example)? {
+        return Ok(Ok(String::new()));
+    }
+    println!("🔍 {}: checking its loom models...", example.name);
+    Ok(match loom::check(example, &course.build_dir())? {
+        Ok(passed) => Ok(format!(" (loom tests pass: {})", passed.join(", "))),
+        Err(output) => Err(format!("failed its loom model check:\n{output}")),
+    })
+}
+
+fn check_one(course: &Course, exercise: &Exercise, progress: &mut Progress) -> Result<bool> {
+    println!(
+        "{}\n",
+        t!(
+            "check-title",
+            name = exercise.name.as_str(),
+            topic = exercise.topic.as_str(),
+            path = exercise.path.display().to_string()
+        )
+    );
+
+    let report = checker::check(exercise, &course.build_dir())?;
+    progress.record_attempt(Kind::Exercise, &exercise.name, report.passed());
+    let compiled = !matches!(report, Report::CompileError(_));
+    progress.record_build(Kind::Exercise, &exercise.name, compiled);
+    checker::print_report(exercise, &report, &errors::load(&course.root)?);
+    Ok(report.passed())
+}
+
+fn check_all(course: &Course, exercises: &[Exercise], progress: &mut Progress) -> Result<bool> {
+    let mut done = 0;
+    for exercise in exercises {
+        let report = checker::check(exercise, &course.build_dir())?;
+        match &report {
+            Report::CompileError(_) => {
+                println!("❌ {:<14} {}", exercise.name, t!("check-all-compile-error"))
+            }
+            Report::Tested(results) if report.passed() => {
+                done += 1;
+                progress.mark_completed(Kind::Exercise, &exercise.name);
+                println!(
+                    "✅ {:<14} {}",
+                    exercise.name,
+                    t!("check-all-pass", total = results.len())
+                );
+            }
+            Report::Tested(results) => println!(
+                "❌ {:<14} {}",
+                exercise.name,
+                t!(
+                    "check-all-failing",
+                    failing = report.failures().count(),
+                    total = results.len()
+                )
+            ),
+        }
+    }
+    println!(
+        "\n{}",
+        t!("check-all-summary", done = done, total = exercises.len())
+    );
+    Ok(done == exercises.len())
+}
+
+fn show_hint(exercise: &Exercise, progress: &mut Progress) -> bool {
+    let Some(hints) = &exercise.hints else {
+        println!("{}", t!("hint-none", name = exercise.name.as_str()));
+        return false;
+    };
+    let levels = hints.levels();
+    let labels = [
+        t!("hint-nudge"),
+        t!("hint-explanation"),
+        t!("hint-solution"),
+    ];
+    let already = progress
+        .get(Kind::Exercise, &exercise.name)
+        .map_or(0, |record| record.hints_used);
+    let revealed = progress.reveal_hint(&exercise.name, levels.len());
+
+    println!("{}\n", t!("hint-title", name = exercise.name.as_str()));
+    for (number, (label, (_, text))) in labels.iter().zip(&levels).take(revealed).enumerate() {
+        println!("{}. {label}", number + 1);
+        for line in text.lines() {
+            println!("   {line}");
+        }
+        println!();
+    }
+    if already == levels.len() {
+        println!("{}", t!("hint-all-shown"));
+    } else if revealed < levels.len() {
+        println!("{}", t!("hint-more", name = exercise.name.as_str()));
+    }
+    true
+}
+
+/// Checks stage `only` of `project`, or, without it, every stage in order
+/// until one isn't finished yet.
+fn check_project(
+    course: &Course,
+    project: &project::Project,
+    only: Option<usize>,
+    progress: &mut Progress,
+) -> Result<bool> {
+    let errors = errors::load(&course.root)?;
+    let count = project.stages.len();
+    let stages = match only {
+        Some(number) if (1..=count).contains(&number) => number..=number,
+        Some(number) => bail!("{} has stages 1 to {count}, not {number}", project.name),
+        None => 1..=count,
+    };
+    for number in stages {
+        let report = project::check(project, number, &course.build_dir())?;
+        let passed = report.passed();
+        progress.record_attempt(Kind::ProjectStage, &project.stage_key(number), passed);
+        if only.is_none() && passed {
+            println!(
+                "{}",
+                t!(
+                    "project-stage-passed",
+                    number = number,
+                    title = project.stages[number - 1].title.as_str()
+                )
+            );
+            continue;
+        }
+        if only.is_none() && number > 1 {
+            println!();
+        }
+        project::print_report(project, number, &report, &errors);
+        if !passed {
+            return Ok(false);
+        }
+    }
+    if only.is_none() {
+        println!("\n{}", t!("project-finished", name = project.name.as_str()));
+    }
+    Ok(true)
+}
+
+/// Replays `target`'s stored inputs and, if they all pass, fuzzes it for
+/// `seconds`.
+fn fuzz_one(course: &Course, target: &fuzz::Target, seconds: u64) -> Result<bool> {
+    println!("🐛 {} ({})\n", target.name, target.chapter);
+    let replays = match fuzz::replay(target, &course.build_dir())? {
+        Ok(replays) => replays,
+        Err(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!("❌ {} does not compile.", target.name);
+            return Ok(false);
+        }
+    };
+    for replay in &replays {
+        let input = replay
+            .input
+            .strip_prefix(&target.dir)
+            .unwrap_or(&replay.input);
+        match &replay.crash {
+            None => println!("✅ {}", input.display()),
+            Some(message) => println!("❌ {}\n     {message}", input.display()),
+        }
+    }
+    let crashes = replays
+        .iter()
+        .filter(|replay| replay.crash.is_some())
+        .count();
+    if crashes > 0 {
+        println!(
+            "\n{crashes} of {} stored inputs still crash {}. Fix the code, then run `tutor fuzz` again.",
+            replays.len(),
+            target.name
+        );
+        return Ok(false);
+    }
+    println!(
+        "\n🎉 {} survives all {} stored inputs.",
+        target.name,
+        replays.len()
+    );
+
+    if seconds == 0 {
+        return Ok(true);
+    }
+    if !fuzz::cargo_fuzz_available() {
+        println!(
+            "\nTo go looking for new crashes, install a nightly compiler and cargo-fuzz:\n\n    \
+             rustup toolchain install nightly\n    cargo install cargo-fuzz\n\n\
+             and run `tutor fuzz` again."
+        );
+        return Ok(true);
+    }
+    println!("\n🔎 Fuzzing {} for {seconds}s...\n", target.name);
+    let survived = fuzz::fuzz(target, seconds, &course.build_dir())?;
+    if survived {
+        println!("\n✅ No new crashes in {seconds}s.");
+    } else {
+        println!(
+            "\n❌ The fuzzer found a crash and saved it under {}. Run `tutor fuzz` to replay it.",
+            target.dir.join("artifacts").join(&target.name).display()
+        );
+    }
+    Ok(survived)
+}
+
+/// Runs `suite`'s benchmarks and prints the summary table.
+fn bench_one(
+    course: &Course,
+    suite: &bench::Suite,
+    filter: Option<&str>,
+    save_baseline: bool,
+) -> Result<bool> {
+    println!("⏱️  {} (this takes a minute)\n", suite.chapter);
+    let measurements = match bench::run(suite, filter, &course.build_dir())? {
+        Ok(measurements) => measurements,
+        Err(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!("❌ The {} benchmarks did not run.", suite.chapter);
+            return Ok(false);
+        }
+    };
+    if measurements.is_empty() {
+        println!("No benchmark matched `{}`.", filter.unwrap_or_default());
+        return Ok(true);
+    }
+    let baselines = suite.baselines()?;
+    println!("\n{}", bench::render(&measurements, baselines.as_ref()));
+    match &baselines {
+        Some(baselines) => println!(
+            "Baselines measured on {}; this machine is {}. Changes under {:.0}% are noise.",
+            baselines.machine,
+            bench::this_machine(),
+            bench::NOISE * 100.0
+        ),
+        None => println!("There are no baselines yet; `--save-baseline` records this run."),
+    }
+    if save_baseline {
+        bench::save_baselines(suite, &measurements)?;
+        println!(
+            "💾 Saved as the new baselines in {}.",
+            suite.baselines_path().display()
+        );
+    }
+    Ok(true)
+}
+
+/// Profiles `example`, then its rewrite on the same input, if it has one.
+fn profile_one(course: &Course, example: &course::Example, args: &[String]) -> Result<bool> {
+    let Some(target) = profile::target(example)? else {
+        let mut profiled = Vec::new();
+        for example in &course.examples {
+            if profile::target(example)?.is_some() {
+                profiled.push(example.short_name());
+            }
+        }
+        bail!(
+            "{} has no profile set up; these do: {}",
+            example.name,
+            profiled.join(", ")
+        );
+    };
+    let args = if args.is_empty() { &target.args } else { args };
+    let Some(first) = sample_one(course, example, args)? else {
+        return Ok(false);
+    };
+    let Some(rewrite) = &target.rewrite else {
+        return Ok(true);
+    };
+    let Some(rewrite) = profile::find_rewrite(course, example, rewrite) else {
+        bail!(
+            "{} names {rewrite} as its rewrite, which isn't in its chapter",
+            example.name
+        );
+    };
+    println!();
+    let Some(second) = sample_one(course, rewrite, args)? else {
+        return Ok(false);
+    };
+    let ratio = first.elapsed.as_secs_f64() / second.elapsed.as_secs_f64().max(1e-9);
+    println!(
+        "\n✅ On the same input, {} runs {:.1}x faster than {}.",
+        rewrite.short_name(),
+        ratio,
+        example.short_name()
+    );
+    if first.samples.is_some() && second.samples.is_some() {
+        println!("Open both flamegraphs in a browser to see where the difference went.");
+    }
+    Ok(true)
+}
+
+/// Builds `example` for profiling and runs it on `args`, printing its time
+/// and where its flamegraph is. Returns `None` if it didn't build or run.
+fn sample_one(
+    course: &Course,
+    example: &course::Example,
+    args: &[String],
+) -> Result<Option<profile::Sampled>> {
+    println!("🔥 {}: building with optimizations...", example.name);
+    let binary = match profile::build(example)? {
+        Ok(binary) => binary,
+        Err(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!("❌ {} does not build.", example.name);
+            return Ok(None);
+        }
+    };
+    let flamegraph = profile::flamegraph_path(&course.build_dir(), example)?;
+    println!("   running it under the profiler: {}", args.join(" "));
+    let sampled = match profile::run(&binary, args, &flamegraph)? {
+        Ok(sampled) => sampled,
+        Err(stderr) => {
+            eprintln!("{stderr}");
+            println!("❌ {} failed.", example.name);
+            return Ok(None);
+        }
+    };
+    print!("   {:.2} s", sampled.elapsed.as_secs_f64());
+    match sampled.samples {
+        Some(samples) => println!(", {samples} samples in {}", sampled.flamegraph.display()),
+        None => {
+            println!(", and no flamegraph.");
+            for line in sampled.stderr.lines() {
+                println!("   {line}");
+            }
+            if sampled.stderr.is_empty() {
+                // Its own entry may have a bigger input than the one it shared
+                let own_args = profile::target(example)?.map(|target| target.args);
+                let command = match own_args {
+                    Some(own) if own != args => format!("tutor profile {}", example.short_name()),
+                    _ => format!("tutor profile {} <bigger input>", example.short_name()),
+                };
+                println!(
+                    "   It finished before the profiler took a sample. To see where its time \
+                     goes, give it more work: `{command}`."
+                );
+            }
+        }
+    }
+    Ok(Some(sampled))
+}
+
+/// Builds the rustdoc for `package` and opens it, unless `no_open`.
+fn docs_one(course: &Course, package: &str, no_open: bool) -> Result<bool> {
+    println!("📚 Documenting {package} with rustdoc...");
+    let page = match docs::build(&course.root, package)? {
+        Ok(page) => page,
+        Err(stderr) => {
+            eprint!("{stderr}");
+            println!("❌ Could not document {package}. rustdoc's warnings count as errors here.");
+            return Ok(false);
+        }
+    };
+    println!("✅ {}", page.display());
+    println!("   Its examples run as tests with `cargo test --doc -p {package}`.");
+    if !no_open && !docs::open(&page) {
+        println!("   Couldn't open a browser; open the page above in one.");
+    }
+    Ok(true)
+}
+
+/// Builds `package` with wasm-pack and runs its WebAssembly tests.
+fn build_one(package: &wasm::Package) -> Result<bool> {
+    println!("🕸️  {}: building for the browser...", package.chapter);
+    let pkg = match wasm::build(package)? {
+        Ok(pkg) => pkg,
+        Err(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!("❌ {} does not build for wasm32.", package.chapter);
+            return Ok(false);
+        }
+    };
+    println!(
+        "✅ {}/{}.js and {}_bg.wasm",
+        pkg.display(),
+        package.lib,
+        package.lib
+    );
+
+    println!("\n🧪 Running its tests as WebAssembly in Node...");
+    match wasm::test(package)? {
+        Ok(passed) => {
+            for name in &passed {
+                println!("✅ {name}");
+            }
+            println!(
+                "\n🎉 {} tests pass in wasm32. To try it in a browser, serve the chapter:\n\n    \
+                 python3 -m http.server --directory {}\n\n\
+                 and open http://localhost:8000/www/",
+                passed.len(),
+                package.dir.display()
+            );
+            Ok(true)
+        }
+        Err(output) => {
+            println!("{output}");
+            println!("❌ {}'s tests fail as WebAssembly.", package.chapter);
+            Ok(false)
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//! The lesson manifest, `rust/lessons.toml`.
+//!
+//! It gives each example a title, a difficulty, some tags, and the lessons
+//! it builds on. The manifest is optional and needn't list every example:
+//! an unlisted example has no prerequisites.
+//!
+//! The course is put in an order where every lesson comes after the lessons
+//! it requires. Among the lessons that are ready at any point, the one from
+//! the earliest chapter in the manifest's `chapters` list goes next, and
+//! within a chapter the one that comes first in the usual numbered order.
+//! The top-level files come before every chapter, and chapters that aren't
+//! listed after the ones that are, so without prerequisites or a chapter
+//! list the order is exactly the numbered one.
+//!
+//! The titles can be translated in `lessons.<language>.toml` (see
+//! [`crate::i18n`]), which lists only an `id` and a `title` for each
+//! lesson it translates.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::course::Example;
+use crate::i18n;
+
+/// How much Rust a lesson expects the learner to know already.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Advanced => "advanced",
+        })
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "beginner" => Ok(Difficulty::Beginner),
+            "intermediate" => Ok(Difficulty::Intermediate),
+            "advanced" => Ok(Difficulty::Advanced),
+            _ => Err(format!(
+                "`{s}` is not a difficulty; use beginner, intermediate, or advanced"
+            )),
+        }
+    }
+}
+
+/// One entry in `lessons.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Lesson {
+    /// The example's name, e.g. `lifetimes/01_elision`.
+    pub id: String,
+    pub title: String,
+    pub difficulty: Difficulty,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Lessons that must be completed before this one.
+    #[serde(default)]
+    pub requires: Vec<String>,
+}
+
+/// Everything in `lessons.toml`, keyed by lesson id.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    lessons: BTreeMap<String, Lesson>,
+    /// The chapters in teaching order.
+    chapters: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(default)]
+    chapters: Vec<String>,
+    #[serde(default, rename = "lesson")]
+    lessons: Vec<Lesson>,
+}
+
+/// A translation of `lessons.toml`.
+#[derive(Deserialize)]
+struct Translation {
+    #[serde(default, rename = "lesson")]
+    lessons: Vec<TranslatedLesson>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TranslatedLesson {
+    id: String,
+    title: String,
+}
+
+impl Manifest {
+    /// Reads `<root>/lessons.toml`, or returns an empty manifest if there
+    /// isn't one.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join("lessons.toml");
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+        let file: File =
+            toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+
+        let mut manifest = Self::from_file(&path, file)?;
+        if let Some(path) = i18n::current().localized(&path) {
+            let text = fs::read_to_string(&path)
+                .with_context(|| format!("cannot read {}", path.display()))?;
+            let translation: Translation = toml::from_str(&text)
+                .with_context(|| format!("{} is not valid", path.display()))?;
+            for translated in translation.lessons {
+                let Some(lesson) = manifest.lessons.get_mut(&translated.id) else {
+                    bail!(
+                        "{} translates `{}`, which isn't in lessons.toml",
+                        path.display(),
+                        translated.id
+                    );
+                };
+                lesson.title = translated.title;
+            }
+        }
+        Ok(manifest)
+    }
+
+    fn from_file(path: &Path, file: File) -> Result<Self> {
+        let mut lessons = BTreeMap::new();
+        for lesson in file.lessons {
+            if lessons.contains_key(&lesson.id) {
+                bail!("{} lists `{}` twice", path.display(), lesson.id);
+            }
+            lessons.insert(lesson.id.clone(), lesson);
+        }
+        let mut seen = BTreeSet::new();
+        if let Some(twice) = file.chapters.iter().find(|chapter| !seen.insert(*chapter)) {
+            bail!("{} lists the chapter `{twice}` twice", path.display());
+        }
+        Ok(Manifest {
+            lessons,
+            chapters: file.chapters,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Lesson> {
+        self.lessons.get(id)
+    }
+
+    /// The lessons `id` requires, or none if it isn't in the manifest.
+    pub fn requires(&self, id: &str) -> &[String] {
+        self.get(id).map_or(&[], |lesson| &lesson.requires)
+    }
+
+    /// Reorders `examples` so that every lesson comes after its
+    /// prerequisites, keeping the order of `chapters`, and otherwise the
+    /// given order, wherever the prerequisites allow it.
+    ///
+    /// Fails if the manifest mentions a lesson or a chapter that doesn't
+    /// exist, or the prerequisites form a cycle.
+    pub fn sort(&self, examples: Vec<Example>) -> Result<Vec<Example>> {
+        let position: BTreeMap<&str, usize> = examples
+            .iter()
+            .enumerate()
+            .map(|(index, example)| (example.name.as_str(), index))
+            .collect();
+        for lesson in self.lessons.values() {
+            if !position.contains_key(lesson.id.as_str()) {
+                bail!(
+                    "lessons.toml lists `{}`, but there is no such example",
+                    lesson.id
+                );
+            }
+            if let Some(missing) = lesson
+                .requires
+                .iter()
+                .find(|id| !position.contains_key(id.as_str()))
+            {
+                bail!(
+                    "`{}` requires `{missing}` in lessons.toml, but there is no such example",
+                    lesson.id
+                );
+            }
+        }
+
+        if let Some(missing) = self.chapters.iter().find(|chapter| {
+            !examples
+                .iter()
+                .any(|example| example.group.as_ref() == Some(*chapter))
+        }) {
+            bail!("lessons.toml lists the chapter `{missing}` in `chapters`, but there is no such chapter");
+        }
+        // Top-level files first, then the listed chapters, then the rest
+        let rank = |example: &Example| match &example.group {
+            None => 0,
+            Some(group) => self
+                .chapters
+                .iter()
+                .position(|chapter| chapter == group)
+                .map_or(self.chapters.len() + 1, |position| position + 1),
+        };
+
+        // Kahn's algorithm, always taking the ready lesson from the earliest
+        // chapter, and within it the earliest in the original order.
+        let mut waiting_on: Vec<usize> = examples
+            .iter()
+            .map(|example| self.requires(&example.name).len())
+            .collect();
+        let mut unlocks: Vec<Vec<usize>> = vec![Vec::new(); examples.len()];
+        for (index, example) in examples.iter().enumerate() {
+            for id in self.requires(&example.name) {
+                unlocks[position[id.as_str()]].push(index);
+            }
+        }
+        let mut ready: BTreeSet<(usize, usize)> = (0..examples.len())
+            .filter(|&index| waiting_on[index] == 0)
+            .map(|index| (rank(&examples[index]), index))
+            .collect();
+        let mut order = Vec::with_capacity(examples.len());
+        while let Some((_, index)) = ready.pop_first() {
+            order.push(index);
+            for &next in &unlocks[index] {
+                waiting_on[next] -= 1;
+                if waiting_on[next] == 0 {
+                    ready.insert((rank(&examples[next]), next));
+                }
+            }
+        }
+        if order.len() < examples.len() {
+            let stuck: Vec<&str> = (0..examples.len())
+                .filter(|&index| waiting_on[index] > 0)
+                .map(|index| examples[index].name.as_str())
+                .collect();
+            bail!(
+                "the prerequisites in lessons.toml form a cycle among: {}",
+                stuck.join(", ")
+            );
+        }
+
+        let mut examples: Vec<Option<Example>> = examples.into_iter().map(Some).collect();
+        Ok(order
+            .into_iter()
+            .filter_map(|index| examples[index].take())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn example(name: &str) -> Example {
+        Example {
+            name: name.to_string(),
+            group: name.split_once('/').map(|(group, _)| group.to_string()),
+            path: PathBuf::from(format!("examples/{name}.rs")),
+            manifest: None,
+        }
+    }
+
+    /// The examples, in the order discovery finds them.
+    fn examples() -> Vec<Example> {
+        [
+            "01_hello",
+            "02_more",
+            "enums/01_match",
+            "structs/01_define",
+            "structs/02_methods",
+        ]
+        .into_iter()
+        .map(example)
+        .collect()
+    }
+
+    fn manifest(toml: &str) -> Manifest {
+        let file: File = toml::from_str(toml).unwrap();
+        Manifest::from_file(Path::new("lessons.toml"), file).unwrap()
+    }
+
+    fn sorted(manifest: &Manifest) -> Result<Vec<String>> {
+        let sorted = manifest.sort(examples())?;
+        Ok(sorted.into_iter().map(|example| example.name).collect())
+    }
+
+    fn lesson(id: &str, requires: &[&str]) -> String {
+        format!(
+            "[[lesson]]\nid = {id:?}\ntitle = \"A lesson\"\ndifficulty = \"beginner\"\nrequires = {requires:?}\n"
+        )
+    }
+
+    #[test]
+    fn without_prerequisites_the_order_is_kept() {
+        let names: Vec<String> = examples().into_iter().map(|example| example.name).collect();
+        assert_eq!(sorted(&Manifest::default()).unwrap(), names);
+        let listed = manifest(&(lesson("01_hello", &[]) + &lesson("enums/01_match", &[])));
+        assert_eq!(sorted(&listed).unwrap(), names);
+    }
+
+    #[test]
+    fn a_prerequisite_moves_a_lesson_later() {
+        let manifest = manifest(&lesson("enums/01_match", &["structs/02_methods"]));
+        assert_eq!(
+            sorted(&manifest).unwrap(),
+            [
+                "01_hello",
+                "02_more",
+                "structs/01_define",
+                "structs/02_methods",
+                "enums/01_match"
+            ]
+        );
+    }
+
+    #[test]
+    fn the_chapter_list_orders_the_lessons_that_are_ready() {
+        let manifest = manifest("chapters = [\"structs\", \"enums\"]\n");
+        assert_eq!(
+            sorted(&manifest).unwrap(),
+            [
+                "01_hello",
+                "02_more",
+                "structs/01_define",
+                "structs/02_methods",
+                "enums/01_match"
+            ]
+        );
+    }
+
+    #[test]
+    fn a_cycle_is_reported() {
+        let manifest = manifest(
+            &(lesson("structs/01_define", &["enums/01_match"])
+                + &lesson("enums/01_match", &["structs/02_methods"])
+                + &lesson("structs/02_methods", &["structs/01_define"])),
+        );
+        let error = sorted(&manifest).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "the prerequisites in lessons.toml form a cycle among: \
+             enums/01_match, structs/01_define, structs/02_methods"
+        );
+    }
+
+    #[test]
+    fn an_unknown_prerequisite_is_reported() {
+        let manifest = manifest(&lesson("enums/01_match", &["structs/09_missing"]));
+        let error = sorted(&manifest).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "`enums/01_match` requires `structs/09_missing` in lessons.toml, but there is no such example"
+        );
+    }
+
+    #[test]
+    fn an_unknown_chapter_is_reported() {
+        let manifest = manifest("chapters = [\"structs\", \"traits\"]\n");
+        let error = sorted(&manifest).unwrap_err().to_string();
+        assert!(error.contains("the chapter `traits`"), "{error}");
+    }
+}
@@ -0,0 +1,240 @@
+//! Splitting an example's source into the parts of a page, for the book
+//! that `bookgen` writes and the lessons `tutor serve` shows.
+//!
+//! The examples all follow the same layout:
+//!
+//! ```text
+//! // Example 2: Variables and Mutability      <- title line
+//! // Demonstrates immutable and ...           <- introduction
+//! //
+//! // Concepts: let, mut, ...                  <- metadata (left out)
+//! // Difficulty: beginner
+//!
+//! fn main() { ... }                           <- the code
+//!
+//! /*
+//!  * Key Concepts:                            <- notes
+//!  * - Variables are immutable by default
+//!  */
+//!
+//! //# `let x = 5;`                            <- explanations (left out)
+//! //# ...
+//!
+//! // EXPECTED:                                <- what it prints
+//! // The value of x is: 5
+//! ```
+//!
+//! Every part except the code is optional here, although `tutor verify`
+//! insists on the metadata (see [`::checker::parse_header`]).
+
+use crate::{explain, runner};
+
+/// The parts of one example.
+#[derive(Debug, Default, PartialEq)]
+pub struct Page {
+    /// The header comment without its title line, as Markdown.
+    pub intro: String,
+    pub code: String,
+    /// The closing block comment, as Markdown.
+    pub notes: String,
+    /// The example's `// EXPECTED:` block, if it has one.
+    pub expected: Option<String>,
+}
+
+impl Page {
+    pub fn parse(source: &str) -> Page {
+        let expected = runner::expected_output(source);
+        let source = match source.find(runner::EXPECTED_MARKER) {
+            Some(at) => &source[..at],
+            None => source,
+        };
+        let source = explain::strip(source);
+        let lines: Vec<&str> = source.trim_end().lines().collect();
+
+        let header_len = lines
+            .iter()
+            .take_while(|line| line.starts_with("//"))
+            .count();
+        let header: Vec<&str> = lines[..header_len]
+            .iter()
+            .skip(1)
+            .filter(|line| !::checker::is_field(line))
+            .map(|line| line.strip_prefix("//").unwrap_or(line))
+            .map(|text| text.strip_prefix(' ').unwrap_or(text))
+            .collect();
+
+        // The notes are a block comment that closes the file.
+        let mut code_end = lines.len();
+        if lines.last().is_some_and(|line| line.trim() == "*/") {
+            if let Some(start) = lines[header_len..]
+                .iter()
+                .rposition(|line| line.starts_with("/*"))
+            {
+                code_end = header_len + start;
+            }
+        }
+        let notes: Vec<&str> = lines[code_end..]
+            .iter()
+            .filter(|line| !matches!(line.trim(), "/*" | "*/"))
+            .map(|line| {
+                let text = line.trim_start().trim_start_matches('*');
+                text.strip_prefix(' ').unwrap_or(text)
+            })
+            .collect();
+
+        Page {
+            intro: intro_markdown(&header),
+            code: lines[header_len..code_end]
+                .join("\n")
+                .trim_matches('\n')
+                .to_string(),
+            notes: notes_markdown(&notes),
+            expected,
+        }
+    }
+}
+
+/// Header comment lines to Markdown: blank lines separate paragraphs, and
+/// indented lines (usually commands to run) become code blocks.
+fn intro_markdown(lines: &[&str]) -> String {
+    let mut markdown = String::new();
+    let mut in_block = false;
+    for line in lines {
+        let indented = line.starts_with("  ");
+        if indented != in_block {
+            markdown.push_str(if indented { "\n```text\n" } else { "```\n\n" });
+            in_block = indented;
+        }
+        if indented {
+            markdown.push_str(line.trim_start());
+        } else if line.trim().is_empty() {
+            markdown.push('\n');
+            continue;
+        } else {
+            markdown.push_str(line);
+        }
+        markdown.push('\n');
+    }
+    if in_block {
+        markdown.push_str("```\n");
+    }
+    tidy(&markdown)
+}
+
+/// Block comment lines to Markdown: a line like `Key Concepts:` becomes a
+/// heading, and lists are kept apart from the text that follows them.
+fn notes_markdown(lines: &[&str]) -> String {
+    let mut markdown = String::new();
+    let mut in_list = false;
+    for line in lines {
+        let item = is_list_item(line);
+        let continuation = in_list && line.starts_with(' ');
+        if line.ends_with(':') && !item && !continuation {
+            markdown.push_str(&format!("\n### {}\n\n", line.trim_end_matches(':')));
+            in_list = false;
+            continue;
+        }
+        if in_list && !item && !continuation {
+            markdown.push('\n');
+        }
+        in_list = item || continuation;
+        markdown.push_str(line);
+        markdown.push('\n');
+    }
+    tidy(&markdown)
+}
+
+fn is_list_item(line: &str) -> bool {
+    line.starts_with("- ")
+        || line.split_once(". ").is_some_and(|(number, _)| {
+            !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+/// Collapses runs of blank lines and trims the ends.
+fn tidy(markdown: &str) -> String {
+    let mut tidied = String::new();
+    for line in markdown.trim().lines() {
+        if line.is_empty() && tidied.ends_with("\n\n") {
+            continue;
+        }
+        tidied.push_str(line);
+        tidied.push('\n');
+    }
+    tidied
+}
+
+/// A code fence long enough not to be closed by backticks inside `code`.
+pub fn fence(code: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in code.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+// Example 9: Something
+// Demonstrates a thing
+//
+// Concepts: things
+// Minutes: 5
+//
+// Try it:
+//   rustc 09_something.rs
+
+fn main() {
+    println!(\"hi\");
+}
+
+/*
+ * Key Concepts:
+ * - One
+ *   still one
+ * - Two
+ * Afterword
+ */
+
+// EXPECTED:
+// hi
+";
+
+    #[test]
+    fn splits_an_example_into_its_parts() {
+        let page = Page::parse(SOURCE);
+        assert_eq!(
+            page.intro,
+            "Demonstrates a thing\n\nTry it:\n\n```text\nrustc 09_something.rs\n```\n"
+        );
+        assert_eq!(page.code, "fn main() {\n    println!(\"hi\");\n}");
+        assert_eq!(
+            page.notes,
+            "### Key Concepts\n\n- One\n  still one\n- Two\n\nAfterword\n"
+        );
+        assert_eq!(page.expected.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn code_without_comments_is_all_code() {
+        let page = Page::parse("fn main() {}\n");
+        assert_eq!(
+            page,
+            Page {
+                code: "fn main() {}".to_string(),
+                ..Page::default()
+            }
+        );
+    }
+
+    #[test]
+    fn fences_outgrow_backticks_in_the_code() {
+        assert_eq!(fence("let x = 1;"), "```");
+        assert_eq!(fence("/// ```\n/// x\n/// ```"), "````");
+    }
+}
@@ -0,0 +1,170 @@
+//! `tutor play`: a scratch file to try anything in, outside the lessons.
+//!
+//! The playground is `target/tutor/play/play.rs`, opened in the learner's
+//! editor (`$VISUAL`, else `$EDITOR`). When the editor closes, the file is
+//! compiled with `rustc` and run the way `tutor run` runs an example: no
+//! input, output captured, and stopped after [`runner::TIMEOUT`]. Then the
+//! learner can edit it again or quit.
+//!
+//! The file starts as a bare `main`, or as a copy of any single-file
+//! example with `--template`. `--resume` reopens it as it was left.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::compiler;
+use crate::course::{Course, Example};
+use crate::explain;
+use crate::runner;
+use crate::t;
+
+const STARTER: &str = "\
+// The playground: write any Rust you like, then save and close the editor
+// to run it.
+
+fn main() {
+    println!(\"Hello, playground!\");
+}
+";
+
+/// Opens the playground and runs it every time the editor closes, until
+/// the learner quits. Returns whether the last run succeeded.
+pub fn play(course: &Course, template: Option<&Example>, resume: bool) -> Result<bool> {
+    let dir = course.build_dir().join("play");
+    let scratch = dir.join("play.rs");
+    fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    if !(resume && scratch.is_file()) {
+        let source = match template {
+            Some(example) => from_example(example)?,
+            None => STARTER.to_string(),
+        };
+        fs::write(&scratch, source)
+            .with_context(|| format!("cannot write {}", scratch.display()))?;
+    }
+
+    let editor = editor_command(
+        std::env::var("VISUAL")
+            .ok()
+            .or_else(|| std::env::var("EDITOR").ok()),
+    );
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    loop {
+        println!(
+            "{}",
+            t!(
+                "play-opening",
+                file = scratch.display().to_string(),
+                editor = editor.join(" ")
+            )
+        );
+        edit(&editor, &scratch)?;
+        let passed = run(&scratch, &dir)?;
+
+        print!("\n{} ", t!("play-again"));
+        io::stdout().flush()?;
+        let mut reply = String::new();
+        if input.read_line(&mut reply)? == 0 || reply.trim().eq_ignore_ascii_case("q") {
+            println!("{}", t!("play-saved"));
+            return Ok(passed);
+        }
+    }
+}
+
+/// The source of `example` without its `//#` explanations, to start the
+/// playground from.
+fn from_example(example: &Example) -> Result<String> {
+    if example.manifest.is_some() {
+        bail!(
+            "{} is built with its chapter's crates, so it can't run on its own in the playground; \
+             pick a single-file example",
+            example.name
+        );
+    }
+    let source = fs::read_to_string(&example.path)
+        .with_context(|| format!("cannot read {}", example.path.display()))?;
+    Ok(explain::strip(&source))
+}
+
+/// The program and arguments to edit a file with: `var` split on
+/// whitespace (so `code --wait` works), or a default editor.
+fn editor_command(var: Option<String>) -> Vec<String> {
+    let words: Vec<String> = var
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    if !words.is_empty() {
+        return words;
+    }
+    let fallback = if cfg!(windows) { "notepad" } else { "vi" };
+    vec![fallback.to_string()]
+}
+
+fn edit(editor: &[String], file: &Path) -> Result<()> {
+    let status = Command::new(&editor[0])
+        .args(&editor[1..])
+        .arg(file)
+        .status()
+        .with_context(|| {
+            format!(
+                "cannot start the editor `{}`; set $EDITOR to the one you use",
+                editor[0]
+            )
+        })?;
+    if !status.success() {
+        bail!("the editor `{}` exited with an error", editor[0]);
+    }
+    Ok(())
+}
+
+/// Compiles and runs the playground, printing what happened.
+fn run(scratch: &Path, dir: &Path) -> Result<bool> {
+    let binary = dir.join(format!("play{}", std::env::consts::EXE_SUFFIX));
+    if let Err(diagnostics) = compiler::rustc(scratch, &binary, &[])? {
+        eprintln!("{diagnostics}");
+        println!("{}", t!("play-compile-error"));
+        return Ok(false);
+    }
+
+    println!("{}\n", t!("play-running"));
+    let run = runner::run(&mut Command::new(&binary), runner::TIMEOUT)?;
+    print!("{}", run.stdout);
+    eprint!("{}", run.stderr);
+    match run.status {
+        Some(status) if status.success() => {
+            println!("\n{}", t!("play-success"));
+            Ok(true)
+        }
+        Some(_) => {
+            println!("\n{}", t!("play-failed"));
+            Ok(false)
+        }
+        None => {
+            println!(
+                "\n{}",
+                t!("play-timed-out", seconds = runner::TIMEOUT.as_secs())
+            );
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_editor_may_come_with_arguments() {
+        assert_eq!(
+            editor_command(Some(String::from("code --wait"))),
+            ["code", "--wait"]
+        );
+        assert_eq!(editor_command(Some(String::from("  "))).len(), 1);
+        assert_eq!(editor_command(None).len(), 1);
+    }
+}
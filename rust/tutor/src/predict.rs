@@ -0,0 +1,252 @@
+//! `tutor predict`: read a snippet from one of the examples, guess what it
+//! prints, then see what it really prints.
+//!
+//! Snippets are listed in `rust/predict.toml` by the example they come from
+//! and the line they start at, so they stay in sync with the examples. The
+//! snippet replaces the body of the example's `main`, keeping the file's
+//! other items, and runs without arguments, input, or environment, in an
+//! empty directory, and is killed if it takes too long.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::compiler;
+use crate::course::Course;
+use crate::runner;
+use crate::t;
+
+/// How long a snippet may run before it is killed.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One entry in `predict.toml`.
+#[derive(Debug, Deserialize)]
+pub struct Snippet {
+    pub name: String,
+    /// The example the snippet comes from, e.g. `02_variables`.
+    example: String,
+    /// How the first line of the snippet starts.
+    start: String,
+    /// Other paragraphs (by their first line) the snippet needs.
+    #[serde(default)]
+    setup: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(rename = "snippet")]
+    snippets: Vec<Snippet>,
+}
+
+/// A snippet cut out of its example, ready to show and run.
+struct Extracted {
+    /// The lines shown to the learner.
+    code: Vec<String>,
+    /// A complete program running `code`.
+    program: String,
+}
+
+pub fn load(root: &Path) -> Result<Vec<Snippet>> {
+    let path = root.join("predict.toml");
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let file: File =
+        toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+    Ok(file.snippets)
+}
+
+/// Plays one round with `snippet`. Returns whether the prediction was right.
+pub fn play(course: &Course, snippet: &Snippet) -> Result<bool> {
+    let Some(index) = course.find(&snippet.example) else {
+        bail!(
+            "snippet `{}` comes from `{}`, but there is no such example",
+            snippet.name,
+            snippet.example
+        );
+    };
+    let example = &course.examples[index];
+    if example.manifest.is_some() {
+        bail!(
+            "snippet `{}`: examples in cargo packages aren't supported",
+            snippet.name
+        );
+    }
+    let source = fs::read_to_string(&example.path)
+        .with_context(|| format!("cannot read {}", example.path.display()))?;
+    let extracted = extract(&source, snippet)
+        .with_context(|| format!("cannot find snippet `{}` in {}", snippet.name, example.name))?;
+
+    println!("{}\n", t!("predict-title", name = example.name.as_str()));
+    for line in &extracted.code {
+        if line.is_empty() {
+            println!();
+        } else {
+            println!("    {line}");
+        }
+    }
+    println!("\n{}", t!("predict-prompt"));
+    let prediction = read_prediction()?;
+
+    let dir = course.build_dir().join("predict");
+    let source_path = dir.join(format!("{}.rs", snippet.name));
+    let binary = dir.join(format!("{}{}", snippet.name, std::env::consts::EXE_SUFFIX));
+    fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    fs::write(&source_path, &extracted.program)
+        .with_context(|| format!("cannot write {}", source_path.display()))?;
+    if let Err(diagnostics) = compiler::rustc(&source_path, &binary, &[])? {
+        eprintln!("{diagnostics}");
+        bail!("snippet `{}` does not compile on its own", snippet.name);
+    }
+    // An empty directory, no environment, and a time limit: the snippet
+    // only gets to print.
+    let sandbox = dir.join("sandbox");
+    fs::create_dir_all(&sandbox).with_context(|| format!("cannot create {}", sandbox.display()))?;
+    let run = runner::run(
+        Command::new(&binary).current_dir(&sandbox).env_clear(),
+        TIMEOUT,
+    )?;
+    eprint!("{}", run.stderr);
+    match run.status {
+        None => bail!(
+            "the snippet ran for more than {} seconds",
+            TIMEOUT.as_secs()
+        ),
+        Some(status) if !status.success() => bail!("the snippet exited with {status}"),
+        Some(_) => {}
+    }
+    let actual = run.stdout;
+
+    let prediction: Vec<&str> = prediction.iter().map(|line| line.trim_end()).collect();
+    let actual: Vec<&str> = actual.lines().map(str::trim_end).collect();
+    println!();
+    if prediction == actual {
+        println!("{}", t!("predict-right"));
+        for line in &actual {
+            println!("   {line}");
+        }
+        return Ok(true);
+    }
+    println!("{}", t!("predict-wrong"));
+    for row in 0..prediction.len().max(actual.len()) {
+        match (prediction.get(row), actual.get(row)) {
+            (Some(guess), Some(real)) if guess == real => println!("   {real}"),
+            (guess, real) => {
+                if let Some(guess) = guess {
+                    println!(" - {guess}");
+                }
+                if let Some(real) = real {
+                    println!(" + {real}");
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Cuts `snippet` (and its setup) out of the example's source.
+fn extract(source: &str, snippet: &Snippet) -> Result<Extracted> {
+    let lines: Vec<&str> = source.lines().collect();
+    let main_start = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("fn main()"))
+        .context("the example has no `fn main()`")?;
+    let main_end = main_start
+        + lines[main_start..]
+            .iter()
+            .position(|line| line.starts_with('}'))
+            .context("cannot find the end of `fn main()`")?;
+
+    let mut code = Vec::new();
+    for start in snippet.setup.iter().chain([&snippet.start]) {
+        let first = lines[main_start..main_end]
+            .iter()
+            .position(|line| line.trim_start().starts_with(start.as_str()))
+            .map(|offset| main_start + offset)
+            .with_context(|| format!("no line in `main` starts with `{start}`"))?;
+        let last = lines[first..main_end]
+            .iter()
+            .position(|line| line.trim().is_empty())
+            .map_or(main_end, |offset| first + offset);
+        if !code.is_empty() {
+            code.push(String::new());
+        }
+        // Keep the comment a paragraph opens with; drop the others.
+        let paragraph = &lines[first..last];
+        let heading = paragraph
+            .iter()
+            .take_while(|line| line.trim_start().starts_with("//"))
+            .count();
+        for (number, line) in paragraph.iter().enumerate() {
+            let line = line.strip_prefix("    ").unwrap_or(line);
+            let line = if number < heading {
+                line.trim_end().to_string()
+            } else {
+                strip_comment(line)
+            };
+            if !line.trim().is_empty() {
+                code.push(line);
+            }
+        }
+    }
+
+    let mut program = String::from("#![allow(unused)]\n");
+    for line in lines[..=main_start].iter().chain(&lines[main_end..]) {
+        if line.starts_with('}') && program.ends_with("{\n") {
+            // Just after `fn main() {`: the snippet goes here.
+            for code_line in &code {
+                program.push_str("    ");
+                program.push_str(code_line);
+                program.push('\n');
+            }
+        }
+        program.push_str(line);
+        program.push('\n');
+    }
+    Ok(Extracted { code, program })
+}
+
+/// Drops a trailing `// comment` (which may give the answer away), leaving
+/// `//` inside string literals alone.
+fn strip_comment(line: &str) -> String {
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut previous = '\0';
+    for (offset, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '/' if !in_string && previous == '/' => {
+                return line[..offset - 1].trim_end().to_string();
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+    line.trim_end().to_string()
+}
+
+/// Reads lines from stdin until an empty one.
+fn read_prediction() -> Result<Vec<String>> {
+    let stdin = io::stdin();
+    let mut lines = Vec::new();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.trim().is_empty() {
+            break;
+        }
+        lines.push(line.to_string());
+    }
+    Ok(lines)
+}
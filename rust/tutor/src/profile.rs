@@ -0,0 +1,203 @@
+//! `tutor profile`: run an example under a sampling profiler and draw a
+//! flamegraph of where its time went, then do the same for its rewrite.
+//!
+//! A chapter package lists the binaries worth profiling in its
+//! `Cargo.toml`:
+//!
+//! ```text
+//! [package.metadata.tutor.profile.01_slow_report]
+//! args = ["40000"]            <- an input big enough to take some time
+//! rewrite = "02_fast_report"  <- the faster version, run on the same input
+//! ```
+//!
+//! The binary samples itself, with the pprof crate, when
+//! [`FLAMEGRAPH_VAR`] names the SVG to write (see
+//! `examples/profiling/reports/lib.rs`). The tutor builds it with the
+//! workspace's `profiling` profile, a release build that keeps the
+//! symbols the flamegraph names functions by, runs it, and times it.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::course::{file_stem, Course, Example};
+
+/// Set to the path of the flamegraph the profiled program should write.
+pub const FLAMEGRAPH_VAR: &str = "TUTOR_FLAMEGRAPH";
+
+/// The cargo profile profiled examples are built with.
+pub const PROFILE: &str = "profiling";
+
+/// How to profile one binary.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Target {
+    /// Passed to the binary, unless the learner gives their own.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// The binary that does the same job faster, profiled after this one.
+    pub rewrite: Option<String>,
+}
+
+/// One profiled run.
+#[derive(Debug)]
+pub struct Sampled {
+    pub elapsed: Duration,
+    pub flamegraph: PathBuf,
+    /// How many samples the flamegraph holds, or `None` if the program
+    /// wrote none, because it finished before the first sample or has no
+    /// profiler on this platform.
+    pub samples: Option<u64>,
+    pub stderr: String,
+}
+
+/// The targets in a package manifest's `[package.metadata.tutor.profile]`,
+/// by binary name.
+pub fn parse_targets(manifest: &str) -> Result<BTreeMap<String, Target>> {
+    let manifest: toml::Value = toml::from_str(manifest)?;
+    let Some(table) = manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("tutor"))
+        .and_then(|tutor| tutor.get("profile"))
+    else {
+        return Ok(BTreeMap::new());
+    };
+    Ok(table.clone().try_into()?)
+}
+
+/// How to profile `example`, or `None` if its chapter doesn't say.
+pub fn target(example: &Example) -> Result<Option<Target>> {
+    let Some(manifest) = &example.manifest else {
+        return Ok(None);
+    };
+    let text = fs::read_to_string(manifest)
+        .with_context(|| format!("cannot read {}", manifest.display()))?;
+    let mut targets =
+        parse_targets(&text).with_context(|| format!("{} is not valid", manifest.display()))?;
+    Ok(targets.remove(&file_stem(&example.path)?))
+}
+
+/// Builds `example` with the profiling profile, returning the binary's
+/// path or the compiler's diagnostics.
+pub fn build(example: &Example) -> Result<Result<PathBuf, String>> {
+    let manifest = example
+        .manifest
+        .as_deref()
+        .with_context(|| format!("{} is not part of a cargo package", example.name))?;
+    crate::compiler::cargo_build(manifest, example, &["--profile", PROFILE])
+}
+
+/// The example called `rewrite` in `example`'s chapter package.
+pub fn find_rewrite<'a>(
+    course: &'a Course,
+    example: &Example,
+    rewrite: &str,
+) -> Option<&'a Example> {
+    course.examples.iter().find(|other| {
+        other.manifest == example.manifest
+            && file_stem(&other.path).is_ok_and(|stem| stem == rewrite)
+    })
+}
+
+/// Where the flamegraph of `example` goes.
+pub fn flamegraph_path(build_dir: &Path, example: &Example) -> Result<PathBuf> {
+    let bin = file_stem(&example.path)?;
+    Ok(build_dir.join("profiles").join(format!("{bin}.svg")))
+}
+
+/// Runs `binary` with `args`, asking it for a flamegraph at `flamegraph`.
+/// Returns what it printed to stderr instead if it fails.
+pub fn run(binary: &Path, args: &[String], flamegraph: &Path) -> Result<Result<Sampled, String>> {
+    if let Some(dir) = flamegraph.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+    // A flamegraph left from an earlier run would pass for this one's
+    if flamegraph.exists() {
+        fs::remove_file(flamegraph)
+            .with_context(|| format!("cannot remove {}", flamegraph.display()))?;
+    }
+    let started = Instant::now();
+    let output = Command::new(binary)
+        .args(args)
+        .env(FLAMEGRAPH_VAR, flamegraph)
+        .output()
+        .with_context(|| format!("failed to run {}", binary.display()))?;
+    let elapsed = started.elapsed();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    if !output.status.success() {
+        return Ok(Err(stderr));
+    }
+    let samples = fs::read_to_string(flamegraph)
+        .ok()
+        .and_then(|svg| samples_in(&svg));
+    Ok(Ok(Sampled {
+        elapsed,
+        flamegraph: flamegraph.to_path_buf(),
+        samples,
+        stderr,
+    }))
+}
+
+/// The number of samples in a flamegraph, from the title of its bottom
+/// box: `<title>all (164 samples, 100%)</title>`.
+pub fn samples_in(svg: &str) -> Option<u64> {
+    let (_, rest) = svg.split_once("<title>all (")?;
+    let (count, _) = rest.split_once(" sample")?;
+    count.replace(',', "").parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_come_from_the_package_metadata() {
+        let targets = parse_targets(
+            r#"
+            [package]
+            name = "profiling-examples"
+
+            [package.metadata.tutor.profile.01_slow]
+            args = ["40000"]
+            rewrite = "02_fast"
+
+            [package.metadata.tutor.profile.02_fast]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            targets["01_slow"],
+            Target {
+                args: vec!["40000".to_string()],
+                rewrite: Some("02_fast".to_string()),
+            }
+        );
+        assert_eq!(targets["02_fast"], Target::default());
+    }
+
+    #[test]
+    fn a_package_without_profiles_has_no_targets() {
+        let targets = parse_targets("[package]\nname = \"plain\"\n").unwrap();
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn a_misspelled_key_is_an_error() {
+        let manifest = "[package.metadata.tutor.profile.01_slow]\nrewrites = \"02_fast\"\n";
+        assert!(parse_targets(manifest).is_err());
+    }
+
+    #[test]
+    fn counts_the_samples_in_a_flamegraph() {
+        let svg = "<svg><g><title>all (1,164 samples, 100%)</title></g></svg>";
+        assert_eq!(samples_in(svg), Some(1164));
+        assert_eq!(samples_in("<svg></svg>"), None);
+        assert_eq!(samples_in(""), None);
+    }
+}
@@ -0,0 +1,724 @@
+//! Persistent record of what the learner has completed.
+//!
+//! Stored as JSON in `~/.learn-rust/progress.json`. Every example,
+//! exercise, puzzle, and project stage gets a [`Record`] with how many times
+//! it was attempted and when it was first finished. Quizzes get a [`QuizRecord`] with their scores.
+//! Everything `tutor review` asks about gets a [`ReviewRecord`] with its
+//! place in the review schedule. The days the learner practiced and the
+//! achievements they unlocked (see [`crate::achievements`]) are kept too.
+//! Timestamps are seconds since the Unix epoch, and days are counted from
+//! it in UTC.
+//!
+//! The file says which version of this format it is in `schema_version`.
+//! A file from an older tutor, after `git pull` brings a newer one, goes
+//! through the [`MIGRATIONS`] that lead from its version to
+//! [`SCHEMA_VERSION`], as JSON, before it is read into a [`Progress`].
+//! [`Progress::load`] then saves the upgraded file in place of the old
+//! one, keeping a copy of the old one next to it. Files from before the
+//! versions were counted have no `schema_version`, and are version 1.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::state;
+
+/// What a record belongs to.
+#[derive(Debug, Clone, Copy)]
+pub enum Kind {
+    Example,
+    Exercise,
+    Puzzle,
+    /// A stage of a project, keyed `<project>/<stage number>`.
+    ProjectStage,
+}
+
+/// Progress on a single example, exercise, puzzle, or project stage.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Record {
+    /// How many times the learner explicitly ran or checked it.
+    pub attempts: u32,
+    pub first_attempt: Option<u64>,
+    pub last_attempt: Option<u64>,
+    /// When it first passed. Never cleared, even if it breaks again later.
+    pub completed_at: Option<u64>,
+    /// How many hint levels have been revealed (exercises only).
+    #[serde(default)]
+    pub hints_used: usize,
+    /// Whether the latest attempt failed to compile.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub broken: bool,
+}
+
+/// Results of one chapter's quiz.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct QuizRecord {
+    pub attempts: u32,
+    pub last_attempt: Option<u64>,
+    /// Correct answers in the latest attempt, out of `last_total`.
+    pub last_score: usize,
+    pub last_total: usize,
+    /// The best `score / total` so far, as a percentage.
+    pub best_percent: u32,
+}
+
+/// Where one quiz question or exercise is in the review schedule. See
+/// [`crate::review`] for how it changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewRecord {
+    /// Reviews in a row that went well; a bad one starts over at 0.
+    pub repetitions: u32,
+    /// Days from the latest review to the next.
+    pub interval_days: u32,
+    /// How fast the interval grows: 2.5 to start with, never below 1.3.
+    pub ease: f64,
+    /// The day it is next due, counted in days since the Unix epoch (UTC).
+    pub due_day: u64,
+    pub last_reviewed: Option<u64>,
+}
+
+/// The version of the format this tutor writes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// One step up from a version of the format to the next, on the file's
+/// JSON object.
+struct Migration {
+    /// The version it upgrades from, to `from + 1`.
+    from: u32,
+    apply: fn(&mut Map<String, Value>) -> Result<()>,
+}
+
+/// Every step, oldest first. A new field that older files can do without
+/// only needs a `#[serde(default)]`; a change that does more, like moving
+/// or renaming one, bumps [`SCHEMA_VERSION`] and adds a step here, with a
+/// fixture of a file in the old format in `tutor/fixtures/progress/`.
+const MIGRATIONS: &[Migration] = &[];
+
+/// Everything in `progress.json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Progress {
+    /// The version of the format, [`SCHEMA_VERSION`] once loaded.
+    #[serde(default = "first_version")]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub examples: BTreeMap<String, Record>,
+    #[serde(default)]
+    pub exercises: BTreeMap<String, Record>,
+    #[serde(default)]
+    pub quizzes: BTreeMap<String, QuizRecord>,
+    #[serde(default)]
+    pub puzzles: BTreeMap<String, Record>,
+    #[serde(default)]
+    pub project_stages: BTreeMap<String, Record>,
+    /// Keyed by review item, e.g. `exercise/variables1`.
+    #[serde(default)]
+    pub reviews: BTreeMap<String, ReviewRecord>,
+    /// Every day the learner ran, checked, or reviewed something.
+    #[serde(default)]
+    pub active_days: BTreeSet<u64>,
+    /// How many times something that didn't compile was made to compile.
+    #[serde(default)]
+    pub compile_errors_fixed: u32,
+    /// When each achievement was unlocked, by its id.
+    #[serde(default)]
+    pub achievements: BTreeMap<String, u64>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Progress {
+            schema_version: SCHEMA_VERSION,
+            examples: BTreeMap::new(),
+            exercises: BTreeMap::new(),
+            quizzes: BTreeMap::new(),
+            puzzles: BTreeMap::new(),
+            project_stages: BTreeMap::new(),
+            reviews: BTreeMap::new(),
+            active_days: BTreeSet::new(),
+            compile_errors_fixed: 0,
+            achievements: BTreeMap::new(),
+        }
+    }
+}
+
+impl Progress {
+    pub fn path() -> Result<PathBuf> {
+        Ok(state::data_dir()?.join("progress.json"))
+    }
+
+    /// Loads the progress file, starting fresh if it doesn't exist yet. A
+    /// file from an older tutor is upgraded in place; the old one is kept.
+    pub fn load() -> Result<Self> {
+        let (progress, backup) = Self::load_upgrading(&Self::path()?, MIGRATIONS)?;
+        if let Some(backup) = backup {
+            eprintln!(
+                "📝 Upgraded your progress file to version {SCHEMA_VERSION}. The old one is saved at {}.",
+                backup.display()
+            );
+        }
+        Ok(progress)
+    }
+
+    /// Loads the progress file at `path`, and if it was in an older format,
+    /// saves it upgraded. The file as it was is copied to a backup first,
+    /// whose path is returned.
+    fn load_upgrading(path: &Path, migrations: &[Migration]) -> Result<(Self, Option<PathBuf>)> {
+        let Some(json) = read(path)? else {
+            return Ok((Self::default(), None));
+        };
+        let (progress, version) = Self::parse(&json, migrations)
+            .with_context(|| format!("{} is not a valid progress file", path.display()))?;
+        if version == progress.schema_version {
+            return Ok((progress, None));
+        }
+        let backup = backup_path(path, version);
+        fs::write(&backup, &json).with_context(|| {
+            format!(
+                "cannot save a copy of {} before upgrading it",
+                path.display()
+            )
+        })?;
+        progress.save_to(path)?;
+        Ok((progress, Some(backup)))
+    }
+
+    /// Loads a progress file from anywhere, such as a copy of a learner's.
+    /// An older format is upgraded in memory; the file is left alone.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        match read(path)? {
+            Some(json) => Self::from_json(&json)
+                .with_context(|| format!("{} is not a valid progress file", path.display())),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Reads the contents of a progress file of any version.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(Self::parse(json, MIGRATIONS)?.0)
+    }
+
+    /// Reads the contents of a progress file, returning the progress and
+    /// the version the file was in. `migrations` are [`MIGRATIONS`] but in
+    /// tests, and the last one leads to the current version.
+    fn parse(json: &str, migrations: &[Migration]) -> Result<(Self, u32)> {
+        let latest = first_version() + migrations.len() as u32;
+        let mut value: Value = serde_json::from_str(json)?;
+        let Value::Object(fields) = &mut value else {
+            bail!("it is not a JSON object");
+        };
+        let version = match fields.get("schema_version") {
+            None => 1,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .with_context(|| format!("its schema_version, {version}, is not a version"))?,
+        };
+        if version > latest {
+            bail!(
+                "it was written by a newer tutor (schema version {version}, this one reads up to \
+                 {latest}); update this repository to use it"
+            );
+        }
+        for migration in migrations
+            .iter()
+            .filter(|migration| migration.from >= version)
+        {
+            (migration.apply)(fields).with_context(|| {
+                format!(
+                    "cannot upgrade it from version {} to {}",
+                    migration.from,
+                    migration.from + 1
+                )
+            })?;
+        }
+        fields.insert("schema_version".to_string(), latest.into());
+        Ok((serde_json::from_value(value)?, version))
+    }
+
+    /// Writes the progress file, replacing it atomically so a crash can't
+    /// leave half a file behind.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path()?)
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("cannot write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("cannot replace {}", path.display()))
+    }
+
+    pub fn get(&self, kind: Kind, name: &str) -> Option<&Record> {
+        self.records(kind).get(name)
+    }
+
+    pub fn is_completed(&self, kind: Kind, name: &str) -> bool {
+        self.get(kind, name)
+            .is_some_and(|record| record.completed_at.is_some())
+    }
+
+    /// Records a deliberate attempt (`tutor run`, `tutor check <name>`, ...).
+    pub fn record_attempt(&mut self, kind: Kind, name: &str, passed: bool) {
+        let now = now();
+        let record = self.records_mut(kind).entry(name.to_string()).or_default();
+        record.attempts += 1;
+        record.first_attempt.get_or_insert(now);
+        record.last_attempt = Some(now);
+        if passed {
+            record.completed_at.get_or_insert(now);
+        }
+        self.record_activity();
+    }
+
+    /// Records whether an attempt compiled, counting it as a fixed compile
+    /// error if the attempt before it didn't.
+    pub fn record_build(&mut self, kind: Kind, name: &str, compiled: bool) {
+        let record = self.records_mut(kind).entry(name.to_string()).or_default();
+        let fixed = compiled && record.broken;
+        record.broken = !compiled;
+        if fixed {
+            self.compile_errors_fixed += 1;
+        }
+    }
+
+    /// Counts today as a day of practice, for the streak.
+    pub fn record_activity(&mut self) {
+        self.active_days.insert(today());
+    }
+
+    /// Marks something as completed without counting an attempt. Used by the
+    /// bulk commands (`verify`, `check` with no arguments) that touch
+    /// everything at once.
+    pub fn mark_completed(&mut self, kind: Kind, name: &str) {
+        let record = self.records_mut(kind).entry(name.to_string()).or_default();
+        record.completed_at.get_or_insert_with(now);
+    }
+
+    /// Reveals one more hint level for an exercise, returning how many are
+    /// now revealed. Never goes past `available`.
+    pub fn reveal_hint(&mut self, name: &str, available: usize) -> usize {
+        let record = self.exercises.entry(name.to_string()).or_default();
+        record.hints_used = (record.hints_used + 1).min(available);
+        record.hints_used
+    }
+
+    /// Records a finished quiz for `chapter`.
+    pub fn record_quiz(&mut self, chapter: &str, correct: usize, total: usize) {
+        let record = self.quizzes.entry(chapter.to_string()).or_default();
+        record.attempts += 1;
+        record.last_attempt = Some(now());
+        record.last_score = correct;
+        record.last_total = total;
+        let percent = (correct * 100).checked_div(total).unwrap_or(0) as u32;
+        record.best_percent = record.best_percent.max(percent);
+        self.record_activity();
+    }
+
+    /// Folds in `other`, this learner's progress from somewhere else (see
+    /// `tutor import`). The more recent of two records wins, nothing
+    /// finished on either side is lost, and merging the same progress a
+    /// second time changes nothing.
+    pub fn merge(&mut self, other: Progress) {
+        for (mine, theirs) in [
+            (&mut self.examples, other.examples),
+            (&mut self.exercises, other.exercises),
+            (&mut self.puzzles, other.puzzles),
+            (&mut self.project_stages, other.project_stages),
+        ] {
+            for (name, record) in theirs {
+                let merged = match mine.remove(&name) {
+                    Some(existing) => merge_records(existing, record),
+                    None => record,
+                };
+                mine.insert(name, merged);
+            }
+        }
+        for (chapter, theirs) in other.quizzes {
+            let merged = match self.quizzes.remove(&chapter) {
+                Some(mine) => {
+                    let best_percent = mine.best_percent.max(theirs.best_percent);
+                    let attempts = mine.attempts.max(theirs.attempts);
+                    let latest = if theirs.last_attempt > mine.last_attempt {
+                        theirs
+                    } else {
+                        mine
+                    };
+                    QuizRecord {
+                        attempts,
+                        best_percent,
+                        ..latest
+                    }
+                }
+                None => theirs,
+            };
+            self.quizzes.insert(chapter, merged);
+        }
+        for (item, theirs) in other.reviews {
+            match self.reviews.get(&item) {
+                Some(mine) if mine.last_reviewed >= theirs.last_reviewed => {}
+                _ => {
+                    self.reviews.insert(item, theirs);
+                }
+            }
+        }
+        self.active_days.extend(other.active_days);
+        self.compile_errors_fixed = self.compile_errors_fixed.max(other.compile_errors_fixed);
+        for (id, unlocked) in other.achievements {
+            let earliest = self.achievements.entry(id).or_insert(unlocked);
+            *earliest = (*earliest).min(unlocked);
+        }
+    }
+
+    fn records(&self, kind: Kind) -> &BTreeMap<String, Record> {
+        match kind {
+            Kind::Example => &self.examples,
+            Kind::Exercise => &self.exercises,
+            Kind::Puzzle => &self.puzzles,
+            Kind::ProjectStage => &self.project_stages,
+        }
+    }
+
+    fn records_mut(&mut self, kind: Kind) -> &mut BTreeMap<String, Record> {
+        match kind {
+            Kind::Example => &mut self.examples,
+            Kind::Exercise => &mut self.exercises,
+            Kind::Puzzle => &mut self.puzzles,
+            Kind::ProjectStage => &mut self.project_stages,
+        }
+    }
+}
+
+/// The contents of the file at `path`, or None if there is none.
+fn read(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(json) => Ok(Some(json)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("cannot read {}", path.display())),
+    }
+}
+
+/// Where a progress file in `version` is copied before it is upgraded:
+/// `progress.json.v1.bak`, or `progress.json.v1.2.bak` and so on if an
+/// earlier upgrade left one already.
+fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let mut backup = path.with_extension(format!("json.v{version}.bak"));
+    let mut copy = 2;
+    while backup.exists() {
+        backup = path.with_extension(format!("json.v{version}.{copy}.bak"));
+        copy += 1;
+    }
+    backup
+}
+
+/// The version of files from before `schema_version`.
+fn first_version() -> u32 {
+    1
+}
+
+/// Two records of the same thing: the latest attempt's, with the
+/// earliest first attempt and completion of either.
+fn merge_records(mine: Record, theirs: Record) -> Record {
+    let first_attempt = earliest(mine.first_attempt, theirs.first_attempt);
+    let completed_at = earliest(mine.completed_at, theirs.completed_at);
+    let attempts = mine.attempts.max(theirs.attempts);
+    let hints_used = mine.hints_used.max(theirs.hints_used);
+    let latest = if theirs.last_attempt > mine.last_attempt {
+        theirs
+    } else {
+        mine
+    };
+    Record {
+        attempts,
+        first_attempt,
+        completed_at,
+        hints_used,
+        ..latest
+    }
+}
+
+fn earliest(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        _ => a.or(b),
+    }
+}
+
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Today, counted in days since the Unix epoch (UTC).
+pub fn today() -> u64 {
+    now() / SECONDS_PER_DAY
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(attempts: u32, last_attempt: u64, completed_at: Option<u64>) -> Record {
+        Record {
+            attempts,
+            first_attempt: Some(last_attempt - u64::from(attempts)),
+            last_attempt: Some(last_attempt),
+            completed_at,
+            ..Record::default()
+        }
+    }
+
+    #[test]
+    fn merging_keeps_the_latest_attempt_and_every_completion() {
+        let mut here = Progress::default();
+        here.exercises
+            .insert("variables1".into(), record(3, 100, Some(90)));
+        here.exercises.insert("bits1".into(), record(1, 300, None));
+        let mut there = Progress::default();
+        there
+            .exercises
+            .insert("variables1".into(), record(5, 200, None));
+        there
+            .exercises
+            .insert("bits2".into(), record(2, 50, Some(50)));
+        there.active_days.insert(7);
+
+        here.merge(there);
+        let variables1 = &here.exercises["variables1"];
+        assert_eq!(variables1.attempts, 5);
+        assert_eq!(variables1.last_attempt, Some(200));
+        assert_eq!(variables1.first_attempt, Some(97));
+        assert_eq!(variables1.completed_at, Some(90));
+        assert!(here.is_completed(Kind::Exercise, "bits2"));
+        assert!(!here.is_completed(Kind::Exercise, "bits1"));
+        assert!(here.active_days.contains(&7));
+    }
+
+    #[test]
+    fn merging_twice_changes_nothing_more() {
+        let mut there = Progress::default();
+        there
+            .examples
+            .insert("02_variables".into(), record(2, 20, Some(20)));
+        there.record_quiz("variables", 3, 4);
+        there.achievements.insert("first_steps".into(), 20);
+
+        let mut once = Progress::default();
+        once.merge(serde_json::from_str(&serde_json::to_string(&there).unwrap()).unwrap());
+        let mut twice =
+            serde_json::from_str::<Progress>(&serde_json::to_string(&once).unwrap()).unwrap();
+        twice.merge(there);
+        assert_eq!(
+            serde_json::to_string(&once).unwrap(),
+            serde_json::to_string(&twice).unwrap()
+        );
+    }
+
+    /// Progress files as tutors before `schema_version` wrote them: just
+    /// after progress tracking was added, after quizzes, puzzles, and
+    /// projects, and after achievements.
+    const LEGACY: [(&str, &str); 3] = [
+        (
+            "v1_first_tracking",
+            include_str!("../fixtures/progress/v1_first_tracking.json"),
+        ),
+        (
+            "v1_quizzes_and_puzzles",
+            include_str!("../fixtures/progress/v1_quizzes_and_puzzles.json"),
+        ),
+        (
+            "v1_achievements",
+            include_str!("../fixtures/progress/v1_achievements.json"),
+        ),
+    ];
+
+    /// A fresh directory for one test's files.
+    fn scratch_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("tutor-progress-{}-{test}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A step for the tests of the upgrade itself, while the format has
+    /// needed none: as if version 1 had called `compile_errors_fixed`
+    /// `errors_fixed`.
+    const RENAME_ERRORS_FIXED: &[Migration] = &[Migration {
+        from: 1,
+        apply: |fields| {
+            if let Some(fixed) = fields.remove("errors_fixed") {
+                fields.insert("compile_errors_fixed".to_string(), fixed);
+            }
+            Ok(())
+        },
+    }];
+
+    /// The achievements fixture, with that field under its old name.
+    fn with_errors_fixed() -> String {
+        LEGACY[2]
+            .1
+            .replace("\"compile_errors_fixed\"", "\"errors_fixed\"")
+    }
+
+    #[test]
+    fn the_migrations_lead_from_version_1_to_the_current_one() {
+        let froms: Vec<u32> = MIGRATIONS.iter().map(|migration| migration.from).collect();
+        assert_eq!(froms, (1..SCHEMA_VERSION).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn legacy_files_keep_everything_they_recorded() {
+        let (first, version) = Progress::parse(LEGACY[0].1, MIGRATIONS).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(first.schema_version, SCHEMA_VERSION);
+        assert!(first.is_completed(Kind::Example, "02_variables"));
+        assert!(!first.is_completed(Kind::Example, "03_ownership"));
+        let variables1 = first.get(Kind::Exercise, "variables1").unwrap();
+        assert_eq!(variables1.attempts, 3);
+        assert_eq!(variables1.completed_at, Some(1696346400));
+        assert_eq!(variables1.hints_used, 0);
+
+        let (quizzes, _) = Progress::parse(LEGACY[1].1, MIGRATIONS).unwrap();
+        assert_eq!(quizzes.exercises["variables2"].hints_used, 1);
+        assert_eq!(quizzes.quizzes["closures"].best_percent, 80);
+        assert!(quizzes.is_completed(Kind::Puzzle, "01_moved_name"));
+        assert!(quizzes.is_completed(Kind::ProjectStage, "minigrep/1"));
+
+        let (achievements, _) = Progress::parse(LEGACY[2].1, MIGRATIONS).unwrap();
+        assert!(achievements.exercises["variables3"].broken);
+        assert_eq!(achievements.reviews["exercise/variables1"].due_day, 19677);
+        assert_eq!(achievements.active_days, BTreeSet::from([19675, 19676]));
+        assert_eq!(achievements.compile_errors_fixed, 1);
+        assert_eq!(achievements.achievements["first_fix"], 1700001200);
+    }
+
+    #[test]
+    fn legacy_files_are_version_1_even_read_with_serde_alone() {
+        for (name, json) in LEGACY {
+            let progress: Progress = serde_json::from_str(json).expect(name);
+            assert_eq!(progress.schema_version, 1, "{name}");
+        }
+    }
+
+    #[test]
+    fn a_saved_legacy_file_reads_back_unchanged() {
+        for (name, json) in LEGACY {
+            let (loaded, _) = Progress::parse(json, MIGRATIONS).unwrap();
+            let saved = serde_json::to_string_pretty(&loaded).unwrap();
+            let (again, version) = Progress::parse(&saved, MIGRATIONS).unwrap();
+            assert_eq!(version, SCHEMA_VERSION, "{name}");
+            assert_eq!(
+                serde_json::to_string_pretty(&again).unwrap(),
+                saved,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn the_steps_from_a_files_version_on_are_applied() {
+        let (upgraded, version) =
+            Progress::parse(&with_errors_fixed(), RENAME_ERRORS_FIXED).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(upgraded.schema_version, 2);
+        assert_eq!(upgraded.compile_errors_fixed, 1);
+
+        // A file already in version 2 skips the step
+        let current = with_errors_fixed().replacen('{', "{\"schema_version\": 2,", 1);
+        let (current, version) = Progress::parse(&current, RENAME_ERRORS_FIXED).unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(current.compile_errors_fixed, 0);
+    }
+
+    #[test]
+    fn files_from_a_newer_tutor_are_refused() {
+        let newer = format!("{{\"schema_version\": {}}}", SCHEMA_VERSION + 1);
+        let err = Progress::from_json(&newer).unwrap_err().to_string();
+        assert!(err.contains("newer tutor"), "{err}");
+        assert!(Progress::from_json("{\"schema_version\": \"1\"}").is_err());
+        assert!(Progress::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn loading_a_current_file_leaves_it_alone() {
+        let dir = scratch_dir("current");
+        let path = dir.join("progress.json");
+        // Version-less, which is version 1, the current one
+        fs::write(&path, LEGACY[2].1).unwrap();
+
+        let (progress, backup) = Progress::load_upgrading(&path, MIGRATIONS).unwrap();
+        assert_eq!(backup, None);
+        assert!(progress.is_completed(Kind::Exercise, "variables1"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), LEGACY[2].1);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn loading_an_old_file_upgrades_it_in_place_and_keeps_a_copy() {
+        let dir = scratch_dir("upgrade");
+        let path = dir.join("progress.json");
+        let old = with_errors_fixed();
+        fs::write(&path, &old).unwrap();
+
+        let (progress, backup) = Progress::load_upgrading(&path, RENAME_ERRORS_FIXED).unwrap();
+        let backup = backup.expect("the file was upgraded");
+        assert_eq!(backup, dir.join("progress.json.v1.bak"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), old);
+        assert_eq!(progress.compile_errors_fixed, 1);
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("\"schema_version\": 2"));
+        assert!(saved.contains("\"compile_errors_fixed\": 1"));
+
+        // Now it is current, and loading it again changes nothing
+        let (again, backup) = Progress::load_upgrading(&path, RENAME_ERRORS_FIXED).unwrap();
+        assert_eq!(backup, None);
+        assert_eq!(fs::read_to_string(&path).unwrap(), saved);
+        assert_eq!(again.compile_errors_fixed, 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_second_upgrade_keeps_the_first_copy_too() {
+        let dir = scratch_dir("second-upgrade");
+        let path = dir.join("progress.json");
+        // As if the learner put back an older copy of their file
+        for json in &LEGACY[..2] {
+            fs::write(&path, json.1).unwrap();
+            Progress::load_upgrading(&path, RENAME_ERRORS_FIXED).unwrap();
+        }
+        assert_eq!(
+            fs::read_to_string(dir.join("progress.json.v1.bak")).unwrap(),
+            LEGACY[0].1
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("progress.json.v1.2.bak")).unwrap(),
+            LEGACY[1].1
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_from_leaves_someone_elses_old_file_alone() {
+        let dir = scratch_dir("load-from");
+        let path = dir.join("progress.json");
+        fs::write(&path, LEGACY[0].1).unwrap();
+        let progress = Progress::load_from(&path).unwrap();
+        assert!(progress.is_completed(Kind::Exercise, "variables1"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), LEGACY[0].1);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
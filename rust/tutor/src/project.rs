@@ -0,0 +1,186 @@
+//! `tutor project`: bigger programs, built one stage at a time.
+//!
+//! A project is a directory under `rust/projects/` with a `project.toml`.
+//! It is a cargo package of its own, outside the workspace because its tests
+//! fail until the learner has done the work. `project.toml` lists the stages
+//! in order, and stage N is
+//! checked by the integration tests in `tests/stageN.rs`, so a stage can be
+//! checked without the later ones compiling or passing.
+//!
+//! ```text
+//! projects/minigrep/
+//! ├── project.toml      <- title and stages (goal, hint)
+//! ├── src/              <- the code the learner writes
+//! └── tests/stage1.rs   <- what stage 1 has to do
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::checker::{self, Report};
+use crate::course::{file_stem, sorted_entries};
+use crate::errors::{self, ErrorCode};
+use crate::t;
+
+/// One project and its stages.
+#[derive(Debug)]
+pub struct Project {
+    /// Directory name, e.g. `minigrep`.
+    pub name: String,
+    pub dir: PathBuf,
+    pub title: String,
+    pub summary: String,
+    pub stages: Vec<Stage>,
+}
+
+/// One stage in `project.toml`.
+#[derive(Debug, Deserialize)]
+pub struct Stage {
+    pub title: String,
+    /// What to build, shown before the stage's test results.
+    pub goal: String,
+    pub hint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct File {
+    title: String,
+    #[serde(default)]
+    summary: String,
+    #[serde(default, rename = "stage")]
+    stages: Vec<Stage>,
+}
+
+impl Project {
+    /// The progress key of stage `number` (counted from 1).
+    pub fn stage_key(&self, number: usize) -> String {
+        format!("{}/{number}", self.name)
+    }
+}
+
+/// Finds every project under `<root>/projects`, in order.
+pub fn discover(root: &Path) -> Result<Vec<Project>> {
+    let dir = root.join("projects");
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut projects = Vec::new();
+    for dir in sorted_entries(&dir)? {
+        let path = dir.join("project.toml");
+        if !path.is_file() {
+            continue;
+        }
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+        let file: File =
+            toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+        projects.push(Project {
+            name: file_stem(&dir)?,
+            dir,
+            title: file.title,
+            summary: file.summary,
+            stages: file.stages,
+        });
+    }
+    Ok(projects)
+}
+
+/// Looks up a project by name.
+pub fn find<'a>(projects: &'a [Project], name: &str) -> Option<&'a Project> {
+    projects.iter().find(|project| project.name == name)
+}
+
+/// Builds `project` and runs the tests of stage `number` (counted from 1),
+/// placing artifacts under `build_dir`.
+pub fn check(project: &Project, number: usize, build_dir: &Path) -> Result<Report> {
+    let test = format!("stage{number}");
+    let target_dir = build_dir.join("projects").join(&project.name);
+    let cargo = |args: &[&str]| {
+        let mut command = Command::new("cargo");
+        command
+            .arg("test")
+            .arg("--manifest-path")
+            .arg(project.dir.join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .args(["--test", &test])
+            .args(args);
+        command
+    };
+
+    // Build first, so a compile error isn't mistaken for a test run.
+    let build = cargo(&["--no-run", "--quiet", "--color=always"])
+        .output()
+        .context("failed to launch cargo; is Rust installed and on your PATH?")?;
+    if !build.status.success() {
+        return Ok(Report::CompileError(
+            String::from_utf8_lossy(&build.stderr).into_owned(),
+        ));
+    }
+
+    let output = cargo(&["--", "--color", "never", "--test-threads", "1"])
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .with_context(|| format!("failed to run the tests of {}", project.name))?;
+    Ok(Report::Tested(checker::parse_test_output(
+        &String::from_utf8_lossy(&output.stdout),
+    )))
+}
+
+/// Prints the goal of stage `number` and how its tests went.
+pub fn print_report(project: &Project, number: usize, report: &Report, errors: &[ErrorCode]) {
+    let stage = &project.stages[number - 1];
+    println!(
+        "{}\n",
+        t!(
+            "project-stage",
+            name = project.name.as_str(),
+            number = number,
+            total = project.stages.len(),
+            title = stage.title.as_str()
+        )
+    );
+    println!("{}\n", stage.goal.trim());
+    match report {
+        Report::CompileError(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!(
+                "{}",
+                t!("project-compile-error", name = project.name.as_str())
+            );
+            errors::suggest(errors, diagnostics);
+        }
+        Report::Tested(results) => {
+            checker::print_results(results);
+            let failing = report.failures().count();
+            if failing == 0 {
+                println!("\n{}", t!("project-stage-done", number = number));
+                if let Some(next) = project.stages.get(number) {
+                    println!(
+                        "   {}",
+                        t!(
+                            "project-next-stage",
+                            number = number + 1,
+                            title = next.title.as_str(),
+                            name = project.name.as_str()
+                        )
+                    );
+                }
+            } else {
+                println!(
+                    "\n{}",
+                    t!("project-failing", failing = failing, total = results.len())
+                );
+            }
+        }
+    }
+    if !report.passed() {
+        if let Some(hint) = &stage.hint {
+            println!("💡 {hint}");
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! `tutor puzzle`: programs that don't compile, to be fixed under rules.
+//!
+//! Each puzzle in `rust/puzzles/` fails for an ownership or lifetime reason.
+//! The rules in `puzzles/puzzles.toml` close off the easy ways out ("no
+//! `.clone()`"), and are checked on the syntax tree, parsed with `syn`,
+//! before the program is compiled. The puzzle is solved when the code obeys
+//! every rule, compiles, and prints its `// EXPECTED:` output.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+
+use crate::compiler::{self, Outcome};
+use crate::course::{file_stem, sorted_entries, Course, Example};
+use crate::errors::ErrorCode;
+use crate::t;
+use crate::{errors, runner};
+
+/// One puzzle file and its rules.
+#[derive(Debug)]
+pub struct Puzzle {
+    /// File stem without its number, e.g. `moved_name`.
+    pub name: String,
+    pub path: PathBuf,
+    pub rules: Rules,
+}
+
+/// A puzzle's entry in `puzzles.toml`.
+#[derive(Debug, Default, Deserialize)]
+pub struct Rules {
+    #[serde(default)]
+    forbid_methods: Vec<String>,
+    #[serde(default)]
+    forbid_types: Vec<String>,
+    #[serde(default)]
+    forbid_unsafe: bool,
+    /// Signatures like `fn add_points(player: &mut Player, points: u32)`.
+    #[serde(default)]
+    keep_signatures: Vec<String>,
+    pub hint: Option<String>,
+}
+
+impl Rules {
+    /// The rules in words, one per line.
+    pub fn describe(&self) -> Vec<String> {
+        let mut rules = Vec::new();
+        if !self.forbid_methods.is_empty() {
+            let methods: Vec<String> = self
+                .forbid_methods
+                .iter()
+                .map(|method| format!("`.{method}()`"))
+                .collect();
+            rules.push(t!("puzzle-rule-forbid", items = methods.join(", ")));
+        }
+        if !self.forbid_types.is_empty() {
+            let types: Vec<String> = self
+                .forbid_types
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect();
+            rules.push(t!("puzzle-rule-forbid", items = types.join(", ")));
+        }
+        if self.forbid_unsafe {
+            rules.push(t!("puzzle-rule-forbid", items = "`unsafe`"));
+        }
+        for signature in &self.keep_signatures {
+            rules.push(t!("puzzle-rule-keep", signature = signature.as_str()));
+        }
+        rules
+    }
+}
+
+/// A broken rule, and where.
+struct Violation {
+    line: usize,
+    message: String,
+}
+
+/// Finds every puzzle in `<root>/puzzles`, in order.
+pub fn discover(root: &Path) -> Result<Vec<Puzzle>> {
+    let dir = root.join("puzzles");
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let rules_path = dir.join("puzzles.toml");
+    let mut rules: BTreeMap<String, Rules> = if rules_path.is_file() {
+        let text = fs::read_to_string(&rules_path)
+            .with_context(|| format!("cannot read {}", rules_path.display()))?;
+        toml::from_str(&text).with_context(|| format!("{} is not valid", rules_path.display()))?
+    } else {
+        BTreeMap::new()
+    };
+
+    let mut puzzles = Vec::new();
+    for path in sorted_entries(&dir)? {
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            let stem = file_stem(&path)?;
+            let name = match stem.split_once('_') {
+                Some((prefix, rest)) if prefix.chars().all(|c| c.is_ascii_digit()) => rest,
+                _ => stem.as_str(),
+            }
+            .to_string();
+            puzzles.push(Puzzle {
+                rules: rules.remove(&name).unwrap_or_default(),
+                name,
+                path,
+            });
+        }
+    }
+    Ok(puzzles)
+}
+
+/// Checks `puzzle`'s rules, then compiles and runs it. Returns whether it
+/// is solved.
+pub fn check(course: &Course, puzzle: &Puzzle, known_errors: &[ErrorCode]) -> Result<bool> {
+    println!("🧩 {} ({})\n", puzzle.name, puzzle.path.display());
+    let source = fs::read_to_string(&puzzle.path)
+        .with_context(|| format!("cannot read {}", puzzle.path.display()))?;
+
+    // Code that doesn't parse is left to the compiler, which explains
+    // syntax errors much better.
+    if let Ok(file) = syn::parse_file(&source) {
+        let violations = violations(&file, &puzzle.rules);
+        if !violations.is_empty() {
+            for violation in &violations {
+                println!(
+                    "{}",
+                    t!(
+                        "puzzle-violation",
+                        line = violation.line,
+                        message = violation.message.as_str()
+                    )
+                );
+            }
+            println!("\n{}", t!("puzzle-rules-broken"));
+            return Ok(false);
+        }
+    }
+
+    let example = Example {
+        name: format!("puzzles/{}", puzzle.name),
+        group: None,
+        path: puzzle.path.clone(),
+        manifest: None,
+    };
+    match compiler::build_and_run(&example, &course.build_dir())? {
+        Outcome::Ran { success: true, .. } => {
+            println!("{}", t!("puzzle-solved"));
+            Ok(true)
+        }
+        Outcome::CompileError(diagnostics) => {
+            eprintln!("{diagnostics}");
+            println!(
+                "{}",
+                t!("puzzle-compile-error", name = puzzle.name.as_str())
+            );
+            errors::suggest(known_errors, &diagnostics);
+            if puzzle.rules.hint.is_some() {
+                println!("{}", t!("puzzle-stuck", name = puzzle.name.as_str()));
+            }
+            Ok(false)
+        }
+        Outcome::WrongOutput {
+            expected, stdout, ..
+        } => {
+            print!("{stdout}");
+            println!("\n{}\n\n{expected}", t!("puzzle-wrong-output"));
+            Ok(false)
+        }
+        Outcome::Ran { stderr, .. } => {
+            eprint!("{stderr}");
+            println!("\n{}", t!("puzzle-failed"));
+            Ok(false)
+        }
+        Outcome::TimedOut { .. } => {
+            println!(
+                "{}",
+                t!("puzzle-timed-out", seconds = runner::TIMEOUT.as_secs())
+            );
+            Ok(false)
+        }
+    }
+}
+
+fn violations(file: &syn::File, rules: &Rules) -> Vec<Violation> {
+    let mut scan = Scan {
+        rules,
+        violations: Vec::new(),
+    };
+    scan.visit_file(file);
+    let mut violations = scan.violations;
+
+    for expected in &rules.keep_signatures {
+        // A signature alone isn't an item; with a `;` it parses as one.
+        let Ok(expected) = syn::parse_str::<syn::ForeignItemFn>(&format!("{expected};")) else {
+            violations.push(Violation {
+                line: 0,
+                message: format!("puzzles.toml has a signature that doesn't parse: `{expected}`"),
+            });
+            continue;
+        };
+        let mut finder = FindFn {
+            name: &expected.sig.ident,
+            found: None,
+        };
+        finder.visit_file(file);
+        match finder.found {
+            Some(found) if *found == expected.sig => {}
+            Some(found) => violations.push(Violation {
+                line: found.span().start().line,
+                message: t!(
+                    "puzzle-keep-signature",
+                    name = expected.sig.ident.to_string()
+                ),
+            }),
+            None => violations.push(Violation {
+                line: 0,
+                message: t!("puzzle-keep-fn", name = expected.sig.ident.to_string()),
+            }),
+        }
+    }
+    violations.sort_by_key(|violation| violation.line);
+    violations
+}
+
+/// Looks for method calls, names, and `unsafe` that the rules forbid.
+struct Scan<'a> {
+    rules: &'a Rules,
+    violations: Vec<Violation>,
+}
+
+impl Scan<'_> {
+    fn report(&mut self, span: proc_macro2::Span, message: String) {
+        self.violations.push(Violation {
+            line: span.start().line,
+            message,
+        });
+    }
+
+    /// Reports `what`, in backticks, as something the rules forbid.
+    fn not_allowed(&mut self, span: proc_macro2::Span, what: String) {
+        self.report(span, t!("puzzle-not-allowed", what = what));
+    }
+
+    fn check_name(&mut self, ident: &syn::Ident) {
+        if self.rules.forbid_types.iter().any(|name| ident == name) {
+            self.not_allowed(ident.span(), format!("`{ident}`"));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Scan<'_> {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = &call.method;
+        if self.rules.forbid_methods.iter().any(|name| method == name) {
+            self.not_allowed(method.span(), format!("`.{method}()`"));
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_path_segment(&mut self, segment: &'ast syn::PathSegment) {
+        self.check_name(&segment.ident);
+        visit::visit_path_segment(self, segment);
+    }
+
+    fn visit_use_name(&mut self, name: &'ast syn::UseName) {
+        self.check_name(&name.ident);
+    }
+
+    fn visit_use_path(&mut self, path: &'ast syn::UsePath) {
+        self.check_name(&path.ident);
+        visit::visit_use_path(self, path);
+    }
+
+    fn visit_expr_unsafe(&mut self, block: &'ast syn::ExprUnsafe) {
+        if self.rules.forbid_unsafe {
+            self.not_allowed(block.unsafe_token.span, "`unsafe`".to_string());
+        }
+        visit::visit_expr_unsafe(self, block);
+    }
+
+    fn visit_signature(&mut self, signature: &'ast syn::Signature) {
+        if let (true, Some(token)) = (self.rules.forbid_unsafe, &signature.unsafety) {
+            self.not_allowed(token.span, "`unsafe fn`".to_string());
+        }
+        visit::visit_signature(self, signature);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        if let (true, Some(token)) = (self.rules.forbid_unsafe, &item.unsafety) {
+            self.not_allowed(token.span, "`unsafe impl`".to_string());
+        }
+        visit::visit_item_impl(self, item);
+    }
+}
+
+/// Finds the signature of the function (or method) called `name`.
+struct FindFn<'a, 'ast> {
+    name: &'a syn::Ident,
+    found: Option<&'ast syn::Signature>,
+}
+
+impl<'ast> Visit<'ast> for FindFn<'_, 'ast> {
+    fn visit_signature(&mut self, signature: &'ast syn::Signature) {
+        if self.found.is_none() && signature.ident == *self.name {
+            self.found = Some(signature);
+        }
+    }
+}
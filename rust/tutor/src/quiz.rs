@@ -0,0 +1,315 @@
+//! `tutor quiz`: short multiple-choice and predict-the-output quizzes.
+//!
+//! Each chapter's questions live in `rust/quizzes/<chapter>.toml` (or
+//! `.json`, with the same shape):
+//!
+//! ```toml
+//! [[question]]
+//! kind = "choice"
+//! prompt = "Which keyword makes a variable mutable?"
+//! choices = ["var", "mut", "let"]
+//! answer = "mut"
+//! explanation = "Variables are immutable unless declared with `let mut`."
+//!
+//! [[question]]
+//! kind = "output"
+//! prompt = "What does this print?"
+//! code = '''
+//! let x = 5;
+//! let x = x + 1;
+//! println!("{x}");
+//! '''
+//! answer = "6"
+//! ```
+//!
+//! A bank can be translated whole, as `<chapter>.<language>.toml` next to
+//! it (see [`crate::i18n`]); `tutor --lang es quiz variables` asks the
+//! questions in `variables.es.toml`.
+//!
+//! Questions and choices are shuffled on every run. Answers to output
+//! questions are compared line by line, ignoring surrounding whitespace.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::course::{file_stem, sorted_entries};
+use crate::i18n;
+use crate::t;
+
+/// One question from a bank.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Question {
+    /// Pick one of several answers.
+    Choice {
+        prompt: String,
+        choices: Vec<String>,
+        /// The correct choice, spelled exactly as in `choices`.
+        answer: String,
+        explanation: Option<String>,
+    },
+    /// Read a snippet and type what it prints.
+    Output {
+        prompt: String,
+        code: String,
+        answer: String,
+        explanation: Option<String>,
+    },
+}
+
+impl Question {
+    /// Whether `reply` answers the question: the text of the right choice,
+    /// or the snippet's output, compared as in the quiz.
+    pub fn is_correct(&self, reply: &str) -> bool {
+        match self {
+            Question::Choice { answer, .. } => reply.trim() == answer.trim(),
+            Question::Output { answer, .. } => normalize(reply) == normalize(answer),
+        }
+    }
+}
+
+/// All the questions for one chapter.
+#[derive(Debug)]
+pub struct Bank {
+    pub chapter: String,
+    pub questions: Vec<Question>,
+}
+
+#[derive(Deserialize)]
+struct File {
+    #[serde(rename = "question")]
+    questions: Vec<Question>,
+}
+
+/// How a quiz went.
+#[derive(Debug, Clone, Copy)]
+pub struct Score {
+    pub correct: usize,
+    pub total: usize,
+}
+
+/// Lists the chapters that have a question bank, in alphabetical order.
+pub fn chapters(root: &Path) -> Result<Vec<String>> {
+    let dir = root.join("quizzes");
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut chapters = Vec::new();
+    for path in sorted_entries(&dir)? {
+        if bank_format(&path).is_some() && !i18n::is_translation(&path) {
+            chapters.push(file_stem(&path)?);
+        }
+    }
+    Ok(chapters)
+}
+
+/// Loads `<root>/quizzes/<chapter>.toml` or `.json`.
+pub fn load(root: &Path, chapter: &str) -> Result<Bank> {
+    let dir = root.join("quizzes");
+    let path = ["toml", "json"]
+        .iter()
+        .map(|ext| dir.join(format!("{chapter}.{ext}")))
+        .find(|path| path.is_file());
+    let Some(path) = path else {
+        bail!("there is no quiz for `{chapter}`; run `tutor quiz` to list them");
+    };
+    let path = i18n::current().localized(&path).unwrap_or(path);
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let file: File = match bank_format(&path) {
+        Some(Format::Json) => serde_json::from_str(&text).map_err(anyhow::Error::from),
+        Some(Format::Toml) | None => toml::from_str(&text).map_err(anyhow::Error::from),
+    }
+    .with_context(|| format!("{} is not a valid question bank", path.display()))?;
+
+    for (number, question) in file.questions.iter().enumerate() {
+        if let Question::Choice {
+            choices, answer, ..
+        } = question
+        {
+            if !choices.contains(answer) {
+                bail!(
+                    "question {} in {} has an answer that isn't one of its choices",
+                    number + 1,
+                    path.display()
+                );
+            }
+        }
+    }
+    if file.questions.is_empty() {
+        bail!("{} has no questions", path.display());
+    }
+    Ok(Bank {
+        chapter: chapter.to_string(),
+        questions: file.questions,
+    })
+}
+
+enum Format {
+    Toml,
+    Json,
+}
+
+fn bank_format(path: &Path) -> Option<Format> {
+    match path.extension()?.to_str()? {
+        "toml" => Some(Format::Toml),
+        "json" => Some(Format::Json),
+        _ => None,
+    }
+}
+
+/// Asks up to `count` questions from the bank in random order, reading
+/// answers from stdin.
+pub fn run(bank: &Bank, count: Option<usize>, rng: &mut fastrand::Rng) -> Result<Score> {
+    let mut questions = bank.questions.clone();
+    rng.shuffle(&mut questions);
+    questions.truncate(count.unwrap_or(questions.len()).max(1));
+
+    let total = questions.len();
+    println!(
+        "{}",
+        t!("quiz-title", chapter = bank.chapter.as_str(), total = total)
+    );
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut correct = 0;
+    for (number, question) in questions.iter().enumerate() {
+        println!(
+            "\n{}",
+            t!("quiz-question", number = number + 1, total = total)
+        );
+        if ask(question, &mut input, rng)? {
+            correct += 1;
+        }
+    }
+    println!("\n{}", t!("quiz-score", correct = correct, total = total));
+    Ok(Score { correct, total })
+}
+
+/// Scores `answers`, one per question in the bank's order, without asking
+/// anything. Questions without an answer count as wrong.
+pub fn grade(bank: &Bank, answers: &[String]) -> Score {
+    let correct = bank
+        .questions
+        .iter()
+        .zip(answers)
+        .filter(|(question, answer)| question.is_correct(answer))
+        .count();
+    Score {
+        correct,
+        total: bank.questions.len(),
+    }
+}
+
+/// Asks one question, returning whether the learner got it right.
+pub(crate) fn ask(
+    question: &Question,
+    input: &mut impl BufRead,
+    rng: &mut fastrand::Rng,
+) -> Result<bool> {
+    let (right, answer, explanation) = match question {
+        Question::Choice {
+            prompt,
+            choices,
+            answer,
+            explanation,
+        } => {
+            let mut choices = choices.clone();
+            rng.shuffle(&mut choices);
+            println!("{prompt}");
+            for (letter, choice) in ('a'..).zip(&choices) {
+                println!("  {letter}) {choice}");
+            }
+            let correct_letter = ('a'..)
+                .zip(&choices)
+                .find(|(_, choice)| *choice == answer)
+                .map(|(letter, _)| letter)
+                .unwrap_or('a');
+            let reply = loop {
+                let line = prompt_line("> ", input)?;
+                let reply = line.trim().to_lowercase();
+                let valid = reply.len() == 1
+                    && reply
+                        .chars()
+                        .next()
+                        .is_some_and(|c| ('a'..).take(choices.len()).any(|letter| letter == c));
+                if valid {
+                    break reply;
+                }
+                println!("{}", t!("quiz-pick-letter"));
+            };
+            (
+                reply.starts_with(correct_letter),
+                format!("{correct_letter}) {answer}"),
+                explanation,
+            )
+        }
+        Question::Output {
+            prompt,
+            code,
+            answer,
+            explanation,
+        } => {
+            println!("{prompt}\n");
+            for line in code.trim_end().lines() {
+                println!("    {line}");
+            }
+            println!("\n{}", t!("quiz-type-output"));
+            let mut lines = Vec::new();
+            loop {
+                let line = prompt_line("> ", input)?;
+                if line.trim().is_empty() {
+                    break;
+                }
+                lines.push(line);
+            }
+            (
+                question.is_correct(&lines.join("\n")),
+                answer.trim().to_string(),
+                explanation,
+            )
+        }
+    };
+
+    if right {
+        println!("{}", t!("quiz-correct"));
+    } else {
+        println!("{}", t!("quiz-wrong"));
+        for line in answer.lines() {
+            println!("   {line}");
+        }
+    }
+    if let Some(explanation) = explanation {
+        println!("💡 {}", explanation.trim());
+    }
+    Ok(right)
+}
+
+/// Prints `prompt` and reads one line, failing if stdin is closed.
+pub(crate) fn prompt_line(prompt: &str, input: &mut impl BufRead) -> Result<String> {
+    print!("{prompt}");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        bail!("the quiz was stopped before it finished");
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Trims every line and drops blank ones at either end.
+fn normalize(text: &str) -> Vec<&str> {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let start = lines
+        .iter()
+        .position(|line| !line.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(start, |end| end + 1);
+    lines[start..end].to_vec()
+}
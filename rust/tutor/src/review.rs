@@ -0,0 +1,350 @@
+//! `tutor review`: spaced repetition of what the learner has already done,
+//! so that it sticks.
+//!
+//! The items are the questions of every quiz the learner has taken, and the
+//! exercises they have completed. Each is due for the first time the day
+//! after the quiz or the exercise, and again at growing intervals as long
+//! as the reviews go well:
+//!
+//! - a quiz question is asked again. A right answer is a good review
+//!   (quality 4 of 5), a wrong one a bad review (quality 1).
+//! - an exercise shows its task; the learner recalls how they solved it,
+//!   then sees the solution and rates how well they remembered, 0 to 5.
+//!
+//! The intervals follow SM-2, the algorithm behind SuperMemo and Anki.
+//! After a review of quality `q`, an item that went well (`q >= 3`) comes
+//! back in 1 day, then 6, then the last interval times its ease; one that
+//! went badly starts over at 1 day. The ease starts at 2.5 and changes by
+//! `0.1 - (5 - q) * (0.08 + (5 - q) * 0.02)`, but never drops below 1.3.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::exercise::Exercise;
+use crate::progress::{self, today, Kind, Progress, ReviewRecord, SECONDS_PER_DAY};
+use crate::quiz::{self, Question};
+use crate::t;
+
+const FIRST_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+/// Quality of a review whose quiz question was answered right, or wrong.
+const RIGHT: u8 = 4;
+const WRONG: u8 = 1;
+
+/// Something to review.
+#[derive(Debug)]
+pub enum Item<'a> {
+    Question { chapter: String, question: Question },
+    Exercise(&'a Exercise),
+}
+
+/// An item and where it is in the schedule.
+#[derive(Debug)]
+pub struct Card<'a> {
+    /// The item's key in [`Progress::reviews`].
+    pub id: String,
+    pub item: Item<'a>,
+    pub due_day: u64,
+    /// When it was last reviewed, or else done for the first time.
+    pub last_seen: Option<u64>,
+}
+
+impl Card<'_> {
+    /// The chapter the item belongs to.
+    pub fn chapter(&self) -> &str {
+        match &self.item {
+            Item::Question { chapter, .. } => chapter,
+            Item::Exercise(exercise) => &exercise.topic,
+        }
+    }
+}
+
+/// Every item there is to review, most overdue first.
+pub fn cards<'a>(
+    root: &Path,
+    exercises: &'a [Exercise],
+    progress: &Progress,
+) -> Result<Vec<Card<'a>>> {
+    let mut cards = Vec::new();
+    let mut add = |id: String, item: Item<'a>, done: Option<u64>| {
+        let record = progress.reviews.get(&id);
+        let due_day = match record {
+            Some(record) => record.due_day,
+            None => done.map_or(0, |done| done / SECONDS_PER_DAY + 1),
+        };
+        let last_seen = record.and_then(|record| record.last_reviewed).or(done);
+        cards.push(Card {
+            id,
+            item,
+            due_day,
+            last_seen,
+        });
+    };
+
+    for chapter in quiz::chapters(root)? {
+        let Some(taken) = progress.quizzes.get(&chapter) else {
+            continue;
+        };
+        for question in quiz::load(root, &chapter)?.questions {
+            add(
+                question_id(&chapter, &question),
+                Item::Question {
+                    chapter: chapter.clone(),
+                    question,
+                },
+                taken.last_attempt,
+            );
+        }
+    }
+    for exercise in exercises {
+        let Some(record) = progress.get(Kind::Exercise, &exercise.name) else {
+            continue;
+        };
+        if record.completed_at.is_some() && exercise.hints.is_some() {
+            add(
+                format!("exercise/{}", exercise.name),
+                Item::Exercise(exercise),
+                record.completed_at,
+            );
+        }
+    }
+    cards.sort_by(|a, b| (a.due_day, &a.id).cmp(&(b.due_day, &b.id)));
+    Ok(cards)
+}
+
+/// Reviews up to `count` of the cards due today, reading answers from
+/// stdin, and reschedules each in `progress`, saving it after every card.
+/// Returns how many were reviewed.
+pub fn review(
+    cards: &[Card],
+    progress: &mut Progress,
+    count: usize,
+    rng: &mut fastrand::Rng,
+) -> Result<usize> {
+    let today = today();
+    let due: Vec<&Card> = cards.iter().filter(|card| card.due_day <= today).collect();
+    if due.is_empty() {
+        match cards.first() {
+            Some(next) => println!(
+                "{}",
+                t!("review-nothing-today", when = when(next.due_day, today))
+            ),
+            None => println!("{}", t!("review-nothing-yet")),
+        }
+        return Ok(0);
+    }
+
+    let session = &due[..due.len().min(count.max(1))];
+    println!(
+        "{}",
+        t!("review-title", count = session.len(), due = due.len())
+    );
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    for (number, card) in session.iter().enumerate() {
+        println!(
+            "\n── {}/{} · {} ──",
+            number + 1,
+            session.len(),
+            card.chapter()
+        );
+        let quality = match &card.item {
+            Item::Question { question, .. } => {
+                if quiz::ask(question, &mut input, rng)? {
+                    RIGHT
+                } else {
+                    WRONG
+                }
+            }
+            Item::Exercise(exercise) => recall(exercise, &mut input)?,
+        };
+        progress.record_activity();
+        let record = progress
+            .reviews
+            .entry(card.id.clone())
+            .or_insert_with(|| first_record(today));
+        schedule(record, quality, today);
+        record.last_reviewed = Some(progress::now());
+        println!("{}", t!("review-next", when = when(record.due_day, today)));
+        // Each review counts even if the session is cut short
+        progress.save()?;
+    }
+
+    let left = due.len() - session.len();
+    println!("\n{}", t!("review-done", count = session.len()));
+    if left > 0 {
+        println!("{}", t!("review-more-due", left = left));
+    }
+    Ok(session.len())
+}
+
+/// Shows an exercise's task, then its solution, and asks how well the
+/// learner remembered it.
+fn recall(exercise: &Exercise, input: &mut impl BufRead) -> Result<u8> {
+    println!("{}\n", t!("review-exercise", name = exercise.name.as_str()));
+    let source = std::fs::read_to_string(&exercise.path).unwrap_or_default();
+    let mut task: Vec<&str> = source
+        .lines()
+        .take_while(|line| line.starts_with("//"))
+        .take_while(|line| !line.contains("Check your work"))
+        .collect();
+    while task.last().is_some_and(|line| line.trim() == "//") {
+        task.pop();
+    }
+    for line in task {
+        println!("  {line}");
+    }
+    quiz::prompt_line(&format!("\n{} ", t!("review-think")), input)?;
+    if let Some(hints) = &exercise.hints {
+        println!();
+        for line in hints.solution.trim_matches('\n').lines() {
+            println!("    {line}");
+        }
+    }
+    loop {
+        let reply = quiz::prompt_line(&format!("\n{} ", t!("review-remembered")), input)?;
+        match reply.trim().parse::<u8>() {
+            Ok(quality) if quality <= 5 => return Ok(quality),
+            _ => println!("{}", t!("review-pick-number")),
+        }
+    }
+}
+
+/// Prints, for each chapter, how many items it has, how many are due, and
+/// when the learner last worked on it.
+pub fn print_schedule(cards: &[Card]) {
+    let today = today();
+    if cards.is_empty() {
+        println!("{}", t!("review-nothing-yet"));
+        return;
+    }
+    let mut chapters: BTreeMap<&str, (usize, usize, Option<u64>)> = BTreeMap::new();
+    for card in cards {
+        let (items, due, last) = chapters.entry(card.chapter()).or_default();
+        *items += 1;
+        if card.due_day <= today {
+            *due += 1;
+        }
+        *last = (*last).max(card.last_seen);
+    }
+
+    println!("{}\n", t!("review-schedule-title"));
+    println!(
+        "  {:<22} {:>5} {:>5}  {}",
+        t!("review-column-chapter"),
+        t!("review-column-items"),
+        t!("review-column-due"),
+        t!("review-column-last")
+    );
+    for (chapter, (items, due, last)) in &chapters {
+        let last = last.map_or_else(
+            || t!("review-never"),
+            |last| ago(today.saturating_sub(last / SECONDS_PER_DAY)),
+        );
+        println!("  {chapter:<22} {items:>5} {due:>5}  {last}");
+    }
+    let due = cards.iter().filter(|card| card.due_day <= today).count();
+    match cards.iter().find(|card| card.due_day > today) {
+        Some(next) if due == 0 => println!(
+            "\n{}",
+            t!("review-nothing-due", when = when(next.due_day, today))
+        ),
+        _ => println!("\n{}", t!("review-due", due = due)),
+    }
+}
+
+/// A record for an item reviewed for the first time today.
+fn first_record(today: u64) -> ReviewRecord {
+    ReviewRecord {
+        repetitions: 0,
+        interval_days: 0,
+        ease: FIRST_EASE,
+        due_day: today,
+        last_reviewed: None,
+    }
+}
+
+/// Moves `record` along after a review of `quality` (0 to 5) on `today`,
+/// following SM-2.
+fn schedule(record: &mut ReviewRecord, quality: u8, today: u64) {
+    let quality = quality.min(5);
+    if quality >= 3 {
+        record.interval_days = match record.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (f64::from(record.interval_days) * record.ease).round() as u32,
+        };
+        record.repetitions += 1;
+    } else {
+        record.repetitions = 0;
+        record.interval_days = 1;
+    }
+    let miss = f64::from(5 - quality);
+    record.ease = (record.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+    record.due_day = today + u64::from(record.interval_days);
+}
+
+/// A key for a quiz question that survives reordering the bank: a hash of
+/// what it asks.
+fn question_id(chapter: &str, question: &Question) -> String {
+    let text = match question {
+        Question::Choice { prompt, .. } => prompt.clone(),
+        Question::Output { prompt, code, .. } => format!("{prompt}\n{code}"),
+    };
+    // FNV-1a, since std's hasher may change between Rust releases.
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("quiz/{chapter}/{hash:016x}")
+}
+
+fn when(day: u64, today: u64) -> String {
+    t!("review-when", days = day.saturating_sub(today))
+}
+
+fn ago(days: u64) -> String {
+    t!("review-ago", days = days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn good_reviews_space_out_one_six_then_by_ease() {
+        let mut record = first_record(100);
+        let mut intervals = Vec::new();
+        for day in [100, 101, 107] {
+            schedule(&mut record, 4, day);
+            intervals.push(record.interval_days);
+        }
+        assert_eq!(intervals, [1, 6, 15]);
+        assert_eq!(record.due_day, 107 + 15);
+        assert!((record.ease - FIRST_EASE).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_bad_review_starts_over_and_lowers_the_ease() {
+        let mut record = first_record(0);
+        schedule(&mut record, 5, 0);
+        schedule(&mut record, 5, 1);
+        schedule(&mut record, 1, 7);
+        assert_eq!(
+            (record.repetitions, record.interval_days, record.due_day),
+            (0, 1, 8)
+        );
+        assert!(record.ease < FIRST_EASE);
+    }
+
+    #[test]
+    fn the_ease_never_drops_below_the_minimum() {
+        let mut record = first_record(0);
+        for day in 0..20 {
+            schedule(&mut record, 0, day);
+        }
+        assert_eq!(record.ease, MIN_EASE);
+    }
+}
@@ -0,0 +1,206 @@
+//! Running a compiled program as a child process with a time limit, and
+//! checking what it printed.
+//!
+//! An example can state what it prints in an `// EXPECTED:` block, usually
+//! at the very end of the file:
+//!
+//! ```text
+//! // EXPECTED:
+//! // The value of x is: 5
+//! //
+//! // Done!
+//! ```
+//!
+//! Every `//` line after the marker is one line of output, up to the first
+//! line that isn't a `//` comment. Trailing whitespace and trailing blank
+//! lines don't count when comparing.
+
+use std::io::{Read, Write};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+/// How long an example may run before it is killed.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The line that starts an expected-output block.
+pub const EXPECTED_MARKER: &str = "// EXPECTED:";
+
+/// What happened when a program ran.
+#[derive(Debug)]
+pub struct Run {
+    /// How it exited, or `None` if it was killed for running too long.
+    pub status: Option<ExitStatus>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Runs `command` with no input, capturing its output, and kills it if it
+/// is still running after `timeout`.
+pub fn run(command: &mut Command, timeout: Duration) -> Result<Run> {
+    run_with_input(command, None, timeout)
+}
+
+/// [`run`], with `input` (if any) as the program's stdin.
+pub fn run_with_input(
+    command: &mut Command,
+    input: Option<&str>,
+    timeout: Duration,
+) -> Result<Run> {
+    let stdin = if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
+    let mut child = command
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {:?}", command.get_program()))?;
+    if let (Some(input), Some(mut pipe)) = (input, child.stdin.take()) {
+        // Written on its own thread, and then closed, so a program that
+        // reads only part of it, or none, can't stop us either
+        let input = input.to_string();
+        thread::spawn(move || pipe.write_all(input.as_bytes()));
+    }
+    // Read both pipes on their own threads so a chatty program can't fill
+    // one of them and block while we wait for it to exit.
+    let stdout = capture(child.stdout.take().context("no stdout")?);
+    let stderr = capture(child.stderr.take().context("no stderr")?);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    Ok(Run {
+        status,
+        stdout: join(stdout)?,
+        stderr: join(stderr)?,
+    })
+}
+
+fn capture(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<std::io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        pipe.read_to_end(&mut bytes).map(|_| bytes)
+    })
+}
+
+fn join(reader: thread::JoinHandle<std::io::Result<Vec<u8>>>) -> Result<String> {
+    let bytes = reader
+        .join()
+        .map_err(|_| anyhow::anyhow!("reading the program's output failed"))??;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// The output promised by the source's `// EXPECTED:` block, if it has one.
+pub fn expected_output(source: &str) -> Option<String> {
+    let mut lines = source.lines();
+    lines.find(|line| line.trim() == EXPECTED_MARKER)?;
+    let expected: Vec<&str> = lines
+        .map(str::trim)
+        .map_while(|line| line.strip_prefix("//"))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+    Some(expected.join("\n"))
+}
+
+/// Whether `stdout` is `expected`, ignoring trailing whitespace and blank
+/// lines.
+pub fn output_matches(stdout: &str, expected: &str) -> bool {
+    normalize(stdout) == normalize(expected)
+}
+
+/// The expected lines (`-`) next to the lines actually printed (`+`),
+/// wherever they differ.
+pub fn output_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+    for row in 0..expected.len().max(actual.len()) {
+        let (want, got) = (expected.get(row), actual.get(row));
+        if want.map(|line| line.trim_end()) == got.map(|line| line.trim_end()) {
+            continue;
+        }
+        if let Some(want) = want {
+            diff.push_str(&format!("  line {}: - {want}\n", row + 1));
+        }
+        if let Some(got) = got {
+            diff.push_str(&format!("  line {}: + {got}\n", row + 1));
+        }
+    }
+    diff
+}
+
+fn normalize(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_expected_block_keeps_indentation_and_empty_lines() {
+        let source = "fn main() {}\n\n// EXPECTED:\n// Scores:\n//   alice: 3\n//\n// Done!\n";
+        assert_eq!(
+            expected_output(source).as_deref(),
+            Some("Scores:\n  alice: 3\n\nDone!")
+        );
+    }
+
+    #[test]
+    fn the_expected_block_ends_at_the_first_line_that_isnt_a_comment() {
+        let source = "    // EXPECTED:\n    // 1\nfn main() {}\n// not output\n";
+        assert_eq!(expected_output(source).as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn a_source_without_a_block_promises_nothing() {
+        assert_eq!(expected_output("fn main() {}\n// EXPECTED 5\n"), None);
+    }
+
+    #[test]
+    fn only_trailing_whitespace_and_blank_lines_are_ignored() {
+        assert!(output_matches("a  \nb\n\n\n", "a\nb"));
+        assert!(!output_matches(" a\nb\n", "a\nb"));
+    }
+
+    #[test]
+    fn the_diff_shows_only_the_lines_that_differ() {
+        let diff = output_diff("one\ntwo\nthree", "one\n2\nthree\nfour");
+        assert_eq!(diff, "  line 2: - two\n  line 2: + 2\n  line 4: + four\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn input_goes_to_stdin_and_output_is_captured() {
+        let run = run_with_input(&mut Command::new("cat"), Some("hi\n"), TIMEOUT).unwrap();
+        assert!(run.status.unwrap().success());
+        assert_eq!(run.stdout, "hi\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_program_past_the_timeout_is_killed() {
+        let started = Instant::now();
+        let run = run(Command::new("sleep").arg("30"), Duration::from_millis(200)).unwrap();
+        assert!(run.status.is_none());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,502 @@
+//! `tutor new-lesson <chapter> <name>`: the files a new lesson needs, with
+//! TODOs where the teaching goes.
+//!
+//! ```text
+//! examples/<chapter>/NN_<name>.rs        <- the example, with an `// EXPECTED:` stub
+//! examples/<chapter>/Cargo.toml          <- a `[[bin]]` for it
+//! golden/<chapter>/NN_<name>.txt         <- what the stub prints
+//! exercises/MM_<chapter>/<topic>K.rs     <- a matching exercise with TODOs
+//! exercises/MM_<chapter>/tests/<topic>K.rs
+//! exercises/MM_<chapter>/info.toml       <- its hints
+//! solutions/MM_<chapter>/<topic>K.rs     <- its reference solution
+//! lessons.toml                           <- title, difficulty, tags, requires
+//! ```
+//!
+//! `NN` comes after the chapter's last example, which the new lesson
+//! requires, and whose difficulty and first tag it starts out with. A
+//! chapter that doesn't exist yet is created as a standalone one (see
+//! [`crate::course`]), and so is its exercise topic.
+//!
+//! The stubs already pass `tutor verify`, `tutor check-output`, and the
+//! checker, so the lesson can be filled in one piece at a time; the
+//! exercise fails `tutor check` until someone solves it, as an exercise
+//! should.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::course::{file_stem, sorted_entries, Course, Example};
+use crate::exercise;
+use crate::golden;
+use crate::manifest::Difficulty;
+
+/// What `tutor new-lesson` was asked for.
+#[derive(Debug)]
+pub struct Request<'a> {
+    pub chapter: &'a str,
+    /// The file name without its number, e.g. `pattern_guards`.
+    pub name: &'a str,
+    /// Defaults to `name` as a sentence, e.g. "Pattern guards".
+    pub title: Option<&'a str>,
+    /// Defaults to that of the chapter's last lesson, or beginner.
+    pub difficulty: Option<Difficulty>,
+}
+
+/// The lesson that was added.
+#[derive(Debug)]
+pub struct Scaffold {
+    /// The example's name, e.g. `enums/06_pattern_guards`.
+    pub id: String,
+    /// The exercise's name, e.g. `enums3`.
+    pub exercise: String,
+    /// Every file written, relative to the course root, and whether it is
+    /// new (`true`) or was added to.
+    pub files: Vec<(PathBuf, bool)>,
+}
+
+/// Writes the files for a new lesson in `request.chapter`.
+pub fn new_lesson(course: &Course, request: &Request) -> Result<Scaffold> {
+    let Request { chapter, name, .. } = *request;
+    check_identifier("chapter", chapter)?;
+    check_identifier("lesson name", name)?;
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        bail!("leave the number off `{name}`; the lesson gets the next one in {chapter}");
+    }
+
+    let root = &course.root;
+    let chapter_dir = root.join("examples").join(chapter);
+    let siblings: Vec<&Example> = course
+        .examples
+        .iter()
+        .filter(|example| example.group.as_deref() == Some(chapter))
+        .collect();
+    if let Some(taken) = siblings.iter().find(|example| example.short_name() == name) {
+        bail!(
+            "{chapter} already has a lesson called {name}: {}",
+            taken.name
+        );
+    }
+    // The chapter's last numbered example, which the new one follows
+    let previous = siblings
+        .iter()
+        .filter_map(|example| Some((number_prefix(example.name.rsplit('/').next()?)?, example)))
+        .max_by_key(|(number, _)| *number);
+    let number = previous.map_or(1, |(number, _)| number + 1);
+    let stem = format!("{number:02}_{name}");
+    let id = format!("{chapter}/{stem}");
+    let title = match request.title {
+        Some(title) => title.to_string(),
+        None => sentence(name),
+    };
+    let heading = match previous {
+        Some((_, example)) => chapter_heading(&example.path)?,
+        None => None,
+    }
+    .unwrap_or_else(|| title_case(chapter));
+    let previous_lesson = previous.and_then(|(_, example)| course.lessons.get(&example.name));
+
+    let mut files = Vec::new();
+    let mut write = |path: PathBuf, text: String| -> Result<()> {
+        let created = !path.exists();
+        fs::write(&path, text).with_context(|| format!("cannot write {}", path.display()))?;
+        files.push((
+            path.strip_prefix(root).unwrap_or(&path).to_path_buf(),
+            created,
+        ));
+        Ok(())
+    };
+
+    let manifest = chapter_dir.join("Cargo.toml");
+    let manifest_text = if previous.is_none() && !chapter_dir.exists() {
+        fs::create_dir_all(&chapter_dir)
+            .with_context(|| format!("cannot create {}", chapter_dir.display()))?;
+        Some(chapter_manifest(chapter, &heading))
+    } else if manifest.is_file() {
+        Some(read(&manifest)?)
+    } else {
+        None
+    };
+    if let Some(text) = manifest_text {
+        write(manifest, add_bin(&text, &stem))?;
+    }
+    let difficulty = request
+        .difficulty
+        .or(previous_lesson.map(|lesson| lesson.difficulty))
+        .unwrap_or(Difficulty::Beginner);
+    let example_path = chapter_dir.join(format!("{stem}.rs"));
+    write(
+        example_path,
+        example_file(&format!("{heading} {number}"), &title, difficulty),
+    )?;
+    let golden_dir = root.join(golden::DIR).join(chapter);
+    fs::create_dir_all(&golden_dir)
+        .with_context(|| format!("cannot create {}", golden_dir.display()))?;
+    write(
+        golden_dir.join(format!("{stem}.txt")),
+        format!("TODO: {title}\n"),
+    )?;
+
+    // The exercise
+    let topic_dir = topic_dir(root, chapter)?;
+    let exercise_names: Vec<String> = exercise::discover(root)?
+        .into_iter()
+        .filter(|exercise| exercise.topic == chapter)
+        .map(|exercise| exercise.name)
+        .collect();
+    let base = exercise_names
+        .first()
+        .map(|name| name.trim_end_matches(|c: char| c.is_ascii_digit()))
+        .unwrap_or(chapter)
+        .to_string();
+    let count = exercise_names
+        .iter()
+        .filter_map(|name| name.strip_prefix(base.as_str())?.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    let exercise = format!("{base}{count}");
+    fs::create_dir_all(topic_dir.join("tests"))
+        .with_context(|| format!("cannot create {}", topic_dir.join("tests").display()))?;
+    let exercise_text = exercise_file(
+        &heading,
+        count,
+        &title,
+        &format!("examples/{id}.rs"),
+        &exercise,
+    );
+    write(
+        topic_dir.join(format!("{exercise}.rs")),
+        exercise_text.clone(),
+    )?;
+    write(
+        topic_dir.join("tests").join(format!("{exercise}.rs")),
+        EXERCISE_TESTS.to_string(),
+    )?;
+    let info = topic_dir.join("info.toml");
+    let text = if info.is_file() {
+        read(&info)?
+    } else {
+        INFO_HEADER.to_string()
+    };
+    write(info, add_hints(&text, &exercise))?;
+    let solution_dir = root.join("solutions").join(file_stem(&topic_dir)?);
+    fs::create_dir_all(&solution_dir)
+        .with_context(|| format!("cannot create {}", solution_dir.display()))?;
+    write(
+        solution_dir.join(format!("{exercise}.rs")),
+        exercise_text.replace(EXERCISE_BODY, SOLUTION_BODY),
+    )?;
+
+    // The manifest entry
+    let tag = previous_lesson
+        .and_then(|lesson| lesson.tags.first().cloned())
+        .unwrap_or_else(|| chapter.replace('_', "-"));
+    let requires: Vec<&str> = previous
+        .map(|(_, example)| example.name.as_str())
+        .into_iter()
+        .collect();
+    let entry = lesson_entry(&id, &title, difficulty, &[&tag], &requires);
+    let lessons = root.join("lessons.toml");
+    let text = if lessons.is_file() {
+        read(&lessons)?
+    } else {
+        String::new()
+    };
+    write(lessons, add_lesson(&text, chapter, &id, &entry))?;
+
+    Ok(Scaffold {
+        id,
+        exercise,
+        files,
+    })
+}
+
+/// Chapters and lesson names become directory, file, and package names.
+fn check_identifier(what: &str, name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        bail!("the {what} `{name}` should be snake_case, like `pattern_guards`");
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))
+}
+
+/// `3` for `03_destructuring`.
+fn number_prefix(stem: &str) -> Option<u32> {
+    stem.split_once('_')?.0.parse().ok()
+}
+
+/// `pattern_guards` -> `Pattern guards`.
+fn sentence(name: &str) -> String {
+    let words = name.replace('_', " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => words,
+    }
+}
+
+/// `interior_mutability` -> `Interior Mutability`.
+fn title_case(chapter: &str) -> String {
+    chapter
+        .split('_')
+        .map(sentence)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// How an example's first line names its chapter: `Smart Pointers` in
+/// `// Smart Pointers 1: Box<T>`.
+pub fn chapter_heading(example: &Path) -> Result<Option<String>> {
+    let text = read(example)?;
+    let heading = text
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("// "))
+        .and_then(|line| line.split_once(':'))
+        .and_then(|(before, _)| before.rsplit_once(' '))
+        .filter(|(_, number)| number.chars().all(|c| c.is_ascii_digit()))
+        .map(|(heading, _)| heading.to_string());
+    Ok(heading)
+}
+
+/// The exercise topic directory for `chapter`, e.g. `exercises/05_enums`,
+/// created with the next free number if there isn't one.
+fn topic_dir(root: &Path, chapter: &str) -> Result<PathBuf> {
+    let dir = root.join("exercises");
+    let mut last = 0;
+    if dir.is_dir() {
+        for path in sorted_entries(&dir)? {
+            if !path.is_dir() {
+                continue;
+            }
+            let name = file_stem(&path)?;
+            if let Some((number, topic)) = name.split_once('_') {
+                if topic == chapter {
+                    return Ok(path);
+                }
+                last = last.max(number.parse().unwrap_or(0));
+            }
+        }
+    }
+    let path = dir.join(format!("{:02}_{chapter}", last + 1));
+    fs::create_dir_all(&path).with_context(|| format!("cannot create {}", path.display()))?;
+    Ok(path)
+}
+
+/// A `Cargo.toml` for a new chapter of std-only files.
+fn chapter_manifest(chapter: &str, heading: &str) -> String {
+    let package = format!("{}-examples", chapter.replace('_', "-"));
+    format!(
+        "\
+# Every file in examples/{chapter}/ is a standalone program: the tutor compiles
+# each one with rustc by itself, and so can you. This package only lets
+# cargo build, test, and lint them along with the rest of the workspace:
+#
+#     cargo test -p {package}
+
+[package]
+name = \"{package}\"
+version = \"0.1.0\"
+description = \"{heading} chapter\"
+edition.workspace = true
+license.workspace = true
+publish = false
+autobins = false
+
+# Tells the tutor and the checker to keep compiling these files with
+# rustc, one at a time, rather than as this package's binaries.
+[package.metadata.tutor]
+standalone = true
+
+[features]
+# The deliberately broken code, which the tutor switches on for a single
+# file with `--cfg 'feature=\"broken\"'`.
+broken = []
+"
+    )
+}
+
+/// `manifest` with a `[[bin]]` for `stem` after its last one.
+fn add_bin(manifest: &str, stem: &str) -> String {
+    let mut text = manifest.to_string();
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    // Tables are separated by blank lines, so the last `[[bin]]` ends at
+    // the first blank line after it
+    let at = match text.rfind("[[bin]]") {
+        Some(last) => text[last..]
+            .find("\n\n")
+            .map_or(text.len(), |end| last + end + 1),
+        None => text.len(),
+    };
+    let bin = format!("\n[[bin]]\nname = \"{stem}\"\npath = \"{stem}.rs\"\n");
+    text.insert_str(at, &bin);
+    text
+}
+
+fn example_file(heading: &str, title: &str, difficulty: Difficulty) -> String {
+    format!(
+        "\
+// {heading}: {title}
+// TODO: say in one line what this example demonstrates
+//
+// Concepts: TODO
+// Difficulty: {difficulty}
+// Minutes: 10
+
+fn main() {{
+    // TODO: build the idea up one step at a time, printing as you go
+    println!(\"TODO: {title}\");
+
+    // TODO: replace this with a mistake learners make here. The checker
+    // uncomments it and expects exactly the error on the line below.
+    // This would cause an error:
+    // let count: u32 = \"zero\";
+    // error[E0308]: mismatched types
+}}
+
+/*
+ * Key Concepts:
+ * - TODO
+ */
+
+// EXPECTED:
+// TODO: {title}
+"
+    )
+}
+
+fn exercise_file(heading: &str, count: u32, title: &str, example: &str, name: &str) -> String {
+    format!(
+        "\
+// Exercise: {heading} {count} - {title}
+// Related example: {example}
+//
+// TODO: describe what the code below is meant to do, and why it doesn't
+//       do it yet.
+//
+// TODO: Tell the learner what to change.
+//
+// Check your work with: cargo run -p tutor -- check {name}
+
+pub fn answer() -> u32 {{
+{EXERCISE_BODY}}}
+"
+    )
+}
+
+const EXERCISE_BODY: &str = "    todo!() // TODO: the code the learner has to fix or finish\n";
+
+/// What the solution has instead of [`EXERCISE_BODY`]: enough to pass the
+/// test stub.
+const SOLUTION_BODY: &str = "    42 // TODO: the finished code\n";
+
+const EXERCISE_TESTS: &str = "\
+// TODO: test what a correct solution does; these run against the
+// learner's file when they `tutor check` it.
+#[test]
+fn the_answer_is_right() {
+    assert_eq!(answer(), 42);
+}
+";
+
+const INFO_HEADER: &str = "\
+# Metadata for the exercises in this topic, keyed by exercise name.
+# See 01_variables/info.toml for the hint format.
+";
+
+/// `info` with TODO hints for `exercise` at the end.
+fn add_hints(info: &str, exercise: &str) -> String {
+    let mut text = info.trim_end().to_string();
+    text.push_str(&format!(
+        "\n\n[{exercise}.hints]\n\
+         nudge = \"TODO: a gentle push in the right direction\"\n\
+         explanation = \"\"\"\n\
+         TODO: what is going on and why\"\"\"\n\
+         solution = \"\"\"\n\
+         TODO: the exact change, as a diff\"\"\"\n"
+    ));
+    text
+}
+
+fn lesson_entry(
+    id: &str,
+    title: &str,
+    difficulty: Difficulty,
+    tags: &[&str],
+    requires: &[&str],
+) -> String {
+    let strings = |items: &[&str]| -> String {
+        let quoted: Vec<String> = items.iter().map(|item| toml_string(item)).collect();
+        format!("[{}]", quoted.join(", "))
+    };
+    format!(
+        "[[lesson]]\nid = {}\ntitle = {}\ndifficulty = \"{difficulty}\"\ntags = {}\nrequires = {}\n",
+        toml_string(id),
+        toml_string(title),
+        strings(tags),
+        strings(requires),
+    )
+}
+
+fn toml_string(text: &str) -> String {
+    toml::Value::String(text.to_string()).to_string()
+}
+
+/// `lessons` with `entry` after the last lesson of `chapter` or, for a new
+/// chapter, before the first chapter lesson whose id `id` sorts before.
+fn add_lesson(lessons: &str, chapter: &str, id: &str, entry: &str) -> String {
+    // Where each `[[lesson]]` starts, and its id
+    let mut starts = Vec::new();
+    let mut offset = 0;
+    let mut pending = None;
+    for line in lessons.split_inclusive('\n') {
+        if line.trim() == "[[lesson]]" {
+            pending = Some(offset);
+        } else if let (Some(start), Some(value)) = (pending, line.strip_prefix("id = ")) {
+            starts.push((start, value.trim().trim_matches('"').to_string()));
+            pending = None;
+        }
+        offset += line.len();
+    }
+
+    let prefix = format!("{chapter}/");
+    let after_chapter = starts
+        .iter()
+        .rposition(|(_, lesson)| lesson.starts_with(&prefix))
+        .map(|last| {
+            starts
+                .get(last + 1)
+                .map_or(lessons.len(), |(next, _)| *next)
+        });
+    let at = after_chapter.or_else(|| {
+        starts
+            .iter()
+            .find(|(_, lesson)| lesson.contains('/') && lesson.as_str() > id)
+            .map(|(start, _)| *start)
+    });
+
+    let mut text = lessons.to_string();
+    match at {
+        Some(at) if at < text.len() => text.insert_str(at, &format!("{entry}\n")),
+        _ => {
+            let trimmed = text.trim_end().len();
+            text.truncate(trimmed);
+            if !text.is_empty() {
+                text.push_str("\n\n");
+            }
+            text.push_str(entry);
+        }
+    }
+    text
+}
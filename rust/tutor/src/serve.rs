@@ -0,0 +1,711 @@
+//! `tutor serve`: the course and the learner's progress as web pages, to
+//! read in a browser while running the code in a terminal.
+//!
+//! ```text
+//! /                       progress by chapter, quiz results, and every lesson
+//! /lessons/<example>      an example: introduction, code, output, and notes
+//! /exercises/<exercise>   an exercise's code and the hints revealed so far
+//! /reading/<lesson>       a lesson from `lessons/`, rendered from Markdown
+//! /progress.json          the progress file, as the CLI wrote it
+//! ```
+//!
+//! The pages are built from the same `lessons.toml`, sources, and
+//! `progress.json` as the CLI, read again for every request, so reloading
+//! a page shows what `tutor run` or `tutor check` has done since. The
+//! server only listens on localhost.
+//!
+//! [`files`] builds the same pages once, as files linked to each other
+//! (`lessons/02_variables.html`), for `tutor export` to put in its bundle.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+
+use crate::course::{self, Course, Example};
+use crate::exercise::Exercise;
+use crate::explain::highlight;
+use crate::grade::escape;
+use crate::i18n;
+use crate::page::Page;
+use crate::progress::{Kind, Progress, Record};
+use crate::quiz;
+use crate::state;
+
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; line-height: 1.5; }
+nav { margin-bottom: 1.5rem; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2rem; }
+th, td { text-align: left; padding: 0.3rem 0.6rem; border-bottom: 1px solid #ddd; }
+td.count { text-align: right; font-variant-numeric: tabular-nums; white-space: nowrap; }
+.bar { background: #eee; border-radius: 0.25rem; height: 0.8rem; min-width: 10rem; }
+.bar div { background: #1a7f37; border-radius: 0.25rem; height: 100%; }
+.done { color: #1a7f37; }
+.meta { color: #666; }
+ul.lessons { list-style: none; padding-left: 0; columns: 2; }
+pre { background: #f6f8fa; padding: 0.8rem; overflow-x: auto; border-radius: 0.3rem; }
+.k { color: #8250df; } .s { color: #0a3069; } .c { color: #6e7781; }
+.n { color: #0550ae; } .m { color: #0550ae; font-weight: bold; } .t { color: #953800; }
+";
+
+/// What every request works from.
+struct Site {
+    course: Course,
+    exercises: Vec<Exercise>,
+    links: Links,
+}
+
+/// How the pages link to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Links {
+    /// The server's routes: `/lessons/02_variables`.
+    Server,
+    /// Files next to each other, as `tutor export` writes them:
+    /// `lessons/02_variables.html`, relative to the page the link is on.
+    Files,
+}
+
+impl Links {
+    /// Where the page at `route` (`""` for the dashboard, maybe with an
+    /// `#anchor`) is, seen from the page at `from`.
+    fn href(self, from: &str, route: &str) -> String {
+        match self {
+            Links::Server => format!("/{route}"),
+            Links::Files => {
+                let (path, anchor) = match route.split_once('#') {
+                    Some((path, anchor)) => (path, format!("#{anchor}")),
+                    None => (route, String::new()),
+                };
+                let file = match path {
+                    "" => "index.html".to_string(),
+                    _ if path.ends_with(".json") => path.to_string(),
+                    _ => format!("{path}.html"),
+                };
+                format!("{}{file}{anchor}", "../".repeat(from.matches('/').count()))
+            }
+        }
+    }
+}
+
+/// A chapter, as `tutor status` counts them: its examples, then its
+/// exercises.
+struct Chapter<'a> {
+    name: &'a str,
+    examples: Vec<&'a Example>,
+    exercises: Vec<&'a Exercise>,
+}
+
+impl Chapter<'_> {
+    fn done(&self, progress: &Progress) -> usize {
+        let examples = self
+            .examples
+            .iter()
+            .filter(|example| progress.is_completed(Kind::Example, &example.name));
+        let exercises = self
+            .exercises
+            .iter()
+            .filter(|exercise| progress.is_completed(Kind::Exercise, &exercise.name));
+        examples.count() + exercises.count()
+    }
+
+    fn total(&self) -> usize {
+        self.examples.len() + self.exercises.len()
+    }
+}
+
+/// Serves the course on `http://127.0.0.1:<port>` until the process is
+/// stopped.
+pub fn serve(course: Course, exercises: Vec<Exercise>, port: u16) -> Result<bool> {
+    let site = Arc::new(Site {
+        course,
+        exercises,
+        links: Links::Server,
+    });
+    let app = Router::new()
+        .route("/", get(dashboard))
+        .route("/lessons/{*name}", get(lesson))
+        .route("/exercises/{name}", get(exercise))
+        .route("/reading/{name}", get(reading))
+        .route("/progress.json", get(progress_json))
+        .fallback(|| async { not_found() })
+        .with_state(site);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("cannot start the server's runtime")?;
+    runtime.block_on(async {
+        let address = SocketAddr::from(([127, 0, 0, 1], port));
+        let listener = tokio::net::TcpListener::bind(address)
+            .await
+            .with_context(|| format!("cannot listen on {address}; pick another --port"))?;
+        println!("🌐 Serving the course at http://{address} (Ctrl-C to stop)");
+        axum::serve(listener, app)
+            .await
+            .context("the server stopped")?;
+        Ok(true)
+    })
+}
+
+async fn dashboard(State(site): State<Arc<Site>>) -> Response {
+    respond(site.dashboard().map(Some))
+}
+
+async fn lesson(State(site): State<Arc<Site>>, Path(name): Path<String>) -> Response {
+    respond(site.lesson(&name))
+}
+
+async fn exercise(State(site): State<Arc<Site>>, Path(name): Path<String>) -> Response {
+    respond(site.exercise(&name))
+}
+
+async fn reading(State(site): State<Arc<Site>>, Path(name): Path<String>) -> Response {
+    respond(site.reading(&name))
+}
+
+async fn progress_json() -> Response {
+    match Progress::load() {
+        Ok(progress) => Json(progress).into_response(),
+        Err(err) => error(&err),
+    }
+}
+
+/// Every page of the site, as `(path, html)` with the path relative to the
+/// site's root, linked to each other as files. The dashboard is
+/// `index.html`, and `progress.json` is left for the caller to add.
+pub fn files(course: Course, exercises: Vec<Exercise>) -> Result<Vec<(String, String)>> {
+    let site = Site {
+        course,
+        exercises,
+        links: Links::Files,
+    };
+    let mut files = vec![("index.html".to_string(), site.dashboard()?)];
+    for example in &site.course.examples {
+        if let Some(page) = site.lesson(&example.name)? {
+            files.push((format!("lessons/{}.html", example.name), page));
+        }
+    }
+    for exercise in &site.exercises {
+        if let Some(page) = site.exercise(&exercise.name)? {
+            files.push((format!("exercises/{}.html", exercise.name), page));
+        }
+    }
+    for (stem, _) in site.readings()? {
+        if let Some(page) = site.reading(&stem)? {
+            files.push((format!("reading/{stem}.html"), page));
+        }
+    }
+    Ok(files)
+}
+
+/// A page, a 404 if there is no such page, or the error that kept it
+/// from being built.
+fn respond(page: Result<Option<String>>) -> Response {
+    match page {
+        Ok(Some(html)) => Html(html).into_response(),
+        Ok(None) => not_found(),
+        Err(err) => error(&err),
+    }
+}
+
+fn not_found() -> Response {
+    let body =
+        "<h1>Not found</h1>\n<p>There is no such page. <a href=\"/\">Back to the course</a></p>\n";
+    (StatusCode::NOT_FOUND, Html(layout("Not found", body, "/"))).into_response()
+}
+
+fn error(err: &anyhow::Error) -> Response {
+    let body = format!(
+        "<h1>Something went wrong</h1>\n<pre>{}</pre>\n",
+        escape(&format!("{err:#}"))
+    );
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Html(layout("Error", &body, "/")),
+    )
+        .into_response()
+}
+
+impl Site {
+    fn chapters(&self) -> Vec<Chapter<'_>> {
+        let mut chapters: Vec<Chapter> = Vec::new();
+        let examples = self
+            .course
+            .examples
+            .iter()
+            .map(|example| (example.topic(), Some(example), None));
+        let exercises = self
+            .exercises
+            .iter()
+            .map(|exercise| (exercise.topic.as_str(), None, Some(exercise)));
+        for (name, example, exercise) in examples.chain(exercises) {
+            let index = match chapters.iter().position(|chapter| chapter.name == name) {
+                Some(index) => index,
+                None => {
+                    chapters.push(Chapter {
+                        name,
+                        examples: Vec::new(),
+                        exercises: Vec::new(),
+                    });
+                    chapters.len() - 1
+                }
+            };
+            chapters[index].examples.extend(example);
+            chapters[index].exercises.extend(exercise);
+        }
+        chapters
+    }
+
+    fn dashboard(&self) -> Result<String> {
+        let progress = Progress::load()?;
+        let chapters = self.chapters();
+        let mut body = String::from("<h1>Learn Rust</h1>\n");
+
+        let done: usize = chapters.iter().map(|chapter| chapter.done(&progress)).sum();
+        let total: usize = chapters.iter().map(Chapter::total).sum();
+        let _ = writeln!(
+            body,
+            "<p>{done} of {total} lessons and exercises done.</p>\n{}",
+            bar(done, total)
+        );
+        if let Some(current) = state::load_current()?
+            .and_then(|name| self.course.find(&name))
+            .map(|index| &self.course.examples[index])
+        {
+            let _ = writeln!(
+                body,
+                "<p>You are on <a href=\"{}\">{}</a>.</p>",
+                self.href("", &format!("lessons/{}", current.name)),
+                escape(&self.title(current))
+            );
+        }
+
+        body.push_str("<h2>Progress</h2>\n<table>\n<tr><th>Chapter</th><th></th><th></th></tr>\n");
+        for chapter in &chapters {
+            let _ = writeln!(
+                body,
+                "<tr><td><a href=\"#{name}\">{name}</a></td><td class=\"count\">{}/{}</td><td>{}</td></tr>",
+                chapter.done(&progress),
+                chapter.total(),
+                bar(chapter.done(&progress), chapter.total()),
+                name = escape(chapter.name)
+            );
+        }
+        body.push_str("</table>\n");
+
+        body.push_str("<h2>Quizzes</h2>\n<table>\n<tr><th>Chapter</th><th>Attempts</th><th>Last score</th><th>Best</th><th></th></tr>\n");
+        for name in quiz::chapters(&self.course.root)? {
+            let row = match progress.quizzes.get(&name) {
+                Some(taken) => format!(
+                    "<td class=\"count\">{}</td><td class=\"count\">{}/{}</td><td class=\"count\">{}%</td><td>{}</td>",
+                    taken.attempts,
+                    taken.last_score,
+                    taken.last_total,
+                    taken.best_percent,
+                    bar(taken.best_percent as usize, 100)
+                ),
+                None => String::from("<td colspan=\"4\" class=\"meta\">not taken yet</td>"),
+            };
+            let _ = writeln!(body, "<tr><td>{}</td>{row}</tr>", escape(&name));
+        }
+        body.push_str("</table>\n<p class=\"meta\">Take one with <code>cargo run -p tutor -- quiz &lt;chapter&gt;</code>.</p>\n");
+
+        let readings = self.readings()?;
+        if !readings.is_empty() {
+            body.push_str("<h2>Reading</h2>\n<ul>\n");
+            for (stem, title) in &readings {
+                let _ = writeln!(
+                    body,
+                    "<li><a href=\"{}\">{}</a></li>",
+                    self.href("", &format!("reading/{stem}")),
+                    escape(title)
+                );
+            }
+            body.push_str("</ul>\n");
+        }
+
+        for chapter in &chapters {
+            let _ = writeln!(
+                body,
+                "<h2 id=\"{name}\">{name}</h2>\n<ul class=\"lessons\">",
+                name = escape(chapter.name)
+            );
+            for example in &chapter.examples {
+                let done = progress.is_completed(Kind::Example, &example.name);
+                let _ = writeln!(
+                    body,
+                    "<li>{} <a href=\"{}\">{}</a></li>",
+                    mark(done),
+                    self.href("", &format!("lessons/{}", example.name)),
+                    escape(&self.title(example))
+                );
+            }
+            for exercise in &chapter.exercises {
+                let done = progress.is_completed(Kind::Exercise, &exercise.name);
+                let _ = writeln!(
+                    body,
+                    "<li>{} <a href=\"{}\">Exercise {name}</a></li>",
+                    mark(done),
+                    self.href("", &format!("exercises/{}", exercise.name)),
+                    name = escape(&exercise.name)
+                );
+            }
+            body.push_str("</ul>\n");
+        }
+        let fresh = match self.links {
+            Links::Server => "Reload the page to see what you've done in the terminal since.",
+            Links::Files => {
+                "This is a copy from <code>tutor export</code>, as things were then; \
+                 <code>tutor import</code> carries on from it."
+            }
+        };
+        let _ = writeln!(
+            body,
+            "<p class=\"meta\">{fresh} The raw data is in <a href=\"{}\">progress.json</a>.</p>",
+            self.href("", "progress.json")
+        );
+        Ok(layout("Learn Rust", &body, &self.href("", "")))
+    }
+
+    /// An example's page, like its page in the book, with the learner's
+    /// progress on it.
+    fn lesson(&self, name: &str) -> Result<Option<String>> {
+        let Some(index) = self.course.find(name) else {
+            return Ok(None);
+        };
+        let example = &self.course.examples[index];
+        if example.name != name {
+            // `find` also takes numbers and short names; keep one address
+            // per page
+            return Ok(None);
+        }
+        let progress = Progress::load()?;
+        let here = format!("lessons/{name}");
+        let source = fs::read_to_string(&example.path)
+            .with_context(|| format!("cannot read {}", example.path.display()))?;
+        let page = Page::parse(&source);
+        let title = self.title(example);
+
+        let mut body = format!("<h1>{}</h1>\n", escape(&title));
+        if let Some(lesson) = self.course.lessons.get(&example.name) {
+            let mut about = format!("{} · {}", lesson.difficulty, lesson.tags.join(", "));
+            let required: Vec<String> = lesson
+                .requires
+                .iter()
+                .filter_map(|id| {
+                    self.course
+                        .find(id)
+                        .map(|index| &self.course.examples[index])
+                })
+                .map(|required| {
+                    format!(
+                        "{} <a href=\"{}\">{}</a>",
+                        mark(progress.is_completed(Kind::Example, &required.name)),
+                        self.href(&here, &format!("lessons/{}", required.name)),
+                        escape(&self.title(required))
+                    )
+                })
+                .collect();
+            if !required.is_empty() {
+                let _ = write!(about, " — builds on {}", required.join(", "));
+            }
+            let _ = writeln!(body, "<p class=\"meta\">{about}</p>");
+        }
+        let _ = writeln!(
+            body,
+            "<p>{}</p>",
+            record_status(progress.get(Kind::Example, &example.name), "run")
+        );
+        body.push_str(&markdown(&page.intro));
+        let _ = writeln!(
+            body,
+            "<pre><code>{}</code></pre>\n<p>Run it with <code>cargo run -p tutor -- run {}</code>.</p>",
+            highlighted(&page.code),
+            escape(&example.name)
+        );
+        if let Some(expected) = &page.expected {
+            let _ = writeln!(
+                body,
+                "<h3>Output</h3>\n<pre>{}</pre>",
+                escape(expected.trim_end())
+            );
+        }
+        body.push_str(&markdown(&page.notes));
+
+        let link = |index: usize, label: &str| {
+            let example = &self.course.examples[index];
+            format!(
+                "<a href=\"{}\">{label} {}</a>",
+                self.href(&here, &format!("lessons/{}", example.name)),
+                escape(&self.title(example))
+            )
+        };
+        let mut around = Vec::new();
+        if index > 0 {
+            around.push(link(index - 1, "←"));
+        }
+        if index + 1 < self.course.examples.len() {
+            around.push(link(index + 1, "→"));
+        }
+        let _ = writeln!(body, "<hr>\n<p>{}</p>", around.join(" · "));
+        Ok(Some(layout(&title, &body, &self.href(&here, ""))))
+    }
+
+    /// An exercise as the learner has it now, and the hints they have
+    /// already revealed; the others stay hidden, as with `tutor hint`.
+    fn exercise(&self, name: &str) -> Result<Option<String>> {
+        let Some(exercise) = crate::exercise::find(&self.exercises, name) else {
+            return Ok(None);
+        };
+        let progress = Progress::load()?;
+        let record = progress.get(Kind::Exercise, &exercise.name);
+        let source = fs::read_to_string(&exercise.path)
+            .with_context(|| format!("cannot read {}", exercise.path.display()))?;
+        let path = exercise
+            .path
+            .strip_prefix(&self.course.root)
+            .unwrap_or(&exercise.path);
+
+        let here = format!("exercises/{name}");
+        let mut body = format!(
+            "<h1>Exercise {name}</h1>\n<p class=\"meta\"><a href=\"{}\">{topic}</a> · {}</p>\n<p>{}</p>\n",
+            self.href(&here, &format!("#{}", exercise.topic)),
+            escape(&path.display().to_string()),
+            record_status(record, "checked"),
+            name = escape(&exercise.name),
+            topic = escape(&exercise.topic),
+        );
+        let _ = writeln!(
+            body,
+            "<pre><code>{}</code></pre>\n<p>Check it with <code>cargo run -p tutor -- check {}</code>.</p>",
+            highlighted(source.trim_end()),
+            escape(&exercise.name)
+        );
+        if let Some(hints) = &exercise.hints {
+            let revealed = record.map_or(0, |record| record.hints_used);
+            body.push_str("<h2>Hints</h2>\n");
+            for (label, text) in hints.levels().iter().take(revealed) {
+                let _ = writeln!(
+                    body,
+                    "<h3>{label}</h3>\n<pre>{}</pre>",
+                    escape(text.trim_matches('\n'))
+                );
+            }
+            if revealed < hints.levels().len() {
+                let _ = writeln!(
+                    body,
+                    "<p class=\"meta\">{} of {} hints revealed. Run <code>cargo run -p tutor -- hint {}</code> for the next one.</p>",
+                    revealed,
+                    hints.levels().len(),
+                    escape(&exercise.name)
+                );
+            }
+        }
+        Ok(Some(layout(
+            &format!("Exercise {}", exercise.name),
+            &body,
+            &self.href(&here, ""),
+        )))
+    }
+
+    /// A lesson from `lessons/`, by its file stem.
+    fn reading(&self, name: &str) -> Result<Option<String>> {
+        let Some((_, title)) = self.readings()?.into_iter().find(|(stem, _)| stem == name) else {
+            return Ok(None);
+        };
+        let path = self.course.root.join("lessons").join(format!("{name}.md"));
+        let path = i18n::current().localized(&path).unwrap_or(path);
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+        let home = self.href(&format!("reading/{name}"), "");
+        Ok(Some(layout(&title, &markdown(&text), &home)))
+    }
+
+    /// The Markdown lessons in `lessons/`: each file's stem and its first
+    /// heading, in the current language if the lesson has a translation.
+    fn readings(&self) -> Result<Vec<(String, String)>> {
+        let dir = self.course.root.join("lessons");
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut readings = Vec::new();
+        for path in course::sorted_entries(&dir)? {
+            if path.extension().is_none_or(|ext| ext != "md") || i18n::is_translation(&path) {
+                continue;
+            }
+            let stem = course::file_stem(&path)?;
+            let path = i18n::current().localized(&path).unwrap_or(path);
+            let text = fs::read_to_string(&path)
+                .with_context(|| format!("cannot read {}", path.display()))?;
+            let title = text
+                .lines()
+                .find_map(|line| line.strip_prefix("# "))
+                .map_or_else(|| stem.clone(), |title| title.trim().to_string());
+            readings.push((stem, title));
+        }
+        Ok(readings)
+    }
+
+    /// A link to `route` from the page at `from`, escaped for an attribute.
+    fn href(&self, from: &str, route: &str) -> String {
+        escape(&self.links.href(from, route))
+    }
+
+    /// The lesson title from `lessons.toml`, or else the example's name.
+    fn title(&self, example: &Example) -> String {
+        self.course
+            .lessons
+            .get(&example.name)
+            .map_or_else(|| example.name.clone(), |lesson| lesson.title.clone())
+    }
+}
+
+/// A whole page around `body`, with a link back to the dashboard at `home`.
+fn layout(title: &str, body: &str, home: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>\n\
+         <nav><a href=\"{home}\">← All lessons</a></nav>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+/// A horizontal bar filled `done / total` of the way.
+fn bar(done: usize, total: usize) -> String {
+    let percent = (done * 100).checked_div(total).unwrap_or(0);
+    format!("<div class=\"bar\"><div style=\"width: {percent}%\"></div></div>")
+}
+
+fn mark(done: bool) -> &'static str {
+    if done {
+        "<span class=\"done\">✓</span>"
+    } else {
+        "<span class=\"meta\">·</span>"
+    }
+}
+
+/// How far the learner got with a lesson: `action` is what they do to it,
+/// such as `run`.
+fn record_status(record: Option<&Record>, action: &str) -> String {
+    match record {
+        Some(record) if record.completed_at.is_some() => format!(
+            "<span class=\"done\">✓ Done</span>, {} {}.",
+            record.attempts,
+            if record.attempts == 1 {
+                "attempt"
+            } else {
+                "attempts"
+            }
+        ),
+        Some(record) if record.attempts > 0 => format!(
+            "Not done yet: {action} {} {} so far.",
+            record.attempts,
+            if record.attempts == 1 {
+                "time"
+            } else {
+                "times"
+            }
+        ),
+        _ => format!("<span class=\"meta\">Not {action} yet.</span>"),
+    }
+}
+
+fn markdown(text: &str) -> String {
+    let parser = pulldown_cmark::Parser::new_ext(text, pulldown_cmark::Options::ENABLE_TABLES);
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    html
+}
+
+/// `code` highlighted the way `tutor explain` colors it, with a CSS class
+/// for each of its ANSI colors.
+fn highlighted(code: &str) -> String {
+    let mut html = String::new();
+    let mut in_comment = false;
+    for line in code.lines() {
+        let colored = highlight(line, &mut in_comment);
+        let mut pieces = colored.split("\x1b[");
+        html.push_str(&escape(pieces.next().unwrap_or("")));
+        for piece in pieces {
+            let (code, text) = piece.split_once('m').unwrap_or(("0", piece));
+            let class = match code {
+                "35" => Some("k"),
+                "32" => Some("s"),
+                "90" => Some("c"),
+                "36" => Some("n"),
+                "34" => Some("m"),
+                "33" => Some("t"),
+                _ => None,
+            };
+            match class {
+                Some(class) if !text.is_empty() => {
+                    let _ = write!(html, "<span class=\"{class}\">{}</span>", escape(text));
+                }
+                _ => html.push_str(&escape(text)),
+            }
+        }
+        html.push('\n');
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_is_escaped_and_classed_by_token() {
+        assert_eq!(
+            highlighted("let v: Vec<u8> = vec![1]; // <none>"),
+            "<span class=\"k\">let</span> v: <span class=\"t\">Vec</span>&lt;u8&gt; = \
+             <span class=\"m\">vec!</span>[<span class=\"n\">1</span>]; \
+             <span class=\"c\">// &lt;none&gt;</span>\n"
+        );
+    }
+
+    #[test]
+    fn links_are_routes_or_relative_files() {
+        let from = "lessons/bits/01_overflow";
+        assert_eq!(
+            Links::Server.href(from, "lessons/02_variables"),
+            "/lessons/02_variables"
+        );
+        assert_eq!(
+            Links::Files.href(from, "lessons/02_variables"),
+            "../../lessons/02_variables.html"
+        );
+        assert_eq!(Links::Files.href("", ""), "index.html");
+        assert_eq!(
+            Links::Files.href("exercises/bits1", "#bits"),
+            "../index.html#bits"
+        );
+        assert_eq!(Links::Files.href("", "progress.json"), "progress.json");
+        assert_eq!(Links::Server.href("", "#bits"), "/#bits");
+    }
+
+    #[test]
+    fn records_read_as_a_status() {
+        assert!(record_status(None, "run").contains("Not run yet"));
+        let tried = Record {
+            attempts: 2,
+            ..Record::default()
+        };
+        assert_eq!(
+            record_status(Some(&tried), "checked"),
+            "Not done yet: checked 2 times so far."
+        );
+        let done = Record {
+            attempts: 1,
+            completed_at: Some(1),
+            ..Record::default()
+        };
+        assert!(record_status(Some(&done), "run").contains("1 attempt."));
+    }
+}
@@ -0,0 +1,281 @@
+//! `tutor solution <exercise>`: the reference solution, shown as a diff
+//! against the learner's attempt.
+//!
+//! Every exercise has a solution at the same place under `solutions/` as
+//! the exercise under `exercises/`:
+//!
+//! ```text
+//! exercises/01_variables/variables1.rs   <- what the learner edits
+//! solutions/01_variables/variables1.rs   <- the same file, solved
+//! ```
+//!
+//! A solution keeps the exercise's comments as they are, so that the diff
+//! shows only the code that changes. `cargo xtask check-solutions` checks
+//! each one against the exercise's hidden tests.
+
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::exercise::Exercise;
+use crate::explain::highlight;
+use crate::t;
+
+/// Lines of unchanged code shown around each change.
+const CONTEXT: usize = 3;
+
+const REMOVED: &str = "\x1b[31m";
+const ADDED: &str = "\x1b[32m";
+const HUNK: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Where the solution to `exercise` is, whether or not it exists.
+pub fn path(root: &Path, exercise: &Exercise) -> PathBuf {
+    let relative = exercise
+        .path
+        .strip_prefix(root.join("exercises"))
+        .unwrap_or(&exercise.path);
+    root.join("solutions").join(relative)
+}
+
+/// The reference solution to `exercise`.
+pub fn load(root: &Path, exercise: &Exercise) -> Result<String> {
+    let path = path(root, exercise);
+    if !path.is_file() {
+        bail!(
+            "{} has no reference solution yet (it would be at {})",
+            exercise.name,
+            path.display()
+        );
+    }
+    fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))
+}
+
+/// Prints the unified diff from the learner's attempt to the solution.
+/// Returns whether there was any difference. Colors are used when printing
+/// to a terminal, unless `NO_COLOR` is set.
+pub fn print(root: &Path, exercise: &Exercise) -> Result<bool> {
+    let attempt = fs::read_to_string(&exercise.path)
+        .with_context(|| format!("cannot read {}", exercise.path.display()))?;
+    let solution = load(root, exercise)?;
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+
+    let shown = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    let diff = unified(
+        &t!("solution-your-attempt", path = shown(&exercise.path)),
+        &shown(&path(root, exercise)),
+        &attempt,
+        &solution,
+        color,
+    );
+    if diff.is_empty() {
+        println!("{}", t!("solution-same", name = exercise.name.as_str()));
+        return Ok(false);
+    }
+    println!("{}\n", t!("solution-title", name = exercise.name.as_str()));
+    print!("{diff}");
+    Ok(true)
+}
+
+/// Replaces the learner's attempt with the solution, after saving the
+/// attempt in `backup_dir`. Returns where the attempt was saved.
+pub fn apply(root: &Path, exercise: &Exercise, backup_dir: &Path) -> Result<PathBuf> {
+    let solution = load(root, exercise)?;
+    fs::create_dir_all(backup_dir)
+        .with_context(|| format!("cannot create {}", backup_dir.display()))?;
+    let backup = backup_dir.join(format!("{}.rs", exercise.name));
+    fs::copy(&exercise.path, &backup)
+        .with_context(|| format!("cannot save your attempt to {}", backup.display()))?;
+    fs::write(&exercise.path, solution)
+        .with_context(|| format!("cannot write {}", exercise.path.display()))?;
+    Ok(backup)
+}
+
+/// One line of a diff, by its index in the old or the new text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line {
+    Same(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+/// The diff from `old` to `new` in the unified format, with `---` and
+/// `+++` headers and `@@` hunks; empty if there is no difference. The
+/// lines are syntax highlighted if `color` is set.
+pub fn unified(old_name: &str, new_name: &str, old: &str, new: &str, color: bool) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let lines = diff(&old, &new);
+    if lines.iter().all(|line| matches!(line, Line::Same(..))) {
+        return String::new();
+    }
+
+    // Highlighting goes through each text in order, so that a `/* ... */`
+    // comment is colored on every line it spans
+    let paint = |text: &[&str]| -> Vec<String> {
+        let mut in_comment = false;
+        text.iter()
+            .map(|line| {
+                if color {
+                    highlight(line, &mut in_comment)
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect()
+    };
+    let (old_code, new_code) = (paint(&old), paint(&new));
+    let (removed, added, hunk, reset) = if color {
+        (REMOVED, ADDED, HUNK, RESET)
+    } else {
+        ("", "", "", "")
+    };
+
+    let mut out = format!("{removed}--- {old_name}{reset}\n{added}+++ {new_name}{reset}\n");
+    for range in hunks(&lines) {
+        let (old_before, new_before) = counts(&lines[..range.start]);
+        let hunk_lines = &lines[range];
+        let (mut old_start, mut new_start) = (None, None);
+        let (mut old_count, mut new_count) = (0, 0);
+        for line in hunk_lines {
+            if let Line::Same(o, _) | Line::Removed(o) = *line {
+                old_start.get_or_insert(o);
+                old_count += 1;
+            }
+            if let Line::Same(_, n) | Line::Added(n) = *line {
+                new_start.get_or_insert(n);
+                new_count += 1;
+            }
+        }
+        // An empty side starts after the line before it, as `diff -u` has it
+        let start = |first: Option<usize>, before: usize| first.map_or(before, |line| line + 1);
+        out.push_str(&format!(
+            "{hunk}@@ -{},{old_count} +{},{new_count} @@{reset}\n",
+            start(old_start, old_before),
+            start(new_start, new_before),
+        ));
+        for line in hunk_lines {
+            match *line {
+                Line::Same(o, _) => out.push_str(&format!(" {}\n", old_code[o])),
+                Line::Removed(o) => out.push_str(&format!("{removed}-{reset}{}\n", old_code[o])),
+                Line::Added(n) => out.push_str(&format!("{added}+{reset}{}\n", new_code[n])),
+            }
+        }
+    }
+    out
+}
+
+/// How many lines of the old and of the new text `lines` cover.
+fn counts(lines: &[Line]) -> (usize, usize) {
+    lines.iter().fold((0, 0), |(old, new), line| match line {
+        Line::Same(..) => (old + 1, new + 1),
+        Line::Removed(_) => (old + 1, new),
+        Line::Added(_) => (old, new + 1),
+    })
+}
+
+/// The whole of both texts, matched up along their longest common
+/// subsequence of lines. Removals come before the additions that replace
+/// them.
+fn diff(old: &[&str], new: &[&str]) -> Vec<Line> {
+    // common[i][j]: the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i].trim_end() == new[j].trim_end() {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i].trim_end() == new[j].trim_end() {
+            lines.push(Line::Same(i, j));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(Line::Removed(i));
+            i += 1;
+        } else {
+            lines.push(Line::Added(j));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/// The ranges of `lines` to show: each change with `CONTEXT` lines around
+/// it, merged where they overlap.
+fn hunks(lines: &[Line]) -> Vec<std::ops::Range<usize>> {
+    let mut ranges: Vec<std::ops::Range<usize>> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if matches!(line, Line::Same(..)) {
+            continue;
+        }
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + 1 + CONTEXT).min(lines.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.end => last.end = end,
+            _ => ranges.push(start..end),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_texts_have_no_diff() {
+        let text = "fn main() {\n    println!(\"hi\");\n}\n";
+        assert_eq!(unified("a", "b", text, text, false), "");
+    }
+
+    #[test]
+    fn a_changed_line_is_shown_with_its_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\n";
+        let new = "a\nb\nc\nd\nE\nf\ng\nh\ni\n";
+        assert_eq!(
+            unified("old.rs", "new.rs", old, new, false),
+            "--- old.rs\n+++ new.rs\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+        );
+    }
+
+    #[test]
+    fn changes_far_apart_get_their_own_hunks() {
+        let old: String = (1..=20).map(|n| format!("{n}\n")).collect();
+        let new: String = (1..=20)
+            .filter(|n| *n != 19)
+            .map(|n| {
+                if n == 2 {
+                    "two\n".to_string()
+                } else {
+                    format!("{n}\n")
+                }
+            })
+            .collect();
+        let diff = unified("old", "new", &old, &new, false);
+        let headers: Vec<&str> = diff.lines().filter(|line| line.starts_with("@@")).collect();
+        assert_eq!(headers, ["@@ -1,5 +1,5 @@", "@@ -16,5 +16,4 @@"]);
+    }
+
+    #[test]
+    fn added_lines_at_the_start_count_from_zero() {
+        let diff = unified("old", "new", "b\n", "a\nb\n", false);
+        assert_eq!(diff, "--- old\n+++ new\n@@ -1,1 +1,2 @@\n+a\n b\n");
+        let diff = unified("old", "new", "", "a\n", false);
+        assert_eq!(diff, "--- old\n+++ new\n@@ -0,0 +1,1 @@\n+a\n");
+    }
+}
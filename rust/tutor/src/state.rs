@@ -0,0 +1,36 @@
+//! Remembers which example the learner is currently on.
+//!
+//! The state lives in `~/.learn-rust/current` as a single line holding the
+//! example name, so it survives between terminal sessions.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+/// Directory where the tutor keeps per-learner data.
+pub fn data_dir() -> Result<PathBuf> {
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .context("cannot locate your home directory (set HOME)")?;
+    Ok(PathBuf::from(home).join(".learn-rust"))
+}
+
+/// Reads the name of the current example, if one has been started.
+pub fn load_current() -> Result<Option<String>> {
+    let path = data_dir()?.join("current");
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(Some(contents.trim().to_string()).filter(|name| !name.is_empty())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("cannot read {}", path.display())),
+    }
+}
+
+/// Records `name` as the current example.
+pub fn save_current(name: &str) -> Result<()> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    let path = dir.join("current");
+    fs::write(&path, format!("{name}\n"))
+        .with_context(|| format!("cannot write {}", path.display()))
+}
@@ -0,0 +1,131 @@
+//! `tutor status`: how far along the learner is, chapter by chapter.
+//!
+//! A chapter is a topic such as `variables`. It collects the example with
+//! that name (`02_variables.rs`), every example in a group directory of
+//! that name (`examples/lifetimes/`), and every exercise in the matching
+//! topic directory (`exercises/01_variables/`). Below the chapters come the
+//! daily streak and the achievements, unlocked or not.
+
+use crate::achievements;
+use crate::course::Course;
+use crate::exercise::Exercise;
+use crate::progress::{Kind, Progress};
+use crate::t;
+
+const BAR_WIDTH: usize = 20;
+
+struct Chapter<'a> {
+    name: &'a str,
+    done: usize,
+    total: usize,
+}
+
+pub fn print(course: &Course, exercises: &[Exercise], progress: &Progress) {
+    let mut chapters: Vec<Chapter> = Vec::new();
+    let items = course
+        .examples
+        .iter()
+        .map(|example| (example.topic(), Kind::Example, example.name.as_str()))
+        .chain(exercises.iter().map(|exercise| {
+            (
+                exercise.topic.as_str(),
+                Kind::Exercise,
+                exercise.name.as_str(),
+            )
+        }));
+
+    for (topic, kind, name) in items {
+        let index = match chapters.iter().position(|chapter| chapter.name == topic) {
+            Some(index) => index,
+            None => {
+                chapters.push(Chapter {
+                    name: topic,
+                    done: 0,
+                    total: 0,
+                });
+                chapters.len() - 1
+            }
+        };
+        chapters[index].total += 1;
+        if progress.is_completed(kind, name) {
+            chapters[index].done += 1;
+        }
+    }
+
+    println!("{}\n", t!("status-title"));
+    let width = chapters
+        .iter()
+        .map(|chapter| chapter.name.len())
+        .max()
+        .unwrap_or(0);
+    for chapter in &chapters {
+        println!(
+            "{:<width$}  {:>2}/{:<2} {}",
+            chapter.name,
+            chapter.done,
+            chapter.total,
+            bar(chapter.done, chapter.total)
+        );
+    }
+
+    let done: usize = chapters.iter().map(|chapter| chapter.done).sum();
+    let total: usize = chapters.iter().map(|chapter| chapter.total).sum();
+    println!(
+        "\n{:<width$}  {done:>2}/{total:<2} {}",
+        t!("status-overall"),
+        bar(done, total)
+    );
+
+    let streak = achievements::streak(progress);
+    match streak.current {
+        0 => println!("\n{}", t!("status-no-streak")),
+        current if streak.practiced_today => println!(
+            "\n{}",
+            t!("status-streak", current = current, best = streak.best)
+        ),
+        current => println!(
+            "\n{}",
+            t!(
+                "status-streak-at-risk",
+                current = current,
+                best = streak.best
+            )
+        ),
+    }
+
+    println!(
+        "\n{}",
+        t!(
+            "status-achievements",
+            unlocked = progress.achievements.len(),
+            total = achievements::ALL.len()
+        )
+    );
+    let titles: Vec<String> = achievements::ALL
+        .iter()
+        .map(|achievement| achievement.title())
+        .collect();
+    let width = titles
+        .iter()
+        .map(|title| title.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (achievement, title) in achievements::ALL.iter().zip(&titles) {
+        let mark = if progress.achievements.contains_key(achievement.id) {
+            "✓"
+        } else {
+            "·"
+        };
+        println!("   {mark} {title:<width$} {}", achievement.description());
+    }
+}
+
+fn bar(done: usize, total: usize) -> String {
+    let percent = (done * 100).checked_div(total).unwrap_or(0);
+    let filled = (done * BAR_WIDTH).checked_div(total).unwrap_or(0);
+    format!(
+        "{}{} {percent:>3}%",
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled)
+    )
+}
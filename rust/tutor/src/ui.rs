@@ -0,0 +1,712 @@
+//! `tutor ui`: the whole course on one screen, for learners who would
+//! rather not juggle separate commands.
+//!
+//! ```text
+//! ┌ Lessons ──────┐┌ examples/02_variables.rs ─────────────────────┐
+//! │ variables     ││  1 // Variables and mutability                │
+//! │   ✓ 02_varia… ││  2 fn main() {                                │
+//! │   · variables1││ ...                                           │
+//! │ ...           │└───────────────────────────────────────────────┘
+//! │               │┌ Output ─────────────────┐┌ Hints ─────────────┐
+//! │               ││ ✅ x                     ││ 1. Nudge           │
+//! └───────────────┘└─────────────────────────┘└────────────────────┘
+//! ```
+//!
+//! The list on the left goes chapter by chapter, like `tutor status`: the
+//! chapter's examples, then its exercises, each ticked once it is done.
+//! Enter runs the selected example or checks the selected exercise, and
+//! `h` reveals its next hint, with progress recorded just as `tutor run`,
+//! `tutor check` and `tutor hint` record it. The list's title shows the
+//! daily streak and how many achievements are unlocked, and the line at
+//! the bottom announces new ones. The source is read from disk
+//! every time the screen is drawn, so edits saved in an editor next to the
+//! tutor show up right away.
+//!
+//! Compiler output and source code are colored with ANSI escapes
+//! elsewhere in the tutor; [`ansi_text`] turns those into ratatui styles
+//! so that both look the same here as on the command line.
+
+use std::fs;
+use std::io::IsTerminal;
+
+use anyhow::{bail, Context, Result};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::achievements;
+use crate::checker::{self, Report};
+use crate::compiler::{self, Outcome};
+use crate::course::Course;
+use crate::errors::{self, ErrorCode};
+use crate::exercise::Exercise;
+use crate::explain::highlight;
+use crate::progress::{Kind, Progress};
+use crate::runner;
+use crate::state;
+use crate::t;
+
+/// How far Page Up and Page Down scroll.
+const PAGE: i16 = 10;
+
+/// One line of the lesson list.
+#[derive(Debug, PartialEq, Eq)]
+enum Row {
+    Chapter(String),
+    Example(usize),
+    Exercise(usize),
+}
+
+/// The panes that take the keyboard, in the order Tab goes through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Lessons,
+    Source,
+    Output,
+    Hints,
+}
+
+const PANES: [Pane; 4] = [Pane::Lessons, Pane::Source, Pane::Output, Pane::Hints];
+
+struct App<'a> {
+    course: &'a Course,
+    exercises: &'a [Exercise],
+    errors: Vec<ErrorCode>,
+    rows: Vec<Row>,
+    /// Index into `rows`; never a chapter.
+    selected: usize,
+    focus: Pane,
+    /// How far the source, output and hints panes are scrolled.
+    scroll: [u16; 3],
+    /// What the last run or check printed, with ANSI colors.
+    output: String,
+    /// A line under the panes saying what just happened.
+    status: String,
+}
+
+/// Runs the dashboard until the learner quits.
+pub fn run(course: &Course, exercises: &[Exercise], progress: &mut Progress) -> Result<bool> {
+    if !std::io::stdout().is_terminal() {
+        bail!("`tutor ui` needs a terminal; the other commands work without one");
+    }
+    let rows = rows(course, exercises);
+    if rows.is_empty() {
+        bail!("there are no examples or exercises to show");
+    }
+    let mut app = App {
+        course,
+        exercises,
+        errors: errors::load(&course.root)?,
+        selected: 0,
+        rows,
+        focus: Pane::Lessons,
+        scroll: [0; 3],
+        output: String::new(),
+        status: t!("ui-welcome"),
+    };
+    // Start at the first thing that isn't done yet
+    app.selected = (0..app.rows.len())
+        .find(|&row| app.done(row, progress) == Some(false))
+        .or_else(|| (0..app.rows.len()).find(|&row| app.done(row, progress).is_some()))
+        .unwrap_or(0);
+
+    let mut terminal = ratatui::try_init().context("cannot set up the terminal")?;
+    let result = app.event_loop(&mut terminal, progress);
+    ratatui::restore();
+    result.map(|()| true)
+}
+
+/// The lesson list: every chapter, with its examples and then its
+/// exercises.
+fn rows(course: &Course, exercises: &[Exercise]) -> Vec<Row> {
+    let mut chapters: Vec<(&str, Vec<Row>)> = Vec::new();
+    let items = course
+        .examples
+        .iter()
+        .enumerate()
+        .map(|(index, example)| (example.topic(), Row::Example(index)))
+        .chain(
+            exercises
+                .iter()
+                .enumerate()
+                .map(|(index, exercise)| (exercise.topic.as_str(), Row::Exercise(index))),
+        );
+    for (topic, row) in items {
+        match chapters.iter_mut().find(|(name, _)| *name == topic) {
+            Some((_, rows)) => rows.push(row),
+            None => chapters.push((topic, vec![row])),
+        }
+    }
+    chapters
+        .into_iter()
+        .flat_map(|(name, rows)| std::iter::once(Row::Chapter(name.to_string())).chain(rows))
+        .collect()
+}
+
+impl App<'_> {
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        progress: &mut Progress,
+    ) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame, progress))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Tab => self.focus = PANES[(self.pane_index() + 1) % PANES.len()],
+                KeyCode::BackTab => {
+                    self.focus = PANES[(self.pane_index() + PANES.len() - 1) % PANES.len()]
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.step(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.step(1),
+                KeyCode::PageUp => self.scroll_by(-PAGE),
+                KeyCode::PageDown => self.scroll_by(PAGE),
+                KeyCode::Enter => {
+                    self.status = format!("⏳ Building {}…", self.selected_name());
+                    terminal.draw(|frame| self.draw(frame, progress))?;
+                    self.run_selected(progress)?;
+                }
+                KeyCode::Char('h') => self.reveal_hint(progress)?,
+                _ => {}
+            }
+        }
+    }
+
+    fn pane_index(&self) -> usize {
+        PANES
+            .iter()
+            .position(|pane| *pane == self.focus)
+            .unwrap_or(0)
+    }
+
+    /// Up or down: a lesson in the list, or a line in the other panes.
+    fn step(&mut self, by: i16) {
+        if self.focus != Pane::Lessons {
+            self.scroll_by(by);
+            return;
+        }
+        let mut row = self.selected;
+        loop {
+            row = match row.checked_add_signed(isize::from(by)) {
+                Some(row) if row < self.rows.len() => row,
+                _ => return,
+            };
+            if !matches!(self.rows[row], Row::Chapter(_)) {
+                break;
+            }
+        }
+        self.selected = row;
+        self.scroll = [0; 3];
+        self.output.clear();
+        self.status.clear();
+    }
+
+    /// Scrolls the focused pane; in the list, scrolls the source.
+    fn scroll_by(&mut self, by: i16) {
+        let scroll = &mut self.scroll[scroll_slot(self.focus)];
+        *scroll = scroll.saturating_add_signed(by);
+    }
+
+    /// Whether a row's lesson is done, or `None` for a chapter.
+    fn done(&self, row: usize, progress: &Progress) -> Option<bool> {
+        match self.rows[row] {
+            Row::Chapter(_) => None,
+            Row::Example(index) => {
+                Some(progress.is_completed(Kind::Example, &self.course.examples[index].name))
+            }
+            Row::Exercise(index) => {
+                Some(progress.is_completed(Kind::Exercise, &self.exercises[index].name))
+            }
+        }
+    }
+
+    fn selected_name(&self) -> &str {
+        match self.rows[self.selected] {
+            Row::Chapter(ref name) => name,
+            Row::Example(index) => &self.course.examples[index].name,
+            Row::Exercise(index) => &self.exercises[index].name,
+        }
+    }
+
+    /// Runs the selected example or checks the selected exercise.
+    fn run_selected(&mut self, progress: &mut Progress) -> Result<()> {
+        self.scroll[1] = 0;
+        match self.rows[self.selected] {
+            Row::Chapter(_) => {}
+            Row::Example(index) => {
+                let example = &self.course.examples[index];
+                state::save_current(&example.name)?;
+                let outcome = compiler::build_and_run(example, &self.course.build_dir())?;
+                progress.record_attempt(Kind::Example, &example.name, outcome.passed());
+                let compiled = !matches!(outcome, Outcome::CompileError(_));
+                progress.record_build(Kind::Example, &example.name, compiled);
+                self.status = outcome_status(&example.name, &outcome);
+                self.output = outcome_output(outcome);
+            }
+            Row::Exercise(index) => {
+                let exercise = &self.exercises[index];
+                let report = checker::check(exercise, &self.course.build_dir())?;
+                progress.record_attempt(Kind::Exercise, &exercise.name, report.passed());
+                let compiled = !matches!(report, Report::CompileError(_));
+                progress.record_build(Kind::Exercise, &exercise.name, compiled);
+                self.status = report_status(exercise, &report);
+                self.output = report_output(&report, &self.errors);
+            }
+        }
+        let unlocked = achievements::unlock(self.course, self.exercises, progress);
+        if let Some(achievement) = unlocked.last() {
+            self.status = achievements::unlocked(achievement);
+        }
+        // The loop only ends when the learner quits, so save as we go
+        progress.save()
+    }
+
+    fn reveal_hint(&mut self, progress: &mut Progress) -> Result<()> {
+        let Row::Exercise(index) = self.rows[self.selected] else {
+            self.status = t!("ui-example-hints");
+            return Ok(());
+        };
+        let exercise = &self.exercises[index];
+        let Some(hints) = &exercise.hints else {
+            self.status = t!("hint-none", name = exercise.name.as_str());
+            return Ok(());
+        };
+        let levels = hints.levels().len();
+        let already = progress
+            .get(Kind::Exercise, &exercise.name)
+            .map_or(0, |record| record.hints_used);
+        let revealed = progress.reveal_hint(&exercise.name, levels);
+        self.status = if already == levels {
+            t!("hint-all-shown")
+        } else {
+            t!(
+                "ui-hint-revealed",
+                number = revealed,
+                total = levels,
+                name = exercise.name.as_str()
+            )
+        };
+        self.focus = Pane::Hints;
+        progress.save()
+    }
+
+    fn draw(&mut self, frame: &mut Frame, progress: &Progress) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(frame.area());
+        let [list, right] =
+            Layout::horizontal([Constraint::Length(32), Constraint::Min(0)]).areas(main);
+        let [source, bottom] =
+            Layout::vertical([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(right);
+        let [output, hints] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(bottom);
+
+        self.draw_list(frame, list, progress);
+        let (title, code) = self.source();
+        self.draw_text(frame, source, Pane::Source, title, code, false);
+        let output_text = if self.output.is_empty() {
+            Text::raw(t!("ui-press-enter")).style(Style::new().fg(Color::DarkGray))
+        } else {
+            ansi_text(&self.output)
+        };
+        self.draw_text(
+            frame,
+            output,
+            Pane::Output,
+            t!("ui-output"),
+            output_text,
+            true,
+        );
+        let hints_text = self.hints(progress);
+        self.draw_text(frame, hints, Pane::Hints, t!("ui-hints"), hints_text, true);
+
+        let footer_text = Text::from(vec![
+            Line::raw(format!(" {}", self.status)),
+            Line::styled(
+                format!(" {}", t!("ui-keys")),
+                Style::new().fg(Color::DarkGray),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(footer_text), footer);
+    }
+
+    fn draw_list(&self, frame: &mut Frame, area: Rect, progress: &Progress) {
+        let items: Vec<ListItem> = (0..self.rows.len())
+            .map(|row| match &self.rows[row] {
+                Row::Chapter(name) => ListItem::new(Line::styled(
+                    name.clone(),
+                    Style::new().add_modifier(Modifier::BOLD),
+                )),
+                Row::Example(index) => {
+                    let name = &self.course.examples[*index].name;
+                    let name = name.rsplit('/').next().unwrap_or(name);
+                    self.list_item(name, self.done(row, progress))
+                }
+                Row::Exercise(index) => {
+                    self.list_item(&self.exercises[*index].name, self.done(row, progress))
+                }
+            })
+            .collect();
+        // The streak and achievements, to keep an eye on
+        let title = format!(
+            "{} · 🔥 {} · 🏆 {}/{}",
+            t!("ui-lessons"),
+            achievements::streak(progress).current,
+            progress.achievements.len(),
+            achievements::ALL.len()
+        );
+        let list = List::new(items)
+            .block(self.block(Pane::Lessons, title))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn list_item(&self, name: &str, done: Option<bool>) -> ListItem<'static> {
+        let mark = if done == Some(true) {
+            Span::styled("✓ ", Style::new().fg(Color::Green))
+        } else {
+            Span::styled("· ", Style::new().fg(Color::DarkGray))
+        };
+        ListItem::new(Line::from(vec![
+            Span::raw("  "),
+            mark,
+            Span::raw(name.to_string()),
+        ]))
+    }
+
+    /// Draws a scrollable pane, keeping it from scrolling past its end.
+    fn draw_text(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        pane: Pane,
+        title: String,
+        text: Text<'static>,
+        wrap: bool,
+    ) {
+        let index = scroll_slot(pane);
+        let last = u16::try_from(text.lines.len().saturating_sub(1)).unwrap_or(u16::MAX);
+        self.scroll[index] = self.scroll[index].min(last);
+        let mut paragraph = Paragraph::new(text)
+            .block(self.block(pane, title))
+            .scroll((self.scroll[index], 0));
+        if wrap {
+            paragraph = paragraph.wrap(Wrap { trim: false });
+        }
+        frame.render_widget(paragraph, area);
+    }
+
+    fn block(&self, pane: Pane, title: String) -> Block<'static> {
+        let color = if self.focus == pane {
+            Color::Cyan
+        } else {
+            Color::DarkGray
+        };
+        Block::bordered()
+            .title(title)
+            .border_style(Style::new().fg(color))
+    }
+
+    /// The selected lesson's path, relative to the course, and its source
+    /// with line numbers.
+    fn source(&self) -> (String, Text<'static>) {
+        let path = match self.rows[self.selected] {
+            Row::Chapter(_) => return (String::new(), Text::default()),
+            Row::Example(index) => &self.course.examples[index].path,
+            Row::Exercise(index) => &self.exercises[index].path,
+        };
+        let title = path
+            .strip_prefix(&self.course.root)
+            .unwrap_or(path)
+            .display()
+            .to_string();
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(err) => return (title, Text::raw(format!("cannot read this file: {err}"))),
+        };
+        let mut in_comment = false;
+        let numbered: String = source
+            .lines()
+            .enumerate()
+            .map(|(number, line)| {
+                format!(
+                    "\x1b[90m{:>3}\x1b[0m {}\n",
+                    number + 1,
+                    highlight(line, &mut in_comment)
+                )
+            })
+            .collect();
+        (title, ansi_text(&numbered))
+    }
+
+    /// The hints revealed so far for the selected exercise, or the
+    /// selected example's title and the lessons it requires.
+    fn hints(&self, progress: &Progress) -> Text<'static> {
+        let bold = Style::new().add_modifier(Modifier::BOLD);
+        let dim = Style::new().fg(Color::DarkGray);
+        let mut lines = Vec::new();
+        match self.rows[self.selected] {
+            Row::Chapter(_) => {}
+            Row::Example(index) => {
+                let example = &self.course.examples[index];
+                let Some(lesson) = self.course.lessons.get(&example.name) else {
+                    return Text::styled(t!("ui-example-hints"), dim);
+                };
+                lines.push(Line::styled(lesson.title.clone(), bold));
+                lines.push(Line::raw(format!(
+                    "{} · {}",
+                    t!("difficulty", level = lesson.difficulty.to_string()),
+                    lesson.tags.join(", ")
+                )));
+                if !lesson.requires.is_empty() {
+                    lines.push(Line::raw(""));
+                    lines.push(Line::raw(t!("ui-builds-on")));
+                    for required in &lesson.requires {
+                        let mark = if progress.is_completed(Kind::Example, required) {
+                            "✓"
+                        } else {
+                            "·"
+                        };
+                        lines.push(Line::raw(format!("  {mark} {required}")));
+                    }
+                }
+            }
+            Row::Exercise(index) => {
+                let exercise = &self.exercises[index];
+                let Some(hints) = &exercise.hints else {
+                    return Text::styled(t!("hint-none", name = exercise.name.as_str()), dim);
+                };
+                let revealed = progress
+                    .get(Kind::Exercise, &exercise.name)
+                    .map_or(0, |record| record.hints_used);
+                let levels = hints.levels();
+                let labels = [
+                    t!("hint-nudge"),
+                    t!("hint-explanation"),
+                    t!("hint-solution"),
+                ];
+                for (number, (label, (_, text))) in
+                    labels.iter().zip(&levels).take(revealed).enumerate()
+                {
+                    lines.push(Line::styled(format!("{}. {label}", number + 1), bold));
+                    lines.extend(text.lines().map(|line| Line::raw(format!("   {line}"))));
+                    lines.push(Line::raw(""));
+                }
+                if revealed < levels.len() {
+                    lines.push(Line::styled(
+                        t!("ui-press-h", number = revealed + 1, total = levels.len()),
+                        dim,
+                    ));
+                }
+            }
+        }
+        Text::from(lines)
+    }
+}
+
+/// Which of [`App::scroll`] belongs to `pane`; the list scrolls the source.
+fn scroll_slot(pane: Pane) -> usize {
+    match pane {
+        Pane::Lessons | Pane::Source => 0,
+        Pane::Output => 1,
+        Pane::Hints => 2,
+    }
+}
+
+/// The footer line for a run example.
+fn outcome_status(name: &str, outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::CompileError(_) => {
+            format!("❌ {name} does not compile yet. Read the errors and try again.")
+        }
+        Outcome::Ran { success: true, .. } => format!("✅ {name} ran successfully."),
+        Outcome::Ran { .. } => format!("❌ {name} compiled but exited with an error."),
+        Outcome::TimedOut { .. } => format!(
+            "❌ {name} was still running after {} seconds, so it was stopped.",
+            runner::TIMEOUT.as_secs()
+        ),
+        Outcome::WrongOutput { .. } => {
+            format!("❌ {name} ran, but didn't print what its `// EXPECTED:` block says.")
+        }
+    }
+}
+
+fn outcome_output(outcome: Outcome) -> String {
+    match outcome {
+        Outcome::CompileError(diagnostics) => diagnostics,
+        Outcome::Ran { stdout, stderr, .. } | Outcome::TimedOut { stdout, stderr } => {
+            stdout + &stderr
+        }
+        Outcome::WrongOutput {
+            expected,
+            stdout,
+            stderr,
+        } => format!("{stdout}{stderr}\n\x1b[1mExpected:\x1b[0m\n{expected}"),
+    }
+}
+
+/// The footer line for a checked exercise.
+fn report_status(exercise: &Exercise, report: &Report) -> String {
+    match report {
+        Report::CompileError(_) => t!("check-compile-error", name = exercise.name.as_str()),
+        Report::Tested(_) if report.passed() => t!("check-done", name = exercise.name.as_str()),
+        Report::Tested(results) => format!(
+            "{} {}",
+            t!(
+                "check-failing",
+                failing = report.failures().count(),
+                total = results.len()
+            ),
+            t!("ui-stuck")
+        ),
+    }
+}
+
+/// A checked exercise's diagnostics, or its tests one per line with the
+/// failure message under failing ones, like [`checker::print_report`].
+fn report_output(report: &Report, errors: &[ErrorCode]) -> String {
+    match report {
+        Report::CompileError(diagnostics) => {
+            let mut out = diagnostics.clone();
+            let found = errors::found_in(errors, diagnostics);
+            if !found.is_empty() {
+                out.push_str(&format!("\n{}\n", t!("errors-suggest")));
+                for error in found {
+                    out.push_str(&format!("   {}\n", errors::suggestion(error)));
+                }
+            }
+            out
+        }
+        Report::Tested(results) => results
+            .iter()
+            .map(|result| {
+                let mark = if result.passed { "✅" } else { "❌" };
+                let mut line = format!("{mark} {}\n", result.name);
+                for message in result.message.iter().flat_map(|message| message.lines()) {
+                    line.push_str(&format!("     {message}\n"));
+                }
+                line
+            })
+            .collect(),
+    }
+}
+
+/// `text` with its ANSI color escapes turned into styles. Other escapes
+/// are dropped, and tabs become four spaces.
+fn ansi_text(text: &str) -> Text<'static> {
+    let mut style = Style::new();
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let mut spans = Vec::new();
+        let mut rest = line;
+        while let Some(start) = rest.find('\x1b') {
+            if start > 0 {
+                spans.push(Span::styled(rest[..start].replace('\t', "    "), style));
+            }
+            let escape = &rest[start + 1..];
+            // A control sequence is `[`, parameters, and one final letter
+            let end = match escape.strip_prefix('[') {
+                Some(sequence) => match sequence.find(|c: char| c.is_ascii_alphabetic()) {
+                    Some(at) => {
+                        if sequence[at..].starts_with('m') {
+                            style = apply_sgr(style, &sequence[..at]);
+                        }
+                        at + 2
+                    }
+                    None => escape.len(),
+                },
+                None => 0,
+            };
+            rest = &escape[end..];
+        }
+        if !rest.is_empty() {
+            spans.push(Span::styled(rest.replace('\t', "    "), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    Text::from(lines)
+}
+
+/// `style` after the "select graphic rendition" parameters `params`, such
+/// as `1;31` for bold red.
+fn apply_sgr(style: Style, params: &str) -> Style {
+    let numbers: Vec<u8> = params
+        .split(';')
+        .map(|number| number.parse().unwrap_or(0))
+        .collect();
+    let mut style = style;
+    let mut i = 0;
+    while i < numbers.len() {
+        style = match numbers[i] {
+            0 => Style::new(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            n @ 30..=37 => style.fg(Color::Indexed(n - 30)),
+            39 => style.fg(Color::Reset),
+            n @ 90..=97 => style.fg(Color::Indexed(n - 90 + 8)),
+            38 if numbers.get(i + 1) == Some(&5) => {
+                i += 2;
+                style.fg(Color::Indexed(numbers.get(i).copied().unwrap_or(0)))
+            }
+            38 if numbers.get(i + 1) == Some(&2) => {
+                i += 4;
+                let channel = |at: usize| numbers.get(at).copied().unwrap_or(0);
+                style.fg(Color::Rgb(channel(i - 2), channel(i - 1), channel(i)))
+            }
+            _ => style,
+        };
+        i += 1;
+    }
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_become_styles_and_reset_clears_them() {
+        let text = ansi_text("plain \x1b[1;31merror\x1b[0m: done\n\x1b[38;5;12mnext");
+        let first = &text.lines[0].spans;
+        assert_eq!(first[0].content, "plain ");
+        assert_eq!(first[0].style, Style::new());
+        assert_eq!(first[1].content, "error");
+        assert_eq!(
+            first[1].style,
+            Style::new()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Indexed(1))
+        );
+        assert_eq!(first[2].style, Style::new());
+        assert_eq!(
+            text.lines[1].spans[0].style,
+            Style::new().fg(Color::Indexed(12))
+        );
+    }
+
+    #[test]
+    fn a_style_carries_over_to_the_next_line_and_other_escapes_are_dropped() {
+        let text = ansi_text("\x1b[32mgreen\nstill\x1b[K green\tend");
+        let second = &text.lines[1].spans;
+        assert_eq!(second[0].content, "still");
+        assert_eq!(second[1].content, " green    end");
+        assert!(second
+            .iter()
+            .all(|span| span.style == Style::new().fg(Color::Indexed(2))));
+    }
+}
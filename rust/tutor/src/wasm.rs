@@ -0,0 +1,134 @@
+//! `tutor build`: build a WebAssembly chapter with wasm-pack, then run its
+//! tests as WebAssembly.
+//!
+//! A WebAssembly chapter is a cargo package that depends on wasm-bindgen,
+//! with its exported functions in a `cdylib` library:
+//!
+//! ```text
+//! examples/wasm/
+//! ├── Cargo.toml        <- [lib] crate-type = ["cdylib", "rlib"]
+//! ├── textprimes/lib.rs <- the #[wasm_bindgen] exports
+//! ├── tests/web.rs      <- #[wasm_bindgen_test] tests
+//! ├── www/index.html    <- a page that calls them
+//! └── pkg/              <- what wasm-pack builds, not committed
+//! ```
+//!
+//! `wasm-pack build` compiles the library for `wasm32-unknown-unknown` and
+//! generates its JS glue in `pkg/`; `wasm-pack test --node` compiles
+//! `tests/` for the same target and runs them in Node, with no browser.
+//! Neither comes with Rust, so without them `tutor build` says how to
+//! install them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use crate::course::{file_stem, sorted_entries};
+
+/// One chapter that builds to WebAssembly.
+#[derive(Debug)]
+pub struct Package {
+    /// The chapter's directory name, e.g. `wasm`.
+    pub chapter: String,
+    pub dir: PathBuf,
+    /// The library's name, which names the files in `pkg/`.
+    pub lib: String,
+}
+
+impl Package {
+    /// Where wasm-pack writes the module and its JS glue.
+    pub fn pkg_dir(&self) -> PathBuf {
+        self.dir.join("pkg")
+    }
+}
+
+/// Finds every `<root>/examples/*/` package that depends on wasm-bindgen.
+pub fn discover(root: &Path) -> Result<Vec<Package>> {
+    let mut packages = Vec::new();
+    for dir in sorted_entries(&root.join("examples"))? {
+        let path = dir.join("Cargo.toml");
+        if !path.is_file() {
+            continue;
+        }
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+        let manifest: toml::Table =
+            toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))?;
+        let depends_on_wasm_bindgen = manifest
+            .get("dependencies")
+            .and_then(|deps| deps.get("wasm-bindgen"))
+            .is_some();
+        if !depends_on_wasm_bindgen {
+            continue;
+        }
+        let chapter = file_stem(&dir)?;
+        let lib = manifest
+            .get("lib")
+            .and_then(|lib| lib.get("name"))
+            .and_then(|name| name.as_str())
+            .map_or_else(|| chapter.replace('-', "_"), str::to_string);
+        packages.push(Package { chapter, dir, lib });
+    }
+    Ok(packages)
+}
+
+pub fn find<'a>(packages: &'a [Package], name: &str) -> Option<&'a Package> {
+    packages.iter().find(|package| package.chapter == name)
+}
+
+/// Whether `wasm-pack` is installed.
+pub fn wasm_pack_available() -> bool {
+    Command::new("wasm-pack")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Builds `package`'s library for the browser (`--target web`, an ES
+/// module the page imports) into `pkg/`, named after the library. Returns
+/// what wasm-pack printed if it fails.
+pub fn build(package: &Package) -> Result<Result<PathBuf, String>> {
+    let output = Command::new("wasm-pack")
+        .args(["build", "--target", "web", "--no-typescript"])
+        .args(["--out-name", &package.lib])
+        .arg("--out-dir")
+        .arg(package.pkg_dir())
+        .arg(&package.dir)
+        .output()
+        .context("failed to launch wasm-pack")?;
+    if output.status.success() {
+        Ok(Ok(package.pkg_dir()))
+    } else {
+        Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()))
+    }
+}
+
+/// Runs `package`'s `tests/` as WebAssembly in Node. Returns the names of
+/// the tests that passed, or else what the tests (or the compiler) printed.
+pub fn test(package: &Package) -> Result<Result<Vec<String>, String>> {
+    let mut command = Command::new("wasm-pack");
+    command.args(["test", "--node"]).arg(&package.dir);
+    // Only the files in tests/: the unit tests are plain #[test]s that
+    // `cargo test` already runs natively
+    for path in sorted_entries(&package.dir.join("tests"))? {
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            command.args(["--test", &file_stem(&path)?]);
+        }
+    }
+    let output = command
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .context("failed to launch wasm-pack")?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if output.status.success() {
+        Ok(Ok(crate::loom::passed_tests(&stdout)))
+    } else if stdout.is_empty() {
+        Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()))
+    } else {
+        Ok(Err(stdout))
+    }
+}
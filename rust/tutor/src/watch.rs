@@ -0,0 +1,157 @@
+//! `tutor watch`: re-check the active exercise every time a file is saved.
+//!
+//! The active exercise is the one named on the command line, or else the
+//! first one that doesn't pass yet. Saving a different exercise file makes
+//! that exercise active, and finishing one moves on to the next unfinished
+//! exercise automatically.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::achievements;
+use crate::checker;
+use crate::course::Course;
+use crate::errors;
+use crate::exercise::Exercise;
+use crate::progress::{Kind, Progress};
+use crate::t;
+
+/// Editors often write a file in several steps; wait this long for the
+/// burst of events to settle before recompiling.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+pub fn watch(
+    course: &Course,
+    exercises: &[Exercise],
+    start: Option<&str>,
+    progress: &mut Progress,
+) -> Result<bool> {
+    if exercises.is_empty() {
+        bail!("there are no exercises to watch");
+    }
+    let exercises_dir = course.root.join("exercises");
+    let errors = errors::load(&course.root)?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("cannot start a file watcher")?;
+    watcher
+        .watch(&exercises_dir, RecursiveMode::Recursive)
+        .with_context(|| format!("cannot watch {}", exercises_dir.display()))?;
+
+    let mut active = match start {
+        Some(name) => exercises.iter().position(|exercise| exercise.name == name),
+        None => next_unfinished(course, exercises, 0, progress)?,
+    };
+    let mut just_finished: Option<&str> = None;
+
+    loop {
+        let Some(index) = active else {
+            clear_screen();
+            println!("{}", t!("watch-all-done"));
+            return Ok(true);
+        };
+        let exercise = &exercises[index];
+        let report = checker::check(exercise, &course.build_dir())?;
+        progress.record_attempt(Kind::Exercise, &exercise.name, report.passed());
+        let compiled = !matches!(report, checker::Report::CompileError(_));
+        progress.record_build(Kind::Exercise, &exercise.name, compiled);
+        let unlocked = achievements::unlock(course, exercises, progress);
+        // Watch mode never returns normally, so save after every check.
+        progress.save()?;
+
+        clear_screen();
+        println!(
+            "{}\n",
+            t!("watch-title", dir = exercises_dir.display().to_string())
+        );
+        if let Some(name) = just_finished.take() {
+            println!("{}\n", t!("watch-next", name = name));
+        }
+        println!(
+            "[{}/{}] {} [{}] ({})\n",
+            index + 1,
+            exercises.len(),
+            exercise.name,
+            exercise.topic,
+            exercise.path.display()
+        );
+        checker::print_report(exercise, &report, &errors);
+        achievements::announce(&unlocked);
+        std::io::stdout().flush()?;
+
+        if report.passed() {
+            just_finished = Some(&exercise.name);
+            active = next_unfinished(course, exercises, index + 1, progress)?;
+            continue;
+        }
+
+        let Some(changed) = wait_for_change(&rx)? else {
+            return Ok(false);
+        };
+        if let Some(edited) = exercises
+            .iter()
+            .position(|exercise| exercise.path == changed)
+        {
+            active = Some(edited);
+        }
+    }
+}
+
+/// Index of the first exercise at or after `from` that doesn't pass yet.
+fn next_unfinished(
+    course: &Course,
+    exercises: &[Exercise],
+    from: usize,
+    progress: &mut Progress,
+) -> Result<Option<usize>> {
+    for (index, exercise) in exercises.iter().enumerate().skip(from) {
+        if !checker::check(exercise, &course.build_dir())?.passed() {
+            return Ok(Some(index));
+        }
+        progress.mark_completed(Kind::Exercise, &exercise.name);
+    }
+    Ok(None)
+}
+
+/// Blocks until a `.rs` file changes, returning its path, or `None` if the
+/// watcher shut down.
+fn wait_for_change(rx: &mpsc::Receiver<notify::Result<notify::Event>>) -> Result<Option<PathBuf>> {
+    let mut changed = None;
+    while changed.is_none() {
+        let Ok(event) = rx.recv() else {
+            return Ok(None);
+        };
+        changed = relevant_path(event?);
+    }
+    // Swallow the rest of the burst, but remember the latest file touched.
+    while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+        if let Some(path) = relevant_path(event?) {
+            changed = Some(path);
+        }
+    }
+    Ok(changed)
+}
+
+fn relevant_path(event: notify::Event) -> Option<PathBuf> {
+    let is_edit = matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    );
+    if !is_edit {
+        return None;
+    }
+    event
+        .paths
+        .into_iter()
+        .find(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .map(|path| path.canonicalize().unwrap_or(path))
+}
+
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+}
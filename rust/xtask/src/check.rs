@@ -0,0 +1,303 @@
+//! `cargo xtask check-manifest`: the bookkeeping around the examples and
+//! exercises agrees with the files themselves.
+//!
+//! - Every example has an entry in `lessons.toml`, and every entry (and
+//!   everything it requires) is an example. The tutor's own discovery
+//!   also rejects prerequisites that form a cycle.
+//! - Every `.rs` file in a chapter is a `[[bin]]` of the chapter's
+//!   `Cargo.toml`, named after the file, and every `[[bin]]` points at a
+//!   file that exists.
+//! - Every exercise has hints in its topic's `info.toml`, and every entry
+//!   there, or in its `tests/mutants.toml`, is an exercise.
+//! - Every exercise has a reference solution under `solutions/`, and every
+//!   file there solves an exercise.
+//! - Every translation (`lessons.es.toml`, `info.es.toml`,
+//!   `quizzes/variables.es.toml`) translates something that exists: a
+//!   lesson, an exercise's hints, or a quiz with as many questions.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tutor::course::Course;
+use tutor::{exercise, i18n, solution};
+
+pub fn run(root: &Path) -> Result<bool> {
+    let course = match Course::discover(root) {
+        Ok(course) => course,
+        Err(err) => {
+            println!("❌ {err:#}");
+            return Ok(false);
+        }
+    };
+    let mut problems = Vec::new();
+    for example in &course.examples {
+        if course.lessons.get(&example.name).is_none() {
+            problems.push(format!("{} has no entry in lessons.toml", example.name));
+        }
+    }
+    let examples = course.root.join("examples");
+    check_bins(&course.root, &examples, &mut problems)?;
+    for chapter in sorted_dirs(&examples)? {
+        check_bins(&course.root, &chapter, &mut problems)?;
+    }
+    let exercises = check_exercises(&course.root, &mut problems)?;
+    check_translations(&course, &mut problems)?;
+
+    if problems.is_empty() {
+        println!(
+            "✅ lessons.toml, the chapters' manifests, the hints, and the solutions match all {} examples and {exercises} exercises.",
+            course.examples.len()
+        );
+        return Ok(true);
+    }
+    for problem in &problems {
+        println!("❌ {problem}");
+    }
+    Ok(false)
+}
+
+/// Compares the `.rs` files in `dir` with the `[[bin]]` entries of its
+/// `Cargo.toml`, if it has one and it isn't a workspace of its own.
+fn check_bins(root: &Path, dir: &Path, problems: &mut Vec<String>) -> Result<()> {
+    let path = dir.join("Cargo.toml");
+    if !path.is_file() {
+        return Ok(());
+    }
+    let manifest = read_toml(&path)?;
+    if manifest.contains_key("workspace") {
+        return Ok(());
+    }
+    let shown = path.strip_prefix(root).unwrap_or(&path).display();
+    let mut bins = BTreeSet::new();
+    for bin in manifest
+        .get("bin")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let name = bin
+            .get("name")
+            .and_then(toml::Value::as_str)
+            .unwrap_or_default();
+        let file = bin
+            .get("path")
+            .and_then(toml::Value::as_str)
+            .unwrap_or_default();
+        if !dir.join(file).is_file() {
+            problems.push(format!(
+                "{shown}: [[bin]] `{name}` points at `{file}`, which doesn't exist"
+            ));
+        }
+        bins.insert((name.to_string(), file.to_string()));
+    }
+    for entry in fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))? {
+        let file = entry?.path();
+        let Some(stem) = file.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if file.extension().is_none_or(|ext| ext != "rs") || stem == "build" {
+            continue;
+        }
+        let bin = (stem.to_string(), format!("{stem}.rs"));
+        if !bins.contains(&bin) {
+            problems.push(format!(
+                "{shown} has no [[bin]] named `{stem}` with path `{stem}.rs`"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Compares each topic's exercises with its `info.toml`. Returns how many
+/// exercises there are.
+fn check_exercises(root: &Path, problems: &mut Vec<String>) -> Result<usize> {
+    let exercises = exercise::discover(root)?;
+    for exercise in &exercises {
+        if exercise.hints.is_none() {
+            problems.push(format!(
+                "exercise {} has no hints in info.toml",
+                exercise.name
+            ));
+        }
+    }
+    let mut solutions = BTreeSet::new();
+    for exercise in &exercises {
+        let path = solution::path(root, exercise);
+        if !path.is_file() {
+            problems.push(format!(
+                "exercise {} has no solution at {}",
+                exercise.name,
+                path.strip_prefix(root).unwrap_or(&path).display()
+            ));
+        }
+        solutions.insert(path);
+    }
+    let dir = root.join("solutions");
+    if dir.is_dir() {
+        for topic in sorted_dirs(&dir)? {
+            for entry in
+                fs::read_dir(&topic).with_context(|| format!("cannot read {}", topic.display()))?
+            {
+                let path = entry?.path();
+                if !solutions.contains(&path) {
+                    problems.push(format!(
+                        "{} is not the solution to any exercise",
+                        path.strip_prefix(root).unwrap_or(&path).display()
+                    ));
+                }
+            }
+        }
+    }
+    let names: BTreeSet<&str> = exercises
+        .iter()
+        .map(|exercise| exercise.name.as_str())
+        .collect();
+    let dir = root.join("exercises");
+    if dir.is_dir() {
+        for topic in sorted_dirs(&dir)? {
+            let info = topic.join("info.toml");
+            if info.is_file() {
+                for name in read_toml(&info)?.keys() {
+                    if !names.contains(name.as_str()) || !topic.join(format!("{name}.rs")).is_file()
+                    {
+                        problems.push(format!(
+                            "{} has hints for `{name}`, but there is no {name}.rs",
+                            info.strip_prefix(root).unwrap_or(&info).display()
+                        ));
+                    }
+                }
+            }
+            let mutants = topic.join("tests").join("mutants.toml");
+            if mutants.is_file() {
+                for name in read_toml(&mutants)?.keys() {
+                    if !topic.join(format!("{name}.rs")).is_file() {
+                        problems.push(format!(
+                            "{} has mutants for `{name}`, but there is no {name}.rs",
+                            mutants.strip_prefix(root).unwrap_or(&mutants).display()
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    Ok(exercises.len())
+}
+
+/// Compares every translation with the English file it translates.
+fn check_translations(course: &Course, problems: &mut Vec<String>) -> Result<()> {
+    let root = &course.root;
+    let shown = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    for &(code, _) in i18n::LANGUAGES {
+        if code == i18n::ENGLISH {
+            continue;
+        }
+        let lessons = i18n::translation_path(&root.join("lessons.toml"), code);
+        if lessons.is_file() {
+            for lesson in array(&read_toml(&lessons)?, "lesson") {
+                let id = lesson.get("id").and_then(toml::Value::as_str);
+                if id.is_none_or(|id| course.lessons.get(id).is_none()) {
+                    problems.push(format!(
+                        "{} translates `{}`, which isn't in lessons.toml",
+                        shown(&lessons),
+                        id.unwrap_or_default()
+                    ));
+                }
+            }
+        }
+
+        let exercises = root.join("exercises");
+        let topics = if exercises.is_dir() {
+            sorted_dirs(&exercises)?
+        } else {
+            Vec::new()
+        };
+        for topic in topics {
+            let info = topic.join("info.toml");
+            let translated = i18n::translation_path(&info, code);
+            if !translated.is_file() {
+                continue;
+            }
+            let english = if info.is_file() {
+                read_toml(&info)?
+            } else {
+                toml::Table::new()
+            };
+            for name in read_toml(&translated)?.keys() {
+                if !english.contains_key(name) {
+                    problems.push(format!(
+                        "{} has hints for `{name}`, which isn't in info.toml",
+                        shown(&translated)
+                    ));
+                }
+            }
+        }
+
+        let quizzes = root.join("quizzes");
+        if quizzes.is_dir() {
+            for entry in fs::read_dir(&quizzes)
+                .with_context(|| format!("cannot read {}", quizzes.display()))?
+            {
+                let translated = entry?.path();
+                if !i18n::is_translation(&translated)
+                    || !translated
+                        .to_string_lossy()
+                        .ends_with(&format!(".{code}.toml"))
+                {
+                    continue;
+                }
+                let stem = Path::new(translated.file_stem().unwrap_or_default());
+                let english = quizzes.join(stem.with_extension("toml"));
+                if !english.is_file() {
+                    problems.push(format!(
+                        "{} translates {}, which doesn't exist",
+                        shown(&translated),
+                        shown(&english)
+                    ));
+                    continue;
+                }
+                let want = array(&read_toml(&english)?, "question").len();
+                let have = array(&read_toml(&translated)?, "question").len();
+                if have != want {
+                    problems.push(format!(
+                        "{} has {have} questions, but {} has {want}",
+                        shown(&translated),
+                        shown(&english)
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn array<'a>(table: &'a toml::Table, key: &str) -> &'a [toml::Value] {
+    table
+        .get(key)
+        .and_then(toml::Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+pub fn read_toml(path: &Path) -> Result<toml::Table> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    toml::from_str(&text).with_context(|| format!("{} is not valid", path.display()))
+}
+
+pub fn sorted_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
@@ -0,0 +1,162 @@
+//! `cargo xtask compile-fail`: the trybuild cases in `compile_fail/`, one
+//! for every `// This would cause an error:` snippet in the examples.
+//!
+//! Each case is a copy of its example with that one snippet uncommented,
+//! so its line numbers, and those in its `.stderr` snapshot, are the
+//! example's own. `compile_fail/cases.toml` lists the cases with the
+//! example, the line of the snippet, and the error its comment documents.
+//! The cases and the list are generated; the `.stderr` snapshots are
+//! written by trybuild itself, with
+//! `TRYBUILD=overwrite cargo test -p compile-fail`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use checker::{discover, find_snippets, Expected};
+
+/// The test crate, under the course root.
+const DIR: &str = "compile_fail";
+/// Where its cases live, under [`DIR`].
+const CASES: &str = "cases";
+
+pub fn run(root: &Path, check: bool) -> Result<bool> {
+    let (cases, manifest) = generate(root)?;
+    let dir = root.join(DIR);
+    let cases_dir = dir.join(CASES);
+    let manifest_path = dir.join("cases.toml");
+
+    let mut changes = Vec::new();
+    if fs::read_to_string(&manifest_path).ok().as_deref() != Some(manifest.as_str()) {
+        changes.push(format!("{DIR}/cases.toml"));
+    }
+    for (name, source) in &cases {
+        let path = cases_dir.join(format!("{name}.rs"));
+        if fs::read_to_string(&path).ok().as_deref() != Some(source.as_str()) {
+            changes.push(format!("{DIR}/{CASES}/{name}.rs"));
+        }
+    }
+    let mut stale = Vec::new();
+    if cases_dir.is_dir() {
+        collect_stale(&cases_dir, &cases_dir, &cases, &mut stale)?;
+    }
+    stale.sort();
+
+    if changes.is_empty() && stale.is_empty() {
+        println!("✅ The {} compile-fail cases are up to date.", cases.len());
+        return Ok(true);
+    }
+    if check {
+        println!("❌ The compile-fail cases are out of date; run `cargo xtask compile-fail`:");
+        for change in &changes {
+            println!("  - {change} needs regenerating");
+        }
+        for path in &stale {
+            println!(
+                "  - {} belongs to no snippet",
+                path.strip_prefix(root).unwrap_or(path).display()
+            );
+        }
+        return Ok(false);
+    }
+
+    for (name, source) in &cases {
+        let path = cases_dir.join(format!("{name}.rs"));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("cannot create {}", parent.display()))?;
+        }
+        fs::write(&path, source).with_context(|| format!("cannot write {}", path.display()))?;
+    }
+    fs::write(&manifest_path, &manifest)
+        .with_context(|| format!("cannot write {}", manifest_path.display()))?;
+    for path in &stale {
+        fs::remove_file(path).with_context(|| format!("cannot delete {}", path.display()))?;
+        println!(
+            "🗑  removed {}",
+            path.strip_prefix(root).unwrap_or(path).display()
+        );
+    }
+    println!(
+        "📝 Updated {DIR}/: {} of the {} files changed.",
+        changes.len(),
+        cases.len() + 1
+    );
+    println!("   Record their errors with `TRYBUILD=overwrite cargo test -p compile-fail`.");
+    Ok(true)
+}
+
+/// Every case's source by name, and `cases.toml`.
+fn generate(root: &Path) -> Result<(BTreeMap<String, String>, String)> {
+    let mut cases = BTreeMap::new();
+    let mut manifest = String::from(
+        "# Generated by `cargo xtask compile-fail`; don't edit it by hand.\n\
+         #\n\
+         # One trybuild case for every `// This would cause an error:` snippet\n\
+         # in the examples: `cases/<name>.rs` is the example with that snippet\n\
+         # uncommented, and `cases/<name>.stderr` the errors it fails with.\n\
+         # A case's name is its example's, with the snippet's number.\n",
+    );
+    for example in discover(root)? {
+        let source = fs::read_to_string(&example.path)
+            .with_context(|| format!("cannot read {}", example.path.display()))?;
+        let path = example.path.strip_prefix(root).unwrap_or(&example.path);
+        let path = path.to_string_lossy().replace('\\', "/");
+        for (index, snippet) in find_snippets(&source).iter().enumerate() {
+            let name = format!("{}-{}", example.name, index + 1);
+            let mut case = snippet.uncomment(&source);
+            writeln!(
+                case,
+                "\n// Generated by `cargo xtask compile-fail` from {path}, with the\n\
+                 // snippet at line {} uncommented.",
+                snippet.line
+            )?;
+            cases.insert(name.clone(), case);
+
+            write!(
+                manifest,
+                "\n[[case]]\nname = \"{name}\"\nexample = \"{path}\"\nline = {}\n",
+                snippet.line
+            )?;
+            match &snippet.expected {
+                Expected::Code(code) => writeln!(manifest, "code = \"{code}\"")?,
+                Expected::Message(message) => writeln!(
+                    manifest,
+                    "message = {}",
+                    toml::Value::String(message.clone())
+                )?,
+                Expected::AnyError => {}
+            }
+        }
+    }
+    Ok((cases, manifest))
+}
+
+/// The files under `dir` that belong to none of `cases`, whose names are
+/// relative to `cases_dir`.
+fn collect_stale(
+    cases_dir: &Path,
+    dir: &Path,
+    cases: &BTreeMap<String, String>,
+    stale: &mut Vec<PathBuf>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_stale(cases_dir, &path, cases, stale)?;
+            continue;
+        }
+        let name = path
+            .strip_prefix(cases_dir)
+            .unwrap_or(&path)
+            .with_extension("")
+            .to_string_lossy()
+            .replace('\\', "/");
+        if !cases.contains_key(&name) {
+            stale.push(path);
+        }
+    }
+    Ok(())
+}
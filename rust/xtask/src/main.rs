@@ -0,0 +1,113 @@
+//! `cargo xtask` runs the chores of keeping the course in order, so they
+//! are written once, in Rust, instead of in shell scripts:
+//!
+//! ```text
+//! cargo xtask renumber <CHAPTER>   close the gaps in a chapter's numbering
+//!     --dry-run                    only show what would be renamed
+//! cargo xtask check-manifest       check lessons.toml, the chapters' [[bin]]s,
+//!                                  and the exercises' hints and solutions
+//!                                  against the files
+//! cargo xtask check-solutions      run every solution against its exercise's
+//!                                  hidden tests
+//! cargo xtask compile-fail         regenerate compile_fail/, a trybuild case
+//!                                  for every broken snippet in the examples
+//!     --check                      fail instead if it is out of date
+//! cargo xtask toc                  regenerate the contents list in README.md
+//!     --check                      fail instead if it is out of date
+//! cargo xtask verify               everything CI runs: the checks above, then
+//!                                  build, clippy, tests, tutor verify,
+//!                                  tutor check-output, tutor equivalence,
+//!                                  tutor docs, checker, check-solutions
+//! ```
+//!
+//! The alias is in `.cargo/config.toml`; without it, `cargo run -p xtask --`
+//! does the same.
+
+mod check;
+mod compile_fail;
+mod renumber;
+mod solutions;
+mod toc;
+mod verify;
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "xtask", about = "Maintenance tasks for the Rust course")]
+struct Cli {
+    /// Path to the `rust/` directory of this repository.
+    #[arg(long, global = true)]
+    root: Option<PathBuf>,
+
+    #[command(subcommand)]
+    task: Task,
+}
+
+#[derive(Subcommand)]
+enum Task {
+    /// Renumber a chapter's examples 01, 02, ... in their current order,
+    /// and update every reference to the old names.
+    Renumber {
+        /// The directory under `examples/`, e.g. `enums`.
+        chapter: String,
+        /// List the renames and the files that mention them, and change
+        /// nothing.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Check that `lessons.toml`, each chapter's `[[bin]]` entries, the
+    /// exercises' `info.toml` files, and `solutions/` agree with the files
+    /// on disk.
+    CheckManifest,
+    /// Check every reference solution against its exercise's hidden tests.
+    CheckSolutions,
+    /// Regenerate the trybuild cases in `compile_fail/`, one per broken
+    /// snippet in the examples.
+    CompileFail {
+        /// Change nothing, and fail if the cases are out of date.
+        #[arg(long)]
+        check: bool,
+    },
+    /// Regenerate the list of chapters and lessons in `README.md`.
+    Toc {
+        /// Change nothing, and fail if the list is out of date.
+        #[arg(long)]
+        check: bool,
+    },
+    /// Run every check the course has, stopping at the first that fails.
+    Verify,
+}
+
+fn main() -> ExitCode {
+    match try_main() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns `Ok(false)` when a check failed, so the process exits non-zero.
+fn try_main() -> Result<bool> {
+    let cli = Cli::parse();
+    let root = cli
+        .root
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."));
+    let root = fs::canonicalize(&root)
+        .with_context(|| format!("cannot find the course at {}", root.display()))?;
+    match cli.task {
+        Task::Renumber { chapter, dry_run } => renumber::run(&root, &chapter, dry_run),
+        Task::CheckManifest => check::run(&root),
+        Task::CheckSolutions => solutions::run(&root),
+        Task::CompileFail { check } => compile_fail::run(&root, check),
+        Task::Toc { check } => toc::run(&root, check),
+        Task::Verify => verify::run(&root),
+    }
+}
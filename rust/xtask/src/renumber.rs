@@ -0,0 +1,393 @@
+//! `cargo xtask renumber <chapter>`: give a chapter's examples the numbers
+//! 01, 02, ... in their current order, closing any gaps, and rename every
+//! reference to them along with the files and their output in `golden/`.
+//!
+//! Examples are ordered by their current number, and by name when two
+//! share one. A reference is an example's name where it appears in full
+//! (`enums/03_destructuring`, which covers `lessons.toml`, paths such as
+//! `examples/enums/03_destructuring.rs`, and tutor commands), or its file
+//! stem alone inside the chapter's directory (its `[[bin]]` and comments)
+//! and on lines that run the chapter's package (`cargo run -p
+//! enums-examples --bin 03_destructuring`). The chapter's compile-fail
+//! cases are renamed with it, and regenerated.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::check::read_toml;
+use crate::compile_fail;
+
+/// Where references are looked for: the text files under `rust/` and the
+/// repository's top-level files, skipping build output and generated files.
+const EXTENSIONS: [&str; 5] = ["rs", "toml", "md", "json", "stderr"];
+const SKIPPED_DIRS: [&str; 5] = ["target", "book", "node_modules", "pkg", ".git"];
+
+pub fn run(root: &Path, chapter: &str, dry_run: bool) -> Result<bool> {
+    let dir = root.join("examples").join(chapter);
+    if chapter.is_empty() || chapter.contains(['/', '\\', '.']) || !dir.is_dir() {
+        bail!("there is no chapter `{chapter}` in examples/");
+    }
+
+    let renames = renames(&dir)?;
+    if renames.is_empty() {
+        println!("✅ {chapter} is already numbered without gaps.");
+        return Ok(true);
+    }
+    for (old, new) in &renames {
+        println!("   {chapter}/{old} -> {chapter}/{new}");
+    }
+
+    let package = package_name(&dir)?;
+    let mut files = Vec::new();
+    collect_files(root, &mut files)?;
+    if let Some(repo) = root.parent() {
+        for entry in
+            fs::read_dir(repo).with_context(|| format!("cannot read {}", repo.display()))?
+        {
+            let path = entry?.path();
+            if path.is_file() && has_text_extension(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut changed = Vec::new();
+    for path in files {
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let in_chapter = path.starts_with(&dir);
+        let updated: String = text
+            .split_inclusive('\n')
+            .map(|line| {
+                // Bare stems too, in the chapter and where its package runs
+                let runs_package = package
+                    .as_deref()
+                    .is_some_and(|package| line.contains(&format!("-p {package}")));
+                let scope = (!in_chapter && !runs_package).then_some(chapter);
+                replace_names(line, &renames, scope)
+            })
+            .collect();
+        let renamed = renames
+            .iter()
+            .find(|(old, _)| path == dir.join(format!("{old}.rs")));
+        let updated = match renamed {
+            Some((_, new)) => renumber_heading(&updated, new),
+            None => updated,
+        };
+        if updated != text {
+            if !dry_run {
+                fs::write(&path, updated)
+                    .with_context(|| format!("cannot write {}", path.display()))?;
+            }
+            changed.push(path);
+        }
+    }
+
+    if !dry_run {
+        // The examples, and their recorded output in `golden/`
+        let golden = root.join("golden").join(chapter);
+        for (dir, extension) in [(&dir, "rs"), (&golden, "txt")] {
+            rename_all(dir, extension, &renames)?;
+        }
+        // Their compile-fail cases, `<old>-1.rs` and its `.stderr`; the
+        // headings in the cases are renumbered by regenerating them
+        let cases = root.join("compile_fail").join("cases").join(chapter);
+        if cases.is_dir() {
+            rename_cases(&cases, &renames)?;
+            compile_fail::run(root, false)?;
+        }
+    }
+
+    let verb = if dry_run { "would change" } else { "changed" };
+    println!("\n{} renamed file(s); {verb} references in:", renames.len());
+    for path in &changed {
+        println!("   {}", path.strip_prefix(root).unwrap_or(path).display());
+    }
+    if dry_run {
+        println!("\nNothing was changed. Run it again without --dry-run to apply it.");
+    } else {
+        println!(
+            "\nProgress saved under the old names is kept, but no longer counts. \
+             Run `cargo xtask check-manifest` to check the result."
+        );
+    }
+    Ok(true)
+}
+
+/// Renames `<old>.<extension>` to `<new>.<extension>` in `dir`, for each
+/// rename whose file exists.
+fn rename_all(dir: &Path, extension: &str, renames: &[(String, String)]) -> Result<()> {
+    // In two steps, so that `02_b -> 01_b` can't overwrite an `01_a` that
+    // is about to become `02_a`
+    let mut moved = Vec::new();
+    for (old, new) in renames {
+        let from = dir.join(format!("{old}.{extension}"));
+        if !from.is_file() {
+            continue;
+        }
+        let to = dir.join(format!("{new}.{extension}.renumbering"));
+        fs::rename(&from, &to).with_context(|| format!("cannot rename {}", from.display()))?;
+        moved.push(new);
+    }
+    for new in moved {
+        let from = dir.join(format!("{new}.{extension}.renumbering"));
+        let to = dir.join(format!("{new}.{extension}"));
+        fs::rename(&from, &to).with_context(|| format!("cannot rename {}", from.display()))?;
+    }
+    Ok(())
+}
+
+/// Renames the compile-fail cases `<old>-<n>.<extension>` in `dir` to
+/// `<new>-<n>.<extension>`, in two steps like [`rename_all`].
+fn rename_cases(dir: &Path, renames: &[(String, String)]) -> Result<()> {
+    let mut moved = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))? {
+        let from = entry?.path();
+        let name = from
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let renamed = renames.iter().find_map(|(old, new)| {
+            let rest = name.strip_prefix(old.as_str())?.strip_prefix('-')?;
+            Some(format!("{new}-{rest}"))
+        });
+        let Some(renamed) = renamed else {
+            continue;
+        };
+        let to = dir.join(format!("{renamed}.renumbering"));
+        fs::rename(&from, &to).with_context(|| format!("cannot rename {}", from.display()))?;
+        moved.push(renamed);
+    }
+    for renamed in moved {
+        let from = dir.join(format!("{renamed}.renumbering"));
+        let to = dir.join(&renamed);
+        fs::rename(&from, &to).with_context(|| format!("cannot rename {}", from.display()))?;
+    }
+    Ok(())
+}
+
+/// The numbered files in `dir` whose numbers change, as (old stem, new stem).
+fn renames(dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut numbered = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if let Some((number, rest)) = stem.split_once('_') {
+            if let Ok(number) = number.parse::<u32>() {
+                numbered.push((number, rest.to_string(), stem.to_string()));
+            }
+        }
+    }
+    numbered.sort();
+    Ok(numbered
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, rest, old))| (old, format!("{:02}_{rest}", index + 1)))
+        .filter(|(old, new)| old != new)
+        .collect())
+}
+
+/// The chapter's package name, if it is a package.
+fn package_name(dir: &Path) -> Result<Option<String>> {
+    let manifest = dir.join("Cargo.toml");
+    if !manifest.is_file() {
+        return Ok(None);
+    }
+    Ok(read_toml(&manifest)?
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(toml::Value::as_str)
+        .map(str::to_string))
+}
+
+/// `source` with the number in its first line, as in `// Enums 3: ...`,
+/// changed to the one `stem` starts with.
+fn renumber_heading(source: &str, stem: &str) -> String {
+    let number = stem
+        .split('_')
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('0');
+    let Some((first, rest)) = source.split_once('\n') else {
+        return source.to_string();
+    };
+    let renumbered = first.split_once(':').and_then(|(before, after)| {
+        let (heading, old) = before.rsplit_once(' ')?;
+        old.chars()
+            .all(|c| c.is_ascii_digit())
+            .then(|| format!("{heading} {number}:{after}"))
+    });
+    match renumbered {
+        Some(first) if first.starts_with("// ") => format!("{first}\n{rest}"),
+        _ => source.to_string(),
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            let name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            if !SKIPPED_DIRS.contains(&name) {
+                collect_files(&path, files)?;
+            }
+        } else if has_text_extension(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn has_text_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| EXTENSIONS.contains(&ext))
+}
+
+/// `text` with each old name in `renames` replaced by its new one, in a
+/// single pass so that one rename never feeds another. With a `chapter`,
+/// only `chapter/old` is replaced; without one, `old` on its own. Either
+/// way the name must not be part of a longer identifier.
+fn replace_names(text: &str, renames: &[(String, String)], chapter: Option<&str>) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let prefix = chapter
+        .map(|chapter| format!("{chapter}/"))
+        .unwrap_or_default();
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut previous: Option<char> = None;
+    'scan: while let Some(c) = rest.chars().next() {
+        if !previous.is_some_and(is_ident) {
+            for (old, new) in renames {
+                let Some(after) = rest
+                    .strip_prefix(prefix.as_str())
+                    .and_then(|after| after.strip_prefix(old.as_str()))
+                else {
+                    continue;
+                };
+                if after.chars().next().is_some_and(is_ident) {
+                    continue;
+                }
+                out.push_str(&prefix);
+                out.push_str(new);
+                previous = new.chars().last();
+                rest = after;
+                continue 'scan;
+            }
+        }
+        out.push(c);
+        previous = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renames_of(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(old, new)| (old.to_string(), new.to_string()))
+            .collect()
+    }
+
+    /// A fresh directory for one test's files.
+    fn scratch_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("xtask-renumber-{}-{test}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn names_are_replaced_only_as_whole_identifiers() {
+        let renames = renames_of(&[("03_match", "02_match")]);
+        assert_eq!(
+            replace_names("03_match 03_matches x03_match 03_match.rs", &renames, None),
+            "02_match 03_matches x03_match 02_match.rs"
+        );
+    }
+
+    #[test]
+    fn outside_the_chapter_only_the_full_name_is_replaced() {
+        let renames = renames_of(&[("03_match", "02_match")]);
+        assert_eq!(
+            replace_names(
+                "examples/enums/03_match.rs traits/03_match 03_match",
+                &renames,
+                Some("enums")
+            ),
+            "examples/enums/02_match.rs traits/03_match 03_match"
+        );
+    }
+
+    #[test]
+    fn renames_are_made_in_a_single_pass() {
+        let renames = renames_of(&[("01_a", "02_a"), ("02_a", "03_a")]);
+        assert_eq!(
+            replace_names("[01_a, 02_a]", &renames, None),
+            "[02_a, 03_a]"
+        );
+    }
+
+    #[test]
+    fn the_heading_takes_the_new_number() {
+        let source = "// Enums 3: Destructuring\n// Demonstrates ...\n";
+        assert_eq!(
+            renumber_heading(source, "02_destructuring"),
+            "// Enums 2: Destructuring\n// Demonstrates ...\n"
+        );
+        assert_eq!(
+            renumber_heading("// Enums 9: Last\n", "10_last"),
+            "// Enums 10: Last\n"
+        );
+        // A heading without a number is left alone
+        let unnumbered = "// Example: Hello\nfn main() {}\n";
+        assert_eq!(renumber_heading(unnumbered, "02_hello"), unnumbered);
+    }
+
+    #[test]
+    fn gaps_and_shared_numbers_are_closed_up() {
+        let dir = scratch_dir("gaps");
+        for file in [
+            "01_first.rs",
+            "03_second.rs",
+            "03_third.rs",
+            "07_last.rs",
+            "build.rs",
+            "09_notes.md",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        assert_eq!(
+            renames(&dir).unwrap(),
+            renames_of(&[("03_second", "02_second"), ("07_last", "04_last")])
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_chapter_without_gaps_needs_no_renames() {
+        let dir = scratch_dir("no-gaps");
+        for file in ["01_first.rs", "02_second.rs"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        assert!(renames(&dir).unwrap().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
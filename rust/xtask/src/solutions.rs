@@ -0,0 +1,58 @@
+//! `cargo xtask check-solutions`: every reference solution under
+//! `solutions/` passes its exercise's hidden tests, the same way
+//! `tutor check` would run them on the learner's file.
+
+use std::path::Path;
+
+use anyhow::Result;
+use tutor::checker::{self, Report};
+use tutor::course::Course;
+use tutor::errors;
+use tutor::exercise::{self, Exercise};
+use tutor::solution;
+
+pub fn run(root: &Path) -> Result<bool> {
+    let course = Course::discover(root)?;
+    let exercises = exercise::discover(&course.root)?;
+    let known = errors::load(&course.root)?;
+    let mut failed = Vec::new();
+    for exercise in &exercises {
+        let solved = Exercise {
+            path: solution::path(&course.root, exercise),
+            ..exercise.clone()
+        };
+        if !solved.path.is_file() {
+            println!("❌ {:<15} has no solution", exercise.name);
+            failed.push(exercise.name.as_str());
+            continue;
+        }
+        let report = checker::check(&solved, &course.build_dir())?;
+        match &report {
+            Report::CompileError(_) => println!("❌ {:<15} does not compile", exercise.name),
+            Report::Tested(results) if report.passed() => {
+                println!("✅ {:<15} all {} tests pass", exercise.name, results.len());
+                continue;
+            }
+            Report::Tested(results) => println!(
+                "❌ {:<15} {} of {} tests failing",
+                exercise.name,
+                report.failures().count(),
+                results.len()
+            ),
+        }
+        checker::print_report(&solved, &report, &known);
+        failed.push(exercise.name.as_str());
+    }
+
+    if failed.is_empty() {
+        println!("\n✅ All {} solutions pass their tests.", exercises.len());
+        return Ok(true);
+    }
+    println!(
+        "\n❌ {} of {} solutions fail: {}",
+        failed.len(),
+        exercises.len(),
+        failed.join(", ")
+    );
+    Ok(false)
+}
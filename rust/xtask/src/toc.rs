@@ -0,0 +1,86 @@
+//! `cargo xtask toc`: the list of chapters and lessons in `README.md`,
+//! between `<!-- toc:start -->` and `<!-- toc:end -->`, generated from the
+//! examples and the titles in `lessons.toml`.
+//!
+//! Chapters come in the order the course reaches them, as in the book
+//! `bookgen` writes, and each chapter's lessons in course order.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use tutor::course::{Course, Example};
+use tutor::scaffold::chapter_heading;
+
+const START: &str = "<!-- toc:start -->";
+const END: &str = "<!-- toc:end -->";
+
+pub fn run(root: &Path, check: bool) -> Result<bool> {
+    let course = Course::discover(root)?;
+    let path = course.root.join("README.md");
+    let readme =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let updated = replace_toc(&readme, &render(&course)?)?;
+    if updated == readme {
+        println!("✅ The contents in README.md are up to date.");
+        return Ok(true);
+    }
+    if check {
+        println!("❌ The contents in README.md are out of date; run `cargo xtask toc`.");
+        return Ok(false);
+    }
+    fs::write(&path, updated).with_context(|| format!("cannot write {}", path.display()))?;
+    println!("📝 Updated the contents in README.md.");
+    Ok(true)
+}
+
+/// One line per chapter, linking each of its lessons.
+fn render(course: &Course) -> Result<String> {
+    let mut chapters: Vec<(Option<&str>, Vec<&Example>)> = Vec::new();
+    for example in &course.examples {
+        let group = example.group.as_deref();
+        match chapters.iter_mut().find(|(chapter, _)| *chapter == group) {
+            Some((_, examples)) => examples.push(example),
+            None => chapters.push((group, vec![example])),
+        }
+    }
+
+    let mut toc = String::new();
+    for (group, examples) in chapters {
+        let heading = match group {
+            None => "Getting Started".to_string(),
+            Some(group) => chapter_heading(&examples[0].path)?.unwrap_or_else(|| group.to_string()),
+        };
+        let links: Vec<String> = examples
+            .iter()
+            .map(|example| {
+                let title = course
+                    .lessons
+                    .get(&example.name)
+                    .map_or(example.name.as_str(), |lesson| lesson.title.as_str())
+                    .replace('<', "\\<")
+                    .replace('>', "\\>");
+                let path = example
+                    .path
+                    .strip_prefix(&course.root)
+                    .unwrap_or(&example.path);
+                format!("[{title}]({})", path.to_string_lossy().replace('\\', "/"))
+            })
+            .collect();
+        writeln!(toc, "- **{heading}**: {}", links.join(" · "))?;
+    }
+    Ok(toc)
+}
+
+/// `readme` with the text between the markers replaced by `toc`.
+fn replace_toc(readme: &str, toc: &str) -> Result<String> {
+    let (Some(start), Some(end)) = (readme.find(START), readme.find(END)) else {
+        bail!("README.md has no `{START}` ... `{END}` section for the contents");
+    };
+    if end < start {
+        bail!("README.md has `{END}` before `{START}`");
+    }
+    let start = start + START.len();
+    Ok(format!("{}\n{toc}{}", &readme[..start], &readme[end..]))
+}
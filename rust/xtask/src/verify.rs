@@ -0,0 +1,93 @@
+//! `cargo xtask verify`: every check the course has, in the order that
+//! finds problems fastest, stopping at the first that fails.
+
+use std::env;
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+
+use crate::{check, compile_fail, solutions, toc};
+
+/// The cargo commands, after the bookkeeping checks.
+const CARGO_STEPS: [&[&str]; 8] = [
+    &["build", "--workspace"],
+    &[
+        "clippy",
+        "--workspace",
+        "--all-targets",
+        "--",
+        "-D",
+        "warnings",
+    ],
+    &["test", "--workspace"],
+    // Runs and checks the output of every example, and model checks the
+    // loom tests
+    &["run", "--quiet", "-p", "tutor", "--", "verify", "--all"],
+    // Every example still prints what its file in `golden/` recorded
+    &["run", "--quiet", "-p", "tutor", "--", "check-output"],
+    // The Rust, Go, and Java versions of a lesson print the same thing
+    &["run", "--quiet", "-p", "tutor", "--", "equivalence"],
+    // The shared library's docs build, with no broken links
+    &["run", "--quiet", "-p", "tutor", "--", "docs", "--no-open"],
+    // Every `// This would cause an error:` snippet still fails as documented
+    &["run", "--quiet", "-p", "checker"],
+];
+
+pub fn run(root: &Path) -> Result<bool> {
+    let started = Instant::now();
+    let mut steps = 0;
+
+    println!("▶ cargo xtask check-manifest");
+    steps += 1;
+    if !check::run(root)? {
+        return Ok(failed("check-manifest"));
+    }
+    println!("\n▶ cargo xtask toc --check");
+    steps += 1;
+    if !toc::run(root, true)? {
+        return Ok(failed("toc --check"));
+    }
+    println!("\n▶ cargo xtask compile-fail --check");
+    steps += 1;
+    if !compile_fail::run(root, true)? {
+        return Ok(failed("compile-fail --check"));
+    }
+
+    // The cargo that runs this task, so that `cargo +nightly xtask verify`
+    // uses nightly throughout
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    for args in CARGO_STEPS {
+        let command = format!("cargo {}", args.join(" "));
+        println!("\n▶ {command}");
+        steps += 1;
+        let status = Command::new(&cargo)
+            .args(args)
+            .current_dir(root)
+            .status()
+            .with_context(|| format!("failed to launch `{command}`"))?;
+        if !status.success() {
+            return Ok(failed(&command));
+        }
+    }
+
+    // Last, since it is the slowest; it only depends on the exercises
+    println!("\n▶ cargo xtask check-solutions");
+    steps += 1;
+    if !solutions::run(root)? {
+        return Ok(failed("check-solutions"));
+    }
+
+    println!(
+        "\n✅ All {steps} checks passed in {:.0?}.",
+        started.elapsed()
+    );
+    Ok(true)
+}
+
+fn failed(step: &str) -> bool {
+    println!("\n❌ `{step}` failed; fix it and run `cargo xtask verify` again.");
+    false
+}